@@ -4,6 +4,7 @@
 use std::{
     collections::{BTreeMap, VecDeque},
     ops::Bound::{Excluded, Included},
+    path::Path,
     time::Duration,
 };
 
@@ -13,7 +14,9 @@ use consensus_types::block::{BlockDigest, BlockRef, Round, TransactionIndex};
 use mysten_common::ZipDebugEqIteratorExt;
 use sui_macros::fail_point;
 #[cfg(not(tidehunter))]
-use typed_store::rocks::{DBMapTableConfigMap, default_db_options};
+use typed_store::rocks::{
+    DBMapTableConfigMap, ReadWriteOptions, default_db_options, open_cf_opts_secondary,
+};
 use typed_store::{
     DBMapUtils, Map as _,
     metrics::SamplingInterval,
@@ -91,6 +94,67 @@ impl RocksDBStore {
         )
     }
 
+    /// Opens an existing store at `path` read-only, as a RocksDB secondary instance keeping its own
+    /// files under `secondary_path`, so it can be read while consensus holds it open. Unlike
+    /// [`Self::new`], this returns an error instead of panicking if the store can't be opened.
+    #[cfg(not(tidehunter))]
+    pub fn open_secondary(path: &Path, secondary_path: &Path) -> ConsensusResult<Self> {
+        let cf_options = default_db_options().options;
+        let cfs = [
+            Self::BLOCKS_CF,
+            Self::DIGESTS_BY_AUTHORITIES_CF,
+            Self::COMMITS_CF,
+            Self::COMMIT_VOTES_CF,
+            Self::COMMIT_INFO_CF,
+            Self::FINALIZED_COMMITS_CF,
+        ]
+        .map(|cf| (cf, cf_options.clone()));
+
+        let db = open_cf_opts_secondary(
+            path,
+            Some(secondary_path),
+            None,
+            MetricConf::new("consensus_secondary"),
+            &cfs,
+        )
+        .map_err(ConsensusError::RocksDBFailure)?;
+
+        let rw_options = ReadWriteOptions::default();
+        Ok(Self {
+            blocks: DBMap::reopen(&db, Some(Self::BLOCKS_CF), &rw_options, false)
+                .map_err(ConsensusError::RocksDBFailure)?,
+            digests_by_authorities: DBMap::reopen(
+                &db,
+                Some(Self::DIGESTS_BY_AUTHORITIES_CF),
+                &rw_options,
+                false,
+            )
+            .map_err(ConsensusError::RocksDBFailure)?,
+            commits: DBMap::reopen(&db, Some(Self::COMMITS_CF), &rw_options, false)
+                .map_err(ConsensusError::RocksDBFailure)?,
+            commit_votes: DBMap::reopen(&db, Some(Self::COMMIT_VOTES_CF), &rw_options, false)
+                .map_err(ConsensusError::RocksDBFailure)?,
+            commit_info: DBMap::reopen(&db, Some(Self::COMMIT_INFO_CF), &rw_options, false)
+                .map_err(ConsensusError::RocksDBFailure)?,
+            finalized_commits: DBMap::reopen(
+                &db,
+                Some(Self::FINALIZED_COMMITS_CF),
+                &rw_options,
+                false,
+            )
+            .map_err(ConsensusError::RocksDBFailure)?,
+        })
+    }
+
+    #[cfg(tidehunter)]
+    pub fn open_secondary(_path: &Path, _secondary_path: &Path) -> ConsensusResult<Self> {
+        Err(ConsensusError::RocksDBFailure(
+            typed_store::TypedStoreError::RocksDBError(
+                "Secondary instances are not supported by tidehunter".to_string(),
+            ),
+        ))
+    }
+
     #[cfg(tidehunter)]
     pub fn new(path: &str) -> Self {
         tracing::warn!("Consensus store using tidehunter");
@@ -379,3 +379,33 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[cfg(not(tidehunter))]
+#[tokio::test]
+async fn open_secondary() {
+    let primary_dir = TempDir::new().unwrap();
+    let secondary_dir = TempDir::new().unwrap();
+    let primary = RocksDBStore::new(primary_dir.path().to_str().unwrap());
+
+    let written_commits = vec![TrustedCommit::new_for_test(
+        1,
+        CommitDigest::MIN,
+        1,
+        BlockRef::new(1, AuthorityIndex::new_for_test(0), BlockDigest::default()),
+        vec![],
+    )];
+    primary
+        .write(WriteBatch::default().commits(written_commits.clone()))
+        .unwrap();
+
+    // The store can be read while the primary still holds it open.
+    let secondary = RocksDBStore::open_secondary(primary_dir.path(), secondary_dir.path())
+        .expect("Opening the store as a secondary should not fail");
+    let scanned_commits = secondary.scan_commits((1..=1).into()).unwrap();
+    assert_eq!(scanned_commits, written_commits);
+
+    // A directory that doesn't hold a store is an error, not a panic.
+    let empty_dir = TempDir::new().unwrap();
+    let secondary_dir = TempDir::new().unwrap();
+    assert!(RocksDBStore::open_secondary(empty_dir.path(), secondary_dir.path()).is_err());
+}
@@ -23,6 +23,7 @@ use std::time::Duration;
 use tokio::sync::oneshot;
 use tokio::time::Instant;
 use tokio::time::interval_at;
+use tokio::time::timeout;
 use tracing::warn;
 
 type Registrations<V> = Vec<oneshot::Sender<V>>;
@@ -54,9 +55,43 @@ const LONG_WAIT_LOG_INTERVAL_SECS: u64 = 10;
 pub const CHECKPOINT_BUILDER_NOTIFY_READ_TASK_NAME: &str =
     "CheckpointBuilder::notify_read_executed_effects";
 
+/// Bounds on the registrations made through [`NotifyRead::register_all_bounded`]. Registrations
+/// made through the unbounded methods are not counted against these limits.
+#[derive(Clone, Debug)]
+pub struct NotifyReadLimits {
+    /// Maximum number of outstanding bounded registrations, across all callers.
+    pub max_pending: usize,
+    /// Maximum number of outstanding bounded registrations of a single caller.
+    pub max_pending_per_caller: usize,
+    /// How long a bounded wait may last before it fails with [`NotifyReadError::Expired`].
+    pub expiry: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NotifyReadError {
+    #[error("Too many pending notify-read registrations (limit {limit})")]
+    PoolExhausted { limit: usize },
+    #[error("Too many pending notify-read registrations for {caller} (limit {limit})")]
+    CallerQuotaExceeded { caller: &'static str, limit: usize },
+    #[error("Notify-read for {caller} expired after {expiry:?}")]
+    Expired {
+        caller: &'static str,
+        expiry: Duration,
+    },
+}
+
+/// Outstanding bounded registrations, in total and per caller.
+#[derive(Default)]
+struct BoundedPool {
+    total: usize,
+    per_caller: HashMap<&'static str, usize>,
+}
+
 pub struct NotifyRead<K, V> {
     pending: Vec<Mutex<HashMap<K, Registrations<V>>>>,
     count_pending: AtomicUsize,
+    limits: Option<NotifyReadLimits>,
+    bounded: Mutex<BoundedPool>,
 }
 
 impl<K: Eq + Hash + Clone, V: Clone> NotifyRead<K, V> {
@@ -66,6 +101,17 @@ impl<K: Eq + Hash + Clone, V: Clone> NotifyRead<K, V> {
         Self {
             pending,
             count_pending,
+            limits: None,
+            bounded: Default::default(),
+        }
+    }
+
+    /// Like [`NotifyRead::new`], but registrations made through
+    /// [`NotifyRead::register_all_bounded`] are subject to `limits`.
+    pub fn with_limits(limits: NotifyReadLimits) -> Self {
+        Self {
+            limits: Some(limits),
+            ..Self::new()
         }
     }
 
@@ -131,6 +177,80 @@ impl<K: Eq + Hash + Clone, V: Clone> NotifyRead<K, V> {
         self.count_pending.load(Ordering::Relaxed)
     }
 
+    /// Registers for `keys` on behalf of `caller`, failing if that would exceed the pool's limits.
+    /// The registrations count against the limits until the returned value is dropped, and waiting
+    /// on them fails once the pool's expiry has passed.
+    pub fn register_all_bounded(
+        &self,
+        caller: &'static str,
+        keys: &[K],
+    ) -> Result<BoundedRegistrations<'_, K, V>, NotifyReadError> {
+        if let Some(limits) = &self.limits {
+            let mut bounded = self.bounded.lock();
+            if bounded.total + keys.len() > limits.max_pending {
+                return Err(NotifyReadError::PoolExhausted {
+                    limit: limits.max_pending,
+                });
+            }
+            let per_caller = bounded.per_caller.get(caller).copied().unwrap_or_default();
+            if per_caller + keys.len() > limits.max_pending_per_caller {
+                return Err(NotifyReadError::CallerQuotaExceeded {
+                    caller,
+                    limit: limits.max_pending_per_caller,
+                });
+            }
+            if !keys.is_empty() {
+                bounded.per_caller.insert(caller, per_caller + keys.len());
+                bounded.total += keys.len();
+            }
+        }
+
+        Ok(BoundedRegistrations {
+            registrations: self.register_all(keys),
+            quota: BoundedQuota {
+                this: self,
+                caller,
+                count: keys.len(),
+            },
+        })
+    }
+
+    /// The number of outstanding bounded registrations of each caller that has any.
+    pub fn outstanding_waiters(&self) -> Vec<(&'static str, usize)> {
+        let bounded = self.bounded.lock();
+        let mut waiters: Vec<_> = bounded
+            .per_caller
+            .iter()
+            .map(|(caller, count)| (*caller, *count))
+            .collect();
+        waiters.sort_unstable();
+        waiters
+    }
+
+    /// The number of outstanding bounded registrations of `caller`.
+    pub fn outstanding_waiters_for(&self, caller: &'static str) -> usize {
+        self.bounded
+            .lock()
+            .per_caller
+            .get(caller)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn release(&self, caller: &'static str, count: usize) {
+        if self.limits.is_none() || count == 0 {
+            return;
+        }
+        let mut bounded = self.bounded.lock();
+        bounded.total -= count;
+        if let Some(per_caller) = bounded.per_caller.get_mut(caller) {
+            *per_caller -= count;
+            if *per_caller == 0 {
+                bounded.per_caller.remove(caller);
+            }
+        }
+    }
+
     fn cleanup(&self, key: &K) {
         let mut pending = self.pending(key);
         // it is possible that registration was fulfilled before we get here
@@ -277,6 +397,59 @@ impl<K: Eq + Hash + Clone, V: Clone> Drop for Registration<'_, K, V> {
         }
     }
 }
+/// Registrations counted against the limits of a bounded [`NotifyRead`], until dropped.
+pub struct BoundedRegistrations<'a, K: Eq + Hash + Clone, V: Clone> {
+    registrations: Vec<Registration<'a, K, V>>,
+    quota: BoundedQuota<'a, K, V>,
+}
+
+struct BoundedQuota<'a, K: Eq + Hash + Clone, V: Clone> {
+    this: &'a NotifyRead<K, V>,
+    caller: &'static str,
+    count: usize,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Drop for BoundedQuota<'_, K, V> {
+    fn drop(&mut self) {
+        self.this.release(self.caller, self.count);
+    }
+}
+
+impl<K: Eq + Hash + Clone + Unpin, V: Clone + Unpin> BoundedRegistrations<'_, K, V> {
+    /// Waits for the registrations for which `wait` yields true, in the order they were
+    /// registered, returning `None` for the others. Fails if the pool's expiry passes first.
+    pub async fn wait(
+        self,
+        wait: impl IntoIterator<Item = bool>,
+    ) -> Result<Vec<Option<V>>, NotifyReadError> {
+        let Self {
+            registrations,
+            quota,
+        } = self;
+        let results = registrations
+            .into_iter()
+            .zip_debug_eq(wait)
+            .map(|(registration, wait)| {
+                if wait {
+                    Either::Left(async move { Some(registration.await) })
+                } else {
+                    // Dropping the registration cancels it.
+                    Either::Right(futures::future::ready(None))
+                }
+            });
+        let results = join_all(results);
+        let Some(limits) = &quota.this.limits else {
+            return Ok(results.await);
+        };
+        timeout(limits.expiry, results)
+            .await
+            .map_err(|_| NotifyReadError::Expired {
+                caller: quota.caller,
+                expiry: limits.expiry,
+            })
+    }
+}
+
 impl<K: Eq + Hash + Clone, V: Clone> Default for NotifyRead<K, V> {
     fn default() -> Self {
         Self::new()
@@ -308,6 +481,69 @@ mod tests {
         }
     }
 
+    fn test_limits(expiry: Duration) -> NotifyReadLimits {
+        NotifyReadLimits {
+            max_pending: 4,
+            max_pending_per_caller: 3,
+            expiry,
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_notify_read_bounded_quotas() {
+        let notify_read = NotifyRead::<u64, u64>::with_limits(test_limits(Duration::from_secs(60)));
+
+        let first = notify_read.register_all_bounded("a", &[1, 2]).unwrap();
+        assert_eq!(
+            notify_read.register_all_bounded("a", &[3, 4]).err(),
+            Some(NotifyReadError::CallerQuotaExceeded {
+                caller: "a",
+                limit: 3
+            })
+        );
+        let second = notify_read.register_all_bounded("b", &[3, 4]).unwrap();
+        assert_eq!(
+            notify_read.register_all_bounded("c", &[5]).err(),
+            Some(NotifyReadError::PoolExhausted { limit: 4 })
+        );
+        assert_eq!(notify_read.outstanding_waiters(), vec![("a", 2), ("b", 2)]);
+
+        // Unbounded registrations do not count against the limits.
+        let _unbounded = notify_read.register_all(&[6, 7, 8]);
+
+        notify_read.notify(&1, &10);
+        assert_eq!(
+            first.wait([true, false]).await.unwrap(),
+            vec![Some(10), None]
+        );
+        assert_eq!(notify_read.outstanding_waiters_for("a"), 0);
+        drop(second);
+        assert!(notify_read.outstanding_waiters().is_empty());
+        assert!(notify_read.register_all_bounded("c", &[5, 6, 7]).is_ok());
+    }
+
+    #[tokio::test]
+    pub async fn test_notify_read_bounded_expiry() {
+        let notify_read =
+            NotifyRead::<u64, u64>::with_limits(test_limits(Duration::from_millis(50)));
+
+        let registrations = notify_read.register_all_bounded("a", &[1, 2]).unwrap();
+        assert_eq!(
+            registrations.wait([true, true]).await,
+            Err(NotifyReadError::Expired {
+                caller: "a",
+                expiry: Duration::from_millis(50),
+            })
+        );
+
+        // Expired registrations are cleaned up and release their quota.
+        assert_eq!(0, notify_read.num_pending());
+        assert!(notify_read.outstanding_waiters().is_empty());
+        for pending in &notify_read.pending {
+            assert!(pending.lock().is_empty());
+        }
+    }
+
     #[tokio::test]
     pub async fn test_notify_read_cancellation() {
         let notify_read = Arc::new(NotifyRead::<u64, u64>::new());
@@ -63,6 +63,7 @@ pub enum IntentScope {
     BridgeEventUnused = 7, // for bridge purposes but it's currently not included in messages.
     ConsensusBlock = 8,    // Used for consensus authority signature on block's digest.
     DiscoveryPeers = 9,    // Used for reporting peer addresses in discovery.
+    ExecutionTimeObservations = 10, // Used for an authority signature on exported execution time observations.
 }
 
 impl TryFrom<u8> for IntentScope {
@@ -935,14 +935,26 @@ impl Simulacrum {
 mod tests {
     use std::time::Duration;
 
+    use move_core_types::ident_str;
+    use move_core_types::language_storage::TypeTag;
     use rand::{SeedableRng, rngs::StdRng};
+    use serde::{Deserialize, Serialize};
+    use sui_types::dynamic_field::Field;
+    use sui_types::governance::{FungibleStakedSui, FungibleStakedSuiData};
+    use sui_types::kiosk::{Kiosk, KioskOwnerCap, Listing};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::sui_system_state::SuiSystemStateTrait;
+    use sui_types::transaction::{Argument, CallArg, Command, ObjectArg, TransactionData};
     use sui_types::{
-        base_types::SuiAddress, effects::TransactionEffectsAPI, gas_coin::GasCoin,
-        transaction::TransactionDataAPI,
+        MoveTypeTagTrait, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID, base_types::SuiAddress,
+        effects::TransactionEffectsAPI, gas_coin::GasCoin, transaction::TransactionDataAPI,
     };
 
     use super::*;
 
+    /// 5 SUI gas budget
+    const GAS_BUDGET: u64 = 5_000_000_000;
+
     #[test]
     fn deterministic_genesis() {
         let rng = StdRng::from_seed([9; 32]);
@@ -1047,4 +1059,215 @@ mod tests {
             assert_eq!(checkpoint.network_total_transactions, 2); // genesis + 1 user txn
         };
     }
+
+    /// Signs and executes the transaction built by `builder`, paying with the latest version of
+    /// `gas`, and checks that it succeeded.
+    fn execute(
+        sim: &mut Simulacrum,
+        sender: SuiAddress,
+        kp: &AccountKeyPair,
+        gas: ObjectID,
+        builder: ProgrammableTransactionBuilder,
+    ) -> TransactionEffects {
+        let gas = object(sim, &gas).compute_object_reference();
+        let data = TransactionData::new_programmable(
+            sender,
+            vec![gas],
+            builder.finish(),
+            GAS_BUDGET,
+            sim.reference_gas_price(),
+        );
+        let (fx, error) = sim
+            .execute_transaction(Transaction::from_data_and_signer(data, vec![kp]))
+            .unwrap();
+        assert!(fx.status().is_ok(), "{error:?}");
+        fx
+    }
+
+    fn object(sim: &Simulacrum, id: &ObjectID) -> Object {
+        store::SimulatorStore::get_object(sim.store(), id).unwrap()
+    }
+
+    /// The objects created by a transaction, with the given type.
+    fn created(
+        sim: &Simulacrum,
+        fx: &TransactionEffects,
+        is_type: impl Fn(&Object) -> bool,
+    ) -> Vec<Object> {
+        fx.created()
+            .into_iter()
+            .map(|((id, _, _), _)| object(sim, &id))
+            .filter(|o| is_type(o))
+            .collect()
+    }
+
+    /// Checks that `value` serializes back to the object's contents.
+    fn assert_round_trip<T: Serialize>(object: &Object, value: &T) {
+        let contents = object.data.try_as_move().unwrap().contents();
+        assert_eq!(bcs::to_bytes(value).unwrap(), contents);
+    }
+
+    #[test]
+    fn kiosk_bindings() {
+        let mut sim = Simulacrum::new();
+        let (sender, kp, gas) = sim.funded_account(10 * MIST_PER_SUI).unwrap();
+
+        // Create a kiosk and list a coin in it for 42 MIST.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            ident_str!("kiosk").to_owned(),
+            ident_str!("new").to_owned(),
+            vec![],
+            vec![],
+        );
+        let (kiosk, cap) = (Argument::NestedResult(0, 0), Argument::NestedResult(0, 1));
+        let amount = builder.pure(MIST_PER_SUI).unwrap();
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+        let price = builder.pure(42u64).unwrap();
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            ident_str!("kiosk").to_owned(),
+            ident_str!("place_and_list").to_owned(),
+            vec![TypeTag::Struct(Box::new(GasCoin::type_()))],
+            vec![kiosk, cap, Argument::NestedResult(1, 0), price],
+        );
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            ident_str!("transfer").to_owned(),
+            ident_str!("public_share_object").to_owned(),
+            vec![TypeTag::Struct(Box::new(Kiosk::type_()))],
+            vec![kiosk],
+        );
+        builder.transfer_arg(sender, cap);
+        let fx = execute(&mut sim, sender, &kp, gas.0, builder);
+
+        let [kiosk_object] = created(&sim, &fx, |o| {
+            o.struct_tag().is_some_and(|tag| Kiosk::is_kiosk(&tag))
+        })
+        .try_into()
+        .unwrap();
+        let kiosk = Kiosk::try_from(&kiosk_object).unwrap();
+        assert_eq!(kiosk.id(), kiosk_object.id());
+        assert_eq!(kiosk.owner, sender);
+        assert_eq!(kiosk.item_count, 1);
+        assert_eq!(kiosk.profits.value(), 0);
+        assert!(!kiosk.allow_extensions);
+        assert_round_trip(&kiosk_object, &kiosk);
+
+        let [cap_object] = created(&sim, &fx, |o| {
+            o.struct_tag()
+                .is_some_and(|tag| KioskOwnerCap::is_kiosk_owner_cap(&tag))
+        })
+        .try_into()
+        .unwrap();
+        let cap = KioskOwnerCap::try_from(&cap_object).unwrap();
+        assert_eq!(cap.kiosk_id(), kiosk.id());
+        assert_round_trip(&cap_object, &cap);
+        assert!(Kiosk::try_from(&cap_object).is_err());
+
+        // The listing's price is a dynamic field of the kiosk, keyed by its `Listing`.
+        let [listing_object] = created(&sim, &fx, |o| {
+            o.struct_tag()
+                .is_some_and(|tag| tag.type_params.first() == Some(&Listing::get_type_tag()))
+        })
+        .try_into()
+        .unwrap();
+        let contents = listing_object.data.try_as_move().unwrap().contents();
+        let listing: Field<Listing, u64> = bcs::from_bytes(contents).unwrap();
+        assert!(!listing.name.is_exclusive);
+        assert_eq!(listing.value, 42);
+        assert_round_trip(&listing_object, &listing);
+    }
+
+    #[test]
+    fn fungible_staked_sui_bindings() {
+        /// Rust version of the Move sui_system::staking_pool::FungibleStakedSuiDataKey type.
+        #[derive(Serialize, Deserialize)]
+        struct FungibleStakedSuiDataKey {
+            dummy_field: bool,
+        }
+
+        let mut sim = Simulacrum::new();
+        let (sender, kp, gas) = sim.funded_account(10 * MIST_PER_SUI).unwrap();
+        let validator = sim
+            .store()
+            .get_system_state()
+            .into_sui_system_state_summary()
+            .active_validators[0]
+            .clone();
+
+        // Stake 1 SUI, and wait for the stake to become active.
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let system_state = builder.input(CallArg::SUI_SYSTEM_MUT).unwrap();
+        let amount = builder.pure(MIST_PER_SUI).unwrap();
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+        let validator_address = builder.pure(validator.sui_address).unwrap();
+        builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            ident_str!("sui_system").to_owned(),
+            ident_str!("request_add_stake").to_owned(),
+            vec![],
+            vec![
+                system_state,
+                Argument::NestedResult(0, 0),
+                validator_address,
+            ],
+        );
+        let fx = execute(&mut sim, sender, &kp, gas.0, builder);
+        let staked_sui = fx
+            .created()
+            .into_iter()
+            .find_map(|(oref, owner)| {
+                matches!(owner, Owner::AddressOwner(owner) if owner == sender).then_some(oref)
+            })
+            .unwrap();
+        sim.create_checkpoint();
+        sim.advance_epoch(AdvanceEpochConfig::default());
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let system_state = builder.input(CallArg::SUI_SYSTEM_MUT).unwrap();
+        let staked_sui = builder
+            .obj(ObjectArg::ImmOrOwnedObject(
+                object(&sim, &staked_sui.0).compute_object_reference(),
+            ))
+            .unwrap();
+        builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            ident_str!("sui_system").to_owned(),
+            ident_str!("convert_to_fungible_staked_sui").to_owned(),
+            vec![],
+            vec![system_state, staked_sui],
+        );
+        builder.transfer_arg(sender, Argument::Result(0));
+        let fx = execute(&mut sim, sender, &kp, gas.0, builder);
+
+        let [fungible_object] = created(&sim, &fx, |o| {
+            o.struct_tag()
+                .is_some_and(|tag| FungibleStakedSui::is_fungible_staked_sui(&tag))
+        })
+        .try_into()
+        .unwrap();
+        let fungible = FungibleStakedSui::try_from(&fungible_object).unwrap();
+        assert_eq!(fungible.id(), fungible_object.id());
+        assert_eq!(fungible.pool_id(), validator.staking_pool_id);
+        assert!(fungible.value() > 0);
+        assert_round_trip(&fungible_object, &fungible);
+
+        // The pool records the SUI backing its fungible stakes in its extra fields.
+        let [data_object] = created(&sim, &fx, |o| {
+            o.struct_tag().is_some_and(|tag| {
+                tag.type_params.get(1)
+                    == Some(&TypeTag::Struct(Box::new(FungibleStakedSuiData::type_())))
+            })
+        })
+        .try_into()
+        .unwrap();
+        let contents = data_object.data.try_as_move().unwrap().contents();
+        let data: Field<FungibleStakedSuiDataKey, FungibleStakedSuiData> =
+            bcs::from_bytes(contents).unwrap();
+        assert_eq!(data.value.total_supply(), fungible.value());
+        assert_eq!(data.value.principal(), MIST_PER_SUI);
+        assert_round_trip(&data_object, &data);
+    }
 }
@@ -4,6 +4,7 @@
 use alloy::dyn_abi::DynSolValue;
 use alloy::primitives::{Address as EthAddress, Bytes, U256};
 use alloy::providers::{Provider, WalletProvider};
+use alloy::rpc::types::{TransactionReceipt, TransactionRequest};
 use anyhow::anyhow;
 use clap::*;
 use fastcrypto::encoding::Encoding;
@@ -20,6 +21,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 use sui_bridge::abi::EthBridgeCommittee;
 use sui_bridge::abi::{EthSuiBridge, eth_sui_bridge};
+use sui_bridge::chain_adapter::{ChainAdapter, ChainAdapterConfig};
 use sui_bridge::crypto::BridgeAuthorityPublicKeyBytes;
 use sui_bridge::encoding::TOKEN_TRANSFER_MESSAGE_VERSION_V2;
 use sui_bridge::sui_client::SuiBridgeClient;
@@ -407,6 +409,10 @@ pub struct BridgeCliConfig {
     pub sui_key_path: Option<PathBuf>,
     /// See `sui_key_path`. Must be Secp256k1 key.
     pub eth_key_path: Option<PathBuf>,
+    /// The EVM chain the bridge contracts are deployed on, which decides how transactions are
+    /// submitted to it. Defaults to Ethereum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_adapter: Option<ChainAdapterConfig>,
 }
 
 impl Config for BridgeCliConfig {}
@@ -428,6 +434,8 @@ pub struct LoadedBridgeCliConfig {
     sui_key: SuiKeyPair,
     /// Key pair for Eth operations, must be Secp256k1 key
     eth_signer_provider: EthSignerProvider,
+    /// Submits transactions to the EVM chain.
+    chain_adapter: Arc<dyn ChainAdapter>,
 }
 
 impl LoadedBridgeCliConfig {
@@ -492,6 +500,7 @@ impl LoadedBridgeCliConfig {
             eth_bridge_config_proxy_address,
             sui_key,
             eth_signer_provider,
+            chain_adapter: cli_config.chain_adapter.unwrap_or_default().build(),
         })
     }
 }
@@ -501,6 +510,23 @@ impl LoadedBridgeCliConfig {
         self.eth_signer_provider.clone()
     }
 
+    /// Signs and submits `tx` to the EVM chain the way its `ChainAdapter` requires, waiting for
+    /// its receipt.
+    pub async fn submit_eth_transaction(
+        self: &LoadedBridgeCliConfig,
+        tx: TransactionRequest,
+    ) -> anyhow::Result<TransactionReceipt> {
+        self.chain_adapter
+            .submit_transaction(&self.eth_signer_provider, tx)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to submit transaction to {}: {e:?}",
+                    self.chain_adapter.name()
+                )
+            })
+    }
+
     pub async fn get_sui_account_info(
         self: &LoadedBridgeCliConfig,
     ) -> anyhow::Result<(SuiKeyPair, SuiAddress, ObjectRef)> {
@@ -651,23 +677,17 @@ async fn deposit_native_ether_on_eth(
         config.eth_signer_provider().clone(),
     );
     let amount: U256 = alloy::primitives::utils::parse_units(ether_amount, "ether")?.into();
-    let pending_tx = match version {
-        BridgeVersion::V2 => {
-            eth_sui_bridge
-                .bridgeETHV2(sui_recipient_address.to_vec().into(), target_chain)
-                .value(amount)
-                .send()
-                .await?
-        }
-        BridgeVersion::V1 => {
-            eth_sui_bridge
-                .bridgeETH(sui_recipient_address.to_vec().into(), target_chain)
-                .value(amount)
-                .send()
-                .await?
-        }
+    let tx = match version {
+        BridgeVersion::V2 => eth_sui_bridge
+            .bridgeETHV2(sui_recipient_address.to_vec().into(), target_chain)
+            .value(amount)
+            .into_transaction_request(),
+        BridgeVersion::V1 => eth_sui_bridge
+            .bridgeETH(sui_recipient_address.to_vec().into(), target_chain)
+            .value(amount)
+            .into_transaction_request(),
     };
-    let tx_receipt = pending_tx.get_receipt().await?;
+    let tx_receipt = config.submit_eth_transaction(tx).await?;
     info!(
         "Deposited {ether_amount} Ethers ({version:?}) to {:?} (target chain {target_chain}). Receipt: {:?}",
         sui_recipient_address, tx_receipt,
@@ -851,12 +871,7 @@ async fn claim_on_eth(
             resp
         );
     } else {
-        let eth_claim_tx_receipt = config
-            .eth_signer_provider
-            .send_transaction(tx)
-            .await?
-            .get_receipt()
-            .await?;
+        let eth_claim_tx_receipt = config.submit_eth_transaction(tx).await?;
         println!(
             "Sui to Eth bridge transfer ({version_label}) claimed: {:?}",
             eth_claim_tx_receipt
@@ -15,6 +15,7 @@ use std::str::from_utf8;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_bridge::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use sui_bridge::committee_health::CommitteeHealth;
 use sui_bridge::crypto::{BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes};
 use sui_bridge::eth_transaction_builder::build_eth_transaction;
 use sui_bridge::metrics::BridgeMetrics;
@@ -105,6 +106,7 @@ async fn main() -> anyhow::Result<()> {
                 bridge_committee,
                 metrics,
                 Arc::new(BTreeMap::new()),
+                Arc::new(CommitteeHealth::default()),
             );
 
             // Handle Sui Side
@@ -164,7 +166,6 @@ async fn main() -> anyhow::Result<()> {
 
             // Handle eth side
             // TODO assert chain id returned from rpc matches chain_id
-            let eth_signer_provider = config.eth_signer_provider();
             // Create BridgeAction
             let eth_action = make_action(chain_id, &cmd);
             println!("Action to execute on Eth: {:?}", eth_action);
@@ -183,11 +184,7 @@ async fn main() -> anyhow::Result<()> {
                 .await
                 .expect("Failed to build eth transaction");
             println!("sending Eth tx: {:?}", tx);
-            let tx_receipt_result = eth_signer_provider
-                .send_transaction(tx)
-                .await?
-                .get_receipt()
-                .await;
+            let tx_receipt_result = config.submit_eth_transaction(tx).await;
             match tx_receipt_result {
                 Ok(tx_receipt) => {
                     println!(
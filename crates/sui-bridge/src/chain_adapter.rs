@@ -0,0 +1,200 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chain specific behaviour of the EVM side of the bridge.
+//!
+//! `EthClient`, the orchestrator and the bridge CLI delegate everything that differs between EVM
+//! chains to a `ChainAdapter`: which blocks are considered final, how logs emitted by the bridge
+//! contracts are decoded, and how transactions are submitted. Ethereum uses `EthereumAdapter`. Most other
+//! EVM chains can be described with `ConfirmationDepthAdapter` through configuration alone;
+//! chains that need more can implement the trait directly.
+
+use crate::abi::EthBridgeEvent;
+use crate::error::{BridgeError, BridgeResult};
+use crate::types::EthLog;
+use crate::utils::{EthProvider, EthSignerProvider};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Block, TransactionReceipt, TransactionRequest};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[async_trait]
+pub trait ChainAdapter: Send + Sync + 'static {
+    /// Name of the chain, used in logs.
+    fn name(&self) -> &str;
+
+    /// Returns the number of the latest block that can no longer be reorged out.
+    async fn get_last_finalized_block_id(&self, provider: &EthProvider) -> BridgeResult<u64>;
+
+    /// Decodes a log emitted by one of the bridge contracts. Returns `None` if the log is not
+    /// a bridge event.
+    fn decode_bridge_event(&self, log: &EthLog) -> Option<EthBridgeEvent> {
+        EthBridgeEvent::try_from_eth_log(log)
+    }
+
+    /// Signs and submits `tx`, waiting for its receipt.
+    async fn submit_transaction(
+        &self,
+        signer: &EthSignerProvider,
+        tx: TransactionRequest,
+    ) -> BridgeResult<TransactionReceipt> {
+        let pending = signer.send_transaction(tx).await?;
+        Ok(pending.get_receipt().await?)
+    }
+}
+
+/// Ethereum mainnet and testnets, using the `finalized` block tag.
+#[derive(Debug, Default)]
+pub struct EthereumAdapter;
+
+#[async_trait]
+impl ChainAdapter for EthereumAdapter {
+    fn name(&self) -> &str {
+        "ethereum"
+    }
+
+    async fn get_last_finalized_block_id(&self, provider: &EthProvider) -> BridgeResult<u64> {
+        let block: Option<Block> = provider
+            .raw_request("eth_getBlockByNumber".into(), ("finalized", false))
+            .await?;
+        let block = block.ok_or(BridgeError::TransientProviderError(
+            "Provider fails to return last finalized block".into(),
+        ))?;
+        Ok(block.number())
+    }
+}
+
+/// EVM chains without a reliable `finalized` block tag. A block is considered final once
+/// `confirmations` blocks have been built on top of it.
+#[derive(Debug)]
+pub struct ConfirmationDepthAdapter {
+    name: String,
+    confirmations: u64,
+    legacy_transactions: bool,
+}
+
+impl ConfirmationDepthAdapter {
+    pub fn new(name: String, confirmations: u64, legacy_transactions: bool) -> Self {
+        Self {
+            name,
+            confirmations,
+            legacy_transactions,
+        }
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for ConfirmationDepthAdapter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn get_last_finalized_block_id(&self, provider: &EthProvider) -> BridgeResult<u64> {
+        let latest = provider.get_block_number().await?;
+        latest.checked_sub(self.confirmations).ok_or_else(|| {
+            BridgeError::TransientProviderError(format!(
+                "Latest block {latest} is below the confirmation depth {}",
+                self.confirmations
+            ))
+        })
+    }
+
+    async fn submit_transaction(
+        &self,
+        signer: &EthSignerProvider,
+        mut tx: TransactionRequest,
+    ) -> BridgeResult<TransactionReceipt> {
+        // Chains without EIP-1559 reject dynamic fee transactions; setting a gas price makes the
+        // gas filler produce a legacy transaction instead.
+        if self.legacy_transactions && tx.gas_price.is_none() {
+            tx.gas_price = Some(signer.get_gas_price().await?);
+        }
+        let pending = signer.send_transaction(tx).await?;
+        Ok(pending.get_receipt().await?)
+    }
+}
+
+/// Selects the `ChainAdapter` used for the EVM side of the bridge.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum ChainAdapterConfig {
+    #[default]
+    Ethereum,
+    #[serde(rename_all = "kebab-case")]
+    ConfirmationDepth {
+        name: String,
+        /// Number of blocks on top of a block before it is considered final.
+        confirmations: u64,
+        /// Submit legacy (pre EIP-1559) transactions.
+        #[serde(default)]
+        legacy_transactions: bool,
+    },
+}
+
+impl ChainAdapterConfig {
+    pub fn build(&self) -> Arc<dyn ChainAdapter> {
+        match self {
+            Self::Ethereum => Arc::new(EthereumAdapter),
+            Self::ConfirmationDepth {
+                name,
+                confirmations,
+                legacy_transactions,
+            } => Arc::new(ConfirmationDepthAdapter::new(
+                name.clone(),
+                *confirmations,
+                *legacy_transactions,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockService;
+    use alloy::primitives::U64;
+
+    #[tokio::test]
+    async fn test_confirmation_depth_finality() {
+        let mock_provider = EthMockService::new();
+        mock_provider
+            .add_response("eth_blockNumber", (), U64::from(110))
+            .unwrap();
+        let provider = mock_provider.as_provider();
+
+        let adapter = ConfirmationDepthAdapter::new("test".to_string(), 10, false);
+        assert_eq!(
+            adapter
+                .get_last_finalized_block_id(&provider)
+                .await
+                .unwrap(),
+            100
+        );
+
+        let adapter = ConfirmationDepthAdapter::new("test".to_string(), 200, false);
+        assert!(
+            adapter
+                .get_last_finalized_block_id(&provider)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_chain_adapter_config_deserialization() {
+        let config: ChainAdapterConfig = serde_json::from_str(
+            r#"{"type": "confirmation-depth", "name": "some-l2", "confirmations": 64}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            ChainAdapterConfig::ConfirmationDepth {
+                name: "some-l2".to_string(),
+                confirmations: 64,
+                legacy_transactions: false,
+            }
+        );
+        assert_eq!(config.build().name(), "some-l2");
+    }
+}
@@ -4,6 +4,7 @@
 //! BridgeAuthorityAggregator aggregates signatures from BridgeCommittee.
 
 use crate::client::bridge_client::BridgeClient;
+use crate::committee_health::CommitteeHealth;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::crypto::BridgeAuthoritySignInfo;
 use crate::error::{BridgeError, BridgeResult};
@@ -34,6 +35,7 @@ pub struct BridgeAuthorityAggregator {
     pub clients: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, Arc<BridgeClient>>>,
     pub metrics: Arc<BridgeMetrics>,
     pub committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    pub health: Arc<CommitteeHealth>,
 }
 
 impl BridgeAuthorityAggregator {
@@ -41,7 +43,9 @@ impl BridgeAuthorityAggregator {
         committee: Arc<BridgeCommittee>,
         metrics: Arc<BridgeMetrics>,
         committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+        health: Arc<CommitteeHealth>,
     ) -> Self {
+        health.update_committee(committee.clone(), committee_keys_to_names.clone());
         let clients: BTreeMap<BridgeAuthorityPublicKeyBytes, Arc<BridgeClient>> = committee
             .members()
             .iter()
@@ -72,6 +76,7 @@ impl BridgeAuthorityAggregator {
             clients: Arc::new(clients),
             metrics,
             committee_keys_to_names,
+            health,
         }
     }
 
@@ -81,6 +86,7 @@ impl BridgeAuthorityAggregator {
             committee,
             Arc::new(BridgeMetrics::new_for_testing()),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         )
     }
 
@@ -88,20 +94,27 @@ impl BridgeAuthorityAggregator {
         &self,
         action: BridgeAction,
     ) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+        self.health.record_attempt(&action);
+        let digest = action.digest();
         let state = GetSigsState::new(
             action.approval_threshold(),
             self.committee.clone(),
             self.metrics.clone(),
             self.committee_keys_to_names.clone(),
+            self.health.clone(),
         );
-        request_sign_bridge_action_into_certification(
+        let result = request_sign_bridge_action_into_certification(
             action,
             self.committee.clone(),
             self.clients.clone(),
             state,
             Duration::from_millis(PREFETCH_TIMEOUT_MS),
         )
-        .await
+        .await;
+        if result.is_ok() {
+            self.health.record_certified(&digest);
+        }
+        result
     }
 }
 
@@ -114,6 +127,7 @@ struct GetSigsState {
     committee: Arc<BridgeCommittee>,
     metrics: Arc<BridgeMetrics>,
     committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    health: Arc<CommitteeHealth>,
 }
 
 impl GetSigsState {
@@ -122,6 +136,7 @@ impl GetSigsState {
         committee: Arc<BridgeCommittee>,
         metrics: Arc<BridgeMetrics>,
         committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+        health: Arc<CommitteeHealth>,
     ) -> Self {
         Self {
             committee,
@@ -131,6 +146,7 @@ impl GetSigsState {
             validity_threshold,
             metrics,
             committee_keys_to_names,
+            health,
         }
     }
 
@@ -153,6 +169,8 @@ impl GetSigsState {
             Entry::Vacant(e) => {
                 e.insert(signed_action.auth_sig().clone());
                 self.add_ok_stake(stake, &name);
+                self.health
+                    .record_signature(&name, stake, signed_action.data().digest());
             }
             Entry::Occupied(_e) => {
                 return Err(BridgeError::AuthoritySignatureDuplication(format!(
@@ -295,6 +313,7 @@ async fn request_sign_bridge_action_into_certification(
                                     name.concise(),
                                     e
                                 );
+                                state.health.record_failure(&name, format!("{:?}", e));
                                 state.add_bad_stake(stake, &name);
                             }
                         }
@@ -305,6 +324,7 @@ async fn request_sign_bridge_action_into_certification(
                             name.concise(),
                             e
                         );
+                        state.health.record_failure(&name, format!("{:?}", e));
                         state.add_bad_stake(stake, &name);
                     }
                 };
@@ -620,6 +640,7 @@ mod tests {
             committee.clone(),
             metrics.clone(),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         );
         let resp = request_sign_bridge_action_into_certification(
             action.clone(),
@@ -642,6 +663,7 @@ mod tests {
             committee.clone(),
             metrics.clone(),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         );
         let resp = request_sign_bridge_action_into_certification(
             action.clone(),
@@ -664,6 +686,7 @@ mod tests {
             committee.clone(),
             metrics.clone(),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         );
         let start = std::time::Instant::now();
         let resp = request_sign_bridge_action_into_certification(
@@ -815,6 +838,7 @@ mod tests {
             Arc::new(committee),
             metrics.clone(),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         );
 
         assert!(!state.is_too_many_error());
@@ -845,6 +869,7 @@ mod tests {
             Arc::new(committee),
             metrics.clone(),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         );
 
         assert!(!state.is_too_many_error());
@@ -869,6 +894,7 @@ mod tests {
             Arc::new(committee.clone()),
             metrics.clone(),
             Arc::new(BTreeMap::new()),
+            Arc::new(CommitteeHealth::default()),
         );
 
         let sui_tx_digest = TransactionDigest::random();
@@ -0,0 +1,398 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Committee health tracking for the bridge client.
+//!
+//! `CommitteeHealth` is fed by `BridgeAuthorityAggregator` with the outcome of every signature
+//! request it sends to committee members. It keeps, per member, the last action it signed and
+//! the last error it returned, and per action still waiting for a certificate, the stake
+//! collected so far in the latest attempt. The report is served as JSON on a local-only port so
+//! operators can spot an unresponsive member or a stuck transfer.
+
+use crate::config::DEFAULT_STALE_ACTION_THRESHOLD_SECS;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::types::{BridgeAction, BridgeActionDigest, BridgeActionType, BridgeCommittee};
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_types::bridge::BridgeChainId;
+use sui_types::committee::StakeUnit;
+use tracing::{info, warn};
+
+pub const COMMITTEE_HEALTH_PATH: &str = "/committee_health";
+
+// Actions that are abandoned by the executor stay in the pending set so they keep showing up as
+// stale; this bounds how many are kept.
+const MAX_PENDING_ACTIONS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberHealth {
+    pub name: Option<String>,
+    /// Hex encoded bridge authority public key.
+    pub pubkey: String,
+    pub voting_power: StakeUnit,
+    pub is_blocklisted: bool,
+    /// Whether the member's most recent response was a valid signature.
+    pub live: bool,
+    pub last_signed_action: Option<BridgeActionDigest>,
+    pub last_signed_at_ms: Option<u64>,
+    pub last_error: Option<String>,
+    pub last_error_at_ms: Option<u64>,
+    pub consecutive_failures: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingActionHealth {
+    pub digest: BridgeActionDigest,
+    pub action_type: BridgeActionType,
+    pub chain_id: BridgeChainId,
+    pub seq_number: u64,
+    pub first_attempt_at_ms: u64,
+    pub attempts: u64,
+    /// Stake that signed the action in the latest attempt.
+    pub signed_stake: StakeUnit,
+    pub approval_threshold: StakeUnit,
+    pub signers: Vec<String>,
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitteeHealthReport {
+    pub members: Vec<MemberHealth>,
+    pub pending_actions: Vec<PendingActionHealth>,
+    /// Human readable alerts for stale actions and members that keep failing.
+    pub alerts: Vec<String>,
+}
+
+#[derive(Default)]
+struct MemberRecord {
+    last_signed: Option<(BridgeActionDigest, SystemTime)>,
+    last_error: Option<(String, SystemTime)>,
+    consecutive_failures: u64,
+}
+
+struct PendingRecord {
+    action_type: BridgeActionType,
+    chain_id: BridgeChainId,
+    seq_number: u64,
+    first_attempt: SystemTime,
+    attempts: u64,
+    signed_stake: StakeUnit,
+    approval_threshold: StakeUnit,
+    signers: BTreeSet<BridgeAuthorityPublicKeyBytes>,
+}
+
+#[derive(Default)]
+struct Inner {
+    committee: Option<Arc<BridgeCommittee>>,
+    committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    members: BTreeMap<BridgeAuthorityPublicKeyBytes, MemberRecord>,
+    pending: BTreeMap<BridgeActionDigest, PendingRecord>,
+}
+
+pub struct CommitteeHealth {
+    stale_action_threshold: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl std::fmt::Debug for CommitteeHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitteeHealth")
+            .field("stale_action_threshold", &self.stale_action_threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for CommitteeHealth {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_STALE_ACTION_THRESHOLD_SECS))
+    }
+}
+
+impl CommitteeHealth {
+    pub fn new(stale_action_threshold: Duration) -> Self {
+        Self {
+            stale_action_threshold,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Called whenever the aggregator is rebuilt for a new committee.
+    pub fn update_committee(
+        &self,
+        committee: Arc<BridgeCommittee>,
+        committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .members
+            .retain(|name, _| committee.member(name).is_some());
+        inner.committee = Some(committee);
+        inner.committee_keys_to_names = committee_keys_to_names;
+    }
+
+    /// Records the start of a new round of signature requests for `action`.
+    pub fn record_attempt(&self, action: &BridgeAction) {
+        let now = SystemTime::now();
+        let digest = action.digest();
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.pending.contains_key(&digest) && inner.pending.len() >= MAX_PENDING_ACTIONS {
+            let oldest = inner
+                .pending
+                .iter()
+                .min_by_key(|(_, record)| record.first_attempt)
+                .map(|(digest, _)| *digest);
+            if let Some(oldest) = oldest {
+                inner.pending.remove(&oldest);
+            }
+        }
+        let record = inner
+            .pending
+            .entry(digest)
+            .or_insert_with(|| PendingRecord {
+                action_type: action.action_type(),
+                chain_id: action.chain_id(),
+                seq_number: action.seq_number(),
+                first_attempt: now,
+                attempts: 0,
+                signed_stake: 0,
+                approval_threshold: action.approval_threshold(),
+                signers: BTreeSet::new(),
+            });
+        record.attempts += 1;
+        record.signed_stake = 0;
+        record.signers.clear();
+        if is_stale(record.first_attempt, now, self.stale_action_threshold) {
+            warn!(
+                ?digest,
+                attempts = record.attempts,
+                "Bridge action has been waiting for a certificate longer than {:?}",
+                self.stale_action_threshold,
+            );
+        }
+    }
+
+    pub fn record_signature(
+        &self,
+        name: &BridgeAuthorityPublicKeyBytes,
+        stake: StakeUnit,
+        digest: BridgeActionDigest,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        let member = inner.members.entry(name.clone()).or_default();
+        member.last_signed = Some((digest, SystemTime::now()));
+        member.consecutive_failures = 0;
+        if let Some(pending) = inner.pending.get_mut(&digest)
+            && pending.signers.insert(name.clone())
+        {
+            pending.signed_stake += stake;
+        }
+    }
+
+    pub fn record_failure(&self, name: &BridgeAuthorityPublicKeyBytes, error: String) {
+        let mut inner = self.inner.lock().unwrap();
+        let member = inner.members.entry(name.clone()).or_default();
+        member.last_error = Some((error, SystemTime::now()));
+        member.consecutive_failures += 1;
+    }
+
+    /// Records that `digest` reached quorum and is no longer pending.
+    pub fn record_certified(&self, digest: &BridgeActionDigest) {
+        self.inner.lock().unwrap().pending.remove(digest);
+    }
+
+    pub fn report(&self) -> CommitteeHealthReport {
+        let now = SystemTime::now();
+        let inner = self.inner.lock().unwrap();
+        let mut alerts = vec![];
+
+        let mut members = vec![];
+        if let Some(committee) = &inner.committee {
+            for (key, authority) in committee.members() {
+                let name = inner.committee_keys_to_names.get(key).cloned();
+                let record = inner.members.get(key);
+                let last_signed = record.and_then(|r| r.last_signed);
+                let last_error = record.and_then(|r| r.last_error.clone());
+                let consecutive_failures = record.map_or(0, |r| r.consecutive_failures);
+                let live = match (&last_signed, &last_error) {
+                    (Some((_, signed_at)), Some((_, failed_at))) => signed_at >= failed_at,
+                    (Some(_), None) => true,
+                    _ => false,
+                };
+                if consecutive_failures > 0 && !authority.is_blocklisted {
+                    alerts.push(format!(
+                        "member {} failed the last {} signature requests",
+                        name.as_deref().unwrap_or(&Hex::encode(key.as_bytes())),
+                        consecutive_failures,
+                    ));
+                }
+                members.push(MemberHealth {
+                    name,
+                    pubkey: Hex::encode(key.as_bytes()),
+                    voting_power: authority.voting_power,
+                    is_blocklisted: authority.is_blocklisted,
+                    live,
+                    last_signed_action: last_signed.map(|(digest, _)| digest),
+                    last_signed_at_ms: last_signed.map(|(_, at)| unix_ms(at)),
+                    last_error_at_ms: last_error.as_ref().map(|(_, at)| unix_ms(*at)),
+                    last_error: last_error.map(|(error, _)| error),
+                    consecutive_failures,
+                });
+            }
+        }
+
+        let mut pending_actions = vec![];
+        for (digest, record) in &inner.pending {
+            let stale = is_stale(record.first_attempt, now, self.stale_action_threshold);
+            if stale {
+                alerts.push(format!(
+                    "{} action {:?} (chain {}, seq {}) has no certificate after {} attempts, signed stake {}/{}",
+                    record.action_type,
+                    digest,
+                    record.chain_id,
+                    record.seq_number,
+                    record.attempts,
+                    record.signed_stake,
+                    record.approval_threshold,
+                ));
+            }
+            pending_actions.push(PendingActionHealth {
+                digest: *digest,
+                action_type: record.action_type,
+                chain_id: record.chain_id,
+                seq_number: record.seq_number,
+                first_attempt_at_ms: unix_ms(record.first_attempt),
+                attempts: record.attempts,
+                signed_stake: record.signed_stake,
+                approval_threshold: record.approval_threshold,
+                signers: record
+                    .signers
+                    .iter()
+                    .map(|key| {
+                        inner
+                            .committee_keys_to_names
+                            .get(key)
+                            .cloned()
+                            .unwrap_or_else(|| Hex::encode(key.as_bytes()))
+                    })
+                    .collect(),
+                stale,
+            });
+        }
+        pending_actions.sort_by_key(|action| action.first_attempt_at_ms);
+
+        CommitteeHealthReport {
+            members,
+            pending_actions,
+            alerts,
+        }
+    }
+}
+
+fn is_stale(first_attempt: SystemTime, now: SystemTime, threshold: Duration) -> bool {
+    now.duration_since(first_attempt)
+        .is_ok_and(|elapsed| elapsed >= threshold)
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
+
+pub fn run_committee_health_server(
+    socket_address: SocketAddr,
+    health: Arc<CommitteeHealth>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let listener = tokio::net::TcpListener::bind(socket_address).await.unwrap();
+        info!("Committee health endpoint listening on {}", socket_address);
+        let router = Router::new()
+            .route(COMMITTEE_HEALTH_PATH, get(committee_health))
+            .with_state(health);
+        axum::serve(listener, router.into_make_service())
+            .await
+            .unwrap();
+    })
+}
+
+async fn committee_health(
+    State(health): State<Arc<CommitteeHealth>>,
+) -> Json<CommitteeHealthReport> {
+    Json(health.report())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_authority_and_key, get_test_sui_to_eth_bridge_action};
+
+    #[test]
+    fn test_committee_health_report() {
+        let mut authorities = vec![];
+        for _ in 0..4 {
+            let (authority, _, _) = get_test_authority_and_key(2500, 12345);
+            authorities.push(authority);
+        }
+        let keys = authorities
+            .iter()
+            .map(|a| a.pubkey_bytes())
+            .collect::<Vec<_>>();
+        let committee = Arc::new(BridgeCommittee::new(authorities).unwrap());
+
+        let health = CommitteeHealth::new(Duration::ZERO);
+        health.update_committee(
+            committee,
+            Arc::new(BTreeMap::from([(
+                keys[0].clone(),
+                "validator-0".to_string(),
+            )])),
+        );
+
+        let action = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let digest = action.digest();
+        health.record_attempt(&action);
+        health.record_signature(&keys[0], 2500, digest);
+        health.record_signature(&keys[1], 2500, digest);
+        health.record_failure(&keys[2], "timeout".to_string());
+
+        let report = health.report();
+        assert_eq!(report.members.len(), 4);
+        let member0 = report
+            .members
+            .iter()
+            .find(|m| m.name.as_deref() == Some("validator-0"))
+            .unwrap();
+        assert!(member0.live);
+        assert_eq!(member0.last_signed_action, Some(digest));
+        let member2 = report
+            .members
+            .iter()
+            .find(|m| m.pubkey == Hex::encode(keys[2].as_bytes()))
+            .unwrap();
+        assert!(!member2.live);
+        assert_eq!(member2.consecutive_failures, 1);
+
+        assert_eq!(report.pending_actions.len(), 1);
+        let pending = &report.pending_actions[0];
+        assert_eq!(pending.signed_stake, 5000);
+        assert_eq!(pending.approval_threshold, action.approval_threshold());
+        assert!(pending.stale);
+        assert_eq!(report.alerts.len(), 2);
+
+        // A new attempt resets the signers of the previous one.
+        health.record_attempt(&action);
+        let report = health.report();
+        assert_eq!(report.pending_actions[0].attempts, 2);
+        assert_eq!(report.pending_actions[0].signed_stake, 0);
+
+        health.record_certified(&digest);
+        assert!(health.report().pending_actions.is_empty());
+    }
+}
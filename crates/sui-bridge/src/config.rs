@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::abi::EthBridgeConfig;
+use crate::chain_adapter::ChainAdapterConfig;
 use crate::crypto::BridgeAuthorityKeyPair;
 use crate::error::BridgeError;
 use crate::eth_client::EthClient;
@@ -21,6 +22,7 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_config::Config;
 use sui_keys::keypair_file::read_key;
 use sui_types::base_types::ObjectRef;
@@ -71,6 +73,9 @@ pub struct EthConfig {
     /// reprocess the events from this block number every time it starts.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eth_contracts_start_block_override: Option<u64>,
+    /// Finality and transaction rules of the EVM chain. Defaults to Ethereum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_adapter: Option<ChainAdapterConfig>,
 }
 
 fn default_quorum() -> usize {
@@ -158,6 +163,11 @@ pub struct BridgeNodeConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watchdog_config: Option<WatchdogConfig>,
+
+    /// Serves committee liveness and pending action quorum progress on a local port.
+    /// Only used when `run_client` is true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee_health: Option<CommitteeHealthConfig>,
 }
 
 pub fn default_ed25519_key_pair() -> NetworkKeyPair {
@@ -179,6 +189,27 @@ pub struct WatchdogConfig {
     pub total_supplies: BTreeMap<String, String>,
 }
 
+pub const DEFAULT_STALE_ACTION_THRESHOLD_SECS: u64 = 15 * 60;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeHealthConfig {
+    /// Port of the committee health endpoint. It only listens on localhost.
+    pub listen_port: u16,
+    /// How long an action may wait for a certificate before it is reported as stale.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_action_threshold_secs: Option<u64>,
+}
+
+impl CommitteeHealthConfig {
+    pub fn stale_action_threshold(&self) -> Duration {
+        Duration::from_secs(
+            self.stale_action_threshold_secs
+                .unwrap_or(DEFAULT_STALE_ACTION_THRESHOLD_SECS),
+        )
+    }
+}
+
 impl Config for BridgeNodeConfig {}
 
 impl BridgeNodeConfig {
@@ -376,7 +407,9 @@ impl BridgeNodeConfig {
         }
 
         let eth_client = Arc::new(
-            EthClient::from_provider(provider, HashSet::from_iter(valid_addresses.clone())).await?,
+            EthClient::from_provider(provider, HashSet::from_iter(valid_addresses.clone()))
+                .await?
+                .with_chain_adapter(self.eth.chain_adapter.clone().unwrap_or_default().build()),
         );
         info!("Ethereum client setup complete");
         Ok((eth_client, valid_addresses))
@@ -869,6 +869,7 @@ pub(crate) async fn start_bridge_cluster(
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: None,
+                chain_adapter: None,
             },
             sui: SuiConfig {
                 sui_rpc_url: test_cluster.inner.fullnode_handle.rpc_url.clone(),
@@ -881,6 +882,7 @@ pub(crate) async fn start_bridge_cluster(
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            committee_health: None,
         };
         // Spawn bridge node in memory
         handles.push(
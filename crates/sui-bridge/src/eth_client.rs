@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::abi::EthBridgeEvent;
+use crate::chain_adapter::{ChainAdapter, EthereumAdapter};
 use crate::error::{BridgeError, BridgeResult};
 use crate::metered_eth_provider::new_metered_eth_provider;
 use crate::metrics::BridgeMetrics;
@@ -9,7 +9,7 @@ use crate::types::{BridgeAction, EthLog, RawEthLog};
 use crate::utils::EthProvider;
 use alloy::primitives::{Address as EthAddress, TxHash};
 use alloy::providers::Provider;
-use alloy::rpc::types::{Block, Filter, Log};
+use alloy::rpc::types::{Filter, Log};
 use std::collections::HashSet;
 use std::sync::Arc;
 use tap::TapFallible;
@@ -20,6 +20,7 @@ use crate::eth_mock_provider::EthMockService;
 pub struct EthClient {
     provider: EthProvider,
     contract_addresses: HashSet<EthAddress>,
+    chain_adapter: Arc<dyn ChainAdapter>,
 }
 
 impl EthClient {
@@ -32,6 +33,7 @@ impl EthClient {
         let self_ = Self {
             provider,
             contract_addresses,
+            chain_adapter: Arc::new(EthereumAdapter),
         };
         self_.describe().await?;
         Ok(self_)
@@ -46,14 +48,25 @@ impl EthClient {
         let self_ = Self {
             provider,
             contract_addresses,
+            chain_adapter: Arc::new(EthereumAdapter),
         };
         self_.describe().await?;
         Ok(self_)
     }
 
+    /// Replace the default `EthereumAdapter`, for bridges to other EVM chains.
+    pub fn with_chain_adapter(mut self, chain_adapter: Arc<dyn ChainAdapter>) -> Self {
+        self.chain_adapter = chain_adapter;
+        self
+    }
+
     pub fn provider(&self) -> EthProvider {
         self.provider.clone()
     }
+
+    pub fn chain_adapter(&self) -> Arc<dyn ChainAdapter> {
+        self.chain_adapter.clone()
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +79,7 @@ impl EthClient {
         Self {
             provider,
             contract_addresses,
+            chain_adapter: Arc::new(EthereumAdapter),
         }
     }
 }
@@ -81,7 +95,8 @@ impl EthClient {
         let chain_id = self.get_chain_id().await?;
         let block_number = self.provider.get_block_number().await?;
         tracing::info!(
-            "EthClient is connected to chain {chain_id}, current block number: {block_number}"
+            "EthClient is connected to {} chain {chain_id}, current block number: {block_number}",
+            self.chain_adapter.name(),
         );
         Ok(())
     }
@@ -128,7 +143,9 @@ impl EthClient {
             log_index_in_tx: event_idx,
             log: log.clone(),
         };
-        let bridge_event = EthBridgeEvent::try_from_eth_log(&eth_log)
+        let bridge_event = self
+            .chain_adapter
+            .decode_bridge_event(&eth_log)
             .ok_or(BridgeError::NoBridgeEventsInTxPosition)?;
         bridge_event
             .try_into_bridge_action(tx_hash, event_idx)?
@@ -136,14 +153,9 @@ impl EthClient {
     }
 
     pub async fn get_last_finalized_block_id(&self) -> BridgeResult<u64> {
-        let block: Option<Block> = self
-            .provider
-            .raw_request("eth_getBlockByNumber".into(), ("finalized", false))
-            .await?;
-        let block = block.ok_or(BridgeError::TransientProviderError(
-            "Provider fails to return last finalized block".into(),
-        ))?;
-        Ok(block.number())
+        self.chain_adapter
+            .get_last_finalized_block_id(&self.provider)
+            .await
     }
 
     // Note: query may fail if range is too big. Callsite is responsible
@@ -3,7 +3,9 @@
 
 pub mod abi;
 pub mod action_executor;
+pub mod chain_adapter;
 pub mod client;
+pub mod committee_health;
 pub mod config;
 pub mod crypto;
 pub mod encoding;
@@ -166,11 +166,12 @@ where
                     Duration::from_secs(10),
                 )
                 .await;
-                let committee_names = bridge_auth_agg.load().committee_keys_to_names.clone();
+                let current_agg = bridge_auth_agg.load();
                 bridge_auth_agg.store(Arc::new(BridgeAuthorityAggregator::new(
                     Arc::new(new_committee),
                     bridge_metrics.clone(),
-                    committee_names,
+                    current_agg.committee_keys_to_names.clone(),
+                    current_agg.health.clone(),
                 )));
                 info!("Committee updated with CommitteeMemberUrlUpdateEvent");
             }
@@ -187,11 +188,12 @@ where
                     Duration::from_secs(10),
                 )
                 .await;
-                let committee_names = bridge_auth_agg.load().committee_keys_to_names.clone();
+                let current_agg = bridge_auth_agg.load();
                 bridge_auth_agg.store(Arc::new(BridgeAuthorityAggregator::new(
                     Arc::new(new_committee),
                     bridge_metrics.clone(),
-                    committee_names,
+                    current_agg.committee_keys_to_names.clone(),
+                    current_agg.health.clone(),
                 )));
                 info!("Committee updated with BlocklistValidatorEvent");
             }
@@ -3,6 +3,7 @@
 
 use crate::action_executor::BridgeActionExecutor;
 use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use crate::committee_health::{CommitteeHealth, run_committee_health_server};
 use crate::config::{BridgeClientConfig, BridgeNodeConfig, WatchdogConfig};
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::eth_syncer::EthSyncer;
@@ -51,6 +52,7 @@ pub async fn run_bridge_node(
     init_all_struct_tags();
     let metrics = Arc::new(BridgeMetrics::new(&prometheus_registry));
     let watchdog_config = config.watchdog_config.clone();
+    let committee_health_config = config.committee_health.clone();
     let (server_config, client_config) = config.validate(metrics.clone()).await?;
     let sui_chain_identifier = server_config
         .sui_client
@@ -105,14 +107,26 @@ pub async fn run_bridge_node(
     if let Some(client_config) = client_config {
         let committee_keys_to_names =
             Arc::new(get_validator_names_by_pub_keys(&committee, &sui_system).await);
+        let committee_health = Arc::new(match &committee_health_config {
+            Some(config) => CommitteeHealth::new(config.stale_action_threshold()),
+            None => CommitteeHealth::default(),
+        });
         let client_components = start_client_components(
             client_config,
             committee.clone(),
             committee_keys_to_names,
+            committee_health.clone(),
             metrics.clone(),
         )
         .await?;
         handles.extend(client_components);
+
+        if let Some(config) = committee_health_config {
+            handles.push(run_committee_health_server(
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), config.listen_port),
+                committee_health,
+            ));
+        }
     }
 
     let committee_name_mapping = get_committee_voting_power_by_name(&committee, &sui_system).await;
@@ -255,6 +269,7 @@ async fn start_client_components(
     client_config: BridgeClientConfig,
     committee: Arc<BridgeCommittee>,
     committee_keys_to_names: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, String>>,
+    committee_health: Arc<CommitteeHealth>,
     metrics: Arc<BridgeMetrics>,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
     let store: std::sync::Arc<BridgeOrchestratorTables> =
@@ -272,6 +287,7 @@ async fn start_client_components(
     );
 
     let sui_client = client_config.sui_client.clone();
+    let chain_adapter = client_config.eth_client.chain_adapter();
 
     let last_processed_bridge_event_id = sui_modules_to_watch
         .get(&BRIDGE_MODULE_NAME.to_owned())
@@ -312,6 +328,7 @@ async fn start_client_components(
         committee,
         metrics.clone(),
         committee_keys_to_names,
+        committee_health,
     ))));
     // TODO: should we use one query instead of two?
     let sui_token_type_tags = sui_client.get_token_id_map().await.unwrap();
@@ -367,6 +384,7 @@ async fn start_client_components(
         sui_client,
         sui_grpc_events_rx,
         eth_events_rx,
+        chain_adapter,
         store.clone(),
         eth_monitor_tx,
         metrics,
@@ -610,6 +628,7 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: None,
                 eth_contracts_start_block_override: None,
+                chain_adapter: None,
             },
             approved_governance_actions: vec![],
             run_client: false,
@@ -617,6 +636,7 @@ mod tests {
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            committee_health: None,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -681,6 +701,7 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: None,
+                chain_adapter: None,
             },
             approved_governance_actions: vec![],
             run_client: true,
@@ -688,6 +709,7 @@ mod tests {
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            committee_health: None,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -763,6 +785,7 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: Some(0),
+                chain_adapter: None,
             },
             approved_governance_actions: vec![],
             run_client: true,
@@ -770,6 +793,7 @@ mod tests {
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
             watchdog_config: None,
+            committee_health: None,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
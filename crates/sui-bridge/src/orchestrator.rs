@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! `BridgeOrchestrator` is the component that:
-//! 1. monitors Sui and Ethereum events with the help of `SuiSyncer` and `EthSyncer`
+//! 1. monitors Sui and Ethereum events with the help of `SuiSyncer` and `EthSyncer`, decoding
+//!    Ethereum logs with the configured `ChainAdapter`
 //! 2. updates WAL table and cursor tables
 //! 2. hands actions to `BridgeExecutor` for execution
 
@@ -10,6 +11,7 @@ use crate::abi::EthBridgeEvent;
 use crate::action_executor::{
     BridgeActionExecutionWrapper, BridgeActionExecutorTrait, submit_to_executor,
 };
+use crate::chain_adapter::ChainAdapter;
 use crate::events::SuiBridgeEvent;
 use crate::metrics::BridgeMetrics;
 use crate::storage::BridgeOrchestratorTables;
@@ -27,6 +29,7 @@ pub struct BridgeOrchestrator<C> {
     _sui_client: Arc<SuiClient<C>>,
     sui_grpc_events_rx: mysten_metrics::metered_channel::Receiver<(u64, Vec<SuiBridgeEvent>)>,
     eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+    chain_adapter: Arc<dyn ChainAdapter>,
     store: Arc<BridgeOrchestratorTables>,
     eth_monitor_tx: mysten_metrics::metered_channel::Sender<EthBridgeEvent>,
     metrics: Arc<BridgeMetrics>,
@@ -40,6 +43,7 @@ where
         sui_client: Arc<SuiClient<C>>,
         sui_grpc_events_rx: mysten_metrics::metered_channel::Receiver<(u64, Vec<SuiBridgeEvent>)>,
         eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+        chain_adapter: Arc<dyn ChainAdapter>,
         store: Arc<BridgeOrchestratorTables>,
         eth_monitor_tx: mysten_metrics::metered_channel::Sender<EthBridgeEvent>,
         metrics: Arc<BridgeMetrics>,
@@ -48,6 +52,7 @@ where
             _sui_client: sui_client,
             sui_grpc_events_rx,
             eth_events_rx,
+            chain_adapter,
             store,
             eth_monitor_tx,
             metrics,
@@ -92,6 +97,7 @@ where
             store_clone,
             executor_sender,
             self.eth_events_rx,
+            self.chain_adapter,
             self.eth_monitor_tx,
             metrics_clone,
         )));
@@ -171,6 +177,7 @@ where
             u64,
             Vec<EthLog>,
         )>,
+        chain_adapter: Arc<dyn ChainAdapter>,
         eth_monitor_tx: mysten_metrics::metered_channel::Sender<EthBridgeEvent>,
         metrics: Arc<BridgeMetrics>,
     ) {
@@ -190,7 +197,7 @@ where
 
             let bridge_events = logs
                 .iter()
-                .map(EthBridgeEvent::try_from_eth_log)
+                .map(|log| chain_adapter.decode_bridge_event(log))
                 .collect::<Vec<_>>();
 
             let mut actions = vec![];
@@ -262,6 +269,7 @@ mod tests {
     use sui_types::Identifier;
 
     use super::*;
+    use crate::chain_adapter::EthereumAdapter;
     use crate::events::SuiBridgeEvent;
     use crate::events::init_all_struct_tags;
     use crate::test_utils::get_test_sui_to_eth_bridge_action;
@@ -291,6 +299,7 @@ mod tests {
             Arc::new(sui_client),
             sui_grpc_events_rx,
             eth_events_rx,
+            Arc::new(EthereumAdapter),
             store.clone(),
             eth_monitor_tx,
             metrics,
@@ -360,6 +369,7 @@ mod tests {
             Arc::new(sui_client),
             sui_grpc_events_rx,
             eth_events_rx,
+            Arc::new(EthereumAdapter),
             store.clone(),
             eth_monitor_tx,
             metrics,
@@ -446,6 +456,7 @@ mod tests {
             Arc::new(sui_client),
             sui_grpc_events_rx,
             eth_events_rx,
+            Arc::new(EthereumAdapter),
             store.clone(),
             eth_monitor_tx,
             metrics,
@@ -499,6 +510,7 @@ mod tests {
             Arc::new(sui_client),
             sui_grpc_events_rx,
             eth_events_rx,
+            Arc::new(EthereumAdapter),
             store.clone(),
             eth_monitor_tx,
             metrics,
@@ -286,9 +286,11 @@ pub async fn new_wallet_context_from_cluster(
             ws: None,
             basic_auth: None,
             chain_id: None,
+            gas_swap_routes: vec![],
         }],
         active_address: Some(address),
         active_env: Some("localnet".to_string()),
+        watch_only: vec![],
     }
     .persisted(&wallet_config_path)
     .save()
@@ -109,6 +109,11 @@ impl Genesis {
         &self.events
     }
 
+    /// The genesis checkpoint, without verifying its signatures like [`Self::checkpoint`] does.
+    pub fn certified_checkpoint(&self) -> &CertifiedCheckpointSummary {
+        &self.checkpoint
+    }
+
     pub fn checkpoint(&self) -> VerifiedCheckpoint {
         self.checkpoint
             .clone()
@@ -361,7 +366,7 @@ pub struct GenesisChainParameters {
 }
 
 /// Initial set of parameters for a chain.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GenesisCeremonyParameters {
     #[serde(default = "GenesisCeremonyParameters::default_timestamp_ms")]
     pub chain_start_timestamp_ms: u64,
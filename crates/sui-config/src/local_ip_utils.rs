@@ -108,6 +108,29 @@ fn get_ephemeral_port(host: &str) -> std::io::Result<u16> {
     Ok(addr.port())
 }
 
+/// Reserves `port` on `host` the way `get_available_port` reserves the ports it returns, so that
+/// the addresses of a network configured by an earlier run can be used again. Returns false if the
+/// port is in use.
+#[cfg(not(msim))]
+pub fn reserve_port(host: &str, port: u16) -> bool {
+    use std::net::{TcpListener, TcpStream};
+
+    let reserve = || -> std::io::Result<()> {
+        let listener = TcpListener::bind((host, port))?;
+        let _sender = TcpStream::connect(listener.local_addr()?)?;
+        let _incoming = listener.accept()?;
+        Ok(())
+    };
+    reserve().is_ok()
+}
+
+/// In simtest, ports are handed out by a counter, so a port allocated earlier can't be reserved
+/// again.
+#[cfg(msim)]
+pub fn reserve_port(_host: &str, _port: u16) -> bool {
+    false
+}
+
 /// Returns a new unique TCP address for the given host, by finding a new available port.
 pub fn new_tcp_address_for_testing(host: &str) -> Multiaddr {
     format!("/ip4/{}/tcp/{}/https", host, get_available_port(host))
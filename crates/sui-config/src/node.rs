@@ -6,6 +6,7 @@ use crate::genesis;
 use crate::object_storage_config::ObjectStoreConfig;
 use crate::p2p::P2pConfig;
 use crate::transaction_deny_config::{PeerDenySyncConfig, TransactionDenyConfig};
+use crate::transaction_policy_config::DenylistFeedConfig;
 use crate::validator_client_monitor_config::ValidatorClientMonitorConfig;
 use crate::verifier_signing_config::VerifierSigningConfig;
 use anyhow::Result;
@@ -13,7 +14,8 @@ use consensus_config::Parameters as ConsensusParameters;
 use mysten_common::fatal;
 use nonzero_ext::nonzero;
 use once_cell::sync::OnceCell;
-use rand::rngs::OsRng;
+use rand::rngs::{OsRng, StdRng};
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::{BTreeMap, BTreeSet};
@@ -121,6 +123,26 @@ pub struct NodeConfig {
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub disable_json_rpc: bool,
 
+    /// How the JSON-RPC service accepts addresses and object IDs in request parameters. By
+    /// default short and mixed-case hex literals are normalized to their canonical form.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jsonrpc_address_config: Option<JsonRpcAddressConfig>,
+
+    /// Lets JSON-RPC clients ask for large binary fields to be returned as content-addressed
+    /// references instead of inline. Disabled when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jsonrpc_blob_config: Option<JsonRpcBlobConfig>,
+
+    /// Rate limits on JSON-RPC methods, per client and per API key. Disabled when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jsonrpc_rate_limit_config: Option<JsonRpcRateLimitConfig>,
+
+    /// Namespaces of the app-specific JSON-RPC extensions to serve, e.g. `kiosk`. Each must be
+    /// compiled into the node binary and registered with
+    /// `sui_json_rpc::extensions::register_rpc_extension`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jsonrpc_extensions: Vec<String>,
+
     #[serde(default)]
     pub grpc_load_shed: Option<bool>,
 
@@ -290,6 +312,59 @@ pub struct NodeConfig {
     /// Configuration for the trusted peer address prober.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub address_prober: Option<AddressProberConfig>,
+
+    /// Configuration for per-package gas usage accounting.
+    /// When set, gas consumed by executed checkpoints is aggregated per invoked package and
+    /// exposed through the admin interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_gas_accounting: Option<PackageGasAccountingConfig>,
+
+    /// Signed feed of addresses and packages flagged for active exploits. When set, the node
+    /// periodically fetches the feed, and denies transactions from the flagged addresses or
+    /// calling the flagged packages at signing time, until each entry expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denylist_feed: Option<DenylistFeedConfig>,
+
+    /// Configuration for the Move source verification service. When set on a fullnode serving
+    /// JSON-RPC, the node accepts package sources, rebuilds them and records verified-source
+    /// attestations that can be queried over RPC.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_verification_config: Option<SourceVerificationConfig>,
+
+    /// Configuration for the detailed health endpoint. When set, the node serves
+    /// `/health/detailed` on its own address, with a status code load balancers can use as a
+    /// readiness check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
+
+    /// Configuration for per-epoch compliance reports. When set on a fullnode, the node records
+    /// the activity of the configured addresses as it executes checkpoints, and writes a signed
+    /// report with inclusion proofs at the end of every epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance_report: Option<ComplianceReportConfig>,
+
+    /// HTTP endpoints the node POSTs executed transactions and events to, as they match each
+    /// endpoint's filters. Requires a fullnode with index processing enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Sizing of the epoch store's lock tables, which serialize the execution of each transaction
+    /// and the assignment of versions to each shared object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutex_table_config: Option<MutexTableConfig>,
+
+    /// Packages whose functions the validator votes to reject calls to in consensus, e.g. while
+    /// an exploit is being mitigated. Packages can also be blocked and unblocked at runtime
+    /// through the admin interface; those changes are persisted and take precedence over this
+    /// list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consensus_blocked_packages: Vec<ObjectID>,
+
+    /// Configuration for safe mode diagnostics bundles. When set on a validator, the node writes a
+    /// bundle whenever the advance-epoch transaction it builds leaves the system in safe mode.
+    /// Bundles can also be written on demand through the admin interface.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe_mode_diagnostics: Option<SafeModeDiagnosticsConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -461,6 +536,291 @@ impl AddressProberConfig {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HealthCheckConfig {
+    /// Address the detailed health endpoint listens on. Unlike the admin interface this is not
+    /// restricted to localhost, since load balancers need to reach it.
+    pub listen_address: SocketAddr,
+
+    /// Maximum number of checkpoints the highest executed checkpoint may trail the highest
+    /// checkpoint certified by the committee that this node has verified.
+    ///
+    /// If unspecified, this defaults to `100`.
+    pub max_checkpoint_lag: Option<u64>,
+
+    /// Maximum age, in seconds, of the timestamp of the highest executed checkpoint.
+    ///
+    /// If unspecified, this defaults to `60`.
+    pub max_checkpoint_age_secs: Option<u64>,
+
+    /// Minimum share of recent consensus commits that must include a block proposed by this
+    /// validator. Not checked on fullnodes.
+    ///
+    /// If unspecified, this defaults to `0.5`.
+    pub min_consensus_participation: Option<f64>,
+
+    /// Whether a RocksDB column family with stopped writes makes the node unready.
+    ///
+    /// If unspecified, this defaults to `true`.
+    pub unready_on_write_stall: Option<bool>,
+}
+
+impl HealthCheckConfig {
+    pub fn max_checkpoint_lag(&self) -> u64 {
+        self.max_checkpoint_lag.unwrap_or(100)
+    }
+
+    pub fn max_checkpoint_age(&self) -> Duration {
+        Duration::from_secs(self.max_checkpoint_age_secs.unwrap_or(60))
+    }
+
+    pub fn min_consensus_participation(&self) -> f64 {
+        self.min_consensus_participation.unwrap_or(0.5)
+    }
+
+    pub fn unready_on_write_stall(&self) -> bool {
+        self.unready_on_write_stall.unwrap_or(true)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ComplianceReportConfig {
+    /// Addresses whose activity is reported: transactions they sent, and transactions that
+    /// changed their balances or objects.
+    pub addresses: Vec<SuiAddress>,
+
+    /// Directory reports are written to, one sub-directory per epoch.
+    pub output_path: PathBuf,
+
+    /// Key the manifest of every report is signed with, as a personal message, so that the
+    /// signature can be checked with the usual tooling for personal message signatures.
+    pub signing_key_pair: KeyPairWithPath,
+
+    /// Formats the activity is written in. Inclusion proofs and the signed manifest are always
+    /// written.
+    ///
+    /// If unspecified, this defaults to both JSON and CSV.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formats: Vec<ComplianceReportFormat>,
+}
+
+impl ComplianceReportConfig {
+    pub fn formats(&self) -> Vec<ComplianceReportFormat> {
+        if self.formats.is_empty() {
+            vec![ComplianceReportFormat::Json, ComplianceReportFormat::Csv]
+        } else {
+            self.formats.clone()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComplianceReportFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SafeModeDiagnosticsConfig {
+    /// Directory bundles are written to.
+    pub output_path: PathBuf,
+
+    /// How many of the latest consensus commits a bundle holds.
+    #[serde(default = "default_safe_mode_diagnostics_num_commits")]
+    pub num_commits: u32,
+}
+
+pub fn default_safe_mode_diagnostics_num_commits() -> u32 {
+    50
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WebhookConfig {
+    /// URL that matching payloads are POSTed to, as JSON.
+    pub url: String,
+
+    /// Key every payload is signed with. The `X-Sui-Webhook-Signature` header of each request
+    /// holds the hex-encoded HMAC-SHA3-256 of the request's `X-Sui-Webhook-Timestamp` header, a
+    /// `.`, and its body.
+    pub secret: String,
+
+    /// Send transactions that were sent by, or that transferred objects to, any of these
+    /// addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<SuiAddress>,
+
+    /// Send transactions that call a function in any of these packages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<ObjectID>,
+
+    /// Send events of any of these Move types, e.g. `0x2::coin::CoinMetadata<0x2::sui::SUI>`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<String>,
+
+    /// Number of times a payload is sent before it is dropped, including the first attempt.
+    ///
+    /// If unspecified, this defaults to `5`.
+    pub max_attempts: Option<u32>,
+
+    /// Delay before the first retry. Each subsequent retry waits twice as long as the last, up to
+    /// `max-backoff-ms`.
+    ///
+    /// If unspecified, this defaults to `1000`.
+    pub initial_backoff_ms: Option<u64>,
+
+    /// If unspecified, this defaults to `60000`.
+    pub max_backoff_ms: Option<u64>,
+
+    /// If unspecified, this defaults to `10000`.
+    pub request_timeout_ms: Option<u64>,
+
+    /// Number of payloads waiting to be sent to the endpoint. Payloads that match while the queue
+    /// is full are dropped.
+    ///
+    /// If unspecified, this defaults to `10000`.
+    pub queue_capacity: Option<usize>,
+}
+
+impl WebhookConfig {
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts.unwrap_or(5).max(1)
+    }
+
+    pub fn initial_backoff(&self) -> Duration {
+        Duration::from_millis(self.initial_backoff_ms.unwrap_or(1_000))
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        Duration::from_millis(self.max_backoff_ms.unwrap_or(60_000))
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms.unwrap_or(10_000))
+    }
+
+    pub fn queue_capacity(&self) -> usize {
+        self.queue_capacity.unwrap_or(10_000).max(1)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MutexTableConfig {
+    /// Number of shards in the table of per-transaction execution locks.
+    ///
+    /// If unspecified, this defaults to `1024`.
+    pub transaction_lock_shards: Option<usize>,
+
+    /// Number of shards in the table of per-object shared version assignment locks.
+    ///
+    /// If unspecified, this defaults to `1024`.
+    pub version_assignment_lock_shards: Option<usize>,
+
+    /// When set, a table whose shards were contended on in more than this fraction of lookups
+    /// during an epoch is doubled in size for the next epoch, up to `max-lock-shards`. Sizes are
+    /// reset to the configured ones when the node restarts.
+    pub resize_contention_ratio: Option<f64>,
+
+    /// If unspecified, this defaults to `16384`.
+    pub max_lock_shards: Option<usize>,
+}
+
+impl MutexTableConfig {
+    pub fn transaction_lock_shards(&self) -> usize {
+        self.transaction_lock_shards.unwrap_or(1024).max(1)
+    }
+
+    pub fn version_assignment_lock_shards(&self) -> usize {
+        self.version_assignment_lock_shards.unwrap_or(1024).max(1)
+    }
+
+    pub fn max_lock_shards(&self) -> usize {
+        self.max_lock_shards.unwrap_or(16_384).max(1)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PackageGasAccountingConfig {
+    /// Number of most recently executed checkpoints aggregated in the rolling window.
+    ///
+    /// If unspecified, this defaults to `10_000`.
+    pub window_checkpoints: Option<NonZeroUsize>,
+
+    /// Maximum number of packages returned by a single top-K report.
+    ///
+    /// If unspecified, this defaults to `1_000`.
+    pub max_report_size: Option<usize>,
+}
+
+impl PackageGasAccountingConfig {
+    pub fn window_checkpoints(&self) -> NonZeroUsize {
+        self.window_checkpoints.unwrap_or(nonzero!(10_000usize))
+    }
+
+    pub fn max_report_size(&self) -> usize {
+        self.max_report_size.unwrap_or(1_000)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SourceVerificationConfig {
+    /// Directory in which verified-source attestations are stored.
+    pub attestation_path: PathBuf,
+
+    /// Compiler versions that submitted packages may record in their `Move.lock`. Submissions
+    /// recording any other version, or none at all, are rejected. If empty, any version is
+    /// accepted.
+    ///
+    /// This is only an allowlist: packages are always rebuilt with this node's own compiler, so
+    /// it should only list versions whose output that compiler reproduces.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_compiler_versions: Vec<String>,
+
+    /// Callers allowed to submit package sources, by the name they are identified by in logs,
+    /// mapped to the token they authenticate with, as `Authorization: Bearer <token>`. If empty,
+    /// submissions are disabled, and the node only serves the attestations already stored under
+    /// `attestation-path`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub submitters: BTreeMap<String, String>,
+
+    /// Maximum number of submissions each caller can make in an hour, whether they verify or not.
+    ///
+    /// If unspecified, this will default to `10`.
+    pub max_submissions_per_hour: Option<NonZeroU32>,
+
+    /// Maximum total size of the source files in a single submission, in bytes.
+    ///
+    /// If unspecified, this will default to `10 MiB`.
+    pub max_source_bytes: Option<usize>,
+
+    /// Maximum number of submissions that are built concurrently. Submissions made while this
+    /// many are being built are rejected, rather than queued.
+    ///
+    /// If unspecified, this will default to `1`.
+    pub max_concurrent_builds: Option<NonZeroUsize>,
+}
+
+impl SourceVerificationConfig {
+    pub fn max_submissions_per_hour(&self) -> NonZeroU32 {
+        self.max_submissions_per_hour.unwrap_or(nonzero!(10u32))
+    }
+
+    pub fn max_source_bytes(&self) -> usize {
+        self.max_source_bytes.unwrap_or(10 * 1024 * 1024)
+    }
+
+    pub fn max_concurrent_builds(&self) -> NonZeroUsize {
+        self.max_concurrent_builds.unwrap_or(nonzero!(1usize))
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct ExecutionTimeObserverConfig {
@@ -540,6 +900,20 @@ pub struct ExecutionTimeObserverConfig {
     /// If unspecified, this will default to `20`.
     pub weighted_moving_average_window_size: Option<usize>,
 
+    /// Signed execution time observation files exported by other validators (see the
+    /// `export-execution-time-observations` admin command). At the start of each epoch their
+    /// observations are merged and used to pre-populate local observations, so that a new
+    /// validator does not start from default estimates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imported_observations: Vec<ImportedExecutionTimeObservations>,
+
+    /// Number of samples each imported observation counts for when pre-populating local
+    /// observations. Local measurements replace imported ones as the moving average window
+    /// fills up, so a higher value makes imported estimates persist for longer.
+    ///
+    /// If unspecified, this will default to `5`.
+    pub imported_observation_sample_count: Option<NonZeroUsize>,
+
     /// Whether to inject synthetic execution time for testing in simtest.
     /// When enabled, synthetic timings will be generated for execution time observations
     /// to enable deterministic testing of congestion control features.
@@ -606,12 +980,35 @@ impl ExecutionTimeObserverConfig {
         self.weighted_moving_average_window_size.unwrap_or(20)
     }
 
+    pub fn imported_observation_sample_count(&self) -> NonZeroUsize {
+        self.imported_observation_sample_count
+            .unwrap_or(nonzero!(5usize))
+    }
+
     #[cfg(msim)]
     pub fn inject_synthetic_execution_time(&self) -> bool {
         self.inject_synthetic_execution_time.unwrap_or(false)
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportedExecutionTimeObservations {
+    /// Path to a signed execution time observation file.
+    pub path: PathBuf,
+
+    /// Relative weight of this file when merging observations from several files.
+    ///
+    /// If unspecified, this will default to `1.0`.
+    pub weight: Option<f64>,
+}
+
+impl ImportedExecutionTimeObservations {
+    pub fn weight(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -824,6 +1221,116 @@ pub enum ServerType {
     Both,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcAddressConfig {
+    /// Only accept addresses and object IDs as the endpoints themselves parse them, without
+    /// padding short literals, lowercasing, or resolving aliases first.
+    #[serde(default)]
+    pub strict: bool,
+
+    /// Names that clients may pass as `@name` wherever an address or object ID is expected.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, SuiAddress>,
+}
+
+/// Settings for returning large JSON-RPC response fields (object BCS, transaction BCS, package
+/// modules and their disassembly) as references resolvable through `GET /blobs/{digest}`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcBlobConfig {
+    /// Fields smaller than this many bytes are always returned inline.
+    #[serde(default = "default_blob_min_size")]
+    pub min_size: usize,
+
+    /// Total size in bytes of the blobs kept resolvable. The oldest blobs are evicted first.
+    #[serde(default = "default_blob_cache_size")]
+    pub cache_size: usize,
+}
+
+impl Default for JsonRpcBlobConfig {
+    fn default() -> Self {
+        Self {
+            min_size: default_blob_min_size(),
+            cache_size: default_blob_cache_size(),
+        }
+    }
+}
+
+fn default_blob_min_size() -> usize {
+    1024
+}
+
+fn default_blob_cache_size() -> usize {
+    256 * 1024 * 1024
+}
+
+/// Token-bucket rate limits on JSON-RPC requests. Requests carrying a configured API key are
+/// limited per key, other requests are limited per client IP address.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcRateLimitConfig {
+    /// Request header carrying the client's API key.
+    #[serde(default = "default_api_key_header")]
+    pub api_key_header: String,
+
+    /// Limits on methods, by method name (e.g. `sui_dryRunTransactionBlock`). Methods without a
+    /// limit are not rate limited.
+    #[serde(default)]
+    pub methods: BTreeMap<String, RateLimit>,
+
+    /// API keys, by the name their holder is identified by in metrics.
+    #[serde(default)]
+    pub api_keys: BTreeMap<String, ApiKeyQuota>,
+}
+
+impl Default for JsonRpcRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            api_key_header: default_api_key_header(),
+            methods: BTreeMap::new(),
+            api_keys: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_api_key_header() -> String {
+    "x-api-key".to_string()
+}
+
+/// A token bucket, refilled at `requests-per-second` and holding at most `burst` tokens. Each
+/// request takes a token.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimit {
+    pub requests_per_second: NonZeroU32,
+
+    /// Defaults to one second's worth of requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<NonZeroU32>,
+}
+
+impl RateLimit {
+    pub fn burst(&self) -> NonZeroU32 {
+        self.burst.unwrap_or(self.requests_per_second)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApiKeyQuota {
+    /// The key, as sent in the API key header.
+    pub key: String,
+
+    /// Limit on all requests made with this key. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<RateLimit>,
+
+    /// Limits on methods for this key, replacing those that apply to other clients.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub methods: BTreeMap<String, RateLimit>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TransactionKeyValueStoreReadConfig {
@@ -1144,6 +1651,12 @@ pub struct ConsensusConfig {
     /// When neither this nor `listen_address` is set, peers use the on-chain committee address.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_address: Option<Multiaddr>,
+
+    /// Artificial latency added to every consensus commit before it is handled, so that a local
+    /// network finalizes transactions as slowly as a real one. For testing only: it is ignored,
+    /// with an error logged, on mainnet and testnet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_latency: Option<CommitLatencyConfig>,
 }
 
 impl ConsensusConfig {
@@ -1167,6 +1680,35 @@ impl ConsensusConfig {
     }
 }
 
+/// Latency injected between consensus committing a sub-dag and the validator handling it.
+///
+/// Each commit is handled `latency-ms` plus up to `jitter-ms` after it is output by consensus.
+/// The jitter of a commit only depends on `seed` and the commit index, so every validator, and
+/// every run, delays the same commit by the same amount. Commits are still handled in order and
+/// without waiting on each other, so the delay does not reduce throughput.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitLatencyConfig {
+    pub latency_ms: u64,
+    #[serde(default)]
+    pub jitter_ms: u64,
+    #[serde(default)]
+    pub seed: u64,
+}
+
+impl CommitLatencyConfig {
+    /// The delay to add to the commit at `commit_index`.
+    pub fn delay(&self, commit_index: u32) -> Duration {
+        let jitter = if self.jitter_ms == 0 {
+            0
+        } else {
+            let mut rng = StdRng::seed_from_u64(self.seed ^ u64::from(commit_index));
+            rng.gen_range(0..=self.jitter_ms)
+        };
+        Duration::from_millis(self.latency_ms + jitter)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct CheckpointExecutorConfig {
@@ -1341,6 +1883,58 @@ pub struct AuthorityStorePruningConfig {
     pub smooth: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_epochs_to_retain_for_indexes: Option<u64>,
+    /// per-component retention, overriding the epoch counts above for individual tables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_retention: Option<ComponentRetentionConfig>,
+}
+
+/// Number of epochs to retain each prunable component for. Unset components follow
+/// `num-epochs-to-retain-for-checkpoints` (transactions, effects, events, checkpoint contents) or
+/// `num-epochs-to-retain` (object history).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ComponentRetentionConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effects: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub objects_history: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_contents: Option<u64>,
+}
+
+/// A component of the perpetual store that is pruned by age.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrunedComponent {
+    Transactions,
+    Effects,
+    Events,
+    ObjectsHistory,
+    CheckpointContents,
+}
+
+impl PrunedComponent {
+    pub const ALL: [PrunedComponent; 5] = [
+        PrunedComponent::Transactions,
+        PrunedComponent::Effects,
+        PrunedComponent::Events,
+        PrunedComponent::ObjectsHistory,
+        PrunedComponent::CheckpointContents,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrunedComponent::Transactions => "transactions",
+            PrunedComponent::Effects => "effects",
+            PrunedComponent::Events => "events",
+            PrunedComponent::ObjectsHistory => "objects_history",
+            PrunedComponent::CheckpointContents => "checkpoint_contents",
+        }
+    }
 }
 
 fn default_num_latest_epoch_dbs_to_retain() -> usize {
@@ -1381,6 +1975,7 @@ impl Default for AuthorityStorePruningConfig {
             killswitch_tombstone_pruning: false,
             smooth: true,
             num_epochs_to_retain_for_indexes: None,
+            component_retention: None,
         }
     }
 }
@@ -1395,7 +1990,10 @@ impl AuthorityStorePruningConfig {
     }
 
     pub fn num_epochs_to_retain_for_checkpoints(&self) -> Option<u64> {
-        self.num_epochs_to_retain_for_checkpoints
+        self.component_retention
+            .as_ref()
+            .and_then(|retention| retention.checkpoint_contents)
+            .or(self.num_epochs_to_retain_for_checkpoints)
             // if n less than 2, coerce to 2 and log
             .map(|n| {
                 if n < 2 {
@@ -1410,6 +2008,97 @@ impl AuthorityStorePruningConfig {
     pub fn set_killswitch_tombstone_pruning(&mut self, killswitch_tombstone_pruning: bool) {
         self.killswitch_tombstone_pruning = killswitch_tombstone_pruning;
     }
+
+    pub fn num_epochs_to_retain_for_objects(&self) -> u64 {
+        self.component_retention
+            .as_ref()
+            .and_then(|retention| retention.objects_history)
+            .unwrap_or(self.num_epochs_to_retain)
+    }
+
+    /// Number of epochs `component` is retained for, or `None` if it is never pruned.
+    pub fn retention(&self, component: PrunedComponent) -> Option<u64> {
+        let retention = self.component_retention.clone().unwrap_or_default();
+        let epochs = match component {
+            PrunedComponent::ObjectsHistory => Some(self.num_epochs_to_retain_for_objects()),
+            PrunedComponent::CheckpointContents => self.num_epochs_to_retain_for_checkpoints(),
+            PrunedComponent::Transactions => retention
+                .transactions
+                .or(self.num_epochs_to_retain_for_checkpoints()),
+            PrunedComponent::Effects => retention
+                .effects
+                .or(self.num_epochs_to_retain_for_checkpoints()),
+            PrunedComponent::Events => retention
+                .events
+                .or(self.num_epochs_to_retain_for_checkpoints()),
+        };
+        epochs.filter(|epochs| *epochs != u64::MAX)
+    }
+
+    /// Fold `component-retention` overrides for object history and checkpoint contents into
+    /// `num-epochs-to-retain` and `num-epochs-to-retain-for-checkpoints`.
+    pub fn resolve_component_retention(&mut self) {
+        self.num_epochs_to_retain = self.num_epochs_to_retain_for_objects();
+        self.num_epochs_to_retain_for_checkpoints = self.num_epochs_to_retain_for_checkpoints();
+    }
+
+    /// Rejects retention settings that would leave the node unable to serve RPC reads for data
+    /// it still claims to hold, or that the pruner cannot honour.
+    ///
+    /// Retention must be ordered `objects-history <= effects == transactions <= events <=
+    /// checkpoint-contents`: object pruning reads effects, transactions are served together with
+    /// their effects, transaction responses include events, and per-component pruning finds the
+    /// keys to delete through checkpoint contents.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let Some(retention) = &self.component_retention else {
+            return Ok(());
+        };
+        for (name, epochs) in [
+            ("transactions", retention.transactions),
+            ("effects", retention.effects),
+            ("events", retention.events),
+            ("checkpoint-contents", retention.checkpoint_contents),
+        ] {
+            if let Some(epochs) = epochs {
+                anyhow::ensure!(
+                    epochs >= 2,
+                    "component-retention.{name} must be at least 2 epochs, got {epochs}"
+                );
+            }
+        }
+
+        // `None` means the component is never pruned, which orders after any epoch count.
+        let order = |component| self.retention(component).unwrap_or(u64::MAX);
+        let objects = order(PrunedComponent::ObjectsHistory);
+        let transactions = order(PrunedComponent::Transactions);
+        let effects = order(PrunedComponent::Effects);
+        let events = order(PrunedComponent::Events);
+        let contents = order(PrunedComponent::CheckpointContents);
+
+        if self.num_epochs_to_retain_for_objects() != u64::MAX {
+            anyhow::ensure!(
+                objects <= effects,
+                "effects must be retained at least as long as object history, since object \
+                 pruning reads effects"
+            );
+        }
+        anyhow::ensure!(
+            transactions == effects,
+            "transactions and effects must be retained for the same number of epochs, since \
+             they are served together"
+        );
+        anyhow::ensure!(
+            effects <= events,
+            "events must be retained at least as long as effects, since transaction responses \
+             include their events"
+        );
+        anyhow::ensure!(
+            events <= contents,
+            "checkpoint contents must be retained at least as long as transactions, effects and \
+             events, since the pruner locates them through checkpoint contents"
+        );
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1559,6 +2248,37 @@ pub struct AuthorityOverloadConfig {
     // reject behavior as when the queue is disabled) until progress resumes.
     #[serde(default = "default_admission_queue_failover_timeout")]
     pub admission_queue_failover_timeout: Duration,
+
+    // Caps how many transactions from the same sender (or gas owner) the execution driver runs
+    // at once, so a single busy account cannot take every execution slot. Unset by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender_concurrency_limit: Option<SenderConcurrencyLimitConfig>,
+}
+
+/// Limit on the number of concurrently executing transactions that share an account. Ready
+/// transactions over the limit wait in a per-account FIFO queue, and each one that finishes
+/// releases the next waiting transaction of the same account ahead of newly ready transactions,
+/// so throttled accounts still make steady progress. System transactions and transactions ordered
+/// by consensus are never held back.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SenderConcurrencyLimitConfig {
+    /// Maximum number of transactions per account that may execute concurrently.
+    pub max_executing_transactions: NonZeroUsize,
+
+    /// Which account a transaction is counted against.
+    #[serde(default)]
+    pub key: SenderConcurrencyKey,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SenderConcurrencyKey {
+    /// The transaction's sender.
+    #[default]
+    Sender,
+    /// The account paying for gas, which differs from the sender for sponsored transactions.
+    GasOwner,
 }
 
 fn default_max_txn_age_in_queue() -> Duration {
@@ -1631,6 +2351,7 @@ impl Default for AuthorityOverloadConfig {
             admission_queue_capacity_fraction: default_admission_queue_capacity_fraction(),
             admission_queue_enabled: default_admission_queue_enabled(),
             admission_queue_failover_timeout: default_admission_queue_failover_timeout(),
+            sender_concurrency_limit: None,
         }
     }
 }
@@ -1892,7 +2613,7 @@ mod tests {
     use sui_keys::keypair_file::{write_authority_keypair_to_file, write_keypair_to_file};
     use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair, SuiKeyPair, get_key_pair_from_rng};
 
-    use super::{Genesis, StateArchiveConfig};
+    use super::{Genesis, SafeModeDiagnosticsConfig, StateArchiveConfig};
     use crate::NodeConfig;
 
     #[test]
@@ -1912,6 +2633,17 @@ mod tests {
         let _template: NodeConfig = serde_yaml::from_str(TEMPLATE).unwrap();
     }
 
+    #[test]
+    fn safe_mode_diagnostics_config() {
+        let config: SafeModeDiagnosticsConfig =
+            serde_yaml::from_str("output-path: /tmp/diagnostics\n").unwrap();
+        assert_eq!(config.output_path, PathBuf::from("/tmp/diagnostics"));
+        assert_eq!(
+            config.num_commits,
+            super::default_safe_mode_diagnostics_num_commits()
+        );
+    }
+
     /// Tests that a legacy validator config (captured on 12/06/2024) can be parsed.
     #[test]
     fn legacy_validator_config() {
@@ -2111,6 +2843,7 @@ remote-store-options:
                 parameters: Default::default(),
                 listen_address: None,
                 external_address: None,
+                commit_latency: None,
             }
         }
 
@@ -2194,6 +2927,71 @@ remote-store-options:
             config.intended_node_role();
         }
     }
+
+    #[test]
+    fn component_retention_validation() {
+        use super::{AuthorityStorePruningConfig, ComponentRetentionConfig, PrunedComponent};
+
+        let with_retention = |component_retention| AuthorityStorePruningConfig {
+            num_epochs_to_retain: 0,
+            num_epochs_to_retain_for_checkpoints: Some(10),
+            component_retention: Some(component_retention),
+            ..Default::default()
+        };
+
+        let config = with_retention(ComponentRetentionConfig {
+            transactions: Some(5),
+            effects: Some(5),
+            events: Some(8),
+            ..Default::default()
+        });
+        config.validate().unwrap();
+        assert_eq!(config.retention(PrunedComponent::Transactions), Some(5));
+        assert_eq!(
+            config.retention(PrunedComponent::CheckpointContents),
+            Some(10)
+        );
+        assert_eq!(config.retention(PrunedComponent::ObjectsHistory), Some(0));
+
+        // transactions without effects cannot be served
+        with_retention(ComponentRetentionConfig {
+            transactions: Some(5),
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+        // events are read for every transaction response
+        with_retention(ComponentRetentionConfig {
+            transactions: Some(5),
+            effects: Some(5),
+            events: Some(3),
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+        // pruning effects before the objects they modified would stall the objects pruner
+        with_retention(ComponentRetentionConfig {
+            transactions: Some(5),
+            effects: Some(5),
+            objects_history: Some(7),
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+        // checkpoint contents are needed to find the keys of the other components
+        with_retention(ComponentRetentionConfig {
+            events: Some(20),
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+        with_retention(ComponentRetentionConfig {
+            checkpoint_contents: Some(1),
+            ..Default::default()
+        })
+        .validate()
+        .unwrap_err();
+    }
 }
 
 // RunWithRange is used to specify the ending epoch/checkpoint to process.
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::BTreeSet;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{AuthorityName, ObjectID, SuiAddress};
@@ -10,6 +11,7 @@ pub use sui_types::transaction_deny_rules::{DenyElementKind, TransactionDenyRule
 use crate::dynamic_transaction_signing_checks::{
     DynamicCheckRunnerContext, DynamicCheckRunnerError,
 };
+use crate::transaction_policy_config::{TransactionPolicy, TransactionPolicyConfig};
 
 /// Configuration for activating recommended `TransactionDenyConfig` rules shared by
 /// peers via consensus. The operator pre-defines named rulesets, each gated on a
@@ -180,6 +182,17 @@ pub struct TransactionDenyConfig {
         deserialize_with = "crate::dynamic_transaction_signing_checks::deserialize_dynamic_transaction_checks"
     )]
     dynamic_transaction_checks: Option<DynamicCheckRunnerContext>,
+
+    /// Allow/deny policy rules checked after the deny lists above. A policy read from a file can
+    /// be reloaded at runtime.
+    /// Local-only: never propagated through the consensus-shared recommendation flow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transaction_policy: Option<TransactionPolicyConfig>,
+
+    /// The policy checked at signing time: the loaded `transaction_policy`, preceded by any
+    /// rules synced from a denylist feed. Maintained by the deny config manager.
+    #[serde(skip)]
+    active_transaction_policy: Arc<TransactionPolicy>,
 }
 
 impl TransactionDenyConfig {
@@ -239,12 +252,31 @@ impl TransactionDenyConfig {
         self.dynamic_transaction_checks.is_some()
     }
 
+    pub fn transaction_policy_config(&self) -> Option<&TransactionPolicyConfig> {
+        self.transaction_policy.as_ref()
+    }
+
+    pub fn transaction_policy(&self) -> &Arc<TransactionPolicy> {
+        &self.active_transaction_policy
+    }
+
     /// Return a copy of this config with `rules` replaced, carrying
-    /// `dynamic_transaction_checks` over verbatim (it is local-only and never shared).
+    /// `dynamic_transaction_checks` and the transaction policy over verbatim (they are
+    /// local-only and never shared).
     pub fn with_rules(&self, rules: TransactionDenyRules) -> Self {
         Self {
             rules,
             dynamic_transaction_checks: self.dynamic_transaction_checks.clone(),
+            transaction_policy: self.transaction_policy.clone(),
+            active_transaction_policy: self.active_transaction_policy.clone(),
+        }
+    }
+
+    /// Return a copy of this config with the policy checked at signing time replaced.
+    pub fn with_transaction_policy(&self, policy: TransactionPolicy) -> Self {
+        Self {
+            active_transaction_policy: Arc::new(policy),
+            ..self.clone()
         }
     }
 }
@@ -328,15 +360,21 @@ impl TransactionDenyConfigBuilder {
         self.config.dynamic_transaction_checks = Some(DynamicCheckRunnerContext::new(checks)?);
         Ok(self)
     }
+
+    pub fn set_transaction_policy(mut self, config: TransactionPolicyConfig) -> Self {
+        self.config.transaction_policy = Some(config);
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transaction_policy_config::PolicyAction;
     use sui_types::base_types::dbg_addr;
 
     #[test]
-    fn with_rules_replaces_rules_and_keeps_dynamic_checks() {
+    fn with_rules_replaces_rules_and_keeps_local_settings() {
         let starlark =
             "def predicate(tx_data, tx_signatures, input_objects, receiving_objects):\n    pass\n"
                 .to_string();
@@ -344,7 +382,12 @@ mod tests {
             .add_denied_object(ObjectID::from_single_byte(1))
             .add_dynamic_transaction_checks(starlark)
             .expect("starlark should parse")
-            .build();
+            .set_transaction_policy(TransactionPolicyConfig::default())
+            .build()
+            .with_transaction_policy(TransactionPolicy {
+                default_action: PolicyAction::Deny,
+                rules: vec![],
+            });
 
         let mut new_rules = TransactionDenyRules::default();
         new_rules
@@ -365,8 +408,13 @@ mod tests {
                 .contains(&ObjectID::from_single_byte(2))
         );
         assert!(updated.user_transaction_disabled());
-        // dynamic_transaction_checks is local-only and carried over verbatim.
+        // dynamic_transaction_checks and the policy are local-only and carried over verbatim.
         assert!(updated.has_dynamic_transaction_checks());
+        assert!(updated.transaction_policy_config().is_some());
+        assert_eq!(
+            updated.transaction_policy().default_action,
+            PolicyAction::Deny
+        );
     }
 
     #[test]
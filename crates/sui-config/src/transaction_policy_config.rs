@@ -0,0 +1,312 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operator-defined allow/deny policy applied to transactions at signing time.
+//!
+//! A policy is an ordered list of rules. Each rule matches on any combination of sender,
+//! called package, called Move function and accessed object; the first matching rule decides
+//! whether the transaction is allowed or denied. Transactions matching no rule get the
+//! policy's `default-action`.
+//!
+//! The policy is part of the node's `transaction-deny-config`, and is checked after its deny
+//! lists. It can be given inline, or in a separate YAML file that is re-read when the node
+//! receives SIGHUP or through the admin interface. Deny rules can also be
+//! synced from a signed denylist feed (see `DenylistFeedConfig`), and are evaluated before the
+//! operator's rules.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransactionPolicyConfig {
+    /// YAML file containing a `TransactionPolicy`. Takes precedence over `policy`, and is
+    /// re-read on reload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+
+    /// Inline policy, used when `path` is unset. Cannot be reloaded at runtime.
+    #[serde(default)]
+    pub policy: TransactionPolicy,
+}
+
+impl TransactionPolicyConfig {
+    /// Load the policy this config refers to, reading `path` if set.
+    pub fn load(&self) -> Result<TransactionPolicy, String> {
+        match &self.path {
+            Some(path) => TransactionPolicy::load(path),
+            None => {
+                self.policy.validate()?;
+                Ok(self.policy.clone())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct DenylistFeedConfig {
+    /// URL the signed feed is fetched from.
+    pub url: String,
+
+    /// Base64-encoded Ed25519 public key the feed must be signed with.
+    pub public_key: String,
+
+    /// If unspecified, this defaults to `60000`.
+    pub poll_interval_ms: Option<u64>,
+
+    /// If unspecified, this defaults to `10000`.
+    pub request_timeout_ms: Option<u64>,
+}
+
+impl DenylistFeedConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms.unwrap_or(60_000))
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms.unwrap_or(10_000))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyAction {
+    #[default]
+    Allow,
+    Deny,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransactionPolicy {
+    /// Action applied to transactions that match no rule.
+    #[serde(default)]
+    pub default_action: PolicyAction,
+
+    /// Rules evaluated in order; the first match wins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<TransactionPolicyRule>,
+}
+
+/// A single policy rule. Within a criterion any listed value matches; across criteria all
+/// non-empty criteria must match. A rule must set at least one criterion.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TransactionPolicyRule {
+    /// Operator-chosen identifier, reported in rejection errors and metrics.
+    pub name: String,
+
+    pub action: PolicyAction,
+
+    /// Matches the transaction sender.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub senders: BTreeSet<SuiAddress>,
+
+    /// Matches if any `MoveCall` command targets one of these packages.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub packages: BTreeSet<ObjectID>,
+
+    /// Matches if any `MoveCall` command targets one of these functions.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub functions: BTreeSet<MoveFunctionPattern>,
+
+    /// Matches if any input or receiving object has one of these IDs.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub objects: BTreeSet<ObjectID>,
+}
+
+impl TransactionPolicyRule {
+    fn has_criteria(&self) -> bool {
+        !self.senders.is_empty()
+            || !self.packages.is_empty()
+            || !self.functions.is_empty()
+            || !self.objects.is_empty()
+    }
+}
+
+impl TransactionPolicy {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read transaction policy {}: {e}", path.display()))?;
+        let policy: Self = serde_yaml::from_str(&contents)
+            .map_err(|e| format!("failed to parse transaction policy {}: {e}", path.display()))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.default_action == PolicyAction::Allow
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = BTreeSet::new();
+        for rule in &self.rules {
+            if rule.name.is_empty() {
+                return Err("transaction policy rule has an empty name".to_string());
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(format!(
+                    "duplicate transaction policy rule name: {}",
+                    rule.name
+                ));
+            }
+            if !rule.has_criteria() {
+                return Err(format!(
+                    "transaction policy rule {} does not set any criteria",
+                    rule.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A Move function written as `package::module::function`. `module` and `function` may be `*`
+/// to match any module or function in the package.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MoveFunctionPattern {
+    pub package: ObjectID,
+    pub module: Option<String>,
+    pub function: Option<String>,
+}
+
+impl MoveFunctionPattern {
+    pub fn matches(&self, package: &ObjectID, module: &str, function: &str) -> bool {
+        &self.package == package
+            && self.module.as_deref().is_none_or(|m| m == module)
+            && self.function.as_deref().is_none_or(|f| f == function)
+    }
+}
+
+impl FromStr for MoveFunctionPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split("::").collect();
+        let [package, module, function] = parts[..] else {
+            return Err(format!(
+                "invalid function pattern '{s}', expected package::module::function"
+            ));
+        };
+        let package = ObjectID::from_str(package)
+            .map_err(|e| format!("invalid package in function pattern '{s}': {e}"))?;
+        let wildcard = |part: &str| (part != "*").then(|| part.to_string());
+        let module = wildcard(module);
+        let function = wildcard(function);
+        if module.is_none() && function.is_some() {
+            return Err(format!(
+                "invalid function pattern '{s}', a function requires a module"
+            ));
+        }
+        Ok(Self {
+            package,
+            module,
+            function,
+        })
+    }
+}
+
+impl TryFrom<String> for MoveFunctionPattern {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<MoveFunctionPattern> for String {
+    fn from(pattern: MoveFunctionPattern) -> Self {
+        pattern.to_string()
+    }
+}
+
+impl fmt::Display for MoveFunctionPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{}::{}",
+            self.package,
+            self.module.as_deref().unwrap_or("*"),
+            self.function.as_deref().unwrap_or("*"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_pattern_round_trip() {
+        let pattern: MoveFunctionPattern = "0x2::coin::split".parse().unwrap();
+        assert!(pattern.matches(&ObjectID::from_single_byte(2), "coin", "split"));
+        assert!(!pattern.matches(&ObjectID::from_single_byte(2), "coin", "join"));
+        assert_eq!(pattern, pattern.to_string().parse().unwrap());
+
+        let wildcard: MoveFunctionPattern = "0x2::coin::*".parse().unwrap();
+        assert!(wildcard.matches(&ObjectID::from_single_byte(2), "coin", "join"));
+        assert!(!wildcard.matches(&ObjectID::from_single_byte(2), "balance", "join"));
+
+        assert!("0x2::coin".parse::<MoveFunctionPattern>().is_err());
+        assert!("0x2::*::split".parse::<MoveFunctionPattern>().is_err());
+    }
+
+    #[test]
+    fn transaction_policy_yaml_round_trip() {
+        let yaml = r#"
+default-action: allow
+rules:
+  - name: block-exploit
+    action: deny
+    packages:
+      - "0x0000000000000000000000000000000000000000000000000000000000000abc"
+    functions:
+      - "0x2::coin::*"
+  - name: allow-ops
+    action: allow
+    senders:
+      - "0x0000000000000000000000000000000000000000000000000000000000000001"
+"#;
+        let policy: TransactionPolicy = serde_yaml::from_str(yaml).unwrap();
+        policy.validate().unwrap();
+        assert_eq!(policy.rules.len(), 2);
+        assert_eq!(policy.rules[0].action, PolicyAction::Deny);
+
+        let serialized = serde_yaml::to_string(&policy).unwrap();
+        let parsed: TransactionPolicy = serde_yaml::from_str(&serialized).unwrap();
+        assert_eq!(policy, parsed);
+    }
+
+    #[test]
+    fn validate_rejects_malformed_rules() {
+        let rule = TransactionPolicyRule {
+            name: "r".to_string(),
+            action: PolicyAction::Deny,
+            objects: BTreeSet::from([ObjectID::ZERO]),
+            ..Default::default()
+        };
+
+        let duplicate = TransactionPolicy {
+            default_action: PolicyAction::Allow,
+            rules: vec![rule.clone(), rule.clone()],
+        };
+        assert!(duplicate.validate().is_err());
+
+        let no_criteria = TransactionPolicy {
+            default_action: PolicyAction::Allow,
+            rules: vec![TransactionPolicyRule {
+                objects: BTreeSet::new(),
+                ..rule
+            }],
+        };
+        assert!(no_criteria.validate().is_err());
+    }
+}
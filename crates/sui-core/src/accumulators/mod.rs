@@ -38,6 +38,7 @@ pub mod funds_read;
 pub mod balances;
 pub mod coin_reservations;
 pub mod object_funds_checker;
+pub mod settlement_tracker;
 pub(crate) mod transaction_rewriting;
 
 /// Merged value is the value stored inside accumulator objects.
@@ -0,0 +1,137 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use sui_types::transaction::TransactionKey;
+
+/// Tracks the accumulator settlements scheduled in the current epoch that have not executed yet.
+///
+/// A settlement is registered when the consensus handler schedules the transactions of its batch,
+/// and resolved once its settlement and barrier transactions have executed. Settlements resolve in
+/// order, so a growing number of pending settlements, or a growing gap between the checkpoint of
+/// the oldest one and the latest scheduled checkpoint, means settlement is falling behind
+/// consensus commits.
+#[derive(Default)]
+pub struct SettlementTracker {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    pending: BTreeMap<TransactionKey, Registration>,
+    latest_checkpoint_seq: u64,
+}
+
+struct Registration {
+    checkpoint_seq: u64,
+    checkpoint_height: u64,
+    num_transactions: usize,
+    registered_at: Instant,
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingSettlement {
+    pub settlement_key: TransactionKey,
+    pub checkpoint_seq: u64,
+    pub checkpoint_height: u64,
+    /// Number of transactions in the settled batch.
+    pub num_transactions: usize,
+    /// Time since the settlement was scheduled.
+    pub age: Duration,
+}
+
+impl SettlementTracker {
+    pub fn register(
+        &self,
+        settlement_key: TransactionKey,
+        checkpoint_seq: u64,
+        checkpoint_height: u64,
+        num_transactions: usize,
+    ) {
+        let mut inner = self.inner.lock();
+        inner.latest_checkpoint_seq = inner.latest_checkpoint_seq.max(checkpoint_seq);
+        inner.pending.insert(
+            settlement_key,
+            Registration {
+                checkpoint_seq,
+                checkpoint_height,
+                num_transactions,
+                registered_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Stop tracking a settlement, returning the time since it was registered.
+    pub fn resolve(&self, settlement_key: &TransactionKey) -> Option<Duration> {
+        self.inner
+            .lock()
+            .pending
+            .remove(settlement_key)
+            .map(|registration| registration.registered_at.elapsed())
+    }
+
+    /// The pending settlements, oldest checkpoint first.
+    pub fn pending(&self) -> Vec<PendingSettlement> {
+        let inner = self.inner.lock();
+        let mut pending: Vec<_> = inner
+            .pending
+            .iter()
+            .map(|(key, registration)| PendingSettlement {
+                settlement_key: *key,
+                checkpoint_seq: registration.checkpoint_seq,
+                checkpoint_height: registration.checkpoint_height,
+                num_transactions: registration.num_transactions,
+                age: registration.registered_at.elapsed(),
+            })
+            .collect();
+        pending.sort_by_key(|p| (p.checkpoint_seq, p.checkpoint_height));
+        pending
+    }
+
+    pub fn num_pending(&self) -> usize {
+        self.inner.lock().pending.len()
+    }
+
+    /// Number of checkpoints between the oldest pending settlement and the latest registered one,
+    /// or 0 if no settlement is pending.
+    pub fn lag_checkpoints(&self) -> u64 {
+        let inner = self.inner.lock();
+        inner
+            .pending
+            .values()
+            .map(|registration| registration.checkpoint_seq)
+            .min()
+            .map_or(0, |oldest| inner.latest_checkpoint_seq - oldest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::digests::TransactionDigest;
+
+    #[test]
+    fn test_pending_settlements() {
+        let tracker = SettlementTracker::default();
+        let keys: Vec<_> = (0..3)
+            .map(|_| TransactionKey::Digest(TransactionDigest::random()))
+            .collect();
+        assert_eq!(tracker.lag_checkpoints(), 0);
+
+        tracker.register(keys[0], 10, 100, 5);
+        tracker.register(keys[1], 11, 101, 2);
+        tracker.register(keys[2], 11, 102, 1);
+        assert_eq!(tracker.num_pending(), 3);
+        assert_eq!(tracker.lag_checkpoints(), 1);
+        let pending = tracker.pending();
+        assert_eq!(pending[0].settlement_key, keys[0]);
+        assert_eq!(pending[0].num_transactions, 5);
+
+        assert!(tracker.resolve(&keys[0]).is_some());
+        assert!(tracker.resolve(&keys[0]).is_none());
+        assert_eq!(tracker.lag_checkpoints(), 0);
+        assert_eq!(tracker.pending()[0].checkpoint_height, 101);
+    }
+}
@@ -10,14 +10,18 @@ use crate::accumulators::transaction_rewriting::rewrite_transaction_for_coin_res
 use crate::accumulators::{self, AccumulatorSettlementTxBuilder};
 use crate::checkpoints::CheckpointBuilderError;
 use crate::checkpoints::CheckpointBuilderResult;
+use crate::compliance_report::ComplianceReporter;
 use crate::congestion_tracker::CongestionTracker;
+use crate::consensus_rejection_policy::ConsensusRejectionPolicies;
 use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::execution_cache::TransactionCacheRead;
 use crate::execution_cache::writeback_cache::WritebackCache;
 use crate::execution_scheduler::ExecutionScheduler;
 use crate::execution_scheduler::funds_withdraw_scheduler::FundsSettlement;
+use crate::execution_scheduler::sender_concurrency_limiter::SenderConcurrencyLimiter;
 use crate::gasless_rate_limiter::ConsensusGaslessCounter;
 use crate::jsonrpc_index::CoinIndexKey2;
+use crate::package_gas_tracker::PackageGasTracker;
 use crate::traffic_controller::TrafficController;
 use crate::traffic_controller::metrics::TrafficControllerMetrics;
 use crate::transaction_deny_config_manager::TransactionDenyConfigManager;
@@ -131,10 +135,10 @@ use sui_types::accumulator_root::AccumulatorValue;
 use sui_types::authenticator_state::get_authenticator_state;
 use sui_types::balance::Balance;
 use sui_types::coin_reservation;
-use sui_types::committee::{EpochId, ProtocolVersion};
+use sui_types::committee::{EpochId, ProtocolVersion, StakeUnit};
 use sui_types::crypto::{AuthoritySignInfo, Signer};
 use sui_types::deny_list_v1::check_coin_deny_list_v1;
-use sui_types::digests::ChainIdentifier;
+use sui_types::digests::{ChainIdentifier, Digest};
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
 use sui_types::effects::{
     InputConsensusObject, SignedTransactionEffects, TransactionEffects, TransactionEffectsAPI,
@@ -166,7 +170,9 @@ use sui_types::storage::{
 use sui_types::sui_system_state::SuiSystemStateTrait;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::{SuiSystemState, get_sui_system_state};
-use sui_types::supported_protocol_versions::{ProtocolConfig, SupportedProtocolVersions};
+use sui_types::supported_protocol_versions::{
+    ProtocolConfig, SupportedProtocolVersions, SupportedProtocolVersionsWithHashes,
+};
 use sui_types::{
     SUI_SYSTEM_ADDRESS,
     base_types::*,
@@ -184,10 +190,11 @@ use crate::authority::authority_per_epoch_store::{AuthorityPerEpochStore, CertTx
 use crate::authority::authority_per_epoch_store_pruner::AuthorityPerEpochStorePruner;
 use crate::authority::authority_store::ExecutionLockReadGuard;
 use crate::authority::authority_store_pruner::{
-    AuthorityStorePruner, EPOCH_DURATION_MS_FOR_TESTING,
+    AuthorityStorePruner, EPOCH_DURATION_MS_FOR_TESTING, ReclaimEstimate,
 };
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
+use crate::authority::previous_epoch_tables::PreviousEpochTables;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::{
@@ -195,11 +202,14 @@ use crate::execution_cache::{
     ObjectCacheRead, StateSyncAPI,
 };
 use crate::execution_driver::execution_process;
-use crate::global_state_hasher::{GlobalStateHashStore, GlobalStateHasher, WrappedObject};
+use crate::global_state_hasher::{
+    GlobalStateHashStore, GlobalStateHasher, RunningRootAudit, WrappedObject,
+};
 use crate::metrics::LatencyObserver;
 use crate::metrics::RateTracker;
 use crate::module_cache_metrics::ResolverMetrics;
 use crate::overload_monitor::{AuthorityOverloadInfo, overload_monitor_accept_tx};
+use crate::safe_mode_diagnostics::{SafeModeDiagnostics, SafeModeDiagnosticsTrigger};
 use crate::stake_aggregator::StakeAggregator;
 use crate::subscription_handler::SubscriptionHandler;
 use crate::transaction_input_loader::TransactionInputLoader;
@@ -261,8 +271,11 @@ pub mod congestion_log;
 pub mod consensus_tx_status_cache;
 pub(crate) mod epoch_marker_key;
 pub mod epoch_start_configuration;
+pub mod epoch_store_faults;
 pub mod execution_time_estimator;
+pub mod execution_time_observation_export;
 pub mod finalized_transactions_cache;
+pub mod previous_epoch_tables;
 pub mod shared_object_congestion_tracker;
 pub mod shared_object_version_manager;
 pub mod submitted_transaction_cache;
@@ -311,6 +324,7 @@ pub struct AuthorityMetrics {
     pub(crate) execution_driver_executed_transactions: IntCounter,
     pub(crate) execution_driver_paused_transactions: IntCounter,
     pub(crate) execution_driver_dispatch_queue: IntGauge,
+    pub(crate) execution_driver_sender_throttled_transactions: IntGauge,
     pub(crate) execution_queueing_delay_s: Histogram,
     pub(crate) prepare_cert_gas_latency_ratio: Histogram,
     pub(crate) execution_gas_latency_ratio: Histogram,
@@ -346,6 +360,11 @@ pub struct AuthorityMetrics {
     pub consensus_committed_subdags: IntCounterVec,
     pub accumulator_deposits: IntCounter,
     pub accumulator_withdrawals: IntCounter,
+    pub accumulator_settlements_pending: IntGauge,
+    pub accumulator_settlement_lag_checkpoints: IntGauge,
+    pub accumulator_settlement_resolution_latency: Histogram,
+    pub accumulator_settlement_txns: Histogram,
+    pub accumulator_settlement_batch_size: Histogram,
     pub consensus_committed_messages: IntGaugeVec,
     pub consensus_committed_user_transactions: IntGaugeVec,
     pub consensus_finalized_user_transactions: IntGaugeVec,
@@ -421,6 +440,9 @@ pub const DEV_INSPECT_GAS_COIN_VALUE: u64 = 1_000_000_000_000_000_000;
 // When submitted by TransactionDriver, it will retry quickly if there is no return from this validator too.
 pub const WAIT_FOR_FASTPATH_INPUT_TIMEOUT: Duration = Duration::from_secs(2);
 
+// Requests for effects signatures rarely outlive more than one reconfiguration.
+const PREVIOUS_EPOCHS_FOR_EFFECTS_SIGNATURES: u64 = 2;
+
 impl AuthorityMetrics {
     pub fn new(registry: &prometheus::Registry) -> AuthorityMetrics {
         Self {
@@ -597,6 +619,12 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            execution_driver_sender_throttled_transactions: register_int_gauge_with_registry!(
+                "execution_driver_sender_throttled_transactions",
+                "Number of ready transactions waiting because their sender is at its concurrency limit",
+                registry,
+            )
+            .unwrap(),
             execution_queueing_delay_s: register_histogram_with_registry!(
                 "execution_queueing_delay_s",
                 "Queueing delay between a transaction is ready for execution until it starts executing.",
@@ -748,6 +776,37 @@ impl AuthorityMetrics {
                 "Total accumulator withdrawal (split) events processed in settlement",
                 registry,
             ).unwrap(),
+            accumulator_settlements_pending: register_int_gauge_with_registry!(
+                "accumulator_settlements_pending",
+                "Number of scheduled accumulator settlements that have not executed yet",
+                registry,
+            ).unwrap(),
+            accumulator_settlement_lag_checkpoints: register_int_gauge_with_registry!(
+                "accumulator_settlement_lag_checkpoints",
+                "Number of checkpoints between the oldest pending accumulator settlement and the \
+                 latest scheduled one",
+                registry,
+            ).unwrap(),
+            accumulator_settlement_resolution_latency: register_histogram_with_registry!(
+                "accumulator_settlement_resolution_latency",
+                "Seconds from scheduling an accumulator settlement to the execution of its \
+                 barrier transaction",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
+            accumulator_settlement_txns: register_histogram_with_registry!(
+                "accumulator_settlement_txns",
+                "Number of settlement transactions built per accumulator settlement, excluding \
+                 the barrier",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
+            accumulator_settlement_batch_size: register_histogram_with_registry!(
+                "accumulator_settlement_batch_size",
+                "Number of transactions settled per accumulator settlement",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
             consensus_committed_messages: register_int_gauge_vec_with_registry!(
                 "consensus_committed_messages",
                 "Total number of committed consensus messages, sliced by author",
@@ -968,6 +1027,51 @@ impl ForkRecoveryState {
 
 pub type PostProcessingOutput = (StagedBatch, IndexStoreCacheUpdates);
 
+/// The validators that would upgrade to a protocol version with the same protocol config digest
+/// and system packages, and their combined stake.
+#[derive(Clone, Debug)]
+pub struct ProtocolUpgradeVotes {
+    pub protocol_config_digest: Digest,
+    pub system_packages: Vec<ObjectRef>,
+    pub authorities: Vec<AuthorityName>,
+    pub stake: StakeUnit,
+}
+
+/// What the end-of-epoch transaction would upgrade the protocol to, given the capabilities
+/// collected so far. See [`AuthorityState::preview_protocol_upgrade`].
+#[derive(Clone, Debug)]
+pub struct ProtocolUpgradePreview {
+    pub current_protocol_version: ProtocolVersion,
+    pub next_protocol_version: ProtocolVersion,
+    /// Empty if the protocol version does not change.
+    pub next_epoch_system_packages: Vec<ObjectRef>,
+    pub buffer_stake_bps: u64,
+    pub quorum_threshold: StakeUnit,
+    /// The stake a set of votes needs for the upgrade to happen.
+    pub effective_threshold: StakeUnit,
+    /// The votes for each protocol version considered, in order. The last version listed is the
+    /// first one without enough support, unless protocol versions cannot be skipped.
+    pub votes: Vec<(ProtocolVersion, Vec<ProtocolUpgradeVotes>)>,
+}
+
+/// What validators running a node binary advertise in their capabilities.
+#[derive(Clone, Debug)]
+pub struct NodeBinaryCapabilities {
+    pub supported_protocol_versions: SupportedProtocolVersionsWithHashes,
+    pub available_system_packages: Vec<ObjectRef>,
+}
+
+/// The outcome of rolling out a node binary to a committee in stages. See
+/// [`AuthorityState::simulate_protocol_upgrade_rollout`].
+#[derive(Clone, Debug)]
+pub struct ProtocolUpgradeRollout {
+    /// The upgrade preview at the end of each stage's epoch, up to the first stage that upgrades
+    /// the protocol.
+    pub stages: Vec<ProtocolUpgradePreview>,
+    /// The first stage whose end-of-epoch transaction upgrades the protocol, if any.
+    pub upgrade_stage: Option<usize>,
+}
+
 pub struct AuthorityState {
     // Fixed size, static, identity of the authority
     /// The name of this authority.
@@ -1006,6 +1110,9 @@ pub struct AuthorityState {
     _pruner: AuthorityStorePruner,
     _authority_per_epoch_pruner: AuthorityPerEpochStorePruner,
 
+    /// Serves effects signatures to requests that outlived the epoch they were made in.
+    previous_epoch_tables: PreviousEpochTables,
+
     /// Take db checkpoints of different dbs
     db_checkpoint_config: DBCheckpointConfig,
 
@@ -1019,6 +1126,13 @@ pub struct AuthorityState {
 
     pub(crate) congestion_tracker: Arc<CongestionTracker>,
 
+    /// Aggregates gas usage per invoked package. Only set when enabled in the node config.
+    pub(crate) package_gas_tracker: Option<Arc<PackageGasTracker>>,
+
+    /// Collects the activity of the addresses reported on in per-epoch compliance reports. Only
+    /// set when enabled in the node config.
+    pub(crate) compliance_reporter: Option<Arc<ComplianceReporter>>,
+
     /// Consumed by gasless tx rate limiter.
     pub(crate) consensus_gasless_counter: Arc<ConsensusGaslessCounter>,
 
@@ -1047,6 +1161,9 @@ pub struct AuthorityState {
     /// Created once per process, then re-attached to each new `AuthorityPerEpochStore`
     /// at reconfiguration.
     transaction_deny_config_manager: Arc<TransactionDenyConfigManager>,
+
+    /// Operator-defined rules that vote to reject transactions in consensus.
+    consensus_rejection_policies: Arc<ConsensusRejectionPolicies>,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -1111,7 +1228,11 @@ impl AuthorityState {
             receiving_objects_refs,
             &deny_config,
             self.get_backing_package_store().as_ref(),
-        )?;
+        )
+        .inspect_err(|e| {
+            self.transaction_deny_config_manager
+                .record_policy_rejection(e)
+        })?;
 
         let declared_withdrawals = tx_data.process_funds_withdrawals_for_signing(
             self.chain_identifier,
@@ -3732,6 +3853,18 @@ impl AuthorityState {
             epoch_store.get_parent_path(),
             &config.authority_store_pruning_config,
         );
+        // Only the epoch databases that are not pruned can be read.
+        let previous_epochs_for_effects_signatures = match config
+            .authority_store_pruning_config
+            .num_latest_epoch_dbs_to_retain
+        {
+            0 | usize::MAX => PREVIOUS_EPOCHS_FOR_EFFECTS_SIGNATURES,
+            retained => PREVIOUS_EPOCHS_FOR_EFFECTS_SIGNATURES.min(retained as u64 - 1),
+        };
+        let previous_epoch_tables = PreviousEpochTables::new(
+            epoch_store.get_parent_path(),
+            previous_epochs_for_effects_signatures,
+        );
         let _pruner = AuthorityStorePruner::new(
             store.perpetual_tables.clone(),
             checkpoint_store.clone(),
@@ -3794,6 +3927,8 @@ impl AuthorityState {
             );
         }
 
+        let consensus_rejection_policies = ConsensusRejectionPolicies::new(prometheus_registry);
+
         let state = Arc::new(AuthorityState {
             name,
             secret,
@@ -3811,11 +3946,20 @@ impl AuthorityState {
             metrics,
             _pruner,
             _authority_per_epoch_pruner,
+            previous_epoch_tables,
             db_checkpoint_config: db_checkpoint_config.clone(),
             config,
             overload_info: AuthorityOverloadInfo::default(),
             chain_identifier,
             congestion_tracker: Arc::new(CongestionTracker::new()),
+            package_gas_tracker: config
+                .package_gas_accounting
+                .as_ref()
+                .map(|config| Arc::new(PackageGasTracker::new(config))),
+            compliance_reporter: config
+                .compliance_report
+                .as_ref()
+                .map(|config| Arc::new(ComplianceReporter::new(config))),
             consensus_gasless_counter: Arc::new(ConsensusGaslessCounter::default()),
             traffic_controller,
             fork_recovery_state,
@@ -3825,15 +3969,31 @@ impl AuthorityState {
             pending_post_processing: Arc::new(DashMap::new()),
             post_processing_semaphore: Arc::new(tokio::sync::Semaphore::new(num_cpus::get())),
             transaction_deny_config_manager,
+            consensus_rejection_policies,
         });
         state.init_object_funds_checker().await;
 
         // Start a task to execute ready certificates.
         let authority_state = Arc::downgrade(&state);
+        let sender_limiter = state
+            .config
+            .authority_overload_config
+            .sender_concurrency_limit
+            .as_ref()
+            .map(|config| {
+                SenderConcurrencyLimiter::new(
+                    config,
+                    state
+                        .metrics
+                        .execution_driver_sender_throttled_transactions
+                        .clone(),
+                )
+            });
         spawn_monitored_task!(execution_process(
             authority_state,
             rx_ready_certificates,
             rx_execution_shutdown,
+            sender_limiter,
         ));
         // TODO: This doesn't belong to the constructor of AuthorityState.
         state
@@ -3989,6 +4149,37 @@ impl AuthorityState {
             .database_for_testing()
     }
 
+    /// Dry run of a pruning configuration change: estimates the space each component would free
+    /// if the node switched from `current` to `proposed`.
+    pub fn estimate_pruning_reclaim(
+        &self,
+        current: &sui_config::node::AuthorityStorePruningConfig,
+        proposed: &sui_config::node::AuthorityStorePruningConfig,
+    ) -> anyhow::Result<ReclaimEstimate> {
+        AuthorityStorePruner::estimate_reclaim(
+            &self.authority_store().perpetual_tables,
+            &self.checkpoint_store,
+            current,
+            proposed,
+        )
+    }
+
+    /// Recomputes the running root state hash of checkpoints `start..=end` of the current epoch
+    /// and compares it against the stored one, for fork investigations.
+    pub fn audit_running_root(
+        &self,
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+    ) -> SuiResult<RunningRootAudit> {
+        let epoch_store = self.load_epoch_store_one_call_per_task();
+        crate::global_state_hasher::audit_running_root(
+            self.get_global_state_hash_store().as_ref(),
+            &epoch_store,
+            start,
+            end,
+        )
+    }
+
     pub fn cache_for_testing(&self) -> &WritebackCache {
         self.execution_cache_trait_pointers
             .testing_api
@@ -4565,6 +4756,22 @@ impl AuthorityState {
         self.load_epoch_store_one_call_per_task()
     }
 
+    pub fn package_gas_tracker(&self) -> Option<&Arc<PackageGasTracker>> {
+        self.package_gas_tracker.as_ref()
+    }
+
+    pub fn compliance_reporter(&self) -> Option<&Arc<ComplianceReporter>> {
+        self.compliance_reporter.as_ref()
+    }
+
+    pub fn congestion_tracker(&self) -> &Arc<CongestionTracker> {
+        &self.congestion_tracker
+    }
+
+    pub fn consensus_rejection_policies(&self) -> &Arc<ConsensusRejectionPolicies> {
+        &self.consensus_rejection_policies
+    }
+
     pub fn transaction_deny_config_manager(&self) -> &Arc<TransactionDenyConfigManager> {
         &self.transaction_deny_config_manager
     }
@@ -5476,7 +5683,21 @@ impl AuthorityState {
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) -> SuiResult<VerifiedSignedTransactionEffects> {
         let tx_digest = *effects.transaction_digest();
-        let signed_effects = match epoch_store.get_effects_signature(&tx_digest)? {
+        let existing_sig = match epoch_store.get_effects_signature(&tx_digest) {
+            // The epoch ended while this request was being served. The signature from that epoch
+            // is still valid, if there is one, but the ended epoch cannot sign anymore.
+            Err(e) if matches!(e.as_inner(), SuiErrorKind::EpochEnded(_)) => Some(
+                self.previous_epoch_tables
+                    .get_effects_signature(
+                        epoch_store.epoch(),
+                        self.load_epoch_store_one_call_per_task().epoch(),
+                        &tx_digest,
+                    )?
+                    .ok_or(e)?,
+            ),
+            result => result?,
+        };
+        let signed_effects = match existing_sig {
             Some(sig) => {
                 debug_assert!(sig.epoch == epoch_store.epoch());
                 SignedTransactionEffects::new_from_data_and_sig(effects, sig)
@@ -5746,7 +5967,7 @@ impl AuthorityState {
         protocol_config: &ProtocolConfig,
         committee: &Committee,
         capabilities: Vec<AuthorityCapabilitiesV2>,
-        mut buffer_stake_bps: u64,
+        buffer_stake_bps: u64,
     ) -> Option<(ProtocolVersion, Vec<ObjectRef>)> {
         if proposed_protocol_version > current_protocol_version + 1
             && !protocol_config.advance_to_highest_supported_protocol_version()
@@ -5754,11 +5975,36 @@ impl AuthorityState {
             return None;
         }
 
-        if buffer_stake_bps > 10000 {
-            warn!("clamping buffer_stake_bps to 10000");
-            buffer_stake_bps = 10000;
-        }
+        let quorum_threshold = committee.quorum_threshold();
+        let effective_threshold = Self::protocol_upgrade_threshold(committee, buffer_stake_bps);
+
+        // There can only be one set of votes that have a majority, find one if it exists.
+        Self::protocol_upgrade_votes(proposed_protocol_version, committee, capabilities)
+            .into_iter()
+            .find_map(|votes| {
+                info!(
+                    protocol_config_digest = ?votes.protocol_config_digest,
+                    total_votes = ?votes.stake,
+                    ?quorum_threshold,
+                    ?buffer_stake_bps,
+                    ?effective_threshold,
+                    ?proposed_protocol_version,
+                    packages = ?votes.system_packages,
+                    "support for upgrade"
+                );
+
+                let has_support = votes.stake >= effective_threshold;
+                has_support.then_some((proposed_protocol_version, votes.system_packages))
+            })
+    }
 
+    /// Group the validators by the protocol config digest and system packages they would like to
+    /// upgrade to at `proposed_protocol_version`, and tally their stake.
+    fn protocol_upgrade_votes(
+        proposed_protocol_version: ProtocolVersion,
+        committee: &Committee,
+        capabilities: Vec<AuthorityCapabilitiesV2>,
+    ) -> Vec<ProtocolUpgradeVotes> {
         // For each validator, gather the protocol version and system packages that it would like
         // to upgrade to in the next epoch.
         let mut desired_upgrades: Vec<_> = capabilities
@@ -5787,45 +6033,48 @@ impl AuthorityState {
             })
             .collect();
 
-        // There can only be one set of votes that have a majority, find one if it exists.
         desired_upgrades.sort();
         desired_upgrades
             .into_iter()
             .chunk_by(|(digest, packages, _authority)| (*digest, packages.clone()))
             .into_iter()
-            .find_map(|((digest, packages), group)| {
+            .map(|((digest, packages), group)| {
                 // should have been filtered out earlier.
                 assert!(!packages.is_empty());
 
                 let mut stake_aggregator: StakeAggregator<(), true> =
                     StakeAggregator::new(Arc::new(committee.clone()));
 
+                let mut authorities = vec![];
                 for (_, _, authority) in group {
                     stake_aggregator.insert_generic(authority, ());
+                    authorities.push(authority);
                 }
 
-                let total_votes = stake_aggregator.total_votes();
-                let quorum_threshold = committee.quorum_threshold();
-                let f = committee.total_votes() - committee.quorum_threshold();
+                ProtocolUpgradeVotes {
+                    protocol_config_digest: digest,
+                    system_packages: packages,
+                    authorities,
+                    stake: stake_aggregator.total_votes(),
+                }
+            })
+            .collect()
+    }
 
-                // multiple by buffer_stake_bps / 10000, rounded up.
-                let buffer_stake = (f * buffer_stake_bps).div_ceil(10000);
-                let effective_threshold = quorum_threshold + buffer_stake;
+    /// The stake needed to upgrade the protocol: a quorum, plus `buffer_stake_bps` of the
+    /// remaining stake.
+    fn protocol_upgrade_threshold(committee: &Committee, mut buffer_stake_bps: u64) -> StakeUnit {
+        if buffer_stake_bps > 10000 {
+            warn!("clamping buffer_stake_bps to 10000");
+            buffer_stake_bps = 10000;
+        }
 
-                info!(
-                    protocol_config_digest = ?digest,
-                    ?total_votes,
-                    ?quorum_threshold,
-                    ?buffer_stake_bps,
-                    ?effective_threshold,
-                    ?proposed_protocol_version,
-                    ?packages,
-                    "support for upgrade"
-                );
+        let quorum_threshold = committee.quorum_threshold();
+        let f = committee.total_votes() - quorum_threshold;
 
-                let has_support = total_votes >= effective_threshold;
-                has_support.then_some((proposed_protocol_version, packages))
-            })
+        // multiple by buffer_stake_bps / 10000, rounded up.
+        let buffer_stake = (f * buffer_stake_bps).div_ceil(10000);
+        quorum_threshold + buffer_stake
     }
 
     fn choose_protocol_version_and_system_packages_v2(
@@ -5854,6 +6103,117 @@ impl AuthorityState {
         (next_protocol_version, system_packages)
     }
 
+    /// Preview the protocol version and system packages that the end-of-epoch transaction would
+    /// upgrade to if the epoch ended with `capabilities`, along with the votes for each protocol
+    /// version that was considered.
+    pub fn preview_protocol_upgrade(
+        current_protocol_version: ProtocolVersion,
+        protocol_config: &ProtocolConfig,
+        committee: &Committee,
+        capabilities: Vec<AuthorityCapabilitiesV2>,
+        buffer_stake_bps: u64,
+    ) -> ProtocolUpgradePreview {
+        let (next_protocol_version, next_epoch_system_packages) =
+            Self::choose_protocol_version_and_system_packages_v2(
+                current_protocol_version,
+                protocol_config,
+                committee,
+                capabilities.clone(),
+                buffer_stake_bps,
+            );
+
+        // Every version up to the one after the chosen version was considered, unless versions
+        // cannot be skipped.
+        let mut votes = vec![];
+        let mut proposed_protocol_version = current_protocol_version + 1;
+        while proposed_protocol_version <= next_protocol_version + 1
+            && (proposed_protocol_version == current_protocol_version + 1
+                || protocol_config.advance_to_highest_supported_protocol_version())
+        {
+            votes.push((
+                proposed_protocol_version,
+                Self::protocol_upgrade_votes(
+                    proposed_protocol_version,
+                    committee,
+                    capabilities.clone(),
+                ),
+            ));
+            proposed_protocol_version = proposed_protocol_version + 1;
+        }
+
+        ProtocolUpgradePreview {
+            current_protocol_version,
+            next_protocol_version,
+            next_epoch_system_packages,
+            buffer_stake_bps,
+            quorum_threshold: committee.quorum_threshold(),
+            effective_threshold: Self::protocol_upgrade_threshold(committee, buffer_stake_bps),
+            votes,
+        }
+    }
+
+    /// Simulate rolling out a node binary to the validators of `committee`, one stage per epoch.
+    /// By the end of stage `i`'s epoch, the validators in `stages[..=i]` run the binary and
+    /// advertise `binary`, while the others still advertise their entry in `capabilities`, if they
+    /// have one. The committee and the current protocol version stay the same until the first
+    /// stage that upgrades the protocol, where the simulation stops.
+    ///
+    /// Upgrades need `buffer_stake_override_bps` of buffer stake if it is set, as when the buffer
+    /// stake is overridden on the validators, and the protocol config's buffer otherwise.
+    pub fn simulate_protocol_upgrade_rollout(
+        current_protocol_version: ProtocolVersion,
+        protocol_config: &ProtocolConfig,
+        committee: &Committee,
+        capabilities: Vec<AuthorityCapabilitiesV2>,
+        binary: &NodeBinaryCapabilities,
+        stages: &[Vec<AuthorityName>],
+        buffer_stake_override_bps: Option<u64>,
+    ) -> ProtocolUpgradeRollout {
+        let buffer_stake_bps = buffer_stake_override_bps
+            .unwrap_or_else(|| protocol_config.buffer_stake_for_protocol_upgrade_bps());
+
+        let mut capabilities: BTreeMap<_, _> = capabilities
+            .into_iter()
+            .map(|cap| (cap.authority, cap))
+            .collect();
+
+        let mut rollout = ProtocolUpgradeRollout {
+            stages: vec![],
+            upgrade_stage: None,
+        };
+
+        for (i, stage) in stages.iter().enumerate() {
+            for authority in stage {
+                capabilities.insert(
+                    *authority,
+                    AuthorityCapabilitiesV2 {
+                        authority: *authority,
+                        generation: 0,
+                        supported_protocol_versions: binary.supported_protocol_versions.clone(),
+                        available_system_packages: binary.available_system_packages.clone(),
+                    },
+                );
+            }
+
+            let preview = Self::preview_protocol_upgrade(
+                current_protocol_version,
+                protocol_config,
+                committee,
+                capabilities.values().cloned().collect(),
+                buffer_stake_bps,
+            );
+
+            let upgrades = preview.next_protocol_version != current_protocol_version;
+            rollout.stages.push(preview);
+            if upgrades {
+                rollout.upgrade_stage = Some(i);
+                break;
+            }
+        }
+
+        rollout
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn create_authenticator_state_tx(
         &self,
@@ -6428,11 +6788,92 @@ impl AuthorityState {
             effects.summary_for_debug()
         );
         epoch_store.record_checkpoint_builder_is_safe_mode_metric(system_obj.safe_mode());
+        if system_obj.safe_mode() {
+            self.spawn_write_safe_mode_diagnostics(epoch_store, &system_obj, &effects);
+        }
         // The change epoch transaction cannot fail to execute.
         assert!(effects.status().is_ok());
         Ok((system_obj, effects))
     }
 
+    /// Writes a safe mode diagnostics bundle for an advance-epoch transaction built by the
+    /// checkpoint builder, in the background, if the node is configured to.
+    fn spawn_write_safe_mode_diagnostics(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        system_state: &SuiSystemState,
+        effects: &TransactionEffects,
+    ) {
+        let Some(config) = self.config.safe_mode_diagnostics.clone() else {
+            return;
+        };
+
+        let epoch = epoch_store.epoch();
+        let protocol_config = epoch_store.protocol_config().clone();
+        let consensus_store = epoch_store.consensus_store();
+        let system_state = system_state.clone();
+        let effects = effects.clone();
+        tokio::task::spawn_blocking(move || {
+            let diagnostics = SafeModeDiagnostics::collect(
+                SafeModeDiagnosticsTrigger::AdvanceEpoch,
+                epoch,
+                &protocol_config,
+                system_state,
+                Some(effects),
+                consensus_store.as_deref(),
+                config.num_commits,
+            );
+            match diagnostics.write(&config.output_path) {
+                Ok(path) => warn!(?path, "Wrote safe mode diagnostics bundle"),
+                Err(e) => error!("Failed to write safe mode diagnostics bundle: {e:#}"),
+            }
+        });
+    }
+
+    /// Writes a safe mode diagnostics bundle for the advance-epoch transaction that started the
+    /// current epoch to `dir`, and returns its path. The bundle holds the current epoch's protocol
+    /// config values and consensus commits.
+    pub fn write_safe_mode_diagnostics(
+        &self,
+        dir: &Path,
+        num_commits: u32,
+    ) -> anyhow::Result<PathBuf> {
+        let epoch_store = self.load_epoch_store_one_call_per_task();
+        let system_state = self
+            .get_object_cache_reader()
+            .get_sui_system_state_object_unsafe()?;
+
+        // The advance-epoch transaction is the last transaction of the previous epoch.
+        let advance_epoch_effects = match epoch_store.epoch().checked_sub(1) {
+            Some(previous_epoch) => self
+                .checkpoint_store
+                .get_epoch_last_checkpoint(previous_epoch)?
+                .map(|checkpoint| {
+                    self.checkpoint_store
+                        .get_checkpoint_contents(&checkpoint.content_digest)
+                })
+                .transpose()?
+                .flatten()
+                .and_then(|contents| contents.iter().last().copied())
+                .and_then(|digests| {
+                    self.get_transaction_cache_reader()
+                        .get_executed_effects(&digests.transaction)
+                }),
+            None => None,
+        };
+
+        let diagnostics = SafeModeDiagnostics::collect(
+            SafeModeDiagnosticsTrigger::OnDemand,
+            system_state.epoch().saturating_sub(1),
+            epoch_store.protocol_config(),
+            system_state,
+            advance_epoch_effects,
+            epoch_store.consensus_store().as_deref(),
+            num_commits,
+        );
+        diagnostics.write(dir)
+    }
+
     #[instrument(level = "error", skip_all)]
     async fn reopen_epoch_db(
         &self,
@@ -4,12 +4,14 @@
 use dashmap::DashMap;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::future::Future;
+use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 
 use arc_swap::ArcSwapOption;
+use consensus_core::storage::rocksdb_store::RocksDBStore;
 use enum_dispatch::enum_dispatch;
 use fastcrypto::groups::bls12381;
 use fastcrypto_tbls::dkg_v1;
@@ -22,13 +24,16 @@ use move_bytecode_utils::module_cache::SyncModuleCache;
 use mysten_common::ZipDebugEqIteratorExt;
 use mysten_common::assert_reachable;
 use mysten_common::random_util::randomize_cache_capacity_in_tests;
-use mysten_common::sync::notify_read::NotifyRead;
+use mysten_common::sync::notify_read::{
+    BoundedRegistrations, NotifyRead, NotifyReadError, NotifyReadLimits,
+};
 use mysten_common::{debug_fatal, in_test_configuration};
 use mysten_metrics::monitored_scope;
 use parking_lot::RwLock;
 use parking_lot::{Mutex, RwLockReadGuard, RwLockWriteGuard};
+use prometheus::Registry;
 use serde::{Deserialize, Serialize};
-use sui_config::node::ExpensiveSafetyCheckConfig;
+use sui_config::node::{ExpensiveSafetyCheckConfig, MutexTableConfig};
 use sui_execution::{self, Executor};
 use sui_macros::fail_point;
 use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion};
@@ -78,9 +83,9 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, trace, warn};
 use typed_store::DBMapUtils;
 use typed_store::Map;
-use typed_store::rocks::{DBBatch, DBMap, MetricConf};
+use typed_store::rocks::{DBBatch, DBMap, MetricConf, read_size_from_env};
 #[cfg(not(tidehunter))]
-use typed_store::rocks::{DBOptions, ReadWriteOptions, default_db_options, read_size_from_env};
+use typed_store::rocks::{DBOptions, ReadWriteOptions, default_db_options};
 use typed_store::rocksdb::Options;
 
 #[cfg(not(tidehunter))]
@@ -98,8 +103,10 @@ use super::submitted_transaction_cache::{
 };
 use super::transaction_deferral::{DeferralKey, DeferralReason};
 use super::transaction_reject_reason_cache::TransactionRejectReasonCache;
+use crate::accumulators::settlement_tracker::SettlementTracker;
 use crate::authority::ResolverWrapper;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
+use crate::authority::epoch_store_faults::{self, EpochStoreNotification, EpochStoreWrite};
 use crate::authority::execution_time_estimator::{
     EXTRA_FIELD_EXECUTION_TIME_ESTIMATES_CHUNK_COUNT_KEY, EXTRA_FIELD_EXECUTION_TIME_ESTIMATES_KEY,
 };
@@ -113,6 +120,7 @@ use crate::consensus_handler::{
     ConsensusCommitInfo, SequencedConsensusTransaction, SequencedConsensusTransactionKey,
     SequencedConsensusTransactionKind, VerifiedSequencedConsensusTransaction,
 };
+use crate::consensus_participation::ConsensusParticipation;
 use crate::epoch::epoch_metrics::EpochMetrics;
 use crate::epoch::randomness::{
     RandomnessManager, RandomnessReporter, SINGLETON_KEY, VersionedProcessedMessage,
@@ -134,6 +142,20 @@ const RECONFIG_STATE_INDEX: u64 = 0;
 const OVERRIDE_PROTOCOL_UPGRADE_BUFFER_STAKE_INDEX: u64 = 0;
 pub const EPOCH_DB_PREFIX: &str = "epoch_";
 
+/// Writes to the epoch tables taking longer than this many milliseconds are logged with the keys
+/// and sizes they wrote to each table, to find the tables stalling the consensus handler.
+const ENV_VAR_EPOCH_DB_SLOW_WRITE_THRESHOLD_MS: &str = "EPOCH_DB_SLOW_WRITE_THRESHOLD_MS";
+const DEFAULT_EPOCH_DB_SLOW_WRITE_THRESHOLD_MS: usize = 100;
+
+/// Limits on the bounded registrations of `consensus_notify_read` and
+/// `executed_digests_notify_read`. Waits made on behalf of clients go through the bounded
+/// registrations, since the keys they wait on may never arrive.
+const BOUNDED_NOTIFY_READ_LIMITS: NotifyReadLimits = NotifyReadLimits {
+    max_pending: 100_000,
+    max_pending_per_caller: 20_000,
+    expiry: Duration::from_secs(60),
+};
+
 // Types for randomness DKG.
 pub(crate) type PkG = bls12381::G2Element;
 pub(crate) type EncG = bls12381::G2Element;
@@ -371,6 +393,8 @@ pub struct AuthorityPerEpochStore {
     mutex_table: MutexTable<TransactionDigest>,
     /// Mutex table for shared version assignment
     version_assignment_mutex_table: MutexTable<ObjectID>,
+    /// The sizing the mutex tables were created with, used to size them for the next epoch.
+    mutex_table_config: MutexTableConfig,
 
     /// The moment when the current epoch started locally on this validator. Note that this
     /// value could be skewed if the node crashed and restarted in the middle of the epoch. That's
@@ -411,8 +435,18 @@ pub struct AuthorityPerEpochStore {
     // Saved at end of epoch for propagating observations to the next.
     pub(crate) end_of_epoch_execution_time_observations: OnceCell<StoredExecutionTimeObservations>,
 
+    /// Store of the consensus instance running in this epoch, if this node runs consensus. Held
+    /// weakly, so that it is closed when consensus shuts down.
+    consensus_store: OnceCell<Weak<RocksDBStore>>,
+
     pub(crate) consensus_tx_status_cache: ConsensusTxStatusCache,
 
+    /// Share of recent consensus commits that included a block proposed by this validator.
+    pub consensus_participation: ConsensusParticipation,
+
+    /// Accumulator settlements scheduled in this epoch that have not executed yet.
+    pub settlement_tracker: SettlementTracker,
+
     /// A cache that maintains the reject vote reason for a transaction.
     pub(crate) tx_reject_reason_cache: TransactionRejectReasonCache,
 
@@ -425,6 +459,10 @@ pub struct AuthorityPerEpochStore {
     /// The node's role for this epoch, derived from committee membership and
     /// the configured sync mode. Computed once at construction.
     node_role: NodeRole,
+
+    /// Whether this is a shadow copy of the epoch store, replaying consensus commits to be compared
+    /// with the live store. Shadow stores take no part in DKG, so randomness is disabled in them.
+    shadow: bool,
 }
 
 /// AuthorityEpochTables contains tables that contain data that is only valid within an epoch.
@@ -440,7 +478,7 @@ pub struct AuthorityEpochTables {
     /// Note that this may contain signatures for effects from previous epochs, in the case
     /// that a user requests a signature for effects from a previous epoch. However, the
     /// signature is still epoch-specific and so is stored in the epoch store.
-    effects_signatures: DBMap<TransactionDigest, AuthoritySignInfo>,
+    pub(crate) effects_signatures: DBMap<TransactionDigest, AuthoritySignInfo>,
 
     /// When we sign a TransactionEffects, we must record the digest of the effects in order
     /// to detect and prevent equivocation when re-executing a transaction that may not have been
@@ -559,11 +597,18 @@ fn owned_object_transaction_locks_table_default_config() -> DBOptions {
 }
 
 impl AuthorityEpochTables {
+    fn metric_conf() -> MetricConf {
+        let threshold_ms = read_size_from_env(ENV_VAR_EPOCH_DB_SLOW_WRITE_THRESHOLD_MS)
+            .unwrap_or(DEFAULT_EPOCH_DB_SLOW_WRITE_THRESHOLD_MS);
+        MetricConf::new("epoch")
+            .with_slow_write_threshold(Duration::from_millis(threshold_ms as u64))
+    }
+
     #[cfg(not(tidehunter))]
     pub fn open(epoch: EpochId, parent_path: &Path, db_options: Option<Options>) -> Self {
         Self::open_tables_read_write(
             Self::path(epoch, parent_path),
-            MetricConf::new("epoch"),
+            Self::metric_conf(),
             db_options,
             None,
         )
@@ -759,7 +804,7 @@ impl AuthorityEpochTables {
         ];
         Self::open_tables_read_write(
             path.to_path_buf(),
-            MetricConf::new("epoch"),
+            Self::metric_conf(),
             configs.into_iter().collect(),
         )
     }
@@ -798,6 +843,20 @@ impl AuthorityEpochTables {
             .map(|s| s.index))
     }
 
+    pub fn get_capabilities_v2(&self) -> SuiResult<Vec<AuthorityCapabilitiesV2>> {
+        Ok(self
+            .authority_capabilities_v2
+            .safe_iter()
+            .map(|item| item.map(|(_, v)| v))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn get_override_protocol_upgrade_buffer_stake(&self) -> SuiResult<Option<u64>> {
+        Ok(self
+            .override_protocol_upgrade_buffer_stake
+            .get(&OVERRIDE_PROTOCOL_UPGRADE_BUFFER_STAKE_INDEX)?)
+    }
+
     pub fn get_last_consensus_stats(&self) -> SuiResult<Option<ExecutionIndicesWithStatsV2>> {
         Ok(self
             .last_consensus_stats_v2
@@ -834,7 +893,20 @@ impl AuthorityEpochTables {
     }
 }
 
-pub(crate) const MUTEX_TABLE_SIZE: usize = 1024;
+const TRANSACTION_MUTEX_TABLE: &str = "transaction";
+const VERSION_ASSIGNMENT_MUTEX_TABLE: &str = "version_assignment";
+
+/// The number of shards a mutex table with `shards` shards should have in the next epoch, given
+/// the fraction of its shard accesses that were contended this epoch: doubled (up to the maximum)
+/// if adaptive resizing is enabled and `contention` exceeds its threshold, otherwise unchanged.
+fn resized_mutex_table_shards(shards: usize, contention: f64, config: &MutexTableConfig) -> usize {
+    match config.resize_contention_ratio {
+        Some(threshold) if contention > threshold && shards < config.max_lock_shards() => {
+            (shards * 2).min(config.max_lock_shards())
+        }
+        _ => shards,
+    }
+}
 
 impl AuthorityPerEpochStore {
     #[instrument(name = "AuthorityPerEpochStore::new", level = "error", skip_all, fields(epoch = committee.epoch))]
@@ -855,6 +927,7 @@ impl AuthorityPerEpochStore {
         previous_epoch_last_checkpoint: CheckpointSequenceNumber,
         submitted_transaction_cache_metrics: Arc<SubmittedTransactionCacheMetrics>,
         fullnode_sync_mode: Option<FullNodeSyncMode>,
+        mutex_table_config: MutexTableConfig,
     ) -> SuiResult<Arc<Self>> {
         let current_time = Instant::now();
         let epoch_id = committee.epoch;
@@ -1014,6 +1087,22 @@ impl AuthorityPerEpochStore {
         let finalized_transactions_cache =
             FinalizedTransactionsCache::new(randomize_cache_capacity_in_tests(100_000));
 
+        let mutex_table = MutexTable::new(mutex_table_config.transaction_lock_shards());
+        let version_assignment_mutex_table =
+            MutexTable::new(mutex_table_config.version_assignment_lock_shards());
+        for (table, shards) in [
+            (TRANSACTION_MUTEX_TABLE, mutex_table.num_shards()),
+            (
+                VERSION_ASSIGNMENT_MUTEX_TABLE,
+                version_assignment_mutex_table.num_shards(),
+            ),
+        ] {
+            metrics
+                .mutex_table_shards
+                .with_label_values(&[table])
+                .set(shards as i64);
+        }
+
         let s = Arc::new(Self {
             name,
             committee: committee.clone(),
@@ -1029,16 +1118,19 @@ impl AuthorityPerEpochStore {
             reconfig_state_mem: RwLock::new(reconfig_state),
             epoch_alive_token,
             epoch_alive: tokio::sync::RwLock::new(true),
-            consensus_notify_read: NotifyRead::new(),
-            executed_transactions_to_checkpoint_notify_read: NotifyRead::new(),
+            consensus_notify_read: NotifyRead::with_limits(BOUNDED_NOTIFY_READ_LIMITS),
+            executed_transactions_to_checkpoint_notify_read: NotifyRead::with_limits(
+                BOUNDED_NOTIFY_READ_LIMITS,
+            ),
             signature_verifier,
             checkpoint_state_notify_read: NotifyRead::new(),
             running_root_notify_read: NotifyRead::new(),
-            executed_digests_notify_read: NotifyRead::new(),
+            executed_digests_notify_read: NotifyRead::with_limits(BOUNDED_NOTIFY_READ_LIMITS),
             signed_effects_digests_cache,
             end_of_publish: Mutex::new(end_of_publish),
-            mutex_table: MutexTable::new(MUTEX_TABLE_SIZE),
-            version_assignment_mutex_table: MutexTable::new(MUTEX_TABLE_SIZE),
+            mutex_table,
+            version_assignment_mutex_table,
+            mutex_table_config,
             epoch_open_time: current_time,
             epoch_close_time: Default::default(),
             metrics,
@@ -1053,11 +1145,15 @@ impl AuthorityPerEpochStore {
             tx_local_execution_time: OnceCell::new(),
             tx_object_debts: OnceCell::new(),
             end_of_epoch_execution_time_observations: OnceCell::new(),
+            consensus_store: OnceCell::new(),
             consensus_tx_status_cache,
+            consensus_participation: ConsensusParticipation::default(),
+            settlement_tracker: SettlementTracker::default(),
             tx_reject_reason_cache,
             submitted_transaction_cache,
             finalized_transactions_cache,
             node_role: NodeRole::from_committee(&committee, &name, fullnode_sync_mode),
+            shadow: false,
         });
 
         s.update_buffer_stake_metric();
@@ -1095,7 +1191,7 @@ impl AuthorityPerEpochStore {
     // Returns true if randomness state is enabled in the protocol config *and* the
     // randomness state object already exists
     pub fn randomness_state_enabled(&self) -> bool {
-        self.protocol_config().random_beacon() && self.randomness_state_exists()
+        !self.shadow && self.protocol_config().random_beacon() && self.randomness_state_exists()
     }
 
     pub fn randomness_state_exists(&self) -> bool {
@@ -1260,9 +1356,87 @@ impl AuthorityPerEpochStore {
             epoch_last_checkpoint,
             self.submitted_transaction_cache.metrics(),
             fullnode_sync_mode,
+            self.next_mutex_table_config(),
         )
     }
 
+    /// Opens a fresh store for this epoch under `parent_path`, with none of its consensus output
+    /// yet, to replay its consensus commits into for [`crate::consensus_shadow_replay`]. The shadow
+    /// store records its epoch metrics in its own registry, so they don't mix with the live
+    /// store's.
+    pub fn new_shadow(
+        &self,
+        parent_path: &Path,
+        epoch_start_configuration: EpochStartConfiguration,
+        backing_package_store: Arc<dyn BackingPackageStore + Send + Sync>,
+        object_store: Arc<dyn ObjectStore + Send + Sync>,
+        expensive_safety_check_config: &ExpensiveSafetyCheckConfig,
+        highest_executed_checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<Arc<Self>> {
+        let mut shadow = Self::new(
+            self.name,
+            self.committee.clone(),
+            parent_path,
+            self.db_options.clone(),
+            EpochMetrics::new(&Registry::new()),
+            epoch_start_configuration,
+            backing_package_store,
+            object_store,
+            self.execution_component.metrics(),
+            self.signature_verifier.metrics.clone(),
+            expensive_safety_check_config,
+            self.chain,
+            highest_executed_checkpoint,
+            self.previous_epoch_last_checkpoint,
+            self.submitted_transaction_cache.metrics(),
+            None,
+            self.mutex_table_config.clone(),
+        )?;
+
+        Arc::get_mut(&mut shadow)
+            .expect("newly opened epoch store is not shared")
+            .shadow = true;
+        Ok(shadow)
+    }
+
+    /// The mutex table sizing for the next epoch. Reports each table's shard contention during
+    /// this epoch, and if adaptive resizing is enabled, doubles the tables that were contended on
+    /// more often than configured.
+    fn next_mutex_table_config(&self) -> MutexTableConfig {
+        let mut config = self.mutex_table_config.clone();
+        config.transaction_lock_shards =
+            Some(self.next_mutex_table_shards(TRANSACTION_MUTEX_TABLE, &self.mutex_table, &config));
+        config.version_assignment_lock_shards = Some(self.next_mutex_table_shards(
+            VERSION_ASSIGNMENT_MUTEX_TABLE,
+            &self.version_assignment_mutex_table,
+            &config,
+        ));
+        config
+    }
+
+    fn next_mutex_table_shards<K: Hash + Eq + Send + Sync + 'static>(
+        &self,
+        table: &str,
+        mutex_table: &MutexTable<K>,
+        config: &MutexTableConfig,
+    ) -> usize {
+        let shards = mutex_table.num_shards();
+        let contention = mutex_table.contention().ratio();
+        self.metrics
+            .mutex_table_shard_contention_bps
+            .with_label_values(&[table])
+            .set((contention * 10_000.0) as i64);
+
+        let resized = resized_mutex_table_shards(shards, contention, config);
+        if resized != shards {
+            info!(
+                table,
+                contention, shards, resized, "Resizing contended mutex table for the next epoch"
+            );
+        }
+        resized
+    }
+
     pub fn new_at_next_epoch_for_testing(
         &self,
         backing_package_store: Arc<dyn BackingPackageStore + Send + Sync>,
@@ -1461,6 +1635,22 @@ impl AuthorityPerEpochStore {
         }
     }
 
+    pub fn set_consensus_store(&self, consensus_store: &Arc<RocksDBStore>) {
+        if self
+            .consensus_store
+            .set(Arc::downgrade(consensus_store))
+            .is_err()
+        {
+            warn!("Consensus store was already set on AuthorityPerEpochStore");
+        }
+    }
+
+    /// Store of the consensus instance running in this epoch, if this node runs consensus and it
+    /// is running.
+    pub fn consensus_store(&self) -> Option<Arc<RocksDBStore>> {
+        self.consensus_store.get().and_then(Weak::upgrade)
+    }
+
     pub fn record_local_execution_time(
         &self,
         tx: &TransactionData,
@@ -1641,6 +1831,11 @@ impl AuthorityPerEpochStore {
 
     /// Acquire the lock for a tx without writing to the WAL.
     pub fn acquire_tx_lock(&self, digest: &TransactionDigest) -> CertLockGuard {
+        let _timer = self
+            .metrics
+            .mutex_table_lock_wait
+            .with_label_values(&[TRANSACTION_MUTEX_TABLE])
+            .start_timer();
         CertLockGuard(self.mutex_table.acquire_lock(*digest))
     }
 
@@ -1699,8 +1894,13 @@ impl AuthorityPerEpochStore {
         tables
             .transaction_key_to_digest
             .insert(&tx_key, &tx_digest)?;
-        self.executed_digests_notify_read
-            .notify(&tx_key, &tx_digest);
+        if !epoch_store_faults::drop_notification(
+            &self.name,
+            EpochStoreNotification::TransactionKeyExecuted,
+        ) {
+            self.executed_digests_notify_read
+                .notify(&tx_key, &tx_digest);
+        }
         Ok(())
     }
 
@@ -1708,6 +1908,12 @@ impl AuthorityPerEpochStore {
     /// Unlike `insert_tx_key`, this does NOT persist to the DB, avoiding crash
     /// inconsistency where the key survives restart but the effects do not.
     pub(crate) fn notify_barrier_executed(&self, key: TransactionKey, digest: TransactionDigest) {
+        if epoch_store_faults::drop_notification(
+            &self.name,
+            EpochStoreNotification::TransactionKeyExecuted,
+        ) {
+            return;
+        }
         self.executed_digests_notify_read.notify(&key, &digest);
     }
 
@@ -1982,6 +2188,7 @@ impl AuthorityPerEpochStore {
             Err(e) if matches!(e.as_inner(), SuiErrorKind::EpochEnded(_)) => return Ok(()),
             Err(e) => return Err(e),
         };
+        epoch_store_faults::check_db_write(&self.name, EpochStoreWrite::FinalizedCheckpoint)?;
         let mut batch = tables.signed_effects_digests.batch();
 
         // Now that the transaction effects are committed, we will never re-execute, so we
@@ -2097,9 +2304,15 @@ impl AuthorityPerEpochStore {
         // get_or_init_next_object_versions can be called
         // from consensus or checkpoint executor,
         // so we need to protect version assignment with a critical section
+        let lock_wait = self
+            .metrics
+            .mutex_table_lock_wait
+            .with_label_values(&[VERSION_ASSIGNMENT_MUTEX_TABLE])
+            .start_timer();
         let _locks = self
             .version_assignment_mutex_table
             .acquire_locks(objects_to_init.iter().map(|(id, _)| *id));
+        lock_wait.observe_duration();
         let tables = self.tables()?;
 
         let next_versions = self
@@ -2410,6 +2623,23 @@ impl AuthorityPerEpochStore {
         Ok(())
     }
 
+    /// Like `consensus_messages_processed_notify`, but the wait is registered on behalf of
+    /// `caller`, counts against its quota and fails once it expires.
+    pub async fn consensus_messages_processed_notify_bounded(
+        &self,
+        caller: &'static str,
+        keys: Vec<SequencedConsensusTransactionKey>,
+    ) -> SuiResult {
+        let registrations =
+            self.register_bounded(&self.consensus_notify_read, "consensus", caller, &keys)?;
+        let processed = self.check_consensus_messages_processed(keys.into_iter())?;
+        let result = registrations
+            .wait(processed.into_iter().map(|processed| !processed))
+            .await;
+        self.finish_bounded(&self.consensus_notify_read, "consensus", caller, result)?;
+        Ok(())
+    }
+
     /// Get notified when transactions get executed as part of a checkpoint execution.
     pub async fn transactions_executed_in_checkpoint_notify(
         &self,
@@ -2432,6 +2662,43 @@ impl AuthorityPerEpochStore {
             .await)
     }
 
+    /// Like `transactions_executed_in_checkpoint_notify`, but the wait is registered on behalf of
+    /// `caller`, counts against its quota and fails once it expires.
+    pub async fn transactions_executed_in_checkpoint_notify_bounded(
+        &self,
+        caller: &'static str,
+        digests: Vec<TransactionDigest>,
+    ) -> SuiResult<Vec<CheckpointSequenceNumber>> {
+        let registrations = self.register_bounded(
+            &self.executed_transactions_to_checkpoint_notify_read,
+            "executed_transactions_to_checkpoint",
+            caller,
+            &digests,
+        )?;
+        let checkpoints = self
+            .tables()?
+            .executed_transactions_to_checkpoint
+            .multi_get(&digests)?;
+        let result = registrations
+            .wait(checkpoints.iter().map(Option::is_none))
+            .await;
+        let notified = self.finish_bounded(
+            &self.executed_transactions_to_checkpoint_notify_read,
+            "executed_transactions_to_checkpoint",
+            caller,
+            result,
+        )?;
+        Ok(checkpoints
+            .into_iter()
+            .zip_debug_eq(notified)
+            .map(|(checkpoint, notified)| {
+                checkpoint
+                    .or(notified)
+                    .expect("every unexecuted digest was waited on")
+            })
+            .collect())
+    }
+
     pub fn has_received_end_of_publish_from(&self, authority: &AuthorityName) -> bool {
         self.end_of_publish
             .try_lock()
@@ -2487,6 +2754,112 @@ impl AuthorityPerEpochStore {
             .collect())
     }
 
+    /// Like `notify_read_tx_key_to_digest`, but the wait is registered on behalf of `caller`,
+    /// counts against its quota and fails once it expires.
+    pub async fn notify_read_tx_key_to_digest_bounded(
+        &self,
+        caller: &'static str,
+        keys: &[TransactionKey],
+    ) -> SuiResult<Vec<TransactionDigest>> {
+        let non_digest_keys: Vec<_> = keys
+            .iter()
+            .filter(|key| !matches!(key, TransactionKey::Digest(_)))
+            .copied()
+            .collect();
+
+        let registrations = self.register_bounded(
+            &self.executed_digests_notify_read,
+            "executed_digests",
+            caller,
+            &non_digest_keys,
+        )?;
+        let executed_digests = self
+            .tables()?
+            .transaction_key_to_digest
+            .multi_get(&non_digest_keys)?;
+        let result = registrations
+            .wait(executed_digests.iter().map(Option::is_none))
+            .await;
+        let notified = self.finish_bounded(
+            &self.executed_digests_notify_read,
+            "executed_digests",
+            caller,
+            result,
+        )?;
+        let mut results: VecDeque<_> = executed_digests
+            .into_iter()
+            .zip_debug_eq(notified)
+            .map(|(executed, notified)| {
+                executed
+                    .or(notified)
+                    .expect("every unexecuted key was waited on")
+            })
+            .collect();
+
+        Ok(keys
+            .iter()
+            .map(|key| {
+                if let TransactionKey::Digest(digest) = key {
+                    *digest
+                } else {
+                    results
+                        .pop_front()
+                        .expect("number of returned results should match number of non-digest keys")
+                }
+            })
+            .collect())
+    }
+
+    fn register_bounded<'a, K, V>(
+        &self,
+        notify_read: &'a NotifyRead<K, V>,
+        name: &'static str,
+        caller: &'static str,
+        keys: &[K],
+    ) -> SuiResult<BoundedRegistrations<'a, K, V>>
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+    {
+        let result = notify_read.register_all_bounded(caller, keys);
+        self.finish_bounded(notify_read, name, caller, result)
+    }
+
+    /// Updates the outstanding waiter metrics of `caller` and converts a failed registration or
+    /// wait into a `SuiError`.
+    fn finish_bounded<K, V, T>(
+        &self,
+        notify_read: &NotifyRead<K, V>,
+        name: &'static str,
+        caller: &'static str,
+        result: Result<T, NotifyReadError>,
+    ) -> SuiResult<T>
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+    {
+        self.metrics
+            .notify_read_outstanding_waiters
+            .with_label_values(&[name, caller])
+            .set(notify_read.outstanding_waiters_for(caller) as i64);
+        result.map_err(|e| {
+            let (reason, kind) = match e {
+                NotifyReadError::PoolExhausted { .. } => {
+                    ("pool_exhausted", SuiErrorKind::TooManyRequests)
+                }
+                NotifyReadError::CallerQuotaExceeded { .. } => {
+                    ("caller_quota_exceeded", SuiErrorKind::TooManyRequests)
+                }
+                NotifyReadError::Expired { .. } => ("expired", SuiErrorKind::TimeoutError),
+            };
+            self.metrics
+                .notify_read_bounded_failures
+                .with_label_values(&[name, caller, reason])
+                .inc();
+            kind.into()
+        })
+    }
+
     /// Caller must call consensus_message_processed_notify before calling this to ensure that all
     /// user signatures are available.
     pub fn user_signatures_for_checkpoint(
@@ -2605,12 +2978,7 @@ impl AuthorityPerEpochStore {
 
     pub fn get_capabilities_v2(&self) -> SuiResult<Vec<AuthorityCapabilitiesV2>> {
         assert!(self.protocol_config.authority_capabilities_v2());
-        Ok(self
-            .tables()?
-            .authority_capabilities_v2
-            .safe_iter()
-            .map(|item| item.map(|(_, v)| v))
-            .collect::<Result<Vec<_>, _>>()?)
+        self.tables()?.get_capabilities_v2()
     }
 
     pub(crate) fn record_jwk_vote(
@@ -3046,6 +3414,18 @@ impl AuthorityPerEpochStore {
         Ok(assigned_versions)
     }
 
+    /// The next versions of consensus objects, as assigned by the consensus output processed so
+    /// far, or `None` for objects that have not been used in this epoch yet.
+    pub fn get_next_shared_object_versions(
+        &self,
+        objects: &[ConsensusObjectSequenceKey],
+    ) -> SuiResult<Vec<Option<SequenceNumber>>> {
+        let tables = self.tables()?;
+        self.consensus_quarantine
+            .read()
+            .get_next_shared_object_versions(&tables, objects)
+    }
+
     pub fn get_highest_pending_checkpoint_height(&self) -> CheckpointHeight {
         self.consensus_quarantine
             .read()
@@ -3094,6 +3474,12 @@ impl AuthorityPerEpochStore {
         notifications: impl Iterator<Item = &'a SequencedConsensusTransactionKey>,
     ) {
         for key in notifications {
+            if epoch_store_faults::drop_notification(
+                &self.name,
+                EpochStoreNotification::ConsensusMessageProcessed,
+            ) {
+                continue;
+            }
             self.consensus_notify_read.notify(key, &());
         }
     }
@@ -3412,6 +3798,21 @@ impl AuthorityPerEpochStore {
             .get_observations()
     }
 
+    /// Returns the execution time observations this validator has shared through consensus,
+    /// as currently known to the execution time estimator. Empty if this node is not in the
+    /// committee.
+    pub async fn get_own_execution_time_observations(
+        &self,
+    ) -> Vec<(ExecutionTimeObservationKey, Duration)> {
+        let Some(index) = self.committee.authority_index(&self.name) else {
+            return vec![];
+        };
+        self.execution_time_estimator
+            .lock()
+            .await
+            .get_observations_from(index)
+    }
+
     /// Returns the role of this node for the current epoch.
     pub fn node_role(&self) -> NodeRole {
         self.node_role
@@ -685,7 +685,7 @@ impl AuthorityStore {
     }
 
     pub fn get_epoch_start_configuration(&self) -> SuiResult<Option<EpochStartConfiguration>> {
-        Ok(self.perpetual_tables.epoch_start_configuration.get(&())?)
+        self.perpetual_tables.get_epoch_start_configuration()
     }
 
     /// Updates the state resulting from the execution of a certificate.
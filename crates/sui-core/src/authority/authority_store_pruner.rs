@@ -11,14 +11,13 @@ use mysten_metrics::spawn_monitored_task;
 #[cfg(not(tidehunter))]
 use once_cell::sync::Lazy;
 use prometheus::{
-    IntCounter, IntGauge, Registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry,
+    IntCounter, IntGauge, IntGaugeVec, Registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry,
 };
+use serde::Serialize;
 #[cfg(tidehunter)]
 use serde::de::DeserializeOwned;
-#[cfg(not(tidehunter))]
-use std::cmp::max;
-use std::cmp::min;
+use std::cmp::{max, min};
 #[cfg(not(tidehunter))]
 use std::collections::{BTreeSet, HashMap};
 #[cfg(not(tidehunter))]
@@ -27,14 +26,15 @@ use std::sync::atomic::AtomicU64;
 #[cfg(not(tidehunter))]
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{sync::Arc, time::Duration};
-use sui_config::node::AuthorityStorePruningConfig;
+use sui_config::node::{AuthorityStorePruningConfig, PrunedComponent};
 use sui_rpc_store::Store as RpcStore;
 #[cfg(not(tidehunter))]
 use sui_types::base_types::VersionNumber;
 use sui_types::committee::EpochId;
+#[cfg(not(tidehunter))]
+use sui_types::digests::TransactionEffectsDigest;
 use sui_types::effects::TransactionEffects;
 use sui_types::effects::TransactionEffectsAPI;
-use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointDigest, CheckpointSequenceNumber,
 };
@@ -84,6 +84,7 @@ pub struct AuthorityStorePruningMetrics {
     pub last_pruned_indexes_transaction: IntGauge,
     pub num_epochs_to_retain_for_objects: IntGauge,
     pub num_epochs_to_retain_for_checkpoints: IntGauge,
+    pub last_pruned_component_checkpoint: IntGaugeVec,
 }
 
 impl AuthorityStorePruningMetrics {
@@ -131,6 +132,13 @@ impl AuthorityStorePruningMetrics {
                 registry
             )
             .unwrap(),
+            last_pruned_component_checkpoint: register_int_gauge_vec_with_registry!(
+                "last_pruned_component_checkpoint",
+                "Last checkpoint pruned for components with their own retention",
+                &["component"],
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
@@ -146,6 +154,30 @@ pub enum PruningMode {
     Checkpoints,
 }
 
+/// Number of checkpoints read per component when estimating reclaimable space.
+const RECLAIM_ESTIMATE_SAMPLE_CHECKPOINTS: u64 = 20;
+
+/// Dry-run result of switching from one pruning configuration to another.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReclaimEstimate {
+    pub current_epoch: EpochId,
+    pub components: Vec<ComponentReclaimEstimate>,
+    pub total_estimated_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentReclaimEstimate {
+    pub component: PrunedComponent,
+    pub current_retention: Option<u64>,
+    pub proposed_retention: Option<u64>,
+    /// Inclusive range of checkpoints whose data would become prunable, if any.
+    pub checkpoints: Option<(CheckpointSequenceNumber, CheckpointSequenceNumber)>,
+    pub transactions: u64,
+    /// Extrapolated from the serialized size of a sample of checkpoints in the range; does not
+    /// account for compression or space amplification in the underlying store.
+    pub estimated_bytes: u64,
+}
+
 impl AuthorityStorePruner {
     /// prunes old versions of objects based on transaction effects
     #[cfg(not(tidehunter))]
@@ -307,7 +339,6 @@ impl AuthorityStorePruner {
         checkpoint_number: CheckpointSequenceNumber,
         checkpoints_to_prune: Vec<CheckpointDigest>,
         checkpoint_content_to_prune: Vec<CheckpointContents>,
-        effects_to_prune: &Vec<(CheckpointSequenceNumber, TransactionEffects)>,
         metrics: Arc<AuthorityStorePruningMetrics>,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("EffectsLivePruner");
@@ -325,17 +356,13 @@ impl AuthorityStorePruner {
             transactions.iter(),
         )?;
 
-        let mut effect_digests = vec![];
-        for (_checkpoint, effects) in effects_to_prune {
-            let effects_digest = effects.digest();
-            debug!("Pruning effects {:?}", effects_digest);
-            effect_digests.push(effects_digest);
-
-            if effects.events_digest().is_some() {
-                perpetual_batch
-                    .delete_batch(&perpetual_db.events_2, [effects.transaction_digest()])?;
-            }
-        }
+        // Keyed by the checkpoint contents rather than by the effects that can still be loaded:
+        // effects pruned ahead of their contents (see `component-retention`) would otherwise
+        // leave their events behind forever.
+        let effect_digests = checkpoint_content_to_prune
+            .iter()
+            .flat_map(|content| content.iter().map(|tx| tx.effects));
+        perpetual_batch.delete_batch(&perpetual_db.events_2, transactions.iter())?;
         perpetual_batch.delete_batch(
             &perpetual_db.unchanged_loaded_runtime_objects,
             transactions.iter(),
@@ -529,6 +556,364 @@ impl AuthorityStorePruner {
         Ok(())
     }
 
+    /// Components pruned ahead of checkpoint contents because their `component-retention` is
+    /// shorter. Everything else is pruned together with checkpoint contents.
+    #[cfg(not(tidehunter))]
+    fn separately_pruned_components(config: &AuthorityStorePruningConfig) -> Vec<PrunedComponent> {
+        let contents = config.retention(PrunedComponent::CheckpointContents);
+        [
+            PrunedComponent::Transactions,
+            PrunedComponent::Effects,
+            PrunedComponent::Events,
+        ]
+        .into_iter()
+        .filter(|component| {
+            config
+                .retention(*component)
+                .is_some_and(|epochs| contents.is_none_or(|contents| epochs < contents))
+        })
+        .collect()
+    }
+
+    fn component_watermark(component: PrunedComponent) -> anyhow::Result<CheckpointWatermark> {
+        match component {
+            PrunedComponent::Transactions => Ok(CheckpointWatermark::HighestPrunedTransactions),
+            PrunedComponent::Effects => Ok(CheckpointWatermark::HighestPrunedEffects),
+            PrunedComponent::Events => Ok(CheckpointWatermark::HighestPrunedEvents),
+            PrunedComponent::ObjectsHistory | PrunedComponent::CheckpointContents => Err(anyhow!(
+                "{} is not pruned separately from checkpoints",
+                component.as_str()
+            )),
+        }
+    }
+
+    /// Prunes transactions, effects and events whose `component-retention` is shorter than that
+    /// of checkpoint contents. Contents are left in place, since they are how the keys of the
+    /// remaining components are found once their own retention expires.
+    #[cfg(not(tidehunter))]
+    async fn prune_components_for_eligible_epochs(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        rpc_store: Option<&RpcStore>,
+        config: &AuthorityStorePruningConfig,
+        metrics: &Arc<AuthorityStorePruningMetrics>,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("PruneComponentsForEligibleEpochs");
+        let (mut max_eligible_checkpoint, current_epoch) = checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .map(|c| (*c.sequence_number(), c.epoch))
+            .unwrap_or_default();
+        // Object pruning reads effects, so effects must not go before the objects pruner is done
+        // with them.
+        if config.num_epochs_to_retain != u64::MAX {
+            max_eligible_checkpoint = min(
+                max_eligible_checkpoint,
+                perpetual_db
+                    .get_highest_pruned_checkpoint()?
+                    .unwrap_or_default(),
+            );
+        }
+        max_eligible_checkpoint = min(
+            max_eligible_checkpoint,
+            Self::rpc_store_max_eligible_checkpoint(rpc_store)?,
+        );
+        let contents_pruned = checkpoint_store
+            .get_highest_pruned_checkpoint_seq_number()?
+            .unwrap_or(0);
+
+        for component in Self::separately_pruned_components(config) {
+            let num_epochs_to_retain = config
+                .retention(component)
+                .ok_or_else(|| anyhow!("config value not set"))?;
+            let watermark = Self::component_watermark(component)?;
+            let mut checkpoint_number = max(
+                contents_pruned,
+                checkpoint_store
+                    .tables
+                    .watermarks
+                    .get(&watermark)?
+                    .map_or(0, |(seq, _)| seq),
+            );
+
+            let mut num_checkpoints = 0;
+            let mut transactions = vec![];
+            let mut effects = vec![];
+            while let Some(ckpt) = checkpoint_store
+                .tables
+                .certified_checkpoints
+                .get(&(checkpoint_number + 1))?
+            {
+                let checkpoint = ckpt.into_inner();
+                if (current_epoch < checkpoint.epoch() + num_epochs_to_retain)
+                    || (*checkpoint.sequence_number() >= max_eligible_checkpoint)
+                {
+                    break;
+                }
+                checkpoint_number = *checkpoint.sequence_number();
+                let content = checkpoint_store
+                    .get_checkpoint_contents(&checkpoint.content_digest)?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "checkpoint content data is missing: {}",
+                            checkpoint.sequence_number
+                        )
+                    })?;
+                transactions.extend(content.iter().map(|tx| tx.transaction));
+                effects.extend(content.iter().map(|tx| tx.effects));
+                num_checkpoints += 1;
+
+                if transactions.len() >= config.max_transactions_in_batch
+                    || num_checkpoints >= config.max_checkpoints_in_batch
+                {
+                    Self::prune_component(
+                        perpetual_db,
+                        checkpoint_store,
+                        component,
+                        checkpoint_number,
+                        std::mem::take(&mut transactions),
+                        std::mem::take(&mut effects),
+                        metrics,
+                    )?;
+                    num_checkpoints = 0;
+                    // yield back to the tokio runtime. Prevent potential halt of other tasks
+                    tokio::task::yield_now().await;
+                }
+            }
+
+            if num_checkpoints > 0 {
+                Self::prune_component(
+                    perpetual_db,
+                    checkpoint_store,
+                    component,
+                    checkpoint_number,
+                    transactions,
+                    effects,
+                    metrics,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(tidehunter))]
+    fn prune_component(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_db: &Arc<CheckpointStore>,
+        component: PrunedComponent,
+        checkpoint_number: CheckpointSequenceNumber,
+        transactions: Vec<TransactionDigest>,
+        effects: Vec<TransactionEffectsDigest>,
+        metrics: &Arc<AuthorityStorePruningMetrics>,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("ComponentLivePruner");
+        let watermark = Self::component_watermark(component)?;
+
+        let mut perpetual_batch = perpetual_db.objects.batch();
+        match component {
+            PrunedComponent::Transactions => {
+                perpetual_batch.delete_batch(&perpetual_db.transactions, transactions.iter())?;
+            }
+            PrunedComponent::Effects => {
+                perpetual_batch
+                    .delete_batch(&perpetual_db.executed_effects, transactions.iter())?;
+                perpetual_batch.delete_batch(
+                    &perpetual_db.unchanged_loaded_runtime_objects,
+                    transactions.iter(),
+                )?;
+                perpetual_batch.delete_batch(&perpetual_db.effects, effects.iter())?;
+            }
+            PrunedComponent::Events => {
+                perpetual_batch.delete_batch(&perpetual_db.events_2, transactions.iter())?;
+            }
+            PrunedComponent::ObjectsHistory | PrunedComponent::CheckpointContents => {}
+        }
+        perpetual_batch.write()?;
+
+        checkpoint_db
+            .tables
+            .watermarks
+            .insert(&watermark, &(checkpoint_number, CheckpointDigest::random()))?;
+        metrics
+            .last_pruned_component_checkpoint
+            .with_label_values(&[component.as_str()])
+            .set(checkpoint_number as i64);
+        Ok(())
+    }
+
+    /// Estimates how much data would become prunable if the pruner switched from `current` to
+    /// `proposed`, per component. Nothing is deleted.
+    ///
+    /// For each component this finds the checkpoints that are retained today but would be
+    /// eligible under `proposed`, and extrapolates their size from a sample of at most
+    /// `RECLAIM_ESTIMATE_SAMPLE_CHECKPOINTS` checkpoints.
+    pub fn estimate_reclaim(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        current: &AuthorityStorePruningConfig,
+        proposed: &AuthorityStorePruningConfig,
+    ) -> anyhow::Result<ReclaimEstimate> {
+        let current_epoch = checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .map(|c| c.epoch())
+            .unwrap_or_default();
+        // Last checkpoint that is eligible for pruning when retaining `retention` epochs.
+        let eligible_up_to = |retention: Option<u64>| -> anyhow::Result<Option<u64>> {
+            match retention {
+                Some(retention) if current_epoch >= retention => Ok(checkpoint_store
+                    .get_epoch_last_checkpoint_seq_number(current_epoch - retention)?),
+                _ => Ok(None),
+            }
+        };
+        let contents_pruned = checkpoint_store.get_highest_pruned_checkpoint_seq_number()?;
+
+        let mut components = vec![];
+        for component in PrunedComponent::ALL {
+            let current_retention = current.retention(component);
+            let proposed_retention = proposed.retention(component);
+            let already_pruned = match component {
+                PrunedComponent::ObjectsHistory => perpetual_db.get_highest_pruned_checkpoint()?,
+                PrunedComponent::CheckpointContents => contents_pruned,
+                _ => max(
+                    contents_pruned,
+                    checkpoint_store
+                        .tables
+                        .watermarks
+                        .get(&Self::component_watermark(component)?)?
+                        .map(|(seq, _)| seq),
+                ),
+            };
+            let retained_from =
+                max(eligible_up_to(current_retention)?, already_pruned).map_or(0, |seq| seq + 1);
+
+            let mut estimate = ComponentReclaimEstimate {
+                component,
+                current_retention,
+                proposed_retention,
+                checkpoints: None,
+                transactions: 0,
+                estimated_bytes: 0,
+            };
+            if let Some(last) = eligible_up_to(proposed_retention)?
+                && last >= retained_from
+            {
+                estimate.checkpoints = Some((retained_from, last));
+                estimate.transactions =
+                    Self::transactions_between(checkpoint_store, retained_from, last)?;
+                estimate.estimated_bytes = Self::estimate_component_bytes(
+                    perpetual_db,
+                    checkpoint_store,
+                    component,
+                    retained_from,
+                    last,
+                    estimate.transactions,
+                )?;
+            }
+            components.push(estimate);
+        }
+
+        Ok(ReclaimEstimate {
+            current_epoch,
+            total_estimated_bytes: components.iter().map(|c| c.estimated_bytes).sum(),
+            components,
+        })
+    }
+
+    /// Number of transactions in the inclusive checkpoint range `first..=last`.
+    fn transactions_between(
+        checkpoint_store: &Arc<CheckpointStore>,
+        first: CheckpointSequenceNumber,
+        last: CheckpointSequenceNumber,
+    ) -> anyhow::Result<u64> {
+        let total_at = |seq| -> anyhow::Result<u64> {
+            Ok(checkpoint_store
+                .get_checkpoint_by_sequence_number(seq)?
+                .ok_or_else(|| anyhow!("checkpoint {seq} is missing"))?
+                .network_total_transactions)
+        };
+        let before = match first {
+            0 => 0,
+            first => total_at(first - 1)?,
+        };
+        Ok(total_at(last)?.saturating_sub(before))
+    }
+
+    fn estimate_component_bytes(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        component: PrunedComponent,
+        first: CheckpointSequenceNumber,
+        last: CheckpointSequenceNumber,
+        transactions: u64,
+    ) -> anyhow::Result<u64> {
+        let range = last - first + 1;
+        let samples = min(range, RECLAIM_ESTIMATE_SAMPLE_CHECKPOINTS);
+        let mut sampled_transactions = 0u64;
+        let mut sampled_bytes = 0u64;
+        for i in 0..samples {
+            let seq = first + i * range / samples;
+            let Some(checkpoint) = checkpoint_store.get_checkpoint_by_sequence_number(seq)? else {
+                continue;
+            };
+            let Some(content) =
+                checkpoint_store.get_checkpoint_contents(&checkpoint.content_digest)?
+            else {
+                continue;
+            };
+            sampled_transactions += content.size() as u64;
+            let tx_digests: Vec<_> = content.iter().map(|tx| tx.transaction).collect();
+            let bytes = match component {
+                PrunedComponent::Transactions => perpetual_db
+                    .transactions
+                    .multi_get(&tx_digests)?
+                    .iter()
+                    .flatten()
+                    .map(bcs::serialized_size)
+                    .sum::<Result<usize, _>>()?,
+                PrunedComponent::Effects => perpetual_db
+                    .effects
+                    .multi_get(content.iter().map(|tx| tx.effects))?
+                    .iter()
+                    .flatten()
+                    .map(bcs::serialized_size)
+                    .sum::<Result<usize, _>>()?,
+                PrunedComponent::Events => perpetual_db
+                    .events_2
+                    .multi_get(&tx_digests)?
+                    .iter()
+                    .flatten()
+                    .map(bcs::serialized_size)
+                    .sum::<Result<usize, _>>()?,
+                PrunedComponent::ObjectsHistory => {
+                    // Object pruning deletes the versions each transaction superseded.
+                    let old_versions: Vec<_> = perpetual_db
+                        .effects
+                        .multi_get(content.iter().map(|tx| tx.effects))?
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|effects| effects.modified_at_versions())
+                        .map(|(id, version)| ObjectKey(id, version))
+                        .collect();
+                    perpetual_db
+                        .objects
+                        .multi_get(&old_versions)?
+                        .iter()
+                        .flatten()
+                        .map(bcs::serialized_size)
+                        .sum::<Result<usize, _>>()?
+                }
+                PrunedComponent::CheckpointContents => {
+                    bcs::serialized_size(&content)? + bcs::serialized_size(checkpoint.inner())?
+                }
+            };
+            sampled_bytes += bytes as u64;
+        }
+
+        if sampled_transactions == 0 {
+            return Ok(0);
+        }
+        Ok((sampled_bytes as u128 * transactions as u128 / sampled_transactions as u128) as u64)
+    }
+
     /// Prunes old object versions based on effects from all checkpoints from epochs eligible for pruning
     pub async fn prune_for_eligible_epochs(
         perpetual_db: &Arc<AuthorityPerpetualTables>,
@@ -555,6 +940,7 @@ impl AuthorityStorePruner {
         // embedded rpc-store's `object_version_by_checkpoint` retraction can
         // keep each object's anchor at its true supersession checkpoint.
         let mut effects_to_prune: Vec<(CheckpointSequenceNumber, TransactionEffects)> = vec![];
+        let mut num_transactions = 0;
         // Absolute tx-seq floor (exclusive) after pruning the current
         // batch — the last-pruned checkpoint's `network_total_transactions`.
         // The embedded rpc-store's history-cohort prune consumes this
@@ -587,21 +973,26 @@ impl AuthorityStorePruner {
                         checkpoint.sequence_number
                     )
                 })?;
-            let effects = perpetual_db
-                .effects
-                .multi_get(content.iter().map(|tx| tx.effects))?;
+            // Only the objects pruner needs the effects themselves. Checkpoint pruning deletes
+            // everything keyed by the contents, including effects that are already gone.
+            if let PruningMode::Objects = mode {
+                let effects = perpetual_db
+                    .effects
+                    .multi_get(content.iter().map(|tx| tx.effects))?;
+                effects_to_prune.extend(
+                    effects
+                        .into_iter()
+                        .flatten()
+                        .map(|effects| (checkpoint_number, effects)),
+                );
+            }
 
             info!("scheduling pruning for checkpoint {:?}", checkpoint_number);
+            num_transactions += content.size();
             checkpoints_to_prune.push(*checkpoint.digest());
             checkpoint_content_to_prune.push(content);
-            effects_to_prune.extend(
-                effects
-                    .into_iter()
-                    .flatten()
-                    .map(|effects| (checkpoint_number, effects)),
-            );
 
-            if effects_to_prune.len() >= config.max_transactions_in_batch
+            if num_transactions >= config.max_transactions_in_batch
                 || checkpoints_to_prune.len() >= config.max_checkpoints_in_batch
             {
                 match mode {
@@ -623,13 +1014,13 @@ impl AuthorityStorePruner {
                         checkpoint_number,
                         checkpoints_to_prune,
                         checkpoint_content_to_prune,
-                        &effects_to_prune,
                         metrics.clone(),
                     )?,
                 };
                 checkpoints_to_prune = vec![];
                 checkpoint_content_to_prune = vec![];
                 effects_to_prune = vec![];
+                num_transactions = 0;
                 // yield back to the tokio runtime. Prevent potential halt of other tasks
                 tokio::task::yield_now().await;
             }
@@ -655,7 +1046,6 @@ impl AuthorityStorePruner {
                     checkpoint_number,
                     checkpoints_to_prune,
                     checkpoint_content_to_prune,
-                    &effects_to_prune,
                     metrics.clone(),
                 )?,
             };
@@ -980,6 +1370,9 @@ impl AuthorityStorePruner {
         {
             let mut indexes_prune_interval =
                 tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
+            let mut components_prune_interval =
+                tokio::time::interval_at(Instant::now() + pruning_initial_delay, tick_duration);
+            let prune_components = !Self::separately_pruned_components(&config).is_empty();
 
             let perpetual_db_for_compaction = perpetual_db.clone();
             if let Some(delay_days) = config.periodic_compaction_threshold_days {
@@ -1026,6 +1419,11 @@ impl AuthorityStorePruner {
                                 error!("Failed to prune indexes: {:?}", err);
                             }
                         }
+                        _ = components_prune_interval.tick(), if prune_components => {
+                            if let Err(err) = Self::prune_components_for_eligible_epochs(&perpetual_db, &checkpoint_store, rpc_store.as_ref(), &config, &metrics).await {
+                                error!("Failed to prune components: {:?}", err);
+                            }
+                        }
                         _ = &mut recv => break,
                     }
                 }
@@ -1045,6 +1443,7 @@ impl AuthorityStorePruner {
         registry: &Registry,
         pruner_watermarks: Arc<PrunerWatermarks>, // used by tidehunter relocation filters
     ) -> Self {
+        pruning_config.resolve_component_retention();
         // On tidehunter, the per-keyspace `objects_compactor`
         // (see `AuthorityPerpetualTables::open`) already retains only the latest
         // version per ObjectID during compaction, so running the object pruner
@@ -1140,14 +1539,30 @@ mod tests {
     use tracing::log::info;
 
     use crate::authority::authority_store_pruner::AuthorityStorePruningMetrics;
+    #[cfg(not(tidehunter))]
+    use crate::authority::authority_store_pruner::PrunerWatermarks;
     use crate::authority::authority_store_tables::AuthorityPerpetualTables;
     use crate::authority::authority_store_types::get_store_object;
     #[cfg(not(tidehunter))]
     use crate::authority::authority_store_types::{StoreObject, StoreObjectWrapper};
+    #[cfg(not(tidehunter))]
+    use crate::checkpoints::CheckpointStore;
     use prometheus::Registry;
+    #[cfg(not(tidehunter))]
+    use sui_config::node::{
+        AuthorityStorePruningConfig, ComponentRetentionConfig, PrunedComponent,
+    };
     use sui_types::base_types::ObjectDigest;
     use sui_types::effects::TransactionEffects;
     use sui_types::effects::TransactionEffectsAPI;
+    #[cfg(not(tidehunter))]
+    use sui_types::{
+        base_types::ExecutionDigests,
+        event::Event,
+        message_envelope::Message,
+        messages_checkpoint::VerifiedCheckpoint,
+        test_checkpoint_data_builder::{AdvanceEpochConfig, TestCheckpointBuilder},
+    };
     use sui_types::{
         base_types::{ObjectID, SequenceNumber},
         object::Object,
@@ -1462,4 +1877,154 @@ mod tests {
         ma::assert_le!(after_compaction_size, before_compaction_size);
         Ok(())
     }
+
+    /// Stores one checkpoint with a single event-emitting transaction in each of `num_epochs`
+    /// epochs, each followed by the epoch's last checkpoint (which emits events too), and marks
+    /// them all executed.
+    /// Returns the transactions of every checkpoint, indexed by sequence number.
+    #[cfg(not(tidehunter))]
+    fn populate_checkpoints(
+        perpetual_db: &AuthorityPerpetualTables,
+        checkpoint_store: &CheckpointStore,
+        num_epochs: u64,
+    ) -> Vec<Vec<ExecutionDigests>> {
+        let mut builder = TestCheckpointBuilder::new(0);
+        let mut checkpoints = vec![builder.build_checkpoint()];
+        for _ in 0..num_epochs {
+            builder = builder
+                .start_transaction(0)
+                .create_owned_object(0)
+                .with_events(vec![Event::random_for_testing()])
+                .finish_transaction();
+            checkpoints.push(builder.build_checkpoint());
+            checkpoints.push(builder.advance_epoch(AdvanceEpochConfig::default()));
+        }
+
+        let mut digests = vec![];
+        for checkpoint in checkpoints {
+            for tx in &checkpoint.transactions {
+                let tx_digest = *tx.effects.transaction_digest();
+                perpetual_db
+                    .effects
+                    .insert(&tx.effects.digest(), &tx.effects)
+                    .unwrap();
+                perpetual_db
+                    .executed_effects
+                    .insert(&tx_digest, &tx.effects.digest())
+                    .unwrap();
+                if let Some(events) = &tx.events {
+                    perpetual_db.events_2.insert(&tx_digest, events).unwrap();
+                }
+            }
+            digests.push(checkpoint.contents.iter().copied().collect());
+            let summary = VerifiedCheckpoint::new_unchecked(checkpoint.summary);
+            checkpoint_store
+                .insert_certified_checkpoint(&summary)
+                .unwrap();
+            checkpoint_store
+                .insert_checkpoint_contents(checkpoint.contents)
+                .unwrap();
+            checkpoint_store
+                .update_highest_executed_checkpoint(&summary)
+                .unwrap();
+        }
+        digests
+    }
+
+    /// Runs the component pruner and then the checkpoint pruner over five epochs of checkpoints
+    /// with the given retention, and checks that each component is gone exactly for the epochs
+    /// its retention has expired.
+    #[cfg(not(tidehunter))]
+    async fn run_component_pruning(retention: ComponentRetentionConfig) {
+        let path = tempfile::tempdir().unwrap().keep();
+        let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path, None, None));
+        let checkpoint_store = CheckpointStore::new_for_tests();
+        let checkpoints = populate_checkpoints(&perpetual_db, &checkpoint_store, 5);
+
+        let mut config = AuthorityStorePruningConfig {
+            num_epochs_to_retain: u64::MAX,
+            smooth: false,
+            component_retention: Some(retention),
+            ..Default::default()
+        };
+        config.resolve_component_retention();
+        let effects_retention = config.retention(PrunedComponent::Effects).unwrap();
+        let events_retention = config.retention(PrunedComponent::Events).unwrap();
+        let metrics = AuthorityStorePruningMetrics::new(&Registry::default());
+
+        AuthorityStorePruner::prune_components_for_eligible_epochs(
+            &perpetual_db,
+            &checkpoint_store,
+            None,
+            &config,
+            &metrics,
+        )
+        .await
+        .unwrap();
+        AuthorityStorePruner::prune_checkpoints_for_eligible_epochs(
+            &perpetual_db,
+            &checkpoint_store,
+            None,
+            config,
+            metrics,
+            0,
+            &Arc::new(PrunerWatermarks::default()),
+        )
+        .await
+        .unwrap();
+
+        // The highest executed checkpoint is the last one of epoch 4. Checkpoint 0 is never
+        // pruned.
+        let pruned = |seq: usize, retention: u64| seq > 0 && (seq as u64 - 1) / 2 + retention <= 4;
+        for (seq, digests) in checkpoints.iter().enumerate() {
+            for tx in digests {
+                assert_eq!(
+                    perpetual_db.effects.contains_key(&tx.effects).unwrap(),
+                    !pruned(seq, effects_retention),
+                    "effects of checkpoint {seq}"
+                );
+                assert_eq!(
+                    perpetual_db
+                        .executed_effects
+                        .contains_key(&tx.transaction)
+                        .unwrap(),
+                    !pruned(seq, effects_retention),
+                    "executed effects of checkpoint {seq}"
+                );
+                assert_eq!(
+                    perpetual_db.events_2.contains_key(&tx.transaction).unwrap(),
+                    !pruned(seq, events_retention),
+                    "events of checkpoint {seq}"
+                );
+            }
+        }
+    }
+
+    // Effects are pruned ahead of their checkpoint contents; the events must still go with the
+    // contents even though the effects that point at them are already gone.
+    #[cfg(not(tidehunter))]
+    #[tokio::test]
+    async fn test_pruning_effects_before_checkpoints() {
+        run_component_pruning(ComponentRetentionConfig {
+            transactions: Some(2),
+            effects: Some(2),
+            checkpoint_contents: Some(3),
+            ..Default::default()
+        })
+        .await;
+    }
+
+    // Every component on its own schedule, each shorter than checkpoint contents.
+    #[cfg(not(tidehunter))]
+    #[tokio::test]
+    async fn test_pruning_all_components_before_checkpoints() {
+        run_component_pruning(ComponentRetentionConfig {
+            transactions: Some(2),
+            effects: Some(2),
+            events: Some(3),
+            checkpoint_contents: Some(4),
+            ..Default::default()
+        })
+        .await;
+    }
 }
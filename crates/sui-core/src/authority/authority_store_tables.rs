@@ -168,6 +168,10 @@ pub struct AuthorityPerpetualTables {
     /// Singleton: the highest generation this node has ever broadcast in an
     /// `UpdateTransactionDenyConfig` message.
     pub(crate) last_broadcast_deny_generation: DBMap<(), u64>,
+
+    /// Packages blocked (`true`) or unblocked (`false`) in consensus through the admin
+    /// interface. Applied on top of the node config's `consensus_blocked_packages` at start-up.
+    pub(crate) consensus_blocked_packages: DBMap<ObjectID, bool>,
 }
 
 impl AuthorityPerpetualTables {
@@ -431,6 +435,10 @@ impl AuthorityPerpetualTables {
                 "last_broadcast_deny_generation".to_string(),
                 ThConfig::new(0, 1, KeyType::uniform(1)),
             ),
+            (
+                "consensus_blocked_packages".to_string(),
+                ThConfig::new(32, 1, KeyType::uniform(1)),
+            ),
         ];
         Self::open_tables_read_write(
             Self::path(parent_path),
@@ -596,6 +604,10 @@ impl AuthorityPerpetualTables {
         Ok(None)
     }
 
+    pub fn get_epoch_start_configuration(&self) -> SuiResult<Option<EpochStartConfiguration>> {
+        Ok(self.epoch_start_configuration.get(&())?)
+    }
+
     pub fn get_recovery_epoch_at_restart(&self) -> SuiResult<EpochId> {
         Ok(self
             .epoch_start_configuration
@@ -4,6 +4,7 @@
 use crate::authority::authority_per_epoch_store::{
     AuthorityEpochTables, EncG, ExecutionIndicesWithStatsV2, LockDetails, LockDetailsWrapper, PkG,
 };
+use crate::authority::epoch_store_faults::{self, EpochStoreWrite};
 use crate::authority::transaction_deferral::DeferralKey;
 use crate::checkpoints::BuilderCheckpointSummary;
 use crate::epoch::randomness::SINGLETON_KEY;
@@ -585,6 +586,7 @@ impl ConsensusOutputQuarantine {
     }
 
     pub(super) fn commit(&mut self, epoch_store: &AuthorityPerEpochStore) -> SuiResult {
+        epoch_store_faults::check_db_write(&epoch_store.name, EpochStoreWrite::QuarantineCommit)?;
         let mut batch = epoch_store.db_batch()?;
         self.commit_with_batch(epoch_store, &mut batch)?;
         batch.write()?;
@@ -603,6 +605,10 @@ impl ConsensusOutputQuarantine {
         //    checkpoint.
         // 3. Commit all consensus output at that height or below.
 
+        if epoch_store_faults::quarantine_commit_delayed(&epoch_store.name) {
+            return Ok(());
+        }
+
         let tables = epoch_store.tables()?;
 
         let mut highest_committed_height = None;
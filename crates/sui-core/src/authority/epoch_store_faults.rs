@@ -0,0 +1,287 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fault injection for [`AuthorityPerEpochStore`], so that crash-recovery paths such as
+//! quarantine replay can be exercised deterministically from tests and simtests.
+//!
+//! Faults are armed on an [`EpochStoreFaultInjector`] and reach the epoch store through fail
+//! points, so they only take effect in builds with fail points enabled (msim, or
+//! `--cfg fail_points`). Elsewhere the hooks compile to nothing.
+//!
+//! [`AuthorityPerEpochStore`]: super::authority_per_epoch_store::AuthorityPerEpochStore
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use sui_macros::{clear_fail_point, fail_point_arg, register_fail_point_arg};
+use sui_types::base_types::AuthorityName;
+use sui_types::error::{SuiErrorKind, SuiResult};
+
+const FAULTS_FAIL_POINT: &str = "epoch_store_faults";
+
+/// The DB writes of the epoch store that can be made to fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EpochStoreWrite {
+    /// Committing consensus output from the quarantine to the DB.
+    QuarantineCommit,
+    /// Recording a finalized checkpoint, which also advances the quarantine watermark.
+    FinalizedCheckpoint,
+}
+
+/// The notifications of the epoch store that can be dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EpochStoreNotification {
+    /// A consensus message was processed (`consensus_messages_processed_notify`).
+    ConsensusMessageProcessed,
+    /// A transaction key was resolved to a digest (`notify_read_tx_key_to_digest`).
+    TransactionKeyExecuted,
+}
+
+/// Counts of the faults that were actually injected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InjectedFaults {
+    pub failed_writes: HashMap<EpochStoreWrite, usize>,
+    pub delayed_quarantine_commits: usize,
+    pub dropped_notifications: HashMap<EpochStoreNotification, usize>,
+}
+
+#[derive(Default)]
+struct FaultState {
+    /// If set, faults are only injected into the epoch store of this authority.
+    authority: Option<AuthorityName>,
+    /// Number of upcoming writes of each kind that will fail.
+    failing_writes: HashMap<EpochStoreWrite, usize>,
+    quarantine_commits_delayed: bool,
+    /// Number of upcoming notifications of each kind that will be dropped.
+    dropped_notifications: HashMap<EpochStoreNotification, usize>,
+    injected: InjectedFaults,
+}
+
+#[cfg_attr(not(any(msim, fail_points, test)), allow(dead_code))]
+impl FaultState {
+    fn applies_to(&self, authority: &AuthorityName) -> bool {
+        self.authority.is_none_or(|a| a == *authority)
+    }
+
+    /// Uses up an armed failure of `write`, if any.
+    fn take_failing_write(&mut self, write: EpochStoreWrite) -> bool {
+        let Some(remaining) = self.failing_writes.get_mut(&write).filter(|r| **r > 0) else {
+            return false;
+        };
+        *remaining -= 1;
+        *self.injected.failed_writes.entry(write).or_default() += 1;
+        true
+    }
+
+    fn quarantine_commit_delayed(&mut self) -> bool {
+        if self.quarantine_commits_delayed {
+            self.injected.delayed_quarantine_commits += 1;
+        }
+        self.quarantine_commits_delayed
+    }
+
+    /// Uses up an armed drop of `notification`, if any.
+    fn take_dropped_notification(&mut self, notification: EpochStoreNotification) -> bool {
+        let Some(remaining) = self
+            .dropped_notifications
+            .get_mut(&notification)
+            .filter(|r| **r > 0)
+        else {
+            return false;
+        };
+        *remaining -= 1;
+        *self
+            .injected
+            .dropped_notifications
+            .entry(notification)
+            .or_default() += 1;
+        true
+    }
+}
+
+/// Arms faults in epoch stores. Faults stay armed until they are used up, disarmed, or the
+/// injector is dropped. Only one injector may exist at a time.
+pub struct EpochStoreFaultInjector {
+    state: Arc<Mutex<FaultState>>,
+}
+
+impl EpochStoreFaultInjector {
+    /// Creates an injector whose faults apply to the epoch stores of all authorities.
+    pub fn new() -> Self {
+        Self::new_impl(None)
+    }
+
+    /// Creates an injector whose faults only apply to the epoch store of `authority`.
+    pub fn for_authority(authority: AuthorityName) -> Self {
+        Self::new_impl(Some(authority))
+    }
+
+    fn new_impl(authority: Option<AuthorityName>) -> Self {
+        let state = Arc::new(Mutex::new(FaultState {
+            authority,
+            ..Default::default()
+        }));
+        let registered = state.clone();
+        register_fail_point_arg(FAULTS_FAIL_POINT, move || Some(registered.clone()));
+        Self { state }
+    }
+
+    /// Makes the next `count` writes of kind `write` fail with a storage error, without writing
+    /// anything.
+    pub fn fail_db_writes(&self, write: EpochStoreWrite, count: usize) {
+        *self.state.lock().failing_writes.entry(write).or_default() += count;
+    }
+
+    /// Holds consensus output in the quarantine instead of committing it to the DB, even once the
+    /// checkpoints covering it are executed, until [`Self::release_quarantine_commits`] is called.
+    /// A crash in the meantime leaves the output to be replayed from consensus on restart.
+    pub fn delay_quarantine_commits(&self) {
+        self.state.lock().quarantine_commits_delayed = true;
+    }
+
+    /// Lets the quarantine commit again. Held back output is committed the next time the
+    /// quarantine commits.
+    pub fn release_quarantine_commits(&self) {
+        self.state.lock().quarantine_commits_delayed = false;
+    }
+
+    /// Drops the next `count` notifications of kind `notification`, leaving their waiters to be
+    /// woken by another path (or not at all).
+    pub fn drop_notifications(&self, notification: EpochStoreNotification, count: usize) {
+        *self
+            .state
+            .lock()
+            .dropped_notifications
+            .entry(notification)
+            .or_default() += count;
+    }
+
+    /// Disarms all faults that have not been used up yet.
+    pub fn disarm(&self) {
+        let mut state = self.state.lock();
+        state.failing_writes.clear();
+        state.quarantine_commits_delayed = false;
+        state.dropped_notifications.clear();
+    }
+
+    /// The faults injected so far.
+    pub fn injected(&self) -> InjectedFaults {
+        self.state.lock().injected.clone()
+    }
+}
+
+impl Default for EpochStoreFaultInjector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EpochStoreFaultInjector {
+    fn drop(&mut self) {
+        clear_fail_point(FAULTS_FAIL_POINT);
+    }
+}
+
+/// Runs `f` on the armed fault state if an injector exists and applies to `authority`.
+#[allow(unused_variables)]
+fn with_faults<T: Default>(authority: &AuthorityName, f: impl FnOnce(&mut FaultState) -> T) -> T {
+    #[allow(unused_mut)]
+    let mut result = T::default();
+    fail_point_arg!(FAULTS_FAIL_POINT, |state: Arc<Mutex<FaultState>>| {
+        let mut state = state.lock();
+        if state.applies_to(authority) {
+            result = f(&mut state);
+        }
+    });
+    result
+}
+
+/// Called before a DB write of kind `write`; fails if a write failure is armed.
+pub(crate) fn check_db_write(authority: &AuthorityName, write: EpochStoreWrite) -> SuiResult {
+    let fail = with_faults(authority, |state| state.take_failing_write(write));
+    if fail {
+        return Err(SuiErrorKind::Storage(format!(
+            "injected failure of epoch store write {write:?}"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Whether committing quarantined consensus output should be held back.
+pub(crate) fn quarantine_commit_delayed(authority: &AuthorityName) -> bool {
+    with_faults(authority, FaultState::quarantine_commit_delayed)
+}
+
+/// Whether a notification of kind `notification` should be dropped.
+pub(crate) fn drop_notification(
+    authority: &AuthorityName,
+    notification: EpochStoreNotification,
+) -> bool {
+    with_faults(authority, |state| {
+        state.take_dropped_notification(notification)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::crypto::{AuthorityKeyPair, KeypairTraits, get_key_pair};
+
+    fn authority() -> AuthorityName {
+        let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+        key.public().into()
+    }
+
+    #[test]
+    fn test_faults_apply_to_their_authority() {
+        let (a, b) = (authority(), authority());
+        assert!(FaultState::default().applies_to(&a));
+        let state = FaultState {
+            authority: Some(a),
+            ..Default::default()
+        };
+        assert!(state.applies_to(&a));
+        assert!(!state.applies_to(&b));
+    }
+
+    #[test]
+    fn test_armed_faults_are_used_up_and_counted() {
+        let mut state = FaultState::default();
+        state
+            .failing_writes
+            .insert(EpochStoreWrite::QuarantineCommit, 2);
+        state
+            .dropped_notifications
+            .insert(EpochStoreNotification::ConsensusMessageProcessed, 1);
+
+        assert!(state.take_failing_write(EpochStoreWrite::QuarantineCommit));
+        assert!(state.take_failing_write(EpochStoreWrite::QuarantineCommit));
+        assert!(!state.take_failing_write(EpochStoreWrite::QuarantineCommit));
+        assert!(!state.take_failing_write(EpochStoreWrite::FinalizedCheckpoint));
+
+        assert!(!state.take_dropped_notification(EpochStoreNotification::TransactionKeyExecuted));
+        assert!(state.take_dropped_notification(EpochStoreNotification::ConsensusMessageProcessed));
+        assert!(
+            !state.take_dropped_notification(EpochStoreNotification::ConsensusMessageProcessed)
+        );
+
+        assert!(!state.quarantine_commit_delayed());
+        state.quarantine_commits_delayed = true;
+        assert!(state.quarantine_commit_delayed());
+        assert!(state.quarantine_commit_delayed());
+
+        assert_eq!(
+            state.injected,
+            InjectedFaults {
+                failed_writes: HashMap::from([(EpochStoreWrite::QuarantineCommit, 2)]),
+                delayed_quarantine_commits: 2,
+                dropped_notifications: HashMap::from([(
+                    EpochStoreNotification::ConsensusMessageProcessed,
+                    1
+                )]),
+            }
+        );
+    }
+}
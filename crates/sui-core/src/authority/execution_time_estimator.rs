@@ -12,6 +12,7 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use super::authority_per_epoch_store::AuthorityPerEpochStore;
+use super::execution_time_observation_export::load_imported_execution_time_observations;
 use super::weighted_moving_average::WeightedMovingAverage;
 use crate::consensus_adapter::SubmitToConsensus;
 use governor::{Quota, RateLimiter, clock::MonotonicClock};
@@ -197,6 +198,10 @@ impl ExecutionTimeObserver {
         epoch_store.set_local_execution_time_channels(tx_local_execution_time, tx_object_debts);
 
         // TODO: pre-populate local observations with stored data from prior epoch.
+        let imported_observations = load_imported_execution_time_observations(
+            &config.imported_observations,
+            epoch_store.committee(),
+        );
         let mut observer = Self {
             epoch_store: Arc::downgrade(&epoch_store),
             consensus_adapter,
@@ -217,6 +222,7 @@ impl ExecutionTimeObserver {
                 .try_into()
                 .expect("This build of sui is not supported in the year 500,000"),
         };
+        observer.seed_local_observations(imported_observations, epoch_store.reference_gas_price());
         spawn_monitored_task!(epoch_store.within_alive_epoch(async move {
             loop {
                 tokio::select! {
@@ -278,6 +284,35 @@ impl ExecutionTimeObserver {
     }
 
     // Used by execution to report observed per-entry-point execution times to the estimator.
+    /// Pre-populate local observations with estimates imported from other validators. Each
+    /// estimate is added as `imported_observation_sample_count` samples, so that local
+    /// measurements gradually take over as they arrive.
+    fn seed_local_observations(
+        &mut self,
+        observations: HashMap<ExecutionTimeObservationKey, Duration>,
+        gas_price: u64,
+    ) {
+        if observations.is_empty() {
+            return;
+        }
+        let sample_count = self.config.imported_observation_sample_count().get();
+        for (key, duration) in observations {
+            if matches!(key, ExecutionTimeObservationKey::Publish) {
+                continue;
+            }
+            let local_observation = self.local_observations.get_or_insert_mut(key.clone(), || {
+                LocalObservations::new(self.config.clone(), Duration::ZERO)
+            });
+            for _ in 0..sample_count {
+                local_observation.add_sample(duration, gas_price);
+            }
+        }
+        info!(
+            "seeded {} local execution time observations from imported files",
+            self.local_observations.len()
+        );
+    }
+
     // Updates moving averages and submits observation to consensus if local observation differs
     // from consensus median.
     // TODO: Consider more detailed heuristic to account for overhead outside of commands.
@@ -869,6 +904,24 @@ impl ExecutionTimeEstimator {
         )
     }
 
+    /// Returns the latest observation received from `source` for each key.
+    pub fn get_observations_from(
+        &self,
+        source: AuthorityIndex,
+    ) -> Vec<(ExecutionTimeObservationKey, Duration)> {
+        let source: usize = source.try_into().unwrap();
+        self.consensus_observations
+            .iter()
+            .filter_map(|(key, observations)| {
+                observations
+                    .observations
+                    .get(source)
+                    .and_then(|(_, duration)| *duration)
+                    .map(|duration| (key.clone(), duration))
+            })
+            .collect()
+    }
+
     pub fn get_observations(&self) -> Vec<(ExecutionTimeObservationKey, ConsensusObservations)> {
         self.consensus_observations
             .iter()
@@ -0,0 +1,236 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signed files of execution time observations, used to share a validator's observations with
+//! operators of other (typically newly joining) validators.
+//!
+//! A validator exports the observations it has shared through consensus, signed with its
+//! protocol key. Importing validators verify the signature, merge the observations from all
+//! configured files by weight, and use the result to pre-populate their local observations.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use fastcrypto::hash::HashFunction as _;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
+use sui_config::node::ImportedExecutionTimeObservations;
+use sui_types::base_types::ConciseableName;
+use sui_types::committee::Committee;
+use sui_types::crypto::{AuthoritySignInfo, DefaultHash, SuiAuthoritySignature};
+use sui_types::digests::Digest;
+use sui_types::error::{SuiErrorKind, SuiResult};
+use sui_types::execution::ExecutionTimeObservationKey;
+use sui_types::message_envelope::{Envelope, Message};
+use tracing::{info, warn};
+
+use crate::authority::AuthorityState;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionTimeObservations {
+    pub observations: Vec<(ExecutionTimeObservationKey, Duration)>,
+}
+
+impl Message for ExecutionTimeObservations {
+    type DigestType = Digest;
+    const SCOPE: IntentScope = IntentScope::ExecutionTimeObservations;
+
+    fn digest(&self) -> Self::DigestType {
+        let mut hash = DefaultHash::new();
+        bcs::serialize_into(&mut hash, self).unwrap();
+        Digest::new(hash.finalize().into())
+    }
+}
+
+pub type SignedExecutionTimeObservations = Envelope<ExecutionTimeObservations, AuthoritySignInfo>;
+
+/// Verify that `signed` was signed by the authority it names. The signer need not be a member
+/// of the current committee.
+pub fn verify_execution_time_observations(signed: &SignedExecutionTimeObservations) -> SuiResult {
+    let sig = signed.auth_sig();
+    sig.signature.verify_secure(
+        &IntentMessage::new(
+            Intent::sui_app(ExecutionTimeObservations::SCOPE),
+            signed.data(),
+        ),
+        sig.epoch,
+        sig.authority,
+    )
+}
+
+pub fn read_execution_time_observations(
+    path: &Path,
+) -> anyhow::Result<SignedExecutionTimeObservations> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("failed to read execution time observations {path:?}"))?;
+    let signed: SignedExecutionTimeObservations = bcs::from_bytes(&bytes)
+        .with_context(|| format!("failed to parse execution time observations {path:?}"))?;
+    verify_execution_time_observations(&signed)
+        .with_context(|| format!("invalid signature on execution time observations {path:?}"))?;
+    Ok(signed)
+}
+
+/// Sign this validator's current consensus observations and write them to `path`. Returns the
+/// number of exported observations.
+pub async fn export_execution_time_observations(
+    state: &AuthorityState,
+    path: &Path,
+) -> SuiResult<usize> {
+    let epoch_store = state.load_epoch_store_one_call_per_task();
+    if !epoch_store.committee().authority_exists(&state.name) {
+        return Err(SuiErrorKind::UnsupportedFeatureError {
+            error:
+                "only validators in the current committee can export execution time observations"
+                    .to_string(),
+        }
+        .into());
+    }
+
+    let mut observations = epoch_store.get_own_execution_time_observations().await;
+    observations.sort();
+    let count = observations.len();
+    let signed = SignedExecutionTimeObservations::new(
+        epoch_store.epoch(),
+        ExecutionTimeObservations { observations },
+        &*state.secret,
+        state.name,
+    );
+
+    let bytes = bcs::to_bytes(&signed).expect("serialization should not fail");
+    std::fs::write(path, bytes).map_err(|e| {
+        SuiErrorKind::FileIOError(format!(
+            "failed to write execution time observations to {path:?}: {e}"
+        ))
+    })?;
+    info!(
+        epoch = epoch_store.epoch(),
+        count, "exported execution time observations to {path:?}"
+    );
+    Ok(count)
+}
+
+/// Load the configured observation files and merge them into a single estimate per key, taking
+/// the weighted mean across files. Files that cannot be read, fail verification, or were not
+/// signed by a member of `committee` are skipped with a warning.
+pub fn load_imported_execution_time_observations(
+    imports: &[ImportedExecutionTimeObservations],
+    committee: &Committee,
+) -> HashMap<ExecutionTimeObservationKey, Duration> {
+    let mut sets = Vec::with_capacity(imports.len());
+    for import in imports {
+        let weight = import.weight();
+        if !weight.is_finite() || weight <= 0.0 {
+            warn!(
+                "ignoring execution time observations {:?} with non-positive weight {weight}",
+                import.path
+            );
+            continue;
+        }
+        let signed = match read_execution_time_observations(&import.path) {
+            Ok(signed) => signed,
+            Err(e) => {
+                warn!("ignoring execution time observations: {e:#}");
+                continue;
+            }
+        };
+        let signer = signed.auth_sig().authority;
+        if !committee.authority_exists(&signer) {
+            warn!(
+                "ignoring execution time observations {:?} signed by {} which is not in the committee",
+                import.path,
+                signer.concise()
+            );
+            continue;
+        }
+        info!(
+            "imported {} execution time observations from {} (epoch {})",
+            signed.data().observations.len(),
+            signer.concise(),
+            signed.epoch(),
+        );
+        sets.push((weight, signed.into_data()));
+    }
+    merge_execution_time_observations(&sets)
+}
+
+fn merge_execution_time_observations(
+    sets: &[(f64, ExecutionTimeObservations)],
+) -> HashMap<ExecutionTimeObservationKey, Duration> {
+    let mut sums: HashMap<ExecutionTimeObservationKey, (f64, f64)> = HashMap::new();
+    for (weight, set) in sets {
+        for (key, duration) in &set.observations {
+            let (weighted_sum, total_weight) = sums.entry(key.clone()).or_default();
+            *weighted_sum += duration.as_secs_f64() * weight;
+            *total_weight += weight;
+        }
+    }
+    sums.into_iter()
+        .map(|(key, (weighted_sum, total_weight))| {
+            (key, Duration::from_secs_f64(weighted_sum / total_weight))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::ObjectID;
+    use sui_types::crypto::{AuthorityKeyPair, KeypairTraits, get_key_pair};
+
+    fn move_key(function: &str) -> ExecutionTimeObservationKey {
+        ExecutionTimeObservationKey::MoveEntryPoint {
+            package: ObjectID::from_single_byte(2),
+            module: "m".to_string(),
+            function: function.to_string(),
+            type_arguments: vec![],
+        }
+    }
+
+    fn signed(
+        keypair: &AuthorityKeyPair,
+        observations: Vec<(ExecutionTimeObservationKey, Duration)>,
+    ) -> SignedExecutionTimeObservations {
+        SignedExecutionTimeObservations::new(
+            7,
+            ExecutionTimeObservations { observations },
+            keypair,
+            keypair.public().into(),
+        )
+    }
+
+    #[test]
+    fn signed_file_round_trip() {
+        let (_, keypair): (_, AuthorityKeyPair) = get_key_pair();
+        let signed = signed(&keypair, vec![(move_key("f"), Duration::from_millis(3))]);
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), bcs::to_bytes(&signed).unwrap()).unwrap();
+        let read = read_execution_time_observations(file.path()).unwrap();
+        assert_eq!(read.data(), signed.data());
+        assert_eq!(read.epoch(), 7);
+
+        let mut tampered = signed;
+        tampered.data_mut_for_testing().observations[0].1 = Duration::from_millis(1);
+        assert!(verify_execution_time_observations(&tampered).is_err());
+        assert_ne!(tampered.data().digest(), read.data().digest());
+    }
+
+    #[test]
+    fn merge_takes_weighted_mean() {
+        let a = ExecutionTimeObservations {
+            observations: vec![
+                (move_key("f"), Duration::from_millis(10)),
+                (move_key("g"), Duration::from_millis(4)),
+            ],
+        };
+        let b = ExecutionTimeObservations {
+            observations: vec![(move_key("f"), Duration::from_millis(40))],
+        };
+
+        let merged = merge_execution_time_observations(&[(2.0, a), (1.0, b)]);
+        assert_eq!(merged[&move_key("f")], Duration::from_millis(20));
+        assert_eq!(merged[&move_key("g")], Duration::from_millis(4));
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read-only access to the tables of recent previous epochs.
+//!
+//! Once an epoch ends, its `AuthorityPerEpochStore` releases its tables, and any request still
+//! holding that store fails with `EpochEnded`. Requests for effects signatures commonly straddle
+//! reconfiguration, and the signatures stored in the ended epoch are still valid, so they are
+//! served from a read-only handle to that epoch's database instead, for a bounded number of
+//! previous epochs.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use sui_types::base_types::EpochId;
+use sui_types::crypto::AuthoritySignInfo;
+use sui_types::digests::TransactionDigest;
+use sui_types::error::SuiResult;
+use tracing::debug;
+use typed_store::Map;
+
+use crate::authority::authority_per_epoch_store::AuthorityEpochTables;
+#[cfg(not(tidehunter))]
+use crate::authority::authority_per_epoch_store::AuthorityEpochTablesReadOnly;
+
+#[cfg(not(tidehunter))]
+type ReadOnlyEpochTables = AuthorityEpochTablesReadOnly;
+#[cfg(tidehunter)]
+type ReadOnlyEpochTables = AuthorityEpochTables;
+
+pub struct PreviousEpochTables {
+    parent_path: PathBuf,
+    /// How many epochs before the current one can be read.
+    num_epochs: u64,
+    tables: Mutex<BTreeMap<EpochId, Arc<ReadOnlyEpochTables>>>,
+}
+
+impl PreviousEpochTables {
+    pub fn new(parent_path: PathBuf, num_epochs: u64) -> Self {
+        Self {
+            parent_path,
+            num_epochs,
+            tables: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The signature over the effects of `tx_digest` stored in `epoch`, if `epoch` is one of the
+    /// readable epochs before `current_epoch` and a signature was stored.
+    pub fn get_effects_signature(
+        &self,
+        epoch: EpochId,
+        current_epoch: EpochId,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<AuthoritySignInfo>> {
+        let Some(tables) = self.tables(epoch, current_epoch) else {
+            return Ok(None);
+        };
+        Ok(tables.effects_signatures.get(tx_digest)?)
+    }
+
+    fn tables(&self, epoch: EpochId, current_epoch: EpochId) -> Option<Arc<ReadOnlyEpochTables>> {
+        let mut tables = self.tables.lock();
+
+        // Close the epochs that fell out of the window, before the pruner removes them.
+        let oldest = current_epoch.saturating_sub(self.num_epochs);
+        tables.retain(|e, _| *e >= oldest);
+
+        if epoch < oldest || epoch >= current_epoch {
+            return None;
+        }
+
+        if let Some(t) = tables.get(&epoch) {
+            return Some(t.clone());
+        }
+
+        if !AuthorityEpochTables::path(epoch, &self.parent_path).exists() {
+            return None;
+        }
+
+        debug!(epoch, "Opening tables of previous epoch read-only");
+        let t = Arc::new(AuthorityEpochTables::open_readonly(
+            epoch,
+            &self.parent_path,
+        ));
+        tables.insert(epoch, t.clone());
+        Some(t)
+    }
+}
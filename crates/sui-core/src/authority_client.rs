@@ -12,7 +12,7 @@ use sui_network::{api::ValidatorClient, tonic};
 use sui_types::base_types::AuthorityName;
 use sui_types::committee::CommitteeWithNetworkMetadata;
 use sui_types::crypto::NetworkPublicKey;
-use sui_types::error::{SuiError, SuiResult};
+use sui_types::error::{SuiError, SuiErrorKind, SuiResult};
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointRequestV2, CheckpointResponse, CheckpointResponseV2,
 };
@@ -25,9 +25,9 @@ use sui_network::tonic::metadata::KeyAndValueRef;
 use sui_network::tonic::transport::Channel;
 use sui_types::messages_grpc::{
     ObjectInfoRequest, ObjectInfoResponse, RawValidatorHealthRequest, RawWaitForEffectsRequest,
-    SubmitTxRequest, SubmitTxResponse, SystemStateRequest, TransactionInfoRequest,
-    TransactionInfoResponse, ValidatorHealthRequest, ValidatorHealthResponse,
-    WaitForEffectsRequest, WaitForEffectsResponse,
+    SubmitSoftBundleRequest, SubmitTxRequest, SubmitTxResponse, SystemStateRequest,
+    TransactionInfoRequest, TransactionInfoResponse, ValidatorHealthRequest,
+    ValidatorHealthResponse, WaitForEffectsRequest, WaitForEffectsResponse,
 };
 
 #[async_trait]
@@ -39,6 +39,19 @@ pub trait AuthorityAPI {
         client_addr: Option<SocketAddr>,
     ) -> Result<SubmitTxResponse, SuiError>;
 
+    /// Submits a soft bundle of transactions, which the validator tries to sequence adjacently.
+    /// Returns one result per transaction, in request order.
+    async fn submit_soft_bundle(
+        &self,
+        _request: SubmitSoftBundleRequest,
+        _client_addr: Option<SocketAddr>,
+    ) -> Result<SubmitTxResponse, SuiError> {
+        Err(SuiErrorKind::UnsupportedFeatureError {
+            error: "Soft bundle submission is not supported by this client".to_string(),
+        }
+        .into())
+    }
+
     /// Waits for effects of a transaction that has been submitted to the network
     /// through the `submit_transaction` API.
     async fn wait_for_effects(
@@ -158,6 +171,22 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .try_into()
     }
 
+    async fn submit_soft_bundle(
+        &self,
+        request: SubmitSoftBundleRequest,
+        client_addr: Option<SocketAddr>,
+    ) -> Result<SubmitTxResponse, SuiError> {
+        let mut request = request.into_raw()?.into_request();
+        insert_metadata(&mut request, client_addr);
+
+        self.client()?
+            .submit_transaction(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(Into::<SuiError>::into)?
+            .try_into()
+    }
+
     async fn wait_for_effects(
         &self,
         request: WaitForEffectsRequest,
@@ -670,6 +670,14 @@ impl CheckpointExecutor {
             );
         }
 
+        if let Some(package_gas_tracker) = self.state.package_gas_tracker() {
+            package_gas_tracker.process_checkpoint_effects(
+                sequence_number,
+                &tx_data.transactions,
+                &tx_data.effects,
+            );
+        }
+
         self.insert_finalized_transactions(&ckpt_state.data.tx_digests, sequence_number);
 
         // The early versions of the hasher (prior to effectsv2) rely on db
@@ -687,6 +695,12 @@ impl CheckpointExecutor {
 
         ckpt_state.full_data = self.process_checkpoint_data(&ckpt_state.data, tx_data);
 
+        if let (Some(compliance_reporter), Some(checkpoint)) =
+            (self.state.compliance_reporter(), &ckpt_state.full_data)
+        {
+            compliance_reporter.process_checkpoint(checkpoint);
+        }
+
         finish_stage!(pipeline_handle, ProcessCheckpointData);
 
         ckpt_state
@@ -721,6 +735,7 @@ impl CheckpointExecutor {
     fn checkpoint_data_enabled(&self) -> bool {
         self.subscription_service_checkpoint_sender.is_some()
             || self.config.data_ingestion_dir.is_some()
+            || self.state.compliance_reporter().is_some()
     }
 
     fn insert_finalized_transactions(
@@ -15,6 +15,7 @@ pub use crate::checkpoints::checkpoint_output::{
     CheckpointOutput, LogCheckpointOutput, SendCheckpointToStateSync, SubmitCheckpointToConsensus,
 };
 pub use crate::checkpoints::metrics::CheckpointMetrics;
+use crate::commit_trace;
 use crate::consensus_manager::ReplayWaiter;
 use crate::execution_cache::TransactionCacheRead;
 
@@ -84,7 +85,7 @@ use sui_types::transaction::{
     TransactionDataAPI, TransactionKey, TransactionKind, VerifiedTransaction,
 };
 use tokio::sync::Notify;
-use tracing::{debug, error, info, instrument, trace, warn};
+use tracing::{Instrument, debug, error, info, instrument, trace, warn};
 use typed_store::DBMapUtils;
 use typed_store::Map;
 use typed_store::{
@@ -1341,6 +1342,11 @@ pub enum CheckpointWatermark {
     /// Retired: checkpoint fork markers now live in the `checkpoint_fork_detected` table. Kept
     /// because existing databases may hold this key.
     CheckpointForkDetected,
+    /// Highest checkpoint whose transactions, effects or events were pruned ahead of its
+    /// contents under `component-retention`.
+    HighestPrunedTransactions,
+    HighestPrunedEffects,
+    HighestPrunedEvents,
 }
 
 struct CheckpointStateHasher {
@@ -1557,12 +1563,27 @@ impl CheckpointBuilder {
         let (poll_count, result) = poll_count(self.resolve_checkpoint_transactions(pending)).await;
         let (sorted_tx_effects_included_in_checkpoint, all_roots) = result?;
 
+        let commit_span = commit_trace::stage_span(
+            "checkpoint_builder",
+            None,
+            Some(details.consensus_commit_ref.index.into()),
+        );
+        commit_span.in_scope(|| {
+            commit_trace::record_transactions(
+                "checkpoint_builder",
+                sorted_tx_effects_included_in_checkpoint
+                    .iter()
+                    .map(|effects| effects.transaction_digest()),
+            )
+        });
+
         let new_checkpoint = self
             .create_checkpoint(
                 sorted_tx_effects_included_in_checkpoint,
                 &details,
                 &all_roots,
             )
+            .instrument(commit_span.clone())
             .await?;
         let sequence = *new_checkpoint.0.sequence_number();
         let digest = new_checkpoint.0.digest();
@@ -1573,6 +1594,7 @@ impl CheckpointBuilder {
         }
 
         self.write_checkpoint(details.checkpoint_height, new_checkpoint)
+            .instrument(commit_span)
             .await?;
         info!(
             seq = sequence,
@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spans that follow a consensus commit, and a sample of its transactions, through the validator
+//! pipeline: consensus handler, shared object version assignment, execution scheduler and
+//! checkpoint builder.
+//!
+//! The spans are emitted at info level under the [`TARGET`] target, and are exported over OTLP
+//! like any other span when OTLP tracing is enabled, e.g. with
+//! `TRACE_FILTER=sui_core::commit_trace=info`. Stage spans carry the commit round; each sampled
+//! transaction gets a `transaction` span carrying its digest and stage, so a single transaction
+//! can be followed by searching for its digest.
+//!
+//! Transactions are sampled by digest, so a sampled transaction is traced at every stage. The
+//! sample rate is read from `SUI_COMMIT_TRACE_SAMPLE_RATE` (between 0 and 1, default 0.01).
+
+use std::sync::OnceLock;
+
+use sui_types::committee::EpochId;
+use sui_types::digests::TransactionDigest;
+use tracing::{Level, Span, info_span};
+
+pub(crate) const TARGET: &str = "sui_core::commit_trace";
+
+const DEFAULT_SAMPLE_RATE: f64 = 0.01;
+
+fn sample_rate() -> f64 {
+    static SAMPLE_RATE: OnceLock<f64> = OnceLock::new();
+    *SAMPLE_RATE.get_or_init(|| {
+        std::env::var("SUI_COMMIT_TRACE_SAMPLE_RATE")
+            .ok()
+            .and_then(|rate| rate.parse::<f64>().ok())
+            .map(|rate| rate.clamp(0.0, 1.0))
+            .unwrap_or(DEFAULT_SAMPLE_RATE)
+    })
+}
+
+/// Whether commit tracing is enabled at all, so callers can skip collecting digests.
+pub(crate) fn enabled() -> bool {
+    tracing::enabled!(target: TARGET, Level::INFO)
+}
+
+/// Whether `digest` is in the traced sample. The decision only depends on the digest, so every
+/// stage agrees on it.
+pub(crate) fn is_sampled(digest: &TransactionDigest, rate: f64) -> bool {
+    let bytes: [u8; 8] = digest.inner()[..8].try_into().unwrap();
+    let position = u64::from_le_bytes(bytes) as f64 / u64::MAX as f64;
+    rate >= 1.0 || position < rate
+}
+
+/// The root span of a consensus commit in the consensus handler.
+pub(crate) fn commit_span(epoch: EpochId, commit_round: u64, commit_index: u64) -> Span {
+    info_span!(
+        target: TARGET,
+        "consensus_commit",
+        epoch,
+        commit_round,
+        commit_index
+    )
+}
+
+/// The span of a pipeline stage processing (part of) a commit. Stages that run after the commit
+/// handler only know some of the commit's identifiers, or none.
+pub(crate) fn stage_span(
+    stage: &'static str,
+    commit_round: Option<u64>,
+    commit_index: Option<u64>,
+) -> Span {
+    info_span!(
+        target: TARGET,
+        "commit_stage",
+        stage,
+        commit_round,
+        commit_index
+    )
+}
+
+/// Emits a `transaction` span, within the current span, for each sampled digest in `digests`.
+pub(crate) fn record_transactions<'a>(
+    stage: &'static str,
+    digests: impl IntoIterator<Item = &'a TransactionDigest>,
+) {
+    if !enabled() {
+        return;
+    }
+    let rate = sample_rate();
+    for digest in digests {
+        if is_sampled(digest, rate) {
+            info_span!(target: TARGET, "transaction", stage, tx_digest = %digest).in_scope(|| {});
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_is_deterministic() {
+        let digests: Vec<_> = (0..1000).map(|_| TransactionDigest::random()).collect();
+
+        assert!(digests.iter().all(|d| is_sampled(d, 1.0)));
+        assert!(!digests.iter().any(|d| is_sampled(d, 0.0)));
+
+        let sampled: Vec<_> = digests.iter().filter(|d| is_sampled(d, 0.1)).collect();
+        let resampled: Vec<_> = digests.iter().filter(|d| is_sampled(d, 0.1)).collect();
+        assert_eq!(sampled, resampled);
+        assert!((20..200).contains(&sampled.len()));
+
+        // A digest sampled at a lower rate is also sampled at any higher rate.
+        assert!(sampled.iter().all(|d| is_sampled(d, 0.5)));
+    }
+}
@@ -0,0 +1,741 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signed, per-epoch reports of the on-chain activity of a configured set of addresses, for audit
+//! and compliance teams that must not rely on a third-party indexer.
+//!
+//! Every epoch is written to its own directory under the configured output path:
+//!
+//! - `activity.json` and/or `activity.csv`: one entry per transaction and reported address, with
+//!   the address's balance changes and the other addresses involved in the transaction.
+//! - `proofs.bcs`: a BCS-encoded list of [`CheckpointInclusionProof`]s, one per checkpoint that
+//!   contains reported transactions. Each proof carries the certified checkpoint summary, the
+//!   checkpoint contents, and the reported transactions with their effects, so that inclusion can
+//!   be checked against the committee of the epoch alone (see
+//!   [`CheckpointInclusionProof::verify`]).
+//! - `manifest.json`: a [`ReportManifest`] with the digests of the files above, and
+//!   `manifest.sig`: the Base64 signature of the manifest as a personal message by the configured
+//!   key.
+//!
+//! While checkpoints are executed, activity and proofs are appended to staging files in a
+//! `.staging-epoch-<epoch>` directory next to the reports, so the memory used does not grow with
+//! the activity of an epoch. The report is assembled from the staging files at the end of the
+//! epoch. A report for an epoch the node only executed part of, e.g. because it was restarted or
+//! could not write to the staging files, is marked as incomplete and records the checkpoints it
+//! covers.
+
+use anyhow::Context;
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sui_config::node::{ComplianceReportConfig, ComplianceReportFormat, KeyPairWithPath};
+use sui_types::balance_change::derive_balance_changes_2;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::crypto::Signature;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::{SuiErrorKind, SuiResult};
+use sui_types::full_checkpoint_content::{Checkpoint, ExecutedTransaction, ObjectSet};
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointTimestamp,
+};
+use sui_types::object::Owner;
+use sui_types::storage::ObjectKey;
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+use tracing::{error, info};
+
+const ACTIVITY_JSON_FILE: &str = "activity.json";
+const ACTIVITY_CSV_FILE: &str = "activity.csv";
+const PROOFS_FILE: &str = "proofs.bcs";
+const MANIFEST_FILE: &str = "manifest.json";
+const SIGNATURE_FILE: &str = "manifest.sig";
+/// Staged activity, one JSON-encoded [`ReportedActivity`] per line.
+const STAGED_ACTIVITY_FILE: &str = "activity.jsonl";
+/// Staged proofs, BCS-encoded back to back.
+const STAGED_PROOFS_FILE: &str = "proofs.bcs";
+const ACTIVITY_CSV_HEADER: &str =
+    "address,transaction,checkpoint,timestamp_ms,sender,success,balance_changes,counterparties\n";
+
+/// The activity of one reported address in one transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedActivity {
+    pub address: SuiAddress,
+    pub transaction: TransactionDigest,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: CheckpointTimestamp,
+    pub sender: SuiAddress,
+    pub success: bool,
+    /// Net balance changes of `address`, by coin type.
+    pub balance_changes: Vec<ReportedBalanceChange>,
+    /// The other addresses that sent the transaction, or whose balances or objects it changed.
+    pub counterparties: BTreeSet<SuiAddress>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedBalanceChange {
+    pub coin_type: String,
+    pub amount: i128,
+}
+
+/// Evidence that transactions were executed in a checkpoint certified by the committee.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointInclusionProof {
+    pub summary: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    pub transactions: Vec<(TransactionData, TransactionEffects)>,
+}
+
+impl CheckpointInclusionProof {
+    /// Checks that the summary is certified by `committee`, that the contents are the ones it
+    /// commits to, and that every transaction and its effects are part of the contents.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.summary
+            .verify_with_contents(committee, Some(&self.contents))?;
+        let included: BTreeSet<_> = self
+            .contents
+            .iter()
+            .map(|digests| (digests.transaction, digests.effects))
+            .collect();
+        for (transaction, effects) in &self.transactions {
+            let digest = transaction.digest();
+            if effects.transaction_digest() != &digest
+                || !included.contains(&(digest, effects.digest()))
+            {
+                return Err(SuiErrorKind::GenericAuthorityError {
+                    error: format!(
+                        "Transaction {digest} is not included in checkpoint {}",
+                        self.summary.sequence_number
+                    ),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Describes the files of a report. Signed as a personal message in `manifest.sig`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportManifest {
+    pub epoch: EpochId,
+    pub first_checkpoint: CheckpointSequenceNumber,
+    pub last_checkpoint: CheckpointSequenceNumber,
+    /// Whether the report covers every checkpoint of the epoch.
+    pub complete: bool,
+    pub addresses: BTreeSet<SuiAddress>,
+    pub signer: SuiAddress,
+    /// Hex-encoded Blake2b-256 digest of each file of the report, by file name.
+    pub files: BTreeMap<String, String>,
+}
+
+/// The activity collected so far for an epoch, staged on disk.
+struct EpochActivity {
+    epoch: EpochId,
+    first_checkpoint: CheckpointSequenceNumber,
+    last_checkpoint: Option<CheckpointSequenceNumber>,
+    complete: bool,
+    staging: PathBuf,
+    activity: BufWriter<File>,
+    proofs: BufWriter<File>,
+    num_proofs: usize,
+}
+
+impl EpochActivity {
+    /// Starts staging the activity of `epoch` under `output_path`, discarding anything left
+    /// staged for it by a previous run.
+    fn new(
+        output_path: &Path,
+        epoch: EpochId,
+        first_checkpoint: CheckpointSequenceNumber,
+        complete: bool,
+    ) -> anyhow::Result<Self> {
+        let staging = output_path.join(format!(".staging-epoch-{epoch}"));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)
+                .with_context(|| format!("Failed to remove {}", staging.display()))?;
+        }
+        fs::create_dir_all(&staging)
+            .with_context(|| format!("Failed to create directory {}", staging.display()))?;
+        let create = |name| {
+            let path = staging.join(name);
+            File::create(&path)
+                .map(BufWriter::new)
+                .with_context(|| format!("Failed to create {}", path.display()))
+        };
+        Ok(Self {
+            epoch,
+            first_checkpoint,
+            last_checkpoint: None,
+            complete,
+            activity: create(STAGED_ACTIVITY_FILE)?,
+            proofs: create(STAGED_PROOFS_FILE)?,
+            staging,
+            num_proofs: 0,
+        })
+    }
+
+    fn append(
+        &mut self,
+        activity: &[ReportedActivity],
+        proof: Option<&CheckpointInclusionProof>,
+    ) -> anyhow::Result<()> {
+        for entry in activity {
+            serde_json::to_writer(&mut self.activity, entry)?;
+            self.activity.write_all(b"\n")?;
+        }
+        if let Some(proof) = proof {
+            bcs::serialize_into(&mut self.proofs, proof)?;
+            self.num_proofs += 1;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        self.activity.flush()?;
+        self.proofs.flush()?;
+        Ok(())
+    }
+
+    /// The staged activity, in the order it was recorded.
+    fn staged_activity(&self) -> anyhow::Result<impl Iterator<Item = anyhow::Result<String>>> {
+        let file = File::open(self.staging.join(STAGED_ACTIVITY_FILE))?;
+        Ok(BufReader::new(file).lines().map(|line| Ok(line?)))
+    }
+}
+
+/// Collects the activity of the configured addresses from executed checkpoints, and writes a
+/// signed report at the end of every epoch.
+pub struct ComplianceReporter {
+    addresses: BTreeSet<SuiAddress>,
+    output_path: PathBuf,
+    formats: Vec<ComplianceReportFormat>,
+    signing_key_pair: KeyPairWithPath,
+    current: Mutex<Option<EpochActivity>>,
+}
+
+impl ComplianceReporter {
+    pub fn new(config: &ComplianceReportConfig) -> Self {
+        Self {
+            addresses: config.addresses.iter().copied().collect(),
+            output_path: config.output_path.clone(),
+            formats: config.formats(),
+            signing_key_pair: config.signing_key_pair.clone(),
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Records the activity of the reported addresses in an executed checkpoint. After the last
+    /// checkpoint of an epoch, the report for the epoch is written in the background.
+    pub fn process_checkpoint(self: &Arc<Self>, checkpoint: &Checkpoint) {
+        let finished = match self.record_checkpoint(checkpoint) {
+            Ok(Some(finished)) => finished,
+            Ok(None) => return,
+            Err(e) => {
+                error!(
+                    checkpoint = checkpoint.summary.sequence_number,
+                    "Failed to stage compliance report activity, the report for the epoch will \
+                     be incomplete: {e:#}"
+                );
+                return;
+            }
+        };
+        let reporter = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let epoch = finished.epoch;
+            match reporter.write_report(finished) {
+                Ok(path) => info!(epoch, ?path, "Wrote compliance report"),
+                Err(e) => error!(epoch, "Failed to write compliance report: {e:#}"),
+            }
+        });
+    }
+
+    /// Records `checkpoint`, returning the activity of the epoch if it was the last checkpoint
+    /// of the epoch. If the checkpoint cannot be staged, the activity staged so far for the epoch
+    /// is dropped, and the report restarts with the next checkpoint.
+    fn record_checkpoint(&self, checkpoint: &Checkpoint) -> anyhow::Result<Option<EpochActivity>> {
+        let summary = checkpoint.summary.data();
+        let sequence_number = summary.sequence_number;
+
+        let mut transactions = vec![];
+        let mut activity = vec![];
+        for tx in &checkpoint.transactions {
+            let tx_activity = self.transaction_activity(
+                tx,
+                &checkpoint.object_set,
+                sequence_number,
+                summary.timestamp_ms,
+            );
+            if !tx_activity.is_empty() {
+                transactions.push((tx.transaction.clone(), tx.effects.clone()));
+                activity.extend(tx_activity);
+            }
+        }
+
+        let proof = (!transactions.is_empty()).then(|| CheckpointInclusionProof {
+            summary: checkpoint.summary.clone(),
+            contents: checkpoint.contents.clone(),
+            transactions,
+        });
+
+        let mut current = self.current.lock();
+        let mut epoch = match current.take() {
+            Some(epoch) if epoch.epoch == summary.epoch => epoch,
+            // Starting from the genesis checkpoint is the only way to know that a report covers
+            // the whole epoch without having executed the end of the previous one.
+            _ => EpochActivity::new(
+                &self.output_path,
+                summary.epoch,
+                sequence_number,
+                sequence_number == 0,
+            )?,
+        };
+        epoch.last_checkpoint = Some(sequence_number);
+        epoch.append(&activity, proof.as_ref())?;
+
+        if summary.end_of_epoch_data.is_none() {
+            *current = Some(epoch);
+            return Ok(None);
+        }
+        epoch.flush()?;
+        match EpochActivity::new(
+            &self.output_path,
+            summary.epoch + 1,
+            sequence_number + 1,
+            true,
+        ) {
+            Ok(next) => *current = Some(next),
+            Err(e) => error!(
+                epoch = summary.epoch + 1,
+                "Failed to start staging compliance report activity: {e:#}"
+            ),
+        }
+        Ok(Some(epoch))
+    }
+
+    /// The activity of each reported address involved in `tx`.
+    fn transaction_activity(
+        &self,
+        tx: &ExecutedTransaction,
+        objects: &ObjectSet,
+        checkpoint: CheckpointSequenceNumber,
+        timestamp_ms: CheckpointTimestamp,
+    ) -> Vec<ReportedActivity> {
+        let sender = tx.transaction.sender();
+        let balance_changes = derive_balance_changes_2(&tx.effects, objects);
+
+        let input_owners = tx
+            .effects
+            .modified_at_versions()
+            .into_iter()
+            .filter_map(|(id, version)| objects.get(&ObjectKey(id, version)))
+            .map(|object| object.owner.clone());
+        let output_owners = tx
+            .effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|(_, owner, _)| owner);
+        let involved: BTreeSet<SuiAddress> = input_owners
+            .chain(output_owners)
+            .filter_map(|owner| match owner {
+                Owner::AddressOwner(address)
+                | Owner::ConsensusAddressOwner { owner: address, .. } => Some(address),
+                Owner::ObjectOwner(_)
+                | Owner::Shared { .. }
+                | Owner::Immutable
+                | Owner::Party { .. } => None,
+            })
+            .chain(balance_changes.iter().map(|change| change.address))
+            .chain([sender])
+            .collect();
+
+        involved
+            .intersection(&self.addresses)
+            .map(|address| ReportedActivity {
+                address: *address,
+                transaction: *tx.effects.transaction_digest(),
+                checkpoint,
+                timestamp_ms,
+                sender,
+                success: tx.effects.status().is_ok(),
+                balance_changes: balance_changes
+                    .iter()
+                    .filter(|change| change.address == *address)
+                    .map(|change| ReportedBalanceChange {
+                        coin_type: change
+                            .coin_type
+                            .to_canonical_string(/* with_prefix */ true),
+                        amount: change.amount,
+                    })
+                    .collect(),
+                counterparties: involved
+                    .iter()
+                    .filter(|other| *other != address)
+                    .copied()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Writes the report for `epoch`, returning the directory it was written to.
+    fn write_report(&self, epoch: EpochActivity) -> anyhow::Result<PathBuf> {
+        let dir = self.output_path.join(format!("epoch-{}", epoch.epoch));
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+        let mut files = BTreeMap::new();
+        for format in &self.formats {
+            match format {
+                ComplianceReportFormat::Json => {
+                    let mut file = ReportFile::create(&dir, ACTIVITY_JSON_FILE)?;
+                    file.write_all(b"[")?;
+                    for (i, line) in epoch.staged_activity()?.enumerate() {
+                        file.write_all(if i == 0 { b"\n" } else { b",\n" })?;
+                        file.write_all(line?.as_bytes())?;
+                    }
+                    file.write_all(b"\n]\n")?;
+                    file.finish(&mut files)?;
+                }
+                ComplianceReportFormat::Csv => {
+                    let mut file = ReportFile::create(&dir, ACTIVITY_CSV_FILE)?;
+                    file.write_all(ACTIVITY_CSV_HEADER.as_bytes())?;
+                    for line in epoch.staged_activity()? {
+                        let entry: ReportedActivity = serde_json::from_str(&line?)?;
+                        file.write_all(activity_csv_row(&entry).as_bytes())?;
+                    }
+                    file.finish(&mut files)?;
+                }
+            }
+        }
+
+        // The staged proofs are the elements of the BCS-encoded list, which only lacks its length.
+        let mut file = ReportFile::create(&dir, PROOFS_FILE)?;
+        file.write_all(&uleb128(epoch.num_proofs))?;
+        let staged_proofs = epoch.staging.join(STAGED_PROOFS_FILE);
+        std::io::copy(&mut File::open(&staged_proofs)?, &mut file)?;
+        file.finish(&mut files)?;
+
+        let key_pair = self.signing_key_pair.keypair();
+        let manifest = ReportManifest {
+            epoch: epoch.epoch,
+            first_checkpoint: epoch.first_checkpoint,
+            last_checkpoint: epoch.last_checkpoint.unwrap_or(epoch.first_checkpoint),
+            complete: epoch.complete,
+            addresses: self.addresses.clone(),
+            signer: SuiAddress::from(&key_pair.public()),
+            files,
+        };
+        let manifest = serde_json::to_vec_pretty(&manifest)?;
+        let signature = Signature::new_secure(
+            &IntentMessage::new(
+                Intent::personal_message(),
+                PersonalMessage {
+                    message: manifest.clone(),
+                },
+            ),
+            key_pair,
+        );
+        write_file(&dir, MANIFEST_FILE, &manifest, &mut BTreeMap::new())?;
+        write_file(
+            &dir,
+            SIGNATURE_FILE,
+            Base64::encode(signature.as_ref()).as_bytes(),
+            &mut BTreeMap::new(),
+        )?;
+
+        fs::remove_dir_all(&epoch.staging)
+            .with_context(|| format!("Failed to remove {}", epoch.staging.display()))?;
+        Ok(dir)
+    }
+}
+
+/// A file of a report that is hashed as it is written.
+struct ReportFile {
+    name: &'static str,
+    file: BufWriter<File>,
+    hasher: Blake2b256,
+}
+
+impl ReportFile {
+    fn create(dir: &Path, name: &'static str) -> anyhow::Result<Self> {
+        let path = dir.join(name);
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok(Self {
+            name,
+            file: BufWriter::new(file),
+            hasher: Blake2b256::default(),
+        })
+    }
+
+    /// Flushes the file, recording its digest in `files`.
+    fn finish(mut self, files: &mut BTreeMap<String, String>) -> anyhow::Result<()> {
+        self.file
+            .flush()
+            .with_context(|| format!("Failed to write {}", self.name))?;
+        files.insert(
+            self.name.to_string(),
+            Hex::encode(self.hasher.finalize().digest),
+        );
+        Ok(())
+    }
+}
+
+impl Write for ReportFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// The ULEB128 encoding BCS uses for the length of a list.
+fn uleb128(mut value: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Writes `contents` to `dir/name`, recording the digest of the file in `files`.
+fn write_file(
+    dir: &Path,
+    name: &str,
+    contents: &[u8],
+    files: &mut BTreeMap<String, String>,
+) -> anyhow::Result<()> {
+    let path = dir.join(name);
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    files.insert(
+        name.to_string(),
+        Hex::encode(Blake2b256::digest(contents).digest),
+    );
+    Ok(())
+}
+
+fn activity_csv_row(entry: &ReportedActivity) -> String {
+    let balance_changes = entry
+        .balance_changes
+        .iter()
+        .map(|change| format!("{}={}", change.coin_type, change.amount))
+        .collect::<Vec<_>>()
+        .join(";");
+    let counterparties = entry
+        .counterparties
+        .iter()
+        .map(|address| address.to_string())
+        .collect::<Vec<_>>()
+        .join(";");
+    let row = [
+        entry.address.to_string(),
+        entry.transaction.to_string(),
+        entry.checkpoint.to_string(),
+        entry.timestamp_ms.to_string(),
+        entry.sender.to_string(),
+        entry.success.to_string(),
+        balance_changes,
+        counterparties,
+    ];
+    let mut row = row.map(|field| csv_field(&field)).join(",");
+    row.push('\n');
+    row
+}
+
+/// Quotes `field` if it contains characters that are special in CSV, such as the commas in
+/// generic coin types.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::crypto::{
+        AccountKeyPair, SignatureScheme, SuiKeyPair, SuiSignature, ToFromBytes, get_key_pair,
+    };
+    use sui_types::gas_coin::GAS;
+    use sui_types::test_checkpoint_data_builder::{AdvanceEpochConfig, TestCheckpointBuilder};
+
+    fn reporter(output_path: PathBuf, addresses: Vec<SuiAddress>) -> ComplianceReporter {
+        let (_, key_pair): (_, AccountKeyPair) = get_key_pair();
+        ComplianceReporter::new(&ComplianceReportConfig {
+            addresses,
+            output_path,
+            signing_key_pair: KeyPairWithPath::new(SuiKeyPair::Ed25519(key_pair)),
+            formats: vec![],
+        })
+    }
+
+    #[test]
+    fn test_report_covers_epoch_activity() {
+        let dir = tempfile::tempdir().unwrap();
+        let reported = TestCheckpointBuilder::derive_address(1);
+        let reporter = reporter(dir.path().to_path_buf(), vec![reported]);
+
+        let mut builder = TestCheckpointBuilder::new(0)
+            .start_transaction(0)
+            .create_sui_object(0, 100)
+            .finish_transaction();
+        assert!(
+            reporter
+                .record_checkpoint(&builder.build_checkpoint())
+                .unwrap()
+                .is_none()
+        );
+
+        let mut builder = builder
+            .start_transaction(0)
+            .transfer_coin_balance(0, 1, 1, 40)
+            .finish_transaction()
+            .start_transaction(2)
+            .create_owned_object(2)
+            .finish_transaction();
+        assert!(
+            reporter
+                .record_checkpoint(&builder.build_checkpoint())
+                .unwrap()
+                .is_none()
+        );
+
+        let epoch = reporter
+            .record_checkpoint(&builder.advance_epoch(AdvanceEpochConfig::default()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(epoch.epoch, 0);
+        assert!(epoch.complete);
+        assert_eq!(
+            (epoch.first_checkpoint, epoch.last_checkpoint),
+            (0, Some(2))
+        );
+
+        let staging = epoch.staging.clone();
+        let path = reporter.write_report(epoch).unwrap();
+        assert!(!staging.exists());
+
+        let activity: Vec<ReportedActivity> =
+            serde_json::from_slice(&fs::read(path.join(ACTIVITY_JSON_FILE)).unwrap()).unwrap();
+        let [activity] = activity.as_slice() else {
+            panic!("expected one transaction, got {activity:?}");
+        };
+        assert_eq!(activity.address, reported);
+        assert_eq!(activity.checkpoint, 1);
+        assert_eq!(activity.sender, TestCheckpointBuilder::derive_address(0));
+        assert!(
+            activity
+                .counterparties
+                .contains(&TestCheckpointBuilder::derive_address(0))
+        );
+        assert_eq!(
+            activity.balance_changes,
+            vec![ReportedBalanceChange {
+                coin_type: GAS::type_tag().to_canonical_string(true),
+                amount: 40,
+            }]
+        );
+        let csv = fs::read_to_string(path.join(ACTIVITY_CSV_FILE)).unwrap();
+        assert_eq!(
+            csv,
+            format!("{ACTIVITY_CSV_HEADER}{}", activity_csv_row(activity))
+        );
+
+        let (committee, _) = Committee::new_simple_test_committee();
+        let proofs: Vec<CheckpointInclusionProof> =
+            bcs::from_bytes(&fs::read(path.join(PROOFS_FILE)).unwrap()).unwrap();
+        let [proof] = proofs.as_slice() else {
+            panic!("expected one proof");
+        };
+        proof.verify(&committee).unwrap();
+
+        let manifest_bytes = fs::read(path.join(MANIFEST_FILE)).unwrap();
+        let manifest: ReportManifest = serde_json::from_slice(&manifest_bytes).unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec![ACTIVITY_CSV_FILE, ACTIVITY_JSON_FILE, PROOFS_FILE]
+        );
+        for (name, digest) in &manifest.files {
+            let contents = fs::read(path.join(name)).unwrap();
+            assert_eq!(*digest, Hex::encode(Blake2b256::digest(&contents).digest));
+        }
+
+        let signature = Base64::decode(&fs::read_to_string(path.join(SIGNATURE_FILE)).unwrap())
+            .map(|bytes| Signature::from_bytes(&bytes).unwrap())
+            .unwrap();
+        signature
+            .verify_secure(
+                &IntentMessage::new(
+                    Intent::personal_message(),
+                    PersonalMessage {
+                        message: manifest_bytes,
+                    },
+                ),
+                manifest.signer,
+                SignatureScheme::ED25519,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_report_started_mid_epoch_is_incomplete() {
+        let dir = tempfile::tempdir().unwrap();
+        let reporter = reporter(dir.path().to_path_buf(), vec![]);
+        let mut builder = TestCheckpointBuilder::new(10);
+        assert!(
+            reporter
+                .record_checkpoint(&builder.build_checkpoint())
+                .unwrap()
+                .is_none()
+        );
+        let epoch = reporter
+            .record_checkpoint(&builder.advance_epoch(AdvanceEpochConfig::default()))
+            .unwrap()
+            .unwrap();
+        assert!(!epoch.complete);
+        assert_eq!(epoch.first_checkpoint, 10);
+
+        // The next epoch is followed from its first checkpoint.
+        let epoch = reporter
+            .record_checkpoint(&builder.advance_epoch(AdvanceEpochConfig::default()))
+            .unwrap()
+            .unwrap();
+        assert!(epoch.complete);
+        assert_eq!((epoch.epoch, epoch.first_checkpoint), (1, 12));
+    }
+
+    #[test]
+    fn test_uleb128() {
+        for n in [0, 1, 127, 128, 300, 1 << 20] {
+            assert_eq!(uleb128(n), bcs::to_bytes(&vec![(); n]).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_csv_fields_are_quoted() {
+        assert_eq!(csv_field("0x2::sui::SUI=1"), "0x2::sui::SUI=1");
+        assert_eq!(
+            csv_field("0x1::pool::LP<0x2::sui::SUI, 0x3::a::B>=-5"),
+            "\"0x1::pool::LP<0x2::sui::SUI, 0x3::a::B>=-5\""
+        );
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -3,12 +3,15 @@
 
 use moka::ops::compute::Op;
 use moka::sync::Cache;
-use std::collections::HashMap;
+use parking_lot::Mutex;
 use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use sui_types::base_types::ObjectID;
 use sui_types::effects::{InputConsensusObject, TransactionEffects, TransactionEffectsAPI};
 use sui_types::execution_status::CongestedObjects;
-use sui_types::messages_checkpoint::{CheckpointTimestamp, VerifiedCheckpoint};
+use sui_types::messages_checkpoint::{
+    CheckpointSequenceNumber, CheckpointTimestamp, VerifiedCheckpoint,
+};
 use sui_types::transaction::{TransactionData, TransactionDataAPI};
 
 use crate::execution_cache::TransactionCacheRead;
@@ -54,8 +57,31 @@ impl CongestionInfo {
     }
 }
 
+/// Number of recent congested checkpoints whose gas prices are kept by [`CongestionTracker`].
+pub const MAX_RECENT_CONGESTED_CHECKPOINTS: usize = 100;
+
+/// Gas prices bid for a congested object by the transactions of a single checkpoint.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectGasPrices {
+    /// Gas prices of transactions that were sequenced and mutated the object, in ascending order.
+    pub sequenced: Vec<u64>,
+    /// Gas prices of transactions that were deferred until cancelled because the object was
+    /// congested, in ascending order.
+    pub cancelled: Vec<u64>,
+}
+
+/// Gas prices bid for the objects that were congested in a checkpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckpointGasPrices {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub timestamp_ms: CheckpointTimestamp,
+    pub objects: BTreeMap<ObjectID, ObjectGasPrices>,
+}
+
 pub struct CongestionTracker {
     pub congestion_clearing_prices: Cache<ObjectID, CongestionInfo>,
+    // Most recent last. Only checkpoints with at least one congested object are kept.
+    recent_gas_prices: Mutex<VecDeque<CheckpointGasPrices>>,
 }
 
 impl Default for CongestionTracker {
@@ -68,6 +94,7 @@ impl CongestionTracker {
     pub fn new() -> Self {
         Self {
             congestion_clearing_prices: Cache::new(10_000),
+            recent_gas_prices: Mutex::new(VecDeque::new()),
         }
     }
 
@@ -113,6 +140,24 @@ impl CongestionTracker {
             &congestion_events,
             &cleared_events,
         );
+        self.record_checkpoint_gas_prices(
+            checkpoint.sequence_number,
+            checkpoint.timestamp_ms,
+            &congestion_events,
+            &cleared_events,
+        );
+    }
+
+    /// Returns the gas prices bid for congested objects in up to `limit` of the most recent
+    /// checkpoints that had any congestion, newest first.
+    pub fn get_recent_gas_prices(&self, limit: usize) -> Vec<CheckpointGasPrices> {
+        self.recent_gas_prices
+            .lock()
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect()
     }
 
     /// For all the mutable shared inputs, get the highest minimum clearing price (if any exists)
@@ -140,6 +185,51 @@ impl CongestionTracker {
         self.process_checkpoint_congestion(congestion_info_map);
     }
 
+    fn record_checkpoint_gas_prices(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        now: CheckpointTimestamp,
+        congestion_events: &[(u64, Vec<ObjectID>)],
+        cleared_events: &[(u64, Vec<ObjectID>)],
+    ) {
+        if congestion_events.is_empty() {
+            return;
+        }
+
+        let mut objects: BTreeMap<ObjectID, ObjectGasPrices> = BTreeMap::new();
+        for (gas_price, congested_objects) in congestion_events {
+            for object in congested_objects {
+                objects
+                    .entry(*object)
+                    .or_default()
+                    .cancelled
+                    .push(*gas_price);
+            }
+        }
+        // Only the objects that saw cancellations in this checkpoint were auctioned off.
+        for (gas_price, mutated_objects) in cleared_events {
+            for object in mutated_objects {
+                if let Some(prices) = objects.get_mut(object) {
+                    prices.sequenced.push(*gas_price);
+                }
+            }
+        }
+        for prices in objects.values_mut() {
+            prices.sequenced.sort_unstable();
+            prices.cancelled.sort_unstable();
+        }
+
+        let mut recent_gas_prices = self.recent_gas_prices.lock();
+        if recent_gas_prices.len() == MAX_RECENT_CONGESTED_CHECKPOINTS {
+            recent_gas_prices.pop_front();
+        }
+        recent_gas_prices.push_back(CheckpointGasPrices {
+            checkpoint,
+            timestamp_ms: now,
+            objects,
+        });
+    }
+
     fn get_suggested_gas_price_for_objects(
         &self,
         objects: impl Iterator<Item = ObjectID>,
@@ -330,4 +420,62 @@ mod tests {
             Some(150)
         );
     }
+
+    #[test]
+    fn test_record_checkpoint_gas_prices() {
+        let tracker = CongestionTracker::new();
+        let obj1 = ObjectID::random();
+        let obj2 = ObjectID::random();
+
+        // Checkpoints without congestion are not recorded.
+        tracker.record_checkpoint_gas_prices(1, 1000, &[], &[(100, vec![obj1])]);
+        assert!(tracker.get_recent_gas_prices(10).is_empty());
+
+        tracker.record_checkpoint_gas_prices(
+            2,
+            2000,
+            &[(100, vec![obj1]), (50, vec![obj1])],
+            &[(300, vec![obj1, obj2]), (200, vec![obj1])],
+        );
+        tracker.record_checkpoint_gas_prices(3, 3000, &[(400, vec![obj2])], &[]);
+
+        let recent = tracker.get_recent_gas_prices(10);
+        assert_eq!(
+            recent
+                .iter()
+                .map(|prices| prices.checkpoint)
+                .collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+        // obj2 was mutated in checkpoint 2, but not congested, so it is not reported.
+        assert_eq!(
+            recent[1].objects,
+            BTreeMap::from([(
+                obj1,
+                ObjectGasPrices {
+                    sequenced: vec![200, 300],
+                    cancelled: vec![50, 100],
+                }
+            )])
+        );
+        assert_eq!(
+            recent[0].objects,
+            BTreeMap::from([(
+                obj2,
+                ObjectGasPrices {
+                    sequenced: vec![],
+                    cancelled: vec![400],
+                }
+            )])
+        );
+        assert_eq!(tracker.get_recent_gas_prices(1), recent[..1]);
+
+        // Only the most recent congested checkpoints are kept.
+        for checkpoint in 4..(4 + MAX_RECENT_CONGESTED_CHECKPOINTS as u64) {
+            tracker.record_checkpoint_gas_prices(checkpoint, 0, &[(100, vec![obj1])], &[]);
+        }
+        let recent = tracker.get_recent_gas_prices(usize::MAX);
+        assert_eq!(recent.len(), MAX_RECENT_CONGESTED_CHECKPOINTS);
+        assert_eq!(recent.last().unwrap().checkpoint, 4);
+    }
 }
@@ -25,7 +25,7 @@ use mysten_metrics::{
 };
 use parking_lot::RwLockWriteGuard;
 use serde::{Deserialize, Serialize};
-use sui_config::node::CongestionLogConfig;
+use sui_config::node::{CommitLatencyConfig, CongestionLogConfig};
 use sui_macros::{fail_point, fail_point_arg, fail_point_if};
 use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig};
 use sui_types::{
@@ -55,6 +55,7 @@ use sui_types::{
     },
 };
 use tokio::task::JoinSet;
+use tokio::time::Instant;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::{
@@ -74,9 +75,10 @@ use crate::{
         transaction_deferral::{DeferralKey, DeferralReason, transaction_deferral_within_limit},
     },
     checkpoints::{
-        CheckpointHeight, CheckpointRoots, CheckpointService, CheckpointServiceNotify,
-        PendingCheckpoint, PendingCheckpointInfo,
+        CheckpointHeight, CheckpointRoots, CheckpointService, CheckpointServiceNoop,
+        CheckpointServiceNotify, PendingCheckpoint, PendingCheckpointInfo,
     },
+    commit_trace,
     consensus_throughput_calculator::ConsensusThroughputCalculator,
     consensus_types::consensus_output_api::{ConsensusCommitAPI, ParsedTransaction},
     epoch::{
@@ -792,6 +794,38 @@ impl<C> ConsensusHandler<C> {
         congestion_logger: Option<Arc<Mutex<CongestionCommitLogger>>>,
         consensus_gasless_counter: Arc<ConsensusGaslessCounter>,
         transaction_deny_config_manager: Arc<TransactionDenyConfigManager>,
+    ) -> Self {
+        let execution_scheduler_sender =
+            ExecutionSchedulerSender::start(settlement_scheduler, epoch_store.clone());
+        Self::new_with_execution_scheduler_sender(
+            epoch_store,
+            checkpoint_service,
+            execution_scheduler_sender,
+            cache_reader,
+            committee,
+            metrics,
+            throughput_calculator,
+            backpressure_subscriber,
+            traffic_controller,
+            congestion_logger,
+            consensus_gasless_counter,
+            transaction_deny_config_manager,
+        )
+    }
+
+    fn new_with_execution_scheduler_sender(
+        epoch_store: Arc<AuthorityPerEpochStore>,
+        checkpoint_service: Arc<C>,
+        execution_scheduler_sender: ExecutionSchedulerSender,
+        cache_reader: Arc<dyn ObjectCacheRead>,
+        committee: ConsensusCommittee,
+        metrics: Arc<AuthorityMetrics>,
+        throughput_calculator: Arc<ConsensusThroughputCalculator>,
+        backpressure_subscriber: BackpressureSubscriber,
+        traffic_controller: Option<Arc<TrafficController>>,
+        congestion_logger: Option<Arc<Mutex<CongestionCommitLogger>>>,
+        consensus_gasless_counter: Arc<ConsensusGaslessCounter>,
+        transaction_deny_config_manager: Arc<TransactionDenyConfigManager>,
     ) -> Self {
         assert_supported_protocol_config(epoch_store.protocol_config());
 
@@ -811,8 +845,6 @@ impl<C> ConsensusHandler<C> {
             .protocol_config()
             .min_checkpoint_interval_ms_as_option()
             .unwrap_or_default();
-        let execution_scheduler_sender =
-            ExecutionSchedulerSender::start(settlement_scheduler, epoch_store.clone());
         let commit_rate_estimate_window_size = epoch_store
             .protocol_config()
             .get_consensus_commit_rate_estimation_window_size();
@@ -912,6 +944,35 @@ impl<C> ConsensusHandler<C> {
     }
 }
 
+impl ConsensusHandler<CheckpointServiceNoop> {
+    /// A handler for replaying consensus commits into a shadow epoch store: it neither schedules
+    /// transactions for execution nor builds checkpoints, and records its metrics in `metrics`
+    /// rather than the node's.
+    pub(crate) fn new_for_shadow_replay(
+        epoch_store: Arc<AuthorityPerEpochStore>,
+        cache_reader: Arc<dyn ObjectCacheRead>,
+        metrics: Arc<AuthorityMetrics>,
+        backpressure_subscriber: BackpressureSubscriber,
+        transaction_deny_config_manager: Arc<TransactionDenyConfigManager>,
+    ) -> Self {
+        let committee = epoch_store.epoch_start_state().get_consensus_committee();
+        Self::new_with_execution_scheduler_sender(
+            epoch_store,
+            Arc::new(CheckpointServiceNoop {}),
+            ExecutionSchedulerSender::discarding(),
+            cache_reader,
+            committee,
+            metrics.clone(),
+            Arc::new(ConsensusThroughputCalculator::new(None, metrics)),
+            backpressure_subscriber,
+            None,
+            None,
+            Arc::new(ConsensusGaslessCounter::default()),
+            transaction_deny_config_manager,
+        )
+    }
+}
+
 #[derive(Default)]
 struct CommitHandlerInput {
     user_transactions: Vec<VerifiedExecutableTransactionWithAliases>,
@@ -1084,6 +1145,11 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
 
         let (timestamp, leader_author, commit_sub_dag_index) =
             self.gather_commit_metadata(&consensus_commit);
+        let commit_span = commit_trace::commit_span(
+            epoch,
+            commit_info.round,
+            commit_info.consensus_commit_ref.index.into(),
+        );
 
         info!(
             %consensus_commit,
@@ -1103,6 +1169,19 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             .with_label_values(&[&leader_author.to_string()])
             .inc();
 
+        if let Some(own_index) = self
+            .epoch_store
+            .committee()
+            .authority_index(&self.epoch_store.name)
+        {
+            let included = transactions
+                .iter()
+                .any(|(block, _)| block.author.value() == own_index as usize);
+            self.epoch_store
+                .consensus_participation
+                .record_commit(included);
+        }
+
         let mut state = CommitHandlerState::new(&self.epoch_store, commit_info.round);
 
         let FilteredConsensusOutput {
@@ -1142,6 +1221,12 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             new_jwks,
             transaction_deny_config_updates,
         } = self.build_commit_handler_input(transactions);
+        commit_span.in_scope(|| {
+            commit_trace::record_transactions(
+                "consensus_handler",
+                user_transactions.iter().map(|tx| tx.tx().digest()),
+            )
+        });
 
         self.process_gasless_transactions(&commit_info, &user_transactions);
         self.process_jwks(&mut state, &commit_info, new_jwks);
@@ -1223,14 +1308,16 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             .collect();
 
         let num_schedulables = schedulables.len();
-        let checkpoint_height = self.create_pending_checkpoints(
-            &mut state,
-            &commit_info,
-            schedulables,
-            randomness_schedulables,
-            &cancelled_txns,
-            final_round,
-        );
+        let checkpoint_height = commit_span.in_scope(|| {
+            self.create_pending_checkpoints(
+                &mut state,
+                &commit_info,
+                schedulables,
+                randomness_schedulables,
+                &cancelled_txns,
+                final_round,
+            )
+        });
 
         let notifications = state.get_notifications();
 
@@ -1593,16 +1680,29 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
         let randomness_schedulables_for_version_assignment =
             Chunk::all_schedulables_from(&chunked_randomness_schedulables);
 
-        let assigned_versions = self
-            .epoch_store
-            .process_consensus_transaction_shared_object_versions(
-                self.cache_reader.as_ref(),
-                schedulables_for_version_assignment,
-                randomness_schedulables_for_version_assignment,
-                cancelled_txns,
-                &mut state.output,
-            )
-            .expect("failed to assign shared object versions");
+        let assigned_versions = commit_trace::stage_span(
+            "version_assignment",
+            Some(commit_info.round),
+            Some(commit_info.consensus_commit_ref.index.into()),
+        )
+        .in_scope(|| {
+            commit_trace::record_transactions(
+                "version_assignment",
+                schedulables_for_version_assignment
+                    .clone()
+                    .chain(randomness_schedulables_for_version_assignment.clone())
+                    .filter_map(|s| s.as_tx().map(|tx| tx.digest())),
+            );
+            self.epoch_store
+                .process_consensus_transaction_shared_object_versions(
+                    self.cache_reader.as_ref(),
+                    schedulables_for_version_assignment,
+                    randomness_schedulables_for_version_assignment,
+                    cancelled_txns,
+                    &mut state.output,
+                )
+                .expect("failed to assign shared object versions")
+        });
 
         let consensus_commit_prologue =
             self.add_consensus_commit_prologue_transaction(state, commit_info, &assigned_versions);
@@ -2002,8 +2102,12 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
         HashMap<TransactionDigest, DeferralKey>,
     ) {
         let mut previously_deferred_tx_digests = HashMap::new();
+        let fair_ordering = self
+            .epoch_store
+            .protocol_config()
+            .fair_deferred_transaction_ordering();
 
-        let deferred_txs: Vec<_> = self
+        let mut deferred_txs: Vec<_> = self
             .epoch_store
             .load_deferred_transactions_for_up_to_consensus_round_v2(
                 &mut state.output,
@@ -2017,13 +2121,20 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                 tx
             })
             .collect();
+        if fair_ordering {
+            // Senders take turns, starting from the one whose transaction has waited longest.
+            deferred_txs.sort_by_key(|tx| {
+                previously_deferred_tx_digests[tx.tx().digest()].deferred_from_round()
+            });
+            deferred_txs = PostConsensusTxReorder::round_robin_by_sender(deferred_txs);
+        }
         trace!(
             "loading deferred transactions: {:?}",
             deferred_txs.iter().map(|tx| tx.tx().digest())
         );
 
         let deferred_randomness_txs = if state.dkg_failed || state.randomness_round.is_some() {
-            let txns: Vec<_> = self
+            let mut txns: Vec<_> = self
                 .epoch_store
                 .load_deferred_transactions_for_randomness_v2(&mut state.output)
                 .expect("db error")
@@ -2034,6 +2145,9 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                     tx
                 })
                 .collect();
+            if fair_ordering {
+                txns = PostConsensusTxReorder::round_robin_by_sender(txns);
+            }
             trace!(
                 "loading deferred randomness transactions: {:?}",
                 txns.iter().map(|tx| tx.tx().digest())
@@ -3150,6 +3264,12 @@ impl ExecutionSchedulerSender {
         Self { sender }
     }
 
+    /// A sender that drops everything sent to it.
+    fn discarding() -> Self {
+        let (sender, _) = monitored_mpsc::unbounded_channel("execution_scheduler_sender_discarded");
+        Self { sender }
+    }
+
     fn send(
         &self,
         transactions: Vec<(Schedulable, AssignedVersions)>,
@@ -3165,6 +3285,13 @@ impl ExecutionSchedulerSender {
     ) {
         while let Some((transactions, settlement)) = recv.recv().await {
             let _guard = monitored_scope("ConsensusHandler::enqueue");
+            let _span = commit_trace::stage_span("execution_scheduler", None, None).entered();
+            commit_trace::record_transactions(
+                "execution_scheduler",
+                transactions
+                    .iter()
+                    .filter_map(|(s, _)| s.as_tx().map(|tx| tx.digest())),
+            );
             let txns = transactions
                 .into_iter()
                 .map(|(txn, versions)| (txn, ExecutionEnv::new().with_assigned_versions(versions)))
@@ -3199,6 +3326,7 @@ impl MysticetiConsensusHandler {
         mut consensus_handler: ConsensusHandler<CheckpointService>,
         mut commit_receiver: UnboundedReceiver<consensus_core::CommittedSubDag>,
         commit_consumer_monitor: Arc<CommitConsumerMonitor>,
+        commit_latency: Option<CommitLatencyConfig>,
     ) -> Self {
         debug!(
             last_processed_commit_at_startup,
@@ -3206,6 +3334,25 @@ impl MysticetiConsensusHandler {
         );
         let mut tasks = JoinSet::new();
 
+        // Injected latency (local networks only): holds every new commit back for the configured
+        // latency before it reaches the stages below.
+        let chain = consensus_handler.epoch_store.get_chain();
+        if let Some(commit_latency) = local_commit_latency(commit_latency, chain) {
+            warn!(
+                ?commit_latency,
+                "Delaying the handling of consensus commits"
+            );
+            let (delayed_sender, delayed_receiver) =
+                monitored_mpsc::unbounded_channel("consensus_delayed_commits");
+            tasks.spawn(monitored_future!(delay_commits(
+                commit_latency,
+                last_processed_commit_at_startup,
+                commit_receiver,
+                delayed_sender,
+            )));
+            commit_receiver = delayed_receiver;
+        }
+
         // Stage 1 — deserialize worker: BCS-parses each commit's transactions off the handler's
         // critical path, so parsing overlaps the handler processing the previous commit. The
         // channel is bounded (small) so the worker prepares ~1 commit ahead but applies
@@ -3257,6 +3404,71 @@ impl MysticetiConsensusHandler {
     }
 }
 
+/// The injected commit latency to apply on `chain`. It is only honored on local networks, so a
+/// config copied to a public network is ignored instead of slowing the validator down.
+fn local_commit_latency(
+    commit_latency: Option<CommitLatencyConfig>,
+    chain: Chain,
+) -> Option<CommitLatencyConfig> {
+    let commit_latency = commit_latency?;
+    if chain != Chain::Unknown {
+        error!(
+            ?commit_latency,
+            ?chain,
+            "Ignoring consensus commit latency, which is only allowed on local networks"
+        );
+        return None;
+    }
+    Some(commit_latency)
+}
+
+/// Forwards each commit from `receiver` to `sender` once its injected latency has passed since it
+/// was received. Commits are timestamped on arrival and held concurrently, so the latency delays
+/// them without limiting how many are handled per second. Replayed commits are not delayed. Once
+/// `receiver` is closed, the commits still held are forwarded before returning.
+async fn delay_commits(
+    commit_latency: CommitLatencyConfig,
+    last_processed_commit_at_startup: CommitIndex,
+    mut receiver: UnboundedReceiver<consensus_core::CommittedSubDag>,
+    sender: monitored_mpsc::UnboundedSender<consensus_core::CommittedSubDag>,
+) {
+    let mut pending: VecDeque<(Instant, consensus_core::CommittedSubDag)> = VecDeque::new();
+    let mut last_deadline = Instant::now();
+    let mut closed = false;
+    loop {
+        let next_deadline = pending.front().map(|(deadline, _)| *deadline);
+        if closed && next_deadline.is_none() {
+            break;
+        }
+        tokio::select! {
+            commit = receiver.recv(), if !closed => {
+                let Some(commit) = commit else {
+                    closed = true;
+                    continue;
+                };
+                let commit_index = commit.commit_ref.index;
+                let delay = if commit_index <= last_processed_commit_at_startup {
+                    Duration::ZERO
+                } else {
+                    commit_latency.delay(commit_index)
+                };
+                // Jitter must not reorder commits, so a commit is never released before the one
+                // ahead of it.
+                last_deadline = last_deadline.max(Instant::now() + delay);
+                pending.push_back((last_deadline, commit));
+            }
+            _ = tokio::time::sleep_until(next_deadline.unwrap_or(last_deadline)),
+                if next_deadline.is_some() =>
+            {
+                let (_, commit) = pending.pop_front().expect("a commit is pending");
+                if sender.send(commit).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
 fn authenticator_state_update_transaction(
     epoch_store: &AuthorityPerEpochStore,
     round: u64,
@@ -3598,7 +3810,7 @@ mod tests {
         base_types::ExecutionDigests,
         base_types::{AuthorityName, FullObjectRef, ObjectID, SuiAddress, random_object_ref},
         committee::Committee,
-        crypto::deterministic_random_account_key,
+        crypto::{AccountKeyPair, deterministic_random_account_key, get_account_key_pair},
         gas::GasCostSummary,
         message_envelope::Message,
         messages_checkpoint::{
@@ -4419,6 +4631,166 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_round_robin_by_sender() {
+        let (a, b, c) = (
+            get_account_key_pair(),
+            get_account_key_pair(),
+            get_account_key_pair(),
+        );
+        let v = vec![
+            user_txn_from(&a, 1),
+            user_txn_from(&a, 2),
+            user_txn_from(&a, 3),
+            user_txn_from(&b, 4),
+            user_txn_from(&c, 5),
+            user_txn_from(&b, 6),
+        ];
+        assert_eq!(
+            to_short_strings(PostConsensusTxReorder::round_robin_by_sender(v)),
+            vec![
+                "transaction(1)".to_string(),
+                "transaction(4)".to_string(),
+                "transaction(5)".to_string(),
+                "transaction(2)".to_string(),
+                "transaction(6)".to_string(),
+                "transaction(3)".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_load_deferred_transactions_fair_ordering() {
+        let (a, b) = (get_account_key_pair(), get_account_key_pair());
+
+        for (fair_ordering, expected) in [(false, [4, 5, 1, 2, 3]), (true, [1, 4, 2, 5, 3])] {
+            let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+            protocol_config.set_fair_deferred_transaction_ordering_for_testing(fair_ordering);
+            let state = TestAuthorityBuilder::new()
+                .with_protocol_config(protocol_config)
+                .build()
+                .await;
+            let epoch_store = state.epoch_store_for_testing();
+
+            // `a` has waited longer, but `b`'s transactions are due first, so they come first in
+            // key order.
+            epoch_store.insert_deferred_transactions_for_test(
+                DeferralKey::new_for_consensus_round(5, 1),
+                vec![
+                    user_txn_from(&a, 1),
+                    user_txn_from(&a, 2),
+                    user_txn_from(&a, 3),
+                ],
+            );
+            epoch_store.insert_deferred_transactions_for_test(
+                DeferralKey::new_for_consensus_round(4, 3),
+                vec![user_txn_from(&b, 4), user_txn_from(&b, 5)],
+            );
+
+            let setup = setup_consensus_handler_for_testing(&state).await;
+            let mut commit_state = CommitHandlerState::new(&epoch_store, 10);
+            let (txns, randomness_txns, previously_deferred) =
+                setup.consensus_handler.load_deferred_transactions(
+                    &mut commit_state,
+                    &ConsensusCommitInfo::new_for_test(10, 0, None, true),
+                );
+
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|gas_price| format!("transaction({gas_price})"))
+                .collect();
+            assert_eq!(
+                to_short_strings(txns),
+                expected,
+                "fair_ordering: {fair_ordering}"
+            );
+            assert!(randomness_txns.is_empty());
+            assert_eq!(previously_deferred.len(), 5);
+        }
+    }
+
+    #[test]
+    fn test_commit_latency_only_on_local_networks() {
+        let commit_latency = CommitLatencyConfig {
+            latency_ms: 100,
+            jitter_ms: 50,
+            seed: 7,
+        };
+        assert_eq!(
+            local_commit_latency(Some(commit_latency.clone()), Chain::Unknown),
+            Some(commit_latency.clone())
+        );
+        for chain in [Chain::Mainnet, Chain::Testnet] {
+            assert_eq!(
+                local_commit_latency(Some(commit_latency.clone()), chain),
+                None
+            );
+        }
+        assert_eq!(local_commit_latency(None, Chain::Unknown), None);
+
+        // The jitter of a commit is the same every time, and within the configured bound.
+        for index in 0..100 {
+            let delay = commit_latency.delay(index);
+            assert_eq!(delay, commit_latency.delay(index));
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(150));
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_delay_commits() {
+        let commit_latency = CommitLatencyConfig {
+            latency_ms: 100,
+            jitter_ms: 1000,
+            seed: 7,
+        };
+        let (sender, receiver) = monitored_mpsc::unbounded_channel("test_commits");
+        let (delayed_sender, mut delayed_receiver) =
+            monitored_mpsc::unbounded_channel("test_delayed_commits");
+        let task = tokio::spawn(delay_commits(
+            commit_latency.clone(),
+            5,
+            receiver,
+            delayed_sender,
+        ));
+
+        let start = Instant::now();
+        for index in 1..=10 {
+            sender
+                .send(CommittedSubDag::new(
+                    BlockRef::MIN,
+                    vec![],
+                    0,
+                    CommitRef::new(index, CommitDigest::MIN),
+                ))
+                .unwrap();
+        }
+        drop(sender);
+
+        let mut released = vec![];
+        while let Some(commit) = delayed_receiver.recv().await {
+            released.push((commit.commit_ref.index, start.elapsed()));
+        }
+        task.await.unwrap();
+
+        // Every commit is released, in order, even where jitter gives a later commit a shorter
+        // delay than an earlier one.
+        let indices: Vec<_> = released.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, (1..=10).collect::<Vec<_>>());
+        assert!(released.windows(2).all(|w| w[0].1 <= w[1].1));
+
+        for (index, elapsed) in released {
+            if index <= 5 {
+                // Commits replayed at startup are not delayed.
+                assert!(
+                    elapsed < Duration::from_millis(commit_latency.latency_ms),
+                    "commit {index}"
+                );
+            } else {
+                assert!(elapsed >= commit_latency.delay(index), "commit {index}");
+            }
+        }
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn test_checkpoint_signature_dedup() {
         telemetry_subscribers::init_for_testing();
@@ -4783,18 +5155,24 @@ mod tests {
     }
 
     fn user_txn(gas_price: u64) -> VerifiedExecutableTransactionWithAliases {
+        user_txn_from(&deterministic_random_account_key(), gas_price)
+    }
+
+    fn user_txn_from(
+        (sender, sender_keypair): &(SuiAddress, AccountKeyPair),
+        gas_price: u64,
+    ) -> VerifiedExecutableTransactionWithAliases {
         let (committee, keypairs) = Committee::new_simple_test_committee();
-        let (sender, sender_keypair) = deterministic_random_account_key();
         let tx = sui_types::transaction::Transaction::from_data_and_signer(
             TransactionData::new_transfer(
                 SuiAddress::default(),
                 FullObjectRef::from_fastpath_ref(random_object_ref()),
-                sender,
+                *sender,
                 random_object_ref(),
                 1000 * gas_price,
                 gas_price,
             ),
-            vec![&sender_keypair],
+            vec![sender_keypair],
         );
         let tx = VerifiedExecutableTransaction::new_from_certificate(
             VerifiedCertificate::new_unchecked(
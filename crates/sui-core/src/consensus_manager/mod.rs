@@ -315,6 +315,7 @@ impl ConsensusManager {
             consensus_handler,
             commit_receiver,
             monitor.clone(),
+            consensus_config.commit_latency.clone(),
         );
         let mut consensus_handler = self.consensus_handler.lock().await;
         *consensus_handler = Some(handler);
@@ -360,6 +361,7 @@ impl ConsensusManager {
         )
         .await;
         let client = authority.transaction_client();
+        epoch_store.set_consensus_store(&authority.store());
 
         let registry_id = self.registry_service.add(registry.clone());
 
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+/// Number of most recent consensus commits over which participation is measured.
+const PARTICIPATION_WINDOW_COMMITS: usize = 1_000;
+
+/// Tracks how often blocks proposed by this validator make it into consensus commits.
+///
+/// Each commit is recorded as included if its sub-dag contains at least one block authored by
+/// this validator. A healthy validator has blocks in nearly every commit; a low rate means its
+/// proposals are not reaching or being accepted by the rest of the committee.
+pub struct ConsensusParticipation {
+    window: usize,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    commits: VecDeque<bool>,
+    included: usize,
+}
+
+impl Default for ConsensusParticipation {
+    fn default() -> Self {
+        Self::new(PARTICIPATION_WINDOW_COMMITS)
+    }
+}
+
+impl ConsensusParticipation {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub fn record_commit(&self, included: bool) {
+        let mut inner = self.inner.lock();
+        inner.commits.push_back(included);
+        inner.included += included as usize;
+        if inner.commits.len() > self.window && inner.commits.pop_front() == Some(true) {
+            inner.included -= 1;
+        }
+    }
+
+    /// Fraction of the commits in the window that included a block from this validator, or
+    /// `None` if no commit has been recorded yet.
+    pub fn rate(&self) -> Option<f64> {
+        let inner = self.inner.lock();
+        (!inner.commits.is_empty()).then(|| inner.included as f64 / inner.commits.len() as f64)
+    }
+
+    /// Number of commits currently in the window.
+    pub fn commits(&self) -> usize {
+        self.inner.lock().commits.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_over_window() {
+        let participation = ConsensusParticipation::new(4);
+        assert_eq!(participation.rate(), None);
+
+        for included in [true, false, true, true] {
+            participation.record_commit(included);
+        }
+        assert_eq!(participation.rate(), Some(0.75));
+
+        // The oldest commit (included) falls out of the window.
+        participation.record_commit(false);
+        assert_eq!(participation.commits(), 4);
+        assert_eq!(participation.rate(), Some(0.5));
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operator-defined rules for voting to reject transactions in consensus.
+//!
+//! When voting on the user transactions of a block, `SuiTxValidator` consults every
+//! [`ConsensusRejectionPolicy`] installed on the authority, and votes to reject a transaction if
+//! any of them rejects it. The reason is recorded like any other rejection vote, so it is reported
+//! back to the submitter. Policies can be installed and removed at runtime, e.g. to block a
+//! package that is being exploited for the duration of an incident.
+//!
+//! The node installs a [`BlockedPackagesPolicy`] at start-up. Packages blocked or unblocked
+//! through the admin interface are persisted, so they survive a restart.
+//!
+//! Votes do not need to agree across validators: a transaction is only rejected if enough stake
+//! votes to reject it.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use parking_lot::RwLock;
+use prometheus::{IntCounterVec, Registry, register_int_counter_vec_with_registry};
+use sui_types::base_types::ObjectID;
+use sui_types::error::{SuiErrorKind, SuiResult, UserInputError};
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+use tracing::info;
+
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+
+/// A rule that can vote to reject transactions in consensus.
+pub trait ConsensusRejectionPolicy: Send + Sync {
+    /// Identifies the policy in rejection reasons, metrics and the admin interface.
+    fn name(&self) -> &str;
+
+    /// Returns the reason to reject `tx`, or `None` to leave the vote to the other checks.
+    fn check(&self, tx: &TransactionData) -> Option<String>;
+}
+
+pub struct ConsensusRejectionPolicies {
+    policies: ArcSwap<Vec<Arc<dyn ConsensusRejectionPolicy>>>,
+    rejections: IntCounterVec,
+}
+
+impl ConsensusRejectionPolicies {
+    /// Create an empty set of policies. Policies are added with [`Self::install`].
+    pub fn new(registry: &Registry) -> Arc<Self> {
+        Arc::new(Self {
+            policies: ArcSwap::from_pointee(vec![]),
+            rejections: register_int_counter_vec_with_registry!(
+                "consensus_rejection_policy_rejections",
+                "Number of transactions voted to be rejected by each consensus rejection policy",
+                &["policy"],
+                registry,
+            )
+            .unwrap(),
+        })
+    }
+
+    /// Install `policy`, replacing any installed policy with the same name.
+    pub fn install(&self, policy: Arc<dyn ConsensusRejectionPolicy>) {
+        info!(
+            policy = policy.name(),
+            "Installing consensus rejection policy"
+        );
+        self.policies.rcu(|policies| {
+            let mut policies: Vec<_> = policies
+                .iter()
+                .filter(|p| p.name() != policy.name())
+                .cloned()
+                .collect();
+            policies.push(policy.clone());
+            policies
+        });
+    }
+
+    /// Remove the policy called `name`. Returns whether it was installed.
+    pub fn uninstall(&self, name: &str) -> bool {
+        let previous = self.policies.rcu(|policies| {
+            policies
+                .iter()
+                .filter(|p| p.name() != name)
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+
+        let removed = previous.iter().any(|p| p.name() == name);
+        if removed {
+            info!(policy = name, "Uninstalled consensus rejection policy");
+        }
+        removed
+    }
+
+    /// The names of the installed policies, in the order they are consulted.
+    pub fn names(&self) -> Vec<String> {
+        self.policies
+            .load()
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect()
+    }
+
+    /// Check `tx` against every installed policy, returning a
+    /// `UserInputError::TransactionDeniedByPolicy` from the first one that rejects it.
+    pub fn check(&self, tx: &TransactionData) -> SuiResult {
+        for policy in self.policies.load().iter() {
+            if let Some(reason) = policy.check(tx) {
+                self.rejections.with_label_values(&[policy.name()]).inc();
+                return Err(SuiErrorKind::UserInputError {
+                    error: UserInputError::TransactionDeniedByPolicy {
+                        rule: policy.name().to_string(),
+                        reason,
+                    },
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions that call a function in any blocked package.
+pub struct BlockedPackagesPolicy {
+    packages: RwLock<BTreeSet<ObjectID>>,
+    perpetual: Option<Arc<AuthorityPerpetualTables>>,
+}
+
+impl BlockedPackagesPolicy {
+    pub const NAME: &'static str = "blocked-packages";
+
+    /// A policy blocking `packages`, whose changes are not persisted.
+    pub fn new(packages: &[ObjectID]) -> Self {
+        Self {
+            packages: RwLock::new(packages.iter().copied().collect()),
+            perpetual: None,
+        }
+    }
+
+    /// A policy blocking the `configured` packages, with the changes persisted in `perpetual` by
+    /// earlier calls to [`Self::block`] and [`Self::unblock`] applied on top. Later changes are
+    /// persisted too.
+    pub fn load(
+        configured: &[ObjectID],
+        perpetual: Arc<AuthorityPerpetualTables>,
+    ) -> SuiResult<Self> {
+        let mut packages: BTreeSet<_> = configured.iter().copied().collect();
+        for entry in perpetual.consensus_blocked_packages.safe_iter() {
+            let (package, blocked) = entry?;
+            if blocked {
+                packages.insert(package);
+            } else {
+                packages.remove(&package);
+            }
+        }
+
+        Ok(Self {
+            packages: RwLock::new(packages),
+            perpetual: Some(perpetual),
+        })
+    }
+
+    /// Block `package`. Returns whether it was not blocked already.
+    pub fn block(&self, package: ObjectID) -> SuiResult<bool> {
+        let mut packages = self.packages.write();
+        self.persist(package, true)?;
+        let blocked = packages.insert(package);
+        if blocked {
+            info!(%package, "Blocking package in consensus");
+        }
+        Ok(blocked)
+    }
+
+    /// Unblock `package`. Returns whether it was blocked.
+    pub fn unblock(&self, package: ObjectID) -> SuiResult<bool> {
+        let mut packages = self.packages.write();
+        self.persist(package, false)?;
+        let unblocked = packages.remove(&package);
+        if unblocked {
+            info!(%package, "Unblocking package in consensus");
+        }
+        Ok(unblocked)
+    }
+
+    fn persist(&self, package: ObjectID, blocked: bool) -> SuiResult {
+        if let Some(perpetual) = &self.perpetual {
+            perpetual
+                .consensus_blocked_packages
+                .insert(&package, &blocked)?;
+        }
+        Ok(())
+    }
+
+    pub fn packages(&self) -> Vec<ObjectID> {
+        self.packages.read().iter().copied().collect()
+    }
+}
+
+impl ConsensusRejectionPolicy for BlockedPackagesPolicy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn check(&self, tx: &TransactionData) -> Option<String> {
+        let packages = self.packages.read();
+        if packages.is_empty() {
+            return None;
+        }
+
+        tx.move_calls()
+            .into_iter()
+            .find(|(_, package, _, _)| packages.contains(package))
+            .map(|(_, package, module, function)| {
+                format!("Calls {package}::{module}::{function}, which is blocked")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{SuiAddress, random_object_ref};
+    use sui_types::error::SuiError;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::{Identifier, TypeTag};
+
+    fn call_tx(package: ObjectID) -> TransactionData {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            package,
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            Vec::<TypeTag>::new(),
+            vec![],
+        );
+        TransactionData::new_programmable(
+            SuiAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000_000,
+            1_000,
+        )
+    }
+
+    struct RejectAll;
+
+    impl ConsensusRejectionPolicy for RejectAll {
+        fn name(&self) -> &str {
+            "reject-all"
+        }
+
+        fn check(&self, _tx: &TransactionData) -> Option<String> {
+            Some("Rejecting everything".to_string())
+        }
+    }
+
+    fn rejected_by(result: SuiResult) -> Option<String> {
+        match result.map_err(SuiError::into_inner) {
+            Ok(()) => None,
+            Err(SuiErrorKind::UserInputError {
+                error: UserInputError::TransactionDeniedByPolicy { rule, .. },
+            }) => Some(rule),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn blocked_packages_can_be_changed_at_runtime() {
+        let package = ObjectID::random();
+        let blocked = Arc::new(BlockedPackagesPolicy::new(&[package]));
+        let policies = ConsensusRejectionPolicies::new(&Registry::new());
+        policies.install(blocked.clone());
+
+        assert_eq!(
+            rejected_by(policies.check(&call_tx(package))).as_deref(),
+            Some(BlockedPackagesPolicy::NAME)
+        );
+        assert_eq!(
+            rejected_by(policies.check(&call_tx(ObjectID::random()))),
+            None
+        );
+
+        assert!(blocked.unblock(package).unwrap());
+        assert_eq!(rejected_by(policies.check(&call_tx(package))), None);
+    }
+
+    #[test]
+    fn blocked_packages_are_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let perpetual = Arc::new(AuthorityPerpetualTables::open(dir.path(), None, None));
+        let configured = ObjectID::random();
+        let added = ObjectID::random();
+
+        let policy = BlockedPackagesPolicy::load(&[configured], perpetual.clone()).unwrap();
+        assert_eq!(policy.packages(), vec![configured]);
+        assert!(policy.block(added).unwrap());
+        assert!(!policy.block(added).unwrap());
+        assert!(policy.unblock(configured).unwrap());
+
+        // Reloading, as on restart, keeps the changes made at runtime, even the ones that undo
+        // the configured list.
+        let reloaded = BlockedPackagesPolicy::load(&[configured], perpetual).unwrap();
+        assert_eq!(reloaded.packages(), vec![added]);
+    }
+
+    #[test]
+    fn installed_policies_are_consulted() {
+        let policies = ConsensusRejectionPolicies::new(&Registry::new());
+        let tx = call_tx(ObjectID::random());
+        assert!(policies.names().is_empty());
+
+        policies.install(Arc::new(BlockedPackagesPolicy::new(&[])));
+        policies.install(Arc::new(RejectAll));
+        assert_eq!(
+            policies.names(),
+            vec![BlockedPackagesPolicy::NAME, "reject-all"]
+        );
+        assert_eq!(
+            rejected_by(policies.check(&tx)).as_deref(),
+            Some("reject-all")
+        );
+
+        assert!(policies.uninstall("reject-all"));
+        assert!(!policies.uninstall("reject-all"));
+        assert_eq!(rejected_by(policies.check(&tx)), None);
+    }
+}
@@ -0,0 +1,407 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays stored consensus commits into a shadow copy of the current epoch's store, and compares
+//! the consensus-determined state it produces with the live store's, to validate changes to the
+//! consensus handler against real traffic.
+//!
+//! The shadow store is a fresh store for the current epoch, holding none of its consensus output,
+//! and the epoch's commits are replayed into it from the first one, through a `ConsensusHandler`
+//! that neither schedules transactions for execution nor builds checkpoints. For every consensus
+//! message replayed, the shadow and live stores are then compared on whether it was processed, and
+//! on the next versions of the consensus objects it uses.
+//!
+//! Only commits the live node has already processed should be replayed, and some differences are
+//! expected:
+//!
+//! - The shadow store takes no part in DKG, so randomness is disabled in it, and transactions using
+//!   randomness are not assigned versions the way the live node assigned them. The randomness
+//!   state object is left out of the comparison.
+//! - Objects first used in the epoch by a replayed commit get their starting version from the live
+//!   object cache, which is past it if the live node has executed a transaction using the object.
+//! - Consensus does not store which transactions were rejected by voting, so all transactions in
+//!   commits loaded from the consensus store are replayed as accepted.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use consensus_core::storage::Store;
+use consensus_core::{CommitAPI, CommitRange, CommittedSubDag};
+use prometheus::Registry;
+use sui_types::SUI_RANDOMNESS_STATE_OBJECT_ID;
+use sui_types::base_types::{ConsensusObjectSequenceKey, SequenceNumber};
+use sui_types::error::{SuiErrorKind, SuiResult};
+use sui_types::transaction::TransactionDataAPI;
+use tracing::info;
+
+use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use crate::authority::backpressure::BackpressureManager;
+use crate::authority::{AuthorityMetrics, AuthorityState};
+use crate::checkpoints::CheckpointServiceNoop;
+use crate::consensus_handler::{ConsensusHandler, SequencedConsensusTransactionKey};
+use crate::consensus_types::consensus_output_api::ConsensusCommitAPI;
+
+pub struct ConsensusShadowReplay {
+    live: Arc<AuthorityPerEpochStore>,
+    shadow: Arc<AuthorityPerEpochStore>,
+    handler: ConsensusHandler<CheckpointServiceNoop>,
+    /// Index of the last commit replayed.
+    last_commit: u64,
+    commits_replayed: usize,
+    /// Messages in the replayed commits.
+    messages: BTreeSet<SequencedConsensusTransactionKey>,
+    /// Consensus objects used by the replayed transactions.
+    objects: BTreeSet<ConsensusObjectSequenceKey>,
+}
+
+#[derive(Debug, Default)]
+pub struct ShadowReplayReport {
+    pub commits_replayed: usize,
+    pub messages_checked: usize,
+    pub objects_checked: usize,
+    pub mismatches: Vec<ShadowMismatch>,
+}
+
+#[derive(Debug)]
+pub enum ShadowMismatch {
+    MessageProcessed {
+        key: SequencedConsensusTransactionKey,
+        live: bool,
+        shadow: bool,
+    },
+    NextObjectVersion {
+        object: ConsensusObjectSequenceKey,
+        live: Option<SequenceNumber>,
+        shadow: Option<SequenceNumber>,
+    },
+}
+
+impl ConsensusShadowReplay {
+    /// Opens a fresh shadow store for `state`'s current epoch under `shadow_path`, which should be
+    /// an empty directory that is deleted once the replay is done.
+    pub fn open(state: &AuthorityState, shadow_path: &Path) -> SuiResult<Self> {
+        let live = state.load_epoch_store_one_call_per_task().clone();
+        let epoch_start_configuration = state
+            .authority_store()
+            .get_epoch_start_configuration()?
+            .ok_or_else(|| {
+                SuiErrorKind::Storage("Epoch start configuration is missing".to_string())
+            })?;
+        let highest_executed_checkpoint = state
+            .get_checkpoint_store()
+            .get_highest_executed_checkpoint_seq_number()?
+            .unwrap_or_default();
+
+        let shadow = live.new_shadow(
+            shadow_path,
+            epoch_start_configuration,
+            state.get_backing_package_store().clone(),
+            state.get_object_store().clone(),
+            &state.config.expensive_safety_check_config,
+            highest_executed_checkpoint,
+        )?;
+
+        let handler = ConsensusHandler::new_for_shadow_replay(
+            shadow.clone(),
+            state.get_object_cache_reader().clone(),
+            Arc::new(AuthorityMetrics::new(&Registry::new())),
+            BackpressureManager::new_from_checkpoint_store(state.get_checkpoint_store())
+                .subscribe(),
+            state.transaction_deny_config_manager().clone(),
+        );
+
+        info!(
+            epoch = live.epoch(),
+            "Opened shadow epoch store for consensus replay"
+        );
+
+        Ok(Self {
+            live,
+            shadow,
+            handler,
+            last_commit: 0,
+            commits_replayed: 0,
+            messages: BTreeSet::new(),
+            objects: BTreeSet::new(),
+        })
+    }
+
+    /// Index of the last commit replayed, or 0 if none has been.
+    pub fn last_commit(&self) -> u64 {
+        self.last_commit
+    }
+
+    /// Replays a commit into the shadow store. Commits must be replayed in order, and those up to
+    /// the last commit replayed are skipped.
+    pub async fn replay(&mut self, commit: impl ConsensusCommitAPI) {
+        let index = commit.commit_sub_dag_index();
+        if index <= self.last_commit {
+            return;
+        }
+
+        let transactions = commit.transactions();
+        for parsed in transactions.iter().flat_map(|(_, txs)| txs) {
+            self.messages
+                .insert(SequencedConsensusTransactionKey::External(
+                    parsed.transaction.key(),
+                ));
+
+            if let Some(tx) = parsed.transaction.kind.as_user_transaction() {
+                self.objects.extend(
+                    tx.data()
+                        .transaction_data()
+                        .shared_input_objects()
+                        .into_iter()
+                        .filter(|obj| obj.id != SUI_RANDOMNESS_STATE_OBJECT_ID)
+                        .map(|obj| (obj.id, obj.initial_shared_version)),
+                );
+            }
+        }
+
+        self.handler
+            .handle_consensus_commit(commit, transactions)
+            .await;
+        self.last_commit = index;
+        self.commits_replayed += 1;
+    }
+
+    /// Replays the commits in `store` after the last commit replayed, up to and including
+    /// `end_commit`. Fails if any of them is missing from `store`.
+    pub async fn replay_from_store(&mut self, store: &dyn Store, end_commit: u32) -> SuiResult {
+        let start_commit = u32::try_from(self.last_commit + 1).unwrap_or(u32::MAX);
+        if start_commit > end_commit {
+            return Ok(());
+        }
+
+        let commits = store
+            .scan_commits(CommitRange::new(start_commit..=end_commit))
+            .map_err(|e| SuiErrorKind::Storage(e.to_string()))?;
+
+        let missing = |index| {
+            SuiErrorKind::Storage(format!(
+                "Commit {index} is missing from the consensus store"
+            ))
+        };
+
+        let mut next = start_commit as u64;
+        for commit in commits {
+            if commit.index() as u64 != next {
+                return Err(missing(next).into());
+            }
+            next += 1;
+
+            let blocks = store
+                .read_blocks(commit.blocks())
+                .map_err(|e| SuiErrorKind::Storage(e.to_string()))?
+                .into_iter()
+                .zip(commit.blocks())
+                .map(|(block, block_ref)| {
+                    block.ok_or_else(|| {
+                        SuiErrorKind::Storage(format!(
+                            "Block {block_ref} of commit {} is missing",
+                            commit.index()
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            self.replay(CommittedSubDag::new(
+                commit.leader(),
+                blocks,
+                commit.timestamp_ms(),
+                commit.reference(),
+            ))
+            .await;
+        }
+
+        if next <= end_commit as u64 {
+            return Err(missing(next).into());
+        }
+
+        Ok(())
+    }
+
+    /// Compares the shadow store with the live store, on the messages and objects of the commits
+    /// replayed so far.
+    pub fn compare(&self) -> SuiResult<ShadowReplayReport> {
+        let mut mismatches = vec![];
+
+        let live = self
+            .live
+            .check_consensus_messages_processed(self.messages.iter().cloned())?;
+        let shadow = self
+            .shadow
+            .check_consensus_messages_processed(self.messages.iter().cloned())?;
+        for ((key, live), shadow) in self.messages.iter().zip(live).zip(shadow) {
+            if live != shadow {
+                mismatches.push(ShadowMismatch::MessageProcessed {
+                    key: key.clone(),
+                    live,
+                    shadow,
+                });
+            }
+        }
+
+        let objects: Vec<_> = self.objects.iter().copied().collect();
+        let live = self.live.get_next_shared_object_versions(&objects)?;
+        let shadow = self.shadow.get_next_shared_object_versions(&objects)?;
+        for ((object, live), shadow) in objects.iter().zip(live).zip(shadow) {
+            if live != shadow {
+                mismatches.push(ShadowMismatch::NextObjectVersion {
+                    object: *object,
+                    live,
+                    shadow,
+                });
+            }
+        }
+
+        Ok(ShadowReplayReport {
+            commits_replayed: self.commits_replayed,
+            messages_checked: self.messages.len(),
+            objects_checked: objects.len(),
+            mismatches,
+        })
+    }
+}
+
+impl fmt::Display for ShadowReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Replayed {} commits, checked {} messages and {} objects",
+            self.commits_replayed, self.messages_checked, self.objects_checked,
+        )?;
+
+        if self.mismatches.is_empty() {
+            return writeln!(f, "No mismatches");
+        }
+
+        writeln!(f, "{} mismatches:", self.mismatches.len())?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "{mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ShadowMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShadowMismatch::MessageProcessed { key, live, shadow } => {
+                write!(f, "message {key:?}: processed live {live}, shadow {shadow}")
+            }
+            ShadowMismatch::NextObjectVersion {
+                object: (id, initial_shared_version),
+                live,
+                shadow,
+            } => write!(
+                f,
+                "object {id} (initial shared version {initial_shared_version}): next version \
+                 live {live:?}, shadow {shadow:?}",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use consensus_core::MemStore;
+    use sui_types::base_types::ObjectID;
+    use sui_types::crypto::deterministic_random_account_key;
+    use sui_types::messages_consensus::ConsensusTransaction;
+    use sui_types::object::Object;
+
+    use super::*;
+    use crate::authority::test_authority_builder::TestAuthorityBuilder;
+    use crate::consensus_adapter::consensus_tests::test_user_transaction;
+    use crate::consensus_test_utils::{TestConsensusCommit, setup_consensus_handler_for_testing};
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_shadow_replay() {
+        telemetry_subscribers::init_for_testing();
+
+        let (sender, keypair) = deterministic_random_account_key();
+        let gas_objects: Vec<_> = (0..2)
+            .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+            .collect();
+        let shared_object = Object::shared_for_testing();
+        let mut starting_objects = gas_objects.clone();
+        starting_objects.push(shared_object.clone());
+        let state = TestAuthorityBuilder::new()
+            .with_starting_objects(&starting_objects)
+            .build()
+            .await;
+
+        let mut consensus_transactions = vec![];
+        for gas_object in gas_objects {
+            let transaction = test_user_transaction(
+                &state,
+                sender,
+                &keypair,
+                gas_object,
+                vec![shared_object.clone()],
+            )
+            .await;
+            consensus_transactions.push(ConsensusTransaction::new_user_transaction_v2_message(
+                &state.name,
+                transaction.into(),
+            ));
+        }
+        let commit = |index: usize| {
+            let transactions = vec![consensus_transactions[index].clone()];
+            TestConsensusCommit::new(transactions, 100 + index as u64, 1_000, 1 + index as u64)
+        };
+
+        // The live node only processes the first commit.
+        let mut setup = setup_consensus_handler_for_testing(&state).await;
+        setup
+            .consensus_handler
+            .handle_consensus_commit_for_test(commit(0))
+            .await;
+
+        let shadow_dir = tempfile::tempdir().unwrap();
+        let mut replay = ConsensusShadowReplay::open(&state, shadow_dir.path()).unwrap();
+
+        // Replaying the same commit into the fresh shadow store gets to the same state.
+        replay.replay(commit(0)).await;
+        let report = replay.compare().unwrap();
+        assert_eq!(report.commits_replayed, 1);
+        assert_eq!(report.messages_checked, 1);
+        assert_eq!(report.objects_checked, 1);
+        assert!(report.mismatches.is_empty(), "{report}");
+
+        // Commits that were already replayed are skipped.
+        replay.replay(commit(0)).await;
+        assert_eq!(replay.last_commit(), 1);
+        assert_eq!(replay.compare().unwrap().commits_replayed, 1);
+
+        // A commit the live node has not processed shows up in both comparisons.
+        replay.replay(commit(1)).await;
+        let report = replay.compare().unwrap();
+        assert_eq!(report.commits_replayed, 2);
+        assert_eq!(report.messages_checked, 2);
+        assert_eq!(report.objects_checked, 1);
+        assert_eq!(report.mismatches.len(), 2, "{report}");
+        assert!(report.mismatches.iter().any(|mismatch| matches!(
+            mismatch,
+            ShadowMismatch::MessageProcessed {
+                live: false,
+                shadow: true,
+                ..
+            }
+        )));
+        assert!(report.mismatches.iter().any(|mismatch| matches!(
+            mismatch,
+            ShadowMismatch::NextObjectVersion { live, shadow, .. } if live < shadow
+        )));
+
+        // Commits missing from the consensus store are an error.
+        let err = replay
+            .replay_from_store(&MemStore::new(), 3)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Commit 3 is missing"), "{err}");
+    }
+}
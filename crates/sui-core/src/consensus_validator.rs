@@ -297,6 +297,14 @@ impl SuiTxValidator {
         // so they do not run in validate_transactions(). They can run there once we confirm it is safe.
         inner_tx.validity_check(&epoch_store.tx_validity_check_context())?;
 
+        // Operator-defined policies only apply to transactions that have not been finalized, like
+        // the other checks in handle_vote_transaction().
+        if !epoch_store.is_recently_finalized(inner_tx.digest()) {
+            self.authority_state
+                .consensus_rejection_policies()
+                .check(inner_tx.data().transaction_data())?;
+        }
+
         self.authority_state.check_system_overload(
             inner_tx.data(),
             self.authority_state.check_system_overload_at_signing(),
@@ -561,6 +569,7 @@ mod tests {
     use sui_config::transaction_deny_config::TransactionDenyConfigBuilder;
     use sui_macros::sim_test;
     use sui_protocol_config::ProtocolConfig;
+    use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
     use sui_types::crypto::deterministic_random_account_key;
     use sui_types::error::{SuiErrorKind, UserInputError};
     use sui_types::executable_transaction::VerifiedExecutableTransaction;
@@ -587,6 +596,7 @@ mod tests {
         consensus_adapter::consensus_tests::{
             test_gas_objects, test_user_transaction, test_user_transactions,
         },
+        consensus_rejection_policy::BlockedPackagesPolicy,
         consensus_validator::{SuiTxValidator, SuiTxValidatorMetrics},
     };
 
@@ -793,6 +803,87 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_vote_rejects_calls_to_blocked_packages() {
+        let (sender, keypair) = deterministic_random_account_key();
+        let gas_objects: Vec<Object> = (0..2)
+            .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), sender))
+            .collect();
+        let owned_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+
+        let mut objects = gas_objects.clone();
+        objects.push(owned_object.clone());
+
+        let network_config =
+            sui_swarm_config::network_config_builder::ConfigBuilder::new_with_temp_dir()
+                .committee_size(NonZeroUsize::new(1).unwrap())
+                .with_objects(objects)
+                .build();
+        let state = TestAuthorityBuilder::new()
+            .with_network_config(&network_config, 0)
+            .build()
+            .await;
+
+        let validator = SuiTxValidator::new(
+            state.clone(),
+            state.epoch_store_for_testing().clone(),
+            Arc::new(CheckpointServiceNoop {}),
+            SuiTxValidatorMetrics::new(&Default::default()),
+        );
+
+        // `test_user_transaction` calls into the framework package.
+        let validator = &validator;
+        let keypair = &keypair;
+        let vote = |gas: Object| {
+            let state = state.clone();
+            let owned_object = owned_object.clone();
+            async move {
+                let transaction =
+                    test_user_transaction(&state, sender, keypair, gas, vec![owned_object]).await;
+                let serialized =
+                    bcs::to_bytes(&ConsensusTransaction::new_user_transaction_v2_message(
+                        &state.name,
+                        transaction.into(),
+                    ))
+                    .unwrap();
+                validator
+                    .verify_and_vote_batch(&BlockRef::MAX, &[serialized.as_slice()])
+                    .unwrap()
+            }
+        };
+
+        // Nothing is installed until the node installs it.
+        assert_eq!(vote(gas_objects[0].clone()).await, Vec::<usize>::new());
+
+        let blocked = Arc::new(BlockedPackagesPolicy::new(&[SUI_FRAMEWORK_PACKAGE_ID]));
+        state
+            .consensus_rejection_policies()
+            .install(blocked.clone());
+        assert_eq!(vote(gas_objects[1].clone()).await, vec![0]);
+
+        let epoch_store = state.load_epoch_store_one_call_per_task();
+        let reason = epoch_store
+            .get_rejection_vote_reason(ConsensusPosition {
+                epoch: epoch_store.epoch(),
+                block: BlockRef::MAX,
+                index: 0,
+            })
+            .expect("Rejection vote reason should be set");
+        assert!(
+            matches!(
+                &reason,
+                SuiErrorKind::UserInputError {
+                    error: UserInputError::TransactionDeniedByPolicy { rule, .. },
+                } if rule == BlockedPackagesPolicy::NAME
+            ),
+            "Unexpected rejection reason: {reason:?}"
+        );
+
+        // Unblocking the package takes effect for the next vote.
+        assert!(blocked.unblock(SUI_FRAMEWORK_PACKAGE_ID).unwrap());
+        assert_eq!(vote(gas_objects[1].clone()).await, Vec::<usize>::new());
+    }
+
     #[sim_test]
     async fn accept_checkpoint_signature_v2() {
         let network_config =
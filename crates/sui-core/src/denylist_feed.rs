@@ -0,0 +1,329 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sync of the transaction policy with a signed feed of addresses and packages flagged for active
+//! exploits.
+//!
+//! The feed is fetched from the configured URL every poll interval, as a JSON
+//! [`SignedDenylistFeed`]: a Base64-encoded payload, and the Base64-encoded Ed25519 signature of
+//! the payload's bytes by the configured key. The payload is a JSON [`DenylistFeed`], whose
+//! entries become deny rules evaluated before the operator's rules (see
+//! [`TransactionDenyConfigManager::apply_denylist`]).
+//!
+//! Every entry expires at its `expires-at-ms`, whether or not the feed can still be fetched, so a
+//! feed that goes offline does not leave denials in place forever. A feed that fails to be
+//! fetched or verified keeps the previously applied one.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, anyhow};
+use fastcrypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::{ToFromBytes, VerifyingKey};
+use mysten_metrics::spawn_monitored_task;
+use serde::{Deserialize, Serialize};
+use sui_config::transaction_policy_config::{
+    DenylistFeedConfig, PolicyAction, TransactionPolicyRule,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use tracing::{info, warn};
+
+use crate::transaction_deny_config_manager::TransactionDenyConfigManager;
+
+/// Prefix of the names of the rules built from feed entries, in rejection errors and metrics.
+const RULE_NAME_PREFIX: &str = "denylist-feed:";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SignedDenylistFeed {
+    /// Base64-encoded JSON `DenylistFeed`.
+    pub payload: String,
+    /// Base64-encoded Ed25519 signature of the payload's bytes.
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct DenylistFeed {
+    /// Increases with every published feed. Feeds older than the applied one are rejected.
+    pub sequence: u64,
+    #[serde(default)]
+    pub entries: Vec<DenylistEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct DenylistEntry {
+    /// Unique within the feed, reported in rejection errors and metrics.
+    pub id: String,
+    /// Transactions sent by these addresses are denied.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub addresses: BTreeSet<SuiAddress>,
+    /// Transactions calling a function in these packages are denied.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub packages: BTreeSet<ObjectID>,
+    /// When the entry stops applying, in milliseconds since the Unix epoch.
+    pub expires_at_ms: u64,
+}
+
+impl SignedDenylistFeed {
+    /// Check the signature with `public_key`, and decode the payload.
+    pub fn verify(&self, public_key: &Ed25519PublicKey) -> anyhow::Result<DenylistFeed> {
+        let payload = Base64::decode(&self.payload).context("Invalid denylist feed payload")?;
+        let signature = Base64::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| Ed25519Signature::from_bytes(&bytes).ok())
+            .ok_or_else(|| anyhow!("Invalid denylist feed signature"))?;
+        public_key
+            .verify(&payload, &signature)
+            .map_err(|_| anyhow!("Denylist feed signature does not match the configured key"))?;
+        serde_json::from_slice(&payload).context("Invalid denylist feed")
+    }
+}
+
+impl DenylistFeed {
+    /// The deny rules of the feed's entries, with the times they expire at.
+    ///
+    /// A rule only matches transactions meeting all of its criteria, so each entry becomes a rule
+    /// for its addresses, named `denylist-feed:<ID>:senders`, and one for its packages, named
+    /// `denylist-feed:<ID>:packages`, denying transactions that meet either.
+    pub fn rules(&self) -> Vec<(TransactionPolicyRule, u64)> {
+        let mut rules = vec![];
+        for entry in &self.entries {
+            let deny = |criterion: &str| TransactionPolicyRule {
+                name: format!("{RULE_NAME_PREFIX}{}:{criterion}", entry.id),
+                action: PolicyAction::Deny,
+                ..Default::default()
+            };
+
+            if !entry.addresses.is_empty() {
+                let rule = TransactionPolicyRule {
+                    senders: entry.addresses.clone(),
+                    ..deny("senders")
+                };
+                rules.push((rule, entry.expires_at_ms));
+            }
+
+            if !entry.packages.is_empty() {
+                let rule = TransactionPolicyRule {
+                    packages: entry.packages.clone(),
+                    ..deny("packages")
+                };
+                rules.push((rule, entry.expires_at_ms));
+            }
+        }
+        rules
+    }
+}
+
+/// Spawn the task syncing `manager`'s denylist with the feed. Fails if the feed is misconfigured,
+/// before the task is spawned.
+pub fn spawn_denylist_feed_sync(
+    config: &DenylistFeedConfig,
+    manager: Arc<TransactionDenyConfigManager>,
+) -> anyhow::Result<()> {
+    reqwest::Url::parse(&config.url)
+        .with_context(|| format!("Invalid denylist feed URL {}", config.url))?;
+    let public_key = Base64::decode(&config.public_key)
+        .ok()
+        .and_then(|bytes| Ed25519PublicKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| anyhow!("Invalid denylist feed public key {}", config.public_key))?;
+    let client = reqwest::Client::builder()
+        .timeout(config.request_timeout())
+        .build()
+        .context("Failed to build denylist feed HTTP client")?;
+
+    info!(url = config.url, "Starting denylist feed sync");
+    spawn_monitored_task!(sync(config.clone(), public_key, client, manager));
+    Ok(())
+}
+
+async fn sync(
+    config: DenylistFeedConfig,
+    public_key: Ed25519PublicKey,
+    client: reqwest::Client,
+    manager: Arc<TransactionDenyConfigManager>,
+) {
+    let poll_interval = config.poll_interval();
+    let mut next_poll = tokio::time::Instant::now();
+    loop {
+        let now = tokio::time::Instant::now();
+        if now >= next_poll {
+            next_poll = now + poll_interval;
+            if let Err(e) = fetch_and_apply(&config, &public_key, &client, &manager).await {
+                warn!(
+                    url = config.url,
+                    "Failed to sync denylist feed, keeping the applied feed: {e:#}"
+                );
+            }
+        }
+
+        // Wake up early for the next expiry, so that expired rules are dropped on time.
+        let now_ms = now_ms();
+        manager.expire_denylist(now_ms);
+        let mut wake = next_poll;
+        if let Some(expiry) = manager.next_denylist_expiry() {
+            wake = wake.min(
+                tokio::time::Instant::now() + Duration::from_millis(expiry.saturating_sub(now_ms)),
+            );
+        }
+        tokio::time::sleep_until(wake).await;
+    }
+}
+
+async fn fetch_and_apply(
+    config: &DenylistFeedConfig,
+    public_key: &Ed25519PublicKey,
+    client: &reqwest::Client,
+    manager: &TransactionDenyConfigManager,
+) -> anyhow::Result<()> {
+    let signed: SignedDenylistFeed = client
+        .get(&config.url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let feed = signed.verify(public_key)?;
+    manager
+        .apply_denylist(feed.sequence, feed.rules(), now_ms())
+        .map_err(|e| anyhow!(e))?;
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use fastcrypto::traits::{KeyPair, Signer};
+    use sui_config::transaction_policy_config::TransactionPolicy;
+    use sui_transaction_checks::policy::{PolicyDecision, evaluate_transaction_policy};
+    use sui_types::base_types::random_object_ref;
+    use sui_types::crypto::get_key_pair;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::TransactionData;
+
+    use super::*;
+
+    fn sign(feed: &DenylistFeed, key_pair: &Ed25519KeyPair) -> SignedDenylistFeed {
+        let payload = serde_json::to_vec(feed).unwrap();
+        SignedDenylistFeed {
+            payload: Base64::encode(&payload),
+            signature: Base64::encode(key_pair.sign(&payload).as_ref()),
+        }
+    }
+
+    #[test]
+    fn verify_checks_signature() {
+        let (_, key_pair): (_, Ed25519KeyPair) = get_key_pair();
+        let (_, other): (_, Ed25519KeyPair) = get_key_pair();
+        let feed = DenylistFeed {
+            sequence: 7,
+            entries: vec![DenylistEntry {
+                id: "exploit-1".to_string(),
+                addresses: BTreeSet::from([SuiAddress::random_for_testing_only()]),
+                packages: BTreeSet::new(),
+                expires_at_ms: 1_000,
+            }],
+        };
+
+        let signed = sign(&feed, &key_pair);
+        assert_eq!(signed.verify(key_pair.public()).unwrap(), feed);
+        assert!(signed.verify(other.public()).is_err());
+
+        let tampered = SignedDenylistFeed {
+            payload: sign(
+                &DenylistFeed {
+                    sequence: 8,
+                    ..feed.clone()
+                },
+                &key_pair,
+            )
+            .payload,
+            ..signed
+        };
+        assert!(tampered.verify(key_pair.public()).is_err());
+
+        let (rule, expiry) = &feed.rules()[0];
+        assert_eq!(rule.name, "denylist-feed:exploit-1:senders");
+        assert_eq!(rule.action, PolicyAction::Deny);
+        assert_eq!(*expiry, 1_000);
+    }
+
+    fn move_call_tx(sender: SuiAddress, package: ObjectID) -> TransactionData {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                package,
+                "m".parse().unwrap(),
+                "f".parse().unwrap(),
+                vec![],
+                vec![],
+            )
+            .unwrap();
+        TransactionData::new_programmable(
+            sender,
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000_000,
+            1_000,
+        )
+    }
+
+    /// An entry with both addresses and packages denies transactions from its addresses, and
+    /// transactions calling its packages, not only transactions doing both.
+    #[test]
+    fn entry_denies_senders_and_packages_separately() {
+        let sender = SuiAddress::random_for_testing_only();
+        let package = ObjectID::random();
+        let feed = DenylistFeed {
+            sequence: 1,
+            entries: vec![DenylistEntry {
+                id: "exploit-1".to_string(),
+                addresses: BTreeSet::from([sender]),
+                packages: BTreeSet::from([package]),
+                expires_at_ms: 1_000,
+            }],
+        };
+
+        let rules = feed.rules();
+        assert_eq!(
+            rules
+                .iter()
+                .map(|(rule, expiry)| (rule.name.as_str(), *expiry))
+                .collect::<Vec<_>>(),
+            vec![
+                ("denylist-feed:exploit-1:senders", 1_000),
+                ("denylist-feed:exploit-1:packages", 1_000),
+            ],
+        );
+
+        let policy = TransactionPolicy {
+            default_action: PolicyAction::Allow,
+            rules: rules.into_iter().map(|(rule, _)| rule).collect(),
+        };
+        let decide = |tx: &TransactionData| match evaluate_transaction_policy(&policy, tx, &[], &[])
+        {
+            PolicyDecision::Allow => None,
+            PolicyDecision::Deny { rule, .. } => rule.map(str::to_owned),
+        };
+
+        let other_sender = SuiAddress::random_for_testing_only();
+        let other_package = ObjectID::random();
+        assert_eq!(
+            decide(&move_call_tx(sender, other_package)).as_deref(),
+            Some("denylist-feed:exploit-1:senders"),
+        );
+        assert_eq!(
+            decide(&move_call_tx(other_sender, package)).as_deref(),
+            Some("denylist-feed:exploit-1:packages"),
+        );
+        assert_eq!(decide(&move_call_tx(other_sender, other_package)), None);
+    }
+}
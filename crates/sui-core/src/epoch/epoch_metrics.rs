@@ -2,12 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    CounterVec, IntCounter, IntCounterVec, IntGauge, Registry, register_counter_vec_with_registry,
+    CounterVec, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Registry,
+    register_counter_vec_with_registry, register_histogram_vec_with_registry,
     register_int_counter_vec_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry,
 };
 use std::sync::Arc;
 
+// Lock waits are usually short, so these start from 10us.
+const LOCK_WAIT_SEC_BUCKETS: &[f64] = &[
+    0.00001, 0.00002, 0.00005, 0.0001, 0.0002, 0.0005, 0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1,
+    0.2, 0.5, 1., 2., 5., 10., 20., 50., 100.,
+];
+
 pub struct EpochMetrics {
     /// The current epoch ID. This is updated only when the AuthorityState finishes reconfiguration.
     pub current_epoch: IntGauge,
@@ -139,6 +146,23 @@ pub struct EpochMetrics {
 
     /// The number of shared object assignments in the quarantine.
     pub shared_object_assignments_size: IntGauge,
+
+    /// The number of outstanding bounded notify-read registrations, per notify-read and caller.
+    pub notify_read_outstanding_waiters: IntGaugeVec,
+
+    /// The number of bounded notify-read registrations or waits that were rejected or expired,
+    /// per notify-read, caller and reason.
+    pub notify_read_bounded_failures: IntCounterVec,
+
+    /// Time spent waiting to acquire locks from the epoch store's lock tables, per table.
+    pub mutex_table_lock_wait: HistogramVec,
+
+    /// The number of shards in each of the epoch store's lock tables.
+    pub mutex_table_shards: IntGaugeVec,
+
+    /// The fraction of lookups in each lock table that waited for their shard during the last
+    /// epoch, in basis points. Updated at the end of the epoch.
+    pub mutex_table_shard_contention_bps: IntGaugeVec,
 }
 
 impl EpochMetrics {
@@ -326,6 +350,43 @@ impl EpochMetrics {
                 registry
             )
             .unwrap(),
+            notify_read_outstanding_waiters: register_int_gauge_vec_with_registry!(
+                "notify_read_outstanding_waiters",
+                "The number of outstanding bounded notify-read registrations",
+                &["notify_read", "caller"],
+                registry
+            )
+            .unwrap(),
+            notify_read_bounded_failures: register_int_counter_vec_with_registry!(
+                "notify_read_bounded_failures",
+                "The number of bounded notify-read registrations or waits that were rejected or expired",
+                &["notify_read", "caller", "reason"],
+                registry
+            )
+            .unwrap(),
+            mutex_table_lock_wait: register_histogram_vec_with_registry!(
+                "mutex_table_lock_wait",
+                "Seconds spent waiting to acquire locks from each epoch store lock table",
+                &["table"],
+                LOCK_WAIT_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            mutex_table_shards: register_int_gauge_vec_with_registry!(
+                "mutex_table_shards",
+                "The number of shards in each epoch store lock table",
+                &["table"],
+                registry
+            )
+            .unwrap(),
+            mutex_table_shard_contention_bps: register_int_gauge_vec_with_registry!(
+                "mutex_table_shard_contention_bps",
+                "The fraction of lookups in each epoch store lock table that waited for their shard \
+                 during the last epoch, in basis points",
+                &["table"],
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
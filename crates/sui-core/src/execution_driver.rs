@@ -13,6 +13,9 @@ use tracing::{Instrument, error_span, info, trace, warn};
 
 use crate::authority::AuthorityState;
 use crate::execution_scheduler::PendingCertificate;
+use crate::execution_scheduler::sender_concurrency_limiter::{
+    SenderConcurrencyLimiter, SenderPermit,
+};
 
 #[cfg(test)]
 #[path = "unit_tests/execution_driver_tests.rs"]
@@ -20,43 +23,53 @@ mod execution_driver_tests;
 
 const QUEUEING_DELAY_SAMPLING_RATIO: f64 = 0.05;
 
+type SenderLimiter = (
+    Arc<SenderConcurrencyLimiter<PendingCertificate>>,
+    UnboundedReceiver<(PendingCertificate, SenderPermit<PendingCertificate>)>,
+);
+
 /// When a notification that a new pending transaction is received we activate
 /// processing the transaction in a loop.
+///
+/// If `sender_limiter` is set, ready transactions of an account that is at its concurrency limit
+/// are held back until one of the account's executing transactions finishes.
 pub async fn execution_process(
     authority_state: Weak<AuthorityState>,
     mut rx_ready_certificates: UnboundedReceiver<PendingCertificate>,
     mut rx_execution_shutdown: oneshot::Receiver<()>,
+    sender_limiter: Option<SenderLimiter>,
 ) {
     info!("Starting pending certificates execution process.");
 
     // Rate limit concurrent executions to # of cpus.
     let limit = Arc::new(Semaphore::new(num_cpus::get()));
 
+    let (sender_limiter, mut rx_released) = sender_limiter.unzip();
+
     // Loop whenever there is a signal that a new transactions is ready to process.
     loop {
         let _scope = monitored_scope("ExecutionDriver::loop");
 
-        let certificate;
-        let execution_env;
-        let txn_ready_time;
-        let _executing_guard;
-        tokio::select! {
+        let (pending_cert, released_permit) = tokio::select! {
+            biased;
+            _ = &mut rx_execution_shutdown => {
+                info!("Shutdown signal received. Exiting executor ...");
+                return;
+            }
+            // Transactions released by the sender limiter go ahead of newly ready ones, so
+            // throttled senders are not starved.
+            Some((pending_cert, permit)) = recv_released(&mut rx_released) => {
+                (pending_cert, Some(permit))
+            }
             result = rx_ready_certificates.recv() => {
                 if let Some(pending_cert) = result {
-                    certificate = pending_cert.certificate;
-                    execution_env = pending_cert.execution_env;
-                    txn_ready_time = pending_cert.stats.ready_time.unwrap();
-                    _executing_guard = pending_cert.executing_guard;
+                    (pending_cert, None)
                 } else {
                     // Should only happen after the AuthorityState has shut down and tx_ready_certificate
                     // has been dropped by ExecutionScheduler.
                     info!("No more certificate will be received. Exiting executor ...");
                     return;
-                };
-            }
-            _ = &mut rx_execution_shutdown => {
-                info!("Shutdown signal received. Exiting executor ...");
-                return;
+                }
             }
         };
 
@@ -68,24 +81,50 @@ pub async fn execution_process(
             info!("Authority state has shutdown. Exiting ...");
             return;
         };
-        authority.metrics.execution_driver_dispatch_queue.dec();
+        if released_permit.is_none() {
+            authority.metrics.execution_driver_dispatch_queue.dec();
+        }
 
         // TODO: Ideally execution_driver should own a copy of epoch store and recreate each epoch.
         let epoch_store = authority.load_epoch_store_one_call_per_task();
 
-        let digest = *certificate.digest();
+        let digest = *pending_cert.certificate.digest();
         trace!(?digest, "Pending certificate execution activated.");
 
-        if epoch_store.epoch() != certificate.epoch() {
+        if epoch_store.epoch() != pending_cert.certificate.epoch() {
             info!(
                 ?digest,
                 cur_epoch = epoch_store.epoch(),
-                cert_epoch = certificate.epoch(),
+                cert_epoch = pending_cert.certificate.epoch(),
                 "Ignoring certificate from previous epoch."
             );
             continue;
         }
 
+        let (pending_cert, sender_permit) = match (released_permit, &sender_limiter) {
+            (Some(permit), _) => (pending_cert, Some(permit)),
+            (None, Some(limiter)) => {
+                match limiter.account(pending_cert.certificate.transaction_data()) {
+                    Some(account) => {
+                        let Some((pending_cert, permit)) =
+                            limiter.try_acquire(account, pending_cert)
+                        else {
+                            trace!(?digest, ?account, "Sender is at its concurrency limit.");
+                            continue;
+                        };
+                        (pending_cert, Some(permit))
+                    }
+                    None => (pending_cert, None),
+                }
+            }
+            (None, None) => (pending_cert, None),
+        };
+
+        let certificate = pending_cert.certificate;
+        let execution_env = pending_cert.execution_env;
+        let txn_ready_time = pending_cert.stats.ready_time.unwrap();
+        let _executing_guard = pending_cert.executing_guard;
+
         let limit = limit.clone();
         // hold semaphore permit until task completes. unwrap ok because we never close
         // the semaphore in this context.
@@ -111,6 +150,7 @@ pub async fn execution_process(
         spawn_monitored_task!(epoch_store.within_alive_epoch(async move {
             let _scope = monitored_scope("ExecutionDriver::task");
             let _guard = permit;
+            let _sender_permit = sender_permit;
             if authority.is_tx_already_executed(&digest) {
                 return;
             }
@@ -145,3 +185,14 @@ pub async fn execution_process(
         }.instrument(error_span!("execution_driver", tx_digest = ?digest))));
     }
 }
+
+async fn recv_released(
+    rx_released: &mut Option<
+        UnboundedReceiver<(PendingCertificate, SenderPermit<PendingCertificate>)>,
+    >,
+) -> Option<(PendingCertificate, SenderPermit<PendingCertificate>)> {
+    match rx_released {
+        Some(rx_released) => rx_released.recv().await,
+        None => std::future::pending().await,
+    }
+}
@@ -11,6 +11,7 @@ use tokio::time::Instant;
 pub(crate) mod execution_scheduler_impl;
 pub(crate) mod funds_withdraw_scheduler;
 mod overload_tracker;
+pub(crate) mod sender_concurrency_limiter;
 pub(crate) mod settlement_scheduler;
 pub(crate) use settlement_scheduler::{SettlementBatchInfo, SettlementScheduler};
 
@@ -0,0 +1,280 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use mysten_common::debug_fatal;
+use parking_lot::Mutex;
+use prometheus::IntGauge;
+use std::{
+    collections::{HashMap, VecDeque, hash_map::Entry},
+    sync::Arc,
+};
+use sui_config::node::{SenderConcurrencyKey, SenderConcurrencyLimitConfig};
+use sui_types::{
+    base_types::SuiAddress,
+    transaction::{TransactionData, TransactionDataAPI},
+};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+#[derive(Debug)]
+struct AccountQueue<T> {
+    // Number of outstanding permits for the account.
+    executing: usize,
+    // Items waiting for a permit, in arrival order.
+    waiting: VecDeque<T>,
+}
+
+/// Limits how many ready transactions of a single account the execution driver runs at once.
+///
+/// Items that arrive while their account is at the limit wait in a per-account FIFO queue. When
+/// a permit is dropped, its slot is handed directly to the account's oldest waiting item, which
+/// is sent on the channel returned by [`SenderConcurrencyLimiter::new`] together with its permit.
+/// The execution driver polls that channel ahead of newly ready transactions, so a throttled
+/// account keeps making progress at its allowed rate instead of being starved.
+pub(crate) struct SenderConcurrencyLimiter<T> {
+    max_executing: usize,
+    key: SenderConcurrencyKey,
+    accounts: Mutex<HashMap<SuiAddress, AccountQueue<T>>>,
+    tx_released: UnboundedSender<(T, SenderPermit<T>)>,
+    num_waiting: IntGauge,
+}
+
+impl<T> SenderConcurrencyLimiter<T> {
+    pub(crate) fn new(
+        config: &SenderConcurrencyLimitConfig,
+        num_waiting: IntGauge,
+    ) -> (Arc<Self>, UnboundedReceiver<(T, SenderPermit<T>)>) {
+        let (tx_released, rx_released) = unbounded_channel();
+        let limiter = Arc::new(Self {
+            max_executing: config.max_executing_transactions.get(),
+            key: config.key,
+            accounts: Mutex::new(HashMap::new()),
+            tx_released,
+            num_waiting,
+        });
+        (limiter, rx_released)
+    }
+
+    /// The account that `tx_data` counts against, or `None` if it is exempt from the limit.
+    ///
+    /// System transactions and transactions ordered by consensus (those with shared inputs or
+    /// funds withdrawals) are exempt: holding them back would delay the transactions scheduled
+    /// after them on the same shared objects and accumulators, and system transactions must
+    /// never wait behind user transactions.
+    pub(crate) fn account(&self, tx_data: &TransactionData) -> Option<SuiAddress> {
+        if tx_data.is_system_tx()
+            || tx_data.has_funds_withdrawals()
+            || tx_data.kind().shared_input_objects().next().is_some()
+        {
+            return None;
+        }
+
+        Some(match self.key {
+            SenderConcurrencyKey::Sender => tx_data.sender(),
+            SenderConcurrencyKey::GasOwner => tx_data.gas_owner(),
+        })
+    }
+
+    /// Returns `item` with a permit if `account` is below its limit. Otherwise queues `item`, to
+    /// be released once one of the account's permits is dropped, and returns `None`.
+    pub(crate) fn try_acquire(
+        self: &Arc<Self>,
+        account: SuiAddress,
+        item: T,
+    ) -> Option<(T, SenderPermit<T>)> {
+        let mut accounts = self.accounts.lock();
+        let queue = accounts.entry(account).or_insert_with(|| AccountQueue {
+            executing: 0,
+            waiting: VecDeque::new(),
+        });
+
+        if queue.executing < self.max_executing {
+            queue.executing += 1;
+            Some((item, self.permit(account)))
+        } else {
+            queue.waiting.push_back(item);
+            self.num_waiting.inc();
+            None
+        }
+    }
+
+    fn permit(self: &Arc<Self>, account: SuiAddress) -> SenderPermit<T> {
+        SenderPermit {
+            limiter: self.clone(),
+            account,
+        }
+    }
+
+    fn release(self: &Arc<Self>, account: SuiAddress) {
+        let next = {
+            let mut accounts = self.accounts.lock();
+            let Entry::Occupied(mut entry) = accounts.entry(account) else {
+                debug_fatal!("Released a permit for {account} without an account queue");
+                return;
+            };
+            let queue = entry.get_mut();
+
+            // Nothing will receive released items once the driver has shut down.
+            if self.tx_released.is_closed() {
+                self.num_waiting.sub(queue.waiting.len() as i64);
+                queue.waiting.clear();
+            }
+
+            match queue.waiting.pop_front() {
+                // The slot passes to the next waiting item, so `executing` is unchanged.
+                Some(next) => {
+                    self.num_waiting.dec();
+                    next
+                }
+                None => {
+                    queue.executing -= 1;
+                    if queue.executing == 0 {
+                        entry.remove();
+                    }
+                    return;
+                }
+            }
+        };
+
+        // Sent outside the lock: if the send fails, dropping the returned permit releases again.
+        let _ = self.tx_released.send((next, self.permit(account)));
+    }
+}
+
+/// A slot in an account's concurrency limit, returned to the limiter on drop.
+pub(crate) struct SenderPermit<T> {
+    limiter: Arc<SenderConcurrencyLimiter<T>>,
+    account: SuiAddress,
+}
+
+impl<T> Drop for SenderPermit<T> {
+    fn drop(&mut self) {
+        self.limiter.release(self.account);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::num::NonZeroUsize;
+    use sui_types::{
+        SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION,
+        base_types::random_object_ref,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{
+            EndOfEpochTransactionKind, ObjectArg, SharedObjectMutability, TransactionKind,
+        },
+    };
+
+    fn limiter(
+        max_executing: usize,
+    ) -> (
+        Arc<SenderConcurrencyLimiter<u32>>,
+        UnboundedReceiver<(u32, SenderPermit<u32>)>,
+    ) {
+        let config = SenderConcurrencyLimitConfig {
+            max_executing_transactions: NonZeroUsize::new(max_executing).unwrap(),
+            key: SenderConcurrencyKey::Sender,
+        };
+        SenderConcurrencyLimiter::new(&config, IntGauge::new("waiting", "waiting").unwrap())
+    }
+
+    #[test]
+    fn test_limits_per_account() {
+        let (limiter, mut rx_released) = limiter(2);
+        let (a, b) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+
+        let (_, a1) = limiter.try_acquire(a, 1).unwrap();
+        let (_, a2) = limiter.try_acquire(a, 2).unwrap();
+        assert!(limiter.try_acquire(a, 3).is_none());
+        assert!(limiter.try_acquire(a, 4).is_none());
+        assert_eq!(limiter.num_waiting.get(), 2);
+
+        // Other accounts are unaffected.
+        let (_, b1) = limiter.try_acquire(b, 5).unwrap();
+        drop(b1);
+        assert!(rx_released.try_recv().is_err());
+
+        // Waiting items are released in order as permits are dropped.
+        drop(a1);
+        let (item, a3) = rx_released.try_recv().unwrap();
+        assert_eq!(item, 3);
+        drop(a2);
+        let (item, a4) = rx_released.try_recv().unwrap();
+        assert_eq!(item, 4);
+        assert_eq!(limiter.num_waiting.get(), 0);
+
+        // Released items keep their slot until their permit is dropped.
+        assert!(limiter.try_acquire(a, 6).is_none());
+        drop(a3);
+        let (item, a6) = rx_released.try_recv().unwrap();
+        assert_eq!(item, 6);
+
+        drop(a4);
+        drop(a6);
+        assert!(rx_released.try_recv().is_err());
+        assert!(limiter.accounts.lock().is_empty());
+    }
+
+    #[test]
+    fn test_waiting_items_dropped_after_shutdown() {
+        let (limiter, rx_released) = limiter(1);
+        let a = SuiAddress::random_for_testing_only();
+
+        let (_, permit) = limiter.try_acquire(a, 1).unwrap();
+        assert!(limiter.try_acquire(a, 2).is_none());
+        assert!(limiter.try_acquire(a, 3).is_none());
+
+        drop(rx_released);
+        drop(permit);
+        assert_eq!(limiter.num_waiting.get(), 0);
+        assert!(limiter.accounts.lock().is_empty());
+    }
+
+    #[test]
+    fn test_consensus_and_system_transactions_are_exempt() {
+        let (limiter, _rx_released) = limiter(1);
+        let sender = SuiAddress::random_for_testing_only();
+        let sponsor = SuiAddress::random_for_testing_only();
+
+        let owned = TransactionData::new_programmable_allow_sponsor(
+            sender,
+            vec![random_object_ref()],
+            ProgrammableTransactionBuilder::new().finish(),
+            1_000_000,
+            1_000,
+            sponsor,
+        );
+        assert_eq!(limiter.account(&owned), Some(sender));
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .obj(ObjectArg::SharedObject {
+                id: SUI_CLOCK_OBJECT_ID,
+                initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+                mutability: SharedObjectMutability::Immutable,
+            })
+            .unwrap();
+        let shared = TransactionData::new_programmable(
+            sender,
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000_000,
+            1_000,
+        );
+        assert_eq!(limiter.account(&shared), None);
+
+        let system = TransactionData::new(
+            TransactionKind::EndOfEpochTransaction(vec![
+                EndOfEpochTransactionKind::new_randomness_state_create(),
+            ]),
+            SuiAddress::default(),
+            random_object_ref(),
+            0,
+            0,
+        );
+        assert_eq!(limiter.account(&system), None);
+    }
+}
@@ -100,6 +100,13 @@ impl SettlementScheduler {
         epoch_store: &Arc<AuthorityPerEpochStore>,
     ) {
         self.execution_scheduler.enqueue(certs, epoch_store);
+        epoch_store.settlement_tracker.register(
+            settlement.settlement_key,
+            settlement.checkpoint_seq,
+            settlement.checkpoint_height,
+            settlement.tx_keys.len(),
+        );
+        self.update_pending_settlement_metrics(epoch_store);
         let queue = self.get_or_start_queue(epoch_store);
         queue.send(SettlementWorkItem {
             settlement_key: settlement.settlement_key,
@@ -238,6 +245,12 @@ impl SettlementScheduler {
             .collect();
 
         let settlement_tx_count = settlement_txns.len() + 1; // +1 for barrier
+        self.metrics
+            .accumulator_settlement_txns
+            .observe(settlement_txns.len() as f64);
+        self.metrics
+            .accumulator_settlement_batch_size
+            .observe(batch_info.tx_keys.len() as f64);
         let settlement_digests: Vec<_> = settlement_txns.iter().map(|tx| *tx.digest()).collect();
 
         debug!(
@@ -323,11 +336,28 @@ impl SettlementScheduler {
         self.execution_scheduler
             .settle_address_funds(funds_settlement);
 
+        if let Some(latency) = epoch_store.settlement_tracker.resolve(&settlement_key) {
+            self.metrics
+                .accumulator_settlement_resolution_latency
+                .observe(latency.as_secs_f64());
+        }
+        self.update_pending_settlement_metrics(epoch_store);
+
         debug!(?settlement_key, "early settlement: completed");
 
         settlement_tx_count
     }
 
+    fn update_pending_settlement_metrics(&self, epoch_store: &AuthorityPerEpochStore) {
+        let tracker = &epoch_store.settlement_tracker;
+        self.metrics
+            .accumulator_settlements_pending
+            .set(tracker.num_pending() as i64);
+        self.metrics
+            .accumulator_settlement_lag_checkpoints
+            .set(tracker.lag_checkpoints() as i64);
+    }
+
     fn extract_consensus_commit_prologue_digest(
         &self,
         digests: &[TransactionDigest],
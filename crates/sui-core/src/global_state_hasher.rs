@@ -21,7 +21,7 @@ use std::sync::Arc;
 use fastcrypto::hash::MultisetHash;
 use sui_types::effects::TransactionEffects;
 use sui_types::effects::TransactionEffectsAPI;
-use sui_types::error::SuiResult;
+use sui_types::error::{SuiErrorKind, SuiResult};
 use sui_types::global_state_hash::GlobalStateHash;
 use sui_types::messages_checkpoint::{CheckpointSequenceNumber, ECMHLiveObjectSetDigest};
 
@@ -508,22 +508,9 @@ impl GlobalStateHasher {
         epoch_store: &AuthorityPerEpochStore,
         checkpoint_seq_num: CheckpointSequenceNumber,
     ) -> SuiResult<GlobalStateHash> {
-        if checkpoint_seq_num == 0 {
-            return Ok(GlobalStateHash::default());
-        }
-
-        if let Some(prior_running_root) =
-            epoch_store.get_running_root_state_hash(checkpoint_seq_num - 1)?
-        {
-            return Ok(prior_running_root);
-        }
-
-        if let Some((last_checkpoint_prev_epoch, prev_acc)) = self
-            .store
-            .get_root_state_hash_for_epoch(epoch_store.epoch() - 1)?
-            && last_checkpoint_prev_epoch == checkpoint_seq_num - 1
+        if let Some(prior_root) = stored_prior_root(&*self.store, epoch_store, checkpoint_seq_num)?
         {
-            return Ok(prev_acc);
+            return Ok(prior_root);
         }
 
         fatal!(
@@ -607,3 +594,145 @@ impl GlobalStateHasher {
         accumulate_effects(&*self.store, effects, protocol_config)
     }
 }
+
+/// The running root state hash up to the checkpoint before `checkpoint_seq_num`, if it has been
+/// accumulated.
+fn stored_prior_root(
+    store: &dyn GlobalStateHashStore,
+    epoch_store: &AuthorityPerEpochStore,
+    checkpoint_seq_num: CheckpointSequenceNumber,
+) -> SuiResult<Option<GlobalStateHash>> {
+    if checkpoint_seq_num == 0 {
+        return Ok(Some(GlobalStateHash::default()));
+    }
+
+    if let Some(prior_running_root) =
+        epoch_store.get_running_root_state_hash(checkpoint_seq_num - 1)?
+    {
+        return Ok(Some(prior_running_root));
+    }
+
+    if let Some((last_checkpoint_prev_epoch, prev_acc)) =
+        store.get_root_state_hash_for_epoch(epoch_store.epoch().saturating_sub(1))?
+        && last_checkpoint_prev_epoch == checkpoint_seq_num - 1
+    {
+        return Ok(Some(prev_acc));
+    }
+
+    Ok(None)
+}
+
+/// The outcome of recomputing the running root state hash over a range of checkpoints.
+#[derive(Debug)]
+pub struct RunningRootAudit {
+    /// The last checkpoint whose running root was checked, or `None` if none was. The audit stops
+    /// early at the first checkpoint that has not been accumulated yet.
+    pub last_checked: Option<CheckpointSequenceNumber>,
+    /// The first checkpoint whose stored running root differs from the recomputed one.
+    pub divergence: Option<RunningRootDivergence>,
+}
+
+#[derive(Debug)]
+pub struct RunningRootDivergence {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub recomputed: ECMHLiveObjectSetDigest,
+    pub stored: ECMHLiveObjectSetDigest,
+}
+
+/// Recompute the running root state hash of each checkpoint from `start` to `end` of the current
+/// epoch by accumulating the state hash of each checkpoint onto the stored running root before
+/// `start`, and compare it against the stored running root, stopping at the first divergence.
+pub fn audit_running_root(
+    store: &dyn GlobalStateHashStore,
+    epoch_store: &AuthorityPerEpochStore,
+    start: CheckpointSequenceNumber,
+    end: CheckpointSequenceNumber,
+) -> SuiResult<RunningRootAudit> {
+    let Some(mut running_root) = stored_prior_root(store, epoch_store, start)? else {
+        return Err(SuiErrorKind::Unknown(format!(
+            "No running root state hash before checkpoint {start} in epoch {}",
+            epoch_store.epoch()
+        ))
+        .into());
+    };
+
+    let mut audit = RunningRootAudit {
+        last_checked: None,
+        divergence: None,
+    };
+    for checkpoint in start..=end {
+        let (Some(checkpoint_hash), Some(stored)) = (
+            epoch_store.get_state_hash_for_checkpoint(&checkpoint)?,
+            epoch_store.get_running_root_state_hash(checkpoint)?,
+        ) else {
+            break;
+        };
+
+        running_root.union(&checkpoint_hash);
+        audit.last_checked = Some(checkpoint);
+        let (recomputed, stored) = (running_root.digest(), stored.digest());
+        if recomputed != stored {
+            audit.divergence = Some(RunningRootDivergence {
+                checkpoint,
+                recomputed: recomputed.into(),
+                stored: stored.into(),
+            });
+            break;
+        }
+    }
+
+    Ok(audit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority::test_authority_builder::TestAuthorityBuilder;
+
+    fn random_hash() -> GlobalStateHash {
+        let mut hash = GlobalStateHash::default();
+        hash.insert(ObjectDigest::random());
+        hash
+    }
+
+    #[tokio::test]
+    async fn test_audit_running_root_reports_first_divergence() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let epoch_store = state.epoch_store_for_testing();
+        let store = state.get_global_state_hash_store();
+
+        let mut running_root = random_hash();
+        epoch_store
+            .insert_running_root_state_hash(&99, &running_root)
+            .unwrap();
+        for checkpoint in 100..103 {
+            let checkpoint_hash = random_hash();
+            running_root.union(&checkpoint_hash);
+            epoch_store
+                .insert_state_hash_for_checkpoint(&checkpoint, &checkpoint_hash)
+                .unwrap();
+            let stored = if checkpoint == 102 {
+                random_hash()
+            } else {
+                running_root.clone()
+            };
+            epoch_store
+                .insert_running_root_state_hash(&checkpoint, &stored)
+                .unwrap();
+        }
+
+        let audit = audit_running_root(&**store, &epoch_store, 100, 101).unwrap();
+        assert_eq!(audit.last_checked, Some(101));
+        assert!(audit.divergence.is_none());
+
+        let audit = audit_running_root(&**store, &epoch_store, 100, 110).unwrap();
+        assert_eq!(audit.last_checked, Some(102));
+        assert_eq!(audit.divergence.unwrap().checkpoint, 102);
+
+        // The audit stops at the first checkpoint that has not been accumulated.
+        let audit = audit_running_root(&**store, &epoch_store, 103, 110).unwrap();
+        assert_eq!(audit.last_checked, None);
+
+        assert!(audit_running_root(&**store, &epoch_store, 200, 210).is_err());
+    }
+}
@@ -11,15 +11,21 @@ pub mod authority_aggregator;
 pub mod authority_client;
 pub mod authority_server;
 pub mod checkpoints;
+pub(crate) mod commit_trace;
+pub mod compliance_report;
 pub mod congestion_tracker;
 pub mod consensus_adapter;
 pub mod consensus_commit_summary;
 pub mod consensus_handler;
 pub mod consensus_manager;
+pub mod consensus_participation;
+pub mod consensus_rejection_policy;
+pub mod consensus_shadow_replay;
 pub mod consensus_throughput_calculator;
 pub(crate) mod consensus_types;
 pub mod consensus_validator;
 pub mod db_checkpoint_handler;
+pub mod denylist_feed;
 pub mod epoch;
 pub mod execution_cache;
 mod execution_driver;
@@ -33,7 +39,9 @@ pub mod mock_checkpoint_builder;
 pub mod mock_consensus;
 pub mod module_cache_metrics;
 pub mod mysticeti_adapter;
+pub mod object_reservations;
 pub mod overload_monitor;
+pub mod package_gas_tracker;
 pub(crate) mod post_consensus_tx_reorder;
 pub mod randomness_round_receiver;
 pub mod rpc_store_embed;
@@ -44,6 +52,7 @@ pub mod rpc_store_streaming_client;
 mod rpc_store_test_utils;
 pub mod runtime;
 pub mod safe_client;
+pub mod safe_mode_diagnostics;
 pub mod signature_verifier;
 mod stake_aggregator;
 mod status_aggregator;
@@ -59,6 +68,7 @@ pub mod transaction_orchestrator;
 mod transaction_outputs;
 mod transaction_signing_filter;
 pub mod validator_client_monitor;
+pub mod webhooks;
 
 #[cfg(test)]
 #[path = "unit_tests/congestion_control_tests.rs"]
@@ -0,0 +1,302 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::error::{SuiErrorKind, SuiResult, UserInputError};
+
+/// Maximum number of objects a single owner may have reserved at once.
+pub const MAX_RESERVED_OBJECTS_PER_OWNER: usize = 128;
+
+#[derive(Clone, Copy, Debug)]
+struct Reservation {
+    owner: SuiAddress,
+    tx_digest: TransactionDigest,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+struct Inner {
+    reservations: HashMap<ObjectID, Reservation>,
+    /// Number of entries in `reservations` per owner.
+    per_owner: HashMap<SuiAddress, usize>,
+}
+
+/// The message an owner signs to reserve `object_ids` for `tx_digest`: the BCS bytes of both,
+/// signed as a personal message.
+pub fn reservation_message(
+    object_ids: &[ObjectID],
+    tx_digest: TransactionDigest,
+) -> IntentMessage<PersonalMessage> {
+    IntentMessage::new(
+        Intent::personal_message(),
+        PersonalMessage {
+            message: bcs::to_bytes(&(object_ids, tx_digest))
+                .expect("Message serialization should not fail"),
+        },
+    )
+}
+
+/// Short-lived reservations of owned objects for transactions that a client is about to submit
+/// through this node.
+///
+/// Reservations are advisory and local to the node: validators know nothing about them, and they
+/// only make conflicting submissions through this node fail fast, before any validator is
+/// contacted. They are keyed by object ID, so a reservation outlives the object version it was
+/// taken on until it expires or its transaction is finalized. Each owner may hold at most
+/// [`MAX_RESERVED_OBJECTS_PER_OWNER`] reservations at once.
+#[derive(Default)]
+pub struct ObjectReservations {
+    inner: Mutex<Inner>,
+}
+
+impl ObjectReservations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves every object in `objects`, all owned by `owner`, for `tx_digest` for `ttl`.
+    /// Either all objects are reserved or none are: if any of them is reserved for another
+    /// transaction, returns an `ObjectLockConflict` error naming it, and if the reservation
+    /// would take `owner` over [`MAX_RESERVED_OBJECTS_PER_OWNER`], a `SizeLimitExceeded` error.
+    /// Existing reservations held by `tx_digest` are extended.
+    ///
+    /// Callers must have authenticated `owner`.
+    pub fn reserve(
+        &self,
+        owner: SuiAddress,
+        objects: &[ObjectRef],
+        tx_digest: TransactionDigest,
+        ttl: Duration,
+    ) -> SuiResult {
+        self.reserve_at(owner, objects, tx_digest, Instant::now(), ttl)
+    }
+
+    /// Returns an `ObjectLockConflict` error if any object in `objects` is reserved for a
+    /// transaction other than `tx_digest`.
+    pub fn check(&self, objects: &[ObjectRef], tx_digest: TransactionDigest) -> SuiResult {
+        self.inner
+            .lock()
+            .check_at(objects, tx_digest, Instant::now())
+    }
+
+    /// Drops all reservations held by `tx_digest`.
+    pub fn release(&self, tx_digest: TransactionDigest) {
+        self.inner
+            .lock()
+            .retain(|reservation| reservation.tx_digest != tx_digest);
+    }
+
+    fn reserve_at(
+        &self,
+        owner: SuiAddress,
+        objects: &[ObjectRef],
+        tx_digest: TransactionDigest,
+        now: Instant,
+        ttl: Duration,
+    ) -> SuiResult {
+        let mut inner = self.inner.lock();
+        inner.retain(|reservation| reservation.expires_at > now);
+        inner.check_at(objects, tx_digest, now)?;
+
+        // Objects the owner already holds are re-reserved in place and don't count twice.
+        let held = inner.per_owner.get(&owner).copied().unwrap_or(0);
+        let new = objects
+            .iter()
+            .filter(|(id, _, _)| {
+                inner
+                    .reservations
+                    .get(id)
+                    .is_none_or(|reservation| reservation.owner != owner)
+            })
+            .count();
+        if held + new > MAX_RESERVED_OBJECTS_PER_OWNER {
+            return Err(UserInputError::SizeLimitExceeded {
+                limit: "maximum reserved objects per owner".to_string(),
+                value: MAX_RESERVED_OBJECTS_PER_OWNER.to_string(),
+            }
+            .into());
+        }
+
+        let reservation = Reservation {
+            owner,
+            tx_digest,
+            expires_at: now + ttl,
+        };
+        for (object_id, _, _) in objects {
+            if let Some(previous) = inner.reservations.insert(*object_id, reservation) {
+                decrement(&mut inner.per_owner, previous.owner);
+            }
+            *inner.per_owner.entry(owner).or_default() += 1;
+        }
+        Ok(())
+    }
+}
+
+impl Inner {
+    fn retain(&mut self, mut keep: impl FnMut(&Reservation) -> bool) {
+        let per_owner = &mut self.per_owner;
+        self.reservations.retain(|_, reservation| {
+            let keep = keep(reservation);
+            if !keep {
+                decrement(per_owner, reservation.owner);
+            }
+            keep
+        });
+    }
+
+    fn check_at(
+        &self,
+        objects: &[ObjectRef],
+        tx_digest: TransactionDigest,
+        now: Instant,
+    ) -> SuiResult {
+        for obj_ref in objects {
+            if let Some(reservation) = self.reservations.get(&obj_ref.0)
+                && reservation.tx_digest != tx_digest
+                && reservation.expires_at > now
+            {
+                return Err(SuiErrorKind::ObjectLockConflict {
+                    obj_ref: *obj_ref,
+                    pending_transaction: reservation.tx_digest,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn decrement(per_owner: &mut HashMap<SuiAddress, usize>, owner: SuiAddress) {
+    if let Some(count) = per_owner.get_mut(&owner) {
+        *count -= 1;
+        if *count == 0 {
+            per_owner.remove(&owner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::random_object_ref;
+
+    const TTL: Duration = Duration::from_secs(5);
+
+    fn owner() -> SuiAddress {
+        SuiAddress::random_for_testing_only()
+    }
+
+    #[test]
+    fn test_reserve_all_or_nothing() {
+        let reservations = ObjectReservations::new();
+        let (obj1, obj2, obj3) = (
+            random_object_ref(),
+            random_object_ref(),
+            random_object_ref(),
+        );
+        let (tx1, tx2) = (TransactionDigest::random(), TransactionDigest::random());
+        let owner = owner();
+        let now = Instant::now();
+
+        reservations
+            .reserve_at(owner, &[obj1, obj2], tx1, now, TTL)
+            .unwrap();
+        // Reserving again for the same transaction extends the reservation.
+        reservations
+            .reserve_at(owner, &[obj1], tx1, now, TTL)
+            .unwrap();
+
+        let err = reservations
+            .reserve_at(owner, &[obj3, obj2], tx2, now, TTL)
+            .unwrap_err();
+        assert!(matches!(
+            err.as_inner(),
+            SuiErrorKind::ObjectLockConflict { obj_ref, pending_transaction }
+                if *obj_ref == obj2 && *pending_transaction == tx1
+        ));
+        // Nothing was reserved by the failed attempt.
+        reservations.check(&[obj3], tx1).unwrap();
+
+        reservations.check(&[obj1, obj2], tx1).unwrap();
+        assert!(reservations.check(&[obj1], tx2).is_err());
+
+        reservations.release(tx1);
+        reservations.check(&[obj1, obj2], tx2).unwrap();
+        assert!(reservations.inner.lock().per_owner.is_empty());
+    }
+
+    #[test]
+    fn test_reservations_expire() {
+        let reservations = ObjectReservations::new();
+        let obj = random_object_ref();
+        let (tx1, tx2) = (TransactionDigest::random(), TransactionDigest::random());
+        let owner = owner();
+        let now = Instant::now();
+
+        reservations
+            .reserve_at(owner, &[obj], tx1, now, TTL)
+            .unwrap();
+        assert!(
+            reservations
+                .reserve_at(owner, &[obj], tx2, now + TTL / 2, TTL)
+                .is_err()
+        );
+        reservations
+            .reserve_at(owner, &[obj], tx2, now + TTL, TTL)
+            .unwrap();
+        assert!(
+            reservations
+                .inner
+                .lock()
+                .check_at(&[obj], tx1, now + TTL)
+                .is_err()
+        );
+        assert_eq!(reservations.inner.lock().per_owner[&owner], 1);
+    }
+
+    #[test]
+    fn test_reservations_bounded_per_owner() {
+        let reservations = ObjectReservations::new();
+        let (owner1, owner2) = (owner(), owner());
+        let tx = TransactionDigest::random();
+        let now = Instant::now();
+        let objects: Vec<_> = (0..MAX_RESERVED_OBJECTS_PER_OWNER)
+            .map(|_| random_object_ref())
+            .collect();
+
+        reservations
+            .reserve_at(owner1, &objects, tx, now, TTL)
+            .unwrap();
+        // Re-reserving held objects does not count against the limit.
+        reservations
+            .reserve_at(owner1, &objects[..1], tx, now, TTL)
+            .unwrap();
+
+        let err = reservations
+            .reserve_at(owner1, &[random_object_ref()], tx, now, TTL)
+            .unwrap_err();
+        assert!(matches!(
+            err.as_inner(),
+            SuiErrorKind::UserInputError {
+                error: UserInputError::SizeLimitExceeded { .. }
+            }
+        ));
+
+        // Other owners have their own limit.
+        reservations
+            .reserve_at(owner2, &[random_object_ref()], tx, now, TTL)
+            .unwrap();
+
+        // Expired reservations no longer count.
+        reservations
+            .reserve_at(owner1, &[random_object_ref()], tx, now + TTL, TTL)
+            .unwrap();
+        let inner = reservations.inner.lock();
+        assert_eq!(inner.per_owner[&owner1], 1);
+        assert!(!inner.per_owner.contains_key(&owner2));
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use sui_config::node::PackageGasAccountingConfig;
+use sui_types::base_types::ObjectID;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::transaction::TransactionDataAPI;
+
+/// Gas consumed by transactions that invoked a package.
+///
+/// A transaction calling into several packages is attributed in full to each of them, so summing
+/// usage across packages can exceed the gas actually charged by the network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PackageGasUsage {
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub transaction_count: u64,
+}
+
+impl PackageGasUsage {
+    fn add_usage(&mut self, other: &PackageGasUsage) {
+        self.computation_cost += other.computation_cost;
+        self.storage_cost += other.storage_cost;
+        self.storage_rebate += other.storage_rebate;
+        self.transaction_count += other.transaction_count;
+    }
+
+    fn sub_usage(&mut self, other: &PackageGasUsage) {
+        self.computation_cost -= other.computation_cost;
+        self.storage_cost -= other.storage_cost;
+        self.storage_rebate -= other.storage_rebate;
+        self.transaction_count -= other.transaction_count;
+    }
+
+    fn add_gas_cost_summary(&mut self, summary: &GasCostSummary) {
+        self.computation_cost += summary.computation_cost;
+        self.storage_cost += summary.storage_cost;
+        self.storage_rebate += summary.storage_rebate;
+        self.transaction_count += 1;
+    }
+
+    pub fn gas_used(&self) -> u64 {
+        self.computation_cost + self.storage_cost
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PackageGasSortKey {
+    #[default]
+    GasUsed,
+    ComputationCost,
+    StorageCost,
+    TransactionCount,
+}
+
+impl std::str::FromStr for PackageGasSortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gas_used" => Ok(Self::GasUsed),
+            "computation_cost" => Ok(Self::ComputationCost),
+            "storage_cost" => Ok(Self::StorageCost),
+            "transaction_count" => Ok(Self::TransactionCount),
+            _ => Err(format!(
+                "unknown sort key '{s}', expected one of: gas_used, computation_cost, storage_cost, transaction_count"
+            )),
+        }
+    }
+}
+
+impl PackageGasSortKey {
+    fn value(&self, usage: &PackageGasUsage) -> u64 {
+        match self {
+            Self::GasUsed => usage.gas_used(),
+            Self::ComputationCost => usage.computation_cost,
+            Self::StorageCost => usage.storage_cost,
+            Self::TransactionCount => usage.transaction_count,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PackageGasReportEntry {
+    pub package_id: ObjectID,
+    #[serde(flatten)]
+    pub usage: PackageGasUsage,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PackageGasReport {
+    /// Oldest checkpoint included in the rolling window, if any checkpoint has been recorded.
+    pub first_checkpoint: Option<CheckpointSequenceNumber>,
+    /// Newest checkpoint included in the rolling window, if any checkpoint has been recorded.
+    pub last_checkpoint: Option<CheckpointSequenceNumber>,
+    pub packages: Vec<PackageGasReportEntry>,
+}
+
+#[derive(Default)]
+struct Inner {
+    checkpoints: VecDeque<(CheckpointSequenceNumber, HashMap<ObjectID, PackageGasUsage>)>,
+    totals: HashMap<ObjectID, PackageGasUsage>,
+}
+
+/// Aggregates gas consumed per invoked package over a rolling window of executed checkpoints.
+pub struct PackageGasTracker {
+    window_checkpoints: usize,
+    max_report_size: usize,
+    inner: Mutex<Inner>,
+}
+
+impl PackageGasTracker {
+    pub fn new(config: &PackageGasAccountingConfig) -> Self {
+        Self {
+            window_checkpoints: config.window_checkpoints().get(),
+            max_report_size: config.max_report_size(),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub fn process_checkpoint_effects(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+        transactions: &[VerifiedExecutableTransaction],
+        effects: &[TransactionEffects],
+    ) {
+        let per_transaction = transactions
+            .iter()
+            .zip(effects)
+            .filter_map(|(tx, fx)| {
+                let packages: BTreeSet<ObjectID> = tx
+                    .transaction_data()
+                    .move_calls()
+                    .into_iter()
+                    .map(|(_, package, _, _)| *package)
+                    .collect();
+                (!packages.is_empty()).then(|| (packages, fx.gas_cost_summary().clone()))
+            })
+            .collect::<Vec<_>>();
+
+        self.record_checkpoint(sequence_number, &per_transaction);
+    }
+
+    /// Returns the `k` packages with the highest usage in the current window according to
+    /// `sort_key`. `k` is capped by the configured maximum report size.
+    pub fn top_k(&self, k: usize, sort_key: PackageGasSortKey) -> PackageGasReport {
+        let inner = self.inner.lock();
+        let mut packages: Vec<_> = inner
+            .totals
+            .iter()
+            .map(|(package_id, usage)| PackageGasReportEntry {
+                package_id: *package_id,
+                usage: *usage,
+            })
+            .collect();
+        packages.sort_by(|a, b| {
+            sort_key
+                .value(&b.usage)
+                .cmp(&sort_key.value(&a.usage))
+                .then_with(|| a.package_id.cmp(&b.package_id))
+        });
+        packages.truncate(k.min(self.max_report_size));
+
+        PackageGasReport {
+            first_checkpoint: inner.checkpoints.front().map(|(seq, _)| *seq),
+            last_checkpoint: inner.checkpoints.back().map(|(seq, _)| *seq),
+            packages,
+        }
+    }
+}
+
+impl PackageGasTracker {
+    fn record_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+        per_transaction: &[(BTreeSet<ObjectID>, GasCostSummary)],
+    ) {
+        let mut checkpoint_usage: HashMap<ObjectID, PackageGasUsage> = HashMap::new();
+        for (packages, summary) in per_transaction {
+            for package in packages {
+                checkpoint_usage
+                    .entry(*package)
+                    .or_default()
+                    .add_gas_cost_summary(summary);
+            }
+        }
+
+        let mut inner = self.inner.lock();
+        for (package, usage) in &checkpoint_usage {
+            inner.totals.entry(*package).or_default().add_usage(usage);
+        }
+        inner
+            .checkpoints
+            .push_back((sequence_number, checkpoint_usage));
+
+        while inner.checkpoints.len() > self.window_checkpoints {
+            let (_, expired) = inner.checkpoints.pop_front().unwrap();
+            for (package, usage) in expired {
+                if let Entry::Occupied(mut entry) = inner.totals.entry(package) {
+                    entry.get_mut().sub_usage(&usage);
+                    if entry.get().transaction_count == 0 {
+                        entry.remove();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    fn tracker(window_checkpoints: usize) -> PackageGasTracker {
+        PackageGasTracker::new(&PackageGasAccountingConfig {
+            window_checkpoints: NonZeroUsize::new(window_checkpoints),
+            max_report_size: None,
+        })
+    }
+
+    fn gas(computation_cost: u64, storage_cost: u64) -> GasCostSummary {
+        GasCostSummary::new(computation_cost, storage_cost, 0, 0)
+    }
+
+    #[test]
+    fn test_top_k_orders_by_sort_key() {
+        let tracker = tracker(10);
+        let pkg1 = ObjectID::random();
+        let pkg2 = ObjectID::random();
+
+        tracker.record_checkpoint(
+            1,
+            &[
+                (BTreeSet::from([pkg1]), gas(100, 10)),
+                (BTreeSet::from([pkg2]), gas(10, 500)),
+                (BTreeSet::from([pkg2]), gas(10, 0)),
+            ],
+        );
+
+        let report = tracker.top_k(10, PackageGasSortKey::ComputationCost);
+        assert_eq!(report.packages[0].package_id, pkg1);
+        assert_eq!(report.packages[1].package_id, pkg2);
+
+        let report = tracker.top_k(1, PackageGasSortKey::GasUsed);
+        assert_eq!(report.packages.len(), 1);
+        assert_eq!(report.packages[0].package_id, pkg2);
+        assert_eq!(report.packages[0].usage.transaction_count, 2);
+        assert_eq!(report.first_checkpoint, Some(1));
+        assert_eq!(report.last_checkpoint, Some(1));
+    }
+
+    #[test]
+    fn test_multi_package_transaction_attributed_to_each() {
+        let tracker = tracker(10);
+        let pkg1 = ObjectID::random();
+        let pkg2 = ObjectID::random();
+
+        tracker.record_checkpoint(1, &[(BTreeSet::from([pkg1, pkg2]), gas(100, 10))]);
+
+        let report = tracker.top_k(10, PackageGasSortKey::GasUsed);
+        assert_eq!(report.packages.len(), 2);
+        for entry in report.packages {
+            assert_eq!(entry.usage.gas_used(), 110);
+        }
+    }
+
+    #[test]
+    fn test_window_expires_old_checkpoints() {
+        let tracker = tracker(2);
+        let pkg1 = ObjectID::random();
+        let pkg2 = ObjectID::random();
+
+        tracker.record_checkpoint(1, &[(BTreeSet::from([pkg1]), gas(100, 0))]);
+        tracker.record_checkpoint(2, &[(BTreeSet::from([pkg2]), gas(50, 0))]);
+        tracker.record_checkpoint(3, &[(BTreeSet::from([pkg2]), gas(50, 0))]);
+
+        let report = tracker.top_k(10, PackageGasSortKey::GasUsed);
+        assert_eq!(report.first_checkpoint, Some(2));
+        assert_eq!(report.last_checkpoint, Some(3));
+        assert_eq!(report.packages.len(), 1);
+        assert_eq!(report.packages[0].package_id, pkg2);
+        assert_eq!(report.packages[0].usage.computation_cost, 100);
+    }
+}
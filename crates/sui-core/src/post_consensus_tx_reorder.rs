@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::{HashMap, VecDeque};
+
 use mysten_metrics::monitored_scope;
 use sui_protocol_config::ConsensusTransactionOrdering;
 use sui_types::{
@@ -21,6 +23,39 @@ impl PostConsensusTxReorder {
         }
     }
 
+    /// Interleaves `transactions` so that their senders take turns, in the order of their first
+    /// transaction. Each sender's own transactions keep their relative order.
+    ///
+    /// Since the subsequent ordering is stable, this only decides the order of transactions that
+    /// `reorder` considers equal.
+    pub fn round_robin_by_sender(
+        transactions: Vec<VerifiedExecutableTransactionWithAliases>,
+    ) -> Vec<VerifiedExecutableTransactionWithAliases> {
+        let _scope = monitored_scope("ConsensusCommitHandler::round_robin_by_sender");
+        let len = transactions.len();
+
+        let mut sender_queues: Vec<VecDeque<_>> = vec![];
+        let mut sender_index = HashMap::new();
+        for tx in transactions {
+            let sender = tx.tx().transaction_data().sender();
+            let index = *sender_index.entry(sender).or_insert_with(|| {
+                sender_queues.push(VecDeque::new());
+                sender_queues.len() - 1
+            });
+            sender_queues[index].push_back(tx);
+        }
+
+        let mut interleaved = Vec::with_capacity(len);
+        while interleaved.len() < len {
+            for queue in &mut sender_queues {
+                if let Some(tx) = queue.pop_front() {
+                    interleaved.push(tx);
+                }
+            }
+        }
+        interleaved
+    }
+
     fn order_by_gas_price(transactions: &mut [VerifiedExecutableTransactionWithAliases]) {
         let _scope = monitored_scope("ConsensusCommitHandler::order_by_gas_price");
         transactions.sort_by_key(|tx| {
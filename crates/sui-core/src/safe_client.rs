@@ -19,10 +19,10 @@ use sui_types::messages_checkpoint::{
     CertifiedCheckpointSummary, CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
 use sui_types::messages_grpc::{
-    ExecutedData, ObjectInfoRequest, ObjectInfoResponse, SubmitTxRequest, SubmitTxResponse,
-    SystemStateRequest, TransactionInfoRequest, TransactionStatus, ValidatorHealthRequest,
-    ValidatorHealthResponse, VerifiedObjectInfoResponse, WaitForEffectsRequest,
-    WaitForEffectsResponse,
+    ExecutedData, ObjectInfoRequest, ObjectInfoResponse, SubmitSoftBundleRequest, SubmitTxRequest,
+    SubmitTxResponse, SystemStateRequest, TransactionInfoRequest, TransactionStatus,
+    ValidatorHealthRequest, ValidatorHealthResponse, VerifiedObjectInfoResponse,
+    WaitForEffectsRequest, WaitForEffectsResponse,
 };
 use sui_types::messages_safe_client::PlainTransactionInfoResponse;
 use sui_types::object::Object;
@@ -301,6 +301,32 @@ where
             .await
     }
 
+    /// Submit a soft bundle of transactions, checking that there is a result for each of them.
+    pub async fn submit_soft_bundle(
+        &self,
+        request: SubmitSoftBundleRequest,
+        client_addr: Option<SocketAddr>,
+    ) -> Result<SubmitTxResponse, SuiError> {
+        let _timer = self.metrics.handle_certificate_latency.start_timer();
+        let num_transactions = request.transactions.len();
+        let response = self
+            .authority_client
+            .submit_soft_bundle(request, client_addr)
+            .await?;
+        if response.results.len() != num_transactions {
+            return Err(SuiErrorKind::ByzantineAuthoritySuspicion {
+                authority: self.address,
+                reason: format!(
+                    "Expected {} results for soft bundle, got {}",
+                    num_transactions,
+                    response.results.len()
+                ),
+            }
+            .into());
+        }
+        Ok(response)
+    }
+
     /// Wait for effects of a transaction that has been submitted to the network
     /// through the `submit_transaction` API.
     pub async fn wait_for_effects(
@@ -0,0 +1,332 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Diagnostics bundles for support escalation when an advance-epoch transaction leaves the system
+//! in safe mode.
+//!
+//! A bundle is a single zstd-compressed tar archive, named
+//! `safe-mode-diagnostics-epoch-<EPOCH>-<TIMESTAMP_MS>.tar.zst`, holding:
+//!
+//! - `summary.json`: the epoch ended by the advance-epoch transaction, the protocol version and
+//!   safe mode flag, what triggered the bundle, and any error met while capturing it.
+//! - `system_state.json`: a summary of the system state written by the advance-epoch transaction.
+//! - `advance_epoch_effects.json`: the effects of the advance-epoch transaction, if known.
+//! - `protocol_config.json`: the values of the protocol config attributes limiting what the
+//!   advance-epoch transaction can do.
+//! - `consensus_commits.json`: the latest consensus commits of the epoch, with the keys of the
+//!   transactions they sequenced.
+//!
+//! Validators write a bundle as soon as the advance-epoch transaction built by their checkpoint
+//! builder sets the safe mode flag, if configured to (see
+//! [`sui_config::node::SafeModeDiagnosticsConfig`]), and on demand through the admin interface.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use consensus_core::CommitAPI;
+use consensus_core::storage::Store;
+use consensus_core::storage::rocksdb_store::RocksDBStore;
+use serde::Serialize;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::committee::EpochId;
+use sui_types::effects::TransactionEffects;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use sui_types::sui_system_state::{SuiSystemState, SuiSystemStateTrait};
+use tracing::warn;
+
+use crate::consensus_commit_summary::build_consensus_commit_summary;
+
+/// Protocol config attributes bounding the advance-epoch transaction. Exceeding one of them aborts
+/// the transaction's call to `sui_system::advance_epoch`, which is what puts the system in safe
+/// mode.
+const ADVANCE_EPOCH_PROTOCOL_CONFIG_ATTRS: &[&str] = &[
+    "max_tx_gas",
+    "max_gas_computation_bucket",
+    "max_move_object_size",
+    "max_size_written_objects_system_tx",
+    "max_num_new_move_object_ids_system_tx",
+    "max_num_deleted_move_object_ids_system_tx",
+    "max_num_transferred_move_object_ids_system_tx",
+    "max_event_emit_size_total",
+    "storage_gas_price",
+    "storage_rebate_rate",
+    "storage_fund_reinvest_rate",
+    "reward_slashing_rate",
+];
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SafeModeDiagnosticsTrigger {
+    /// The advance-epoch transaction built by this validator's checkpoint builder.
+    AdvanceEpoch,
+    /// A request through the admin interface.
+    OnDemand,
+}
+
+#[derive(Serialize)]
+struct BundleSummary {
+    epoch: EpochId,
+    protocol_version: u64,
+    safe_mode: bool,
+    trigger: SafeModeDiagnosticsTrigger,
+    captured_at_ms: u64,
+    /// Errors met while capturing the bundle. The parts they affect are missing.
+    errors: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CommitDiagnostics {
+    index: u32,
+    leader: String,
+    timestamp_ms: u64,
+    transactions: Vec<String>,
+    missing_blocks: Vec<String>,
+}
+
+pub struct SafeModeDiagnostics {
+    summary: BundleSummary,
+    system_state: SuiSystemStateSummary,
+    advance_epoch_effects: Option<TransactionEffects>,
+    protocol_config: BTreeMap<String, Option<String>>,
+    consensus_commits: Vec<CommitDiagnostics>,
+}
+
+impl SafeModeDiagnostics {
+    /// Captures a bundle for the advance-epoch transaction that ended `epoch`, run under
+    /// `protocol_config` and writing `system_state`. Up to `num_commits` of the latest commits are
+    /// read from `consensus_store`.
+    pub fn collect(
+        trigger: SafeModeDiagnosticsTrigger,
+        epoch: EpochId,
+        protocol_config: &ProtocolConfig,
+        system_state: SuiSystemState,
+        advance_epoch_effects: Option<TransactionEffects>,
+        consensus_store: Option<&RocksDBStore>,
+        num_commits: u32,
+    ) -> Self {
+        let mut errors = vec![];
+
+        let consensus_commits = match consensus_store {
+            Some(store) => latest_commits(store, num_commits).unwrap_or_else(|e| {
+                errors.push(format!("Failed to read consensus commits: {e:#}"));
+                vec![]
+            }),
+            None => {
+                errors.push("Consensus store is not available".to_string());
+                vec![]
+            }
+        };
+
+        let protocol_config_values = ADVANCE_EPOCH_PROTOCOL_CONFIG_ATTRS
+            .iter()
+            .map(|attr| {
+                let value = protocol_config
+                    .lookup_attr(attr.to_string())
+                    .map(|v| v.to_string());
+                (attr.to_string(), value)
+            })
+            .collect();
+
+        let captured_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+
+        Self {
+            summary: BundleSummary {
+                epoch,
+                protocol_version: protocol_config.version.as_u64(),
+                safe_mode: system_state.safe_mode(),
+                trigger,
+                captured_at_ms,
+                errors,
+            },
+            system_state: system_state.into_sui_system_state_summary(),
+            advance_epoch_effects,
+            protocol_config: protocol_config_values,
+            consensus_commits,
+        }
+    }
+
+    /// Writes the bundle as an archive in `dir`, and returns its path.
+    pub fn write(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        let path = dir.join(format!(
+            "safe-mode-diagnostics-epoch-{}-{}.tar.zst",
+            self.summary.epoch, self.summary.captured_at_ms
+        ));
+
+        let mut files = vec![
+            ("summary.json", serde_json::to_vec_pretty(&self.summary)?),
+            (
+                "system_state.json",
+                serde_json::to_vec_pretty(&self.system_state)?,
+            ),
+            (
+                "protocol_config.json",
+                serde_json::to_vec_pretty(&self.protocol_config)?,
+            ),
+            (
+                "consensus_commits.json",
+                serde_json::to_vec_pretty(&self.consensus_commits)?,
+            ),
+        ];
+        if let Some(effects) = &self.advance_epoch_effects {
+            files.push((
+                "advance_epoch_effects.json",
+                serde_json::to_vec_pretty(effects)?,
+            ));
+        }
+
+        let file = fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut archive = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+        for (name, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(self.summary.captured_at_ms / 1000);
+            header.set_cksum();
+            archive.append_data(&mut header, name, contents.as_slice())?;
+        }
+        archive.into_inner()?.finish()?;
+
+        Ok(path)
+    }
+}
+
+/// The latest `num_commits` commits in `store`, oldest first.
+fn latest_commits(
+    store: &RocksDBStore,
+    num_commits: u32,
+) -> anyhow::Result<Vec<CommitDiagnostics>> {
+    let Some(last) = store
+        .read_last_commit()
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+    else {
+        return Ok(vec![]);
+    };
+
+    let last = last.index();
+    let first = last.saturating_sub(num_commits.saturating_sub(1)).max(1);
+    let mut commits = vec![];
+    for index in first..=last {
+        let Some(summary) = build_consensus_commit_summary(store, index)? else {
+            warn!(index, "Consensus commit is missing from the store");
+            continue;
+        };
+        commits.push(CommitDiagnostics {
+            index,
+            leader: summary.commit.leader().to_string(),
+            timestamp_ms: summary.commit.timestamp_ms(),
+            transactions: summary.tx_keys,
+            missing_blocks: summary.missing_blocks,
+        });
+    }
+    Ok(commits)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::io::Read;
+
+    use super::*;
+    use crate::authority::test_authority_builder::TestAuthorityBuilder;
+
+    /// Reads every file in the bundle at `path`, by name.
+    fn read_bundle(path: &Path) -> BTreeMap<String, serde_json::Value> {
+        let file = fs::File::open(path).unwrap();
+        let mut archive = tar::Archive::new(zstd::Decoder::new(file).unwrap());
+        archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let name = entry.path().unwrap().to_string_lossy().into_owned();
+                let mut contents = String::new();
+                entry.read_to_string(&mut contents).unwrap();
+                (name, serde_json::from_str(&contents).unwrap())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_write_bundle() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let system_state = state.get_sui_system_state_object_for_testing().unwrap();
+        let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+
+        let consensus_dir = tempfile::tempdir().unwrap();
+        let consensus_store = RocksDBStore::new(consensus_dir.path().to_str().unwrap());
+
+        let diagnostics = SafeModeDiagnostics::collect(
+            SafeModeDiagnosticsTrigger::AdvanceEpoch,
+            0,
+            &protocol_config,
+            system_state,
+            Some(TransactionEffects::default()),
+            Some(&consensus_store),
+            10,
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = diagnostics.write(&dir.path().join("bundles")).unwrap();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("safe-mode-diagnostics-epoch-0-"), "{name}");
+        assert!(name.ends_with(".tar.zst"), "{name}");
+
+        let files = read_bundle(&path);
+        assert_eq!(
+            files.keys().map(String::as_str).collect::<BTreeSet<_>>(),
+            BTreeSet::from([
+                "advance_epoch_effects.json",
+                "consensus_commits.json",
+                "protocol_config.json",
+                "summary.json",
+                "system_state.json",
+            ]),
+        );
+
+        let summary = &files["summary.json"];
+        assert_eq!(summary["epoch"], 0);
+        assert_eq!(summary["safe_mode"], false);
+        assert_eq!(summary["trigger"], "advance-epoch");
+        assert_eq!(summary["errors"], serde_json::json!([]));
+
+        // The consensus store has no commits yet.
+        assert_eq!(files["consensus_commits.json"], serde_json::json!([]));
+
+        let protocol_config_values = files["protocol_config.json"].as_object().unwrap();
+        assert_eq!(
+            protocol_config_values.len(),
+            ADVANCE_EPOCH_PROTOCOL_CONFIG_ATTRS.len()
+        );
+        assert_eq!(
+            protocol_config_values["max_tx_gas"],
+            protocol_config.max_tx_gas().to_string(),
+        );
+    }
+
+    /// Bundles written on demand record what they could not capture, rather than failing.
+    #[tokio::test]
+    async fn test_write_bundle_on_demand() {
+        let state = TestAuthorityBuilder::new().build().await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = state.write_safe_mode_diagnostics(dir.path(), 10).unwrap();
+
+        let files = read_bundle(&path);
+        assert!(!files.contains_key("advance_epoch_effects.json"));
+
+        let summary = &files["summary.json"];
+        assert_eq!(summary["trigger"], "on-demand");
+        assert_eq!(
+            summary["errors"],
+            serde_json::json!(["Consensus store is not available"]),
+        );
+    }
+}
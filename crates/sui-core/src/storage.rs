@@ -35,6 +35,7 @@ use sui_types::storage::LedgerBitmapBucketIterator;
 use sui_types::storage::LedgerTxSeqDigest;
 use sui_types::storage::LedgerTxSeqDigestIterator;
 use sui_types::storage::ObjectStore;
+use sui_types::storage::ObjectTypeFilter;
 use sui_types::storage::OwnedObjectInfo;
 use sui_types::storage::RpcIndexes;
 use sui_types::storage::RpcStateReader;
@@ -942,7 +943,7 @@ impl RpcIndexes for RpcStoreReadStore {
     fn owned_objects_iter(
         &self,
         owner: SuiAddress,
-        object_type: Option<StructTag>,
+        object_type: Option<ObjectTypeFilter>,
         cursor: Option<OwnedObjectInfo>,
     ) -> Result<Box<dyn Iterator<Item = Result<OwnedObjectInfo, TypedStoreError>> + '_>> {
         self.reader.owned_objects_iter(owner, object_type, cursor)
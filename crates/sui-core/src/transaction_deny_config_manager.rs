@@ -14,6 +14,11 @@
 //!   each proposed a superset of its rules.
 //! - A "default" bucket threshold-gates each individual proposed rule *element*
 //!   (deny-list entry or boolean kill switch) that peers have proposed.
+//!
+//! The effective config also carries the transaction policy checked at signing time: the
+//! operator's `transaction-policy`, preceded by any deny rules synced from a denylist feed
+//! (see `crate::denylist_feed`). A policy read from a file can be reloaded without a restart
+//! (SIGHUP or the admin interface); a reload that fails keeps the previous policy.
 
 use crate::authority::AuthorityState;
 use crate::authority::authority_per_epoch_store::AuthorityPerEpochStore;
@@ -23,7 +28,8 @@ use arc_swap::ArcSwap;
 use itertools::Itertools;
 use parking_lot::Mutex;
 use prometheus::{
-    IntCounterVec, IntGauge, IntGaugeVec, Registry, register_int_counter_vec_with_registry,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
     register_int_gauge_vec_with_registry, register_int_gauge_with_registry,
 };
 use std::collections::{BTreeMap, BTreeSet};
@@ -31,10 +37,11 @@ use std::sync::Arc;
 use sui_config::transaction_deny_config::{
     PeerDenySyncConfig, TransactionDenyConfig, ValidatorEligibility,
 };
+use sui_config::transaction_policy_config::{TransactionPolicy, TransactionPolicyRule};
 use sui_types::base_types::AuthorityName;
 use sui_types::base_types::ConciseableName;
 use sui_types::committee::{Committee, StakeUnit, TOTAL_VOTING_POWER};
-use sui_types::error::{SuiError, SuiResult};
+use sui_types::error::{SuiError, SuiErrorKind, SuiResult, UserInputError};
 use sui_types::messages_consensus::{
     ConsensusTransaction, SharedTransactionDenyConfig, SharedTransactionDenyConfigV1,
 };
@@ -62,9 +69,22 @@ pub struct TransactionDenyConfigManager {
     /// Serializes the read-modify-write in `allocate_next_broadcast_generation` so
     /// concurrent callers cannot read the same prior generation and hand out duplicates.
     broadcast_generation_lock: Mutex<()>,
+    /// The operator's transaction policy, loaded from `local_config`.
+    operator_policy: ArcSwap<TransactionPolicy>,
+    /// Deny rules synced from a denylist feed, checked before the operator's policy.
+    policy_denylist: Mutex<PolicyDenylist>,
     metrics: TransactionDenyConfigMetrics,
 }
 
+/// Deny rules synced from a denylist feed.
+#[derive(Default)]
+struct PolicyDenylist {
+    /// Sequence number of the applied feed.
+    sequence: Option<u64>,
+    /// Rules, with the time they expire at in milliseconds since the Unix epoch.
+    rules: Vec<(TransactionPolicyRule, u64)>,
+}
+
 impl TransactionDenyConfigManager {
     pub fn new(
         self_authority: AuthorityName,
@@ -93,9 +113,17 @@ impl TransactionDenyConfigManager {
             }
         }
 
+        let operator_policy = match local_config.transaction_policy_config() {
+            Some(config) => config.load().map_err(SuiError::from)?,
+            None => TransactionPolicy::default(),
+        };
+
         let metrics = TransactionDenyConfigMetrics::new(registry);
+        metrics.policy_rules.set(operator_policy.rules.len() as i64);
         let evaluation = evaluate(&local_config, &sync_config, &peer_configs, &committee);
-        let effective = local_config.with_rules(evaluation.effective_rules.clone());
+        let effective = local_config
+            .with_rules(evaluation.effective_rules.clone())
+            .with_transaction_policy(operator_policy.clone());
         metrics.record(
             &local_config,
             active_proposal_count(&peer_configs),
@@ -118,6 +146,8 @@ impl TransactionDenyConfigManager {
             effective_config: ArcSwap::from_pointee(effective),
             perpetual,
             broadcast_generation_lock: Mutex::new(()),
+            operator_policy: ArcSwap::from_pointee(operator_policy),
+            policy_denylist: Mutex::new(PolicyDenylist::default()),
             metrics,
         }))
     }
@@ -395,12 +425,147 @@ impl TransactionDenyConfigManager {
     fn apply_evaluation(&self, evaluation: DenyConfigEvaluation, active_proposals: usize) {
         self.metrics
             .record(&self.local_config, active_proposals, &evaluation);
-        let prev = self.effective_config.load();
-        if &evaluation.effective_rules != prev.rules() {
-            let new_effective = self.local_config.with_rules(evaluation.effective_rules);
-            self.effective_config.store(Arc::new(new_effective));
+        if &evaluation.effective_rules != self.effective_config.load().rules() {
+            // Start from the current effective config so the active transaction policy is kept.
+            self.effective_config
+                .rcu(|prev| prev.with_rules(evaluation.effective_rules.clone()));
+        }
+    }
+
+    /// Returns the transaction policy currently checked at signing time.
+    pub fn transaction_policy(&self) -> Arc<TransactionPolicy> {
+        self.effective_config.load().transaction_policy().clone()
+    }
+
+    /// Whether the transaction policy can be reloaded, i.e. it is backed by a file.
+    pub fn is_transaction_policy_reloadable(&self) -> bool {
+        self.local_config
+            .transaction_policy_config()
+            .is_some_and(|config| config.path.is_some())
+    }
+
+    /// Re-read the transaction policy file and atomically swap in the new policy. Returns the
+    /// number of rules in the new policy.
+    pub fn reload_transaction_policy(&self) -> Result<usize, String> {
+        let Some(config) = self
+            .local_config
+            .transaction_policy_config()
+            .filter(|config| config.path.is_some())
+        else {
+            return Err("transaction policy is not loaded from a file".to_string());
+        };
+
+        match config.load() {
+            Ok(policy) => {
+                let rules = policy.rules.len();
+                self.operator_policy.store(Arc::new(policy));
+                self.update_transaction_policy(&self.policy_denylist.lock());
+                self.metrics.policy_reloads.inc();
+                info!(rules, "transaction policy reloaded");
+                Ok(rules)
+            }
+            Err(e) => {
+                self.metrics.policy_reload_failures.inc();
+                warn!("failed to reload transaction policy, keeping previous policy: {e}");
+                Err(e)
+            }
+        }
+    }
+
+    /// Replace the denylist with the rules of the feed with the given sequence number, each with
+    /// the time it expires at, in milliseconds since the Unix epoch. Feeds older than the applied
+    /// one are rejected, so that replaying an older feed cannot lift denials. Returns the number of
+    /// unexpired rules.
+    pub fn apply_denylist(
+        &self,
+        sequence: u64,
+        rules: Vec<(TransactionPolicyRule, u64)>,
+        now_ms: u64,
+    ) -> Result<usize, String> {
+        let mut denylist = self.policy_denylist.lock();
+        match denylist.sequence {
+            Some(applied) if sequence < applied => {
+                return Err(format!(
+                    "denylist feed {sequence} is older than the applied feed {applied}"
+                ));
+            }
+            Some(applied) if sequence == applied => return Ok(denylist.rules.len()),
+            _ => (),
+        }
+
+        let feed_policy = TransactionPolicy {
+            rules: rules.iter().map(|(rule, _)| rule.clone()).collect(),
+            ..Default::default()
+        };
+        feed_policy.validate()?;
+
+        denylist.sequence = Some(sequence);
+        denylist.rules = rules;
+        denylist.rules.retain(|(_, expiry)| *expiry > now_ms);
+        self.update_transaction_policy(&denylist);
+        info!(
+            sequence,
+            rules = denylist.rules.len(),
+            "denylist feed applied"
+        );
+        Ok(denylist.rules.len())
+    }
+
+    /// Drop the denylist rules that expired by `now_ms`.
+    pub fn expire_denylist(&self, now_ms: u64) {
+        let mut denylist = self.policy_denylist.lock();
+        let rules = denylist.rules.len();
+        denylist.rules.retain(|(_, expiry)| *expiry > now_ms);
+        if denylist.rules.len() != rules {
+            info!(
+                expired = rules - denylist.rules.len(),
+                "denylist feed rules expired"
+            );
+            self.update_transaction_policy(&denylist);
+        }
+    }
+
+    /// When the next denylist rule expires, in milliseconds since the Unix epoch.
+    pub fn next_denylist_expiry(&self) -> Option<u64> {
+        self.policy_denylist
+            .lock()
+            .rules
+            .iter()
+            .map(|(_, expiry)| *expiry)
+            .min()
+    }
+
+    /// Count a signing-time rejection if it came from the transaction policy.
+    pub fn record_policy_rejection(&self, error: &SuiError) {
+        if let SuiErrorKind::UserInputError {
+            error: UserInputError::TransactionDeniedByPolicy { rule, .. },
+        } = error.as_inner()
+        {
+            self.metrics
+                .policy_rejections
+                .with_label_values(&[rule.as_str()])
+                .inc();
         }
     }
+
+    fn update_transaction_policy(&self, denylist: &PolicyDenylist) {
+        let operator_policy = self.operator_policy.load();
+        let policy = TransactionPolicy {
+            default_action: operator_policy.default_action,
+            rules: denylist
+                .rules
+                .iter()
+                .map(|(rule, _)| rule.clone())
+                .chain(operator_policy.rules.iter().cloned())
+                .collect(),
+        };
+        self.metrics.policy_rules.set(policy.rules.len() as i64);
+        self.metrics
+            .policy_denylist_rules
+            .set(denylist.rules.len() as i64);
+        self.effective_config
+            .rcu(|prev| prev.with_transaction_policy(policy.clone()));
+    }
 }
 
 /// Evaluate the current voting state from a manager's fields. A free function so `new`
@@ -696,6 +861,11 @@ pub struct TransactionDenyConfigMetrics {
     shared_config_active: IntGaugeVec,
     shared_config_voted_bps: IntGaugeVec,
     shared_config_eligible_stake: IntGaugeVec,
+    policy_rejections: IntCounterVec,
+    policy_rules: IntGauge,
+    policy_reloads: IntCounter,
+    policy_reload_failures: IntCounter,
+    policy_denylist_rules: IntGauge,
 }
 
 impl TransactionDenyConfigMetrics {
@@ -757,6 +927,37 @@ impl TransactionDenyConfigMetrics {
                 registry,
             )
             .unwrap(),
+            policy_rejections: register_int_counter_vec_with_registry!(
+                "tx_policy_rejections",
+                "Number of transactions rejected by the transaction policy, by rule",
+                &["rule"],
+                registry,
+            )
+            .unwrap(),
+            policy_rules: register_int_gauge_with_registry!(
+                "tx_policy_rules",
+                "Number of rules in the active transaction policy",
+                registry,
+            )
+            .unwrap(),
+            policy_reloads: register_int_counter_with_registry!(
+                "tx_policy_reloads",
+                "Number of successful transaction policy reloads",
+                registry,
+            )
+            .unwrap(),
+            policy_reload_failures: register_int_counter_with_registry!(
+                "tx_policy_reload_failures",
+                "Number of transaction policy reloads that failed and kept the previous policy",
+                registry,
+            )
+            .unwrap(),
+            policy_denylist_rules: register_int_gauge_with_registry!(
+                "tx_policy_denylist_rules",
+                "Number of unexpired rules from the denylist feed in the active transaction policy",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -807,11 +1008,16 @@ fn stake_bps(voted: StakeUnit, eligible: StakeUnit) -> i64 {
 mod tests {
     use super::*;
     use fastcrypto::traits::VerifyingKey;
+    use std::io::Write;
     use sui_config::transaction_deny_config::{
         DefaultDenyBucket, SharedDenyRuleThreshold, SharedDenyRuleset,
         TransactionDenyConfigBuilder, ValidatorEligibility,
     };
-    use sui_types::base_types::{ObjectID, dbg_addr};
+    use sui_config::transaction_policy_config::{PolicyAction, TransactionPolicyConfig};
+    use sui_transaction_checks::policy::{PolicyDecision, evaluate_transaction_policy};
+    use sui_types::base_types::{ObjectID, SuiAddress, dbg_addr, random_object_ref};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::TransactionData;
 
     fn fake_name(byte: u8) -> AuthorityName {
         AuthorityName::new([byte; sui_types::crypto::AuthorityPublicKey::LENGTH])
@@ -1614,4 +1820,166 @@ mod tests {
             .is_err()
         );
     }
+
+    fn deny_sender_rule(name: &str, sender: SuiAddress) -> TransactionPolicyRule {
+        TransactionPolicyRule {
+            name: name.to_string(),
+            action: PolicyAction::Deny,
+            senders: BTreeSet::from([sender]),
+            ..Default::default()
+        }
+    }
+
+    /// The name of the rule that denies a transfer from `sender` under the manager's active
+    /// policy, or `None` if it is allowed.
+    fn denying_rule(manager: &TransactionDenyConfigManager, sender: SuiAddress) -> Option<String> {
+        let tx_data = TransactionData::new_programmable(
+            sender,
+            vec![random_object_ref()],
+            ProgrammableTransactionBuilder::new().finish(),
+            1_000_000,
+            1_000,
+        );
+        let effective = manager.effective_config().load();
+        match evaluate_transaction_policy(effective.transaction_policy(), &tx_data, &[], &[]) {
+            PolicyDecision::Allow => None,
+            PolicyDecision::Deny { rule, .. } => Some(rule.unwrap_or_default().to_string()),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn transaction_policy_reload_keeps_previous_on_error() {
+        let (committee, names) = test_committee(4);
+        let sender = SuiAddress::random_for_testing_only();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"default-action: allow\n").unwrap();
+        let local = TransactionDenyConfigBuilder::new()
+            .set_transaction_policy(TransactionPolicyConfig {
+                path: Some(file.path().to_path_buf()),
+                policy: Default::default(),
+            })
+            .build();
+        let (manager, _dir) =
+            manager_with(names[0], local, PeerDenySyncConfig::default(), committee);
+        assert!(manager.is_transaction_policy_reloadable());
+        assert_eq!(denying_rule(&manager, sender), None);
+
+        let policy = TransactionPolicy {
+            default_action: PolicyAction::Allow,
+            rules: vec![deny_sender_rule("deny-sender", sender)],
+        };
+        std::fs::write(file.path(), serde_yaml::to_string(&policy).unwrap()).unwrap();
+        assert_eq!(manager.reload_transaction_policy().unwrap(), 1);
+        assert_eq!(
+            denying_rule(&manager, sender).as_deref(),
+            Some("deny-sender")
+        );
+
+        std::fs::write(file.path(), "rules: not-a-list").unwrap();
+        assert!(manager.reload_transaction_policy().is_err());
+        assert_eq!(
+            denying_rule(&manager, sender).as_deref(),
+            Some("deny-sender")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn inline_transaction_policy_is_not_reloadable() {
+        let (committee, names) = test_committee(4);
+        let sender = SuiAddress::random_for_testing_only();
+        let local = TransactionDenyConfigBuilder::new()
+            .set_transaction_policy(TransactionPolicyConfig {
+                path: None,
+                policy: TransactionPolicy {
+                    default_action: PolicyAction::Allow,
+                    rules: vec![deny_sender_rule("deny-sender", sender)],
+                },
+            })
+            .build();
+        let (manager, _dir) =
+            manager_with(names[0], local, PeerDenySyncConfig::default(), committee);
+        assert_eq!(
+            denying_rule(&manager, sender).as_deref(),
+            Some("deny-sender")
+        );
+        assert!(!manager.is_transaction_policy_reloadable());
+        assert!(manager.reload_transaction_policy().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn denylist_rules_apply_before_operator_rules_and_expire() {
+        let (committee, names) = test_committee(4);
+        let sender = SuiAddress::random_for_testing_only();
+        let (manager, _dir) = manager_with(
+            names[0],
+            TransactionDenyConfigBuilder::new().build(),
+            PeerDenySyncConfig::default(),
+            committee,
+        );
+
+        assert_eq!(
+            manager
+                .apply_denylist(
+                    2,
+                    vec![
+                        (deny_sender_rule("a", sender), 1_000),
+                        (deny_sender_rule("b", sender), 2_000)
+                    ],
+                    500
+                )
+                .unwrap(),
+            2
+        );
+        assert_eq!(manager.next_denylist_expiry(), Some(1_000));
+        assert_eq!(denying_rule(&manager, sender).as_deref(), Some("a"));
+
+        // Older feeds are rejected, and the applied one is kept.
+        assert!(manager.apply_denylist(1, vec![], 500).is_err());
+        assert_eq!(denying_rule(&manager, sender).as_deref(), Some("a"));
+
+        manager.expire_denylist(1_500);
+        assert_eq!(manager.next_denylist_expiry(), Some(2_000));
+        assert_eq!(denying_rule(&manager, sender).as_deref(), Some("b"));
+        manager.expire_denylist(2_000);
+        assert_eq!(manager.next_denylist_expiry(), None);
+        assert_eq!(denying_rule(&manager, sender), None);
+    }
+
+    /// Re-evaluating peer proposals replaces the deny rules but keeps the active policy.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn peer_evaluation_keeps_transaction_policy() {
+        let (committee, names) = test_committee(4);
+        let sender = SuiAddress::random_for_testing_only();
+        let sync = PeerDenySyncConfig {
+            default_buckets: vec![default_bucket(
+                "objs",
+                &[DenyElementKind::Object],
+                ValidatorEligibility::default(),
+                10,
+            )],
+            ..Default::default()
+        };
+        let (manager, _dir) = manager_with(
+            names[0],
+            TransactionDenyConfigBuilder::new().build(),
+            sync,
+            committee,
+        );
+        manager
+            .apply_denylist(1, vec![(deny_sender_rule("feed", sender), u64::MAX)], 0)
+            .unwrap();
+
+        manager.apply_updates(
+            names[1],
+            vec![make_msg(names[1], 10, Some(rules_with_objects(&[1])))],
+        );
+        assert!(
+            manager
+                .effective_config()
+                .load()
+                .get_object_deny_set()
+                .contains(&ObjectID::from_single_byte(1))
+        );
+        assert_eq!(denying_rule(&manager, sender).as_deref(), Some("feed"));
+    }
 }
@@ -21,6 +21,8 @@ use std::{
 
 use arc_swap::ArcSwap;
 use effects_certifier::*;
+use error::{AggregatedEffectsDigests, TransactionRequestError, aggregate_request_errors};
+use futures::future::join_all;
 use mysten_common::backoff::ExponentialBackoff;
 use mysten_metrics::{monitored_future, spawn_logged_monitored_task};
 use parking_lot::Mutex;
@@ -28,10 +30,10 @@ use rand::Rng;
 use sui_config::NodeConfig;
 use sui_types::{
     base_types::AuthorityName,
-    committee::EpochId,
+    committee::{CommitteeTrait as _, EpochId},
     error::{ErrorCategory, UserInputError},
-    messages_grpc::{SubmitTxRequest, SubmitTxResult, TxType},
-    transaction::TransactionDataAPI as _,
+    messages_grpc::{SubmitSoftBundleRequest, SubmitTxRequest, SubmitTxResult, TxType},
+    transaction::{Transaction, TransactionDataAPI as _},
 };
 use tokio::{
     task::JoinSet,
@@ -316,6 +318,105 @@ where
         }
     }
 
+    /// Submits `transactions` as a soft bundle and drives each of them to finalization.
+    ///
+    /// The bundle is accepted by a single validator, which makes a best effort to sequence the
+    /// transactions adjacently and in order. After that each transaction is finalized on its own:
+    /// one can be rejected or deferred without affecting the others, so a result is returned per
+    /// transaction, in request order. Rejected transactions are not resubmitted, since they would
+    /// no longer be sequenced with the rest of the bundle.
+    #[instrument(level = "error", skip_all, fields(num_transactions = transactions.len()))]
+    pub async fn drive_soft_bundle(
+        &self,
+        transactions: Vec<Transaction>,
+        options: SubmitTransactionOptions,
+        timeout_duration: Option<Duration>,
+    ) -> Result<
+        Vec<Result<QuorumTransactionResponse, TransactionDriverError>>,
+        TransactionDriverError,
+    > {
+        if transactions.is_empty() {
+            return Err(TransactionDriverError::ValidationFailed {
+                error: "Soft bundle must contain at least one transaction".to_string(),
+            });
+        }
+
+        let auth_agg = self.authority_aggregator.load_full();
+        let request = SubmitSoftBundleRequest { transactions };
+
+        let drive = async {
+            let (name, results) = self
+                .submitter
+                .submit_soft_bundle(&auth_agg, &self.client_monitor, &request, &options)
+                .await?;
+
+            let finalize = request
+                .transactions
+                .iter()
+                .zip(results)
+                .map(|(transaction, result)| {
+                    let auth_agg = &auth_agg;
+                    let options = &options;
+                    async move {
+                        if let SubmitTxResult::Rejected { error } = result {
+                            let error = TransactionRequestError::RejectedAtValidator(error);
+                            let retriable = error.is_submission_retriable();
+                            let errors = aggregate_request_errors(vec![(
+                                name,
+                                auth_agg.committee.weight(&name),
+                                error,
+                            )]);
+                            let no_errors = aggregate_request_errors(vec![]);
+                            return Err(if retriable {
+                                TransactionDriverError::Aborted {
+                                    submission_non_retriable_errors: no_errors,
+                                    submission_retriable_errors: errors,
+                                    observed_effects_digests: AggregatedEffectsDigests {
+                                        digests: Vec::new(),
+                                    },
+                                }
+                            } else {
+                                TransactionDriverError::RejectedByValidators {
+                                    submission_non_retriable_errors: errors,
+                                    submission_retriable_errors: no_errors,
+                                }
+                            });
+                        }
+
+                        let tx_type = if transaction.is_consensus_tx() {
+                            TxType::SharedObject
+                        } else {
+                            TxType::SingleWriter
+                        };
+                        self.certifier
+                            .get_certified_finalized_effects(
+                                auth_agg,
+                                &self.client_monitor,
+                                Some(*transaction.digest()),
+                                tx_type,
+                                name,
+                                result,
+                                options,
+                            )
+                            .await
+                    }
+                });
+
+            Ok::<_, TransactionDriverError>(join_all(finalize).await)
+        };
+
+        match timeout_duration {
+            Some(duration) => tokio::time::timeout(duration, drive).await.unwrap_or(Err(
+                TransactionDriverError::TimeoutWithLastRetriableError {
+                    last_error: None,
+                    attempts: 0,
+                    timeout: duration,
+                },
+            )),
+            None => drive.await,
+        }
+    }
+
     #[instrument(level = "error", skip_all, err(level = "debug"))]
     async fn drive_transaction_once(
         &self,
@@ -10,7 +10,7 @@ use futures::stream::{FuturesUnordered, StreamExt};
 use sui_types::{
     base_types::AuthorityName,
     error::ErrorCategory,
-    messages_grpc::{SubmitTxRequest, SubmitTxResult, TxType},
+    messages_grpc::{SubmitSoftBundleRequest, SubmitTxRequest, SubmitTxResult, TxType},
 };
 use tokio::time::timeout;
 use tracing::instrument;
@@ -221,6 +221,52 @@ impl TransactionSubmitter {
         }
     }
 
+    /// Submits a soft bundle to one validator at a time, until one accepts it.
+    ///
+    /// Unlike single transactions, the bundle is never amplified to several validators at once,
+    /// since their copies of the bundle could be sequenced apart from each other.
+    /// Returns the validator that accepted the bundle, and its result for each transaction.
+    #[instrument(level = "debug", skip_all, err(level = "debug"))]
+    pub(crate) async fn submit_soft_bundle<A>(
+        &self,
+        authority_aggregator: &Arc<AuthorityAggregator<A>>,
+        client_monitor: &Arc<ValidatorClientMonitor<A>>,
+        request: &SubmitSoftBundleRequest,
+        options: &SubmitTransactionOptions,
+    ) -> Result<(AuthorityName, Vec<SubmitTxResult>), TransactionDriverError>
+    where
+        A: AuthorityAPI + Send + Sync + 'static + Clone,
+    {
+        let mut retrier = RequestRetrier::new(
+            authority_aggregator,
+            client_monitor,
+            options.allowed_validators.clone(),
+            options.blocked_validators.clone(),
+        );
+
+        loop {
+            let (name, client) = retrier.next_target()?;
+            let error = match timeout(
+                SUBMIT_TRANSACTION_TIMEOUT,
+                client.submit_soft_bundle(request.clone(), options.forwarded_client_addr),
+            )
+            .await
+            {
+                Ok(Ok(response)) => return Ok((name, response.results)),
+                Ok(Err(error)) => TransactionRequestError::RejectedAtValidator(error),
+                Err(_) => TransactionRequestError::TimedOutSubmittingTransaction,
+            };
+
+            let display_name = authority_aggregator.get_display_name(&name);
+            let error_type: &str = error.categorize().into();
+            self.metrics
+                .validator_submit_transaction_errors
+                .with_label_values(&[display_name.as_str(), error_type, "soft_bundle", "false"])
+                .inc();
+            retrier.add_error(name, error)?;
+        }
+    }
+
     #[instrument(level = "debug", skip_all, err(level = "debug"), ret, fields(validator_display_name = ?display_name))]
     pub(crate) async fn submit_transaction_once<A>(
         &self,
@@ -20,13 +20,20 @@ use prometheus::{
 };
 use rand::Rng;
 use sui_config::NodeConfig;
+use sui_protocol_config::ProtocolConfig;
 use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
-use sui_types::base_types::TransactionDigest;
+use sui_types::base_types::{ObjectID, ObjectRef, TransactionDigest};
+use sui_types::crypto::{Signature, SuiSignature};
 use sui_types::effects::TransactionEffectsAPI;
-use sui_types::error::{ErrorCategory, SuiError, SuiErrorKind, SuiResult};
+use sui_types::error::{ErrorCategory, SuiError, SuiErrorKind, SuiResult, UserInputError};
+use sui_types::fp_ensure;
 use sui_types::messages_grpc::{SubmitTxRequest, TxType};
+use sui_types::object::Owner;
+use sui_types::storage::ObjectStore;
 use sui_types::sui_system_state::SuiSystemState;
-use sui_types::transaction::{Transaction, TransactionData, VerifiedTransaction};
+use sui_types::transaction::{
+    InputObjectKind, Transaction, TransactionData, TransactionDataAPI, VerifiedTransaction,
+};
 use sui_types::transaction_driver_types::{
     EffectsFinalityInfo, ExecuteTransactionRequestType, ExecuteTransactionRequestV3,
     ExecuteTransactionResponseV3, FinalizedEffects, IsTransactionExecutedLocally,
@@ -40,6 +47,9 @@ use tracing::{Instrument, debug, error_span, info, instrument, warn};
 use crate::authority::AuthorityState;
 use crate::authority_aggregator::AuthorityAggregator;
 use crate::authority_client::{AuthorityAPI, NetworkAuthorityClient};
+use crate::object_reservations::{
+    MAX_RESERVED_OBJECTS_PER_OWNER, ObjectReservations, reservation_message,
+};
 use crate::transaction_driver::{OnsiteReconfigObserver, ReconfigObserver};
 use crate::transaction_driver::{
     QuorumTransactionResponse, SubmitTransactionOptions, TransactionDriver, TransactionDriverError,
@@ -53,6 +63,13 @@ const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(10);
 // Timeout for waiting for finality for each transaction.
 const WAIT_FOR_FINALITY_TIMEOUT: Duration = Duration::from_secs(90);
 
+// Longest time owned objects can be reserved for an upcoming transaction.
+pub const MAX_OBJECT_RESERVATION_TTL: Duration = Duration::from_secs(30);
+
+/// The caller that the orchestrator's waits on the epoch store are registered as, so that they
+/// count against a quota of their own.
+const ORCHESTRATOR_NOTIFY_READ_CALLER: &str = "orchestrator";
+
 pub type QuorumTransactionEffectsResult = Result<
     (Transaction, QuorumTransactionResponse),
     (TransactionDigest, TransactionSubmissionError),
@@ -162,6 +179,7 @@ where
             td_allowed_submission_list,
             td_blocked_submission_list,
             enable_early_validation,
+            object_reservations: ObjectReservations::new(),
         });
         Self { inner }
     }
@@ -194,8 +212,10 @@ where
         let tx_digest = *request.transaction.digest();
 
         let inner = self.inner.clone();
+        // Run in the request's span, so that its logs can be correlated with the client's.
         let (response, mut executed_locally) = spawn_monitored_task!(
             Inner::<A>::execute_transaction_with_retry(inner, request, client_addr)
+                .in_current_span()
         )
         .await
         .map_err(|e| TransactionSubmissionError::TransactionFailed {
@@ -275,11 +295,10 @@ where
         };
 
         let inner = self.inner.clone();
-        let (response, _) = spawn_monitored_task!(Inner::<A>::execute_transaction_with_retry(
-            inner,
-            request,
-            client_addr
-        ))
+        let (response, _) = spawn_monitored_task!(
+            Inner::<A>::execute_transaction_with_retry(inner, request, client_addr)
+                .in_current_span()
+        )
         .await
         .map_err(|e| TransactionSubmissionError::TransactionFailed {
             category: ErrorCategory::Internal,
@@ -309,6 +328,185 @@ where
         })
     }
 
+    /// Submits the transactions in `requests` as a soft bundle, which a validator sequences
+    /// adjacently and in order on a best-effort basis, and waits for each of them to finalize.
+    ///
+    /// Returns a result per transaction, in request order. Unlike single transactions, bundled
+    /// transactions that fail are not retried in the background, since a retry would not be
+    /// sequenced with the rest of the bundle.
+    #[instrument(name = "tx_orchestrator_execute_soft_bundle", level = "debug", skip_all,
+                 fields(num_transactions = requests.len(), tx_type = ?request_type))]
+    pub async fn execute_soft_bundle(
+        &self,
+        requests: Vec<ExecuteTransactionRequestV3>,
+        request_type: ExecuteTransactionRequestType,
+        client_addr: Option<SocketAddr>,
+    ) -> Result<
+        Vec<
+            Result<
+                (ExecuteTransactionResponseV3, IsTransactionExecutedLocally),
+                TransactionSubmissionError,
+            >,
+        >,
+        TransactionSubmissionError,
+    > {
+        let validator_state = &self.inner.validator_state;
+        if !validator_state.config.enable_soft_bundle {
+            return Err(TransactionSubmissionError::TransactionFailed {
+                category: ErrorCategory::InvalidTransaction,
+                details: "Soft bundle submission is disabled on this node".to_string(),
+            });
+        }
+
+        let epoch_store = validator_state.load_epoch_store_one_call_per_task();
+        check_soft_bundle_limits(epoch_store.protocol_config(), &requests)?;
+        let transactions = requests
+            .iter()
+            .map(|request| {
+                let verified_transaction = epoch_store
+                    .verify_transaction_with_current_aliases(request.transaction.clone())
+                    .map_err(TransactionSubmissionError::InvalidUserSignature)?
+                    .into_tx();
+                self.inner
+                    .check_object_reservations(&verified_transaction)
+                    .map_err(|e| TransactionSubmissionError::TransactionFailed {
+                        category: e.categorize(),
+                        details: e.to_string(),
+                    })?;
+                Ok(request.transaction.clone())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let results = self
+            .inner
+            .transaction_driver
+            .drive_soft_bundle(
+                transactions,
+                SubmitTransactionOptions {
+                    forwarded_client_addr: client_addr,
+                    allowed_validators: self.inner.td_allowed_submission_list.clone(),
+                    blocked_validators: self.inner.td_blocked_submission_list.clone(),
+                },
+                Some(WAIT_FOR_FINALITY_TIMEOUT),
+            )
+            .await
+            .map_err(into_submission_error)?;
+
+        let wait_for_local_execution = matches!(
+            request_type,
+            ExecuteTransactionRequestType::WaitForLocalExecution
+        );
+        let responses = requests
+            .iter()
+            .zip(results)
+            .map(|(request, result)| async move {
+                let response = result.map_err(into_submission_error)?;
+                self.inner
+                    .object_reservations
+                    .release(*request.transaction.digest());
+                let executed_locally = wait_for_local_execution
+                    && Inner::<A>::wait_for_finalized_tx_executed_locally_with_timeout(
+                        validator_state,
+                        *request.transaction.digest(),
+                        if request.transaction.is_consensus_tx() {
+                            TxType::SharedObject
+                        } else {
+                            TxType::SingleWriter
+                        },
+                        &self.inner.metrics,
+                    )
+                    .await
+                    .is_ok();
+
+                let QuorumTransactionResponse {
+                    effects,
+                    events,
+                    input_objects,
+                    output_objects,
+                    auxiliary_data,
+                } = response;
+                Ok::<_, TransactionSubmissionError>((
+                    ExecuteTransactionResponseV3 {
+                        effects,
+                        events,
+                        input_objects,
+                        output_objects,
+                        auxiliary_data,
+                    },
+                    executed_locally,
+                ))
+            });
+
+        Ok(futures::future::join_all(responses).await)
+    }
+
+    /// Reserves the owned objects `object_ids` for the upcoming transaction `tx_digest`, for `ttl`
+    /// capped at [`MAX_OBJECT_RESERVATION_TTL`]. Until the reservation expires or the transaction
+    /// is finalized, other transactions using any of the objects are rejected by this node
+    /// before submission. Fails without reserving anything if an object is already reserved for
+    /// another transaction. Returns the latest known references of the reserved objects.
+    ///
+    /// All objects must be owned by the same address, and `signature` must be that owner's
+    /// signature over [`reservation_message`] for `object_ids` and `tx_digest`.
+    pub fn reserve_objects(
+        &self,
+        object_ids: &[ObjectID],
+        tx_digest: TransactionDigest,
+        signature: &Signature,
+        ttl: Duration,
+    ) -> SuiResult<Vec<ObjectRef>> {
+        fp_ensure!(
+            !object_ids.is_empty() && object_ids.len() <= MAX_RESERVED_OBJECTS_PER_OWNER,
+            UserInputError::SizeLimitExceeded {
+                limit: "maximum reserved objects per owner".to_string(),
+                value: MAX_RESERVED_OBJECTS_PER_OWNER.to_string(),
+            }
+            .into()
+        );
+
+        let object_store = self.inner.validator_state.get_object_store();
+        let mut owner = None;
+        let mut objects = Vec::with_capacity(object_ids.len());
+        for object_id in object_ids {
+            let object =
+                object_store
+                    .get_object(object_id)
+                    .ok_or(UserInputError::ObjectNotFound {
+                        object_id: *object_id,
+                        version: None,
+                    })?;
+            let Owner::AddressOwner(object_owner) = object.owner else {
+                return Err(UserInputError::NotOwnedObjectError.into());
+            };
+            if *owner.get_or_insert(object_owner) != object_owner {
+                return Err(SuiErrorKind::InvalidRequest(
+                    "All reserved objects must have the same owner".to_string(),
+                )
+                .into());
+            }
+            objects.push(object.compute_object_reference());
+        }
+        let owner = owner.expect("at least one object is reserved");
+
+        signature
+            .verify_secure(
+                &reservation_message(object_ids, tx_digest),
+                owner,
+                signature.scheme(),
+            )
+            .map_err(|e| UserInputError::IncorrectUserSignature {
+                error: format!("Reservation is not signed by the owner {owner}: {e}"),
+            })?;
+
+        self.inner.object_reservations.reserve(
+            owner,
+            &objects,
+            tx_digest,
+            ttl.min(MAX_OBJECT_RESERVATION_TTL),
+        )?;
+        Ok(objects)
+    }
+
     pub fn authority_state(&self) -> &Arc<AuthorityState> {
         &self.inner.validator_state
     }
@@ -333,6 +531,74 @@ where
     }
 }
 
+/// Rejects soft bundles that validators would refuse for their number of transactions or total
+/// size, before any of their signatures are verified. Bundles get half of the consensus block
+/// size, as on validators.
+fn check_soft_bundle_limits(
+    protocol_config: &ProtocolConfig,
+    requests: &[ExecuteTransactionRequestV3],
+) -> Result<(), TransactionSubmissionError> {
+    let invalid = |details: String| TransactionSubmissionError::TransactionFailed {
+        category: ErrorCategory::InvalidTransaction,
+        details,
+    };
+
+    if requests.is_empty() {
+        return Err(invalid(
+            "A soft bundle must contain at least one transaction".to_string(),
+        ));
+    }
+
+    let max_transactions = protocol_config
+        .max_soft_bundle_size_as_option()
+        .unwrap_or(0);
+    if requests.len() as u64 > max_transactions {
+        return Err(invalid(format!(
+            "Too many transactions in soft bundle: {} vs {max_transactions}",
+            requests.len()
+        )));
+    }
+
+    let max_bytes = protocol_config.consensus_max_transactions_in_block_bytes() / 2;
+    let mut total_bytes = 0u64;
+    for request in requests {
+        let size = request
+            .transaction
+            .data()
+            .serialized_size()
+            .map_err(|e| invalid(e.to_string()))?;
+        total_bytes += size as u64;
+        if total_bytes > max_bytes {
+            return Err(invalid(
+                UserInputError::TotalTransactionSizeTooLargeInBatch {
+                    size: total_bytes as usize,
+                    limit: max_bytes,
+                }
+                .to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn into_submission_error(error: TransactionDriverError) -> TransactionSubmissionError {
+    match error {
+        TransactionDriverError::TimeoutWithLastRetriableError {
+            last_error,
+            attempts,
+            timeout,
+        } => TransactionSubmissionError::TimeoutBeforeFinalityWithErrors {
+            last_error: last_error.map(|e| e.to_string()).unwrap_or_default(),
+            attempts,
+            timeout,
+        },
+        other => TransactionSubmissionError::TransactionFailed {
+            category: other.categorize(),
+            details: other.to_string(),
+        },
+    }
+}
+
 struct Inner<A: Clone> {
     validator_state: Arc<AuthorityState>,
     pending_tx_log: Arc<WritePathPendingTransactionLog>,
@@ -341,6 +607,7 @@ struct Inner<A: Clone> {
     td_allowed_submission_list: Vec<String>,
     td_blocked_submission_list: Vec<String>,
     enable_early_validation: bool,
+    object_reservations: ObjectReservations,
 }
 
 impl<A> Inner<A>
@@ -361,6 +628,12 @@ where
             )
             .await;
 
+        if result.is_ok() {
+            inner
+                .object_reservations
+                .release(*request.transaction.digest());
+        }
+
         // If the error is retriable, retry the transaction sufficiently long.
         if let Err(e) = &result
             && e.is_retriable()
@@ -425,12 +698,28 @@ where
                     tracing::debug!("Wait for {:.3}s before next retry", delay.as_secs_f32());
                     sleep(delay).await;
                 }
-            });
+            }
+            .in_current_span());
         }
 
         result
     }
 
+    fn check_object_reservations(&self, transaction: &VerifiedTransaction) -> SuiResult {
+        let owned_objects = transaction
+            .data()
+            .transaction_data()
+            .input_objects()?
+            .into_iter()
+            .filter_map(|kind| match kind {
+                InputObjectKind::ImmOrOwnedMoveObject(obj_ref) => Some(obj_ref),
+                InputObjectKind::MovePackage(_) | InputObjectKind::SharedMoveObject { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        self.object_reservations
+            .check(&owned_objects, *transaction.digest())
+    }
+
     fn build_response_from_local_effects(
         &self,
         effects: sui_types::effects::TransactionEffects,
@@ -491,6 +780,13 @@ where
             .into_tx();
         let tx_digest = *verified_transaction.digest();
 
+        // Fail fast if an owned input is reserved for a different transaction.
+        self.check_object_reservations(&verified_transaction)
+            .map_err(|e| TransactionSubmissionError::TransactionFailed {
+                category: e.categorize(),
+                details: e.to_string(),
+            })?;
+
         // Early validation check against local state before submission to catch non-retriable errors
         // TODO: Consider moving this check to TransactionDriver for per-retry validation
         if self.enable_early_validation
@@ -783,21 +1079,7 @@ where
                 timeout_duration,
             )
             .await
-            .map_err(|e| match e {
-                TransactionDriverError::TimeoutWithLastRetriableError {
-                    last_error,
-                    attempts,
-                    timeout,
-                } => TransactionSubmissionError::TimeoutBeforeFinalityWithErrors {
-                    last_error: last_error.map(|e| e.to_string()).unwrap_or_default(),
-                    attempts,
-                    timeout,
-                },
-                other => TransactionSubmissionError::TransactionFailed {
-                    category: other.categorize(),
-                    details: other.to_string(),
-                },
-            });
+            .map_err(into_submission_error);
 
         match td_response {
             Err(e) => {
@@ -847,9 +1129,12 @@ where
             // so it is guaranteed to be available when this resolves.
             let epoch_store = validator_state.load_epoch_store_one_call_per_task();
             epoch_store
-                .transactions_executed_in_checkpoint_notify(vec![tx_digest])
+                .transactions_executed_in_checkpoint_notify_bounded(
+                    ORCHESTRATOR_NOTIFY_READ_CALLER,
+                    vec![tx_digest],
+                )
                 .await
-                .expect("db error waiting for transaction checkpointing");
+                .map(|_| ())
         })
         .instrument(error_span!(
             "transaction_orchestrator::local_execution",
@@ -865,7 +1150,11 @@ where
                 metrics.local_execution_timeout.inc();
                 Err(SuiErrorKind::TimeoutError.into())
             }
-            Ok(_) => {
+            Ok(Err(e)) => {
+                debug!("Waiting for finalized tx to be executed locally failed: {e}");
+                Err(e)
+            }
+            Ok(Ok(())) => {
                 metrics.local_execution_success.inc();
                 Ok(())
             }
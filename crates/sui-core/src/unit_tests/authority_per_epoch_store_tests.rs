@@ -59,6 +59,49 @@ async fn test_notify_read_executed_transactions_to_checkpoint() {
     assert_eq!(result[2].unwrap(), checkpoint_sequence_2);
 }
 
+/// A bounded wait resolves like the unbounded one, and stops counting against its caller's quota
+/// once it has.
+#[tokio::test]
+async fn test_bounded_notify_read_executed_transactions_to_checkpoint() {
+    let authority_state = TestAuthorityBuilder::new().build().await;
+    let store = authority_state.epoch_store_for_testing();
+    let digests = vec![TransactionDigest::random(), TransactionDigest::random()];
+    store
+        .insert_finalized_transactions(&digests[..1], 10)
+        .expect("Should not fail");
+
+    let store_clone = authority_state.epoch_store_for_testing();
+    let digests_cloned = digests.clone();
+    let handle = tokio::spawn(async move {
+        store_clone
+            .transactions_executed_in_checkpoint_notify_bounded("test", digests_cloned)
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+        store
+            .executed_transactions_to_checkpoint_notify_read
+            .outstanding_waiters_for("test"),
+        2
+    );
+
+    store
+        .insert_finalized_transactions(&digests[1..], 12)
+        .expect("Should not fail");
+    let checkpoints = timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("Should not timeout")
+        .expect("Should not panic")
+        .expect("Should not fail");
+    assert_eq!(checkpoints, vec![10, 12]);
+    assert_eq!(
+        store
+            .executed_transactions_to_checkpoint_notify_read
+            .outstanding_waiters_for("test"),
+        0
+    );
+}
+
 /// Verifies that calling `notify_barrier_executed` with an `AccumulatorSettlement`
 /// key resolves `notify_read_tx_key_to_digest`, which is the mechanism used by the
 /// scheduler to detect that the barrier transaction has already been executed
@@ -103,3 +146,92 @@ async fn test_notify_barrier_executed_resolves_tx_key_wait() {
         "notify_read_tx_key_to_digest should resolve after notify_barrier_executed"
     );
 }
+
+/// A dropped notification leaves its waiter pending until the key is notified again, and is
+/// counted by the injector.
+#[cfg(msim)]
+#[sui_macros::sim_test]
+async fn test_fault_injection_drops_notifications() {
+    use crate::authority::epoch_store_faults::{EpochStoreFaultInjector, EpochStoreNotification};
+
+    let authority_state = TestAuthorityBuilder::new().build().await;
+    let store = authority_state.epoch_store_for_testing();
+    let key = TransactionKey::AccumulatorSettlement(store.epoch(), 7);
+    let barrier_digest = TransactionDigest::random();
+
+    let faults = EpochStoreFaultInjector::for_authority(store.name);
+    faults.drop_notifications(EpochStoreNotification::TransactionKeyExecuted, 1);
+
+    let store_clone = authority_state.epoch_store_for_testing();
+    let mut handle = tokio::spawn(async move {
+        store_clone
+            .notify_read_tx_key_to_digest(&[key])
+            .await
+            .unwrap()
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    store.notify_barrier_executed(key, barrier_digest);
+    assert!(
+        timeout(Duration::from_millis(500), &mut handle)
+            .await
+            .is_err(),
+        "the dropped notification should not wake the waiter"
+    );
+
+    store.notify_barrier_executed(key, barrier_digest);
+    let digests = timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("should not timeout")
+        .expect("task should not panic");
+    assert_eq!(digests, vec![barrier_digest]);
+
+    let injected = faults.injected();
+    assert_eq!(
+        injected
+            .dropped_notifications
+            .get(&EpochStoreNotification::TransactionKeyExecuted),
+        Some(&1)
+    );
+}
+
+#[test]
+fn test_resized_mutex_table_shards() {
+    use super::resized_mutex_table_shards;
+    use sui_config::node::MutexTableConfig;
+
+    // Without a threshold, tables are never resized.
+    let config = MutexTableConfig::default();
+    assert_eq!(resized_mutex_table_shards(1024, 1.0, &config), 1024);
+
+    let config = MutexTableConfig {
+        resize_contention_ratio: Some(0.01),
+        max_lock_shards: Some(3000),
+        ..Default::default()
+    };
+
+    // Tables contended at or below the threshold keep their size.
+    assert_eq!(resized_mutex_table_shards(1024, 0.0, &config), 1024);
+    assert_eq!(resized_mutex_table_shards(1024, 0.01, &config), 1024);
+
+    // More contended tables double, up to the maximum.
+    assert_eq!(resized_mutex_table_shards(1024, 0.02, &config), 2048);
+    assert_eq!(resized_mutex_table_shards(2048, 0.02, &config), 3000);
+    assert_eq!(resized_mutex_table_shards(3000, 0.5, &config), 3000);
+}
+
+#[tokio::test]
+async fn test_next_epoch_mutex_table_config() {
+    let authority_state = TestAuthorityBuilder::new().build().await;
+    let store = authority_state.epoch_store_for_testing();
+
+    // Resizing is off by default, so the next epoch keeps the configured sizes.
+    let config = store.next_mutex_table_config();
+    assert_eq!(config.transaction_lock_shards(), 1024);
+    assert_eq!(config.version_assignment_lock_shards(), 1024);
+
+    // Lock lookups are counted towards the table's contention ratio.
+    let digest = TransactionDigest::random();
+    drop(store.acquire_tx_lock(&digest));
+    assert!(store.mutex_table.contention().shard_accesses > 0);
+}
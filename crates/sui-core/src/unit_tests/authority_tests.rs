@@ -47,7 +47,9 @@ use sui_types::object::Data;
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::randomness_state::get_randomness_state_obj_initial_shared_version;
 use sui_types::sui_system_state::SuiSystemStateWrapper;
-use sui_types::supported_protocol_versions::SupportedProtocolVersions;
+use sui_types::supported_protocol_versions::{
+    SupportedProtocolVersions, SupportedProtocolVersionsWithHashes,
+};
 use sui_types::utils::{
     to_sender_signed_transaction, to_sender_signed_transaction_with_multi_signers,
 };
@@ -5100,6 +5102,154 @@ fn test_choose_next_system_packages() {
     );
 }
 
+#[test]
+fn test_preview_protocol_upgrade() {
+    let o1 = random_object_ref();
+    let o2 = random_object_ref();
+    let o3 = random_object_ref();
+
+    let committee = Committee::new_simple_test_committee().0;
+    let v = &committee.voting_rights;
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_advance_to_highest_supported_protocol_version_for_testing(false);
+
+    let capabilities: Vec<_> = [vec![o1, o2], vec![o1, o2], vec![o1, o2], vec![o1, o3]]
+        .into_iter()
+        .zip(v.iter())
+        .map(|(packages, (name, _))| {
+            AuthorityCapabilitiesV2::new(
+                *name,
+                Chain::Unknown,
+                SupportedProtocolVersions::new_for_testing(1, 2),
+                packages,
+            )
+        })
+        .collect();
+
+    let majority_stake: u64 = v[..3].iter().map(|(_, stake)| stake).sum();
+
+    // Without a buffer, the three validators that agree are enough to upgrade.
+    let preview = AuthorityState::preview_protocol_upgrade(
+        ProtocolVersion::MIN,
+        &protocol_config,
+        &committee,
+        capabilities.clone(),
+        0,
+    );
+    assert_eq!(preview.next_protocol_version, ProtocolVersion::new(2));
+    let mut expected = vec![o1, o2];
+    expected.sort();
+    assert_eq!(preview.next_epoch_system_packages, expected);
+    assert_eq!(preview.effective_threshold, committee.quorum_threshold());
+
+    // Versions cannot be skipped, so only the next version is considered.
+    assert_eq!(preview.votes.len(), 1);
+    let (version, votes) = &preview.votes[0];
+    assert_eq!(*version, ProtocolVersion::new(2));
+    let mut stakes: Vec<_> = votes.iter().map(|v| v.stake).collect();
+    stakes.sort();
+    assert_eq!(stakes, vec![v[3].1, majority_stake]);
+
+    // With the whole buffer, every validator has to agree.
+    let preview = AuthorityState::preview_protocol_upgrade(
+        ProtocolVersion::MIN,
+        &protocol_config,
+        &committee,
+        capabilities,
+        10_000,
+    );
+    assert_eq!(preview.next_protocol_version, ProtocolVersion::MIN);
+    assert!(preview.next_epoch_system_packages.is_empty());
+    assert_eq!(preview.effective_threshold, committee.total_votes());
+    assert_eq!(preview.votes.len(), 1);
+}
+
+#[test]
+fn test_simulate_protocol_upgrade_rollout() {
+    let o1 = random_object_ref();
+    let o2 = random_object_ref();
+
+    let committee = Committee::new_simple_test_committee().0;
+    let names: Vec<_> = committee
+        .voting_rights
+        .iter()
+        .map(|(name, _)| *name)
+        .collect();
+    let mut protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    protocol_config.set_advance_to_highest_supported_protocol_version_for_testing(false);
+
+    // Every validator runs a binary that only supports the current version.
+    let capabilities: Vec<_> = names
+        .iter()
+        .map(|name| {
+            AuthorityCapabilitiesV2::new(
+                *name,
+                Chain::Unknown,
+                SupportedProtocolVersions::new_for_testing(1, 1),
+                vec![o1],
+            )
+        })
+        .collect();
+
+    let binary = NodeBinaryCapabilities {
+        supported_protocol_versions: SupportedProtocolVersionsWithHashes::from_supported_versions(
+            SupportedProtocolVersions::new_for_testing(1, 2),
+            Chain::Unknown,
+        ),
+        available_system_packages: vec![o1, o2],
+    };
+
+    // One more validator installs the new binary every epoch.
+    let stages: Vec<_> = names.iter().map(|name| vec![*name]).collect();
+
+    // Without a buffer, the upgrade happens as soon as a quorum runs the new binary.
+    let rollout = AuthorityState::simulate_protocol_upgrade_rollout(
+        ProtocolVersion::MIN,
+        &protocol_config,
+        &committee,
+        capabilities.clone(),
+        &binary,
+        &stages,
+        Some(0),
+    );
+    assert_eq!(rollout.upgrade_stage, Some(2));
+    assert_eq!(rollout.stages.len(), 3);
+    assert_eq!(
+        rollout.stages[2].next_protocol_version,
+        ProtocolVersion::new(2)
+    );
+    assert!(
+        rollout.stages[..2]
+            .iter()
+            .all(|stage| stage.next_protocol_version == ProtocolVersion::MIN)
+    );
+
+    // With the whole buffer, every validator has to run it.
+    let rollout = AuthorityState::simulate_protocol_upgrade_rollout(
+        ProtocolVersion::MIN,
+        &protocol_config,
+        &committee,
+        capabilities.clone(),
+        &binary,
+        &stages,
+        Some(10_000),
+    );
+    assert_eq!(rollout.upgrade_stage, Some(3));
+
+    // The upgrade never happens if the rollout stops early.
+    let rollout = AuthorityState::simulate_protocol_upgrade_rollout(
+        ProtocolVersion::MIN,
+        &protocol_config,
+        &committee,
+        capabilities,
+        &binary,
+        &stages[..2],
+        Some(0),
+    );
+    assert_eq!(rollout.upgrade_stage, None);
+    assert_eq!(rollout.stages.len(), 2);
+}
+
 #[tokio::test]
 async fn test_gas_smashing() {
     // run a create move object transaction with a given set o gas coins and a budget
@@ -6434,6 +6584,50 @@ async fn test_single_authority_reconfigure() {
     assert_eq!(state.epoch_store_for_testing().epoch(), 1);
 }
 
+#[tokio::test]
+async fn test_effects_signature_served_after_epoch_ends() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object = Object::with_owner_for_testing(sender);
+    let state = init_state_with_objects(vec![gas_object.clone()]).await;
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+
+    let tx_data = TransactionData::new_transfer_sui(
+        dbg_addr(2),
+        sender,
+        None,
+        gas_object.compute_object_reference(),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        rgp,
+    );
+    let transaction = to_sender_signed_transaction(tx_data, &sender_key);
+    let tx_digest = *transaction.digest();
+    submit_and_execute(&state, transaction).await.unwrap();
+
+    let epoch_store = state.epoch_store_for_testing().clone();
+    let signed = state
+        .get_signed_effects_and_maybe_resign(&tx_digest, &epoch_store)
+        .unwrap()
+        .unwrap();
+
+    // A request holding the ended epoch's store is still served the signature from that epoch.
+    state.reconfigure_for_testing().await;
+    epoch_store.release_db_handles();
+    let served = state
+        .get_signed_effects_and_maybe_resign(&tx_digest, &epoch_store)
+        .unwrap()
+        .unwrap();
+    assert_eq!(served.auth_sig(), signed.auth_sig());
+    assert_eq!(served.auth_sig().epoch, 0);
+
+    // Requests made in the new epoch get the effects signed in it.
+    let new_epoch_store = state.epoch_store_for_testing().clone();
+    let resigned = state
+        .get_signed_effects_and_maybe_resign(&tx_digest, &new_epoch_store)
+        .unwrap()
+        .unwrap();
+    assert_eq!(resigned.auth_sig().epoch, 1);
+}
+
 #[tokio::test]
 async fn test_insufficient_balance_for_withdraw_early_error() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
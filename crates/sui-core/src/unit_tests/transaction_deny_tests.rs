@@ -10,10 +10,14 @@ use crate::test_utils::make_transfer_sui_transaction;
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::traits::KeyPair;
 use move_core_types::ident_str;
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::certificate_deny_config::CertificateDenyConfigBuilder;
 use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigBuilder};
+use sui_config::transaction_policy_config::{
+    PolicyAction, TransactionPolicy, TransactionPolicyConfig, TransactionPolicyRule,
+};
 use sui_swarm_config::genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT};
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_test_transaction_builder::TestTransactionBuilder;
@@ -280,6 +284,40 @@ async fn test_signer_denied() {
     assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state));
 }
 
+#[tokio::test]
+async fn test_transaction_policy_denied() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+
+    let state = reload_state_with_new_deny_config(
+        &network_config,
+        state,
+        TransactionDenyConfigBuilder::new()
+            .set_transaction_policy(TransactionPolicyConfig {
+                path: None,
+                policy: TransactionPolicy {
+                    default_action: PolicyAction::Allow,
+                    rules: vec![TransactionPolicyRule {
+                        name: "deny-sender".to_string(),
+                        action: PolicyAction::Deny,
+                        senders: BTreeSet::from([accounts[0].0]),
+                        ..Default::default()
+                    }],
+                },
+            })
+            .build(),
+    )
+    .await;
+    let result = transfer_with_account(&accounts[0], &accounts[0], &state);
+    assert!(matches!(
+        result.unwrap_err().as_inner(),
+        SuiErrorKind::UserInputError {
+            error: UserInputError::TransactionDeniedByPolicy { rule, .. }
+        } if rule == "deny-sender"
+    ));
+    transfer_with_account(&accounts[1], &accounts[1], &state).unwrap();
+}
+
 #[tokio::test]
 async fn test_shared_object_transaction_disabled() {
     let (network_config, state) = setup_test(
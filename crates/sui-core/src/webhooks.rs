@@ -0,0 +1,341 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Webhooks that push executed transactions and events to HTTP endpoints.
+//!
+//! Every endpoint in the node's `webhooks` config subscribes to the [`SubscriptionHandler`] with
+//! filters built from its addresses, packages and event types. Matching payloads are queued per
+//! endpoint and POSTed in order, signed with the endpoint's secret. A payload that fails to be
+//! delivered is retried with exponential backoff, and dropped once the endpoint's attempts are
+//! exhausted, so that one unreachable endpoint does not hold the others back.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, ensure};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hmac::{HmacKey, hmac_sha3_256};
+use fastcrypto::traits::ToFromBytes;
+use futures::StreamExt;
+use futures::stream::{self, BoxStream};
+use mysten_metrics::spawn_monitored_task;
+use prometheus::{IntCounterVec, Registry, register_int_counter_vec_with_registry};
+use serde::Serialize;
+use sui_config::node::WebhookConfig;
+use sui_json_rpc_types::{
+    EventFilter, SuiEvent, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    TransactionFilter,
+};
+use sui_types::base_types::TransactionDigest;
+use sui_types::parse_sui_struct_tag;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::subscription_handler::SubscriptionHandler;
+
+/// Header holding the hex-encoded HMAC-SHA3-256 of the timestamp, a `.`, and the body.
+pub const SIGNATURE_HEADER: &str = "X-Sui-Webhook-Signature";
+
+/// Header holding the time the request was signed at, in milliseconds since the Unix epoch.
+/// Receivers can reject old timestamps to guard against replays.
+pub const TIMESTAMP_HEADER: &str = "X-Sui-Webhook-Timestamp";
+
+/// Number of recently queued transaction digests remembered per endpoint, so that a transaction
+/// matching several of the endpoint's filters is only sent once.
+const RECENT_DIGESTS: usize = 1_000;
+
+pub struct WebhookMetrics {
+    delivered: IntCounterVec,
+    failed_attempts: IntCounterVec,
+    dropped: IntCounterVec,
+}
+
+impl WebhookMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            delivered: register_int_counter_vec_with_registry!(
+                "webhook_delivered",
+                "Number of payloads delivered to each webhook endpoint",
+                &["url"],
+                registry,
+            )
+            .unwrap(),
+            failed_attempts: register_int_counter_vec_with_registry!(
+                "webhook_failed_attempts",
+                "Number of failed attempts to deliver a payload to each webhook endpoint",
+                &["url"],
+                registry,
+            )
+            .unwrap(),
+            dropped: register_int_counter_vec_with_registry!(
+                "webhook_dropped",
+                "Number of payloads dropped for each webhook endpoint, because its queue was full \
+                 or because all attempts to deliver them failed",
+                &["url", "reason"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum WebhookPayload {
+    Transaction { effects: SuiTransactionBlockEffects },
+    Event { event: SuiEvent },
+}
+
+struct Endpoint {
+    config: WebhookConfig,
+    client: reqwest::Client,
+    key: HmacKey,
+    metrics: Arc<WebhookMetrics>,
+}
+
+/// Subscribe every webhook in `configs` to `subscription_handler`, and spawn the tasks that
+/// deliver their payloads. Fails if any webhook is misconfigured, before any task is spawned.
+pub fn spawn_webhooks(
+    subscription_handler: &SubscriptionHandler,
+    configs: &[WebhookConfig],
+    registry: &Registry,
+) -> anyhow::Result<()> {
+    let metrics = Arc::new(WebhookMetrics::new(registry));
+
+    let mut webhooks = vec![];
+    for config in configs {
+        let endpoint = Endpoint::new(config.clone(), metrics.clone())?;
+        let payloads = payloads(subscription_handler, config)?;
+        webhooks.push((endpoint, payloads));
+    }
+
+    for (endpoint, payloads) in webhooks {
+        info!(url = endpoint.config.url, "Starting webhook");
+        let (tx, rx) = mpsc::channel(endpoint.config.queue_capacity());
+        spawn_monitored_task!(enqueue(
+            payloads,
+            tx,
+            endpoint.config.url.clone(),
+            endpoint.metrics.clone(),
+        ));
+        spawn_monitored_task!(endpoint.deliver(rx));
+    }
+
+    Ok(())
+}
+
+/// The stream of payloads matching any of `config`'s filters.
+fn payloads(
+    subscription_handler: &SubscriptionHandler,
+    config: &WebhookConfig,
+) -> anyhow::Result<BoxStream<'static, WebhookPayload>> {
+    let mut streams = vec![];
+
+    let transaction_filters = config
+        .addresses
+        .iter()
+        .flat_map(|a| {
+            [
+                TransactionFilter::FromAddress(*a),
+                TransactionFilter::ToAddress(*a),
+            ]
+        })
+        .chain(
+            config
+                .packages
+                .iter()
+                .map(|p| TransactionFilter::MoveFunction {
+                    package: *p,
+                    module: None,
+                    function: None,
+                }),
+        );
+
+    for filter in transaction_filters {
+        streams.push(
+            subscription_handler
+                .subscribe_transactions(filter)
+                .map(|effects| WebhookPayload::Transaction { effects })
+                .boxed(),
+        );
+    }
+
+    if !config.event_types.is_empty() {
+        let filters = config
+            .event_types
+            .iter()
+            .map(|t| {
+                let tag = parse_sui_struct_tag(t).with_context(|| {
+                    format!("Invalid event type {t} for webhook {}", config.url)
+                })?;
+                Ok(EventFilter::MoveEventType(tag))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        streams.push(
+            subscription_handler
+                .subscribe_events(EventFilter::Any(filters))
+                .map(|event| WebhookPayload::Event { event })
+                .boxed(),
+        );
+    }
+
+    ensure!(
+        !streams.is_empty(),
+        "Webhook {} has no addresses, packages or event types to filter on",
+        config.url
+    );
+
+    Ok(stream::select_all(streams).boxed())
+}
+
+/// Serialize payloads from `payloads` onto the endpoint's queue, skipping transactions that were
+/// queued recently, and dropping payloads while the queue is full.
+async fn enqueue(
+    mut payloads: BoxStream<'static, WebhookPayload>,
+    tx: mpsc::Sender<Vec<u8>>,
+    url: String,
+    metrics: Arc<WebhookMetrics>,
+) {
+    let mut recent: HashSet<TransactionDigest> = HashSet::new();
+    let mut recent_order = VecDeque::new();
+
+    while let Some(payload) = payloads.next().await {
+        if let WebhookPayload::Transaction { effects } = &payload {
+            let digest = *effects.transaction_digest();
+            if !recent.insert(digest) {
+                continue;
+            }
+            recent_order.push_back(digest);
+            if recent_order.len() > RECENT_DIGESTS
+                && let Some(oldest) = recent_order.pop_front()
+            {
+                recent.remove(&oldest);
+            }
+        }
+
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(url, "Failed to serialize webhook payload: {e}");
+                continue;
+            }
+        };
+
+        match tx.try_send(body) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                metrics
+                    .dropped
+                    .with_label_values(&[&url, "queue_full"])
+                    .inc();
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => return,
+        }
+    }
+}
+
+impl Endpoint {
+    fn new(config: WebhookConfig, metrics: Arc<WebhookMetrics>) -> anyhow::Result<Self> {
+        reqwest::Url::parse(&config.url)
+            .with_context(|| format!("Invalid webhook URL {}", config.url))?;
+
+        let key = HmacKey::from_bytes(config.secret.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Invalid secret for webhook {}: {e}", config.url))?;
+
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()
+            .context("Failed to build webhook HTTP client")?;
+
+        Ok(Self {
+            config,
+            client,
+            key,
+            metrics,
+        })
+    }
+
+    /// Deliver queued payloads one at a time, in the order they were queued.
+    async fn deliver(self, mut rx: mpsc::Receiver<Vec<u8>>) {
+        let url = self.config.url.as_str();
+        let max_attempts = self.config.max_attempts();
+
+        while let Some(body) = rx.recv().await {
+            let mut backoff = self.config.initial_backoff();
+            for attempt in 1..=max_attempts {
+                let Err(e) = self.post(&body).await else {
+                    self.metrics.delivered.with_label_values(&[url]).inc();
+                    break;
+                };
+
+                self.metrics.failed_attempts.with_label_values(&[url]).inc();
+                if attempt == max_attempts {
+                    warn!(
+                        url,
+                        "Dropping webhook payload after {attempt} attempts: {e:#}"
+                    );
+                    self.metrics
+                        .dropped
+                        .with_label_values(&[url, "attempts_exhausted"])
+                        .inc();
+                    break;
+                }
+
+                debug!(
+                    url,
+                    attempt,
+                    ?backoff,
+                    "Failed to deliver webhook payload: {e:#}"
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(self.config.max_backoff());
+            }
+        }
+    }
+
+    async fn post(&self, body: &[u8]) -> anyhow::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_millis()
+            .to_string();
+
+        self.client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, &timestamp)
+            .header(SIGNATURE_HEADER, sign(&self.key, &timestamp, body))
+            .body(body.to_vec())
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+/// The signature of a request with `body`, sent at `timestamp`.
+fn sign(key: &HmacKey, timestamp: &str, body: &[u8]) -> String {
+    let message = [timestamp.as_bytes(), b".", body].concat();
+    Hex::encode(hmac_sha3_256(key, &message).to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_covers_timestamp_and_body() {
+        let key = HmacKey::from_bytes(b"secret").unwrap();
+        let signature = sign(&key, "1000", b"{}");
+
+        assert_eq!(signature, sign(&key, "1000", b"{}"));
+        assert_ne!(signature, sign(&key, "1001", b"{}"));
+        assert_ne!(signature, sign(&key, "1000", b"[]"));
+        assert_eq!(
+            signature,
+            Hex::encode(hmac_sha3_256(&key, b"1000.{}").to_vec())
+        );
+    }
+}
@@ -122,6 +122,37 @@ async fn test_full_node_shared_objects() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_full_node_removal_fails_over() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let (_, _, _, digest, _) = transfer_coin(&test_cluster.wallet).await?;
+
+    // Joins after genesis, and can only learn about the network from the existing fullnode.
+    let late_fullnode = test_cluster.spawn_new_fullnode_from_fullnodes().await;
+    late_fullnode
+        .sui_node
+        .state()
+        .get_transaction_cache_reader()
+        .notify_read_executed_effects("", &[digest])
+        .await;
+
+    let primary = test_cluster.fullnode_handle.name();
+    test_cluster.remove_fullnode(&primary).await;
+    assert_eq!(test_cluster.fullnode_handle.name(), late_fullnode.name());
+    assert_eq!(test_cluster.rpc_url(), late_fullnode.rpc_url);
+
+    // The wallet now goes through the remaining fullnode.
+    let (_, _, _, digest, _) = transfer_coin(&test_cluster.wallet).await?;
+    late_fullnode
+        .sui_node
+        .state()
+        .get_transaction_cache_reader()
+        .notify_read_executed_effects("", &[digest])
+        .await;
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_sponsored_transaction() -> Result<(), anyhow::Error> {
     telemetry_subscribers::init_for_testing();
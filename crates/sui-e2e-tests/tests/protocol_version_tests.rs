@@ -154,6 +154,30 @@ mod sim_only_tests {
         expect_upgrade_succeeded(&test_cluster).await;
     }
 
+    #[sim_test]
+    async fn test_protocol_upgrade_rehearsal() {
+        ProtocolConfig::poison_get_for_min_version();
+
+        let test_cluster = TestClusterBuilder::new()
+            .with_protocol_upgrade_rehearsal(ProtocolVersion::new(START), 2)
+            .build()
+            .await;
+
+        let system_state = test_cluster
+            .rehearse_protocol_upgrade(
+                async |cluster| {
+                    assert_eq!(cluster.get_sui_system_state().protocol_version(), START);
+                    assert_eq!(cluster.get_sui_system_state().epoch(), 0);
+                },
+                async |cluster| {
+                    assert_eq!(cluster.highest_protocol_version().as_u64(), FINISH);
+                },
+            )
+            .await;
+        assert_eq!(system_state.epoch(), 2);
+        assert_eq!(system_state.protocol_version(), FINISH);
+    }
+
     #[sim_test]
     async fn test_protocol_version_upgrade_with_shutdown_validator() {
         let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
@@ -7,6 +7,7 @@ use sui_rpc_api::Client;
 use sui_sdk_types::Address;
 use sui_sdk_types::BalanceChange;
 use sui_test_transaction_builder::make_transfer_sui_transaction;
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
 use sui_types::base_types::SuiAddress;
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::messages_checkpoint::CheckpointArtifacts;
@@ -127,3 +128,86 @@ async fn get_checkpoint_artifacts() {
         assert_eq!(artifacts_digest.unwrap(), &expected_digest.unwrap());
     }
 }
+
+#[sim_test]
+async fn metadata_cache() {
+    let test_cluster = TestClusterBuilder::new()
+        .with_num_validators(1)
+        .build()
+        .await;
+
+    let cached = Client::new(test_cluster.rpc_url())
+        .unwrap()
+        .with_metadata_cache();
+    let uncached = Client::new(test_cluster.rpc_url()).unwrap();
+
+    let chain_id = cached.get_chain_identifier().await.unwrap();
+    let reference_gas_price = cached.get_reference_gas_price().await.unwrap();
+    let protocol_config = cached.get_protocol_config(None).await.unwrap();
+    assert_eq!(chain_id, uncached.get_chain_identifier().await.unwrap());
+    assert_eq!(
+        reference_gas_price,
+        uncached.get_reference_gas_price().await.unwrap()
+    );
+    assert_eq!(
+        protocol_config,
+        uncached.get_protocol_config(None).await.unwrap()
+    );
+
+    // Once the fullnode is gone, only answers from the cache are available, and they are shared
+    // with clones of the client.
+    for node in test_cluster.swarm.fullnodes() {
+        node.stop();
+    }
+    let clone = cached.clone();
+    assert_eq!(clone.get_chain_identifier().await.unwrap(), chain_id);
+    assert_eq!(
+        clone.get_reference_gas_price().await.unwrap(),
+        reference_gas_price
+    );
+    assert_eq!(
+        clone.get_protocol_config(None).await.unwrap(),
+        protocol_config
+    );
+
+    // The protocol configs of given epochs are not cached.
+    assert!(clone.get_protocol_config(Some(0)).await.is_err());
+    assert!(uncached.get_reference_gas_price().await.is_err());
+}
+
+#[sim_test]
+async fn batch_get_objects_over_the_server_limit() {
+    let test_cluster = TestClusterBuilder::new()
+        .with_num_validators(1)
+        .build()
+        .await;
+
+    let client = Client::new(test_cluster.rpc_url()).unwrap();
+
+    // More than the 1000 objects the server accepts in one request.
+    let ids = vec![SUI_SYSTEM_STATE_OBJECT_ID; 1500];
+    let objects = client.batch_get_objects(&ids).await.unwrap();
+    assert_eq!(objects.len(), ids.len());
+    assert!(objects.iter().all(|o| o.id() == SUI_SYSTEM_STATE_OBJECT_ID));
+}
+
+#[sim_test]
+async fn wallet_get_object_refs() {
+    let test_cluster = TestClusterBuilder::new()
+        .with_num_validators(1)
+        .build()
+        .await;
+
+    let wallet = &test_cluster.wallet;
+    let (_, gas) = wallet.get_one_account().await.unwrap();
+    assert!(gas.len() > 1);
+
+    // References come back in the order they were asked for.
+    let mut ids: Vec<_> = gas.iter().map(|(id, _, _)| *id).collect();
+    ids.reverse();
+    let refs = wallet.get_object_refs(&ids).await.unwrap();
+
+    let mut expected = gas.clone();
+    expected.reverse();
+    assert_eq!(refs, expected);
+}
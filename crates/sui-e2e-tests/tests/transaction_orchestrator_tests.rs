@@ -4,6 +4,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::object_reservations::reservation_message;
 use sui_core::test_utils::wait_for_tx;
 use sui_core::transaction_driver::SubmitTransactionOptions;
 use sui_core::transaction_orchestrator::TransactionOrchestrator;
@@ -13,8 +14,10 @@ use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
 use sui_test_transaction_builder::{
     batch_make_transfer_transactions, make_staking_transaction, make_transfer_sui_transaction,
 };
+use sui_types::crypto::Signature;
+use sui_types::digests::TransactionDigest;
 use sui_types::effects::TransactionEffectsAPI;
-use sui_types::error::ErrorCategory;
+use sui_types::error::{ErrorCategory, SuiErrorKind, UserInputError};
 use sui_types::messages_grpc::SubmitTxRequest;
 use sui_types::object::PastObjectRead;
 use sui_types::transaction::Transaction;
@@ -320,6 +323,123 @@ async fn execute_with_orchestrator(
         .await
 }
 
+#[sim_test]
+async fn test_soft_bundle_limits_checked_before_signatures() {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let context = &mut test_cluster.wallet;
+    let handle = &test_cluster.fullnode_handle.sui_node;
+    let orchestrator = handle.with(|n| n.transaction_orchestrator().as_ref().unwrap().clone());
+    let max_soft_bundle_size = handle.with(|n| {
+        n.state()
+            .load_epoch_store_one_call_per_task()
+            .protocol_config()
+            .max_soft_bundle_size()
+    });
+
+    // A transaction without signatures, which fails verification.
+    let txn = batch_make_transfer_transactions(context, 1)
+        .await
+        .swap_remove(0);
+    let unsigned = Transaction::from_generic_sig_data(txn.transaction_data().clone(), vec![]);
+    let bundle = |len| {
+        (0..len)
+            .map(|_| ExecuteTransactionRequestV3::new_v2(unsigned.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // Oversized bundles are rejected for their size, without verifying any signature.
+    let error = orchestrator
+        .execute_soft_bundle(
+            bundle(max_soft_bundle_size + 1),
+            ExecuteTransactionRequestType::WaitForEffectsCert,
+            None,
+        )
+        .await
+        .unwrap_err();
+    let TransactionSubmissionError::TransactionFailed { category, details } = &error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert_eq!(*category, ErrorCategory::InvalidTransaction);
+    assert!(details.contains("Too many transactions"), "{details}");
+
+    let error = orchestrator
+        .execute_soft_bundle(
+            bundle(0),
+            ExecuteTransactionRequestType::WaitForEffectsCert,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, TransactionSubmissionError::TransactionFailed { .. }),
+        "{error:?}"
+    );
+
+    // Bundles within the limits go on to signature verification.
+    let error = orchestrator
+        .execute_soft_bundle(
+            bundle(1),
+            ExecuteTransactionRequestType::WaitForEffectsCert,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, TransactionSubmissionError::InvalidUserSignature(_)),
+        "{error:?}"
+    );
+}
+
+#[sim_test]
+async fn test_reserve_objects_requires_owner_signature() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let handle = &test_cluster.fullnode_handle.sui_node;
+    let orchestrator = handle.with(|n| n.transaction_orchestrator().as_ref().unwrap().clone());
+    let addresses = test_cluster.wallet.get_addresses();
+    let (owner, other) = (addresses[0], addresses[1]);
+    let gas = test_cluster
+        .wallet
+        .get_one_gas_object_owned_by_address(owner)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let object_ids = [gas.0];
+    let tx_digest = TransactionDigest::random();
+    let sign = |signer, tx_digest| {
+        Signature::new_secure(
+            &reservation_message(&object_ids, tx_digest),
+            test_cluster.wallet.config.keystore.export(&signer).unwrap(),
+        )
+    };
+    let ttl = Duration::from_secs(5);
+
+    // Neither another address nor a signature over a different reservation can reserve the
+    // owner's objects.
+    for signature in [
+        sign(other, tx_digest),
+        sign(owner, TransactionDigest::random()),
+    ] {
+        let error = orchestrator
+            .reserve_objects(&object_ids, tx_digest, &signature, ttl)
+            .unwrap_err();
+        assert!(
+            matches!(
+                error.as_inner(),
+                SuiErrorKind::UserInputError {
+                    error: UserInputError::IncorrectUserSignature { .. }
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    let objects = orchestrator
+        .reserve_objects(&object_ids, tx_digest, &sign(owner, tx_digest), ttl)
+        .unwrap();
+    assert_eq!(objects, vec![gas]);
+}
+
 #[sim_test]
 async fn execute_transaction_v3() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -3,15 +3,25 @@
 
 use crate::FaucetConfig;
 use crate::LocalFaucet;
+use crate::QuotaStore;
 use std::sync::Arc;
 
 pub struct AppState<F = Arc<LocalFaucet>> {
     pub faucet: F,
     pub config: FaucetConfig,
+    pub quota: Option<Arc<QuotaStore>>,
 }
 
 impl<F> AppState<F> {
     pub fn new(faucet: F, config: FaucetConfig) -> Self {
-        Self { faucet, config }
+        let quota = config
+            .quota_db_path
+            .as_ref()
+            .map(|path| Arc::new(QuotaStore::open(path, config.quota_limits())));
+        Self {
+            faucet,
+            config,
+            quota,
+        }
     }
 }
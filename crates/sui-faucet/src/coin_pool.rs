@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pool of pre-funded coins for the faucet's batch funding mode.
+//!
+//! Each pool entry is a single coin worth one full request plus its gas budget. A request pays
+//! gas with its entry and splits the recipient's coins off it, so concurrent requests touch
+//! disjoint objects instead of all queueing on the faucet's main coin. What is left of an entry
+//! after the request (unused gas) is merged back into the main coin by the next refill, which
+//! also tops the pool up again in a single transaction.
+
+use prometheus::{
+    IntCounter, IntGauge, Registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry,
+};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use sui_sdk::types::base_types::ObjectRef;
+
+pub(crate) struct CoinPool {
+    target_size: usize,
+    entry_value: u64,
+    available: Mutex<VecDeque<ObjectRef>>,
+    spent: Mutex<Vec<ObjectRef>>,
+    pub(crate) metrics: CoinPoolMetrics,
+}
+
+impl CoinPool {
+    pub(crate) fn new(target_size: usize, entry_value: u64, registry: &Registry) -> Self {
+        let metrics = CoinPoolMetrics::new(registry);
+        metrics.target_size.set(target_size as i64);
+        Self {
+            target_size,
+            entry_value,
+            available: Mutex::new(VecDeque::new()),
+            spent: Mutex::new(vec![]),
+            metrics,
+        }
+    }
+
+    /// Value of each pool entry.
+    pub(crate) fn entry_value(&self) -> u64 {
+        self.entry_value
+    }
+
+    /// Takes an entry out of the pool, or returns `None` if the pool is empty.
+    pub(crate) fn take(&self) -> Option<ObjectRef> {
+        let mut available = self.available.lock().unwrap();
+        let entry = available.pop_front();
+        self.metrics.depth.set(available.len() as i64);
+        if entry.is_none() {
+            self.metrics.misses.inc();
+        }
+        entry
+    }
+
+    /// Records what is left of an entry after use, to be merged back by the next refill.
+    pub(crate) fn return_spent(&self, coin: ObjectRef) {
+        self.spent.lock().unwrap().push(coin);
+    }
+
+    /// Takes up to `max` spent coins to merge into the main coin.
+    pub(crate) fn take_spent(&self, max: usize) -> Vec<ObjectRef> {
+        let mut spent = self.spent.lock().unwrap();
+        let split_at = spent.len().saturating_sub(max);
+        spent.split_off(split_at)
+    }
+
+    /// Number of entries needed to bring the pool back to its target size.
+    pub(crate) fn missing(&self) -> usize {
+        self.target_size
+            .saturating_sub(self.available.lock().unwrap().len())
+    }
+
+    pub(crate) fn add(&self, entries: impl IntoIterator<Item = ObjectRef>) {
+        let mut available = self.available.lock().unwrap();
+        available.extend(entries);
+        self.metrics.depth.set(available.len() as i64);
+    }
+}
+
+pub(crate) struct CoinPoolMetrics {
+    depth: IntGauge,
+    target_size: IntGauge,
+    misses: IntCounter,
+    pub(crate) refills: IntCounter,
+    pub(crate) refill_failures: IntCounter,
+}
+
+impl CoinPoolMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            depth: register_int_gauge_with_registry!(
+                "faucet_coin_pool_depth",
+                "Number of pre-funded coins available in the faucet coin pool",
+                registry,
+            )
+            .unwrap(),
+            target_size: register_int_gauge_with_registry!(
+                "faucet_coin_pool_target_size",
+                "Number of pre-funded coins the faucet keeps in its coin pool",
+                registry,
+            )
+            .unwrap(),
+            misses: register_int_counter_with_registry!(
+                "faucet_coin_pool_misses",
+                "Number of requests served from the main coin because the coin pool was empty",
+                registry,
+            )
+            .unwrap(),
+            refills: register_int_counter_with_registry!(
+                "faucet_coin_pool_refills",
+                "Number of successful coin pool refill transactions",
+                registry,
+            )
+            .unwrap(),
+            refill_failures: register_int_counter_with_registry!(
+                "faucet_coin_pool_refill_failures",
+                "Number of failed coin pool refill transactions",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::random_object_ref;
+
+    #[test]
+    fn test_coin_pool_bookkeeping() {
+        let pool = CoinPool::new(3, 100, &Registry::new());
+        assert_eq!(pool.missing(), 3);
+        assert!(pool.take().is_none());
+        assert_eq!(pool.metrics.misses.get(), 1);
+
+        let entries = [random_object_ref(), random_object_ref()];
+        pool.add(entries);
+        assert_eq!(pool.missing(), 1);
+        assert_eq!(pool.metrics.depth.get(), 2);
+
+        assert_eq!(pool.take(), Some(entries[0]));
+        assert_eq!(pool.metrics.depth.get(), 1);
+        assert_eq!(pool.missing(), 2);
+
+        let spent = [
+            random_object_ref(),
+            random_object_ref(),
+            random_object_ref(),
+        ];
+        for coin in spent {
+            pool.return_spent(coin);
+        }
+        assert_eq!(pool.take_spent(2).len(), 2);
+        assert_eq!(pool.take_spent(2), vec![spent[0]]);
+        assert!(pool.take_spent(2).is_empty());
+    }
+}
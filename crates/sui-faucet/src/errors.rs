@@ -14,6 +14,12 @@ pub enum FaucetError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
+    #[error("Request denied: {0}")]
+    Denied(String),
 }
 
 impl FaucetError {
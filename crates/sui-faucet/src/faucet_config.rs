@@ -1,8 +1,10 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::quota::QuotaLimits;
 use clap::Parser;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
 
 pub const DEFAULT_AMOUNT: u64 = 200_000_000_000;
 pub const DEFAULT_NUM_COINS: usize = 5;
@@ -29,6 +31,52 @@ pub struct FaucetConfig {
 
     #[clap(long, default_value_t = 60)]
     pub wallet_client_timeout_secs: u64,
+
+    /// Serve requests from a pool of this many pre-funded coins, refilled in batches, instead of
+    /// one transaction per request against a single gas coin.
+    #[clap(long)]
+    pub coin_pool_size: Option<usize>,
+
+    /// How often the coin pool is refilled, in milliseconds.
+    #[clap(long, default_value_t = 1000)]
+    pub coin_pool_refill_interval_ms: u64,
+
+    /// Directory of the persistent request quota database. Quotas and the denylist are only
+    /// enforced when this is set.
+    #[clap(long)]
+    pub quota_db_path: Option<PathBuf>,
+
+    /// Maximum requests per recipient address per UTC day.
+    #[clap(long)]
+    pub max_requests_per_address: Option<u64>,
+
+    /// Maximum requests per client IP per UTC day.
+    #[clap(long)]
+    pub max_requests_per_ip: Option<u64>,
+
+    /// Maximum requests per Discord user ID (sent in the `X-Discord-Id` header) per UTC day.
+    #[clap(long)]
+    pub max_requests_per_discord_id: Option<u64>,
+
+    /// Addresses of the Discord bot or proxies trusted to identify the Discord user a request is
+    /// made for. The `X-Discord-Id` header is ignored on requests from any other address.
+    #[clap(long, num_args(1..))]
+    pub trusted_discord_proxies: Vec<IpAddr>,
+
+    /// Port of the admin API for quota resets and the denylist. It only listens on localhost
+    /// and requires `--quota-db-path`.
+    #[clap(long)]
+    pub admin_port: Option<u16>,
+}
+
+impl FaucetConfig {
+    pub fn quota_limits(&self) -> QuotaLimits {
+        QuotaLimits {
+            per_address: self.max_requests_per_address,
+            per_ip: self.max_requests_per_ip,
+            per_discord_id: self.max_requests_per_discord_id,
+        }
+    }
 }
 
 impl Default for FaucetConfig {
@@ -39,6 +87,14 @@ impl Default for FaucetConfig {
             amount: DEFAULT_AMOUNT,
             num_coins: DEFAULT_NUM_COINS,
             wallet_client_timeout_secs: 60,
+            coin_pool_size: None,
+            coin_pool_refill_interval_ms: 1000,
+            quota_db_path: None,
+            max_requests_per_address: None,
+            max_requests_per_ip: None,
+            max_requests_per_discord_id: None,
+            trusted_discord_proxies: vec![],
+            admin_port: None,
         }
     }
 }
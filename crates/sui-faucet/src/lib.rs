@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod app_state;
+mod coin_pool;
 mod errors;
 mod faucet_config;
 mod local_faucet;
+mod quota;
 mod server;
 mod types;
 
@@ -12,5 +14,6 @@ pub use app_state::AppState;
 pub use errors::FaucetError;
 pub use faucet_config::FaucetConfig;
 pub use local_faucet::LocalFaucet;
+pub use quota::{DenylistEntry, QuotaKey, QuotaLimits, QuotaStore};
 pub use server::{create_wallet_context, start_faucet};
 pub use types::{CoinInfo, FaucetRequest, FaucetResponse, FixedAmountRequest, RequestStatus};
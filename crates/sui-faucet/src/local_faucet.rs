@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
 use anyhow::bail;
 use backoff::ExponentialBackoff;
@@ -10,25 +10,34 @@ use sui_rpc_api::client::ExecutedTransaction;
 use sui_sdk::types::effects::TransactionEffectsAPI;
 use tokio::sync::Mutex;
 use tokio::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::FaucetConfig;
 use crate::FaucetError;
+use crate::coin_pool::CoinPool;
 
 use crate::CoinInfo;
+use prometheus::Registry;
 use shared_crypto::intent::Intent;
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_sdk::types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, ObjectRef, SuiAddress},
     gas_coin::GasCoin,
-    transaction::{Transaction, TransactionData},
+    transaction::{
+        Argument, Command, ObjectArg, ProgrammableTransaction, Transaction, TransactionData,
+    },
 };
 use sui_sdk::wallet_context::WalletContext;
 
 const GAS_BUDGET: u64 = 10_000_000;
 const NUM_RETRIES: u8 = 2;
 
+/// Limits on the work done by a single coin pool refill transaction.
+const MAX_POOL_ENTRIES_PER_REFILL: usize = 500;
+const MAX_SPENT_COINS_PER_REFILL: usize = 500;
+const REFILL_GAS_BUDGET: u64 = 5_000_000_000;
+
 /// On a freshly created `--force-regenesis` network the genesis coin objects may not yet be
 /// readable the instant the cluster reports started — especially on slow or contended storage —
 /// so the gas-coin scan is retried with backoff for up to this long before failing.
@@ -41,6 +50,8 @@ pub struct LocalFaucet {
     coin_id: Arc<Mutex<ObjectID>>,
     coin_amount: u64,
     num_coins: usize,
+    coin_pool: Option<CoinPool>,
+    metrics_registry: Registry,
 }
 
 /// We do not just derive(Debug) because WalletContext and the WriteAheadLog do not implement Debug / are also hard
@@ -62,13 +73,35 @@ impl LocalFaucet {
         let (coins, active_address) = find_gas_coins_and_address(&mut wallet, &config).await?;
         info!("Starting faucet with address: {:?}", active_address);
 
-        Ok(Arc::new(LocalFaucet {
+        let metrics_registry = Registry::new();
+        let coin_pool = config.coin_pool_size.map(|size| {
+            let entry_value = config.amount * config.num_coins as u64 + GAS_BUDGET;
+            CoinPool::new(size, entry_value, &metrics_registry)
+        });
+
+        let faucet = Arc::new(LocalFaucet {
             wallet,
             active_address,
             coin_id: Arc::new(Mutex::new(*coins[0].id())),
             coin_amount: config.amount,
             num_coins: config.num_coins,
-        }))
+            coin_pool,
+            metrics_registry,
+        });
+
+        if faucet.coin_pool.is_some() {
+            tokio::spawn(run_coin_pool_refill(
+                Arc::downgrade(&faucet),
+                Duration::from_millis(config.coin_pool_refill_interval_ms),
+            ));
+        }
+
+        Ok(faucet)
+    }
+
+    /// Registry holding the faucet's metrics.
+    pub fn metrics_registry(&self) -> &Registry {
+        &self.metrics_registry
     }
 
     /// Make transaction and execute it.
@@ -90,6 +123,10 @@ impl LocalFaucet {
 
         let ptb = ptb.finish();
 
+        if let Some(entry) = self.coin_pool.as_ref().and_then(|pool| pool.take()) {
+            return self.execute_pool_request(entry, ptb, gas_price).await;
+        }
+
         let coin_id = self.coin_id.lock().await;
         let coin_id_ref = self
             .wallet
@@ -109,8 +146,128 @@ impl LocalFaucet {
             .await
             .map_err(FaucetError::internal)?;
 
-        let coins: Vec<CoinInfo> = tx
-            .effects
+        Ok(self.coins_sent(&tx))
+    }
+
+    /// Executes a request paying gas with, and splitting the recipient's coins off, a coin pool
+    /// entry.
+    async fn execute_pool_request(
+        &self,
+        entry: ObjectRef,
+        ptb: ProgrammableTransaction,
+        gas_price: u64,
+    ) -> Result<Vec<CoinInfo>, FaucetError> {
+        // Unwrap safe: entries are only handed out when the pool is enabled.
+        let pool = self.coin_pool.as_ref().unwrap();
+        let tx_data = TransactionData::new_programmable(
+            self.active_address,
+            vec![entry],
+            ptb,
+            GAS_BUDGET,
+            gas_price,
+        );
+
+        match self
+            .execute_txn_with_retries(tx_data, entry.0, NUM_RETRIES)
+            .await
+        {
+            Ok(tx) => {
+                if let Some((gas_object, _)) = tx.effects.gas_object() {
+                    pool.return_spent(gas_object);
+                }
+                Ok(self.coins_sent(&tx))
+            }
+            Err(e) => {
+                // The entry's version changes if the transaction was executed, so hand its
+                // latest reference to the next refill to merge back.
+                match self.wallet.get_object_ref(entry.0).await {
+                    Ok(latest) => pool.return_spent(latest),
+                    Err(err) => warn!("Dropping coin pool entry {}: {err}", entry.0),
+                }
+                Err(FaucetError::internal(e))
+            }
+        }
+    }
+
+    /// Tops the coin pool up to its target size and merges spent entries back into the main
+    /// coin, in a single transaction.
+    async fn refill_coin_pool(&self, pool: &CoinPool) -> Result<(), FaucetError> {
+        let missing = pool.missing().min(MAX_POOL_ENTRIES_PER_REFILL);
+        let spent = pool.take_spent(MAX_SPENT_COINS_PER_REFILL);
+        if missing == 0 && spent.is_empty() {
+            return Ok(());
+        }
+
+        let result = self.execute_refill(pool, missing, &spent).await;
+        if result.is_err() {
+            pool.metrics.refill_failures.inc();
+            for coin in spent {
+                if let Ok(latest) = self.wallet.get_object_ref(coin.0).await {
+                    pool.return_spent(latest);
+                }
+            }
+        }
+        result
+    }
+
+    async fn execute_refill(
+        &self,
+        pool: &CoinPool,
+        missing: usize,
+        spent: &[ObjectRef],
+    ) -> Result<(), FaucetError> {
+        let gas_price = self
+            .wallet
+            .get_reference_gas_price()
+            .await
+            .map_err(|e| FaucetError::internal(format!("Failed to get gas price: {}", e)))?;
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        if !spent.is_empty() {
+            let coins = spent
+                .iter()
+                .map(|coin| ptb.obj(ObjectArg::ImmOrOwnedObject(*coin)))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(FaucetError::internal)?;
+            ptb.command(Command::MergeCoins(Argument::GasCoin, coins));
+        }
+        if missing > 0 {
+            ptb.pay_sui(
+                vec![self.active_address; missing],
+                vec![pool.entry_value(); missing],
+            )
+            .map_err(FaucetError::internal)?;
+        }
+
+        let coin_id = self.coin_id.lock().await;
+        let coin_id_ref = self
+            .wallet
+            .get_object_ref(*coin_id)
+            .await
+            .map_err(|e| FaucetError::internal(format!("Failed to get object ref: {}", e)))?;
+        let tx_data = TransactionData::new_programmable(
+            self.active_address,
+            vec![coin_id_ref],
+            ptb.finish(),
+            REFILL_GAS_BUDGET,
+            gas_price,
+        );
+        let tx = self
+            .execute_txn_with_retries(tx_data, *coin_id, NUM_RETRIES)
+            .await
+            .map_err(FaucetError::internal)?;
+
+        pool.add(tx.effects.created().into_iter().map(|(coin, _)| coin));
+        pool.metrics.refills.inc();
+        info!(
+            "Refilled faucet coin pool with {missing} coins, merged {} spent coins",
+            spent.len()
+        );
+        Ok(())
+    }
+
+    fn coins_sent(&self, tx: &ExecutedTransaction) -> Vec<CoinInfo> {
+        tx.effects
             .created()
             .into_iter()
             .map(|o| CoinInfo {
@@ -118,9 +275,7 @@ impl LocalFaucet {
                 id: o.0.0,
                 transfer_tx_digest: *tx.effects.transaction_digest(),
             })
-            .collect();
-
-        Ok(coins)
+            .collect()
     }
 
     async fn execute_txn(
@@ -180,6 +335,23 @@ impl LocalFaucet {
     }
 }
 
+async fn run_coin_pool_refill(faucet: Weak<LocalFaucet>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        // Stop once the faucet has been dropped.
+        let Some(faucet) = faucet.upgrade() else {
+            return;
+        };
+        let Some(pool) = &faucet.coin_pool else {
+            return;
+        };
+        if let Err(e) = faucet.refill_coin_pool(pool).await {
+            warn!("Failed to refill faucet coin pool: {e}");
+        }
+    }
+}
+
 /// Finds gas coins with sufficient balance and returns the address to use as the active address
 /// for the faucet. If the initial active address in the wallet does not have enough gas coins,
 /// it will iterate through the addresses to find one with sufficient gas coins.
@@ -285,6 +457,45 @@ mod tests {
         assert_eq!(coins.items.len(), 2 * local_faucet.num_coins);
     }
 
+    #[tokio::test]
+    async fn test_coin_pool_execute_txn() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let client = cluster.grpc_client();
+
+        let config = FaucetConfig {
+            coin_pool_size: Some(4),
+            // Refill explicitly below.
+            coin_pool_refill_interval_ms: 3_600_000,
+            ..Default::default()
+        };
+        let local_faucet = LocalFaucet::new(cluster.wallet, config).await.unwrap();
+        let pool = local_faucet.coin_pool.as_ref().unwrap();
+        // Wait for the refill task's first, immediate run.
+        while pool.missing() > 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let (first, second) = tokio::join!(
+            local_faucet.local_request_execute_tx(recipient),
+            local_faucet.local_request_execute_tx(recipient),
+        );
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(pool.missing(), 2);
+
+        let coins = client
+            .get_owned_objects(recipient, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(coins.items.len(), 2 * local_faucet.num_coins);
+
+        // The next refill replaces the used entries and merges their leftovers.
+        local_faucet.refill_coin_pool(pool).await.unwrap();
+        assert_eq!(pool.missing(), 0);
+        assert!(pool.take_spent(usize::MAX).is_empty());
+    }
+
     #[tokio::test]
     async fn test_find_gas_coins_and_address() {
         let mut cluster = TestClusterBuilder::new().build().await;
@@ -0,0 +1,306 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistent request quotas for the faucet.
+//!
+//! Each request is charged against the recipient address, the client IP and, when a trusted
+//! proxy forwards it, the Discord user ID. Counters are kept per UTC day in a RocksDB database so
+//! restarting the faucet does not reset them. Keys on the denylist are always rejected.
+
+use crate::FaucetError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_sdk::types::base_types::SuiAddress;
+use typed_store::DBMapUtils;
+use typed_store::Map;
+use typed_store::rocks::{DBMap, MetricConf};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// What a request is charged against.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaKey {
+    Address(SuiAddress),
+    Ip(IpAddr),
+    DiscordId(String),
+}
+
+impl fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaKey::Address(address) => write!(f, "address {address}"),
+            QuotaKey::Ip(ip) => write!(f, "IP {ip}"),
+            QuotaKey::DiscordId(id) => write!(f, "Discord ID {id}"),
+        }
+    }
+}
+
+/// Maximum number of requests per UTC day. `None` means unlimited.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuotaLimits {
+    pub per_address: Option<u64>,
+    pub per_ip: Option<u64>,
+    pub per_discord_id: Option<u64>,
+}
+
+impl QuotaLimits {
+    fn limit(&self, key: &QuotaKey) -> Option<u64> {
+        match key {
+            QuotaKey::Address(_) => self.per_address,
+            QuotaKey::Ip(_) => self.per_ip,
+            QuotaKey::DiscordId(_) => self.per_discord_id,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    /// Days since the unix epoch (UTC) the count applies to.
+    pub day: u64,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DenylistEntry {
+    pub reason: String,
+    pub added_at_ms: u64,
+}
+
+#[derive(DBMapUtils)]
+struct QuotaTables {
+    /// Requests made by each key on the day it was last charged.
+    usage: DBMap<QuotaKey, QuotaUsage>,
+    /// Keys whose requests are always rejected.
+    denylist: DBMap<QuotaKey, DenylistEntry>,
+}
+
+pub struct QuotaStore {
+    tables: QuotaTables,
+    limits: QuotaLimits,
+    // Serializes check-then-increment so concurrent requests cannot exceed a limit.
+    lock: Mutex<()>,
+}
+
+impl QuotaStore {
+    pub fn open(path: &Path, limits: QuotaLimits) -> Self {
+        let tables = QuotaTables::open_tables_read_write(
+            path.to_path_buf(),
+            MetricConf::new("faucet_quota"),
+            None,
+            None,
+        );
+        Self {
+            tables,
+            limits,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Charges one request against every key, failing without charging anything if a key is
+    /// denylisted or has used up its daily quota.
+    pub fn reserve(&self, keys: &[QuotaKey]) -> Result<(), FaucetError> {
+        self.reserve_on_day(keys, today())
+    }
+
+    /// Returns a reservation made by `reserve`, e.g. because the transfer failed.
+    pub fn release(&self, keys: &[QuotaKey]) -> Result<(), FaucetError> {
+        let day = today();
+        let _guard = self.lock.lock().unwrap();
+        for key in keys {
+            if let Some(mut usage) = self.tables.usage.get(key).map_err(FaucetError::internal)?
+                && usage.day == day
+                && usage.count > 0
+            {
+                usage.count -= 1;
+                self.tables
+                    .usage
+                    .insert(key, &usage)
+                    .map_err(FaucetError::internal)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of requests charged against `key` today.
+    pub fn usage(&self, key: &QuotaKey) -> Result<u64, FaucetError> {
+        let day = today();
+        Ok(self
+            .tables
+            .usage
+            .get(key)
+            .map_err(FaucetError::internal)?
+            .filter(|usage| usage.day == day)
+            .map_or(0, |usage| usage.count))
+    }
+
+    pub fn reset(&self, key: &QuotaKey) -> Result<(), FaucetError> {
+        let _guard = self.lock.lock().unwrap();
+        self.tables.usage.remove(key).map_err(FaucetError::internal)
+    }
+
+    pub fn deny(&self, key: &QuotaKey, reason: String) -> Result<(), FaucetError> {
+        let added_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        self.tables
+            .denylist
+            .insert(
+                key,
+                &DenylistEntry {
+                    reason,
+                    added_at_ms,
+                },
+            )
+            .map_err(FaucetError::internal)
+    }
+
+    pub fn allow(&self, key: &QuotaKey) -> Result<(), FaucetError> {
+        self.tables
+            .denylist
+            .remove(key)
+            .map_err(FaucetError::internal)
+    }
+
+    pub fn denylist(&self) -> Result<Vec<(QuotaKey, DenylistEntry)>, FaucetError> {
+        self.tables
+            .denylist
+            .safe_iter()
+            .collect::<Result<_, _>>()
+            .map_err(FaucetError::internal)
+    }
+
+    fn reserve_on_day(&self, keys: &[QuotaKey], day: u64) -> Result<(), FaucetError> {
+        let _guard = self.lock.lock().unwrap();
+
+        let mut updated = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some(entry) = self
+                .tables
+                .denylist
+                .get(key)
+                .map_err(FaucetError::internal)?
+            {
+                return Err(FaucetError::Denied(format!(
+                    "{key} is not allowed to request gas: {}",
+                    entry.reason
+                )));
+            }
+
+            let count = self
+                .tables
+                .usage
+                .get(key)
+                .map_err(FaucetError::internal)?
+                .filter(|usage| usage.day == day)
+                .map_or(0, |usage| usage.count);
+            if let Some(limit) = self.limits.limit(key)
+                && count >= limit
+            {
+                return Err(FaucetError::TooManyRequests(format!(
+                    "{key} has reached its limit of {limit} requests per day"
+                )));
+            }
+            updated.push((
+                key,
+                QuotaUsage {
+                    day,
+                    count: count + 1,
+                },
+            ));
+        }
+
+        let mut batch = self.tables.usage.batch();
+        batch
+            .insert_batch(&self.tables.usage, updated)
+            .map_err(FaucetError::internal)?;
+        batch.write().map_err(FaucetError::internal)
+    }
+}
+
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() / SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_limits_and_denylist() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = QuotaStore::open(
+            dir.path(),
+            QuotaLimits {
+                per_address: Some(2),
+                per_ip: Some(3),
+                per_discord_id: None,
+            },
+        );
+
+        let address = QuotaKey::Address(SuiAddress::random_for_testing_only());
+        let other = QuotaKey::Address(SuiAddress::random_for_testing_only());
+        let ip = QuotaKey::Ip("10.0.0.1".parse().unwrap());
+
+        store
+            .reserve_on_day(&[address.clone(), ip.clone()], 1)
+            .unwrap();
+        store
+            .reserve_on_day(&[address.clone(), ip.clone()], 1)
+            .unwrap();
+        assert!(matches!(
+            store.reserve_on_day(&[address.clone(), ip.clone()], 1),
+            Err(FaucetError::TooManyRequests(_))
+        ));
+        // The rejected request must not have been charged against the IP.
+        store
+            .reserve_on_day(&[other.clone(), ip.clone()], 1)
+            .unwrap();
+        assert!(matches!(
+            store.reserve_on_day(&[other.clone(), ip.clone()], 1),
+            Err(FaucetError::TooManyRequests(_))
+        ));
+
+        // Counters start over on the next day.
+        store
+            .reserve_on_day(&[address.clone(), ip.clone()], 2)
+            .unwrap();
+
+        store.reset(&ip).unwrap();
+        store.deny(&other, "abuse".to_string()).unwrap();
+        assert!(matches!(
+            store.reserve_on_day(&[other.clone(), ip.clone()], 2),
+            Err(FaucetError::Denied(_))
+        ));
+        assert_eq!(store.denylist().unwrap().len(), 1);
+
+        store.allow(&other).unwrap();
+        store.reserve_on_day(&[other, ip], 2).unwrap();
+    }
+
+    #[test]
+    fn test_quota_persists_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let limits = QuotaLimits {
+            per_address: Some(1),
+            ..Default::default()
+        };
+        let address = QuotaKey::Address(SuiAddress::random_for_testing_only());
+
+        let store = QuotaStore::open(dir.path(), limits);
+        store.reserve(std::slice::from_ref(&address)).unwrap();
+        assert_eq!(store.usage(&address).unwrap(), 1);
+        drop(store);
+
+        let store = QuotaStore::open(dir.path(), limits);
+        assert!(store.reserve(std::slice::from_ref(&address)).is_err());
+        store.release(std::slice::from_ref(&address)).unwrap();
+        store.reserve(std::slice::from_ref(&address)).unwrap();
+    }
+}
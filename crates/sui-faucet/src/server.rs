@@ -2,49 +2,105 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::types::*;
-use crate::{AppState, FaucetConfig, FaucetError, FaucetRequest};
+use crate::{AppState, FaucetConfig, FaucetError, FaucetRequest, QuotaKey, QuotaStore};
 use anyhow::Context;
 use axum::{
     BoxError, Extension, Json, Router,
     error_handling::HandleErrorLayer,
-    http::StatusCode,
+    extract::ConnectInfo,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     routing::{get, post},
 };
 use http::Method;
+use serde::Deserialize;
 use std::{
     borrow::Cow,
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
     sync::Arc,
     time::Duration,
 };
 use sui_config::SUI_CLIENT_CONFIG;
 use sui_futures::service::Service;
+use sui_sdk::types::base_types::SuiAddress;
 use sui_sdk::wallet_context::WalletContext;
 use tokio::sync::oneshot;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
-use tracing::info;
+use tracing::{info, warn};
 
 /// basic handler that responds with a static string
 async fn health() -> &'static str {
     "OK"
 }
 
+async fn metrics(Extension(state): Extension<Arc<AppState>>) -> impl IntoResponse {
+    let metric_families = state.faucet.metrics_registry().gather();
+    match prometheus::TextEncoder::new().encode_to_string(&metric_families) {
+        Ok(metrics) => (StatusCode::OK, metrics),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Unable to encode metrics: {e}"),
+        ),
+    }
+}
+
+/// Header the Discord bot uses to identify the user a request is made for.
+const DISCORD_ID_HEADER: &str = "x-discord-id";
+
+/// Keys a request for `recipient` from `client` is charged against. The Discord ID header can be
+/// set by anyone, so it only counts on requests from a trusted proxy.
+fn quota_keys(
+    config: &FaucetConfig,
+    recipient: SuiAddress,
+    client: IpAddr,
+    headers: &HeaderMap,
+) -> Vec<QuotaKey> {
+    let mut keys = vec![QuotaKey::Address(recipient), QuotaKey::Ip(client)];
+    if config.trusted_discord_proxies.contains(&client)
+        && let Some(discord_id) = headers
+            .get(DISCORD_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+    {
+        keys.push(QuotaKey::DiscordId(discord_id.to_string()));
+    }
+    keys
+}
+
 async fn request_local_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
     // ) -> &'static str {
 ) -> impl IntoResponse {
     let FaucetRequest::FixedAmountRequest(request) = payload;
     info!("Local request for address: {}", request.recipient);
+
+    let quota_keys = quota_keys(&state.config, request.recipient, client.ip(), &headers);
+    if let Some(quota) = &state.quota
+        && let Err(e) = quota.reserve(&quota_keys)
+    {
+        let status = match &e {
+            FaucetError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            FaucetError::Denied(_) => StatusCode::FORBIDDEN,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        return (status, Json(FaucetResponse::from(e)));
+    }
+
     let request = state
         .faucet
         .local_request_execute_tx(request.recipient)
         .await;
 
     if let Err(e) = request {
+        if let Some(quota) = &state.quota
+            && let Err(release_err) = quota.release(&quota_keys)
+        {
+            warn!("Failed to release faucet quota: {release_err}");
+        }
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(FaucetResponse {
@@ -113,6 +169,7 @@ pub async fn start_faucet(app_state: Arc<AppState>) -> anyhow::Result<Service> {
 
     let app = Router::new()
         .route("/", get(health))
+        .route("/metrics", get(metrics))
         .route("/v2/gas", post(request_local_gas))
         .route("/v1/gas", post(request_local_gas))
         .route("/gas", post(request_local_gas))
@@ -127,9 +184,32 @@ pub async fn start_faucet(app_state: Arc<AppState>) -> anyhow::Result<Service> {
 
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("Starting local faucet service on {addr}");
+    let mut service = serve(app, addr, "faucet").await?;
+
+    if let Some(admin_port) = app_state.config.admin_port {
+        let quota = app_state
+            .quota
+            .clone()
+            .context("The faucet admin API requires --quota-db-path")?;
+        let admin = Router::new()
+            .route("/quota/usage", post(quota_usage))
+            .route("/quota/reset", post(quota_reset))
+            .route("/denylist", get(list_denylist))
+            .route("/denylist/add", post(add_to_denylist))
+            .route("/denylist/remove", post(remove_from_denylist))
+            .layer(Extension(quota));
+        let admin_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), admin_port);
+        info!("Starting faucet admin service on {admin_addr}");
+        service = service.merge(serve(admin, admin_addr, "faucet admin").await?);
+    }
+
+    Ok(service)
+}
+
+async fn serve(app: Router, addr: SocketAddr, name: &'static str) -> anyhow::Result<Service> {
     let listener = tokio::net::TcpListener::bind(addr)
         .await
-        .context("Failed to bind faucet to listen address")?;
+        .with_context(|| format!("Failed to bind {name} to listen address"))?;
 
     let (stx, srx) = oneshot::channel::<()>();
     Ok(Service::new()
@@ -143,21 +223,107 @@ pub async fn start_faucet(app_state: Arc<AppState>) -> anyhow::Result<Service> {
             )
             .with_graceful_shutdown(async move {
                 let _ = srx.await;
-                info!("Shutdown received, shutting down faucet service");
+                info!("Shutdown received, shutting down {name} service");
             })
             .await
-            .context("Failed to start faucet service")
+            .with_context(|| format!("Failed to start {name} service"))
         }))
 }
 
+// Admin API. Keys are given as JSON, e.g. `{"address": "0x..."}`, `{"ip": "1.2.3.4"}` or
+// `{"discord_id": "..."}`.
+//
+// curl -X POST localhost:<admin_port>/quota/reset -H 'Content-Type: application/json' -d '{"ip": "1.2.3.4"}'
+// curl -X POST localhost:<admin_port>/denylist/add -H 'Content-Type: application/json' \
+//     -d '{"key": {"address": "0x..."}, "reason": "abuse"}'
+// curl localhost:<admin_port>/denylist
+
+#[derive(Deserialize)]
+struct DenylistAddRequest {
+    key: QuotaKey,
+    reason: String,
+}
+
+fn admin_error(e: FaucetError) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+async fn quota_usage(
+    Extension(quota): Extension<Arc<QuotaStore>>,
+    Json(key): Json<QuotaKey>,
+) -> Result<Json<u64>, (StatusCode, String)> {
+    quota.usage(&key).map(Json).map_err(admin_error)
+}
+
+async fn quota_reset(
+    Extension(quota): Extension<Arc<QuotaStore>>,
+    Json(key): Json<QuotaKey>,
+) -> Result<String, (StatusCode, String)> {
+    quota.reset(&key).map_err(admin_error)?;
+    info!("Reset faucet quota of {key}");
+    Ok(format!("Reset quota of {key}\n"))
+}
+
+async fn list_denylist(
+    Extension(quota): Extension<Arc<QuotaStore>>,
+) -> Result<Json<Vec<(QuotaKey, crate::DenylistEntry)>>, (StatusCode, String)> {
+    quota.denylist().map(Json).map_err(admin_error)
+}
+
+async fn add_to_denylist(
+    Extension(quota): Extension<Arc<QuotaStore>>,
+    Json(request): Json<DenylistAddRequest>,
+) -> Result<String, (StatusCode, String)> {
+    quota
+        .deny(&request.key, request.reason)
+        .map_err(admin_error)?;
+    info!("Added {} to the faucet denylist", request.key);
+    Ok(format!("Added {} to the denylist\n", request.key))
+}
+
+async fn remove_from_denylist(
+    Extension(quota): Extension<Arc<QuotaStore>>,
+    Json(key): Json<QuotaKey>,
+) -> Result<String, (StatusCode, String)> {
+    quota.allow(&key).map_err(admin_error)?;
+    info!("Removed {key} from the faucet denylist");
+    Ok(format!("Removed {key} from the denylist\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::LocalFaucet;
+    use http::HeaderValue;
     use serde_json::json;
-    use sui_sdk::types::base_types::SuiAddress;
     use test_cluster::TestClusterBuilder;
 
+    #[test]
+    fn test_discord_id_only_trusted_from_proxies() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let config = FaucetConfig {
+            trusted_discord_proxies: vec![proxy],
+            ..Default::default()
+        };
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut headers = HeaderMap::new();
+        headers.insert(DISCORD_ID_HEADER, HeaderValue::from_static("1234"));
+
+        assert_eq!(
+            quota_keys(&config, recipient, proxy, &headers),
+            vec![
+                QuotaKey::Address(recipient),
+                QuotaKey::Ip(proxy),
+                QuotaKey::DiscordId("1234".to_string()),
+            ]
+        );
+        assert_eq!(
+            quota_keys(&config, recipient, other, &headers),
+            vec![QuotaKey::Address(recipient), QuotaKey::Ip(other)]
+        );
+    }
+
     #[tokio::test]
     async fn test_v2_gas_endpoint() {
         // Setup test cluster and faucet
@@ -172,10 +338,7 @@ mod tests {
             .await
             .unwrap();
 
-        let app_state = Arc::new(AppState {
-            faucet: local_faucet,
-            config,
-        });
+        let app_state = Arc::new(AppState::new(local_faucet, config));
 
         // Start the faucet as a background Service
         let service = start_faucet(app_state)
@@ -53,6 +53,7 @@ use sui_types::storage::LedgerBitmapBucketIterator;
 use sui_types::storage::LedgerTxSeqDigest;
 use sui_types::storage::LedgerTxSeqDigestIterator;
 use sui_types::storage::ObjectStore;
+use sui_types::storage::ObjectTypeFilter;
 use sui_types::storage::OwnedObjectInfo;
 use sui_types::storage::PackageObject;
 use sui_types::storage::ParentSync;
@@ -315,8 +316,9 @@ impl DataStore {
 
     /// Get the object at the latest version available on disk. If not found, it will fetch the
     /// object at the forked checkpoint from remote rpc and save it to disk for future use. Returns
-    /// `None` in the latter case.
-    pub(crate) fn get_object(&self, object_id: &ObjectID) -> anyhow::Result<Option<Object>> {
+    /// `None` in the latter case. Unlike the `ObjectStore` implementation, failures to read the
+    /// local store or to reach the remote rpc are returned rather than reported as a missing object.
+    pub fn get_object(&self, object_id: &ObjectID) -> anyhow::Result<Option<Object>> {
         self.get_latest_object(object_id)
     }
 
@@ -742,7 +744,7 @@ impl DataStore {
     fn get_owned_objects(
         &self,
         owner: SuiAddress,
-        object_type: Option<StructTag>,
+        object_type: Option<ObjectTypeFilter>,
         cursor: Option<OwnedObjectInfo>,
     ) -> StorageResult<Vec<OwnedObjectInfo>> {
         self.get_owned_object_infos(owner, object_type, cursor)
@@ -752,7 +754,7 @@ impl DataStore {
     fn get_owned_object_infos(
         &self,
         owner: SuiAddress,
-        object_type: Option<StructTag>,
+        object_type: Option<ObjectTypeFilter>,
         cursor: Option<OwnedObjectInfo>,
     ) -> StorageResult<Vec<OwnedObjectInfo>> {
         self.ensure_owned_object_index_initialized()
@@ -776,7 +778,7 @@ impl DataStore {
             .filter(|entry| {
                 object_type
                     .as_ref()
-                    .is_none_or(|filter| struct_tag_filter_matches(filter, &entry.object_type))
+                    .is_none_or(|filter| filter.matches(&entry.object_type))
             })
             .map(|entry| OwnedObjectInfo {
                 owner: entry.owner,
@@ -789,20 +791,6 @@ impl DataStore {
     }
 }
 
-/// Check if the these two `StructTag`s match for the purposes of owned-object filtering. The filter
-/// may have empty type parameters, in which case they are ignored and only the address, module, and
-/// name are compared.
-///
-/// This allows a wildcard filter like `0x2::coin::Coin` to match all versions of the `Coin` struct,
-/// regardless of the type parameter (e.g., `0x2::coin::Coin<0x1::sui::SUI>`).
-fn struct_tag_filter_matches(filter: &StructTag, candidate: &StructTag) -> bool {
-    filter.address == candidate.address
-        && filter.module.as_ident_str() == candidate.module.as_ident_str()
-        && filter.name.as_ident_str() == candidate.name.as_ident_str()
-        && (filter.type_params.is_empty()
-            || filter.type_params.as_slice() == candidate.type_params.as_slice())
-}
-
 /// Preserve effect removal categories before passing removals through `update_objects`, whose trait
 /// signature does not distinguish deleted, wrapped, or unwrapped-then-deleted objects.
 fn removed_objects_from_effects(effects: &TransactionEffects) -> Vec<RemovedObject> {
@@ -1337,7 +1325,7 @@ impl RpcIndexes for DataStore {
     fn owned_objects_iter(
         &self,
         owner: SuiAddress,
-        object_type: Option<StructTag>,
+        object_type: Option<ObjectTypeFilter>,
         cursor: Option<OwnedObjectInfo>,
     ) -> StorageResult<Box<dyn Iterator<Item = Result<OwnedObjectInfo, TypedStoreError>> + '_>>
     {
@@ -338,10 +338,11 @@ fn test_owned_objects_tracks_consensus_address_owner_writes() {
 
     store.update_objects(BTreeMap::from([(object_id, object)]), vec![]);
 
-    let infos: Vec<_> = RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
-        .expect("owned-object iterator should build")
-        .map(|result| result.expect("owned-object entry should decode"))
-        .collect();
+    let infos: Vec<_> =
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
+            .expect("owned-object iterator should build")
+            .map(|result| result.expect("owned-object entry should decode"))
+            .collect();
     assert_eq!(infos.len(), 1);
     assert_eq!(infos[0].owner, owner);
     assert_eq!(infos[0].object_id, object_id);
@@ -351,7 +352,7 @@ fn test_owned_objects_tracks_consensus_address_owner_writes() {
     let immutable = make_gas_object(object_id, 2, Owner::Immutable);
     store.update_objects(BTreeMap::from([(object_id, immutable)]), vec![]);
     assert_eq!(
-        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
             .expect("owned-object iterator should build")
             .count(),
         0,
@@ -390,10 +391,11 @@ fn test_owned_object_info_uses_index_metadata_until_deleted() {
         }])
         .unwrap();
 
-    let infos: Vec<_> = RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
-        .expect("seeded owned-object iterator should build")
-        .map(|result| result.expect("seeded entry should decode"))
-        .collect();
+    let infos: Vec<_> =
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
+            .expect("seeded owned-object iterator should build")
+            .map(|result| result.expect("seeded entry should decode"))
+            .collect();
     assert_eq!(infos.len(), 1);
     assert_eq!(infos[0].object_id, object_id);
     assert_eq!(infos[0].version, SequenceNumber::from_u64(7));
@@ -410,7 +412,7 @@ fn test_owned_object_info_uses_index_metadata_until_deleted() {
     );
 
     assert_eq!(
-        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
             .expect("owned-object iterator should build")
             .count(),
         0,
@@ -443,10 +445,11 @@ async fn test_owned_object_query_lazily_initializes_complete_index_from_seed_man
 
     assert!(!store.local().owned_object_index_exists());
 
-    let infos: Vec<_> = RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
-        .expect("owned-object iterator should initialize from seed")
-        .map(|result| result.expect("owned-object entry should decode"))
-        .collect();
+    let infos: Vec<_> =
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
+            .expect("owned-object iterator should initialize from seed")
+            .map(|result| result.expect("owned-object entry should decode"))
+            .collect();
 
     assert_eq!(infos.len(), 1);
     assert_eq!(infos[0].object_id, object_id);
@@ -507,7 +510,7 @@ async fn test_local_execution_before_owned_query_preserves_other_seeded_entries(
     store.update_objects(BTreeMap::from([(first_id, transferred)]), vec![]);
 
     let owner_infos: Vec<_> =
-        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
             .expect("owned-object iterator should build")
             .map(|result| result.expect("owned-object entry should decode"))
             .collect();
@@ -516,7 +519,7 @@ async fn test_local_execution_before_owned_query_preserves_other_seeded_entries(
     assert_eq!(owner_infos[0].version, SequenceNumber::from_u64(1));
 
     let recipient_infos: Vec<_> =
-        RpcIndexes::owned_objects_iter(&store, recipient, Some(GasCoin::type_()), None)
+        RpcIndexes::owned_objects_iter(&store, recipient, Some(GasCoin::type_().into()), None)
             .expect("owned-object iterator should build")
             .map(|result| result.expect("owned-object entry should decode"))
             .collect();
@@ -550,10 +553,11 @@ fn test_missing_owned_index_after_local_checkpoint_advancement_fails_closed() {
         .write_checkpoint_summary(&checkpoint)
         .expect("advanced checkpoint should write");
 
-    let err = match RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None) {
-        Ok(_) => panic!("owned-object iterator should fail closed"),
-        Err(err) => err,
-    };
+    let err =
+        match RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None) {
+            Ok(_) => panic!("owned-object iterator should fail closed"),
+            Err(err) => err,
+        };
     assert!(
         err.to_string()
             .contains("owned-object index is missing while local checkpoints have advanced")
@@ -887,10 +891,11 @@ fn test_rpc_owned_objects_iter_filters_and_pages_by_object_id() {
         vec![],
     );
 
-    let infos: Vec<_> = RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_()), None)
-        .expect("owned-object iterator should build")
-        .map(|result| result.expect("owned-object entry should decode"))
-        .collect();
+    let infos: Vec<_> =
+        RpcIndexes::owned_objects_iter(&store, owner, Some(GasCoin::type_().into()), None)
+            .expect("owned-object iterator should build")
+            .map(|result| result.expect("owned-object entry should decode"))
+            .collect();
     assert_eq!(infos.len(), 2);
     assert!(infos[0].object_id < infos[1].object_id);
     assert!(infos.iter().all(|info| info.owner == owner));
@@ -898,7 +903,7 @@ fn test_rpc_owned_objects_iter_filters_and_pages_by_object_id() {
 
     let wrong_type = "0x2::clock::Clock".parse::<StructTag>().unwrap();
     assert_eq!(
-        RpcIndexes::owned_objects_iter(&store, owner, Some(wrong_type), None)
+        RpcIndexes::owned_objects_iter(&store, owner, Some(wrong_type.into()), None)
             .expect("owned-object iterator should build")
             .count(),
         0,
@@ -907,7 +912,7 @@ fn test_rpc_owned_objects_iter_filters_and_pages_by_object_id() {
     let page_from_cursor: Vec<_> = RpcIndexes::owned_objects_iter(
         &store,
         owner,
-        Some(GasCoin::type_()),
+        Some(GasCoin::type_().into()),
         Some(infos[1].clone()),
     )
     .expect("owned-object iterator should build")
@@ -936,10 +941,37 @@ fn test_cloned_store_shares_owned_object_snapshot_guard() {
     drop(local_snapshot_guard);
 
     let infos: Vec<_> =
-        RpcIndexes::owned_objects_iter(&reader, owner, Some(GasCoin::type_()), None)
+        RpcIndexes::owned_objects_iter(&reader, owner, Some(GasCoin::type_().into()), None)
             .expect("owned-object iterator should build")
             .map(|result| result.expect("owned-object entry should decode"))
             .collect();
     assert_eq!(infos.len(), 1);
     assert_eq!(infos[0].object_id, object_id);
 }
+
+#[tokio::test]
+async fn test_get_object_reports_remote_failures() {
+    let temp = tempfile::tempdir().expect("failed to create tempdir");
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let store = DataStore::new_for_testing_with_remote(temp.path().to_path_buf(), server.uri(), 42);
+    let object_id = ObjectID::random();
+
+    // The fallible read surfaces the failure, while the `ObjectStore` view can only report the
+    // object as missing.
+    assert!(DataStore::get_object(&store, &object_id).is_err());
+    assert!(sui_types::storage::ObjectStore::get_object(&store, &object_id).is_none());
+    assert!(
+        store
+            .local()
+            .get_latest_object(&object_id)
+            .expect("local lookup should succeed")
+            .is_none(),
+        "a failed fetch must not be cached",
+    );
+}
@@ -88,7 +88,8 @@ pub(crate) async fn tests(path: &Path) -> datatest_stable::Result<()> {
             Some(testing_config),
             false,
             false,
-            SuiFlavor::new()
+            SuiFlavor::new(),
+            None,
         )
         .await
         .unwrap(),
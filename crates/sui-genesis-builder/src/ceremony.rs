@@ -0,0 +1,337 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Genesis ceremonies run by a coordinator with validators who take part from elsewhere.
+//!
+//! The coordinator keeps the ceremony directory of a [`Builder`], and validators send
+//! [`Contribution`] files instead of editing it: first a [`Contribution::Validator`] to join the
+//! committee, then, once the coordinator has built the unsigned genesis checkpoint and shared the
+//! [`CeremonyState`], a [`Contribution::Signature`] over the checkpoint. Contributions are
+//! verified before they are added, and the directory is saved after each one, so that the
+//! ceremony can be resumed at any step. Finally, anyone with the ceremony state can check the
+//! genesis it produced with [`Builder::verify_genesis`].
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, bail, ensure};
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentScope};
+use sui_config::genesis::{
+    Genesis, GenesisCeremonyParameters, TokenDistributionSchedule, UnsignedGenesis,
+};
+use sui_types::committee::{CommitteeTrait, StakeUnit};
+use sui_types::crypto::{AuthoritySignInfo, AuthoritySignInfoTrait};
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::CheckpointDigest;
+use sui_types::object::Object;
+
+use crate::Builder;
+use crate::validator_info::GenesisValidatorInfo;
+
+/// What a validator adds to a ceremony.
+#[derive(Serialize, Deserialize)]
+pub enum Contribution {
+    /// A validator joining the committee.
+    Validator(GenesisValidatorInfo),
+    /// A validator's signature over the unsigned genesis checkpoint.
+    Signature(AuthoritySignInfo),
+}
+
+impl Contribution {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)
+            .with_context(|| format!("Unable to read contribution from {}", path.display()))?;
+        bcs::from_bytes(&bytes)
+            .with_context(|| format!("Unable to parse contribution from {}", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, bcs::to_bytes(self)?)
+            .with_context(|| format!("Unable to save contribution to {}", path.display()))
+    }
+}
+
+impl fmt::Display for Contribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Contribution::Validator(validator) => write!(
+                f,
+                "validator {} ({})",
+                validator.info.name(),
+                validator.info.sui_address()
+            ),
+            Contribution::Signature(signature) => {
+                write!(f, "genesis checkpoint signature by {}", signature.authority)
+            }
+        }
+    }
+}
+
+/// The inputs and unsigned genesis checkpoint of a ceremony, in a single file, for validators to
+/// verify and sign the checkpoint without a copy of the ceremony directory.
+#[derive(Serialize, Deserialize)]
+pub struct CeremonyState {
+    parameters: GenesisCeremonyParameters,
+    token_distribution_schedule: Option<TokenDistributionSchedule>,
+    objects: Vec<Object>,
+    validators: Vec<GenesisValidatorInfo>,
+    signatures: Vec<AuthoritySignInfo>,
+    unsigned_genesis: Option<UnsignedGenesis>,
+}
+
+impl CeremonyState {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)
+            .with_context(|| format!("Unable to read ceremony state from {}", path.display()))?;
+        bcs::from_bytes(&bytes)
+            .with_context(|| format!("Unable to parse ceremony state from {}", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, bcs::to_bytes(self)?)
+            .with_context(|| format!("Unable to save ceremony state to {}", path.display()))
+    }
+}
+
+/// The step a ceremony is at.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CeremonyStage {
+    /// Validators are joining, and the unsigned genesis checkpoint has not been built.
+    AddingValidators { validators: usize },
+    /// Validators are signing the unsigned genesis checkpoint. Genesis can be finalized once the
+    /// stake of the signers reaches the quorum threshold.
+    Signing {
+        checkpoint: CheckpointDigest,
+        validators: usize,
+        signatures: usize,
+        signed_stake: StakeUnit,
+        quorum_threshold: StakeUnit,
+    },
+}
+
+impl CeremonyStage {
+    pub fn can_finalize(&self) -> bool {
+        match self {
+            CeremonyStage::AddingValidators { .. } => false,
+            CeremonyStage::Signing {
+                signed_stake,
+                quorum_threshold,
+                ..
+            } => signed_stake >= quorum_threshold,
+        }
+    }
+}
+
+impl fmt::Display for CeremonyStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CeremonyStage::AddingValidators { validators } => write!(
+                f,
+                "Adding validators: {validators} joined, the unsigned genesis checkpoint has not \
+                 been built"
+            ),
+            CeremonyStage::Signing {
+                checkpoint,
+                validators,
+                signatures,
+                signed_stake,
+                quorum_threshold,
+            } => {
+                write!(
+                    f,
+                    "Signing genesis checkpoint {checkpoint}: {signatures} of {validators} \
+                     validators signed, with {signed_stake} of the {quorum_threshold} votes \
+                     needed"
+                )?;
+                if self.can_finalize() {
+                    write!(f, ", ready to finalize")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Builder {
+    /// The current step of the ceremony.
+    pub fn stage(&self) -> CeremonyStage {
+        let Some(unsigned_genesis) = &self.built_genesis else {
+            return CeremonyStage::AddingValidators {
+                validators: self.validators.len(),
+            };
+        };
+
+        let committee = Self::committee(&unsigned_genesis.objects);
+        CeremonyStage::Signing {
+            checkpoint: unsigned_genesis.checkpoint.digest(),
+            validators: self.validators.len(),
+            signatures: self.signatures.len(),
+            signed_stake: self
+                .signatures
+                .keys()
+                .map(|name| committee.weight(name))
+                .sum(),
+            quorum_threshold: committee.quorum_threshold(),
+        }
+    }
+
+    /// Check that `contribution` can be added to the ceremony at its current step.
+    pub fn verify_contribution(&self, contribution: &Contribution) -> anyhow::Result<()> {
+        match contribution {
+            Contribution::Validator(validator) => {
+                ensure!(
+                    self.built_genesis.is_none(),
+                    "Validators can't join once the unsigned genesis checkpoint is built"
+                );
+                validator.validate().with_context(|| {
+                    format!(
+                        "metadata for validator {} is invalid",
+                        validator.info.name()
+                    )
+                })?;
+
+                let protocol_key = validator.info.protocol_key();
+                for existing in self.validators.values() {
+                    let is_same = existing.info.protocol_key() == protocol_key;
+                    if !is_same && existing.info.name() == validator.info.name() {
+                        bail!(
+                            "A different validator named {} already joined",
+                            validator.info.name()
+                        );
+                    }
+                    if !is_same && existing.info.account_address == validator.info.account_address {
+                        bail!(
+                            "Validator {} already joined with account {}",
+                            existing.info.name(),
+                            validator.info.account_address
+                        );
+                    }
+                }
+            }
+
+            Contribution::Signature(signature) => {
+                let Some(unsigned_genesis) = &self.built_genesis else {
+                    bail!("The unsigned genesis checkpoint hasn't been built yet");
+                };
+                ensure!(
+                    self.validators.contains_key(&signature.authority),
+                    "Signature by {}, which is not a validator of the ceremony",
+                    signature.authority
+                );
+                signature
+                    .verify_secure(
+                        &unsigned_genesis.checkpoint,
+                        Intent::sui_app(IntentScope::CheckpointSummary),
+                        &Self::committee(&unsigned_genesis.objects),
+                    )
+                    .context("The signature is not over the unsigned genesis checkpoint")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify `contribution`, and add it to the ceremony.
+    pub fn add_contribution(mut self, contribution: Contribution) -> anyhow::Result<Self> {
+        self.verify_contribution(&contribution)?;
+        match contribution {
+            Contribution::Validator(validator) => {
+                self.validators
+                    .insert(validator.info.protocol_key(), validator);
+            }
+            Contribution::Signature(signature) => {
+                self.signatures.insert(signature.authority, signature);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Check that `genesis` is the genesis of this ceremony: that it holds the unsigned genesis
+    /// checkpoint and state built by the ceremony, certified by a quorum of its committee.
+    pub fn verify_genesis(&self, genesis: &Genesis) -> anyhow::Result<()> {
+        let Some(unsigned_genesis) = &self.built_genesis else {
+            bail!("The unsigned genesis checkpoint hasn't been built yet");
+        };
+
+        let checkpoint = genesis.certified_checkpoint();
+        ensure!(
+            checkpoint.data() == &unsigned_genesis.checkpoint,
+            "The genesis checkpoint {} is not the ceremony's unsigned genesis checkpoint {}",
+            checkpoint.data().digest(),
+            unsigned_genesis.checkpoint.digest(),
+        );
+        ensure!(
+            genesis.checkpoint_contents() == &unsigned_genesis.checkpoint_contents
+                && genesis.transaction() == &unsigned_genesis.transaction
+                && genesis.effects() == &unsigned_genesis.effects
+                && genesis.events() == &unsigned_genesis.events
+                && genesis.objects() == unsigned_genesis.objects.as_slice(),
+            "The genesis state is not the one built by the ceremony"
+        );
+
+        checkpoint
+            .verify_authority_signatures(&Self::committee(&unsigned_genesis.objects))
+            .context("The genesis checkpoint is not certified by a quorum of the committee")?;
+        Ok(())
+    }
+
+    /// The state of the ceremony, to share with validators.
+    pub fn state(&self) -> CeremonyState {
+        CeremonyState {
+            parameters: self.parameters.clone(),
+            token_distribution_schedule: self.token_distribution_schedule.clone(),
+            objects: self.objects.values().cloned().collect(),
+            validators: self.validators.values().cloned().collect(),
+            signatures: self.signatures.values().cloned().collect(),
+            unsigned_genesis: self.built_genesis.clone(),
+        }
+    }
+
+    /// Restore a ceremony from its `state`, checking that its unsigned genesis checkpoint, if any,
+    /// is the one built from its inputs.
+    pub fn from_state(state: CeremonyState) -> anyhow::Result<Self> {
+        let mut builder = Self {
+            parameters: state.parameters,
+            token_distribution_schedule: state.token_distribution_schedule,
+            objects: state.objects.into_iter().map(|o| (o.id(), o)).collect(),
+            validators: state
+                .validators
+                .into_iter()
+                .map(|v| (v.info.protocol_key(), v))
+                .collect(),
+            signatures: state
+                .signatures
+                .into_iter()
+                .map(|s| (s.authority, s))
+                .collect(),
+            built_genesis: None,
+        };
+
+        if let Some(unsigned_genesis) = state.unsigned_genesis {
+            ensure!(
+                builder.token_distribution_schedule.is_some(),
+                "A ceremony with an unsigned genesis checkpoint must have a token distribution \
+                 schedule"
+            );
+            builder.validate_inputs()?;
+
+            let built = builder.build_unsigned_genesis_checkpoint();
+            unsigned_genesis.checkpoint_contents.digest(); // cache digest before compare
+            ensure!(
+                built == unsigned_genesis,
+                "The unsigned genesis checkpoint {} is not the one built from the ceremony's \
+                 inputs, {}",
+                unsigned_genesis.checkpoint.digest(),
+                built.checkpoint.digest(),
+            );
+        }
+
+        Ok(builder)
+    }
+}
@@ -1,11 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{Context, bail};
+use anyhow::{Context, bail, ensure};
 use camino::Utf8Path;
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::KeyPair;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use mysten_common::ZipDebugEqIteratorExt;
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
 use std::collections::BTreeMap;
@@ -52,6 +55,7 @@ use sui_types::{BRIDGE_ADDRESS, SUI_BRIDGE_OBJECT_ID, SUI_FRAMEWORK_ADDRESS, SUI
 use tracing::trace;
 use validator_info::{GenesisValidatorInfo, GenesisValidatorMetadata, ValidatorInfo};
 
+pub mod ceremony;
 pub mod validator_info;
 
 const GENESIS_BUILDER_COMMITTEE_DIR: &str = "committee";
@@ -59,6 +63,7 @@ const GENESIS_BUILDER_PARAMETERS_FILE: &str = "parameters";
 const GENESIS_BUILDER_TOKEN_DISTRIBUTION_SCHEDULE_FILE: &str = "token-distribution-schedule";
 const GENESIS_BUILDER_SIGNATURE_DIR: &str = "signatures";
 const GENESIS_BUILDER_UNSIGNED_GENESIS_FILE: &str = "unsigned-genesis";
+const GENESIS_BUILDER_OBJECTS_DIR: &str = "objects";
 
 pub struct Builder {
     parameters: GenesisCeremonyParameters,
@@ -275,6 +280,8 @@ impl Builder {
             );
         }
 
+        validate_extra_objects(&self.objects)?;
+
         Ok(())
     }
 
@@ -595,10 +602,27 @@ impl Builder {
             signatures.insert(sigs.authority, sigs);
         }
 
+        // Load extra objects, which directories saved before they were persisted do not have
+        let mut objects = BTreeMap::new();
+        let objects_dir = path.join(GENESIS_BUILDER_OBJECTS_DIR);
+        if objects_dir.exists() {
+            for entry in objects_dir.read_dir_utf8()? {
+                let entry = entry?;
+                if entry.file_name().starts_with('.') {
+                    continue;
+                }
+
+                let path = entry.path();
+                let object: Object = bcs::from_bytes(&fs::read(path)?)
+                    .with_context(|| format!("unable to load genesis object from {path}"))?;
+                objects.insert(object.id(), object);
+            }
+        }
+
         let mut builder = Self {
             parameters,
             token_distribution_schedule,
-            objects: Default::default(),
+            objects,
             validators: committee,
             signatures,
             built_genesis: None, // Leave this as none, will build and compare below
@@ -647,6 +671,13 @@ impl Builder {
             )?)?;
         }
 
+        // Write extra objects
+        let objects_dir = path.join(GENESIS_BUILDER_OBJECTS_DIR);
+        fs::create_dir_all(&objects_dir)?;
+        for (id, object) in &self.objects {
+            fs::write(objects_dir.join(id.to_string()), bcs::to_bytes(object)?)?;
+        }
+
         // Write Signatures
         let signature_dir = path.join(GENESIS_BUILDER_SIGNATURE_DIR);
         std::fs::create_dir_all(&signature_dir)?;
@@ -702,6 +733,129 @@ fn create_genesis_digest(
     TransactionDigest::new(hash.into())
 }
 
+/// Objects created by genesis itself, which extra objects must not replace.
+const RESERVED_OBJECT_IDS: [ObjectID; 10] = [
+    sui_types::SUI_SYSTEM_STATE_OBJECT_ID,
+    sui_types::SUI_CLOCK_OBJECT_ID,
+    sui_types::SUI_AUTHENTICATOR_STATE_OBJECT_ID,
+    sui_types::SUI_RANDOMNESS_STATE_OBJECT_ID,
+    sui_types::SUI_BRIDGE_OBJECT_ID,
+    sui_types::SUI_COIN_REGISTRY_OBJECT_ID,
+    sui_types::SUI_DISPLAY_REGISTRY_OBJECT_ID,
+    sui_types::SUI_DENY_LIST_OBJECT_ID,
+    sui_types::SUI_ACCUMULATOR_ROOT_OBJECT_ID,
+    sui_types::SUI_ADDRESS_ALIAS_STATE_OBJECT_ID,
+];
+
+/// Check that the extra objects inserted into genesis are usable once the chain starts: Move
+/// objects must not replace objects created by genesis, their types must be defined by the
+/// framework or by packages inserted alongside them, and inserted packages must only depend on
+/// packages that exist at genesis.
+fn validate_extra_objects(objects: &BTreeMap<ObjectID, Object>) -> anyhow::Result<()> {
+    if objects.is_empty() {
+        return Ok(());
+    }
+
+    // Modules that exist at genesis, keyed by the address types refer to them with. System
+    // packages inserted as objects replace the built-in ones.
+    let mut modules: BTreeMap<(AccountAddress, String), CompiledModule> = BTreeMap::new();
+    for package in BuiltInFramework::iter_system_packages() {
+        if objects.contains_key(&package.id) {
+            continue;
+        }
+        for module in package.modules() {
+            let id = module.self_id();
+            modules.insert((*id.address(), id.name().to_string()), module);
+        }
+    }
+    for package in objects.values().filter_map(|o| o.data.try_as_package()) {
+        for (name, bytes) in package.serialized_module_map() {
+            let module = CompiledModule::deserialize_with_defaults(bytes)
+                .with_context(|| format!("module {name} of package {} is invalid", package.id()))?;
+            modules.insert((*module.self_id().address(), name.clone()), module);
+        }
+    }
+
+    for (id, object) in objects {
+        if let Some(package) = object.data.try_as_package() {
+            if is_system_package(*id) {
+                continue;
+            }
+            for dependency in package.linkage_table().values() {
+                let dependency = dependency.upgraded_id;
+                ensure!(
+                    is_system_package(dependency)
+                        || objects
+                            .get(&dependency)
+                            .is_some_and(|o| o.data.try_as_package().is_some()),
+                    "package {id} depends on {dependency}, which is not a package at genesis"
+                );
+            }
+        } else if let Some(move_object) = object.data.try_as_move() {
+            ensure!(
+                !RESERVED_OBJECT_IDS.contains(id),
+                "object {id} would replace an object created by genesis"
+            );
+            let type_: StructTag = move_object.type_().clone().into();
+            validate_genesis_struct(&modules, &type_, true)
+                .with_context(|| format!("object {id} has an invalid type"))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_genesis_type(
+    modules: &BTreeMap<(AccountAddress, String), CompiledModule>,
+    type_: &TypeTag,
+) -> anyhow::Result<()> {
+    match type_ {
+        TypeTag::Vector(inner) => validate_genesis_type(modules, inner),
+        TypeTag::Struct(struct_) => validate_genesis_struct(modules, struct_, false),
+        _ => Ok(()),
+    }
+}
+
+fn validate_genesis_struct(
+    modules: &BTreeMap<(AccountAddress, String), CompiledModule>,
+    type_: &StructTag,
+    is_object: bool,
+) -> anyhow::Result<()> {
+    let module = modules
+        .get(&(type_.address, type_.module.to_string()))
+        .with_context(|| {
+            format!(
+                "module {}::{} does not exist at genesis",
+                type_.address.to_canonical_display(true),
+                type_.module
+            )
+        })?;
+    let handle = if let Some((_, def)) = module.find_struct_def_by_name(type_.name.as_str()) {
+        module.datatype_handle_at(def.struct_handle)
+    } else if let Some((_, def)) = module.find_enum_def_by_name(type_.name.as_str()) {
+        ensure!(!is_object, "{type_} is an enum, which cannot be an object");
+        module.datatype_handle_at(def.enum_handle)
+    } else {
+        bail!("type {type_} is not defined");
+    };
+
+    ensure!(
+        handle.type_parameters.len() == type_.type_params.len(),
+        "{type_} has {} type arguments, but {} expects {}",
+        type_.type_params.len(),
+        type_.name,
+        handle.type_parameters.len()
+    );
+    ensure!(
+        !is_object || handle.abilities.has_key(),
+        "{type_} does not have the key ability"
+    );
+    for param in &type_.type_params {
+        validate_genesis_type(modules, param)?;
+    }
+    Ok(())
+}
+
 fn get_genesis_protocol_config(version: ProtocolVersion) -> ProtocolConfig {
     // We have a circular dependency here. Protocol config depends on chain ID, which
     // depends on genesis checkpoint (digest), which depends on genesis transaction, which
@@ -1215,15 +1369,17 @@ mod test {
     use crate::Builder;
     use crate::validator_info::ValidatorInfo;
     use fastcrypto::traits::KeyPair;
+    use move_core_types::language_storage::{StructTag, TypeTag};
     use sui_config::genesis::*;
     use sui_config::local_ip_utils;
     use sui_config::node::DEFAULT_COMMISSION_RATE;
     use sui_config::node::DEFAULT_VALIDATOR_GAS_PRICE;
-    use sui_types::base_types::SuiAddress;
+    use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
     use sui_types::crypto::{
         AccountKeyPair, AuthorityKeyPair, NetworkKeyPair, generate_proof_of_possession,
         get_key_pair_from_rng,
     };
+    use sui_types::object::{MoveObject, Object, Owner};
 
     #[test]
     fn allocation_csv() {
@@ -1242,6 +1398,42 @@ mod test {
         std::io::Write::write_all(&mut std::io::stdout(), &output).unwrap();
     }
 
+    fn coin(coin_type: &str, id: ObjectID) -> Object {
+        let coin_type: StructTag = coin_type.parse().unwrap();
+        Object::new_move(
+            MoveObject::new_coin(
+                TypeTag::Struct(Box::new(coin_type)),
+                SequenceNumber::new(),
+                id,
+                100,
+            ),
+            Owner::AddressOwner(SuiAddress::ZERO),
+            TransactionDigest::genesis_marker(),
+        )
+    }
+
+    #[test]
+    fn extra_objects_are_validated() {
+        let validate = |object| Builder::new().add_object(object).validate();
+
+        // Objects whose types are defined by the framework are accepted.
+        validate(coin("0x2::sui::SUI", ObjectID::random())).unwrap();
+        validate(Object::immutable_with_id_for_testing(ObjectID::random())).unwrap();
+
+        // Types must be defined by a package at genesis.
+        let err = validate(coin("0x2::sui::NOT_SUI", ObjectID::random())).unwrap_err();
+        assert!(format!("{err:#}").contains("is not defined"), "{err:#}");
+        let err = validate(coin("0x1234::my_coin::MY_COIN", ObjectID::random())).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("does not exist at genesis"),
+            "{err:#}"
+        );
+
+        // Objects created by genesis cannot be replaced.
+        let err = validate(coin("0x2::sui::SUI", sui_types::SUI_CLOCK_OBJECT_ID)).unwrap_err();
+        assert!(format!("{err:#}").contains("would replace"), "{err:#}");
+    }
+
     #[test]
     #[cfg_attr(msim, ignore)]
     fn ceremony() {
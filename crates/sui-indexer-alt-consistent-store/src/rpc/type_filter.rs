@@ -9,16 +9,16 @@ use bincode::error::EncodeError;
 use bincode::serde::BorrowCompat;
 use move_core_types::language_storage::StructTag;
 use sui_indexer_alt_framework::types::base_types::SuiAddress;
-use sui_indexer_alt_framework::types::parse_sui_address;
-use sui_indexer_alt_framework::types::parse_sui_module_id;
-use sui_indexer_alt_framework::types::parse_sui_struct_tag;
+use sui_indexer_alt_framework::types::storage::ObjectTypeFilter;
 
 /// Structured form of a type filter that could be just a package, a module, an uninstantiated
-/// type, or a fully qualified type with generics.
+/// type, a fully qualified type with generics, or a generic type whose trailing type parameters
+/// are wildcards.
 pub(crate) enum TypeFilter {
     Package(SuiAddress),
     Module(SuiAddress, String),
     Type(StructTag),
+    Instantiation(StructTag, usize),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -51,6 +51,18 @@ impl Encode for TypeFilter {
             TypeFilter::Type(tag) => {
                 BorrowCompat(tag).encode(e)?;
             }
+
+            // Encode the length of the type param vector, but only the leading type params that
+            // are not wildcards, so that we can find every instantiation that starts with them.
+            TypeFilter::Instantiation(tag, arity) => {
+                BorrowCompat(tag.address).encode(e)?;
+                tag.module.as_str().encode(e)?;
+                tag.name.as_str().encode(e)?;
+                arity.encode(e)?;
+                for param in &tag.type_params {
+                    BorrowCompat(param).encode(e)?;
+                }
+            }
         }
         Ok(())
     }
@@ -60,17 +72,23 @@ impl FromStr for TypeFilter {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Error> {
-        if let Ok(tag) = parse_sui_struct_tag(s) {
-            Ok(TypeFilter::Type(tag))
-        } else if let Ok(module) = parse_sui_module_id(s) {
-            Ok(TypeFilter::Module(
+        let filter: ObjectTypeFilter = s.parse().map_err(|_| {
+            Error(
+                "package[::module[::name[<type, ...>]]], where the module, name, or trailing \
+                 types can be '*'",
+            )
+        })?;
+
+        Ok(match filter {
+            ObjectTypeFilter::Package(package) => TypeFilter::Package(package),
+            ObjectTypeFilter::Module(module) => TypeFilter::Module(
                 SuiAddress::from(*module.address()),
                 module.name().to_string(),
-            ))
-        } else if let Ok(package) = parse_sui_address(s) {
-            Ok(TypeFilter::Package(package))
-        } else {
-            Err(Error("package[::module[::name[<type, ...>]]]"))
-        }
+            ),
+            ObjectTypeFilter::Type(tag) => TypeFilter::Type(tag),
+            ObjectTypeFilter::Instantiation { type_, arity } => {
+                TypeFilter::Instantiation(type_, arity)
+            }
+        })
     }
 }
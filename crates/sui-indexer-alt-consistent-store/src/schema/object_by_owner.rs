@@ -74,6 +74,16 @@ impl Key {
     pub fn encode(&self) -> Vec<u8> {
         crate::db::key::encode(self)
     }
+
+    /// Decode a key from its encoded form, e.g. a page token from a `ListOwnedObjects` response.
+    pub fn decode(bytes: &[u8]) -> Result<Key, DecodeError> {
+        crate::db::key::decode(bytes)
+    }
+
+    /// The type of the object this key indexes.
+    pub fn object_type(&self) -> &StructTag {
+        &self.type_
+    }
 }
 
 impl OwnerKind {
@@ -143,6 +153,8 @@ pub(crate) fn options(base_options: &rocksdb::Options) -> rocksdb::Options {
 
 #[cfg(test)]
 mod tests {
+    use sui_indexer_alt_framework::types::gas_coin::GasCoin;
+
     use crate::db::key;
 
     use super::*;
@@ -161,4 +173,21 @@ mod tests {
         let immutable = OwnerKind::Immutable;
         assert_eq!(immutable, key::decode(&key::encode(&immutable)).unwrap());
     }
+
+    #[test]
+    fn test_key_roundtrip() {
+        let owner = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+        let type_ = GasCoin::type_();
+        let key = Key::from_coin_parts(&owner, type_.clone(), 42, ObjectID::random());
+
+        let decoded = Key::decode(&key.encode()).unwrap();
+        assert!(key == decoded);
+        assert_eq!(decoded.object_type(), &type_);
+        assert_eq!(decoded.balance, Some(!42));
+    }
+
+    #[test]
+    fn test_key_decode_invalid() {
+        assert!(Key::decode(&[4]).is_err());
+    }
 }
@@ -133,6 +133,25 @@ async fn test_type_filter() {
         (tu64s.values().map(repr).collect(), None)
     );
 
+    // All tables, by wildcard type params
+    assert_eq!(
+        list_objects_by_type(&cluster, "0x2::table::Table<*, *>", None, Some(50))
+            .await
+            .unwrap(),
+        (
+            tu8s.values().chain(tu64s.values()).map(repr).collect(),
+            None
+        )
+    );
+
+    // All Table<u8, _>s, by a trailing wildcard
+    assert_eq!(
+        list_objects_by_type(&cluster, "0x2::table::Table<u8, *>", None, Some(50))
+            .await
+            .unwrap(),
+        (tu8s.values().map(repr).collect(), None)
+    );
+
     // Try to paginate Table<u8, u64>s -- none should exist.
     assert_eq!(
         list_objects_by_type(&cluster, "0x2::table::Table<u8, u64>", None, Some(50))
@@ -173,7 +192,8 @@ async fn test_type_filter() {
     assert_eq!(err.code(), tonic::Code::InvalidArgument);
     assert_eq!(
         err.message(),
-        "Bad 'object_type' filter, expected: package[::module[::name[<type, ...>]]]"
+        "Bad 'object_type' filter, expected: package[::module[::name[<type, ...>]]], where the \
+         module, name, or trailing types can be '*'"
     );
 
     // Missing type filter
@@ -466,7 +466,8 @@ async fn test_type_filters() {
     assert_eq!(err.code(), tonic::Code::InvalidArgument);
     assert_eq!(
         err.message(),
-        "Bad 'object_type' filter, expected: package[::module[::name[<type, ...>]]]"
+        "Bad 'object_type' filter, expected: package[::module[::name[<type, ...>]]], where the \
+         module, name, or trailing types can be '*'"
     );
 }
 
@@ -0,0 +1,126 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::get_account_key_pair;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::gas_coin::GasCoin;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::Transaction;
+use sui_types::transaction::TransactionData;
+
+use sui_indexer_alt_e2e_tests::FullCluster;
+
+const DEFAULT_GAS_BUDGET: u64 = 5_000_000_000;
+
+/// Deserialized successful JSON-RPC response for `suix_getAddressActivity`.
+#[derive(Deserialize)]
+struct Response {
+    result: Value,
+}
+
+#[tokio::test]
+async fn test_address_activity() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let with_prefix = true;
+    let coin_type = GasCoin::type_().to_canonical_string(with_prefix);
+    let (a, _) = get_account_key_pair();
+
+    send_sui(&mut cluster, a, 100);
+    send_sui(&mut cluster, a, 200);
+    send_sui(&mut cluster, a, 300);
+
+    cluster.create_checkpoint().await;
+
+    let Response { result } = get_address_activity(&cluster, a).await;
+
+    assert_eq!(result["transactionsScanned"], json!("3"));
+    assert_eq!(result["transactionsTruncated"], json!(false));
+    assert_eq!(
+        result["transactionKinds"],
+        json!({ "ProgrammableTransaction": "3" })
+    );
+
+    // The transfers are built without move calls.
+    assert_eq!(result["packages"], json!([]));
+
+    let first = &result["firstSeen"];
+    let last = &result["lastSeen"];
+    assert!(first["digest"].is_string());
+    assert!(last["digest"].is_string());
+    assert_ne!(first["digest"], last["digest"]);
+
+    let counts = result["objectCounts"].as_object().unwrap();
+    let (type_, count) = counts.iter().next().expect("No objects counted");
+    assert_eq!(counts.len(), 1);
+    assert_eq!(type_, &coin_type);
+    assert_eq!(count, &json!("3"));
+    assert_eq!(result["objectsTruncated"], json!(false));
+
+    let balances = result["balances"].as_array().unwrap();
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0]["coinObjectCount"], json!(3));
+    assert_eq!(balances[0]["totalBalance"], json!("600"));
+}
+
+#[tokio::test]
+async fn test_address_activity_unknown_address() {
+    let cluster = FullCluster::new().await.unwrap();
+    let (a, _) = get_account_key_pair();
+
+    let Response { result } = get_address_activity(&cluster, a).await;
+
+    assert_eq!(result["firstSeen"], Value::Null);
+    assert_eq!(result["lastSeen"], Value::Null);
+    assert_eq!(result["transactionsScanned"], json!("0"));
+    assert_eq!(result["transactionKinds"], json!({}));
+    assert_eq!(result["objectCounts"], json!({}));
+    assert_eq!(result["balances"], json!([]));
+}
+
+/// Run a transaction on `cluster` signed by a fresh funded account that sends a coin with value
+/// `amount` to `owner`.
+fn send_sui(cluster: &mut FullCluster, owner: SuiAddress, amount: u64) {
+    let (sender, kp, gas) = cluster
+        .funded_account(DEFAULT_GAS_BUDGET + amount)
+        .expect("Failed to fund account");
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(owner, Some(amount));
+
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        builder.finish(),
+        DEFAULT_GAS_BUDGET,
+        cluster.reference_gas_price(),
+    );
+
+    let (fx, _) = cluster
+        .execute_transaction(Transaction::from_data_and_signer(data, vec![&kp]))
+        .expect("Failed to execute transaction");
+
+    assert!(fx.status().is_ok(), "transfer transaction failed");
+}
+
+async fn get_address_activity(cluster: &FullCluster, address: SuiAddress) -> Response {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "method": "suix_getAddressActivity",
+        "params": [address.to_string()],
+        "id": 1
+    });
+
+    reqwest::Client::new()
+        .post(cluster.jsonrpc_url().as_str())
+        .json(&query)
+        .send()
+        .await
+        .expect("Request to JSON-RPC server failed")
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response")
+}
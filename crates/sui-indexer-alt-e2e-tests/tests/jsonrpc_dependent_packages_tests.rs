@@ -0,0 +1,125 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use sui_json_rpc_types::Page;
+use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+use sui_types::base_types::ObjectID;
+
+use sui_indexer_alt_e2e_tests::FullCluster;
+use sui_indexer_alt_e2e_tests::coin_registry;
+use sui_indexer_alt_e2e_tests::find;
+
+/// Deserialized successful JSON-RPC response for `suix_getDependentPackages`.
+#[derive(Deserialize)]
+struct Response {
+    result: Page<DependentPackage, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DependentPackage {
+    package_id: ObjectID,
+    dependency_id: ObjectID,
+}
+
+#[tokio::test]
+async fn test_published_package_depends_on_framework() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let (_, _, fx) = coin_registry::publish(&mut cluster, "fixed_supply").await;
+    let package = find::immutable(&fx).unwrap().0;
+    cluster.create_checkpoint().await;
+
+    // Page through all the dependents of the framework, one at a time, to exercise the cursor.
+    let mut dependents = vec![];
+    let mut cursor = None;
+    loop {
+        let Response {
+            result:
+                Page {
+                    data,
+                    next_cursor,
+                    has_next_page,
+                },
+        } = get_dependent_packages(&cluster, SUI_FRAMEWORK_PACKAGE_ID, cursor, 1)
+            .await
+            .expect("Request failed");
+
+        assert!(data.len() <= 1);
+        dependents.extend(data);
+        if !has_next_page {
+            break;
+        }
+        cursor = next_cursor;
+    }
+
+    // Dependents are returned in ascending order of package ID, without repeats.
+    let ids: Vec<_> = dependents.iter().map(|d| d.package_id).collect();
+    assert!(ids.windows(2).all(|w| w[0] < w[1]), "{ids:?}");
+
+    let dependent = dependents
+        .iter()
+        .find(|d| d.package_id == package)
+        .expect("Published package is not a dependent of the framework");
+    assert_eq!(dependent.dependency_id, SUI_FRAMEWORK_PACKAGE_ID);
+}
+
+#[tokio::test]
+async fn test_package_without_dependents() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let (_, _, fx) = coin_registry::publish(&mut cluster, "fixed_supply").await;
+    let package = find::immutable(&fx).unwrap().0;
+    cluster.create_checkpoint().await;
+
+    let Response { result } = get_dependent_packages(&cluster, package, None, 10)
+        .await
+        .expect("Request failed");
+
+    assert!(result.data.is_empty());
+    assert!(!result.has_next_page);
+}
+
+#[tokio::test]
+async fn test_unknown_package() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    cluster.create_checkpoint().await;
+
+    let error = get_dependent_packages(&cluster, ObjectID::random(), None, 10)
+        .await
+        .expect_err("Request for an unknown package succeeded");
+
+    assert!(error.contains("not found"), "{error}");
+}
+
+/// Call `suix_getDependentPackages`, returning the error message if the call failed.
+async fn get_dependent_packages(
+    cluster: &FullCluster,
+    package: ObjectID,
+    cursor: Option<String>,
+    limit: usize,
+) -> Result<Response, String> {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "method": "suix_getDependentPackages",
+        "params": [package.to_string(), cursor, limit],
+        "id": 1
+    });
+
+    let response: Value = reqwest::Client::new()
+        .post(cluster.jsonrpc_url().as_str())
+        .json(&query)
+        .send()
+        .await
+        .expect("Request to JSON-RPC server failed")
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response");
+
+    if let Some(error) = response.get("error") {
+        return Err(error["message"].to_string());
+    }
+
+    Ok(serde_json::from_value(response).expect("Failed to deserialize response"))
+}
@@ -0,0 +1,156 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::Value;
+use serde_json::json;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::get_account_key_pair;
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::Transaction;
+use sui_types::transaction::TransactionData;
+
+use sui_indexer_alt_e2e_tests::FullCluster;
+
+const DEFAULT_GAS_BUDGET: u64 = 5_000_000_000;
+
+/// The default limit on the number of digests in a `sui_multiGetTransactionBlocks` request.
+const MAX_MULTI_GET_TRANSACTIONS: usize = 50;
+
+#[tokio::test]
+async fn test_multi_get_in_request_order() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let (a, _) = get_account_key_pair();
+
+    let mut digests = vec![];
+    for amount in [1, 2, 3] {
+        digests.push(send_sui(&mut cluster, a, amount));
+    }
+
+    cluster.create_checkpoint().await;
+
+    digests.reverse();
+    let result = multi_get_transactions(&cluster, &digests, json!({ "showEffects": true }))
+        .await
+        .expect("Request failed");
+
+    let responses = result.as_array().unwrap();
+    let returned: Vec<_> = responses
+        .iter()
+        .map(|r| r["digest"].as_str().unwrap().to_owned())
+        .collect();
+    let expected: Vec<_> = digests.iter().map(|d| d.to_string()).collect();
+    assert_eq!(returned, expected);
+
+    // The options apply to every transaction.
+    for response in responses {
+        assert!(response["effects"].is_object());
+        assert!(response.get("events").is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_multi_get_empty() {
+    let cluster = FullCluster::new().await.unwrap();
+    let result = multi_get_transactions(&cluster, &[], json!({}))
+        .await
+        .expect("Request failed");
+    assert_eq!(result, json!([]));
+}
+
+#[tokio::test]
+async fn test_multi_get_duplicate_digest() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let (a, _) = get_account_key_pair();
+    let digest = send_sui(&mut cluster, a, 1);
+    cluster.create_checkpoint().await;
+
+    let error = multi_get_transactions(&cluster, &[digest, digest], json!({}))
+        .await
+        .expect_err("Duplicate digests were accepted");
+    assert!(error.contains("Duplicate transaction digest"), "{error}");
+}
+
+#[tokio::test]
+async fn test_multi_get_not_found() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    let (a, _) = get_account_key_pair();
+    let digest = send_sui(&mut cluster, a, 1);
+    cluster.create_checkpoint().await;
+
+    let error = multi_get_transactions(&cluster, &[digest, TransactionDigest::random()], json!({}))
+        .await
+        .expect_err("Missing transaction was not reported");
+    assert!(error.contains("not found"), "{error}");
+}
+
+#[tokio::test]
+async fn test_multi_get_too_many_digests() {
+    let cluster = FullCluster::new().await.unwrap();
+    let digests: Vec<_> = (0..=MAX_MULTI_GET_TRANSACTIONS)
+        .map(|_| TransactionDigest::random())
+        .collect();
+
+    let error = multi_get_transactions(&cluster, &digests, json!({}))
+        .await
+        .expect_err("Oversized request was accepted");
+    assert!(error.contains("exceeding maximum"), "{error}");
+}
+
+/// Run a transaction on `cluster` signed by a fresh funded account that sends a coin with value
+/// `amount` to `owner`, returning its digest.
+fn send_sui(cluster: &mut FullCluster, owner: SuiAddress, amount: u64) -> TransactionDigest {
+    let (sender, kp, gas) = cluster
+        .funded_account(DEFAULT_GAS_BUDGET + amount)
+        .expect("Failed to fund account");
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(owner, Some(amount));
+
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        builder.finish(),
+        DEFAULT_GAS_BUDGET,
+        cluster.reference_gas_price(),
+    );
+
+    let (fx, _) = cluster
+        .execute_transaction(Transaction::from_data_and_signer(data, vec![&kp]))
+        .expect("Failed to execute transaction");
+
+    assert!(fx.status().is_ok(), "transfer transaction failed");
+    *fx.transaction_digest()
+}
+
+/// Call `sui_multiGetTransactionBlocks`, returning the error message if the call failed.
+async fn multi_get_transactions(
+    cluster: &FullCluster,
+    digests: &[TransactionDigest],
+    options: Value,
+) -> Result<Value, String> {
+    let digests: Vec<_> = digests.iter().map(|d| d.to_string()).collect();
+    let query = json!({
+        "jsonrpc": "2.0",
+        "method": "sui_multiGetTransactionBlocks",
+        "params": [digests, options],
+        "id": 1
+    });
+
+    let mut response: Value = reqwest::Client::new()
+        .post(cluster.jsonrpc_url().as_str())
+        .json(&query)
+        .send()
+        .await
+        .expect("Request to JSON-RPC server failed")
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response");
+
+    if let Some(error) = response.get("error") {
+        return Err(error["message"].to_string());
+    }
+
+    Ok(response["result"].take())
+}
@@ -0,0 +1,224 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for `suix_getCheckpointPackages` and `suix_getPackageActivity`, which are served from the
+//! `cp_affected_packages` pipeline, and must only return checkpoints within its watermarks.
+
+use std::time::Duration;
+
+use move_core_types::ident_str;
+use move_core_types::language_storage::TypeTag;
+use serde_json::Value;
+use serde_json::json;
+use simulacrum::Simulacrum;
+use sui_indexer_alt::config::ConcurrentLayer;
+use sui_indexer_alt::config::IndexerConfig;
+use sui_indexer_alt::config::PipelineLayer;
+use sui_indexer_alt::config::PrunerLayer;
+use sui_json_rpc_types::Page;
+use sui_types::MOVE_STDLIB_PACKAGE_ID;
+use sui_types::base_types::ObjectID;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::sui_serde::BigInt;
+use sui_types::transaction::Transaction;
+use sui_types::transaction::TransactionData;
+
+use sui_indexer_alt_e2e_tests::FullCluster;
+use sui_indexer_alt_e2e_tests::OffchainClusterConfig;
+
+/// 5 SUI gas budget
+const DEFAULT_GAS_BUDGET: u64 = 5_000_000_000;
+
+#[tokio::test]
+async fn test_package_activity() {
+    let mut cluster = FullCluster::new().await.unwrap();
+
+    // Only checkpoints 1 and 3 call the standard library.
+    call_stdlib(&mut cluster);
+    cluster.create_checkpoint().await;
+    cluster.create_checkpoint().await;
+    call_stdlib(&mut cluster);
+    cluster.create_checkpoint().await;
+
+    let all = get_package_activity(&cluster, MOVE_STDLIB_PACKAGE_ID, None, 10, false)
+        .await
+        .unwrap();
+    assert_eq!(checkpoints(&all), vec![1, 3]);
+    assert!(!all.has_next_page);
+
+    let all = get_package_activity(&cluster, MOVE_STDLIB_PACKAGE_ID, None, 10, true)
+        .await
+        .unwrap();
+    assert_eq!(checkpoints(&all), vec![3, 1]);
+
+    // Page through the activity one checkpoint at a time.
+    let first = get_package_activity(&cluster, MOVE_STDLIB_PACKAGE_ID, None, 1, false)
+        .await
+        .unwrap();
+    assert_eq!(checkpoints(&first), vec![1]);
+    assert!(first.has_next_page);
+    let second = get_package_activity(
+        &cluster,
+        MOVE_STDLIB_PACKAGE_ID,
+        first.next_cursor,
+        1,
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(checkpoints(&second), vec![3]);
+    assert!(!second.has_next_page);
+
+    let packages = get_checkpoint_packages(&cluster, 1).await.unwrap();
+    assert!(packages.contains(&MOVE_STDLIB_PACKAGE_ID), "{packages:?}");
+    let packages = get_checkpoint_packages(&cluster, 2).await.unwrap();
+    assert!(!packages.contains(&MOVE_STDLIB_PACKAGE_ID), "{packages:?}");
+
+    // Checkpoints that have not been indexed are not reported as empty.
+    let error = get_checkpoint_packages(&cluster, 1000)
+        .await
+        .expect_err("Request for an unindexed checkpoint succeeded");
+    assert!(error.contains("not available"), "{error}");
+}
+
+/// Activity that has been pruned is not returned, even before the pruner deletes its rows.
+#[tokio::test]
+async fn test_package_activity_pruned() {
+    let mut cluster = FullCluster::new_with_configs(
+        Simulacrum::new(),
+        OffchainClusterConfig {
+            indexer_config: IndexerConfig {
+                pipeline: PipelineLayer {
+                    cp_affected_packages: Some(ConcurrentLayer {
+                        pruner: Some(PrunerLayer {
+                            retention: Some(5),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    cp_sequence_numbers: Some(ConcurrentLayer::default()),
+                    ..Default::default()
+                },
+                ..IndexerConfig::for_test()
+            },
+            ..Default::default()
+        },
+        &prometheus::Registry::new(),
+    )
+    .await
+    .expect("Failed to create cluster");
+
+    for _ in 0..10 {
+        call_stdlib(&mut cluster);
+        cluster.create_checkpoint().await;
+    }
+
+    cluster
+        .wait_for_pruner("cp_affected_packages", 5, Duration::from_secs(10))
+        .await
+        .unwrap();
+
+    for descending in [false, true] {
+        let page = get_package_activity(&cluster, MOVE_STDLIB_PACKAGE_ID, None, 100, descending)
+            .await
+            .unwrap();
+        let checkpoints = checkpoints(&page);
+        assert!(!checkpoints.is_empty());
+        assert!(checkpoints.iter().all(|cp| *cp >= 5), "{checkpoints:?}");
+    }
+
+    let error = get_checkpoint_packages(&cluster, 1)
+        .await
+        .expect_err("Request for a pruned checkpoint succeeded");
+    assert!(error.contains("not available"), "{error}");
+}
+
+/// Execute a transaction that calls a function in the standard library.
+fn call_stdlib(cluster: &mut FullCluster) {
+    let (sender, kp, gas) = cluster
+        .funded_account(DEFAULT_GAS_BUDGET)
+        .expect("Failed to fund account");
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.programmable_move_call(
+        MOVE_STDLIB_PACKAGE_ID,
+        ident_str!("option").to_owned(),
+        ident_str!("none").to_owned(),
+        vec![TypeTag::U64],
+        vec![],
+    );
+
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas],
+        builder.finish(),
+        DEFAULT_GAS_BUDGET,
+        cluster.reference_gas_price(),
+    );
+
+    let (fx, _) = cluster
+        .execute_transaction(Transaction::from_data_and_signer(data, vec![&kp]))
+        .expect("Failed to execute transaction");
+
+    assert!(fx.status().is_ok(), "Call to the standard library failed");
+}
+
+fn checkpoints(page: &Page<BigInt<u64>, String>) -> Vec<u64> {
+    page.data.iter().map(|cp| **cp).collect()
+}
+
+/// Call `suix_getPackageActivity`, returning the error message if the call failed.
+async fn get_package_activity(
+    cluster: &FullCluster,
+    package: ObjectID,
+    cursor: Option<String>,
+    limit: usize,
+    descending: bool,
+) -> Result<Page<BigInt<u64>, String>, String> {
+    call(
+        cluster,
+        "suix_getPackageActivity",
+        json!([package.to_string(), cursor, limit, descending]),
+    )
+    .await
+}
+
+/// Call `suix_getCheckpointPackages`, returning the error message if the call failed.
+async fn get_checkpoint_packages(cluster: &FullCluster, seq: u64) -> Result<Vec<ObjectID>, String> {
+    call(
+        cluster,
+        "suix_getCheckpointPackages",
+        json!([seq.to_string()]),
+    )
+    .await
+}
+
+async fn call<T: serde::de::DeserializeOwned>(
+    cluster: &FullCluster,
+    method: &str,
+    params: Value,
+) -> Result<T, String> {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1
+    });
+
+    let response: Value = reqwest::Client::new()
+        .post(cluster.jsonrpc_url().as_str())
+        .json(&query)
+        .send()
+        .await
+        .expect("Request to JSON-RPC server failed")
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response");
+
+    if let Some(error) = response.get("error") {
+        return Err(error["message"].to_string());
+    }
+
+    Ok(serde_json::from_value(response["result"].clone()).expect("Failed to deserialize result"))
+}
@@ -0,0 +1,145 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use sui_json_rpc_types::Page;
+use sui_types::base_types::SuiAddress;
+
+use sui_indexer_alt_e2e_tests::FullCluster;
+
+/// Deserialized successful JSON-RPC response.
+#[derive(Deserialize)]
+struct Response<T> {
+    result: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidatorEpochPerformance {
+    epoch: String,
+    checkpoints_signed: Option<String>,
+    stake: String,
+}
+
+#[tokio::test]
+async fn test_validator_epoch_performance() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    cluster.create_checkpoint().await;
+    cluster.create_checkpoint().await;
+
+    let validators = active_validators(&cluster).await;
+    assert!(!validators.is_empty());
+
+    // Nothing is reported for an epoch that has not ended yet.
+    let Response { result } = get_performance(&cluster, validators[0], None, 10, false).await;
+    assert!(result.data.is_empty());
+
+    cluster.advance_clock(Duration::from_secs(1));
+    cluster.advance_epoch();
+    cluster.create_checkpoint().await;
+    cluster.advance_epoch();
+    cluster.create_checkpoint().await;
+
+    for validator in &validators {
+        let Response { result } = get_performance(&cluster, *validator, None, 10, false).await;
+        let epochs: Vec<_> = result.data.iter().map(|p| p.epoch.as_str()).collect();
+        assert_eq!(epochs, vec!["0", "1"], "{validator}");
+
+        // Every checkpoint of the genesis epoch, including its last, was signed by the whole
+        // committee.
+        let signed: u64 = result.data[0]
+            .checkpoints_signed
+            .as_ref()
+            .expect("Validator not found in the committee")
+            .parse()
+            .unwrap();
+        assert!(signed >= 3, "{validator} signed {signed} checkpoints");
+
+        let stake: u64 = result.data[0].stake.parse().unwrap();
+        assert!(stake > 0, "{validator} has no stake");
+    }
+
+    // Pages can be read newest epoch first, one at a time.
+    let Response { result } = get_performance(&cluster, validators[0], None, 1, true).await;
+    let epochs: Vec<_> = result.data.iter().map(|p| p.epoch.as_str()).collect();
+    assert_eq!(epochs, vec!["1"]);
+    assert!(result.has_next_page);
+
+    let Response { result } =
+        get_performance(&cluster, validators[0], result.next_cursor, 1, true).await;
+    let epochs: Vec<_> = result.data.iter().map(|p| p.epoch.as_str()).collect();
+    assert_eq!(epochs, vec!["0"]);
+    assert!(!result.has_next_page);
+}
+
+#[tokio::test]
+async fn test_unknown_validator() {
+    let mut cluster = FullCluster::new().await.unwrap();
+    cluster.advance_epoch();
+    cluster.create_checkpoint().await;
+
+    let Response { result } = get_performance(
+        &cluster,
+        SuiAddress::random_for_testing_only(),
+        None,
+        10,
+        false,
+    )
+    .await;
+    assert!(result.data.is_empty());
+    assert!(!result.has_next_page);
+}
+
+/// The addresses of the current epoch's validators.
+async fn active_validators(cluster: &FullCluster) -> Vec<SuiAddress> {
+    let Response::<Value> { result } =
+        call(cluster, "suix_getLatestSuiSystemState", json!([])).await;
+    result["activeValidators"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["suiAddress"].as_str().unwrap().parse().unwrap())
+        .collect()
+}
+
+async fn get_performance(
+    cluster: &FullCluster,
+    validator: SuiAddress,
+    cursor: Option<String>,
+    limit: usize,
+    descending: bool,
+) -> Response<Page<ValidatorEpochPerformance, String>> {
+    call(
+        cluster,
+        "suix_getValidatorEpochPerformance",
+        json!([validator.to_string(), cursor, limit, descending]),
+    )
+    .await
+}
+
+async fn call<T: serde::de::DeserializeOwned>(
+    cluster: &FullCluster,
+    method: &str,
+    params: Value,
+) -> Response<T> {
+    let query = json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1
+    });
+
+    reqwest::Client::new()
+        .post(cluster.jsonrpc_url().as_str())
+        .json(&query)
+        .send()
+        .await
+        .expect("Request to JSON-RPC server failed")
+        .json()
+        .await
+        .expect("Failed to parse JSON-RPC response")
+}
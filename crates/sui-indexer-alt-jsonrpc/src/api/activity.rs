@@ -0,0 +1,375 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use anyhow::Context as _;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::dsl::count_star;
+use futures::try_join;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use sui_indexer_alt_consistent_store::ObjectByOwnerKey;
+use sui_indexer_alt_reader::consistent_reader::proto::owner::OwnerKind;
+use sui_indexer_alt_reader::tx_digests::TxDigestKey;
+use sui_indexer_alt_schema::schema::tx_affected_addresses;
+use sui_indexer_alt_schema::schema::tx_calls;
+use sui_indexer_alt_schema::schema::tx_kinds;
+use sui_indexer_alt_schema::transactions::StoredKind;
+use sui_json_rpc_types::Balance;
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::ObjectID;
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::TransactionDigest;
+use sui_types::sui_serde::BigInt;
+
+use crate::api::coin::all_balances;
+use crate::api::rpc_module::RpcModule;
+use crate::api::transactions::filter::paginate;
+use crate::context::Context;
+use crate::error::InternalContext;
+use crate::error::RpcError;
+use crate::paginate::JsonCursor;
+use crate::paginate::Page;
+
+#[open_rpc(namespace = "suix", tag = "Activity API")]
+#[rpc(server, namespace = "suix")]
+trait ActivityApi {
+    /// Return a summary of an address's activity: the first and last transactions that affected
+    /// it, the kinds of its recent transactions and the packages they called, the types of the
+    /// objects it currently owns, and its coin balances.
+    ///
+    /// Transaction statistics are computed over the address's most recent transactions, and object
+    /// counts over a bounded number of its owned objects. The response indicates whether either
+    /// limit was reached.
+    #[method(name = "getAddressActivity")]
+    async fn get_address_activity(
+        &self,
+        /// the address to summarize
+        address: SuiAddress,
+    ) -> RpcResult<AddressActivity>;
+}
+
+pub(crate) struct Activity(pub Context);
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AddressActivity {
+    /// The earliest transaction affecting the address that has not been pruned.
+    pub first_seen: Option<ActivityTransaction>,
+
+    /// The latest transaction affecting the address.
+    pub last_seen: Option<ActivityTransaction>,
+
+    /// The number of recent transactions that `transactionKinds` and `packages` were computed
+    /// over.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub transactions_scanned: u64,
+
+    /// Whether the address has older transactions than the ones that were scanned.
+    pub transactions_truncated: bool,
+
+    /// Counts of the scanned transactions, by transaction kind.
+    #[schemars(with = "BTreeMap<String, BigInt<u64>>")]
+    #[serde_as(as = "BTreeMap<_, BigInt<u64>>")]
+    pub transaction_kinds: BTreeMap<String, u64>,
+
+    /// The packages called by the scanned transactions.
+    pub packages: BTreeSet<ObjectID>,
+
+    /// Counts of the objects owned by the address, by type.
+    #[schemars(with = "BTreeMap<String, BigInt<u64>>")]
+    #[serde_as(as = "BTreeMap<_, BigInt<u64>>")]
+    pub object_counts: BTreeMap<String, u64>,
+
+    /// Whether the address owns more objects than were counted.
+    pub objects_truncated: bool,
+
+    /// The address's balance for every coin type it holds.
+    pub balances: Vec<Balance>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActivityTransaction {
+    pub digest: TransactionDigest,
+
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub checkpoint: Option<u64>,
+
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub timestamp_ms: Option<u64>,
+}
+
+/// Statistics gathered from the address's most recent transactions.
+struct RecentTransactions {
+    last_seen: Option<i64>,
+    scanned: u64,
+    truncated: bool,
+    kinds: BTreeMap<String, u64>,
+    packages: BTreeSet<ObjectID>,
+}
+
+#[async_trait::async_trait]
+impl ActivityApiServer for Activity {
+    async fn get_address_activity(&self, address: SuiAddress) -> RpcResult<AddressActivity> {
+        let Self(ctx) = self;
+        Ok(response(ctx, address)
+            .await
+            .with_internal_context(|| format!("Failed to summarize activity for {address}"))?)
+    }
+}
+
+impl RpcModule for Activity {
+    fn schema(&self) -> Module {
+        ActivityApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+async fn response(ctx: &Context, address: SuiAddress) -> Result<AddressActivity, RpcError> {
+    let (first_seen, recent, (object_counts, objects_truncated), balances) = try_join!(
+        first_transaction(ctx, address),
+        recent_transactions(ctx, address),
+        object_counts(ctx, address),
+        async {
+            all_balances(ctx, address)
+                .await
+                .context("Failed to fetch balances")
+        },
+    )?;
+
+    let (first_seen, last_seen) = try_join!(
+        activity_transaction(ctx, first_seen),
+        activity_transaction(ctx, recent.last_seen),
+    )?;
+
+    Ok(AddressActivity {
+        first_seen,
+        last_seen,
+        transactions_scanned: recent.scanned,
+        transactions_truncated: recent.truncated,
+        transaction_kinds: recent.kinds,
+        packages: recent.packages,
+        object_counts,
+        objects_truncated,
+        balances,
+    })
+}
+
+/// The sequence number of the earliest unpruned transaction that affected `address`.
+async fn first_transaction(ctx: &Context, address: SuiAddress) -> anyhow::Result<Option<i64>> {
+    use tx_affected_addresses::dsl as a;
+
+    let page: Page<JsonCursor<u64>> = Page {
+        cursor: None,
+        limit: 1,
+        descending: false,
+    };
+
+    let query = a::tx_affected_addresses
+        .select(a::tx_sequence_number)
+        .filter(a::affected.eq(address.to_inner()))
+        .into_boxed();
+
+    let rows: Vec<i64> = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?
+        .results(paginate(
+            &page,
+            "tx_affected_addresses",
+            a::tx_sequence_number,
+            query,
+        ))
+        .await
+        .context("Failed to fetch first transaction")?;
+
+    Ok(rows.first().copied())
+}
+
+/// Scan the most recent transactions that affected `address` (up to the configured limit),
+/// counting them by kind and gathering the packages they called.
+async fn recent_transactions(
+    ctx: &Context,
+    address: SuiAddress,
+) -> anyhow::Result<RecentTransactions> {
+    use tx_affected_addresses::dsl as a;
+    use tx_calls::dsl as c;
+    use tx_kinds::dsl as k;
+
+    let config = &ctx.config().activity;
+    let page: Page<JsonCursor<u64>> = Page {
+        cursor: None,
+        limit: config.max_transactions as i64,
+        descending: true,
+    };
+
+    let query = a::tx_affected_addresses
+        .select(a::tx_sequence_number)
+        .filter(a::affected.eq(address.to_inner()))
+        .into_boxed();
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let mut seqs: Vec<i64> = conn
+        .results(paginate(
+            &page,
+            "tx_affected_addresses",
+            a::tx_sequence_number,
+            query,
+        ))
+        .await
+        .context("Failed to fetch recent transactions")?;
+
+    let truncated = seqs.len() > config.max_transactions;
+    seqs.truncate(config.max_transactions);
+
+    let Some(&last_seen) = seqs.first() else {
+        return Ok(RecentTransactions {
+            last_seen: None,
+            scanned: 0,
+            truncated,
+            kinds: BTreeMap::new(),
+            packages: BTreeSet::new(),
+        });
+    };
+
+    let kinds: Vec<(StoredKind, i64)> = conn
+        .results(
+            k::tx_kinds
+                .filter(k::tx_sequence_number.eq_any(&seqs))
+                .group_by(k::tx_kind)
+                .select((k::tx_kind, count_star())),
+        )
+        .await
+        .context("Failed to count transaction kinds")?;
+
+    let packages: Vec<Vec<u8>> = conn
+        .results(
+            c::tx_calls
+                .filter(c::tx_sequence_number.eq_any(&seqs))
+                .select(c::package)
+                .distinct(),
+        )
+        .await
+        .context("Failed to fetch called packages")?;
+
+    Ok(RecentTransactions {
+        last_seen: Some(last_seen),
+        scanned: seqs.len() as u64,
+        truncated,
+        kinds: kinds
+            .into_iter()
+            .map(|(kind, count)| (format!("{kind:?}"), count as u64))
+            .collect(),
+        packages: packages
+            .into_iter()
+            .map(|bytes| ObjectID::from_bytes(bytes).context("Failed to deserialize package ID"))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Count the objects owned by `address`, by type, up to the configured limit. Types are read off
+/// the owner index's keys, so the objects themselves do not need to be loaded.
+async fn object_counts(
+    ctx: &Context,
+    address: SuiAddress,
+) -> anyhow::Result<(BTreeMap<String, u64>, bool)> {
+    let consistent_reader = ctx.consistent_reader();
+    let max_objects = ctx.config().activity.max_objects;
+    let page_size = ctx.config().objects.max_page_size;
+
+    let mut counts = BTreeMap::new();
+    let mut scanned = 0;
+    let mut after_token: Option<Vec<u8>> = None;
+
+    loop {
+        let limit = page_size.min(max_objects - scanned);
+        let page = consistent_reader
+            .list_owned_objects(
+                None, /* checkpoint */
+                OwnerKind::Address,
+                Some(address.to_string()),
+                None, /* object_type */
+                Some(limit as u32),
+                after_token.clone(),
+                None,
+                true,
+            )
+            .await
+            .context("Failed to list owned objects")?;
+
+        for edge in &page.results {
+            let key = ObjectByOwnerKey::decode(&edge.token)
+                .context("Failed to decode owned object key")?;
+
+            let type_ = key
+                .object_type()
+                .to_canonical_string(/* with_prefix */ true);
+            *counts.entry(type_).or_insert(0) += 1;
+        }
+
+        scanned += page.results.len();
+        if !page.has_next_page {
+            return Ok((counts, false));
+        } else if scanned >= max_objects {
+            return Ok((counts, true));
+        }
+
+        after_token = page.results.last().map(|edge| edge.token.clone());
+    }
+}
+
+/// Load the digest, checkpoint and timestamp of the transaction with sequence number `seq`.
+async fn activity_transaction(
+    ctx: &Context,
+    seq: Option<i64>,
+) -> anyhow::Result<Option<ActivityTransaction>> {
+    let Some(seq) = seq else {
+        return Ok(None);
+    };
+
+    let stored = ctx
+        .pg_loader()
+        .load_one(TxDigestKey(seq as u64))
+        .await
+        .context("Failed to load transaction digest")?
+        .with_context(|| format!("Missing transaction digest for transaction {seq}"))?;
+
+    let digest = TransactionDigest::try_from(stored.tx_digest.as_slice())
+        .context("Failed to deserialize transaction digest")?;
+
+    let tx = ctx
+        .kv_loader()
+        .load_one_transaction(digest)
+        .await
+        .context("Failed to load transaction")?
+        .with_context(|| format!("Transaction {digest} not found"))?;
+
+    Ok(Some(ActivityTransaction {
+        digest,
+        checkpoint: tx.cp_sequence_number(),
+        timestamp_ms: tx.timestamp_ms(),
+    }))
+}
@@ -249,38 +249,10 @@ impl CoinsApiServer for Coins {
 
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
         let Self(ctx) = self;
-        let consistent_reader = ctx.consistent_reader();
-        let config = &ctx.config().coins;
-
-        let mut all_balances = Vec::new();
-        let mut after_token: Option<Vec<u8>> = None;
-
-        loop {
-            let page = consistent_reader
-                .list_balances(
-                    None,
-                    owner.to_string(),
-                    Some(config.max_page_size as u32),
-                    after_token.clone(),
-                    None,
-                    true,
-                )
-                .await
-                .context("Failed to get all balances")
-                .map_err(RpcError::<Error>::from)?;
-
-            for edge in &page.results {
-                all_balances.push(try_from_proto(edge.value.clone())?);
-            }
-
-            if page.has_next_page {
-                after_token = page.results.last().map(|edge| edge.token.clone());
-            } else {
-                break;
-            }
-        }
-
-        Ok(all_balances)
+        Ok(all_balances(ctx, owner)
+            .await
+            .context("Failed to get all balances")
+            .map_err(RpcError::<Error>::from)?)
     }
 
     async fn get_balance(
@@ -308,7 +280,7 @@ impl CoinsApiServer for Coins {
             .context("Failed to get balance")
             .map_err(RpcError::<Error>::from)?;
 
-        Ok(try_from_proto(response)?)
+        Ok(try_from_proto(response).map_err(RpcError::<Error>::from)?)
     }
 }
 
@@ -322,7 +294,41 @@ impl RpcModule for Coins {
     }
 }
 
-fn try_from_proto(proto: ProtoBalance) -> Result<Balance, RpcError<Error>> {
+/// Fetch the balances of all coin types owned by `owner`, paging through the Consistent Store.
+pub(crate) async fn all_balances(ctx: &Context, owner: SuiAddress) -> anyhow::Result<Vec<Balance>> {
+    let consistent_reader = ctx.consistent_reader();
+    let config = &ctx.config().coins;
+
+    let mut all_balances = Vec::new();
+    let mut after_token: Option<Vec<u8>> = None;
+
+    loop {
+        let page = consistent_reader
+            .list_balances(
+                None,
+                owner.to_string(),
+                Some(config.max_page_size as u32),
+                after_token.clone(),
+                None,
+                true,
+            )
+            .await?;
+
+        for edge in &page.results {
+            all_balances.push(try_from_proto(edge.value.clone())?);
+        }
+
+        if page.has_next_page {
+            after_token = page.results.last().map(|edge| edge.token.clone());
+        } else {
+            break;
+        }
+    }
+
+    Ok(all_balances)
+}
+
+fn try_from_proto(proto: ProtoBalance) -> anyhow::Result<Balance> {
     let coin_type: TypeTag = proto
         .coin_type
         .context("coin type missing")?
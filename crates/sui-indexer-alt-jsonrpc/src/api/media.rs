@@ -0,0 +1,557 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use anyhow::bail;
+use fastcrypto::encoding::Encoding;
+use fastcrypto::encoding::Hex;
+use fastcrypto::hash::HashFunction;
+use fastcrypto::hash::Sha256;
+use futures::future;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use lru::LruCache;
+use reqwest::dns::Addrs;
+use reqwest::dns::Name;
+use reqwest::dns::Resolve;
+use reqwest::dns::Resolving;
+use reqwest::redirect;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value as Json;
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::ObjectID;
+use url::Host;
+use url::Url;
+
+use crate::api::objects::response::display;
+use crate::api::rpc_module::RpcModule;
+use crate::context::Context;
+use crate::data::load_live;
+use crate::error::InternalContext;
+use crate::error::RpcError;
+use crate::error::invalid_params;
+
+#[open_rpc(namespace = "suix", tag = "Media API")]
+#[rpc(server, namespace = "suix")]
+trait MediaApi {
+    /// Return metadata (MIME type, dimensions, size and hash) for the media referenced by an
+    /// object's Display fields. Every Display field whose value is an `http(s)://` or `ipfs://`
+    /// URL is included. Metadata is fetched once per URL and cached.
+    #[method(name = "getObjectMedia")]
+    async fn get_object_media(
+        &self,
+        /// the ID of the object whose media to describe
+        object_id: ObjectID,
+    ) -> RpcResult<Vec<MediaField>>;
+}
+
+pub(crate) struct Media {
+    ctx: Context,
+    client: reqwest::Client,
+    /// Metadata for previously fetched URLs, keyed by the URL that was fetched (after `ipfs://`
+    /// URLs have been rewritten to the gateway).
+    cache: Mutex<LruCache<String, MediaMetadata>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MediaField {
+    /// The name of the Display field.
+    pub field: String,
+
+    /// The URL from the Display field, as it appears in the Display.
+    pub url: String,
+
+    /// Metadata for the media at `url`, if it could be fetched.
+    pub metadata: Option<MediaMetadata>,
+
+    /// Why the media's metadata could not be fetched.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MediaMetadata {
+    /// The MIME type reported by the server hosting the media.
+    pub mime_type: Option<String>,
+
+    /// The width of the image, in pixels, for PNG, GIF and JPEG images.
+    pub width: Option<u32>,
+
+    /// The height of the image, in pixels, for PNG, GIF and JPEG images.
+    pub height: Option<u32>,
+
+    /// The size of the media, in bytes.
+    pub size: u64,
+
+    /// The hex-encoded SHA-256 hash of the media's contents.
+    pub sha256: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Object {0} not found")]
+    NotFound(ObjectID),
+}
+
+impl Media {
+    pub fn new(ctx: Context) -> anyhow::Result<Self> {
+        let config = &ctx.config().media;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.fetch_timeout_ms))
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(redirect::Policy::custom(|attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error(format!("Too many redirects (limit {MAX_REDIRECTS})"))
+                } else if let Err(e) = check_url(attempt.url()) {
+                    attempt.error(e)
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .context("Failed to build media HTTP client")?;
+
+        let cache_size = NonZeroUsize::new(config.cache_size).unwrap_or(NonZeroUsize::MIN);
+
+        Ok(Self {
+            ctx,
+            client,
+            cache: Mutex::new(LruCache::new(cache_size)),
+        })
+    }
+
+    /// Fetch metadata for the media at `url`, consulting the cache first.
+    async fn metadata(&self, url: &str) -> anyhow::Result<MediaMetadata> {
+        let url = resolve_url(&self.ctx.config().media.ipfs_gateway, url);
+        if let Some(metadata) = self.cache.lock().unwrap().get(&url) {
+            return Ok(metadata.clone());
+        }
+
+        let metadata = self.fetch(&url).await?;
+        self.cache.lock().unwrap().put(url, metadata.clone());
+        Ok(metadata)
+    }
+
+    /// Download the media at `url` (up to the configured size limit), hashing it and sniffing its
+    /// dimensions. Only `http(s)` URLs on public hosts are fetched: the client's resolver and
+    /// redirect policy refuse anything else, so that Display fields cannot point the server at
+    /// internal services.
+    async fn fetch(&self, url: &str) -> anyhow::Result<MediaMetadata> {
+        let max_bytes = self.ctx.config().media.max_media_bytes;
+
+        let url = Url::parse(url).context("Invalid URL")?;
+        check_url(&url)?;
+
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .context("Request failed")?
+            .error_for_status()
+            .context("Server returned an error")?;
+
+        if response
+            .content_length()
+            .is_some_and(|len| len > max_bytes as u64)
+        {
+            bail!("Media is larger than {max_bytes} bytes");
+        }
+
+        let mime_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_owned());
+
+        let mut hasher = Sha256::default();
+        let mut header = Vec::new();
+        let mut size = 0;
+
+        while let Some(chunk) = response.chunk().await.context("Failed to read media")? {
+            size += chunk.len();
+            if size > max_bytes {
+                bail!("Media is larger than {max_bytes} bytes");
+            }
+
+            hasher.update(&chunk);
+            if header.len() < HEADER_BYTES {
+                let take = chunk.len().min(HEADER_BYTES - header.len());
+                header.extend_from_slice(&chunk[..take]);
+            }
+        }
+
+        let (width, height) = match dimensions(&header) {
+            Some((w, h)) => (Some(w), Some(h)),
+            None => (None, None),
+        };
+
+        Ok(MediaMetadata {
+            mime_type,
+            width,
+            height,
+            size: size as u64,
+            sha256: Hex::encode(hasher.finalize().digest),
+        })
+    }
+}
+
+/// The number of bytes from the start of a file that are kept to detect the image's dimensions.
+/// JPEGs can place their frame header after large metadata segments, so this is generous.
+const HEADER_BYTES: usize = 64 * 1024;
+
+/// The number of redirects followed when fetching media.
+const MAX_REDIRECTS: usize = 5;
+
+/// Resolves host names for the media client, failing if any of a host's addresses is not public.
+/// Every connection the client makes, including ones for redirects, goes through this resolver,
+/// except to hosts that are IP literals, which [check_url] handles.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(resolve_public(name))
+    }
+}
+
+async fn resolve_public(name: Name) -> Result<Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let host = name.as_str();
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+
+    if addrs.is_empty() {
+        return Err(format!("{host} did not resolve to any address").into());
+    }
+
+    if let Some(addr) = addrs.iter().find(|addr| !is_public(addr.ip())) {
+        return Err(format!("{host} resolves to non-public address {}", addr.ip()).into());
+    }
+
+    Ok(Box::new(addrs.into_iter()))
+}
+
+#[async_trait::async_trait]
+impl MediaApiServer for Media {
+    async fn get_object_media(&self, object_id: ObjectID) -> RpcResult<Vec<MediaField>> {
+        Ok(self
+            .media_fields(object_id)
+            .await
+            .with_internal_context(|| format!("Failed to fetch media for object {object_id}"))?)
+    }
+}
+
+impl Media {
+    async fn media_fields(&self, object_id: ObjectID) -> Result<Vec<MediaField>, RpcError<Error>> {
+        let object = load_live(&self.ctx, object_id)
+            .await
+            .context("Failed to load latest object")?
+            .ok_or_else(|| invalid_params(Error::NotFound(object_id)))?;
+
+        let Some(fields) = display(&self.ctx, &object).await.data else {
+            return Ok(vec![]);
+        };
+
+        let urls: Vec<_> = fields
+            .into_iter()
+            .filter_map(|(field, value)| match value {
+                Json::String(url) if is_media_url(&url) => Some((field, url)),
+                _ => None,
+            })
+            .collect();
+
+        let metadata = future::join_all(urls.iter().map(|(_, url)| self.metadata(url))).await;
+
+        Ok(urls
+            .into_iter()
+            .zip(metadata)
+            .map(|((field, url), metadata)| match metadata {
+                Ok(metadata) => MediaField {
+                    field,
+                    url,
+                    metadata: Some(metadata),
+                    error: None,
+                },
+                Err(e) => MediaField {
+                    field,
+                    url,
+                    metadata: None,
+                    error: Some(format!("{e:#}")),
+                },
+            })
+            .collect())
+    }
+}
+
+impl RpcModule for Media {
+    fn schema(&self) -> Module {
+        MediaApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+fn is_media_url(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://") || value.starts_with("ipfs://")
+}
+
+/// Check that `url` can be fetched: its scheme must be `http` or `https`, and if its host is an IP
+/// address, it must be public. Host names are checked when they are resolved.
+fn check_url(url: &Url) -> anyhow::Result<()> {
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("Unsupported URL scheme {:?}", url.scheme());
+    }
+
+    let ip = match url.host() {
+        None => bail!("URL has no host"),
+        Some(Host::Domain(_)) => return Ok(()),
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+    };
+
+    if !is_public(ip) {
+        bail!("Refusing to fetch from non-public address {ip}");
+    }
+
+    Ok(())
+}
+
+/// Whether `ip` is a publicly routable address, i.e. not in a private, loopback, link-local
+/// (including cloud metadata endpoints), shared, documentation, multicast or reserved range.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => is_public_v6(ip),
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "This network", 0.0.0.0/8
+        || a == 0
+        // Shared address space (carrier-grade NAT), 100.64.0.0/10
+        || (a == 100 && (b & 0xc0) == 64)
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    // Addresses that embed an IPv4 address are as public as the address they embed.
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_v4(v4);
+    }
+
+    let segments = ip.segments();
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [_, _, _, _, _, _, hi, lo] = segments;
+        return is_public_v4(Ipv4Addr::from(((hi as u32) << 16) | lo as u32));
+    }
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // IPv4-compatible (deprecated), ::/96
+        || segments[..6] == [0; 6]
+        // Unique local, fc00::/7
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (segments[0] & 0xffc0) == 0xfe80
+        // Site-local (deprecated), fec0::/10
+        || (segments[0] & 0xffc0) == 0xfec0
+        // Documentation, 2001:db8::/32
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        // 6to4, 2002::/16, which can tunnel to any IPv4 address
+        || segments[0] == 0x2002)
+}
+
+/// Rewrite `ipfs://` URLs to go through `gateway`, leaving other URLs untouched.
+fn resolve_url(gateway: &str, url: &str) -> String {
+    match url.strip_prefix("ipfs://") {
+        Some(path) => format!(
+            "{}/{}",
+            gateway.trim_end_matches('/'),
+            path.trim_start_matches("ipfs/")
+        ),
+        None => url.to_owned(),
+    }
+}
+
+/// Detect the width and height of a PNG, GIF or JPEG image from the start of its contents.
+fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |b: &[u8]| u16::from_be_bytes([b[0], b[1]]) as u32;
+    let le16 = |b: &[u8]| u16::from_le_bytes([b[0], b[1]]) as u32;
+    let be32 = |b: &[u8]| u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") && bytes.get(12..16)? == b"IHDR" {
+        return Some((be32(bytes.get(16..20)?), be32(bytes.get(20..24)?)));
+    }
+
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some((le16(bytes.get(6..8)?), le16(bytes.get(8..10)?)));
+    }
+
+    if bytes.starts_with(&[0xFF, 0xD8]) {
+        // Walk the JPEG's segments until a Start of Frame marker, which holds the dimensions.
+        let mut i = 2;
+        loop {
+            let marker = bytes.get(i..i + 4)?;
+            if marker[0] != 0xFF {
+                return None;
+            }
+
+            let len = be16(&marker[2..4]) as usize;
+            match marker[1] {
+                0xC0..=0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => {
+                    let frame = bytes.get(i + 5..i + 9)?;
+                    return Some((be16(&frame[2..4]), be16(&frame[0..2])));
+                }
+                _ => i += 2 + len,
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&480u32.to_be_bytes());
+        assert_eq!(dimensions(&png), Some((640, 480)));
+    }
+
+    #[test]
+    fn test_gif_dimensions() {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&32u16.to_le_bytes());
+        gif.extend_from_slice(&16u16.to_le_bytes());
+        assert_eq!(dimensions(&gif), Some((32, 16)));
+    }
+
+    #[test]
+    fn test_jpeg_dimensions() {
+        let jpeg = [
+            0xFF, 0xD8, // Start of Image
+            0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // APP0 segment
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0x00, 0x02, 0x00, // SOF0, 512x256
+        ];
+        assert_eq!(dimensions(&jpeg), Some((512, 256)));
+    }
+
+    #[test]
+    fn test_truncated_or_unknown() {
+        assert_eq!(dimensions(b"\x89PNG\r\n\x1a\n"), None);
+        assert_eq!(dimensions(&[0xFF, 0xD8, 0xFF]), None);
+        assert_eq!(dimensions(b"<svg></svg>"), None);
+    }
+
+    #[test]
+    fn test_public_addresses() {
+        for ip in [
+            "8.8.8.8",
+            "1.1.1.1",
+            "2606:4700:4700::1111",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_non_public_addresses() {
+        for ip in [
+            "0.0.0.0",
+            "10.1.2.3",
+            "100.64.0.1",
+            "127.0.0.1",
+            "169.254.169.254",
+            "172.16.0.1",
+            "192.0.0.1",
+            "192.168.1.1",
+            "198.18.0.1",
+            "203.0.113.1",
+            "224.0.0.1",
+            "240.0.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "::127.0.0.1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "64:ff9b::a9fe:a9fe",
+            "2001:db8::1",
+            "2002:7f00:1::",
+            "fc00::1",
+            "fd00:ec2::254",
+            "fe80::1",
+            "ff02::1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_check_url() {
+        let ok = |url: &str| check_url(&Url::parse(url).unwrap()).is_ok();
+        assert!(ok("https://example.com/a.png"));
+        assert!(ok("http://8.8.8.8/a.png"));
+        assert!(!ok("ftp://example.com/a.png"));
+        assert!(!ok("file:///etc/passwd"));
+        assert!(!ok("http://127.0.0.1:8080/"));
+        assert!(!ok("http://169.254.169.254/latest/meta-data/"));
+        assert!(!ok("http://[::1]/"));
+        assert!(!ok("http://[::ffff:10.0.0.1]/"));
+    }
+
+    #[tokio::test]
+    async fn test_resolver_refuses_localhost() {
+        let name: Name = "localhost".parse().unwrap();
+        assert!(PublicResolver.resolve(name).await.is_err());
+    }
+
+    #[test]
+    fn test_resolve_ipfs_url() {
+        let gateway = "https://ipfs.io/ipfs/";
+        assert_eq!(
+            resolve_url(gateway, "ipfs://bafy/1.png"),
+            "https://ipfs.io/ipfs/bafy/1.png"
+        );
+        assert_eq!(
+            resolve_url(gateway, "ipfs://ipfs/bafy"),
+            "https://ipfs.io/ipfs/bafy"
+        );
+        assert_eq!(
+            resolve_url(gateway, "https://example.com/a.png"),
+            "https://example.com/a.png"
+        );
+    }
+}
@@ -9,16 +9,20 @@ use sui_types::digests::ObjectDigest;
 use sui_types::effects::IDOperation;
 use sui_types::object::Object;
 
+pub(crate) mod activity;
 pub(crate) mod checkpoints;
 pub(crate) mod coin;
 pub(crate) mod dynamic_fields;
 pub(crate) mod governance;
+pub(crate) mod media;
 pub(crate) mod move_utils;
 pub(crate) mod name_service;
 pub(crate) mod objects;
+pub(crate) mod packages;
 pub(crate) mod protocol;
 pub(crate) mod rpc_module;
 pub(crate) mod transactions;
+pub(crate) mod validators;
 pub mod write;
 
 /// Map an object change from the effects to a `SuiObjectChange` response type. Returns `None` for
@@ -179,7 +179,7 @@ async fn object_data<D: SuiData>(ctx: &Context, object: &Object) -> Result<D, Rp
 /// reason, the value is captured in the response's error field, rather than using a `Result`, so
 /// that the failure to generate a Display does not prevent the rest of the object's data from
 /// being returned.
-async fn display(ctx: &Context, object: &Object) -> DisplayFieldsResponse {
+pub(crate) async fn display(ctx: &Context, object: &Object) -> DisplayFieldsResponse {
     let fields = match display_fields(ctx, object).await {
         Ok(fields) => fields,
         Err(e) => {
@@ -0,0 +1,370 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::RangeInclusive;
+
+use anyhow::Context as _;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use sui_indexer_alt_reader::pg_reader::Connection;
+use sui_indexer_alt_schema::packages::StoredPkgDependency;
+use sui_indexer_alt_schema::schema::cp_affected_packages;
+use sui_indexer_alt_schema::schema::kv_packages;
+use sui_indexer_alt_schema::schema::pkg_dependencies;
+use sui_indexer_alt_schema::schema::watermarks;
+use sui_json_rpc_types::Page as PageResponse;
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::ObjectID;
+use sui_types::sui_serde::BigInt;
+
+use crate::api::rpc_module::RpcModule;
+use crate::context::Context;
+use crate::error::InternalContext;
+use crate::error::RpcError;
+use crate::error::invalid_params;
+use crate::paginate::BcsCursor;
+use crate::paginate::Cursor as _;
+use crate::paginate::Page;
+
+#[open_rpc(namespace = "suix", tag = "Packages API")]
+#[rpc(server, namespace = "suix")]
+trait PackagesApi {
+    /// Return the packages that depend on a package, at any version. Dependencies are read from
+    /// the linkage tables of published packages, so packages that depend on it transitively are
+    /// included.
+    #[method(name = "getDependentPackages")]
+    async fn get_dependent_packages(
+        &self,
+        /// the ID of any version of the package
+        package: ObjectID,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<DependentPackage, String>>;
+
+    /// Return the IDs of the packages whose functions were called by transactions in a
+    /// checkpoint, in no particular order.
+    #[method(name = "getCheckpointPackages")]
+    async fn get_checkpoint_packages(
+        &self,
+        /// Checkpoint sequence number.
+        seq: BigInt<u64>,
+    ) -> RpcResult<Vec<ObjectID>>;
+
+    /// Return the sequence numbers of checkpoints containing transactions that called functions
+    /// of a package at this exact version.
+    #[method(name = "getPackageActivity")]
+    async fn get_package_activity(
+        &self,
+        /// the ID of the package
+        package: ObjectID,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest record first
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<BigInt<u64>, String>>;
+}
+
+pub(crate) struct Packages(pub Context);
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DependentPackage {
+    /// The ID of the package that depends on the queried package.
+    pub package_id: ObjectID,
+
+    /// The ID of the queried package's version that the dependent package links against.
+    pub dependency_id: ObjectID,
+
+    /// The version of the queried package that the dependent package links against.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub dependency_version: u64,
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Package {0} not found")]
+    NotFound(ObjectID),
+
+    #[error("Packages for checkpoint {0} are not available")]
+    CheckpointNotAvailable(u64),
+
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
+
+type Cursor = BcsCursor<Vec<u8>>;
+type CheckpointCursor = BcsCursor<u64>;
+
+#[async_trait::async_trait]
+impl PackagesApiServer for Packages {
+    async fn get_dependent_packages(
+        &self,
+        package: ObjectID,
+        cursor: Option<String>,
+        limit: Option<usize>,
+    ) -> RpcResult<PageResponse<DependentPackage, String>> {
+        let Self(ctx) = self;
+        Ok(dependent_packages(ctx, package, cursor, limit)
+            .await
+            .with_internal_context(|| format!("Failed to fetch dependents of {package}"))?)
+    }
+
+    async fn get_checkpoint_packages(&self, seq: BigInt<u64>) -> RpcResult<Vec<ObjectID>> {
+        let Self(ctx) = self;
+        Ok(checkpoint_packages(ctx, *seq)
+            .await
+            .with_internal_context(|| format!("Failed to fetch packages for checkpoint {seq}"))?)
+    }
+
+    async fn get_package_activity(
+        &self,
+        package: ObjectID,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<BigInt<u64>, String>> {
+        let Self(ctx) = self;
+        Ok(
+            package_activity(ctx, package, cursor, limit, descending_order)
+                .await
+                .with_internal_context(|| format!("Failed to fetch activity of {package}"))?,
+        )
+    }
+}
+
+impl RpcModule for Packages {
+    fn schema(&self) -> Module {
+        PackagesApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Fetch a page of packages whose linkage tables mention any version of `package`, ordered by
+/// their IDs.
+async fn dependent_packages(
+    ctx: &Context,
+    package: ObjectID,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<PageResponse<DependentPackage, String>, RpcError<Error>> {
+    use kv_packages::dsl as p;
+    use pkg_dependencies::dsl as d;
+
+    let config = &ctx.config().packages;
+    let page: Page<Cursor> = Page::from_params::<Error>(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        None,
+    )?;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let original_ids: Vec<Vec<u8>> = conn
+        .results(
+            p::kv_packages
+                .select(p::original_id)
+                .filter(p::package_id.eq(package.to_vec()))
+                .limit(1),
+        )
+        .await
+        .context("Failed to fetch package's original ID")?;
+
+    let Some(original_id) = original_ids.into_iter().next() else {
+        return Err(invalid_params(Error::NotFound(package)));
+    };
+
+    let mut query = d::pkg_dependencies
+        .filter(d::dependency_original_id.eq(original_id))
+        .into_boxed();
+
+    if let Some(BcsCursor(after)) = &page.cursor {
+        query = query.filter(d::package_id.gt(after.clone()));
+    }
+
+    let mut rows: Vec<StoredPkgDependency> = conn
+        .results(query.order(d::package_id.asc()).limit(page.limit + 1))
+        .await
+        .context("Failed to fetch dependent packages")?;
+
+    let has_next_page = rows.len() > page.limit as usize;
+    rows.truncate(page.limit as usize);
+
+    let next_cursor = rows
+        .last()
+        .map(|last| BcsCursor(last.package_id.clone()).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let data = rows
+        .into_iter()
+        .map(|row| {
+            Ok(DependentPackage {
+                package_id: ObjectID::from_bytes(&row.package_id)
+                    .context("Failed to deserialize package ID")?,
+                dependency_id: ObjectID::from_bytes(&row.dependency_id)
+                    .context("Failed to deserialize dependency ID")?,
+                dependency_version: row.dependency_version as u64,
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// Fetch the packages called in checkpoint `seq`. Checkpoints outside the range indexed by the
+/// `cp_affected_packages` pipeline are reported as unavailable, rather than as having no packages.
+async fn checkpoint_packages(ctx: &Context, seq: u64) -> Result<Vec<ObjectID>, RpcError<Error>> {
+    use cp_affected_packages::dsl as c;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let available = indexed_range(&mut conn)
+        .await?
+        .is_some_and(|range| range.contains(&(seq as i64)));
+    if !available {
+        return Err(invalid_params(Error::CheckpointNotAvailable(seq)));
+    }
+
+    let packages: Vec<Vec<u8>> = conn
+        .results(
+            c::cp_affected_packages
+                .select(c::package_id)
+                .filter(c::cp_sequence_number.eq(seq as i64)),
+        )
+        .await
+        .context("Failed to fetch checkpoint packages")?;
+
+    Ok(packages
+        .into_iter()
+        .map(|id| ObjectID::from_bytes(&id).context("Failed to deserialize package ID"))
+        .collect::<anyhow::Result<_>>()?)
+}
+
+/// Fetch a page of the sequence numbers of checkpoints that called `package`, among the
+/// checkpoints indexed by the `cp_affected_packages` pipeline. Rows that the pruner has yet to
+/// delete, or that were committed ahead of the pipeline's watermark, are not returned.
+async fn package_activity(
+    ctx: &Context,
+    package: ObjectID,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<PageResponse<BigInt<u64>, String>, RpcError<Error>> {
+    use cp_affected_packages::dsl as c;
+
+    let config = &ctx.config().packages;
+    let page: Page<CheckpointCursor> = Page::from_params::<Error>(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        descending_order,
+    )?;
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let Some(range) = indexed_range(&mut conn).await? else {
+        return Ok(PageResponse {
+            data: vec![],
+            next_cursor: None,
+            has_next_page: false,
+        });
+    };
+
+    let mut query = c::cp_affected_packages
+        .select(c::cp_sequence_number)
+        .filter(c::package_id.eq(package.to_vec()))
+        .filter(c::cp_sequence_number.between(*range.start(), *range.end()))
+        .limit(page.limit + 1)
+        .into_boxed();
+
+    if let Some(BcsCursor(after)) = page.cursor {
+        query = if page.descending {
+            query.filter(c::cp_sequence_number.lt(after as i64))
+        } else {
+            query.filter(c::cp_sequence_number.gt(after as i64))
+        };
+    }
+
+    query = if page.descending {
+        query.order(c::cp_sequence_number.desc())
+    } else {
+        query.order(c::cp_sequence_number.asc())
+    };
+
+    let mut checkpoints: Vec<i64> = conn
+        .results(query)
+        .await
+        .context("Failed to fetch package activity")?;
+
+    let has_next_page = checkpoints.len() > page.limit as usize;
+    checkpoints.truncate(page.limit as usize);
+
+    let next_cursor = checkpoints
+        .last()
+        .map(|last| BcsCursor(*last as u64).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    Ok(PageResponse {
+        data: checkpoints
+            .into_iter()
+            .map(|cp| BigInt::from(cp as u64))
+            .collect(),
+        next_cursor,
+        has_next_page,
+    })
+}
+
+/// The range of checkpoints indexed by the `cp_affected_packages` pipeline and not yet pruned, or
+/// `None` if the pipeline has not committed any checkpoints.
+async fn indexed_range(
+    conn: &mut Connection<'_>,
+) -> Result<Option<RangeInclusive<i64>>, RpcError<Error>> {
+    use watermarks::dsl as w;
+
+    let bounds: Vec<(i64, i64)> = conn
+        .results(
+            w::watermarks
+                .select((w::reader_lo, w::checkpoint_hi_inclusive))
+                .filter(w::pipeline.eq("cp_affected_packages")),
+        )
+        .await
+        .context("Failed to fetch watermark")?;
+
+    Ok(bounds.into_iter().next().map(|(lo, hi)| lo..=hi))
+}
@@ -9,6 +9,12 @@ pub(super) enum Error {
     #[error("Cannot filter by function name {function:?} without specifying a module")]
     MissingModule { function: String },
 
+    #[error("Requested {requested} keys, exceeding maximum {max}")]
+    TooManyKeys { requested: usize, max: usize },
+
+    #[error("Duplicate transaction digest {0}")]
+    DuplicateDigest(TransactionDigest),
+
     #[error("Transaction {0} not found")]
     NotFound(TransactionDigest),
 
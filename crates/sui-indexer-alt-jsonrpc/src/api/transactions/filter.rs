@@ -338,7 +338,7 @@ async fn tx_affected_addresses(
 /// avoid scanning dead tuples due to pruning.
 ///
 /// The query fetches one more element than the limit, to determine if there is a next page.
-fn paginate<'q, TX, ST, QS>(
+pub(crate) fn paginate<'q, TX, ST, QS>(
     page: &Page<Cursor>,
     pipeline: &'static str,
     tx_sequence_number: TX,
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
 use futures::future;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
@@ -18,10 +20,11 @@ use crate::api::transactions::filter::SuiTransactionBlockResponseQuery;
 use crate::context::Context;
 use crate::error::InternalContext;
 use crate::error::RpcError;
+use crate::error::invalid_params;
 use crate::error::rpc_bail;
 
 mod error;
-mod filter;
+pub(crate) mod filter;
 mod response;
 
 #[open_rpc(namespace = "sui", tag = "Transactions API")]
@@ -36,6 +39,17 @@ trait TransactionsApi {
         /// Options controlling the output format.
         options: Option<SuiTransactionBlockResponseOptions>,
     ) -> RpcResult<SuiTransactionBlockResponse>;
+
+    /// Fetch multiple transactions by their digests, in the order they were requested. Every
+    /// transaction must exist, and the digests must be unique.
+    #[method(name = "multiGetTransactionBlocks")]
+    async fn multi_get_transaction_blocks(
+        &self,
+        /// The digests of the queried transactions.
+        digests: Vec<TransactionDigest>,
+        /// Options controlling the output format, shared by all transactions.
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>>;
 }
 
 #[open_rpc(namespace = "suix", tag = "Query Transactions API")]
@@ -84,6 +98,30 @@ impl TransactionsApiServer for Transactions {
                 .with_internal_context(|| format!("Failed to get transaction {digest}"))?,
         )
     }
+
+    async fn multi_get_transaction_blocks(
+        &self,
+        digests: Vec<TransactionDigest>,
+        options: Option<SuiTransactionBlockResponseOptions>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>> {
+        let Self(ctx) = self;
+        let config = &ctx.config().transactions;
+        if digests.len() > config.max_multi_get_transactions {
+            return Err(invalid_params(Error::TooManyKeys {
+                requested: digests.len(),
+                max: config.max_multi_get_transactions,
+            })
+            .into());
+        }
+
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = digests.iter().find(|d| !seen.insert(*d)) {
+            return Err(invalid_params(Error::DuplicateDigest(*duplicate)).into());
+        }
+
+        let options = options.unwrap_or_default();
+        Ok(transactions(ctx, digests, &options).await?)
+    }
 }
 
 #[async_trait::async_trait]
@@ -105,56 +143,7 @@ impl QueryTransactionsApiServer for QueryTransactions {
             .await?;
 
         let options = query.options.unwrap_or_default();
-
-        let tx_futures = digests.iter().map(|d| {
-            async {
-                let mut tx = response::transaction(ctx, *d, &options).await;
-
-                let config = &ctx.config().transactions;
-                let mut interval = tokio::time::interval(std::time::Duration::from_millis(
-                    config.tx_retry_interval_ms,
-                ));
-
-                let mut retries = 0;
-                for _ in 0..config.tx_retry_count {
-                    // Retry only if the error is an invalid params error, which can only be due to
-                    // the transaction not being found in the kv store or tx balance changes table.
-                    if let Err(RpcError::InvalidParams(
-                        _e @ (Error::BalanceChangesNotFound(_) | Error::NotFound(_)),
-                    )) = tx
-                    {
-                        interval.tick().await;
-                        retries += 1;
-                        tx = response::transaction(ctx, *d, &options).await;
-                        ctx.metrics()
-                            .read_retries
-                            .with_label_values(&["tx_response"])
-                            .inc();
-                    } else {
-                        break;
-                    }
-                }
-
-                ctx.metrics()
-                    .read_retries_per_request
-                    .with_label_values(&["tx_response"])
-                    .observe(retries as f64);
-                tx
-            }
-        });
-
-        let data = future::join_all(tx_futures)
-            .await
-            .into_iter()
-            .zip_debug_eq(digests)
-            .map(|(r, d)| {
-                if let Err(RpcError::InvalidParams(e @ Error::NotFound(_))) = r {
-                    rpc_bail!(e)
-                } else {
-                    r.with_internal_context(|| format!("Failed to get transaction {d}"))
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let data = transactions(ctx, digests, &options).await?;
 
         Ok(Page {
             data,
@@ -164,6 +153,63 @@ impl QueryTransactionsApiServer for QueryTransactions {
     }
 }
 
+/// Fetch responses for all `digests`, in order. The fetches run concurrently, so the data loaders
+/// batch their reads from the stores across transactions. Transactions that are not found yet are
+/// retried, in case the store they were read from is behind the others.
+async fn transactions(
+    ctx: &Context,
+    digests: Vec<TransactionDigest>,
+    options: &SuiTransactionBlockResponseOptions,
+) -> Result<Vec<SuiTransactionBlockResponse>, RpcError<Error>> {
+    let tx_futures = digests.iter().map(|d| async {
+        let mut tx = response::transaction(ctx, *d, options).await;
+
+        let config = &ctx.config().transactions;
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(
+            config.tx_retry_interval_ms,
+        ));
+
+        let mut retries = 0;
+        for _ in 0..config.tx_retry_count {
+            // Retry only if the error is an invalid params error, which can only be due to
+            // the transaction not being found in the kv store or tx balance changes table.
+            if let Err(RpcError::InvalidParams(
+                _e @ (Error::BalanceChangesNotFound(_) | Error::NotFound(_)),
+            )) = tx
+            {
+                interval.tick().await;
+                retries += 1;
+                tx = response::transaction(ctx, *d, options).await;
+                ctx.metrics()
+                    .read_retries
+                    .with_label_values(&["tx_response"])
+                    .inc();
+            } else {
+                break;
+            }
+        }
+
+        ctx.metrics()
+            .read_retries_per_request
+            .with_label_values(&["tx_response"])
+            .observe(retries as f64);
+        tx
+    });
+
+    future::join_all(tx_futures)
+        .await
+        .into_iter()
+        .zip_debug_eq(digests)
+        .map(|(r, d)| {
+            if let Err(RpcError::InvalidParams(e @ Error::NotFound(_))) = r {
+                rpc_bail!(e)
+            } else {
+                r.with_internal_context(|| format!("Failed to get transaction {d}"))
+            }
+        })
+        .collect()
+}
+
 impl RpcModule for Transactions {
     fn schema(&self) -> Module {
         TransactionsApiOpenRpc::module_doc()
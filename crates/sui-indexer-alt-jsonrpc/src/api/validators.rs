@@ -0,0 +1,242 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use sui_indexer_alt_schema::schema::kv_validator_epochs;
+use sui_indexer_alt_schema::schema::sum_validator_signatures;
+use sui_indexer_alt_schema::validators::StoredValidatorEpoch;
+use sui_indexer_alt_schema::validators::StoredValidatorSignatures;
+use sui_json_rpc_types::Page as PageResponse;
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::SuiAddress;
+use sui_types::sui_serde::BigInt;
+
+use crate::api::rpc_module::RpcModule;
+use crate::context::Context;
+use crate::error::InternalContext;
+use crate::error::RpcError;
+use crate::paginate::Cursor as _;
+use crate::paginate::JsonCursor;
+use crate::paginate::Page;
+
+#[open_rpc(namespace = "suix", tag = "Validators API")]
+#[rpc(server, namespace = "suix")]
+trait ValidatorsApi {
+    /// Return a validator's performance in each epoch it was part of the committee: the number
+    /// of checkpoints it signed, the validators that reported it under the tallying rule, and its
+    /// stake, rewards, and commission rate. Epochs are only included once they have ended.
+    #[method(name = "getValidatorEpochPerformance")]
+    async fn get_validator_epoch_performance(
+        &self,
+        /// the validator's Sui address
+        validator: SuiAddress,
+        /// optional paging cursor
+        cursor: Option<String>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest epoch first
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<ValidatorEpochPerformance, String>>;
+}
+
+pub(crate) struct Validators(pub Context);
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ValidatorEpochPerformance {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: u64,
+
+    /// The number of the epoch's checkpoints whose certificate this validator signed. `None` if
+    /// the validator's position in the committee could not be determined.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub checkpoints_signed: Option<u64>,
+
+    /// The validators that reported this validator under the tallying rule during the epoch.
+    pub tallying_rule_reporters: Vec<SuiAddress>,
+
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub tallying_rule_global_score: u64,
+
+    /// The stake in the validator's staking pool at the end of the epoch.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub stake: u64,
+
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub voting_power: u64,
+
+    /// The commission rate, in basis points.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub commission_rate: u64,
+
+    /// The staking rewards distributed to the validator's pool for the epoch.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub pool_staking_reward: u64,
+
+    /// The staking rewards from the storage fund distributed to the validator for the epoch.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub storage_fund_staking_reward: u64,
+
+    /// The gas price the validator quoted for the reference gas price survey.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub reference_gas_survey_quote: u64,
+}
+
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("Pagination issue: {0}")]
+    Pagination(#[from] crate::paginate::Error),
+}
+
+type Cursor = JsonCursor<u64>;
+
+#[async_trait::async_trait]
+impl ValidatorsApiServer for Validators {
+    async fn get_validator_epoch_performance(
+        &self,
+        validator: SuiAddress,
+        cursor: Option<String>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<PageResponse<ValidatorEpochPerformance, String>> {
+        let Self(ctx) = self;
+        Ok(
+            epoch_performance(ctx, validator, cursor, limit, descending_order)
+                .await
+                .with_internal_context(|| {
+                    format!("Failed to fetch epoch performance for validator {validator}")
+                })?,
+        )
+    }
+}
+
+impl RpcModule for Validators {
+    fn schema(&self) -> Module {
+        ValidatorsApiOpenRpc::module_doc()
+    }
+
+    fn into_impl(self) -> jsonrpsee::RpcModule<Self> {
+        self.into_rpc()
+    }
+}
+
+/// Fetch a page of `validator`'s per-epoch statistics, and combine them with the number of
+/// checkpoints it signed in each of those epochs.
+async fn epoch_performance(
+    ctx: &Context,
+    validator: SuiAddress,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    descending_order: Option<bool>,
+) -> Result<PageResponse<ValidatorEpochPerformance, String>, RpcError<Error>> {
+    use kv_validator_epochs::dsl as v;
+    use sum_validator_signatures::dsl as s;
+
+    let config = &ctx.config().validators;
+    let page: Page<Cursor> = Page::from_params::<Error>(
+        config.default_page_size,
+        config.max_page_size,
+        cursor,
+        limit,
+        descending_order,
+    )?;
+
+    let mut query = v::kv_validator_epochs
+        .filter(v::validator_address.eq(validator.to_vec()))
+        .into_boxed();
+
+    if let Some(JsonCursor(epoch)) = page.cursor {
+        query = if page.descending {
+            query.filter(v::epoch.lt(epoch as i64))
+        } else {
+            query.filter(v::epoch.gt(epoch as i64))
+        };
+    }
+
+    query = if page.descending {
+        query.order(v::epoch.desc())
+    } else {
+        query.order(v::epoch.asc())
+    };
+
+    let mut conn = ctx
+        .pg_reader()
+        .connect()
+        .await
+        .context("Failed to connect to the database")?;
+
+    let mut rows: Vec<StoredValidatorEpoch> = conn
+        .results(query.limit(page.limit + 1))
+        .await
+        .context("Failed to fetch validator epochs")?;
+
+    let has_next_page = rows.len() > page.limit as usize;
+    rows.truncate(page.limit as usize);
+
+    let epochs: Vec<i64> = rows.iter().map(|r| r.epoch).collect();
+    let signatures: Vec<StoredValidatorSignatures> = conn
+        .results(s::sum_validator_signatures.filter(s::epoch.eq_any(epochs)))
+        .await
+        .context("Failed to fetch checkpoint signature counts")?;
+
+    let signed: HashMap<(i64, i16), i64> = signatures
+        .into_iter()
+        .map(|s| ((s.epoch, s.committee_index), s.checkpoints_signed))
+        .collect();
+
+    let next_cursor = rows
+        .last()
+        .map(|last| JsonCursor(last.epoch as u64).encode())
+        .transpose()
+        .context("Failed to encode next cursor")?;
+
+    let data = rows
+        .into_iter()
+        .map(|row| {
+            let checkpoints_signed = row
+                .committee_index
+                .map(|index| signed.get(&(row.epoch, index)).copied().unwrap_or(0) as u64);
+
+            Ok(ValidatorEpochPerformance {
+                epoch: row.epoch as u64,
+                checkpoints_signed,
+                tallying_rule_reporters: bcs::from_bytes(&row.tallying_rule_reporters)
+                    .context("Failed to deserialize tallying rule reporters")?,
+                tallying_rule_global_score: row.tallying_rule_global_score as u64,
+                stake: row.stake as u64,
+                voting_power: row.voting_power as u64,
+                commission_rate: row.commission_rate as u64,
+                pool_staking_reward: row.pool_staking_reward as u64,
+                storage_fund_staking_reward: row.storage_fund_staking_reward as u64,
+                reference_gas_survey_quote: row.reference_gas_survey_quote as u64,
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(PageResponse {
+        data,
+        next_cursor,
+        has_next_page,
+    })
+}
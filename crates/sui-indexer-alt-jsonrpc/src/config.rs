@@ -26,6 +26,18 @@ pub struct RpcConfig {
     /// Configuration for coin-related RPC methods.
     pub coins: CoinsConfig,
 
+    /// Configuration for the address activity summary.
+    pub activity: ActivityConfig,
+
+    /// Configuration for package dependency and activity queries.
+    pub packages: PackagesConfig,
+
+    /// Configuration for fetching and caching metadata about media referenced by Display fields.
+    pub media: MediaConfig,
+
+    /// Configuration for historical validator performance queries.
+    pub validators: ValidatorsConfig,
+
     /// Configuration for methods that require a fullnode RPC connection,
     /// including transaction execution, dry-running, and delegation coin queries etc.
     pub node: NodeConfig,
@@ -42,6 +54,10 @@ pub struct RpcLayer {
     pub transactions: TransactionsLayer,
     pub name_service: NameServiceLayer,
     pub coins: CoinsLayer,
+    pub activity: ActivityLayer,
+    pub packages: PackagesLayer,
+    pub media: MediaLayer,
+    pub validators: ValidatorsLayer,
     pub node: NodeLayer,
     pub package_resolver: PackageResolverLayer,
 }
@@ -124,6 +140,9 @@ pub struct DynamicFieldsLayer {
 
 #[derive(Debug, Clone)]
 pub struct TransactionsConfig {
+    /// The maximum number of digests that can be queried in a single multi-get request.
+    pub max_multi_get_transactions: usize,
+
     /// The default page size limit when querying transactions, if none is provided.
     pub default_page_size: usize,
 
@@ -142,6 +161,7 @@ pub struct TransactionsConfig {
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
 #[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
 pub struct TransactionsLayer {
+    pub max_multi_get_transactions: Option<usize>,
     pub default_page_size: Option<usize>,
     pub max_page_size: Option<usize>,
     pub tx_retry_count: Option<usize>,
@@ -173,6 +193,88 @@ pub struct CoinsLayer {
     pub max_page_size: Option<usize>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ActivityConfig {
+    /// The number of an address's most recent transactions that are scanned to count transaction
+    /// kinds and find the packages it has called.
+    pub max_transactions: usize,
+
+    /// The number of an address's owned objects that are scanned to count objects by type.
+    pub max_objects: usize,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ActivityLayer {
+    pub max_transactions: Option<usize>,
+    pub max_objects: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackagesConfig {
+    /// The default page size limit when querying dependent packages or package activity, if none
+    /// is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying dependent packages or package activity.
+    /// Requesting a page larger than this is a user error.
+    pub max_page_size: usize,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PackagesLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MediaConfig {
+    /// Whether to serve media metadata. When enabled, the RPC makes outbound HTTP requests to
+    /// the URLs found in objects' Display fields.
+    pub enabled: bool,
+
+    /// The gateway that `ipfs://` URLs are rewritten to, e.g. `https://ipfs.io/ipfs/`.
+    pub ipfs_gateway: String,
+
+    /// How long to wait for a media URL to respond, in milliseconds.
+    pub fetch_timeout_ms: u64,
+
+    /// The largest media file that will be downloaded, in bytes. Metadata is not returned for
+    /// larger files.
+    pub max_media_bytes: usize,
+
+    /// The number of URLs whose metadata is cached.
+    pub cache_size: usize,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MediaLayer {
+    pub enabled: Option<bool>,
+    pub ipfs_gateway: Option<String>,
+    pub fetch_timeout_ms: Option<u64>,
+    pub max_media_bytes: Option<usize>,
+    pub cache_size: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidatorsConfig {
+    /// The default page size limit when querying a validator's epochs, if none is provided.
+    pub default_page_size: usize,
+
+    /// The largest acceptable page size when querying a validator's epochs. Requesting a page
+    /// larger than this is a user error.
+    pub max_page_size: usize,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ValidatorsLayer {
+    pub default_page_size: Option<usize>,
+    pub max_page_size: Option<usize>,
+}
+
 #[derive(Clone, Debug)]
 pub struct NodeConfig {
     /// The value of the header to be sent to the fullnode RPC, used to distinguish between different instances.
@@ -207,6 +309,10 @@ impl RpcLayer {
             transactions: TransactionsConfig::default().into(),
             name_service: NameServiceConfig::default().into(),
             coins: CoinsConfig::default().into(),
+            activity: ActivityConfig::default().into(),
+            packages: PackagesConfig::default().into(),
+            media: MediaConfig::default().into(),
+            validators: ValidatorsConfig::default().into(),
             package_resolver: PackageResolverLayer::default(),
             node: NodeConfig::default().into(),
         }
@@ -219,6 +325,10 @@ impl RpcLayer {
             transactions: self.transactions.finish(TransactionsConfig::default()),
             name_service: self.name_service.finish(NameServiceConfig::default()),
             coins: self.coins.finish(CoinsConfig::default()),
+            activity: self.activity.finish(ActivityConfig::default()),
+            packages: self.packages.finish(PackagesConfig::default()),
+            media: self.media.finish(MediaConfig::default()),
+            validators: self.validators.finish(ValidatorsConfig::default()),
             node: self.node.finish(NodeConfig::default()),
             package_resolver: self.package_resolver.finish(),
         }
@@ -268,6 +378,9 @@ impl DynamicFieldsLayer {
 impl TransactionsLayer {
     pub fn finish(self, base: TransactionsConfig) -> TransactionsConfig {
         TransactionsConfig {
+            max_multi_get_transactions: self
+                .max_multi_get_transactions
+                .unwrap_or(base.max_multi_get_transactions),
             default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
             max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
             tx_retry_count: self.tx_retry_count.unwrap_or(base.tx_retry_count),
@@ -297,6 +410,45 @@ impl CoinsLayer {
     }
 }
 
+impl ActivityLayer {
+    pub fn finish(self, base: ActivityConfig) -> ActivityConfig {
+        ActivityConfig {
+            max_transactions: self.max_transactions.unwrap_or(base.max_transactions),
+            max_objects: self.max_objects.unwrap_or(base.max_objects),
+        }
+    }
+}
+
+impl PackagesLayer {
+    pub fn finish(self, base: PackagesConfig) -> PackagesConfig {
+        PackagesConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
+impl MediaLayer {
+    pub fn finish(self, base: MediaConfig) -> MediaConfig {
+        MediaConfig {
+            enabled: self.enabled.unwrap_or(base.enabled),
+            ipfs_gateway: self.ipfs_gateway.unwrap_or(base.ipfs_gateway),
+            fetch_timeout_ms: self.fetch_timeout_ms.unwrap_or(base.fetch_timeout_ms),
+            max_media_bytes: self.max_media_bytes.unwrap_or(base.max_media_bytes),
+            cache_size: self.cache_size.unwrap_or(base.cache_size),
+        }
+    }
+}
+
+impl ValidatorsLayer {
+    pub fn finish(self, base: ValidatorsConfig) -> ValidatorsConfig {
+        ValidatorsConfig {
+            default_page_size: self.default_page_size.unwrap_or(base.default_page_size),
+            max_page_size: self.max_page_size.unwrap_or(base.max_page_size),
+        }
+    }
+}
+
 impl NodeLayer {
     pub fn finish(self, base: NodeConfig) -> NodeConfig {
         NodeConfig {
@@ -325,6 +477,10 @@ impl Default for RpcConfig {
             transactions: TransactionsConfig::default(),
             name_service: NameServiceConfig::default(),
             coins: CoinsConfig::default(),
+            activity: ActivityConfig::default(),
+            packages: PackagesConfig::default(),
+            media: MediaConfig::default(),
+            validators: ValidatorsConfig::default(),
             node: NodeConfig::default(),
             package_resolver: PackageResolverLayer::default().finish(),
         }
@@ -364,6 +520,7 @@ impl Default for DynamicFieldsConfig {
 impl Default for TransactionsConfig {
     fn default() -> Self {
         Self {
+            max_multi_get_transactions: 50,
             default_page_size: 50,
             max_page_size: 100,
             tx_retry_count: 5,
@@ -381,6 +538,45 @@ impl Default for CoinsConfig {
     }
 }
 
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        Self {
+            max_transactions: 10_000,
+            max_objects: 10_000,
+        }
+    }
+}
+
+impl Default for PackagesConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ipfs_gateway: "https://ipfs.io/ipfs/".to_owned(),
+            fetch_timeout_ms: 10_000,
+            max_media_bytes: 20 * 1024 * 1024,
+            cache_size: 10_000,
+        }
+    }
+}
+
+impl Default for ValidatorsConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
 impl Default for NodeConfig {
     fn default() -> Self {
         Self {
@@ -447,6 +643,7 @@ impl From<DynamicFieldsConfig> for DynamicFieldsLayer {
 impl From<TransactionsConfig> for TransactionsLayer {
     fn from(config: TransactionsConfig) -> Self {
         Self {
+            max_multi_get_transactions: Some(config.max_multi_get_transactions),
             default_page_size: Some(config.default_page_size),
             max_page_size: Some(config.max_page_size),
             tx_retry_count: Some(config.tx_retry_count),
@@ -474,6 +671,45 @@ impl From<CoinsConfig> for CoinsLayer {
     }
 }
 
+impl From<ActivityConfig> for ActivityLayer {
+    fn from(config: ActivityConfig) -> Self {
+        Self {
+            max_transactions: Some(config.max_transactions),
+            max_objects: Some(config.max_objects),
+        }
+    }
+}
+
+impl From<PackagesConfig> for PackagesLayer {
+    fn from(config: PackagesConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+        }
+    }
+}
+
+impl From<MediaConfig> for MediaLayer {
+    fn from(config: MediaConfig) -> Self {
+        Self {
+            enabled: Some(config.enabled),
+            ipfs_gateway: Some(config.ipfs_gateway),
+            fetch_timeout_ms: Some(config.fetch_timeout_ms),
+            max_media_bytes: Some(config.max_media_bytes),
+            cache_size: Some(config.cache_size),
+        }
+    }
+}
+
+impl From<ValidatorsConfig> for ValidatorsLayer {
+    fn from(config: ValidatorsConfig) -> Self {
+        Self {
+            default_page_size: Some(config.default_page_size),
+            max_page_size: Some(config.max_page_size),
+        }
+    }
+}
+
 impl From<NodeConfig> for NodeLayer {
     fn from(config: NodeConfig) -> Self {
         Self {
@@ -26,18 +26,22 @@ use tracing::info;
 use tracing::warn;
 use url::Url;
 
+use crate::api::activity::Activity;
 use crate::api::checkpoints::Checkpoints;
 use crate::api::coin::Coins;
 use crate::api::dynamic_fields::DynamicFields;
 use crate::api::governance::Governance;
+use crate::api::media::Media;
 use crate::api::move_utils::MoveUtils;
 use crate::api::name_service::NameService;
 use crate::api::objects::Objects;
 use crate::api::objects::QueryObjects;
+use crate::api::packages::Packages;
 use crate::api::protocol::Protocol;
 use crate::api::rpc_module::RpcModule;
 use crate::api::transactions::QueryTransactions;
 use crate::api::transactions::Transactions;
+use crate::api::validators::Validators;
 use crate::api::write::Write;
 use crate::config::RpcConfig;
 use crate::context::Context;
@@ -303,16 +307,23 @@ pub async fn start_rpc(
         context.package_resolver().package_store().clone(),
     );
 
+    rpc.add_module(Activity(context.clone()))?;
     rpc.add_module(Checkpoints(context.clone()))?;
     rpc.add_module(Coins(context.clone()))?;
     rpc.add_module(DynamicFields(context.clone()))?;
     rpc.add_module(MoveUtils(context.clone()))?;
     rpc.add_module(NameService(context.clone()))?;
     rpc.add_module(Objects(context.clone()))?;
+    rpc.add_module(Packages(context.clone()))?;
     rpc.add_module(Protocol(context.clone()))?;
     rpc.add_module(QueryObjects(context.clone()))?;
     rpc.add_module(QueryTransactions(context.clone()))?;
     rpc.add_module(Transactions(context.clone()))?;
+    rpc.add_module(Validators(context.clone()))?;
+
+    if context.config().media.enabled {
+        rpc.add_module(Media::new(context.clone())?)?;
+    }
 
     if let Some(_fullnode_client) = fullnode_client {
         rpc.add_module(Governance::new(context.clone()))?;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS pkg_dependencies;
@@ -0,0 +1,16 @@
+-- Reverse index from a package to the packages that link against it. One row
+-- is written per entry in a published package's linkage table, so transitive
+-- dependencies are included. Dependencies are keyed by original ID, so that
+-- packages depending on any version of a dependency can be found together.
+CREATE TABLE IF NOT EXISTS pkg_dependencies
+(
+    dependency_original_id      BYTEA         NOT NULL,
+    package_id                  BYTEA         NOT NULL,
+    dependency_id               BYTEA         NOT NULL,
+    dependency_version          BIGINT        NOT NULL,
+    cp_sequence_number          BIGINT        NOT NULL,
+    PRIMARY KEY (dependency_original_id, package_id)
+);
+
+CREATE INDEX IF NOT EXISTS pkg_dependencies_dependency_id
+ON pkg_dependencies (dependency_id, package_id);
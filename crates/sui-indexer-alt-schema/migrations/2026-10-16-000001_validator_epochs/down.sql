@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS sum_validator_signatures;
+DROP TABLE IF EXISTS kv_validator_epochs;
@@ -0,0 +1,32 @@
+-- Per-validator statistics for each epoch, as reported by the validator set
+-- at the end of the epoch.
+CREATE TABLE IF NOT EXISTS kv_validator_epochs
+(
+    epoch                       BIGINT        NOT NULL,
+    validator_address           BYTEA         NOT NULL,
+    -- The validator's index in the epoch's committee, if it could be
+    -- determined. Used to look up signature counts in
+    -- sum_validator_signatures.
+    committee_index             SMALLINT,
+    stake                       BIGINT        NOT NULL,
+    voting_power                BIGINT        NOT NULL,
+    commission_rate             BIGINT        NOT NULL,
+    pool_staking_reward         BIGINT        NOT NULL,
+    storage_fund_staking_reward BIGINT        NOT NULL,
+    reference_gas_survey_quote  BIGINT        NOT NULL,
+    -- BCS serialized list of the addresses of validators that reported this
+    -- validator under the tallying rule.
+    tallying_rule_reporters     BYTEA         NOT NULL,
+    tallying_rule_global_score  BIGINT        NOT NULL,
+    PRIMARY KEY (validator_address, epoch)
+);
+
+-- The number of checkpoints each member of an epoch's committee signed,
+-- accumulated as checkpoints are indexed.
+CREATE TABLE IF NOT EXISTS sum_validator_signatures
+(
+    epoch                       BIGINT        NOT NULL,
+    committee_index             SMALLINT      NOT NULL,
+    checkpoints_signed          BIGINT        NOT NULL,
+    PRIMARY KEY (epoch, committee_index)
+);
@@ -0,0 +1,14 @@
+-- Maps a checkpoint's sequence number to the packages whose functions were
+-- called by its transactions, so that activity on a package can be detected
+-- without scanning transactions. One row is written per distinct package per
+-- checkpoint. The reverse direction is served by the secondary index on
+-- `package_id`.
+CREATE TABLE IF NOT EXISTS cp_affected_packages
+(
+    cp_sequence_number BIGINT NOT NULL,
+    package_id         BYTEA  NOT NULL,
+    PRIMARY KEY (cp_sequence_number, package_id)
+);
+
+CREATE INDEX IF NOT EXISTS cp_affected_packages_package_id
+    ON cp_affected_packages (package_id, cp_sequence_number);
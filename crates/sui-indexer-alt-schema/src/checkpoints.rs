@@ -10,6 +10,7 @@ use sui_protocol_config::ProtocolVersion;
 use sui_types::digests::ChainIdentifier;
 use sui_types::digests::CheckpointDigest;
 
+use crate::schema::cp_affected_packages;
 use crate::schema::cp_digests;
 use crate::schema::kv_checkpoints;
 use crate::schema::kv_genesis;
@@ -26,6 +27,13 @@ pub struct StoredCheckpoint {
     pub validator_signatures: Vec<u8>,
 }
 
+#[derive(Insertable, Debug, Clone, FieldCount, Queryable, Selectable)]
+#[diesel(table_name = cp_affected_packages)]
+pub struct StoredCpAffectedPackage {
+    pub cp_sequence_number: i64,
+    pub package_id: Vec<u8>,
+}
+
 #[derive(Insertable, Debug, Clone, FieldCount, Queryable, Selectable)]
 #[diesel(table_name = cp_digests)]
 pub struct StoredCpDigest {
@@ -16,5 +16,6 @@ pub mod objects;
 pub mod packages;
 pub mod schema;
 pub mod transactions;
+pub mod validators;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
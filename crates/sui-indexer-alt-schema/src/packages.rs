@@ -5,6 +5,7 @@ use diesel::prelude::*;
 use sui_field_count::FieldCount;
 
 use crate::schema::kv_packages;
+use crate::schema::pkg_dependencies;
 
 #[derive(Insertable, Queryable, QueryableByName, Debug, Clone, FieldCount)]
 #[diesel(table_name = kv_packages, primary_key(package_id, package_version))]
@@ -24,3 +25,13 @@ pub struct StoredPackageOriginalId {
     pub original_id: Vec<u8>,
     pub cp_sequence_number: i64,
 }
+
+#[derive(Insertable, Queryable, Debug, Clone, FieldCount)]
+#[diesel(table_name = pkg_dependencies, primary_key(dependency_original_id, package_id))]
+pub struct StoredPkgDependency {
+    pub dependency_original_id: Vec<u8>,
+    pub package_id: Vec<u8>,
+    pub dependency_id: Vec<u8>,
+    pub dependency_version: i64,
+    pub cp_sequence_number: i64,
+}
@@ -2,6 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    cp_affected_packages (cp_sequence_number, package_id) {
+        cp_sequence_number -> Int8,
+        package_id -> Bytea,
+    }
+}
+
 diesel::table! {
     cp_bloom_blocks (cp_block_index, bloom_block_index) {
         cp_block_index -> Int8,
@@ -146,6 +153,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    kv_validator_epochs (validator_address, epoch) {
+        epoch -> Int8,
+        validator_address -> Bytea,
+        committee_index -> Nullable<Int2>,
+        stake -> Int8,
+        voting_power -> Int8,
+        commission_rate -> Int8,
+        pool_staking_reward -> Int8,
+        storage_fund_staking_reward -> Int8,
+        reference_gas_survey_quote -> Int8,
+        tallying_rule_reporters -> Bytea,
+        tallying_rule_global_score -> Int8,
+    }
+}
+
 diesel::table! {
     obj_versions (object_id, object_version) {
         object_id -> Bytea,
@@ -155,6 +178,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    pkg_dependencies (dependency_original_id, package_id) {
+        dependency_original_id -> Bytea,
+        package_id -> Bytea,
+        dependency_id -> Bytea,
+        dependency_version -> Int8,
+        cp_sequence_number -> Int8,
+    }
+}
+
 diesel::table! {
     sum_displays (object_type) {
         object_type -> Bytea,
@@ -164,6 +197,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sum_validator_signatures (epoch, committee_index) {
+        epoch -> Int8,
+        committee_index -> Int2,
+        checkpoints_signed -> Int8,
+    }
+}
+
 diesel::table! {
     tx_affected_addresses (affected, tx_sequence_number) {
         affected -> Bytea,
@@ -226,6 +267,7 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    cp_affected_packages,
     cp_bloom_blocks,
     cp_blooms,
     cp_digests,
@@ -241,8 +283,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     kv_packages,
     kv_protocol_configs,
     kv_transactions,
+    kv_validator_epochs,
     obj_versions,
+    pkg_dependencies,
     sum_displays,
+    sum_validator_signatures,
     tx_affected_addresses,
     tx_affected_objects,
     tx_balance_changes,
@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use sui_field_count::FieldCount;
+
+use crate::schema::kv_validator_epochs;
+use crate::schema::sum_validator_signatures;
+
+#[derive(Insertable, Debug, Clone, FieldCount, Queryable)]
+#[diesel(table_name = kv_validator_epochs, primary_key(validator_address, epoch))]
+pub struct StoredValidatorEpoch {
+    pub epoch: i64,
+    pub validator_address: Vec<u8>,
+    pub committee_index: Option<i16>,
+    pub stake: i64,
+    pub voting_power: i64,
+    pub commission_rate: i64,
+    pub pool_staking_reward: i64,
+    pub storage_fund_staking_reward: i64,
+    pub reference_gas_survey_quote: i64,
+    pub tallying_rule_reporters: Vec<u8>,
+    pub tallying_rule_global_score: i64,
+}
+
+#[derive(Insertable, Debug, Clone, FieldCount, Queryable)]
+#[diesel(table_name = sum_validator_signatures, primary_key(epoch, committee_index))]
+pub struct StoredValidatorSignatures {
+    pub epoch: i64,
+    pub committee_index: i16,
+    pub checkpoints_signed: i64,
+}
@@ -121,8 +121,10 @@ pub struct PrunerLayer {
 pub struct PipelineLayer {
     // Sequential pipelines
     pub sum_displays: Option<SequentialLayer>,
+    pub sum_validator_signatures: Option<SequentialLayer>,
 
     // All concurrent pipelines
+    pub cp_affected_packages: Option<ConcurrentLayer>,
     pub cp_bloom_blocks: Option<ConcurrentLayer>,
     pub cp_blooms: Option<ConcurrentLayer>,
     pub cp_digests: Option<ConcurrentLayer>,
@@ -137,7 +139,9 @@ pub struct PipelineLayer {
     pub kv_packages: Option<ConcurrentLayer>,
     pub kv_protocol_configs: Option<ConcurrentLayer>,
     pub kv_transactions: Option<ConcurrentLayer>,
+    pub kv_validator_epochs: Option<ConcurrentLayer>,
     pub obj_versions: Option<ConcurrentLayer>,
+    pub pkg_dependencies: Option<ConcurrentLayer>,
     pub tx_affected_addresses: Option<ConcurrentLayer>,
     pub tx_affected_objects: Option<ConcurrentLayer>,
     pub tx_balance_changes: Option<ConcurrentLayer>,
@@ -310,10 +314,12 @@ impl PipelineLayer {
     /// configure.
     pub fn example() -> Self {
         PipelineLayer {
+            cp_affected_packages: Some(Default::default()),
             cp_blooms: Some(Default::default()),
             cp_bloom_blocks: Some(Default::default()),
             cp_digests: Some(Default::default()),
             sum_displays: Some(Default::default()),
+            sum_validator_signatures: Some(Default::default()),
             cp_sequence_numbers: Some(Default::default()),
             ev_emit_mod: Some(Default::default()),
             ev_struct_inst: Some(Default::default()),
@@ -325,7 +331,9 @@ impl PipelineLayer {
             kv_packages: Some(Default::default()),
             kv_protocol_configs: Some(Default::default()),
             kv_transactions: Some(Default::default()),
+            kv_validator_epochs: Some(Default::default()),
             obj_versions: Some(Default::default()),
+            pkg_dependencies: Some(Default::default()),
             tx_affected_addresses: Some(Default::default()),
             tx_affected_objects: Some(Default::default()),
             tx_balance_changes: Some(Default::default()),
@@ -443,10 +451,16 @@ impl Merge for PrunerLayer {
 impl Merge for PipelineLayer {
     fn merge(self, other: PipelineLayer) -> anyhow::Result<PipelineLayer> {
         Ok(PipelineLayer {
+            cp_affected_packages: self
+                .cp_affected_packages
+                .merge(other.cp_affected_packages)?,
             cp_blooms: self.cp_blooms.merge(other.cp_blooms)?,
             cp_bloom_blocks: self.cp_bloom_blocks.merge(other.cp_bloom_blocks)?,
             cp_digests: self.cp_digests.merge(other.cp_digests)?,
             sum_displays: self.sum_displays.merge(other.sum_displays)?,
+            sum_validator_signatures: self
+                .sum_validator_signatures
+                .merge(other.sum_validator_signatures)?,
             cp_sequence_numbers: self.cp_sequence_numbers.merge(other.cp_sequence_numbers)?,
             ev_emit_mod: self.ev_emit_mod.merge(other.ev_emit_mod)?,
             ev_struct_inst: self.ev_struct_inst.merge(other.ev_struct_inst)?,
@@ -458,7 +472,9 @@ impl Merge for PipelineLayer {
             kv_packages: self.kv_packages.merge(other.kv_packages)?,
             kv_protocol_configs: self.kv_protocol_configs.merge(other.kv_protocol_configs)?,
             kv_transactions: self.kv_transactions.merge(other.kv_transactions)?,
+            kv_validator_epochs: self.kv_validator_epochs.merge(other.kv_validator_epochs)?,
             obj_versions: self.obj_versions.merge(other.obj_versions)?,
+            pkg_dependencies: self.pkg_dependencies.merge(other.pkg_dependencies)?,
             tx_affected_addresses: self
                 .tx_affected_addresses
                 .merge(other.tx_affected_addresses)?,
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::pipeline::Processor;
+use sui_indexer_alt_framework::postgres::Connection;
+use sui_indexer_alt_framework::postgres::handler::Handler;
+use sui_indexer_alt_framework::types::full_checkpoint_content::Checkpoint;
+use sui_indexer_alt_schema::checkpoints::StoredCpAffectedPackage;
+use sui_indexer_alt_schema::schema::cp_affected_packages;
+use sui_types::transaction::TransactionDataAPI;
+
+pub(crate) struct CpAffectedPackages;
+
+#[async_trait]
+impl Processor for CpAffectedPackages {
+    const NAME: &'static str = "cp_affected_packages";
+
+    type Value = StoredCpAffectedPackage;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let cp_sequence_number = checkpoint.summary.sequence_number as i64;
+
+        let packages: BTreeSet<_> = checkpoint
+            .transactions
+            .iter()
+            .flat_map(|tx| tx.transaction.move_calls())
+            .map(|(_cmd_idx, package, _module, _function)| *package)
+            .collect();
+
+        Ok(packages
+            .into_iter()
+            .map(|package| StoredCpAffectedPackage {
+                cp_sequence_number,
+                package_id: package.to_vec(),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Handler for CpAffectedPackages {
+    const MIN_EAGER_ROWS: usize = 100;
+    const MAX_PENDING_ROWS: usize = 10000;
+
+    async fn commit<'a>(values: &[Self::Value], conn: &mut Connection<'a>) -> Result<usize> {
+        Ok(diesel::insert_into(cp_affected_packages::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?)
+    }
+
+    async fn prune<'a>(
+        &self,
+        from: u64,
+        to_exclusive: u64,
+        conn: &mut Connection<'a>,
+    ) -> Result<usize> {
+        let filter = cp_affected_packages::table.filter(
+            cp_affected_packages::cp_sequence_number.between(from as i64, to_exclusive as i64 - 1),
+        );
+
+        Ok(diesel::delete(filter).execute(conn).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::RunQueryDsl;
+    use sui_indexer_alt_framework::Indexer;
+    use sui_indexer_alt_framework::types::base_types::ObjectID;
+    use sui_indexer_alt_framework::types::test_checkpoint_data_builder::TestCheckpointBuilder;
+    use sui_indexer_alt_schema::MIGRATIONS;
+
+    use super::*;
+
+    async fn get_all_cp_affected_packages(
+        conn: &mut Connection<'_>,
+    ) -> Result<Vec<StoredCpAffectedPackage>> {
+        let query = cp_affected_packages::table
+            .order_by((
+                cp_affected_packages::cp_sequence_number,
+                cp_affected_packages::package_id,
+            ))
+            .load(conn)
+            .await?;
+        Ok(query)
+    }
+
+    /// Verify that each package called in a checkpoint is recorded once, no matter how many
+    /// transactions called it, and that the pruner removes entries by sequence-number range.
+    #[tokio::test]
+    async fn test_cp_affected_packages_pruning() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.store().connect().await.unwrap();
+
+        let pkg_a = ObjectID::from_single_byte(0xa);
+        let pkg_b = ObjectID::from_single_byte(0xb);
+
+        // Checkpoint 0 calls both packages, twice for one of them.
+        let mut builder = TestCheckpointBuilder::new(0)
+            .start_transaction(0)
+            .add_move_call(pkg_a, "module", "function")
+            .finish_transaction()
+            .start_transaction(1)
+            .add_move_call(pkg_a, "module", "function")
+            .add_move_call(pkg_b, "module", "function")
+            .finish_transaction();
+        let checkpoint = Arc::new(builder.build_checkpoint());
+        let values = CpAffectedPackages.process(&checkpoint).await.unwrap();
+        assert_eq!(values.len(), 2);
+        CpAffectedPackages::commit(&values, &mut conn)
+            .await
+            .unwrap();
+
+        // Checkpoints 1 and 2 only call one package each.
+        for pkg in [pkg_a, pkg_b] {
+            builder = builder
+                .start_transaction(0)
+                .add_move_call(pkg, "module", "function")
+                .finish_transaction();
+            let checkpoint = Arc::new(builder.build_checkpoint());
+            let values = CpAffectedPackages.process(&checkpoint).await.unwrap();
+            CpAffectedPackages::commit(&values, &mut conn)
+                .await
+                .unwrap();
+        }
+
+        // Prune checkpoints from `[0, 2)`.
+        let rows_pruned = CpAffectedPackages.prune(0, 2, &mut conn).await.unwrap();
+        assert_eq!(rows_pruned, 3);
+
+        // Only the entry for checkpoint 2 should remain.
+        let remaining = get_all_cp_affected_packages(&mut conn).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].cp_sequence_number, 2);
+        assert_eq!(remaining[0].package_id, pkg_b.to_vec());
+    }
+}
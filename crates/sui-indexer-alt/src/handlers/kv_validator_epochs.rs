@@ -0,0 +1,119 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use async_trait::async_trait;
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::pipeline::Processor;
+use sui_indexer_alt_framework::postgres::Connection;
+use sui_indexer_alt_framework::postgres::handler::Handler;
+use sui_indexer_alt_framework::types::base_types::SuiAddress;
+use sui_indexer_alt_framework::types::crypto::AuthorityPublicKeyBytes;
+use sui_indexer_alt_framework::types::crypto::ToFromBytes;
+use sui_indexer_alt_framework::types::event::ValidatorEpochInfoEventV2;
+use sui_indexer_alt_framework::types::full_checkpoint_content::Checkpoint;
+use sui_indexer_alt_framework::types::sui_system_state::SuiSystemStateTrait;
+use sui_indexer_alt_framework::types::sui_system_state::get_sui_system_state;
+use sui_indexer_alt_framework::types::transaction::TransactionKind;
+use sui_indexer_alt_schema::schema::kv_validator_epochs;
+use sui_indexer_alt_schema::validators::StoredValidatorEpoch;
+use sui_types::transaction::TransactionDataAPI;
+
+pub(crate) struct KvValidatorEpochs;
+
+#[async_trait]
+impl Processor for KvValidatorEpochs {
+    const NAME: &'static str = "kv_validator_epochs";
+
+    type Value = StoredValidatorEpoch;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let Checkpoint {
+            summary,
+            transactions,
+            ..
+        } = checkpoint.as_ref();
+
+        if !summary.is_last_checkpoint_of_epoch() {
+            return Ok(vec![]);
+        }
+
+        let Some(transaction) = transactions.iter().find(|tx| {
+            matches!(
+                tx.transaction.kind(),
+                TransactionKind::ChangeEpoch(_) | TransactionKind::EndOfEpochTransaction(_)
+            )
+        }) else {
+            bail!(
+                "Failed to get end of epoch transaction in checkpoint {} with EndOfEpochData",
+                summary.sequence_number,
+            );
+        };
+
+        // The system state that the end of epoch transaction reads holds the committee for the
+        // epoch that is ending, which is needed to map validators to the indices used in
+        // checkpoint signatures.
+        let input_objects: Vec<_> = transaction
+            .input_objects(&checkpoint.object_set)
+            .cloned()
+            .collect();
+
+        let system_state = get_sui_system_state(&input_objects.as_slice())
+            .context("Failed to find system state object input to end of epoch transaction")?;
+
+        let committee = system_state.get_current_epoch_committee();
+        let committee_indices: HashMap<SuiAddress, i16> = system_state
+            .into_sui_system_state_summary()
+            .active_validators
+            .into_iter()
+            .filter_map(|v| {
+                let name = AuthorityPublicKeyBytes::from_bytes(&v.protocol_pubkey_bytes).ok()?;
+                let index = committee.committee().authority_index(&name)?;
+                Some((v.sui_address, index as i16))
+            })
+            .collect();
+
+        let mut values = vec![];
+        for event in transaction.events.iter().flat_map(|events| &events.data) {
+            if !event.is_validator_epoch_info_event() {
+                continue;
+            }
+
+            let info: ValidatorEpochInfoEventV2 = bcs::from_bytes(&event.contents)
+                .context("Failed to deserialize ValidatorEpochInfoEventV2")?;
+
+            values.push(StoredValidatorEpoch {
+                epoch: info.epoch as i64,
+                validator_address: info.validator_address.to_vec(),
+                committee_index: committee_indices.get(&info.validator_address).copied(),
+                stake: info.stake as i64,
+                voting_power: info.voting_power as i64,
+                commission_rate: info.commission_rate as i64,
+                pool_staking_reward: info.pool_staking_reward as i64,
+                storage_fund_staking_reward: info.storage_fund_staking_reward as i64,
+                reference_gas_survey_quote: info.reference_gas_survey_quote as i64,
+                tallying_rule_reporters: bcs::to_bytes(&info.tallying_rule_reporters)
+                    .context("Failed to serialize tallying rule reporters")?,
+                tallying_rule_global_score: info.tallying_rule_global_score as i64,
+            });
+        }
+
+        Ok(values)
+    }
+}
+
+#[async_trait]
+impl Handler for KvValidatorEpochs {
+    async fn commit<'a>(values: &[Self::Value], conn: &mut Connection<'a>) -> Result<usize> {
+        Ok(diesel::insert_into(kv_validator_epochs::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?)
+    }
+}
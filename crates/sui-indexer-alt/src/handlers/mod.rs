@@ -5,6 +5,7 @@ use sui_indexer_alt_framework::types::base_types::SuiAddress;
 use sui_indexer_alt_framework::types::effects::TransactionEffects;
 use sui_indexer_alt_framework::types::object::Owner;
 
+pub(crate) mod cp_affected_packages;
 pub(crate) mod cp_bloom_blocks;
 pub(crate) mod cp_blooms;
 pub(crate) mod cp_digests;
@@ -19,8 +20,11 @@ pub(crate) mod kv_objects;
 pub(crate) mod kv_packages;
 pub(crate) mod kv_protocol_configs;
 pub(crate) mod kv_transactions;
+pub(crate) mod kv_validator_epochs;
 pub(crate) mod obj_versions;
+pub(crate) mod pkg_dependencies;
 pub(crate) mod sum_displays;
+pub(crate) mod sum_validator_signatures;
 pub(crate) mod tx_affected_addresses;
 pub(crate) mod tx_affected_objects;
 pub(crate) mod tx_balance_changes;
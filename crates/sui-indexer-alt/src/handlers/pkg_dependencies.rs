@@ -0,0 +1,69 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::pipeline::Processor;
+use sui_indexer_alt_framework::postgres::Connection;
+use sui_indexer_alt_framework::postgres::handler::Handler;
+use sui_indexer_alt_framework::types::full_checkpoint_content::Checkpoint;
+use sui_indexer_alt_schema::packages::StoredPkgDependency;
+use sui_indexer_alt_schema::schema::pkg_dependencies;
+
+/// Indexes the linkage table of every published package, so that the packages depending on a
+/// given package (at any version) can be looked up.
+pub(crate) struct PkgDependencies;
+
+#[async_trait]
+impl Processor for PkgDependencies {
+    const NAME: &'static str = "pkg_dependencies";
+
+    type Value = StoredPkgDependency;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let Checkpoint {
+            summary,
+            transactions,
+            ..
+        } = checkpoint.as_ref();
+
+        let cp_sequence_number = summary.sequence_number as i64;
+        let mut values = vec![];
+        for tx in transactions {
+            for obj in tx.output_objects(&checkpoint.object_set) {
+                let Some(package) = obj.data.try_as_package() else {
+                    continue;
+                };
+
+                for (original_id, upgrade_info) in package.linkage_table() {
+                    values.push(StoredPkgDependency {
+                        dependency_original_id: original_id.to_vec(),
+                        package_id: obj.id().to_vec(),
+                        dependency_id: upgrade_info.upgraded_id.to_vec(),
+                        dependency_version: upgrade_info.upgraded_version.value() as i64,
+                        cp_sequence_number,
+                    });
+                }
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+#[async_trait]
+impl Handler for PkgDependencies {
+    const MIN_EAGER_ROWS: usize = 100;
+    const MAX_PENDING_ROWS: usize = 10000;
+
+    async fn commit<'a>(values: &[Self::Value], conn: &mut Connection<'a>) -> Result<usize> {
+        Ok(diesel::insert_into(pkg_dependencies::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?)
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::ExpressionMethods;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use sui_indexer_alt_framework::FieldCount;
+use sui_indexer_alt_framework::pipeline::Processor;
+use sui_indexer_alt_framework::pipeline::sequential::Handler;
+use sui_indexer_alt_framework::postgres::Connection;
+use sui_indexer_alt_framework::postgres::Db;
+use sui_indexer_alt_framework::types::full_checkpoint_content::Checkpoint;
+use sui_indexer_alt_schema::schema::sum_validator_signatures;
+use sui_indexer_alt_schema::validators::StoredValidatorSignatures;
+
+const MAX_INSERT_CHUNK_ROWS: usize = i16::MAX as usize / StoredValidatorSignatures::FIELD_COUNT;
+
+/// Counts the checkpoints signed by each member of an epoch's committee. Counts are accumulated
+/// into existing rows, which relies on the sequential pipeline committing each checkpoint exactly
+/// once.
+pub(crate) struct SumValidatorSignatures;
+
+#[async_trait]
+impl Processor for SumValidatorSignatures {
+    const NAME: &'static str = "sum_validator_signatures";
+
+    type Value = StoredValidatorSignatures;
+
+    async fn process(&self, checkpoint: &Arc<Checkpoint>) -> Result<Vec<Self::Value>> {
+        let Checkpoint { summary, .. } = checkpoint.as_ref();
+
+        let epoch = summary.epoch as i64;
+        Ok(summary
+            .auth_sig()
+            .signers_map
+            .iter()
+            .map(|index| StoredValidatorSignatures {
+                epoch,
+                committee_index: index as i16,
+                checkpoints_signed: 1,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Handler for SumValidatorSignatures {
+    type Store = Db;
+    type Batch = BTreeMap<(i64, i16), Self::Value>;
+
+    fn batch(&self, batch: &mut Self::Batch, values: std::vec::IntoIter<Self::Value>) {
+        for value in values {
+            batch
+                .entry((value.epoch, value.committee_index))
+                .and_modify(|v| v.checkpoints_signed += value.checkpoints_signed)
+                .or_insert(value);
+        }
+    }
+
+    async fn commit<'a>(&self, batch: &Self::Batch, conn: &mut Connection<'a>) -> Result<usize> {
+        use sum_validator_signatures::dsl as s;
+
+        let values: Vec<_> = batch.values().cloned().collect();
+        let mut updates = 0;
+        for chunk in values.chunks(MAX_INSERT_CHUNK_ROWS) {
+            updates += diesel::insert_into(s::sum_validator_signatures)
+                .values(chunk)
+                .on_conflict((s::epoch, s::committee_index))
+                .do_update()
+                .set(
+                    s::checkpoints_signed
+                        .eq(s::checkpoints_signed + excluded(s::checkpoints_signed)),
+                )
+                .execute(conn)
+                .await?;
+        }
+
+        Ok(updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::QueryDsl;
+    use sui_indexer_alt_framework::Indexer;
+    use sui_indexer_alt_framework::types::test_checkpoint_data_builder::TestCheckpointBuilder;
+    use sui_indexer_alt_schema::MIGRATIONS;
+
+    use super::*;
+
+    async fn get_all_signatures(conn: &mut Connection<'_>) -> Result<Vec<(i64, i16, i64)>> {
+        use sum_validator_signatures::dsl as s;
+
+        Ok(s::sum_validator_signatures
+            .select((s::epoch, s::committee_index, s::checkpoints_signed))
+            .order_by((s::epoch, s::committee_index))
+            .load(conn)
+            .await?)
+    }
+
+    /// Checkpoints from the test builder are signed by all four members of its committee, so
+    /// every member's count goes up by one per checkpoint, whether the checkpoints are batched
+    /// together or committed separately.
+    #[tokio::test]
+    async fn test_signatures_accumulate() {
+        let (indexer, _db) = Indexer::new_for_testing(&MIGRATIONS).await;
+        let mut conn = indexer.store().connect().await.unwrap();
+        let mut builder = TestCheckpointBuilder::new(0);
+
+        let mut batch = BTreeMap::new();
+        for _ in 0..2 {
+            let checkpoint = Arc::new(builder.build_checkpoint());
+            let values = SumValidatorSignatures.process(&checkpoint).await.unwrap();
+            assert_eq!(values.len(), 4);
+            SumValidatorSignatures.batch(&mut batch, values.into_iter());
+        }
+
+        assert_eq!(batch.len(), 4);
+        SumValidatorSignatures
+            .commit(&batch, &mut conn)
+            .await
+            .unwrap();
+
+        let checkpoint = Arc::new(builder.build_checkpoint());
+        let values = SumValidatorSignatures.process(&checkpoint).await.unwrap();
+        let mut batch = BTreeMap::new();
+        SumValidatorSignatures.batch(&mut batch, values.into_iter());
+        SumValidatorSignatures
+            .commit(&batch, &mut conn)
+            .await
+            .unwrap();
+
+        let signatures = get_all_signatures(&mut conn).await.unwrap();
+        assert_eq!(signatures, vec![(0, 0, 3), (0, 1, 3), (0, 2, 3), (0, 3, 3)],);
+    }
+}
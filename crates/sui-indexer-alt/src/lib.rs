@@ -20,6 +20,7 @@ use url::Url;
 use crate::bootstrap::bootstrap;
 use crate::config::IndexerConfig;
 use crate::config::PipelineLayer;
+use crate::handlers::cp_affected_packages::CpAffectedPackages;
 use crate::handlers::cp_bloom_blocks::CpBloomBlocks;
 use crate::handlers::cp_blooms::CpBlooms;
 use crate::handlers::cp_digests::CpDigests;
@@ -34,8 +35,11 @@ use crate::handlers::kv_objects::KvObjects;
 use crate::handlers::kv_packages::KvPackages;
 use crate::handlers::kv_protocol_configs::KvProtocolConfigs;
 use crate::handlers::kv_transactions::KvTransactions;
+use crate::handlers::kv_validator_epochs::KvValidatorEpochs;
 use crate::handlers::obj_versions::ObjVersions;
+use crate::handlers::pkg_dependencies::PkgDependencies;
 use crate::handlers::sum_displays::SumDisplays;
+use crate::handlers::sum_validator_signatures::SumValidatorSignatures;
 use crate::handlers::tx_affected_addresses::TxAffectedAddresses;
 use crate::handlers::tx_affected_objects::TxAffectedObjects;
 use crate::handlers::tx_balance_changes::TxBalanceChanges;
@@ -70,6 +74,8 @@ pub async fn setup_indexer(
 
     let PipelineLayer {
         sum_displays,
+        sum_validator_signatures,
+        cp_affected_packages,
         cp_blooms,
         cp_bloom_blocks,
         cp_digests,
@@ -84,7 +90,9 @@ pub async fn setup_indexer(
         kv_packages,
         kv_protocol_configs,
         kv_transactions,
+        kv_validator_epochs,
         obj_versions,
+        pkg_dependencies,
         tx_affected_addresses,
         tx_affected_objects,
         tx_balance_changes,
@@ -179,7 +187,9 @@ pub async fn setup_indexer(
 
     // Summary tables (without write-ahead log)
     add_sequential!(SumDisplays, sum_displays);
+    add_sequential!(SumValidatorSignatures, sum_validator_signatures);
 
+    add_concurrent!(CpAffectedPackages, cp_affected_packages);
     add_concurrent!(CpBlooms, cp_blooms);
     add_concurrent!(CpBloomBlocks, cp_bloom_blocks);
     add_concurrent!(CpDigests, cp_digests);
@@ -192,7 +202,9 @@ pub async fn setup_indexer(
     add_concurrent!(KvObjects, kv_objects);
     add_concurrent!(KvPackages, kv_packages);
     add_concurrent!(KvTransactions, kv_transactions);
+    add_concurrent!(KvValidatorEpochs, kv_validator_epochs);
     add_concurrent!(ObjVersions, obj_versions);
+    add_concurrent!(PkgDependencies, pkg_dependencies);
     add_concurrent!(TxAffectedAddresses, tx_affected_addresses);
     add_concurrent!(TxAffectedObjects, tx_affected_objects);
     add_concurrent!(TxBalanceChanges, tx_balance_changes);
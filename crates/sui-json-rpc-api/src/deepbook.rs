@@ -4,11 +4,26 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
+use sui_json_rpc_types::DeepBookOrderBook;
 use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::{ObjectID, SequenceNumber};
 
 #[open_rpc(namespace = "suix", tag = "DeepBook Read API")]
 #[rpc(server, client, namespace = "suix")]
 pub trait DeepBookApi {
     #[method(name = "ping")]
     async fn ping(&self) -> RpcResult<String>;
+
+    /// Return a snapshot of a DeepBook pool's order book, aggregated by price level.
+    #[method(name = "getDeepBookOrderBook")]
+    async fn get_order_book(
+        &self,
+        /// the ID of the DeepBook pool
+        pool_id: ObjectID,
+        /// maximum number of price levels to return on each side, default to 20 if not specified
+        depth: Option<usize>,
+        /// version of the pool to read, as returned by a previous snapshot; defaults to the
+        /// latest version
+        pool_version: Option<SequenceNumber>,
+    ) -> RpcResult<DeepBookOrderBook>;
 }
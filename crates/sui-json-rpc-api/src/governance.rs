@@ -4,7 +4,9 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
-use sui_json_rpc_types::{DelegatedStake, SuiCommittee, ValidatorApys};
+use sui_json_rpc_types::{
+    CheckpointCongestionGasPrices, DelegatedStake, SuiCommittee, ValidatorApys,
+};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::sui_serde::BigInt;
@@ -40,6 +42,18 @@ pub trait GovernanceReadApi {
     #[method(name = "getReferenceGasPrice")]
     async fn get_reference_gas_price(&self) -> RpcResult<BigInt<u64>>;
 
+    /// Return, for the most recent checkpoints in which consensus objects were congested, the gas
+    /// prices of the transactions that were sequenced and of those that were cancelled for each
+    /// congested object, newest first. Only available on fullnodes.
+    #[method(name = "getCongestionGasPrices")]
+    async fn get_congestion_gas_prices(
+        &self,
+        /// Only report these objects. If None, report every congested object
+        object_ids: Option<Vec<ObjectID>>,
+        /// Maximum number of checkpoints to return, default to 20
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<CheckpointCongestionGasPrices>>;
+
     /// Return the validator APY
     #[method(name = "getValidatorsApy")]
     async fn get_validators_apy(&self) -> RpcResult<ValidatorApys>;
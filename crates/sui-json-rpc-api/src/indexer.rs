@@ -6,9 +6,9 @@ use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::SuiTransactionBlockEffects;
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiEvent, SuiObjectResponse,
-    SuiObjectResponseQuery, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
-    TransactionFilter,
+    CheckpointObjectChanges, DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiEvent,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponseQuery,
+    TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -77,6 +77,15 @@ pub trait IndexerApi {
     #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
     fn subscribe_transaction(&self, filter: TransactionFilter) -> SubscriptionResult;
 
+    /// Subscribe to the net change to objects in each checkpoint, starting from the next
+    /// checkpoint the node executes. The subscription is closed if the client falls too far
+    /// behind, after which it should resume from the last checkpoint it received.
+    #[subscription(
+        name = "subscribeCheckpointObjectChanges",
+        item = CheckpointObjectChanges
+    )]
+    fn subscribe_checkpoint_object_changes(&self) -> SubscriptionResult;
+
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
     async fn get_dynamic_fields(
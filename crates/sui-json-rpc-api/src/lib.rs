@@ -8,6 +8,9 @@ pub use bridge::BridgeReadApiServer;
 pub use coin::CoinReadApiClient;
 pub use coin::CoinReadApiOpenRpc;
 pub use coin::CoinReadApiServer;
+pub use deepbook::DeepBookApiClient;
+pub use deepbook::DeepBookApiOpenRpc;
+pub use deepbook::DeepBookApiServer;
 pub use extended::ExtendedApiClient;
 pub use extended::ExtendedApiOpenRpc;
 pub use extended::ExtendedApiServer;
@@ -24,9 +27,15 @@ use once_cell::sync::Lazy;
 use prometheus::Histogram;
 use prometheus::register_histogram_with_registry;
 use prometheus::{IntCounter, register_int_counter_with_registry};
+pub use proof::ProofApiClient;
+pub use proof::ProofApiOpenRpc;
+pub use proof::ProofApiServer;
 pub use read::ReadApiClient;
 pub use read::ReadApiOpenRpc;
 pub use read::ReadApiServer;
+pub use source_verification::SourceVerificationApiClient;
+pub use source_verification::SourceVerificationApiOpenRpc;
+pub use source_verification::SourceVerificationApiServer;
 use tap::TapFallible;
 use tracing::warn;
 pub use transaction_builder::TransactionBuilderClient;
@@ -38,11 +47,14 @@ pub use write::WriteApiServer;
 
 mod bridge;
 mod coin;
+mod deepbook;
 mod extended;
 mod governance;
 mod indexer;
 mod move_utils;
+mod proof;
 mod read;
+mod source_verification;
 mod transaction_builder;
 mod write;
 
@@ -344,6 +356,9 @@ pub const CLIENT_SDK_VERSION_HEADER: &str = "client-sdk-version";
 /// The RPC API version that the client is targeting. Different SDK versions may target the same
 /// API version.
 pub const CLIENT_TARGET_API_VERSION_HEADER: &str = "client-target-api-version";
+/// An ID chosen by the client to correlate its requests with the node's logs. It is recorded on
+/// the node's tracing spans for the request, and echoed in the response.
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
 
 pub const TRANSIENT_ERROR_CODE: i32 = -32050;
 pub const TRANSACTION_EXECUTION_CLIENT_ERROR_CODE: i32 = -32002;
@@ -8,7 +8,7 @@ use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
     MoveFunctionArgType, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
-    SuiMoveNormalizedStruct,
+    SuiMoveNormalizedStruct, SuiMoveStructLayouts,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::ObjectID;
@@ -58,4 +58,10 @@ pub trait MoveUtils {
         module_name: String,
         function_name: String,
     ) -> RpcResult<SuiMoveNormalizedFunction>;
+
+    /// Return the BCS layouts of the types declared by the given package and of the
+    /// struct types its entry functions accept, including their dependencies, so
+    /// that its objects and events can be decoded without the package's source
+    #[method(name = "getMoveStructLayouts")]
+    async fn get_move_struct_layouts(&self, package: ObjectID) -> RpcResult<SuiMoveStructLayouts>;
 }
@@ -0,0 +1,36 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+
+use sui_json_rpc_types::{SuiFinalityCertificate, SuiTransactionProof};
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::TransactionDigest;
+
+#[open_rpc(namespace = "sui", tag = "Proof API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait ProofApi {
+    /// Return the data needed to prove that a transaction, its effects and its events were
+    /// included in a certified checkpoint. The response can be checked with the light client
+    /// library against a trusted committee, so the node serving it does not need to be trusted.
+    /// Proofs of object state at a checkpoint are served by the gRPC
+    /// `ProofService.GetCheckpointObjectProof` method.
+    #[method(name = "getTransactionProof")]
+    async fn get_transaction_proof(
+        &self,
+        /// the digest of the executed transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiTransactionProof>;
+
+    /// Return the finality certificate of a transaction: the certified checkpoint summary that
+    /// includes it, with the committee's signature, and the checkpoint contents proving its
+    /// inclusion. Clients that must keep evidence of finality, such as custodians and bridges, can
+    /// store the certificate and check it later against the committee of its epoch.
+    #[method(name = "getTransactionFinalityCertificate")]
+    async fn get_transaction_finality_certificate(
+        &self,
+        /// the digest of the executed transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiFinalityCertificate>;
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+
+use sui_json_rpc_types::{
+    SuiMoveSourceLocation, SuiResolvedMoveAbort, SuiSourceAttestation, SuiSourceVerificationStatus,
+};
+use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::ObjectID;
+use sui_types::sui_serde::BigInt;
+
+#[open_rpc(namespace = "sui", tag = "Source Verification API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait SourceVerificationApi {
+    /// Submit the source of a published package for verification. The source is rebuilt and
+    /// compared against the on-chain bytecode; if it matches, an attestation is recorded and
+    /// returned. Only callers configured as submitters on the node can submit sources, by sending
+    /// their token as `Authorization: Bearer <token>`.
+    #[method(name = "submitPackageSource", with_extensions)]
+    async fn submit_package_source(
+        &self,
+        /// the ID of the on-chain package
+        package_id: ObjectID,
+        /// source files of the package, keyed by path relative to the package root, including
+        /// `Move.toml` and `Move.lock`
+        files: BTreeMap<String, String>,
+    ) -> RpcResult<SuiSourceAttestation>;
+
+    /// Return whether the source of a package has been verified by this node.
+    #[method(name = "isSourceVerified")]
+    async fn is_source_verified(
+        &self,
+        package_id: ObjectID,
+    ) -> RpcResult<SuiSourceVerificationStatus>;
+
+    /// Return the source location of an instruction in a verified package, as reported in the
+    /// `MoveLocation` of a Move abort. Returns null if the package's source is not verified.
+    #[method(name = "getMoveAbortSourceLocation")]
+    async fn get_move_abort_source_location(
+        &self,
+        package_id: ObjectID,
+        module: String,
+        /// the function definition index
+        function: u16,
+        /// the bytecode offset within the function
+        instruction: u16,
+    ) -> RpcResult<Option<SuiMoveSourceLocation>>;
+
+    /// Resolve the code of a Move abort in a verified package to the error constant it was
+    /// raised with, using the error-code manifest generated when the package's source was
+    /// verified. Returns null if the package's source is not verified or it has no such module.
+    #[method(name = "resolveMoveAbort")]
+    async fn resolve_move_abort(
+        &self,
+        /// the ID of the package containing the aborting module
+        package_id: ObjectID,
+        module: String,
+        abort_code: BigInt<u64>,
+    ) -> RpcResult<Option<SuiResolvedMoveAbort>>;
+}
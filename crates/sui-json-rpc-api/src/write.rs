@@ -6,11 +6,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiObjectReservation,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc_macros::open_rpc;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::sui_serde::BigInt;
 use sui_types::transaction_driver_types::ExecuteTransactionRequestType;
 
@@ -40,6 +40,45 @@ pub trait WriteApi {
         request_type: Option<ExecuteTransactionRequestType>,
     ) -> RpcResult<SuiTransactionBlockResponse>;
 
+    /// Execute a soft bundle: a small, ordered list of transactions that are submitted together,
+    /// so that a validator can sequence them adjacently and in order. Ordering is best effort, and
+    /// requires all transactions in the bundle to use the same gas price.
+    /// Each transaction is finalized independently, and one failing does not affect the others.
+    /// Returns a response per transaction, in request order, with `errors` set for transactions
+    /// that failed to finalize.
+    #[method(name = "executeSoftBundle")]
+    async fn execute_soft_bundle(
+        &self,
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string, for each transaction in the bundle.
+        tx_bytes: Vec<Base64>,
+        /// The signatures of each transaction, in the same order as `tx_bytes`.
+        signatures: Vec<Vec<Base64>>,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionBlockResponseOptions>,
+        /// The request type, derived from `SuiTransactionBlockResponseOptions` if None
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>>;
+
+    /// Reserve owned objects for an upcoming transaction, so that pipelines building several
+    /// transactions can detect conflicts before submission. While the reservation lasts, this
+    /// node rejects any other transaction using one of the objects. Fails without reserving
+    /// anything if an object is already reserved for a different transaction. The reservation is
+    /// local to this node and is released once the transaction is executed through it. All
+    /// objects must belong to the same owner, who signs the reservation, and an owner can hold a
+    /// bounded number of reservations at once.
+    #[method(name = "reserveObjects")]
+    async fn reserve_objects(
+        &self,
+        /// The owned objects to reserve, all owned by the same address.
+        object_ids: Vec<ObjectID>,
+        /// Digest of the transaction that will use the objects.
+        tx_digest: TransactionDigest,
+        /// The owner's `flag || signature || pubkey` signature over the personal message holding the BCS bytes of `(object_ids, tx_digest)`, in base64.
+        signature: Base64,
+        /// How long to hold the reservation, in milliseconds. Default to 5 seconds, capped at 30 seconds
+        ttl_ms: Option<BigInt<u64>>,
+    ) -> RpcResult<SuiObjectReservation>;
+
     /// Runs the transaction in dev-inspect mode. Which allows for nearly any
     /// transaction (or Move call) with any arguments. Detailed results are
     /// provided, including both the transaction effects and any return values.
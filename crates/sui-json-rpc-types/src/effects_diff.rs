@@ -0,0 +1,327 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use move_core_types::language_storage::StructTag;
+use serde::Serialize;
+use sui_types::TypeTag;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::gas::GasCostSummary;
+use sui_types::object::Owner;
+
+use crate::{
+    BalanceChange, DryRunTransactionBlockResponse, ObjectChange, SuiEvent, SuiExecutionStatus,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+
+/// A value that differs between a dry run and the actual execution of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiDivergence<T> {
+    pub dry_run: T,
+    pub executed: T,
+}
+
+/// A shared object input that was read at a different version by the dry run than by the
+/// actual execution, i.e. it was modified by another transaction in between.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiSharedObjectVersionDiff {
+    pub object_id: ObjectID,
+    /// Version read by the dry run, or `None` if the dry run did not access the object.
+    pub dry_run_version: Option<SequenceNumber>,
+    /// Version read by the execution, or `None` if the execution did not access the object.
+    pub executed_version: Option<SequenceNumber>,
+}
+
+/// An object that was changed differently by the dry run and the actual execution. Versions and
+/// digests are not compared, as they depend on the versions of the transaction's inputs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SuiObjectChangeDiff {
+    /// The object was changed by the dry run only.
+    DryRunOnly { change: ObjectChange },
+    /// The object was changed by the execution only.
+    ExecutedOnly { change: ObjectChange },
+    /// The object was changed by both, but with a different kind of change, owner or type.
+    Diverged {
+        dry_run: ObjectChange,
+        executed: ObjectChange,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiBalanceChangeDiff {
+    pub owner: Owner,
+    pub coin_type: TypeTag,
+    pub dry_run_amount: i128,
+    pub executed_amount: i128,
+}
+
+/// Structured difference between the result of dry running a transaction and the effects of
+/// actually executing it. Empty fields mean the two agree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiEffectsDiff {
+    pub status: Option<SuiDivergence<SuiExecutionStatus>>,
+    pub gas_used: Option<SuiDivergence<GasCostSummary>>,
+    pub shared_object_versions: Vec<SuiSharedObjectVersionDiff>,
+    pub object_changes: Vec<SuiObjectChangeDiff>,
+    pub balance_changes: Vec<SuiBalanceChangeDiff>,
+    /// Events emitted by the dry run but not by the execution.
+    pub dry_run_only_events: Vec<SuiEvent>,
+    /// Events emitted by the execution but not by the dry run.
+    pub executed_only_events: Vec<SuiEvent>,
+}
+
+impl SuiEffectsDiff {
+    /// Diff a dry run against the executed transaction. `executed` must have been fetched with
+    /// effects, and with events, object changes and balance changes for those to be compared;
+    /// returns `None` if it has no effects.
+    pub fn new(
+        dry_run: &DryRunTransactionBlockResponse,
+        executed: &SuiTransactionBlockResponse,
+    ) -> Option<Self> {
+        let executed_effects = executed.effects.as_ref()?;
+
+        let status =
+            (dry_run.effects.status() != executed_effects.status()).then(|| SuiDivergence {
+                dry_run: dry_run.effects.status().clone(),
+                executed: executed_effects.status().clone(),
+            });
+        let gas_used = (dry_run.effects.gas_cost_summary() != executed_effects.gas_cost_summary())
+            .then(|| SuiDivergence {
+                dry_run: dry_run.effects.gas_cost_summary().clone(),
+                executed: executed_effects.gas_cost_summary().clone(),
+            });
+
+        let mut shared_versions: BTreeMap<
+            ObjectID,
+            (Option<SequenceNumber>, Option<SequenceNumber>),
+        > = BTreeMap::new();
+        for object in dry_run.effects.shared_objects() {
+            shared_versions.entry(object.object_id).or_default().0 = Some(object.version);
+        }
+        for object in executed_effects.shared_objects() {
+            shared_versions.entry(object.object_id).or_default().1 = Some(object.version);
+        }
+        let shared_object_versions = shared_versions
+            .into_iter()
+            .filter(|(_, (dry_run_version, executed_version))| dry_run_version != executed_version)
+            .map(
+                |(object_id, (dry_run_version, executed_version))| SuiSharedObjectVersionDiff {
+                    object_id,
+                    dry_run_version,
+                    executed_version,
+                },
+            )
+            .collect();
+
+        let object_changes = diff_object_changes(
+            &dry_run.object_changes,
+            executed.object_changes.as_deref().unwrap_or_default(),
+        );
+        let balance_changes = diff_balance_changes(
+            &dry_run.balance_changes,
+            executed.balance_changes.as_deref().unwrap_or_default(),
+        );
+        let (dry_run_only_events, executed_only_events) = diff_events(
+            &dry_run.events.data,
+            executed
+                .events
+                .as_ref()
+                .map(|events| events.data.as_slice())
+                .unwrap_or_default(),
+        );
+
+        Some(Self {
+            status,
+            gas_used,
+            shared_object_versions,
+            object_changes,
+            balance_changes,
+            dry_run_only_events,
+            executed_only_events,
+        })
+    }
+
+    /// Whether the dry run and the execution agree on everything that is compared.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// The parts of an object change that do not depend on input object versions.
+fn object_change_shape(
+    change: &ObjectChange,
+) -> (&'static str, Option<&Owner>, Option<&StructTag>) {
+    match change {
+        ObjectChange::Published { .. } => ("published", None, None),
+        ObjectChange::Transferred {
+            recipient,
+            object_type,
+            ..
+        } => ("transferred", Some(recipient), Some(object_type)),
+        ObjectChange::Mutated {
+            owner, object_type, ..
+        } => ("mutated", Some(owner), Some(object_type)),
+        ObjectChange::Deleted { object_type, .. } => ("deleted", None, Some(object_type)),
+        ObjectChange::Wrapped { object_type, .. } => ("wrapped", None, Some(object_type)),
+        ObjectChange::Created {
+            owner, object_type, ..
+        } => ("created", Some(owner), Some(object_type)),
+    }
+}
+
+fn diff_object_changes(
+    dry_run: &[ObjectChange],
+    executed: &[ObjectChange],
+) -> Vec<SuiObjectChangeDiff> {
+    let mut executed: BTreeMap<_, _> = executed
+        .iter()
+        .map(|change| (change.object_id(), change))
+        .collect();
+
+    let mut diffs = vec![];
+    for change in dry_run {
+        match executed.remove(&change.object_id()) {
+            None => diffs.push(SuiObjectChangeDiff::DryRunOnly {
+                change: change.clone(),
+            }),
+            Some(executed) if object_change_shape(change) != object_change_shape(executed) => diffs
+                .push(SuiObjectChangeDiff::Diverged {
+                    dry_run: change.clone(),
+                    executed: executed.clone(),
+                }),
+            Some(_) => {}
+        }
+    }
+    diffs.extend(
+        executed
+            .into_values()
+            .map(|change| SuiObjectChangeDiff::ExecutedOnly {
+                change: change.clone(),
+            }),
+    );
+    diffs
+}
+
+fn diff_balance_changes(
+    dry_run: &[BalanceChange],
+    executed: &[BalanceChange],
+) -> Vec<SuiBalanceChangeDiff> {
+    let mut amounts: BTreeMap<(&Owner, &TypeTag), (i128, i128)> = BTreeMap::new();
+    for change in dry_run {
+        amounts
+            .entry((&change.owner, &change.coin_type))
+            .or_default()
+            .0 += change.amount;
+    }
+    for change in executed {
+        amounts
+            .entry((&change.owner, &change.coin_type))
+            .or_default()
+            .1 += change.amount;
+    }
+    amounts
+        .into_iter()
+        .filter(|(_, (dry_run_amount, executed_amount))| dry_run_amount != executed_amount)
+        .map(
+            |((owner, coin_type), (dry_run_amount, executed_amount))| SuiBalanceChangeDiff {
+                owner: owner.clone(),
+                coin_type: coin_type.clone(),
+                dry_run_amount,
+                executed_amount,
+            },
+        )
+        .collect()
+}
+
+/// Events present on only one side, matched by type and contents. Event IDs and timestamps are
+/// ignored.
+fn diff_events(dry_run: &[SuiEvent], executed: &[SuiEvent]) -> (Vec<SuiEvent>, Vec<SuiEvent>) {
+    let mut unmatched: Vec<Option<&SuiEvent>> = executed.iter().map(Some).collect();
+    let mut dry_run_only = vec![];
+    for event in dry_run {
+        let matched = unmatched.iter_mut().find(|candidate| {
+            candidate.is_some_and(|candidate| {
+                candidate.type_ == event.type_ && candidate.bcs.bytes() == event.bcs.bytes()
+            })
+        });
+        match matched {
+            Some(matched) => *matched = None,
+            None => dry_run_only.push(event.clone()),
+        }
+    }
+    let executed_only = unmatched.into_iter().flatten().cloned().collect();
+    (dry_run_only, executed_only)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{ObjectDigest, SuiAddress};
+    use sui_types::gas_coin::GAS;
+
+    fn mutated(object_id: ObjectID, owner: Owner, version: u64) -> ObjectChange {
+        ObjectChange::Mutated {
+            sender: SuiAddress::ZERO,
+            owner,
+            object_type: GAS::type_(),
+            object_id,
+            version: SequenceNumber::from_u64(version),
+            previous_version: SequenceNumber::from_u64(version - 1),
+            digest: ObjectDigest::random(),
+        }
+    }
+
+    #[test]
+    fn object_changes_ignore_versions() {
+        let same = ObjectID::random();
+        let moved = ObjectID::random();
+        let dry_run_only = ObjectID::random();
+        let alice = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+        let bob = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+
+        let diffs = diff_object_changes(
+            &[
+                mutated(same, alice.clone(), 2),
+                mutated(moved, alice.clone(), 2),
+                mutated(dry_run_only, alice.clone(), 2),
+            ],
+            &[mutated(same, alice, 5), mutated(moved, bob, 5)],
+        );
+        assert_eq!(diffs.len(), 2);
+        assert!(matches!(
+            &diffs[0],
+            SuiObjectChangeDiff::Diverged { executed, .. } if executed.object_id() == moved
+        ));
+        assert!(matches!(
+            &diffs[1],
+            SuiObjectChangeDiff::DryRunOnly { change } if change.object_id() == dry_run_only
+        ));
+    }
+
+    #[test]
+    fn balance_changes_are_netted_per_owner_and_coin() {
+        let owner = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+        let change = |amount| BalanceChange {
+            owner: owner.clone(),
+            coin_type: GAS::type_tag(),
+            amount,
+        };
+
+        assert!(diff_balance_changes(&[change(-10)], &[change(-4), change(-6)]).is_empty());
+        assert_eq!(
+            diff_balance_changes(&[change(-10)], &[change(-12)]),
+            vec![SuiBalanceChangeDiff {
+                owner: owner.clone(),
+                coin_type: GAS::type_tag(),
+                dry_run_amount: -10,
+                executed_amount: -12,
+            }]
+        );
+    }
+}
@@ -6,10 +6,12 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub use balance_changes::*;
+pub use effects_diff::*;
 pub use object_changes::*;
 use serde_with::serde_as;
 pub use sui_checkpoint::*;
 pub use sui_coin::*;
+pub use sui_deepbook::*;
 pub use sui_event::*;
 pub use sui_extended::*;
 pub use sui_governance::*;
@@ -25,9 +27,11 @@ mod rpc_types_tests;
 
 mod balance_changes;
 mod displays;
+mod effects_diff;
 mod object_changes;
 mod sui_checkpoint;
 mod sui_coin;
+mod sui_deepbook;
 mod sui_event;
 mod sui_extended;
 mod sui_governance;
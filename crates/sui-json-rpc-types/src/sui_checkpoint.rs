@@ -6,17 +6,20 @@ use fastcrypto::encoding::Base64;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use sui_types::base_types::TransactionDigest;
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, TransactionDigest};
 use sui_types::committee::EpochId;
 use sui_types::crypto::AggregateAuthoritySignature;
-use sui_types::digests::CheckpointDigest;
+use sui_types::digests::{CheckpointDigest, TransactionEffectsDigest};
+use sui_types::full_checkpoint_content::{self, ObjectDelta, ObjectDeltaKind, ObjectDeltaState};
 use sui_types::gas::GasCostSummary;
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
     CheckpointCommitment, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
     CheckpointTimestamp, EndOfEpochData,
 };
+use sui_types::object::Owner;
 use sui_types::sui_serde::BigInt;
+use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
 pub type CheckpointPage = Page<Checkpoint, BigInt<u64>>;
 
 #[serde_as]
@@ -132,3 +135,184 @@ impl From<CheckpointDigest> for CheckpointId {
         Self::Digest(digest)
     }
 }
+
+/// Evidence that a transaction was executed in a certified checkpoint. Every field is BCS-encoded
+/// exactly as it is hashed or signed, so a client can check the chain
+/// `summary -> contents -> (transaction, effects) -> events` against a committee it trusts,
+/// without trusting the node that served it.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiTransactionProof {
+    pub transaction_digest: TransactionDigest,
+    /// Sequence number of the checkpoint that includes the transaction
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    /// BCS encoded `CertifiedCheckpointSummary`, signed by the committee of its epoch
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub checkpoint_summary: Vec<u8>,
+    /// BCS encoded `CheckpointContents`, committed to by the summary's content digest
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub checkpoint_contents: Vec<u8>,
+    /// BCS encoded sender-signed `Transaction`
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub transaction: Vec<u8>,
+    /// BCS encoded `TransactionEffects`
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub effects: Vec<u8>,
+    /// BCS encoded `TransactionEvents`, if the transaction emitted any
+    #[serde_as(as = "Option<Base64>")]
+    #[schemars(with = "Option<Base64>")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub events: Option<Vec<u8>>,
+}
+
+/// Evidence that a transaction is final: the checkpoint summary that includes it, the committee's
+/// signature over that summary, and the checkpoint contents that place the transaction and its
+/// effects in the checkpoint. Unlike [`SuiTransactionProof`], it does not carry the transaction,
+/// effects or events, so it stays small enough to be stored alongside every transaction.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiFinalityCertificate {
+    pub transaction_digest: TransactionDigest,
+    /// Digest of the transaction's effects, as recorded in the checkpoint contents
+    pub effects_digest: TransactionEffectsDigest,
+    /// Epoch of the committee that signed the checkpoint
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    /// Sequence number of the checkpoint that includes the transaction
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    /// BCS encoded `CheckpointSummary`
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub checkpoint_summary: Vec<u8>,
+    /// BCS encoded `AuthorityStrongQuorumSignInfo` of the committee over the checkpoint summary
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub signature: Vec<u8>,
+    /// BCS encoded `CheckpointContents`, committed to by the summary's content digest
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub checkpoint_contents: Vec<u8>,
+    /// Position of the transaction in the checkpoint contents
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub position: u64,
+}
+
+/// The net change to objects over a checkpoint, for services that mirror on-chain state without
+/// processing every transaction's effects.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointObjectChanges {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub epoch: EpochId,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub sequence_number: CheckpointSequenceNumber,
+    pub digest: CheckpointDigest,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub timestamp_ms: CheckpointTimestamp,
+    /// Every object changed by the checkpoint's transactions, ordered by object ID. Objects that
+    /// were both created and removed within the checkpoint are omitted.
+    pub changes: Vec<SuiObjectDelta>,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiObjectDelta {
+    pub object_id: ObjectID,
+    pub kind: SuiObjectDeltaKind,
+    /// The object's state before the checkpoint, absent if it was created or unwrapped.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub input: Option<SuiObjectDeltaState>,
+    /// The object's state after the checkpoint, absent if it was deleted or wrapped.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub output: Option<SuiObjectDeltaState>,
+}
+
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SuiObjectDeltaKind {
+    /// The object was created or unwrapped.
+    Created,
+    Mutated,
+    /// The object was deleted or wrapped.
+    Deleted,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiObjectDeltaState {
+    #[schemars(with = "AsSequenceNumber")]
+    #[serde_as(as = "AsSequenceNumber")]
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub owner: Owner,
+}
+
+impl From<&full_checkpoint_content::Checkpoint> for CheckpointObjectChanges {
+    fn from(checkpoint: &full_checkpoint_content::Checkpoint) -> Self {
+        let summary = checkpoint.summary.data();
+        Self {
+            epoch: summary.epoch,
+            sequence_number: summary.sequence_number,
+            digest: *checkpoint.summary.digest(),
+            timestamp_ms: summary.timestamp_ms,
+            changes: checkpoint
+                .object_deltas()
+                .into_iter()
+                .map(SuiObjectDelta::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<ObjectDelta> for SuiObjectDelta {
+    fn from(delta: ObjectDelta) -> Self {
+        Self {
+            object_id: delta.object_id,
+            kind: delta.kind().into(),
+            input: delta.input.map(SuiObjectDeltaState::from),
+            output: delta.output.map(SuiObjectDeltaState::from),
+        }
+    }
+}
+
+impl From<ObjectDeltaKind> for SuiObjectDeltaKind {
+    fn from(kind: ObjectDeltaKind) -> Self {
+        match kind {
+            ObjectDeltaKind::Created => Self::Created,
+            ObjectDeltaKind::Mutated => Self::Mutated,
+            ObjectDeltaKind::Deleted => Self::Deleted,
+        }
+    }
+}
+
+impl From<ObjectDeltaState> for SuiObjectDeltaState {
+    fn from(state: ObjectDeltaState) -> Self {
+        let ObjectDeltaState {
+            version,
+            digest,
+            owner,
+        } = state;
+        Self {
+            version,
+            digest,
+            owner,
+        }
+    }
+}
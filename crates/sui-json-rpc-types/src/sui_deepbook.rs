@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::sui_serde::BigInt;
+use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
+
+/// Snapshot of a DeepBook pool's order book, aggregated by price level.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepBookOrderBook {
+    pub pool_id: ObjectID,
+    /// Version of the pool object the snapshot was read at. Passing it back returns the same
+    /// snapshot for as long as the node retains that version.
+    #[schemars(with = "AsSequenceNumber")]
+    #[serde_as(as = "AsSequenceNumber")]
+    pub pool_version: SequenceNumber,
+    /// Checkpoint of the transaction that wrote this version of the pool, which the snapshot is
+    /// pinned to, or null if this node has not executed that checkpoint yet.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<BigInt<u64>>")]
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    pub base_asset: String,
+    pub quote_asset: String,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub tick_size: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub lot_size: u64,
+    /// Taker fee rate, scaled by 10^9.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub taker_fee_rate: u64,
+    /// Maker rebate rate, scaled by 10^9.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub maker_rebate_rate: u64,
+    /// Bid levels, best (highest) price first.
+    pub bids: Vec<DeepBookPriceLevel>,
+    /// Ask levels, best (lowest) price first.
+    pub asks: Vec<DeepBookPriceLevel>,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepBookPriceLevel {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub price: u64,
+    /// Total remaining base asset quantity of the open orders at this price, including orders
+    /// that have expired but not yet been removed from the book.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub quantity: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub order_count: u64,
+}
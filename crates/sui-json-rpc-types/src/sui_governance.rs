@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sui_types::base_types::{AuthorityName, EpochId, ObjectID, SuiAddress};
 use sui_types::committee::{Committee, StakeUnit};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
 
 /// RPC representation of the [Committee] type.
@@ -88,3 +89,67 @@ pub struct ValidatorApy {
     pub address: SuiAddress,
     pub apy: f64,
 }
+
+/// Gas prices bid for the consensus objects that were congested in a checkpoint.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointCongestionGasPrices {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub timestamp_ms: u64,
+    pub objects: Vec<CongestedObjectGasPrices>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CongestedObjectGasPrices {
+    pub object_id: ObjectID,
+    /// Gas prices of the transactions that were sequenced and mutated the object. None if every
+    /// transaction touching the object was cancelled.
+    pub sequenced: Option<GasPriceDistribution>,
+    /// Gas prices of the transactions that were deferred until cancelled because the object was
+    /// congested.
+    pub cancelled: GasPriceDistribution,
+}
+
+/// Summary of the gas prices bid by a set of transactions.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GasPriceDistribution {
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub count: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub min: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub median: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub p90: u64,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub max: u64,
+}
+
+impl GasPriceDistribution {
+    /// Summarizes `prices`, which must be sorted in ascending order. Returns None if empty.
+    pub fn from_sorted(prices: &[u64]) -> Option<Self> {
+        let (min, max) = (*prices.first()?, *prices.last()?);
+        // Nearest-rank percentile.
+        let percentile = |p: usize| prices[(prices.len() * p).div_ceil(100).max(1) - 1];
+        Some(Self {
+            count: prices.len() as u64,
+            min,
+            median: percentile(50),
+            p90: percentile(90),
+            max,
+        })
+    }
+}
@@ -15,6 +15,7 @@ use move_core_types::language_storage::StructTag;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
+use serde_reflection::ContainerFormat;
 use serde_with::serde_as;
 use std::collections::BTreeMap;
 use std::fmt;
@@ -25,7 +26,7 @@ use tracing::warn;
 
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::execution_status::MoveLocation;
-use sui_types::sui_serde::SuiStructTag;
+use sui_types::sui_serde::{BigInt, SuiStructTag};
 
 pub type SuiMoveTypeParameterIndex = u16;
 
@@ -152,6 +153,17 @@ impl PartialEq for SuiMoveNormalizedModule {
     }
 }
 
+/// Serde-reflection layouts of the types declared by a package and of the struct types its
+/// `entry` functions accept, including the layouts of their transitive dependencies. Clients can
+/// use these to decode the BCS contents of the package's objects and events without its source.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq)]
+#[serde(rename = "MoveStructLayouts")]
+pub struct SuiMoveStructLayouts {
+    /// Layouts keyed by fully qualified type name.
+    #[schemars(with = "BTreeMap<String, Value>")]
+    pub layouts: BTreeMap<String, ContainerFormat>,
+}
+
 impl<S: std::hash::Hash + Eq + ToString> From<&NormalizedModule<S>> for SuiMoveNormalizedModule {
     fn from(module: &NormalizedModule<S>) -> Self {
         Self {
@@ -752,6 +764,55 @@ impl From<MoveStruct> for SuiMoveStruct {
     }
 }
 
+/// A record that the source of a package was rebuilt by this node and matched its on-chain
+/// bytecode.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiSourceAttestation {
+    pub package_id: ObjectID,
+    /// Compiler version recorded in the submitted `Move.lock`, if any.
+    pub toolchain_version: Option<String>,
+    /// When the source was verified, in milliseconds since the Unix epoch.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub verified_at_ms: u64,
+    pub modules: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiSourceVerificationStatus {
+    pub package_id: ObjectID,
+    pub verified: bool,
+    pub attestation: Option<SuiSourceAttestation>,
+}
+
+/// Location in a verified package's source, e.g. of a Move abort.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiMoveSourceLocation {
+    /// Path of the source file, relative to the package root.
+    pub file: String,
+    /// 1-based line number.
+    pub line: u32,
+    /// 1-based column number.
+    pub column: u32,
+}
+
+/// A Move abort code resolved to the error constant it was raised with.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiResolvedMoveAbort {
+    pub module: String,
+    /// Name of the constant the module aborted with, if it could be identified.
+    pub constant_name: Option<String>,
+    /// Value of a clever error constant, if it is a string.
+    pub message: Option<String>,
+    /// Source line of the abort, if the abort code is a clever error.
+    pub line_number: Option<u16>,
+}
+
 #[test]
 fn enum_size() {
     assert_eq!(std::mem::size_of::<SuiMoveNormalizedType>(), 16);
@@ -698,6 +698,21 @@ impl From<ObjectRef> for SuiObjectRef {
     }
 }
 
+/// Owned objects reserved for an upcoming transaction.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", rename = "ObjectReservation")]
+pub struct SuiObjectReservation {
+    /// Digest of the transaction the objects are reserved for.
+    pub tx_digest: TransactionDigest,
+    /// Latest references of the reserved objects known to the node.
+    pub objects: Vec<SuiObjectRef>,
+    /// When the reservation expires, in milliseconds since the Unix epoch.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub expires_at_ms: u64,
+}
+
 pub trait SuiData: Sized {
     type ObjectType;
     type PackageType;
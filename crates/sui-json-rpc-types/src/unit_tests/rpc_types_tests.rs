@@ -16,7 +16,7 @@ use sui_types::gas_coin::GasCoin;
 use sui_types::object::{MoveObject, Owner};
 use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, parse_sui_struct_tag};
 
-use crate::{ObjectChange, SuiMoveStruct, SuiMoveValue};
+use crate::{GasPriceDistribution, ObjectChange, SuiMoveStruct, SuiMoveValue};
 
 #[test]
 fn test_move_value_to_sui_coin() {
@@ -319,3 +319,30 @@ fn test_type_tag_struct_tag_devnet_inc_222() {
         assert_eq!(oc, deser);
     }
 }
+
+#[test]
+fn test_gas_price_distribution() {
+    assert_eq!(GasPriceDistribution::from_sorted(&[]), None);
+    assert_eq!(
+        GasPriceDistribution::from_sorted(&[750]),
+        Some(GasPriceDistribution {
+            count: 1,
+            min: 750,
+            median: 750,
+            p90: 750,
+            max: 750,
+        })
+    );
+
+    let prices = (1..=20).map(|i| i * 100).collect::<Vec<_>>();
+    assert_eq!(
+        GasPriceDistribution::from_sorted(&prices),
+        Some(GasPriceDistribution {
+            count: 20,
+            min: 100,
+            median: 1000,
+            p90: 1800,
+            max: 2000,
+        })
+    );
+}
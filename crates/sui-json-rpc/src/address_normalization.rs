@@ -0,0 +1,448 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+use schemars::schema::{Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+use serde_json::value::RawValue;
+use sui_config::node::JsonRpcAddressConfig;
+use sui_open_rpc::Project;
+use sui_types::base_types::{SUI_ADDRESS_LENGTH, SuiAddress};
+
+/// Schemas of the values that hold an address or object ID.
+const ADDRESS_SCHEMAS: &[&str] = &["SuiAddress", "ObjectID"];
+
+/// Prefix of references to the component schemas of the RPC doc.
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// How deep into a parameter's value addresses are normalized, to bound the work done for
+/// deeply nested filters.
+const MAX_DEPTH: usize = 32;
+
+/// Rewrites address and object ID parameters into their canonical form before they reach the
+/// method handlers, so every endpoint accepts the same spellings: `0x`-prefixed short literals
+/// (`0x2`), mixed-case hex, and `@name` aliases from the node config.
+#[derive(Clone)]
+pub struct AddressNormalizationLayer<S> {
+    inner: S,
+    normalizer: Option<Arc<AddressNormalizer>>,
+}
+
+impl<S> AddressNormalizationLayer<S> {
+    pub fn new(service: S, normalizer: Option<Arc<AddressNormalizer>>) -> Self {
+        Self {
+            inner: service,
+            normalizer,
+        }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for AddressNormalizationLayer<S>
+where
+    S: RpcServiceT<'a> + Send + Sync,
+{
+    type Future = S::Future;
+
+    fn call(&self, mut req: jsonrpsee::types::Request<'a>) -> Self::Future {
+        if let Some(normalizer) = &self.normalizer
+            && let Some(params) = &req.params
+            && let Some(normalized) = normalizer.normalize_params(req.method_name(), params.get())
+        {
+            req.params = Some(Cow::Owned(normalized));
+        }
+        self.inner.call(req)
+    }
+}
+
+pub struct AddressNormalizer {
+    /// For each method, the positions, names and schemas of the parameters that can hold an
+    /// address, directly or nested inside them.
+    methods: HashMap<String, Vec<(usize, String, SchemaObject)>>,
+    /// The component schemas of the RPC doc, to resolve references with.
+    schemas: BTreeMap<String, SchemaObject>,
+    /// The component schemas that can hold an address, directly or nested inside them.
+    address_schemas: HashSet<String>,
+    aliases: BTreeMap<String, SuiAddress>,
+}
+
+impl AddressNormalizer {
+    /// Build a normalizer for the methods documented in `rpc_doc`, or `None` if `config` asks
+    /// for strict parsing.
+    pub fn new(rpc_doc: &Project, config: &JsonRpcAddressConfig) -> Option<Self> {
+        if config.strict {
+            return None;
+        }
+
+        let schemas = rpc_doc.component_schemas().clone();
+        let address_schemas = address_schemas(&schemas);
+        let methods = rpc_doc
+            .param_schemas()
+            .into_iter()
+            .filter_map(|(method, params)| {
+                let params: Vec<_> = params
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, (_, schema))| references_any(schema, &address_schemas))
+                    .map(|(i, (name, schema))| (i, name.to_owned(), schema.clone()))
+                    .collect();
+                (!params.is_empty()).then(|| (method.to_owned(), params))
+            })
+            .collect();
+
+        Some(Self {
+            methods,
+            schemas,
+            address_schemas,
+            aliases: config.aliases.clone(),
+        })
+    }
+
+    /// The normalized JSON for `params` of a call to `method`, or `None` if nothing changed or
+    /// the parameters could not be parsed (in which case the handler reports the error).
+    pub fn normalize_params(&self, method: &str, params: &str) -> Option<Box<RawValue>> {
+        let address_params = self.methods.get(method)?;
+        let mut params: Value = serde_json::from_str(params).ok()?;
+
+        let mut changed = false;
+        for (position, name, schema) in address_params {
+            let param = match &mut params {
+                Value::Array(params) => params.get_mut(*position),
+                Value::Object(params) => params.get_mut(name),
+                _ => None,
+            };
+
+            if let Some(param) = param {
+                changed |= self.normalize_value(param, schema, 0);
+            }
+        }
+
+        if !changed {
+            return None;
+        }
+
+        RawValue::from_string(params.to_string()).ok()
+    }
+
+    /// Normalize the addresses in `value`, following `schema` into nested objects, arrays and
+    /// enum variants. Returns whether anything changed.
+    fn normalize_value(&self, value: &mut Value, schema: &SchemaObject, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+
+        if let Some(name) = schema_ref(schema) {
+            if ADDRESS_SCHEMAS.contains(&name) {
+                return match value {
+                    Value::String(s) => self.normalize_string(s),
+                    _ => false,
+                };
+            }
+
+            return match self.schemas.get(name) {
+                Some(schema) if self.address_schemas.contains(name) => {
+                    self.normalize_value(value, schema, depth + 1)
+                }
+                _ => false,
+            };
+        }
+
+        let mut changed = false;
+        if let Some(subschemas) = &schema.subschemas {
+            let alternatives = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+            for subschema in alternatives.into_iter().flatten().flatten() {
+                if let Schema::Object(subschema) = subschema
+                    && matches_required(value, subschema)
+                {
+                    changed |= self.normalize_value(value, subschema, depth + 1);
+                }
+            }
+        }
+
+        match value {
+            Value::Object(fields) => {
+                let Some(object) = &schema.object else {
+                    return changed;
+                };
+                for (key, field) in fields {
+                    let field_schema = object
+                        .properties
+                        .get(key)
+                        .or(object.additional_properties.as_deref());
+                    if let Some(Schema::Object(field_schema)) = field_schema {
+                        changed |= self.normalize_value(field, field_schema, depth + 1);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let Some(array_items) = schema.array.as_ref().and_then(|a| a.items.as_ref()) else {
+                    return changed;
+                };
+                match array_items {
+                    SingleOrVec::Single(item_schema) => {
+                        if let Schema::Object(item_schema) = item_schema.as_ref() {
+                            for item in items {
+                                changed |= self.normalize_value(item, item_schema, depth + 1);
+                            }
+                        }
+                    }
+                    SingleOrVec::Vec(item_schemas) => {
+                        for (item, item_schema) in items.iter_mut().zip(item_schemas) {
+                            if let Schema::Object(item_schema) = item_schema {
+                                changed |= self.normalize_value(item, item_schema, depth + 1);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        changed
+    }
+
+    /// Replace `value` with its canonical form, returning whether it changed.
+    fn normalize_string(&self, value: &mut String) -> bool {
+        match normalize_address(value, &self.aliases) {
+            Some(normalized) if normalized != *value => {
+                *value = normalized;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The name of the component schema `schema` refers to, if it is a reference.
+fn schema_ref(schema: &SchemaObject) -> Option<&str> {
+    schema.reference.as_deref()?.strip_prefix(SCHEMA_REF_PREFIX)
+}
+
+/// Whether `value` has every property `schema` requires, so that only the enum variant a value
+/// actually is gets normalized.
+fn matches_required(value: &Value, schema: &SchemaObject) -> bool {
+    let Some(object) = &schema.object else {
+        return true;
+    };
+    match value {
+        Value::Object(fields) => object.required.iter().all(|key| fields.contains_key(key)),
+        _ => object.required.is_empty(),
+    }
+}
+
+/// The names of the component schemas that can hold an address, directly or nested inside them.
+fn address_schemas(schemas: &BTreeMap<String, SchemaObject>) -> HashSet<String> {
+    let mut found: HashSet<String> = ADDRESS_SCHEMAS.iter().map(|s| s.to_string()).collect();
+    loop {
+        let before = found.len();
+        for (name, schema) in schemas {
+            if !found.contains(name) && references_any(schema, &found) {
+                found.insert(name.clone());
+            }
+        }
+        if found.len() == before {
+            return found;
+        }
+    }
+}
+
+/// Whether `schema` refers to any of the component schemas in `names`, anywhere inside it.
+fn references_any(schema: &SchemaObject, names: &HashSet<String>) -> bool {
+    fn any_of<'a>(schemas: impl IntoIterator<Item = &'a Schema>, names: &HashSet<String>) -> bool {
+        schemas.into_iter().any(|schema| match schema {
+            Schema::Object(schema) => references_any(schema, names),
+            Schema::Bool(_) => false,
+        })
+    }
+
+    if schema_ref(schema).is_some_and(|name| names.contains(name)) {
+        return true;
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        let alternatives = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+        if any_of(alternatives.into_iter().flatten().flatten(), names) {
+            return true;
+        }
+    }
+
+    if let Some(object) = &schema.object
+        && any_of(
+            object
+                .properties
+                .values()
+                .chain(object.additional_properties.as_deref()),
+            names,
+        )
+    {
+        return true;
+    }
+
+    match schema.array.as_ref().and_then(|a| a.items.as_ref()) {
+        Some(SingleOrVec::Single(item)) => any_of([item.as_ref()], names),
+        Some(SingleOrVec::Vec(items)) => any_of(items, names),
+        None => false,
+    }
+}
+
+/// The canonical, lowercase, full-length `0x`-prefixed form of `value`, if it is an address
+/// literal or a known `@name` alias. Values that are neither are left for the endpoint to reject.
+pub fn normalize_address(value: &str, aliases: &BTreeMap<String, SuiAddress>) -> Option<String> {
+    if let Some(alias) = value.strip_prefix('@') {
+        return aliases.get(alias).map(|address| address.to_string());
+    }
+
+    let hex = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .or_else(|| (value.len() == SUI_ADDRESS_LENGTH * 2).then_some(value))?;
+
+    if hex.is_empty()
+        || hex.len() > SUI_ADDRESS_LENGTH * 2
+        || !hex.bytes().all(|b| b.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    Some(format!(
+        "0x{:0>width$}",
+        hex.to_ascii_lowercase(),
+        width = SUI_ADDRESS_LENGTH * 2
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::coin_api::CoinReadApi;
+    use crate::indexer_api::IndexerApi;
+    use crate::read_api::ReadApi;
+    use crate::{SuiRpcModule, sui_rpc_doc};
+
+    fn build_normalizer(config: &JsonRpcAddressConfig) -> Option<AddressNormalizer> {
+        let mut rpc_doc = sui_rpc_doc("0.0.0");
+        rpc_doc.add_module(ReadApi::rpc_doc_module());
+        rpc_doc.add_module(CoinReadApi::rpc_doc_module());
+        rpc_doc.add_module(IndexerApi::<ReadApi>::rpc_doc_module());
+        AddressNormalizer::new(&rpc_doc, config)
+    }
+
+    #[test]
+    fn test_normalize_address() {
+        let aliases =
+            BTreeMap::from([("treasury".to_owned(), SuiAddress::from_str("0x7").unwrap())]);
+        let canonical = format!("0x{:0>64}", "ab");
+
+        assert_eq!(normalize_address("0xab", &aliases), Some(canonical.clone()));
+        assert_eq!(normalize_address("0XAb", &aliases), Some(canonical.clone()));
+        assert_eq!(
+            normalize_address(&canonical[2..], &aliases),
+            Some(canonical)
+        );
+        assert_eq!(
+            normalize_address("@treasury", &aliases),
+            Some(format!("0x{:0>64}", "7"))
+        );
+
+        assert_eq!(normalize_address("@unknown", &aliases), None);
+        assert_eq!(normalize_address("0x", &aliases), None);
+        assert_eq!(normalize_address("0xzz", &aliases), None);
+        assert_eq!(normalize_address("ab", &aliases), None);
+        assert_eq!(
+            normalize_address(&format!("0x{}", "1".repeat(65)), &aliases),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_params() {
+        let normalizer = build_normalizer(&JsonRpcAddressConfig::default()).unwrap();
+        let two = format!("0x{:0>64}", "2");
+
+        // Positional and named parameters, leaving non-address parameters alone.
+        let normalized = normalizer
+            .normalize_params("sui_getObject", r#"["0x2", {"showContent": true}]"#)
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(normalized.get()).unwrap(),
+            serde_json::json!([two, {"showContent": true}])
+        );
+
+        let normalized = normalizer
+            .normalize_params(
+                "suix_getBalance",
+                r#"{"owner": "0x2", "coin_type": "0x2::sui::SUI"}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(normalized.get()).unwrap(),
+            serde_json::json!({"owner": two, "coin_type": "0x2::sui::SUI"})
+        );
+
+        // Arrays of object IDs are normalized element by element.
+        let normalized = normalizer
+            .normalize_params("sui_multiGetObjects", r#"[["0x2", "0x6"]]"#)
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(normalized.get()).unwrap(),
+            serde_json::json!([[two, format!("0x{:0>64}", "6")]])
+        );
+
+        // Canonical input is passed through untouched.
+        assert!(
+            normalizer
+                .normalize_params("sui_getObject", &format!(r#"["{two}"]"#))
+                .is_none()
+        );
+
+        // Addresses nested inside filters are normalized too, including inside combinators.
+        let normalized = normalizer
+            .normalize_params(
+                "suix_getOwnedObjects",
+                r#"["0x2", {"filter": {"MatchAny": [{"AddressOwner": "0x6"}, {"Package": "0x2"}]}}]"#,
+            )
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(normalized.get()).unwrap(),
+            serde_json::json!([
+                two,
+                {"filter": {"MatchAny": [
+                    {"AddressOwner": format!("0x{:0>64}", "6")},
+                    {"Package": two},
+                ]}},
+            ])
+        );
+
+        let normalized = normalizer
+            .normalize_params(
+                "suix_queryTransactionBlocks",
+                r#"{"query": {"filter": {"FromAddress": "0x2"}}, "limit": 5}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(normalized.get()).unwrap(),
+            serde_json::json!({"query": {"filter": {"FromAddress": two}}, "limit": 5})
+        );
+
+        // Strings that only look like addresses in non-address positions are left alone.
+        assert!(
+            normalizer
+                .normalize_params(
+                    "suix_getOwnedObjects",
+                    &format!(r#"["{two}", {{"filter": {{"StructType": "0x2::coin::Coin"}}}}]"#),
+                )
+                .is_none()
+        );
+
+        // Strict mode disables normalization entirely.
+        let strict = JsonRpcAddressConfig {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(build_normalizer(&strict).is_none());
+    }
+}
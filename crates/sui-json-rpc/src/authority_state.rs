@@ -9,6 +9,7 @@ use std::sync::Arc;
 use sui_core::accumulators::balances::{get_all_balances_for_owner, get_balance};
 use sui_core::authority::AuthorityState;
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use sui_core::congestion_tracker::CheckpointGasPrices;
 use sui_core::execution_cache::ObjectCacheRead;
 use sui_core::jsonrpc_index::{CoinIndexKey2, CoinInfo, TotalBalance};
 use sui_core::subscription_handler::SubscriptionHandler;
@@ -159,6 +160,7 @@ pub trait StateRead: Send + Sync {
     async fn get_staked_sui(&self, owner: SuiAddress) -> StateReadResult<Vec<StakedSui>>;
     fn get_system_state(&self) -> StateReadResult<SuiSystemState>;
     fn get_or_latest_committee(&self, epoch: Option<BigInt<u64>>) -> StateReadResult<Committee>;
+    fn get_recent_congestion_gas_prices(&self, limit: usize) -> Vec<CheckpointGasPrices>;
 
     // bridge_api
     fn get_bridge(&self) -> StateReadResult<Bridge>;
@@ -455,6 +457,10 @@ impl StateRead for AuthorityState {
             .get_or_latest_committee(epoch.map(|e| *e))?)
     }
 
+    fn get_recent_congestion_gas_prices(&self, limit: usize) -> Vec<CheckpointGasPrices> {
+        self.congestion_tracker().get_recent_gas_prices(limit)
+    }
+
     fn get_bridge(&self) -> StateReadResult<Bridge> {
         self.get_cache_reader()
             .get_bridge_object_unsafe()
@@ -0,0 +1,387 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use axum::body::{Body, Bytes, to_bytes};
+use axum::extract::{Path, Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sui_config::node::JsonRpcBlobConfig;
+
+/// Request header through which a client opts in to receiving blob references.
+pub const BLOB_REFERENCES_HEADER: &str = "blob-references";
+
+/// Blobs are addressed by the hash of their contents, so they never change.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Fields under which responses carry user-controlled data: Move values, event payloads, display
+/// fields and dynamic field names. They are returned untouched, even if a Move struct happens to
+/// have a field named like one of the fields replaced below.
+const USER_DATA_FIELDS: &[&str] = &["fields", "parsedJson", "display", "value"];
+
+/// What a large field is replaced by. The envelope names its own type, so a client can tell a
+/// reference apart from the field's inline contents without knowing the size threshold.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename = "blobReference")]
+pub struct BlobReference {
+    /// Hex-encoded Blake2b256 digest of the contents, served from `GET /blobs/{digest}`.
+    pub digest: String,
+    /// Size of the contents, in bytes.
+    pub size: usize,
+}
+
+#[derive(Clone, Copy)]
+enum FieldEncoding {
+    Base64,
+    Text,
+}
+
+impl FieldEncoding {
+    fn decode(self, field: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Base64 => Base64::decode(field).ok(),
+            Self::Text => Some(field.as_bytes().to_vec()),
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Base64 => "application/octet-stream",
+            Self::Text => "text/plain; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Blob {
+    bytes: Bytes,
+    content_type: &'static str,
+}
+
+#[derive(Default)]
+struct Blobs {
+    blobs: HashMap<String, Blob>,
+    /// Digests in insertion order, oldest first.
+    order: VecDeque<String>,
+    size: usize,
+}
+
+/// Content-addressed store for large fields lifted out of JSON-RPC responses.
+///
+/// When a request carries the [`BLOB_REFERENCES_HEADER`], the object BCS (`bcsBytes` of raw move
+/// object data), package modules (`moduleMap` of raw package data), package disassembly
+/// (`disassembled` of parsed package data) and transaction BCS (`rawTransaction` of a transaction
+/// block) that are at least `min_size` bytes are replaced in the response by a [`BlobReference`],
+/// and their contents are served from `GET /blobs/{digest}`. The store is bounded by `cache_size`
+/// bytes, so a reference only stays resolvable for a while after the response that produced it.
+pub struct BlobStore {
+    config: JsonRpcBlobConfig,
+    inner: Mutex<Blobs>,
+}
+
+impl BlobStore {
+    pub fn new(config: JsonRpcBlobConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Store `bytes` and return their digest, or `None` if they could never fit in the store.
+    fn put(&self, bytes: Vec<u8>, content_type: &'static str) -> Option<String> {
+        if bytes.len() > self.config.cache_size {
+            return None;
+        }
+        let digest = Hex::encode(Blake2b256::digest(&bytes).digest);
+
+        let mut inner = self.inner.lock().unwrap();
+        if inner.blobs.contains_key(&digest) {
+            return Some(digest);
+        }
+        while inner.size + bytes.len() > self.config.cache_size {
+            let Some(oldest) = inner.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = inner.blobs.remove(&oldest) {
+                inner.size -= evicted.bytes.len();
+            }
+        }
+        inner.size += bytes.len();
+        inner.order.push_back(digest.clone());
+        inner.blobs.insert(
+            digest.clone(),
+            Blob {
+                bytes: bytes.into(),
+                content_type,
+            },
+        );
+        Some(digest)
+    }
+
+    fn get(&self, digest: &str) -> Option<Blob> {
+        self.inner
+            .lock()
+            .unwrap()
+            .blobs
+            .get(&digest.to_ascii_lowercase())
+            .cloned()
+    }
+
+    /// Replace every large field in `value` with a blob reference. Returns whether anything was
+    /// replaced.
+    fn replace_large_fields(&self, value: &mut Value) -> bool {
+        match value {
+            Value::Array(items) => items.iter_mut().fold(false, |replaced, item| {
+                self.replace_large_fields(item) | replaced
+            }),
+            Value::Object(fields) => {
+                // Large fields are only recognized in the objects that carry them: object and
+                // package data are tagged with their `dataType`, and transaction blocks always
+                // have a `digest`.
+                let data_type = fields
+                    .get("dataType")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned);
+                let is_transaction_block = fields.contains_key("digest");
+                let mut replaced = false;
+                for (key, field) in fields.iter_mut() {
+                    replaced |= match (key.as_str(), data_type.as_deref()) {
+                        ("bcsBytes", Some("moveObject")) => {
+                            self.replace_field(field, FieldEncoding::Base64)
+                        }
+                        ("moduleMap", Some("package")) => {
+                            self.replace_map_fields(field, FieldEncoding::Base64)
+                        }
+                        ("disassembled", Some("package")) => {
+                            self.replace_map_fields(field, FieldEncoding::Text)
+                        }
+                        ("rawTransaction", _) if is_transaction_block => {
+                            self.replace_field(field, FieldEncoding::Base64)
+                        }
+                        (key, _) if USER_DATA_FIELDS.contains(&key) => false,
+                        _ => self.replace_large_fields(field),
+                    };
+                }
+                replaced
+            }
+            _ => false,
+        }
+    }
+
+    fn replace_map_fields(&self, value: &mut Value, encoding: FieldEncoding) -> bool {
+        let Value::Object(fields) = value else {
+            return false;
+        };
+        fields.values_mut().fold(false, |replaced, field| {
+            self.replace_field(field, encoding) | replaced
+        })
+    }
+
+    fn replace_field(&self, value: &mut Value, encoding: FieldEncoding) -> bool {
+        let Some(bytes) = value.as_str().and_then(|field| encoding.decode(field)) else {
+            return false;
+        };
+        if bytes.len() < self.config.min_size {
+            return false;
+        }
+        let size = bytes.len();
+        let Some(digest) = self.put(bytes, encoding.content_type()) else {
+            return false;
+        };
+        // Safe to unwrap, a `BlobReference` always serializes.
+        *value = serde_json::to_value(BlobReference { digest, size }).unwrap();
+        true
+    }
+}
+
+/// Middleware replacing large response fields with blob references for clients that opt in.
+pub async fn replace_large_fields(
+    State(store): State<Arc<BlobStore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let opted_in = request.method() == Method::POST
+        && request
+            .headers()
+            .get(BLOB_REFERENCES_HEADER)
+            .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"true"));
+    let response = next.run(request).await;
+    if !opted_in {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let Ok(mut value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    if !store.replace_large_fields(&mut value) {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    // Safe to unwrap, a `Value` always serializes.
+    let body = serde_json::to_vec(&value).unwrap();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Serve the contents of a blob referenced by an earlier response.
+pub async fn get_blob(
+    State(store): State<Arc<BlobStore>>,
+    Path(digest): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(blob) = store.get(&digest) else {
+        return (StatusCode::NOT_FOUND, "blob not found").into_response();
+    };
+
+    let etag = format!("\"{}\"", digest.to_ascii_lowercase());
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, IMMUTABLE_CACHE_CONTROL.to_owned()),
+    ];
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|v| v.as_bytes() == etag.as_bytes())
+    {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    (
+        cache_headers,
+        [(header::CONTENT_TYPE, blob.content_type)],
+        blob.bytes,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store(min_size: usize, cache_size: usize) -> BlobStore {
+        BlobStore::new(JsonRpcBlobConfig {
+            min_size,
+            cache_size,
+        })
+    }
+
+    #[test]
+    fn test_replace_large_fields() {
+        let store = store(4, 1024);
+        let large = vec![7u8; 8];
+        let mut value = json!({
+            "jsonrpc": "2.0",
+            "result": [{
+                "data": {
+                    "bcs": {
+                        "dataType": "moveObject",
+                        "bcsBytes": Base64::encode(&large),
+                        "version": 1,
+                    },
+                    "content": {
+                        "dataType": "package",
+                        "disassembled": { "m": "module m {}", "n": "" },
+                    },
+                },
+            }, {
+                "data": {
+                    "bcs": { "dataType": "package", "moduleMap": { "m": Base64::encode([1, 2]) } },
+                },
+            }, {
+                "digest": "tx",
+                "rawTransaction": Base64::encode(&large),
+            }],
+        });
+
+        assert!(store.replace_large_fields(&mut value));
+
+        let result = &value["result"];
+        let reference: BlobReference =
+            serde_json::from_value(result[0]["data"]["bcs"]["bcsBytes"].clone()).unwrap();
+        assert_eq!(reference.size, 8);
+        assert_eq!(
+            result[0]["data"]["bcs"]["bcsBytes"]["type"],
+            "blobReference"
+        );
+        assert_eq!(result[0]["data"]["bcs"]["version"], 1);
+        assert_eq!(store.get(&reference.digest).unwrap().bytes, large);
+
+        let disassembled = &result[0]["data"]["content"]["disassembled"];
+        assert_eq!(disassembled["m"]["type"], "blobReference");
+        assert_eq!(disassembled["n"], "");
+
+        // Below the minimum size, so left inline.
+        assert_eq!(
+            result[1]["data"]["bcs"]["moduleMap"]["m"],
+            Base64::encode([1, 2])
+        );
+
+        assert_eq!(
+            result[2]["rawTransaction"]["digest"],
+            json!(reference.digest)
+        );
+    }
+
+    /// Move values and event payloads are user data: a field that happens to share a name with a
+    /// large field is returned as is.
+    #[test]
+    fn test_user_fields_untouched() {
+        let store = store(4, 1024);
+        let large = Base64::encode(vec![7u8; 8]);
+        let mut value = json!({
+            "result": {
+                "data": {
+                    "content": {
+                        "dataType": "moveObject",
+                        "fields": {
+                            "bcsBytes": large,
+                            "inner": { "dataType": "moveObject", "bcsBytes": large },
+                        },
+                    },
+                },
+                "events": [{
+                    "parsedJson": {
+                        "dataType": "package",
+                        "moduleMap": { "m": large },
+                        "rawTransaction": large,
+                        "digest": "x",
+                    },
+                }],
+                // Not raw object data, so not object BCS.
+                "other": { "bcsBytes": large },
+            },
+        });
+        let original = value.clone();
+
+        assert!(!store.replace_large_fields(&mut value));
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let store = store(0, 8);
+        let first = store.put(vec![1; 4], "").unwrap();
+        let second = store.put(vec![2; 4], "").unwrap();
+        assert_eq!(store.put(vec![1; 4], ""), Some(first.clone()));
+
+        // Evicts the oldest blob to make room.
+        let third = store.put(vec![3; 4], "").unwrap();
+        assert!(store.get(&first).is_none());
+        assert!(store.get(&second).is_some());
+        assert!(store.get(&third).is_some());
+
+        // Never fits.
+        assert!(store.put(vec![4; 9], "").is_none());
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::Cell;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::RpcModule;
+use jsonrpsee::core::RpcResult;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::de::DeserializeOwned;
+use sui_core::authority::AuthorityState;
+use sui_core::execution_cache::ObjectCacheRead;
+use sui_json_rpc_api::{DeepBookApiOpenRpc, DeepBookApiServer, cap_page_limit};
+use sui_json_rpc_types::{DeepBookOrderBook, DeepBookPriceLevel};
+use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::collection_types::LinkedTableNode;
+use sui_types::deepbook::{CritbitTree, InternalNode, Leaf, Order, Pool, TickLevel};
+use sui_types::dynamic_field::{Field, derive_dynamic_field_id};
+use sui_types::object::Object;
+use tracing::instrument;
+
+use crate::authority_state::StateRead;
+use crate::error::{Error, SuiRpcInputError};
+use crate::{SuiRpcModule, with_tracing};
+
+const DEFAULT_ORDER_BOOK_DEPTH: usize = 20;
+
+/// Maximum number of order book entries read for a single snapshot. Each price level reads one
+/// entry per open order, so a few deep levels can otherwise make a snapshot arbitrarily expensive.
+const MAX_ORDER_BOOK_READS: usize = 10_000;
+
+pub struct DeepBookApi {
+    state: Arc<dyn StateRead>,
+}
+
+impl DeepBookApi {
+    pub fn new(state: Arc<AuthorityState>) -> Self {
+        Self { state }
+    }
+}
+
+impl SuiRpcModule for DeepBookApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        DeepBookApiOpenRpc::module_doc()
+    }
+}
+
+#[async_trait]
+impl DeepBookApiServer for DeepBookApi {
+    async fn ping(&self) -> RpcResult<String> {
+        Ok("pong".to_string())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_order_book(
+        &self,
+        pool_id: ObjectID,
+        depth: Option<usize>,
+        pool_version: Option<SequenceNumber>,
+    ) -> RpcResult<DeepBookOrderBook> {
+        with_tracing!(async move {
+            let depth = cap_page_limit(Some(depth.unwrap_or(DEFAULT_ORDER_BOOK_DEPTH)));
+            let object = match pool_version {
+                Some(version) => self
+                    .state
+                    .get_past_object_read(&pool_id, version)
+                    .map_err(Error::from)?
+                    .into_object()?,
+                None => self
+                    .state
+                    .get_object(&pool_id)
+                    .await
+                    .map_err(Error::from)?
+                    .ok_or_else(|| {
+                        SuiRpcInputError::GenericNotFound(format!("Object {pool_id} not found"))
+                    })?,
+            };
+
+            let type_ = object
+                .type_()
+                .map(|type_| StructTag::from(type_.clone()))
+                .ok_or_else(|| not_a_pool(pool_id))?;
+            let (base_asset, quote_asset) =
+                Pool::asset_types(&type_).ok_or_else(|| not_a_pool(pool_id))?;
+            let pool: Pool = move_contents(&object)?;
+
+            // The snapshot is the state of the order book as of the transaction that wrote this
+            // version of the pool, so it is pinned to that transaction's checkpoint, rather than
+            // to whichever checkpoint the node happens to have executed up to.
+            let checkpoint = self
+                .state
+                .deprecated_get_transaction_checkpoint(&object.previous_transaction)
+                .map_err(Error::from)?
+                .map(|(_, checkpoint)| checkpoint);
+
+            // Every change to the order book mutates the pool, so its dynamic fields are read at
+            // or below the pool's version for a consistent snapshot.
+            let reader = OrderBookReader {
+                cache: self.state.get_cache_reader().as_ref(),
+                pool_version: object.version(),
+                reads: Cell::new(0),
+            };
+            Ok(DeepBookOrderBook {
+                pool_id,
+                pool_version: object.version(),
+                checkpoint,
+                base_asset: base_asset.to_canonical_string(/* with_prefix */ true),
+                quote_asset: quote_asset.to_canonical_string(/* with_prefix */ true),
+                tick_size: pool.tick_size,
+                lot_size: pool.lot_size,
+                taker_fee_rate: pool.taker_fee_rate,
+                maker_rebate_rate: pool.maker_rebate_rate,
+                bids: reader.price_levels(&pool.bids, true, depth)?,
+                asks: reader.price_levels(&pool.asks, false, depth)?,
+            })
+        })
+    }
+}
+
+/// Reads a pool's order book tables from the object cache, as of a version of the pool.
+struct OrderBookReader<'a> {
+    cache: &'a dyn ObjectCacheRead,
+    pool_version: SequenceNumber,
+    /// Number of entries read so far, bounded by `MAX_ORDER_BOOK_READS`.
+    reads: Cell<usize>,
+}
+
+impl OrderBookReader<'_> {
+    fn table_entry<V: DeserializeOwned>(&self, table: ObjectID, key: u64) -> Result<V, Error> {
+        let reads = self.reads.get() + 1;
+        if reads > MAX_ORDER_BOOK_READS {
+            return Err(SuiRpcInputError::SizeLimitExceeded(format!(
+                "{MAX_ORDER_BOOK_READS} order book entries, request fewer price levels"
+            ))
+            .into());
+        }
+        self.reads.set(reads);
+
+        let field_id = derive_dynamic_field_id(table, &TypeTag::U64, &bcs::to_bytes(&key)?)?;
+        let object = self
+            .cache
+            .find_object_lt_or_eq_version(field_id, self.pool_version)
+            .ok_or_else(|| {
+                Error::UnexpectedError(format!(
+                    "Order book entry {field_id} not found at pool version {}",
+                    self.pool_version
+                ))
+            })?;
+        let field: Field<u64, V> = move_contents(&object)?;
+        Ok(field.value)
+    }
+
+    /// Aggregate up to `depth` price levels of `tree`, best price first.
+    fn price_levels(
+        &self,
+        tree: &CritbitTree,
+        is_bid: bool,
+        depth: usize,
+    ) -> Result<Vec<DeepBookPriceLevel>, Error> {
+        let leaves = tree.leaf_indices(is_bid, depth, |index| {
+            self.table_entry::<InternalNode>(tree.internal_nodes.id, index)
+        })?;
+
+        leaves
+            .into_iter()
+            .map(|index| {
+                let leaf: Leaf<TickLevel> = self.table_entry(tree.leaves.id, index)?;
+                let mut level = DeepBookPriceLevel {
+                    price: leaf.key,
+                    quantity: 0,
+                    order_count: 0,
+                };
+                let mut next = leaf.value.open_orders.head;
+                while let Some(order_id) = next {
+                    let node: LinkedTableNode<u64, Order> =
+                        self.table_entry(leaf.value.open_orders.id, order_id)?;
+                    level.quantity = level.quantity.saturating_add(node.value.quantity);
+                    level.order_count += 1;
+                    next = node.next;
+                }
+                Ok(level)
+            })
+            .collect()
+    }
+}
+
+fn move_contents<T: DeserializeOwned>(object: &Object) -> Result<T, Error> {
+    let move_object = object.data.try_as_move().ok_or_else(|| {
+        Error::UnexpectedError(format!("Object {} is not a Move object", object.id()))
+    })?;
+    Ok(bcs::from_bytes(move_object.contents())?)
+}
+
+fn not_a_pool(pool_id: ObjectID) -> Error {
+    SuiRpcInputError::GenericInvalid(format!("Object {pool_id} is not a DeepBook pool")).into()
+}
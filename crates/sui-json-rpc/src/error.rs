@@ -120,6 +120,7 @@ impl From<Error> for ErrorObjectOwned {
         match e {
             Error::UserInputError(_) => invalid_params(e),
             Error::UnsupportedFeature(_) => invalid_params(e),
+            Error::RPCError(err) => err,
             Error::SuiObjectResponseError(err) => match err {
                 SuiObjectResponseError::NotExists { .. }
                 | SuiObjectResponseError::DynamicFieldNotFound { .. }
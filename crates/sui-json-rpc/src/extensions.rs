@@ -0,0 +1,316 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! App-specific JSON-RPC methods, served alongside the node's own APIs.
+//!
+//! An extension owns a namespace, e.g. `kiosk`, and only exposes methods prefixed with it, e.g.
+//! `kiosk_getKiosk`. Extensions are compiled into the node binary, typically behind a cargo
+//! feature of the crate defining them, and made available with [`register_rpc_extension`] before
+//! the node starts. Operators then choose which of the available extensions to serve, by
+//! namespace, with `jsonrpc-extensions` in the node config.
+//!
+//! Extensions only get read access to the node's state, through the [`RpcObjectReader`] in
+//! [`RpcExtensionContext`]: they cannot execute transactions, write to the node's stores, or
+//! otherwise change the state of its caches.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use jsonrpsee::Methods;
+use once_cell::sync::Lazy;
+use sui_core::execution_cache::ObjectCacheRead;
+use sui_open_rpc::Module;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber};
+use sui_types::error::SuiResult;
+use sui_types::object::Object;
+use sui_types::storage::{ObjectKey, ObjectStore, PackageObject};
+use sui_types::sui_system_state::SuiSystemState;
+
+use crate::error::Error;
+
+/// Namespaces of the node's own APIs, which extensions cannot use.
+const RESERVED_NAMESPACES: &[&str] = &["rpc", "sui", "suix", "unsafe"];
+
+static EXTENSIONS: Lazy<Mutex<BTreeMap<&'static str, Arc<dyn RpcExtension>>>> =
+    Lazy::new(Default::default);
+
+pub trait RpcExtension: Send + Sync {
+    /// The prefix of the extension's methods, made of lowercase ASCII letters and digits.
+    fn namespace(&self) -> &'static str;
+
+    /// The extension's methods, reading the node's state through `context`. Every method name
+    /// must start with the namespace followed by `_`.
+    fn methods(&self, context: RpcExtensionContext) -> Methods;
+
+    /// Documentation of the extension's methods, added to the node's OpenRPC spec.
+    fn rpc_doc_module(&self) -> Option<Module> {
+        None
+    }
+}
+
+/// Read-only access to the node's state, given to extensions.
+#[derive(Clone)]
+pub struct RpcExtensionContext {
+    object_reader: RpcObjectReader,
+}
+
+/// Reads objects through the node's object cache. Only exposes the cache's reads, and not the
+/// operations that change what it holds, such as reloading the system packages.
+#[derive(Clone)]
+pub struct RpcObjectReader {
+    object_cache: Arc<dyn ObjectCacheRead>,
+}
+
+impl RpcExtensionContext {
+    pub fn new(object_cache: Arc<dyn ObjectCacheRead>) -> Self {
+        Self {
+            object_reader: RpcObjectReader { object_cache },
+        }
+    }
+
+    pub fn object_reader(&self) -> &RpcObjectReader {
+        &self.object_reader
+    }
+}
+
+impl RpcObjectReader {
+    /// The latest version of the object, if it is live.
+    pub fn get_object(&self, id: &ObjectID) -> Option<Object> {
+        self.object_cache.get_object(id)
+    }
+
+    /// The latest versions of the objects, for those that are live.
+    pub fn get_objects(&self, ids: &[ObjectID]) -> Vec<Option<Object>> {
+        self.object_cache.get_objects(ids)
+    }
+
+    pub fn get_object_by_key(&self, id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        self.object_cache.get_object_by_key(id, version)
+    }
+
+    pub fn multi_get_objects_by_key(&self, keys: &[ObjectKey]) -> Vec<Option<Object>> {
+        self.object_cache.multi_get_objects_by_key(keys)
+    }
+
+    /// The reference to the latest version of the object, which is a tombstone if the object was
+    /// deleted or wrapped.
+    pub fn get_latest_object_ref_or_tombstone(&self, id: ObjectID) -> Option<ObjectRef> {
+        self.object_cache.get_latest_object_ref_or_tombstone(id)
+    }
+
+    /// The latest version of the object at or before `version`, e.g. to read a child object at
+    /// the version of its parent.
+    pub fn find_object_lt_or_eq_version(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Option<Object> {
+        self.object_cache.find_object_lt_or_eq_version(id, version)
+    }
+
+    pub fn get_package_object(&self, id: &ObjectID) -> SuiResult<Option<PackageObject>> {
+        self.object_cache.get_package_object(id)
+    }
+
+    pub fn get_sui_system_state(&self) -> SuiResult<SuiSystemState> {
+        self.object_cache.get_sui_system_state_object_unsafe()
+    }
+}
+
+impl ObjectStore for RpcObjectReader {
+    fn get_object(&self, object_id: &ObjectID) -> Option<Object> {
+        RpcObjectReader::get_object(self, object_id)
+    }
+
+    fn get_object_by_key(&self, object_id: &ObjectID, version: SequenceNumber) -> Option<Object> {
+        RpcObjectReader::get_object_by_key(self, object_id, version)
+    }
+}
+
+/// Make `extension` available to nodes started by this process. Fails if its namespace is invalid
+/// or already taken.
+pub fn register_rpc_extension(extension: Arc<dyn RpcExtension>) -> Result<(), Error> {
+    let namespace = extension.namespace();
+    check_namespace(namespace)?;
+
+    let mut extensions = EXTENSIONS.lock().unwrap();
+    if extensions.contains_key(namespace) {
+        return Err(Error::UnexpectedError(format!(
+            "JSON-RPC extension namespace '{namespace}' is already registered"
+        )));
+    }
+    extensions.insert(namespace, extension);
+    Ok(())
+}
+
+/// The registered extension with the given namespace.
+pub fn rpc_extension(namespace: &str) -> Option<Arc<dyn RpcExtension>> {
+    EXTENSIONS.lock().unwrap().get(namespace).cloned()
+}
+
+/// The namespaces of the registered extensions.
+pub fn registered_rpc_extensions() -> Vec<&'static str> {
+    EXTENSIONS.lock().unwrap().keys().copied().collect()
+}
+
+/// The methods of `extension`, checked to all be in its namespace.
+pub(crate) fn extension_methods(
+    extension: &dyn RpcExtension,
+    context: RpcExtensionContext,
+) -> Result<Methods, Error> {
+    let namespace = extension.namespace();
+    check_namespace(namespace)?;
+    let methods = extension.methods(context);
+    check_method_names(namespace, &methods)?;
+    Ok(methods)
+}
+
+fn check_namespace(namespace: &str) -> Result<(), Error> {
+    if namespace.is_empty()
+        || !namespace
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err(Error::UnexpectedError(format!(
+            "Invalid JSON-RPC extension namespace '{namespace}', expected lowercase ASCII \
+             letters and digits"
+        )));
+    }
+    if RESERVED_NAMESPACES.contains(&namespace) {
+        return Err(Error::UnexpectedError(format!(
+            "JSON-RPC extension namespace '{namespace}' is reserved for the node's own APIs"
+        )));
+    }
+    Ok(())
+}
+
+fn check_method_names(namespace: &str, methods: &Methods) -> Result<(), Error> {
+    let prefix = format!("{namespace}_");
+    if let Some(method) = methods
+        .method_names()
+        .find(|method| !method.starts_with(&prefix) || method.len() == prefix.len())
+    {
+        return Err(Error::UnexpectedError(format!(
+            "JSON-RPC extension method '{method}' is outside of the extension's namespace \
+             '{namespace}'"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http;
+    use jsonrpsee::RpcModule;
+    use jsonrpsee::types::ErrorObjectOwned;
+    use prometheus::Registry;
+    use serde_json::{Value, json};
+    use sui_core::authority::test_authority_builder::TestAuthorityBuilder;
+    use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{JsonRpcServerBuilder, ServerType};
+
+    /// Serves the version of an object, read through the context.
+    struct VersionExtension;
+
+    impl RpcExtension for VersionExtension {
+        fn namespace(&self) -> &'static str {
+            "versiontest"
+        }
+
+        fn methods(&self, context: RpcExtensionContext) -> Methods {
+            let mut module = RpcModule::new(context);
+            module
+                .register_method("versiontest_getVersion", |params, context, _| {
+                    let id: ObjectID = params.one()?;
+                    let object = context.object_reader().get_object(&id);
+                    Ok::<_, ErrorObjectOwned>(object.map(|o| o.version().value()))
+                })
+                .unwrap();
+            module.into()
+        }
+    }
+
+    fn methods(names: &[&'static str]) -> Methods {
+        let mut module = RpcModule::new(());
+        for name in names.iter().copied() {
+            module
+                .register_method(name, |_, _, _| Ok::<_, ErrorObjectOwned>("pong"))
+                .unwrap();
+        }
+        module.into()
+    }
+
+    #[test]
+    fn extensions_stay_in_their_namespace() {
+        check_namespace("kiosk").unwrap();
+        check_namespace("deepbook3").unwrap();
+        for namespace in ["", "Kiosk", "my_app", "sui", "suix", "unsafe"] {
+            assert!(check_namespace(namespace).is_err(), "{namespace}");
+        }
+
+        check_method_names("kiosk", &methods(&["kiosk_getKiosk", "kiosk_ping"])).unwrap();
+        for names in [
+            &["sui_getObject"][..],
+            &["kiosk_ping", "kiosks_ping"],
+            &["kiosk_"],
+        ] {
+            assert!(
+                check_method_names("kiosk", &methods(names)).is_err(),
+                "{names:?}"
+            );
+        }
+    }
+
+    async fn call(router: &axum::Router, method: &str, params: Value) -> Value {
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+        let request = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_serve_extension() {
+        let state = TestAuthorityBuilder::new().build().await;
+        let object_cache = state.get_object_cache_reader().clone();
+        let framework = object_cache.get_object(&SUI_FRAMEWORK_PACKAGE_ID).unwrap();
+
+        register_rpc_extension(Arc::new(VersionExtension)).unwrap();
+        assert!(registered_rpc_extensions().contains(&"versiontest"));
+        assert!(register_rpc_extension(Arc::new(VersionExtension)).is_err());
+
+        let extension = rpc_extension("versiontest").unwrap();
+        let mut builder = JsonRpcServerBuilder::new("1.0.0", &Registry::new(), None, None);
+        builder
+            .register_extension(extension.as_ref(), RpcExtensionContext::new(object_cache))
+            .unwrap();
+        let router = builder.to_router(ServerType::Http).await.unwrap();
+
+        let response = call(
+            &router,
+            "versiontest_getVersion",
+            json!([SUI_FRAMEWORK_PACKAGE_ID]),
+        )
+        .await;
+        assert_eq!(
+            response["result"],
+            json!(framework.version().value()),
+            "{response}"
+        );
+
+        let response = call(&router, "versiontest_getVersion", json!([ObjectID::ZERO])).await;
+        assert_eq!(response["result"], Value::Null, "{response}");
+    }
+}
@@ -0,0 +1,414 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use axum::body::{Body, to_bytes};
+use axum::extract::Request;
+use axum::http::{Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::Value;
+
+/// Request header listing the response fields a client wants, as comma-separated dotted paths
+/// relative to each result item, e.g. `digest,effects.status`.
+pub const FIELD_MASK_HEADER: &str = "field-mask";
+
+/// The `show*` options of transaction block reads, and the response field each one controls.
+const TRANSACTION_OPTIONS: &[(&str, &str)] = &[
+    ("showInput", "transaction"),
+    ("showRawInput", "rawTransaction"),
+    ("showEffects", "effects"),
+    ("showEvents", "events"),
+    ("showObjectChanges", "objectChanges"),
+    ("showBalanceChanges", "balanceChanges"),
+    ("showRawEffects", "rawEffects"),
+];
+
+/// The `show*` options of object reads, and the field of the object data each one controls.
+const OBJECT_OPTIONS: &[(&str, &str)] = &[
+    ("showType", "type"),
+    ("showOwner", "owner"),
+    ("showPreviousTransaction", "previousTransaction"),
+    ("showDisplay", "display"),
+    ("showContent", "content"),
+    ("showBcs", "bcs"),
+    ("showStorageRebate", "storageRebate"),
+];
+
+/// Where a read method takes its `show*` options, so that the sections a mask drops are not
+/// computed and serialized in the first place.
+struct MethodOptions {
+    /// Position and name of the parameter holding the options.
+    param: (usize, &'static str),
+    /// Whether the parameter is a query, holding the options under `options`.
+    in_query: bool,
+    /// The field of each result item that the options control the fields of, if not the item.
+    within: Option<&'static str>,
+    options: &'static [(&'static str, &'static str)],
+}
+
+impl MethodOptions {
+    fn of(method: &str) -> Option<Self> {
+        let (param, in_query, within, options) = match method {
+            "sui_getTransactionBlock" | "sui_multiGetTransactionBlocks" => {
+                ((1, "options"), false, None, TRANSACTION_OPTIONS)
+            }
+            "suix_queryTransactionBlocks" => ((0, "query"), true, None, TRANSACTION_OPTIONS),
+            "sui_getObject" | "sui_multiGetObjects" => {
+                ((1, "options"), false, Some("data"), OBJECT_OPTIONS)
+            }
+            "suix_getOwnedObjects" => ((1, "query"), true, Some("data"), OBJECT_OPTIONS),
+            _ => return None,
+        };
+        Some(Self {
+            param,
+            in_query,
+            within,
+            options,
+        })
+    }
+
+    /// The options object of a call's `params`, if the call passes one.
+    fn options_mut<'a>(&self, params: &'a mut Value) -> Option<&'a mut Value> {
+        let (index, name) = self.param;
+        let param = match params {
+            Value::Array(params) => params.get_mut(index)?,
+            Value::Object(params) => params.get_mut(name)?,
+            _ => return None,
+        };
+        let options = if self.in_query {
+            param.get_mut("options")?
+        } else {
+            param
+        };
+        options.is_object().then_some(options)
+    }
+}
+
+/// The set of fields to keep in a response. Each key is kept along with the fields selected by
+/// its own mask, and a key whose mask is empty is kept whole.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FieldMask(BTreeMap<String, FieldMask>);
+
+impl FieldMask {
+    pub fn parse(paths: &str) -> Self {
+        let mut mask = Self::default();
+        for path in paths.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            mask.insert(path.split('.').filter(|s| !s.is_empty()));
+        }
+        mask
+    }
+
+    fn insert<'a>(&mut self, mut path: impl Iterator<Item = &'a str>) {
+        let Some(field) = path.next() else {
+            return;
+        };
+        let mut path = path.peekable();
+        let is_last = path.peek().is_none();
+        match self.0.get_mut(field) {
+            // Already kept whole.
+            Some(child) if child.0.is_empty() => {}
+            Some(child) if is_last => child.0.clear(),
+            Some(child) => child.insert(path),
+            None => {
+                let mut child = Self::default();
+                child.insert(path);
+                self.0.insert(field.to_owned(), child);
+            }
+        }
+    }
+
+    /// Whether this mask keeps `field` of the value it applies to.
+    fn selects(&self, field: &str) -> bool {
+        self.0.is_empty() || self.0.contains_key(field)
+    }
+
+    /// Turn off the `show*` options of a JSON-RPC call whose sections this mask would drop from
+    /// the response anyway. Options are only ever turned off, never on. Returns whether the call
+    /// changed.
+    fn narrow_call(&self, call: &mut Value) -> bool {
+        let Some(method) = call.get("method").and_then(Value::as_str) else {
+            return false;
+        };
+        let Some(method) = MethodOptions::of(method) else {
+            return false;
+        };
+        let Some(options) = call
+            .get_mut("params")
+            .and_then(|params| method.options_mut(params))
+        else {
+            return false;
+        };
+
+        let selects = |field: &str| match method.within {
+            None => self.selects(field),
+            Some(within) => self.0.get(within).is_some_and(|mask| mask.selects(field)),
+        };
+        let mut narrowed = false;
+        for (option, field) in method.options {
+            if let Some(show) = options.get_mut(*option)
+                && show.as_bool() == Some(true)
+                && !selects(*field)
+            {
+                *show = Value::Bool(false);
+                narrowed = true;
+            }
+        }
+        narrowed
+    }
+
+    /// Narrow every call of a JSON-RPC request, single or batch. Returns whether it changed.
+    fn narrow_request(&self, request: &mut Value) -> bool {
+        match request {
+            Value::Array(calls) => calls
+                .iter_mut()
+                .fold(false, |narrowed, call| self.narrow_call(call) | narrowed),
+            call => self.narrow_call(call),
+        }
+    }
+
+    /// Drop every field of `value` not selected by this mask. Arrays are masked element-wise.
+    pub fn apply(&self, value: &mut Value) {
+        if self.0.is_empty() {
+            return;
+        }
+        match value {
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply(item)),
+            Value::Object(fields) => fields.retain(|key, field| match self.0.get(key) {
+                Some(child) => {
+                    child.apply(field);
+                    true
+                }
+                None => false,
+            }),
+            _ => {}
+        }
+    }
+
+    /// Mask the result of a JSON-RPC response, or of every response of a batch. For paginated
+    /// results the mask applies to each item of the page, leaving the cursor fields in place.
+    fn apply_to_response(&self, response: &mut Value) {
+        if let Value::Array(responses) = response {
+            responses
+                .iter_mut()
+                .for_each(|response| self.apply_to_response(response));
+            return;
+        }
+        let Some(result) = response.get_mut("result") else {
+            return;
+        };
+        let is_page = result.get("hasNextPage").is_some();
+        match result.get_mut("data") {
+            Some(data) if is_page => self.apply(data),
+            _ => self.apply(result),
+        }
+    }
+}
+
+/// Middleware dropping the response fields a client did not ask for in the [`FIELD_MASK_HEADER`].
+///
+/// Read calls whose `show*` options select sections the mask drops have those options turned off
+/// before the call is served, so the server does not load and serialize them only for the mask to
+/// throw them away.
+pub async fn mask_response_fields(request: Request, next: Next) -> Response {
+    let mask = (request.method() == Method::POST)
+        .then(|| request.headers().get(FIELD_MASK_HEADER))
+        .flatten()
+        .and_then(|v| v.to_str().ok())
+        .map(FieldMask::parse)
+        .filter(|mask| !mask.0.is_empty());
+    let Some(mask) = mask else {
+        return next.run(request).await;
+    };
+
+    let (mut parts, body) = request.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let body = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(mut calls) if mask.narrow_request(&mut calls) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            // Safe to unwrap, a `Value` always serializes.
+            Body::from(serde_json::to_vec(&calls).unwrap())
+        }
+        _ => Body::from(bytes),
+    };
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let Ok(mut value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    mask.apply_to_response(&mut value);
+
+    // Safe to unwrap, a `Value` always serializes.
+    let body = serde_json::to_vec(&value).unwrap();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        let mask = FieldMask::parse(" digest, effects.status ,effects.gasUsed.computationCost,,");
+        assert_eq!(
+            mask,
+            FieldMask::parse("effects.gasUsed.computationCost,digest,effects.status")
+        );
+
+        // A shorter path keeps the whole field, whichever order the paths come in.
+        assert_eq!(
+            FieldMask::parse("effects.status,effects"),
+            FieldMask::parse("effects")
+        );
+        assert_eq!(
+            FieldMask::parse("effects,effects.status"),
+            FieldMask::parse("effects")
+        );
+    }
+
+    #[test]
+    fn test_apply_to_response() {
+        let mask = FieldMask::parse("digest,effects.status,events.type");
+        let mut response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "data": [{
+                    "digest": "A",
+                    "effects": { "status": { "status": "success" }, "gasUsed": {} },
+                    "events": [{ "type": "0x2::m::E", "parsedJson": {} }],
+                    "checkpoint": "5",
+                }],
+                "nextCursor": "A",
+                "hasNextPage": false,
+            },
+        });
+
+        mask.apply_to_response(&mut response);
+        assert_eq!(
+            response,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "data": [{
+                        "digest": "A",
+                        "effects": { "status": { "status": "success" } },
+                        "events": [{ "type": "0x2::m::E" }],
+                    }],
+                    "nextCursor": "A",
+                    "hasNextPage": false,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_to_batch_response() {
+        let mask = FieldMask::parse("digest");
+        let mut response = json!([
+            { "jsonrpc": "2.0", "id": 1, "result": { "digest": "A", "checkpoint": "5" } },
+            { "jsonrpc": "2.0", "id": 2, "error": { "code": -32602, "message": "invalid" } },
+        ]);
+
+        mask.apply_to_response(&mut response);
+        assert_eq!(
+            response,
+            json!([
+                { "jsonrpc": "2.0", "id": 1, "result": { "digest": "A" } },
+                { "jsonrpc": "2.0", "id": 2, "error": { "code": -32602, "message": "invalid" } },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_narrow_request() {
+        let mask = FieldMask::parse("digest,effects.status");
+        let mut request = json!([{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sui_getTransactionBlock",
+            "params": ["A", { "showEffects": true, "showEvents": true, "showInput": false }],
+        }, {
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "suix_queryTransactionBlocks",
+            "params": { "query": { "options": { "showEvents": true } }, "limit": 5 },
+        }, {
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "sui_getCheckpoint",
+            "params": ["5"],
+        }]);
+
+        assert!(mask.narrow_request(&mut request));
+        assert_eq!(
+            request[0]["params"][1],
+            json!({ "showEffects": true, "showEvents": false, "showInput": false })
+        );
+        assert_eq!(
+            request[1]["params"]["query"]["options"],
+            json!({ "showEvents": false })
+        );
+        assert_eq!(request[2]["params"], json!(["5"]));
+
+        // Already as narrow as the mask.
+        assert!(!mask.narrow_request(&mut request));
+    }
+
+    #[test]
+    fn test_narrow_object_request() {
+        let options = json!({ "showType": true, "showContent": true, "showBcs": true });
+        let call = |options: &Value| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "suix_getOwnedObjects",
+                "params": ["0x1", { "options": options }],
+            })
+        };
+
+        // Object options control the fields of each item's object data.
+        let mut request = call(&options);
+        assert!(FieldMask::parse("data.objectId,data.type").narrow_request(&mut request));
+        assert_eq!(
+            request["params"][1]["options"],
+            json!({ "showType": true, "showContent": false, "showBcs": false })
+        );
+
+        // The whole object data is kept, so no option is turned off.
+        let mut request = call(&options);
+        assert!(!FieldMask::parse("data").narrow_request(&mut request));
+
+        // The object data is dropped, so none of it needs computing.
+        let mut request = call(&options);
+        assert!(FieldMask::parse("error").narrow_request(&mut request));
+        assert_eq!(
+            request["params"][1]["options"],
+            json!({ "showType": false, "showContent": false, "showBcs": false })
+        );
+    }
+
+    #[test]
+    fn test_apply_to_error_response() {
+        let mask = FieldMask::parse("digest");
+        let mut response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32602, "message": "invalid params" },
+        });
+        let expected = response.clone();
+
+        mask.apply_to_response(&mut response);
+        assert_eq!(response, expected);
+    }
+}
@@ -15,7 +15,11 @@ use mysten_common::ZipDebugEqIteratorExt;
 use tracing::{info, instrument};
 
 use sui_core::authority::AuthorityState;
+use sui_core::congestion_tracker::{CheckpointGasPrices, MAX_RECENT_CONGESTED_CHECKPOINTS};
 use sui_json_rpc_api::{GovernanceReadApiOpenRpc, GovernanceReadApiServer, JsonRpcMetrics};
+use sui_json_rpc_types::{
+    CheckpointCongestionGasPrices, CongestedObjectGasPrices, GasPriceDistribution,
+};
 use sui_json_rpc_types::{DelegatedStake, Stake, StakeStatus};
 use sui_json_rpc_types::{SuiCommittee, ValidatorApy, ValidatorApys};
 use sui_open_rpc::Module;
@@ -36,6 +40,8 @@ use crate::authority_state::StateRead;
 use crate::error::{Error, RpcInterimResult, SuiRpcInputError};
 use crate::{ObjectProvider, SuiRpcModule, with_tracing};
 
+const DEFAULT_CONGESTION_GAS_PRICES_LIMIT: usize = 20;
+
 #[derive(Clone)]
 pub struct GovernanceReadApi {
     state: Arc<dyn StateRead>,
@@ -249,6 +255,26 @@ impl GovernanceReadApiServer for GovernanceReadApi {
         })
     }
 
+    #[instrument(skip(self))]
+    async fn get_congestion_gas_prices(
+        &self,
+        object_ids: Option<Vec<ObjectID>>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<CheckpointCongestionGasPrices>> {
+        with_tracing!(async move {
+            let limit = limit
+                .unwrap_or(DEFAULT_CONGESTION_GAS_PRICES_LIMIT)
+                .min(MAX_RECENT_CONGESTED_CHECKPOINTS);
+            Ok(self
+                .state
+                .get_recent_congestion_gas_prices(MAX_RECENT_CONGESTED_CHECKPOINTS)
+                .into_iter()
+                .filter_map(|prices| congestion_gas_prices(prices, object_ids.as_deref()))
+                .take(limit)
+                .collect())
+        })
+    }
+
     #[instrument(skip(self))]
     async fn get_validators_apy(&self) -> RpcResult<ValidatorApys> {
         info!("get_validator_apy");
@@ -477,6 +503,32 @@ fn backfill_rates(
     filled_rates
 }
 
+/// Converts the gas prices recorded for a checkpoint, keeping only `object_ids` if given. Returns
+/// None if no requested object was congested in the checkpoint.
+fn congestion_gas_prices(
+    prices: CheckpointGasPrices,
+    object_ids: Option<&[ObjectID]>,
+) -> Option<CheckpointCongestionGasPrices> {
+    let objects = prices
+        .objects
+        .into_iter()
+        .filter(|(object_id, _)| object_ids.is_none_or(|ids| ids.contains(object_id)))
+        .filter_map(|(object_id, prices)| {
+            Some(CongestedObjectGasPrices {
+                object_id,
+                sequenced: GasPriceDistribution::from_sorted(&prices.sequenced),
+                cancelled: GasPriceDistribution::from_sorted(&prices.cancelled)?,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    (!objects.is_empty()).then_some(CheckpointCongestionGasPrices {
+        checkpoint: prices.checkpoint,
+        timestamp_ms: prices.timestamp_ms,
+        objects,
+    })
+}
+
 impl SuiRpcModule for GovernanceReadApi {
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
@@ -490,6 +542,7 @@ impl SuiRpcModule for GovernanceReadApi {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sui_core::congestion_tracker::ObjectGasPrices;
     use sui_types::sui_system_state::PoolTokenExchangeRate;
 
     #[test]
@@ -526,4 +579,48 @@ mod tests {
         ];
         assert_eq!(backfill_rates(rates), expected);
     }
+
+    #[test]
+    fn test_congestion_gas_prices_filters_objects() {
+        let (obj1, obj2) = (ObjectID::random(), ObjectID::random());
+        let prices = CheckpointGasPrices {
+            checkpoint: 7,
+            timestamp_ms: 1000,
+            objects: BTreeMap::from([
+                (
+                    obj1,
+                    ObjectGasPrices {
+                        sequenced: vec![300, 400],
+                        cancelled: vec![100],
+                    },
+                ),
+                (
+                    obj2,
+                    ObjectGasPrices {
+                        sequenced: vec![],
+                        cancelled: vec![200],
+                    },
+                ),
+            ]),
+        };
+
+        let all = congestion_gas_prices(prices.clone(), None).unwrap();
+        assert_eq!(all.checkpoint, 7);
+        assert_eq!(all.objects.len(), 2);
+
+        let only_obj2 = congestion_gas_prices(prices.clone(), Some(&[obj2])).unwrap();
+        assert_eq!(
+            only_obj2.objects,
+            vec![CongestedObjectGasPrices {
+                object_id: obj2,
+                sequenced: None,
+                cancelled: GasPriceDistribution::from_sorted(&[200]).unwrap(),
+            }]
+        );
+
+        assert_eq!(
+            congestion_gas_prices(prices, Some(&[ObjectID::random()])),
+            None
+        );
+    }
 }
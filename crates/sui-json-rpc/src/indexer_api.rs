@@ -21,8 +21,8 @@ use sui_json_rpc_api::{
     cap_page_limit, validate_limit,
 };
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataOptions,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
+    CheckpointObjectChanges, DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page,
+    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
     SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
 use sui_name_service::{Domain, NameRecord, NameServiceConfig, NameServiceError};
@@ -34,8 +34,9 @@ use sui_types::{
     dynamic_field::{DynamicFieldName, Field},
     error::SuiObjectResponseError,
     event::EventID,
+    full_checkpoint_content::Checkpoint,
 };
-use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, broadcast};
 use tracing::{instrument, warn};
 
 use crate::{
@@ -93,6 +94,7 @@ pub struct IndexerApi<R> {
     name_service_config: NameServiceConfig,
     pub metrics: Arc<JsonRpcMetrics>,
     subscription_semaphore: Arc<Semaphore>,
+    checkpoints: Option<broadcast::Sender<Arc<Checkpoint>>>,
 }
 
 impl<R: ReadApiServer> IndexerApi<R> {
@@ -103,6 +105,7 @@ impl<R: ReadApiServer> IndexerApi<R> {
         name_service_config: NameServiceConfig,
         metrics: Arc<JsonRpcMetrics>,
         max_subscriptions: Option<usize>,
+        checkpoints: Option<broadcast::Sender<Arc<Checkpoint>>>,
     ) -> Self {
         let max_subscriptions = max_subscriptions.unwrap_or(DEFAULT_MAX_SUBSCRIPTIONS);
         Self {
@@ -112,6 +115,7 @@ impl<R: ReadApiServer> IndexerApi<R> {
             name_service_config,
             metrics,
             subscription_semaphore: Arc::new(Semaphore::new(max_subscriptions)),
+            checkpoints,
         }
     }
 
@@ -340,6 +344,27 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         Ok(())
     }
 
+    fn subscribe_checkpoint_object_changes(
+        &self,
+        sink: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let Some(checkpoints) = &self.checkpoints else {
+            return Err("Checkpoint subscriptions are not enabled on this node".into());
+        };
+
+        let permit = self.acquire_subscribe_permit()?;
+
+        // A lagging subscriber ends the stream rather than skipping checkpoints, so that the
+        // client notices the gap in the deltas it has applied.
+        let changes = futures::stream::unfold(checkpoints.subscribe(), |mut rx| async move {
+            let checkpoint = rx.recv().await.ok()?;
+            Some((CheckpointObjectChanges::from(checkpoint.as_ref()), rx))
+        });
+
+        spawn_subscription(sink, Box::pin(changes), Some(permit));
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     async fn get_dynamic_fields(
         &self,
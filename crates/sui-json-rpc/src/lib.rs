@@ -13,33 +13,48 @@ use metrics::MetricsLayer;
 use prometheus::Registry;
 use std::time::Duration;
 use sui_core::traffic_controller::TrafficController;
+use sui_json_rpc_api::CORRELATION_ID_HEADER;
 use sui_types::traffic_control::PolicyConfig;
 use tokio::runtime::Handle;
 use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
+use tower_http::propagate_header::PropagateHeaderLayer;
 use tower_http::trace::TraceLayer;
 use tracing::info;
 
+use address_normalization::{AddressNormalizationLayer, AddressNormalizer};
 pub use balance_changes::*;
+use blob_store::BlobStore;
 pub use object_changes::*;
+use rate_limit::RateLimiter;
 pub use sui_config::node::ServerType;
+use sui_config::node::{JsonRpcAddressConfig, JsonRpcBlobConfig, JsonRpcRateLimitConfig};
 use sui_open_rpc::{Module, Project};
 use traffic_control::TrafficControllerService;
 
 use crate::error::Error;
+use crate::extensions::{RpcExtension, RpcExtensionContext};
 
+pub mod address_normalization;
 pub mod authority_state;
 mod balance_changes;
+pub mod blob_store;
 pub mod bridge_api;
 pub mod coin_api;
+pub mod deepbook_api;
 pub mod error;
+pub mod extensions;
+pub mod field_mask;
 pub mod governance_api;
 pub mod indexer_api;
 pub mod logger;
 mod metrics;
 pub mod move_utils;
 mod object_changes;
+pub mod proof_api;
+pub mod rate_limit;
 pub mod read_api;
+pub mod source_verification_api;
 mod traffic_control;
 pub mod transaction_builder_api;
 pub mod transaction_execution_api;
@@ -48,12 +63,23 @@ pub const APP_NAME_HEADER: &str = "app-name";
 
 pub const MAX_REQUEST_SIZE: u32 = 2 << 30;
 
+/// Longer correlation IDs are not recorded in logs.
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+/// The token a request carried as `Authorization: Bearer <token>`, available to methods that
+/// authenticate their callers through the request's extensions.
+#[derive(Clone, Debug)]
+pub struct BearerToken(pub String);
+
 pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     rpc_doc: Project,
     registry: Registry,
     traffic_controller: Option<Arc<TrafficController>>,
     policy_config: Option<PolicyConfig>,
+    address_config: JsonRpcAddressConfig,
+    blob_config: Option<JsonRpcBlobConfig>,
+    rate_limit_config: Option<JsonRpcRateLimitConfig>,
 }
 
 pub fn sui_rpc_doc(version: &str) -> Project {
@@ -82,14 +108,47 @@ impl JsonRpcServerBuilder {
             registry: prometheus_registry.clone(),
             traffic_controller,
             policy_config,
+            address_config: JsonRpcAddressConfig::default(),
+            blob_config: None,
+            rate_limit_config: None,
         }
     }
 
+    /// Configure how address and object ID parameters are normalized before they reach the
+    /// method handlers.
+    pub fn set_address_config(&mut self, config: JsonRpcAddressConfig) {
+        self.address_config = config;
+    }
+
+    /// Let clients opt in to receiving large response fields as references resolvable through
+    /// `GET /blobs/{digest}`.
+    pub fn set_blob_config(&mut self, config: JsonRpcBlobConfig) {
+        self.blob_config = Some(config);
+    }
+
+    /// Rate limit methods per client, and per API key for clients that send one.
+    pub fn set_rate_limit_config(&mut self, config: JsonRpcRateLimitConfig) {
+        self.rate_limit_config = Some(config);
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
     }
 
+    /// Serve the methods of an app-specific extension, which must all be in its namespace.
+    pub fn register_extension(
+        &mut self,
+        extension: &dyn RpcExtension,
+        context: RpcExtensionContext,
+    ) -> Result<(), Error> {
+        let methods = extensions::extension_methods(extension, context)?;
+        if let Some(module) = extension.rpc_doc_module() {
+            self.rpc_doc.add_module(module);
+        }
+        Ok(self.module.merge(methods)?)
+    }
+
     fn trace_layer() -> TraceLayer<
         tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>,
         impl tower_http::trace::MakeSpan<Body> + Clone,
@@ -113,10 +172,18 @@ impl JsonRpcServerBuilder {
                     .and_then(|v| v.to_str().ok())
                     .map(tracing::field::display);
 
+                let correlation_id = request
+                    .headers()
+                    .get(CORRELATION_ID_HEADER)
+                    .and_then(|v| v.to_str().ok())
+                    .filter(|id| id.len() <= MAX_CORRELATION_ID_LEN)
+                    .map(tracing::field::display);
+
                 tracing::info_span!(
                     "json-rpc-request",
                     "x-req-id" = request_id,
-                    "origin" = origin
+                    "origin" = origin,
+                    "x-correlation-id" = correlation_id
                 )
             })
             .on_request(())
@@ -142,12 +209,24 @@ impl JsonRpcServerBuilder {
 
         let metrics_clone = metrics.clone();
         let middleware = ServiceBuilder::new()
+            .layer(PropagateHeaderLayer::new(http::HeaderName::from_static(
+                CORRELATION_ID_HEADER,
+            )))
             .layer(Self::trace_layer())
             .map_request(move |mut request: http::Request<_>| {
                 metrics_clone.on_http_request(request.headers());
                 if let Some(client_id_source) = client_id_source.clone() {
                     traffic_control::determine_client_ip(client_id_source, &mut request);
                 }
+                let token = request
+                    .headers()
+                    .get(http::header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Bearer "))
+                    .map(|token| BearerToken(token.trim().to_owned()));
+                if let Some(token) = token {
+                    request.extensions_mut().insert(token);
+                }
                 request
             });
 
@@ -160,13 +239,16 @@ impl JsonRpcServerBuilder {
             .unwrap_or(60);
 
         let traffic_controller = self.traffic_controller.clone();
+        let address_normalizer =
+            AddressNormalizer::new(&self.rpc_doc, &self.address_config).map(Arc::new);
         let rpc_middleware = jsonrpsee::server::middleware::rpc::RpcServiceBuilder::new()
             .layer_fn(move |s| TimeoutLayer::new(s, Duration::from_secs(timeout)))
             .layer_fn(move |s| MetricsLayer::new(s, metrics.clone()))
             .layer_fn({
                 let traffic_controller = traffic_controller.clone();
                 move |s| TrafficControllerService::new(s, traffic_controller.clone())
-            });
+            })
+            .layer_fn(move |s| AddressNormalizationLayer::new(s, address_normalizer.clone()));
         let service_builder = jsonrpsee::server::ServerBuilder::new()
             // Since we're not using jsonrpsee's server to actually handle connections this value
             // is instead limiting the number of concurrent requests and has no impact on the
@@ -219,6 +301,35 @@ impl JsonRpcServerBuilder {
             }
         }
 
+        if !matches!(server_type, ServerType::WebSocket) {
+            router = router.layer(axum::middleware::from_fn(field_mask::mask_response_fields));
+        }
+
+        if let Some(config) = &self.blob_config
+            && !matches!(server_type, ServerType::WebSocket)
+        {
+            let store = Arc::new(BlobStore::new(config.clone()));
+            router = router
+                .layer(axum::middleware::from_fn_with_state(
+                    store.clone(),
+                    blob_store::replace_large_fields,
+                ))
+                .route(
+                    "/blobs/{digest}",
+                    axum::routing::get(blob_store::get_blob).with_state(store),
+                );
+        }
+
+        if let Some(config) = &self.rate_limit_config
+            && !matches!(server_type, ServerType::WebSocket)
+        {
+            let limiter = Arc::new(RateLimiter::new(config.clone(), &self.registry));
+            router = router.layer(axum::middleware::from_fn_with_state(
+                limiter,
+                rate_limit::limit_requests,
+            ));
+        }
+
         let app = router.layer(middleware);
 
         info!("Available JSON-RPC methods : {:?}", methods_names);
@@ -326,3 +437,98 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use jsonrpsee::types::ErrorObjectOwned;
+    use tower::ServiceExt;
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+
+    use super::*;
+
+    /// Records the correlation ID of every `json-rpc-request` span, or `None` if it had none.
+    #[derive(Clone, Default)]
+    struct CaptureCorrelationIds(Arc<Mutex<Vec<Option<String>>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureCorrelationIds {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: Context<'_, S>,
+        ) {
+            if attrs.metadata().name() == "json-rpc-request" {
+                let mut visitor = CorrelationIdVisitor(None);
+                attrs.record(&mut visitor);
+                self.0.lock().unwrap().push(visitor.0);
+            }
+        }
+    }
+
+    struct CorrelationIdVisitor(Option<String>);
+
+    impl Visit for CorrelationIdVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "x-correlation-id" {
+                self.0 = Some(format!("{value:?}"));
+            }
+        }
+    }
+
+    async fn router() -> axum::Router {
+        let mut builder = JsonRpcServerBuilder::new("1.0.0", &Registry::new(), None, None);
+        builder
+            .module
+            .register_method("test_ping", |_, _, _| Ok::<_, ErrorObjectOwned>("pong"))
+            .unwrap();
+        builder.to_router(ServerType::Http).await.unwrap()
+    }
+
+    async fn ping(router: &axum::Router, correlation_id: Option<&str>) -> http::Response<Body> {
+        let mut request = Request::builder()
+            .method(http::Method::POST)
+            .uri("/")
+            .header(http::header::CONTENT_TYPE, "application/json");
+        if let Some(id) = correlation_id {
+            request = request.header(CORRELATION_ID_HEADER, id);
+        }
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"test_ping","params":[]}"#;
+        let response = router
+            .clone()
+            .oneshot(request.body(Body::from(body)).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        response
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id() {
+        let captured = CaptureCorrelationIds::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(captured.clone()));
+        let router = router().await;
+
+        // The ID is recorded on the request's span and echoed in the response.
+        let response = ping(&router, Some("wallet-42")).await;
+        assert_eq!(response.headers()[CORRELATION_ID_HEADER], "wallet-42");
+
+        // Overlong IDs are still echoed, but not logged.
+        let long_id = "x".repeat(MAX_CORRELATION_ID_LEN + 1);
+        let response = ping(&router, Some(&long_id)).await;
+        assert_eq!(response.headers()[CORRELATION_ID_HEADER], long_id.as_str());
+
+        // Requests without an ID don't get one.
+        let response = ping(&router, None).await;
+        assert!(!response.headers().contains_key(CORRELATION_ID_HEADER));
+
+        assert_eq!(
+            *captured.0.lock().unwrap(),
+            vec![Some("wallet-42".to_owned()), None, None]
+        );
+    }
+}
@@ -9,19 +9,22 @@ use jsonrpsee::RpcModule;
 use jsonrpsee::core::RpcResult;
 #[cfg(test)]
 use mockall::automock;
-use move_binary_format::{binary_config::BinaryConfig, normalized};
+use move_binary_format::{CompiledModule, binary_config::BinaryConfig, normalized};
+use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_api::{MoveUtilsOpenRpc, MoveUtilsServer};
 use sui_json_rpc_types::{
     MoveFunctionArgType, ObjectValueKind, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
-    SuiMoveNormalizedStruct,
+    SuiMoveNormalizedStruct, SuiMoveStructLayouts,
 };
+use sui_move_build::generate_struct_layouts;
 use sui_open_rpc::Module;
 use sui_types::base_types::ObjectID;
-use sui_types::move_package::normalize_modules;
+use sui_types::move_package::{MovePackage, normalize_modules};
 use sui_types::object::{Data, ObjectRead};
 use tap::TapFallible;
 use tracing::{error, instrument, warn};
@@ -137,6 +140,53 @@ impl MoveUtils {
                 as Arc<dyn MoveUtilsInternalTrait + Send + Sync>,
         }
     }
+
+    fn get_package(&self, package: ObjectID) -> Result<MovePackage, Error> {
+        match self.internal.get_object_read(package)? {
+            ObjectRead::Exists(_obj_ref, object, _layout) => match object.into_inner().data {
+                Data::Package(p) => Ok(p),
+                _ => Err(SuiRpcInputError::GenericInvalid(format!(
+                    "Object is not a package with ID {}",
+                    package
+                )))?,
+            },
+            _ => Err(SuiRpcInputError::GenericNotFound(format!(
+                "Package object does not exist with ID {}",
+                package
+            )))?,
+        }
+    }
+}
+
+/// Modules of a package and of every package in its linkage table, keyed by their runtime ID.
+struct LinkedModules(BTreeMap<ModuleId, CompiledModule>);
+
+impl LinkedModules {
+    fn add_package(&mut self, package: &MovePackage) -> Result<Vec<ModuleId>, Error> {
+        // we are on the read path - it's OK to use VERSION_MAX of the supported Move
+        // binary format
+        let binary_config = BinaryConfig::legacy_with_flags(
+            /* check_no_extraneous_bytes */ false,
+            /* deprecate_global_storage_ops */ false,
+        );
+        let mut ids = vec![];
+        for bytes in package.serialized_module_map().values() {
+            let module = CompiledModule::deserialize_with_config(bytes, &binary_config)
+                .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+            ids.push(module.self_id());
+            self.0.insert(module.self_id(), module);
+        }
+        Ok(ids)
+    }
+}
+
+impl GetModule for LinkedModules {
+    type Error = anyhow::Error;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(self.0.get(id).cloned())
+    }
 }
 
 impl SuiRpcModule for MoveUtils {
@@ -221,6 +271,25 @@ impl MoveUtilsServer for MoveUtils {
         })
     }
 
+    #[instrument(skip(self))]
+    async fn get_move_struct_layouts(&self, package: ObjectID) -> RpcResult<SuiMoveStructLayouts> {
+        with_tracing!(async move {
+            let package = self.get_package(package)?;
+
+            let mut modules = LinkedModules(BTreeMap::new());
+            for dependency in package.linkage_table().values() {
+                modules.add_package(&self.get_package(dependency.upgraded_id)?)?;
+            }
+            let package_modules = modules.add_package(&package)?;
+
+            let layouts = generate_struct_layouts(
+                package_modules.iter().filter_map(|id| modules.0.get(id)),
+                &modules,
+            )?;
+            Ok(SuiMoveStructLayouts { layouts })
+        })
+    }
+
     #[instrument(skip(self))]
     async fn get_move_function_arg_types(
         &self,
@@ -359,4 +428,46 @@ mod tests {
             assert_eq!(error_object.message(), &error_string);
         }
     }
+
+    mod get_move_struct_layouts_tests {
+        use super::super::*;
+        use sui_framework::BuiltInFramework;
+        use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+
+        #[tokio::test]
+        async fn test_layouts_include_dependencies() {
+            let mut mock_internal = MockMoveUtilsInternalTrait::new();
+            mock_internal.expect_get_object_read().returning(|id| {
+                let object = BuiltInFramework::get_package_by_id(&id).genesis_object();
+                Ok(ObjectRead::Exists(
+                    object.compute_object_reference(),
+                    object,
+                    None,
+                ))
+            });
+            let move_utils = MoveUtils {
+                internal: Arc::new(mock_internal),
+            };
+
+            let response = move_utils
+                .get_move_struct_layouts(SUI_FRAMEWORK_PACKAGE_ID)
+                .await
+                .unwrap();
+
+            // Types declared by the package itself...
+            assert!(
+                response
+                    .layouts
+                    .keys()
+                    .any(|k| k.ends_with("::clock::Clock"))
+            );
+            // ...and the types they depend on from other packages.
+            assert!(
+                response
+                    .layouts
+                    .keys()
+                    .any(|k| k.ends_with("::string::String"))
+            );
+        }
+    }
 }
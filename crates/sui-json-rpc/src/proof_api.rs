@@ -0,0 +1,150 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::RpcModule;
+use jsonrpsee::core::RpcResult;
+use sui_json_rpc_api::{ProofApiOpenRpc, ProofApiServer};
+use sui_json_rpc_types::{SuiFinalityCertificate, SuiTransactionProof};
+use sui_open_rpc::Module;
+use sui_storage::key_value_store::TransactionKeyValueStore;
+use sui_types::base_types::TransactionDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::instrument;
+
+use crate::error::{Error, SuiRpcInputError};
+use crate::{SuiRpcModule, with_tracing};
+
+/// Serves the data a light client needs to verify transaction inclusion. Everything is read
+/// through the transaction key-value store, so proofs remain available for data that has been
+/// pruned locally but is still held by the configured fallback store.
+pub struct ProofApi {
+    transaction_kv_store: Arc<TransactionKeyValueStore>,
+}
+
+impl ProofApi {
+    pub fn new(transaction_kv_store: Arc<TransactionKeyValueStore>) -> Self {
+        Self {
+            transaction_kv_store,
+        }
+    }
+
+    async fn get_transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<CheckpointSequenceNumber, Error> {
+        self.transaction_kv_store
+            .multi_get_transaction_checkpoint(&[digest])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| {
+                SuiRpcInputError::GenericNotFound(format!(
+                    "transaction {digest} has not been included in a checkpoint"
+                ))
+                .into()
+            })
+    }
+
+    async fn get_transaction_proof_internal(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<SuiTransactionProof, Error> {
+        let kv_store = &self.transaction_kv_store;
+        let checkpoint = self.get_transaction_checkpoint(digest).await?;
+
+        let transaction = kv_store.get_tx(digest).await?;
+        let effects = kv_store.get_fx_by_tx_digest(digest).await?;
+        let events = if effects.events_digest().is_some() {
+            let events = kv_store
+                .multi_get_events_by_tx_digests(&[digest])
+                .await?
+                .pop()
+                .flatten()
+                .ok_or_else(|| {
+                    Error::UnexpectedError(format!("events of transaction {digest} not found"))
+                })?;
+            Some(bcs::to_bytes(&events)?)
+        } else {
+            None
+        };
+        let checkpoint_summary = kv_store.get_checkpoint_summary(checkpoint).await?;
+        let checkpoint_contents = kv_store.get_checkpoint_contents(checkpoint).await?;
+
+        Ok(SuiTransactionProof {
+            transaction_digest: digest,
+            checkpoint,
+            checkpoint_summary: bcs::to_bytes(&checkpoint_summary)?,
+            checkpoint_contents: bcs::to_bytes(&checkpoint_contents)?,
+            transaction: bcs::to_bytes(&transaction)?,
+            effects: bcs::to_bytes(&effects)?,
+            events,
+        })
+    }
+
+    async fn get_transaction_finality_certificate_internal(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<SuiFinalityCertificate, Error> {
+        let kv_store = &self.transaction_kv_store;
+        let checkpoint = self.get_transaction_checkpoint(digest).await?;
+        let checkpoint_summary = kv_store.get_checkpoint_summary(checkpoint).await?;
+        let checkpoint_contents = kv_store.get_checkpoint_contents(checkpoint).await?;
+
+        let (position, execution_digests) = checkpoint_contents
+            .iter()
+            .enumerate()
+            .find(|(_, digests)| digests.transaction == digest)
+            .ok_or_else(|| {
+                Error::UnexpectedError(format!(
+                    "transaction {digest} not found in the contents of checkpoint {checkpoint}"
+                ))
+            })?;
+        let effects_digest = execution_digests.effects;
+
+        let epoch = checkpoint_summary.epoch();
+        let (summary, signature) = checkpoint_summary.into_data_and_sig();
+        Ok(SuiFinalityCertificate {
+            transaction_digest: digest,
+            effects_digest,
+            epoch,
+            checkpoint,
+            checkpoint_summary: bcs::to_bytes(&summary)?,
+            signature: bcs::to_bytes(&signature)?,
+            checkpoint_contents: bcs::to_bytes(&checkpoint_contents)?,
+            position: position as u64,
+        })
+    }
+}
+
+impl SuiRpcModule for ProofApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        ProofApiOpenRpc::module_doc()
+    }
+}
+
+#[async_trait]
+impl ProofApiServer for ProofApi {
+    #[instrument(skip(self))]
+    async fn get_transaction_proof(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiTransactionProof> {
+        with_tracing!(self.get_transaction_proof_internal(digest))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_transaction_finality_certificate(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiFinalityCertificate> {
+        with_tracing!(self.get_transaction_finality_certificate_internal(digest))
+    }
+}
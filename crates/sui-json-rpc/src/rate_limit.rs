@@ -0,0 +1,400 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, to_bytes};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use jsonrpsee::types::ErrorCode;
+use lru::LruCache;
+use prometheus::{IntCounterVec, Registry, register_int_counter_vec_with_registry};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sui_config::node::{JsonRpcRateLimitConfig, RateLimit};
+
+use crate::MAX_REQUEST_SIZE;
+
+const TOO_MANY_REQUESTS_MSG: &str = "Too many requests";
+
+/// Label identifying clients without an API key in metrics.
+const ANONYMOUS_LABEL: &str = "anonymous";
+
+/// Label identifying a key's limit on all methods in metrics.
+const ALL_METHODS_LABEL: &str = "*";
+
+/// Once this many buckets are tracked, the least recently used one is dropped to make room for a
+/// new one. Idle buckets refill, and a full bucket behaves like a new one, so this only bounds
+/// memory, without affecting the limits of active clients.
+const MAX_BUCKETS: NonZeroUsize = NonZeroUsize::new(100_000).unwrap();
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Client {
+    /// A client with a configured API key, identified by the key's name.
+    ApiKey(String),
+    /// Any other client, identified by its IP address if it is known.
+    Anonymous(Option<IpAddr>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct BucketKey {
+    client: Client,
+    /// The method limited by this bucket, or `None` for an API key's limit on all methods.
+    method: Option<String>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst().get() as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        let rate = self.limit.requests_per_second.get() as f64;
+        self.tokens = (self.tokens + elapsed * rate).min(self.limit.burst().get() as f64);
+        self.updated = now;
+    }
+
+    /// How long until the bucket holds a token, zero if it already does.
+    fn wait(&self) -> Duration {
+        let missing = (1.0 - self.tokens).max(0.0);
+        Duration::from_secs_f64(missing / self.limit.requests_per_second.get() as f64)
+    }
+}
+
+struct RateLimitMetrics {
+    /// Requests rejected by a rate limit, by the method whose limit was hit and the client.
+    rate_limited_requests: IntCounterVec,
+    /// Requests made with an API key, by the key's name.
+    api_key_requests: IntCounterVec,
+}
+
+impl RateLimitMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            rate_limited_requests: register_int_counter_vec_with_registry!(
+                "json_rpc_rate_limited_requests",
+                "Number of requests rejected by a rate limit, by limited method and client",
+                &["method", "client"],
+                registry,
+            )
+            .unwrap(),
+            api_key_requests: register_int_counter_vec_with_registry!(
+                "json_rpc_api_key_requests",
+                "Number of requests made with an API key, by key name",
+                &["client"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Enforces the token-bucket rate limits of a [`JsonRpcRateLimitConfig`] on JSON-RPC requests.
+///
+/// Requests carrying a configured API key are limited per key: by the key's limit on all methods,
+/// and by its limits on individual methods, or the common ones if it has none for a method. Other
+/// requests are limited per client IP address, by the common limits on individual methods.
+/// Requests with an unknown API key are treated as if they had none.
+pub struct RateLimiter {
+    config: JsonRpcRateLimitConfig,
+    /// API keys, mapped to their names.
+    api_keys: HashMap<String, String>,
+    buckets: Mutex<LruCache<BucketKey, TokenBucket>>,
+    metrics: RateLimitMetrics,
+}
+
+impl RateLimiter {
+    pub fn new(config: JsonRpcRateLimitConfig, registry: &Registry) -> Self {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|(name, quota)| (quota.key.clone(), name.clone()))
+            .collect();
+
+        Self {
+            config,
+            api_keys,
+            buckets: Mutex::new(LruCache::new(MAX_BUCKETS)),
+            metrics: RateLimitMetrics::new(registry),
+        }
+    }
+
+    fn client(&self, api_key: Option<&str>, ip: Option<IpAddr>) -> Client {
+        match api_key.and_then(|key| self.api_keys.get(key)) {
+            Some(name) => Client::ApiKey(name.clone()),
+            None => Client::Anonymous(ip),
+        }
+    }
+
+    /// Take a token from every bucket limiting `client`'s calls to `method`. If any of them is
+    /// empty, no token is taken, and the error holds how long until they all hold a token again.
+    fn check(&self, client: &Client, method: &str, now: Instant) -> Result<(), Duration> {
+        let mut limits = vec![];
+        match client {
+            Client::ApiKey(name) => {
+                let quota = &self.config.api_keys[name];
+                if let Some(limit) = quota.limit {
+                    limits.push((None, limit));
+                }
+                if let Some(limit) = quota
+                    .methods
+                    .get(method)
+                    .or_else(|| self.config.methods.get(method))
+                {
+                    limits.push((Some(method), *limit));
+                }
+            }
+            Client::Anonymous(_) => {
+                if let Some(limit) = self.config.methods.get(method) {
+                    limits.push((Some(method), *limit));
+                }
+            }
+        }
+
+        if limits.is_empty() {
+            return Ok(());
+        }
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let keys: Vec<_> = limits
+            .into_iter()
+            .map(|(method, limit)| {
+                let key = BucketKey {
+                    client: client.clone(),
+                    method: method.map(str::to_owned),
+                };
+                let bucket =
+                    buckets.get_or_insert_mut(key.clone(), || TokenBucket::new(limit, now));
+                bucket.refill(now);
+                key
+            })
+            .collect();
+
+        let wait = keys
+            .iter()
+            .map(|key| buckets.peek(key).unwrap().wait())
+            .max();
+        if let Some(wait) = wait.filter(|wait| !wait.is_zero()) {
+            let limited = keys
+                .iter()
+                .find(|key| !buckets.peek(key).unwrap().wait().is_zero())
+                .and_then(|key| key.method.as_deref())
+                .unwrap_or(ALL_METHODS_LABEL);
+            let client = match client {
+                Client::ApiKey(name) => name.as_str(),
+                Client::Anonymous(_) => ANONYMOUS_LABEL,
+            };
+            self.metrics
+                .rate_limited_requests
+                .with_label_values(&[limited, client])
+                .inc();
+            return Err(wait);
+        }
+
+        for key in &keys {
+            buckets.get_mut(key).unwrap().tokens -= 1.0;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct RequestHead {
+    method: String,
+    #[serde(default)]
+    id: Value,
+}
+
+/// Middleware rejecting JSON-RPC requests over their rate limits with a `429 Too Many Requests`,
+/// whose `Retry-After` header says how many seconds to wait before retrying.
+pub async fn limit_requests(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+
+    let api_key = request
+        .headers()
+        .get(limiter.config.api_key_header.as_str())
+        .and_then(|v| v.to_str().ok());
+    let ip = request.extensions().get::<IpAddr>().copied().or_else(|| {
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|info| info.0.ip())
+    });
+    let client = limiter.client(api_key, ip);
+    if let Client::ApiKey(name) = &client {
+        limiter
+            .metrics
+            .api_key_requests
+            .with_label_values(&[name])
+            .inc();
+    }
+
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = to_bytes(body, MAX_REQUEST_SIZE as usize).await else {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    };
+
+    // Requests that aren't a single JSON-RPC call are left for the server to reject.
+    if let Ok(head) = serde_json::from_slice::<RequestHead>(&bytes)
+        && let Err(wait) = limiter.check(&client, &head.method, Instant::now())
+    {
+        return too_many_requests(head.id, wait);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+fn too_many_requests(id: Value, wait: Duration) -> Response {
+    let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": ErrorCode::ServerIsBusy.code(),
+            "message": TOO_MANY_REQUESTS_MSG,
+            "data": { "retryAfter": retry_after },
+        },
+    });
+
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, axum::Json(body)).into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::net::Ipv4Addr;
+    use std::num::NonZeroU32;
+
+    use sui_config::node::ApiKeyQuota;
+
+    use super::*;
+
+    const DRY_RUN: &str = "sui_dryRunTransactionBlock";
+    const GET_OBJECT: &str = "sui_getObject";
+
+    fn limit(requests_per_second: u32, burst: u32) -> RateLimit {
+        RateLimit {
+            requests_per_second: NonZeroU32::new(requests_per_second).unwrap(),
+            burst: NonZeroU32::new(burst),
+        }
+    }
+
+    fn limiter() -> RateLimiter {
+        let config = JsonRpcRateLimitConfig {
+            methods: BTreeMap::from([(DRY_RUN.to_owned(), limit(1, 2))]),
+            api_keys: BTreeMap::from([(
+                "partner".to_owned(),
+                ApiKeyQuota {
+                    key: "secret".to_owned(),
+                    limit: Some(limit(2, 3)),
+                    methods: BTreeMap::from([(DRY_RUN.to_owned(), limit(10, 10))]),
+                },
+            )]),
+            ..Default::default()
+        };
+        RateLimiter::new(config, &Registry::new())
+    }
+
+    #[test]
+    fn test_method_limit_per_client() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let alice = limiter.client(None, Some([127, 0, 0, 1].into()));
+        let bob = limiter.client(Some("unknown"), Some([127, 0, 0, 2].into()));
+
+        // The burst is available straight away, then requests are limited.
+        assert_eq!(limiter.check(&alice, DRY_RUN, now), Ok(()));
+        assert_eq!(limiter.check(&alice, DRY_RUN, now), Ok(()));
+        assert_eq!(
+            limiter.check(&alice, DRY_RUN, now),
+            Err(Duration::from_secs(1))
+        );
+
+        // Other methods and other clients are not affected.
+        assert_eq!(limiter.check(&alice, GET_OBJECT, now), Ok(()));
+        assert_eq!(limiter.check(&bob, DRY_RUN, now), Ok(()));
+
+        // Tokens are refilled over time.
+        let later = now + Duration::from_millis(500);
+        assert_eq!(
+            limiter.check(&alice, DRY_RUN, later),
+            Err(Duration::from_millis(500))
+        );
+        let later = now + Duration::from_secs(1);
+        assert_eq!(limiter.check(&alice, DRY_RUN, later), Ok(()));
+    }
+
+    #[test]
+    fn test_api_key_quota() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let partner = limiter.client(Some("secret"), None);
+        assert_eq!(partner, Client::ApiKey("partner".to_owned()));
+
+        // The key's own method limit replaces the common one, but its quota on all methods
+        // still applies.
+        for _ in 0..3 {
+            assert_eq!(limiter.check(&partner, DRY_RUN, now), Ok(()));
+        }
+        assert_eq!(
+            limiter.check(&partner, GET_OBJECT, now),
+            Err(Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn test_bucket_eviction() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let alice = limiter.client(None, Some([127, 0, 0, 1].into()));
+
+        assert_eq!(limiter.check(&alice, DRY_RUN, now), Ok(()));
+        assert_eq!(limiter.check(&alice, DRY_RUN, now), Ok(()));
+
+        // Requests from many other clients don't push out the bucket of a client that is still
+        // making requests.
+        for i in 0..MAX_BUCKETS.get() as u32 {
+            let client = limiter.client(None, Some(Ipv4Addr::from((1u32 << 24) + i).into()));
+            assert_eq!(limiter.check(&client, DRY_RUN, now), Ok(()));
+            if i % 1000 == 0 {
+                assert!(limiter.check(&alice, DRY_RUN, now).is_err());
+            }
+        }
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_BUCKETS.get());
+        assert!(buckets.contains(&BucketKey {
+            client: alice,
+            method: Some(DRY_RUN.to_owned()),
+        }));
+    }
+}
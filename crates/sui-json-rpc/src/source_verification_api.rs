@@ -0,0 +1,150 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::{ErrorCode, ErrorObject};
+use jsonrpsee::{Extensions, RpcModule};
+use sui_json_rpc_api::{SourceVerificationApiOpenRpc, SourceVerificationApiServer};
+use sui_json_rpc_types::{
+    SuiMoveSourceLocation, SuiResolvedMoveAbort, SuiSourceAttestation, SuiSourceVerificationStatus,
+};
+use sui_open_rpc::Module;
+use sui_source_validation::service::{
+    SourceAttestation, SourceVerificationService, SubmissionError,
+};
+use sui_types::base_types::ObjectID;
+use sui_types::sui_serde::BigInt;
+use tracing::instrument;
+
+use crate::error::{Error, SuiRpcInputError};
+use crate::{BearerToken, SuiRpcModule, with_tracing};
+
+pub struct SourceVerificationApi {
+    service: Arc<SourceVerificationService>,
+}
+
+impl SourceVerificationApi {
+    pub fn new(service: Arc<SourceVerificationService>) -> Self {
+        Self { service }
+    }
+}
+
+impl SuiRpcModule for SourceVerificationApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        SourceVerificationApiOpenRpc::module_doc()
+    }
+}
+
+#[async_trait]
+impl SourceVerificationApiServer for SourceVerificationApi {
+    #[instrument(skip(self, ext, files))]
+    async fn submit_package_source(
+        &self,
+        ext: &Extensions,
+        package_id: ObjectID,
+        files: BTreeMap<String, String>,
+    ) -> RpcResult<SuiSourceAttestation> {
+        let token = ext.get::<BearerToken>().map(|token| token.0.as_str());
+        with_tracing!(async move {
+            let attestation = self
+                .service
+                .submit(token, package_id, files)
+                .await
+                .map_err(|e| match e {
+                    SubmissionError::Disabled => Error::UnsupportedFeature(e.to_string()),
+                    SubmissionError::Unauthorized => ErrorObject::owned(
+                        ErrorCode::InvalidRequest.code(),
+                        e.to_string(),
+                        None::<()>,
+                    )
+                    .into(),
+                    SubmissionError::QuotaExceeded { .. } | SubmissionError::Busy => {
+                        ErrorObject::owned(
+                            ErrorCode::ServerIsBusy.code(),
+                            e.to_string(),
+                            None::<()>,
+                        )
+                        .into()
+                    }
+                    SubmissionError::Storage(_) => Error::UnexpectedError(e.to_string()),
+                    _ => SuiRpcInputError::GenericInvalid(e.to_string()).into(),
+                })?;
+            Ok(to_rpc_attestation(attestation))
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn is_source_verified(
+        &self,
+        package_id: ObjectID,
+    ) -> RpcResult<SuiSourceVerificationStatus> {
+        with_tracing!(async move {
+            let attestation = self.service.attestation(&package_id);
+            Ok(SuiSourceVerificationStatus {
+                package_id,
+                verified: attestation.is_some(),
+                attestation: attestation.map(to_rpc_attestation),
+            })
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_move_abort_source_location(
+        &self,
+        package_id: ObjectID,
+        module: String,
+        function: u16,
+        instruction: u16,
+    ) -> RpcResult<Option<SuiMoveSourceLocation>> {
+        with_tracing!(async move {
+            Ok(self
+                .service
+                .source_location(&package_id, &module, function, instruction)
+                .map(|location| SuiMoveSourceLocation {
+                    file: location.file,
+                    line: location.line,
+                    column: location.column,
+                }))
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn resolve_move_abort(
+        &self,
+        package_id: ObjectID,
+        module: String,
+        abort_code: BigInt<u64>,
+    ) -> RpcResult<Option<SuiResolvedMoveAbort>> {
+        with_tracing!(async move {
+            Ok(self
+                .service
+                .resolve_abort(&package_id, &module, *abort_code)
+                .map(|resolved| {
+                    let constant = resolved.constant;
+                    SuiResolvedMoveAbort {
+                        module: resolved.module,
+                        constant_name: constant.as_ref().map(|c| c.name.clone()),
+                        message: constant.and_then(|c| c.message),
+                        line_number: resolved.line,
+                    }
+                }))
+        })
+    }
+}
+
+fn to_rpc_attestation(attestation: SourceAttestation) -> SuiSourceAttestation {
+    SuiSourceAttestation {
+        package_id: attestation.package_id,
+        toolchain_version: attestation.toolchain_version,
+        verified_at_ms: attestation.verified_at_ms,
+        modules: attestation.modules,
+    }
+}
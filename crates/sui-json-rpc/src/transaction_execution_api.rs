@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use fastcrypto::encoding::Base64;
@@ -18,14 +18,16 @@ use crate::{
 };
 use sui_core::authority::AuthorityState;
 use sui_core::authority_client::NetworkAuthorityClient;
-use sui_core::transaction_orchestrator::TransactionOrchestrator;
+use sui_core::transaction_orchestrator::{MAX_OBJECT_RESERVATION_TTL, TransactionOrchestrator};
 use sui_json_rpc_api::{JsonRpcMetrics, WriteApiOpenRpc, WriteApiServer};
 use sui_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiObjectReservation,
+    SuiTransactionBlock, SuiTransactionBlockEvents, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::Signature;
 use sui_types::digests::TransactionDigest;
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::signature::GenericSignature;
@@ -39,6 +41,8 @@ use sui_types::transaction_driver_types::{
 };
 use tracing::instrument;
 
+const DEFAULT_OBJECT_RESERVATION_TTL: Duration = Duration::from_secs(5);
+
 pub struct TransactionExecutionApi {
     state: Arc<dyn StateRead>,
     transaction_orchestrator: Arc<TransactionOrchestrator<NetworkAuthorityClient>>,
@@ -166,6 +170,109 @@ impl TransactionExecutionApi {
         .await
     }
 
+    async fn execute_soft_bundle(
+        &self,
+        tx_bytes: Vec<Base64>,
+        signatures: Vec<Vec<Base64>>,
+        opts: Option<SuiTransactionBlockResponseOptions>,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<Vec<SuiTransactionBlockResponse>, Error> {
+        if tx_bytes.len() != signatures.len() {
+            return Err(SuiRpcInputError::GenericInvalid(format!(
+                "Expected signatures for {} transactions, got {}",
+                tx_bytes.len(),
+                signatures.len()
+            ))
+            .into());
+        }
+        let request_type =
+            request_type.unwrap_or(ExecuteTransactionRequestType::WaitForEffectsCert);
+        let prepared = tx_bytes
+            .into_iter()
+            .zip(signatures)
+            .map(|(tx_bytes, signatures)| {
+                self.prepare_execute_transaction_block(tx_bytes, signatures, opts.clone())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let requests = prepared
+            .iter()
+            .map(|(request, ..)| request.clone())
+            .collect();
+
+        let orch_timer = self.metrics.orchestrator_latency_ms.start_timer();
+        let results = self
+            .transaction_orchestrator
+            .execute_soft_bundle(requests, request_type, None)
+            .await
+            .map_err(Error::from)?;
+        drop(orch_timer);
+
+        let mut responses = Vec::with_capacity(results.len());
+        for ((_, opts, sender, input_objs, txn, transaction, raw_transaction), result) in
+            prepared.into_iter().zip(results)
+        {
+            let digest = *txn.digest();
+            let response = match result {
+                Ok((response, is_executed_locally)) => {
+                    self.handle_post_orchestration(
+                        response,
+                        is_executed_locally,
+                        opts,
+                        digest,
+                        input_objs,
+                        transaction,
+                        raw_transaction,
+                        sender,
+                    )
+                    .await?
+                }
+                Err(error) => SuiTransactionBlockResponse {
+                    transaction,
+                    raw_transaction,
+                    errors: vec![error.to_string()],
+                    ..SuiTransactionBlockResponse::new(digest)
+                },
+            };
+            responses.push(response);
+        }
+        Ok(responses)
+    }
+
+    fn reserve_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        tx_digest: TransactionDigest,
+        signature: Base64,
+        ttl_ms: Option<BigInt<u64>>,
+    ) -> Result<SuiObjectReservation, Error> {
+        let signature = signature
+            .to_vec()
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(&bytes).ok())
+            .ok_or_else(|| {
+                SuiRpcInputError::GenericInvalid("Invalid reservation signature".to_string())
+            })?;
+        let ttl = ttl_ms
+            .map(|ttl_ms| Duration::from_millis(*ttl_ms))
+            .unwrap_or(DEFAULT_OBJECT_RESERVATION_TTL)
+            .min(MAX_OBJECT_RESERVATION_TTL);
+        let expires_at = SystemTime::now() + ttl;
+        let objects = self.transaction_orchestrator.reserve_objects(
+            &object_ids,
+            tx_digest,
+            &signature,
+            ttl,
+        )?;
+        Ok(SuiObjectReservation {
+            tx_digest,
+            objects: objects.into_iter().map(Into::into).collect(),
+            expires_at_ms: expires_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        })
+    }
+
     async fn handle_post_orchestration(
         &self,
         response: ExecuteTransactionResponseV3,
@@ -327,6 +434,31 @@ impl WriteApiServer for TransactionExecutionApi {
         })
     }
 
+    #[instrument(skip_all)]
+    async fn execute_soft_bundle(
+        &self,
+        tx_bytes: Vec<Base64>,
+        signatures: Vec<Vec<Base64>>,
+        opts: Option<SuiTransactionBlockResponseOptions>,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> RpcResult<Vec<SuiTransactionBlockResponse>> {
+        with_tracing!(Duration::from_secs(10), async move {
+            self.execute_soft_bundle(tx_bytes, signatures, opts, request_type)
+                .await
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn reserve_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+        tx_digest: TransactionDigest,
+        signature: Base64,
+        ttl_ms: Option<BigInt<u64>>,
+    ) -> RpcResult<SuiObjectReservation> {
+        with_tracing!(async move { self.reserve_objects(object_ids, tx_digest, signature, ttl_ms) })
+    }
+
     #[instrument(skip(self))]
     async fn dev_inspect_transaction_block(
         &self,
@@ -14,8 +14,10 @@ pub use move_core_types::annotated_value::MoveTypeLayout;
 use move_core_types::annotated_value::{MoveFieldLayout, MoveVariant};
 use move_core_types::u256::U256;
 use move_core_types::{
+    account_address::AccountAddress,
     annotated_value::{MoveStruct, MoveValue},
-    identifier::Identifier,
+    ident_str,
+    identifier::{IdentStr, Identifier},
     language_storage::{StructTag, TypeTag},
     runtime_value as R,
 };
@@ -23,20 +25,60 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Number, Value as JsonValue, json};
 
-use sui_types::MOVE_STDLIB_ADDRESS;
 use sui_types::base_types::{
     ObjectID, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR, STD_ASCII_MODULE_NAME,
     STD_ASCII_STRUCT_NAME, STD_OPTION_MODULE_NAME, STD_OPTION_STRUCT_NAME, STD_UTF8_MODULE_NAME,
     STD_UTF8_STRUCT_NAME, SuiAddress, TxContext, TxContextKind, is_primitive_type_tag,
     move_ascii_str_layout, move_utf8_str_layout,
 };
+use sui_types::clock::RESOLVED_SUI_CLOCK;
 use sui_types::id::{self, ID, RESOLVED_SUI_ID};
 use sui_types::move_package::MovePackage;
 use sui_types::object::bounded_visitor::BoundedVisitor;
+use sui_types::randomness_state::RESOLVED_SUI_RANDOMNESS_STATE;
 use sui_types::transfer::RESOLVED_RECEIVING_STRUCT;
+use sui_types::{
+    MOVE_STDLIB_ADDRESS, SUI_CLOCK_OBJECT_ID, SUI_COIN_REGISTRY_OBJECT_ID, SUI_DENY_LIST_OBJECT_ID,
+    SUI_FRAMEWORK_ADDRESS, SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_ADDRESS,
+    SUI_SYSTEM_STATE_OBJECT_ID,
+};
 
 const HEX_PREFIX: &str = "0x";
 
+/// System objects that exist once per network, which parameters of their type can be filled in
+/// with when they are left out of a call.
+const WELL_KNOWN_OBJECTS: [((&AccountAddress, &IdentStr, &IdentStr), ObjectID); 5] = [
+    (RESOLVED_SUI_CLOCK, SUI_CLOCK_OBJECT_ID),
+    (
+        RESOLVED_SUI_RANDOMNESS_STATE,
+        SUI_RANDOMNESS_STATE_OBJECT_ID,
+    ),
+    (
+        (
+            &SUI_FRAMEWORK_ADDRESS,
+            ident_str!("deny_list"),
+            ident_str!("DenyList"),
+        ),
+        SUI_DENY_LIST_OBJECT_ID,
+    ),
+    (
+        (
+            &SUI_FRAMEWORK_ADDRESS,
+            ident_str!("coin_registry"),
+            ident_str!("CoinRegistry"),
+        ),
+        SUI_COIN_REGISTRY_OBJECT_ID,
+    ),
+    (
+        (
+            &SUI_SYSTEM_ADDRESS,
+            ident_str!("sui_system"),
+            ident_str!("SuiSystemState"),
+        ),
+        SUI_SYSTEM_STATE_OBJECT_ID,
+    ),
+];
+
 #[cfg(test)]
 mod tests;
 
@@ -803,8 +845,71 @@ fn resolve_call_args(
         .collect()
 }
 
+/// The well-known system object a parameter refers to, if any, e.g. `0x6` for `&Clock` and `0x8`
+/// for `&Random`.
+pub fn well_known_object_arg(view: &CompiledModule, param: &SignatureToken) -> Option<ObjectID> {
+    let (SignatureToken::Reference(inner) | SignatureToken::MutableReference(inner)) = param else {
+        return None;
+    };
+    let SignatureToken::Datatype(idx) = &**inner else {
+        return None;
+    };
+    let resolved = resolve_struct(view, *idx);
+    WELL_KNOWN_OBJECTS
+        .iter()
+        .find(|(type_, _)| *type_ == resolved)
+        .map(|(_, id)| *id)
+}
+
+/// Match `args` up with `parameters` (which exclude `TxContext`), filling in the parameters that
+/// refer to well-known system objects (see [well_known_object_arg]) with `object(id)` if they were
+/// left out. Either all or none of these parameters must be left out: arguments are used as they
+/// are if there is one per parameter, and `None` is returned if the count matches neither.
+pub fn fill_well_known_object_args<T>(
+    view: &CompiledModule,
+    parameters: &[SignatureToken],
+    args: Vec<T>,
+    object: impl Fn(ObjectID) -> T,
+) -> Option<Vec<T>> {
+    fill_default_args(view, parameters, args, BTreeMap::new(), object)
+}
+
+/// Like [fill_well_known_object_args], but parameters can also have a preset value, e.g. from a
+/// package's argument-preset manifest. `presets` maps positions in `parameters` to the value the
+/// parameter is filled in with, which takes precedence over a well-known object.
+pub fn fill_default_args<T>(
+    view: &CompiledModule,
+    parameters: &[SignatureToken],
+    args: Vec<T>,
+    mut presets: BTreeMap<usize, T>,
+    object: impl Fn(ObjectID) -> T,
+) -> Option<Vec<T>> {
+    if args.len() == parameters.len() {
+        return Some(args);
+    }
+    let defaults: Vec<_> = parameters
+        .iter()
+        .enumerate()
+        .map(|(idx, param)| {
+            presets
+                .remove(&idx)
+                .or_else(|| well_known_object_arg(view, param).map(&object))
+        })
+        .collect();
+    let omitted = defaults.iter().flatten().count();
+    if omitted == 0 || args.len() + omitted != parameters.len() {
+        return None;
+    }
+    let mut args = args.into_iter();
+    defaults
+        .into_iter()
+        .map(|default| default.or_else(|| args.next()))
+        .collect()
+}
+
 /// Resolve the JSON args of a function into the expected formats to make them usable by Move call
-/// This is because we have special types which we need to specify in other formats
+/// This is because we have special types which we need to specify in other formats. Parameters
+/// that refer to well-known system objects, like the `Clock`, can be left out.
 pub fn resolve_move_function_args(
     package: &MovePackage,
     module_ident: Identifier,
@@ -838,13 +943,15 @@ pub fn resolve_move_function_args(
         }
         _ => parameters.len(),
     };
-    if combined_args_json.len() != expected_len {
-        bail!(
-            "Expected {} args, found {}",
-            expected_len,
-            combined_args_json.len()
-        );
-    }
+    let found_len = combined_args_json.len();
+    let Some(combined_args_json) = fill_well_known_object_args(
+        &module,
+        &parameters[..expected_len],
+        combined_args_json,
+        SuiJsonValue::from_object_id,
+    ) else {
+        bail!("Expected {} args, found {}", expected_len, found_len);
+    };
     // Check that the args are valid and convert to the correct format
     let call_args = resolve_call_args(&module, type_args, &combined_args_json, parameters)?;
     #[allow(clippy::disallowed_methods)]
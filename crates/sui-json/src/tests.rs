@@ -22,7 +22,10 @@ use sui_types::base_types::{
 use sui_types::dynamic_field::derive_dynamic_field_id;
 use sui_types::gas_coin::GasCoin;
 use sui_types::object::Object;
-use sui_types::{MOVE_STDLIB_ADDRESS, parse_sui_type_tag};
+use sui_types::{
+    MOVE_STDLIB_ADDRESS, SUI_CLOCK_OBJECT_ID, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID,
+    SUI_SYSTEM_STATE_OBJECT_ID, parse_sui_type_tag,
+};
 
 use crate::ResolvedCallArg;
 
@@ -789,3 +792,122 @@ fn test_string_vec_df_name_child_id_eq() {
         child_id.to_string()
     );
 }
+
+#[test]
+fn test_well_known_object_args_are_filled_in() {
+    let packages: Vec<_> = BuiltInFramework::genesis_move_packages().collect();
+    let package = |id: ObjectID| packages.iter().find(|p| p.id() == id).unwrap();
+    let resolve = |id, module: &str, function: &str, args: Vec<Value>| {
+        let args = args
+            .into_iter()
+            .map(|v| SuiJsonValue::new(v).unwrap())
+            .collect();
+        resolve_move_function_args(
+            package(id),
+            Identifier::new(module).unwrap(),
+            Identifier::new(function).unwrap(),
+            &[],
+            args,
+        )
+        .map(|args| args.into_iter().map(|(arg, _)| arg).collect::<Vec<_>>())
+    };
+
+    // clock::timestamp_ms(clock: &Clock)
+    assert_eq!(
+        resolve(SUI_FRAMEWORK_PACKAGE_ID, "clock", "timestamp_ms", vec![]).unwrap(),
+        vec![ResolvedCallArg::Object(SUI_CLOCK_OBJECT_ID)],
+    );
+
+    // sui_system::request_add_stake(wrapper: &mut SuiSystemState, stake: Coin<SUI>,
+    //     validator_address: address, ctx: &mut TxContext)
+    let coin = ObjectID::random();
+    let validator = SuiAddress::random_for_testing_only();
+    let args = vec![json!(coin.to_string()), json!(validator.to_string())];
+    let filled = resolve(
+        SUI_SYSTEM_PACKAGE_ID,
+        "sui_system",
+        "request_add_stake",
+        args.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        filled,
+        vec![
+            ResolvedCallArg::Object(SUI_SYSTEM_STATE_OBJECT_ID),
+            ResolvedCallArg::Object(coin),
+            ResolvedCallArg::Pure(bcs::to_bytes(&validator).unwrap()),
+        ],
+    );
+
+    // Passing the object explicitly still works, but partially filled arguments do not.
+    let mut explicit = vec![json!(SUI_SYSTEM_STATE_OBJECT_ID.to_string())];
+    explicit.extend(args.clone());
+    assert_eq!(
+        resolve(
+            SUI_SYSTEM_PACKAGE_ID,
+            "sui_system",
+            "request_add_stake",
+            explicit
+        )
+        .unwrap(),
+        filled
+    );
+    assert!(
+        resolve(
+            SUI_SYSTEM_PACKAGE_ID,
+            "sui_system",
+            "request_add_stake",
+            args[..1].to_vec()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_preset_args_are_filled_in() {
+    use move_binary_format::binary_config::BinaryConfig;
+
+    let package = BuiltInFramework::genesis_move_packages()
+        .find(|p| p.id() == SUI_SYSTEM_PACKAGE_ID)
+        .unwrap();
+    let module = package
+        .deserialize_module_by_str("sui_system", &BinaryConfig::standard())
+        .unwrap();
+    let (_, def) = module
+        .find_function_def_by_name("request_add_stake")
+        .unwrap();
+    // (wrapper: &mut SuiSystemState, stake: Coin<SUI>, validator_address: address), without the
+    // trailing `TxContext`.
+    let mut parameters = module
+        .signature_at(module.function_handle_at(def.function).parameters)
+        .0
+        .clone();
+    parameters.pop();
+
+    let fill = |args: Vec<&str>| {
+        crate::fill_default_args(
+            &module,
+            &parameters,
+            args.into_iter().map(String::from).collect(),
+            [(2, "validator".to_string())].into(),
+            |id| id.to_string(),
+        )
+    };
+
+    // The preset and the well-known object are filled in together.
+    assert_eq!(
+        fill(vec!["coin"]).unwrap(),
+        vec![
+            SUI_SYSTEM_STATE_OBJECT_ID.to_string(),
+            "coin".into(),
+            "validator".into()
+        ],
+    );
+    // Passing every argument overrides the preset.
+    assert_eq!(
+        fill(vec!["system", "coin", "other"]).unwrap(),
+        vec!["system".to_string(), "coin".into(), "other".into()],
+    );
+    // Only leaving out some of the defaulted parameters is ambiguous.
+    assert!(fill(vec!["coin", "other"]).is_none());
+}
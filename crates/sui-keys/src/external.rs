@@ -139,7 +139,7 @@ pub trait CommandRunner: Send + Sync + Debug {
 }
 
 #[derive(Debug)]
-struct StdCommandRunner;
+pub(crate) struct StdCommandRunner;
 #[async_trait]
 impl CommandRunner for StdCommandRunner {
     async fn run(
@@ -1,17 +1,22 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::anyhow;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use anyhow::{anyhow, ensure};
 use bip32::{ChildNumber, DerivationPath, XPrv};
 
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::secp256r1::{Secp256r1KeyPair, Secp256r1PrivateKey};
 use fastcrypto::{
     ed25519::Ed25519PrivateKey,
     secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey},
     traits::{KeyPair, ToFromBytes},
 };
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
 use slip10_ed25519::derive_ed25519_private_key;
 use sui_types::{
     base_types::SuiAddress,
@@ -24,9 +29,15 @@ pub const DERVIATION_PATH_PURPOSE_ED25519: u32 = 44;
 pub const DERVIATION_PATH_PURPOSE_SECP256K1: u32 = 54;
 pub const DERVIATION_PATH_PURPOSE_SECP256R1: u32 = 74;
 
+/// The purpose and application number of BIP-85 derivation paths for BIP-39 child mnemonics.
+const BIP85_PURPOSE: u32 = 83696968;
+const BIP85_APPLICATION_BIP39: u32 = 39;
+const BIP85_LANGUAGE_ENGLISH: u32 = 0;
+
 /// Ed25519 follows SLIP-0010 using hardened path: m/44'/784'/0'/0'/{index}'
 /// Secp256k1 follows BIP-32/44 using path where the first 3 levels are hardened: m/54'/784'/0'/0/{index}
-/// Secp256r1 follows BIP-32/44 using path where the first 3 levels are hardened: m/74'/784'/0'/0/{index}
+/// Secp256r1 follows BIP-32/44 using path where the first 3 levels are hardened: m/74'/784'/0'/0/{index},
+/// or where all levels are hardened: m/74'/784'/0'/0'/{index}', for HSMs that only derive hardened keys.
 /// Note that the purpose node is used to distinguish signature schemes.
 pub fn derive_key_pair_from_path(
     seed: &[u8],
@@ -145,15 +156,15 @@ pub fn validate_path(
         SignatureScheme::Secp256r1 => {
             match path {
                 Some(p) => {
-                    // The derivation path must be hardened at first 3 levels with purpose = 74, coin_type = 784
+                    // The derivation path must be hardened at first 3 levels with purpose = 74, coin_type = 784,
+                    // and the last 2 levels must be either both hardened or both not hardened.
                     if let &[purpose, coin_type, account, change, address] = p.as_ref() {
                         if Some(purpose)
                             == ChildNumber::new(DERVIATION_PATH_PURPOSE_SECP256R1, true).ok()
                             && Some(coin_type)
                                 == ChildNumber::new(DERIVATION_PATH_COIN_TYPE, true).ok()
                             && account.is_hardened()
-                            && !change.is_hardened()
-                            && !address.is_hardened()
+                            && change.is_hardened() == address.is_hardened()
                         {
                             Ok(p)
                         } else {
@@ -185,6 +196,196 @@ pub fn validate_path(
     }
 }
 
+/// The derivation path of the account at `index`, which varies the account level of the default
+/// path the same way the Sui Wallet does: m/44'/784'/{index}'/0'/0' for Ed25519, and
+/// m/54'/784'/{index}'/0/0 and m/74'/784'/{index}'/0/0 for Secp256k1 and Secp256r1.
+pub fn derivation_path_for_index(
+    key_scheme: &SignatureScheme,
+    index: u32,
+) -> Result<DerivationPath, SuiError> {
+    let path = match key_scheme {
+        SignatureScheme::ED25519 => {
+            format!(
+                "m/{DERVIATION_PATH_PURPOSE_ED25519}'/{DERIVATION_PATH_COIN_TYPE}'/{index}'/0'/0'"
+            )
+        }
+        SignatureScheme::Secp256k1 => {
+            format!(
+                "m/{DERVIATION_PATH_PURPOSE_SECP256K1}'/{DERIVATION_PATH_COIN_TYPE}'/{index}'/0/0"
+            )
+        }
+        SignatureScheme::Secp256r1 => {
+            format!(
+                "m/{DERVIATION_PATH_PURPOSE_SECP256R1}'/{DERIVATION_PATH_COIN_TYPE}'/{index}'/0/0"
+            )
+        }
+        _ => {
+            return Err(SuiErrorKind::UnsupportedFeatureError {
+                error: format!("key derivation not supported {:?}", key_scheme),
+            }
+            .into());
+        }
+    };
+    let path = path
+        .parse()
+        .map_err(|_| SuiErrorKind::SignatureKeyGenError("Cannot parse path".to_string()))?;
+    validate_path(key_scheme, Some(path))
+}
+
+/// Derive the first `count` accounts of a mnemonic phrase, using [derivation_path_for_index] for
+/// each index. The same phrase always gives the same addresses, which makes it suitable for test
+/// fixtures and examples that need stable addresses.
+pub fn derive_key_pairs_from_mnemonic(
+    phrase: &str,
+    key_scheme: &SignatureScheme,
+    count: u32,
+) -> Result<Vec<(SuiAddress, SuiKeyPair)>, anyhow::Error> {
+    let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+        .map_err(|e| anyhow!("Invalid mnemonic phrase: {:?}", e))?;
+    let seed = Seed::new(&mnemonic, "");
+    (0..count)
+        .map(|index| {
+            let path = derivation_path_for_index(key_scheme, index)?;
+            derive_key_pair_from_path(seed.as_bytes(), Some(path), key_scheme)
+                .map_err(|e| anyhow!("Failed to derive keypair {index}: {:?}", e))
+        })
+        .collect()
+}
+
+/// Derive the BIP-39 child mnemonic at `index` from the master key of `phrase`, following BIP-85
+/// (m/83696968'/39'/0'/{words}'/{index}'). Each child is an independent mnemonic that can be handed
+/// to another wallet or device, and derived again from the master phrase if it is lost. Returns the
+/// child mnemonic and its derivation path.
+pub fn derive_bip85_child_mnemonic(
+    phrase: &str,
+    word_length: Option<String>,
+    index: u32,
+) -> Result<(String, DerivationPath), anyhow::Error> {
+    let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+        .map_err(|e| anyhow!("Invalid mnemonic phrase: {:?}", e))?;
+    let seed = Seed::new(&mnemonic, "");
+    let master =
+        XPrv::new(seed.as_bytes()).map_err(|e| anyhow!("Failed to derive master key: {e}"))?;
+    derive_bip85_child_mnemonic_from_xprv(&master, parse_word_length(word_length)?, index)
+}
+
+/// Derive the BIP-39 child mnemonic at `index` from the BIP-32 master key `master`, following
+/// BIP-85. Only 12, 18 and 24 word children are defined by BIP-85.
+pub fn derive_bip85_child_mnemonic_from_xprv(
+    master: &XPrv,
+    mnemonic_type: MnemonicType,
+    index: u32,
+) -> Result<(String, DerivationPath), anyhow::Error> {
+    let words = mnemonic_type.word_count();
+    ensure!(
+        matches!(words, 12 | 18 | 24),
+        "BIP-85 only defines child mnemonics of 12, 18 or 24 words"
+    );
+
+    let path: DerivationPath = format!(
+        "m/{BIP85_PURPOSE}'/{BIP85_APPLICATION_BIP39}'/{BIP85_LANGUAGE_ENGLISH}'/{words}'/{index}'"
+    )
+    .parse()
+    .map_err(|e| anyhow!("Invalid child index {index}: {e}"))?;
+    let child = path
+        .as_ref()
+        .iter()
+        .try_fold(master.clone(), |xprv, child| xprv.derive_child(*child))
+        .map_err(|e| anyhow!("Failed to derive child key: {e}"))?;
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(b"bip-entropy-from-k")
+        .expect("HMAC accepts keys of any length");
+    mac.update(&child.private_key().to_bytes());
+    let entropy = mac.finalize().into_bytes();
+
+    let mnemonic = Mnemonic::from_entropy(
+        &entropy[..mnemonic_type.entropy_bits() / 8],
+        Language::English,
+    )
+    .map_err(|e| anyhow!("Failed to encode child mnemonic: {:?}", e))?;
+    Ok((mnemonic.phrase().to_string(), path))
+}
+
+/// Search the accounts of `seed`, as derived by [derivation_path_for_index], for one whose address
+/// starts with the hex digits of `prefix`. Account indexes below `max_index` are split between
+/// `workers` threads. Returns the lowest matching index and its key pair, or `None` if no account
+/// below `max_index` matches.
+pub fn find_vanity_account(
+    seed: &[u8],
+    key_scheme: &SignatureScheme,
+    prefix: &str,
+    workers: usize,
+    max_index: u32,
+) -> Result<Option<(u32, SuiAddress, SuiKeyPair)>, anyhow::Error> {
+    let prefix = prefix.trim_start_matches("0x").to_lowercase();
+    ensure!(
+        prefix.len() <= 64 && prefix.chars().all(|c| c.is_ascii_hexdigit()),
+        "The vanity prefix must be at most 64 hex digits"
+    );
+    // Account indexes are hardened, so they must be below 2^31.
+    let max_index = max_index.min(ChildNumber::HARDENED_FLAG);
+    let workers = workers.max(1) as u32;
+
+    // The lowest matching index found so far. Workers stop once their next index is above it.
+    let found = AtomicU32::new(u32::MAX);
+    let result = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                let (found, prefix) = (&found, &prefix);
+                s.spawn(move || -> Result<(), anyhow::Error> {
+                    let mut index = worker;
+                    while index < max_index && index < found.load(Ordering::Relaxed) {
+                        let path = derivation_path_for_index(key_scheme, index)?;
+                        let (address, _) = derive_key_pair_from_path(seed, Some(path), key_scheme)?;
+                        if Hex::encode(address).starts_with(prefix.as_str()) {
+                            found.fetch_min(index, Ordering::Relaxed);
+                            break;
+                        }
+                        index = match index.checked_add(workers) {
+                            Some(next) => next,
+                            None => break,
+                        };
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .try_for_each(|h| h.join().expect("Vanity search worker panicked"))
+    });
+    result?;
+
+    let index = found.into_inner();
+    if index == u32::MAX {
+        return Ok(None);
+    }
+    let path = derivation_path_for_index(key_scheme, index)?;
+    let (address, kp) = derive_key_pair_from_path(seed, Some(path), key_scheme)?;
+    Ok(Some((index, address, kp)))
+}
+
+/// Generate a new mnemonic and search its accounts for an address starting with `prefix`, using
+/// [find_vanity_account]. Returns the address, key pair, derivation path and mnemonic phrase of
+/// the match, so that the key can be recovered from the phrase like any other account.
+pub fn generate_vanity_key(
+    key_scheme: SignatureScheme,
+    prefix: &str,
+    word_length: Option<String>,
+    workers: usize,
+    max_index: u32,
+) -> Result<Option<(SuiAddress, SuiKeyPair, DerivationPath, String)>, anyhow::Error> {
+    let mnemonic = Mnemonic::new(parse_word_length(word_length)?, Language::English);
+    let seed = Seed::new(&mnemonic, "");
+    let Some((index, address, kp)) =
+        find_vanity_account(seed.as_bytes(), &key_scheme, prefix, workers, max_index)?
+    else {
+        return Ok(None);
+    };
+    let path = derivation_path_for_index(&key_scheme, index)?;
+    Ok(Some((address, kp, path, mnemonic.phrase().to_string())))
+}
+
 pub fn generate_new_key(
     key_scheme: SignatureScheme,
     derivation_path: Option<DerivationPath>,
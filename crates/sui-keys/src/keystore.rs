@@ -18,14 +18,15 @@ use rand::{SeedableRng, rngs::StdRng};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use shared_crypto::intent::{Intent, IntentMessage};
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::fs;
-use std::io::BufReader;
+use std::io::{BufReader, Write as _};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
@@ -33,6 +34,9 @@ use sui_types::crypto::{
 };
 
 pub const ALIASES_FILE_EXTENSION: &str = "aliases";
+/// How long to wait for another process to release the lock on a keystore.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
@@ -236,17 +240,38 @@ impl Display for Keystore {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Alias {
     pub alias: String,
     pub public_key_base64: String,
 }
 
+/// A keystore saved to a file of keys, and a file of their aliases next to it.
+///
+/// Several processes can share the files: saving locks them, merges the changes other processes
+/// saved since they were loaded, and replaces them atomically.
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
     aliases: BTreeMap<SuiAddress, Alias>,
     path: Option<PathBuf>,
+    /// The keys and aliases in the files when they were last read or written.
+    on_disk: Snapshot,
+}
+
+#[derive(Default)]
+struct Snapshot {
+    keys: BTreeSet<SuiAddress>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Snapshot {
+    fn of(keys: &BTreeMap<SuiAddress, SuiKeyPair>, aliases: &BTreeMap<SuiAddress, Alias>) -> Self {
+        Self {
+            keys: keys.keys().copied().collect(),
+            aliases: aliases.clone(),
+        }
+    }
 }
 
 impl Serialize for FileBasedKeystore {
@@ -392,15 +417,15 @@ impl AccountKeystore for FileBasedKeystore {
         new_alias: Option<&str>,
     ) -> Result<String, anyhow::Error> {
         let new_alias_name = self.update_alias_value(old_alias, new_alias)?;
-        self.save_aliases().await?;
+        self.save().await?;
         Ok(new_alias_name)
     }
 }
 
 impl FileBasedKeystore {
     pub fn load_or_create(path: &PathBuf) -> Result<Self, anyhow::Error> {
-        let keys = if path.exists() {
-            #[cfg(unix)]
+        #[cfg(unix)]
+        if path.exists() {
             let _ = set_reduced_file_permissions(path).inspect_err(|error| {
                 eprintln!(
                     "[{}]: while attempting to ensure reduced file permissions on '{}'. Cannot set \
@@ -409,90 +434,43 @@ impl FileBasedKeystore {
                     path.display(),
                 );
             });
+        }
 
-            let reader =
-                BufReader::new(fs::File::open(path).with_context(|| {
-                    format!("Cannot open the keystore file: {}", path.display())
-                })?);
-            let kp_strings: Vec<String> = serde_json::from_reader(reader).with_context(|| {
-                format!("Cannot deserialize the keystore file: {}", path.display(),)
-            })?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (SuiAddress::from(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))?
-        } else {
-            BTreeMap::new()
-        };
-
-        // check aliases
-        let mut aliases_path = path.clone();
-        aliases_path.set_extension(ALIASES_FILE_EXTENSION);
-
-        let aliases = if aliases_path.exists() {
-            let reader = BufReader::new(fs::File::open(&aliases_path).with_context(|| {
-                format!(
-                    "Cannot open aliases file in keystore: {}",
-                    aliases_path.display()
-                )
-            })?);
-
-            let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
-                format!(
-                    "Cannot deserialize aliases file in keystore: {}",
-                    aliases_path.display(),
-                )
-            })?;
-
-            aliases
-                .into_iter()
-                .map(|alias| {
-                    let key = PublicKey::decode_base64(&alias.public_key_base64);
-                    key.map(|k| (Into::<SuiAddress>::into(&k), alias))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
-                .map_err(|e| {
-                    anyhow!(
-                        "Invalid aliases file in keystore: {}. {}",
-                        aliases_path.display(),
-                        e
-                    )
-                })?
-        } else if keys.is_empty() {
-            BTreeMap::new()
-        } else {
-            let names: Vec<String> = random_names(HashSet::new(), keys.len());
-            let aliases = keys
-                .iter()
-                .zip_debug_eq(names)
-                .map(|((sui_address, skp), alias)| {
-                    let public_key_base64 = skp.public().encode_base64();
-                    (
-                        *sui_address,
-                        Alias {
-                            alias,
-                            public_key_base64,
-                        },
-                    )
-                })
-                .collect::<BTreeMap<_, _>>();
-            let aliases_store = serde_json::to_string_pretty(&aliases.values().collect::<Vec<_>>())
-                .with_context(|| {
-                    format!(
-                        "Cannot serialize aliases to file in keystore: {}",
-                        aliases_path.display()
-                    )
-                })?;
-
-            fs::write(aliases_path, aliases_store)?;
-            aliases
+        let keys = read_keys(path)?;
+        let aliases_path = aliases_path(path);
+        let aliases = match read_aliases(&aliases_path)? {
+            Some(aliases) => aliases,
+            None if keys.is_empty() => BTreeMap::new(),
+            None => {
+                let names: Vec<String> = random_names(HashSet::new(), keys.len());
+                let aliases = keys
+                    .iter()
+                    .zip_debug_eq(names)
+                    .map(|((sui_address, skp), alias)| {
+                        let public_key_base64 = skp.public().encode_base64();
+                        (
+                            *sui_address,
+                            Alias {
+                                alias,
+                                public_key_base64,
+                            },
+                        )
+                    })
+                    .collect::<BTreeMap<_, _>>();
+
+                let _lock = KeystoreLock::acquire(path)?;
+                // Another process may have created the aliases while waiting for the lock.
+                if let Some(aliases) = read_aliases(&aliases_path)? {
+                    aliases
+                } else {
+                    write_aliases(&aliases_path, &aliases)?;
+                    aliases
+                }
+            }
         };
 
         Ok(Self {
+            on_disk: Snapshot::of(&keys, &aliases),
             keys,
             aliases,
             path: Some(path.to_path_buf()),
@@ -503,65 +481,69 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
-    pub async fn save_aliases(&self) -> Result<(), anyhow::Error> {
-        if let Some(path) = &self.path {
-            let aliases_store =
-                serde_json::to_string_pretty(&self.aliases.values().collect::<Vec<_>>())
-                    .with_context(|| {
-                        format!(
-                            "Cannot serialize aliases to file in keystore: {}",
-                            path.display()
-                        )
-                    })?;
-
-            let mut aliases_path = path.clone();
-            aliases_path.set_extension(ALIASES_FILE_EXTENSION);
-            // no reactor for tokio::fs::write in simtest, so we use spawn_blocking
-            tokio::task::spawn_blocking(move || fs::write(aliases_path, aliases_store))
-                .await?
-                .with_context(|| format!("Cannot write aliases to file: {}", path.display()))?;
-        }
-        Ok(())
-    }
-
-    /// Keys saved as Base64 with 33 bytes `flag || privkey` ($BASE64_STR).
+    /// Save the keys and their aliases. Keys are saved as Base64 with 33 bytes
+    /// `flag || privkey` ($BASE64_STR).
     /// To see Bech32 format encoding, use `sui keytool export $SUI_ADDRESS` where
     /// $SUI_ADDRESS can be found with `sui keytool list`. Or use `sui keytool convert $BASE64_STR`
-    pub async fn save_keystore(&self) -> Result<(), anyhow::Error> {
-        if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
-                    .values()
-                    .map(|k| k.encode_base64())
-                    .collect::<Vec<_>>(),
-            )
-            .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
-            let keystore_path = path.clone();
-            // no reactor for tokio::fs::write in simtest, so we use spawn_blocking
-            tokio::task::spawn_blocking(move || {
-                let ret = fs::write(&keystore_path, store);
-                #[cfg(unix)]
-                if ret.is_ok() {
-                    let _ = set_reduced_file_permissions(&keystore_path).inspect_err(|error| {
-                        eprintln!(
-                            "While attempting to set reduced file permissions on '{}'. Cannot set \
-                                permissions for keystore file. Error: {error}",
-                            keystore_path.display()
-                        );
-                    });
-                }
-                ret
-            })
-            .await?
-            .with_context(|| format!("Cannot write keystore to file: {}", path.display()))?;
+    ///
+    /// The files are locked while saving. Keys and aliases that other processes saved since the
+    /// files were last read are kept, unless they were also changed here, and the keystore is
+    /// updated with them.
+    pub async fn save(&mut self) -> Result<(), anyhow::Error> {
+        let Some(path) = self.path.clone() else {
+            return Ok(());
+        };
+
+        // no reactor for tokio::fs in simtest, so we wait for the lock with spawn_blocking
+        let lock_path = path.clone();
+        let _lock =
+            tokio::task::spawn_blocking(move || KeystoreLock::acquire(&lock_path)).await??;
+
+        let aliases_path = aliases_path(&path);
+        let mut keys = read_keys(&path)?;
+        let mut aliases = read_aliases(&aliases_path)?.unwrap_or_default();
+
+        // Apply the changes made here since the files were read to their current contents.
+        keys.retain(|address, _| {
+            self.keys.contains_key(address) || !self.on_disk.keys.contains(address)
+        });
+        for (address, key) in &self.keys {
+            if !self.on_disk.keys.contains(address) {
+                keys.insert(*address, key.copy());
+            }
+        }
+        aliases.retain(|address, _| {
+            self.aliases.contains_key(address) || !self.on_disk.aliases.contains_key(address)
+        });
+        for (address, alias) in &self.aliases {
+            if self.on_disk.aliases.get(address) != Some(alias) {
+                aliases.insert(*address, alias.clone());
+            }
         }
-        Ok(())
-    }
 
-    pub async fn save(&self) -> Result<(), anyhow::Error> {
-        self.save_aliases().await?;
-        self.save_keystore().await?;
+        let mut owners = BTreeMap::new();
+        for (address, alias) in &aliases {
+            if let Some(other) = owners.insert(&alias.alias, address) {
+                bail!(
+                    "Alias {} was given to both {other} and {address} by concurrent changes to \
+                     the keystore {}. Please retry.",
+                    alias.alias,
+                    path.display(),
+                );
+            }
+        }
+
+        write_aliases(&aliases_path, &aliases)?;
+        let store = serde_json::to_string_pretty(
+            &keys.values().map(|k| k.encode_base64()).collect::<Vec<_>>(),
+        )
+        .with_context(|| format!("Cannot serialize keystore to file: {}", path.display()))?;
+        write_atomically(&path, &store)
+            .with_context(|| format!("Cannot write keystore to file: {}", path.display()))?;
+
+        self.on_disk = Snapshot::of(&keys, &aliases);
+        self.keys = keys;
+        self.aliases = aliases;
         Ok(())
     }
 
@@ -570,6 +552,144 @@ impl FileBasedKeystore {
     }
 }
 
+fn aliases_path(path: &Path) -> PathBuf {
+    let mut aliases_path = path.to_path_buf();
+    aliases_path.set_extension(ALIASES_FILE_EXTENSION);
+    aliases_path
+}
+
+/// The keys in the keystore file at `path`, if it exists.
+fn read_keys(path: &Path) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let reader = BufReader::new(
+        fs::File::open(path)
+            .with_context(|| format!("Cannot open the keystore file: {}", path.display()))?,
+    );
+    let kp_strings: Vec<String> = serde_json::from_reader(reader)
+        .with_context(|| format!("Cannot deserialize the keystore file: {}", path.display(),))?;
+    kp_strings
+        .iter()
+        .map(|kpstr| {
+            let key = SuiKeyPair::decode_base64(kpstr);
+            key.map(|k| (SuiAddress::from(&k.public()), k))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .map_err(|e| anyhow!("Invalid keystore file: {}. {}", path.display(), e))
+}
+
+/// The aliases in the aliases file at `aliases_path`, if it exists.
+fn read_aliases(aliases_path: &Path) -> Result<Option<BTreeMap<SuiAddress, Alias>>, anyhow::Error> {
+    if !aliases_path.exists() {
+        return Ok(None);
+    }
+
+    let reader = BufReader::new(fs::File::open(aliases_path).with_context(|| {
+        format!(
+            "Cannot open aliases file in keystore: {}",
+            aliases_path.display()
+        )
+    })?);
+
+    let aliases: Vec<Alias> = serde_json::from_reader(reader).with_context(|| {
+        format!(
+            "Cannot deserialize aliases file in keystore: {}",
+            aliases_path.display(),
+        )
+    })?;
+
+    aliases
+        .into_iter()
+        .map(|alias| {
+            let key = PublicKey::decode_base64(&alias.public_key_base64);
+            key.map(|k| (Into::<SuiAddress>::into(&k), alias))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()
+        .map(Some)
+        .map_err(|e| {
+            anyhow!(
+                "Invalid aliases file in keystore: {}. {}",
+                aliases_path.display(),
+                e
+            )
+        })
+}
+
+fn write_aliases(
+    aliases_path: &Path,
+    aliases: &BTreeMap<SuiAddress, Alias>,
+) -> Result<(), anyhow::Error> {
+    let aliases_store = serde_json::to_string_pretty(&aliases.values().collect::<Vec<_>>())
+        .with_context(|| {
+            format!(
+                "Cannot serialize aliases to file in keystore: {}",
+                aliases_path.display()
+            )
+        })?;
+    write_atomically(aliases_path, &aliases_store)
+        .with_context(|| format!("Cannot write aliases to file: {}", aliases_path.display()))
+}
+
+/// Replace the contents of `path` by renaming a temporary file over it, so that readers never see
+/// a partially written file. The file is only readable by its owner.
+fn write_atomically(path: &Path, contents: &str) -> Result<(), anyhow::Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(contents.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.persist(path)?;
+    Ok(())
+}
+
+/// An exclusive advisory lock on the files of a keystore, released when dropped.
+///
+/// The lock is taken on a separate `<keystore>.lock` file, as the keystore files themselves are
+/// replaced when they are written.
+struct KeystoreLock {
+    _file: fs::File,
+}
+
+impl KeystoreLock {
+    /// Lock the keystore at `path`, waiting up to [`LOCK_TIMEOUT`] for other processes to release
+    /// it.
+    fn acquire(path: &Path) -> Result<Self, anyhow::Error> {
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        let lock_path = PathBuf::from(lock_path);
+
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Cannot open keystore lock file: {}", lock_path.display()))?;
+
+        let start = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(fs::TryLockError::WouldBlock) if start.elapsed() < LOCK_TIMEOUT => {
+                    std::thread::sleep(LOCK_RETRY_INTERVAL)
+                }
+                Err(fs::TryLockError::WouldBlock) => bail!(
+                    "Timed out waiting for another process to release the keystore lock: {}",
+                    lock_path.display()
+                ),
+                Err(fs::TryLockError::Error(e)) => {
+                    return Err(e).with_context(|| {
+                        format!("Cannot lock the keystore: {}", lock_path.display())
+                    });
+                }
+            }
+        }
+    }
+}
+
 #[cfg(unix)]
 fn set_reduced_file_permissions(path: impl AsRef<Path>) -> Result<(), anyhow::Error> {
     let path = path.as_ref();
@@ -6,4 +6,5 @@ pub mod key_derive;
 pub mod key_identity;
 pub mod keypair_file;
 pub mod keystore;
+pub mod passkey;
 pub mod random_names;
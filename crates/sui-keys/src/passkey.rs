@@ -0,0 +1,327 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with passkeys (WebAuthn credentials).
+//!
+//! The private key of a passkey never leaves its authenticator. To sign, the authenticator is
+//! asked for an assertion over the hash of the client data, which embeds a challenge. For Sui, the
+//! challenge is the digest of the intent message, and the assertion is assembled into a
+//! `PasskeyAuthenticator` signature.
+//!
+//! Authenticators are reached through a helper binary that talks to the OS-level authenticator
+//! (e.g. Windows Hello, the macOS keychain or a security key), using the same JSON-RPC over
+//! stdin/stdout protocol as external signers. The helper implements two methods:
+//!
+//! - `create_passkey`, with params `{ rpId, userName, challenge }`, returning
+//!   `{ credentialId, publicKey }`: the Base64URL credential ID and the Base64 DER
+//!   (SubjectPublicKeyInfo) encoded P-256 public key of a new credential.
+//! - `get_passkey_assertion`, with params `{ rpId, credentialId, clientDataHash }`, returning
+//!   `{ authenticatorData, signature }`: the Base64 authenticator data and DER encoded signature of
+//!   the assertion.
+//!
+//! Passkeys are not part of any [`Keystore`](crate::keystore::Keystore):
+//! [`AccountKeystore::sign_secure`](crate::keystore::AccountKeystore::sign_secure) returns a
+//! plain `Signature`, which cannot carry the authenticator data and client data of an assertion.
+//! [`PasskeySigner`] is used directly instead, and the CLI only exposes it through the
+//! `sui keytool passkey-register` and `passkey-test` commands.
+
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow, ensure};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::{HashFunction, Sha256};
+use fastcrypto::rsa::Base64UrlUnpadded;
+use fastcrypto::secp256r1::Secp256r1PublicKey;
+use fastcrypto::traits::ToFromBytes;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::pkcs8::DecodePublicKey;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use shared_crypto::intent::IntentMessage;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{PublicKey, Signature, SignatureScheme};
+use sui_types::passkey_authenticator::{
+    PasskeyAuthenticator, RawPasskeyAuthenticator, to_signing_message,
+};
+use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
+use sui_types::signature_verification::VerifiedDigestCache;
+
+use crate::external::{CommandRunner, StdCommandRunner};
+
+/// A passkey registered with an authenticator.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyCredential {
+    /// The relying party the credential is scoped to.
+    pub rp_id: String,
+    /// The Base64URL encoded credential ID assigned by the authenticator.
+    pub credential_id: String,
+    pub public_key: PublicKey,
+}
+
+impl PasskeyCredential {
+    pub fn address(&self) -> SuiAddress {
+        SuiAddress::from(&self.public_key)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedPasskey {
+    credential_id: String,
+    public_key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PasskeyAssertion {
+    authenticator_data: String,
+    signature: String,
+}
+
+/// Creates passkeys and signs with them through a helper binary.
+pub struct PasskeySigner {
+    helper: String,
+    command_runner: Box<dyn CommandRunner>,
+}
+
+impl PasskeySigner {
+    /// A signer using the helper binary `helper`, found on the `PATH` if it is not a path.
+    pub fn new(helper: impl Into<String>) -> Self {
+        Self::new_with_runner(helper, Box::new(StdCommandRunner))
+    }
+
+    pub fn new_with_runner(
+        helper: impl Into<String>,
+        command_runner: Box<dyn CommandRunner>,
+    ) -> Self {
+        Self {
+            helper: helper.into(),
+            command_runner,
+        }
+    }
+
+    /// Register a new passkey for `user_name` with the relying party `rp_id`.
+    pub async fn create_credential(
+        &self,
+        rp_id: &str,
+        user_name: &str,
+    ) -> Result<PasskeyCredential, anyhow::Error> {
+        let mut challenge = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut challenge);
+
+        let created: CreatedPasskey = self
+            .call(
+                "create_passkey",
+                json!({
+                    "rpId": rp_id,
+                    "userName": user_name,
+                    "challenge": Base64UrlUnpadded::encode_string(&challenge),
+                }),
+            )
+            .await?;
+
+        let der = Base64::decode(&created.public_key)
+            .map_err(|e| anyhow!("Invalid Base64 passkey public key: {e}"))?;
+        let public_key = p256::PublicKey::from_public_key_der(&der).map_err(|e| {
+            anyhow!("Invalid passkey public key, only P-256 keys are supported: {e}")
+        })?;
+        let compressed = public_key.to_encoded_point(true);
+        let pk = Secp256r1PublicKey::from_bytes(compressed.as_bytes())
+            .map_err(|e| anyhow!("Invalid passkey public key: {e}"))?;
+
+        Ok(PasskeyCredential {
+            rp_id: rp_id.to_string(),
+            credential_id: created.credential_id,
+            public_key: PublicKey::Passkey((&pk).into()),
+        })
+    }
+
+    /// Ask the authenticator of `credential` for an assertion over `intent_msg`, and assemble it
+    /// into a signature. The signature is verified before it is returned.
+    pub async fn sign<T: Serialize>(
+        &self,
+        credential: &PasskeyCredential,
+        intent_msg: &IntentMessage<T>,
+    ) -> Result<GenericSignature, anyhow::Error> {
+        ensure!(
+            credential.public_key.scheme() == SignatureScheme::PasskeyAuthenticator,
+            "The credential does not have a passkey public key"
+        );
+
+        let client_data_json = client_data_json(&credential.rp_id, &to_signing_message(intent_msg));
+        let client_data_hash = Sha256::digest(client_data_json.as_bytes()).digest;
+
+        let assertion: PasskeyAssertion = self
+            .call(
+                "get_passkey_assertion",
+                json!({
+                    "rpId": credential.rp_id,
+                    "credentialId": credential.credential_id,
+                    "clientDataHash": Base64::encode(client_data_hash),
+                }),
+            )
+            .await?;
+
+        let authenticator_data = Base64::decode(&assertion.authenticator_data)
+            .map_err(|e| anyhow!("Invalid Base64 authenticator data: {e}"))?;
+        let der = Base64::decode(&assertion.signature)
+            .map_err(|e| anyhow!("Invalid Base64 assertion signature: {e}"))?;
+
+        let authenticator = PasskeyAuthenticator::try_from(RawPasskeyAuthenticator {
+            authenticator_data,
+            client_data_json,
+            user_signature: user_signature(&credential.public_key, &der)?,
+        })?;
+        authenticator
+            .verify_claims(
+                intent_msg,
+                credential.address(),
+                &VerifyParams::default(),
+                Arc::new(VerifiedDigestCache::new_empty()),
+            )
+            .context("The passkey assertion does not verify against the credential")?;
+
+        Ok(GenericSignature::PasskeyAuthenticator(authenticator))
+    }
+
+    async fn call<R: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<R, anyhow::Error> {
+        let result = self
+            .command_runner
+            .run(&self.helper, method, params)
+            .await
+            .with_context(|| format!("Passkey helper {} failed to {method}", self.helper))?;
+        serde_json::from_value(result)
+            .with_context(|| format!("Invalid {method} response from passkey helper"))
+    }
+}
+
+/// The client data of an assertion over `challenge` for `rp_id`, as a browser would build it.
+fn client_data_json(rp_id: &str, challenge: &[u8]) -> String {
+    json!({
+        "type": "webauthn.get",
+        "challenge": Base64UrlUnpadded::encode_string(challenge),
+        "origin": format!("https://{rp_id}"),
+        "crossOrigin": false,
+    })
+    .to_string()
+}
+
+/// The `flag || signature || pk` Secp256r1 signature of a DER encoded assertion signature, with
+/// its `s` normalized to the lower half of the curve order, as Sui requires.
+fn user_signature(public_key: &PublicKey, der: &[u8]) -> Result<Signature, anyhow::Error> {
+    let sig = p256::ecdsa::Signature::from_der(der)
+        .map_err(|e| anyhow!("Invalid DER assertion signature: {e}"))?;
+    let sig = sig.normalize_s().unwrap_or(sig);
+
+    let mut bytes = vec![SignatureScheme::Secp256r1.flag()];
+    bytes.extend_from_slice(&sig.to_bytes());
+    bytes.extend_from_slice(public_key.as_ref());
+    Signature::from_bytes(&bytes).map_err(|e| anyhow!("Invalid assertion signature: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::MockCommandRunner;
+    use p256::ecdsa::SigningKey;
+    use p256::ecdsa::signature::Signer;
+    use p256::pkcs8::EncodePublicKey;
+    use serde_json::Value as JsonValue;
+    use shared_crypto::intent::{Intent, PersonalMessage};
+
+    const RP_ID: &str = "www.sui.io";
+
+    /// A helper backed by an in-memory P-256 key, signing like a platform authenticator.
+    fn software_authenticator(key: SigningKey) -> MockCommandRunner {
+        let mut mock = MockCommandRunner::new();
+        mock.expect_run().returning(move |_, method, params| {
+            Ok(match method {
+                "create_passkey" => {
+                    let der = key.verifying_key().to_public_key_der().unwrap();
+                    json!({
+                        "credentialId": "Y3JlZGVudGlhbA",
+                        "publicKey": Base64::encode(der.as_bytes()),
+                    })
+                }
+                "get_passkey_assertion" => {
+                    // rpIdHash || flags (user present and verified) || signature counter.
+                    let mut authenticator_data = Sha256::digest(RP_ID.as_bytes()).digest.to_vec();
+                    authenticator_data.extend_from_slice(&[0x05, 0, 0, 0, 1]);
+                    let client_data_hash = match &params["clientDataHash"] {
+                        JsonValue::String(h) => Base64::decode(h).unwrap(),
+                        _ => panic!("Missing client data hash"),
+                    };
+                    let message = [authenticator_data.as_slice(), &client_data_hash].concat();
+                    let sig: p256::ecdsa::Signature = key.sign(&message);
+                    json!({
+                        "authenticatorData": Base64::encode(&authenticator_data),
+                        "signature": Base64::encode(sig.to_der().as_bytes()),
+                    })
+                }
+                _ => JsonValue::Null,
+            })
+        });
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_register_and_sign() {
+        let key = SigningKey::random(&mut rand::thread_rng());
+        let signer = PasskeySigner::new_with_runner(
+            "sui-passkey-helper",
+            Box::new(software_authenticator(key)),
+        );
+
+        let credential = signer.create_credential(RP_ID, "alice").await.unwrap();
+        assert_eq!(
+            credential.public_key.scheme(),
+            SignatureScheme::PasskeyAuthenticator
+        );
+
+        let msg = IntentMessage::new(
+            Intent::personal_message(),
+            PersonalMessage {
+                message: b"hello".to_vec(),
+            },
+        );
+        let signature = signer.sign(&credential, &msg).await.unwrap();
+        let GenericSignature::PasskeyAuthenticator(authenticator) = signature else {
+            panic!("Expected a passkey signature");
+        };
+        assert_eq!(
+            authenticator.get_pk().unwrap().as_ref(),
+            credential.public_key.as_ref()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assertion_from_wrong_key_is_rejected() {
+        let signer = PasskeySigner::new_with_runner(
+            "sui-passkey-helper",
+            Box::new(software_authenticator(SigningKey::random(
+                &mut rand::thread_rng(),
+            ))),
+        );
+        let other = PasskeySigner::new_with_runner(
+            "sui-passkey-helper",
+            Box::new(software_authenticator(SigningKey::random(
+                &mut rand::thread_rng(),
+            ))),
+        );
+
+        let credential = signer.create_credential(RP_ID, "alice").await.unwrap();
+        let msg = IntentMessage::new(
+            Intent::personal_message(),
+            PersonalMessage {
+                message: b"hello".to_vec(),
+            },
+        );
+        assert!(other.sign(&credential, &msg).await.is_err());
+    }
+}
@@ -4,9 +4,15 @@
 use std::fs;
 use std::str::FromStr;
 
+use bip32::{DerivationPath, XPrv};
+use bip39::MnemonicType;
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::EncodeDecodeBase64;
-use sui_keys::key_derive::generate_new_key;
+use sui_keys::key_derive::{
+    derivation_path_for_index, derive_bip85_child_mnemonic, derive_bip85_child_mnemonic_from_xprv,
+    derive_key_pair_from_path, derive_key_pairs_from_mnemonic, find_vanity_account,
+    generate_new_key, validate_path,
+};
 use tempfile::TempDir;
 
 use sui_keys::keystore::{
@@ -279,6 +285,116 @@ async fn sui_wallet_address_mnemonic_test() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn derive_key_pairs_from_mnemonic_test() -> Result<(), anyhow::Error> {
+    let phrase = "result crisp session latin must fruit genuine question prevent start coconut brave speak student dismiss";
+
+    for scheme in [
+        SignatureScheme::ED25519,
+        SignatureScheme::Secp256k1,
+        SignatureScheme::Secp256r1,
+    ] {
+        let derived = derive_key_pairs_from_mnemonic(phrase, &scheme, 3)?;
+        assert_eq!(derived.len(), 3);
+
+        // Deriving again gives the same accounts, and each index matches an import of its path.
+        let again = derive_key_pairs_from_mnemonic(phrase, &scheme, 2)?;
+        let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+        for (index, (address, _)) in derived.iter().enumerate() {
+            if let Some((expected, _)) = again.get(index) {
+                assert_eq!(address, expected);
+            }
+            let path = derivation_path_for_index(&scheme, index as u32)?;
+            let imported = keystore
+                .import_from_mnemonic(phrase, scheme, Some(path), None)
+                .await?;
+            assert_eq!(*address, imported);
+        }
+        assert_ne!(derived[0].0, derived[1].0);
+    }
+
+    // The first Ed25519 account is the one the Sui Wallet derives from the phrase.
+    let (address, _) = &derive_key_pairs_from_mnemonic(phrase, &SignatureScheme::ED25519, 1)?[0];
+    assert_eq!(
+        *address,
+        SuiAddress::from_str("0x936accb491f0facaac668baaedcf4d0cfc6da1120b66f77fa6a43af718669973")?
+    );
+
+    assert!(derivation_path_for_index(&SignatureScheme::BLS12381, 0).is_err());
+    assert!(derive_key_pairs_from_mnemonic("not a phrase", &SignatureScheme::ED25519, 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn secp256r1_hardened_path_test() {
+    let path = |p: &str| Some(DerivationPath::from_str(p).unwrap());
+    let scheme = SignatureScheme::Secp256r1;
+
+    assert!(validate_path(&scheme, path("m/74'/784'/0'/0/0")).is_ok());
+    assert!(validate_path(&scheme, path("m/74'/784'/3'/0'/7'")).is_ok());
+    assert!(validate_path(&scheme, path("m/74'/784'/0'/0'/0")).is_err());
+    assert!(validate_path(&scheme, path("m/74'/784'/0/0'/0'")).is_err());
+
+    // Hardened and non-hardened paths derive different keys.
+    let seed = [7u8; 64];
+    let (hardened, _) =
+        derive_key_pair_from_path(&seed, path("m/74'/784'/0'/0'/0'"), &scheme).unwrap();
+    let (normal, _) = derive_key_pair_from_path(&seed, path("m/74'/784'/0'/0/0"), &scheme).unwrap();
+    assert_ne!(hardened, normal);
+}
+
+/// Test vector from BIP-85, for a 12 word child mnemonic at index 0.
+#[test]
+fn bip85_child_mnemonic_test() -> Result<(), anyhow::Error> {
+    let master = XPrv::from_str(
+        "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb",
+    )?;
+    let (mnemonic, path) =
+        derive_bip85_child_mnemonic_from_xprv(&master, MnemonicType::Words12, 0)?;
+    assert_eq!(
+        mnemonic,
+        "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+    );
+    assert_eq!(path.to_string(), "m/83696968'/39'/0'/12'/0'");
+
+    let phrase = "result crisp session latin must fruit genuine question prevent start coconut brave speak student dismiss";
+    let (first, _) = derive_bip85_child_mnemonic(phrase, Some("word24".to_string()), 0)?;
+    let (second, _) = derive_bip85_child_mnemonic(phrase, Some("word24".to_string()), 1)?;
+    assert_eq!(first.split(' ').count(), 24);
+    assert_ne!(first, second);
+    assert_eq!(
+        first,
+        derive_bip85_child_mnemonic(phrase, Some("word24".to_string()), 0)?.0
+    );
+
+    assert!(derive_bip85_child_mnemonic(phrase, Some("word15".to_string()), 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn vanity_account_test() -> Result<(), anyhow::Error> {
+    let seed = [3u8; 64];
+    let scheme = SignatureScheme::ED25519;
+    let (index, address, _) = find_vanity_account(&seed, &scheme, "0xa", 4, 1_000)?
+        .expect("a 1 digit prefix is found within 1000 accounts");
+    assert!(address.to_string().starts_with("0xa"));
+
+    // The lowest matching index is returned, however the work is split.
+    for i in 0..index {
+        let path = derivation_path_for_index(&scheme, i)?;
+        let (other, _) = derive_key_pair_from_path(&seed, Some(path), &scheme)?;
+        assert!(!other.to_string().starts_with("0xa"));
+    }
+    assert_eq!(
+        find_vanity_account(&seed, &scheme, "A", 1, 1_000)?.map(|(i, _, _)| i),
+        Some(index)
+    );
+
+    assert!(find_vanity_account(&seed, &scheme, "0xaaaaaaaa", 2, 10)?.is_none());
+    assert!(find_vanity_account(&seed, &scheme, "xyz", 2, 10).is_err());
+    Ok(())
+}
+
 #[test]
 fn keystore_display_test() -> Result<(), anyhow::Error> {
     let temp_dir = TempDir::new().unwrap();
@@ -372,3 +488,68 @@ async fn keystore_file_permissions_test() {
         "Keystore file permissions should remain 0o600 after operations"
     );
 }
+
+#[tokio::test]
+async fn concurrent_keystore_saves_are_merged_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+
+    let mut keystore = Keystore::from(FileBasedKeystore::load_or_create(&keystore_path).unwrap());
+    let removed = keystore
+        .generate(None, GenerateOptions::default())
+        .await
+        .unwrap()
+        .address;
+
+    // Each task loads the keystore before any of them saves it.
+    let keystores: Vec<_> = (0..8)
+        .map(|_| FileBasedKeystore::load_or_create(&keystore_path).unwrap())
+        .collect();
+    let handles = keystores.into_iter().map(|keystore| {
+        tokio::spawn(async move {
+            let mut keystore = Keystore::from(keystore);
+            keystore
+                .generate(None, GenerateOptions::default())
+                .await
+                .unwrap()
+                .address
+        })
+    });
+    let mut generated = vec![];
+    for handle in handles.collect::<Vec<_>>() {
+        generated.push(handle.await.unwrap());
+    }
+
+    // A keystore loaded before the others saved keeps their keys when it saves.
+    keystore.remove(removed).await.unwrap();
+    assert_eq!(keystore.addresses().len(), generated.len());
+
+    let reloaded = Keystore::from(FileBasedKeystore::load_or_create(&keystore_path).unwrap());
+    let mut addresses = reloaded.addresses();
+    addresses.sort();
+    generated.sort();
+    assert_eq!(addresses, generated);
+    assert_eq!(reloaded.aliases().len(), generated.len());
+}
+
+#[tokio::test]
+async fn conflicting_concurrent_aliases_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+
+    let mut first = Keystore::from(FileBasedKeystore::load_or_create(&keystore_path).unwrap());
+    let mut second = Keystore::from(FileBasedKeystore::load_or_create(&keystore_path).unwrap());
+    first
+        .generate(Some("alice".to_string()), GenerateOptions::default())
+        .await
+        .unwrap();
+    let err = second
+        .generate(Some("alice".to_string()), GenerateOptions::default())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Alias alice"), "{err}");
+
+    // The keystore is left as the first save wrote it.
+    let reloaded = Keystore::from(FileBasedKeystore::load_or_create(&keystore_path).unwrap());
+    assert_eq!(reloaded.addresses().len(), 1);
+}
@@ -0,0 +1,124 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the proofs served by fullnodes over JSON-RPC.
+//!
+//! Only built with the `json-rpc` feature, so that light clients that do not talk to a JSON-RPC
+//! endpoint do not depend on its types. Nothing in a response is trusted: every part of it is
+//! checked against the committees tracked by the [`LightClient`].
+
+use sui_json_rpc_types::{SuiFinalityCertificate, SuiTransactionProof};
+use sui_types::{
+    base_types::ExecutionDigests,
+    crypto::AuthorityStrongQuorumSignInfo,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary},
+};
+
+use crate::light_client::LightClient;
+use crate::proof::{
+    base::{Proof, ProofContents, ProofTarget},
+    error::{ProofError, ProofResult},
+    transaction_proof::TransactionProof,
+};
+
+fn decode_error(e: bcs::Error) -> ProofError {
+    ProofError::GeneralError(e.to_string())
+}
+
+impl Proof {
+    /// Assemble a proof for `targets` from a fullnode's `sui_getTransactionProof` response.
+    /// The returned proof must still be verified against a trusted committee.
+    pub fn from_transaction_proof_response(
+        response: &SuiTransactionProof,
+        targets: ProofTarget,
+    ) -> ProofResult<Self> {
+        Ok(Proof {
+            targets,
+            checkpoint_summary: bcs::from_bytes(&response.checkpoint_summary)
+                .map_err(decode_error)?,
+            proof_contents: ProofContents::TransactionProof(TransactionProof {
+                checkpoint_contents: bcs::from_bytes(&response.checkpoint_contents)
+                    .map_err(decode_error)?,
+                transaction: bcs::from_bytes(&response.transaction).map_err(decode_error)?,
+                effects: bcs::from_bytes(&response.effects).map_err(decode_error)?,
+                events: response
+                    .events
+                    .as_deref()
+                    .map(bcs::from_bytes)
+                    .transpose()
+                    .map_err(decode_error)?,
+            }),
+        })
+    }
+}
+
+impl LightClient {
+    /// Verify a fullnode's `sui_getTransactionProof` response and return the now authenticated
+    /// effects and events of the requested transaction.
+    pub fn verify_transaction_proof_response(
+        &mut self,
+        response: &SuiTransactionProof,
+    ) -> ProofResult<(TransactionEffects, Option<TransactionEvents>)> {
+        let effects: TransactionEffects =
+            bcs::from_bytes(&response.effects).map_err(decode_error)?;
+        let events: Option<TransactionEvents> = response
+            .events
+            .as_deref()
+            .map(bcs::from_bytes)
+            .transpose()
+            .map_err(decode_error)?;
+        // The target is derived from the response itself, so tie it to the digest that was
+        // asked for; verification then checks it against the certified checkpoint contents.
+        let digests = effects.execution_digests();
+        if digests.transaction != response.transaction_digest {
+            return Err(ProofError::TransactionDigestMismatch);
+        }
+        if effects.events_digest() != events.as_ref().map(|e| e.digest()).as_ref() {
+            return Err(ProofError::EventsDigestMismatch);
+        }
+
+        self.verify_proof(Proof::from_transaction_proof_response(
+            response,
+            ProofTarget::new_transaction(digests),
+        )?)?;
+        Ok((effects, events))
+    }
+
+    /// Verify a fullnode's `sui_getTransactionFinalityCertificate` response and return the now
+    /// authenticated transaction and effects digests.
+    pub fn verify_finality_certificate(
+        &mut self,
+        certificate: &SuiFinalityCertificate,
+    ) -> ProofResult<ExecutionDigests> {
+        let summary: CheckpointSummary =
+            bcs::from_bytes(&certificate.checkpoint_summary).map_err(decode_error)?;
+        let signature: AuthorityStrongQuorumSignInfo =
+            bcs::from_bytes(&certificate.signature).map_err(decode_error)?;
+        let contents: CheckpointContents =
+            bcs::from_bytes(&certificate.checkpoint_contents).map_err(decode_error)?;
+
+        let verified = self.verify_checkpoint(
+            CertifiedCheckpointSummary::new_from_data_and_sig(summary, signature),
+        )?;
+        if verified.epoch != certificate.epoch {
+            return Err(ProofError::EpochMismatch);
+        }
+        if verified.sequence_number != certificate.checkpoint {
+            return Err(ProofError::InvalidProof);
+        }
+        if *contents.digest() != verified.content_digest {
+            return Err(ProofError::ContentsDigestMismatch);
+        }
+
+        let digests =
+            ExecutionDigests::new(certificate.transaction_digest, certificate.effects_digest);
+        match usize::try_from(certificate.position)
+            .ok()
+            .and_then(|position| contents.iter().nth(position))
+        {
+            Some(included) if *included == digests => Ok(digests),
+            _ => Err(ProofError::TransactionDigestNotFound),
+        }
+    }
+}
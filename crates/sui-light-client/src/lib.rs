@@ -14,6 +14,11 @@ pub mod graphql;
 
 pub mod verifier;
 
+pub mod light_client;
+
+#[cfg(feature = "json-rpc")]
+pub mod json_rpc;
+
 pub mod authenticated_events;
 
 #[doc(inline)]
@@ -0,0 +1,138 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Embeddable light client state.
+//!
+//! A [`LightClient`] starts from a trusted committee (usually the genesis committee) and
+//! ratchets forward by verifying end-of-epoch checkpoint summaries: each one is certified by the
+//! committee of its epoch and names the committee of the next. Once the committee of an epoch is
+//! known, any checkpoint summary of that epoch, and any [`Proof`] anchored in one, can be
+//! verified without further network access. Committees of past epochs are kept so that proofs
+//! about older checkpoints keep verifying after a rotation.
+
+use std::collections::BTreeMap;
+
+use sui_config::genesis::Genesis;
+use sui_types::{
+    committee::{Committee, EpochId},
+    messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointSequenceNumber, VerifiedCheckpoint,
+    },
+};
+
+use crate::proof::{
+    base::{Proof, ProofTarget, ProofVerifier},
+    committee::extract_new_committee_info,
+    error::{ProofError, ProofResult},
+};
+
+pub struct LightClient {
+    committees: BTreeMap<EpochId, Committee>,
+    highest_verified_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+impl LightClient {
+    /// Create a light client that trusts `committee` for its epoch.
+    pub fn new(committee: Committee) -> Self {
+        Self {
+            committees: BTreeMap::from([(committee.epoch, committee)]),
+            highest_verified_checkpoint: None,
+        }
+    }
+
+    pub fn from_genesis(genesis: &Genesis) -> Self {
+        Self::new(genesis.committee())
+    }
+
+    /// The latest epoch whose committee is known.
+    pub fn epoch(&self) -> EpochId {
+        *self
+            .committees
+            .last_key_value()
+            .expect("light client always has a committee")
+            .0
+    }
+
+    /// The committee of the latest known epoch.
+    pub fn committee(&self) -> &Committee {
+        self.committees
+            .last_key_value()
+            .expect("light client always has a committee")
+            .1
+    }
+
+    pub fn committee_for_epoch(&self, epoch: EpochId) -> Option<&Committee> {
+        self.committees.get(&epoch)
+    }
+
+    pub fn highest_verified_checkpoint(&self) -> Option<CheckpointSequenceNumber> {
+        self.highest_verified_checkpoint
+    }
+
+    /// Verify a checkpoint summary against the committee of its epoch. If it is the
+    /// end-of-epoch checkpoint of the latest known epoch, the next epoch's committee is trusted
+    /// from then on.
+    pub fn verify_checkpoint(
+        &mut self,
+        summary: CertifiedCheckpointSummary,
+    ) -> ProofResult<VerifiedCheckpoint> {
+        let epoch = summary.epoch();
+        let committee = self
+            .committees
+            .get(&epoch)
+            .ok_or(ProofError::UnknownCommittee(epoch))?;
+        let verified = summary
+            .try_into_verified(committee)
+            .map_err(|e| ProofError::SummaryVerificationFailed(e.to_string()))?;
+
+        if epoch == self.epoch() && verified.end_of_epoch_data.is_some() {
+            let next_committee = extract_new_committee_info(verified.inner())?;
+            self.committees.insert(next_committee.epoch, next_committee);
+        }
+        self.highest_verified_checkpoint = self
+            .highest_verified_checkpoint
+            .max(Some(*verified.sequence_number()));
+        Ok(verified)
+    }
+
+    /// Verify end-of-epoch checkpoint summaries in epoch order, advancing the tracked committee
+    /// through each rotation.
+    pub fn sync(
+        &mut self,
+        end_of_epoch_summaries: impl IntoIterator<Item = CertifiedCheckpointSummary>,
+    ) -> ProofResult<()> {
+        for summary in end_of_epoch_summaries {
+            if summary.end_of_epoch_data.is_none() {
+                return Err(ProofError::ExpectedEndOfEpochCheckpoint);
+            }
+            self.verify_checkpoint(summary)?;
+        }
+        Ok(())
+    }
+
+    /// Verify a proof against the committee of the epoch its checkpoint belongs to. A verified
+    /// committee proof for the latest known epoch advances the tracked committee.
+    pub fn verify_proof(&mut self, proof: Proof) -> ProofResult<()> {
+        let epoch = proof.checkpoint_summary.epoch();
+        let committee = self
+            .committees
+            .get(&epoch)
+            .ok_or(ProofError::UnknownCommittee(epoch))?;
+        let sequence_number = *proof.checkpoint_summary.sequence_number();
+        let next_committee = match &proof.targets {
+            ProofTarget::Committee(target) if epoch == self.epoch() => {
+                Some(target.committee.clone())
+            }
+            _ => None,
+        };
+
+        proof.verify(committee)?;
+
+        if let Some(next_committee) = next_committee {
+            self.committees.insert(next_committee.epoch, next_committee);
+        }
+        self.highest_verified_checkpoint =
+            self.highest_verified_checkpoint.max(Some(sequence_number));
+        Ok(())
+    }
+}
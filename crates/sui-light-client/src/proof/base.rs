@@ -3,7 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use sui_types::{
-    base_types::{ObjectID, ObjectRef},
+    base_types::{ExecutionDigests, ObjectID, ObjectRef},
     committee::Committee,
     event::{Event, EventID},
     full_checkpoint_content::CheckpointData,
@@ -18,6 +18,7 @@ use crate::proof::{
     objects::ObjectsTarget,
     ocs::{OCSProof, OCSTarget},
     transaction_proof::TransactionProof,
+    transactions::TransactionTarget,
 };
 
 pub trait ProofBuilder {
@@ -38,6 +39,7 @@ pub enum ProofTarget {
     Events(EventsTarget),
     Committee(CommitteeTarget),
     ObjectCheckpointState(OCSTarget),
+    Transaction(TransactionTarget),
 }
 
 impl ProofTarget {
@@ -60,6 +62,10 @@ impl ProofTarget {
     pub fn new_ocs_non_inclusion(object_id: ObjectID) -> Self {
         ProofTarget::ObjectCheckpointState(OCSTarget::new_non_inclusion_target(object_id))
     }
+
+    pub fn new_transaction(digests: ExecutionDigests) -> Self {
+        ProofTarget::Transaction(TransactionTarget { digests })
+    }
 }
 
 impl ProofBuilder for ProofTarget {
@@ -69,6 +75,7 @@ impl ProofBuilder for ProofTarget {
             ProofTarget::Events(target) => target.construct(checkpoint),
             ProofTarget::Committee(target) => target.construct(checkpoint),
             ProofTarget::ObjectCheckpointState(target) => target.construct(checkpoint),
+            ProofTarget::Transaction(target) => target.construct(checkpoint),
         }
     }
 }
@@ -77,7 +84,8 @@ impl ProofBuilder for ProofTarget {
 /// evidence to certify objects and events.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Proof {
-    /// Targets of the proof are a committee, objects, or events that need to be certified.
+    /// Targets of the proof are a committee, transaction, objects, or events that need to be
+    /// certified.
     pub targets: ProofTarget,
 
     /// A summary of the checkpoint being certified.
@@ -91,7 +99,7 @@ pub struct Proof {
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize, Deserialize)]
 pub enum ProofContents {
-    /// Used by ObjectsTarget & EventsTarget.
+    /// Used by ObjectsTarget, EventsTarget & TransactionTarget.
     TransactionProof(TransactionProof),
 
     /// Used by CommitteeTarget.
@@ -111,7 +119,7 @@ impl ProofVerifier for Proof {
 
         // Sanity check that targets & proof types match
         match &self.targets {
-            ProofTarget::Objects(_) | ProofTarget::Events(_) => {
+            ProofTarget::Objects(_) | ProofTarget::Events(_) | ProofTarget::Transaction(_) => {
                 if !matches!(self.proof_contents, ProofContents::TransactionProof(_)) {
                     return Err(ProofError::MismatchedTargetAndProofType);
                 }
@@ -53,6 +53,12 @@ pub enum ProofError {
     #[error("Transaction digest not found in the checkpoint contents")]
     TransactionDigestNotFound,
 
+    #[error("Effects digest does not match the target")]
+    EffectsDigestMismatch,
+
+    #[error("No trusted committee for epoch {0}")]
+    UnknownCommittee(u64),
+
     #[error("Epoch overflow when calculating next epoch")]
     EpochAddOverflow,
 
@@ -8,6 +8,7 @@ pub mod committee;
 pub mod events;
 pub mod objects;
 pub mod ocs;
+pub mod transactions;
 
 // Proofs
 pub mod transaction_proof;
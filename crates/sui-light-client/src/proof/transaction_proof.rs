@@ -146,6 +146,12 @@ impl ProofContentsVerifier for TransactionProof {
         match targets {
             ProofTarget::Objects(target) => self.verify_objects(&target.objects),
             ProofTarget::Events(target) => self.verify_events(&target.events, &digests.transaction),
+            ProofTarget::Transaction(target) => {
+                if target.digests != digests {
+                    return Err(ProofError::EffectsDigestMismatch);
+                }
+                Ok(())
+            }
             _ => Err(ProofError::MismatchedTargetAndProofType),
         }
     }
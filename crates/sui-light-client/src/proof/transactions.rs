@@ -0,0 +1,29 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use sui_types::{base_types::ExecutionDigests, full_checkpoint_content::CheckpointData};
+
+use crate::proof::{
+    base::{Proof, ProofBuilder, ProofContents, ProofTarget},
+    error::ProofResult,
+    transaction_proof::TransactionProof,
+};
+
+/// A transaction and its effects, certified as executed in the checkpoint.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransactionTarget {
+    pub digests: ExecutionDigests,
+}
+
+impl ProofBuilder for TransactionTarget {
+    fn construct(self, checkpoint: &CheckpointData) -> ProofResult<Proof> {
+        let transaction_proof = TransactionProof::new(self.digests.transaction, checkpoint, false)?;
+
+        Ok(Proof {
+            targets: ProofTarget::Transaction(self),
+            checkpoint_summary: checkpoint.checkpoint_summary.clone(),
+            proof_contents: ProofContents::TransactionProof(transaction_proof),
+        })
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, path::PathBuf};
+
+use sui_light_client::light_client::LightClient;
+use sui_light_client::proof::{
+    base::{ProofBuilder, ProofTarget},
+    committee::extract_new_committee_info,
+    error::ProofError,
+};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::CheckpointData;
+use sui_types::message_envelope::Message;
+
+fn read_full_checkpoint(seq: u64) -> CheckpointData {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push(format!("test_files/{}.chk", seq));
+    let (_, data): (u8, CheckpointData) = bcs::from_bytes(&fs::read(path).unwrap()).unwrap();
+    data
+}
+
+fn light_client_and_checkpoint() -> (LightClient, CheckpointData) {
+    let committee_checkpoint = read_full_checkpoint(15918264);
+    let committee = extract_new_committee_info(&committee_checkpoint.checkpoint_summary).unwrap();
+    (LightClient::new(committee), read_full_checkpoint(16005062))
+}
+
+#[test]
+fn test_end_of_epoch_checkpoint_rotates_committee() {
+    let (mut client, checkpoint) = light_client_and_checkpoint();
+    let epoch = client.epoch();
+    assert_eq!(epoch, checkpoint.checkpoint_summary.epoch());
+
+    client
+        .verify_checkpoint(checkpoint.checkpoint_summary.clone())
+        .unwrap();
+
+    assert_eq!(client.epoch(), epoch + 1);
+    assert_eq!(
+        client.committee(),
+        &extract_new_committee_info(&checkpoint.checkpoint_summary).unwrap()
+    );
+    // The previous committee is kept for proofs about older checkpoints.
+    assert!(client.committee_for_epoch(epoch).is_some());
+    assert_eq!(
+        client.highest_verified_checkpoint(),
+        Some(*checkpoint.checkpoint_summary.sequence_number())
+    );
+}
+
+#[test]
+fn test_unknown_epoch_is_rejected() {
+    let (_, checkpoint) = light_client_and_checkpoint();
+    let next_committee = extract_new_committee_info(&checkpoint.checkpoint_summary).unwrap();
+    let mut client = LightClient::new(next_committee);
+
+    assert!(matches!(
+        client.verify_checkpoint(checkpoint.checkpoint_summary.clone()),
+        Err(ProofError::UnknownCommittee(_))
+    ));
+}
+
+#[test]
+fn test_transaction_proof_after_rotation() {
+    let (mut client, checkpoint) = light_client_and_checkpoint();
+    client
+        .verify_checkpoint(checkpoint.checkpoint_summary.clone())
+        .unwrap();
+
+    let digests = checkpoint.transactions[0].effects.execution_digests();
+    let proof = ProofTarget::new_transaction(digests)
+        .construct(&checkpoint)
+        .unwrap();
+    client.verify_proof(proof).unwrap();
+
+    let mut wrong_digests = digests;
+    wrong_digests.effects = checkpoint.transactions[1].effects.digest();
+    let proof = ProofTarget::new_transaction(wrong_digests)
+        .construct(&checkpoint)
+        .unwrap();
+    assert!(client.verify_proof(proof).is_err());
+}
+
+#[cfg(feature = "json-rpc")]
+mod json_rpc {
+    use super::*;
+    use sui_json_rpc_types::{SuiFinalityCertificate, SuiTransactionProof};
+
+    fn transaction_proof_response(
+        checkpoint: &CheckpointData,
+        index: usize,
+    ) -> SuiTransactionProof {
+        let tx = &checkpoint.transactions[index];
+        SuiTransactionProof {
+            transaction_digest: *tx.effects.transaction_digest(),
+            checkpoint: *checkpoint.checkpoint_summary.sequence_number(),
+            checkpoint_summary: bcs::to_bytes(&checkpoint.checkpoint_summary).unwrap(),
+            checkpoint_contents: bcs::to_bytes(&checkpoint.checkpoint_contents).unwrap(),
+            transaction: bcs::to_bytes(&tx.transaction).unwrap(),
+            effects: bcs::to_bytes(&tx.effects).unwrap(),
+            events: tx.events.as_ref().map(|e| bcs::to_bytes(e).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_verify_transaction_proof_response() {
+        let (mut client, checkpoint) = light_client_and_checkpoint();
+
+        let response = transaction_proof_response(&checkpoint, 1);
+        let (effects, events) = client.verify_transaction_proof_response(&response).unwrap();
+        assert_eq!(effects, checkpoint.transactions[1].effects);
+        assert_eq!(events, checkpoint.transactions[1].events);
+
+        // A response for a different transaction than the one requested is rejected.
+        let mut response = transaction_proof_response(&checkpoint, 1);
+        response.transaction_digest = *checkpoint.transactions[0].effects.transaction_digest();
+        assert!(client.verify_transaction_proof_response(&response).is_err());
+    }
+
+    fn finality_certificate(checkpoint: &CheckpointData, index: usize) -> SuiFinalityCertificate {
+        let digests = checkpoint.transactions[index].effects.execution_digests();
+        let summary = &checkpoint.checkpoint_summary;
+        SuiFinalityCertificate {
+            transaction_digest: digests.transaction,
+            effects_digest: digests.effects,
+            epoch: summary.epoch(),
+            checkpoint: *summary.sequence_number(),
+            checkpoint_summary: bcs::to_bytes(summary.data()).unwrap(),
+            signature: bcs::to_bytes(summary.auth_sig()).unwrap(),
+            checkpoint_contents: bcs::to_bytes(&checkpoint.checkpoint_contents).unwrap(),
+            position: index as u64,
+        }
+    }
+
+    #[test]
+    fn test_verify_finality_certificate() {
+        let (mut client, checkpoint) = light_client_and_checkpoint();
+
+        let certificate = finality_certificate(&checkpoint, 1);
+        let digests = client.verify_finality_certificate(&certificate).unwrap();
+        assert_eq!(
+            digests,
+            checkpoint.transactions[1].effects.execution_digests()
+        );
+
+        // The transaction must be at the given position in the contents.
+        let mut certificate = finality_certificate(&checkpoint, 1);
+        certificate.position = 0;
+        assert!(client.verify_finality_certificate(&certificate).is_err());
+
+        // The signature must be over the given summary.
+        let mut certificate = finality_certificate(&checkpoint, 1);
+        let mut summary = checkpoint.checkpoint_summary.data().clone();
+        summary.timestamp_ms += 1;
+        certificate.checkpoint_summary = bcs::to_bytes(&summary).unwrap();
+        assert!(client.verify_finality_certificate(&certificate).is_err());
+    }
+}
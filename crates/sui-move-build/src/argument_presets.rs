@@ -0,0 +1,189 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Argument-preset manifests: default values that functions declare for their parameters with
+//! `#[ext(preset(<parameter> = <value>, ...))]`, so that clients can fill in the arguments a call
+//! leaves out. For example:
+//!
+//! ```move
+//! #[ext(preset(amount = 100, recipient = @0xA))]
+//! public fun mint(cap: &mut TreasuryCap<COIN>, amount: u64, recipient: address, ...) { ... }
+//! ```
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, bail};
+use move_compiler::{
+    compiled_unit::{AnnotatedCompiledModule, FunctionInfo},
+    expansion::ast::{Address, Value_},
+    shared::known_attributes::{
+        AttributeKind_, ExternalAttributeEntry_, ExternalAttributeValue_, KnownAttribute,
+    },
+};
+use move_core_types::account_address::AccountAddress;
+use move_package_alt_compilation::compiled_package::CompiledPackage as MoveCompiledPackage;
+use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as JsonValue, json};
+
+/// Name of the external attribute that declares presets.
+const PRESET_ATTRIBUTE: &str = "preset";
+
+/// Separates a parameter's name from the suffix the compiler adds to make it unique.
+const NAME_SUFFIX_DELIM: char = '#';
+
+/// The value a parameter is filled in with when a call leaves it out.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgumentPreset {
+    /// Position of the parameter in the function's signature.
+    pub index: usize,
+    /// The value, in the same format as the arguments of `sui client call`.
+    pub value: JsonValue,
+}
+
+/// Presets of a function, keyed by parameter name.
+pub type FunctionPresets = BTreeMap<String, ArgumentPreset>;
+
+/// Presets declared by the functions of a package, keyed by module name, function name and
+/// parameter name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgumentPresetManifest {
+    pub modules: BTreeMap<String, BTreeMap<String, FunctionPresets>>,
+}
+
+/// The presets of every compiled module, keyed by module address and name, before they are
+/// narrowed down to the modules of the root package. Invalid presets are only reported if they
+/// belong to the root package.
+pub(crate) type ModulePresets =
+    BTreeMap<(AccountAddress, String), anyhow::Result<BTreeMap<String, FunctionPresets>>>;
+
+impl ArgumentPresetManifest {
+    /// The presets of `module::function`.
+    pub fn function(&self, module: &str, function: &str) -> Option<&FunctionPresets> {
+        self.modules.get(module)?.get(function)
+    }
+
+    /// Build the manifest for the root modules of `package` out of the presets collected while
+    /// compiling it, failing on presets that do not name a parameter of their function or whose
+    /// value is not a literal.
+    pub(crate) fn for_root_package(
+        package: &MoveCompiledPackage,
+        mut presets: ModulePresets,
+    ) -> anyhow::Result<Self> {
+        let mut modules = BTreeMap::new();
+        for unit in &package.root_compiled_units {
+            let key = (unit.unit.address.into_inner(), unit.unit.name.to_string());
+            let Some(functions) = presets.remove(&key) else {
+                continue;
+            };
+            let functions = functions?;
+            if !functions.is_empty() {
+                modules.insert(key.1, functions);
+            }
+        }
+        Ok(Self { modules })
+    }
+}
+
+/// Collect the presets declared by the functions of `units`.
+pub(crate) fn module_presets(units: &[AnnotatedCompiledModule]) -> ModulePresets {
+    units
+        .iter()
+        .map(|unit| {
+            let module = unit.named_module.name.to_string();
+            let functions = unit
+                .function_infos
+                .iter()
+                .map(|(_, function, info)| {
+                    let presets = function_presets(info).with_context(|| {
+                        format!("invalid preset on function {module}::{function}")
+                    })?;
+                    Ok((function.to_string(), presets))
+                })
+                .filter(|presets| !matches!(presets, Ok((_, p)) if p.is_empty()))
+                .collect();
+            ((unit.named_module.address.into_inner(), module), functions)
+        })
+        .collect()
+}
+
+fn function_presets(info: &FunctionInfo) -> anyhow::Result<FunctionPresets> {
+    let mut presets = BTreeMap::new();
+    let Some(attribute) = info.attributes.get_(&AttributeKind_::External) else {
+        return Ok(presets);
+    };
+    let KnownAttribute::External(external) = &attribute.value else {
+        return Ok(presets);
+    };
+    let Some(entry) = external.attrs.get_(&Symbol::from(PRESET_ATTRIBUTE)) else {
+        return Ok(presets);
+    };
+    let ExternalAttributeEntry_::Parameterized(_, entries) = &entry.value else {
+        bail!("presets must be declared as #[ext({PRESET_ATTRIBUTE}(<parameter> = <value>, ...))]");
+    };
+
+    for (_, parameter, entry) in entries {
+        let ExternalAttributeEntry_::Assigned(_, value) = &entry.value else {
+            bail!("preset for '{parameter}' has no value");
+        };
+        let index = info
+            .parameters
+            .iter()
+            .find(|(var, _)| parameter_name(var.0.value.as_str()) == parameter.as_str())
+            .map(|(_, var_info)| usize::from(var_info.index))
+            .with_context(|| format!("there is no parameter named '{parameter}'"))?;
+        let value = preset_value(&value.value)
+            .with_context(|| format!("preset for '{parameter}' is invalid"))?;
+        presets.insert(parameter.to_string(), ArgumentPreset { index, value });
+    }
+    Ok(presets)
+}
+
+/// The name a parameter was declared with, without the compiler's suffix.
+fn parameter_name(var: &str) -> &str {
+    var.split(NAME_SUFFIX_DELIM).next().unwrap_or(var)
+}
+
+/// Convert a literal into the JSON format of `sui client call` arguments: integers are strings,
+/// addresses are hex strings, byte and hex strings are byte arrays, and string literals are
+/// strings.
+fn preset_value(value: &ExternalAttributeValue_) -> anyhow::Result<JsonValue> {
+    let ExternalAttributeValue_::Value(value) = value else {
+        bail!("only literal values can be presets");
+    };
+    Ok(match &value.value {
+        Value_::Address(Address::Numerical { value, .. }) => {
+            json!(
+                value
+                    .value
+                    .into_inner()
+                    .to_canonical_string(/* with_prefix */ true)
+            )
+        }
+        Value_::Address(Address::NamedUnassigned(name)) => {
+            bail!("address '{name}' is not assigned")
+        }
+        Value_::InferredNum(n) | Value_::U256(n) => json!(n.to_string()),
+        Value_::U8(n) => json!(n.to_string()),
+        Value_::U16(n) => json!(n.to_string()),
+        Value_::U32(n) => json!(n.to_string()),
+        Value_::U64(n) => json!(n.to_string()),
+        Value_::U128(n) => json!(n.to_string()),
+        Value_::Bool(b) => json!(b),
+        Value_::Bytearray(bytes) => json!(bytes),
+        Value_::InferredString(bytes) => {
+            json!(std::str::from_utf8(bytes).context("string is not valid UTF-8")?)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameter_name_strips_compiler_suffix() {
+        assert_eq!(parameter_name("amount#0#0"), "amount");
+        assert_eq!(parameter_name("amount"), "amount");
+    }
+}
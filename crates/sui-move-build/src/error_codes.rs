@@ -0,0 +1,222 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Error-code manifests: a per-module listing of the constants a package aborts with, so that a
+//! `MoveAbort(location, code)` can be mapped back to the constant's name and message.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_binary_format::{
+    CompiledModule,
+    file_format::{Constant, SignatureToken},
+};
+use move_core_types::runtime_value::MoveValue;
+use serde::{Deserialize, Serialize};
+
+use crate::CompiledPackage;
+
+/// An error constant: a constant annotated with `#[error]`, whose abort codes are clever errors,
+/// or a `u64` constant named with the `E` prefix, used directly as an abort code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorConstant {
+    pub name: String,
+    /// Index of the constant in its module's constant pool.
+    pub constant_index: u16,
+    /// Value of a `u64` constant.
+    pub code: Option<u64>,
+    /// Whether the constant is annotated with `#[error]`.
+    pub clever: bool,
+    /// Value of a clever error constant, if it is a UTF-8 `vector<u8>`.
+    pub message: Option<String>,
+}
+
+/// Error constants declared by each module of a package, keyed by module name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorCodeManifest {
+    pub modules: BTreeMap<String, Vec<ErrorConstant>>,
+}
+
+impl ErrorCodeManifest {
+    /// The error constant in `module` whose value is the plain abort code `code`. If several
+    /// constants share the value, the first one in constant pool order is returned.
+    pub fn constant_by_code(&self, module: &str, code: u64) -> Option<&ErrorConstant> {
+        self.modules
+            .get(module)?
+            .iter()
+            .find(|constant| constant.code == Some(code))
+    }
+
+    /// The error constant at `constant_index` in `module`'s constant pool.
+    pub fn constant_by_index(&self, module: &str, constant_index: u16) -> Option<&ErrorConstant> {
+        self.modules
+            .get(module)?
+            .iter()
+            .find(|constant| constant.constant_index == constant_index)
+    }
+}
+
+impl CompiledPackage {
+    /// Build the error-code manifest for the root package's modules, from their bytecode and the
+    /// constant names recorded in their source maps.
+    pub fn error_code_manifest(&self) -> ErrorCodeManifest {
+        let modules = self
+            .package
+            .root_compiled_units
+            .iter()
+            .map(|unit| {
+                let names = unit
+                    .unit
+                    .source_map
+                    .constant_map
+                    .iter()
+                    .map(|(name, index)| (name.0.as_str(), *index));
+                let constants = module_error_constants(&unit.unit.module, names);
+                (unit.unit.name.to_string(), constants)
+            })
+            .collect();
+        ErrorCodeManifest { modules }
+    }
+}
+
+/// The error constants among the constants of `module` named by `names`. The compiler adds the
+/// name of every `#[error]` constant to the constant pool as a `vector<u8>`, for clever errors
+/// to refer to, which is how those constants are told apart.
+fn module_error_constants<'a>(
+    module: &CompiledModule,
+    names: impl IntoIterator<Item = (&'a str, u16)>,
+) -> Vec<ErrorConstant> {
+    let pool_strings: BTreeSet<_> = module
+        .constant_pool()
+        .iter()
+        .filter_map(utf8_constant)
+        .collect();
+    let mut constants: Vec<_> = names
+        .into_iter()
+        .filter_map(|(name, index)| {
+            let constant = module.constant_pool().get(index as usize)?;
+            let clever = pool_strings.contains(name);
+            let code = match constant.type_ {
+                SignatureToken::U64 if is_error_name(name) => Some(u64::from_le_bytes(
+                    constant.data.as_slice().try_into().ok()?,
+                )),
+                _ => None,
+            };
+            if code.is_none() && !clever {
+                return None;
+            }
+            let message = if clever {
+                utf8_constant(constant)
+            } else {
+                None
+            };
+            Some(ErrorConstant {
+                name: name.to_string(),
+                constant_index: index,
+                code,
+                clever,
+                message,
+            })
+        })
+        .collect();
+    constants.sort_by_key(|constant| constant.constant_index);
+    constants
+}
+
+/// Whether `name` follows the convention for error constants: an `E` followed by a capitalized
+/// name, as in `ENotOwner` or `EINDEX_OUT_OF_BOUNDS`.
+fn is_error_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next() == Some('E') && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn utf8_constant(constant: &Constant) -> Option<String> {
+    let MoveValue::Vector(elements) = constant.deserialize_constant()? else {
+        return None;
+    };
+    let bytes = elements
+        .into_iter()
+        .map(|element| match element {
+            MoveValue::U8(byte) => Some(byte),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use move_binary_format::file_format::empty_module;
+    use move_core_types::runtime_value::MoveTypeLayout;
+
+    use super::*;
+
+    fn constant(layout: MoveTypeLayout, value: MoveValue) -> Constant {
+        Constant::serialize_constant(&layout, &value).unwrap()
+    }
+
+    fn bytes(value: &str) -> Constant {
+        constant(
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            MoveValue::vector_u8(value.as_bytes().to_vec()),
+        )
+    }
+
+    #[test]
+    fn module_error_constants_keeps_error_constants_only() {
+        let mut module = empty_module();
+        module.constant_pool = vec![
+            constant(MoveTypeLayout::U64, MoveValue::U64(0)),
+            constant(MoveTypeLayout::U64, MoveValue::U64(1)),
+            constant(MoveTypeLayout::U64, MoveValue::U64(100)),
+            bytes("not enough"),
+            bytes("ENotEnough"),
+            bytes("a banner"),
+            constant(MoveTypeLayout::U8, MoveValue::U8(3)),
+        ];
+        let names = [
+            ("EEmpty", 0),
+            ("ENOT_OWNER", 1),
+            ("MAX_SUPPLY", 2),
+            ("ENotEnough", 3),
+            ("BANNER", 5),
+            ("EByte", 6),
+        ];
+
+        let constants = module_error_constants(&module, names);
+        assert_eq!(
+            constants,
+            vec![
+                ErrorConstant {
+                    name: "EEmpty".to_string(),
+                    constant_index: 0,
+                    code: Some(0),
+                    clever: false,
+                    message: None,
+                },
+                ErrorConstant {
+                    name: "ENOT_OWNER".to_string(),
+                    constant_index: 1,
+                    code: Some(1),
+                    clever: false,
+                    message: None,
+                },
+                ErrorConstant {
+                    name: "ENotEnough".to_string(),
+                    constant_index: 3,
+                    code: None,
+                    clever: true,
+                    message: Some("not enough".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn error_names_need_a_capital_after_the_prefix() {
+        assert!(is_error_name("ENotOwner"));
+        assert!(is_error_name("EINDEX_OUT_OF_BOUNDS"));
+        assert!(!is_error_name("Empty"));
+        assert!(!is_error_name("E"));
+        assert!(!is_error_name("MAX_SUPPLY"));
+    }
+}
@@ -32,6 +32,7 @@ use move_package_alt_compilation::{
 };
 use move_symbol_pool::Symbol;
 
+use crate::argument_presets::{ArgumentPresetManifest, ModulePresets, module_presets};
 use sui_package_alt::{SuiFlavor, testnet_environment};
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_types::{
@@ -44,6 +45,9 @@ use sui_types::{
 };
 use sui_verifier::verifier as sui_bytecode_verifier;
 
+pub mod argument_presets;
+pub mod error_codes;
+
 #[cfg(test)]
 #[path = "unit_tests/build_tests.rs"]
 mod build_tests;
@@ -79,6 +83,8 @@ pub struct CompiledPackage {
     pub published_at: Option<ObjectID>,
     /// The dependency IDs of this package
     pub dependency_ids: PackageDependencies,
+    /// Argument presets declared by the functions of the root package.
+    pub argument_presets: ArgumentPresetManifest,
 }
 
 /// Wrapper around the core Move `BuildConfig` with some Sui-specific info
@@ -149,7 +155,7 @@ impl BuildConfig {
         &self,
         root_pkg: &RootPackage<F>,
         writer: &mut W,
-    ) -> anyhow::Result<(MoveCompiledPackage, FnInfoMap)> {
+    ) -> anyhow::Result<(MoveCompiledPackage, FnInfoMap, ModulePresets)> {
         let mut config = self.config.clone();
         // set the default flavor to Sui if not already set by the user
         if config.default_flavor.is_none() {
@@ -157,12 +163,14 @@ impl BuildConfig {
         }
         let build_plan = BuildPlan::create(root_pkg, &config)?;
         let mut fn_info = None;
+        let mut presets = None;
         let compiled_pkg = build_plan.compile_with_driver(writer, |compiler| {
             let (files, units_res) = compiler.build()?;
             match units_res {
                 Ok((units, warning_diags)) => {
                     decorate_warnings(warning_diags, Some(&files));
                     fn_info = Some(Self::fn_info(&units));
+                    presets = Some(module_presets(&units));
                     Ok((files, units))
                 }
                 Err(error_diags) => {
@@ -178,7 +186,7 @@ impl BuildConfig {
                 }
             }
         })?;
-        Ok((compiled_pkg, fn_info.unwrap()))
+        Ok((compiled_pkg, fn_info.unwrap(), presets.unwrap()))
     }
 
     pub async fn build_async(self, path: &Path) -> anyhow::Result<CompiledPackage> {
@@ -220,7 +228,7 @@ impl BuildConfig {
             self.compile_package(root_pkg, &mut std::io::sink())
         };
 
-        let (package, fn_info) = result.map_err(|error| {
+        let (package, fn_info, presets) = result.map_err(|error| {
             SuiError::from(SuiErrorKind::ModuleBuildFailure {
                 // Use [Debug] formatting to capture [anyhow] error context
                 error: format!("{:?}", error),
@@ -231,6 +239,7 @@ impl BuildConfig {
             verify_bytecode(&package, &fn_info)?;
         }
 
+        let argument_presets = ArgumentPresetManifest::for_root_package(&package, presets)?;
         let dependency_ids = PackageDependencies::new(root_pkg)?;
         let published_at = root_pkg
             .publication()
@@ -242,6 +251,7 @@ impl BuildConfig {
             package,
             dependency_ids,
             published_at,
+            argument_presets,
         })
     }
 }
@@ -436,65 +446,7 @@ impl CompiledPackage {
     /// These layout schemas can be consumed by clients (e.g., the TypeScript SDK) to enable
     /// BCS serialization/deserialization of the package's objects, tx arguments, and events.
     pub fn generate_struct_layouts(&self) -> Registry {
-        let pool = &mut normalized::RcPool::new();
-        let mut package_types = BTreeSet::new();
-        for m in self.get_modules() {
-            let normalized_m = normalized::Module::new(pool, m, /* include code */ false);
-            // 1. generate struct layouts for all declared types
-            'structs: for (name, s) in normalized_m.structs {
-                let mut dummy_type_parameters = Vec::new();
-                for t in &s.type_parameters {
-                    if t.is_phantom {
-                        // if all of t's type parameters are phantom, we can generate a type layout
-                        // we make this happen by creating a StructTag with dummy `type_params`, since the layout generator won't look at them.
-                        // we need to do this because SerdeLayoutBuilder will refuse to generate a layout for any open StructTag, but phantom types
-                        // cannot affect the layout of a struct, so we just use dummy values
-                        dummy_type_parameters.push(TypeTag::Signer)
-                    } else {
-                        // open type--do not attempt to generate a layout
-                        // TODO: handle generating layouts for open types?
-                        continue 'structs;
-                    }
-                }
-                debug_assert!(dummy_type_parameters.len() == s.type_parameters.len());
-                package_types.insert(StructTag {
-                    address: *m.address(),
-                    module: m.name().to_owned(),
-                    name: name.as_ident_str().to_owned(),
-                    type_params: dummy_type_parameters,
-                });
-            }
-            // 2. generate struct layouts for all parameters of `entry` funs
-            for (_name, f) in normalized_m.functions {
-                if f.is_entry {
-                    for t in &*f.parameters {
-                        let tag_opt = match &**t {
-                            Type::Address
-                            | Type::Bool
-                            | Type::Signer
-                            | Type::TypeParameter(_)
-                            | Type::U8
-                            | Type::U16
-                            | Type::U32
-                            | Type::U64
-                            | Type::U128
-                            | Type::U256
-                            | Type::Vector(_) => continue,
-                            Type::Reference(_, inner) => inner.to_struct_tag(pool),
-                            Type::Datatype(_) => t.to_struct_tag(pool),
-                        };
-                        if let Some(tag) = tag_opt {
-                            package_types.insert(tag);
-                        }
-                    }
-                }
-            }
-        }
-        let mut layout_builder = SerdeLayoutBuilder::new(self);
-        for typ in &package_types {
-            layout_builder.build_data_layout(typ).unwrap();
-        }
-        layout_builder.into_registry()
+        generate_struct_layouts(self.get_modules(), self).unwrap()
     }
 
     /// Checks whether this package corresponds to a built-in framework
@@ -576,6 +528,74 @@ impl CompiledPackage {
     }
 }
 
+/// Generate layout schemas for all types declared by `modules`, as well as all struct types passed
+/// into their `entry` functions, resolving the layouts of dependencies through `resolver`. This
+/// works equally on a freshly built package and on the modules of a published one.
+pub fn generate_struct_layouts<'a, R: GetModule>(
+    modules: impl IntoIterator<Item = &'a CompiledModule>,
+    resolver: &R,
+) -> anyhow::Result<Registry> {
+    let pool = &mut normalized::RcPool::new();
+    let mut package_types = BTreeSet::new();
+    for m in modules {
+        let normalized_m = normalized::Module::new(pool, m, /* include code */ false);
+        // 1. generate struct layouts for all declared types
+        'structs: for (name, s) in normalized_m.structs {
+            let mut dummy_type_parameters = Vec::new();
+            for t in &s.type_parameters {
+                if t.is_phantom {
+                    // if all of t's type parameters are phantom, we can generate a type layout
+                    // we make this happen by creating a StructTag with dummy `type_params`, since the layout generator won't look at them.
+                    // we need to do this because SerdeLayoutBuilder will refuse to generate a layout for any open StructTag, but phantom types
+                    // cannot affect the layout of a struct, so we just use dummy values
+                    dummy_type_parameters.push(TypeTag::Signer)
+                } else {
+                    // open type--do not attempt to generate a layout
+                    // TODO: handle generating layouts for open types?
+                    continue 'structs;
+                }
+            }
+            debug_assert!(dummy_type_parameters.len() == s.type_parameters.len());
+            package_types.insert(StructTag {
+                address: *m.address(),
+                module: m.name().to_owned(),
+                name: name.as_ident_str().to_owned(),
+                type_params: dummy_type_parameters,
+            });
+        }
+        // 2. generate struct layouts for all parameters of `entry` funs
+        for (_name, f) in normalized_m.functions {
+            if f.is_entry {
+                for t in &*f.parameters {
+                    let tag_opt = match &**t {
+                        Type::Address
+                        | Type::Bool
+                        | Type::Signer
+                        | Type::TypeParameter(_)
+                        | Type::U8
+                        | Type::U16
+                        | Type::U32
+                        | Type::U64
+                        | Type::U128
+                        | Type::U256
+                        | Type::Vector(_) => continue,
+                        Type::Reference(_, inner) => inner.to_struct_tag(pool),
+                        Type::Datatype(_) => t.to_struct_tag(pool),
+                    };
+                    if let Some(tag) = tag_opt {
+                        package_types.insert(tag);
+                    }
+                }
+            }
+        }
+    }
+    let mut layout_builder = SerdeLayoutBuilder::new(resolver);
+    for typ in &package_types {
+        layout_builder.build_data_layout(typ)?;
+    }
+    Ok(layout_builder.into_registry())
+}
+
 impl GetModule for CompiledPackage {
     type Error = anyhow::Error;
     // TODO: return ref here for better efficiency? Borrow checker + all_modules_map() make it hard to do this
@@ -5,6 +5,8 @@ use std::path::Path;
 
 use fs_extra::dir::CopyOptions;
 use move_compiler::editions::Edition;
+use move_core_types::account_address::AccountAddress;
+use serde_json::json;
 
 use crate::BuildConfig;
 
@@ -62,3 +64,60 @@ async fn development_mode_not_allowed() {
             .contains(&Edition::DEVELOPMENT.unknown_edition_error().to_string())
     );
 }
+
+#[tokio::test]
+async fn argument_presets() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("unit_tests")
+        .join("data")
+        .join("argument_presets");
+    let pkg = BuildConfig::new_for_testing()
+        .build_async(&path)
+        .await
+        .unwrap();
+
+    let presets = pkg.argument_presets.function("presets", "mint").unwrap();
+    let preset = |name: &str| {
+        let preset = &presets[name];
+        (preset.index, preset.value.clone())
+    };
+    assert_eq!(presets.len(), 5);
+    assert_eq!(preset("amount"), (0, json!("100")));
+    assert_eq!(
+        preset("recipient"),
+        (
+            1,
+            json!(
+                AccountAddress::from_hex_literal("0xA")
+                    .unwrap()
+                    .to_canonical_string(true)
+            )
+        )
+    );
+    assert_eq!(preset("memo"), (2, json!([b'h', b'i'])));
+    assert_eq!(preset("name"), (3, json!("sui")));
+    assert_eq!(preset("flag"), (4, json!(true)));
+    assert!(
+        pkg.argument_presets
+            .function("presets", "no_presets")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn argument_presets_must_name_a_parameter() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("unit_tests")
+        .join("data")
+        .join("invalid_argument_presets");
+    let err = BuildConfig::new_for_testing()
+        .build_async(&path)
+        .await
+        .expect_err("presets for unknown parameters should fail the build");
+    assert!(
+        format!("{err:#}").contains("there is no parameter named 'amount'"),
+        "{err:#}"
+    );
+}
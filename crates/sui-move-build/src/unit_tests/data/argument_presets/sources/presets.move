@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module argument_presets::presets;
+
+#[ext(preset(amount = 100, recipient = @0xA, memo = b"hi", name = "sui", flag = true))]
+public fun mint(
+    amount: u64,
+    recipient: address,
+    memo: vector<u8>,
+    name: vector<u8>,
+    flag: bool,
+    extra: u8,
+) {}
+
+public fun no_presets(amount: u64) {}
@@ -0,0 +1,7 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module invalid_argument_presets::presets;
+
+#[ext(preset(amount = 100))]
+public fun mint(value: u64) {}
@@ -11,6 +11,8 @@ use sui_sdk::wallet_context::WalletContext;
 
 const LAYOUTS_DIR: &str = "layouts";
 const STRUCT_LAYOUTS_FILENAME: &str = "struct_layouts.yaml";
+const ERROR_CODES_FILENAME: &str = "error_codes.json";
+const ARGUMENT_PRESETS_FILENAME: &str = "argument_presets.json";
 
 #[derive(Parser)]
 #[group(id = "sui-move-build")]
@@ -36,6 +38,16 @@ pub struct Build {
     /// and events.
     #[clap(long, global = true)]
     pub generate_struct_layouts: bool,
+    /// If true, generate a manifest of the error constants declared by modules in this package,
+    /// with their abort codes and clever error messages. The manifest can be used to map Move
+    /// aborts back to the constant that caused them.
+    #[clap(long, global = true)]
+    pub generate_error_codes: bool,
+    /// If true, generate a manifest of the argument presets declared by functions in this package
+    /// with `#[ext(preset(<parameter> = <value>, ...))]`. Pass the manifest to `sui client call
+    /// --presets` to leave those arguments out of calls.
+    #[clap(long, global = true)]
+    pub generate_argument_presets: bool,
 }
 
 impl Build {
@@ -50,6 +62,8 @@ impl Build {
             &rerooted_path,
             build_config,
             self.generate_struct_layouts,
+            self.generate_error_codes,
+            self.generate_argument_presets,
             wallet,
         )
         .await
@@ -59,6 +73,8 @@ impl Build {
         rerooted_path: &Path,
         config: MoveBuildConfig,
         generate_struct_layouts: bool,
+        generate_error_codes: bool,
+        generate_argument_presets: bool,
         wallet: &WalletContext,
     ) -> anyhow::Result<()> {
         let environment =
@@ -72,18 +88,33 @@ impl Build {
         }
         .build(rerooted_path)?;
 
+        let package_build_dir = rerooted_path
+            .join("build")
+            .join(pkg.package.compiled_package_info.package_name.as_str());
+
         if generate_struct_layouts {
             let layout_str = serde_yaml::to_string(&pkg.generate_struct_layouts()).unwrap();
             // store under <package_path>/build/<package_name>/layouts/struct_layouts.yaml
-            let dir_name = rerooted_path
-                .join("build")
-                .join(pkg.package.compiled_package_info.package_name.as_str())
-                .join(LAYOUTS_DIR);
+            let dir_name = package_build_dir.join(LAYOUTS_DIR);
             let layout_filename = dir_name.join(STRUCT_LAYOUTS_FILENAME);
             fs::create_dir_all(dir_name)?;
             fs::write(layout_filename, layout_str)?
         }
 
+        if generate_error_codes {
+            // store under <package_path>/build/<package_name>/error_codes.json
+            let error_codes = serde_json::to_string_pretty(&pkg.error_code_manifest())?;
+            fs::create_dir_all(&package_build_dir)?;
+            fs::write(package_build_dir.join(ERROR_CODES_FILENAME), error_codes)?
+        }
+
+        if generate_argument_presets {
+            // store under <package_path>/build/<package_name>/argument_presets.json
+            let presets = serde_json::to_string_pretty(&pkg.argument_presets)?;
+            fs::create_dir_all(&package_build_dir)?;
+            fs::write(package_build_dir.join(ARGUMENT_PRESETS_FILENAME), presets)?
+        }
+
         Ok(())
     }
 }
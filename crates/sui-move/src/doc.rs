@@ -0,0 +1,449 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use move_binary_format::file_format::{AbilitySet, Visibility};
+use move_cli::base::reroot_path;
+use move_compiler::parser::ast::TargetKind;
+use move_core_types::{account_address::AccountAddress, identifier::IdentStr};
+use move_docgen::{DocgenFlags, DocgenOptions};
+use move_model_2::{model::Datatype, normalized, source_model};
+use move_package_alt_compilation::{build_config::BuildConfig, find_env};
+use move_symbol_pool::Symbol;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+use sui_package_alt::SuiFlavor;
+use sui_types::{
+    SUI_FRAMEWORK_ADDRESS,
+    base_types::{
+        RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR, TX_CONTEXT_MODULE_NAME,
+        TX_CONTEXT_STRUCT_NAME,
+    },
+    id::RESOLVED_SUI_ID,
+    transfer::RESOLVED_RECEIVING_STRUCT,
+};
+
+const DEFAULT_OUTPUT_DIRECTORY: &str = "build/docs";
+
+/// Generate cross-linked Markdown documentation for a package and its dependencies
+#[derive(Parser)]
+#[group(id = "sui-move-doc")]
+pub struct Doc {
+    #[clap(flatten)]
+    pub flags: DocgenFlags,
+    /// Directory to write the documentation to, relative to the package root
+    #[clap(long = "output-directory", value_name = "PATH", default_value = DEFAULT_OUTPUT_DIRECTORY)]
+    pub output_directory: String,
+    /// Do not append the table of entry and public functions to each module of the root package
+    #[clap(long = "no-entry-functions")]
+    pub no_entry_functions: bool,
+}
+
+impl Doc {
+    pub async fn execute(
+        self,
+        path: Option<&Path>,
+        build_config: BuildConfig,
+        flavor: SuiFlavor,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let env = find_env::<SuiFlavor>(&rerooted_path, &build_config, &flavor)?;
+        let model = build_config
+            .move_model_from_path::<SuiFlavor, _>(
+                &rerooted_path,
+                env,
+                flavor,
+                &mut std::io::stdout(),
+            )
+            .await?;
+
+        let options = DocgenOptions {
+            flags: self.flags,
+            output_directory: self.output_directory,
+            ..DocgenOptions::default()
+        };
+
+        // Docgen writes root package modules to `<output>/<package>/<module file>.md`; key the
+        // entry function tables by that path so they can be appended to the generated pages.
+        let mut entry_tables = BTreeMap::new();
+        if !self.no_entry_functions {
+            for module in model.modules() {
+                if !matches!(
+                    module.info().target_kind,
+                    TargetKind::Source {
+                        is_root_package: true
+                    }
+                ) {
+                    continue;
+                }
+                let functions = entry_functions(module);
+                if functions.is_empty() {
+                    continue;
+                }
+                entry_tables.insert(
+                    module_output_file(&options.output_directory, module),
+                    render_entry_functions(&functions),
+                );
+            }
+        }
+
+        let docgen = move_docgen::Docgen::new(&model, &options);
+        for (file, mut content) in docgen.generate(&model)? {
+            if let Some(table) = entry_tables.get(&file) {
+                content.push('\n');
+                content.push_str(table);
+            }
+            let path = PathBuf::from(&file);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path.as_path(), content)?;
+            println!("Generated {:?}", path);
+        }
+
+        println!("\nDocumentation generation successful!");
+        Ok(())
+    }
+}
+
+/// A function of the root package that can be called from a programmable transaction.
+struct EntryFunction {
+    name: Symbol,
+    /// Anchor of the function's section in the generated module page.
+    label: String,
+    is_entry: bool,
+    type_parameters: usize,
+    object_arguments: Vec<Argument>,
+    pure_arguments: Vec<Argument>,
+    other_arguments: Vec<Argument>,
+}
+
+struct Argument {
+    name: Option<Symbol>,
+    type_: String,
+}
+
+/// How a transaction supplies a function argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArgumentKind {
+    /// An object input: a type with `key` (or a vector of them), a `Receiving<T>`, or a type
+    /// parameter constrained by `key`.
+    Object,
+    /// A pure input: primitives, `ID`, strings, and vectors and options of pure types.
+    Pure,
+    /// Anything else, which can only be the result of an earlier command (or, for an
+    /// unconstrained type parameter, depends on the type argument).
+    Other,
+}
+
+/// Collects the `entry` and `public` functions of `module`, splitting their parameters into
+/// object, pure and other arguments (see [`ArgumentKind`]). The `TxContext` parameter is supplied
+/// by the runtime and is left out.
+fn entry_functions(module: source_model::Module<'_>) -> Vec<EntryFunction> {
+    let module_label = format!("{}", module.ident()).replace("::", "_");
+    let mut functions = vec![];
+    for function in module.functions() {
+        // Macros have no compiled form and cannot be called from a transaction.
+        let Some(compiled) = function.maybe_compiled() else {
+            continue;
+        };
+        if !compiled.is_entry && compiled.visibility != Visibility::Public {
+            continue;
+        }
+
+        let names = &function.summary().parameters;
+        let has_key = |dt: &normalized::Datatype| has_key(module.model(), dt);
+        let mut object_arguments = vec![];
+        let mut pure_arguments = vec![];
+        let mut other_arguments = vec![];
+        for (idx, type_) in compiled.parameters.iter().enumerate() {
+            if is_tx_context(type_) {
+                continue;
+            }
+            let argument = Argument {
+                name: names.get(idx).and_then(|p| p.name),
+                type_: type_.to_string(),
+            };
+            match classify(type_, &compiled.type_parameters, &has_key) {
+                ArgumentKind::Object => object_arguments.push(argument),
+                ArgumentKind::Pure => pure_arguments.push(argument),
+                ArgumentKind::Other => other_arguments.push(argument),
+            }
+        }
+
+        functions.push(EntryFunction {
+            name: compiled.name,
+            label: format!("{module_label}_{}", compiled.name),
+            is_entry: compiled.is_entry,
+            type_parameters: compiled.type_parameters.len(),
+            object_arguments,
+            pure_arguments,
+            other_arguments,
+        });
+    }
+    functions
+}
+
+fn render_entry_functions(functions: &[EntryFunction]) -> String {
+    let mut out = String::new();
+    writeln!(out, "## Entry Functions\n").unwrap();
+    writeln!(
+        out,
+        "| Function | Kind | Type parameters | Object arguments | Pure arguments | Other arguments |"
+    )
+    .unwrap();
+    writeln!(out, "|---|---|---|---|---|---|").unwrap();
+    for function in functions {
+        writeln!(
+            out,
+            "| [`{}`](#{}) | {} | {} | {} | {} | {} |",
+            function.name,
+            function.label,
+            if function.is_entry { "entry" } else { "public" },
+            function.type_parameters,
+            render_arguments(&function.object_arguments),
+            render_arguments(&function.pure_arguments),
+            render_arguments(&function.other_arguments),
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn render_arguments(arguments: &[Argument]) -> String {
+    if arguments.is_empty() {
+        return "-".to_string();
+    }
+    arguments
+        .iter()
+        .map(|Argument { name, type_ }| match name {
+            Some(name) => format!("<code>{name}: {}</code>", escape(type_)),
+            None => format!("<code>{}</code>", escape(type_)),
+        })
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+/// Escapes a type for use inside an HTML element of a Markdown table cell.
+fn escape(type_: &str) -> String {
+    type_
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "&#124;")
+}
+
+fn is_tx_context(type_: &normalized::Type) -> bool {
+    let normalized::Type::Reference(_, inner) = type_ else {
+        return false;
+    };
+    let normalized::Type::Datatype(dt) = &**inner else {
+        return false;
+    };
+    dt.module.address == SUI_FRAMEWORK_ADDRESS
+        && dt.module.name.as_str() == TX_CONTEXT_MODULE_NAME.as_str()
+        && dt.name.as_str() == TX_CONTEXT_STRUCT_NAME.as_str()
+}
+
+/// Classifies a parameter of type `type_`, in a function with `type_parameters`. `has_key`
+/// tells whether a datatype has the `key` ability.
+fn classify(
+    type_: &normalized::Type,
+    type_parameters: &[AbilitySet],
+    has_key: &dyn Fn(&normalized::Datatype) -> bool,
+) -> ArgumentKind {
+    use normalized::Type as T;
+    match type_ {
+        T::Bool | T::U8 | T::U16 | T::U32 | T::U64 | T::U128 | T::U256 | T::Address => {
+            ArgumentKind::Pure
+        }
+        T::Signer => ArgumentKind::Other,
+        // Object and pure inputs can be borrowed as well as passed by value.
+        T::Reference(_, inner) => classify(inner, type_parameters, has_key),
+        T::Vector(inner) => classify(inner, type_parameters, has_key),
+        T::TypeParameter(idx) => match type_parameters.get(*idx as usize) {
+            Some(abilities) if abilities.has_key() => ArgumentKind::Object,
+            _ => ArgumentKind::Other,
+        },
+        T::Datatype(dt) => {
+            if is_datatype(dt, RESOLVED_SUI_ID)
+                || is_datatype(dt, RESOLVED_UTF8_STR)
+                || is_datatype(dt, RESOLVED_ASCII_STR)
+            {
+                return ArgumentKind::Pure;
+            }
+            if is_datatype(dt, RESOLVED_STD_OPTION) {
+                return match dt.type_arguments.as_slice() {
+                    [inner] if classify(inner, type_parameters, has_key) == ArgumentKind::Pure => {
+                        ArgumentKind::Pure
+                    }
+                    _ => ArgumentKind::Other,
+                };
+            }
+            if is_datatype(dt, RESOLVED_RECEIVING_STRUCT) || has_key(dt) {
+                return ArgumentKind::Object;
+            }
+            ArgumentKind::Other
+        }
+    }
+}
+
+fn is_datatype(
+    dt: &normalized::Datatype,
+    (address, module, name): (&AccountAddress, &IdentStr, &IdentStr),
+) -> bool {
+    dt.module.address == *address
+        && dt.module.name.as_str() == module.as_str()
+        && dt.name.as_str() == name.as_str()
+}
+
+fn has_key(model: &source_model::Model, dt: &normalized::Datatype) -> bool {
+    let Some(module) = model.maybe_module((dt.module.address, dt.module.name)) else {
+        return false;
+    };
+    match module.maybe_datatype(dt.name) {
+        Some(Datatype::Struct(s)) => s.compiled().abilities.has_key(),
+        Some(Datatype::Enum(e)) => e.compiled().abilities.has_key(),
+        None => false,
+    }
+}
+
+/// The file docgen generates for a root package module, mirroring its naming scheme.
+fn module_output_file(output_directory: &str, module: source_model::Module<'_>) -> String {
+    let package_name = match module.package().name() {
+        Some(name) => name.to_string(),
+        None => module.id().address.to_string(),
+    };
+    let file_name = PathBuf::from(module.source_path().as_str())
+        .with_extension("md")
+        .file_name()
+        .expect("file name")
+        .to_os_string();
+    let mut path = PathBuf::from(output_directory);
+    path.push(format!("{}/{}", package_name, file_name.to_string_lossy()));
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::Ability;
+    use sui_types::MOVE_STDLIB_ADDRESS;
+
+    fn datatype(
+        address: AccountAddress,
+        module: &str,
+        name: &str,
+        type_arguments: Vec<normalized::Type>,
+    ) -> normalized::Type {
+        normalized::Type::Datatype(Box::new(normalized::Datatype {
+            module: normalized::ModuleId {
+                address,
+                name: Symbol::from(module),
+            },
+            name: Symbol::from(name),
+            type_arguments,
+        }))
+    }
+
+    /// Classifies `type_` in a function whose only type parameter has `abilities`, treating
+    /// `0x2::coin::Coin` as the only type with `key`.
+    fn kind(type_: normalized::Type, abilities: AbilitySet) -> ArgumentKind {
+        let has_key = |dt: &normalized::Datatype| {
+            dt.module.address == SUI_FRAMEWORK_ADDRESS && dt.name.as_str() == "Coin"
+        };
+        classify(&type_, &[abilities], &has_key)
+    }
+
+    #[test]
+    fn test_classify_arguments() {
+        use normalized::Type as T;
+        let reference = |type_| T::Reference(false, Box::new(type_));
+        let vector = |type_| T::Vector(Box::new(type_));
+        let coin = || {
+            datatype(
+                SUI_FRAMEWORK_ADDRESS,
+                "coin",
+                "Coin",
+                vec![T::TypeParameter(0)],
+            )
+        };
+        let balance = || datatype(SUI_FRAMEWORK_ADDRESS, "balance", "Balance", vec![]);
+        let option = |type_| datatype(MOVE_STDLIB_ADDRESS, "option", "Option", vec![type_]);
+        let string = || datatype(MOVE_STDLIB_ADDRESS, "string", "String", vec![]);
+        let id = || datatype(SUI_FRAMEWORK_ADDRESS, "object", "ID", vec![]);
+        let receiving = datatype(SUI_FRAMEWORK_ADDRESS, "transfer", "Receiving", vec![coin()]);
+        let empty = AbilitySet::EMPTY;
+
+        for pure in [
+            T::U64,
+            T::Address,
+            vector(T::U8),
+            reference(vector(T::U8)),
+            string(),
+            id(),
+            option(id()),
+            vector(option(string())),
+        ] {
+            assert_eq!(kind(pure.clone(), empty), ArgumentKind::Pure, "{pure}");
+        }
+
+        for object in [
+            coin(),
+            T::Reference(true, Box::new(coin())),
+            vector(coin()),
+            receiving,
+        ] {
+            assert_eq!(
+                kind(object.clone(), empty),
+                ArgumentKind::Object,
+                "{object}"
+            );
+        }
+        assert_eq!(
+            kind(T::TypeParameter(0), AbilitySet::EMPTY | Ability::Key),
+            ArgumentKind::Object
+        );
+
+        // Values that are neither pure nor objects come from earlier commands.
+        for other in [
+            balance(),
+            reference(balance()),
+            option(coin()),
+            vector(balance()),
+            T::Signer,
+        ] {
+            assert_eq!(kind(other.clone(), empty), ArgumentKind::Other, "{other}");
+        }
+        assert_eq!(
+            kind(T::TypeParameter(0), AbilitySet::PRIMITIVES),
+            ArgumentKind::Other
+        );
+    }
+
+    #[test]
+    fn test_render_entry_functions() {
+        let argument = |name: Option<&str>, type_: &str| Argument {
+            name: name.map(Symbol::from),
+            type_: type_.to_string(),
+        };
+        let table = render_entry_functions(&[EntryFunction {
+            name: Symbol::from("split"),
+            label: "0x2_coin_split".to_string(),
+            is_entry: false,
+            type_parameters: 1,
+            object_arguments: vec![argument(Some("coin"), "&mut 0x2::coin::Coin<T0>")],
+            pure_arguments: vec![argument(None, "u64")],
+            other_arguments: vec![],
+        }]);
+
+        assert_eq!(
+            table.lines().last().unwrap(),
+            "| [`split`](#0x2_coin_split) | public | 1 \
+             | <code>coin: &amp;mut 0x2::coin::Coin&lt;T0&gt;</code> | <code>u64</code> | - |"
+        );
+        assert_eq!(escape("a|b"), "a&#124;b");
+    }
+}
@@ -12,8 +12,10 @@ pub mod build;
 pub mod cache_package;
 pub mod coverage;
 pub mod disassemble;
+pub mod doc;
 pub mod format;
 pub mod lint;
+pub mod manage_package;
 pub mod migrate;
 pub mod new;
 pub mod summary;
@@ -27,8 +29,10 @@ pub enum Command {
     #[command(hide = true)]
     CachePackage(cache_package::CachePackage),
     Disassemble(disassemble::Disassemble),
+    Doc(doc::Doc),
     Format(format::Format),
     Lint(lint::Lint),
+    ManagePackage(manage_package::ManagePackage),
     Migrate(migrate::Migrate),
     New(new::New),
     Test(unit_test::Test),
@@ -55,8 +59,10 @@ pub async fn execute_move_command(
         Command::CachePackage(c) => c.execute(flavor).await,
         Command::Coverage(c) => c.execute(package_path, build_config, flavor).await,
         Command::Disassemble(c) => c.execute(package_path, build_config, flavor).await,
+        Command::Doc(c) => c.execute(package_path, build_config, flavor).await,
         Command::Format(c) => c.execute().await,
         Command::Lint(c) => c.execute(package_path, build_config, flavor).await,
+        Command::ManagePackage(c) => c.execute(package_path, wallet).await,
         Command::Migrate(c) => c.execute(package_path, build_config, flavor).await,
         Command::New(c) => c.execute(package_path),
         Command::Summary(s) => {
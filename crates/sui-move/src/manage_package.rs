@@ -0,0 +1,276 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, bail};
+use clap::{Parser, Subcommand};
+use move_cli::base::reroot_path;
+use move_package_alt::schema::{
+    EnvironmentName, ParsedPublishedFile, Publication, PublishedID, RenderToml,
+};
+use std::{fs, path::Path};
+use sui_package_alt::SuiFlavor;
+use sui_sdk::{digests::chain_ids_match, sui_client_config::SuiEnv, wallet_context::WalletContext};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+
+const PUBLISHED_FILE_NAME: &str = "Published.toml";
+
+/// Manage the publication records of a package
+#[derive(Parser)]
+#[group(id = "sui-move-manage-package")]
+pub struct ManagePackage {
+    #[clap(subcommand)]
+    pub cmd: ManagePackageCommand,
+}
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum ManagePackageCommand {
+    /// Reconcile the recorded publications of the package with the chain, for every environment
+    /// configured in the CLI. Upgrades performed from another machine are picked up by moving
+    /// `published-at` and `version` to the latest version of the package on chain.
+    Sync {
+        /// Report the changes without writing them
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+/// What happened to the publication of one environment during a sync.
+#[derive(Debug)]
+enum SyncOutcome {
+    Updated {
+        from: (ObjectID, u64),
+        to: (ObjectID, u64),
+    },
+    Unchanged,
+    /// The record is ahead of the chain, e.g. because the network was wiped.
+    AheadOfChain {
+        latest: (ObjectID, u64),
+    },
+    NotFoundOnChain,
+    NoConfiguredEnv,
+    Failed(anyhow::Error),
+}
+
+impl ManagePackage {
+    pub async fn execute(self, path: Option<&Path>, wallet: &WalletContext) -> anyhow::Result<()> {
+        let ManagePackageCommand::Sync { dry_run } = self.cmd;
+        let path = reroot_path(path)?;
+        let pubfile_path = path.join(PUBLISHED_FILE_NAME);
+        if !pubfile_path.exists() {
+            // Publications recorded in a legacy `Move.lock` are migrated to `Published.toml` the
+            // next time the package is built.
+            bail!(
+                "No {PUBLISHED_FILE_NAME} found in {}; if the package's publications are recorded \
+                 in its Move.lock, run `sui move build` first to migrate them",
+                path.display()
+            );
+        }
+        let contents = fs::read_to_string(&pubfile_path)
+            .with_context(|| format!("Failed to read {}", pubfile_path.display()))?;
+        let mut pubfile: ParsedPublishedFile<SuiFlavor> = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", pubfile_path.display()))?;
+
+        let envs = configured_chain_ids(wallet).await;
+        let mut changed = false;
+        for (env_name, publication) in pubfile.published.iter_mut() {
+            let outcome = match envs
+                .iter()
+                .find(|(_, chain_id)| chain_ids_match(chain_id, &publication.chain_id))
+            {
+                None => SyncOutcome::NoConfiguredEnv,
+                Some((env, _)) => sync_publication(env, publication)
+                    .await
+                    .unwrap_or_else(SyncOutcome::Failed),
+            };
+            changed |= matches!(outcome, SyncOutcome::Updated { .. });
+            report(env_name, &outcome);
+        }
+
+        if !changed {
+            println!("{PUBLISHED_FILE_NAME} is up to date");
+        } else if dry_run {
+            println!("Dry run: {PUBLISHED_FILE_NAME} was not modified");
+        } else {
+            fs::write(&pubfile_path, pubfile.render_as_toml())
+                .with_context(|| format!("Failed to write {}", pubfile_path.display()))?;
+            println!("Updated {}", pubfile_path.display());
+        }
+        Ok(())
+    }
+}
+
+/// The chain IDs of the CLI's environments, using the cached ID where there is one. Environments
+/// whose chain cannot be reached are left out.
+async fn configured_chain_ids(wallet: &WalletContext) -> Vec<(SuiEnv, String)> {
+    let mut envs = vec![];
+    for env in &wallet.config.envs {
+        let chain_id = match &env.chain_id {
+            Some(chain_id) => chain_id.clone(),
+            None => {
+                let Ok(client) = env.create_grpc_client() else {
+                    continue;
+                };
+                let Ok(chain_id) = client.get_chain_identifier().await else {
+                    continue;
+                };
+                chain_id.to_string()
+            }
+        };
+        envs.push((env.clone(), chain_id));
+    }
+    envs
+}
+
+async fn sync_publication(
+    env: &SuiEnv,
+    publication: &mut Publication<SuiFlavor>,
+) -> anyhow::Result<SyncOutcome> {
+    let original_id = ObjectID::from(publication.addresses.original_id.0);
+    let versions = env
+        .create_grpc_client()?
+        .list_package_versions(original_id)
+        .await
+        .with_context(|| format!("Failed to list the versions of package {original_id}"))?;
+    Ok(reconcile(publication, &versions))
+}
+
+/// Move `publication` to the latest of the package's on-chain `versions` (in ascending order), if
+/// it is behind.
+fn reconcile(
+    publication: &mut Publication<SuiFlavor>,
+    versions: &[(ObjectID, SequenceNumber)],
+) -> SyncOutcome {
+    let Some((latest_id, latest_version)) = versions.last() else {
+        return SyncOutcome::NotFoundOnChain;
+    };
+    let latest = (*latest_id, latest_version.value());
+    let recorded = (
+        ObjectID::from(publication.addresses.published_at.0),
+        publication.version,
+    );
+
+    if latest == recorded {
+        SyncOutcome::Unchanged
+    } else if latest.1 < recorded.1 {
+        SyncOutcome::AheadOfChain { latest }
+    } else {
+        publication.addresses.published_at = PublishedID(latest.0.into());
+        publication.version = latest.1;
+        SyncOutcome::Updated {
+            from: recorded,
+            to: latest,
+        }
+    }
+}
+
+fn report(env_name: &EnvironmentName, outcome: &SyncOutcome) {
+    match outcome {
+        SyncOutcome::Updated { from, to } => println!(
+            "{env_name}: updated published-at {} (version {}) -> {} (version {})",
+            from.0, from.1, to.0, to.1
+        ),
+        SyncOutcome::Unchanged => println!("{env_name}: up to date"),
+        SyncOutcome::AheadOfChain { latest } => println!(
+            "{env_name}: the latest version on chain is {} (version {}), older than the \
+             recorded one; left unchanged",
+            latest.0, latest.1
+        ),
+        SyncOutcome::NotFoundOnChain => {
+            println!("{env_name}: package not found on chain; left unchanged")
+        }
+        SyncOutcome::NoConfiguredEnv => {
+            println!("{env_name}: no CLI environment has this chain ID; skipped")
+        }
+        SyncOutcome::Failed(e) => println!("{env_name}: {e:#}; left unchanged"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL_ID: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+    const UPGRADED_ID: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+
+    fn published_file() -> ParsedPublishedFile<SuiFlavor> {
+        toml::from_str(&format!(
+            r#"
+[published.testnet]
+chain-id = "4c78adac"
+published-at = "{ORIGINAL_ID}"
+original-id = "{ORIGINAL_ID}"
+version = 1
+toolchain-version = "1.0.0"
+build-config = {{ flavor = "sui", edition = "2024" }}
+upgrade-capability = "{ORIGINAL_ID}"
+"#
+        ))
+        .unwrap()
+    }
+
+    fn id(id: &str) -> ObjectID {
+        id.parse().unwrap()
+    }
+
+    fn version(package: &str, version: u64) -> (ObjectID, SequenceNumber) {
+        (id(package), SequenceNumber::from_u64(version))
+    }
+
+    #[test]
+    fn test_reconcile_moves_publication_to_latest_version() {
+        let mut pubfile = published_file();
+        let publication = pubfile.published.values_mut().next().unwrap();
+
+        let outcome = reconcile(
+            publication,
+            &[version(ORIGINAL_ID, 1), version(UPGRADED_ID, 2)],
+        );
+        let SyncOutcome::Updated { from, to } = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(from, (id(ORIGINAL_ID), 1));
+        assert_eq!(to, (id(UPGRADED_ID), 2));
+
+        // The original ID is kept, and the update survives rendering the file.
+        let rendered: ParsedPublishedFile<SuiFlavor> =
+            toml::from_str(&pubfile.render_as_toml()).unwrap();
+        let publication = rendered.published.values().next().unwrap();
+        assert_eq!(
+            ObjectID::from(publication.addresses.published_at.0),
+            id(UPGRADED_ID)
+        );
+        assert_eq!(
+            ObjectID::from(publication.addresses.original_id.0),
+            id(ORIGINAL_ID)
+        );
+        assert_eq!(publication.version, 2);
+    }
+
+    #[test]
+    fn test_reconcile_leaves_other_publications_unchanged() {
+        let mut pubfile = published_file();
+        let publication = pubfile.published.values_mut().next().unwrap();
+
+        assert!(matches!(
+            reconcile(publication, &[version(ORIGINAL_ID, 1)]),
+            SyncOutcome::Unchanged
+        ));
+        assert!(matches!(
+            reconcile(publication, &[]),
+            SyncOutcome::NotFoundOnChain
+        ));
+
+        // A record ahead of the chain (e.g. after a network wipe) is left alone.
+        publication.version = 3;
+        assert!(matches!(
+            reconcile(publication, &[version(UPGRADED_ID, 2)]),
+            SyncOutcome::AheadOfChain { latest: (_, 2) }
+        ));
+        assert_eq!(publication.version, 3);
+        assert_eq!(
+            ObjectID::from(publication.addresses.published_at.0),
+            id(ORIGINAL_ID)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::Context;
 use clap::Parser;
 use move_cli::base::{
     self,
@@ -14,26 +15,32 @@ use std::{
     cell::RefCell,
     collections::BTreeMap,
     ops::{Deref, DerefMut},
-    path::Path,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::{Arc, LazyLock},
 };
 use sui_adapter::gas_meter::SuiGasMeter;
+use sui_fork::{DataStore, GraphQLClient, Node};
 use sui_move_build::decorate_warnings;
 use sui_move_natives::{
-    NativesCostTable, object_runtime::ObjectRuntime, scratch::ScratchRuntime,
-    test_scenario::InMemoryTestStore, transaction_context::TransactionContext,
+    NativesCostTable,
+    object_runtime::ObjectRuntime,
+    scratch::ScratchRuntime,
+    test_scenario::{ForkedObjectSource, InMemoryTestStore},
+    transaction_context::TransactionContext,
 };
 use sui_package_alt::{SuiFlavor, find_environment};
 use sui_protocol_config::ProtocolConfig;
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::{
-    base_types::{SuiAddress, TxContext},
+    base_types::{ObjectID, SuiAddress, TxContext},
     digests::TransactionDigest,
+    error::{SuiErrorKind, SuiResult},
     gas::{SuiGasStatus, SuiGasStatusAPI},
     gas_model::{tables::GasStatus, units_types::Gas},
     in_memory_storage::InMemoryStorage,
     metrics::ExecutionMetrics,
+    object::Object,
 };
 
 // Move unit tests will halt after executing this many steps. This is a protection to avoid divergence
@@ -48,6 +55,56 @@ const TEST_GAS_PRICE: u64 = 500;
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+
+    #[clap(flatten)]
+    pub fork: ForkArgs,
+}
+
+/// Options for running tests against the state of a live network.
+#[derive(Parser)]
+pub struct ForkArgs {
+    /// Fork the state of a live network (`mainnet`, `testnet`, `devnet`, or a GraphQL URL) for
+    /// the tests. Objects that tests take by ID through `test_scenario` (and their dynamic
+    /// fields) are fetched from the network on first use and cached locally. Package code is
+    /// not fetched: the dependencies of the package under test are compiled from source.
+    #[clap(long = "fork", value_name = "NETWORK")]
+    pub fork: Option<Node>,
+
+    /// The checkpoint to fork at. Defaults to the latest checkpoint of the network; pin it to
+    /// keep test runs reproducible and to reuse the local cache between runs.
+    #[clap(long = "fork-checkpoint", value_name = "CHECKPOINT", requires = "fork")]
+    pub fork_checkpoint: Option<u64>,
+
+    /// Directory for the local cache of forked objects. Defaults to the `sui-fork` data
+    /// directory, which can also be set with `SUI_FORK_DATA`.
+    #[clap(long = "fork-data-dir", value_name = "DIR", requires = "fork")]
+    pub fork_data_dir: Option<PathBuf>,
+}
+
+impl ForkArgs {
+    /// Connect to the forked network, if any, resolving the checkpoint to fork at.
+    pub async fn data_store(self) -> anyhow::Result<Option<DataStore>> {
+        let Some(node) = self.fork else {
+            return Ok(None);
+        };
+        let version = env!("CARGO_PKG_VERSION");
+        let checkpoint = match self.fork_checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => GraphQLClient::new(node.clone(), version)?
+                .get_latest_checkpoint_sequence_number()
+                .await?
+                .with_context(|| {
+                    format!(
+                        "Failed to get the latest checkpoint for {}",
+                        node.network_name()
+                    )
+                })?,
+        };
+        eprintln!("Forking {} at checkpoint {checkpoint}", node.network_name());
+        Ok(Some(
+            DataStore::new(node, checkpoint, version, self.fork_data_dir).await?,
+        ))
+    }
 }
 
 impl Test {
@@ -89,6 +146,8 @@ impl Test {
             find_environment(&rerooted_path, build_config.environment, wallet, false).await?;
         build_config.environment = Some(environment.name);
 
+        let fork = self.fork.data_store().await?;
+
         run_move_unit_tests(
             &rerooted_path,
             build_config,
@@ -96,6 +155,7 @@ impl Test {
             compute_coverage,
             save_disassembly,
             flavor,
+            fork,
         )
         .await
     }
@@ -103,6 +163,7 @@ impl Test {
 
 /// This function returns a result of UnitTestResult. The outer result indicates whether it
 /// successfully started running the test, and the inner result indicatests whether all tests pass.
+/// If `fork` is provided, tests read objects missing from their own storage from it.
 pub async fn run_move_unit_tests(
     path: &Path,
     build_config: BuildConfig,
@@ -110,6 +171,7 @@ pub async fn run_move_unit_tests(
     compute_coverage: bool,
     save_disassembly: bool,
     flavor: SuiFlavor,
+    fork: Option<DataStore>,
 ) -> anyhow::Result<UnitTestResult> {
     let config = config.unwrap_or_else(|| {
         UnitTestingConfig::default_with_bound(Some(*MAX_UNIT_TEST_INSTRUCTIONS))
@@ -123,7 +185,7 @@ pub async fn run_move_unit_tests(
             ..config
         },
         flavor,
-        SuiVMTestSetup::new(),
+        SuiVMTestSetup::new().with_fork(fork),
         compute_coverage,
         save_disassembly,
         &mut std::io::stdout(),
@@ -145,6 +207,7 @@ pub struct SuiVMTestSetup {
     reference_gas_price: u64,
     protocol_config: ProtocolConfig,
     native_function_table: move_vm_runtime::natives::functions::NativeFunctionTable,
+    fork: Option<DataStore>,
 }
 
 impl Default for SuiVMTestSetup {
@@ -163,9 +226,16 @@ impl SuiVMTestSetup {
             reference_gas_price: TEST_GAS_PRICE,
             protocol_config,
             native_function_table,
+            fork: None,
         }
     }
 
+    /// Back the storage of every test with `fork`.
+    pub fn with_fork(mut self, fork: Option<DataStore>) -> Self {
+        self.fork = fork;
+        self
+    }
+
     pub fn max_gas_budget(&self) -> u64 {
         self.protocol_config.max_tx_gas()
     }
@@ -212,7 +282,13 @@ impl VMTestSetup for SuiVMTestSetup {
 
     fn new_extensions_builder(&self) -> SuiExtensionsBuilder<'_> {
         SuiExtensionsBuilder {
-            store: InMemoryTestStore(RefCell::new(InMemoryStorage::default())),
+            store: match &self.fork {
+                Some(fork) => InMemoryTestStore::new_forked(
+                    InMemoryStorage::default(),
+                    Box::new(ForkedNetwork(fork.clone())),
+                ),
+                None => InMemoryTestStore::new(InMemoryStorage::default()),
+            },
             protocol_config: &self.protocol_config,
         }
     }
@@ -256,6 +332,22 @@ impl VMTestSetup for SuiVMTestSetup {
     }
 }
 
+/// Serves objects from a network forked at a checkpoint. The data store caches every object it
+/// fetches on disk, so later test runs at the same checkpoint do not hit the network.
+///
+/// Failing to reach the network is an error rather than a missing object, so that a test does
+/// not silently run as if a mainnet object had never existed.
+struct ForkedNetwork(DataStore);
+
+impl ForkedObjectSource for ForkedNetwork {
+    fn get_object(&self, id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.0.get_object(id).map_err(|e| {
+            SuiErrorKind::Storage(format!("Failed to fetch object {id} from the fork: {e:#}"))
+                .into()
+        })
+    }
+}
+
 // Massaging to get traits to line up.
 pub struct SuiGasStatusTestWrapper(SuiGasStatus);
 
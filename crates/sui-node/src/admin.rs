@@ -10,19 +10,24 @@ use axum::{
     routing::{delete, get, post},
 };
 use base64::Engine;
+use consensus_core::storage::rocksdb_store::RocksDBStore;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::traits::ToFromBytes;
 use humantime::parse_duration;
 use mysten_network::Multiaddr;
 use serde::Deserialize;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     str::FromStr,
 };
+use sui_core::authority::execution_time_observation_export;
+use sui_core::consensus_shadow_replay::ConsensusShadowReplay;
+use sui_core::package_gas_tracker::PackageGasSortKey;
 use sui_network::endpoint_manager::{AddressSource, EndpointId};
 use sui_types::{
-    base_types::{AuthorityName, ConciseableName},
+    base_types::{AuthorityName, ConciseableName, ObjectID},
     crypto::{NetworkPublicKey, RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
     digests::TransactionDigest,
     error::SuiErrorKind,
@@ -91,6 +96,58 @@ use tracing::info;
 // Dump the address prober's latest results (full addresses + per-address outcomes) as JSON.
 //
 //  $ curl 'http://127.0.0.1:1337/address-prober-report'
+//
+// Report the 20 packages with the highest computation cost over the package gas accounting window
+// (requires `package-gas-accounting` in the node config). `sort_by` accepts gas_used (default),
+// computation_cost, storage_cost and transaction_count.
+//
+//  $ curl 'http://127.0.0.1:1337/package-gas-top-k?k=20&sort_by=computation_cost'
+//
+// View the active transaction policy, or reload it from the file configured in
+// `transaction-deny-config.transaction-policy.path` (the same as sending SIGHUP to the node process).
+//
+//  $ curl 'http://127.0.0.1:1337/transaction-policy'
+//  $ curl -X POST 'http://127.0.0.1:1337/reload-transaction-policy'
+//
+// List the consensus rejection policies and the packages blocked in consensus, or block and
+// unblock calls to a package. While a package is blocked, this validator votes to reject every
+// transaction that calls one of its functions.
+//
+//  $ curl 'http://127.0.0.1:1337/consensus-rejection-policies'
+//  $ curl -X POST 'http://127.0.0.1:1337/block-package?package=0x...'
+//  $ curl -X POST 'http://127.0.0.1:1337/unblock-package?package=0x...'
+//
+// Export this validator's execution time observations to a file signed with its protocol key.
+// Other operators can import the file via `execution-time-observer-config.imported-observations`.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/export-execution-time-observations?path=/tmp/observations.bcs'
+//
+// Estimate how much space would be freed by retaining events for 30 epochs and transactions and
+// effects for 10 epochs, leaving all other pruning settings as configured. Nothing is pruned.
+//
+//  $ curl 'http://127.0.0.1:1337/estimate-reclaim?events=30&transactions=10&effects=10'
+//
+// Recompute the running root state hash of checkpoints 1000 to 2000 of the current epoch from the
+// per-checkpoint state hashes, and report the first checkpoint whose stored running root differs.
+//
+//  $ curl 'http://127.0.0.1:1337/audit-running-root?start=1000&end=2000'
+//
+// Replay the current epoch's consensus commits up to commit 5000 into a fresh shadow epoch store,
+// and report where the consensus output it produces differs from the live store's. The epoch's
+// consensus database is opened read-only, as a RocksDB secondary, so it can be the live one.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/replay-consensus-shadow?consensus_db=/opt/sui/db/consensus_db/42&end_commit=5000'
+//
+// Write a safe mode diagnostics bundle for the advance-epoch transaction that started the current
+// epoch, with the latest 100 consensus commits. The bundle is written to `path`, or to the
+// directory configured in `safe-mode-diagnostics.output-path` if omitted.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/safe-mode-diagnostics?path=/tmp/diagnostics&commits=100'
+//
+// List the accumulator settlements scheduled in the current epoch that have not executed yet,
+// oldest first, with the checkpoint they settle and how long they have been pending.
+//
+//  $ curl 'http://127.0.0.1:1337/pending-settlements'
 
 const NO_TRACING_HANDLE: &str = "tracing handle not available";
 const LOGGING_ROUTE: &str = "/logging";
@@ -109,12 +166,24 @@ const DUMP_CONSENSUS_TX_COST_ESTIMATES_ROUTE: &str = "/dump-consensus-tx-cost-es
 const TRAFFIC_CONTROL: &str = "/traffic-control";
 const UPDATE_ENDPOINT: &str = "/update-endpoint";
 const ADDRESS_PROBER_REPORT: &str = "/address-prober-report";
+const PACKAGE_GAS_TOP_K: &str = "/package-gas-top-k";
 const DB_SHELL_LS: &str = "/db-shell/ls";
 const DB_SHELL_READ: &str = "/db-shell/read";
 const DB_SHELL_DELETE: &str = "/db-shell/delete";
 const BROADCAST_TX_DENY_CONFIG: &str = "/broadcast-transaction-deny-config";
 const WITHDRAW_TX_DENY_CONFIG: &str = "/withdraw-transaction-deny-config";
 const TX_DENY_CONFIG: &str = "/transaction-deny-config";
+const TX_POLICY: &str = "/transaction-policy";
+const RELOAD_TX_POLICY: &str = "/reload-transaction-policy";
+const CONSENSUS_REJECTION_POLICIES: &str = "/consensus-rejection-policies";
+const BLOCK_PACKAGE: &str = "/block-package";
+const UNBLOCK_PACKAGE: &str = "/unblock-package";
+const EXPORT_EXECUTION_TIME_OBSERVATIONS: &str = "/export-execution-time-observations";
+const ESTIMATE_RECLAIM: &str = "/estimate-reclaim";
+const AUDIT_RUNNING_ROOT: &str = "/audit-running-root";
+const REPLAY_CONSENSUS_SHADOW: &str = "/replay-consensus-shadow";
+const SAFE_MODE_DIAGNOSTICS: &str = "/safe-mode-diagnostics";
+const PENDING_SETTLEMENTS: &str = "/pending-settlements";
 
 pub(crate) struct AppState {
     pub(crate) node: Arc<SuiNode>,
@@ -169,6 +238,7 @@ pub async fn run_admin_server(
         .route(TRAFFIC_CONTROL, post(traffic_control))
         .route(UPDATE_ENDPOINT, post(update_endpoint))
         .route(ADDRESS_PROBER_REPORT, get(address_prober_report))
+        .route(PACKAGE_GAS_TOP_K, get(package_gas_top_k))
         .route(DB_SHELL_LS, get(handle_ls))
         .route(DB_SHELL_READ, get(handle_read))
         .route(DB_SHELL_DELETE, delete(handle_delete))
@@ -181,6 +251,23 @@ pub async fn run_admin_server(
             post(withdraw_transaction_deny_config),
         )
         .route(TX_DENY_CONFIG, get(transaction_deny_config_dump))
+        .route(TX_POLICY, get(transaction_policy_dump))
+        .route(RELOAD_TX_POLICY, post(reload_transaction_policy))
+        .route(
+            CONSENSUS_REJECTION_POLICIES,
+            get(consensus_rejection_policies),
+        )
+        .route(BLOCK_PACKAGE, post(block_package))
+        .route(UNBLOCK_PACKAGE, post(unblock_package))
+        .route(
+            EXPORT_EXECUTION_TIME_OBSERVATIONS,
+            post(export_execution_time_observations),
+        )
+        .route(ESTIMATE_RECLAIM, get(estimate_reclaim))
+        .route(AUDIT_RUNNING_ROOT, get(audit_running_root))
+        .route(REPLAY_CONSENSUS_SHADOW, post(replay_consensus_shadow))
+        .route(SAFE_MODE_DIAGNOSTICS, post(safe_mode_diagnostics))
+        .route(PENDING_SETTLEMENTS, get(pending_settlements))
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -814,6 +901,109 @@ async fn transaction_deny_config_dump(State(state): State<Arc<AppState>>) -> (St
     }
 }
 
+async fn transaction_policy_dump(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let policy = state
+        .node
+        .state()
+        .transaction_deny_config_manager()
+        .transaction_policy();
+    match serde_json::to_string_pretty(policy.as_ref()) {
+        Ok(json) => (StatusCode::OK, format!("{json}\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn reload_transaction_policy(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let authority_state = state.node.state();
+    let manager = authority_state.transaction_deny_config_manager();
+    if !manager.is_transaction_policy_reloadable() {
+        return (
+            StatusCode::PRECONDITION_FAILED,
+            "transaction policy is not loaded from a file\n".to_string(),
+        );
+    }
+    match manager.reload_transaction_policy() {
+        Ok(rules) => (
+            StatusCode::OK,
+            format!("transaction policy reloaded with {rules} rule(s)\n"),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    }
+}
+
+async fn consensus_rejection_policies(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let policies = state.node.state().consensus_rejection_policies();
+    let body = serde_json::json!({
+        "policies": policies.names(),
+        "blocked_packages": state.node.blocked_packages().packages(),
+    });
+
+    match serde_json::to_string_pretty(&body) {
+        Ok(s) => (StatusCode::OK, format!("{s}\n")),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}\n")),
+    }
+}
+
+#[derive(Deserialize)]
+struct Package {
+    package: ObjectID,
+}
+
+async fn block_package(
+    State(state): State<Arc<AppState>>,
+    args: Query<Package>,
+) -> (StatusCode, String) {
+    let Query(Package { package }) = args;
+    match state.node.blocked_packages().block(package) {
+        Ok(true) => (StatusCode::OK, format!("blocked package {package}\n")),
+        Ok(false) => (
+            StatusCode::OK,
+            format!("package {package} is already blocked\n"),
+        ),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}\n")),
+    }
+}
+
+async fn unblock_package(
+    State(state): State<Arc<AppState>>,
+    args: Query<Package>,
+) -> (StatusCode, String) {
+    let Query(Package { package }) = args;
+    match state.node.blocked_packages().unblock(package) {
+        Ok(true) => (StatusCode::OK, format!("unblocked package {package}\n")),
+        Ok(false) => (
+            StatusCode::BAD_REQUEST,
+            format!("package {package} is not blocked\n"),
+        ),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}\n")),
+    }
+}
+
+#[derive(Deserialize)]
+struct ExportExecutionTimeObservations {
+    path: PathBuf,
+}
+
+async fn export_execution_time_observations(
+    State(state): State<Arc<AppState>>,
+    args: Query<ExportExecutionTimeObservations>,
+) -> (StatusCode, String) {
+    let Query(ExportExecutionTimeObservations { path }) = args;
+    let authority_state = state.node.state();
+    match execution_time_observation_export::export_execution_time_observations(
+        &authority_state,
+        &path,
+    )
+    .await
+    {
+        Ok(count) => (
+            StatusCode::OK,
+            format!("exported {count} execution time observations to {path:?}\n"),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    }
+}
+
 async fn address_prober_report(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
     let Some(report) = state.node.address_prober_report().await else {
         return (
@@ -827,3 +1017,224 @@ async fn address_prober_report(State(state): State<Arc<AppState>>) -> (StatusCod
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+#[derive(Deserialize)]
+struct EstimateReclaim {
+    transactions: Option<u64>,
+    effects: Option<u64>,
+    events: Option<u64>,
+    objects_history: Option<u64>,
+    checkpoint_contents: Option<u64>,
+}
+
+async fn estimate_reclaim(
+    State(state): State<Arc<AppState>>,
+    args: Query<EstimateReclaim>,
+) -> (StatusCode, String) {
+    let Query(EstimateReclaim {
+        transactions,
+        effects,
+        events,
+        objects_history,
+        checkpoint_contents,
+    }) = args;
+
+    let current = state.node.config.authority_store_pruning_config.clone();
+    let mut proposed = current.clone();
+    let retention = proposed.component_retention.get_or_insert_default();
+    retention.transactions = transactions.or(retention.transactions);
+    retention.effects = effects.or(retention.effects);
+    retention.events = events.or(retention.events);
+    retention.objects_history = objects_history.or(retention.objects_history);
+    retention.checkpoint_contents = checkpoint_contents.or(retention.checkpoint_contents);
+    if let Err(err) = proposed.validate() {
+        return (StatusCode::BAD_REQUEST, format!("{err}\n"));
+    }
+
+    let authority_state = state.node.state();
+    let estimate = tokio::task::spawn_blocking(move || {
+        authority_state.estimate_pruning_reclaim(&current, &proposed)
+    })
+    .await;
+    match estimate {
+        Ok(Ok(estimate)) => match serde_json::to_string_pretty(&estimate) {
+            Ok(json) => (StatusCode::OK, format!("{json}\n")),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        },
+        Ok(Err(err)) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err}\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct AuditRunningRoot {
+    start: u64,
+    end: u64,
+}
+
+async fn audit_running_root(
+    State(state): State<Arc<AppState>>,
+    args: Query<AuditRunningRoot>,
+) -> (StatusCode, String) {
+    let Query(AuditRunningRoot { start, end }) = args;
+    if start > end {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("start {start} is after end {end}\n"),
+        );
+    }
+
+    let authority_state = state.node.state();
+    let audit =
+        tokio::task::spawn_blocking(move || authority_state.audit_running_root(start, end)).await;
+    match audit {
+        Ok(Ok(audit)) => {
+            let checked = match audit.last_checked {
+                Some(last) => format!("Checked checkpoints {start} to {last}"),
+                None => format!("Checkpoint {start} has not been accumulated yet"),
+            };
+            match audit.divergence {
+                Some(d) => (
+                    StatusCode::OK,
+                    format!(
+                        "{checked}\nRunning root diverges at checkpoint {}: recomputed {}, stored {}\n",
+                        d.checkpoint, d.recomputed.digest, d.stored.digest,
+                    ),
+                ),
+                None => (StatusCode::OK, format!("{checked}\nNo divergence\n")),
+            }
+        }
+        Ok(Err(err)) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err}\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct PackageGasTopK {
+    k: Option<usize>,
+    sort_by: Option<String>,
+}
+
+async fn package_gas_top_k(
+    State(state): State<Arc<AppState>>,
+    args: Query<PackageGasTopK>,
+) -> (StatusCode, String) {
+    let Query(PackageGasTopK { k, sort_by }) = args;
+
+    let authority_state = state.node.state();
+    let Some(tracker) = authority_state.package_gas_tracker() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "package gas accounting is not enabled in the node config\n".to_string(),
+        );
+    };
+
+    let sort_key = match sort_by.as_deref().map(PackageGasSortKey::from_str) {
+        None => PackageGasSortKey::default(),
+        Some(Ok(sort_key)) => sort_key,
+        Some(Err(err)) => return (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    };
+
+    let report = tracker.top_k(k.unwrap_or(10), sort_key);
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => (StatusCode::OK, format!("{json}\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct ReplayConsensusShadow {
+    consensus_db: PathBuf,
+    end_commit: u32,
+}
+
+async fn replay_consensus_shadow(
+    State(state): State<Arc<AppState>>,
+    args: Query<ReplayConsensusShadow>,
+) -> (StatusCode, String) {
+    let Query(ReplayConsensusShadow {
+        consensus_db,
+        end_commit,
+    }) = args;
+
+    if !consensus_db.is_dir() {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("{} is not a directory\n", consensus_db.display()),
+        );
+    }
+
+    // Reading the consensus database and replaying its commits blocks on disk I/O throughout, so
+    // the replay runs on a blocking thread.
+    let authority_state = state.node.state();
+    let report = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+        let secondary_dir = tempfile::tempdir()?;
+        let store = RocksDBStore::open_secondary(&consensus_db, secondary_dir.path())?;
+
+        let shadow_dir = tempfile::tempdir()?;
+        let mut replay = ConsensusShadowReplay::open(&authority_state, shadow_dir.path())?;
+        tokio::runtime::Handle::current().block_on(replay.replay_from_store(&store, end_commit))?;
+        Ok(replay.compare()?)
+    })
+    .await;
+
+    match report {
+        Ok(Ok(report)) => (StatusCode::OK, report.to_string()),
+        Ok(Err(err)) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err}\n")),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct SafeModeDiagnosticsArgs {
+    path: Option<PathBuf>,
+    commits: Option<u32>,
+}
+
+async fn safe_mode_diagnostics(
+    State(state): State<Arc<AppState>>,
+    args: Query<SafeModeDiagnosticsArgs>,
+) -> (StatusCode, String) {
+    let Query(SafeModeDiagnosticsArgs { path, commits }) = args;
+
+    let authority_state = state.node.state();
+    let config = authority_state.config.safe_mode_diagnostics.as_ref();
+    let Some(dir) = path.or_else(|| config.map(|c| c.output_path.clone())) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "no path given, and safe mode diagnostics are not configured\n".to_string(),
+        );
+    };
+    let num_commits = commits
+        .or_else(|| config.map(|c| c.num_commits))
+        .unwrap_or_else(sui_config::node::default_safe_mode_diagnostics_num_commits);
+
+    match authority_state.write_safe_mode_diagnostics(&dir, num_commits) {
+        Ok(path) => (StatusCode::OK, format!("{}\n", path.display())),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}\n")),
+    }
+}
+
+async fn pending_settlements(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let epoch_store = state.node.state().load_epoch_store_one_call_per_task();
+    let tracker = &epoch_store.settlement_tracker;
+    let pending = tracker.pending();
+
+    let mut output = format!(
+        "{} pending settlements in epoch {}, lagging {} checkpoints\n",
+        pending.len(),
+        epoch_store.epoch(),
+        tracker.lag_checkpoints(),
+    );
+    for settlement in pending {
+        output.push_str(&format!(
+            "{:?}: checkpoint {} height {}, {} transactions, pending for {:?}\n",
+            settlement.settlement_key,
+            settlement.checkpoint_seq,
+            settlement.checkpoint_height,
+            settlement.num_transactions,
+            settlement.age,
+        ));
+    }
+    (StatusCode::OK, output)
+}
@@ -0,0 +1,310 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detailed node health endpoint.
+//!
+//! `/health/detailed` reports checkpoint execution lag, consensus participation (validators
+//! only), RocksDB write stalls and the state of the current epoch store, and returns
+//! `503 Service Unavailable` when any of them crosses the thresholds in the node's
+//! `health-check` config, so that load balancers can use it as a readiness check.
+
+use crate::SuiNode;
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
+use prometheus::IntGaugeVec;
+use prometheus::core::Collector;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_config::node::HealthCheckConfig;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+use tracing::info;
+use typed_store::DBMetrics;
+
+const DETAILED_HEALTH_ROUTE: &str = "/health/detailed";
+
+#[derive(Debug, Serialize)]
+pub struct DetailedHealth {
+    /// Whether the node passes all configured thresholds.
+    pub ready: bool,
+    /// Thresholds the node currently fails.
+    pub failures: Vec<String>,
+    pub checkpoints: CheckpointHealth,
+    /// Only reported by validators.
+    pub consensus: Option<ConsensusHealth>,
+    pub db: DbHealth,
+    pub epoch: EpochHealth,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CheckpointHealth {
+    pub highest_executed: Option<CheckpointSequenceNumber>,
+    /// Highest checkpoint certified by the committee that this node has verified.
+    pub highest_verified: Option<CheckpointSequenceNumber>,
+    pub lag: u64,
+    /// Milliseconds between now and the timestamp of the highest executed checkpoint.
+    pub highest_executed_age_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConsensusHealth {
+    /// Share of recent commits including a block proposed by this validator, `None` until the
+    /// first commit of the epoch.
+    pub participation_rate: Option<f64>,
+    pub commits_observed: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DbHealth {
+    /// Column families on which RocksDB has stopped writes.
+    pub write_stopped: Vec<String>,
+    /// Column families on which RocksDB is currently delaying writes.
+    pub write_delayed: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EpochHealth {
+    pub epoch: u64,
+    pub protocol_version: u64,
+    pub epoch_start_timestamp_ms: u64,
+    pub reconfig_status: &'static str,
+}
+
+impl DetailedHealth {
+    /// Evaluate the collected indicators against `config`, filling in `ready` and `failures`.
+    fn evaluate(mut self, config: &HealthCheckConfig) -> Self {
+        let mut failures = vec![];
+
+        if self.checkpoints.highest_executed.is_none() {
+            failures.push("no checkpoint has been executed".to_string());
+        }
+        if self.checkpoints.lag > config.max_checkpoint_lag() {
+            failures.push(format!(
+                "executed checkpoints trail verified checkpoints by {} (max {})",
+                self.checkpoints.lag,
+                config.max_checkpoint_lag()
+            ));
+        }
+        if let Some(age_ms) = self.checkpoints.highest_executed_age_ms
+            && age_ms > config.max_checkpoint_age().as_millis() as u64
+        {
+            failures.push(format!(
+                "highest executed checkpoint is {age_ms}ms old (max {:?})",
+                config.max_checkpoint_age()
+            ));
+        }
+        if let Some(ConsensusHealth {
+            participation_rate: Some(rate),
+            ..
+        }) = &self.consensus
+            && *rate < config.min_consensus_participation()
+        {
+            failures.push(format!(
+                "consensus participation {rate:.2} is below {:.2}",
+                config.min_consensus_participation()
+            ));
+        }
+        if config.unready_on_write_stall() && !self.db.write_stopped.is_empty() {
+            failures.push(format!(
+                "writes are stopped on {}",
+                self.db.write_stopped.join(", ")
+            ));
+        }
+
+        self.ready = failures.is_empty();
+        self.failures = failures;
+        self
+    }
+}
+
+fn collect(node: &SuiNode, config: &HealthCheckConfig) -> anyhow::Result<DetailedHealth> {
+    let state = node.state();
+    let checkpoint_store = state.get_checkpoint_store();
+    let highest_executed = checkpoint_store.get_highest_executed_checkpoint()?;
+    let highest_verified = checkpoint_store
+        .get_highest_verified_checkpoint()?
+        .map(|checkpoint| *checkpoint.sequence_number());
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let checkpoints = CheckpointHealth {
+        highest_executed: highest_executed
+            .as_ref()
+            .map(|checkpoint| *checkpoint.sequence_number()),
+        highest_verified,
+        lag: highest_verified.unwrap_or_default().saturating_sub(
+            highest_executed
+                .as_ref()
+                .map_or(0, |c| *c.sequence_number()),
+        ),
+        highest_executed_age_ms: highest_executed
+            .as_ref()
+            .map(|checkpoint| now_ms.saturating_sub(checkpoint.timestamp_ms)),
+    };
+
+    let epoch_store = state.load_epoch_store_one_call_per_task();
+    let consensus = epoch_store.is_validator().then(|| ConsensusHealth {
+        participation_rate: epoch_store.consensus_participation.rate(),
+        commits_observed: epoch_store.consensus_participation.commits(),
+    });
+    let reconfig_status = {
+        let reconfig_state = epoch_store.get_reconfig_state_read_lock_guard();
+        if reconfig_state.is_reject_all_certs() {
+            "rejecting_all_certs"
+        } else if reconfig_state.is_reject_user_certs() {
+            "rejecting_user_certs"
+        } else {
+            "accepting_all_certs"
+        }
+    };
+    let epoch = EpochHealth {
+        epoch: epoch_store.epoch(),
+        protocol_version: epoch_store.protocol_version().as_u64(),
+        epoch_start_timestamp_ms: epoch_store.epoch_start_state().epoch_start_timestamp_ms(),
+        reconfig_status,
+    };
+
+    let cf_metrics = &DBMetrics::get().cf_metrics;
+    let db = DbHealth {
+        write_stopped: active_column_families(&cf_metrics.rocksdb_is_write_stopped),
+        write_delayed: active_column_families(&cf_metrics.rocksdb_actual_delayed_write_rate),
+    };
+
+    Ok(DetailedHealth {
+        ready: false,
+        failures: vec![],
+        checkpoints,
+        consensus,
+        db,
+        epoch,
+    }
+    .evaluate(config))
+}
+
+/// Names of the column families for which `gauge` is non-zero.
+fn active_column_families(gauge: &IntGaugeVec) -> Vec<String> {
+    let mut cfs: Vec<String> = gauge
+        .collect()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .filter(|metric| metric.get_gauge().value() > 0.0)
+        .filter_map(|metric| {
+            metric
+                .get_label()
+                .iter()
+                .find(|label| label.name() == "cf_name")
+                .map(|label| label.value().to_string())
+        })
+        .collect();
+    cfs.sort();
+    cfs.dedup();
+    cfs
+}
+
+struct HealthState {
+    node: Arc<SuiNode>,
+    config: HealthCheckConfig,
+}
+
+async fn detailed_health(
+    State(state): State<Arc<HealthState>>,
+) -> Result<(StatusCode, Json<DetailedHealth>), (StatusCode, String)> {
+    let health = collect(&state.node, &state.config)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let status = if health.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((status, Json(health)))
+}
+
+/// Serve the detailed health endpoint if `health-check` is set in the node config.
+pub async fn run_health_server(node: Arc<SuiNode>) {
+    let Some(config) = node.get_config().health_check.clone() else {
+        return;
+    };
+    let listen_address = config.listen_address;
+
+    let app = Router::new()
+        .route(DETAILED_HEALTH_ROUTE, get(detailed_health))
+        .with_state(Arc::new(HealthState { node, config }));
+
+    info!(address =% listen_address, "starting detailed health server");
+    let listener = tokio::net::TcpListener::bind(&listen_address)
+        .await
+        .unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HealthCheckConfig {
+        HealthCheckConfig {
+            listen_address: "127.0.0.1:0".parse().unwrap(),
+            max_checkpoint_lag: Some(10),
+            max_checkpoint_age_secs: Some(30),
+            min_consensus_participation: Some(0.8),
+            unready_on_write_stall: None,
+        }
+    }
+
+    fn healthy() -> DetailedHealth {
+        DetailedHealth {
+            ready: false,
+            failures: vec![],
+            checkpoints: CheckpointHealth {
+                highest_executed: Some(100),
+                highest_verified: Some(105),
+                lag: 5,
+                highest_executed_age_ms: Some(1_000),
+            },
+            consensus: Some(ConsensusHealth {
+                participation_rate: Some(0.95),
+                commits_observed: 1_000,
+            }),
+            db: DbHealth::default(),
+            epoch: EpochHealth {
+                epoch: 1,
+                protocol_version: 1,
+                epoch_start_timestamp_ms: 0,
+                reconfig_status: "accepting_all_certs",
+            },
+        }
+    }
+
+    #[test]
+    fn test_thresholds_flip_readiness() {
+        let health = healthy().evaluate(&config());
+        assert!(health.ready, "{:?}", health.failures);
+
+        let mut lagging = healthy();
+        lagging.checkpoints.lag = 11;
+        lagging.checkpoints.highest_executed_age_ms = Some(31_000);
+        let health = lagging.evaluate(&config());
+        assert!(!health.ready);
+        assert_eq!(health.failures.len(), 2);
+
+        let mut low_participation = healthy();
+        low_participation.consensus = Some(ConsensusHealth {
+            participation_rate: Some(0.5),
+            commits_observed: 1_000,
+        });
+        assert!(!low_participation.evaluate(&config()).ready);
+
+        let mut stalled = healthy();
+        stalled.db.write_stopped = vec!["objects".to_string()];
+        assert!(!stalled.evaluate(&config()).ready);
+
+        let mut stalled = healthy();
+        stalled.db.write_stopped = vec!["objects".to_string()];
+        let config = HealthCheckConfig {
+            unready_on_write_stall: Some(false),
+            ..config()
+        };
+        assert!(stalled.evaluate(&config).ready);
+    }
+}
@@ -36,6 +36,7 @@ use sui_core::authority::epoch_start_configuration::EpochFlag;
 use sui_core::authority::execution_time_estimator::ExecutionTimeObserver;
 use sui_core::consensus_adapter::ConsensusClient;
 use sui_core::consensus_manager::UpdatableConsensusClient;
+use sui_core::consensus_rejection_policy::BlockedPackagesPolicy;
 use sui_core::epoch::randomness::RandomnessManager;
 use sui_core::execution_cache::build_execution_cache;
 use sui_core::randomness_round_receiver::{RandomnessRoundReceiver, RandomnessRoundReceiverHandle};
@@ -47,6 +48,8 @@ use sui_types::node_role::NodeRole;
 use sui_core::global_state_hasher::GlobalStateHashMetrics;
 use sui_core::storage::RestReadStore;
 use sui_json_rpc::bridge_api::BridgeReadApi;
+use sui_json_rpc::deepbook_api::DeepBookApi;
+use sui_json_rpc::extensions::{RpcExtensionContext, registered_rpc_extensions, rpc_extension};
 use sui_json_rpc_api::JsonRpcMetrics;
 use sui_network::randomness;
 use sui_rpc_api::ServerVersion;
@@ -127,7 +130,9 @@ use sui_json_rpc::coin_api::CoinReadApi;
 use sui_json_rpc::governance_api::GovernanceReadApi;
 use sui_json_rpc::indexer_api::IndexerApi;
 use sui_json_rpc::move_utils::MoveUtils;
+use sui_json_rpc::proof_api::ProofApi;
 use sui_json_rpc::read_api::ReadApi;
+use sui_json_rpc::source_verification_api::SourceVerificationApi;
 use sui_json_rpc::transaction_builder_api::TransactionBuilderApi;
 use sui_json_rpc::transaction_execution_api::TransactionExecutionApi;
 use sui_macros::fail_point;
@@ -139,6 +144,7 @@ use sui_network::state_sync;
 use sui_network::validator::server::ServerBuilder;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_snapshot::uploader::StateSnapshotUploader;
+use sui_source_validation::service::SourceVerificationService;
 use sui_storage::{
     http_key_value_store::HttpKVStore,
     key_value_store::{FallbackTransactionKVStore, TransactionKeyValueStore},
@@ -163,6 +169,7 @@ pub mod address_prober;
 pub mod admin;
 pub mod db_shell;
 mod handle;
+pub mod health;
 pub mod metrics;
 
 pub struct ValidatorComponents {
@@ -259,6 +266,9 @@ pub struct SuiNode {
     http_servers: HttpServers,
 
     state: Arc<AuthorityState>,
+    /// The built-in consensus rejection policy, installed on `state` at start-up and managed
+    /// through the admin interface.
+    blocked_packages: Arc<BlockedPackagesPolicy>,
     transaction_orchestrator: Option<Arc<TransactionOrchestrator<NetworkAuthorityClient>>>,
     registry_service: RegistryService,
     metrics: Arc<SuiNodeMetrics>,
@@ -476,6 +486,7 @@ impl SuiNode {
         if let Some(prober_config) = &config.address_prober {
             prober_config.validate()?;
         }
+        config.authority_store_pruning_config.validate()?;
 
         let mut config = config.clone();
         if config.supported_protocol_versions.is_none() {
@@ -558,7 +569,10 @@ impl SuiNode {
         // setting is what the compactor replaces. The pruner is force-disabled
         // in `AuthorityStorePruner::new` whenever this is true.
         let enable_objects_compactor = node_role.is_validator()
-            || config.authority_store_pruning_config.num_epochs_to_retain == 0;
+            || config
+                .authority_store_pruning_config
+                .num_epochs_to_retain_for_objects()
+                == 0;
         let perpetual_tables_options = AuthorityPerpetualTablesOptions {
             enable_write_stall,
             enable_objects_compactor,
@@ -575,8 +589,13 @@ impl SuiNode {
         let backpressure_manager =
             BackpressureManager::new_from_checkpoint_store(&checkpoint_store);
 
-        let store =
-            AuthorityStore::open(perpetual_tables, &genesis, &config, &prometheus_registry).await?;
+        let store = AuthorityStore::open(
+            perpetual_tables.clone(),
+            &genesis,
+            &config,
+            &prometheus_registry,
+        )
+        .await?;
 
         let cur_epoch = store.get_recovery_epoch_at_restart()?;
         let committee = committee_store
@@ -646,6 +665,7 @@ impl SuiNode {
                 &registry_service.default_registry(),
             )),
             config.fullnode_sync_mode,
+            config.mutex_table_config.clone().unwrap_or_default(),
         )?;
 
         info!("created epoch store");
@@ -838,6 +858,15 @@ impl SuiNode {
             pruner_watermarks,
         )
         .await;
+
+        let blocked_packages = Arc::new(BlockedPackagesPolicy::load(
+            &config.consensus_blocked_packages,
+            perpetual_tables,
+        )?);
+        state
+            .consensus_rejection_policies()
+            .install(blocked_packages.clone());
+
         // ensure genesis txn was executed
         if epoch_store.epoch() == 0 {
             let txn = &genesis.transaction();
@@ -876,6 +905,25 @@ impl SuiNode {
             None
         };
 
+        if node_role.is_fullnode() && !config.webhooks.is_empty() {
+            if config.enable_index_processing {
+                sui_core::webhooks::spawn_webhooks(
+                    &state.subscription_handler,
+                    &config.webhooks,
+                    &prometheus_registry,
+                )?;
+            } else {
+                warn!("Webhooks require index processing, which is disabled; not starting them");
+            }
+        }
+
+        if let Some(feed) = &config.denylist_feed {
+            sui_core::denylist_feed::spawn_denylist_feed_sync(
+                feed,
+                state.transaction_deny_config_manager().clone(),
+            )?;
+        }
+
         let (http_servers, subscription_service_checkpoint_sender) = build_http_servers(
             state.clone(),
             state_sync_store,
@@ -1007,6 +1055,7 @@ impl SuiNode {
             validator_components: Mutex::new(validator_components),
             http_servers,
             state,
+            blocked_packages,
             transaction_orchestrator,
             registry_service,
             metrics: sui_node_metrics,
@@ -1771,6 +1820,10 @@ impl SuiNode {
         self.state.clone()
     }
 
+    pub fn blocked_packages(&self) -> &Arc<BlockedPackagesPolicy> {
+        &self.blocked_packages
+    }
+
     /// The embedded `sui-rpc-store` index backend, when the node is a
     /// fullnode with indexing enabled. Exposes the startup bootstrap
     /// decision and per-cohort watermarks for introspection (used by
@@ -2834,6 +2887,7 @@ async fn build_json_rpc_router(
     transaction_orchestrator: &Option<Arc<TransactionOrchestrator<NetworkAuthorityClient>>>,
     config: &NodeConfig,
     prometheus_registry: &Registry,
+    checkpoints: tokio::sync::broadcast::Sender<Arc<Checkpoint>>,
 ) -> Result<axum::Router> {
     let traffic_controller = state.traffic_controller.clone();
     let mut server = JsonRpcServerBuilder::new(
@@ -2842,6 +2896,15 @@ async fn build_json_rpc_router(
         traffic_controller,
         config.policy_config.clone(),
     );
+    if let Some(address_config) = &config.jsonrpc_address_config {
+        server.set_address_config(address_config.clone());
+    }
+    if let Some(blob_config) = &config.jsonrpc_blob_config {
+        server.set_blob_config(blob_config.clone());
+    }
+    if let Some(rate_limit_config) = &config.jsonrpc_rate_limit_config {
+        server.set_rate_limit_config(rate_limit_config.clone());
+    }
 
     let kv_store = build_kv_store(state, config, prometheus_registry)?;
 
@@ -2864,6 +2927,7 @@ async fn build_json_rpc_router(
     }
     server.register_module(GovernanceReadApi::new(state.clone(), metrics.clone()))?;
     server.register_module(BridgeReadApi::new(state.clone(), metrics.clone()))?;
+    server.register_module(DeepBookApi::new(state.clone()))?;
 
     if let Some(transaction_orchestrator) = transaction_orchestrator {
         server.register_module(TransactionExecutionApi::new(
@@ -2891,6 +2955,7 @@ async fn build_json_rpc_router(
         }
     };
 
+    server.register_module(ProofApi::new(kv_store.clone()))?;
     server.register_module(IndexerApi::new(
         state.clone(),
         ReadApi::new(state.clone(), kv_store.clone(), metrics.clone()),
@@ -2898,9 +2963,34 @@ async fn build_json_rpc_router(
         name_service_config,
         metrics,
         config.indexer_max_subscriptions,
+        Some(checkpoints),
     ))?;
     server.register_module(MoveUtils::new(state.clone()))?;
 
+    if let Some(source_verification_config) = &config.source_verification_config {
+        let service = SourceVerificationService::new(
+            source_verification_config.clone(),
+            state.get_chain_identifier(),
+            state.get_object_store().clone(),
+        )?;
+        server.register_module(SourceVerificationApi::new(Arc::new(service)))?;
+    }
+
+    for namespace in &config.jsonrpc_extensions {
+        let extension = rpc_extension(namespace).ok_or_else(|| {
+            anyhow!(
+                "JSON-RPC extension '{namespace}' is not registered in this binary, available \
+                 extensions: {:?}",
+                registered_rpc_extensions()
+            )
+        })?;
+        server.register_extension(
+            extension.as_ref(),
+            RpcExtensionContext::new(state.get_object_cache_reader().clone()),
+        )?;
+        info!(namespace, "serving JSON-RPC extension");
+    }
+
     let server_type = config.jsonrpc_server_type();
 
     Ok(server.to_router(server_type).await?)
@@ -2951,25 +3041,6 @@ async fn build_http_servers(
 
     info!("starting rpc service with config: {:?}", config.rpc);
 
-    let mut router = axum::Router::new();
-
-    // The JSON-RPC service can be disabled independently of the gRPC/REST
-    // service and of JSON-RPC indexing, so that a node can keep indexing
-    // without exposing the JSON-RPC endpoints.
-    if config.json_rpc_enabled() {
-        router = router.merge(
-            build_json_rpc_router(
-                &state,
-                transaction_orchestrator,
-                config,
-                prometheus_registry,
-            )
-            .await?,
-        );
-    } else {
-        info!("json-rpc service is disabled");
-    }
-
     // When the embedded rpc-store is active, gate checkpoint delivery on the
     // index so a client that waits for a checkpoint can immediately read its
     // indexed state (matching the legacy synchronously-committed index).
@@ -2991,6 +3062,27 @@ async fn build_http_servers(
             subscription_max_subscribers,
             subscription_shards,
         );
+
+    let mut router = axum::Router::new();
+
+    // The JSON-RPC service can be disabled independently of the gRPC/REST
+    // service and of JSON-RPC indexing, so that a node can keep indexing
+    // without exposing the JSON-RPC endpoints.
+    if config.json_rpc_enabled() {
+        router = router.merge(
+            build_json_rpc_router(
+                &state,
+                transaction_orchestrator,
+                config,
+                prometheus_registry,
+                subscription_service_checkpoint_sender.clone(),
+            )
+            .await?,
+        );
+    } else {
+        info!("json-rpc service is disabled");
+    }
+
     let rpc_router = {
         // Serve the index read paths from the embedded rpc-store when it
         // is enabled. Raw chain data comes from the perpetual / checkpoint
@@ -180,6 +180,18 @@ fn main() {
         sui_node::admin::run_admin_server(node, admin_interface_port, Some(filter_handle)).await
     });
 
+    let node_once_cell_clone = node_once_cell.clone();
+    runtimes.metrics.spawn(async move {
+        let node = node_once_cell_clone.get().await;
+        sui_node::health::run_health_server(node).await
+    });
+
+    let node_once_cell_clone = node_once_cell.clone();
+    runtimes.metrics.spawn(async move {
+        let node = node_once_cell_clone.get().await;
+        reload_transaction_policy_on_sighup(node.state()).await
+    });
+
     runtimes.metrics.spawn(async move {
         let node = node_once_cell.get().await;
         let state = node.state();
@@ -200,6 +212,25 @@ fn main() {
     drop(runtimes);
 }
 
+#[cfg(not(unix))]
+async fn reload_transaction_policy_on_sighup(_state: Arc<sui_core::authority::AuthorityState>) {}
+
+#[cfg(unix)]
+async fn reload_transaction_policy_on_sighup(state: Arc<sui_core::authority::AuthorityState>) {
+    use tokio::signal::unix::*;
+
+    let manager = state.transaction_deny_config_manager();
+    if !manager.is_transaction_policy_reloadable() {
+        return;
+    }
+    let mut sighup = signal(SignalKind::hangup()).unwrap();
+    while sighup.recv().await.is_some() {
+        info!("received SIGHUP, reloading transaction policy");
+        // Failures are logged and counted by the manager; the previous policy stays active.
+        let _ = manager.reload_transaction_policy();
+    }
+}
+
 #[cfg(not(unix))]
 async fn wait_termination(mut shutdown_rx: tokio::sync::broadcast::Receiver<()>) {
     tokio::select! {
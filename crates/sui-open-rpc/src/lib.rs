@@ -77,6 +77,27 @@ impl Project {
         self.method_routing.extend(module.method_routing);
     }
 
+    /// The parameters of every method, paired with their schemas. Schemas refer to the
+    /// [component schemas](Self::component_schemas) by `#/components/schemas/<name>`.
+    pub fn param_schemas(&self) -> BTreeMap<&str, Vec<(&str, &SchemaObject)>> {
+        self.methods
+            .iter()
+            .map(|method| {
+                let params = method
+                    .params
+                    .iter()
+                    .map(|param| (param.name.as_str(), &param.schema))
+                    .collect();
+                (method.name.as_str(), params)
+            })
+            .collect()
+    }
+
+    /// The schemas of the types used by the methods, keyed by name.
+    pub fn component_schemas(&self) -> &BTreeMap<String, SchemaObject> {
+        &self.components.schemas
+    }
+
     pub fn add_examples(&mut self, mut example_provider: BTreeMap<String, Vec<ExamplePairing>>) {
         for method in &mut self.methods {
             if let Occupied(entry) = example_provider.entry(method.name.clone()) {
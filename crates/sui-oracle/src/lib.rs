@@ -482,7 +482,7 @@ impl OnChainDataUploader {
             rgp,
         );
 
-        let signed_tx = self.wallet_ctx.sign_transaction(&tx).await;
+        let signed_tx = self.wallet_ctx.try_sign_transaction(&tx).await?;
         let tx_digest = *signed_tx.digest();
 
         let timer_start = Instant::now();
@@ -372,6 +372,7 @@ const MAINNET_USDB: &str =
 //              function signatures with `&mut TxContext` + any `&mut _` return
 //              that have no non-`TxContext` `&mut U` parameter.
 // Version 132: Enable defer_owned_object_double_spend on devnet.
+//              Enable round-robin ordering of reloaded deferred transactions by sender on devnet.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -1195,6 +1196,12 @@ struct FeatureFlags {
     // If true enable unified linkage
     #[serde(skip_serializing_if = "is_false")]
     enable_unified_linkage: bool,
+
+    // If true, deferred transactions that are loaded again are interleaved round-robin by
+    // sender, oldest deferral first, before being ordered for scheduling, so that senders with
+    // many deferred transactions do not starve the others during sustained congestion.
+    #[serde(skip_serializing_if = "is_false")]
+    fair_deferred_transaction_ordering: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -4561,6 +4568,7 @@ impl ProtocolConfig {
                 132 => {
                     if chain != Chain::Mainnet && chain != Chain::Testnet {
                         cfg.feature_flags.defer_owned_object_double_spend = true;
+                        cfg.feature_flags.fair_deferred_transaction_ordering = true;
                     }
                 }
                 // Use this template when making changes:
@@ -4881,6 +4889,10 @@ impl ProtocolConfig {
             .include_checkpoint_artifacts_digest_in_summary = true;
         self.feature_flags.split_checkpoints_in_consensus_handler = true;
     }
+
+    pub fn set_fair_deferred_transaction_ordering_for_testing(&mut self, val: bool) {
+        self.feature_flags.fair_deferred_transaction_ordering = val;
+    }
 }
 
 #[cfg(not(msim))]
@@ -7,6 +7,7 @@ use crate::middleware::{
     expect_content_length, expect_mysten_proxy_header, expect_valid_public_key,
 };
 use crate::peers::{AllowedPeer, SuiNodeProvider};
+use crate::relabel::Relabeler;
 use crate::var;
 use anyhow::Error;
 use anyhow::Result;
@@ -95,6 +96,7 @@ pub fn app(
     labels: Labels,
     client: ReqwestClient,
     relay: HistogramRelay,
+    relabeler: Arc<Relabeler>,
     allower: Option<SuiNodeProvider>,
     timeout_secs: Option<u64>,
 ) -> Router {
@@ -120,6 +122,7 @@ pub fn app(
             timeout_secs.unwrap_or(20),
         )))
         .layer(Extension(relay))
+        .layer(Extension(relabeler))
         .layer(Extension(labels))
         .layer(Extension(client))
         .layer(
@@ -5,6 +5,7 @@ use core::time::Duration;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_with::{DurationSeconds, serde_as};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use tracing::debug;
 
 #[serde_as]
@@ -18,6 +19,9 @@ pub struct ProxyConfig {
     pub static_peers: Option<StaticPeerValidationConfig>,
     pub metrics_address: String,
     pub histogram_address: String,
+    /// optional label enrichment and relabeling rules applied before metrics are pushed
+    #[serde(default)]
+    pub relabel: Option<RelabelConfig>,
 }
 
 #[serde_as]
@@ -90,11 +94,30 @@ pub struct StaticPubKey {
     pub peer_id: String,
 }
 
+/// RelabelConfig points at a file of relabeling rules, see [`crate::relabel::RelabelRules`].
+/// The file is polled for changes and reloaded without a restart.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelabelConfig {
+    /// please use an absolute path
+    pub path: PathBuf,
+    /// how often to check the rules file for changes
+    #[serde_as(as = "DurationSeconds<u64>")]
+    #[serde(default = "relabel_reload_interval_default")]
+    pub reload_interval: Duration,
+}
+
 /// the default idle worker per host (reqwest to remote write url call)
 fn pool_max_idle_per_host_default() -> usize {
     8
 }
 
+/// the default interval at which we check the relabel rules file for changes
+fn relabel_reload_interval_default() -> Duration {
+    Duration::from_secs(30)
+}
+
 /// the default hostname we will use if not provided
 fn hostname_default() -> Option<String> {
     Some("localhost".to_string())
@@ -5,6 +5,7 @@ use crate::consumer::{NodeMetric, convert_to_remote_write, populate_labels};
 use crate::histogram_relay::HistogramRelay;
 use crate::middleware::LenDelimProtobuf;
 use crate::peers::AllowedPeer;
+use crate::relabel::Relabeler;
 use axum::{
     extract::{ConnectInfo, Extension},
     http::StatusCode,
@@ -14,6 +15,7 @@ use once_cell::sync::Lazy;
 use prometheus::{CounterVec, HistogramVec};
 use prometheus::{register_counter_vec, register_histogram_vec};
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 static HANDLER_HITS: Lazy<CounterVec> = Lazy::new(|| {
     register_counter_vec!(
@@ -47,6 +49,7 @@ pub async fn publish_metrics(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Extension(AllowedPeer { name, public_key }): Extension<AllowedPeer>,
     Extension(relay): Extension<HistogramRelay>,
+    Extension(relabeler): Extension<Arc<Relabeler>>,
     LenDelimProtobuf(data): LenDelimProtobuf,
 ) -> (StatusCode, &'static str) {
     HANDLER_HITS
@@ -55,7 +58,13 @@ pub async fn publish_metrics(
     let timer = HTTP_HANDLER_DURATION
         .with_label_values(&["publish_metrics", &name])
         .start_timer();
-    let data = populate_labels(name, labels.network, labels.inventory_hostname, data);
+    let data = populate_labels(
+        name.clone(),
+        labels.network,
+        labels.inventory_hostname,
+        data,
+    );
+    let data = relabeler.relabel(&name, data);
     relay.submit(data.clone());
     let response = convert_to_remote_write(
         client.clone(),
@@ -9,6 +9,7 @@ pub mod metrics;
 pub mod middleware;
 pub mod peers;
 pub mod prom_to_mimir;
+pub mod relabel;
 pub mod remote_write;
 
 /// var extracts environment variables at runtime with a default fallback value
@@ -133,6 +134,7 @@ mod tests {
             },
             client,
             HistogramRelay::new(),
+            Default::default(),
             Some(allower.clone()),
             None,
         );
@@ -246,6 +248,7 @@ mod tests {
             },
             client,
             HistogramRelay::new(),
+            Default::default(),
             Some(allower.clone()),
             timeout_secs,
         );
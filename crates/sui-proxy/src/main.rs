@@ -11,6 +11,7 @@ use sui_proxy::{
     },
     config::load,
     histogram_relay, metrics,
+    relabel::Relabeler,
 };
 use sui_tls::TlsAcceptor;
 use telemetry_subscribers::TelemetryConfig;
@@ -73,6 +74,7 @@ async fn main() -> Result<()> {
     let histogram_listener = std::net::TcpListener::bind(config.histogram_address).unwrap();
     let metrics_listener = std::net::TcpListener::bind(config.metrics_address).unwrap();
     let acceptor = TlsAcceptor::new(tls_config);
+    let relabeler = Relabeler::new(config.relabel).expect("unable to load relabel rules");
     let client = make_reqwest_client(config.remote_write, APP_USER_AGENT);
     let histogram_relay = histogram_relay::start_prometheus_server(histogram_listener);
     let registry_service = metrics::start_prometheus_server(metrics_listener);
@@ -98,6 +100,7 @@ async fn main() -> Result<()> {
         },
         client,
         histogram_relay,
+        relabeler,
         allower,
         timeout_secs,
     );
@@ -0,0 +1,453 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operator-defined label enrichment and relabeling applied to metrics before they are pushed.
+//!
+//! Rules are read from a YAML file referenced by the proxy config. The file is polled for
+//! changes and reloaded in place; a file that fails to parse or validate keeps the previously
+//! loaded rules active.
+//!
+//! ```yaml
+//! labels:
+//!   region: us-east-1
+//! host-labels:
+//!   validator-0:
+//!     region: eu-west-1
+//! rules:
+//!   - action: drop
+//!     source-labels: [__name__]
+//!     regex: "tokio_.*"
+//!   - action: labeldrop
+//!     regex: "tx_digest|object_id"
+//! ```
+use crate::config::RelabelConfig;
+use anyhow::{Context, Result, bail};
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use prometheus::proto::{self, MetricFamily};
+use prometheus::{CounterVec, register_counter_vec};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::{error, info};
+
+/// Pseudo label holding the metric name while rules are evaluated.
+const METRIC_NAME_LABEL: &str = "__name__";
+
+static RELABEL_OPS: Lazy<CounterVec> = Lazy::new(|| {
+    register_counter_vec!(
+        "relabel_operations",
+        "Operations counters and status from relabeling, including reloads and dropped series.",
+        &["operation", "status"]
+    )
+    .unwrap()
+});
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Set `target-label` to `replacement` when the joined source labels match `regex`.
+    #[default]
+    Replace,
+    /// Drop series whose joined source labels do not match `regex`.
+    Keep,
+    /// Drop series whose joined source labels match `regex`.
+    Drop,
+    /// Remove labels whose name matches `regex`.
+    LabelDrop,
+    /// Remove labels whose name does not match `regex`.
+    LabelKeep,
+}
+
+/// A single relabeling rule, following Prometheus `relabel_config` semantics.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelabelRule {
+    #[serde(default)]
+    pub action: RelabelAction,
+    /// Labels whose values are joined with `separator` and matched against `regex`. The metric
+    /// name is available as `__name__`.
+    #[serde(default)]
+    pub source_labels: Vec<String>,
+    #[serde(default = "separator_default")]
+    pub separator: String,
+    /// Regular expression, anchored at both ends.
+    #[serde(default = "regex_default")]
+    pub regex: String,
+    pub target_label: Option<String>,
+    /// Value for `target-label`, may reference capture groups as `$1`. An empty result removes
+    /// the label.
+    #[serde(default = "replacement_default")]
+    pub replacement: String,
+}
+
+/// Contents of the relabeling rules file.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelabelRules {
+    /// Labels attached to every series, replacing any label of the same name.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Labels attached to series from a given host (the peer name), applied after `labels`.
+    #[serde(default)]
+    pub host_labels: BTreeMap<String, BTreeMap<String, String>>,
+    /// Rules evaluated in order after the labels above have been attached.
+    #[serde(default)]
+    pub rules: Vec<RelabelRule>,
+}
+
+fn separator_default() -> String {
+    ";".to_string()
+}
+
+fn regex_default() -> String {
+    "(.*)".to_string()
+}
+
+fn replacement_default() -> String {
+    "$1".to_string()
+}
+
+impl RelabelRules {
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let rules: Self = serde_yaml::from_reader(
+            std::fs::File::open(path).context(format!("cannot open {:?}", path))?,
+        )?;
+        Ok(rules)
+    }
+}
+
+struct CompiledRule {
+    rule: RelabelRule,
+    regex: Regex,
+}
+
+#[derive(Default)]
+struct CompiledRules {
+    labels: BTreeMap<String, String>,
+    host_labels: BTreeMap<String, BTreeMap<String, String>>,
+    rules: Vec<CompiledRule>,
+}
+
+impl CompiledRules {
+    fn compile(rules: RelabelRules) -> Result<Self> {
+        let compiled = rules
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let regex = Regex::new(&format!("^(?:{})$", rule.regex))
+                    .with_context(|| format!("invalid regex in relabel rule {i}"))?;
+                if rule.action == RelabelAction::Replace {
+                    match rule.target_label.as_deref() {
+                        None => bail!("relabel rule {i} is a replace without a target-label"),
+                        Some(METRIC_NAME_LABEL) => {
+                            bail!("relabel rule {i} cannot replace the metric name")
+                        }
+                        Some(_) => (),
+                    }
+                }
+                Ok(CompiledRule { rule, regex })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            labels: rules.labels,
+            host_labels: rules.host_labels,
+            rules: compiled,
+        })
+    }
+
+    /// Relabel a single series. Returns false if the series should be dropped.
+    fn relabel_metric(&self, name: &str, host: &str, metric: &mut proto::Metric) -> bool {
+        let mut labels: BTreeMap<String, String> = metric
+            .label
+            .iter()
+            .map(|l| (l.name().to_string(), l.value().to_string()))
+            .collect();
+        labels.extend(self.labels.clone());
+        if let Some(host_labels) = self.host_labels.get(host) {
+            labels.extend(host_labels.clone());
+        }
+        labels.insert(METRIC_NAME_LABEL.to_string(), name.to_string());
+
+        for CompiledRule { rule, regex } in &self.rules {
+            let source = || {
+                rule.source_labels
+                    .iter()
+                    .map(|l| labels.get(l).map(String::as_str).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join(&rule.separator)
+            };
+            match rule.action {
+                RelabelAction::Keep if !regex.is_match(&source()) => return false,
+                RelabelAction::Drop if regex.is_match(&source()) => return false,
+                RelabelAction::Keep | RelabelAction::Drop => (),
+                RelabelAction::Replace => {
+                    let value = source();
+                    let Some(captures) = regex.captures(&value) else {
+                        continue;
+                    };
+                    let mut replacement = String::new();
+                    captures.expand(&rule.replacement, &mut replacement);
+                    // Unwrap safe: validated when the rules were compiled.
+                    let target = rule.target_label.clone().unwrap();
+                    if replacement.is_empty() {
+                        labels.remove(&target);
+                    } else {
+                        labels.insert(target, replacement);
+                    }
+                }
+                RelabelAction::LabelDrop => {
+                    labels.retain(|k, _| k == METRIC_NAME_LABEL || !regex.is_match(k))
+                }
+                RelabelAction::LabelKeep => {
+                    labels.retain(|k, _| k == METRIC_NAME_LABEL || regex.is_match(k))
+                }
+            }
+        }
+
+        labels.remove(METRIC_NAME_LABEL);
+        // proto::LabelPair doesn't have pub fields so we can't use
+        // struct literals to construct
+        metric.label = labels
+            .into_iter()
+            .map(|(name, value)| proto::LabelPair {
+                name: Some(name),
+                value: Some(value),
+                ..Default::default()
+            })
+            .collect();
+        true
+    }
+}
+
+/// Relabeler holds the active relabeling rules and reloads them when their file changes.
+pub struct Relabeler {
+    path: Option<PathBuf>,
+    rules: ArcSwap<CompiledRules>,
+    modified: Mutex<Option<SystemTime>>,
+}
+
+impl Default for Relabeler {
+    /// A relabeler that leaves metrics untouched.
+    fn default() -> Self {
+        Self {
+            path: None,
+            rules: ArcSwap::from_pointee(CompiledRules::default()),
+            modified: Mutex::new(None),
+        }
+    }
+}
+
+impl Relabeler {
+    /// Load the rules file referenced by `config` and, if set, start polling it for changes.
+    pub fn new(config: Option<RelabelConfig>) -> Result<Arc<Self>> {
+        let Some(config) = config else {
+            return Ok(Arc::new(Self::default()));
+        };
+        let relabeler = Arc::new(Self {
+            path: Some(config.path.clone()),
+            ..Default::default()
+        });
+        relabeler.reload()?;
+        relabeler.poll_rules_file(config.reload_interval);
+        Ok(relabeler)
+    }
+
+    /// Re-read the rules file if it changed since the last load.
+    pub fn reload(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let modified = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .context(format!("cannot stat {:?}", path))?;
+        let mut last_modified = self.modified.lock().unwrap();
+        if *last_modified == Some(modified) {
+            return Ok(());
+        }
+
+        let rules = RelabelRules::load(path).and_then(CompiledRules::compile);
+        let rules = match rules {
+            Ok(rules) => rules,
+            Err(error) => {
+                RELABEL_OPS.with_label_values(&["reload", "failed"]).inc();
+                // Don't retry until the file changes again.
+                *last_modified = Some(modified);
+                return Err(error);
+            }
+        };
+        info!("loaded {} relabel rules from {:?}", rules.rules.len(), path);
+        self.rules.store(Arc::new(rules));
+        *last_modified = Some(modified);
+        RELABEL_OPS.with_label_values(&["reload", "success"]).inc();
+        Ok(())
+    }
+
+    fn poll_rules_file(self: &Arc<Self>, reload_interval: Duration) {
+        let relabeler = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(reload_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let Some(relabeler) = relabeler.upgrade() else {
+                    return;
+                };
+                if let Err(error) = relabeler.reload() {
+                    error!("unable to reload relabel rules, keeping previous rules; {error}");
+                }
+            }
+        });
+    }
+
+    /// Apply the active rules to metrics received from `host`, removing dropped series and any
+    /// metric family left empty.
+    pub fn relabel(&self, host: &str, data: Vec<MetricFamily>) -> Vec<MetricFamily> {
+        let rules = self.rules.load();
+        if rules.labels.is_empty() && rules.host_labels.is_empty() && rules.rules.is_empty() {
+            return data;
+        }
+
+        let mut dropped = 0;
+        let mut data = data;
+        for mf in data.iter_mut() {
+            let name = mf.name().to_string();
+            let before = mf.metric.len();
+            mf.metric
+                .retain_mut(|m| rules.relabel_metric(&name, host, m));
+            dropped += before - mf.metric.len();
+        }
+        data.retain(|mf| !mf.metric.is_empty());
+        if dropped > 0 {
+            RELABEL_OPS
+                .with_label_values(&["drop_series", "success"])
+                .inc_by(dropped as f64);
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prom_to_mimir::tests::{
+        create_counter, create_labels, create_metric_counter, create_metric_family,
+    };
+    use std::io::Write;
+
+    fn counter_family(name: &str, labels: Vec<(&str, &str)>) -> MetricFamily {
+        create_metric_family(
+            name,
+            "help",
+            Some(proto::MetricType::COUNTER),
+            vec![create_metric_counter(
+                create_labels(labels),
+                create_counter(1.0),
+            )],
+        )
+    }
+
+    fn relabeler(yaml: &str) -> Relabeler {
+        let rules: RelabelRules = serde_yaml::from_str(yaml).unwrap();
+        let relabeler = Relabeler::default();
+        relabeler
+            .rules
+            .store(Arc::new(CompiledRules::compile(rules).unwrap()));
+        relabeler
+    }
+
+    #[test]
+    fn test_enrich_and_relabel() {
+        let relabeler = relabeler(
+            r#"
+labels:
+  region: us-east-1
+host-labels:
+  validator-0:
+    region: eu-west-1
+rules:
+  - action: drop
+    source-labels: [__name__]
+    regex: "tokio_.*"
+  - action: labeldrop
+    regex: "tx_digest"
+  - action: replace
+    source-labels: [network, region]
+    regex: "(.*);(.*)"
+    target-label: location
+    replacement: "$1-$2"
+"#,
+        );
+
+        let data = relabeler.relabel(
+            "validator-0",
+            vec![
+                counter_family("tokio_tasks", vec![]),
+                counter_family(
+                    "requests",
+                    vec![("network", "testnet"), ("tx_digest", "abc")],
+                ),
+            ],
+        );
+        assert_eq!(data.len(), 1);
+        assert_eq!(
+            data[0].get_metric()[0].get_label(),
+            &create_labels(vec![
+                ("location", "testnet-eu-west-1"),
+                ("network", "testnet"),
+                ("region", "eu-west-1"),
+            ])
+        );
+
+        let data = relabeler.relabel(
+            "validator-1",
+            vec![counter_family("requests", vec![("network", "testnet")])],
+        );
+        assert_eq!(
+            data[0].get_metric()[0].get_label(),
+            &create_labels(vec![
+                ("location", "testnet-us-east-1"),
+                ("network", "testnet"),
+                ("region", "us-east-1"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules_rejected() {
+        let replace_without_target: RelabelRules =
+            serde_yaml::from_str("rules:\n  - action: replace\n").unwrap();
+        assert!(CompiledRules::compile(replace_without_target).is_err());
+
+        let bad_regex: RelabelRules =
+            serde_yaml::from_str("rules:\n  - action: drop\n    regex: \"(\"\n").unwrap();
+        assert!(CompiledRules::compile(bad_regex).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_previous_rules_on_error() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"labels:\n  region: us-east-1\n").unwrap();
+        let relabeler = Relabeler::new(Some(RelabelConfig {
+            path: file.path().to_path_buf(),
+            reload_interval: Duration::from_secs(3600),
+        }))
+        .unwrap();
+        assert_eq!(relabeler.rules.load().labels.len(), 1);
+
+        std::fs::write(file.path(), "rules: not-a-list").unwrap();
+        // Force the reload even if the file's mtime resolution hides the change.
+        *relabeler.modified.lock().unwrap() = None;
+        assert!(relabeler.reload().is_err());
+        assert_eq!(relabeler.rules.load().labels.len(), 1);
+
+        std::fs::write(file.path(), "labels:\n  a: b\n  c: d\n").unwrap();
+        *relabeler.modified.lock().unwrap() = None;
+        relabeler.reload().unwrap();
+        assert_eq!(relabeler.rules.load().labels.len(), 2);
+    }
+}
@@ -7,36 +7,48 @@ use axum_extra::extract::WithRejection;
 use futures::{TryStreamExt, future::join_all};
 
 use prost_types::FieldMask;
+use std::collections::HashMap;
 use std::str::FromStr;
 use sui_rpc::client::Client;
 use sui_rpc::field::FieldMaskUtil;
 use sui_rpc::proto::sui::rpc::v2::{
-    GetBalanceRequest, GetCheckpointRequest, GetEpochRequest, ListOwnedObjectsRequest,
+    AffectedAddressFilter, GetBalanceRequest, GetCheckpointRequest, GetEpochRequest,
+    ListOwnedObjectsRequest, ListTransactionsRequest, QueryEndReason, QueryOptions,
+    TransactionFilter, TransactionLiteral, TransactionTerm, get_checkpoint_request,
+    transaction_literal,
 };
 use sui_sdk_types::{Address, StructTag};
+use sui_types::TypeTag;
 use sui_types::base_types::SuiAddress;
+use sui_types::governance::FungibleStakedSui;
 
 use crate::errors::Error;
 use crate::types::{
     AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
-    Amount, Coin, CoinID, CoinIdentifier, Currencies, Currency, SubAccountType, SubBalance,
+    Amount, BlockHash, Coin, CoinID, CoinIdentifier, Currencies, Currency, PartialBlockIdentifier,
+    SubAccountType, SubBalance,
 };
 use crate::{OnlineServerContext, SuiEnv};
 use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
-/// BCS layout for `0x3::staking_pool::FungibleStakedSui`.
-/// Field order must match the Move struct definition exactly (BCS is positional).
-/// See: crates/sui-framework/packages/sui-system/sources/staking_pool.move
-#[derive(serde::Deserialize)]
-pub(crate) struct FungibleStakedSuiBcs {
-    pub _id: Address,
-    pub pool_id: Address,
-    pub value: u64,
-}
+/// Number of times a historical balance lookup re-reads the live balance while
+/// waiting for a window in which the latest checkpoint does not move.
+const BALANCE_SNAPSHOT_ATTEMPTS: usize = 10;
+
+/// Page size used when scanning an account's transactions to roll balances back.
+const BALANCE_CHANGES_PAGE_SIZE: u32 = 1000;
+
+/// Most pages a historical balance lookup scans before giving up, bounding the work a single
+/// request can cause for an account with a long history since the requested block.
+const MAX_BALANCE_CHANGES_PAGES: usize = 100;
 
 /// Get an array of all AccountBalances for an AccountIdentifier and the BlockIdentifier
 /// at which the balance lookup was performed.
+///
+/// When the request carries a `block_identifier`, the balance is reported as of the end
+/// of that checkpoint: the live balance is read at a known checkpoint and the account's
+/// balance changes from every later checkpoint are subtracted from it.
 /// [Mesh API Spec](https://docs.cdp.coinbase.com/api-reference/mesh/account/get-an-account-balance)
 pub async fn balance(
     State(mut ctx): State<OnlineServerContext>,
@@ -47,15 +59,206 @@ pub async fn balance(
     let address = request.account_identifier.address;
     let currencies = &request.currencies;
 
-    let checkpoint = get_checkpoint(&mut ctx).await?;
-    let balances = get_balances(&mut ctx, &request, address, currencies.clone()).await?;
+    let Some(target) = resolve_block_index(&mut ctx, &request.block_identifier).await? else {
+        let checkpoint = get_checkpoint(&mut ctx).await?;
+        let balances = get_balances(&mut ctx, &request, address, currencies.clone()).await?;
+
+        return Ok(AccountBalanceResponse {
+            block_identifier: ctx.blocks().create_block_identifier(checkpoint).await?,
+            balances,
+        });
+    };
+
+    if request.account_identifier.sub_account.is_some() {
+        return Err(Error::InvalidInput(
+            "Historical balance lookup is not supported for sub-accounts".to_string(),
+        ));
+    }
+
+    let (checkpoint, mut balances) =
+        get_balances_at_checkpoint(&mut ctx, &request, address, currencies).await?;
+    if target > checkpoint {
+        return Err(Error::BlockNotFound {
+            index: Some(target),
+            hash: request.block_identifier.hash,
+        });
+    }
+    if target < checkpoint {
+        let changes =
+            get_balance_changes(&mut ctx.client, address, target + 1, checkpoint + 1).await?;
+        roll_back_balances(&mut balances, &changes)?;
+    }
 
     Ok(AccountBalanceResponse {
-        block_identifier: ctx.blocks().create_block_identifier(checkpoint).await?,
+        block_identifier: ctx.blocks().create_block_identifier(target).await?,
         balances,
     })
 }
 
+/// Resolve a partial block identifier to a checkpoint sequence number, preferring the
+/// index when both are set. Returns `None` when neither is set.
+async fn resolve_block_index(
+    ctx: &mut OnlineServerContext,
+    block_identifier: &PartialBlockIdentifier,
+) -> Result<Option<CheckpointSequenceNumber>, Error> {
+    if let Some(index) = block_identifier.index {
+        return Ok(Some(index));
+    }
+    let Some(hash) = block_identifier.hash else {
+        return Ok(None);
+    };
+    get_checkpoint_by_digest(ctx, hash).await.map(Some)
+}
+
+async fn get_checkpoint_by_digest(
+    ctx: &mut OnlineServerContext,
+    hash: BlockHash,
+) -> Result<CheckpointSequenceNumber, Error> {
+    let mut request =
+        GetCheckpointRequest::default().with_read_mask(FieldMask::from_paths(["sequence_number"]));
+    request.checkpoint_id = Some(get_checkpoint_request::CheckpointId::Digest(
+        hash.to_string(),
+    ));
+
+    let response = ctx
+        .client
+        .ledger_client()
+        .get_checkpoint(request)
+        .await
+        .map_err(|status| match status.code() {
+            tonic::Code::NotFound => Error::BlockNotFound {
+                index: None,
+                hash: Some(hash),
+            },
+            _ => status.into(),
+        })?;
+    Ok(response.into_inner().checkpoint().sequence_number())
+}
+
+/// Read the live balances together with the checkpoint they reflect. The latest
+/// checkpoint is read on both sides of the balance lookup and the read is retried until
+/// the two agree, so the balances are known to include exactly the checkpoints up to
+/// and including the returned one.
+async fn get_balances_at_checkpoint(
+    ctx: &mut OnlineServerContext,
+    request: &AccountBalanceRequest,
+    address: SuiAddress,
+    currencies: &Currencies,
+) -> Result<(CheckpointSequenceNumber, Vec<Amount>), Error> {
+    for _ in 0..BALANCE_SNAPSHOT_ATTEMPTS {
+        let before = get_checkpoint(ctx).await?;
+        let balances = get_balances(ctx, request, address, currencies.clone()).await?;
+        if get_checkpoint(ctx).await? == before {
+            return Ok((before, balances));
+        }
+    }
+    Err(Error::RetryExhausted(format!(
+        "Latest checkpoint advanced during {BALANCE_SNAPSHOT_ATTEMPTS} consecutive balance reads"
+    )))
+}
+
+/// Sum the balance changes of `address`, per coin type, over all transactions in
+/// checkpoints `[start_checkpoint, end_checkpoint)`.
+async fn get_balance_changes(
+    client: &mut Client,
+    address: SuiAddress,
+    start_checkpoint: CheckpointSequenceNumber,
+    end_checkpoint: CheckpointSequenceNumber,
+) -> Result<HashMap<TypeTag, i128>, Error> {
+    let mut affected_address = AffectedAddressFilter::default();
+    affected_address.address = Some(address.to_string());
+    let mut literal = TransactionLiteral::default();
+    literal.predicate = Some(transaction_literal::Predicate::AffectedAddress(
+        affected_address,
+    ));
+    let mut term = TransactionTerm::default();
+    term.literals = vec![literal];
+    let mut filter = TransactionFilter::default();
+    filter.terms = vec![term];
+
+    let mut changes: HashMap<TypeTag, i128> = HashMap::new();
+    let mut after = None;
+    for _ in 0..MAX_BALANCE_CHANGES_PAGES {
+        let mut options = QueryOptions::default();
+        options.limit = Some(BALANCE_CHANGES_PAGE_SIZE);
+        options.after = after.take();
+
+        let mut request = ListTransactionsRequest::default();
+        request.read_mask = Some(FieldMask::from_paths(["balance_changes"]));
+        request.start_checkpoint = Some(start_checkpoint);
+        request.end_checkpoint = Some(end_checkpoint);
+        request.filter = Some(filter.clone());
+        request.options = Some(options);
+
+        let mut stream = client
+            .ledger_client()
+            .list_transactions(request)
+            .await?
+            .into_inner();
+        let mut cursor = None;
+        let mut end_reason = None;
+        while let Some(response) = stream.message().await? {
+            if let Some(watermark_cursor) =
+                response.watermark.as_ref().and_then(|w| w.cursor.clone())
+            {
+                cursor = Some(watermark_cursor);
+            }
+            if let Some(end) = &response.end {
+                end_reason = Some(end.reason());
+            }
+            let Some(transaction) = response.transaction else {
+                continue;
+            };
+            for balance_change in &transaction.balance_changes {
+                if SuiAddress::from_str(balance_change.address()).ok() != Some(address) {
+                    continue;
+                }
+                let coin_type = TypeTag::from_str(balance_change.coin_type())
+                    .map_err(|e| Error::DataError(format!("Invalid coin type: {e}")))?;
+                let amount = i128::from_str(balance_change.amount())
+                    .map_err(|e| Error::DataError(format!("Invalid balance change: {e}")))?;
+                *changes.entry(coin_type).or_default() += amount;
+            }
+        }
+
+        match end_reason {
+            Some(QueryEndReason::ItemLimit | QueryEndReason::ScanLimit) => {
+                after = Some(cursor.ok_or_else(|| {
+                    Error::DataError("Transaction page ended without a cursor".to_string())
+                })?);
+            }
+            _ => return Ok(changes),
+        }
+    }
+    Err(Error::InvalidInput(format!(
+        "Too many transactions for {address} since checkpoint {start_checkpoint} to look up its \
+         historical balance; request a more recent block"
+    )))
+}
+
+/// Undo `changes` on top of `balances`, turning balances read at a later checkpoint
+/// into balances at an earlier one.
+fn roll_back_balances(
+    balances: &mut [Amount],
+    changes: &HashMap<TypeTag, i128>,
+) -> Result<(), Error> {
+    for balance in balances {
+        let coin_type = TypeTag::from_str(&balance.currency.metadata.coin_type)
+            .map_err(|e| Error::InvalidInput(format!("Invalid coin type: {e}")))?;
+        let Some(change) = changes.get(&coin_type) else {
+            continue;
+        };
+        balance.value -= change;
+        if balance.value < 0 {
+            return Err(Error::DataError(format!(
+                "Negative historical balance for {}",
+                balance.currency.metadata.coin_type
+            )));
+        }
+    }
+    Ok(())
+}
+
 async fn get_checkpoint(ctx: &mut OnlineServerContext) -> Result<CheckpointSequenceNumber, Error> {
     let request =
         GetCheckpointRequest::latest().with_read_mask(FieldMask::from_paths(["sequence_number"]));
@@ -346,19 +549,20 @@ async fn get_fungible_staked_sui_value(
             .contents
             .as_ref()
             .ok_or_else(|| Error::DataError("FungibleStakedSui missing contents".to_string()))?;
-        let fss: FungibleStakedSuiBcs = contents.deserialize().map_err(|e| {
+        let fss: FungibleStakedSui = contents.deserialize().map_err(|e| {
             Error::DataError(format!("Failed to deserialize FungibleStakedSui: {}", e))
         })?;
 
-        let pool_id_str = fss.pool_id.to_string();
+        let pool_id_str = fss.pool_id().to_string();
         let rate = pool_rates.get(&pool_id_str).ok_or_else(|| {
             Error::DataError(format!("No exchange rate found for pool {}", pool_id_str))
         })?;
 
         let sui_equivalent = if rate.pool_token_balance > 0 {
-            (fss.value as u128 * rate.sui_balance as u128 / rate.pool_token_balance as u128) as u64
+            (fss.value() as u128 * rate.sui_balance as u128 / rate.pool_token_balance as u128)
+                as u64
         } else {
-            fss.value
+            fss.value()
         };
 
         sub_balances.push(SubBalance {
@@ -424,3 +628,54 @@ pub async fn coins(
         coins,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SUI;
+    use crate::types::CurrencyMetadata;
+
+    fn usdc() -> Currency {
+        Currency {
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            metadata: CurrencyMetadata {
+                coin_type: "0x5::usdc::USDC".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_roll_back_balances() {
+        let mut balances = vec![
+            Amount::new(1_000, Some(SUI.clone())),
+            Amount::new(50, Some(usdc())),
+        ];
+        // Balance changes are keyed by the fully expanded coin type.
+        let changes = HashMap::from([
+            (
+                TypeTag::from_str(
+                    "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI",
+                )
+                .unwrap(),
+                -250,
+            ),
+            (TypeTag::from_str("0x6::other::OTHER").unwrap(), 7),
+        ]);
+
+        roll_back_balances(&mut balances, &changes).unwrap();
+        assert_eq!(balances[0].value, 1_250);
+        assert_eq!(balances[1].value, 50);
+    }
+
+    #[test]
+    fn test_roll_back_balances_rejects_negative() {
+        let mut balances = vec![Amount::new(10, Some(usdc()))];
+        let changes = HashMap::from([(TypeTag::from_str("0x5::usdc::USDC").unwrap(), 11)]);
+
+        assert!(matches!(
+            roll_back_balances(&mut balances, &changes),
+            Err(Error::DataError(_))
+        ));
+    }
+}
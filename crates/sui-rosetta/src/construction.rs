@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -12,7 +13,8 @@ use fastcrypto::hash::HashFunction;
 use prost_types::FieldMask;
 use sui_rpc::field::FieldMaskUtil;
 use sui_rpc::proto::sui::rpc::v2::{
-    ExecuteTransactionRequest, SimulateTransactionRequest, UserSignature,
+    BalanceChange, ExecuteTransactionRequest, SimulateTransactionRequest,
+    Transaction as ProtoTransaction, UserSignature,
     simulate_transaction_request::TransactionChecks,
 };
 
@@ -27,7 +29,7 @@ use sui_types::signature_verification::{
 use sui_types::transaction::TransactionDataAPI;
 
 use crate::errors::Error;
-use crate::operations::reconstruct_operations;
+use crate::operations::{Operations, reconstruct_operations};
 use crate::types::internal_operation::{PayCoin, TransactionObjectData, TryConstructTransaction};
 use crate::types::transaction_envelope;
 use crate::types::{
@@ -37,10 +39,12 @@ use crate::types::{
     ConstructionParseRequest, ConstructionParseResponse, ConstructionPayloadsRequest,
     ConstructionPayloadsResponse, ConstructionPreprocessRequest, ConstructionPreprocessResponse,
     ConstructionSubmitRequest, InternalOperation, MetadataOptions, RosettaTransaction,
-    SignatureType, SigningPayload, TransactionIdentifier, TransactionIdentifierResponse,
+    SignatureType, SigningPayload, Transaction, TransactionIdentifier,
+    TransactionIdentifierResponse,
 };
 use crate::{OnlineServerContext, SuiEnv};
 use move_core_types::language_storage::TypeTag;
+use tracing::warn;
 
 // This module implements the [Mesh Construction API](https://docs.cdp.coinbase.com/mesh/mesh-api-spec/api-reference#construction)
 
@@ -231,6 +235,11 @@ pub async fn submit(
             .iter()
             .map(|bc| bc.coin_type()),
     )?;
+    verify_pay_multi_coin_amounts(
+        &wrapper.aux,
+        &proto_transaction,
+        response.transaction().balance_changes(),
+    )?;
 
     let mut client = context.client.clone();
     let mut execution_client = client.execution_client();
@@ -258,6 +267,29 @@ pub async fn submit(
         .parse::<TransactionDigest>()
         .map_err(|e| Error::DataError(format!("Invalid transaction digest: {}", e)))?;
 
+    // Track the submission until it lands in a checkpoint so `/mempool` can report it.
+    // The transaction has already executed, so failing to derive its operations only
+    // means it is not tracked.
+    match Operations::try_from_executed_transaction(
+        transaction.clone(),
+        &context.coin_metadata_cache,
+    )
+    .await
+    {
+        Ok(operations) => {
+            context
+                .pending_transactions
+                .insert(Transaction {
+                    transaction_identifier: TransactionIdentifier { hash: digest },
+                    operations,
+                    related_transactions: vec![],
+                    metadata: None,
+                })
+                .await
+        }
+        Err(e) => warn!("Failed to track submitted transaction {digest}: {e}"),
+    }
+
     Ok(TransactionIdentifierResponse {
         transaction_identifier: TransactionIdentifier { hash: digest },
         metadata: None,
@@ -376,6 +408,7 @@ pub async fn metadata(
         redeem_token_amount,
         redeem_plan,
         bind_epoch,
+        payment_objects,
     } = needed_objects;
 
     // For backwards compatibility during rolling deployments, populate extra_gas_coins.
@@ -428,6 +461,7 @@ pub async fn metadata(
             redeem_token_amount,
             redeem_plan,
             bind_epoch,
+            payment_objects,
         },
         suggested_fee: vec![Amount::new(budget as i128, None)],
     })
@@ -511,6 +545,66 @@ fn verify_pay_coin_currency<'a>(
     }
 }
 
+/// Check a multi-currency PayCoin against the simulated balance changes: every
+/// recipient must be credited exactly what its reconstructed operations pay it
+/// in each currency. Checking only that the labelled currencies move would
+/// accept a wrapper whose labels are swapped between legs. Other aux data is a
+/// no-op.
+fn verify_pay_multi_coin_amounts(
+    aux: &AuxData,
+    proto: &ProtoTransaction,
+    balance_changes: &[BalanceChange],
+) -> Result<(), Error> {
+    if !matches!(aux, AuxData::PayMultiCoin { .. }) {
+        return Ok(());
+    }
+    let sender = SuiAddress::from_str(proto.sender())
+        .map_err(|e| Error::DataError(format!("invalid transaction sender: {e}")))?;
+    let coin_type = |coin_type: &str| {
+        TypeTag::from_str(coin_type)
+            .map_err(|e| Error::DataError(format!("invalid coin type {coin_type}: {e}")))
+    };
+
+    let mut paid: BTreeMap<(SuiAddress, TypeTag), i128> = BTreeMap::new();
+    for op in reconstruct_operations(proto, aux, None)? {
+        if let (Some(account), Some(amount)) = (op.account, op.amount)
+            && account.address != sender
+            && amount.value > 0
+        {
+            let key = (
+                account.address,
+                coin_type(&amount.currency.metadata.coin_type)?,
+            );
+            *paid.entry(key).or_default() += amount.value;
+        }
+    }
+
+    let mut credited: BTreeMap<(SuiAddress, TypeTag), i128> = BTreeMap::new();
+    for bc in balance_changes {
+        let address = SuiAddress::from_str(bc.address())
+            .map_err(|e| Error::DataError(format!("invalid balance change address: {e}")))?;
+        let amount = i128::from_str(bc.amount())
+            .map_err(|e| Error::DataError(format!("invalid balance change amount: {e}")))?;
+        *credited
+            .entry((address, coin_type(bc.coin_type())?))
+            .or_default() += amount;
+    }
+
+    for ((address, coin_type), amount) in paid {
+        let got = credited
+            .get(&(address, coin_type.clone()))
+            .copied()
+            .unwrap_or_default();
+        if got != amount {
+            return Err(Error::DataError(format!(
+                "PayCoin pays {address} {amount} of {coin_type}, but the simulated transaction \
+                 credits it {got}"
+            )));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -555,4 +649,94 @@ mod tests {
         // Non-PayCoin aux data is a no-op regardless of balance changes.
         assert!(verify_pay_coin_currency(&AuxData::None, std::iter::empty()).is_ok());
     }
+
+    fn currency(symbol: &str, coin_type: &str) -> Currency {
+        Currency {
+            symbol: symbol.to_string(),
+            decimals: 6,
+            metadata: CurrencyMetadata {
+                coin_type: coin_type.to_string(),
+            },
+        }
+    }
+
+    fn balance_change(address: SuiAddress, coin_type: &str, amount: i128) -> BalanceChange {
+        let mut bc = BalanceChange::default();
+        bc.address = Some(address.to_string());
+        bc.coin_type = Some(coin_type.to_string());
+        bc.amount = Some(amount.to_string());
+        bc
+    }
+
+    /// The `/submit` PayMultiCoin check accepts balance changes that credit
+    /// each recipient what its leg pays, and rejects labels swapped between
+    /// legs.
+    #[test]
+    fn test_verify_pay_multi_coin_amounts() {
+        use crate::types::internal_operation::{
+            CoinPayment, CoinPaymentObjects, PayMultiCoin, pay_multi_coin_pt,
+        };
+        use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+        use sui_types::transaction::TransactionData;
+
+        let (usdc, usdt) = ("0x5::usdc::USDC", "0x6::usdt::USDT");
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let pay = PayMultiCoin {
+            sender,
+            payments: vec![
+                CoinPayment {
+                    currency: currency("USDC", usdc),
+                    recipients: vec![recipient],
+                    amounts: vec![10],
+                },
+                CoinPayment {
+                    currency: currency("USDT", usdt),
+                    recipients: vec![recipient],
+                    amounts: vec![20],
+                },
+            ],
+        };
+        let coin = || CoinPaymentObjects {
+            objects: vec![(
+                ObjectID::random(),
+                SequenceNumber::from(1),
+                ObjectDigest::random(),
+            )],
+            ..Default::default()
+        };
+        let pt = pay_multi_coin_pt(sender, pay.payments.clone(), &[coin(), coin()]).unwrap();
+        let gas = (
+            ObjectID::random(),
+            SequenceNumber::from(1),
+            ObjectDigest::random(),
+        );
+        let data = TransactionData::new_programmable(sender, vec![gas], pt, 1_000_000, 1000);
+        let proto = transaction_envelope::decode_inner_proto(
+            &transaction_envelope::encode_inner_proto(&data),
+        )
+        .unwrap();
+        let aux = InternalOperation::PayMultiCoin(pay).aux();
+
+        let credits = [
+            balance_change(sender, usdc, -10),
+            balance_change(sender, usdt, -20),
+            balance_change(sender, "0x2::sui::SUI", -1000),
+            balance_change(recipient, usdc, 10),
+            balance_change(recipient, usdt, 20),
+        ];
+        assert!(verify_pay_multi_coin_amounts(&aux, &proto, &credits).is_ok());
+
+        // The coins behind the two legs are the other way around.
+        let swapped = [
+            balance_change(recipient, usdc, 20),
+            balance_change(recipient, usdt, 10),
+        ];
+        let err = verify_pay_multi_coin_amounts(&aux, &proto, &swapped)
+            .expect_err("swapped labels must be rejected");
+        assert!(format!("{err:?}").contains("credits it"));
+
+        // Other aux data is a no-op.
+        assert!(verify_pay_multi_coin_amounts(&AuxData::None, &proto, &[]).is_ok());
+    }
 }
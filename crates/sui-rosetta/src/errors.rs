@@ -15,6 +15,7 @@ use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use sui_rpc::proto::sui::rpc::v2::ExecutionError;
+use sui_types::digests::TransactionDigest;
 use sui_types::error::{SuiError, SuiErrorKind};
 
 use crate::types::{BlockHash, OperationType, PublicKey, SuiEnv};
@@ -87,6 +88,9 @@ pub enum Error {
     #[error("Coin metadata temporarily unavailable: {0}")]
     #[strum(props(retriable = "true"))]
     CoinMetadataUnavailable(String),
+
+    #[error("Transaction not found in mempool: {0}")]
+    TransactionNotInMempool(TransactionDigest),
 }
 
 impl From<SuiErrorKind> for Error {
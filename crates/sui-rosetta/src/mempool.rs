@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::{Extension, Json};
+use axum_extra::extract::WithRejection;
+use futures::stream::{self, StreamExt};
+use lru::LruCache;
+use prost_types::FieldMask;
+use sui_rpc::field::FieldMaskUtil;
+use sui_rpc::proto::sui::rpc::v2::GetTransactionRequest;
+use sui_types::digests::TransactionDigest;
+use tokio::sync::Mutex;
+
+use crate::types::{
+    MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse, NetworkRequest,
+    Transaction, TransactionIdentifier,
+};
+use crate::{Error, OnlineServerContext, SuiEnv};
+
+// This module implements the [Mesh Mempool API](https://docs.cdp.coinbase.com/mesh/mesh-api-spec/api-reference#mempool)
+
+/// How long a submitted transaction is reported as pending if it is never seen in a
+/// checkpoint. Sui checkpoints executed transactions within seconds, so anything older
+/// than this has either been checkpointed or will never be.
+const PENDING_TRANSACTION_TTL: Duration = Duration::from_secs(300);
+
+/// Upper bound on the number of submissions tracked at once; the oldest are evicted first.
+const PENDING_TRANSACTIONS_CAPACITY: usize = 10_000;
+
+/// Number of concurrent checkpoint lookups made while refreshing the mempool.
+const CHECKPOINT_LOOKUP_CONCURRENCY: usize = 32;
+
+/// Transactions submitted through `/construction/submit` that have executed but are
+/// not yet part of a checkpoint, and so are not yet visible through `/block`.
+#[derive(Clone)]
+pub struct PendingTransactions {
+    transactions: Arc<Mutex<LruCache<TransactionDigest, PendingTransaction>>>,
+}
+
+struct PendingTransaction {
+    transaction: Transaction,
+    submitted_at: Instant,
+}
+
+impl Default for PendingTransactions {
+    fn default() -> Self {
+        Self {
+            transactions: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(PENDING_TRANSACTIONS_CAPACITY).unwrap(),
+            ))),
+        }
+    }
+}
+
+impl PendingTransactions {
+    pub async fn insert(&self, transaction: Transaction) {
+        let digest = transaction.transaction_identifier.hash;
+        self.transactions.lock().await.put(
+            digest,
+            PendingTransaction {
+                transaction,
+                submitted_at: Instant::now(),
+            },
+        );
+    }
+
+    pub async fn get(&self, digest: &TransactionDigest) -> Option<Transaction> {
+        let mut transactions = self.transactions.lock().await;
+        let pending = transactions.peek(digest)?;
+        if pending.submitted_at.elapsed() > PENDING_TRANSACTION_TTL {
+            transactions.pop(digest);
+            return None;
+        }
+        Some(pending.transaction.clone())
+    }
+
+    async fn remove(&self, digest: &TransactionDigest) {
+        self.transactions.lock().await.pop(digest);
+    }
+
+    /// Drop expired entries and return the digests of the remaining ones.
+    async fn digests(&self) -> Vec<TransactionDigest> {
+        let mut transactions = self.transactions.lock().await;
+        let expired: Vec<_> = transactions
+            .iter()
+            .filter(|(_, pending)| pending.submitted_at.elapsed() > PENDING_TRANSACTION_TTL)
+            .map(|(digest, _)| *digest)
+            .collect();
+        for digest in &expired {
+            transactions.pop(digest);
+        }
+        transactions.iter().map(|(digest, _)| *digest).collect()
+    }
+}
+
+/// Get all Transaction Identifiers in the mempool.
+///
+/// Sui has no public mempool; this reports the transactions submitted through this
+/// server that have executed but have not yet been included in a checkpoint.
+/// [Mesh API Spec](https://docs.cdp.coinbase.com/api-reference/mesh/mempool/get-all-mempool-transactions)
+pub async fn mempool(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<NetworkRequest>, Error>,
+) -> Result<MempoolResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+
+    let pending = &context.pending_transactions;
+    let checks: Vec<_> = stream::iter(pending.digests().await)
+        .map(|digest| {
+            let context = context.clone();
+            async move { (digest, is_checkpointed(&context, digest).await) }
+        })
+        .buffer_unordered(CHECKPOINT_LOOKUP_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut transaction_identifiers = Vec::new();
+    for (digest, checkpointed) in checks {
+        match checkpointed {
+            Ok(true) => pending.remove(&digest).await,
+            // Lookup failures leave the entry pending until it is found or expires.
+            Ok(false) | Err(_) => {
+                transaction_identifiers.push(TransactionIdentifier { hash: digest })
+            }
+        }
+    }
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Get a transaction in the mempool by its Transaction Identifier.
+/// [Mesh API Spec](https://docs.cdp.coinbase.com/api-reference/mesh/mempool/get-a-mempool-transaction)
+pub async fn transaction(
+    State(context): State<OnlineServerContext>,
+    Extension(env): Extension<SuiEnv>,
+    WithRejection(Json(request), _): WithRejection<Json<MempoolTransactionRequest>, Error>,
+) -> Result<MempoolTransactionResponse, Error> {
+    env.check_network_identifier(&request.network_identifier)?;
+    let digest = request.transaction_identifier.hash;
+
+    let pending = &context.pending_transactions;
+    let transaction = pending
+        .get(&digest)
+        .await
+        .ok_or(Error::TransactionNotInMempool(digest))?;
+    if is_checkpointed(&context, digest).await? {
+        pending.remove(&digest).await;
+        return Err(Error::TransactionNotInMempool(digest));
+    }
+
+    Ok(MempoolTransactionResponse {
+        transaction,
+        metadata: None,
+    })
+}
+
+async fn is_checkpointed(
+    context: &OnlineServerContext,
+    digest: TransactionDigest,
+) -> Result<bool, Error> {
+    let request = GetTransactionRequest::default()
+        .with_digest(digest.to_string())
+        .with_read_mask(FieldMask::from_paths(["checkpoint"]));
+
+    let response = context
+        .client
+        .clone()
+        .ledger_client()
+        .get_transaction(request)
+        .await?
+        .into_inner();
+    Ok(response
+        .transaction
+        .and_then(|transaction| transaction.checkpoint)
+        .is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::Operations;
+
+    fn transaction(digest: TransactionDigest) -> Transaction {
+        Transaction {
+            transaction_identifier: TransactionIdentifier { hash: digest },
+            operations: Operations::new(vec![]),
+            related_transactions: vec![],
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_transactions() {
+        let pending = PendingTransactions::default();
+        let digest = TransactionDigest::random();
+        pending.insert(transaction(digest)).await;
+
+        assert_eq!(pending.digests().await, vec![digest]);
+        assert!(pending.get(&digest).await.is_some());
+
+        pending.remove(&digest).await;
+        assert!(pending.digests().await.is_empty());
+        assert!(pending.get(&digest).await.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::Not;
 use std::str::FromStr;
 use std::vec;
@@ -38,8 +38,8 @@ use sui_types::{
 #[cfg(test)]
 use crate::types::RedeemPlan;
 use crate::types::internal_operation::{
-    ConsolidateAllStakedSuiToFungible, MergeAndRedeemFungibleStakedSui, PayCoin, PaySui, Stake,
-    WithdrawStake,
+    CoinPayment, ConsolidateAllStakedSuiToFungible, MergeAndRedeemFungibleStakedSui, PayCoin,
+    PayMultiCoin, PaySui, Stake, WithdrawStake,
 };
 use crate::types::{
     AccountIdentifier, Amount, AuxData, CoinAction, CoinChange, CoinID, CoinIdentifier, Currency,
@@ -271,35 +271,78 @@ impl Operations {
         }))
     }
 
+    /// Operations of one currency become a `PayCoin`; operations of several currencies a
+    /// `PayMultiCoin` with one payment per currency, in the order each currency first appears.
     fn pay_coin_ops_to_internal(self) -> Result<InternalOperation, Error> {
-        let mut recipients = vec![];
-        let mut amounts = vec![];
+        let mut payments: Vec<CoinPayment> = vec![];
         let mut sender = None;
-        let mut currency = None;
         for op in self {
             if let (Some(amount), Some(account)) = (op.amount.clone(), op.account.clone()) {
-                currency = currency.or(Some(amount.currency));
+                let payment = match payments.iter().position(|p| p.currency == amount.currency) {
+                    Some(i) => &mut payments[i],
+                    None => {
+                        payments.push(CoinPayment {
+                            currency: amount.currency,
+                            recipients: vec![],
+                            amounts: vec![],
+                        });
+                        payments.last_mut().unwrap()
+                    }
+                };
                 if amount.value.is_negative() {
+                    if sender.is_some_and(|sender| sender != account.address) {
+                        return Err(Error::InvalidInput(
+                            "PayCoin operations must have a single sender".to_string(),
+                        ));
+                    }
                     sender = Some(account.address)
                 } else {
-                    recipients.push(account.address);
+                    payment.recipients.push(account.address);
                     let amount = amount.value.abs();
                     if amount > u64::MAX as i128 {
                         return Err(Error::InvalidInput(
                             "Input amount exceed u64::MAX".to_string(),
                         ));
                     }
-                    amounts.push(amount as u64)
+                    payment.amounts.push(amount as u64)
                 }
             }
         }
         let sender = sender.ok_or_else(|| Error::MissingInput("Sender address".to_string()))?;
-        let currency = currency.ok_or_else(|| Error::MissingInput("Currency".to_string()))?;
-        Ok(InternalOperation::PayCoin(PayCoin {
+        if payments.len() <= 1 {
+            let CoinPayment {
+                currency,
+                recipients,
+                amounts,
+            } = payments
+                .pop()
+                .ok_or_else(|| Error::MissingInput("Currency".to_string()))?;
+            return Ok(InternalOperation::PayCoin(PayCoin {
+                sender,
+                recipients,
+                amounts,
+                currency,
+            }));
+        }
+
+        for payment in &payments {
+            // SUI is paid with PaySui: a SUI leg would compete with gas selection for the coins.
+            if payment.currency.metadata.coin_type == SUI.metadata.coin_type {
+                return Err(Error::InvalidInput(
+                    "SUI cannot be paid alongside other currencies; use a separate PaySui"
+                        .to_string(),
+                ));
+            }
+            if payment.recipients.is_empty() {
+                return Err(Error::MissingInput(format!(
+                    "Recipients of {}",
+                    payment.currency.symbol
+                )));
+            }
+        }
+        Ok(InternalOperation::PayMultiCoin(PayMultiCoin {
             sender,
-            recipients,
-            amounts,
-            currency,
+            payments,
         }))
     }
 
@@ -925,6 +968,106 @@ impl Operations {
         Ok(operations)
     }
 
+    /// Parse a PTB built by `pay_multi_coin_pt`: one leg per currency, each merging its source
+    /// coins (and any `coin::redeem_funds` withdrawal), splitting the amounts off and transferring
+    /// the pieces to the recipients. The k-th `SplitCoins` starts the leg of `currencies[k]`.
+    ///
+    /// The generic payment parser cannot tell the currencies of a PTB apart, so unlike it this
+    /// rejects any other shape instead of falling back to a generic op: the labels only describe a
+    /// transaction of exactly this form.
+    fn parse_pay_multi_coin(
+        tx: TransactionKind,
+        sender: SuiAddress,
+        status: Option<OperationStatus>,
+        currencies: &[Currency],
+    ) -> Result<Vec<Operation>, Error> {
+        let malformed = |what: &str| {
+            Error::DataError(format!(
+                "envelope inconsistency: PayMultiCoin aux data over a transaction that {what}"
+            ))
+        };
+        let Some(TransactionKindData::ProgrammableTransaction(pt)) = tx.data else {
+            return Err(malformed("is not programmable"));
+        };
+        let pure_input = |arg: &Argument| match arg.kind() {
+            ArgumentKind::Input => pt
+                .inputs
+                .get(arg.input() as usize)
+                .filter(|input| input.kind() == InputKind::Pure)
+                .map(|input| input.pure()),
+            _ => None,
+        };
+
+        // The (recipient, amount) pairs paid by each leg.
+        let mut legs: Vec<Vec<(SuiAddress, u64)>> = vec![];
+        // The leg and amounts of each SplitCoins, by command index.
+        let mut splits: HashMap<u32, (usize, Vec<u64>)> = HashMap::new();
+        // Indices of the `coin::redeem_funds` commands.
+        let mut withdrawals: HashSet<u32> = HashSet::new();
+        for (idx, command) in pt.commands.iter().enumerate() {
+            let idx = idx as u32;
+            match &command.command {
+                Some(Command::MergeCoins(_)) => {}
+                Some(Command::MoveCall(m)) if Self::is_coin_redeem_funds_call(m) => {
+                    withdrawals.insert(idx);
+                }
+                Some(Command::SplitCoins(split)) => {
+                    let amounts = split
+                        .amounts
+                        .iter()
+                        .map(|amount| pure_input(amount).and_then(|b| bcs::from_bytes(b).ok()))
+                        .collect::<Option<Vec<u64>>>()
+                        .ok_or_else(|| malformed("splits off a non-constant amount"))?;
+                    splits.insert(idx, (legs.len(), amounts));
+                    legs.push(vec![]);
+                }
+                Some(Command::TransferObjects(transfer)) => {
+                    let recipient = pure_input(transfer.address())
+                        .and_then(|b| bcs::from_bytes::<SuiAddress>(b).ok())
+                        .ok_or_else(|| malformed("transfers to a non-constant address"))?;
+                    for obj in &transfer.objects {
+                        let split = splits.get(&obj.result());
+                        match (obj.kind(), obj.subresult, split) {
+                            (ArgumentKind::Result, Some(i), Some((leg, amounts))) => {
+                                let amount = amounts
+                                    .get(i as usize)
+                                    .ok_or_else(|| malformed("transfers an unknown split"))?;
+                                legs[*leg].push((recipient, *amount));
+                            }
+                            // The rest of an address-balance withdrawal, returned to the sender.
+                            (ArgumentKind::Result, None, None)
+                                if recipient == sender && withdrawals.contains(&obj.result()) => {}
+                            _ => return Err(malformed("transfers a coin it did not split off")),
+                        }
+                    }
+                }
+                _ => return Err(malformed("does more than pay coins")),
+            }
+        }
+        if legs.len() != currencies.len() {
+            return Err(malformed(&format!(
+                "pays {} currencies rather than {}",
+                legs.len(),
+                currencies.len()
+            )));
+        }
+
+        let mut operations = vec![];
+        for (payments, currency) in legs.into_iter().zip(currencies) {
+            let total_paid: i128 = payments.iter().map(|(_, amount)| *amount as i128).sum();
+            operations.extend(payments.into_iter().map(|(recipient, amount)| {
+                Operation::pay_coin(status, recipient, amount.into(), Some(currency.clone()))
+            }));
+            operations.push(Operation::pay_coin(
+                status,
+                sender,
+                -total_paid,
+                Some(currency.clone()),
+            ));
+        }
+        Ok(operations)
+    }
+
     /// Parse a PTB that represents `ConsolidateAllStakedSuiToFungible`.
     ///
     /// Accepts three valid shapes produced by `consolidate_to_fungible_pt`:
@@ -2223,7 +2366,8 @@ impl Operation {
 /// Steps:
 /// 1. Reconstruct operations from the transaction via the shared parser
 ///    (`from_transaction`), seeding the currency map from a `PayCoin` label so
-///    payments are labelled correctly.
+///    payments are labelled correctly. A multi-currency PayCoin is parsed by
+///    the dedicated `parse_pay_multi_coin`, which labels each leg.
 /// 2. Decorate FSS ops with the validator / redeem-mode / cap the PTB cannot
 ///    encode, asserting the parsed family matches the aux-data family.
 pub fn reconstruct_operations(
@@ -2240,11 +2384,18 @@ pub fn reconstruct_operations(
 
     // The PayCoin label is the only currency the PTB cannot encode; everything
     // else reconstructs as SUI. This path never produces `Unresolvable`.
-    let payment_currency = match aux {
-        AuxData::PayCoin { currency } => PaymentCurrency::NonSui(currency.clone()),
-        _ => PaymentCurrency::Sui,
+    let mut ops = match aux {
+        AuxData::PayMultiCoin { currencies } => {
+            Operations::parse_pay_multi_coin(tx_kind, sender, status, currencies)?
+        }
+        AuxData::PayCoin { currency } => Operations::from_transaction(
+            tx_kind,
+            sender,
+            status,
+            PaymentCurrency::NonSui(currency.clone()),
+        )?,
+        _ => Operations::from_transaction(tx_kind, sender, status, PaymentCurrency::Sui)?,
     };
-    let mut ops = Operations::from_transaction(tx_kind, sender, status, payment_currency)?;
 
     // Apply the labels the PTB cannot encode.
     apply_aux(&mut ops, aux)?;
@@ -2270,6 +2421,16 @@ fn apply_aux(ops: &mut [Operation], aux: &AuxData) -> Result<(), Error> {
                 ));
             }
         }
+        AuxData::PayMultiCoin { .. } => {
+            // `parse_pay_multi_coin` only emits PayCoin ops; assert it anyway
+            // so the invariant doesn't rest on the parser alone.
+            if ops.is_empty() || ops.iter().any(|op| op.type_ != OperationType::PayCoin) {
+                return Err(Error::DataError(
+                    "envelope inconsistency: PayMultiCoin aux data over a non-PayCoin transaction"
+                        .to_string(),
+                ));
+            }
+        }
         AuxData::Consolidate { validator } => {
             let op = single_op(ops, OperationType::ConsolidateAllStakedSuiToFungible)?;
             match &mut op.metadata {
@@ -2444,6 +2605,7 @@ mod tests {
             redeem_token_amount: None,
             redeem_plan: None,
             bind_epoch: None,
+            payment_objects: vec![],
         };
         let parsed_data = ops.into_internal()?.try_into_data(metadata)?;
         assert_eq!(data, parsed_data);
@@ -2489,6 +2651,7 @@ mod tests {
             redeem_token_amount: None,
             redeem_plan: None,
             bind_epoch: None,
+            payment_objects: vec![],
         };
         let parsed_data = ops.clone().into_internal()?.try_into_data(metadata)?;
 
@@ -4422,7 +4585,7 @@ mod tests {
     // ---- reconstruct_operations tests -----------------------------------------
 
     use crate::types::CurrencyMetadata;
-    use crate::types::internal_operation::pay_coin_pt;
+    use crate::types::internal_operation::{CoinPaymentObjects, pay_coin_pt, pay_multi_coin_pt};
 
     fn sample_currency() -> Currency {
         Currency {
@@ -4488,6 +4651,161 @@ mod tests {
         );
     }
 
+    fn other_currency() -> Currency {
+        Currency {
+            symbol: "USDT".to_string(),
+            decimals: 6,
+            metadata: CurrencyMetadata {
+                coin_type: "0x6::usdt::USDT".to_string(),
+            },
+        }
+    }
+
+    /// PayCoin operations of several currencies become one payment per
+    /// currency, in the order the currencies first appear; SUI cannot be one of
+    /// them.
+    #[test]
+    fn test_pay_coin_ops_group_by_currency() {
+        let sender = SuiAddress::random_for_testing_only();
+        let (a, b) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+        let (usdc, usdt) = (sample_currency(), other_currency());
+        let ops = Operations::new(vec![
+            Operation::pay_coin(None, a, 10, Some(usdc.clone())),
+            Operation::pay_coin(None, b, 20, Some(usdt.clone())),
+            Operation::pay_coin(None, b, 30, Some(usdc.clone())),
+            Operation::pay_coin(None, sender, -40, Some(usdc.clone())),
+            Operation::pay_coin(None, sender, -20, Some(usdt.clone())),
+        ]);
+        assert_eq!(
+            ops.clone().into_internal().unwrap(),
+            InternalOperation::PayMultiCoin(PayMultiCoin {
+                sender,
+                payments: vec![
+                    CoinPayment {
+                        currency: usdc.clone(),
+                        recipients: vec![a, b],
+                        amounts: vec![10, 30],
+                    },
+                    CoinPayment {
+                        currency: usdt.clone(),
+                        recipients: vec![b],
+                        amounts: vec![20],
+                    },
+                ],
+            })
+        );
+
+        // A single currency is still a plain PayCoin.
+        let single = Operations::new(vec![
+            Operation::pay_coin(None, a, 10, Some(usdc.clone())),
+            Operation::pay_coin(None, sender, -10, Some(usdc.clone())),
+        ]);
+        assert!(matches!(
+            single.into_internal().unwrap(),
+            InternalOperation::PayCoin(_)
+        ));
+
+        let with_sui = Operations::new(vec![
+            Operation::pay_coin(None, a, 10, Some(usdc.clone())),
+            Operation::pay_coin(None, b, 10, Some(SUI.clone())),
+            Operation::pay_coin(None, sender, -10, Some(usdc)),
+            Operation::pay_coin(None, sender, -10, Some(SUI.clone())),
+        ]);
+        assert!(matches!(
+            with_sui.into_internal(),
+            Err(Error::InvalidInput(_))
+        ));
+
+        let two_senders = Operations::new(vec![
+            Operation::pay_coin(None, b, 20, Some(usdt.clone())),
+            Operation::pay_coin(None, sender, -10, Some(usdt.clone())),
+            Operation::pay_coin(None, a, -10, Some(usdt)),
+        ]);
+        assert!(matches!(
+            two_senders.into_internal(),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    /// A multi-currency PayCoin PTB parses back into the operations it was
+    /// built from, with each leg labelled by its own currency, whether the leg
+    /// is paid from coins or from an address-balance withdrawal.
+    #[test]
+    fn test_reconstruct_pay_multi_coin() {
+        let sender = SuiAddress::random_for_testing_only();
+        let (a, b) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+        let pay = PayMultiCoin {
+            sender,
+            payments: vec![
+                CoinPayment {
+                    currency: sample_currency(),
+                    recipients: vec![a, b],
+                    amounts: vec![10, 30],
+                },
+                CoinPayment {
+                    currency: other_currency(),
+                    recipients: vec![b],
+                    amounts: vec![20],
+                },
+            ],
+        };
+        let payment_objects = [
+            CoinPaymentObjects {
+                objects: vec![random_object_ref(), random_object_ref()],
+                ..Default::default()
+            },
+            CoinPaymentObjects {
+                address_balance_withdrawal: 20,
+                ..Default::default()
+            },
+        ];
+        let pt = pay_multi_coin_pt(sender, pay.payments.clone(), &payment_objects).unwrap();
+        let proto = proto_clean(&data_with_pt(sender, pt));
+        let aux = InternalOperation::PayMultiCoin(pay.clone()).aux();
+
+        let ops = reconstruct_operations(&proto, &aux, None).expect("reconstruct ok");
+        assert_eq!(
+            ops.into_internal().unwrap(),
+            InternalOperation::PayMultiCoin(pay)
+        );
+
+        // Labels for a different number of legs are rejected rather than
+        // spread over the wrong coins.
+        let AuxData::PayMultiCoin { mut currencies } = aux else {
+            unreachable!()
+        };
+        currencies.pop();
+        let err = reconstruct_operations(&proto, &AuxData::PayMultiCoin { currencies }, None)
+            .expect_err("leg count mismatch must be rejected");
+        assert!(format!("{err:?}").contains("envelope inconsistency"));
+    }
+
+    /// PayMultiCoin labels over a transaction that does anything but pay coins
+    /// are rejected.
+    #[test]
+    fn test_reconstruct_pay_multi_coin_rejects_other_shapes() {
+        let sender = SuiAddress::random_for_testing_only();
+        let aux = AuxData::PayMultiCoin {
+            currencies: vec![sample_currency(), other_currency()],
+        };
+        let pt = consolidate_to_fungible_pt(
+            sender,
+            vec![random_object_ref()],
+            vec![random_object_ref()],
+        )
+        .unwrap();
+        let proto = proto_clean(&data_with_pt(sender, pt));
+        let err = reconstruct_operations(&proto, &aux, None)
+            .expect_err("non-payment transaction must be rejected");
+        assert!(format!("{err:?}").contains("does more than pay coins"));
+    }
+
     /// Family-mismatch guard: PayCoin aux data applied to a non-payment
     /// (Consolidate) transaction is rejected by `apply_aux`'s family
     /// assertion, regardless of the currency map.
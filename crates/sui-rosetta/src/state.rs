@@ -18,6 +18,7 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use sui_types::digests::ChainIdentifier;
 
+use crate::mempool::PendingTransactions;
 use crate::operations::Operations;
 use crate::types::{
     Block, BlockHash, BlockIdentifier, BlockResponse, Transaction, TransactionIdentifier,
@@ -29,6 +30,7 @@ pub struct OnlineServerContext {
     pub client: GrpcClient,
     pub coin_metadata_cache: CoinMetadataCache,
     pub chain_id: ChainIdentifier,
+    pub pending_transactions: PendingTransactions,
     block_provider: Arc<dyn BlockProvider + Send + Sync>,
 }
 
@@ -44,6 +46,7 @@ impl OnlineServerContext {
             block_provider,
             coin_metadata_cache,
             chain_id,
+            pending_transactions: PendingTransactions::default(),
         }
     }
 
@@ -26,6 +26,7 @@ use sui_types::messages_checkpoint::CheckpointDigest;
 use crate::SUI;
 use crate::errors::{Error, ErrorType};
 use crate::operations::Operations;
+use internal_operation::CoinPaymentObjects;
 pub use internal_operation::InternalOperation;
 
 pub mod internal_operation;
@@ -771,6 +772,10 @@ pub struct ConstructionMetadata {
     /// replayed in a later epoch with a different exchange rate.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bind_epoch: Option<u64>,
+    /// The coins each leg of a multi-currency PayCoin is paid from, in the order of the legs.
+    /// Empty for other operations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub payment_objects: Vec<CoinPaymentObjects>,
 }
 
 impl IntoResponse for ConstructionMetadataResponse {
@@ -1007,6 +1012,36 @@ impl IntoResponse for BlockTransactionResponse {
     }
 }
 
+#[derive(Serialize)]
+pub struct MempoolResponse {
+    pub transaction_identifiers: Vec<TransactionIdentifier>,
+}
+
+impl IntoResponse for MempoolResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MempoolTransactionRequest {
+    pub network_identifier: NetworkIdentifier,
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+#[derive(Serialize)]
+pub struct MempoolTransactionResponse {
+    pub transaction: Transaction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+impl IntoResponse for MempoolTransactionResponse {
+    fn into_response(self) -> Response {
+        Json(self).into_response()
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct PrefundedAccount {
     pub privkey: String,
@@ -1127,6 +1162,7 @@ mod tests {
             redeem_token_amount: None,
             redeem_plan: None,
             bind_epoch: None,
+            payment_objects: vec![],
         };
         let prod_metadata_json = serde_json::to_string(&prod_metadata).unwrap();
 
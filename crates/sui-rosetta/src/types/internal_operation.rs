@@ -35,8 +35,10 @@ pub use consolidate_to_fungible::ConsolidateAllStakedSuiToFungible;
 pub(crate) use consolidate_to_fungible::consolidate_to_fungible_pt;
 pub use merge_and_redeem::MergeAndRedeemFungibleStakedSui;
 pub(crate) use merge_and_redeem::merge_and_redeem_fss_pt;
-pub use pay_coin::PayCoin;
+pub use pay_coin::{CoinPaymentObjects, PayCoin};
 pub(crate) use pay_coin::{pay_coin_gasless_pt, pay_coin_pt};
+pub(crate) use pay_multi_coin::pay_multi_coin_pt;
+pub use pay_multi_coin::{CoinPayment, PayMultiCoin};
 pub use pay_sui::PaySui;
 pub(crate) use pay_sui::{pay_sui_pt_ab_gas, pay_sui_pt_coin_gas};
 pub use stake::Stake;
@@ -47,6 +49,7 @@ pub(crate) use withdraw_stake::withdraw_stake_pt;
 mod consolidate_to_fungible;
 mod merge_and_redeem;
 mod pay_coin;
+mod pay_multi_coin;
 mod pay_sui;
 mod stake;
 mod withdraw_stake;
@@ -87,6 +90,9 @@ pub struct TransactionObjectData {
     /// Quote-time epoch to bind the transaction to (used by amount-sensitive
     /// `MergeAndRedeemFungibleStakedSui` modes). `None` for other operations.
     pub bind_epoch: Option<u64>,
+    /// For PayMultiCoin: the coins each leg is paid from, in the order of its payments.
+    /// Empty for other operations.
+    pub payment_objects: Vec<CoinPaymentObjects>,
 }
 
 impl TransactionObjectData {
@@ -117,6 +123,7 @@ pub trait TryConstructTransaction {
 pub enum InternalOperation {
     PaySui(PaySui),
     PayCoin(PayCoin),
+    PayMultiCoin(PayMultiCoin),
     Stake(Stake),
     WithdrawStake(WithdrawStake),
     ConsolidateAllStakedSuiToFungible(ConsolidateAllStakedSuiToFungible),
@@ -128,6 +135,7 @@ impl InternalOperation {
         match self {
             InternalOperation::PaySui(PaySui { sender, .. })
             | InternalOperation::PayCoin(PayCoin { sender, .. })
+            | InternalOperation::PayMultiCoin(PayMultiCoin { sender, .. })
             | InternalOperation::Stake(Stake { sender, .. })
             | InternalOperation::WithdrawStake(WithdrawStake { sender, .. })
             | InternalOperation::ConsolidateAllStakedSuiToFungible(
@@ -141,13 +149,16 @@ impl InternalOperation {
 
     /// Derive the out-of-band `AuxData` for this operation: the
     /// handful of Rosetta-level labels `/parse` cannot reconstruct from the PTB
-    /// (PayCoin currency, FSS validator, FSS redeem mode + cap). `/metadata`
+    /// (PayCoin currencies, FSS validator, FSS redeem mode + cap). `/metadata`
     /// calls this to populate the wrapper.
     pub fn aux(&self) -> AuxData {
         match self {
             InternalOperation::PayCoin(p) => AuxData::PayCoin {
                 currency: p.currency.clone(),
             },
+            InternalOperation::PayMultiCoin(p) => AuxData::PayMultiCoin {
+                currencies: p.payments.iter().map(|p| p.currency.clone()).collect(),
+            },
             InternalOperation::ConsolidateAllStakedSuiToFungible(c) => AuxData::Consolidate {
                 validator: c.validator,
             },
@@ -232,6 +243,9 @@ impl InternalOperation {
                     )?
                 }
             }
+            Self::PayMultiCoin(PayMultiCoin { sender, payments }) => {
+                pay_multi_coin_pt(sender, payments, &metadata.payment_objects)?
+            }
             InternalOperation::Stake(Stake {
                 sender,
                 validator,
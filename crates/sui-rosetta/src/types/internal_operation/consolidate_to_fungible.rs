@@ -9,8 +9,8 @@ use std::str::FromStr;
 use sui_rpc::client::Client;
 use sui_rpc::field::FieldMaskUtil;
 use sui_rpc::proto::sui::rpc::v2::{GetEpochRequest, ListOwnedObjectsRequest};
-use sui_sdk_types::Address;
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::governance::{FungibleStakedSui, StakedSui};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::rpc_proto_conversions::ObjectReferenceExt;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
@@ -27,27 +27,6 @@ pub struct ConsolidateAllStakedSuiToFungible {
     pub validator: SuiAddress,
 }
 
-/// BCS layout for `0x3::staking_pool::StakedSui`.
-/// Field order must match the Move struct definition exactly (BCS is positional).
-/// See: crates/sui-framework/packages/sui-system/sources/staking_pool.move
-#[derive(Deserialize)]
-struct StakedSuiBcs {
-    _id: Address,
-    pool_id: Address,
-    stake_activation_epoch: u64,
-    _principal: u64,
-}
-
-/// BCS layout for `0x3::staking_pool::FungibleStakedSui`.
-/// Field order must match the Move struct definition exactly (BCS is positional).
-/// See: crates/sui-framework/packages/sui-system/sources/staking_pool.move
-#[derive(Deserialize)]
-struct FungibleStakedSuiBcs {
-    _id: Address,
-    pool_id: Address,
-    _value: u64,
-}
-
 #[async_trait]
 impl TryConstructTransaction for ConsolidateAllStakedSuiToFungible {
     async fn try_fetch_needed_objects(
@@ -111,6 +90,7 @@ impl TryConstructTransaction for ConsolidateAllStakedSuiToFungible {
             redeem_token_amount: None,
             redeem_plan: None,
             bind_epoch: None,
+            payment_objects: vec![],
         })
     }
 }
@@ -144,11 +124,11 @@ async fn discover_staked_sui(
             .contents
             .as_ref()
             .ok_or_else(|| Error::DataError("StakedSui missing contents".to_string()))?;
-        let staked: StakedSuiBcs = contents
+        let staked: StakedSui = contents
             .deserialize()
             .map_err(|e| Error::DataError(format!("Failed to deserialize StakedSui: {}", e)))?;
 
-        if staked.pool_id.to_string() == pool_id && current_epoch >= staked.stake_activation_epoch {
+        if staked.pool_id().to_string() == pool_id && current_epoch >= staked.activation_epoch() {
             refs.push((
                 ObjectID::from_str(obj.object_id())
                     .map_err(|e| Error::DataError(format!("Invalid object_id: {}", e)))?,
@@ -190,11 +170,11 @@ async fn discover_fss(
             .contents
             .as_ref()
             .ok_or_else(|| Error::DataError("FungibleStakedSui missing contents".to_string()))?;
-        let fss: FungibleStakedSuiBcs = contents.deserialize().map_err(|e| {
+        let fss: FungibleStakedSui = contents.deserialize().map_err(|e| {
             Error::DataError(format!("Failed to deserialize FungibleStakedSui: {}", e))
         })?;
 
-        if fss.pool_id.to_string() == pool_id {
+        if fss.pool_id().to_string() == pool_id {
             refs.push((
                 ObjectID::from_str(obj.object_id())
                     .map_err(|e| Error::DataError(format!("Invalid object_id: {}", e)))?,
@@ -33,13 +33,13 @@ use sui_rpc::client::Client;
 use sui_rpc::field::FieldMaskUtil;
 use sui_rpc::proto::sui::rpc::v2::{ListDynamicFieldsRequest, ListOwnedObjectsRequest};
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::governance::{FungibleStakedSui, FungibleStakedSuiData};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::rpc_proto_conversions::ObjectReferenceExt;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{CallArg, Command, ObjectArg, ProgrammableTransaction};
 use sui_types::{Identifier, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
-use crate::account::FungibleStakedSuiBcs;
 use crate::errors::Error;
 use crate::types::{RedeemMode, RedeemPlan};
 
@@ -251,11 +251,9 @@ async fn list_owned_fss(client: &mut Client, sender: SuiAddress) -> Result<Vec<O
             .contents
             .as_ref()
             .ok_or_else(|| Error::DataError("FungibleStakedSui missing contents".to_string()))?;
-        let fss: FungibleStakedSuiBcs = contents.deserialize().map_err(|e| {
+        let fss: FungibleStakedSui = contents.deserialize().map_err(|e| {
             Error::DataError(format!("Failed to deserialize FungibleStakedSui: {e}"))
         })?;
-        let pool_id = ObjectID::from_str(&fss.pool_id.to_string())
-            .map_err(|e| Error::DataError(format!("Invalid pool_id: {e}")))?;
         let object_id = ObjectID::from_str(obj.object_id())
             .map_err(|e| Error::DataError(format!("Invalid object_id: {e}")))?;
         let object_ref: ObjectRef = (
@@ -267,8 +265,8 @@ async fn list_owned_fss(client: &mut Client, sender: SuiAddress) -> Result<Vec<O
         );
         out.push(OwnedFss {
             object_ref,
-            pool_id,
-            value: fss.value,
+            pool_id: fss.pool_id(),
+            value: fss.value(),
         });
     }
     Ok(out)
@@ -610,23 +608,6 @@ struct PoolFssRateSnapshot {
     pool_extra_fields_id: Option<ObjectID>,
 }
 
-/// Subset of `0x3::staking_pool::FungibleStakedSuiData` we BCS-deserialize.
-/// Field order MUST match the Move source at `staking_pool.move:98-104`:
-///   `id: UID, total_supply: u64, principal: Balance<SUI>`
-/// where `UID` is a 32-byte address and `Balance<SUI>` is a `{ value: u64 }`
-/// struct (stored positionally as a u64).
-#[derive(Deserialize, Debug)]
-struct FungibleStakedSuiDataBcs {
-    _id: sui_sdk_types::Address,
-    total_supply: u64,
-    principal: BalanceValueBcs,
-}
-
-#[derive(Deserialize, Debug)]
-struct BalanceValueBcs {
-    value: u64,
-}
-
 /// Fetch the `FungibleStakedSuiData` stored at
 /// `pool.extra_fields[FungibleStakedSuiDataKey {}]`. Lists the bag's dynamic
 /// fields and finds the one whose `value_type` matches
@@ -668,9 +649,9 @@ async fn fetch_fss_data(client: &mut Client, bag_id: ObjectID) -> Result<(u64, u
             .ok_or_else(|| {
                 Error::DataError("FungibleStakedSuiData entry has empty value".to_string())
             })?;
-        let data: FungibleStakedSuiDataBcs = bcs::from_bytes(bytes)
+        let data: FungibleStakedSuiData = bcs::from_bytes(bytes)
             .map_err(|e| Error::DataError(format!("FungibleStakedSuiData decode: {e}")))?;
-        return Ok((data.principal.value, data.total_supply));
+        return Ok((data.principal(), data.total_supply()));
     }
 
     Err(Error::DataError(format!(
@@ -782,6 +763,7 @@ impl TryConstructTransaction for MergeAndRedeemFungibleStakedSui {
             redeem_token_amount,
             redeem_plan: Some(plan),
             bind_epoch,
+            payment_objects: vec![],
         })
     }
 }
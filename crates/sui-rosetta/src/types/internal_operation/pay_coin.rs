@@ -47,61 +47,12 @@ impl TryConstructTransaction for PayCoin {
             currency,
         } = self;
 
-        let sdk_coin_type = SdkTypeTag::from_str(&currency.metadata.coin_type)
-            .map_err(|e| Error::DataError(format!("Invalid coin type: {}", e)))?;
-
         let total_payment: u64 = amounts.iter().sum();
-
-        // Query address balance for the payment coin type
-        let address_balance = {
-            let request = GetBalanceRequest::default()
-                .with_owner(sender.to_string())
-                .with_coin_type(currency.metadata.coin_type.clone());
-            client
-                .state_client()
-                .get_balance(request)
-                .await?
-                .into_inner()
-                .balance()
-                .address_balance()
-        };
-
-        // Select all coin objects (up to 1500). Storage refunds from merging dust outweigh
-        // smashing costs, so we merge as many as possible.
-        let all_coins = client
-            .select_up_to_n_largest_coins(&Address::from(sender), &sdk_coin_type, 1500, &[])
-            .await?;
-
-        let coins_total: u64 = all_coins.iter().map(|c| c.balance()).sum();
-
-        // Separate party objects (ConsensusAddressOwner) from regular objects.
-        let (party_objects, non_party_objects): (Vec<_>, Vec<_>) = all_coins
-            .iter()
-            .partition(|obj| obj.owner().kind() == OwnerKind::ConsensusAddress);
-
-        let coins: Vec<ObjectRef> = non_party_objects
-            .iter()
-            .map(|obj: &&Object| obj.object_reference().try_to_object_ref())
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let party_coins: Vec<(ObjectID, SequenceNumber)> = party_objects
-            .iter()
-            .map(|obj: &&Object| -> Result<_, Error> {
-                let id = ObjectID::from_str(obj.object_id())
-                    .map_err(|e| Error::DataError(format!("Invalid party object ID: {}", e)))?;
-                let start_version = SequenceNumber::from_u64(obj.owner().version());
-                Ok((id, start_version))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Compute deficit: how much we need from address balance beyond what coins provide
-        let deficit = total_payment.saturating_sub(coins_total);
-        if deficit > address_balance {
-            return Err(Error::InvalidInput(format!(
-                "Insufficient funds: need {} but only have {} in coins + {} in address balance",
-                total_payment, coins_total, address_balance
-            )));
-        }
+        let CoinPaymentObjects {
+            objects: coins,
+            party_objects: party_coins,
+            address_balance_withdrawal: deficit,
+        } = CoinPaymentObjects::fetch(client, sender, &currency, total_payment).await?;
 
         // Free-tier ("gasless") attempt. Build an AB-deposit PTB from the same funds the priced
         // path would smash — owned coins, party coins, and an `deficit` top-up from the sender's
@@ -141,6 +92,7 @@ impl TryConstructTransaction for PayCoin {
                     redeem_token_amount: None,
                     redeem_plan: None,
                     bind_epoch: None,
+                    payment_objects: vec![],
                 });
             }
             // Priced fallback (gasless ineligible) or an unexpected dry-run failure: fall through
@@ -176,6 +128,7 @@ impl TryConstructTransaction for PayCoin {
                 redeem_token_amount: None,
                 redeem_plan: None,
                 bind_epoch: None,
+                payment_objects: vec![],
             })
         } else {
             let total_sui_balance = gas_coin_objs.iter().map(|c| c.balance()).sum::<u64>() as i128;
@@ -195,11 +148,95 @@ impl TryConstructTransaction for PayCoin {
                 redeem_token_amount: None,
                 redeem_plan: None,
                 bind_epoch: None,
+                payment_objects: vec![],
             })
         }
     }
 }
 
+/// The coins a payment of one currency is paid from: the sender's coin objects of that currency,
+/// and the amount withdrawn from its address balance to cover what they lack.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoinPaymentObjects {
+    pub objects: Vec<ObjectRef>,
+    /// Party-owned (ConsensusAddress) coins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub party_objects: Vec<(ObjectID, SequenceNumber)>,
+    #[serde(default)]
+    pub address_balance_withdrawal: u64,
+}
+
+impl CoinPaymentObjects {
+    /// Select the coins `sender` pays `total_payment` of `currency` from, failing if its coins and
+    /// address balance together fall short.
+    pub(super) async fn fetch(
+        client: &mut Client,
+        sender: SuiAddress,
+        currency: &Currency,
+        total_payment: u64,
+    ) -> Result<Self, Error> {
+        let sdk_coin_type = SdkTypeTag::from_str(&currency.metadata.coin_type)
+            .map_err(|e| Error::DataError(format!("Invalid coin type: {}", e)))?;
+
+        // Query address balance for the payment coin type
+        let address_balance = {
+            let request = GetBalanceRequest::default()
+                .with_owner(sender.to_string())
+                .with_coin_type(currency.metadata.coin_type.clone());
+            client
+                .state_client()
+                .get_balance(request)
+                .await?
+                .into_inner()
+                .balance()
+                .address_balance()
+        };
+
+        // Select all coin objects (up to 1500). Storage refunds from merging dust outweigh
+        // smashing costs, so we merge as many as possible.
+        let all_coins = client
+            .select_up_to_n_largest_coins(&Address::from(sender), &sdk_coin_type, 1500, &[])
+            .await?;
+
+        let coins_total: u64 = all_coins.iter().map(|c| c.balance()).sum();
+
+        // Separate party objects (ConsensusAddressOwner) from regular objects.
+        let (party_objects, non_party_objects): (Vec<_>, Vec<_>) = all_coins
+            .iter()
+            .partition(|obj| obj.owner().kind() == OwnerKind::ConsensusAddress);
+
+        let objects: Vec<ObjectRef> = non_party_objects
+            .iter()
+            .map(|obj: &&Object| obj.object_reference().try_to_object_ref())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let party_objects: Vec<(ObjectID, SequenceNumber)> = party_objects
+            .iter()
+            .map(|obj: &&Object| -> Result<_, Error> {
+                let id = ObjectID::from_str(obj.object_id())
+                    .map_err(|e| Error::DataError(format!("Invalid party object ID: {}", e)))?;
+                let start_version = SequenceNumber::from_u64(obj.owner().version());
+                Ok((id, start_version))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Compute deficit: how much we need from address balance beyond what coins provide
+        let deficit = total_payment.saturating_sub(coins_total);
+        if deficit > address_balance {
+            return Err(Error::InvalidInput(format!(
+                "Insufficient funds: need {} {} but only have {} in coins + {} in address balance",
+                total_payment, currency.symbol, coins_total, address_balance
+            )));
+        }
+
+        Ok(Self {
+            objects,
+            party_objects,
+            address_balance_withdrawal: deficit,
+        })
+    }
+}
+
 /// Merge coin objects, optionally withdraw deficit from address balance,
 /// split payments and transfer coins to each recipient.
 /// Remainder stays as a coin owned by the sender.
@@ -213,13 +250,38 @@ pub fn pay_coin_pt(
     address_balance_withdrawal: u64,
     currency: &Currency,
 ) -> anyhow::Result<ProgrammableTransaction> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    add_coin_payment(
+        &mut builder,
+        sender,
+        recipients,
+        amounts,
+        coins,
+        party_coins,
+        address_balance_withdrawal,
+        currency,
+    )?;
+    Ok(builder.finish())
+}
+
+/// Add the commands of [`pay_coin_pt`] to `builder`, so that payments of several currencies can
+/// share a transaction.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn add_coin_payment(
+    builder: &mut ProgrammableTransactionBuilder,
+    sender: SuiAddress,
+    recipients: Vec<SuiAddress>,
+    amounts: Vec<u64>,
+    coins: &[ObjectRef],
+    party_coins: &[(ObjectID, SequenceNumber)],
+    address_balance_withdrawal: u64,
+    currency: &Currency,
+) -> anyhow::Result<()> {
     let sdk_type = SdkTypeTag::from_str(&currency.metadata.coin_type)?;
     let core_type = type_tag_sdk_to_core(sdk_type)?;
 
-    let mut builder = ProgrammableTransactionBuilder::new();
-
     let (source, source_is_command_result) = merge_payment_source(
-        &mut builder,
+        builder,
         coins,
         party_coins,
         address_balance_withdrawal,
@@ -246,7 +308,7 @@ pub fn pay_coin_pt(
         builder.transfer_arg(sender, source);
     }
 
-    Ok(builder.finish())
+    Ok(())
 }
 
 /// Build a gasless ("free tier") PTB paying `amounts` to `recipients`, crediting each recipient's
@@ -0,0 +1,120 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use sui_rpc::client::Client;
+use sui_rpc::proto::sui::rpc::v2::Object;
+use sui_types::base_types::SuiAddress;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::rpc_proto_conversions::ObjectReferenceExt;
+use sui_types::transaction::ProgrammableTransaction;
+
+use crate::{Currency, errors::Error};
+
+use super::pay_coin::{CoinPaymentObjects, add_coin_payment};
+use super::{TransactionObjectData, TryConstructTransaction, simulate_transaction};
+
+/// The payments of one currency in a [`PayMultiCoin`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CoinPayment {
+    pub currency: Currency,
+    pub recipients: Vec<SuiAddress>,
+    pub amounts: Vec<u64>,
+}
+
+/// A PayCoin whose operations move more than one (non-SUI) currency. Each currency is paid in its
+/// own leg of the PTB, built the same way as a single-currency [`super::PayCoin`], in the order of
+/// `payments`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PayMultiCoin {
+    pub sender: SuiAddress,
+    pub payments: Vec<CoinPayment>,
+}
+
+#[async_trait]
+impl TryConstructTransaction for PayMultiCoin {
+    async fn try_fetch_needed_objects(
+        self,
+        client: &mut Client,
+        gas_price: Option<u64>,
+        budget: Option<u64>,
+    ) -> Result<TransactionObjectData, Error> {
+        let Self { sender, payments } = self;
+
+        let mut payment_objects = Vec::with_capacity(payments.len());
+        for payment in &payments {
+            let total_payment: u64 = payment.amounts.iter().sum();
+            payment_objects.push(
+                CoinPaymentObjects::fetch(client, sender, &payment.currency, total_payment).await?,
+            );
+        }
+
+        // Unlike a single-currency PayCoin there is no gasless attempt: the free tier covers
+        // payments of one coin type.
+        let pt = pay_multi_coin_pt(sender, payments, &payment_objects)?;
+        let (budget, gas_coin_objs) =
+            simulate_transaction(client, pt, sender, vec![], gas_price, budget).await?;
+
+        let total_sui_balance = if gas_coin_objs.is_empty() {
+            budget as i128
+        } else {
+            gas_coin_objs.iter().map(|c| c.balance()).sum::<u64>() as i128
+        };
+        let gas_coins = gas_coin_objs
+            .iter()
+            .map(|obj: &Object| obj.object_reference().try_to_object_ref())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TransactionObjectData {
+            gas_coins,
+            objects: vec![],
+            party_objects: vec![],
+            total_sui_balance,
+            budget,
+            address_balance_withdrawal: 0,
+            fss_object_count: None,
+            redeem_token_amount: None,
+            redeem_plan: None,
+            bind_epoch: None,
+            payment_objects,
+        })
+    }
+}
+
+/// Pay each of `payments` from the coins in the matching entry of `payment_objects`, one leg per
+/// currency: merge the coins (and any address-balance withdrawal), split the amounts off and
+/// transfer them to the recipients.
+pub fn pay_multi_coin_pt(
+    sender: SuiAddress,
+    payments: Vec<CoinPayment>,
+    payment_objects: &[CoinPaymentObjects],
+) -> anyhow::Result<ProgrammableTransaction> {
+    anyhow::ensure!(
+        payments.len() == payment_objects.len(),
+        "metadata has payment objects for {} currencies, but the operations pay {}",
+        payment_objects.len(),
+        payments.len()
+    );
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for (payment, objects) in payments.into_iter().zip(payment_objects) {
+        let CoinPayment {
+            currency,
+            recipients,
+            amounts,
+        } = payment;
+        add_coin_payment(
+            &mut builder,
+            sender,
+            recipients,
+            amounts,
+            &objects.objects,
+            &objects.party_objects,
+            objects.address_balance_withdrawal,
+            &currency,
+        )?;
+    }
+    Ok(builder.finish())
+}
@@ -129,6 +129,7 @@ impl TryConstructTransaction for PaySui {
                     redeem_token_amount: None,
                     redeem_plan: None,
                     bind_epoch: None,
+                    payment_objects: vec![],
                 })
             }
             _ => {
@@ -172,6 +173,7 @@ impl TryConstructTransaction for PaySui {
                     redeem_token_amount: None,
                     redeem_plan: None,
                     bind_epoch: None,
+                    payment_objects: vec![],
                 })
             }
         }
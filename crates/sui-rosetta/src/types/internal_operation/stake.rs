@@ -153,6 +153,7 @@ impl TryConstructTransaction for Stake {
                     redeem_token_amount: None,
                     redeem_plan: None,
                     bind_epoch: None,
+                    payment_objects: vec![],
                 })
             }
             _ => {
@@ -194,6 +195,7 @@ impl TryConstructTransaction for Stake {
                     redeem_token_amount: None,
                     redeem_plan: None,
                     bind_epoch: None,
+                    payment_objects: vec![],
                 })
             }
         }
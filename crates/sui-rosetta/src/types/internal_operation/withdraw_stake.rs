@@ -127,6 +127,7 @@ impl TryConstructTransaction for WithdrawStake {
             redeem_token_amount: None,
             redeem_plan: None,
             bind_epoch: None,
+            payment_objects: vec![],
         })
     }
 }
@@ -57,6 +57,9 @@ pub enum AuxData {
     /// chain state). `currency` also disambiguates PayCoin from PaySui at parse
     /// time.
     PayCoin { currency: Currency },
+    /// PayMultiCoin: the currency of each leg, in the order the legs appear
+    /// in the PTB. As with `PayCoin`, the coin types are not in the PTB.
+    PayMultiCoin { currencies: Vec<Currency> },
     /// ConsolidateAllStakedSuiToFungible: `validator` is derived from a pool id
     /// via chain-state lookup and is not recoverable from the PTB. Object ids
     /// ARE recoverable from PTB inputs, so they are not carried here.
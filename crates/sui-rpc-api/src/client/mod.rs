@@ -7,6 +7,7 @@ use futures::stream::Stream;
 use futures::stream::TryStreamExt;
 use prost_types::FieldMask;
 use prost_types::value::Kind as ProtoValueKind;
+use std::sync::Arc;
 use std::time::Duration;
 use sui_rpc::field::FieldMaskUtil;
 use sui_rpc::proto::TryFromProtoError;
@@ -22,6 +23,7 @@ use sui_types::signature::GenericSignature;
 use sui_types::transaction::Transaction;
 use sui_types::transaction::TransactionData;
 use tap::Pipe;
+use tokio::sync::OnceCell;
 use tonic::Status;
 use tonic::metadata::MetadataMap;
 
@@ -36,8 +38,24 @@ pub struct Page<T> {
     pub next_page_token: Option<Bytes>,
 }
 
+/// The largest number of objects requested in one `BatchGetObjects` call, matching the limit
+/// enforced by the server.
+const MAX_BATCH_GET_OBJECTS: usize = 1000;
+
+/// The page size requested when listing owned objects. The server caps pages by size as well, so
+/// this only reduces the number of round trips for owners of many small objects.
+const LIST_OWNED_OBJECTS_PAGE_SIZE: u32 = 1000;
+
 #[derive(Clone)]
-pub struct Client(sui_rpc::Client);
+pub struct Client(sui_rpc::Client, Option<Arc<MetadataCache>>);
+
+/// Chain metadata fetched at most once by a client created with [`Client::with_metadata_cache`].
+#[derive(Default)]
+struct MetadataCache {
+    chain_identifier: OnceCell<ChainIdentifier>,
+    reference_gas_price: OnceCell<u64>,
+    protocol_config: OnceCell<proto::ProtocolConfig>,
+}
 
 impl Client {
     pub fn new<T>(uri: T) -> Result<Self>
@@ -45,11 +63,20 @@ impl Client {
         T: TryInto<http::Uri>,
         T::Error: Into<BoxError>,
     {
-        sui_rpc::Client::new(uri).map(Self)
+        sui_rpc::Client::new(uri).map(|client| Self(client, None))
     }
 
     pub fn with_headers(self, headers: HeadersInterceptor) -> Self {
-        Self(self.0.with_headers(headers))
+        Self(self.0.with_headers(headers), self.1)
+    }
+
+    /// Fetch the chain identifier, the reference gas price and the current protocol config at
+    /// most once, and share them with every clone of this client.
+    ///
+    /// The reference gas price and protocol config change at epoch boundaries, so this is only
+    /// meant for short-lived clients, like the one serving a single CLI command.
+    pub fn with_metadata_cache(self) -> Self {
+        Self(self.0, Some(Arc::default()))
     }
 
     pub fn inner_mut(&mut self) -> &mut sui_rpc::Client {
@@ -182,7 +209,17 @@ impl Client {
         Ok((object, json_content))
     }
 
+    /// Fetch the latest versions of the objects with `ids`, in order, splitting the request into
+    /// as many `BatchGetObjects` calls as the server's batch limit requires.
     pub async fn batch_get_objects(&self, ids: &[ObjectID]) -> Result<Vec<Object>> {
+        let mut objects = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(MAX_BATCH_GET_OBJECTS) {
+            objects.extend(self.batch_get_objects_chunk(chunk).await?);
+        }
+        Ok(objects)
+    }
+
+    async fn batch_get_objects_chunk(&self, ids: &[ObjectID]) -> Result<Vec<Object>> {
         let request = proto::BatchGetObjectsRequest::default()
             .with_requests(
                 ids.iter()
@@ -337,6 +374,17 @@ impl Client {
     }
 
     pub async fn get_chain_identifier(&self) -> Result<ChainIdentifier> {
+        match &self.1 {
+            Some(cache) => cache
+                .chain_identifier
+                .get_or_try_init(|| self.fetch_chain_identifier())
+                .await
+                .copied(),
+            None => self.fetch_chain_identifier().await,
+        }
+    }
+
+    async fn fetch_chain_identifier(&self) -> Result<ChainIdentifier> {
         let response = self
             .0
             .clone()
@@ -406,6 +454,7 @@ impl Client {
         let mut request = proto::ListOwnedObjectsRequest::default()
             .with_owner(owner.to_string())
             .with_read_mask(FieldMask::from_paths(["bcs"]));
+        request.set_page_size(LIST_OWNED_OBJECTS_PAGE_SIZE);
 
         if let Some(object_type) = object_type {
             request.set_object_type(object_type.to_canonical_string(true));
@@ -447,7 +496,57 @@ impl Client {
         Ok(response)
     }
 
+    /// Lists every version of the package with original ID `package_id`, in ascending version
+    /// order, as `(storage ID, version)` pairs.
+    pub async fn list_package_versions(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Vec<(ObjectID, SequenceNumber)>> {
+        let mut versions = vec![];
+        let mut page_token = None;
+        loop {
+            let mut request = proto::ListPackageVersionsRequest::default();
+            request.package_id = Some(package_id.to_string());
+            request.page_token = page_token;
+
+            let response = self
+                .0
+                .clone()
+                .package_client()
+                .list_package_versions(request)
+                .await?
+                .into_inner();
+
+            for version in &response.versions {
+                let id = version
+                    .package_id()
+                    .parse::<ObjectID>()
+                    .map_err(|e| TryFromProtoError::invalid("package_id", e))
+                    .map_err(|e| Status::from_error(e.into()))?;
+                versions.push((id, SequenceNumber::from_u64(version.version())));
+            }
+
+            match response.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(versions)
+    }
+
     pub async fn get_reference_gas_price(&self) -> Result<u64> {
+        match &self.1 {
+            Some(cache) => cache
+                .reference_gas_price
+                .get_or_try_init(|| self.fetch_reference_gas_price())
+                .await
+                .copied(),
+            None => self.fetch_reference_gas_price().await,
+        }
+    }
+
+    async fn fetch_reference_gas_price(&self) -> Result<u64> {
         let request = proto::GetEpochRequest::default()
             .with_read_mask(FieldMask::from_paths(["epoch", "reference_gas_price"]));
 
@@ -477,6 +576,51 @@ impl Client {
         Ok(response.epoch().epoch())
     }
 
+    /// The gas prices paid by the user transactions in a checkpoint, or in the latest checkpoint
+    /// if `sequence_number` is `None`, along with the checkpoint's sequence number.
+    pub async fn get_checkpoint_gas_prices(
+        &self,
+        sequence_number: Option<CheckpointSequenceNumber>,
+    ) -> Result<(CheckpointSequenceNumber, Vec<u64>)> {
+        let mut request =
+            proto::GetCheckpointRequest::default().with_read_mask(FieldMask::from_paths([
+                "sequence_number",
+                "transactions.transaction.sender",
+                "transactions.transaction.gas_payment.price",
+            ]));
+        request.checkpoint_id =
+            sequence_number.map(proto::get_checkpoint_request::CheckpointId::SequenceNumber);
+
+        let checkpoint = self
+            .0
+            .clone()
+            .ledger_client()
+            .get_checkpoint(request)
+            .await?
+            .into_inner()
+            .checkpoint
+            .ok_or_else(|| tonic::Status::not_found("no checkpoint returned"))?;
+
+        let prices = checkpoint
+            .transactions
+            .iter()
+            .filter_map(|tx| {
+                let tx = tx.transaction.as_ref()?;
+                // System transactions are sent by 0x0, and don't pay for gas.
+                let sender: SuiAddress = tx.sender.as_deref()?.parse().ok()?;
+                if sender == SuiAddress::ZERO {
+                    return None;
+                }
+                tx.gas_payment.as_ref()?.price
+            })
+            .collect();
+
+        let sequence_number = checkpoint
+            .sequence_number
+            .ok_or_else(|| tonic::Status::not_found("no checkpoint sequence number returned"))?;
+        Ok((sequence_number, prices))
+    }
+
     /// Wait for a transaction to be available in the ledger AND indexed (equivalent to WaitForLocalExecution)
     pub async fn wait_for_transaction(
         &self,
@@ -518,7 +662,19 @@ impl Client {
         Ok(())
     }
 
+    /// The protocol config of `epoch`, or of the current epoch if `epoch` is `None`.
     pub async fn get_protocol_config(&self, epoch: Option<u64>) -> Result<proto::ProtocolConfig> {
+        match (&self.1, epoch) {
+            (Some(cache), None) => cache
+                .protocol_config
+                .get_or_try_init(|| self.fetch_protocol_config(None))
+                .await
+                .cloned(),
+            _ => self.fetch_protocol_config(epoch).await,
+        }
+    }
+
+    async fn fetch_protocol_config(&self, epoch: Option<u64>) -> Result<proto::ProtocolConfig> {
         let mut request = proto::GetEpochRequest::default();
         if let Some(epoch) = epoch {
             request.set_epoch(epoch);
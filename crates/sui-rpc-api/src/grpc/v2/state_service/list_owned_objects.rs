@@ -16,6 +16,7 @@ use sui_rpc::proto::sui::rpc::v2::ListOwnedObjectsResponse;
 use sui_rpc::proto::sui::rpc::v2::Object;
 use sui_sdk_types::Address;
 use sui_types::full_checkpoint_content::ObjectSet;
+use sui_types::storage::ObjectTypeFilter;
 use sui_types::storage::OwnedObjectInfo;
 
 const MAX_PAGE_SIZE: usize = 1000;
@@ -46,11 +47,11 @@ pub fn list_owned_objects(
         })?;
     let object_type = request
         .object_type
-        .map(|s| s.parse())
+        .map(|s| s.parse::<ObjectTypeFilter>())
         .transpose()
         .map_err(|e| {
             FieldViolation::new("object_type")
-                .with_description(format!("invalid object_type: {e}"))
+                .with_description(format!("invalid object_type: {e:#}"))
                 .with_reason(ErrorReason::FieldInvalid)
         })?;
 
@@ -62,10 +63,15 @@ pub fn list_owned_objects(
         .page_token
         .map(|token| decode_page_token(&token))
         .transpose()?;
+    // Besides the query the token was issued for, check that its resume position matches the type
+    // filter, so that a crafted token can't seek the scan outside of the filtered prefix (which
+    // the store rejects as an internal error, rather than an invalid argument).
     if let Some(token) = &page_token
         && (token.owner != owner
             || token.object_type != object_type
-            || !cursor_within_type_filter(token, object_type.as_ref()))
+            || !object_type
+                .as_ref()
+                .is_none_or(|filter| filter.matches(&token.inner.object_type)))
     {
         return Err(FieldViolation::new("page_token")
             .with_description("invalid page_token")
@@ -177,36 +183,10 @@ fn owned_object_to_proto(info: OwnedObjectInfo, mask: &FieldMaskTree) -> Object
 #[derive(serde::Serialize, serde::Deserialize)]
 struct PageToken {
     owner: Address,
-    object_type: Option<move_core_types::language_storage::StructTag>,
+    object_type: Option<ObjectTypeFilter>,
     inner: OwnedObjectInfo,
 }
 
-/// Whether the token's inner cursor position lies within the query's
-/// type filter, mirroring the store's `TypeFilter::Type` matching
-/// contract: a filter with empty type parameters matches every
-/// instantiation of the named type, while one with parameters pins the
-/// exact instantiation. The token's recorded *query* is validated
-/// separately; this checks the resume *position* itself, so a crafted
-/// token cannot seek the scan outside the filtered prefix (the store
-/// also rejects such a cursor, but as an internal error rather than an
-/// invalid argument).
-fn cursor_within_type_filter(
-    token: &PageToken,
-    filter: Option<&move_core_types::language_storage::StructTag>,
-) -> bool {
-    let Some(filter) = filter else {
-        return true;
-    };
-    let cursor_type = &token.inner.object_type;
-    if filter.type_params.is_empty() {
-        cursor_type.address == filter.address
-            && cursor_type.module == filter.module
-            && cursor_type.name == filter.name
-    } else {
-        cursor_type == filter
-    }
-}
-
 fn should_load_object(mask: &FieldMaskTree) -> bool {
     [
         Object::BCS_FIELD,
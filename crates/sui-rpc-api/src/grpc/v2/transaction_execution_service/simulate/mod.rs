@@ -567,7 +567,7 @@ fn select_gas(
             .inner()
             .indexes()
             .ok_or_else(RpcError::not_found)?
-            .owned_objects_iter(owner, Some(GasCoin::type_()), None)?
+            .owned_objects_iter(owner, Some(GasCoin::type_().into()), None)?
             .filter_ok(|info| !input_objects.contains(&info.object_id))
             .filter_map_ok(|info| reader.inner().get_object(&info.object_id))
             // filter for objects which are not ConsensusAddress owned,
@@ -28,6 +28,7 @@ use sui_types::storage::LedgerBitmapBucketIter;
 use sui_types::storage::LedgerBitmapBucketIterator;
 use sui_types::storage::LedgerTxSeqDigest;
 use sui_types::storage::LedgerTxSeqDigestIterator;
+use sui_types::storage::ObjectTypeFilter;
 use sui_types::storage::OwnedObjectInfo;
 use sui_types::storage::RpcIndexes;
 
@@ -157,7 +158,7 @@ impl<R: Reader + Send + Sync> RpcIndexes for RpcStoreReader<R> {
     fn owned_objects_iter(
         &self,
         owner: SuiAddress,
-        object_type: Option<StructTag>,
+        object_type: Option<ObjectTypeFilter>,
         cursor: Option<OwnedObjectInfo>,
     ) -> StorageResult<Box<dyn Iterator<Item = Result<OwnedObjectInfo, TypedStoreError>> + '_>>
     {
@@ -166,18 +167,17 @@ impl<R: Reader + Send + Sync> RpcIndexes for RpcStoreReader<R> {
         let kind = OwnerKind::AddressOwner(owner);
         // When resuming, the page token carries the cursor object's full sort
         // position -- type, balance, and id -- so the scan seeks straight to it
-        // (inclusive) and stops at the end of the prefix. No post-filtering.
+        // (inclusive) and stops at the end of the prefix. Every type filter
+        // is a key prefix, so there is no post-filtering.
         let from = cursor.map(|c| Key {
             kind,
             type_: c.object_type,
             inverted_balance: c.balance.map(|b| !b),
             object_id: c.object_id,
         });
-        let iter = match (object_type, &from) {
-            (Some(struct_tag), Some(from)) => {
-                map.iter_prefix_from(&(kind, TypeFilter::Type(struct_tag)), from)
-            }
-            (Some(struct_tag), None) => map.iter_prefix(&(kind, TypeFilter::Type(struct_tag))),
+        let iter = match (object_type.map(TypeFilter::from), &from) {
+            (Some(filter), Some(from)) => map.iter_prefix_from(&(kind, filter), from),
+            (Some(filter), None) => map.iter_prefix(&(kind, filter)),
             (None, Some(from)) => map.iter_prefix_from(&kind, from),
             (None, None) => map.iter_prefix(&kind),
         }
@@ -14,6 +14,9 @@
 //!   named module.
 //! - [`TypeFilter::Type`] — match a specific Move type, with two
 //!   sub-cases handled by the encoding (see below).
+//! - [`TypeFilter::Instantiation`] — match instantiations of a
+//!   generic type whose leading type parameters are pinned, and
+//!   whose trailing ones are wildcards.
 //!
 //! Encoding is *one-way*: the bytes are designed to compare equal
 //! to the leading bytes of `bcs::to_bytes(&StructTag)` for any
@@ -32,6 +35,11 @@
 //! empty-params tag matches every instantiation of the named
 //! type. A tag with non-empty `type_params` matches only the
 //! exact pinned instantiation.
+//!
+//! [`TypeFilter::Instantiation`] pins the params-length byte to
+//! the type's arity, followed by the encoding of its leading
+//! params, so the wildcards must trail the pinned params for the
+//! filter to remain a prefix.
 
 use bytes::BufMut;
 use move_core_types::identifier::Identifier;
@@ -39,6 +47,7 @@ use move_core_types::language_storage::StructTag;
 use sui_consistent_store::Encode;
 use sui_consistent_store::error::EncodeError;
 use sui_types::base_types::SuiAddress;
+use sui_types::storage::ObjectTypeFilter;
 
 use crate::schema::primitives::write_uleb128;
 
@@ -60,6 +69,25 @@ pub enum TypeFilter {
     /// instantiation (e.g. `Coin<SUI>`, `Coin<USDC>`); otherwise
     /// it pins the full instantiation.
     Type(StructTag),
+    /// Match instantiations of a Move type with `arity` type
+    /// params, whose leading params are those of `type_`.
+    Instantiation { type_: StructTag, arity: usize },
+}
+
+impl From<ObjectTypeFilter> for TypeFilter {
+    fn from(filter: ObjectTypeFilter) -> Self {
+        match filter {
+            ObjectTypeFilter::Package(package) => TypeFilter::Package(package),
+            ObjectTypeFilter::Module(module) => TypeFilter::Module {
+                package: SuiAddress::from(*module.address()),
+                module: module.name().to_owned(),
+            },
+            ObjectTypeFilter::Type(tag) => TypeFilter::Type(tag),
+            ObjectTypeFilter::Instantiation { type_, arity } => {
+                TypeFilter::Instantiation { type_, arity }
+            }
+        }
+    }
 }
 
 impl Encode for TypeFilter {
@@ -86,6 +114,20 @@ impl Encode for TypeFilter {
                     .map_err(|e| EncodeError::with_source("bcs encode StructTag", e))?;
                 buf.put_slice(&bytes);
             }
+            // The params-length byte, followed by the pinned leading
+            // params: the bytes every matching `StructTag` starts
+            // with.
+            TypeFilter::Instantiation { type_, arity } => {
+                buf.put_slice(type_.address.as_ref());
+                write_identifier(&type_.module, buf);
+                write_identifier(&type_.name, buf);
+                write_uleb128(*arity as u32, buf);
+                for param in &type_.type_params {
+                    let bytes = bcs::to_bytes(param)
+                        .map_err(|e| EncodeError::with_source("bcs encode TypeTag", e))?;
+                    buf.put_slice(&bytes);
+                }
+            }
         }
         Ok(())
     }
@@ -206,4 +248,36 @@ mod tests {
         );
         assert_not_prefix_of(&filter, &other);
     }
+
+    #[test]
+    fn instantiation_matches_trailing_wildcards() {
+        let filter = TypeFilter::Instantiation {
+            type_: tag(2, "dynamic_field", "Field", vec![TypeTag::U64]),
+            arity: 2,
+        };
+        for t in [
+            tag(2, "dynamic_field", "Field", vec![TypeTag::U64, TypeTag::U8]),
+            tag(
+                2,
+                "dynamic_field",
+                "Field",
+                vec![
+                    TypeTag::U64,
+                    TypeTag::Struct(Box::new(tag(2, "sui", "SUI", vec![]))),
+                ],
+            ),
+        ] {
+            assert_is_prefix_of(&filter, &t);
+        }
+
+        // A different leading param, or arity, must not match.
+        assert_not_prefix_of(
+            &filter,
+            &tag(2, "dynamic_field", "Field", vec![TypeTag::U8, TypeTag::U64]),
+        );
+        assert_not_prefix_of(
+            &filter,
+            &tag(2, "dynamic_field", "Field", vec![TypeTag::U64]),
+        );
+    }
 }
@@ -24,29 +24,42 @@ use sui_json_rpc_types::ZkLoginVerifyResult;
 use sui_json_rpc_types::{
     Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake, DevInspectResults,
     DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
-    ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
-    SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
-    TransactionFilter,
+    ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEffectsDiff, SuiEvent,
+    SuiGetPastObjectRequest, SuiMoveNormalizedModule, SuiMoveStructLayouts, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiPastObjectResponse, SuiProtocolConfigValue,
+    SuiTransactionBlockDataAPI, SuiTransactionBlockEffects, SuiTransactionBlockKind,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
+use sui_types::SUI_RANDOMNESS_STATE_OBJECT_ID;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::dynamic_field::DynamicFieldName;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
 use sui_types::event::EventID;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
-use sui_types::transaction::{Transaction, TransactionData, TransactionKind};
+use sui_types::transaction::{
+    CallArg, ObjectArg, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+};
 use sui_types::transaction_driver_types::ExecuteTransactionRequestType;
 use tracing::debug;
 
 use crate::RpcClient;
+use crate::dry_run_cache::DryRunCache;
 use crate::error::{Error, SuiRpcResult};
+use crate::journal::SubmissionJournal;
 
 const WAIT_FOR_LOCAL_EXECUTION_MIN_INTERVAL: Duration = Duration::from_millis(100);
 const WAIT_FOR_LOCAL_EXECUTION_MAX_INTERVAL: Duration = Duration::from_secs(2);
 
+/// Default number of consensus rounds a transaction is deferred for before it is cancelled due to
+/// congestion, matching `max_deferral_rounds_for_congestion_control` in the protocol config.
+const DEFAULT_MAX_DEFERRAL_ROUNDS: u64 = 10;
+/// Rough duration of a consensus round, used to turn deferral rounds into a wall-clock backoff.
+const DEFAULT_CONSENSUS_ROUND_INTERVAL: Duration = Duration::from_millis(100);
+
 /// The main read API structure with functions for retrieving data about different objects and transactions
 #[derive(Debug)]
 pub struct ReadApi {
@@ -615,6 +628,98 @@ impl ReadApi {
         Ok(subscription.map(|item| Ok(item?)))
     }
 
+    /// Return the latest round of on-chain randomness, read from the randomness state update that
+    /// last wrote the `Random` object (`0x8`), or an error upon failure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default().build_localnet().await?;
+    ///     let round = sui.read_api().get_latest_randomness_round().await?;
+    ///     println!("round {} of epoch {}", round.randomness_round, round.epoch);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_latest_randomness_round(&self) -> SuiRpcResult<RandomnessRound> {
+        let random = self
+            .get_object_with_options(
+                SUI_RANDOMNESS_STATE_OBJECT_ID,
+                SuiObjectDataOptions::new().with_previous_transaction(),
+            )
+            .await?
+            .into_object()
+            .map_err(|e| Error::DataError(format!("Can't get the Random object: {:?}", e)))?;
+        let digest = random.previous_transaction.ok_or_else(|| {
+            Error::DataError("The Random object has no previous transaction".to_string())
+        })?;
+        let response = self
+            .get_transaction_with_options(
+                digest,
+                SuiTransactionBlockResponseOptions::new().with_input(),
+            )
+            .await?;
+        let Some(SuiTransactionBlockKind::RandomnessStateUpdate(update)) = response
+            .transaction
+            .as_ref()
+            .map(|tx| tx.data.transaction())
+        else {
+            return Err(Error::DataError(format!(
+                "Transaction {digest} that last wrote the Random object is not a randomness \
+                 state update"
+            )));
+        };
+        Ok(RandomnessRound {
+            epoch: update.epoch,
+            randomness_round: update.randomness_round,
+            random_bytes: update.random_bytes.clone(),
+            digest,
+            timestamp_ms: response.timestamp_ms,
+            checkpoint: response.checkpoint,
+        })
+    }
+
+    /// Return a stream of the rounds of on-chain randomness, polling for the latest round every
+    /// `poll_interval`. A round is yielded every time the latest round advances, starting with the
+    /// round that is current when the stream is first polled. Rounds are usually produced faster
+    /// than a useful poll interval, so intermediate rounds are skipped.
+    ///
+    /// Errors are yielded as they happen, and polling continues after them.
+    pub fn subscribe_randomness_rounds(
+        &self,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = SuiRpcResult<RandomnessRound>> + '_ {
+        stream::unfold((None, true), move |(last, first)| async move {
+            if !first {
+                tokio::time::sleep(poll_interval).await;
+            }
+            loop {
+                match self.get_latest_randomness_round().await {
+                    Ok(round) if Some(round.position()) <= last => {
+                        tokio::time::sleep(poll_interval).await
+                    }
+                    Ok(round) => {
+                        let position = Some(round.position());
+                        return Some((Ok(round), (position, false)));
+                    }
+                    Err(e) => return Some((Err(e), (last, false))),
+                }
+            }
+        })
+    }
+
+    /// Return the BCS layouts of the types declared by a package and of the struct types its entry
+    /// functions accept, for decoding its objects and events without the package's source.
+    pub async fn get_move_struct_layouts(
+        &self,
+        package: ObjectID,
+    ) -> SuiRpcResult<SuiMoveStructLayouts> {
+        Ok(self.api.http.get_move_struct_layouts(package).await?)
+    }
+
     /// Return a map consisting of the move package name and the normalized module, or an error upon failure.
     pub async fn get_normalized_move_modules_by_package(
         &self,
@@ -649,6 +754,51 @@ impl ReadApi {
             .await?)
     }
 
+    /// Dry run a transaction block, returning the result cached in `cache` if it is still valid,
+    /// and caching the result otherwise. Returns an error upon failure, which is not cached.
+    ///
+    /// Call [DryRunCache::observe_effects] with the effects of executed transactions, so that
+    /// results depending on objects they modified are not returned.
+    pub async fn dry_run_transaction_block_cached(
+        &self,
+        cache: &DryRunCache,
+        tx: TransactionData,
+    ) -> SuiRpcResult<DryRunTransactionBlockResponse> {
+        if let Some(response) = cache.get(&tx) {
+            return Ok(response);
+        }
+
+        let response = self.dry_run_transaction_block(tx.clone()).await?;
+        cache.insert(&tx, response.clone());
+        Ok(response)
+    }
+
+    /// Compare the result of an earlier dry run with the effects of the transaction as executed,
+    /// or an error upon failure.
+    ///
+    /// The returned [SuiEffectsDiff] lists differences in status, gas, object changes, balance
+    /// changes and events, along with the shared objects whose version changed between the dry
+    /// run and the execution, which is usually what caused the divergence.
+    pub async fn diff_dry_run_with_execution(
+        &self,
+        dry_run: &DryRunTransactionBlockResponse,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<SuiEffectsDiff> {
+        let executed = self
+            .get_transaction_with_options(
+                digest,
+                SuiTransactionBlockResponseOptions::new()
+                    .with_effects()
+                    .with_events()
+                    .with_object_changes()
+                    .with_balance_changes(),
+            )
+            .await?;
+        SuiEffectsDiff::new(dry_run, &executed).ok_or_else(|| {
+            Error::DataError(format!("No effects returned for transaction {digest}"))
+        })
+    }
+
     /// Return the inspection of the transaction block, or an error upon failure.
     ///
     /// Use this function to inspect the current state of the network by running a programmable
@@ -1223,6 +1373,272 @@ impl QuorumDriverApi {
         poll_response.confirmed_local_execution = Some(true);
         Ok(poll_response)
     }
+
+    /// Execute a transaction like [`Self::execute_transaction_block`], recording the intent to
+    /// submit it in `journal` first, and marking it done once its effects are known.
+    ///
+    /// If the process stops before the transaction is marked done, [`Self::reconcile_journal`]
+    /// finds out whether it executed when the journal is next opened, so that the transaction is
+    /// neither lost nor executed twice.
+    pub async fn execute_transaction_block_journaled(
+        &self,
+        journal: &SubmissionJournal,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let tx_digest = *tx.digest();
+        journal.record_intent(&tx)?;
+
+        let response = self
+            .execute_transaction_block(tx, options, request_type)
+            .await?;
+
+        journal.record_done(&tx_digest)?;
+        Ok(response)
+    }
+
+    /// Settle every transaction left pending in `journal` by a previous process.
+    ///
+    /// A transaction that is already checkpointed is reported with its current response. Any other
+    /// transaction is submitted again with the same signed bytes, which executes it if it never
+    /// reached the network and returns its existing effects otherwise. Transactions are marked
+    /// done once their response is known. A transaction that fails to be resubmitted, for example
+    /// because its owned inputs were used by another transaction in the meantime, stays pending
+    /// with its error, for the caller to retry later or to give up on with
+    /// [`SubmissionJournal::record_done`].
+    pub async fn reconcile_journal(
+        &self,
+        journal: &SubmissionJournal,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Vec<(TransactionDigest, SuiRpcResult<SuiTransactionBlockResponse>)> {
+        let mut reconciled = vec![];
+        for tx in journal.pending() {
+            let tx_digest = *tx.digest();
+
+            let response = match self
+                .api
+                .http
+                .get_transaction_block(tx_digest, Some(options.clone()))
+                .await
+            {
+                Ok(response) if response.checkpoint.is_some() => Ok(response),
+                _ => {
+                    debug!(?tx_digest, "Resubmitting journaled transaction");
+                    self.execute_transaction_block(tx, options.clone(), None)
+                        .await
+                }
+            };
+
+            let response = response.and_then(|response| {
+                journal.record_done(&tx_digest)?;
+                Ok(response)
+            });
+
+            reconciled.push((tx_digest, response));
+        }
+
+        reconciled
+    }
+
+    /// Execute a transaction, resubmitting it if it is cancelled due to consensus object
+    /// congestion.
+    ///
+    /// After each cancellation the client waits for the backoff suggested by `policy`, raises the
+    /// gas price, refreshes the owned object references the cancelled transaction bumped, and
+    /// signs the updated transaction data with `sign` before submitting it again. `progress` is
+    /// called before every submission and after every cancellation. The response of the last
+    /// attempt is returned, which is still cancelled if `policy.max_attempts` is exhausted.
+    pub async fn execute_transaction_block_with_congestion_retry(
+        &self,
+        mut tx_data: TransactionData,
+        sign: impl AsyncFn(&TransactionData) -> Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+        policy: &CongestionRetryPolicy,
+        mut progress: impl FnMut(ResubmitProgress<'_>),
+    ) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let show_raw_effects = options.show_raw_effects;
+        let options = options.with_raw_effects();
+
+        let mut attempt = 1;
+        loop {
+            progress(ResubmitProgress::Submitting {
+                attempt,
+                gas_price: tx_data.gas_price(),
+            });
+
+            let tx = sign(&tx_data).await;
+            let mut response = self
+                .execute_transaction_block(tx, options.clone(), request_type.clone())
+                .await?;
+
+            let effects: TransactionEffects = bcs::from_bytes(&response.raw_effects)?;
+            if !show_raw_effects {
+                response.raw_effects.clear();
+            }
+
+            let Some(congested_objects) = effects.status().get_congested_objects() else {
+                return Ok(response);
+            };
+
+            if attempt >= policy.max_attempts {
+                return Ok(response);
+            }
+
+            let backoff = policy.backoff(attempt);
+            let next_gas_price = policy.next_gas_price(tx_data.gas_price());
+            progress(ResubmitProgress::Cancelled {
+                attempt,
+                digest: response.digest,
+                congested_objects: &congested_objects.0,
+                backoff,
+                next_gas_price,
+            });
+
+            refresh_owned_object_refs(&mut tx_data, &effects);
+            tx_data.gas_data_mut().price = next_gas_price;
+
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// A round of on-chain randomness, as returned by [`ReadApi::get_latest_randomness_round`].
+///
+/// Transactions read randomness through the shared `Random` object, which must be passed to an
+/// entry function by immutable reference (`&Random`), e.g. with
+/// [`TransactionBuilder::random_move_call_tx_kind`]. Such transactions do not consume the round
+/// that is current when they are submitted: they are deferred until consensus has produced the
+/// next round of randomness after the commit that sequenced them, and execute with that round in
+/// a later checkpoint than transactions that do not use `Random`. A transaction that stays deferred
+/// for too long, because randomness generation stalls or because of congestion on the objects it
+/// uses, is cancelled, and is then resubmitted like any other cancelled transaction (see
+/// [`QuorumDriverApi::execute_transaction_block_with_congestion_retry`]).
+///
+/// The round is therefore useful to observe the progress of randomness generation, but the bytes
+/// of a round that has already been produced must never be used to derive outcomes that a user
+/// can still influence.
+///
+/// [`TransactionBuilder::random_move_call_tx_kind`]: sui_transaction_builder::TransactionBuilder::random_move_call_tx_kind
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RandomnessRound {
+    pub epoch: u64,
+    /// The round number, which restarts from zero every epoch.
+    pub randomness_round: u64,
+    pub random_bytes: Vec<u8>,
+    /// The randomness state update transaction that published the round.
+    pub digest: TransactionDigest,
+    /// The timestamp of the checkpoint that includes the round, if it has been checkpointed.
+    pub timestamp_ms: Option<u64>,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+impl RandomnessRound {
+    /// Orders rounds across epochs, as round numbers restart every epoch.
+    fn position(&self) -> (u64, u64) {
+        (self.epoch, self.randomness_round)
+    }
+}
+
+/// Controls how [`QuorumDriverApi::execute_transaction_block_with_congestion_retry`] resubmits
+/// transactions that were cancelled due to consensus object congestion.
+#[derive(Clone, Debug)]
+pub struct CongestionRetryPolicy {
+    /// Total number of submissions, including the first one.
+    pub max_attempts: u32,
+    /// Number of consensus rounds a transaction is deferred for before it is cancelled.
+    pub max_deferral_rounds: u64,
+    /// Approximate duration of a consensus round.
+    pub round_interval: Duration,
+    /// Percentage the gas price is raised by on every resubmission.
+    pub gas_price_increase_percent: u64,
+    /// Upper bound for the gas price of resubmitted transactions.
+    pub max_gas_price: Option<u64>,
+}
+
+impl Default for CongestionRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            max_deferral_rounds: DEFAULT_MAX_DEFERRAL_ROUNDS,
+            round_interval: DEFAULT_CONSENSUS_ROUND_INTERVAL,
+            gas_price_increase_percent: 50,
+            max_gas_price: None,
+        }
+    }
+}
+
+impl CongestionRetryPolicy {
+    /// Use the deferral limit from the network's protocol config, if it sets one.
+    pub fn with_protocol_config(mut self, config: &ProtocolConfigResponse) -> Self {
+        if let Some(Some(SuiProtocolConfigValue::U64(rounds))) = config
+            .attributes
+            .get("max_deferral_rounds_for_congestion_control")
+        {
+            self.max_deferral_rounds = *rounds;
+        }
+        self
+    }
+
+    /// How long to wait before resubmitting after the `attempt`-th cancellation. A cancelled
+    /// transaction has already been deferred for `max_deferral_rounds`, so the wait grows in
+    /// multiples of that window to let the congested objects drain.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.round_interval
+            .saturating_mul(self.max_deferral_rounds.try_into().unwrap_or(u32::MAX))
+            .saturating_mul(attempt)
+    }
+
+    /// The gas price to resubmit a transaction with, given the price of the cancelled attempt.
+    pub fn next_gas_price(&self, gas_price: u64) -> u64 {
+        let increase = (gas_price.saturating_mul(self.gas_price_increase_percent) / 100).max(1);
+        let next = gas_price.saturating_add(increase);
+        self.max_gas_price.map_or(next, |max| next.min(max))
+    }
+}
+
+/// Progress reported by [`QuorumDriverApi::execute_transaction_block_with_congestion_retry`].
+#[derive(Debug)]
+pub enum ResubmitProgress<'a> {
+    /// The transaction is about to be submitted.
+    Submitting { attempt: u32, gas_price: u64 },
+    /// The transaction was cancelled because of congestion on `congested_objects`, and will be
+    /// resubmitted with `next_gas_price` after `backoff`.
+    Cancelled {
+        attempt: u32,
+        digest: TransactionDigest,
+        congested_objects: &'a [ObjectID],
+        backoff: Duration,
+        next_gas_price: u64,
+    },
+}
+
+/// Replace references to owned objects (including gas) in `tx_data` with the versions written by
+/// the cancelled transaction's `effects`, so the transaction can be submitted again.
+pub fn refresh_owned_object_refs(tx_data: &mut TransactionData, effects: &TransactionEffects) {
+    let latest: BTreeMap<_, _> = effects
+        .mutated()
+        .into_iter()
+        .map(|(oref, _)| (oref.0, oref))
+        .collect();
+
+    for payment in &mut tx_data.gas_data_mut().payment {
+        if let Some(oref) = latest.get(&payment.0) {
+            *payment = *oref;
+        }
+    }
+
+    if let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind_mut() {
+        for input in &mut pt.inputs {
+            if let CallArg::Object(ObjectArg::ImmOrOwnedObject(oref)) = input
+                && let Some(latest) = latest.get(&oref.0)
+            {
+                *oref = *latest;
+            }
+        }
+    }
 }
 
 /// Governance API provides the staking functionality.
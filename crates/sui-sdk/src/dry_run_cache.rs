@@ -0,0 +1,296 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local cache of dry-run results, for UIs that repeatedly preview the same pending transaction.
+//!
+//! A dry run is fully determined by the transaction's data, which fixes the versions of its owned
+//! inputs, and by the versions of the shared objects it read. Results are cached by the digest of
+//! the (unsigned) transaction data, alongside the versions of the objects the dry run read or
+//! modified. An entry is invalidated as soon as any of those objects is observed at a later
+//! version: by a later dry run reading it, by the effects of an executed transaction modifying
+//! it, or by a version reported to the cache with [`DryRunCache::observe_version`].
+//!
+//! Changes made by other clients are only noticed when they are observed, so caches holding
+//! results that depend on busy shared objects should also be given a maximum age.
+//!
+//! See [`ReadApi::dry_run_transaction_block_cached`].
+//!
+//! [`ReadApi::dry_run_transaction_block_cached`]: crate::apis::ReadApi::dry_run_transaction_block_cached
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sui_json_rpc_types::{
+    DryRunTransactionBlockResponse, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::digests::TransactionDigest;
+use sui_types::transaction::TransactionData;
+
+pub struct DryRunCache {
+    max_age: Option<Duration>,
+    inner: Mutex<Store<DryRunTransactionBlockResponse>>,
+}
+
+/// Cached values, and the versions of the objects each of them depends on.
+struct Store<V> {
+    capacity: usize,
+    entries: HashMap<TransactionDigest, Entry<V>>,
+    /// Digests in insertion order, oldest first, with the insertion they refer to. Digests whose
+    /// entry was invalidated or replaced since are skipped.
+    order: VecDeque<(u64, TransactionDigest)>,
+    /// The entries that depend on each object.
+    dependents: HashMap<ObjectID, HashSet<TransactionDigest>>,
+    insertions: u64,
+}
+
+struct Entry<V> {
+    value: V,
+    inputs: Vec<(ObjectID, SequenceNumber)>,
+    inserted_at: Instant,
+    insertion: u64,
+}
+
+impl DryRunCache {
+    /// A cache holding the results of up to `capacity` transactions. The oldest results are
+    /// evicted first.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            max_age: None,
+            inner: Mutex::new(Store::new(capacity)),
+        }
+    }
+
+    /// Stop returning results older than `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// The cached dry run of `tx`, if none of the objects it depends on has been observed at a
+    /// later version since.
+    pub fn get(&self, tx: &TransactionData) -> Option<DryRunTransactionBlockResponse> {
+        self.inner
+            .lock()
+            .unwrap()
+            .get(&tx.digest(), self.max_age, Instant::now())
+    }
+
+    /// Cache the dry run of `tx`. The versions of the objects it read are observed first, which
+    /// invalidates the results of other transactions that read earlier versions.
+    pub fn insert(&self, tx: &TransactionData, response: DryRunTransactionBlockResponse) {
+        let inputs = inputs(&response.effects);
+        let mut inner = self.inner.lock().unwrap();
+        for (id, version) in &inputs {
+            inner.invalidate_before(id, *version);
+        }
+        inner.insert(tx.digest(), response, inputs, Instant::now());
+    }
+
+    /// Invalidate the results that depend on a version of object `id` older than `version`.
+    pub fn observe_version(&self, id: ObjectID, version: SequenceNumber) {
+        self.inner.lock().unwrap().invalidate_before(&id, version);
+    }
+
+    /// Invalidate the results that depend on objects modified by an executed transaction.
+    pub fn observe_effects(&self, effects: &SuiTransactionBlockEffects) {
+        let mut inner = self.inner.lock().unwrap();
+        for (id, version) in effects.modified_at_versions() {
+            inner.invalidate_before(&id, version.next());
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner = Store::new(inner.capacity);
+    }
+}
+
+/// The versions of the objects a dry run read or modified.
+fn inputs(effects: &SuiTransactionBlockEffects) -> Vec<(ObjectID, SequenceNumber)> {
+    let mut inputs = effects.modified_at_versions();
+    inputs.extend(
+        effects
+            .shared_objects()
+            .iter()
+            .map(|obj| (obj.object_id, obj.version)),
+    );
+    inputs
+}
+
+impl<V: Clone> Store<V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            dependents: HashMap::new(),
+            insertions: 0,
+        }
+    }
+
+    fn get(
+        &mut self,
+        digest: &TransactionDigest,
+        max_age: Option<Duration>,
+        now: Instant,
+    ) -> Option<V> {
+        let entry = self.entries.get(digest)?;
+        if max_age.is_some_and(|max_age| now.duration_since(entry.inserted_at) > max_age) {
+            self.remove(digest);
+            return None;
+        }
+        Some(entry.value.clone())
+    }
+
+    fn insert(
+        &mut self,
+        digest: TransactionDigest,
+        value: V,
+        inputs: Vec<(ObjectID, SequenceNumber)>,
+        now: Instant,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.remove(&digest);
+        while self.entries.len() >= self.capacity {
+            let Some((insertion, oldest)) = self.order.pop_front() else {
+                break;
+            };
+            if self
+                .entries
+                .get(&oldest)
+                .is_some_and(|e| e.insertion == insertion)
+            {
+                self.remove(&oldest);
+            }
+        }
+
+        // Drop the order of entries that are gone, so it stays proportional to the cache's size.
+        if self.order.len() >= 2 * self.capacity {
+            let entries = &self.entries;
+            self.order.retain(|(insertion, digest)| {
+                entries
+                    .get(digest)
+                    .is_some_and(|e| e.insertion == *insertion)
+            });
+        }
+
+        for (id, _) in &inputs {
+            self.dependents.entry(*id).or_default().insert(digest);
+        }
+
+        self.insertions += 1;
+        self.order.push_back((self.insertions, digest));
+        self.entries.insert(
+            digest,
+            Entry {
+                value,
+                inputs,
+                inserted_at: now,
+                insertion: self.insertions,
+            },
+        );
+    }
+
+    fn invalidate_before(&mut self, id: &ObjectID, version: SequenceNumber) {
+        let Some(dependents) = self.dependents.get(id) else {
+            return;
+        };
+
+        let stale: Vec<_> = dependents
+            .iter()
+            .filter(|digest| {
+                self.entries.get(digest).is_some_and(|e| {
+                    e.inputs
+                        .iter()
+                        .any(|(input, read)| input == id && *read < version)
+                })
+            })
+            .copied()
+            .collect();
+
+        for digest in stale {
+            self.remove(&digest);
+        }
+    }
+
+    fn remove(&mut self, digest: &TransactionDigest) {
+        let Some(entry) = self.entries.remove(digest) else {
+            return;
+        };
+
+        for (id, _) in entry.inputs {
+            if let Some(dependents) = self.dependents.get_mut(&id) {
+                dependents.remove(digest);
+                if dependents.is_empty() {
+                    self.dependents.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(v: u64) -> SequenceNumber {
+        SequenceNumber::from_u64(v)
+    }
+
+    #[test]
+    fn later_versions_invalidate_entries() {
+        let now = Instant::now();
+        let (pool, coin) = (ObjectID::random(), ObjectID::random());
+        let (a, b) = (TransactionDigest::random(), TransactionDigest::random());
+
+        let mut store = Store::new(10);
+        store.insert(a, 1, vec![(pool, version(5)), (coin, version(2))], now);
+        store.insert(b, 2, vec![(pool, version(6))], now);
+
+        // Observing the version an entry read does not invalidate it.
+        store.invalidate_before(&pool, version(5));
+        assert_eq!(store.get(&a, None, now), Some(1));
+
+        // Observing a later version invalidates only the entries that read an earlier one.
+        store.invalidate_before(&pool, version(6));
+        assert_eq!(store.get(&a, None, now), None);
+        assert_eq!(store.get(&b, None, now), Some(2));
+        assert!(!store.dependents.contains_key(&coin));
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted() {
+        let now = Instant::now();
+        let digests: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+
+        let mut store = Store::new(2);
+        for (i, digest) in digests.iter().enumerate() {
+            store.insert(*digest, i, vec![], now);
+        }
+
+        assert_eq!(store.get(&digests[0], None, now), None);
+        assert_eq!(store.get(&digests[1], None, now), Some(1));
+        assert_eq!(store.get(&digests[2], None, now), Some(2));
+    }
+
+    #[test]
+    fn old_entries_expire() {
+        let now = Instant::now();
+        let digest = TransactionDigest::random();
+
+        let mut store = Store::new(10);
+        store.insert(digest, 1, vec![], now);
+
+        let max_age = Some(Duration::from_secs(10));
+        let later = now + Duration::from_secs(5);
+        assert_eq!(store.get(&digest, max_age, later), Some(1));
+        let later = now + Duration::from_secs(11);
+        assert_eq!(store.get(&digest, max_age, later), None);
+        assert!(store.entries.is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Strategies for choosing the gas price of a transaction.
+//!
+//! Transactions offering less than the reference gas price are not signed by enough validators to
+//! execute, so every strategy returns at least the reference gas price. Offering more than it
+//! gets transactions that touch congested shared objects sequenced sooner.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, ensure};
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt, stream};
+use sui_rpc_api::Client;
+
+#[async_trait]
+pub trait GasPriceStrategy: Send + Sync {
+    /// The gas price to offer for a transaction submitted now.
+    async fn gas_price(&self, client: &Client) -> anyhow::Result<u64>;
+}
+
+/// Always offer the same gas price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedGasPrice(pub u64);
+
+/// Offer a multiple of the reference gas price.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReferenceGasPriceMultiplier(pub f64);
+
+/// Offer a percentile of the gas prices paid by the user transactions in recent checkpoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckpointPercentile {
+    /// Between 0 and 100.
+    pub percentile: u8,
+    /// How many of the latest checkpoints to sample, at most [`MAX_PERCENTILE_CHECKPOINTS`].
+    pub checkpoints: u64,
+}
+
+/// The strategies available from the CLI, parsed from:
+///
+/// - `rgp`: the reference gas price,
+/// - `rgp*<MULTIPLIER>`: a multiple of the reference gas price, e.g. `rgp*1.5`,
+/// - `p<PERCENTILE>[:<CHECKPOINTS>]`: a percentile of the gas prices paid in the latest
+///   checkpoints (10 by default, and at most 100), e.g. `p90` or `p75:50`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GasPriceStrategyConfig {
+    Multiplier(ReferenceGasPriceMultiplier),
+    Percentile(CheckpointPercentile),
+}
+
+const DEFAULT_PERCENTILE_CHECKPOINTS: u64 = 10;
+
+/// The most checkpoints a percentile strategy samples.
+pub const MAX_PERCENTILE_CHECKPOINTS: u64 = 100;
+
+/// How many checkpoints' gas prices are fetched at once.
+const CHECKPOINT_FETCH_CONCURRENCY: usize = 10;
+
+#[async_trait]
+impl GasPriceStrategy for FixedGasPrice {
+    async fn gas_price(&self, _client: &Client) -> anyhow::Result<u64> {
+        Ok(self.0)
+    }
+}
+
+#[async_trait]
+impl GasPriceStrategy for ReferenceGasPriceMultiplier {
+    async fn gas_price(&self, client: &Client) -> anyhow::Result<u64> {
+        let rgp = client.get_reference_gas_price().await?;
+        Ok(((rgp as f64 * self.0).ceil() as u64).max(rgp))
+    }
+}
+
+#[async_trait]
+impl GasPriceStrategy for CheckpointPercentile {
+    async fn gas_price(&self, client: &Client) -> anyhow::Result<u64> {
+        let rgp = client.get_reference_gas_price().await?;
+        let (latest, mut prices) = client.get_checkpoint_gas_prices(None).await?;
+
+        let checkpoints = self.checkpoints.min(MAX_PERCENTILE_CHECKPOINTS);
+        let earlier = latest.saturating_sub(checkpoints.saturating_sub(1))..latest;
+        let earlier: Vec<_> = stream::iter(earlier)
+            .map(|cp| client.get_checkpoint_gas_prices(Some(cp)))
+            .buffer_unordered(CHECKPOINT_FETCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+        prices.extend(earlier.into_iter().flat_map(|(_, prices)| prices));
+
+        Ok(percentile(prices, self.percentile).map_or(rgp, |price| price.max(rgp)))
+    }
+}
+
+#[async_trait]
+impl GasPriceStrategy for GasPriceStrategyConfig {
+    async fn gas_price(&self, client: &Client) -> anyhow::Result<u64> {
+        match self {
+            Self::Multiplier(strategy) => strategy.gas_price(client).await,
+            Self::Percentile(strategy) => strategy.gas_price(client).await,
+        }
+    }
+}
+
+impl Default for GasPriceStrategyConfig {
+    fn default() -> Self {
+        Self::Multiplier(ReferenceGasPriceMultiplier(1.0))
+    }
+}
+
+impl FromStr for GasPriceStrategyConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        if s == "rgp" {
+            return Ok(Self::default());
+        }
+
+        if let Some(multiplier) = s.strip_prefix("rgp*") {
+            let multiplier: f64 = multiplier.parse()?;
+            ensure!(
+                multiplier.is_finite() && multiplier >= 1.0,
+                "Gas price multiplier must be at least 1, got {multiplier}"
+            );
+            return Ok(Self::Multiplier(ReferenceGasPriceMultiplier(multiplier)));
+        }
+
+        if let Some(percentile) = s.strip_prefix('p') {
+            let (percentile, checkpoints) = match percentile.split_once(':') {
+                Some((percentile, checkpoints)) => (percentile, checkpoints.parse()?),
+                None => (percentile, DEFAULT_PERCENTILE_CHECKPOINTS),
+            };
+            let percentile: u8 = percentile.parse()?;
+            ensure!(
+                percentile <= 100,
+                "Percentile must be at most 100, got {percentile}"
+            );
+            ensure!(checkpoints > 0, "Must sample at least one checkpoint");
+            ensure!(
+                checkpoints <= MAX_PERCENTILE_CHECKPOINTS,
+                "Can sample at most {MAX_PERCENTILE_CHECKPOINTS} checkpoints, got {checkpoints}"
+            );
+            return Ok(Self::Percentile(CheckpointPercentile {
+                percentile,
+                checkpoints,
+            }));
+        }
+
+        bail!(
+            "Invalid gas price strategy '{s}', expected 'rgp', 'rgp*<MULTIPLIER>' or \
+             'p<PERCENTILE>[:<CHECKPOINTS>]'"
+        )
+    }
+}
+
+impl fmt::Display for GasPriceStrategyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Multiplier(ReferenceGasPriceMultiplier(m)) if *m == 1.0 => write!(f, "rgp"),
+            Self::Multiplier(ReferenceGasPriceMultiplier(m)) => write!(f, "rgp*{m}"),
+            Self::Percentile(CheckpointPercentile {
+                percentile,
+                checkpoints,
+            }) => write!(f, "p{percentile}:{checkpoints}"),
+        }
+    }
+}
+
+/// The nearest-rank `p`th percentile of `values`, or `None` if there are none.
+fn percentile(mut values: Vec<u64>, p: u8) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_unstable();
+    let rank = (values.len() * p as usize).div_ceil(100).max(1);
+    Some(values[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let prices = vec![1000, 750, 2000, 1000, 5000];
+        assert_eq!(percentile(vec![], 50), None);
+        assert_eq!(percentile(prices.clone(), 0), Some(750));
+        assert_eq!(percentile(prices.clone(), 50), Some(1000));
+        assert_eq!(percentile(prices.clone(), 80), Some(2000));
+        assert_eq!(percentile(prices, 100), Some(5000));
+    }
+
+    #[test]
+    fn test_parse_strategy() {
+        for s in ["rgp", "rgp*1.5", "p90:10", "p0:1", "p50:100"] {
+            let strategy: GasPriceStrategyConfig = s.parse().unwrap();
+            assert_eq!(strategy.to_string(), s);
+        }
+
+        assert_eq!(
+            "p75".parse::<GasPriceStrategyConfig>().unwrap(),
+            GasPriceStrategyConfig::Percentile(CheckpointPercentile {
+                percentile: 75,
+                checkpoints: DEFAULT_PERCENTILE_CHECKPOINTS,
+            })
+        );
+
+        for s in ["rgp*0.5", "p101", "p50:0", "p50:101", "fast"] {
+            assert!(s.parse::<GasPriceStrategyConfig>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local write-ahead journal of transaction submissions.
+//!
+//! Services that must not lose track of a payment across a crash record each signed transaction in
+//! a [`SubmissionJournal`] before submitting it, and mark it done once its effects are known. A
+//! transaction's digest is fixed by its signed bytes, so after a restart every transaction still
+//! pending in the journal can safely be checked on-chain and resubmitted as-is: it either executed
+//! already or it executes now, but never twice. See
+//! [`QuorumDriverApi::execute_transaction_block_journaled`] and
+//! [`QuorumDriverApi::reconcile_journal`].
+//!
+//! The journal is a file of JSON lines, each either an intent (the digest and signed transaction)
+//! or the completion of an earlier intent. It is compacted to the pending intents when opened.
+//!
+//! [`QuorumDriverApi::execute_transaction_block_journaled`]: crate::apis::QuorumDriverApi::execute_transaction_block_journaled
+//! [`QuorumDriverApi::reconcile_journal`]: crate::apis::QuorumDriverApi::reconcile_journal
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use fastcrypto::encoding::Base64;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sui_types::digests::TransactionDigest;
+use sui_types::transaction::Transaction;
+
+pub struct SubmissionJournal {
+    path: PathBuf,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    file: File,
+    pending: BTreeMap<TransactionDigest, Transaction>,
+}
+
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "camelCase")]
+enum Record {
+    Intent {
+        digest: TransactionDigest,
+        /// The BCS-serialized signed transaction.
+        #[serde_as(as = "Base64")]
+        transaction: Vec<u8>,
+    },
+    Done {
+        digest: TransactionDigest,
+    },
+}
+
+impl SubmissionJournal {
+    /// Open the journal at `path`, creating it if it does not exist. Intents that were recorded
+    /// but not marked done by a previous process are available from [`Self::pending`].
+    ///
+    /// A record that was only partially written when the previous process stopped is discarded.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let pending = match File::open(&path) {
+            Ok(file) => replay(BufReader::new(file))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+
+        // Compact the journal down to its pending intents, replacing it atomically.
+        let compacted = path.with_extension("compacting");
+        {
+            let mut file = File::create(&compacted)?;
+            for tx in pending.values() {
+                write_record(&mut file, &intent(tx)?)?;
+            }
+            file.sync_all()?;
+        }
+        fs::rename(&compacted, &path)?;
+
+        let file = OpenOptions::new().append(true).open(&path)?;
+        Ok(Self {
+            path,
+            inner: Mutex::new(Inner { file, pending }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The transactions recorded as intents that have not been marked done, ordered by digest.
+    pub fn pending(&self) -> Vec<Transaction> {
+        let inner = self.inner.lock().unwrap();
+        inner.pending.values().cloned().collect()
+    }
+
+    /// Durably record the intent to submit `tx`. Returns only once the record has been synced to
+    /// disk, so `tx` must not be submitted before this returns successfully.
+    pub fn record_intent(&self, tx: &Transaction) -> io::Result<()> {
+        let record = intent(tx)?;
+        let mut inner = self.inner.lock().unwrap();
+        write_record(&mut inner.file, &record)?;
+        inner.file.sync_data()?;
+        inner.pending.insert(*tx.digest(), tx.clone());
+        Ok(())
+    }
+
+    /// Mark the intent to submit the transaction with `digest` as done, either because its effects
+    /// are known or because the caller has given up on it.
+    ///
+    /// The record is not synced: if it is lost, the transaction is reported as executed again the
+    /// next time the journal is reconciled.
+    pub fn record_done(&self, digest: &TransactionDigest) -> io::Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.pending.remove(digest).is_none() {
+            return Ok(());
+        }
+        write_record(&mut inner.file, &Record::Done { digest: *digest })
+    }
+}
+
+fn intent(tx: &Transaction) -> io::Result<Record> {
+    Ok(Record::Intent {
+        digest: *tx.digest(),
+        transaction: bcs::to_bytes(tx).map_err(io::Error::other)?,
+    })
+}
+
+fn write_record(file: &mut File, record: &Record) -> io::Result<()> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    file.write_all(&line)
+}
+
+/// Replay the records in `reader`, returning the intents that were not marked done.
+fn replay(reader: impl BufRead) -> io::Result<BTreeMap<TransactionDigest, Transaction>> {
+    let mut pending = BTreeMap::new();
+    let mut lines = reader.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let line = line?;
+        let record = match serde_json::from_str(&line) {
+            Ok(record) => record,
+            // Only the last record can have been torn by a crash.
+            Err(_) if lines.peek().is_none() => break,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+
+        match record {
+            Record::Intent {
+                digest,
+                transaction,
+            } => {
+                let tx: Transaction = bcs::from_bytes(&transaction)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                if *tx.digest() != digest {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Journaled transaction does not match its digest {digest}"),
+                    ));
+                }
+                pending.insert(digest, tx);
+            }
+            Record::Done { digest } => {
+                pending.remove(&digest);
+            }
+        }
+    }
+
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::{SuiAddress, random_object_ref};
+    use sui_types::transaction::TransactionData;
+
+    use super::*;
+
+    fn transaction(amount: u64) -> Transaction {
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+            Some(amount),
+            random_object_ref(),
+            1_000_000,
+            1_000,
+        );
+        Transaction::from_data(data, vec![])
+    }
+
+    #[test]
+    fn pending_intents_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal");
+        let (a, b) = (transaction(1), transaction(2));
+
+        let journal = SubmissionJournal::open(&path).unwrap();
+        journal.record_intent(&a).unwrap();
+        journal.record_intent(&b).unwrap();
+        journal.record_done(a.digest()).unwrap();
+        drop(journal);
+
+        let journal = SubmissionJournal::open(&path).unwrap();
+        let pending = journal.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].digest(), b.digest());
+
+        // Reopening compacts the journal down to the pending intent.
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn torn_last_record_is_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal");
+        let a = transaction(1);
+
+        let journal = SubmissionJournal::open(&path).unwrap();
+        journal.record_intent(&a).unwrap();
+        drop(journal);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"record":"intent","dig"#).unwrap();
+        drop(file);
+
+        let journal = SubmissionJournal::open(&path).unwrap();
+        let pending = journal.pending();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].digest(), a.digest());
+    }
+}
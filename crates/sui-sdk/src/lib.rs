@@ -109,9 +109,13 @@ use crate::error::{Error, SuiRpcResult};
 
 pub mod apis;
 pub mod digests;
+pub mod dry_run_cache;
 pub mod error;
+pub mod gas_price;
+pub mod journal;
 pub mod json_rpc_error;
 pub mod sui_client_config;
+pub mod tx_summary;
 pub mod verify_personal_message_signature;
 pub mod wallet_context;
 
@@ -4,17 +4,21 @@
 use std::fmt::{Display, Formatter, Write};
 
 use anyhow::anyhow;
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 use crate::{SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_MAINNET_URL, SUI_TESTNET_URL};
 use sui_config::Config;
+use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_rpc_api::Client;
 use sui_rpc_api::client::HeadersInterceptor;
+use sui_transaction_builder::GasSwapRoute;
 use sui_types::{
     base_types::*,
     digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier},
+    parse_sui_type_tag,
 };
 
 #[serde_as]
@@ -30,6 +34,9 @@ pub struct SuiClientConfig {
     pub active_env: Option<String>,
     /// The address that is currently active in the keystore.
     pub active_address: Option<SuiAddress>,
+    /// Addresses that can be queried and set as active, but have no key to sign with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watch_only: Vec<WatchOnlyAddress>,
 }
 
 impl SuiClientConfig {
@@ -40,6 +47,7 @@ impl SuiClientConfig {
             envs: vec![],
             active_env: None,
             active_address: None,
+            watch_only: vec![],
         }
     }
 
@@ -70,6 +78,67 @@ impl SuiClientConfig {
         }
     }
 
+    /// Resolve a watch-only address from its alias or address.
+    pub fn get_watch_only(&self, key_identity: &KeyIdentity) -> Option<&WatchOnlyAddress> {
+        self.watch_only.iter().find(|w| match key_identity {
+            KeyIdentity::Address(address) => &w.address == address,
+            KeyIdentity::Alias(alias) => &w.alias == alias,
+        })
+    }
+
+    /// Add a watch-only address, failing if its alias or address is already known.
+    pub fn add_watch_only(&mut self, watch: WatchOnlyAddress) -> Result<(), anyhow::Error> {
+        if self.keystore.addresses().contains(&watch.address)
+            || self
+                .external_keys
+                .as_ref()
+                .is_some_and(|keys| keys.addresses().contains(&watch.address))
+        {
+            return Err(anyhow!(
+                "Address {} is already managed by the keystore",
+                watch.address
+            ));
+        }
+
+        if self.keystore.alias_exists(&watch.alias)
+            || self
+                .external_keys
+                .as_ref()
+                .is_some_and(|keys| keys.alias_exists(&watch.alias))
+            || self.watch_only.iter().any(|w| w.alias == watch.alias)
+        {
+            return Err(anyhow!("Alias {} already exists", watch.alias));
+        }
+
+        if self.watch_only.iter().any(|w| w.address == watch.address) {
+            return Err(anyhow!("Address {} is already watched", watch.address));
+        }
+
+        self.watch_only.push(watch);
+        Ok(())
+    }
+
+    /// Remove a watch-only address, clearing the active address if it was the one removed.
+    pub fn remove_watch_only(
+        &mut self,
+        key_identity: &KeyIdentity,
+    ) -> Result<WatchOnlyAddress, anyhow::Error> {
+        let pos = self
+            .watch_only
+            .iter()
+            .position(|w| match key_identity {
+                KeyIdentity::Address(address) => &w.address == address,
+                KeyIdentity::Alias(alias) => &w.alias == alias,
+            })
+            .ok_or_else(|| anyhow!("No watch-only address found for {key_identity}"))?;
+
+        let removed = self.watch_only.remove(pos);
+        if self.active_address == Some(removed.address) {
+            self.active_address = None;
+        }
+        Ok(removed)
+    }
+
     /// Update the cached chain ID for the specified environment.
     pub fn update_env_chain_id(
         &mut self,
@@ -96,9 +165,60 @@ pub struct SuiEnv {
     /// Cached chain identifier for this environment.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain_id: Option<String>,
+    /// The DeepBook pools that coins can be swapped through to pay for gas on this network, one
+    /// per coin type. Only these routes are used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gas_swap_routes: Vec<GasSwapRouteConfig>,
+}
+
+/// An address tracked by the client without a key, for read-only queries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchOnlyAddress {
+    pub alias: String,
+    pub address: SuiAddress,
+}
+
+/// A DeepBook (v3) pool that a coin can be swapped through for SUI, to pay back a gas sponsor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSwapRouteConfig {
+    /// The type of the coin paid with, e.g. `0x...::usdc::USDC`.
+    pub coin_type: String,
+    pub deepbook_package: ObjectID,
+    pub pool: ObjectID,
+    /// The type of the DEEP token, which DeepBook charges its fees in.
+    pub deep_type: String,
+    /// Whether SUI is the pool's base asset, rather than its quote asset.
+    pub sui_is_base: bool,
+}
+
+impl GasSwapRouteConfig {
+    pub fn route(&self) -> Result<GasSwapRoute, anyhow::Error> {
+        Ok(GasSwapRoute {
+            package: self.deepbook_package,
+            pool: self.pool,
+            coin_type: parse_sui_type_tag(&self.coin_type)?,
+            deep_type: parse_sui_type_tag(&self.deep_type)?,
+            sui_is_base: self.sui_is_base,
+        })
+    }
 }
 
 impl SuiEnv {
+    /// The route configured for paying for gas with `coin_type`, if any.
+    pub fn gas_swap_route(&self, coin_type: &TypeTag) -> Result<GasSwapRoute, anyhow::Error> {
+        for config in &self.gas_swap_routes {
+            let route = config.route()?;
+            if &route.coin_type == coin_type {
+                return Ok(route);
+            }
+        }
+        Err(anyhow!(
+            "No gas swap route is configured for {} in environment {}",
+            coin_type.to_canonical_display(true),
+            self.alias
+        ))
+    }
+
     pub fn create_grpc_client(&self) -> Result<Client, anyhow::Error> {
         let mut client = Client::new(&self.rpc)?;
 
@@ -124,6 +244,7 @@ impl SuiEnv {
             ws: None,
             basic_auth: None,
             chain_id: None,
+            gas_swap_routes: vec![],
         }
     }
     pub fn testnet() -> Self {
@@ -133,6 +254,7 @@ impl SuiEnv {
             ws: None,
             basic_auth: None,
             chain_id: Some(get_testnet_chain_identifier().to_string()),
+            gas_swap_routes: vec![],
         }
     }
 
@@ -143,6 +265,7 @@ impl SuiEnv {
             ws: None,
             basic_auth: None,
             chain_id: None,
+            gas_swap_routes: vec![],
         }
     }
 
@@ -153,6 +276,7 @@ impl SuiEnv {
             ws: None,
             basic_auth: None,
             chain_id: Some(get_mainnet_chain_identifier().to_string()),
+            gas_swap_routes: vec![],
         }
     }
 }
@@ -201,3 +325,30 @@ impl Display for SuiClientConfig {
         write!(f, "{}", writer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_swap_route() {
+        let mut env = SuiEnv::localnet();
+        env.gas_swap_routes.push(GasSwapRouteConfig {
+            coin_type: "0x42::usdc::USDC".to_string(),
+            deepbook_package: ObjectID::from_single_byte(0xd),
+            pool: ObjectID::from_single_byte(0xe),
+            deep_type: "0x43::deep::DEEP".to_string(),
+            sui_is_base: true,
+        });
+
+        let usdc = parse_sui_type_tag("0x42::usdc::USDC").unwrap();
+        let route = env.gas_swap_route(&usdc).unwrap();
+        assert_eq!(route.pool, ObjectID::from_single_byte(0xe));
+        assert_eq!(route.coin_type, usdc);
+        assert!(route.sui_is_base);
+
+        let other = parse_sui_type_tag("0x42::usdt::USDT").unwrap();
+        let err = env.gas_swap_route(&other).unwrap_err().to_string();
+        assert!(err.contains("No gas swap route"), "{err}");
+    }
+}
@@ -0,0 +1,535 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plain-language summaries of transactions, to review before signing them.
+//!
+//! [`summarize`] describes each command of a programmable transaction and its gas payment, e.g.
+//! "Transfers 2 objects (2 x 0x2::coin::Coin<0x2::sui::SUI>) to 0x...". Anything it cannot
+//! describe precisely, like a transfer to a computed recipient or a non-programmable transaction,
+//! is reported as unrecognized, so that callers can refuse to sign transactions they cannot
+//! explain.
+//!
+//! The effects of a Move call depend on the function, so every object a call may take by value or
+//! by mutable reference is listed with it. In strict mode, calls to functions other than a few
+//! well-known framework functions are reported as unrecognized too.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use move_core_types::language_storage::StructTag;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::gas_coin::MIST_PER_SUI;
+use sui_types::transaction::{
+    Argument, CallArg, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+    SharedObjectMutability, TransactionData, TransactionDataAPI, TransactionKind,
+};
+use sui_types::{MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
+
+/// Framework functions whose effects are described by the objects passed to them, and so are
+/// recognized in strict mode: `(package, module, functions)`.
+const KNOWN_CALLS: &[(ObjectID, &str, &[&str])] = &[
+    (MOVE_STDLIB_PACKAGE_ID, "option", &["some", "none"]),
+    (
+        SUI_FRAMEWORK_PACKAGE_ID,
+        "coin",
+        &[
+            "split",
+            "join",
+            "zero",
+            "value",
+            "destroy_zero",
+            "divide_into_n",
+        ],
+    ),
+    (
+        SUI_FRAMEWORK_PACKAGE_ID,
+        "pay",
+        &[
+            "split",
+            "split_vec",
+            "split_and_transfer",
+            "join",
+            "join_vec",
+            "divide_and_keep",
+        ],
+    ),
+    (SUI_FRAMEWORK_PACKAGE_ID, "tx_context", &["sender"]),
+    (
+        SUI_SYSTEM_PACKAGE_ID,
+        "sui_system",
+        &[
+            "request_add_stake",
+            "request_add_stake_mul_coin",
+            "request_withdraw_stake",
+        ],
+    ),
+];
+
+pub struct TransactionSummary {
+    /// One line per described action of the transaction, followed by its gas payment.
+    pub lines: Vec<String>,
+    /// The parts of the transaction that could not be described.
+    pub unrecognized: Vec<String>,
+}
+
+impl TransactionSummary {
+    /// Whether every part of the transaction was described.
+    pub fn is_recognized(&self) -> bool {
+        self.unrecognized.is_empty()
+    }
+}
+
+impl fmt::Display for TransactionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "  - {line}")?;
+        }
+        if !self.unrecognized.is_empty() {
+            writeln!(f, "Not recognized:")?;
+            for line in &self.unrecognized {
+                writeln!(f, "  - {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The IDs of the objects `tx` takes as inputs, whose types [`summarize`] needs.
+pub fn input_object_ids(tx: &TransactionData) -> Vec<ObjectID> {
+    let TransactionKind::ProgrammableTransaction(pt) = tx.kind() else {
+        return vec![];
+    };
+    pt.inputs
+        .iter()
+        .filter_map(|input| match input {
+            CallArg::Object(arg) => Some(arg.id()),
+            CallArg::Pure(_) | CallArg::FundsWithdrawal(_) => None,
+        })
+        .collect()
+}
+
+/// Summarize `tx`, describing input objects with their types from `object_types`. Objects whose
+/// type is missing are described by ID, and reported as unrecognized. With `strict`, calls to
+/// functions that are not in [`KNOWN_CALLS`] are reported as unrecognized as well.
+pub fn summarize(
+    tx: &TransactionData,
+    object_types: &BTreeMap<ObjectID, StructTag>,
+    strict: bool,
+) -> TransactionSummary {
+    let mut summary = TransactionSummary {
+        lines: vec![],
+        unrecognized: vec![],
+    };
+
+    match tx.kind() {
+        TransactionKind::ProgrammableTransaction(pt) => {
+            Summarizer {
+                pt,
+                object_types,
+                strict,
+                summary: &mut summary,
+            }
+            .summarize();
+        }
+        kind => summary
+            .unrecognized
+            .push(format!("{} transaction", kind.name())),
+    }
+
+    let gas = tx.gas_data();
+    let payer = if gas.owner == tx.sender() {
+        "the sender".to_string()
+    } else {
+        format!("sponsor {}", gas.owner)
+    };
+    summary.lines.push(format!(
+        "Spends at most {} of gas at {} MIST per unit, paid by {payer}",
+        format_mist(gas.budget),
+        gas.price,
+    ));
+    summary
+}
+
+struct Summarizer<'a> {
+    pt: &'a ProgrammableTransaction,
+    object_types: &'a BTreeMap<ObjectID, StructTag>,
+    strict: bool,
+    summary: &'a mut TransactionSummary,
+}
+
+impl<'a> Summarizer<'a> {
+    fn summarize(&mut self) {
+        for input in &self.pt.inputs {
+            if let CallArg::FundsWithdrawal(withdrawal) = input {
+                self.summary
+                    .unrecognized
+                    .push(format!("Withdrawal of funds: {withdrawal:?}"));
+            }
+        }
+
+        for (i, command) in self.pt.commands.iter().enumerate() {
+            let line = match command {
+                Command::MoveCall(call) => {
+                    let mut line =
+                        format!("Calls {}::{}::{}", call.package, call.module, call.function);
+                    if !call.type_arguments.is_empty() {
+                        let types: Vec<_> =
+                            call.type_arguments.iter().map(|t| t.to_string()).collect();
+                        line.push_str(&format!("<{}>", types.join(", ")));
+                    }
+                    let objects = self.mutable_objects(&call.arguments);
+                    if !objects.is_empty() {
+                        line.push_str(&format!(
+                            ", which may take or modify {}",
+                            self.describe_objects(&objects)
+                        ));
+                    }
+                    if self.strict && !is_known_call(call) {
+                        self.unrecognize(
+                            i,
+                            &format!(
+                                "call to {}::{}::{}, whose effects are not known",
+                                call.package, call.module, call.function
+                            ),
+                        );
+                    }
+                    Some(line)
+                }
+                Command::TransferObjects(objects, recipient) => {
+                    let described = self.describe_objects(objects);
+                    self.recipient(recipient).map(|recipient| {
+                        format!(
+                            "Transfers {} ({described}) to {recipient}",
+                            plural(objects.len(), "object")
+                        )
+                    })
+                }
+                Command::SplitCoins(coin, amounts) => {
+                    let coin_desc = self.describe(coin);
+                    let amounts: Option<Vec<u64>> = amounts.iter().map(|a| self.pure(a)).collect();
+                    match amounts {
+                        Some(amounts) if matches!(coin, Argument::GasCoin) => {
+                            let amounts: Vec<_> = amounts.into_iter().map(format_mist).collect();
+                            Some(format!("Splits {} off {coin_desc}", amounts.join(", ")))
+                        }
+                        Some(amounts) => {
+                            let amounts: Vec<_> = amounts.iter().map(u64::to_string).collect();
+                            Some(format!(
+                                "Splits amounts {} off {coin_desc}",
+                                amounts.join(", ")
+                            ))
+                        }
+                        None => {
+                            self.unrecognize(i, "split of amounts that are not literal u64s");
+                            None
+                        }
+                    }
+                }
+                Command::MergeCoins(target, sources) => Some(format!(
+                    "Merges {} into {}",
+                    self.describe_objects(sources),
+                    self.describe(target)
+                )),
+                Command::MakeMoveVec(_, elements) => {
+                    let mut line =
+                        format!("Makes a vector of {}", plural(elements.len(), "element"));
+                    let objects = self.mutable_objects(elements);
+                    if !objects.is_empty() {
+                        line.push_str(&format!(", taking {}", self.describe_objects(&objects)));
+                    }
+                    Some(line)
+                }
+                Command::Publish(modules, _) => Some(format!(
+                    "Publishes a package of {}",
+                    plural(modules.len(), "module")
+                )),
+                Command::Upgrade(modules, _, package, _) => Some(format!(
+                    "Upgrades package {package} to {}",
+                    plural(modules.len(), "module")
+                )),
+            };
+
+            if let Some(line) = line {
+                self.summary.lines.push(line);
+            }
+        }
+    }
+
+    /// Describe `objects`, grouping input objects of the same type.
+    fn describe_objects(&mut self, objects: &[Argument]) -> String {
+        let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+        let mut others = vec![];
+        for object in objects {
+            match self.object_type(object) {
+                Some(ty) => *by_type.entry(ty.to_string()).or_default() += 1,
+                None => others.push(self.describe(object)),
+            }
+        }
+        by_type
+            .into_iter()
+            .map(|(ty, n)| format!("{n} x {ty}"))
+            .chain(others)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn describe(&mut self, arg: &Argument) -> String {
+        match arg {
+            Argument::GasCoin => "the gas coin".to_string(),
+            Argument::Result(i) | Argument::NestedResult(i, _) => {
+                format!("the result of command {i}")
+            }
+            Argument::Input(i) => match self.input(*i) {
+                Some(CallArg::Object(arg)) => {
+                    let id = arg.id();
+                    match self.object_types.get(&id) {
+                        Some(ty) => format!("{ty} {id}"),
+                        None => {
+                            self.summary
+                                .unrecognized
+                                .push(format!("Object {id} of unknown type"));
+                            format!("object {id}")
+                        }
+                    }
+                }
+                _ => {
+                    self.summary
+                        .unrecognized
+                        .push(format!("Input {i} used as an object"));
+                    format!("input {i}")
+                }
+            },
+        }
+    }
+
+    /// The arguments among `args` that are objects which may be passed by value or by mutable
+    /// reference: the gas coin, owned and received objects, and shared objects that are not
+    /// accessed read-only.
+    fn mutable_objects(&self, args: &[Argument]) -> Vec<Argument> {
+        args.iter()
+            .filter(|arg| match arg {
+                Argument::GasCoin => true,
+                Argument::Input(i) => matches!(
+                    self.input(*i),
+                    Some(CallArg::Object(
+                        ObjectArg::ImmOrOwnedObject(_)
+                            | ObjectArg::Receiving(_)
+                            | ObjectArg::SharedObject {
+                                mutability: SharedObjectMutability::Mutable
+                                    | SharedObjectMutability::NonExclusiveWrite,
+                                ..
+                            }
+                    ))
+                ),
+                Argument::Result(_) | Argument::NestedResult(_, _) => false,
+            })
+            .copied()
+            .collect()
+    }
+
+    fn object_type(&self, arg: &Argument) -> Option<&'a StructTag> {
+        match arg {
+            Argument::Input(i) => match self.input(*i)? {
+                CallArg::Object(arg) => self.object_types.get(&arg.id()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The address `recipient` is, if it is a literal address.
+    fn recipient(&mut self, recipient: &Argument) -> Option<SuiAddress> {
+        let address = self.pure(recipient);
+        if address.is_none() {
+            self.summary
+                .unrecognized
+                .push(format!("Transfer to a computed recipient ({recipient})"));
+        }
+        address
+    }
+
+    /// The value of `arg`, if it is a pure input holding a `T`.
+    fn pure<T: serde::de::DeserializeOwned>(&self, arg: &Argument) -> Option<T> {
+        match arg {
+            Argument::Input(i) => match self.input(*i)? {
+                CallArg::Pure(bytes) => bcs::from_bytes(bytes).ok(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn input(&self, i: u16) -> Option<&'a CallArg> {
+        self.pt.inputs.get(i as usize)
+    }
+
+    fn unrecognize(&mut self, command: usize, what: &str) {
+        self.summary
+            .unrecognized
+            .push(format!("Command {command}: {what}"));
+    }
+}
+
+fn is_known_call(call: &ProgrammableMoveCall) -> bool {
+    KNOWN_CALLS.iter().any(|(package, module, functions)| {
+        call.package == *package && call.module == *module && functions.contains(&&*call.function)
+    })
+}
+
+fn plural(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+/// `mist` in MIST, and in SUI if it is at least 0.001 SUI.
+fn format_mist(mist: u64) -> String {
+    if mist < MIST_PER_SUI / 1000 {
+        return format!("{mist} MIST");
+    }
+    let sui = format!("{}.{:09}", mist / MIST_PER_SUI, mist % MIST_PER_SUI);
+    let sui = sui.trim_end_matches('0').trim_end_matches('.');
+    format!("{mist} MIST ({sui} SUI)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use sui_types::base_types::{SequenceNumber, random_object_ref};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::{Identifier, TypeTag};
+
+    fn tx(pt: ProgrammableTransaction, sender: SuiAddress) -> TransactionData {
+        TransactionData::new(
+            TransactionKind::ProgrammableTransaction(pt),
+            sender,
+            random_object_ref(),
+            2_000_000_000,
+            1_000,
+        )
+    }
+
+    #[test]
+    fn describes_transfers_and_gas() {
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let coins = [random_object_ref(), random_object_ref()];
+        let coin_type = StructTag::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let objects = coins
+            .iter()
+            .map(|c| builder.obj(ObjectArg::ImmOrOwnedObject(*c)).unwrap())
+            .collect();
+        let recipient_arg = builder.pure(recipient).unwrap();
+        builder.command(Command::TransferObjects(objects, recipient_arg));
+        let tx = tx(builder.finish(), sender);
+
+        let types = coins.iter().map(|c| (c.0, coin_type.clone())).collect();
+        let summary = summarize(&tx, &types, true);
+        assert!(summary.is_recognized(), "{summary}");
+        assert_eq!(
+            summary.lines,
+            vec![
+                format!("Transfers 2 objects (2 x {coin_type}) to {recipient}"),
+                "Spends at most 2000000000 MIST (2 SUI) of gas at 1000 MIST per unit, paid by \
+                 the sender"
+                    .to_string(),
+            ]
+        );
+
+        // Without the types of the inputs, the transfer is not recognized.
+        assert!(!summarize(&tx, &BTreeMap::new(), false).is_recognized());
+    }
+
+    #[test]
+    fn computed_recipients_are_not_recognized() {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let recipient = builder.programmable_move_call(
+            ObjectID::from_single_byte(0x2),
+            Identifier::new("tx_context").unwrap(),
+            Identifier::new("sender").unwrap(),
+            Vec::<TypeTag>::new(),
+            vec![],
+        );
+        builder.command(Command::TransferObjects(vec![Argument::GasCoin], recipient));
+        let tx = tx(builder.finish(), SuiAddress::random_for_testing_only());
+
+        let summary = summarize(&tx, &BTreeMap::new(), true);
+        assert!(summary.lines[0].starts_with("Calls 0x"));
+        assert_eq!(summary.unrecognized.len(), 1, "{summary}");
+    }
+
+    #[test]
+    fn unknown_calls_are_not_recognized_in_strict_mode() {
+        let package = ObjectID::random();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.programmable_move_call(
+            package,
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            Vec::<TypeTag>::new(),
+            vec![],
+        );
+        let tx = tx(builder.finish(), SuiAddress::random_for_testing_only());
+
+        assert!(summarize(&tx, &BTreeMap::new(), false).is_recognized());
+        let summary = summarize(&tx, &BTreeMap::new(), true);
+        assert_eq!(
+            summary.unrecognized,
+            vec![format!(
+                "Command 0: call to {package}::m::f, whose effects are not known"
+            )]
+        );
+    }
+
+    #[test]
+    fn calls_list_objects_they_may_take_or_modify() {
+        let owned = random_object_ref();
+        let shared = |id, mutability| ObjectArg::SharedObject {
+            id,
+            initial_shared_version: SequenceNumber::from_u64(1),
+            mutability,
+        };
+        let (shared_mut, shared_imm) = (ObjectID::random(), ObjectID::random());
+        let ty = StructTag::from_str("0x42::pool::Pool").unwrap();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let arguments = vec![
+            builder.obj(ObjectArg::ImmOrOwnedObject(owned)).unwrap(),
+            builder
+                .obj(shared(shared_mut, SharedObjectMutability::Mutable))
+                .unwrap(),
+            builder
+                .obj(shared(shared_imm, SharedObjectMutability::Immutable))
+                .unwrap(),
+            builder.pure(7u64).unwrap(),
+            Argument::GasCoin,
+        ];
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("coin").unwrap(),
+            Identifier::new("join").unwrap(),
+            Vec::<TypeTag>::new(),
+            arguments,
+        );
+        let tx = tx(builder.finish(), SuiAddress::random_for_testing_only());
+
+        let types = [owned.0, shared_mut, shared_imm]
+            .into_iter()
+            .map(|id| (id, ty.clone()))
+            .collect();
+        let summary = summarize(&tx, &types, true);
+        assert!(summary.is_recognized(), "{summary}");
+        assert_eq!(
+            summary.lines[0],
+            format!(
+                "Calls {SUI_FRAMEWORK_PACKAGE_ID}::coin::join, which may take or modify \
+                 2 x {ty}, the gas coin"
+            )
+        );
+    }
+}
@@ -3,7 +3,6 @@
 
 use crate::sui_client_config::{SuiClientConfig, SuiEnv};
 use anyhow::{anyhow, ensure};
-use futures::future;
 use futures::stream::TryStreamExt;
 use shared_crypto::intent::Intent;
 use std::collections::BTreeSet;
@@ -29,6 +28,8 @@ pub struct WalletContext {
     grpc: OnceLock<Client>,
     max_concurrent_requests: Option<u64>,
     env_override: Option<String>,
+    read_only: bool,
+    cache_metadata: bool,
 }
 
 impl WalletContext {
@@ -47,6 +48,8 @@ impl WalletContext {
             grpc: OnceLock::new(),
             max_concurrent_requests: None,
             env_override: None,
+            read_only: false,
+            cache_metadata: false,
         };
         Ok(context)
     }
@@ -65,6 +68,8 @@ impl WalletContext {
             grpc: OnceLock::new(),
             max_concurrent_requests: None,
             env_override: None,
+            read_only: false,
+            cache_metadata: false,
         }
     }
 
@@ -83,6 +88,30 @@ impl WalletContext {
         self
     }
 
+    /// Refuse to sign anything with this context, for use on machines that should only query.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Fetch the chain identifier, reference gas price and protocol config at most once over the
+    /// lifetime of this context. Meant for contexts serving a single command, as the reference gas
+    /// price and protocol config can change at epoch boundaries.
+    pub fn with_metadata_cache(mut self, cache_metadata: bool) -> Self {
+        self.cache_metadata = cache_metadata;
+        self
+    }
+
+    /// Fail if this context is not allowed to sign.
+    pub fn ensure_can_sign(&self) -> Result<(), anyhow::Error> {
+        if self.read_only {
+            return Err(anyhow!(
+                "Refusing to sign: the client is running in read-only mode (--read-only)"
+            ));
+        }
+        Ok(())
+    }
+
     pub fn get_addresses(&self) -> Vec<SuiAddress> {
         let mut addresses = self.config.keystore.addresses();
 
@@ -123,6 +152,9 @@ impl WalletContext {
             {
                 return Ok(address);
             }
+            if let Some(watch) = self.config.get_watch_only(&key_identity) {
+                return Ok(watch.address);
+            }
 
             Err(anyhow!(
                 "No address found for the provided key identity: {key_identity}"
@@ -136,7 +168,10 @@ impl WalletContext {
         if let Some(client) = self.grpc.get() {
             Ok(client.clone())
         } else {
-            let client = self.get_active_env()?.create_grpc_client()?;
+            let mut client = self.get_active_env()?.create_grpc_client()?;
+            if self.cache_metadata {
+                client = client.with_metadata_cache();
+            }
             Ok(self.grpc.get_or_init(move || client).clone())
         }
     }
@@ -210,6 +245,16 @@ impl WalletContext {
 
     // TODO: Ger rid of mut
     pub fn active_address(&mut self) -> Result<SuiAddress, anyhow::Error> {
+        // A watch-only address can be active even when there are no keys.
+        if let Some(address) = self.config.active_address
+            && self
+                .config
+                .get_watch_only(&KeyIdentity::Address(address))
+                .is_some()
+        {
+            return Ok(address);
+        }
+
         if self.config.keystore.entries().is_empty() {
             return Err(anyhow!(
                 "No managed addresses. Create new address with `new-address` command."
@@ -236,6 +281,20 @@ impl WalletContext {
             .compute_object_reference())
     }
 
+    /// Get the latest object references of `object_ids`, in order, fetching them in batches
+    pub async fn get_object_refs(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> Result<Vec<ObjectRef>, anyhow::Error> {
+        Ok(self
+            .grpc_client()?
+            .batch_get_objects(object_ids)
+            .await?
+            .iter()
+            .map(Object::compute_object_reference)
+            .collect())
+    }
+
     /// Get the latest full object reference given a object id
     pub async fn get_full_object_ref(
         &self,
@@ -295,7 +354,13 @@ impl WalletContext {
         }
 
         // Find the owners of all supplied object IDs
-        let owners = future::try_join_all(gas.iter().map(|id| self.get_object_owner(id))).await?;
+        let owners = self
+            .grpc_client()?
+            .batch_get_objects(gas)
+            .await?
+            .iter()
+            .map(|o| o.owner().get_owner_address())
+            .collect::<Result<Vec<_>, _>>()?;
 
         // SAFETY `gas` is non-empty.
         let owner = owners.first().copied().unwrap();
@@ -478,6 +543,14 @@ impl WalletContext {
         data: &TransactionData,
         intent: Intent,
     ) -> Result<Signature, anyhow::Error> {
+        self.ensure_can_sign()?;
+        if let Some(watch) = self.config.get_watch_only(key_identity) {
+            return Err(anyhow!(
+                "Cannot sign for watch-only address {} ({})",
+                watch.address,
+                watch.alias
+            ));
+        }
         let keystore = self.get_keystore_by_identity(key_identity)?;
         let address = keystore.get_by_identity(key_identity).map_err(|_| {
             anyhow!("No address found for the provided key identity: {key_identity}")
@@ -486,16 +559,29 @@ impl WalletContext {
         Ok(sig)
     }
 
-    /// Sign a transaction with a key currently managed by the WalletContext
-    pub async fn sign_transaction(&self, data: &TransactionData) -> Transaction {
+    /// Sign a transaction with a key currently managed by the WalletContext. Fails if the context
+    /// is read-only, or does not hold the key of the sender.
+    pub async fn try_sign_transaction(
+        &self,
+        data: &TransactionData,
+    ) -> Result<Transaction, anyhow::Error> {
+        self.ensure_can_sign()?;
         let sig = self
             .config
             .keystore
             .sign_secure(&data.sender(), data, Intent::sui_transaction())
-            .await
-            .unwrap();
+            .await?;
         // TODO: To support sponsored transaction, we should also look at the gas owner.
-        Transaction::from_data(data.clone(), vec![sig])
+        Ok(Transaction::from_data(data.clone(), vec![sig]))
+    }
+
+    /// Sign a transaction with a key currently managed by the WalletContext.
+    /// Panics if the transaction cannot be signed, see `try_sign_transaction`. This is usually only
+    /// used by tests and tools that set up their own wallet.
+    pub async fn sign_transaction(&self, data: &TransactionData) -> Transaction {
+        self.try_sign_transaction(data)
+            .await
+            .expect("Failed to sign transaction")
     }
 
     /// Execute a transaction and wait for it to be locally executed on the fullnode.
@@ -4,13 +4,16 @@ use tempfile::TempDir;
 
 use fastcrypto::ed25519::Ed25519KeyPair;
 use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use std::time::Duration;
 use sui_config::{Config, SUI_CLIENT_CONFIG};
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
 use sui_macros::sim_test;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_sdk::rpc_types::{ProtocolConfigResponse, SuiProtocolConfigValue};
 use sui_sdk::{
-    sui_client_config::SuiClientConfig,
+    apis::CongestionRetryPolicy, sui_client_config::SuiClientConfig,
     verify_personal_message_signature::verify_personal_message_signature,
     wallet_context::WalletContext,
 };
@@ -319,3 +322,108 @@ async fn test_chain_id_persistence() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[test]
+fn test_congestion_retry_policy() {
+    let policy = CongestionRetryPolicy {
+        max_deferral_rounds: 10,
+        round_interval: Duration::from_millis(100),
+        gas_price_increase_percent: 50,
+        max_gas_price: Some(2_000),
+        ..Default::default()
+    };
+
+    assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    assert_eq!(policy.backoff(3), Duration::from_secs(3));
+
+    assert_eq!(policy.next_gas_price(1_000), 1_500);
+    assert_eq!(policy.next_gas_price(1), 2);
+    assert_eq!(policy.next_gas_price(1_500), 2_000);
+
+    let config: ProtocolConfigResponse =
+        ProtocolConfig::get_for_version(ProtocolVersion::MAX, Chain::Unknown).into();
+    let rounds = config
+        .attributes
+        .get("max_deferral_rounds_for_congestion_control")
+        .cloned()
+        .flatten();
+    let policy = CongestionRetryPolicy::default().with_protocol_config(&config);
+    if let Some(SuiProtocolConfigValue::U64(rounds)) = rounds {
+        assert_eq!(policy.max_deferral_rounds, rounds);
+    }
+}
+
+#[sim_test]
+async fn test_randomness_rounds() {
+    use futures::StreamExt;
+    use sui_types::SUI_FRAMEWORK_PACKAGE_ID;
+    use sui_types::transaction::{CallArg, ObjectArg, SharedObjectMutability};
+
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let client = test_cluster.sui_client();
+
+    let rounds: Vec<_> = tokio::time::timeout(
+        Duration::from_secs(60),
+        client
+            .read_api()
+            .subscribe_randomness_rounds(Duration::from_millis(100))
+            .take(2)
+            .collect(),
+    )
+    .await
+    .expect("randomness rounds should advance");
+    let rounds: Vec<_> = rounds.into_iter().map(Result::unwrap).collect();
+    assert!(
+        (rounds[0].epoch, rounds[0].randomness_round)
+            < (rounds[1].epoch, rounds[1].randomness_round)
+    );
+    assert!(!rounds[1].random_bytes.is_empty());
+
+    let builder = client.transaction_builder();
+    let random = builder.random_object_arg().await.unwrap();
+    assert!(matches!(
+        random,
+        ObjectArg::SharedObject { id, mutability, .. }
+            if id == sui_types::SUI_RANDOMNESS_STATE_OBJECT_ID
+                && mutability == SharedObjectMutability::Immutable
+    ));
+
+    let TransactionKind::ProgrammableTransaction(ptb) = builder
+        .random_move_call_tx_kind(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            "random",
+            "new_generator",
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap()
+    else {
+        panic!("expected a programmable transaction");
+    };
+    assert_eq!(ptb.inputs, vec![CallArg::Object(random)]);
+
+    let err = builder
+        .random_move_call_tx_kind(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            "random",
+            "update_randomness_state",
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("mutable reference"), "{err}");
+
+    let err = builder
+        .random_move_call_tx_kind(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            "clock",
+            "timestamp_ms",
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not take a &Random"), "{err}");
+}
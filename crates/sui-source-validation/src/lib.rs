@@ -14,9 +14,11 @@ use sui_move_build::CompiledPackage;
 use sui_rpc_api::Client;
 use sui_types::base_types::ObjectID;
 use sui_types::move_package::MovePackage;
+use sui_types::storage::ObjectStore;
 use toolchain::units_for_toolchain;
 
 pub mod error;
+pub mod service;
 mod toolchain;
 
 #[cfg(test)]
@@ -40,7 +42,14 @@ pub enum ValidationMode {
 }
 
 pub struct BytecodeSourceVerifier<'a> {
-    rpc_client: &'a Client,
+    packages: PackageSource<'a>,
+}
+
+/// Where the verifier reads on-chain packages from.
+#[derive(Clone, Copy)]
+enum PackageSource<'a> {
+    Rpc(&'a Client),
+    ObjectStore(&'a (dyn ObjectStore + Send + Sync)),
 }
 
 /// Map package addresses and module names to package names and bytecode.
@@ -329,7 +338,16 @@ impl ValidationMode {
 
 impl<'a> BytecodeSourceVerifier<'a> {
     pub fn new(rpc_client: &'a Client) -> Self {
-        BytecodeSourceVerifier { rpc_client }
+        BytecodeSourceVerifier {
+            packages: PackageSource::Rpc(rpc_client),
+        }
+    }
+
+    /// Verify against packages read directly from a node's object store, rather than over RPC.
+    pub fn new_with_object_store(object_store: &'a (dyn ObjectStore + Send + Sync)) -> Self {
+        BytecodeSourceVerifier {
+            packages: PackageSource::ObjectStore(object_store),
+        }
     }
 
     /// Verify that the `compiled_package` matches its on-chain representation.
@@ -404,12 +422,18 @@ impl<'a> BytecodeSourceVerifier<'a> {
         // fetch the Sui object at the address specified for the package in the local resolution table
         // if future packages with a large set of dependency packages prove too slow to verify,
         // batched object fetching should be added to the ReadApi & used here
-        let obj = self
-            .rpc_client
-            .clone()
-            .get_object(obj_id)
-            .await
-            .map_err(|e| Error::DependencyObjectReadFailure(e.message().to_owned()))?;
+        let obj = match self.packages {
+            PackageSource::Rpc(rpc_client) => rpc_client
+                .clone()
+                .get_object(obj_id)
+                .await
+                .map_err(|e| Error::DependencyObjectReadFailure(e.message().to_owned()))?,
+            PackageSource::ObjectStore(object_store) => {
+                object_store.get_object(&obj_id).ok_or_else(|| {
+                    Error::DependencyObjectReadFailure(format!("object {obj_id} not found"))
+                })?
+            }
+        };
 
         match &obj.data {
             sui_types::object::Data::Package(pkg) => Ok(pkg.to_owned()),
@@ -0,0 +1,588 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Node-side source verification: rebuilds submitted package sources with the node's own
+//! compiler, checks them against the bytecode in the node's object store, and records an
+//! attestation for each package that matches. The compiler version a package's `Move.lock`
+//! records is only checked against an allowlist, never used to pick a compiler. Attestations keep the package's source maps and error-code manifest so that Move
+//! aborts can be resolved to source lines and to the constant they abort with.
+//!
+//! Building a package is expensive, so only the callers configured as submitters can submit
+//! sources, each up to an hourly quota. Submissions are checked against the size limit before
+//! anything is written to disk, and rejected while the maximum number of builds is running.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::error_bitset::ErrorBitset;
+use move_command_line_common::files::FileHash;
+use move_package_alt::SourcePackageLayout;
+use move_package_alt::schema::Environment;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sui_config::node::SourceVerificationConfig;
+use sui_move_build::BuildConfig;
+use sui_move_build::error_codes::{ErrorCodeManifest, ErrorConstant};
+use sui_package_alt::SuiFlavor;
+use sui_types::base_types::ObjectID;
+use sui_types::digests::ChainIdentifier;
+use sui_types::storage::ObjectStore;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use crate::error::AggregateError;
+use crate::toolchain::ToolchainVersion;
+use crate::{BytecodeSourceVerifier, ValidationMode};
+
+const ATTESTATION_EXTENSION: &str = "bcs";
+
+/// The window over which submissions count towards a caller's quota.
+const QUOTA_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Record that a package's on-chain bytecode was reproduced from submitted source.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceAttestation {
+    pub package_id: ObjectID,
+    /// Compiler version recorded in the submitted lock file, if any.
+    pub toolchain_version: Option<String>,
+    pub verified_at_ms: u64,
+    /// Names of the package's modules.
+    pub modules: Vec<String>,
+}
+
+/// A position in a verified package's source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// Path of the source file, relative to the package root.
+    pub file: String,
+    /// 1-based line number.
+    pub line: u32,
+    /// 1-based column, in bytes.
+    pub column: u32,
+}
+
+/// A Move abort code resolved against a verified package's error-code manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedAbort {
+    pub module: String,
+    /// The constant the module aborted with, if it could be identified.
+    pub constant: Option<ErrorConstant>,
+    /// Source line of the abort, if the code is a clever error.
+    pub line: Option<u16>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubmissionError {
+    #[error("Source submissions are not enabled on this node")]
+    Disabled,
+
+    #[error("Source submissions require a submitter token, as `Authorization: Bearer <token>`")]
+    Unauthorized,
+
+    #[error("Submitter {submitter} has used its quota of {limit} submissions per hour")]
+    QuotaExceeded { submitter: String, limit: u32 },
+
+    #[error("Too many submissions are being built, retry later")]
+    Busy,
+
+    #[error("Invalid source submission: {0}")]
+    InvalidSubmission(String),
+
+    #[error("Package records compiler version {0:?}, which is not allowed by this node")]
+    DisallowedCompilerVersion(Option<String>),
+
+    #[error("Failed to build package: {0}")]
+    Build(String),
+
+    #[error("Source does not match on-chain package {package_id}: {error}")]
+    Mismatch {
+        package_id: ObjectID,
+        error: AggregateError,
+    },
+
+    #[error("Failed to store attestation: {0}")]
+    Storage(String),
+}
+
+/// What is persisted for each verified package.
+#[derive(Serialize, Deserialize)]
+struct StoredPackageSource {
+    attestation: SourceAttestation,
+    /// Submitted Move source files, keyed by path relative to the package root.
+    sources: BTreeMap<String, String>,
+    /// Source maps of the package's modules, keyed by module name.
+    source_maps: BTreeMap<String, SourceMap>,
+    error_codes: ErrorCodeManifest,
+}
+
+struct VerifiedPackage {
+    stored: StoredPackageSource,
+    /// Paths of `stored.sources`, keyed by the hash of their contents, which is how source maps
+    /// refer to files.
+    files_by_hash: HashMap<FileHash, String>,
+}
+
+impl From<StoredPackageSource> for VerifiedPackage {
+    fn from(stored: StoredPackageSource) -> Self {
+        let files_by_hash = stored
+            .sources
+            .iter()
+            .map(|(path, contents)| (FileHash::new(contents), path.clone()))
+            .collect();
+        Self {
+            stored,
+            files_by_hash,
+        }
+    }
+}
+
+pub struct SourceVerificationService {
+    config: SourceVerificationConfig,
+    environment: Environment,
+    object_store: Arc<dyn ObjectStore + Send + Sync>,
+    packages: RwLock<HashMap<ObjectID, Arc<VerifiedPackage>>>,
+    /// Names of the configured submitters, keyed by their token.
+    submitters: HashMap<String, String>,
+    /// When each submitter made the submissions counting towards its quota, oldest first.
+    submissions: Mutex<HashMap<String, VecDeque<Instant>>>,
+    builds: Semaphore,
+}
+
+impl SourceVerificationService {
+    /// Create the service, loading attestations previously stored under the configured path.
+    pub fn new(
+        config: SourceVerificationConfig,
+        chain_identifier: ChainIdentifier,
+        object_store: Arc<dyn ObjectStore + Send + Sync>,
+    ) -> anyhow::Result<Self> {
+        let environment = Environment::new(
+            chain_identifier.chain().as_str().to_string(),
+            chain_identifier.to_string(),
+        );
+        std::fs::create_dir_all(&config.attestation_path).with_context(|| {
+            format!(
+                "failed to create attestation directory {:?}",
+                config.attestation_path
+            )
+        })?;
+
+        let mut packages = HashMap::new();
+        for entry in std::fs::read_dir(&config.attestation_path)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_none_or(|ext| ext != ATTESTATION_EXTENSION)
+            {
+                continue;
+            }
+            let stored = std::fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| Ok(bcs::from_bytes::<StoredPackageSource>(&bytes)?));
+            match stored {
+                Ok(stored) => {
+                    packages.insert(
+                        stored.attestation.package_id,
+                        Arc::new(VerifiedPackage::from(stored)),
+                    );
+                }
+                Err(e) => warn!("skipping unreadable source attestation {path:?}: {e}"),
+            }
+        }
+        info!("loaded {} source attestations", packages.len());
+
+        let submitters = config
+            .submitters
+            .iter()
+            .map(|(name, token)| (token.clone(), name.clone()))
+            .collect();
+
+        Ok(Self {
+            builds: Semaphore::new(config.max_concurrent_builds().get()),
+            submitters,
+            submissions: Mutex::default(),
+            config,
+            environment,
+            object_store,
+            packages: RwLock::new(packages),
+        })
+    }
+
+    /// The attestation for `package_id`, if its source has been verified.
+    pub fn attestation(&self, package_id: &ObjectID) -> Option<SourceAttestation> {
+        self.packages
+            .read()
+            .get(package_id)
+            .map(|package| package.stored.attestation.clone())
+    }
+
+    /// Resolve a code offset in a function of a verified package to its source location. This
+    /// is the information carried by a Move abort's `MoveLocation`.
+    pub fn source_location(
+        &self,
+        package_id: &ObjectID,
+        module: &str,
+        function: u16,
+        instruction: u16,
+    ) -> Option<SourceLocation> {
+        let package = self.packages.read().get(package_id).cloned()?;
+        let loc = package
+            .stored
+            .source_maps
+            .get(module)?
+            .get_code_location(FunctionDefinitionIndex(function), instruction)
+            .ok()?;
+        let file = package.files_by_hash.get(&loc.file_hash())?;
+        let contents = package.stored.sources.get(file)?;
+        let (line, column) = line_and_column(contents, loc.start() as usize)?;
+        Some(SourceLocation {
+            file: file.clone(),
+            line,
+            column,
+        })
+    }
+
+    /// Resolve the code of a Move abort raised by `module` of a verified package to the constant
+    /// it aborted with. Clever error codes identify their constant directly; plain codes are
+    /// matched against the module's `u64` error constants. Returns `None` if the package's source
+    /// is not verified or it has no such module.
+    pub fn resolve_abort(
+        &self,
+        package_id: &ObjectID,
+        module: &str,
+        abort_code: u64,
+    ) -> Option<ResolvedAbort> {
+        let package = self.packages.read().get(package_id).cloned()?;
+        let error_codes = &package.stored.error_codes;
+        if !error_codes.modules.contains_key(module) {
+            return None;
+        }
+
+        let (constant, line) = match ErrorBitset::from_u64(abort_code) {
+            Some(bitset) => (
+                bitset
+                    .constant_index()
+                    .and_then(|index| error_codes.constant_by_index(module, index)),
+                bitset.line_number(),
+            ),
+            None => (error_codes.constant_by_code(module, abort_code), None),
+        };
+        Some(ResolvedAbort {
+            module: module.to_string(),
+            constant: constant.cloned(),
+            line,
+        })
+    }
+
+    /// Build the submitted package source and verify it against the on-chain package at
+    /// `package_id`, for the submitter authenticated by `token`. `files` maps paths relative to
+    /// the package root (including `Move.toml` and `Move.lock`) to their contents. On success the
+    /// attestation is persisted and returned.
+    pub async fn submit(
+        &self,
+        token: Option<&str>,
+        package_id: ObjectID,
+        files: BTreeMap<String, String>,
+    ) -> Result<SourceAttestation, SubmissionError> {
+        let submitter = self.authorize(token)?;
+        self.charge_quota(submitter, Instant::now())?;
+        self.check_submission(&files)?;
+
+        let _permit = self
+            .builds
+            .try_acquire()
+            .map_err(|_| SubmissionError::Busy)?;
+        info!("building source of package {package_id} submitted by {submitter}");
+
+        let dir = mysten_common::tempdir().map_err(|e| {
+            SubmissionError::Build(format!("failed to create build directory: {e}"))
+        })?;
+        let root = dir
+            .path()
+            .canonicalize()
+            .map_err(|e| SubmissionError::Build(e.to_string()))?;
+        for (path, contents) in &files {
+            let path = root.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| SubmissionError::Build(e.to_string()))?;
+            }
+            std::fs::write(&path, contents).map_err(|e| SubmissionError::Build(e.to_string()))?;
+        }
+
+        let toolchain_version = ToolchainVersion::read(&root, &self.environment)
+            .map_err(|e| SubmissionError::InvalidSubmission(format!("{e:#}")))?
+            .map(|toolchain| toolchain.compiler_version);
+        if !self.config.allowed_compiler_versions.is_empty()
+            && !toolchain_version
+                .as_ref()
+                .is_some_and(|version| self.config.allowed_compiler_versions.contains(version))
+        {
+            return Err(SubmissionError::DisallowedCompilerVersion(
+                toolchain_version,
+            ));
+        }
+
+        // Compilation is CPU-bound, so keep it off the async runtime.
+        let build_config = self.build_config(&root);
+        let build_root = root.clone();
+        let compiled = tokio::task::spawn_blocking(move || build_config.build(&build_root))
+            .await
+            .map_err(|e| SubmissionError::Build(e.to_string()))?
+            .map_err(|e| SubmissionError::Build(format!("{e:#}")))?;
+
+        let mode = match compiled.published_at {
+            Some(published_at) if published_at == package_id => ValidationMode::root(),
+            Some(published_at) => {
+                return Err(SubmissionError::InvalidSubmission(format!(
+                    "package manifest records it as published at {published_at}, not {package_id}"
+                )));
+            }
+            None => ValidationMode::root_at(package_id.into()),
+        };
+        BytecodeSourceVerifier::new_with_object_store(self.object_store.as_ref())
+            .verify(&compiled, mode, &self.environment)
+            .await
+            .map_err(|error| SubmissionError::Mismatch { package_id, error })?;
+
+        let source_maps: BTreeMap<_, _> = compiled
+            .package
+            .root_compiled_units
+            .iter()
+            .map(|unit| (unit.unit.name.to_string(), unit.unit.source_map.clone()))
+            .collect();
+        let stored = StoredPackageSource {
+            attestation: SourceAttestation {
+                package_id,
+                toolchain_version,
+                verified_at_ms: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system time is before the epoch")
+                    .as_millis() as u64,
+                modules: source_maps.keys().cloned().collect(),
+            },
+            sources: files
+                .into_iter()
+                .filter(|(path, _)| path.ends_with(".move"))
+                .collect(),
+            source_maps,
+            error_codes: compiled.error_code_manifest(),
+        };
+
+        self.persist(&stored)?;
+        let attestation = stored.attestation.clone();
+        info!(
+            "verified source for package {package_id} ({} modules)",
+            attestation.modules.len()
+        );
+        self.packages
+            .write()
+            .insert(package_id, Arc::new(VerifiedPackage::from(stored)));
+        Ok(attestation)
+    }
+
+    /// The name of the submitter authenticated by `token`.
+    fn authorize(&self, token: Option<&str>) -> Result<&str, SubmissionError> {
+        if self.submitters.is_empty() {
+            return Err(SubmissionError::Disabled);
+        }
+        token
+            .and_then(|token| self.submitters.get(token))
+            .map(String::as_str)
+            .ok_or(SubmissionError::Unauthorized)
+    }
+
+    /// Count a submission made at `now` towards `submitter`'s quota, failing if it is used up.
+    fn charge_quota(&self, submitter: &str, now: Instant) -> Result<(), SubmissionError> {
+        let limit = self.config.max_submissions_per_hour().get();
+        let mut submissions = self.submissions.lock();
+        let made = submissions.entry(submitter.to_owned()).or_default();
+        while made
+            .front()
+            .is_some_and(|made_at| now.saturating_duration_since(*made_at) >= QUOTA_WINDOW)
+        {
+            made.pop_front();
+        }
+
+        if made.len() >= limit as usize {
+            return Err(SubmissionError::QuotaExceeded {
+                submitter: submitter.to_owned(),
+                limit,
+            });
+        }
+        made.push_back(now);
+        Ok(())
+    }
+
+    fn check_submission(&self, files: &BTreeMap<String, String>) -> Result<(), SubmissionError> {
+        let total_bytes: usize = files
+            .iter()
+            .map(|(path, contents)| path.len() + contents.len())
+            .sum();
+        if total_bytes > self.config.max_source_bytes() {
+            return Err(SubmissionError::InvalidSubmission(format!(
+                "{total_bytes} bytes of source exceeds the limit of {} bytes",
+                self.config.max_source_bytes()
+            )));
+        }
+
+        for path in files.keys() {
+            let relative = Path::new(path)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+            if !relative {
+                return Err(SubmissionError::InvalidSubmission(format!(
+                    "file path {path:?} must be relative to the package root"
+                )));
+            }
+        }
+
+        for layout in [SourcePackageLayout::Manifest, SourcePackageLayout::Lock] {
+            let required = layout.location_str();
+            if !files.contains_key(required) {
+                return Err(SubmissionError::InvalidSubmission(format!(
+                    "missing {required}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn build_config(&self, root: &Path) -> BuildConfig {
+        BuildConfig {
+            config: move_package_alt_compilation::build_config::BuildConfig {
+                default_flavor: Some(move_compiler::editions::Flavor::Sui),
+                install_dir: Some(root.join("build")),
+                silence_warnings: true,
+                lint_flag: move_package_alt_compilation::lint_flag::LintFlag::LEVEL_NONE,
+                ..Default::default()
+            },
+            run_bytecode_verifier: true,
+            print_diags_to_stderr: false,
+            environment: self.environment.clone(),
+            flavor: SuiFlavor::new(),
+        }
+    }
+
+    fn persist(&self, stored: &StoredPackageSource) -> Result<(), SubmissionError> {
+        let path = self.attestation_file(&stored.attestation.package_id);
+        let tmp_path = path.with_extension("tmp");
+        let bytes = bcs::to_bytes(stored).map_err(|e| SubmissionError::Storage(e.to_string()))?;
+        std::fs::write(&tmp_path, bytes)
+            .and_then(|()| std::fs::rename(&tmp_path, &path))
+            .map_err(|e| SubmissionError::Storage(format!("{path:?}: {e}")))
+    }
+
+    fn attestation_file(&self, package_id: &ObjectID) -> PathBuf {
+        self.config
+            .attestation_path
+            .join(format!("{package_id}.{ATTESTATION_EXTENSION}"))
+    }
+}
+
+/// 1-based line and column of the byte at `offset` in `contents`.
+fn line_and_column(contents: &str, offset: usize) -> Option<(u32, u32)> {
+    let before = contents.get(..offset)?;
+    let line = before.matches('\n').count() + 1;
+    let column = offset - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+    Some((line as u32, column as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use sui_types::digests::CheckpointDigest;
+    use sui_types::object::Object;
+
+    use super::*;
+
+    fn service(submitters: &[(&str, &str)]) -> (tempfile::TempDir, SourceVerificationService) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SourceVerificationConfig {
+            attestation_path: dir.path().to_owned(),
+            allowed_compiler_versions: vec![],
+            submitters: submitters
+                .iter()
+                .map(|(name, token)| (name.to_string(), token.to_string()))
+                .collect(),
+            max_submissions_per_hour: NonZeroU32::new(3),
+            max_source_bytes: Some(64),
+            max_concurrent_builds: None,
+        };
+        let service = SourceVerificationService::new(
+            config,
+            ChainIdentifier::from(CheckpointDigest::default()),
+            Arc::new(BTreeMap::<ObjectID, Object>::new()),
+        )
+        .unwrap();
+        (dir, service)
+    }
+
+    fn files(manifest: &str) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("Move.toml".to_owned(), manifest.to_owned()),
+            ("Move.lock".to_owned(), String::new()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn submissions_are_disabled_without_submitters() {
+        let (_dir, service) = service(&[]);
+        let result = service
+            .submit(Some("token"), ObjectID::random(), files(""))
+            .await;
+        assert!(matches!(result, Err(SubmissionError::Disabled)));
+    }
+
+    #[tokio::test]
+    async fn submissions_are_authenticated_and_limited() {
+        let (_dir, service) = service(&[("ci", "secret")]);
+        let package = ObjectID::random();
+
+        for token in [None, Some("guess")] {
+            let result = service.submit(token, package, files("")).await;
+            assert!(matches!(result, Err(SubmissionError::Unauthorized)));
+        }
+
+        // Oversized submissions are rejected before anything is built, but still count towards
+        // the submitter's quota.
+        let result = service
+            .submit(Some("secret"), package, files(&"x".repeat(64)))
+            .await;
+        assert!(matches!(result, Err(SubmissionError::InvalidSubmission(_))));
+
+        // Submissions are not queued behind running builds.
+        let permit = service.builds.try_acquire().unwrap();
+        let result = service.submit(Some("secret"), package, files("")).await;
+        assert!(matches!(result, Err(SubmissionError::Busy)));
+        drop(permit);
+
+        let result = service
+            .submit(Some("secret"), package, files(&"x".repeat(64)))
+            .await;
+        assert!(matches!(result, Err(SubmissionError::InvalidSubmission(_))));
+        let result = service.submit(Some("secret"), package, files("")).await;
+        assert!(matches!(
+            result,
+            Err(SubmissionError::QuotaExceeded { limit: 3, .. })
+        ));
+
+        // The quota frees up as submissions age out of the window.
+        let later = Instant::now() + QUOTA_WINDOW;
+        assert!(service.charge_quota("ci", later).is_ok());
+    }
+
+    #[test]
+    fn line_and_column_are_one_based() {
+        let contents = "module a::m;\n\npublic fun f() {\n    abort 0\n}\n";
+        let offset = contents.find("abort").unwrap();
+        assert_eq!(line_and_column(contents, offset), Some((4, 5)));
+        assert_eq!(line_and_column(contents, 0), Some((1, 1)));
+        assert_eq!(line_and_column(contents, contents.len() + 1), None);
+    }
+}
@@ -7,10 +7,13 @@ use std::error::Error;
 use std::fmt;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::Duration;
 
-use parking_lot::{ArcMutexGuard, ArcRwLockReadGuard, ArcRwLockWriteGuard, Mutex, RwLock};
+use parking_lot::{
+    ArcMutexGuard, ArcRwLockReadGuard, ArcRwLockWriteGuard, Mutex, RwLock, RwLockReadGuard,
+    RwLockWriteGuard,
+};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
 use tracing::info;
@@ -72,6 +75,27 @@ pub struct LockTable<K: Hash, L: Lock> {
     _cleaner: JoinHandle<()>,
     stop: Arc<AtomicBool>,
     size: Arc<AtomicUsize>,
+    shard_accesses: AtomicU64,
+    contended_shard_accesses: AtomicU64,
+}
+
+/// How often looking up a key's lock had to wait for its shard, because another key in the same
+/// shard was being inserted or cleaned up at the same time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LockTableContention {
+    pub shard_accesses: u64,
+    pub contended_shard_accesses: u64,
+}
+
+impl LockTableContention {
+    /// The fraction of shard accesses that were contended, or 0 if there were none.
+    pub fn ratio(&self) -> f64 {
+        if self.shard_accesses == 0 {
+            0.0
+        } else {
+            self.contended_shard_accesses as f64 / self.shard_accesses as f64
+        }
+    }
 }
 
 pub type MutexTable<K> = LockTable<K, Mutex<()>>;
@@ -133,6 +157,8 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
             }),
             stop,
             size,
+            shard_accesses: AtomicU64::new(0),
+            contended_shard_accesses: AtomicU64::new(0),
         }
     }
 
@@ -149,6 +175,18 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
         self.size.load(Ordering::SeqCst)
     }
 
+    pub fn num_shards(&self) -> usize {
+        self.lock_table.len()
+    }
+
+    /// Shard contention since the table was created.
+    pub fn contention(&self) -> LockTableContention {
+        LockTableContention {
+            shard_accesses: self.shard_accesses.load(Ordering::Relaxed),
+            contended_shard_accesses: self.contended_shard_accesses.load(Ordering::Relaxed),
+        }
+    }
+
     pub fn cleanup(lock_table: Arc<Vec<RwLock<InnerLockTable<K, L>>>>) -> usize {
         let mut num_removed: usize = 0;
         for shard in lock_table.iter() {
@@ -185,6 +223,26 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
         hash % self.lock_table.len()
     }
 
+    fn read_shard(&self, lock_idx: usize) -> RwLockReadGuard<'_, InnerLockTable<K, L>> {
+        let shard = &self.lock_table[lock_idx];
+        self.shard_accesses.fetch_add(1, Ordering::Relaxed);
+        shard.try_read().unwrap_or_else(|| {
+            self.contended_shard_accesses
+                .fetch_add(1, Ordering::Relaxed);
+            shard.read()
+        })
+    }
+
+    fn write_shard(&self, lock_idx: usize) -> RwLockWriteGuard<'_, InnerLockTable<K, L>> {
+        let shard = &self.lock_table[lock_idx];
+        self.shard_accesses.fetch_add(1, Ordering::Relaxed);
+        shard.try_write().unwrap_or_else(|| {
+            self.contended_shard_accesses
+                .fetch_add(1, Ordering::Relaxed);
+            shard.write()
+        })
+    }
+
     pub fn acquire_locks<I>(&self, object_iter: I) -> Vec<L::Guard>
     where
         I: Iterator<Item = K>,
@@ -217,7 +275,7 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
     pub fn get_lock(&self, k: K) -> Arc<L> {
         let lock_idx = self.get_lock_idx(&k);
         let element = {
-            let map = self.lock_table[lock_idx].read();
+            let map = self.read_shard(lock_idx);
             map.get(&k).cloned()
         };
         if let Some(element) = element {
@@ -226,7 +284,7 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
             // element doesn't exist
 
             {
-                let mut map = self.lock_table[lock_idx].write();
+                let mut map = self.write_shard(lock_idx);
                 map.entry(k)
                     .or_insert_with(|| {
                         self.size.fetch_add(1, Ordering::SeqCst);
@@ -477,3 +535,34 @@ async fn test_mutex_table_bg_cleanup_with_size_threshold() {
         assert!(locked.is_empty());
     }
 }
+
+#[tokio::test]
+async fn test_mutex_table_contention() {
+    let mutex_table = Arc::new(MutexTable::<String>::new(1));
+
+    // Inserting a new key reads and then writes its shard.
+    let _john = mutex_table.acquire_lock("john".to_string());
+    assert_eq!(
+        mutex_table.contention(),
+        LockTableContention {
+            shard_accesses: 2,
+            contended_shard_accesses: 0,
+        }
+    );
+
+    let shards: Vec<_> = mutex_table.lock_table.iter().map(|s| s.write()).collect();
+    let handle = {
+        let mutex_table = mutex_table.clone();
+        std::thread::spawn(move || {
+            let _ = mutex_table.acquire_lock("jane".to_string());
+        })
+    };
+    std::thread::sleep(Duration::from_millis(50));
+    drop(shards);
+    handle.join().unwrap();
+
+    let contention = mutex_table.contention();
+    assert_eq!(contention.shard_accesses, 4);
+    assert_eq!(contention.contended_shard_accesses, 1);
+    assert_eq!(contention.ratio(), 0.25);
+}
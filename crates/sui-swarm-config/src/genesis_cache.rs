@@ -0,0 +1,323 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An on-disk cache of the genesis of test networks, so that repeated test runs don't build and
+//! sign the same genesis every time they start a network.
+//!
+//! Entries are keyed by the configuration the genesis is built from, and by the bytecode of the
+//! system packages, so that rebuilding the framework invalidates them. A cached genesis includes
+//! the keys and addresses of its validators, so each entry is leased to one network at a time, by
+//! a lock held by the network's config: networks started at the same time with the same
+//! configuration use different entries.
+//!
+//! The ports of the validators were allocated by the run that built the genesis, and are part of
+//! its committee, so an entry is only reused if all of them can be reserved again, the way fresh
+//! test ports are. Otherwise another process may have been given them since, and the genesis is
+//! built again.
+//!
+//! A cached genesis starts the chain when it was first built, so reusing it shortens the first
+//! epoch. Entries are only reused while the first epoch still has 90% of its duration left.
+//!
+//! Only the genesis is cached: each network still creates its databases from it at startup.
+
+use std::fs::{self, File, TryLockError};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use serde::{Deserialize, Serialize};
+use sui_config::genesis::{Genesis, GenesisCeremonyParameters};
+use sui_config::local_ip_utils;
+use sui_framework::BuiltInFramework;
+use sui_types::crypto::AccountKeyPair;
+use sui_types::object::Object;
+use tracing::{debug, warn};
+
+use crate::genesis_config::{GenesisConfig, ValidatorGenesisConfig};
+
+/// Environment variable naming the directory of the genesis cache used by test networks.
+pub const GENESIS_CACHE_DIR_ENV: &str = "SUI_TEST_GENESIS_CACHE_DIR";
+
+/// Bumped when the format of entries changes, to invalidate existing ones.
+const FORMAT_VERSION: u8 = 1;
+
+/// How many networks with the same configuration can use the cache at the same time.
+const MAX_LEASES_PER_KEY: usize = 32;
+
+#[derive(Clone, Debug)]
+pub struct GenesisCache {
+    dir: PathBuf,
+}
+
+/// Exclusive use of a cache entry, until dropped.
+#[derive(Debug)]
+pub struct GenesisCacheLease {
+    path: PathBuf,
+    _lock: File,
+}
+
+/// A genesis, and the validators and accounts it was built for.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GenesisCacheEntry {
+    pub chain_start_timestamp_ms: u64,
+    pub validators: Vec<ValidatorGenesisConfig>,
+    pub account_keys: Vec<AccountKeyPair>,
+    pub genesis: Genesis,
+}
+
+impl GenesisCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in the directory named by `SUI_TEST_GENESIS_CACHE_DIR`, if it is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(GENESIS_CACHE_DIR_ENV).map(Self::new)
+    }
+
+    /// Lease an entry for a network of `committee_size` generated validators. Returns `None` if
+    /// every entry for this configuration is in use, or the cache can't be used, in which case the
+    /// genesis is built as usual.
+    pub(crate) fn lease(
+        &self,
+        committee_size: NonZeroUsize,
+        reference_gas_price: Option<u64>,
+        genesis_config: &GenesisConfig,
+        additional_objects: &[Object],
+    ) -> Option<GenesisCacheLease> {
+        let key = cache_key(
+            committee_size,
+            reference_gas_price,
+            genesis_config,
+            additional_objects,
+        );
+        match self.try_lease(&key) {
+            Ok(lease) => lease,
+            Err(e) => {
+                warn!(
+                    "Not using the genesis cache in {}: {e:#}",
+                    self.dir.display()
+                );
+                None
+            }
+        }
+    }
+
+    fn try_lease(&self, key: &str) -> anyhow::Result<Option<GenesisCacheLease>> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Unable to create {}", self.dir.display()))?;
+
+        for slot in 0..MAX_LEASES_PER_KEY {
+            let lock_path = self.dir.join(format!("{key}-{slot}.lock"));
+            let lock = File::create(&lock_path)
+                .with_context(|| format!("Unable to create {}", lock_path.display()))?;
+            match lock.try_lock() {
+                Ok(()) => {
+                    return Ok(Some(GenesisCacheLease {
+                        path: self.dir.join(format!("{key}-{slot}.bcs")),
+                        _lock: lock,
+                    }));
+                }
+                Err(TryLockError::WouldBlock) => continue,
+                Err(TryLockError::Error(e)) => {
+                    return Err(e)
+                        .with_context(|| format!("Unable to lock {}", lock_path.display()));
+                }
+            }
+        }
+
+        debug!("All genesis cache entries for {key} are in use");
+        Ok(None)
+    }
+}
+
+impl GenesisCacheLease {
+    /// The cached genesis, if there is one that can start a chain configured by `genesis_config`.
+    pub(crate) fn load(&self, genesis_config: &GenesisConfig) -> Option<GenesisCacheEntry> {
+        let bytes = fs::read(&self.path).ok()?;
+        let entry: GenesisCacheEntry = match bcs::from_bytes(&bytes) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!(
+                    "Ignoring invalid genesis cache entry {}: {e}",
+                    self.path.display()
+                );
+                return None;
+            }
+        };
+
+        if !is_fresh(entry.chain_start_timestamp_ms, &genesis_config.parameters) {
+            debug!("Genesis cache entry {} is stale", self.path.display());
+            return None;
+        }
+        if !reserve_ports(&entry.validators) {
+            debug!(
+                "The ports of genesis cache entry {} are in use",
+                self.path.display()
+            );
+            return None;
+        }
+
+        debug!("Using cached genesis {}", self.path.display());
+        Some(entry)
+    }
+
+    pub(crate) fn save(&self, entry: &GenesisCacheEntry) {
+        let result = bcs::to_bytes(entry)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| Ok(fs::write(&self.path, bytes)?));
+        if let Err(e) = result {
+            warn!("Unable to cache genesis in {}: {e:#}", self.path.display());
+        }
+    }
+}
+
+/// Whether a genesis that starts the chain at `chain_start_timestamp_ms` can be used for a chain
+/// configured by `parameters`: it must not start after the requested time, nor so long before it
+/// that much of the first epoch has passed.
+fn is_fresh(chain_start_timestamp_ms: u64, parameters: &GenesisCeremonyParameters) -> bool {
+    parameters
+        .chain_start_timestamp_ms
+        .checked_sub(chain_start_timestamp_ms)
+        .is_some_and(|age| age <= parameters.epoch_duration_ms / 10)
+}
+
+/// Reserve the ports of `validators`, returning false if any of them is in use.
+fn reserve_ports(validators: &[ValidatorGenesisConfig]) -> bool {
+    let localhost = local_ip_utils::localhost_for_testing();
+    validators
+        .iter()
+        .flat_map(|v| {
+            [
+                v.network_address.port(),
+                v.p2p_address.port(),
+                Some(v.metrics_address.port()),
+                v.narwhal_metrics_address.port(),
+                v.narwhal_primary_address.port(),
+                v.narwhal_worker_address.port(),
+                v.consensus_address.port(),
+            ]
+        })
+        .all(|port| port.is_some_and(|port| local_ip_utils::reserve_port(&localhost, port)))
+}
+
+/// Identifies the genesis built from a configuration, except for the time the chain starts at.
+fn cache_key(
+    committee_size: NonZeroUsize,
+    reference_gas_price: Option<u64>,
+    genesis_config: &GenesisConfig,
+    additional_objects: &[Object],
+) -> String {
+    let mut parameters = genesis_config.parameters.clone();
+    parameters.chain_start_timestamp_ms = 0;
+
+    let mut hasher = Blake2b256::default();
+    hasher.update([FORMAT_VERSION]);
+    for package in BuiltInFramework::iter_system_packages() {
+        hasher.update(package.id);
+        for module in &package.bytes {
+            hasher.update(module);
+        }
+    }
+    let config = (
+        committee_size.get() as u64,
+        reference_gas_price,
+        &parameters,
+        &genesis_config.accounts,
+        &genesis_config.coin_allocations,
+        &genesis_config.objects,
+        additional_objects,
+    );
+    hasher.update(bcs::to_bytes(&config).expect("Genesis config should serialize"));
+    Hex::encode(hasher.finalize().digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::genesis_config::ValidatorGenesisConfigBuilder;
+
+    fn key(committee_size: usize, genesis_config: &GenesisConfig) -> String {
+        cache_key(
+            NonZeroUsize::new(committee_size).unwrap(),
+            None,
+            genesis_config,
+            &[],
+        )
+    }
+
+    #[test]
+    fn key_ignores_only_the_chain_start() {
+        let config = GenesisConfig::for_local_testing();
+        let mut later = GenesisConfig::for_local_testing();
+        later.parameters.chain_start_timestamp_ms =
+            config.parameters.chain_start_timestamp_ms + 1000;
+        assert_eq!(key(4, &config), key(4, &later));
+
+        assert_ne!(key(4, &config), key(5, &config));
+        let mut longer_epochs = GenesisConfig::for_local_testing();
+        longer_epochs.parameters.epoch_duration_ms += 1;
+        assert_ne!(key(4, &config), key(4, &longer_epochs));
+        assert_ne!(
+            key(4, &config),
+            key(4, &GenesisConfig::custom_genesis(1, 1))
+        );
+        assert_ne!(
+            cache_key(NonZeroUsize::new(4).unwrap(), Some(1000), &config, &[]),
+            key(4, &config)
+        );
+    }
+
+    #[test]
+    fn leases_are_exclusive() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = GenesisCache::new(dir.path());
+
+        let first = cache.try_lease("key").unwrap().unwrap();
+        let second = cache.try_lease("key").unwrap().unwrap();
+        assert_ne!(first.path, second.path);
+
+        // Other keys have their own entries.
+        let other = cache.try_lease("other").unwrap().unwrap();
+        assert_ne!(other.path, first.path);
+
+        // Dropping a lease releases its entry.
+        let first_path = first.path.clone();
+        drop(first);
+        assert_eq!(cache.try_lease("key").unwrap().unwrap().path, first_path);
+
+        // No lease is given once every entry is in use.
+        let leases: Vec<_> = (0..MAX_LEASES_PER_KEY)
+            .map(|_| cache.try_lease("full").unwrap().unwrap())
+            .collect();
+        assert!(cache.try_lease("full").unwrap().is_none());
+        drop(leases);
+    }
+
+    #[test]
+    fn stale_entries_are_not_used() {
+        let mut parameters = GenesisConfig::for_local_testing().parameters;
+        parameters.epoch_duration_ms = 10_000;
+        parameters.chain_start_timestamp_ms = 100_000;
+
+        assert!(is_fresh(100_000, &parameters));
+        assert!(is_fresh(99_000, &parameters));
+        assert!(!is_fresh(98_999, &parameters));
+        // A genesis that starts the chain later than requested can't be used either.
+        assert!(!is_fresh(100_001, &parameters));
+    }
+
+    #[test]
+    fn entries_with_ports_in_use_are_not_used() {
+        let validator = ValidatorGenesisConfigBuilder::new().build(&mut rand::rngs::OsRng);
+        assert!(reserve_ports(std::slice::from_ref(&validator)));
+
+        let port = validator.network_address.port().unwrap();
+        let _listener = TcpListener::bind((local_ip_utils::localhost_for_testing(), port)).unwrap();
+        assert!(!reserve_ports(&[validator]));
+    }
+}
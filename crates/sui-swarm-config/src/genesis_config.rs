@@ -5,18 +5,22 @@ use std::net::{IpAddr, SocketAddr};
 
 use anyhow::Result;
 use fastcrypto::traits::KeyPair;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use rand::{SeedableRng, rngs::StdRng};
 use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
 use sui_config::genesis::{GenesisCeremonyParameters, TokenAllocation};
 use sui_config::node::{DEFAULT_COMMISSION_RATE, DEFAULT_VALIDATOR_GAS_PRICE};
 use sui_config::{Config, local_ip_utils};
 use sui_genesis_builder::validator_info::{GenesisValidatorInfo, ValidatorInfo};
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::crypto::{
     AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes, NetworkKeyPair, NetworkPublicKey,
     PublicKey, SuiKeyPair, generate_proof_of_possession, get_key_pair_from_rng,
 };
+use sui_types::gas_coin::GasCoin;
 use sui_types::multiaddr::Multiaddr;
+use sui_types::object::{MoveObject, Object, Owner};
 use tracing::info;
 
 // All information needed to build a NodeConfig for a state sync fullnode.
@@ -225,6 +229,13 @@ pub struct GenesisConfig {
     pub validator_config_info: Option<Vec<ValidatorGenesisConfig>>,
     pub parameters: GenesisCeremonyParameters,
     pub accounts: Vec<AccountConfig>,
+    /// Coins of types other than SUI to create at genesis.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coin_allocations: Vec<CoinAllocation>,
+    /// Objects to insert into genesis as they are, e.g. the packages that define the coin types of
+    /// `coin_allocations` and the objects of an application.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<Object>,
 }
 
 impl Config for GenesisConfig {}
@@ -263,6 +274,66 @@ impl GenesisConfig {
 
         Ok((keys, allocations))
     }
+
+    /// The objects to insert into genesis: `objects`, followed by a `Coin<T>` for each of
+    /// `coin_allocations`. The IDs of the coins are drawn from `rng`. The genesis builder checks
+    /// that the types of these objects are defined by the framework or by inserted packages.
+    pub fn generate_objects<R: rand::RngCore + rand::CryptoRng>(
+        &self,
+        mut rng: R,
+    ) -> Result<Vec<Object>> {
+        let mut objects = self.objects.clone();
+        for allocation in &self.coin_allocations {
+            anyhow::ensure!(
+                allocation.coin_type != GasCoin::type_(),
+                "SUI is allocated through accounts, not coin allocations"
+            );
+            let coin = MoveObject::new_coin(
+                TypeTag::Struct(Box::new(allocation.coin_type.clone())),
+                SequenceNumber::new(),
+                ObjectID::random_from_rng(&mut rng),
+                allocation.amount,
+            );
+            objects.push(Object::new_move(
+                coin,
+                Owner::AddressOwner(allocation.recipient_address),
+                TransactionDigest::genesis_marker(),
+            ));
+        }
+        Ok(objects)
+    }
+
+    /// Create a `Coin<coin_type>` of `amount` owned by `recipient_address` at genesis.
+    pub fn add_coin_allocation(
+        mut self,
+        recipient_address: SuiAddress,
+        coin_type: StructTag,
+        amount: u64,
+    ) -> Self {
+        self.coin_allocations.push(CoinAllocation {
+            recipient_address,
+            coin_type,
+            amount,
+        });
+        self
+    }
+
+    /// Insert `objects` into genesis as they are.
+    pub fn add_objects(mut self, objects: impl IntoIterator<Item = Object>) -> Self {
+        self.objects.extend(objects);
+        self
+    }
+}
+
+/// A coin of a type other than SUI, created at genesis.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CoinAllocation {
+    pub recipient_address: SuiAddress,
+    /// The type `T` of the `Coin<T>`, e.g. `0x123::my_coin::MY_COIN`.
+    #[serde_as(as = "DisplayFromStr")]
+    pub coin_type: StructTag,
+    pub amount: u64,
 }
 
 fn default_socket_address() -> SocketAddr {
@@ -404,6 +475,8 @@ impl GenesisConfig {
             validator_config_info: Some(validator_config_info),
             parameters,
             accounts: account_configs,
+            coin_allocations: vec![],
+            objects: vec![],
         }
     }
 
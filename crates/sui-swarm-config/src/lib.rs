@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod genesis_cache;
 pub mod genesis_config;
 pub mod network_config;
 pub mod network_config_builder;
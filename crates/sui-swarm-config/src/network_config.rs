@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use sui_config::{Config, NodeConfig, genesis};
@@ -8,6 +10,8 @@ use sui_types::committee::CommitteeWithNetworkMetadata;
 use sui_types::crypto::AccountKeyPair;
 use sui_types::multiaddr::Multiaddr;
 
+use crate::genesis_cache::GenesisCacheLease;
+
 /// This is a config that is used for testing or local use as it contains the config and keys for
 /// all validators
 #[serde_as]
@@ -16,6 +20,10 @@ pub struct NetworkConfig {
     pub validator_configs: Vec<NodeConfig>,
     pub account_keys: Vec<AccountKeyPair>,
     pub genesis: genesis::Genesis,
+    /// Keeps the cached genesis this network was built from, if any, from being used by another
+    /// network while this one is.
+    #[serde(skip)]
+    pub genesis_cache_lease: Option<Arc<GenesisCacheLease>>,
 }
 
 impl Config for NetworkConfig {}
@@ -11,6 +11,7 @@ use rand::rngs::OsRng;
 use sui_config::ExecutionCacheConfig;
 use sui_config::genesis::{TokenAllocation, TokenDistributionScheduleBuilder};
 use sui_config::node::AuthorityOverloadConfig;
+use sui_config::node::CommitLatencyConfig;
 #[cfg(msim)]
 use sui_config::node::ExecutionTimeObserverConfig;
 use sui_config::node::FundsWithdrawSchedulerType;
@@ -27,6 +28,7 @@ use sui_types::traffic_control::{PolicyConfig, RemoteFirewallConfig};
 
 use consensus_config::ObserverParameters;
 
+use crate::genesis_cache::{GenesisCache, GenesisCacheEntry};
 use crate::genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT, ValidatorGenesisConfigBuilder};
 use crate::genesis_config::{GenesisConfig, ValidatorGenesisConfig};
 use crate::network_config::NetworkConfig;
@@ -126,6 +128,8 @@ pub struct ConfigBuilder<R = OsRng> {
     #[cfg(msim)]
     execution_time_observer_config: Option<ExecutionTimeObserverConfig>,
     validator_observer_config: Option<ValidatorObserverConfigCallback>,
+    consensus_commit_latency: Option<CommitLatencyConfig>,
+    genesis_cache: Option<GenesisCache>,
 }
 
 impl ConfigBuilder {
@@ -172,6 +176,8 @@ impl ConfigBuilder {
             #[cfg(msim)]
             execution_time_observer_config: None,
             validator_observer_config: None,
+            consensus_commit_latency: None,
+            genesis_cache: None,
         }
     }
 
@@ -355,6 +361,19 @@ impl<R> ConfigBuilder<R> {
         self
     }
 
+    /// Delay the handling of consensus commits on every validator, to emulate the finality times
+    /// of a real network.
+    pub fn with_consensus_commit_latency(mut self, c: CommitLatencyConfig) -> Self {
+        self.consensus_commit_latency = Some(c);
+        self
+    }
+
+    /// Reuse a genesis built by an earlier network with the same configuration, from `cache`.
+    pub fn with_genesis_cache(mut self, cache: GenesisCache) -> Self {
+        self.genesis_cache = Some(cache);
+        self
+    }
+
     pub fn with_authority_overload_config(mut self, c: AuthorityOverloadConfig) -> Self {
         self.authority_overload_config = Some(c);
         self
@@ -399,6 +418,8 @@ impl<R> ConfigBuilder<R> {
             #[cfg(msim)]
             execution_time_observer_config: self.execution_time_observer_config,
             validator_observer_config: self.validator_observer_config,
+            consensus_commit_latency: self.consensus_commit_latency,
+            genesis_cache: self.genesis_cache,
         }
     }
 
@@ -428,11 +449,167 @@ impl<R> ConfigBuilder<R> {
 }
 
 impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
-    //TODO right now we always randomize ports, we may want to have a default port configuration
+    /// Build the network config, panicking if the genesis config is invalid. Use
+    /// [Self::try_build] when the genesis config comes from user input.
     pub fn build(self) -> NetworkConfig {
-        let committee = self.committee;
+        self.try_build().expect("failed to build network config")
+    }
+
+    //TODO right now we always randomize ports, we may want to have a default port configuration
+    pub fn try_build(self) -> anyhow::Result<NetworkConfig> {
+        let genesis_config = self
+            .genesis_config
+            .unwrap_or_else(GenesisConfig::for_local_testing);
+
+        // Only the genesis of generated validators is cached, as validators that are passed in
+        // come with their own keys and addresses.
+        let genesis_cache_lease = match (&self.genesis_cache, &self.committee) {
+            (Some(cache), CommitteeConfig::Size(size)) => cache.lease(
+                *size,
+                self.reference_gas_price,
+                &genesis_config,
+                &self.additional_objects,
+            ),
+            _ => None,
+        };
+        let cached_genesis = genesis_cache_lease
+            .as_ref()
+            .and_then(|lease| lease.load(&genesis_config));
+
+        let GenesisCacheEntry {
+            validators,
+            account_keys,
+            genesis,
+            ..
+        } = match cached_genesis {
+            Some(entry) => entry,
+            None => {
+                let entry = Self::build_genesis(
+                    self.rng.unwrap(),
+                    self.committee,
+                    self.reference_gas_price,
+                    genesis_config,
+                    self.additional_objects,
+                )?;
+                if let Some(lease) = &genesis_cache_lease {
+                    lease.save(&entry);
+                }
+                entry
+            }
+        };
+
+        let all_authority_names: Vec<AuthorityName> = validators
+            .iter()
+            .map(|v| v.key_pair.public().into())
+            .collect();
+        let validator_configs = validators
+            .into_iter()
+            .enumerate()
+            .map(|(idx, validator)| {
+                let mut builder = ValidatorConfigBuilder::new()
+                    .with_config_directory(self.config_directory.clone())
+                    .with_policy_config(self.policy_config.clone())
+                    .with_firewall_config(self.firewall_config.clone());
 
-        let mut rng = self.rng.unwrap();
+                if let Some(chain) = self.chain_override {
+                    builder = builder.with_chain_override(chain);
+                }
+
+                if let Some(jwk_fetch_interval) = self.jwk_fetch_interval {
+                    builder = builder.with_jwk_fetch_interval(jwk_fetch_interval);
+                }
+
+                if let Some(authority_overload_config) = &self.authority_overload_config {
+                    builder =
+                        builder.with_authority_overload_config(authority_overload_config.clone());
+                }
+
+                if let Some(execution_cache_config) = &self.execution_cache_config {
+                    builder = builder.with_execution_cache_config(execution_cache_config.clone());
+                }
+
+                if let Some(path) = &self.data_ingestion_dir {
+                    builder = builder.with_data_ingestion_dir(path.clone());
+                }
+
+                if let Some(state_sync_config) = &self.state_sync_config {
+                    builder = builder.with_state_sync_config(state_sync_config.clone());
+                }
+
+                if let Some(commit_latency) = &self.consensus_commit_latency {
+                    builder = builder.with_consensus_commit_latency(commit_latency.clone());
+                }
+
+                #[cfg(msim)]
+                if let Some(execution_time_observer_config) = &self.execution_time_observer_config {
+                    builder = builder.with_execution_time_observer_config(
+                        execution_time_observer_config.clone(),
+                    );
+                }
+
+                if let Some(spvc) = &self.supported_protocol_versions_config {
+                    let supported_versions = match spvc {
+                        ProtocolVersionsConfig::Default => {
+                            SupportedProtocolVersions::SYSTEM_DEFAULT
+                        }
+                        ProtocolVersionsConfig::Global(v) => *v,
+                        ProtocolVersionsConfig::PerValidator(func) => {
+                            func(idx, Some(validator.key_pair.public().into()))
+                        }
+                    };
+                    builder = builder.with_supported_protocol_versions(supported_versions);
+                }
+                if let Some(acc_v2_config) = &self.global_state_hash_v2_enabled_config {
+                    let global_state_hash_v2_enabled: bool = match acc_v2_config {
+                        GlobalStateHashV2EnabledConfig::Global(enabled) => *enabled,
+                        GlobalStateHashV2EnabledConfig::PerValidator(func) => func(idx),
+                    };
+                    builder =
+                        builder.with_global_state_hash_v2_enabled(global_state_hash_v2_enabled);
+                }
+                if let Some(scheduler_type_config) = &self.funds_withdraw_scheduler_type_config {
+                    let scheduler_type = match scheduler_type_config {
+                        FundsWithdrawSchedulerTypeConfig::Global(t) => *t,
+                        FundsWithdrawSchedulerTypeConfig::PerValidator(func) => func(idx),
+                    };
+                    builder = builder.with_funds_withdraw_scheduler_type(scheduler_type);
+                }
+                if let Some(observer_config_fn) = &self.validator_observer_config
+                    && let Some(observer_config) = observer_config_fn(idx)
+                {
+                    builder = builder.with_observer_config(observer_config);
+                }
+                if let Some(num_unpruned_validators) = self.num_unpruned_validators
+                    && idx < num_unpruned_validators
+                {
+                    builder = builder.with_unpruned_checkpoints();
+                }
+                if let Some(peer_deny_sync_cb) = &self.peer_deny_sync_config {
+                    let this_authority: AuthorityName = validator.key_pair.public().into();
+                    builder = builder.with_peer_deny_sync_config(peer_deny_sync_cb(
+                        this_authority,
+                        &all_authority_names,
+                    ));
+                }
+                builder.build(validator, genesis.clone())
+            })
+            .collect();
+        Ok(NetworkConfig {
+            validator_configs,
+            genesis,
+            account_keys,
+            genesis_cache_lease: genesis_cache_lease.map(Arc::new),
+        })
+    }
+
+    fn build_genesis(
+        mut rng: R,
+        committee: CommitteeConfig,
+        reference_gas_price: Option<u64>,
+        genesis_config: GenesisConfig,
+        additional_objects: Vec<Object>,
+    ) -> anyhow::Result<GenesisCacheEntry> {
+        let chain_start_timestamp_ms = genesis_config.parameters.chain_start_timestamp_ms;
         let validators = match committee {
             CommitteeConfig::Size(size) => {
                 // We always get fixed protocol keys from this function (which is isolated from
@@ -445,7 +622,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     .map(|authority_key| {
                         let mut builder = ValidatorGenesisConfigBuilder::new()
                             .with_protocol_key_pair(authority_key);
-                        if let Some(rgp) = self.reference_gas_price {
+                        if let Some(rgp) = reference_gas_price {
                             builder = builder.with_gas_price(rgp);
                         }
                         builder.build(&mut rng)
@@ -464,7 +641,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                         let mut builder = ValidatorGenesisConfigBuilder::new()
                             .with_protocol_key_pair(protocol_key)
                             .with_account_key_pair(account_key);
-                        if let Some(rgp) = self.reference_gas_price {
+                        if let Some(rgp) = reference_gas_price {
                             builder = builder.with_gas_price(rgp);
                         }
                         builder.build(&mut rng)
@@ -492,7 +669,7 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     if let Some(protocol_key_pair) = key.protocol_key_pair {
                         builder = builder.with_protocol_key_pair(protocol_key_pair);
                     }
-                    if let Some(rgp) = self.reference_gas_price {
+                    if let Some(rgp) = reference_gas_price {
                         builder = builder.with_gas_price(rgp);
                     }
                     configs.push(builder.build(&mut rng));
@@ -501,11 +678,8 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
             }
         };
 
-        let genesis_config = self
-            .genesis_config
-            .unwrap_or_else(GenesisConfig::for_local_testing);
-
-        let (account_keys, allocations) = genesis_config.generate_accounts(&mut rng).unwrap();
+        let (account_keys, allocations) = genesis_config.generate_accounts(&mut rng)?;
+        let genesis_objects = genesis_config.generate_objects(&mut rng)?;
 
         let token_distribution_schedule = {
             let mut builder = TokenDistributionScheduleBuilder::new();
@@ -536,7 +710,8 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
         let genesis = {
             let mut builder = sui_genesis_builder::Builder::new()
                 .with_parameters(genesis_config.parameters)
-                .add_objects(self.additional_objects);
+                .add_objects(additional_objects)
+                .add_objects(genesis_objects);
 
             for (i, validator) in validators.iter().enumerate() {
                 let name = validator
@@ -550,6 +725,10 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
 
             builder = builder.with_token_distribution_schedule(token_distribution_schedule);
 
+            // Report invalid inputs, such as extra objects of unknown types, before building
+            // genesis panics on them.
+            builder.validate()?;
+
             for validator in &validators {
                 builder = builder.add_validator_signature(&validator.key_pair);
             }
@@ -557,103 +736,12 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
             builder.build()
         };
 
-        let all_authority_names: Vec<AuthorityName> = validators
-            .iter()
-            .map(|v| v.key_pair.public().into())
-            .collect();
-        let validator_configs = validators
-            .into_iter()
-            .enumerate()
-            .map(|(idx, validator)| {
-                let mut builder = ValidatorConfigBuilder::new()
-                    .with_config_directory(self.config_directory.clone())
-                    .with_policy_config(self.policy_config.clone())
-                    .with_firewall_config(self.firewall_config.clone());
-
-                if let Some(chain) = self.chain_override {
-                    builder = builder.with_chain_override(chain);
-                }
-
-                if let Some(jwk_fetch_interval) = self.jwk_fetch_interval {
-                    builder = builder.with_jwk_fetch_interval(jwk_fetch_interval);
-                }
-
-                if let Some(authority_overload_config) = &self.authority_overload_config {
-                    builder =
-                        builder.with_authority_overload_config(authority_overload_config.clone());
-                }
-
-                if let Some(execution_cache_config) = &self.execution_cache_config {
-                    builder = builder.with_execution_cache_config(execution_cache_config.clone());
-                }
-
-                if let Some(path) = &self.data_ingestion_dir {
-                    builder = builder.with_data_ingestion_dir(path.clone());
-                }
-
-                if let Some(state_sync_config) = &self.state_sync_config {
-                    builder = builder.with_state_sync_config(state_sync_config.clone());
-                }
-
-                #[cfg(msim)]
-                if let Some(execution_time_observer_config) = &self.execution_time_observer_config {
-                    builder = builder.with_execution_time_observer_config(
-                        execution_time_observer_config.clone(),
-                    );
-                }
-
-                if let Some(spvc) = &self.supported_protocol_versions_config {
-                    let supported_versions = match spvc {
-                        ProtocolVersionsConfig::Default => {
-                            SupportedProtocolVersions::SYSTEM_DEFAULT
-                        }
-                        ProtocolVersionsConfig::Global(v) => *v,
-                        ProtocolVersionsConfig::PerValidator(func) => {
-                            func(idx, Some(validator.key_pair.public().into()))
-                        }
-                    };
-                    builder = builder.with_supported_protocol_versions(supported_versions);
-                }
-                if let Some(acc_v2_config) = &self.global_state_hash_v2_enabled_config {
-                    let global_state_hash_v2_enabled: bool = match acc_v2_config {
-                        GlobalStateHashV2EnabledConfig::Global(enabled) => *enabled,
-                        GlobalStateHashV2EnabledConfig::PerValidator(func) => func(idx),
-                    };
-                    builder =
-                        builder.with_global_state_hash_v2_enabled(global_state_hash_v2_enabled);
-                }
-                if let Some(scheduler_type_config) = &self.funds_withdraw_scheduler_type_config {
-                    let scheduler_type = match scheduler_type_config {
-                        FundsWithdrawSchedulerTypeConfig::Global(t) => *t,
-                        FundsWithdrawSchedulerTypeConfig::PerValidator(func) => func(idx),
-                    };
-                    builder = builder.with_funds_withdraw_scheduler_type(scheduler_type);
-                }
-                if let Some(observer_config_fn) = &self.validator_observer_config
-                    && let Some(observer_config) = observer_config_fn(idx)
-                {
-                    builder = builder.with_observer_config(observer_config);
-                }
-                if let Some(num_unpruned_validators) = self.num_unpruned_validators
-                    && idx < num_unpruned_validators
-                {
-                    builder = builder.with_unpruned_checkpoints();
-                }
-                if let Some(peer_deny_sync_cb) = &self.peer_deny_sync_config {
-                    let this_authority: AuthorityName = validator.key_pair.public().into();
-                    builder = builder.with_peer_deny_sync_config(peer_deny_sync_cb(
-                        this_authority,
-                        &all_authority_names,
-                    ));
-                }
-                builder.build(validator, genesis.clone())
-            })
-            .collect();
-        NetworkConfig {
-            validator_configs,
-            genesis,
+        Ok(GenesisCacheEntry {
+            chain_start_timestamp_ms,
+            validators,
             account_keys,
-        }
+            genesis,
+        })
     }
 }
 
@@ -703,6 +791,57 @@ mod tests {
         loaded_genesis.checkpoint_contents().digest(); // cache digest before comparing.
         assert_eq!(&genesis, loaded_genesis);
     }
+
+    #[test]
+    fn genesis_cache_reuses_genesis_of_dropped_networks() {
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let cache = crate::genesis_cache::GenesisCache::new(cache_dir.path());
+        let build = || {
+            crate::network_config_builder::ConfigBuilder::new_with_temp_dir()
+                .with_genesis_cache(cache.clone())
+                .build()
+        };
+
+        // A network running at the same time can't use the same entry.
+        let first = build();
+        let concurrent = build();
+        assert_ne!(first.genesis.hash(), concurrent.genesis.hash());
+
+        let first_genesis = first.genesis.hash();
+        drop(first);
+        let reused = build();
+        assert_eq!(reused.genesis.hash(), first_genesis);
+    }
+
+    #[test]
+    fn invalid_coin_allocations_are_errors() {
+        use crate::genesis_config::GenesisConfig;
+        use sui_types::base_types::SuiAddress;
+        use sui_types::gas_coin::GasCoin;
+
+        let try_build = |coin_type| {
+            crate::network_config_builder::ConfigBuilder::new_with_temp_dir()
+                .with_genesis_config(GenesisConfig::for_local_testing().add_coin_allocation(
+                    SuiAddress::random_for_testing_only(),
+                    coin_type,
+                    100,
+                ))
+                .try_build()
+        };
+
+        let err = try_build(GasCoin::type_()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("SUI is allocated through accounts")
+        );
+
+        // The package defining the coin type is not part of genesis.
+        let err = try_build("0x123::my_coin::MY_COIN".parse().unwrap()).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("does not exist at genesis"),
+            "{err:#}"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -10,10 +10,11 @@ use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::traits::KeyPair;
 use sui_config::node::{
     AuthorityKeyPairWithPath, AuthorityOverloadConfig, AuthorityStorePruningConfig,
-    CheckpointExecutorConfig, DBCheckpointConfig, DEFAULT_GRPC_CONCURRENCY_LIMIT,
-    ExecutionCacheConfig, ExecutionTimeObserverConfig, ExpensiveSafetyCheckConfig,
-    FundsWithdrawSchedulerType, Genesis, KeyPairWithPath, StateSnapshotConfig,
-    default_enable_index_processing, default_end_of_epoch_broadcast_channel_capacity,
+    CheckpointExecutorConfig, CommitLatencyConfig, DBCheckpointConfig,
+    DEFAULT_GRPC_CONCURRENCY_LIMIT, ExecutionCacheConfig, ExecutionTimeObserverConfig,
+    ExpensiveSafetyCheckConfig, FundsWithdrawSchedulerType, Genesis, KeyPairWithPath,
+    StateSnapshotConfig, default_enable_index_processing,
+    default_end_of_epoch_broadcast_channel_capacity,
 };
 use sui_config::node::{RunWithRange, TransactionDriverConfig, default_zklogin_oauth_providers};
 use sui_config::p2p::{P2pConfig, SeedPeer, StateSyncConfig};
@@ -53,6 +54,7 @@ pub struct ValidatorConfigBuilder {
     state_sync_config: Option<StateSyncConfig>,
     observer_config: Option<ObserverParameters>,
     peer_deny_sync_config: Option<PeerDenySyncConfig>,
+    consensus_commit_latency: Option<CommitLatencyConfig>,
 }
 
 impl ValidatorConfigBuilder {
@@ -155,6 +157,11 @@ impl ValidatorConfigBuilder {
         self
     }
 
+    pub fn with_consensus_commit_latency(mut self, config: CommitLatencyConfig) -> Self {
+        self.consensus_commit_latency = Some(config);
+        self
+    }
+
     pub fn build(
         self,
         validator: ValidatorGenesisConfig,
@@ -191,6 +198,7 @@ impl ValidatorConfigBuilder {
             parameters,
             listen_address: None,
             external_address: None,
+            commit_latency: self.consensus_commit_latency,
         };
 
         let p2p_config = P2pConfig {
@@ -284,6 +292,10 @@ impl ValidatorConfigBuilder {
             execution_cache: self.execution_cache_config.unwrap_or_default(),
             run_with_range: None,
             jsonrpc_server_type: None,
+            jsonrpc_address_config: None,
+            jsonrpc_blob_config: None,
+            jsonrpc_rate_limit_config: None,
+            jsonrpc_extensions: vec![],
             disable_json_rpc: false,
             policy_config: self.policy_config,
             firewall_config: self.firewall_config,
@@ -299,6 +311,15 @@ impl ValidatorConfigBuilder {
             fork_recovery: None,
             transaction_driver_config: Some(TransactionDriverConfig::default()),
             congestion_log: None,
+            package_gas_accounting: None,
+            denylist_feed: None,
+            source_verification_config: None,
+            health_check: None,
+            compliance_report: None,
+            webhooks: vec![],
+            mutex_table_config: None,
+            consensus_blocked_packages: vec![],
+            safe_mode_diagnostics: None,
         }
     }
 
@@ -342,6 +363,7 @@ pub struct FullnodeConfigBuilder {
     rpc_config: Option<sui_config::RpcConfig>,
     state_sync_config: Option<StateSyncConfig>,
     observer_config: Option<ObserverParameters>,
+    seed_peers: Option<Vec<SeedPeer>>,
 }
 
 impl FullnodeConfigBuilder {
@@ -498,6 +520,12 @@ impl FullnodeConfigBuilder {
         self
     }
 
+    /// Peers to discover the network from, instead of the validators of the network.
+    pub fn with_seed_peers(mut self, seed_peers: Vec<SeedPeer>) -> Self {
+        self.seed_peers = Some(seed_peers);
+        self
+    }
+
     pub fn build<R: rand::RngCore + rand::CryptoRng>(
         self,
         rng: &mut R,
@@ -544,19 +572,22 @@ impl FullnodeConfigBuilder {
             }),
             listen_address: None,
             external_address: None,
+            commit_latency: None,
         });
 
         let p2p_config = {
-            let seed_peers = network_config
-                .validator_configs
-                .iter()
-                .map(|config| SeedPeer {
-                    peer_id: Some(anemo::PeerId(
-                        config.network_key_pair().public().0.to_bytes(),
-                    )),
-                    address: config.p2p_config.external_address.clone().unwrap(),
-                })
-                .collect();
+            let seed_peers = self.seed_peers.unwrap_or_else(|| {
+                network_config
+                    .validator_configs
+                    .iter()
+                    .map(|config| SeedPeer {
+                        peer_id: Some(anemo::PeerId(
+                            config.network_key_pair().public().0.to_bytes(),
+                        )),
+                        address: config.p2p_config.external_address.clone().unwrap(),
+                    })
+                    .collect()
+            });
 
             P2pConfig {
                 listen_address: self.p2p_listen_address.unwrap_or_else(|| {
@@ -677,6 +708,10 @@ impl FullnodeConfigBuilder {
             authority_overload_config: Default::default(),
             run_with_range: self.run_with_range,
             jsonrpc_server_type: None,
+            jsonrpc_address_config: None,
+            jsonrpc_blob_config: None,
+            jsonrpc_rate_limit_config: None,
+            jsonrpc_extensions: vec![],
             disable_json_rpc: self.disable_json_rpc,
             policy_config: self.policy_config,
             firewall_config: self.fw_config,
@@ -695,6 +730,15 @@ impl FullnodeConfigBuilder {
                 .transaction_driver_config
                 .or(Some(TransactionDriverConfig::default())),
             congestion_log: None,
+            package_gas_accounting: None,
+            denylist_feed: None,
+            source_verification_config: None,
+            health_check: None,
+            compliance_report: None,
+            webhooks: vec![],
+            mutex_table_config: None,
+            consensus_blocked_packages: vec![],
+            safe_mode_diagnostics: None,
         }
     }
 }
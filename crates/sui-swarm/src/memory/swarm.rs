@@ -17,11 +17,14 @@ use sui_types::traffic_control::{PolicyConfig, RemoteFirewallConfig};
 
 #[cfg(msim)]
 use sui_config::node::ExecutionTimeObserverConfig;
-use sui_config::node::{AuthorityOverloadConfig, DBCheckpointConfig, RunWithRange};
+use sui_config::node::{
+    AuthorityOverloadConfig, CommitLatencyConfig, DBCheckpointConfig, RunWithRange,
+};
 use sui_config::{ExecutionCacheConfig, NodeConfig};
 use sui_macros::nondeterministic;
 use sui_node::SuiNodeHandle;
 use sui_protocol_config::{Chain, ProtocolVersion};
+use sui_swarm_config::genesis_cache::GenesisCache;
 use sui_swarm_config::genesis_config::{AccountConfig, GenesisConfig, ValidatorGenesisConfig};
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_swarm_config::network_config_builder::{
@@ -71,6 +74,8 @@ pub struct SwarmBuilder<R = OsRng> {
     #[cfg(msim)]
     execution_time_observer_config: Option<ExecutionTimeObserverConfig>,
     validator_observer_config: Option<ValidatorObserverConfigCallback>,
+    consensus_commit_latency: Option<CommitLatencyConfig>,
+    genesis_cache: Option<GenesisCache>,
 }
 
 impl SwarmBuilder {
@@ -108,6 +113,8 @@ impl SwarmBuilder {
             #[cfg(msim)]
             execution_time_observer_config: None,
             validator_observer_config: None,
+            consensus_commit_latency: None,
+            genesis_cache: None,
         }
     }
 }
@@ -147,6 +154,8 @@ impl<R> SwarmBuilder<R> {
             #[cfg(msim)]
             execution_time_observer_config: self.execution_time_observer_config,
             validator_observer_config: self.validator_observer_config,
+            consensus_commit_latency: self.consensus_commit_latency,
+            genesis_cache: self.genesis_cache,
         }
     }
 
@@ -302,6 +311,20 @@ impl<R> SwarmBuilder<R> {
         self
     }
 
+    /// Delay the handling of consensus commits on every validator, to emulate the finality times
+    /// of a real network. Ignored when the network config is provided.
+    pub fn with_consensus_commit_latency(mut self, c: CommitLatencyConfig) -> Self {
+        self.consensus_commit_latency = Some(c);
+        self
+    }
+
+    /// Reuse a genesis built by an earlier swarm with the same configuration, from `cache`.
+    /// Ignored when the network config is provided.
+    pub fn with_genesis_cache(mut self, cache: GenesisCache) -> Self {
+        self.genesis_cache = Some(cache);
+        self
+    }
+
     pub fn with_fullnode_supported_protocol_versions_config(
         mut self,
         c: ProtocolVersionsConfig,
@@ -425,6 +448,14 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                 config_builder = config_builder.with_data_ingestion_dir(path);
             }
 
+            if let Some(commit_latency) = self.consensus_commit_latency {
+                config_builder = config_builder.with_consensus_commit_latency(commit_latency);
+            }
+
+            if let Some(genesis_cache) = self.genesis_cache {
+                config_builder = config_builder.with_genesis_cache(genesis_cache);
+            }
+
             #[allow(unused_mut)]
             let mut final_builder = config_builder
                 .committee(self.committee)
@@ -647,6 +678,13 @@ impl Swarm {
         handle
     }
 
+    /// Stop the node named `name` and remove it from the swarm, returning it if it was present.
+    pub fn remove_node(&mut self, name: &AuthorityName) -> Option<Node> {
+        let node = self.nodes.remove(name)?;
+        node.stop();
+        Some(node)
+    }
+
     pub fn get_fullnode_config_builder(&self) -> FullnodeConfigBuilder {
         self.fullnode_config_builder.clone()
     }
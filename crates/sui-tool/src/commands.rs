@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::db_tool::{DbToolCommand, execute_db_tool_command, print_db_all_tables};
+use crate::object_export::{ExportFormat, export_objects};
 use crate::{
     ConciseObjectOutput, GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
     check_completed_snapshot, download_formal_snapshot, get_latest_available_epoch, get_object,
@@ -15,6 +16,7 @@ use futures::future::join_all;
 use std::path::PathBuf;
 use std::{collections::BTreeMap, env, sync::Arc};
 use sui_config::genesis::Genesis;
+use sui_core::authority::execution_time_observation_export::read_execution_time_observations;
 use sui_core::authority_client::AuthorityAPI;
 use sui_protocol_config::Chain;
 use sui_replay::{ReplayToolCommand, execute_replay_command};
@@ -165,6 +167,40 @@ pub enum ToolCommand {
         verbose: bool,
     },
 
+    /// Export the live object set of a node database for offline analysis. Objects are written to
+    /// one sub-directory of the output directory per type (ignoring type parameters), with columns
+    /// for the object's ID, version, digest, type, owner, previous transaction, raw BCS contents,
+    /// and contents decoded as JSON. The database must not be in use by a running node.
+    ///
+    /// Only the state at the database's highest executed checkpoint can be exported. To export an
+    /// earlier epoch, restore its formal snapshot with `download-formal-snapshot` first.
+    #[command(name = "export-objects")]
+    ExportObjects {
+        /// Path to the node database (the directory containing `store` and `checkpoints`).
+        #[arg(long = "db-path")]
+        db_path: PathBuf,
+
+        /// Fail unless the database's live object set is at this checkpoint.
+        #[arg(long = "at-checkpoint")]
+        at_checkpoint: Option<u64>,
+
+        #[arg(long = "format", value_enum, default_value_t = ExportFormat::Parquet)]
+        format: ExportFormat,
+
+        /// Directory to write the exported files to, created if it does not exist.
+        #[arg(long = "output-dir")]
+        output_dir: PathBuf,
+
+        /// Maximum number of rows in a single output file.
+        #[arg(long = "rows-per-file", default_value_t = 100_000)]
+        rows_per_file: usize,
+
+        /// Approximate number of bytes of rows to hold in memory across all object types before
+        /// writing the largest type out early.
+        #[arg(long = "max-buffer-bytes", default_value_t = 512 << 20)]
+        max_buffer_bytes: usize,
+    },
+
     #[command(name = "dump-validators")]
     DumpValidators {
         #[arg(long = "genesis")]
@@ -183,6 +219,13 @@ pub enum ToolCommand {
         genesis: PathBuf,
     },
 
+    /// Verify and print a signed execution time observation file exported by a validator.
+    #[command(name = "dump-execution-time-observations")]
+    DumpExecutionTimeObservations {
+        #[arg(long = "path")]
+        path: PathBuf,
+    },
+
     /// Fetch authenticated checkpoint information at a specific sequence number.
     /// If sequence number is not specified, get the latest authenticated checkpoint.
     #[command(name = "fetch-checkpoint")]
@@ -531,6 +574,38 @@ impl ToolCommand {
 
                 sui_package_dump::dump(rpc_url, output_dir, before_checkpoint).await?;
             }
+            ToolCommand::ExportObjects {
+                db_path,
+                at_checkpoint,
+                format,
+                output_dir,
+                rows_per_file,
+                max_buffer_bytes,
+            } => {
+                let summary = export_objects(
+                    &db_path,
+                    at_checkpoint,
+                    format,
+                    &output_dir,
+                    rows_per_file,
+                    max_buffer_bytes,
+                )
+                .await?;
+                println!(
+                    "Exported {} objects at checkpoint {} to {} files across {} types in {}",
+                    summary.objects,
+                    summary.checkpoint,
+                    summary.files,
+                    summary.partitions,
+                    output_dir.display()
+                );
+                if summary.undecoded > 0 {
+                    println!(
+                        "{} objects could not be decoded as JSON (their json column is null)",
+                        summary.undecoded
+                    );
+                }
+            }
             ToolCommand::DumpValidators { genesis, concise } => {
                 let genesis = Genesis::load(genesis).unwrap();
                 if !concise {
@@ -553,6 +628,17 @@ impl ToolCommand {
                 let genesis = Genesis::load(genesis)?;
                 println!("{:#?}", genesis);
             }
+            ToolCommand::DumpExecutionTimeObservations { path } => {
+                let signed = read_execution_time_observations(&path)?;
+                println!(
+                    "Signed by {} in epoch {}",
+                    signed.auth_sig().authority,
+                    signed.epoch()
+                );
+                for (key, duration) in &signed.data().observations {
+                    println!("{key:?}: {duration:?}");
+                }
+            }
             ToolCommand::FetchCheckpoint {
                 sequence_number,
                 fullnode_rpc_url,
@@ -8,14 +8,18 @@ use anyhow::{anyhow, bail};
 use clap::Parser;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use sui_core::authority::AuthorityState;
 use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
 use sui_core::authority::authority_store_pruner::PrunerWatermarks;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::epoch_start_configuration::EpochStartConfigTrait;
 use sui_core::checkpoints::CheckpointStore;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{EpochId, ObjectID};
-use sui_types::digests::{CheckpointContentsDigest, TransactionDigest};
+use sui_types::digests::{ChainIdentifier, CheckpointContentsDigest, TransactionDigest};
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber};
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 #[cfg(not(tidehunter))]
 use typed_store::rocks::MetricConf;
 use typed_store::rocks::safe_drop_db;
@@ -43,6 +47,7 @@ pub enum DbToolCommand {
     PruneObjects,
     PruneCheckpoints,
     SetCheckpointWatermark(SetCheckpointWatermarkOptions),
+    PreviewAdvanceEpoch(PreviewAdvanceEpochOptions),
 }
 
 #[derive(Parser)]
@@ -179,6 +184,15 @@ pub struct SetCheckpointWatermarkOptions {
     highest_synced: Option<CheckpointSequenceNumber>,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct PreviewAdvanceEpochOptions {
+    /// The buffer stake to evaluate the upgrade with, in basis points. Defaults to the override
+    /// set on the validator, if any, or to the current protocol config's value.
+    #[arg(long)]
+    buffer_stake_bps: Option<u64>,
+}
+
 pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
@@ -234,6 +248,7 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             Ok(())
         }
         DbToolCommand::SetCheckpointWatermark(d) => set_checkpoint_watermark(&db_path, d),
+        DbToolCommand::PreviewAdvanceEpoch(d) => preview_advance_epoch(&db_path, d),
     }
 }
 
@@ -499,3 +514,99 @@ pub fn set_checkpoint_watermark(
     }
     Ok(())
 }
+
+/// Prints the protocol version and system packages that the end-of-epoch transaction would upgrade
+/// to, if the epoch ended now, given the capabilities this validator has collected so far.
+/// Run with (for example):
+/// cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live preview-advance-epoch
+pub fn preview_advance_epoch(
+    path: &Path,
+    options: PreviewAdvanceEpochOptions,
+) -> anyhow::Result<()> {
+    let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None, None);
+    let Some(epoch_start_config) = perpetual_db.get_epoch_start_configuration()? else {
+        bail!("Epoch start configuration not found");
+    };
+    let system_state = epoch_start_config.epoch_start_state();
+    let epoch = system_state.epoch();
+    let current_protocol_version = system_state.protocol_version();
+    let committee = system_state.get_sui_committee();
+
+    let checkpoint_db = CheckpointStore::new(
+        &path.join("checkpoints"),
+        Arc::new(PrunerWatermarks::default()),
+    );
+    let Some(genesis) = checkpoint_db.get_checkpoint_by_sequence_number(0)? else {
+        bail!("Genesis checkpoint not found, cannot determine the chain");
+    };
+    let chain = ChainIdentifier::from(*genesis.digest()).chain();
+    let protocol_config = ProtocolConfig::get_for_version(current_protocol_version, chain);
+
+    let epoch_tables = AuthorityEpochTables::open(epoch, &path.join("store"), None);
+    let capabilities = epoch_tables.get_capabilities_v2()?;
+    let buffer_stake_bps = match options.buffer_stake_bps {
+        Some(bps) => bps,
+        None => epoch_tables
+            .get_override_protocol_upgrade_buffer_stake()?
+            .unwrap_or_else(|| protocol_config.buffer_stake_for_protocol_upgrade_bps()),
+    };
+
+    let preview = AuthorityState::preview_protocol_upgrade(
+        current_protocol_version,
+        &protocol_config,
+        &committee,
+        capabilities,
+        buffer_stake_bps,
+    );
+
+    println!("Epoch: {epoch}");
+    println!(
+        "Current protocol version: {}",
+        preview.current_protocol_version.as_u64()
+    );
+    println!(
+        "Next protocol version: {}",
+        preview.next_protocol_version.as_u64()
+    );
+    if preview.next_epoch_system_packages.is_empty() {
+        println!("System packages: unchanged");
+    } else {
+        println!("System packages:");
+        for package in &preview.next_epoch_system_packages {
+            println!("  {package:?}");
+        }
+    }
+    println!(
+        "Buffer stake: {} bps, quorum threshold: {}, effective threshold: {} of {}",
+        preview.buffer_stake_bps,
+        preview.quorum_threshold,
+        preview.effective_threshold,
+        committee.total_votes(),
+    );
+
+    for (version, votes) in &preview.votes {
+        println!("\nVotes for protocol version {}:", version.as_u64());
+        if votes.is_empty() {
+            println!("  none");
+        }
+        for vote in votes {
+            println!(
+                "  stake {} ({}): protocol config digest {}",
+                vote.stake,
+                if vote.stake >= preview.effective_threshold {
+                    "passes"
+                } else {
+                    "short"
+                },
+                vote.protocol_config_digest,
+            );
+            let authorities: Vec<_> = vote.authorities.iter().map(|a| a.concise()).collect();
+            println!("    authorities: {authorities:?}");
+            for package in &vote.system_packages {
+                println!("    package: {package:?}");
+            }
+        }
+    }
+
+    Ok(())
+}
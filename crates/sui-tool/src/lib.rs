@@ -73,6 +73,7 @@ pub mod commands;
 pub mod db_shell;
 pub mod db_tool;
 mod formal_snapshot_util;
+pub mod object_export;
 #[cfg(all(feature = "tideconsole", not(windows)))]
 pub mod tideconsole_cmd;
 
@@ -0,0 +1,384 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use arrow_array::builder::{BinaryBuilder, StringBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use async_trait::async_trait;
+use clap::ValueEnum;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveTypeLayout;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use sui_core::authority::authority_store_pruner::PrunerWatermarks;
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::checkpoints::CheckpointStore;
+use sui_json_rpc_types::SuiMoveStruct;
+use sui_package_resolver::{Package, PackageStore, PackageStoreWithLruCache, Resolver};
+use sui_types::object::bounded_visitor::BoundedVisitor;
+use sui_types::object::{Data, Object, Owner};
+use sui_types::storage::ObjectStore;
+use tracing::info;
+
+/// Partition that packages are exported to.
+const PACKAGE_PARTITION: &str = "package";
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Parquet,
+}
+
+/// What an export wrote, for reporting back to the user.
+#[derive(Debug, Default)]
+pub struct ExportSummary {
+    pub checkpoint: u64,
+    pub objects: u64,
+    pub undecoded: u64,
+    pub partitions: usize,
+    pub files: usize,
+}
+
+/// Export the live object set of the node database at `db_path` to `output_dir`, one directory
+/// per object type (ignoring type parameters), split into files of at most `rows_per_file` rows.
+///
+/// Objects are streamed out of the database, and at most about `max_buffer_bytes` of rows are
+/// held in memory across all types: once the buffers grow past that, the type with the most
+/// buffered rows is written out to its next file early.
+///
+/// The live object set only reflects the highest executed checkpoint, so `at_checkpoint`, if set,
+/// must match it. To export the state at the end of an earlier epoch, restore a formal snapshot
+/// for that epoch first.
+pub async fn export_objects(
+    db_path: &Path,
+    at_checkpoint: Option<u64>,
+    format: ExportFormat,
+    output_dir: &Path,
+    rows_per_file: usize,
+    max_buffer_bytes: usize,
+) -> Result<ExportSummary> {
+    let ExportFormat::Parquet = format;
+
+    let checkpoint_store = CheckpointStore::new(
+        &db_path.join("checkpoints"),
+        Arc::new(PrunerWatermarks::default()),
+    );
+    let Some(checkpoint) = checkpoint_store.get_highest_executed_checkpoint_seq_number()? else {
+        bail!("No executed checkpoints found in {}", db_path.display());
+    };
+    if let Some(at_checkpoint) = at_checkpoint
+        && at_checkpoint != checkpoint
+    {
+        bail!(
+            "The database's live object set is at checkpoint {checkpoint}, not {at_checkpoint}. \
+             Restore a snapshot taken at the requested checkpoint to export it."
+        );
+    }
+
+    let perpetual = Arc::new(AuthorityPerpetualTables::open(
+        &db_path.join("store"),
+        None,
+        None,
+    ));
+    let resolver = Resolver::new(PackageStoreWithLruCache::new(PerpetualPackageStore(
+        perpetual.clone(),
+    )));
+
+    fs::create_dir_all(output_dir)?;
+    let mut partitions = Partitions::new(output_dir, rows_per_file, max_buffer_bytes);
+    let mut summary = ExportSummary {
+        checkpoint,
+        ..Default::default()
+    };
+
+    info!("Exporting live object set at checkpoint {checkpoint}");
+    for object in perpetual.iter_live_object_set(/* include_wrapped_object */ false) {
+        let LiveObject::Normal(object) = object else {
+            continue;
+        };
+
+        let (partition, json) = match object.struct_tag() {
+            Some(tag) => {
+                let json = decode_json(&resolver, &tag, &object).await;
+                if json.is_none() {
+                    summary.undecoded += 1;
+                }
+                (struct_partition(&tag), json)
+            }
+            None => (PACKAGE_PARTITION.to_owned(), None),
+        };
+
+        summary.objects += 1;
+        partitions.push(partition, &object, json)?;
+    }
+
+    (summary.partitions, summary.files) = partitions.finish()?;
+    Ok(summary)
+}
+
+/// Directory name for objects of type `tag`, shared by all instantiations of a generic type.
+fn struct_partition(tag: &StructTag) -> String {
+    format!(
+        "{}-{}-{}",
+        tag.address.to_canonical_string(/* with_prefix */ true),
+        tag.module,
+        tag.name
+    )
+}
+
+/// Decode `object`'s contents as JSON, if its type's layout can be resolved from the packages in
+/// the database.
+async fn decode_json<S: PackageStore>(
+    resolver: &Resolver<S>,
+    tag: &StructTag,
+    object: &Object,
+) -> Option<String> {
+    let contents = object.data.try_as_move()?.contents();
+    let MoveTypeLayout::Struct(layout) = resolver
+        .type_layout(TypeTag::Struct(Box::new(tag.clone())))
+        .await
+        .ok()?
+    else {
+        return None;
+    };
+
+    let move_struct = BoundedVisitor::deserialize_struct(contents, &layout).ok()?;
+    Some(SuiMoveStruct::from(move_struct).to_json_value().to_string())
+}
+
+struct PerpetualPackageStore(Arc<AuthorityPerpetualTables>);
+
+#[async_trait]
+impl PackageStore for PerpetualPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        let object = self
+            .0
+            .get_object(&id.into())
+            .ok_or(sui_package_resolver::error::Error::PackageNotFound(id))?;
+        Ok(Arc::new(Package::read_from_object(&object)?))
+    }
+}
+
+/// The partitions of an export, and how much memory their buffered rows take up.
+struct Partitions {
+    output_dir: PathBuf,
+    partitions: BTreeMap<String, Partition>,
+    rows_per_file: usize,
+    max_buffer_bytes: usize,
+    buffered_bytes: usize,
+}
+
+impl Partitions {
+    fn new(output_dir: &Path, rows_per_file: usize, max_buffer_bytes: usize) -> Self {
+        Self {
+            output_dir: output_dir.to_owned(),
+            partitions: BTreeMap::new(),
+            rows_per_file,
+            max_buffer_bytes,
+            buffered_bytes: 0,
+        }
+    }
+
+    /// Buffer `object` in `partition`, writing out a file if the partition is full or the
+    /// buffers have grown past their budget.
+    fn push(&mut self, partition: String, object: &Object, json: Option<String>) -> Result<()> {
+        let output_dir = &self.output_dir;
+        let partition = self
+            .partitions
+            .entry(partition)
+            .or_insert_with_key(|name| Partition::new(output_dir.join(name)));
+        self.buffered_bytes += partition.push(object, json);
+        if partition.rows.len >= self.rows_per_file {
+            self.buffered_bytes -= partition.flush()?;
+        }
+
+        if self.buffered_bytes > self.max_buffer_bytes
+            && let Some(largest) = self.partitions.values_mut().max_by_key(|p| p.rows.bytes)
+        {
+            self.buffered_bytes -= largest.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write out the remaining rows, returning the number of partitions and files written.
+    fn finish(mut self) -> Result<(usize, usize)> {
+        let mut files = 0;
+        for partition in self.partitions.values_mut() {
+            partition.flush()?;
+            files += partition.files;
+        }
+        Ok((self.partitions.len(), files))
+    }
+}
+
+/// Rows buffered for one partition, and how many files it has written so far.
+struct Partition {
+    dir: PathBuf,
+    rows: Rows,
+    files: usize,
+}
+
+#[derive(Default)]
+struct Rows {
+    len: usize,
+    /// Approximate size of the buffered values.
+    bytes: usize,
+    object_id: StringBuilder,
+    version: UInt64Builder,
+    digest: StringBuilder,
+    type_: StringBuilder,
+    owner_type: StringBuilder,
+    owner: StringBuilder,
+    previous_transaction: StringBuilder,
+    bcs: BinaryBuilder,
+    json: StringBuilder,
+}
+
+impl Partition {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            rows: Rows::default(),
+            files: 0,
+        }
+    }
+
+    /// Buffer a row for `object`, returning roughly how many bytes it takes up.
+    fn push(&mut self, object: &Object, json: Option<String>) -> usize {
+        let object_id = object.id().to_string();
+        let digest = object.digest().to_string();
+        let type_ = object.type_().map(|t| t.to_string());
+        let owner_type = owner_type(&object.owner);
+        let owner = object.owner.get_owner_address().ok().map(|a| a.to_string());
+        let previous_transaction = object.previous_transaction.to_string();
+        let bcs = match &object.data {
+            Data::Move(o) => o.contents().to_vec(),
+            Data::Package(p) => bcs::to_bytes(p).unwrap(),
+        };
+
+        let bytes = object_id.len()
+            + std::mem::size_of::<u64>()
+            + digest.len()
+            + type_.as_ref().map_or(0, String::len)
+            + owner_type.len()
+            + owner.as_ref().map_or(0, String::len)
+            + previous_transaction.len()
+            + bcs.len()
+            + json.as_ref().map_or(0, String::len);
+
+        let rows = &mut self.rows;
+        rows.len += 1;
+        rows.bytes += bytes;
+        rows.object_id.append_value(object_id);
+        rows.version.append_value(object.version().value());
+        rows.digest.append_value(digest);
+        rows.type_.append_option(type_);
+        rows.owner_type.append_value(owner_type);
+        rows.owner.append_option(owner);
+        rows.previous_transaction.append_value(previous_transaction);
+        rows.bcs.append_value(bcs);
+        rows.json.append_option(json);
+        bytes
+    }
+
+    /// Write the buffered rows out as the partition's next file, returning how many bytes of
+    /// buffered rows that released.
+    fn flush(&mut self) -> Result<usize> {
+        if self.rows.len == 0 {
+            return Ok(0);
+        }
+
+        let mut rows = std::mem::take(&mut self.rows);
+        let columns: [(&str, ArrayRef); 9] = [
+            ("object_id", Arc::new(rows.object_id.finish())),
+            ("version", Arc::new(rows.version.finish())),
+            ("digest", Arc::new(rows.digest.finish())),
+            ("type", Arc::new(rows.type_.finish())),
+            ("owner_type", Arc::new(rows.owner_type.finish())),
+            ("owner", Arc::new(rows.owner.finish())),
+            (
+                "previous_transaction",
+                Arc::new(rows.previous_transaction.finish()),
+            ),
+            ("bcs", Arc::new(rows.bcs.finish())),
+            ("json", Arc::new(rows.json.finish())),
+        ];
+        let batch = RecordBatch::try_from_iter(columns)?;
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("part-{:05}.parquet", self.files));
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(File::create(path)?, batch.schema(), Some(properties))?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        self.files += 1;
+        Ok(rows.bytes)
+    }
+}
+
+fn owner_type(owner: &Owner) -> &'static str {
+    match owner {
+        Owner::AddressOwner(_) => "address",
+        Owner::ObjectOwner(_) => "object",
+        Owner::Shared { .. } => "shared",
+        Owner::Immutable => "immutable",
+        Owner::ConsensusAddressOwner { .. } => "consensus_address",
+        Owner::Party { .. } => "party",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::ObjectID;
+
+    fn files_in(dir: &Path) -> usize {
+        fs::read_dir(dir).map_or(0, |entries| entries.count())
+    }
+
+    #[test]
+    fn test_partitions_flush_when_full_or_over_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let object = || Object::immutable_with_id_for_testing(ObjectID::random());
+
+        // Files are cut at `rows_per_file` rows.
+        let mut partitions = Partitions::new(dir.path(), 2, usize::MAX);
+        for _ in 0..5 {
+            partitions.push("a".to_owned(), &object(), None).unwrap();
+        }
+        assert_eq!(partitions.partitions["a"].files, 2);
+        assert_eq!(partitions.finish().unwrap(), (1, 3));
+        assert_eq!(files_in(&dir.path().join("a")), 3);
+
+        // Once the buffers outgrow their budget, the largest partition is written out early,
+        // and the budget is released again.
+        let dir = tempfile::tempdir().unwrap();
+        let row_bytes = Partition::new(dir.path().join("probe")).push(&object(), None);
+        let mut partitions = Partitions::new(dir.path(), usize::MAX, row_bytes * 7 / 2);
+        for _ in 0..3 {
+            partitions.push("a".to_owned(), &object(), None).unwrap();
+        }
+        assert_eq!(files_in(&dir.path().join("a")), 0);
+
+        partitions.push("b".to_owned(), &object(), None).unwrap();
+        assert_eq!(files_in(&dir.path().join("a")), 1);
+        assert_eq!(files_in(&dir.path().join("b")), 0);
+        assert_eq!(partitions.partitions["a"].rows.len, 0);
+        assert_eq!(
+            partitions.buffered_bytes,
+            partitions.partitions["b"].rows.bytes
+        );
+        assert_eq!(partitions.finish().unwrap(), (2, 2));
+    }
+}
@@ -13,24 +13,35 @@ use move_binary_format::CompiledModule;
 use move_binary_format::binary_config::BinaryConfig;
 use move_binary_format::file_format::SignatureToken;
 use move_core_types::ident_str;
-use move_core_types::identifier::Identifier;
+use move_core_types::identifier::{IdentStr, Identifier};
 use move_core_types::language_storage::{StructTag, TypeTag};
-use sui_json::{ResolvedCallArg, SuiJsonValue, is_receiving_argument, resolve_move_function_args};
+use sui_json::{
+    ResolvedCallArg, SuiJsonValue, is_receiving_argument, resolve_move_function_args,
+    well_known_object_arg,
+};
 use sui_json_rpc_types::{RPCTransactionRequestParams, SuiTypeTag};
 use sui_types::base_types::{
     FullObjectRef, ObjectID, ObjectInfo, ObjectRef, ObjectType, SuiAddress,
 };
 use sui_types::error::UserInputError;
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GAS, GasCoin};
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
+use sui_types::move_package::{PACKAGE_MODULE_NAME, UpgradeCap};
 use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::randomness_state::is_mutable_random;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, SharedObjectMutability,
-    TransactionData, TransactionKind,
+    Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    SharedObjectMutability, TransactionData, TransactionKind,
+};
+use sui_types::{
+    SUI_CLOCK_OBJECT_ID, SUI_DENY_LIST_OBJECT_ID, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_PACKAGE_ID,
+    SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_PACKAGE_ID, coin, fp_ensure,
 };
-use sui_types::{SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID, coin, fp_ensure};
+
+/// The DeepBook (v3) module that defines pools and their swap functions.
+const DEEPBOOK_POOL_MODULE_NAME: &IdentStr = ident_str!("pool");
 
 #[async_trait]
 pub trait DataReader {
@@ -48,6 +59,108 @@ pub trait DataReader {
 #[derive(Clone)]
 pub struct TransactionBuilder(Arc<dyn DataReader + Sync + Send>);
 
+/// An update to the deny list of a regulated coin, authorized by the coin's deny cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenyListAction {
+    /// Deny the address from using the coin.
+    Add(SuiAddress),
+    /// Allow a previously denied address to use the coin again.
+    Remove(SuiAddress),
+    /// Deny every address from using the coin. Requires a `DenyCapV2` that allows global pause.
+    EnableGlobalPause,
+    /// Lift a global pause.
+    DisableGlobalPause,
+}
+
+/// Version of the deny cap regulating a coin, which decides the deny list functions that apply
+/// to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenyCapVersion {
+    /// `DenyCap`: denials apply immediately, and the coin cannot be paused globally.
+    V1,
+    /// `DenyCapV2`: denials and global pauses take effect from the next epoch.
+    V2,
+}
+
+/// A change to the upgrade policy of a package, authorized by the package's `UpgradeCap`. None of
+/// these can be undone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeCapAction {
+    /// Only allow upgrades that add new code, without changing existing function bodies.
+    OnlyAdditiveUpgrades,
+    /// Only allow upgrades that change dependencies.
+    OnlyDepUpgrades,
+    /// Destroy the `UpgradeCap`, so the package can never be upgraded again.
+    MakeImmutable,
+}
+
+/// A DeepBook (v3) pool that trades `coin_type` against SUI, used to convert a coin into SUI to
+/// pay for gas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasSwapRoute {
+    /// The DeepBook package to call the pool's swap and quote functions from.
+    pub package: ObjectID,
+    pub pool: ObjectID,
+    /// The coin that is swapped for SUI.
+    pub coin_type: TypeTag,
+    /// The type of the DEEP token, which DeepBook charges its fees in. Only pools that do not
+    /// charge DEEP fees can be used, as the sender is not expected to hold any.
+    pub deep_type: TypeTag,
+    /// Whether SUI is the pool's base asset (and `coin_type` its quote asset), or the other way
+    /// around.
+    pub sui_is_base: bool,
+}
+
+/// The result of quoting a swap along a [`GasSwapRoute`], with
+/// [`TransactionBuilder::gas_swap_quote_tx_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasSwapQuote {
+    /// The amount of the route's coin that is swapped.
+    pub amount_in: u64,
+    /// The amount of SUI the swap is expected to produce.
+    pub sui_out: u64,
+    /// The amount of DEEP the pool would charge in fees.
+    pub deep_required: u64,
+}
+
+impl GasSwapRoute {
+    fn type_args(&self) -> Vec<TypeTag> {
+        if self.sui_is_base {
+            vec![GAS::type_tag(), self.coin_type.clone()]
+        } else {
+            vec![self.coin_type.clone(), GAS::type_tag()]
+        }
+    }
+
+    /// Interpret the values returned by the quote call built by
+    /// [`TransactionBuilder::gas_swap_quote_tx_kind`]: the base quantity out, the quote quantity
+    /// out and the DEEP required.
+    pub fn quote(
+        &self,
+        amount_in: u64,
+        (base_out, quote_out, deep): (u64, u64, u64),
+    ) -> GasSwapQuote {
+        GasSwapQuote {
+            amount_in,
+            sui_out: if self.sui_is_base {
+                base_out
+            } else {
+                quote_out
+            },
+            deep_required: deep,
+        }
+    }
+}
+
+impl GasSwapQuote {
+    /// The least SUI the swap may produce without exceeding `slippage_bps` (in basis points) of
+    /// slippage relative to the quote.
+    pub fn min_sui_out(&self, slippage_bps: u16) -> u64 {
+        let slippage_bps = slippage_bps.min(10_000) as u128;
+        (self.sui_out as u128 * (10_000 - slippage_bps) / 10_000) as u64
+    }
+}
+
 impl TransactionBuilder {
     pub fn new(data_reader: Arc<dyn DataReader + Sync + Send>) -> Self {
         Self(data_reader)
@@ -196,6 +309,88 @@ impl TransactionBuilder {
         )
     }
 
+    /// Pay exactly `amount` of `coin_type` from `sender` to `recipient`, selecting as many of the
+    /// sender's coins as it takes. The change stays with the sender. SUI is paid out of the gas
+    /// coin.
+    pub async fn pay_exact_tx_kind(
+        &self,
+        sender: SuiAddress,
+        coin_type: TypeTag,
+        amount: u64,
+        recipient: SuiAddress,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        if GAS::is_gas_type(&coin_type) {
+            builder.transfer_sui(recipient, Some(amount));
+        } else {
+            let (coins, balance) = self.select_coins(sender, coin_type.clone(), amount).await?;
+            ensure!(
+                balance >= amount,
+                "Sender {sender} only has {balance} of {coin_type}, but {amount} is needed"
+            );
+            builder.pay_exact(coins, balance, recipient, amount)?;
+        }
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    pub async fn pay_exact(
+        &self,
+        sender: SuiAddress,
+        coin_type: TypeTag,
+        amount: u64,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let tx_kind = self
+            .pay_exact_tx_kind(sender, coin_type, amount, recipient)
+            .await?;
+        let input_objects = tx_kind
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sender, gas, gas_budget, input_objects, gas_price)
+            .await?;
+
+        Ok(TransactionData::new(
+            tx_kind, sender, gas, gas_budget, gas_price,
+        ))
+    }
+
+    /// Select coins of `coin_type` owned by `owner`, until they hold at least `amount` between
+    /// them, returning them and their balance. Returns all of the owner's coins if they hold less.
+    async fn select_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        amount: u64,
+    ) -> Result<(Vec<ObjectRef>, u64), anyhow::Error> {
+        let mut coins = vec![];
+        let mut balance = 0u64;
+        for info in self
+            .0
+            .get_owned_objects(owner, coin::Coin::type_(coin_type))
+            .await?
+        {
+            let object = self.0.get_object(info.object_id).await?;
+            let Some(coin) = object.as_coin_maybe() else {
+                continue;
+            };
+            coins.push(object.compute_object_reference());
+            balance = balance.saturating_add(coin.value());
+            if balance >= amount {
+                break;
+            }
+        }
+        Ok((coins, balance))
+    }
+
     /// Get the object references for a list of object IDs
     pub async fn input_refs(&self, obj_ids: &[ObjectID]) -> Result<Vec<ObjectRef>, anyhow::Error> {
         let handles: Vec<_> = obj_ids.iter().map(|id| self.get_object_ref(*id)).collect();
@@ -953,6 +1148,380 @@ impl TransactionBuilder {
         )
     }
 
+    /// Returns the type of the coin regulated by `deny_cap`, and whether it is a `DenyCap` or a
+    /// `DenyCapV2`.
+    pub async fn deny_cap_coin_type(
+        &self,
+        deny_cap: ObjectID,
+    ) -> Result<(TypeTag, DenyCapVersion), anyhow::Error> {
+        let cap = self.0.get_object(deny_cap).await?;
+        Self::deny_cap_type(&cap)
+    }
+
+    fn deny_cap_type(cap: &Object) -> Result<(TypeTag, DenyCapVersion), anyhow::Error> {
+        let id = cap.id();
+        let cap_type = cap
+            .struct_tag()
+            .ok_or_else(|| anyhow!("Object [{id}] is not a move object."))?;
+        let version = match cap_type.name.as_str() {
+            "DenyCap" => DenyCapVersion::V1,
+            "DenyCapV2" => DenyCapVersion::V2,
+            _ => bail!("Object [{id}] is not a DenyCap or DenyCapV2, found {cap_type}"),
+        };
+        ensure!(
+            cap_type.address == SUI_FRAMEWORK_ADDRESS
+                && cap_type.module.as_ident_str() == coin::COIN_MODULE_NAME,
+            "Object [{id}] is not a DenyCap or DenyCapV2, found {cap_type}"
+        );
+        let coin_type = cap_type
+            .type_params
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("Deny cap [{id}] has no coin type"))?;
+        Ok((coin_type, version))
+    }
+
+    /// Build a transaction updating the deny list of the coin regulated by `deny_cap`. Both
+    /// `DenyCap` and `DenyCapV2` are supported, but only the latter can pause the coin globally.
+    pub async fn deny_list_tx_kind(
+        &self,
+        deny_cap: ObjectID,
+        action: DenyListAction,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let cap = self.0.get_object(deny_cap).await?;
+        let (coin_type, version) = Self::deny_cap_type(&cap)?;
+
+        let function = match (version, action) {
+            (DenyCapVersion::V2, DenyListAction::Add(_)) => "deny_list_v2_add",
+            (DenyCapVersion::V2, DenyListAction::Remove(_)) => "deny_list_v2_remove",
+            (DenyCapVersion::V2, DenyListAction::EnableGlobalPause) => {
+                "deny_list_v2_enable_global_pause"
+            }
+            (DenyCapVersion::V2, DenyListAction::DisableGlobalPause) => {
+                "deny_list_v2_disable_global_pause"
+            }
+            (DenyCapVersion::V1, DenyListAction::Add(_)) => "deny_list_add",
+            (DenyCapVersion::V1, DenyListAction::Remove(_)) => "deny_list_remove",
+            (DenyCapVersion::V1, _) => bail!(
+                "Global pause requires a DenyCapV2, use `coin::migrate_regulated_currency_to_v2` to migrate the coin first"
+            ),
+        };
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let mut arguments = vec![
+            builder.obj(self.deny_list_object_arg(true).await?)?,
+            builder.obj(ObjectArg::ImmOrOwnedObject(cap.compute_object_reference()))?,
+        ];
+        if let DenyListAction::Add(address) | DenyListAction::Remove(address) = action {
+            arguments.push(builder.pure(address)?);
+        }
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            coin::COIN_MODULE_NAME.to_owned(),
+            Identifier::new(function)?,
+            vec![coin_type],
+            arguments,
+        );
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a read-only transaction checking `addresses` against the deny list of `coin_type`,
+    /// for use with dev inspect or simulation. The deny list cannot be enumerated, so only the
+    /// given addresses are checked. See [`deny_list_check_pt`] for the results it returns.
+    pub async fn deny_list_check_tx_kind(
+        &self,
+        coin_type: TypeTag,
+        version: DenyCapVersion,
+        addresses: &[SuiAddress],
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let deny_list = self.deny_list_object_arg(false).await?;
+        Ok(TransactionKind::programmable(deny_list_check_pt(
+            deny_list, coin_type, version, addresses,
+        )?))
+    }
+
+    /// Build a transaction restricting the upgrade policy of the package governed by
+    /// `upgrade_cap`, or making it immutable.
+    pub async fn upgrade_cap_tx_kind(
+        &self,
+        upgrade_cap: ObjectID,
+        action: UpgradeCapAction,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let cap = self.0.get_object(upgrade_cap).await?;
+        let cap_type = cap
+            .struct_tag()
+            .ok_or_else(|| anyhow!("Object [{upgrade_cap}] is not a move object."))?;
+        ensure!(
+            cap_type == UpgradeCap::type_(),
+            "Object [{upgrade_cap}] is not an UpgradeCap, found {cap_type}"
+        );
+
+        let function = match action {
+            UpgradeCapAction::OnlyAdditiveUpgrades => ident_str!("only_additive_upgrades"),
+            UpgradeCapAction::OnlyDepUpgrades => ident_str!("only_dep_upgrades"),
+            UpgradeCapAction::MakeImmutable => ident_str!("make_immutable"),
+        };
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let cap = builder.obj(ObjectArg::ImmOrOwnedObject(cap.compute_object_reference()))?;
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            PACKAGE_MODULE_NAME.to_owned(),
+            function.to_owned(),
+            vec![],
+            vec![cap],
+        );
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Build a transaction calling `function`, which reads on-chain randomness through a `&Random`
+    /// parameter. The `Random` object is filled in, so `call_args` should leave it out (as well as
+    /// any other well-known system object, like the `Clock`).
+    ///
+    /// Transactions using `Random` are only executed once consensus has produced a round of
+    /// randomness after the commit that sequenced them, so they are deferred, and checkpointed
+    /// later than other transactions submitted at the same time. Only `TransferObjects` and
+    /// `MergeCoins` commands may follow the call in the same transaction, which is why the call is
+    /// built on its own.
+    pub async fn random_move_call_tx_kind(
+        &self,
+        package_object_id: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<SuiJsonValue>,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let object = self.0.get_object(package_object_id).await?;
+        let sui_types::object::Data::Package(package) = &object.data else {
+            bail!("Object [{package_object_id}] is not a package.");
+        };
+        let compiled = package
+            .deserialize_module(&Identifier::from_str(module)?, &BinaryConfig::standard())?;
+        let function_ident = Identifier::from_str(function)?;
+        let fdef = compiled
+            .function_defs
+            .iter()
+            .find(|fdef| {
+                compiled.identifier_at(compiled.function_handle_at(fdef.function).name)
+                    == function_ident.as_ident_str()
+            })
+            .ok_or_else(|| anyhow!("Could not resolve function {function} in module {module}"))?;
+        let handle = compiled.function_handle_at(fdef.function);
+        let parameters = &compiled.signature_at(handle.parameters).0;
+        ensure!(
+            !parameters
+                .iter()
+                .any(|param| is_mutable_random(&compiled, param)),
+            "{module}::{function} takes Random by value or by mutable reference, but it can only \
+             be passed as &Random"
+        );
+        ensure!(
+            parameters.iter().any(|param| {
+                well_known_object_arg(&compiled, param) == Some(SUI_RANDOMNESS_STATE_OBJECT_ID)
+            }),
+            "{module}::{function} does not take a &Random parameter"
+        );
+
+        self.move_call_tx_kind(package_object_id, module, function, type_args, call_args)
+            .await
+    }
+
+    /// The `Random` object as a transaction input. Randomness can only be read through an
+    /// immutable reference, so the input is never mutable.
+    pub async fn random_object_arg(&self) -> anyhow::Result<ObjectArg> {
+        self.shared_system_object_arg(SUI_RANDOMNESS_STATE_OBJECT_ID, false)
+            .await
+    }
+
+    /// Build a transaction that quotes swapping `amount_in` of the route's coin for SUI. It is
+    /// meant to be simulated: its single command returns the base quantity out, the quote quantity
+    /// out and the DEEP required, which [`GasSwapRoute::quote`] interprets.
+    pub async fn gas_swap_quote_tx_kind(
+        &self,
+        route: &GasSwapRoute,
+        amount_in: u64,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let pool = builder.obj(self.pool_object_arg(route.pool, false).await?)?;
+        let amount_in = builder.pure(amount_in)?;
+        let clock = builder.obj(
+            self.shared_system_object_arg(SUI_CLOCK_OBJECT_ID, false)
+                .await?,
+        )?;
+        builder.programmable_move_call(
+            route.package,
+            DEEPBOOK_POOL_MODULE_NAME.to_owned(),
+            if route.sui_is_base {
+                ident_str!("get_base_quantity_out").to_owned()
+            } else {
+                ident_str!("get_quote_quantity_out").to_owned()
+            },
+            route.type_args(),
+            vec![pool, amount_in, clock],
+        );
+        Ok(TransactionKind::programmable(builder.finish()))
+    }
+
+    /// Extend `tx_kind` so that its gas, paid by `sponsor`, is paid back by `sender` in the route's
+    /// coin. `amount_in` of the sender's coins is swapped for at least `min_sui_out` SUI,
+    /// `reimbursement` of which is sent to the sponsor, and the rest to the sender.
+    ///
+    /// Gas is reserved before a transaction executes, so SUI produced by a transaction can never
+    /// pay for that same transaction: a sender without SUI needs a sponsor to front the gas. The
+    /// reimbursement is typically the gas budget, the most the sponsor can be charged.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn pay_gas_with_coin_tx_kind(
+        &self,
+        tx_kind: TransactionKind,
+        sender: SuiAddress,
+        sponsor: SuiAddress,
+        route: &GasSwapRoute,
+        amount_in: u64,
+        min_sui_out: u64,
+        reimbursement: u64,
+    ) -> Result<TransactionKind, anyhow::Error> {
+        let TransactionKind::ProgrammableTransaction(mut pt) = tx_kind else {
+            bail!("Only programmable transactions can pay for gas with a coin");
+        };
+        ensure!(
+            sponsor != sender,
+            "Paying for gas with a coin requires a gas sponsor"
+        );
+        ensure!(
+            !pt.commands.iter().any(|cmd| cmd.is_gas_coin_used()),
+            "The transaction uses the gas coin, which belongs to the sponsor"
+        );
+        ensure!(
+            min_sui_out >= reimbursement,
+            "The swap is only guaranteed to produce {min_sui_out} MIST, which does not cover the \
+             {reimbursement} MIST owed to the sponsor"
+        );
+
+        let (coins, balance) = self
+            .select_coins(sender, route.coin_type.clone(), amount_in)
+            .await?;
+        ensure!(
+            balance >= amount_in,
+            "Sender {sender} only has {balance} of {}, but {amount_in} is needed to pay for gas",
+            route.coin_type
+        );
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let pool = builder.obj(self.pool_object_arg(route.pool, true).await?)?;
+        let clock = builder.obj(
+            self.shared_system_object_arg(SUI_CLOCK_OBJECT_ID, false)
+                .await?,
+        )?;
+
+        let coin = builder.smash_coins(coins)?;
+        let amount_in = builder.pure(amount_in)?;
+        let Argument::Result(split) = builder.command(Command::SplitCoins(coin, vec![amount_in]))
+        else {
+            unreachable!("Commands always produce a result");
+        };
+        let coin_in = Argument::NestedResult(split, 0);
+
+        let deep = builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            coin::COIN_MODULE_NAME.to_owned(),
+            ident_str!("zero").to_owned(),
+            vec![route.deep_type.clone()],
+            vec![],
+        );
+        let min_sui_out = builder.pure(min_sui_out)?;
+        let Argument::Result(swap) = builder.programmable_move_call(
+            route.package,
+            DEEPBOOK_POOL_MODULE_NAME.to_owned(),
+            if route.sui_is_base {
+                ident_str!("swap_exact_quote_for_base").to_owned()
+            } else {
+                ident_str!("swap_exact_base_for_quote").to_owned()
+            },
+            route.type_args(),
+            vec![pool, coin_in, deep, min_sui_out, clock],
+        ) else {
+            unreachable!("Commands always produce a result");
+        };
+
+        let (sui_out, coin_left) = if route.sui_is_base {
+            (
+                Argument::NestedResult(swap, 0),
+                Argument::NestedResult(swap, 1),
+            )
+        } else {
+            (
+                Argument::NestedResult(swap, 1),
+                Argument::NestedResult(swap, 0),
+            )
+        };
+
+        let reimbursement = builder.pure(reimbursement)?;
+        let Argument::Result(owed) =
+            builder.command(Command::SplitCoins(sui_out, vec![reimbursement]))
+        else {
+            unreachable!("Commands always produce a result");
+        };
+        builder.transfer_arg(sponsor, Argument::NestedResult(owed, 0));
+        builder.transfer_args(sender, vec![sui_out, coin_left]);
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            coin::COIN_MODULE_NAME.to_owned(),
+            ident_str!("destroy_zero").to_owned(),
+            vec![route.deep_type.clone()],
+            vec![Argument::NestedResult(swap, 2)],
+        );
+
+        append_programmable_transaction(&mut pt, builder.finish())?;
+        Ok(TransactionKind::ProgrammableTransaction(pt))
+    }
+
+    async fn pool_object_arg(&self, pool: ObjectID, mutable: bool) -> anyhow::Result<ObjectArg> {
+        let object = self.0.get_object(pool).await?;
+        let Owner::Shared {
+            initial_shared_version,
+        } = object.owner()
+        else {
+            bail!("Pool [{pool}] is not shared");
+        };
+        Ok(ObjectArg::SharedObject {
+            id: pool,
+            initial_shared_version: *initial_shared_version,
+            mutability: if mutable {
+                SharedObjectMutability::Mutable
+            } else {
+                SharedObjectMutability::Immutable
+            },
+        })
+    }
+
+    async fn deny_list_object_arg(&self, mutable: bool) -> anyhow::Result<ObjectArg> {
+        self.shared_system_object_arg(SUI_DENY_LIST_OBJECT_ID, mutable)
+            .await
+    }
+
+    async fn shared_system_object_arg(
+        &self,
+        id: ObjectID,
+        mutable: bool,
+    ) -> anyhow::Result<ObjectArg> {
+        let object = self.0.get_object(id).await?;
+        let Owner::Shared {
+            initial_shared_version,
+        } = object.owner()
+        else {
+            bail!("System object [{id}] is not shared");
+        };
+        Ok(ObjectArg::SharedObject {
+            id,
+            initial_shared_version: *initial_shared_version,
+            mutability: if mutable {
+                SharedObjectMutability::Mutable
+            } else {
+                SharedObjectMutability::Immutable
+            },
+        })
+    }
+
     // TODO: we should add retrial to reduce the transaction building error rate
     pub async fn get_object_ref(&self, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
         self.get_object_ref_and_type(object_id)
@@ -987,3 +1556,313 @@ impl TransactionBuilder {
         Ok((full_object_ref, object_type))
     }
 }
+
+/// Append `other`'s inputs and commands to `pt`, re-indexing the arguments of its commands. A
+/// transaction can only refer to each object once, so shared objects that are inputs to both are
+/// shared between them (taken mutably if either needs them to be), and owned objects that are
+/// inputs to both are rejected.
+fn append_programmable_transaction(
+    pt: &mut ProgrammableTransaction,
+    other: ProgrammableTransaction,
+) -> anyhow::Result<()> {
+    let mut inputs = Vec::with_capacity(other.inputs.len());
+    for input in other.inputs {
+        let existing = match &input {
+            CallArg::Object(arg) => pt
+                .inputs
+                .iter()
+                .position(|i| matches!(i, CallArg::Object(a) if a.id() == arg.id()))
+                .map(|i| (i, arg.id())),
+            _ => None,
+        };
+
+        let index = if let Some((i, id)) = existing {
+            let (
+                CallArg::Object(ObjectArg::SharedObject {
+                    mutability: needed, ..
+                }),
+                CallArg::Object(ObjectArg::SharedObject { mutability, .. }),
+            ) = (&input, &mut pt.inputs[i])
+            else {
+                bail!("The transaction already uses object [{id}]");
+            };
+            if *needed == SharedObjectMutability::Mutable {
+                *mutability = SharedObjectMutability::Mutable;
+            }
+            i
+        } else {
+            pt.inputs.push(input);
+            pt.inputs.len() - 1
+        };
+
+        inputs.push(u16::try_from(index).map_err(|_| anyhow!("Too many transaction inputs"))?);
+    }
+
+    let results = u16::try_from(pt.commands.len()).map_err(|_| anyhow!("Too many commands"))?;
+    for mut command in other.commands {
+        let arguments: Vec<&mut Argument> = match &mut command {
+            Command::MoveCall(call) => call.arguments.iter_mut().collect(),
+            Command::TransferObjects(objects, recipient) => {
+                objects.iter_mut().chain([recipient]).collect()
+            }
+            Command::SplitCoins(coin, amounts) => [coin].into_iter().chain(amounts).collect(),
+            Command::MergeCoins(coin, coins) => [coin].into_iter().chain(coins).collect(),
+            Command::MakeMoveVec(_, elements) => elements.iter_mut().collect(),
+            Command::Upgrade(_, _, _, ticket) => vec![ticket],
+            Command::Publish(_, _) => vec![],
+        };
+
+        for argument in arguments {
+            match argument {
+                Argument::GasCoin => {}
+                Argument::Input(i) => *i = inputs[*i as usize],
+                Argument::Result(i) | Argument::NestedResult(i, _) => *i += results,
+            }
+        }
+
+        pt.commands.push(command);
+    }
+
+    Ok(())
+}
+
+/// Calls checking `addresses` against the deny list of `coin_type`, through the functions that
+/// apply to coins regulated by a deny cap of `version`. Every call returns a `bool`:
+/// - V1: for every address, in order, whether it is denied. Denials apply immediately.
+/// - V2: for every address, in order, whether it is denied in the current and in the next
+///   epoch, followed by whether the coin is globally paused in the current and in the next
+///   epoch.
+pub fn deny_list_check_pt(
+    deny_list: ObjectArg,
+    coin_type: TypeTag,
+    version: DenyCapVersion,
+    addresses: &[SuiAddress],
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let deny_list = builder.obj(deny_list)?;
+    let call = |builder: &mut ProgrammableTransactionBuilder,
+                function: &'static IdentStr,
+                arguments: Vec<Argument>| {
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            coin::COIN_MODULE_NAME.to_owned(),
+            function.to_owned(),
+            vec![coin_type.clone()],
+            arguments,
+        );
+    };
+    for address in addresses {
+        let address = builder.pure(*address)?;
+        match version {
+            DenyCapVersion::V1 => call(
+                &mut builder,
+                ident_str!("deny_list_contains"),
+                vec![deny_list, address],
+            ),
+            DenyCapVersion::V2 => {
+                call(
+                    &mut builder,
+                    ident_str!("deny_list_v2_contains_current_epoch"),
+                    vec![deny_list, address],
+                );
+                call(
+                    &mut builder,
+                    ident_str!("deny_list_v2_contains_next_epoch"),
+                    vec![deny_list, address],
+                );
+            }
+        }
+    }
+    if version == DenyCapVersion::V2 {
+        call(
+            &mut builder,
+            ident_str!("deny_list_v2_is_global_pause_enabled_current_epoch"),
+            vec![deny_list],
+        );
+        call(
+            &mut builder,
+            ident_str!("deny_list_v2_is_global_pause_enabled_next_epoch"),
+            vec![deny_list],
+        );
+    }
+    Ok(builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::{SequenceNumber, random_object_ref};
+
+    use super::*;
+
+    fn route(sui_is_base: bool) -> GasSwapRoute {
+        GasSwapRoute {
+            package: ObjectID::random(),
+            pool: ObjectID::random(),
+            coin_type: TypeTag::from_str("0x42::usdc::USDC").unwrap(),
+            deep_type: TypeTag::from_str("0x43::deep::DEEP").unwrap(),
+            sui_is_base,
+        }
+    }
+
+    fn shared(id: ObjectID, mutability: SharedObjectMutability) -> ObjectArg {
+        ObjectArg::SharedObject {
+            id,
+            initial_shared_version: SequenceNumber::from_u64(1),
+            mutability,
+        }
+    }
+
+    #[test]
+    fn test_gas_swap_quote() {
+        let quote = route(true).quote(100, (7, 3, 1));
+        assert_eq!(quote.sui_out, 7);
+        assert_eq!(quote.deep_required, 1);
+
+        let quote = route(false).quote(100, (7, 3, 1));
+        assert_eq!(quote.sui_out, 3);
+        assert_eq!(
+            route(false).type_args(),
+            vec![route(false).coin_type, GAS::type_tag()]
+        );
+    }
+
+    #[test]
+    fn test_min_sui_out() {
+        let quote = GasSwapQuote {
+            amount_in: 1,
+            sui_out: 1_000_000,
+            deep_required: 0,
+        };
+        assert_eq!(quote.min_sui_out(0), 1_000_000);
+        assert_eq!(quote.min_sui_out(50), 995_000);
+        assert_eq!(quote.min_sui_out(10_000), 0);
+        assert_eq!(quote.min_sui_out(u16::MAX), 0);
+    }
+
+    #[test]
+    fn test_append_reindexes_arguments() {
+        let pool = ObjectID::random();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let coin = builder
+            .obj(ObjectArg::ImmOrOwnedObject(random_object_ref()))
+            .unwrap();
+        let pool_arg = builder
+            .obj(shared(pool, SharedObjectMutability::Immutable))
+            .unwrap();
+        builder.command(Command::MergeCoins(coin, vec![pool_arg]));
+        let mut pt = builder.finish();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let pool_arg = builder
+            .obj(shared(pool, SharedObjectMutability::Mutable))
+            .unwrap();
+        let amount = builder.pure(5u64).unwrap();
+        let split = builder.command(Command::SplitCoins(pool_arg, vec![amount]));
+        builder.command(Command::TransferObjects(vec![split], amount));
+        append_programmable_transaction(&mut pt, builder.finish()).unwrap();
+
+        // The pool is shared between both halves, and taken mutably because the second half needs
+        // it to be.
+        assert_eq!(pt.inputs.len(), 3);
+        assert_eq!(
+            pt.inputs[1],
+            CallArg::Object(shared(pool, SharedObjectMutability::Mutable))
+        );
+
+        assert_eq!(
+            pt.commands[1],
+            Command::SplitCoins(Argument::Input(1), vec![Argument::Input(2)])
+        );
+        assert_eq!(
+            pt.commands[2],
+            Command::TransferObjects(vec![Argument::Result(1)], Argument::Input(2))
+        );
+    }
+
+    #[test]
+    fn test_append_rejects_shared_owned_object() {
+        let coin = random_object_ref();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap();
+        let mut pt = builder.finish();
+
+        let mut other = ProgrammableTransactionBuilder::new();
+        other.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap();
+        assert!(append_programmable_transaction(&mut pt, other.finish()).is_err());
+    }
+
+    /// The deny list functions called by a deny list check, in order.
+    fn deny_list_check_functions(version: DenyCapVersion, addresses: &[SuiAddress]) -> Vec<String> {
+        let coin_type = TypeTag::from_str("0x42::regulated::REGULATED").unwrap();
+        let pt = deny_list_check_pt(
+            shared(SUI_DENY_LIST_OBJECT_ID, SharedObjectMutability::Immutable),
+            coin_type.clone(),
+            version,
+            addresses,
+        )
+        .unwrap();
+        // The deny list is taken by immutable reference, followed by one input per address.
+        assert_eq!(pt.inputs.len(), 1 + addresses.len());
+        assert_eq!(
+            pt.inputs[0],
+            CallArg::Object(shared(
+                SUI_DENY_LIST_OBJECT_ID,
+                SharedObjectMutability::Immutable
+            ))
+        );
+        pt.commands
+            .iter()
+            .map(|command| {
+                let Command::MoveCall(call) = command else {
+                    panic!("expected a move call, found {command:?}");
+                };
+                assert_eq!(call.package, SUI_FRAMEWORK_PACKAGE_ID);
+                assert_eq!(call.module, coin::COIN_MODULE_NAME.as_str());
+                assert_eq!(call.type_arguments, vec![coin_type.clone().into()]);
+                assert_eq!(call.arguments[0], Argument::Input(0));
+                call.function.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_deny_list_check_v1() {
+        let addresses = [
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        ];
+        assert_eq!(
+            deny_list_check_functions(DenyCapVersion::V1, &addresses),
+            vec!["deny_list_contains", "deny_list_contains"]
+        );
+        assert!(deny_list_check_functions(DenyCapVersion::V1, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_deny_list_check_v2() {
+        let addresses = [
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        ];
+        assert_eq!(
+            deny_list_check_functions(DenyCapVersion::V2, &addresses),
+            vec![
+                "deny_list_v2_contains_current_epoch",
+                "deny_list_v2_contains_next_epoch",
+                "deny_list_v2_contains_current_epoch",
+                "deny_list_v2_contains_next_epoch",
+                "deny_list_v2_is_global_pause_enabled_current_epoch",
+                "deny_list_v2_is_global_pause_enabled_next_epoch",
+            ]
+        );
+        assert_eq!(
+            deny_list_check_functions(DenyCapVersion::V2, &[]),
+            vec![
+                "deny_list_v2_is_global_pause_enabled_current_epoch",
+                "deny_list_v2_is_global_pause_enabled_next_epoch",
+            ]
+        );
+    }
+}
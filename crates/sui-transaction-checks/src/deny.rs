@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::policy::{DEFAULT_ACTION_RULE, PolicyDecision, evaluate_transaction_policy};
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
 use sui_config::{
     dynamic_transaction_signing_checks::DynamicCheckRunnerError,
@@ -46,6 +47,13 @@ pub fn check_transaction_for_signing(
 
     check_receiving_objects(filter_config, receiving_objects)?;
 
+    check_transaction_policy(
+        filter_config,
+        tx_data,
+        input_object_kinds,
+        receiving_objects,
+    )?;
+
     // NB: Only performed at signing time.
     dynamic_transaction_checks(
         filter_config,
@@ -124,6 +132,28 @@ fn check_receiving_objects(
     Ok(())
 }
 
+fn check_transaction_policy(
+    filter_config: &TransactionDenyConfig,
+    tx_data: &TransactionData,
+    input_object_kinds: &[InputObjectKind],
+    receiving_objects: &[ObjectRef],
+) -> SuiResult {
+    let policy = filter_config.transaction_policy();
+    if policy.is_empty() {
+        return Ok(());
+    }
+    match evaluate_transaction_policy(policy, tx_data, input_object_kinds, receiving_objects) {
+        PolicyDecision::Allow => Ok(()),
+        PolicyDecision::Deny { rule, reason } => Err(SuiErrorKind::UserInputError {
+            error: UserInputError::TransactionDeniedByPolicy {
+                rule: rule.unwrap_or(DEFAULT_ACTION_RULE).to_string(),
+                reason,
+            },
+        }
+        .into()),
+    }
+}
+
 fn check_disabled_features(
     filter_config: &TransactionDenyConfig,
     tx_data: &TransactionData,
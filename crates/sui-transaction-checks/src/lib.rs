@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod deny;
+pub mod policy;
 
 pub use checked::*;
 
@@ -0,0 +1,207 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_config::transaction_policy_config::{
+    PolicyAction, TransactionPolicy, TransactionPolicyRule,
+};
+use sui_types::{
+    base_types::ObjectRef,
+    transaction::{InputObjectKind, TransactionData, TransactionDataAPI},
+};
+
+/// Rule name reported for transactions denied by the policy's default action.
+pub const DEFAULT_ACTION_RULE: &str = "default-action";
+
+/// Outcome of evaluating a transaction against a `TransactionPolicy`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolicyDecision<'a> {
+    Allow,
+    Deny {
+        /// Name of the rule that denied the transaction, or `None` if it was denied by the
+        /// policy's default action.
+        rule: Option<&'a str>,
+        reason: String,
+    },
+}
+
+/// Evaluate the policy rules in order and return the decision of the first matching rule, or
+/// the default action if no rule matches.
+pub fn evaluate_transaction_policy<'a>(
+    policy: &'a TransactionPolicy,
+    tx_data: &TransactionData,
+    input_object_kinds: &[InputObjectKind],
+    receiving_objects: &[ObjectRef],
+) -> PolicyDecision<'a> {
+    for rule in &policy.rules {
+        let Some(matched) = match_rule(rule, tx_data, input_object_kinds, receiving_objects) else {
+            continue;
+        };
+        return match rule.action {
+            PolicyAction::Allow => PolicyDecision::Allow,
+            PolicyAction::Deny => PolicyDecision::Deny {
+                rule: Some(rule.name.as_str()),
+                reason: matched.join(", "),
+            },
+        };
+    }
+
+    match policy.default_action {
+        PolicyAction::Allow => PolicyDecision::Allow,
+        PolicyAction::Deny => PolicyDecision::Deny {
+            rule: None,
+            reason: "transaction does not match any allow rule".to_string(),
+        },
+    }
+}
+
+/// Returns a description of each criterion that matched, or `None` if the rule does not apply.
+fn match_rule(
+    rule: &TransactionPolicyRule,
+    tx_data: &TransactionData,
+    input_object_kinds: &[InputObjectKind],
+    receiving_objects: &[ObjectRef],
+) -> Option<Vec<String>> {
+    let mut matched = vec![];
+
+    if !rule.senders.is_empty() {
+        let sender = tx_data.sender();
+        if !rule.senders.contains(&sender) {
+            return None;
+        }
+        matched.push(format!("sender {sender}"));
+    }
+
+    let move_calls = tx_data.move_calls();
+
+    if !rule.packages.is_empty() {
+        let package = move_calls
+            .iter()
+            .find(|(_, package, _, _)| rule.packages.contains(*package))?;
+        matched.push(format!("package {}", package.1));
+    }
+
+    if !rule.functions.is_empty() {
+        let (_, package, module, function) =
+            move_calls.iter().find(|(_, package, module, function)| {
+                rule.functions
+                    .iter()
+                    .any(|pattern| pattern.matches(package, module, function))
+            })?;
+        matched.push(format!("function {package}::{module}::{function}"));
+    }
+
+    if !rule.objects.is_empty() {
+        let object_id = input_object_kinds
+            .iter()
+            .map(|kind| kind.object_id())
+            .chain(receiving_objects.iter().map(|(id, _, _)| *id))
+            .find(|id| rule.objects.contains(id))?;
+        matched.push(format!("object {object_id}"));
+    }
+
+    Some(matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use sui_types::base_types::{ObjectID, SuiAddress, random_object_ref};
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::TransactionData;
+
+    fn move_call_tx(sender: SuiAddress, package: ObjectID) -> TransactionData {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .move_call(
+                package,
+                "m".parse().unwrap(),
+                "f".parse().unwrap(),
+                vec![],
+                vec![],
+            )
+            .unwrap();
+        TransactionData::new_programmable(
+            sender,
+            vec![random_object_ref()],
+            builder.finish(),
+            1_000_000,
+            1_000,
+        )
+    }
+
+    fn rule(name: &str, action: PolicyAction) -> TransactionPolicyRule {
+        TransactionPolicyRule {
+            name: name.to_string(),
+            action,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let sender = SuiAddress::random_for_testing_only();
+        let package = ObjectID::random();
+        let tx = move_call_tx(sender, package);
+
+        let policy = TransactionPolicy {
+            default_action: PolicyAction::Allow,
+            rules: vec![
+                TransactionPolicyRule {
+                    senders: BTreeSet::from([sender]),
+                    ..rule("allow-sender", PolicyAction::Allow)
+                },
+                TransactionPolicyRule {
+                    packages: BTreeSet::from([package]),
+                    ..rule("deny-package", PolicyAction::Deny)
+                },
+            ],
+        };
+        assert_eq!(
+            evaluate_transaction_policy(&policy, &tx, &[], &[]),
+            PolicyDecision::Allow
+        );
+
+        let other = move_call_tx(SuiAddress::random_for_testing_only(), package);
+        assert!(matches!(
+            evaluate_transaction_policy(&policy, &other, &[], &[]),
+            PolicyDecision::Deny {
+                rule: Some("deny-package"),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn all_criteria_must_match() {
+        let sender = SuiAddress::random_for_testing_only();
+        let package = ObjectID::random();
+        let tx = move_call_tx(sender, package);
+
+        let policy = TransactionPolicy {
+            default_action: PolicyAction::Allow,
+            rules: vec![TransactionPolicyRule {
+                packages: BTreeSet::from([package]),
+                functions: BTreeSet::from([format!("{package}::m::g").parse().unwrap()]),
+                ..rule("deny-g", PolicyAction::Deny)
+            }],
+        };
+        assert_eq!(
+            evaluate_transaction_policy(&policy, &tx, &[], &[]),
+            PolicyDecision::Allow
+        );
+    }
+
+    #[test]
+    fn default_deny_applies_when_nothing_matches() {
+        let tx = move_call_tx(SuiAddress::random_for_testing_only(), ObjectID::random());
+        let policy = TransactionPolicy {
+            default_action: PolicyAction::Deny,
+            rules: vec![],
+        };
+        assert!(matches!(
+            evaluate_transaction_policy(&policy, &tx, &[], &[]),
+            PolicyDecision::Deny { rule: None, .. }
+        ));
+    }
+}
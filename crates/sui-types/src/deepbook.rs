@@ -0,0 +1,219 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rust versions of the DeepBook v2 (`0xdee9::clob_v2`) order book types.
+
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::{Deserialize, Serialize};
+
+use crate::DEEPBOOK_ADDRESS;
+use crate::balance::Balance;
+use crate::base_types::SuiAddress;
+use crate::collection_types::{LinkedTable, Table};
+use crate::id::UID;
+
+pub const CLOB_MODULE_NAME: &IdentStr = ident_str!("clob_v2");
+pub const POOL_STRUCT_NAME: &IdentStr = ident_str!("Pool");
+
+/// Critbit tree child pointers at or above this value refer to leaves, below it to internal
+/// nodes. A root equal to it denotes an empty tree.
+const PARTITION_INDEX: u64 = 0x8000000000000000;
+
+/// Rust version of the Move deepbook::clob_v2::Pool type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Pool {
+    pub id: UID,
+    pub bids: CritbitTree,
+    pub asks: CritbitTree,
+    pub next_bid_order_id: u64,
+    pub next_ask_order_id: u64,
+    pub usr_open_orders: Table,
+    pub taker_fee_rate: u64,
+    pub maker_rebate_rate: u64,
+    pub tick_size: u64,
+    pub lot_size: u64,
+    pub base_custodian: Custodian,
+    pub quote_custodian: Custodian,
+    pub creation_fee: Balance,
+    pub base_asset_trading_fees: Balance,
+    pub quote_asset_trading_fees: Balance,
+}
+
+impl Pool {
+    /// If `tag` is a DeepBook pool type, return its base and quote asset types.
+    pub fn asset_types(tag: &StructTag) -> Option<(&TypeTag, &TypeTag)> {
+        if tag.address != DEEPBOOK_ADDRESS
+            || tag.module.as_ident_str() != CLOB_MODULE_NAME
+            || tag.name.as_ident_str() != POOL_STRUCT_NAME
+        {
+            return None;
+        }
+        match tag.type_params.as_slice() {
+            [base, quote] => Some((base, quote)),
+            _ => None,
+        }
+    }
+}
+
+/// Rust version of the Move deepbook::custodian_v2::Custodian type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Custodian {
+    pub id: UID,
+    pub account_balances: Table,
+}
+
+/// Rust version of the Move deepbook::critbit::CritbitTree type. Internal nodes and leaves are
+/// stored in tables keyed by their `u64` index.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CritbitTree {
+    pub root: u64,
+    pub internal_nodes: Table,
+    pub leaves: Table,
+    pub min_leaf: u64,
+    pub max_leaf: u64,
+    pub next_internal_node_index: u64,
+    pub next_leaf_index: u64,
+}
+
+impl CritbitTree {
+    pub fn is_empty(&self) -> bool {
+        self.leaves.size == 0
+    }
+
+    /// Indices of up to `limit` leaves, in ascending key order or descending if `descending` is
+    /// set. `read_node` loads the internal node at an index.
+    pub fn leaf_indices<E>(
+        &self,
+        descending: bool,
+        limit: usize,
+        mut read_node: impl FnMut(u64) -> Result<InternalNode, E>,
+    ) -> Result<Vec<u64>, E> {
+        let mut leaves = vec![];
+        if self.root == PARTITION_INDEX {
+            return Ok(leaves);
+        }
+
+        let mut stack = vec![self.root];
+        while let Some(ptr) = stack.pop() {
+            if leaves.len() >= limit {
+                break;
+            }
+            if ptr >= PARTITION_INDEX {
+                leaves.push(u64::MAX - ptr);
+                continue;
+            }
+            // Push the child to visit first last.
+            let node = read_node(ptr)?;
+            if descending {
+                stack.extend([node.left_child, node.right_child]);
+            } else {
+                stack.extend([node.right_child, node.left_child]);
+            }
+        }
+        Ok(leaves)
+    }
+}
+
+/// Rust version of the Move deepbook::critbit::InternalNode type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct InternalNode {
+    pub mask: u64,
+    pub left_child: u64,
+    pub right_child: u64,
+    pub parent: u64,
+}
+
+/// Rust version of the Move deepbook::critbit::Leaf type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Leaf<V> {
+    pub key: u64,
+    pub value: V,
+    pub parent: u64,
+}
+
+/// Rust version of the Move deepbook::clob_v2::TickLevel type: the open orders at a price,
+/// keyed by order ID.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TickLevel {
+    pub price: u64,
+    pub open_orders: LinkedTable<u64>,
+}
+
+/// Rust version of the Move deepbook::clob_v2::Order type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Order {
+    pub order_id: u64,
+    pub client_order_id: u64,
+    pub price: u64,
+    pub original_quantity: u64,
+    pub quantity: u64,
+    pub is_bid: bool,
+    pub owner: SuiAddress,
+    pub expire_timestamp: u64,
+    pub self_matching_prevention: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn leaf(index: u64) -> u64 {
+        u64::MAX - index
+    }
+
+    #[test]
+    fn leaf_indices_are_in_key_order() {
+        // Leaves 0..3 with keys 10, 20, 30, 40 under a balanced tree of internal nodes.
+        let nodes = HashMap::from([
+            (
+                0,
+                InternalNode {
+                    mask: 32,
+                    left_child: 1,
+                    right_child: 2,
+                    parent: PARTITION_INDEX,
+                },
+            ),
+            (
+                1,
+                InternalNode {
+                    mask: 8,
+                    left_child: leaf(0),
+                    right_child: leaf(1),
+                    parent: 0,
+                },
+            ),
+            (
+                2,
+                InternalNode {
+                    mask: 8,
+                    left_child: leaf(2),
+                    right_child: leaf(3),
+                    parent: 0,
+                },
+            ),
+        ]);
+        let tree = CritbitTree {
+            root: 0,
+            internal_nodes: Table::default(),
+            leaves: Table::default(),
+            min_leaf: 0,
+            max_leaf: 3,
+            next_internal_node_index: 3,
+            next_leaf_index: 4,
+        };
+        let read = |index| nodes.get(&index).cloned().ok_or(index);
+
+        assert_eq!(tree.leaf_indices(false, 10, read), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(tree.leaf_indices(true, 3, read), Ok(vec![3, 2, 1]));
+
+        let empty = CritbitTree {
+            root: PARTITION_INDEX,
+            ..tree
+        };
+        assert_eq!(empty.leaf_indices(false, 10, read), Ok(vec![]));
+    }
+}
@@ -369,6 +369,9 @@ pub enum UserInputError {
 
     #[error("Transaction {digest} appears more than once in the request")]
     RepeatedTransactions { digest: TransactionDigest },
+
+    #[error("Transaction is denied by policy rule {rule}: {reason}")]
+    TransactionDeniedByPolicy { rule: String, reason: String },
 }
 
 #[derive(
@@ -27,6 +27,7 @@ use crate::error::SuiResult;
 use crate::object::bounded_visitor::BoundedVisitor;
 use crate::sui_serde::BigInt;
 use crate::sui_serde::Readable;
+use crate::sui_system_state::PoolTokenExchangeRate;
 
 /// A universal Sui event type encapsulating different types of events
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -146,6 +147,12 @@ impl Event {
             && self.type_.name.as_ident_str() == ident_str!("SystemEpochInfoEvent")
     }
 
+    pub fn is_validator_epoch_info_event(&self) -> bool {
+        self.type_.address == SUI_SYSTEM_ADDRESS
+            && self.type_.module.as_ident_str() == ident_str!("validator_set")
+            && self.type_.name.as_ident_str() == ident_str!("ValidatorEpochInfoEventV2")
+    }
+
     /// Hash of event contents. Not guaranteed to be unique as event contents can be identical.
     pub fn digest(&self) -> Digest {
         let mut h = Blake2b256::new();
@@ -188,3 +195,19 @@ pub struct SystemEpochInfoEvent {
     pub total_stake_rewards_distributed: u64,
     pub leftover_storage_fund_inflow: u64,
 }
+
+// Event emitted in move code `fun advance_epoch`, once per active validator
+#[derive(Serialize, Deserialize, Default)]
+pub struct ValidatorEpochInfoEventV2 {
+    pub epoch: u64,
+    pub validator_address: SuiAddress,
+    pub reference_gas_survey_quote: u64,
+    pub stake: u64,
+    pub voting_power: u64,
+    pub commission_rate: u64,
+    pub pool_staking_reward: u64,
+    pub storage_fund_staking_reward: u64,
+    pub pool_token_exchange_rate: PoolTokenExchangeRate,
+    pub tallying_rule_reporters: Vec<SuiAddress>,
+    pub tallying_rule_global_score: u64,
+}
@@ -3,10 +3,10 @@
 
 use std::collections::BTreeMap;
 
-use crate::base_types::{ExecutionData, ObjectID, ObjectRef};
+use crate::base_types::{ExecutionData, ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
 use crate::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
 use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
-use crate::object::Object;
+use crate::object::{Object, Owner};
 use crate::signature::GenericSignature;
 use crate::storage::ObjectKey;
 use crate::storage::error::Error as StorageError;
@@ -329,6 +329,56 @@ impl Checkpoint {
         eventually_removed_object_refs.into_values().collect()
     }
 
+    /// The net change to every object the checkpoint's transactions touched, ordered by object ID.
+    /// Each object's input is its state before the first transaction that changed it, and its
+    /// output is its state after the last one. Objects that were both created and removed within
+    /// the checkpoint are omitted.
+    pub fn object_deltas(&self) -> Vec<ObjectDelta> {
+        let mut deltas: BTreeMap<ObjectID, ObjectDelta> = BTreeMap::new();
+        for tx in &self.transactions {
+            let input_owners: BTreeMap<_, _> = tx
+                .effects
+                .old_object_metadata()
+                .into_iter()
+                .map(|((id, _, _), owner)| (id, owner))
+                .collect();
+            let output_owners: BTreeMap<_, _> = tx
+                .effects
+                .all_changed_objects()
+                .into_iter()
+                .map(|((id, _, _), owner, _)| (id, owner))
+                .collect();
+
+            for change in tx.effects.object_changes() {
+                let state = |version: Option<SequenceNumber>,
+                             digest: Option<ObjectDigest>,
+                             owners: &BTreeMap<ObjectID, Owner>| {
+                    Some(ObjectDeltaState {
+                        version: version?,
+                        digest: digest?,
+                        owner: owners.get(&change.id)?.clone(),
+                    })
+                };
+
+                let input = state(change.input_version, change.input_digest, &input_owners);
+                let output = state(change.output_version, change.output_digest, &output_owners);
+                deltas
+                    .entry(change.id)
+                    .or_insert_with(|| ObjectDelta {
+                        object_id: change.id,
+                        input,
+                        output: None,
+                    })
+                    .output = output;
+            }
+        }
+
+        deltas
+            .into_values()
+            .filter(|delta| delta.input.is_some() || delta.output.is_some())
+            .collect()
+    }
+
     // Returns the required FieldMask to fetch all necessary fields for populating `Checkpoint`
     pub fn proto_field_mask() -> sui_rpc::field::FieldMask {
         use sui_rpc::field::FieldMaskUtil;
@@ -364,6 +414,50 @@ impl Checkpoint {
     }
 }
 
+/// The net change to an object over a checkpoint, as returned by [`Checkpoint::object_deltas`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectDelta {
+    pub object_id: ObjectID,
+    /// The object's state before the checkpoint, `None` if it was created or unwrapped.
+    pub input: Option<ObjectDeltaState>,
+    /// The object's state after the checkpoint, `None` if it was deleted or wrapped.
+    pub output: Option<ObjectDeltaState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectDeltaState {
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub owner: Owner,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectDeltaKind {
+    /// The object was created or unwrapped.
+    Created,
+    Mutated,
+    /// The object was deleted or wrapped.
+    Deleted,
+}
+
+impl ObjectDelta {
+    pub fn kind(&self) -> ObjectDeltaKind {
+        match (&self.input, &self.output) {
+            (None, _) => ObjectDeltaKind::Created,
+            (Some(_), Some(_)) => ObjectDeltaKind::Mutated,
+            (Some(_), None) => ObjectDeltaKind::Deleted,
+        }
+    }
+
+    /// Whether the object still exists after the checkpoint, with a different owner.
+    pub fn owner_changed(&self) -> bool {
+        matches!(
+            (&self.input, &self.output),
+            (Some(input), Some(output)) if input.owner != output.owner
+        )
+    }
+}
+
 impl ExecutedTransaction {
     pub fn input_objects<'a>(
         &self,
@@ -480,3 +574,188 @@ impl From<CheckpointData> for Checkpoint {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_checkpoint_data_builder::TestCheckpointBuilder;
+
+    use super::*;
+
+    #[test]
+    fn object_deltas_net_out_changes_within_checkpoint() {
+        let mut builder = TestCheckpointBuilder::new(1)
+            .start_transaction(0)
+            .create_owned_object(1)
+            .create_owned_object(2)
+            .create_owned_object(3)
+            .finish_transaction();
+        let before: BTreeMap<_, _> = builder
+            .build_checkpoint()
+            .object_deltas()
+            .into_iter()
+            .map(|delta| (delta.object_id, delta.output.unwrap()))
+            .collect();
+
+        let checkpoint = builder
+            .start_transaction(0)
+            .mutate_owned_object(1)
+            .transfer_object(2, 1)
+            .delete_object(3)
+            .create_owned_object(4)
+            .create_owned_object(5)
+            .finish_transaction()
+            .start_transaction(0)
+            .mutate_owned_object(1)
+            .delete_object(5)
+            .finish_transaction()
+            .build_checkpoint();
+
+        let deltas: BTreeMap<_, _> = checkpoint
+            .object_deltas()
+            .into_iter()
+            .map(|delta| (delta.object_id, delta))
+            .collect();
+        let delta = |idx| deltas.get(&TestCheckpointBuilder::derive_object_id(idx));
+
+        let mutated = delta(1).unwrap();
+        assert_eq!(mutated.kind(), ObjectDeltaKind::Mutated);
+        assert_eq!(mutated.input.as_ref(), before.get(&mutated.object_id));
+        assert!(mutated.output.as_ref().unwrap().version > mutated.input.as_ref().unwrap().version);
+        assert!(!mutated.owner_changed());
+
+        let transferred = delta(2).unwrap();
+        assert_eq!(transferred.kind(), ObjectDeltaKind::Mutated);
+        assert!(transferred.owner_changed());
+        assert_eq!(
+            transferred.output.as_ref().unwrap().owner,
+            Owner::AddressOwner(TestCheckpointBuilder::derive_address(1))
+        );
+
+        assert_eq!(delta(3).unwrap().kind(), ObjectDeltaKind::Deleted);
+        assert_eq!(delta(4).unwrap().kind(), ObjectDeltaKind::Created);
+        assert!(delta(5).is_none());
+    }
+
+    fn deltas_by_id(checkpoint: &Checkpoint) -> BTreeMap<ObjectID, ObjectDelta> {
+        checkpoint
+            .object_deltas()
+            .into_iter()
+            .map(|delta| (delta.object_id, delta))
+            .collect()
+    }
+
+    #[test]
+    fn object_deltas_report_created_objects() {
+        let checkpoint = TestCheckpointBuilder::new(1)
+            .start_transaction(0)
+            .create_owned_object(1)
+            .create_shared_object(2)
+            .finish_transaction()
+            .build_checkpoint();
+        let deltas = deltas_by_id(&checkpoint);
+
+        let owned = &deltas[&TestCheckpointBuilder::derive_object_id(1)];
+        assert_eq!(owned.kind(), ObjectDeltaKind::Created);
+        assert!(owned.input.is_none());
+        assert!(!owned.owner_changed());
+        let output = owned.output.as_ref().unwrap();
+        assert_eq!(
+            output.owner,
+            Owner::AddressOwner(TestCheckpointBuilder::derive_address(0))
+        );
+        assert_eq!(
+            output.version,
+            checkpoint.transactions[0].effects.lamport_version()
+        );
+
+        let shared = &deltas[&TestCheckpointBuilder::derive_object_id(2)];
+        assert_eq!(shared.kind(), ObjectDeltaKind::Created);
+        assert!(matches!(
+            shared.output.as_ref().unwrap().owner,
+            Owner::Shared { .. }
+        ));
+    }
+
+    #[test]
+    fn object_deltas_report_deleted_and_wrapped_objects() {
+        let mut builder = TestCheckpointBuilder::new(1)
+            .start_transaction(0)
+            .create_owned_object(1)
+            .create_owned_object(2)
+            .finish_transaction();
+        let created = deltas_by_id(&builder.build_checkpoint());
+
+        let mut builder = builder
+            .start_transaction(0)
+            .delete_object(1)
+            .wrap_object(2)
+            .finish_transaction();
+        let deltas = deltas_by_id(&builder.build_checkpoint());
+        for idx in [1, 2] {
+            let id = TestCheckpointBuilder::derive_object_id(idx);
+            let delta = &deltas[&id];
+            assert_eq!(delta.kind(), ObjectDeltaKind::Deleted);
+            assert_eq!(delta.input, created[&id].output);
+            assert!(delta.output.is_none());
+            assert!(!delta.owner_changed());
+        }
+
+        let checkpoint = builder
+            .start_transaction(0)
+            .unwrap_object(2)
+            .finish_transaction()
+            .build_checkpoint();
+        let unwrapped = &deltas_by_id(&checkpoint)[&TestCheckpointBuilder::derive_object_id(2)];
+        assert_eq!(unwrapped.kind(), ObjectDeltaKind::Created);
+        assert!(unwrapped.input.is_none());
+        assert!(unwrapped.output.is_some());
+    }
+
+    #[test]
+    fn object_deltas_track_owner_changes() {
+        let mut builder = TestCheckpointBuilder::new(1)
+            .start_transaction(0)
+            .create_owned_object(1)
+            .create_owned_object(2)
+            .create_owned_object(3)
+            .finish_transaction();
+        let created = deltas_by_id(&builder.build_checkpoint());
+
+        let checkpoint = builder
+            .start_transaction(0)
+            .transfer_object(1, 1)
+            .change_object_owner(2, Owner::Immutable)
+            .transfer_object(3, 1)
+            .finish_transaction()
+            .start_transaction(1)
+            .transfer_object(3, 0)
+            .finish_transaction()
+            .build_checkpoint();
+        let deltas = deltas_by_id(&checkpoint);
+        let delta = |idx| &deltas[&TestCheckpointBuilder::derive_object_id(idx)];
+
+        let transferred = delta(1);
+        assert_eq!(transferred.kind(), ObjectDeltaKind::Mutated);
+        assert!(transferred.owner_changed());
+        assert_eq!(transferred.input, created[&transferred.object_id].output);
+        assert_eq!(
+            transferred.output.as_ref().unwrap().owner,
+            Owner::AddressOwner(TestCheckpointBuilder::derive_address(1))
+        );
+
+        let frozen = delta(2);
+        assert!(frozen.owner_changed());
+        assert_eq!(frozen.output.as_ref().unwrap().owner, Owner::Immutable);
+
+        // Transferred away and back within the checkpoint: the net owner is unchanged, but the
+        // object still moved to the version written by the last transaction.
+        let returned = delta(3);
+        assert_eq!(returned.kind(), ObjectDeltaKind::Mutated);
+        assert!(!returned.owner_changed());
+        assert_eq!(returned.input, created[&returned.object_id].output);
+        assert_eq!(
+            returned.output.as_ref().unwrap().version,
+            checkpoint.transactions[1].effects.lamport_version()
+        );
+    }
+}
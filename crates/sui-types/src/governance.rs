@@ -102,6 +102,8 @@ pub const VALIDATOR_LOW_STAKE_GRACE_PERIOD: u64 = 7;
 
 pub const STAKING_POOL_MODULE_NAME: &IdentStr = ident_str!("staking_pool");
 pub const STAKED_SUI_STRUCT_NAME: &IdentStr = ident_str!("StakedSui");
+pub const FUNGIBLE_STAKED_SUI_STRUCT_NAME: &IdentStr = ident_str!("FungibleStakedSui");
+pub const FUNGIBLE_STAKED_SUI_DATA_STRUCT_NAME: &IdentStr = ident_str!("FungibleStakedSuiData");
 
 pub const ADD_STAKE_MUL_COIN_FUN_NAME: &IdentStr = ident_str!("request_add_stake_mul_coin");
 pub const ADD_STAKE_FUN_NAME: &IdentStr = ident_str!("request_add_stake");
@@ -177,3 +179,94 @@ impl TryFrom<&Object> for StakedSui {
         .into())
     }
 }
+
+/// Rust version of the Move sui_system::staking_pool::FungibleStakedSui type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct FungibleStakedSui {
+    id: UID,
+    pool_id: ID,
+    value: u64,
+}
+
+impl FungibleStakedSui {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: STAKING_POOL_MODULE_NAME.to_owned(),
+            name: FUNGIBLE_STAKED_SUI_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_fungible_staked_sui(s: &StructTag) -> bool {
+        s.address == SUI_SYSTEM_ADDRESS
+            && s.module.as_ident_str() == STAKING_POOL_MODULE_NAME
+            && s.name.as_ident_str() == FUNGIBLE_STAKED_SUI_STRUCT_NAME
+            && s.type_params.is_empty()
+    }
+
+    pub fn id(&self) -> ObjectID {
+        self.id.id.bytes
+    }
+
+    pub fn pool_id(&self) -> ObjectID {
+        self.pool_id.bytes
+    }
+
+    /// Amount of pool tokens, redeemable for SUI at the pool's exchange rate.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl TryFrom<&Object> for FungibleStakedSui {
+    type Error = SuiError;
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        if let Some(tag) = object.struct_tag()
+            && Self::is_fungible_staked_sui(&tag)
+        {
+            let contents = object.data.try_as_move().unwrap().contents();
+            return bcs::from_bytes(contents).map_err(|err| {
+                SuiErrorKind::TypeError {
+                    error: format!("Unable to deserialize FungibleStakedSui object: {:?}", err),
+                }
+                .into()
+            });
+        }
+
+        Err(SuiErrorKind::TypeError {
+            error: format!("Object type is not a FungibleStakedSui: {:?}", object),
+        }
+        .into())
+    }
+}
+
+/// Rust version of the Move sui_system::staking_pool::FungibleStakedSuiData type, stored in the
+/// `extra_fields` of a staking pool that has issued `FungibleStakedSui`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct FungibleStakedSuiData {
+    id: UID,
+    total_supply: u64,
+    principal: Balance,
+}
+
+impl FungibleStakedSuiData {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_SYSTEM_ADDRESS,
+            module: STAKING_POOL_MODULE_NAME.to_owned(),
+            name: FUNGIBLE_STAKED_SUI_DATA_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    /// Pool tokens issued as `FungibleStakedSui`.
+    pub fn total_supply(&self) -> u64 {
+        self.total_supply
+    }
+
+    /// SUI principal backing the issued pool tokens.
+    pub fn principal(&self) -> u64 {
+        self.principal.value()
+    }
+}
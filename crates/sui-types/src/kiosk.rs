@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rust versions of the Kiosk (`0x2::kiosk`) and transfer policy (`0x2::transfer_policy`) types.
+
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::balance::Balance;
+use crate::base_types::{ObjectID, SuiAddress};
+use crate::collection_types::VecSet;
+use crate::error::{SuiError, SuiErrorKind};
+use crate::id::{ID, UID};
+use crate::object::Object;
+use crate::{MoveTypeTagTrait, SUI_FRAMEWORK_ADDRESS};
+
+pub const KIOSK_MODULE_NAME: &IdentStr = ident_str!("kiosk");
+pub const KIOSK_STRUCT_NAME: &IdentStr = ident_str!("Kiosk");
+pub const KIOSK_OWNER_CAP_STRUCT_NAME: &IdentStr = ident_str!("KioskOwnerCap");
+pub const PURCHASE_CAP_STRUCT_NAME: &IdentStr = ident_str!("PurchaseCap");
+pub const ITEM_STRUCT_NAME: &IdentStr = ident_str!("Item");
+pub const LISTING_STRUCT_NAME: &IdentStr = ident_str!("Listing");
+pub const LOCK_STRUCT_NAME: &IdentStr = ident_str!("Lock");
+
+pub const TRANSFER_POLICY_MODULE_NAME: &IdentStr = ident_str!("transfer_policy");
+pub const TRANSFER_POLICY_STRUCT_NAME: &IdentStr = ident_str!("TransferPolicy");
+pub const TRANSFER_POLICY_CAP_STRUCT_NAME: &IdentStr = ident_str!("TransferPolicyCap");
+
+/// Rust version of the Move sui::kiosk::Kiosk type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Kiosk {
+    pub id: UID,
+    /// Proceeds of the sales, withdrawable by the owner.
+    pub profits: Balance,
+    pub owner: SuiAddress,
+    pub item_count: u32,
+    pub allow_extensions: bool,
+}
+
+impl Kiosk {
+    pub fn type_() -> StructTag {
+        framework_type(KIOSK_MODULE_NAME, KIOSK_STRUCT_NAME, vec![])
+    }
+
+    pub fn is_kiosk(tag: &StructTag) -> bool {
+        is_framework_type(tag, KIOSK_MODULE_NAME, KIOSK_STRUCT_NAME) && tag.type_params.is_empty()
+    }
+
+    pub fn id(&self) -> ObjectID {
+        self.id.id.bytes
+    }
+}
+
+impl TryFrom<&Object> for Kiosk {
+    type Error = SuiError;
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        deserialize_object(object, Self::is_kiosk, "Kiosk")
+    }
+}
+
+/// Rust version of the Move sui::kiosk::KioskOwnerCap type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct KioskOwnerCap {
+    pub id: UID,
+    /// The kiosk the cap gives access to.
+    pub for_: ID,
+}
+
+impl KioskOwnerCap {
+    pub fn type_() -> StructTag {
+        framework_type(KIOSK_MODULE_NAME, KIOSK_OWNER_CAP_STRUCT_NAME, vec![])
+    }
+
+    pub fn is_kiosk_owner_cap(tag: &StructTag) -> bool {
+        is_framework_type(tag, KIOSK_MODULE_NAME, KIOSK_OWNER_CAP_STRUCT_NAME)
+            && tag.type_params.is_empty()
+    }
+
+    pub fn kiosk_id(&self) -> ObjectID {
+        self.for_.bytes
+    }
+}
+
+impl TryFrom<&Object> for KioskOwnerCap {
+    type Error = SuiError;
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        deserialize_object(object, Self::is_kiosk_owner_cap, "KioskOwnerCap")
+    }
+}
+
+/// Rust version of the Move sui::kiosk::PurchaseCap<T> type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct PurchaseCap {
+    pub id: UID,
+    pub kiosk_id: ID,
+    pub item_id: ID,
+    pub min_price: u64,
+}
+
+impl PurchaseCap {
+    pub fn type_(item_type: TypeTag) -> StructTag {
+        framework_type(KIOSK_MODULE_NAME, PURCHASE_CAP_STRUCT_NAME, vec![item_type])
+    }
+
+    /// If `tag` is a `PurchaseCap<T>` type, return `T`.
+    pub fn item_type(tag: &StructTag) -> Option<&TypeTag> {
+        single_type_param(tag, KIOSK_MODULE_NAME, PURCHASE_CAP_STRUCT_NAME)
+    }
+}
+
+impl TryFrom<&Object> for PurchaseCap {
+    type Error = SuiError;
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        deserialize_object(object, |tag| Self::item_type(tag).is_some(), "PurchaseCap")
+    }
+}
+
+/// Rust version of the Move sui::kiosk::Item type, the key of the dynamic object field holding an
+/// item placed in a kiosk.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Item {
+    pub id: ID,
+}
+
+impl Item {
+    pub fn new(item_id: ObjectID) -> Self {
+        Self {
+            id: ID::new(item_id),
+        }
+    }
+}
+
+impl MoveTypeTagTrait for Item {
+    fn get_type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(framework_type(
+            KIOSK_MODULE_NAME,
+            ITEM_STRUCT_NAME,
+            vec![],
+        )))
+    }
+}
+
+/// Rust version of the Move sui::kiosk::Listing type, the key of the dynamic field holding the
+/// price of a listed item.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Listing {
+    pub id: ID,
+    /// Whether the item can only be bought with a `PurchaseCap`.
+    pub is_exclusive: bool,
+}
+
+impl Listing {
+    pub fn new(item_id: ObjectID, is_exclusive: bool) -> Self {
+        Self {
+            id: ID::new(item_id),
+            is_exclusive,
+        }
+    }
+}
+
+impl MoveTypeTagTrait for Listing {
+    fn get_type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(framework_type(
+            KIOSK_MODULE_NAME,
+            LISTING_STRUCT_NAME,
+            vec![],
+        )))
+    }
+}
+
+/// Rust version of the Move sui::kiosk::Lock type, the key of the dynamic field marking an item
+/// that can only leave its kiosk through a purchase.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Lock {
+    pub id: ID,
+}
+
+impl Lock {
+    pub fn new(item_id: ObjectID) -> Self {
+        Self {
+            id: ID::new(item_id),
+        }
+    }
+}
+
+impl MoveTypeTagTrait for Lock {
+    fn get_type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(framework_type(
+            KIOSK_MODULE_NAME,
+            LOCK_STRUCT_NAME,
+            vec![],
+        )))
+    }
+}
+
+/// Rust version of the Move std::type_name::TypeName type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct TypeName {
+    pub name: String,
+}
+
+/// Rust version of the Move sui::transfer_policy::TransferPolicy<T> type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TransferPolicy {
+    pub id: UID,
+    /// Fees collected by the policy's rules.
+    pub balance: Balance,
+    /// Types of the rules whose receipts a transfer request must collect.
+    pub rules: VecSet<TypeName>,
+}
+
+impl TransferPolicy {
+    pub fn type_(item_type: TypeTag) -> StructTag {
+        framework_type(
+            TRANSFER_POLICY_MODULE_NAME,
+            TRANSFER_POLICY_STRUCT_NAME,
+            vec![item_type],
+        )
+    }
+
+    /// If `tag` is a `TransferPolicy<T>` type, return `T`.
+    pub fn item_type(tag: &StructTag) -> Option<&TypeTag> {
+        single_type_param(
+            tag,
+            TRANSFER_POLICY_MODULE_NAME,
+            TRANSFER_POLICY_STRUCT_NAME,
+        )
+    }
+
+    pub fn id(&self) -> ObjectID {
+        self.id.id.bytes
+    }
+}
+
+impl TryFrom<&Object> for TransferPolicy {
+    type Error = SuiError;
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        deserialize_object(
+            object,
+            |tag| Self::item_type(tag).is_some(),
+            "TransferPolicy",
+        )
+    }
+}
+
+/// Rust version of the Move sui::transfer_policy::TransferPolicyCap<T> type.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct TransferPolicyCap {
+    pub id: UID,
+    pub policy_id: ID,
+}
+
+impl TransferPolicyCap {
+    pub fn type_(item_type: TypeTag) -> StructTag {
+        framework_type(
+            TRANSFER_POLICY_MODULE_NAME,
+            TRANSFER_POLICY_CAP_STRUCT_NAME,
+            vec![item_type],
+        )
+    }
+
+    /// If `tag` is a `TransferPolicyCap<T>` type, return `T`.
+    pub fn item_type(tag: &StructTag) -> Option<&TypeTag> {
+        single_type_param(
+            tag,
+            TRANSFER_POLICY_MODULE_NAME,
+            TRANSFER_POLICY_CAP_STRUCT_NAME,
+        )
+    }
+}
+
+impl TryFrom<&Object> for TransferPolicyCap {
+    type Error = SuiError;
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        deserialize_object(
+            object,
+            |tag| Self::item_type(tag).is_some(),
+            "TransferPolicyCap",
+        )
+    }
+}
+
+fn framework_type(module: &IdentStr, name: &IdentStr, type_params: Vec<TypeTag>) -> StructTag {
+    StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: module.to_owned(),
+        name: name.to_owned(),
+        type_params,
+    }
+}
+
+fn is_framework_type(tag: &StructTag, module: &IdentStr, name: &IdentStr) -> bool {
+    tag.address == SUI_FRAMEWORK_ADDRESS
+        && tag.module.as_ident_str() == module
+        && tag.name.as_ident_str() == name
+}
+
+fn single_type_param<'a>(
+    tag: &'a StructTag,
+    module: &IdentStr,
+    name: &IdentStr,
+) -> Option<&'a TypeTag> {
+    if !is_framework_type(tag, module, name) {
+        return None;
+    }
+    match tag.type_params.as_slice() {
+        [param] => Some(param),
+        _ => None,
+    }
+}
+
+fn deserialize_object<T: DeserializeOwned>(
+    object: &Object,
+    is_type: impl Fn(&StructTag) -> bool,
+    type_name: &str,
+) -> Result<T, SuiError> {
+    match object.struct_tag() {
+        Some(tag) if is_type(&tag) => {
+            let contents = object.data.try_as_move().unwrap().contents();
+            bcs::from_bytes(contents).map_err(|err| {
+                SuiErrorKind::TypeError {
+                    error: format!("Unable to deserialize {type_name} object: {:?}", err),
+                }
+                .into()
+            })
+        }
+        _ => Err(SuiErrorKind::TypeError {
+            error: format!("Object type is not a {type_name}: {:?}", object),
+        }
+        .into()),
+    }
+}
@@ -42,6 +42,7 @@ pub mod collection_types;
 pub mod committee;
 pub mod config;
 pub mod crypto;
+pub mod deepbook;
 pub mod deny_list_v1;
 pub mod deny_list_v2;
 pub mod derived_object;
@@ -66,6 +67,7 @@ pub mod governance;
 pub mod id;
 pub mod in_memory_storage;
 pub mod inner_temporary_store;
+pub mod kiosk;
 pub mod layout_resolver;
 pub mod message_envelope;
 pub mod messages_checkpoint;
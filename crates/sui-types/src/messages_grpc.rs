@@ -332,6 +332,34 @@ impl SubmitTxRequest {
     }
 }
 
+/// Transactions to submit together to a single validator, which tries to sequence them adjacently
+/// and in order. See [SIP-19](https://github.com/sui-foundation/sips/blob/main/sips/sip-19.md).
+#[derive(Clone, Debug)]
+pub struct SubmitSoftBundleRequest {
+    pub transactions: Vec<Transaction>,
+}
+
+impl SubmitSoftBundleRequest {
+    pub fn into_raw(&self) -> Result<RawSubmitTxRequest, SuiError> {
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|transaction| {
+                bcs::to_bytes(transaction).map(Into::into).map_err(|e| {
+                    SuiErrorKind::TransactionSerializationError {
+                        error: e.to_string(),
+                    }
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(RawSubmitTxRequest {
+            transactions,
+            submit_type: SubmitTxType::SoftBundle.into(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 pub enum TxType {
     SingleWriter,
@@ -1086,7 +1114,7 @@ impl TryFrom<RawValidatorHealthResponse> for ValidatorHealthResponse {
 #[cfg(test)]
 mod tests {
     use crate::{
-        messages_grpc::{SubmitTxRequest, SubmitTxType},
+        messages_grpc::{SubmitSoftBundleRequest, SubmitTxRequest, SubmitTxType},
         transaction::{Transaction, TransactionData},
     };
 
@@ -1124,12 +1152,24 @@ mod tests {
             let (_, keypair) = crate::crypto::get_account_key_pair();
             let transaction = Transaction::from_data_and_signer(tx_data, vec![&keypair]);
 
-            let request = SubmitTxRequest::new_transaction(transaction);
+            let request = SubmitTxRequest::new_transaction(transaction.clone());
             let raw_request = request.into_raw().unwrap();
 
             let submit_type = SubmitTxType::try_from(raw_request.submit_type).unwrap();
             assert_eq!(submit_type, SubmitTxType::Default);
             assert_eq!(raw_request.transactions.len(), 1);
+
+            println!(
+                "Case 3. SubmitSoftBundleRequest should be converted to RawSubmitTxRequest with submit_type set to SoftBundle."
+            );
+            let request = SubmitSoftBundleRequest {
+                transactions: vec![transaction.clone(), transaction],
+            };
+            let raw_request = request.into_raw().unwrap();
+
+            let submit_type = SubmitTxType::try_from(raw_request.submit_type).unwrap();
+            assert_eq!(submit_type, SubmitTxType::SoftBundle);
+            assert_eq!(raw_request.transactions.len(), 2);
         }
     }
 }
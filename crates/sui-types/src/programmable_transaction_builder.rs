@@ -337,6 +337,60 @@ impl ProgrammableTransactionBuilder {
         Ok(target_arg)
     }
 
+    /// Pay exactly `amount` to `recipient` out of `coins`, which hold `balance` between them. The
+    /// coins are merged into the first of them, which keeps the change and stays with its owner.
+    /// If `amount` is the whole balance, the merged coin itself is transferred.
+    ///
+    /// Fails if `coins` is empty, or `balance` is less than `amount`.
+    pub fn pay_exact(
+        &mut self,
+        coins: Vec<ObjectRef>,
+        balance: u64,
+        recipient: SuiAddress,
+        amount: u64,
+    ) -> anyhow::Result<()> {
+        let recipient = self.pure(recipient)?;
+        self.pay_exact_arg(coins, balance, recipient, amount)
+    }
+
+    /// Like [`Self::pay_exact`], for a `recipient` that is an argument of the transaction.
+    pub fn pay_exact_arg(
+        &mut self,
+        coins: Vec<ObjectRef>,
+        balance: u64,
+        recipient: Argument,
+        amount: u64,
+    ) -> anyhow::Result<()> {
+        if balance < amount {
+            bail!("Coins only hold {balance}, which is less than the {amount} to pay");
+        }
+
+        let mut coins = coins.into_iter();
+        let Some(coin) = coins.next() else {
+            bail!("coins vector is empty");
+        };
+        let coin_arg = self.obj(ObjectArg::ImmOrOwnedObject(coin))?;
+        let merge_args: Vec<_> = coins
+            .map(|c| self.obj(ObjectArg::ImmOrOwnedObject(c)))
+            .collect::<Result<_, _>>()?;
+        if !merge_args.is_empty() {
+            self.command(Command::MergeCoins(coin_arg, merge_args));
+        }
+
+        let payment = if amount == balance {
+            coin_arg
+        } else {
+            let amount = self.pure(amount)?;
+            let Argument::Result(split) = self.command(Command::SplitCoins(coin_arg, vec![amount]))
+            else {
+                panic!("self.command should always give a Argument::Result")
+            };
+            Argument::NestedResult(split, 0)
+        };
+        self.command(Command::TransferObjects(vec![payment], recipient));
+        Ok(())
+    }
+
     /// Will fail to generate if recipients and amounts do not have the same lengths.
     /// Or if coins is empty
     pub fn pay(
@@ -3,6 +3,7 @@
 
 pub mod error;
 mod object_store_trait;
+mod object_type_filter;
 mod read_store;
 mod shared_in_memory_store;
 mod write_store;
@@ -31,6 +32,7 @@ use move_binary_format::CompiledModule;
 use move_core_types::language_storage::{ModuleId, TypeTag};
 use move_core_types::resolver::SerializedPackage;
 pub use object_store_trait::ObjectStore;
+pub use object_type_filter::ObjectTypeFilter;
 pub use read_store::BalanceInfo;
 pub use read_store::BalanceIterator;
 pub use read_store::CoinInfo;
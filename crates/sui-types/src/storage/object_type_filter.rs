@@ -0,0 +1,285 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::SuiAddress;
+use crate::{parse_sui_address, parse_sui_fq_name, parse_sui_module_id, parse_sui_type_tag};
+
+/// A filter on the Move type of objects, for owned-object queries. Every variant matches a
+/// contiguous range of types in an index sorted by the BCS encoding of their `StructTag`, so these
+/// queries can be served by a prefix scan.
+///
+/// Parsed from `package[::module[::type[<type_params, ...>]]]`, where:
+///
+/// - `0x2` or `0x2::*` matches every type defined in the package,
+/// - `0x2::coin` or `0x2::coin::*` matches every type defined in the module,
+/// - `0x2::coin::Coin` matches every instantiation of the type,
+/// - `0x2::coin::Coin<0x2::sui::SUI>` matches only that instantiation,
+/// - `0x2::coin::Coin<*>` or `0x2::dynamic_field::Field<u64, *>` matches instantiations whose
+///   wildcard type parameters can be anything. Wildcards must come after every concrete type
+///   parameter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ObjectTypeFilter {
+    /// Every type defined in the package at this address.
+    Package(SuiAddress),
+    /// Every type defined in this module.
+    Module(ModuleId),
+    /// A type. If it has no type parameters, this matches every instantiation of the type,
+    /// otherwise only the given instantiation.
+    Type(StructTag),
+    /// Instantiations of a generic type with `arity` type parameters, whose leading type
+    /// parameters are those of `type_`, and whose remaining ones can be anything.
+    Instantiation { type_: StructTag, arity: usize },
+}
+
+impl ObjectTypeFilter {
+    pub fn matches(&self, tag: &StructTag) -> bool {
+        match self {
+            ObjectTypeFilter::Package(package) => SuiAddress::from(tag.address) == *package,
+            ObjectTypeFilter::Module(module) => {
+                &tag.address == module.address() && tag.module.as_ident_str() == module.name()
+            }
+            ObjectTypeFilter::Type(type_) => {
+                same_type(type_, tag)
+                    && (type_.type_params.is_empty() || type_.type_params == tag.type_params)
+            }
+            ObjectTypeFilter::Instantiation { type_, arity } => {
+                same_type(type_, tag)
+                    && tag.type_params.len() == *arity
+                    && tag.type_params.starts_with(&type_.type_params)
+            }
+        }
+    }
+}
+
+impl From<StructTag> for ObjectTypeFilter {
+    fn from(tag: StructTag) -> Self {
+        ObjectTypeFilter::Type(tag)
+    }
+}
+
+impl FromStr for ObjectTypeFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+
+        if let Some(prefix) = s.strip_suffix("::*") {
+            return if let Ok(module) = parse_sui_module_id(prefix) {
+                Ok(ObjectTypeFilter::Module(module))
+            } else if let Ok(package) = parse_sui_address(prefix) {
+                Ok(ObjectTypeFilter::Package(package))
+            } else {
+                Err(invalid_filter(s))
+            };
+        }
+
+        if let Some((name, params)) = s.strip_suffix('>').and_then(|s| s.split_once('<')) {
+            let params = split_type_params(params)?;
+            if params.contains(&"*") {
+                let fixed = params.iter().take_while(|p| **p != "*").count();
+                ensure!(
+                    params[fixed..].iter().all(|p| *p == "*"),
+                    "Invalid type filter '{s}': wildcard type parameters must come after every \
+                     concrete type parameter"
+                );
+
+                let (module, name) = parse_sui_fq_name(name).map_err(|_| invalid_filter(s))?;
+                let type_params = params[..fixed]
+                    .iter()
+                    .map(|p| parse_sui_type_tag(p))
+                    .collect::<anyhow::Result<_>>()
+                    .map_err(|_| invalid_filter(s))?;
+                return Ok(ObjectTypeFilter::Instantiation {
+                    type_: StructTag {
+                        address: *module.address(),
+                        module: module.name().to_owned(),
+                        name: Identifier::new(name)?,
+                        type_params,
+                    },
+                    arity: params.len(),
+                });
+            }
+        }
+
+        if let Ok(tag) = crate::parse_sui_struct_tag(s) {
+            Ok(ObjectTypeFilter::Type(tag))
+        } else if let Ok(module) = parse_sui_module_id(s) {
+            Ok(ObjectTypeFilter::Module(module))
+        } else if let Ok(package) = parse_sui_address(s) {
+            Ok(ObjectTypeFilter::Package(package))
+        } else {
+            Err(invalid_filter(s))
+        }
+    }
+}
+
+impl fmt::Display for ObjectTypeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectTypeFilter::Package(package) => write!(f, "{package}::*"),
+            ObjectTypeFilter::Module(module) => {
+                write!(f, "{}::*", module.to_canonical_display(true))
+            }
+            ObjectTypeFilter::Type(type_) => write!(f, "{}", type_.to_canonical_display(true)),
+            ObjectTypeFilter::Instantiation { type_, arity } => {
+                let tag = StructTag {
+                    type_params: vec![],
+                    ..type_.clone()
+                };
+                write!(f, "{}<", tag.to_canonical_display(true))?;
+                for i in 0..*arity {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match type_.type_params.get(i) {
+                        Some(param) => write!(f, "{}", param.to_canonical_display(true))?,
+                        None => write!(f, "*")?,
+                    }
+                }
+                write!(f, ">")
+            }
+        }
+    }
+}
+
+fn same_type(a: &StructTag, b: &StructTag) -> bool {
+    a.address == b.address && a.module == b.module && a.name == b.name
+}
+
+fn invalid_filter(s: &str) -> anyhow::Error {
+    anyhow!(
+        "Invalid type filter '{s}', expected: package[::module[::type[<type_params, ...>]]], where \
+         the module, the type, or trailing type params can be '*'"
+    )
+}
+
+/// Split a comma-separated list of type parameters, ignoring the commas between the type
+/// parameters of nested types.
+fn split_type_params(params: &str) -> anyhow::Result<Vec<&str>> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| anyhow!("Unbalanced '>' in type parameters '{params}'"))?
+            }
+            ',' if depth == 0 => {
+                parts.push(params[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        bail!("Unbalanced '<' in type parameters '{params}'");
+    }
+    parts.push(params[start..].trim());
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::language_storage::TypeTag;
+
+    use super::*;
+    use crate::parse_sui_struct_tag;
+
+    fn filter(s: &str) -> ObjectTypeFilter {
+        s.parse().unwrap()
+    }
+
+    fn tag(s: &str) -> StructTag {
+        parse_sui_struct_tag(s).unwrap()
+    }
+
+    #[test]
+    fn parses_package_and_module_wildcards() {
+        assert_eq!(filter("0x2::*"), filter("0x2"));
+        assert!(matches!(filter("0x2"), ObjectTypeFilter::Package(_)));
+        assert_eq!(filter("0x2::coin::*"), filter("0x2::coin"));
+        assert!(matches!(filter("0x2::coin"), ObjectTypeFilter::Module(_)));
+    }
+
+    #[test]
+    fn parses_type_param_wildcards() {
+        assert_eq!(
+            filter("0x2::coin::Coin<*>"),
+            ObjectTypeFilter::Instantiation {
+                type_: tag("0x2::coin::Coin"),
+                arity: 1,
+            }
+        );
+        assert_eq!(
+            filter("0x2::dynamic_field::Field<0x2::table::Table<u64, u8>, *>"),
+            ObjectTypeFilter::Instantiation {
+                type_: StructTag {
+                    type_params: vec![TypeTag::Struct(Box::new(tag("0x2::table::Table<u64, u8>")))],
+                    ..tag("0x2::dynamic_field::Field")
+                },
+                arity: 2,
+            }
+        );
+        assert_eq!(
+            filter("0x2::coin::Coin<0x2::sui::SUI>"),
+            ObjectTypeFilter::Type(tag("0x2::coin::Coin<0x2::sui::SUI>"))
+        );
+    }
+
+    #[test]
+    fn rejects_leading_wildcards() {
+        assert!(
+            "0x2::dynamic_field::Field<*, u64>"
+                .parse::<ObjectTypeFilter>()
+                .is_err()
+        );
+        assert!(
+            "0x2::coin::Coin<0x2::lp::LP<*>>"
+                .parse::<ObjectTypeFilter>()
+                .is_err()
+        );
+        assert!("0x2::coin::*<u64>".parse::<ObjectTypeFilter>().is_err());
+    }
+
+    #[test]
+    fn matches_instantiations() {
+        let field = filter("0x2::dynamic_field::Field<u64, *>");
+        assert!(field.matches(&tag("0x2::dynamic_field::Field<u64, u8>")));
+        assert!(field.matches(&tag("0x2::dynamic_field::Field<u64, 0x2::sui::SUI>")));
+        assert!(!field.matches(&tag("0x2::dynamic_field::Field<u8, u64>")));
+
+        let coin = filter("0x2::coin::Coin<*>");
+        assert!(coin.matches(&tag("0x2::coin::Coin<0x2::sui::SUI>")));
+        assert!(!coin.matches(&tag("0x2::coin::TreasuryCap<0x2::sui::SUI>")));
+
+        let module = filter("0x2::coin::*");
+        assert!(module.matches(&tag("0x2::coin::TreasuryCap<0x2::sui::SUI>")));
+        assert!(!module.matches(&tag("0x2::balance::Balance<0x2::sui::SUI>")));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in [
+            "0x2::*",
+            "0x2::coin::*",
+            "0x2::coin::Coin",
+            "0x2::coin::Coin<0x2::sui::SUI>",
+            "0x2::coin::Coin<*>",
+            "0x2::dynamic_field::Field<u64, *>",
+        ] {
+            let f = filter(s);
+            assert_eq!(filter(&f.to_string()), f, "{s}");
+        }
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::ObjectStore;
+use super::ObjectTypeFilter;
 use super::error::Result;
 use crate::balance_change::{BalanceChange, derive_balance_changes};
 use crate::base_types::{EpochId, ObjectID, ObjectType, SequenceNumber, SuiAddress};
@@ -776,10 +777,12 @@ pub struct LedgerBitmapBucket {
 pub trait RpcIndexes: Send + Sync {
     fn get_epoch_info(&self, epoch: EpochId) -> Result<Option<EpochInfo>>;
 
+    /// Iterate the objects owned by `owner`, or only those whose type matches `object_type`, in
+    /// the order of the owned-object index, resuming from `cursor` (inclusive).
     fn owned_objects_iter(
         &self,
         owner: SuiAddress,
-        object_type: Option<StructTag>,
+        object_type: Option<ObjectTypeFilter>,
         cursor: Option<OwnedObjectInfo>,
     ) -> Result<Box<dyn Iterator<Item = Result<OwnedObjectInfo, TypedStoreError>> + '_>>;
 
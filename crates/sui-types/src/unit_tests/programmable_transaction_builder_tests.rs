@@ -1,9 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::base_types::random_object_ref;
+use crate::base_types::{SuiAddress, random_object_ref};
 use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use crate::transaction::Argument::Input;
+use crate::transaction::Argument::{Input, NestedResult};
 use crate::transaction::{CallArg, Command, ObjectArg};
 
 #[test]
@@ -157,3 +157,66 @@ fn test_builder_smash_coins_zero_coin() {
 
     assert!(result.is_err());
 }
+
+#[test]
+fn test_builder_pay_exact_keeps_change() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let coin1_ref = random_object_ref();
+    let coin2_ref = random_object_ref();
+    let recipient = SuiAddress::random_for_testing_only();
+
+    builder
+        .pay_exact(vec![coin1_ref, coin2_ref], 100, recipient, 60)
+        .unwrap();
+
+    let tx = builder.finish();
+
+    assert_eq!(
+        tx.inputs,
+        vec![
+            CallArg::Pure(bcs::to_bytes(&recipient).unwrap()),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(coin1_ref)),
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(coin2_ref)),
+            CallArg::Pure(bcs::to_bytes(&60u64).unwrap()),
+        ]
+    );
+    assert_eq!(
+        tx.commands,
+        vec![
+            Command::MergeCoins(Input(1), vec![Input(2)]),
+            Command::SplitCoins(Input(1), vec![Input(3)]),
+            Command::TransferObjects(vec![NestedResult(1, 0)], Input(0)),
+        ]
+    );
+}
+
+#[test]
+fn test_builder_pay_exact_whole_balance() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let coin_ref = random_object_ref();
+    let recipient = SuiAddress::random_for_testing_only();
+
+    builder
+        .pay_exact(vec![coin_ref], 100, recipient, 100)
+        .unwrap();
+
+    let tx = builder.finish();
+
+    assert_eq!(
+        tx.commands,
+        vec![Command::TransferObjects(vec![Input(1)], Input(0))]
+    );
+}
+
+#[test]
+fn test_builder_pay_exact_insufficient_balance() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let recipient = SuiAddress::random_for_testing_only();
+
+    assert!(
+        builder
+            .pay_exact(vec![random_object_ref()], 50, recipient, 100)
+            .is_err()
+    );
+    assert!(builder.pay_exact(vec![], 100, recipient, 100).is_err());
+}
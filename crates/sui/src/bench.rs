@@ -0,0 +1,794 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A load generator for `sui bench`. It drives a mix of workloads against the active environment
+//! (or a local network started in-process) from the accounts of the wallet, and reports
+//! throughput, latency percentiles and the congestion counters of the validators.
+//!
+//! Each worker owns a gas coin and keeps one transaction in flight at a time, so the number of
+//! workers bounds the load. Transactions only use the Sui framework, so no package needs to be
+//! published first.
+
+use anyhow::{Context, anyhow, bail, ensure};
+use clap::*;
+use futures::future::join_all;
+use move_core_types::ident_str;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+use sui_config::{PersistedConfig, network_config::NetworkConfig};
+use sui_rpc_api::Client;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{
+    SUI_FRAMEWORK_PACKAGE_ID,
+    base_types::{ObjectRef, SuiAddress},
+    effects::TransactionEffectsAPI,
+    gas_coin::{GAS, GasCoin},
+    object::Owner,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{
+        Argument, Command, ObjectArg, ProgrammableTransaction, SharedObjectMutability,
+        TransactionData,
+    },
+};
+use tabled::{
+    builder::Builder as TableBuilder,
+    settings::{Panel as TablePanel, Style as TableStyle},
+};
+use test_cluster::TestClusterBuilder;
+use url::Url;
+
+/// Counters of the consensus handler, reported as the change over the run.
+const DEFERRED_METRIC: &str = "consensus_handler_deferred_transactions";
+const CONGESTED_METRIC: &str = "consensus_handler_congested_transactions";
+
+/// Limits of the setup transaction, which creates two coins per worker.
+const MAX_WORKERS: usize = 500;
+const MAX_SHARED_OBJECTS: usize = 1000;
+const MAX_PTB_SIZE: u64 = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize)]
+#[clap(rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum Workload {
+    /// Transfer an owned object back to the sender
+    OwnedTransfer,
+    /// Mutate one of a few shared objects, which goes through consensus
+    SharedCounter,
+    /// Create a batch of new owned objects
+    NftMint,
+    /// Run a PTB with many commands
+    LargePtb,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// The workloads to run. Workers cycle through them, so the load is split evenly.
+    #[clap(long = "workload", value_enum, num_args(1..), default_values_t = [Workload::OwnedTransfer])]
+    pub workloads: Vec<Workload>,
+
+    /// Number of workers, each keeping one transaction in flight
+    #[clap(long, default_value_t = 8)]
+    pub workers: usize,
+
+    /// How long to run for, in seconds
+    #[clap(long, default_value_t = 30)]
+    pub duration: u64,
+
+    /// Stop after this many transactions, even if the duration has not elapsed
+    #[clap(long)]
+    pub max_transactions: Option<u64>,
+
+    /// Gas budget of each transaction, in MIST
+    #[clap(long, default_value_t = 100_000_000)]
+    pub gas_budget: u64,
+
+    /// Amount of SUI (in MIST) to give to the gas coin of each worker. Defaults to 50 times the
+    /// gas budget.
+    #[clap(long)]
+    pub gas_per_worker: Option<u64>,
+
+    /// Number of shared objects the shared-counter workload spreads its transactions over.
+    /// Fewer objects mean more contention.
+    #[clap(long, default_value_t = 1)]
+    pub shared_objects: usize,
+
+    /// Number of objects created by each nft-mint transaction
+    #[clap(long, default_value_t = 10)]
+    pub mint_batch: u64,
+
+    /// Number of commands in each large-ptb transaction
+    #[clap(long, default_value_t = 500)]
+    pub ptb_size: u64,
+
+    /// Prometheus endpoints of validators to read congestion and deferral counts from, e.g.
+    /// `http://127.0.0.1:9184/metrics`
+    #[clap(long = "metrics-url", num_args(1..))]
+    pub metrics_urls: Vec<Url>,
+
+    /// Read the metrics endpoints of the validators from the network config written by
+    /// `sui start` or `sui genesis`
+    #[clap(long = "network.config")]
+    pub network_config: Option<PathBuf>,
+
+    /// Start a local network in-process and run against it, instead of the active environment
+    #[clap(long, conflicts_with = "network_config")]
+    pub local: bool,
+
+    /// Number of validators of the local network
+    #[clap(long, default_value_t = 4, requires = "local")]
+    pub committee_size: usize,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchReport {
+    pub duration_secs: f64,
+    pub workers: usize,
+    pub workloads: Vec<WorkloadReport>,
+    pub total: WorkloadReport,
+    /// Transactions that could not be executed at all, e.g. because of RPC errors.
+    pub errors: u64,
+    pub first_error: Option<String>,
+    pub congestion: Option<CongestionReport>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadReport {
+    pub workload: String,
+    pub transactions: u64,
+    /// Transactions that were executed but aborted.
+    pub failed: u64,
+    pub tps: f64,
+    pub latency_ms: LatencyReport,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct LatencyReport {
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CongestionReport {
+    /// Transactions deferred by consensus, for any reason.
+    pub deferred_transactions: u64,
+    /// Transactions deferred because the shared objects they use were congested.
+    pub congested_transactions: u64,
+}
+
+impl BenchArgs {
+    /// Run the benchmark from the accounts of `context`, or against a new local network if
+    /// `--local` is set.
+    pub async fn execute(self, context: Option<WalletContext>) -> anyhow::Result<BenchReport> {
+        ensure!(!self.workloads.is_empty(), "No workload to run");
+        ensure!(
+            (1..=MAX_WORKERS).contains(&self.workers),
+            "The number of workers must be between 1 and {MAX_WORKERS}"
+        );
+        ensure!(
+            (1..=MAX_SHARED_OBJECTS).contains(&self.shared_objects),
+            "The number of shared objects must be between 1 and {MAX_SHARED_OBJECTS}"
+        );
+        ensure!(
+            (2..=MAX_PTB_SIZE).contains(&self.ptb_size),
+            "The PTB size must be between 2 and {MAX_PTB_SIZE}"
+        );
+
+        if self.local {
+            let cluster = TestClusterBuilder::new()
+                .with_num_validators(self.committee_size)
+                .build()
+                .await;
+            let metrics_urls = validator_metrics_urls(cluster.swarm.config())?;
+            return self.run(&cluster.wallet, metrics_urls).await;
+        }
+
+        let mut context = context.ok_or_else(|| anyhow!("No wallet to run the benchmark with"))?;
+        let mut metrics_urls = self.metrics_urls.clone();
+        if let Some(path) = &self.network_config {
+            let config: NetworkConfig = PersistedConfig::read(path).map_err(|err| {
+                err.context(format!("Cannot open Sui network config file at {path:?}"))
+            })?;
+            metrics_urls.extend(validator_metrics_urls(&config)?);
+        }
+        // make sure there is an active address to run from
+        context.active_address()?;
+        self.run(&context, metrics_urls).await
+    }
+
+    async fn run(
+        &self,
+        context: &WalletContext,
+        metrics_urls: Vec<Url>,
+    ) -> anyhow::Result<BenchReport> {
+        context.ensure_can_sign()?;
+        let sender = context
+            .config
+            .active_address
+            .ok_or_else(|| anyhow!("No active address to run the benchmark from"))?;
+        let client = context.grpc_client()?;
+        let gas_price = client.get_reference_gas_price().await?;
+
+        eprintln!("Setting up {} workers for {sender}", self.workers);
+        let mut workers = self
+            .setup_workers(context, &client, sender, gas_price)
+            .await?;
+        let shared_objects = if self.workloads.contains(&Workload::SharedCounter) {
+            self.setup_shared_objects(context, &client, &mut workers[0], gas_price)
+                .await?
+        } else {
+            vec![]
+        };
+
+        let metrics_before = scrape_metrics(&metrics_urls).await;
+        eprintln!(
+            "Running {} for {}s",
+            self.workloads
+                .iter()
+                .map(Workload::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.duration
+        );
+        let start = Instant::now();
+        let deadline = start + Duration::from_secs(self.duration);
+        let issued = AtomicU64::new(0);
+        let runs = join_all(workers.into_iter().enumerate().map(|(i, worker)| {
+            let client = client.clone();
+            let shared_objects = &shared_objects;
+            let issued = &issued;
+            async move {
+                self.run_worker(
+                    context,
+                    client,
+                    worker,
+                    i,
+                    shared_objects,
+                    gas_price,
+                    deadline,
+                    issued,
+                )
+                .await
+            }
+        }))
+        .await;
+        let elapsed = start.elapsed();
+        let metrics_after = scrape_metrics(&metrics_urls).await;
+
+        let mut samples: BTreeMap<Workload, Vec<Sample>> = BTreeMap::new();
+        let mut errors = 0;
+        let mut first_error = None;
+        for run in runs {
+            for sample in run.samples {
+                samples.entry(sample.workload).or_default().push(sample);
+            }
+            errors += run.errors;
+            first_error = first_error.or(run.first_error);
+        }
+        let all: Vec<Sample> = samples.values().flatten().cloned().collect();
+
+        Ok(BenchReport {
+            duration_secs: elapsed.as_secs_f64(),
+            workers: self.workers,
+            workloads: samples
+                .iter()
+                .map(|(w, s)| workload_report(&w.to_string(), s, elapsed))
+                .collect(),
+            total: workload_report("total", &all, elapsed),
+            errors,
+            first_error,
+            congestion: congestion_report(metrics_before, metrics_after),
+        })
+    }
+
+    /// Split the gas coins of the sender into a gas coin and a small owned object per worker.
+    async fn setup_workers(
+        &self,
+        context: &WalletContext,
+        client: &Client,
+        sender: SuiAddress,
+        gas_price: u64,
+    ) -> anyhow::Result<Vec<WorkerState>> {
+        let gas_per_worker = self
+            .gas_per_worker
+            .unwrap_or(self.gas_budget.saturating_mul(50));
+        ensure!(
+            gas_per_worker >= self.gas_budget,
+            "Each worker needs at least the gas budget of a transaction"
+        );
+        let needed = gas_per_worker
+            .checked_mul(self.workers as u64)
+            .and_then(|n| n.checked_add(self.gas_budget))
+            .ok_or_else(|| anyhow!("The workers need more SUI than exists"))?;
+        let (_, gas) = context
+            .gas_objects(sender)
+            .await?
+            .into_iter()
+            .max_by_key(|(value, _)| *value)
+            .ok_or_else(|| anyhow!("{sender} has no gas coins"))?;
+        ensure!(
+            gas.get_single_owner() == Some(sender) && GasCoin::try_from(&gas)?.value() >= needed,
+            "The benchmark needs a gas coin of at least {needed} MIST owned by {sender}, \
+             merge coins or lower --workers, --gas-per-worker or --gas-budget"
+        );
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let mut amounts = vec![gas_per_worker; self.workers];
+        amounts.extend(vec![1; self.workers]);
+        builder.pay_sui(vec![sender; amounts.len()], amounts)?;
+        let effects = execute(
+            context,
+            client,
+            TransactionData::new_programmable(
+                sender,
+                vec![gas.compute_object_reference()],
+                builder.finish(),
+                self.gas_budget,
+                gas_price,
+            ),
+        )
+        .await
+        .context("Failed to set up the gas coins of the workers")?;
+
+        let created: Vec<ObjectRef> = effects.created().into_iter().map(|(r, _)| r).collect();
+        let objects = client
+            .batch_get_objects(&created.iter().map(|r| r.0).collect::<Vec<_>>())
+            .await?;
+        let (mut gas_coins, mut owned) = (vec![], vec![]);
+        for object in objects {
+            let object_ref = object.compute_object_reference();
+            if GasCoin::try_from(&object)?.value() == 1 {
+                owned.push(object_ref);
+            } else {
+                gas_coins.push(object_ref);
+            }
+        }
+        ensure!(
+            gas_coins.len() == self.workers && owned.len() == self.workers,
+            "Unexpected objects created while setting up the workers"
+        );
+        Ok(gas_coins
+            .into_iter()
+            .zip(owned)
+            .map(|(gas, owned)| WorkerState { sender, gas, owned })
+            .collect())
+    }
+
+    /// Create and share the objects the shared-counter workload mutates: zero-value SUI coins,
+    /// which anyone can join coins into.
+    async fn setup_shared_objects(
+        &self,
+        context: &WalletContext,
+        client: &Client,
+        worker: &mut WorkerState,
+        gas_price: u64,
+    ) -> anyhow::Result<Vec<ObjectArg>> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        for _ in 0..self.shared_objects {
+            let coin = coin_zero(&mut builder);
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                ident_str!("transfer").to_owned(),
+                ident_str!("public_share_object").to_owned(),
+                vec![GasCoin::type_().into()],
+                vec![coin],
+            );
+        }
+        let effects = execute(
+            context,
+            client,
+            TransactionData::new_programmable(
+                worker.sender,
+                vec![worker.gas],
+                builder.finish(),
+                self.gas_budget,
+                gas_price,
+            ),
+        )
+        .await
+        .context("Failed to create the shared objects")?;
+        if let Some((gas, _)) = effects.gas_object() {
+            worker.gas = gas;
+        }
+
+        Ok(effects
+            .created()
+            .into_iter()
+            .filter_map(|((id, _, _), owner)| match owner {
+                Owner::Shared {
+                    initial_shared_version,
+                } => Some(ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    mutability: SharedObjectMutability::Mutable,
+                }),
+                _ => None,
+            })
+            .collect())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_worker(
+        &self,
+        context: &WalletContext,
+        client: Client,
+        mut worker: WorkerState,
+        index: usize,
+        shared_objects: &[ObjectArg],
+        gas_price: u64,
+        deadline: Instant,
+        issued: &AtomicU64,
+    ) -> WorkerRun {
+        let mut run = WorkerRun::default();
+        let mut seq = index;
+        while Instant::now() < deadline {
+            if let Some(max) = self.max_transactions
+                && issued.fetch_add(1, Ordering::Relaxed) >= max
+            {
+                break;
+            }
+            let workload = self.workloads[seq % self.workloads.len()];
+            let shared = shared_objects.get(seq % shared_objects.len().max(1));
+            seq += 1;
+
+            let pt = match self.build_transaction(workload, &worker, shared) {
+                Ok(pt) => pt,
+                Err(e) => {
+                    run.error(e);
+                    break;
+                }
+            };
+            let data = TransactionData::new_programmable(
+                worker.sender,
+                vec![worker.gas],
+                pt,
+                self.gas_budget,
+                gas_price,
+            );
+            let start = Instant::now();
+            match execute_unchecked(context, &client, data).await {
+                Ok(effects) => {
+                    run.samples.push(Sample {
+                        workload,
+                        latency: start.elapsed(),
+                        ok: effects.status().is_ok(),
+                    });
+                    if let Some((gas, _)) = effects.gas_object() {
+                        worker.gas = gas;
+                    }
+                    if let Some((owned, _)) = effects
+                        .mutated()
+                        .into_iter()
+                        .find(|((id, _, _), _)| *id == worker.owned.0)
+                    {
+                        worker.owned = owned;
+                    }
+                }
+                Err(e) => {
+                    run.error(e);
+                    // the transaction may or may not have gone through, so re-read the objects
+                    match worker.refresh(context).await {
+                        Ok(()) => (),
+                        Err(e) => {
+                            run.error(e);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        run
+    }
+
+    fn build_transaction(
+        &self,
+        workload: Workload,
+        worker: &WorkerState,
+        shared: Option<&ObjectArg>,
+    ) -> anyhow::Result<ProgrammableTransaction> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        match workload {
+            Workload::OwnedTransfer => {
+                let object = builder.obj(ObjectArg::ImmOrOwnedObject(worker.owned))?;
+                builder.transfer_arg(worker.sender, object);
+            }
+            Workload::SharedCounter => {
+                let shared = shared.ok_or_else(|| anyhow!("No shared object to mutate"))?;
+                let shared = builder.obj(*shared)?;
+                let coin = coin_zero(&mut builder);
+                builder.programmable_move_call(
+                    SUI_FRAMEWORK_PACKAGE_ID,
+                    ident_str!("coin").to_owned(),
+                    ident_str!("join").to_owned(),
+                    vec![GAS::type_tag()],
+                    vec![shared, coin],
+                );
+            }
+            Workload::NftMint => {
+                let coins = (0..self.mint_batch)
+                    .map(|_| coin_zero(&mut builder))
+                    .collect();
+                builder.transfer_args(worker.sender, coins);
+            }
+            Workload::LargePtb => {
+                // split off and merge back one MIST per command, so the gas coin is unchanged
+                let amount = builder.pure(1u64)?;
+                let coins = (0..self.ptb_size / 2)
+                    .map(|_| {
+                        let Argument::Result(i) =
+                            builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]))
+                        else {
+                            unreachable!("commands return results");
+                        };
+                        Argument::NestedResult(i, 0)
+                    })
+                    .collect::<Vec<_>>();
+                for coin in coins {
+                    builder.command(Command::MergeCoins(Argument::GasCoin, vec![coin]));
+                }
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
+struct WorkerState {
+    sender: SuiAddress,
+    gas: ObjectRef,
+    owned: ObjectRef,
+}
+
+impl WorkerState {
+    async fn refresh(&mut self, context: &WalletContext) -> anyhow::Result<()> {
+        self.gas = context.get_object_ref(self.gas.0).await?;
+        self.owned = context.get_object_ref(self.owned.0).await?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Sample {
+    pub workload: Workload,
+    pub latency: Duration,
+    pub ok: bool,
+}
+
+#[derive(Default)]
+struct WorkerRun {
+    samples: Vec<Sample>,
+    errors: u64,
+    first_error: Option<String>,
+}
+
+impl WorkerRun {
+    fn error(&mut self, e: anyhow::Error) {
+        self.errors += 1;
+        self.first_error.get_or_insert_with(|| format!("{e:#}"));
+    }
+}
+
+fn coin_zero(builder: &mut ProgrammableTransactionBuilder) -> Argument {
+    builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        ident_str!("coin").to_owned(),
+        ident_str!("zero").to_owned(),
+        vec![GAS::type_tag()],
+        vec![],
+    )
+}
+
+/// Execute a transaction, returning its effects whether or not it aborted.
+async fn execute_unchecked(
+    context: &WalletContext,
+    client: &Client,
+    data: TransactionData,
+) -> anyhow::Result<sui_types::effects::TransactionEffects> {
+    let tx = context.try_sign_transaction(&data).await?;
+    Ok(client.clone().execute_transaction(&tx).await?.effects)
+}
+
+/// Execute a transaction and wait for it to be checkpointed, failing if it aborted.
+async fn execute(
+    context: &WalletContext,
+    client: &Client,
+    data: TransactionData,
+) -> anyhow::Result<sui_types::effects::TransactionEffects> {
+    let tx = context.try_sign_transaction(&data).await?;
+    let effects = client
+        .execute_transaction_and_wait_for_checkpoint(&tx)
+        .await?
+        .effects;
+    if !effects.status().is_ok() {
+        bail!("Transaction failed: {:?}", effects.status());
+    }
+    Ok(effects)
+}
+
+fn validator_metrics_urls(config: &NetworkConfig) -> anyhow::Result<Vec<Url>> {
+    config
+        .validator_configs()
+        .iter()
+        .map(|c| {
+            Ok(Url::parse(&format!(
+                "http://{}/metrics",
+                c.metrics_address
+            ))?)
+        })
+        .collect()
+}
+
+/// Read the congestion counters from each endpoint. Endpoints that cannot be read are skipped.
+async fn scrape_metrics(urls: &[Url]) -> Vec<(Url, BTreeMap<String, u64>)> {
+    let client = reqwest::Client::new();
+    let mut scraped = vec![];
+    for url in urls {
+        let text = match client.get(url.clone()).send().await {
+            Ok(response) => response.text().await,
+            Err(e) => Err(e),
+        };
+        match text {
+            Ok(text) => scraped.push((
+                url.clone(),
+                parse_counters(&text, &[DEFERRED_METRIC, CONGESTED_METRIC]),
+            )),
+            Err(e) => eprintln!("Cannot read metrics from {url}: {e}"),
+        }
+    }
+    scraped
+}
+
+/// Sum the samples of each of `names` in a Prometheus text exposition, across all labels.
+pub fn parse_counters(text: &str, names: &[&str]) -> BTreeMap<String, u64> {
+    let mut counters = BTreeMap::new();
+    for line in text.lines().filter(|l| !l.starts_with('#')) {
+        for name in names {
+            let Some(rest) = line.strip_prefix(name) else {
+                continue;
+            };
+            if !rest.starts_with([' ', '{']) {
+                continue;
+            }
+            let value = rest
+                .rsplit_once('}')
+                .map_or(rest, |(_, value)| value)
+                .split_whitespace()
+                .next()
+                .and_then(|v| v.parse::<f64>().ok());
+            if let Some(value) = value {
+                *counters.entry(name.to_string()).or_default() += value as u64;
+            }
+        }
+    }
+    counters
+}
+
+/// The increase of the counters over the run. Validators see the same consensus output, so the
+/// largest increase is reported rather than the sum.
+fn congestion_report(
+    before: Vec<(Url, BTreeMap<String, u64>)>,
+    after: Vec<(Url, BTreeMap<String, u64>)>,
+) -> Option<CongestionReport> {
+    let before: BTreeMap<_, _> = before.into_iter().collect();
+    let mut report: Option<CongestionReport> = None;
+    for (url, after) in after {
+        let Some(before) = before.get(&url) else {
+            continue;
+        };
+        let delta = |name: &str| {
+            after
+                .get(name)
+                .copied()
+                .unwrap_or_default()
+                .saturating_sub(before.get(name).copied().unwrap_or_default())
+        };
+        let report = report.get_or_insert_with(CongestionReport::default);
+        report.deferred_transactions = report.deferred_transactions.max(delta(DEFERRED_METRIC));
+        report.congested_transactions = report.congested_transactions.max(delta(CONGESTED_METRIC));
+    }
+    report
+}
+
+pub fn workload_report(workload: &str, samples: &[Sample], elapsed: Duration) -> WorkloadReport {
+    let mut latencies: Vec<Duration> = samples.iter().map(|s| s.latency).collect();
+    latencies.sort();
+    WorkloadReport {
+        workload: workload.to_string(),
+        transactions: samples.len() as u64,
+        failed: samples.iter().filter(|s| !s.ok).count() as u64,
+        tps: samples.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        latency_ms: LatencyReport {
+            p50: percentile_ms(&latencies, 50.0),
+            p90: percentile_ms(&latencies, 90.0),
+            p99: percentile_ms(&latencies, 99.0),
+            max: latencies.last().map_or(0.0, |l| l.as_secs_f64() * 1000.0),
+        },
+    }
+}
+
+/// The nearest-rank percentile of sorted `latencies`, in milliseconds.
+pub fn percentile_ms(latencies: &[Duration], percentile: f64) -> f64 {
+    if latencies.is_empty() {
+        return 0.0;
+    }
+    let rank = ((percentile / 100.0) * latencies.len() as f64).ceil() as usize;
+    latencies[rank.clamp(1, latencies.len()) - 1].as_secs_f64() * 1000.0
+}
+
+impl Display for Workload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => write!(f, "{}", value.get_name()),
+            None => write!(f, "{self:?}"),
+        }
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut builder = TableBuilder::default();
+        builder.push_record([
+            "workload",
+            "transactions",
+            "failed",
+            "tps",
+            "p50 (ms)",
+            "p90 (ms)",
+            "p99 (ms)",
+            "max (ms)",
+        ]);
+        for w in self.workloads.iter().chain([&self.total]) {
+            builder.push_record([
+                w.workload.clone(),
+                w.transactions.to_string(),
+                w.failed.to_string(),
+                format!("{:.1}", w.tps),
+                format!("{:.1}", w.latency_ms.p50),
+                format!("{:.1}", w.latency_ms.p90),
+                format!("{:.1}", w.latency_ms.p99),
+                format!("{:.1}", w.latency_ms.max),
+            ]);
+        }
+        let mut table = builder.build();
+        table.with(TablePanel::header(format!(
+            "Benchmark of {:.1}s with {} workers",
+            self.duration_secs, self.workers
+        )));
+        table.with(TableStyle::rounded());
+        writeln!(f, "{table}")?;
+
+        if self.errors > 0 {
+            writeln!(
+                f,
+                "{} transactions could not be executed. First error: {}",
+                self.errors,
+                self.first_error.as_deref().unwrap_or_default()
+            )?;
+        }
+        match &self.congestion {
+            Some(c) => write!(
+                f,
+                "Deferred transactions: {} ({} because of congestion)",
+                c.deferred_transactions, c.congested_transactions
+            ),
+            None => write!(
+                f,
+                "No validator metrics read, pass --metrics-url or --network.config for \
+                 congestion counts"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/bench_tests.rs"]
+mod bench_tests;
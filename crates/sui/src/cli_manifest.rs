@@ -0,0 +1,166 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A machine-readable description of the CLI, generated from its clap definitions, for
+//! documentation tooling and wrapper generators.
+
+use clap::{Arg, ArgAction, Command};
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandManifest {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub about: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    pub hidden: bool,
+    pub args: Vec<ArgManifest>,
+    pub subcommands: Vec<CommandManifest>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ArgManifest {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    pub positional: bool,
+    pub required: bool,
+    pub global: bool,
+    pub hidden: bool,
+    /// Whether the argument takes a value, as opposed to being a flag.
+    pub takes_value: bool,
+    /// Whether the argument can be given several values or repeated.
+    pub multiple: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub value_names: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub possible_values: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub default_values: Vec<String>,
+}
+
+/// Describe `command` and all of its subcommands. The command is built first, so that global
+/// arguments and generated `help`/`version` flags show up on every subcommand.
+pub fn command_manifest(mut command: Command) -> CommandManifest {
+    command.build();
+    describe_command(&command)
+}
+
+fn describe_command(command: &Command) -> CommandManifest {
+    CommandManifest {
+        name: command.get_name().to_string(),
+        version: command.get_version().map(str::to_string),
+        about: command.get_about().map(|about| about.to_string()),
+        aliases: command.get_visible_aliases().map(str::to_string).collect(),
+        hidden: command.is_hide_set(),
+        args: command.get_arguments().map(describe_arg).collect(),
+        subcommands: command.get_subcommands().map(describe_command).collect(),
+    }
+}
+
+fn describe_arg(arg: &Arg) -> ArgManifest {
+    let multiple = matches!(arg.get_action(), ArgAction::Append | ArgAction::Count)
+        || arg
+            .get_num_args()
+            .is_some_and(|range| range.max_values() > 1);
+
+    ArgManifest {
+        id: arg.get_id().to_string(),
+        long: arg.get_long().map(str::to_string),
+        short: arg.get_short(),
+        help: arg.get_help().map(|help| help.to_string()),
+        positional: arg.is_positional(),
+        required: arg.is_required_set(),
+        global: arg.is_global_set(),
+        hidden: arg.is_hide_set(),
+        takes_value: arg.get_action().takes_values(),
+        multiple,
+        value_names: arg
+            .get_value_names()
+            .unwrap_or_default()
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        possible_values: arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect(),
+        default_values: arg
+            .get_default_values()
+            .iter()
+            .map(|value| value.to_string_lossy().into_owned())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+    use crate::sui_commands::SuiCommand;
+
+    fn subcommand<'a>(manifest: &'a CommandManifest, name: &str) -> &'a CommandManifest {
+        manifest
+            .subcommands
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("No subcommand {name} in {}", manifest.name))
+    }
+
+    fn arg<'a>(manifest: &'a CommandManifest, id: &str) -> &'a ArgManifest {
+        manifest
+            .args
+            .iter()
+            .find(|a| a.id == id)
+            .unwrap_or_else(|| panic!("No argument {id} in {}", manifest.name))
+    }
+
+    #[test]
+    fn test_manifest_describes_command_tree() {
+        let manifest = command_manifest(SuiCommand::command());
+
+        assert!(subcommand(&manifest, "analyzer").hidden);
+        assert!(subcommand(&manifest, "__manifest").hidden);
+        assert!(!subcommand(&manifest, "client").hidden);
+
+        let client = subcommand(&manifest, "client");
+        let json = arg(client, "json");
+        assert_eq!(json.long.as_deref(), Some("json"));
+        assert!(json.global && !json.takes_value);
+
+        // Global arguments are propagated to subcommands.
+        let balance = subcommand(client, "balance");
+        assert!(arg(balance, "json").global);
+
+        let split = subcommand(client, "split-coin");
+        let amounts = arg(split, "amounts");
+        assert!(amounts.takes_value && amounts.multiple);
+
+        let completion = subcommand(&manifest, "completion");
+        assert!(
+            arg(completion, "shell")
+                .possible_values
+                .contains(&"bash".to_string())
+        );
+    }
+
+    #[test]
+    fn test_manifest_serializes() {
+        let manifest = command_manifest(SuiCommand::command());
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["name"], "sui");
+        assert!(json["subcommands"].as_array().unwrap().len() > 10);
+    }
+}
@@ -4,9 +4,19 @@
 use crate::{
     clever_error_rendering::render_clever_error_opt,
     client_ptb::ptb::PTB,
+    config_commands::{ConfigBundleOutput, ConfigCommand},
+    deny_list_commands::{DenyListCheckOutput, DenyListCommand},
+    display_commands::{DisplayCommand, DisplayOutput},
     displays::Pretty,
+    error_codes::{ErrorCode, ErrorCodeExt},
+    gas_profile::{GasProfileOutput, profile_gas},
+    history_commands::{HistoryCommand, HistoryEntry, record_transaction},
+    kiosk_commands::{KioskCommand, KioskOutput},
+    package_commands::{PackageCommand, PackageOutput},
+    stake_commands::{CompoundOutput, StakeCommand, StakeOutput},
+    upgrade_cap_commands::{UpgradeCapCommand, UpgradeCapOutput},
     upgrade_compatibility::check_compatibility,
-    verifier_meter::{AccumulatingMeter, Accumulator},
+    verifier_meter::{AccumulatingMeter, Accumulator, NearLimit, percent_of_limit},
 };
 use futures::{StreamExt, TryStreamExt};
 use std::{
@@ -30,7 +40,7 @@ use fastcrypto::{
 };
 use reqwest::StatusCode;
 
-use move_binary_format::CompiledModule;
+use move_binary_format::{CompiledModule, binary_config::BinaryConfig};
 use move_bytecode_utils::module_cache::GetModule;
 use move_bytecode_verifier_meter::Scope;
 use move_core_types::{
@@ -47,7 +57,7 @@ use sui_config::verifier_signing_config::VerifierSigningConfig;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 
 use shared_crypto::intent::Intent;
-use sui_json::SuiJsonValue;
+use sui_json::{SuiJsonValue, fill_default_args};
 use sui_json_rpc_types::{
     BalanceChange as RpcBalanceChange, BcsEvent, Coin as RpcCoin, DryRunTransactionBlockResponse,
     ObjectChange as RpcObjectChange, SuiEvent, SuiTransactionBlock, SuiTransactionBlockEffects,
@@ -55,8 +65,10 @@ use sui_json_rpc_types::{
 };
 use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keystore::AccountKeystore;
-use sui_move_build::{BuildConfig, CompiledPackage, PackageDependencies};
-use sui_package_management::LockCommand;
+use sui_move_build::{
+    BuildConfig, CompiledPackage, PackageDependencies, argument_presets::ArgumentPresetManifest,
+};
+use sui_package_management::{LockCommand, PublishedAtError};
 use sui_rpc_api::{
     Client,
     client::{ExecutedTransaction, SimulateTransactionResponse},
@@ -64,13 +76,18 @@ use sui_rpc_api::{
 use sui_sdk::{
     SUI_DEVNET_URL, SUI_LOCAL_NETWORK_URL, SUI_LOCAL_NETWORK_URL_0, SUI_TESTNET_URL,
     digests::chain_id_base58,
-    sui_client_config::{SuiClientConfig, SuiEnv},
+    gas_price::{GasPriceStrategy, GasPriceStrategyConfig},
+    sui_client_config::{SuiClientConfig, SuiEnv, WatchOnlyAddress},
     sui_sdk_types::bcs::ToBcs,
+    tx_summary,
     wallet_context::WalletContext,
 };
 use sui_types::{
     SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_PACKAGE_ID,
-    base_types::{FullObjectID, ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress},
+    base_types::{
+        FullObjectID, ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress, TxContext,
+        TxContextKind,
+    },
     coin::{COIN_MODULE_NAME, COIN_STRUCT_NAME, Coin},
     crypto::{EmptySignInfo, SignatureScheme},
     digests::TransactionDigest,
@@ -121,6 +138,8 @@ use tracing::{debug, info};
 const NUM_CONCURRENCY_REQS: usize = 8;
 /// Rate limit for RPC calls to avoid being throttled by the server. This is equivalent to 20rps.
 const RATE_LIMIT_MILLIS: u64 = 50;
+/// How far below its quote a swap paying for gas may fall, in basis points, unless overridden.
+const DEFAULT_GAS_SWAP_SLIPPAGE_BPS: u16 = 100;
 
 pub(crate) static USER_AGENT: &str =
     concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
@@ -162,8 +181,22 @@ pub enum SuiClientCommands {
     #[clap(name = "call")]
     Call {
         /// Object ID of the package, which contains the module
+        #[clap(
+            long,
+            required_unless_present = "package_name",
+            conflicts_with = "package_name"
+        )]
+        package: Option<ObjectID>,
+        /// Name of the package, which contains the module, to use instead of `--package`. The
+        /// package ID is read from the publication recorded for the active environment by the
+        /// local Move package at `--package-path`, which can be the named package itself or
+        /// depend on it.
         #[clap(long)]
-        package: ObjectID,
+        package_name: Option<String>,
+        /// Path to the local Move package used to resolve `--package-name` (defaults to the
+        /// current directory)
+        #[clap(long, requires = "package_name")]
+        package_path: Option<PathBuf>,
         /// The name of the module in the package
         #[clap(long)]
         module: String,
@@ -182,6 +215,11 @@ pub enum SuiClientCommands {
         /// ObjectIDs, Addresses must be hex strings
         #[clap(long, num_args(1..))]
         args: Vec<SuiJsonValue>,
+        /// Argument-preset manifest of the package, as generated by `sui move build
+        /// --generate-argument-presets`. The parameters the function declares presets for can
+        /// then be left out of `--args`, together with well-known system objects.
+        #[clap(long, value_name = "FILE")]
+        presets: Option<PathBuf>,
 
         #[clap(flatten)]
         payment: PaymentArgs,
@@ -206,6 +244,30 @@ pub enum SuiClientCommands {
         format: Option<ChainIdentifierFormat>,
     },
 
+    /// Export the client configuration (environments, addresses, aliases and optionally keys) to
+    /// a portable bundle, or import one, e.g. to migrate to a new machine.
+    #[clap(name = "config")]
+    Config {
+        #[clap(subcommand)]
+        cmd: ConfigCommand,
+    },
+
+    /// Manage the deny list of a regulated coin: deny or allow addresses, pause the coin
+    /// globally, or check its current state.
+    #[clap(name = "deny-list")]
+    DenyList {
+        #[clap(subcommand)]
+        cmd: DenyListCommand,
+    },
+
+    /// Inspect and edit the Display of a type: show its template, render it against an object,
+    /// check it for missing fields, or update its fields.
+    #[clap(name = "display")]
+    Display {
+        #[clap(subcommand)]
+        cmd: DisplayCommand,
+    },
+
     /// Query a dynamic field by its address.
     #[clap(name = "dynamic-field")]
     DynamicFieldQuery {
@@ -262,6 +324,22 @@ pub enum SuiClientCommands {
         address: Option<KeyIdentity>,
     },
 
+    /// Browse and label the transactions submitted from this client, recorded in a local
+    /// history next to the client config.
+    #[clap(name = "history")]
+    History {
+        #[clap(subcommand)]
+        cmd: HistoryCommand,
+    },
+
+    /// Create and manage kiosks: place, list, delist, purchase and withdraw, inspect kiosk
+    /// contents, and install or remove transfer policy rules.
+    #[clap(name = "kiosk")]
+    Kiosk {
+        #[clap(subcommand)]
+        cmd: KioskCommand,
+    },
+
     /// Merge two coin objects into one coin
     MergeCoin {
         /// The address of the coin to merge into.
@@ -327,6 +405,14 @@ pub enum SuiClientCommands {
         address: Option<KeyIdentity>,
     },
 
+    /// Inspect on-chain packages: list the public and entry functions, structs and linkage of a
+    /// package, and optionally disassemble its modules.
+    #[clap(name = "package")]
+    Package {
+        #[clap(subcommand)]
+        cmd: PackageCommand,
+    },
+
     /// Transfer object to party ownership
     #[clap(name = "party-transfer")]
     PartyTransfer {
@@ -485,6 +571,14 @@ pub enum SuiClientCommands {
     )]
     TestUpgrade(TestUpgradeArgs),
 
+    /// Manage the UpgradeCap of a package: show its upgrade policy, restrict the policy, make the
+    /// package immutable, or transfer the cap, e.g. to a multisig.
+    #[clap(name = "upgrade-cap")]
+    UpgradeCap {
+        #[clap(subcommand)]
+        cmd: UpgradeCapCommand,
+    },
+
     /// Execute, dry-run, dev-inspect or otherwise inspect an already serialized transaction.
     SerializedTx {
         /// Base64-encoded BCS-serialized TransactionData.
@@ -532,6 +626,13 @@ pub enum SuiClientCommands {
         processing: TxProcessingArgs,
     },
 
+    /// Stake SUI with validators, withdraw and list stakes, or compound staking rewards.
+    #[clap(name = "stake")]
+    Stake {
+        #[clap(subcommand)]
+        cmd: StakeCommand,
+    },
+
     /// Switch active address and network(e.g., devnet, local rpc server).
     #[clap(name = "switch")]
     Switch {
@@ -598,7 +699,8 @@ pub enum SuiClientCommands {
         processing: TxProcessingArgs,
     },
 
-    /// Run the bytecode verifier on the package
+    /// Run the bytecode verifier on the package, and report how close its modules and functions
+    /// are to the verifier's metering limits
     #[clap(name = "verify-bytecode-meter")]
     VerifyBytecodeMeter {
         /// Path to directory containing a Move package, (defaults to the current directory)
@@ -616,6 +718,11 @@ pub enum SuiClientCommands {
         #[clap(name = "module", long, action = clap::ArgAction::Append, global = true)]
         module_paths: Vec<PathBuf>,
 
+        /// Report the modules and functions that use at least this percentage of the ticks
+        /// allowed for them
+        #[clap(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(0..=100))]
+        near_limit_percent: u8,
+
         /// Package build options
         #[clap(flatten)]
         build_config: MoveBuildConfig,
@@ -646,7 +753,19 @@ pub enum SuiClientCommands {
         address_override: Option<ObjectID>,
     },
 
-    /// Remove an existing address by its alias or hexadecimal string.
+    /// Track an address without its key. Watch-only addresses can be queried and set as the
+    /// active address, but transactions cannot be signed for them.
+    #[clap(name = "watch-address")]
+    WatchAddress {
+        /// The address to watch
+        address: SuiAddress,
+        /// The alias to refer to the address by. A random alias is generated if not provided.
+        #[clap(long)]
+        alias: Option<String>,
+    },
+
+    /// Remove an existing address, or stop watching a watch-only address, by its alias or
+    /// hexadecimal string.
     #[clap(name = "remove-address")]
     RemoveAddress { alias_or_address: String },
 
@@ -682,7 +801,7 @@ pub struct GasDataArgs {
     #[arg(long)]
     pub gas_budget: Option<u64>,
     /// An optional gas price for this transaction (in MIST). If gas price is not provided, the
-    /// tool will use the current reference gas price from RPC.
+    /// tool will choose one according to `--gas-price-strategy`.
     ///
     /// Transactions with a gas price lower than the reference will not be signed by enough
     /// validators to execute. Transactions accessing congested shared objects are prioritized by
@@ -690,6 +809,13 @@ pub struct GasDataArgs {
     /// transaction accesses the shared object sooner.
     #[arg(long)]
     pub gas_price: Option<u64>,
+    /// How to choose the gas price when `--gas-price` is not provided: `rgp` for the reference gas
+    /// price (the default), `rgp*<MULTIPLIER>` for a multiple of it (e.g. `rgp*1.5`), or
+    /// `p<PERCENTILE>[:<CHECKPOINTS>]` for a percentile of the gas prices paid by transactions in
+    /// the latest checkpoints (e.g. `p90`, over 10 checkpoints by default and at most 100). The gas
+    /// price is never lower than the reference gas price.
+    #[arg(long, value_name = "STRATEGY", conflicts_with = "gas_price")]
+    pub gas_price_strategy: Option<GasPriceStrategyConfig>,
     /// An optional field to specify a gas sponsor address. If provided, the gas owner is set to
     /// this address, rather than the transaction's sender.
     ///
@@ -697,6 +823,24 @@ pub struct GasDataArgs {
     /// sign and execute transactions that have a sponsor set.
     #[arg(long)]
     pub gas_sponsor: Option<SuiAddress>,
+    /// Pay the gas sponsor back in this coin type, for senders that hold no SUI. The sender's
+    /// coins are swapped for SUI through the DeepBook pool configured for this coin type in the
+    /// active environment's `gas_swap_routes`, and the gas budget is sent to the sponsor out of the
+    /// swap's output. The rest of the output is sent to the sender.
+    #[arg(
+        long,
+        value_parser = parse_sui_type_tag,
+        value_name = "COIN_TYPE",
+        requires_all = ["gas_sponsor", "gas_budget", "gas_swap_amount"],
+    )]
+    pub pay_gas_with: Option<TypeTag>,
+    /// With `--pay-gas-with`, the amount of the coin to swap for SUI, in the coin's smallest unit.
+    #[arg(long, requires = "pay_gas_with")]
+    pub gas_swap_amount: Option<u64>,
+    /// With `--pay-gas-with`, the most the swap may fall short of its quoted output, in basis
+    /// points. Defaults to 100 (1%).
+    #[arg(long, requires = "pay_gas_with")]
+    pub gas_swap_slippage_bps: Option<u16>,
 }
 
 /// Arguments related to what to do to a transaction after it has been built.
@@ -708,6 +852,10 @@ pub struct TxProcessingArgs {
     /// Perform a dry run of the transaction, without executing it.
     #[arg(long)]
     pub dry_run: bool,
+    /// With `--dry-run`, break down the computation cost of the transaction by command, and save
+    /// it as a flamegraph-compatible (speedscope) profile in the working directory.
+    #[arg(long, requires = "dry_run")]
+    pub gas_profile: bool,
     /// Perform a dev inspect
     #[arg(long)]
     pub dev_inspect: bool,
@@ -733,6 +881,18 @@ pub struct TxProcessingArgs {
     /// support sender impersonation.
     #[arg(long)]
     pub skip_signing: bool,
+    /// Record the executed transaction in the local history with this label, to find it later
+    /// with `sui client history list`.
+    #[arg(long)]
+    pub label: Option<String>,
+    /// Before signing, print a plain-language summary of what the transaction does: the objects
+    /// it transfers and to whom, the functions it calls and the most gas it can spend.
+    #[arg(long)]
+    pub preview: bool,
+    /// With `--preview`, refuse to sign transactions that cannot be fully summarized, such as
+    /// transfers to a computed recipient.
+    #[arg(long, requires = "preview")]
+    pub strict_preview: bool,
 }
 
 #[derive(Args, Debug, Default)]
@@ -882,14 +1042,22 @@ impl SuiClientCommands {
                     .into_iter()
                     .map(|(address, alias)| (alias.alias.to_string(), *address))
                     .collect();
+                let mut watch_only: Vec<(String, SuiAddress)> = context
+                    .config
+                    .watch_only
+                    .iter()
+                    .map(|w| (w.alias.clone(), w.address))
+                    .collect();
 
                 if sort_by_alias {
                     addresses.sort();
+                    watch_only.sort();
                 }
 
                 let output = AddressesOutput {
                     active_address,
                     addresses,
+                    watch_only,
                 };
                 SuiClientCommandResult::Addresses(output)
             }
@@ -1002,6 +1170,7 @@ impl SuiClientCommands {
                 protocol_version,
                 module_paths,
                 package_path,
+                near_limit_percent,
                 build_config,
             } => {
                 let client = context.grpc_client()?;
@@ -1078,12 +1247,19 @@ impl SuiClientCommands {
                     Some(allowed_ticks) if allowed_ticks < used_ticks.max_ticks(Scope::Function)
                 );
 
+                let near_limit = used_ticks.near_limit(
+                    meter_config.max_per_mod_meter_units,
+                    meter_config.max_per_fun_meter_units,
+                    near_limit_percent,
+                );
+
                 SuiClientCommandResult::VerifyBytecodeMeter {
                     success: !exceeded,
                     max_package_ticks: meter_config.max_per_pkg_meter_units,
                     max_module_ticks: meter_config.max_per_mod_meter_units,
                     max_function_ticks: meter_config.max_per_fun_meter_units,
                     used_ticks,
+                    near_limit,
                 }
             }
 
@@ -1108,10 +1284,13 @@ impl SuiClientCommands {
 
             SuiClientCommands::Call {
                 package,
+                package_name,
+                package_path,
                 module,
                 function,
                 type_args,
                 args,
+                presets,
                 payment,
                 gas_data,
                 processing,
@@ -1128,9 +1307,26 @@ impl SuiClientCommands {
                     .map(|arg| arg.into())
                     .collect::<Vec<_>>();
 
-                let client = context.grpc_client()?;
+                let package = match (package, package_name) {
+                    (Some(package), _) => package,
+                    (None, Some(package_name)) => {
+                        let package_path = package_path.unwrap_or_else(|| PathBuf::from("."));
+                        resolve_package_id_by_name(context, &package_path, &package_name).await?
+                    }
+                    (None, None) => bail!("Either --package or --package-name must be provided"),
+                };
+
+                let mut client = context.grpc_client()?;
                 let _ = context.cache_chain_id().await?;
 
+                let args = match presets {
+                    Some(presets) => {
+                        fill_preset_args(&mut client, package, &module, &function, args, &presets)
+                            .await?
+                    }
+                    None => args,
+                };
+
                 let tx_kind = client
                     .transaction_builder()
                     .move_call_tx_kind(package, &module, &function, type_args, args)
@@ -1534,6 +1730,13 @@ impl SuiClientCommands {
             SuiClientCommands::RemoveAddress { alias_or_address } => {
                 let identity = KeyIdentity::from_str(&alias_or_address)
                     .map_err(|e| anyhow!("Invalid address or alias: {}", e))?;
+                if context.config.get_watch_only(&identity).is_some() {
+                    context.config.remove_watch_only(&identity)?;
+                    context.config.save()?;
+                    return Ok(SuiClientCommandResult::RemoveAddress(RemoveAddressOutput {
+                        alias_or_address,
+                    }));
+                }
                 let address: SuiAddress = context.config.keystore.get_by_identity(&identity)?;
 
                 context.config.keystore.remove(address).await?;
@@ -1541,6 +1744,14 @@ impl SuiClientCommands {
                 SuiClientCommandResult::RemoveAddress(RemoveAddressOutput { alias_or_address })
             }
 
+            SuiClientCommands::WatchAddress { address, alias } => {
+                let alias = context.config.keystore.create_alias(alias)?;
+                let watch = WatchOnlyAddress { alias, address };
+                context.config.add_watch_only(watch.clone())?;
+                context.config.save()?;
+                SuiClientCommandResult::WatchAddress(watch)
+            }
+
             SuiClientCommands::Gas { address } => {
                 let address = context.get_identity_address(address)?;
                 let coins = context
@@ -1689,6 +1900,7 @@ impl SuiClientCommands {
                     gas_budget: Some(tx_data.gas_budget()),
                     gas_price: Some(tx_data.gas_price()),
                     gas_sponsor: Some(tx_data.gas_owner()),
+                    ..Default::default()
                 };
                 let tx_kind = tx_data.into_kind();
 
@@ -1746,7 +1958,12 @@ impl SuiClientCommands {
 
                 if let Some(address) = address {
                     let address = context.get_identity_address(Some(address))?;
-                    if !context.get_addresses().contains(&address) {
+                    if !context.get_addresses().contains(&address)
+                        && context
+                            .config
+                            .get_watch_only(&KeyIdentity::Address(address))
+                            .is_none()
+                    {
                         return Err(anyhow!("Address {} not managed by wallet", address));
                     }
                     context.config.active_address = Some(address);
@@ -1789,6 +2006,7 @@ impl SuiClientCommands {
                 let transaction = Transaction::from_generic_sig_data(data, sigs);
 
                 let response = context.execute_transaction_may_fail(transaction).await?;
+                record_transaction(context, &response, None);
                 SuiClientCommandResult::TransactionBlock(response)
             }
             SuiClientCommands::ExecuteCombinedSignedTx { signed_tx_bytes } => {
@@ -1800,6 +2018,7 @@ impl SuiClientCommands {
                 ).map_err(|_| anyhow!("Failed to parse SenderSignedData bytes, check if it matches the output of sui client commands with --serialize-signed-transaction"))?;
                 let transaction = Envelope::<SenderSignedData, EmptySignInfo>::new(data);
                 let response = context.execute_transaction_may_fail(transaction).await?;
+                record_transaction(context, &response, None);
                 SuiClientCommandResult::TransactionBlock(response)
             }
             SuiClientCommands::NewEnv {
@@ -1819,6 +2038,7 @@ impl SuiClientCommands {
                     ws,
                     basic_auth,
                     chain_id: None,
+                    gas_swap_routes: vec![],
                 };
 
                 // Check urls are valid and server is reachable
@@ -1950,6 +2170,29 @@ impl SuiClientCommands {
                 )
                 .await?
             }
+            SuiClientCommands::DenyList { cmd } => {
+                let _ = context.cache_chain_id().await?;
+                cmd.execute(context).await?
+            }
+            SuiClientCommands::Display { cmd } => {
+                let _ = context.cache_chain_id().await?;
+                cmd.execute(context).await?
+            }
+            SuiClientCommands::Config { cmd } => cmd.execute(context).await?,
+            SuiClientCommands::History { cmd } => cmd.execute(context).await?,
+            SuiClientCommands::Kiosk { cmd } => {
+                let _ = context.cache_chain_id().await?;
+                cmd.execute(context).await?
+            }
+            SuiClientCommands::Package { cmd } => cmd.execute(context).await?,
+            SuiClientCommands::UpgradeCap { cmd } => {
+                let _ = context.cache_chain_id().await?;
+                cmd.execute(context).await?
+            }
+            SuiClientCommands::Stake { cmd } => {
+                let _ = context.cache_chain_id().await?;
+                cmd.execute(context).await?
+            }
             SuiClientCommands::PTB(ptb) => {
                 let _ = context.cache_chain_id().await?;
                 ptb.execute(context).await?;
@@ -2081,7 +2324,7 @@ pub(crate) async fn compile_package(
         std::io::Stderr,
         SuiFlavor,
     >(root_pkg, &build_config, &mut std::io::stderr())
-    .unwrap();
+    .error_code(ErrorCode::Build)?;
 
     let published_at = root_pkg
         .publication()
@@ -2091,6 +2334,8 @@ pub(crate) async fn compile_package(
         package,
         dependency_ids,
         published_at,
+        // Presets are only used to build calls, not to publish.
+        argument_presets: Default::default(),
     };
 
     if compiled_package
@@ -2214,13 +2459,22 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::Addresses(addresses) => {
                 let mut builder = TableBuilder::default();
                 builder.set_header(vec!["alias", "address", "active address"]);
-                for (alias, address) in &addresses.addresses {
+                let watch_only = addresses
+                    .watch_only
+                    .iter()
+                    .map(|(alias, address)| (format!("{alias} (watch-only)"), address));
+                for (alias, address) in addresses
+                    .addresses
+                    .iter()
+                    .map(|(alias, address)| (alias.to_string(), address))
+                    .chain(watch_only)
+                {
                     let active_address = if address == &addresses.active_address {
                         "*".to_string()
                     } else {
                         "".to_string()
                     };
-                    builder.push_record([alias.to_string(), address.to_string(), active_address]);
+                    builder.push_record([alias, address.to_string(), active_address]);
                 }
                 let mut table = builder.build();
                 let style = TableStyle::rounded();
@@ -2419,12 +2673,21 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::VerifySource => {
                 writeln!(writer, "Source verification succeeded!")?;
             }
+            SuiClientCommandResult::WatchAddress(watch) => {
+                writeln!(
+                    writer,
+                    "Watching address {} as '{}'. It can be queried and set as the active \
+                     address, but cannot sign transactions.",
+                    watch.address, watch.alias
+                )?;
+            }
             SuiClientCommandResult::VerifyBytecodeMeter {
                 success,
                 max_package_ticks,
                 max_module_ticks,
                 max_function_ticks,
                 used_ticks,
+                near_limit,
             } => {
                 let mut builder = TableBuilder::default();
 
@@ -2460,17 +2723,22 @@ impl Display for SuiClientCommandResult {
                 builder.push_record(vec!["Ticks Used"]);
                 let mut stack = vec![used_ticks];
                 while let Some(usage) = stack.pop() {
-                    let indent = match usage.scope {
-                        Scope::Transaction => 0,
-                        Scope::Package => 0,
-                        Scope::Module => 2,
-                        Scope::Function => 4,
+                    let (indent, limit) = match usage.scope {
+                        Scope::Transaction => (0, None),
+                        Scope::Package => (0, *max_package_ticks),
+                        Scope::Module => (2, *max_module_ticks),
+                        Scope::Function => (4, *max_function_ticks),
                     };
 
-                    builder.push_record(vec![
-                        format!("{:indent$}{}", "", usage.name),
-                        format_ticks(usage.ticks),
-                    ]);
+                    let ticks = match limit {
+                        Some(limit) => format!(
+                            "{} ({}%)",
+                            format_ticks(usage.ticks),
+                            percent_of_limit(usage.ticks, limit)
+                        ),
+                        None => format_ticks(usage.ticks),
+                    };
+                    builder.push_record(vec![format!("{:indent$}{}", "", usage.name), ticks]);
 
                     stack.extend(usage.children.iter().rev())
                 }
@@ -2506,6 +2774,24 @@ impl Display for SuiClientCommandResult {
                 table.with(tabled::settings::style::BorderSpanCorrection);
 
                 writeln!(f, "{}", table)?;
+
+                if !near_limit.is_empty() {
+                    writeln!(f, "Close to the metering limits:")?;
+                    for near in near_limit {
+                        let scope = match near.scope {
+                            Scope::Function => "function",
+                            _ => "module",
+                        };
+                        writeln!(
+                            f,
+                            "  {scope} {}: {} of {} ticks ({}%)",
+                            near.name,
+                            format_ticks(near.ticks),
+                            format_ticks(near.limit),
+                            near.percent()
+                        )?;
+                    }
+                }
             }
             SuiClientCommandResult::NoOutput => {}
             SuiClientCommandResult::DryRun(response) => {
@@ -2515,12 +2801,312 @@ impl Display for SuiClientCommandResult {
                     writeln!(f, "{}", Pretty(response))?;
                 }
             }
+            SuiClientCommandResult::DryRunGasProfile(response, profile) => {
+                if let Some(legacy) = to_legacy_dry_run_transaction_block_response(response) {
+                    writeln!(f, "{}", Pretty(&legacy))?;
+                } else {
+                    writeln!(f, "{}", Pretty(response))?;
+                }
+
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec!["command", "computation cost (MIST)"]);
+                builder.push_record(vec!["base".to_string(), profile.base_cost.to_string()]);
+                for command in &profile.commands {
+                    builder.push_record(vec![
+                        command.command.clone(),
+                        command.computation_cost.to_string(),
+                    ]);
+                }
+                builder.push_record(vec![
+                    "total".to_string(),
+                    profile.computation_cost.to_string(),
+                ]);
+                let mut table = builder.build();
+                table.with(TablePanel::header("Gas profile"));
+                table.with(TablePanel::footer(format!(
+                    "Profile saved to {}",
+                    profile.profile_path.display()
+                )));
+                table.with(TableStyle::rounded());
+                writeln!(f, "{}", table)?;
+            }
             SuiClientCommandResult::DevInspect(response) => {
                 writeln!(f, "{}", Pretty(response))?;
             }
             SuiClientCommandResult::ChainIdentifier(ci) => {
                 write!(f, "{}", ci)?;
             }
+            SuiClientCommandResult::ConfigBundle(output) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec!["environments".to_string(), output.envs.to_string()]);
+                builder.push_record(vec!["addresses".to_string(), output.addresses.to_string()]);
+                builder.push_record(vec!["keys".to_string(), output.keys.to_string()]);
+                builder.push_record(vec![
+                    "watch-only".to_string(),
+                    output.watch_only.to_string(),
+                ]);
+                builder.push_record(vec![
+                    "keys encrypted".to_string(),
+                    output.encrypted.to_string(),
+                ]);
+                let mut table = builder.build();
+                table.with(TablePanel::header(if output.imported {
+                    format!("Imported from {}", output.bundle.display())
+                } else {
+                    format!("Exported to {}", output.bundle.display())
+                }));
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::DenyListCheck(status) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec!["", "current epoch", "next epoch"]);
+                if let (Some(current), Some(next)) = (
+                    status.global_pause_current_epoch,
+                    status.global_pause_next_epoch,
+                ) {
+                    builder.push_record(vec![
+                        "global pause".to_string(),
+                        current.to_string(),
+                        next.to_string(),
+                    ]);
+                }
+                for address in &status.addresses {
+                    builder.push_record(vec![
+                        address.address.to_string(),
+                        address.denied_current_epoch.to_string(),
+                        address.denied_next_epoch.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TablePanel::header(format!(
+                    "Deny list of {}",
+                    status.coin_type
+                )));
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::Display(display) => {
+                let mut builder = TableBuilder::default();
+                if display.object_id.is_some() {
+                    builder.set_header(vec!["field", "template", "rendered"]);
+                } else {
+                    builder.set_header(vec!["field", "template"]);
+                }
+                for field in &display.fields {
+                    let mut record = vec![field.name.clone(), field.template.clone()];
+                    if display.object_id.is_some() {
+                        record.push(match (&field.rendered, &field.error) {
+                            (Some(rendered), _) => rendered.clone(),
+                            (None, Some(error)) => format!("error: {error}"),
+                            (None, None) => String::new(),
+                        });
+                    }
+                    builder.push_record(record);
+                }
+                let mut table = builder.build();
+                let source = match display.display_id {
+                    Some(id) => format!(" ({id}, {})", display.version),
+                    None => format!(" (from file, {})", display.version),
+                };
+                let header = match display.object_id {
+                    Some(object_id) => format!(
+                        "Display of {}{source} rendered for {object_id}",
+                        display.object_type
+                    ),
+                    None => format!("Display of {}{source}", display.object_type),
+                };
+                table.with(TablePanel::header(header));
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::History(entries) => {
+                if entries.is_empty() {
+                    return write!(f, "No transactions in the local history");
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "digest",
+                    "env",
+                    "sender",
+                    "status",
+                    "label",
+                    "timestampMs",
+                ]);
+                for entry in entries {
+                    let status = match &entry.error {
+                        Some(error) => format!("{:?}: {error}", entry.status),
+                        None => format!("{:?}", entry.status),
+                    };
+                    builder.push_record(vec![
+                        entry.digest.to_string(),
+                        entry.env.clone(),
+                        entry.sender.to_string(),
+                        status,
+                        entry.label.clone().unwrap_or_default(),
+                        entry.timestamp_ms.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::Kiosk(kiosk) => {
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec!["objectId", "type", "price (MIST)", "locked"]);
+                for item in &kiosk.items {
+                    let price = match item.price {
+                        Some(price) if item.exclusive => format!("{price} (exclusive)"),
+                        Some(price) => price.to_string(),
+                        None => "-".to_string(),
+                    };
+                    builder.push_record(vec![
+                        item.object_id.to_string(),
+                        item.object_type.clone(),
+                        price,
+                        item.locked.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TablePanel::header(format!(
+                    "Kiosk {} owned by {}, {} item(s), profits: {} MIST",
+                    kiosk.kiosk_id, kiosk.owner, kiosk.item_count, kiosk.profits
+                )));
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::Package(package) => {
+                writeln!(
+                    f,
+                    "Package {} (original ID {}, version {})",
+                    package.package_id, package.original_package_id, package.version
+                )?;
+                for module in &package.modules {
+                    let mut builder = TableBuilder::default();
+                    builder.set_header(vec![
+                        "function",
+                        "visibility",
+                        "typeParameters",
+                        "parameters",
+                        "returns",
+                    ]);
+                    for function in &module.functions {
+                        let visibility = match (function.is_public, function.is_entry) {
+                            (true, true) => "public entry",
+                            (true, false) => "public",
+                            (false, _) => "entry",
+                        };
+                        builder.push_record(vec![
+                            function.name.clone(),
+                            visibility.to_string(),
+                            function.type_parameters.join(", "),
+                            function.parameters.join(",\n"),
+                            function.return_.join(",\n"),
+                        ]);
+                    }
+                    let mut table = builder.build();
+                    table.with(TablePanel::header(format!("Module {}", module.name)));
+                    table.with(TableStyle::rounded());
+                    writeln!(f, "{}", table)?;
+
+                    if !module.structs.is_empty() {
+                        let mut builder = TableBuilder::default();
+                        builder.set_header(vec!["struct", "abilities", "typeParameters", "fields"]);
+                        for struct_ in &module.structs {
+                            builder.push_record(vec![
+                                struct_.name.clone(),
+                                struct_.abilities.join(", "),
+                                struct_.type_parameters.join(", "),
+                                struct_
+                                    .fields
+                                    .iter()
+                                    .map(|field| format!("{}: {}", field.name, field.type_))
+                                    .collect::<Vec<_>>()
+                                    .join(",\n"),
+                            ]);
+                        }
+                        let mut table = builder.build();
+                        table.with(TableStyle::rounded());
+                        writeln!(f, "{}", table)?;
+                    }
+
+                    if let Some(disassembly) = &module.disassembly {
+                        writeln!(f, "{disassembly}")?;
+                    }
+                }
+
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec!["originalId", "upgradedId", "upgradedVersion"]);
+                for linkage in &package.linkage {
+                    builder.push_record(vec![
+                        linkage.original_id.to_string(),
+                        linkage.upgraded_id.to_string(),
+                        linkage.upgraded_version.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TablePanel::header("Linkage"));
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::UpgradeCap(cap) => {
+                let mut builder = TableBuilder::default();
+                builder.push_record(vec![
+                    "upgradeCapId".to_string(),
+                    cap.upgrade_cap_id.to_string(),
+                ]);
+                builder.push_record(vec!["packageId".to_string(), cap.package_id.to_string()]);
+                builder.push_record(vec!["version".to_string(), cap.version.to_string()]);
+                builder.push_record(vec!["policy".to_string(), cap.policy.clone()]);
+                builder.push_record(vec!["owner".to_string(), cap.owner.to_string()]);
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::Stakes(stakes) => {
+                if stakes.is_empty() {
+                    return write!(f, "No stakes are owned by this address");
+                }
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec![
+                    "stakedSuiId",
+                    "validatorAddress",
+                    "principal (MIST)",
+                    "estimatedReward (MIST)",
+                    "activationEpoch",
+                    "status",
+                ]);
+                for stake in stakes {
+                    builder.push_record(vec![
+                        stake.staked_sui_id.to_string(),
+                        stake.validator_address.to_string(),
+                        stake.principal.to_string(),
+                        stake.estimated_reward.to_string(),
+                        stake.activation_epoch.to_string(),
+                        if stake.active { "Active" } else { "Pending" }.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?
+            }
+            SuiClientCommandResult::Compound(compound) => match &compound.transaction {
+                None => write!(
+                    writer,
+                    "No stakes of {} have at least {} MIST of rewards to compound.",
+                    compound.sender, compound.min_rewards
+                )?,
+                Some(transaction) => {
+                    writeln!(
+                        writer,
+                        "Compounding {} stakes worth {} MIST into {} validators.",
+                        compound.staked_sui_ids.len(),
+                        compound.total,
+                        compound.allocations.len()
+                    )?;
+                    write!(writer, "{transaction}")?;
+                }
+            },
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -2759,43 +3345,65 @@ fn convert_number_to_string(value: Value) -> Value {
 
 impl Debug for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let s = unwrap_err_to_string(|| match self {
+        let s = unwrap_err_to_string(|| Ok(serde_json::to_string_pretty(&self.to_json()?)?));
+        write!(f, "{}", s)
+    }
+}
+
+fn unwrap_err_to_string<T: Display, F: FnOnce() -> Result<T, anyhow::Error>>(func: F) -> String {
+    match func() {
+        Ok(s) => format!("{s}"),
+        Err(err) => format!("{err}").red().to_string(),
+    }
+}
+
+impl SuiClientCommandResult {
+    /// The JSON output of the command, as printed with `--json`.
+    fn to_json(&self) -> Result<serde_json::Value, anyhow::Error> {
+        Ok(match self {
             SuiClientCommandResult::Gas(gas_coins) => {
                 let gas_coins = gas_coins
                     .iter()
                     .map(GasCoinOutput::from)
                     .collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&gas_coins)?)
+                serde_json::to_value(&gas_coins)?
             }
             SuiClientCommandResult::Object(object, json_content) => {
                 let object = ObjectOutput::from_object_with_json(object, json_content.clone());
-                Ok(serde_json::to_string_pretty(&object)?)
+                serde_json::to_value(&object)?
+            }
+            SuiClientCommandResult::RawObject(object) => serde_json::to_value(object)?,
+            SuiClientCommandResult::TransactionBlock(response) => {
+                serde_json::to_value(to_legacy_transaction_block_response(response))?
             }
-            SuiClientCommandResult::RawObject(object) => Ok(serde_json::to_string_pretty(&object)?),
-            SuiClientCommandResult::TransactionBlock(response) => Ok(serde_json::to_string_pretty(
-                &to_legacy_transaction_block_response(response),
-            )?),
             SuiClientCommandResult::DryRun(response) => {
                 if let Some(legacy) = to_legacy_dry_run_transaction_block_response(response) {
-                    Ok(serde_json::to_string_pretty(&legacy)?)
+                    serde_json::to_value(&legacy)?
                 } else {
-                    Ok(serde_json::to_string_pretty(response)?)
+                    serde_json::to_value(response)?
                 }
             }
-            _ => Ok(serde_json::to_string_pretty(self)?),
-        });
-        write!(f, "{}", s)
-    }
-}
-
-fn unwrap_err_to_string<T: Display, F: FnOnce() -> Result<T, anyhow::Error>>(func: F) -> String {
-    match func() {
-        Ok(s) => format!("{s}"),
-        Err(err) => format!("{err}").red().to_string(),
+            SuiClientCommandResult::DryRunGasProfile(response, profile) => {
+                let dry_run = match to_legacy_dry_run_transaction_block_response(response) {
+                    Some(legacy) => serde_json::to_value(&legacy)?,
+                    None => serde_json::to_value(response)?,
+                };
+                json!({
+                    "dryRun": dry_run,
+                    "gasProfile": profile,
+                })
+            }
+            SuiClientCommandResult::Compound(compound) => {
+                let mut value = serde_json::to_value(compound)?;
+                if let Some(transaction) = &compound.transaction {
+                    value["transaction"] = transaction.to_json()?;
+                }
+                value
+            }
+            _ => serde_json::to_value(self)?,
+        })
     }
-}
 
-impl SuiClientCommandResult {
     pub fn objects_response(&self) -> Option<Vec<Object>> {
         use SuiClientCommandResult::*;
         match self {
@@ -2833,6 +3441,8 @@ impl SuiClientCommandResult {
 pub struct AddressesOutput {
     pub active_address: SuiAddress,
     pub addresses: Vec<(String, SuiAddress)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub watch_only: Vec<(String, SuiAddress)>,
 }
 
 /// The chain identifier in both supported encodings: the full Base58-encoded genesis checkpoint
@@ -2959,32 +3569,44 @@ pub enum SuiClientCommandResult {
     Addresses(AddressesOutput),
     Balance(Vec<BalanceOutput>, bool),
     ChainIdentifier(ChainIdentifierOutput),
+    Compound(CompoundOutput),
     ComputeTransactionDigest(TransactionData),
+    ConfigBundle(ConfigBundleOutput),
+    DenyListCheck(DenyListCheckOutput),
+    Display(DisplayOutput),
     DynamicFieldQuery(proto::ListDynamicFieldsResponse),
     DryRun(SimulateTransactionResponse),
+    DryRunGasProfile(SimulateTransactionResponse, GasProfileOutput),
     DevInspect(SimulateTransactionResponse),
     Envs(Vec<SuiEnv>, Option<String>),
     Gas(Vec<GasCoin>),
+    History(Vec<HistoryEntry>),
+    Kiosk(KioskOutput),
     NewAddress(NewAddressOutput),
     NewEnv(SuiEnv),
     NoOutput,
     Object(Object, Option<serde_json::Value>),
     Objects(Vec<Object>),
+    Package(PackageOutput),
     RawObject(Object),
     RemoveAddress(RemoveAddressOutput),
     SerializedSignedTransaction(SenderSignedData),
     SerializedUnsignedTransaction(TransactionData),
+    Stakes(Vec<StakeOutput>),
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(ExecutedTransaction),
+    UpgradeCap(UpgradeCapOutput),
     VerifyBytecodeMeter {
         success: bool,
         max_package_ticks: Option<u128>,
         max_module_ticks: Option<u128>,
         max_function_ticks: Option<u128>,
         used_ticks: Accumulator,
+        near_limit: Vec<NearLimit>,
     },
     VerifySource,
+    WatchAddress(WatchOnlyAddress),
 }
 
 #[derive(Serialize, Clone)]
@@ -3603,6 +4225,31 @@ async fn select_gas_with_fullnode(
     ))
 }
 
+/// Print a summary of `tx_data` to stderr. With `strict`, fail if any part of it is not
+/// recognized.
+async fn preview_transaction(
+    client: &Client,
+    tx_data: &TransactionData,
+    strict: bool,
+) -> Result<(), anyhow::Error> {
+    let ids = tx_summary::input_object_ids(tx_data);
+    let object_types: BTreeMap<_, _> = client
+        .batch_get_objects(&ids)
+        .await
+        .rpc_error_code()?
+        .into_iter()
+        .filter_map(|o| Some((o.id(), o.struct_tag()?)))
+        .collect();
+
+    let summary = tx_summary::summarize(tx_data, &object_types, strict);
+    eprintln!("This transaction:\n{summary}");
+    ensure!(
+        !strict || summary.is_recognized(),
+        "Refusing to sign a transaction that is not fully recognized (--strict-preview)"
+    );
+    Ok(())
+}
+
 /// Dry run, execute, or serialize a transaction.
 ///
 /// This basically extracts the logical code for each command that deals with dry run, executing,
@@ -3618,17 +4265,25 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     let GasDataArgs {
         gas_budget,
         gas_price,
+        gas_price_strategy,
         gas_sponsor,
+        pay_gas_with,
+        gas_swap_amount,
+        gas_swap_slippage_bps,
     } = gas_data;
 
     let TxProcessingArgs {
         tx_digest,
         dry_run,
+        gas_profile,
         dev_inspect,
         serialize_unsigned_transaction,
         serialize_signed_transaction,
         sender,
         skip_signing,
+        label,
+        preview,
+        strict_preview,
     } = processing;
 
     ensure!(
@@ -3636,16 +4291,39 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
     );
 
-    let gas_price = if let Some(gas_price) = gas_price {
-        gas_price
-    } else {
-        context.get_reference_gas_price().await?
-    };
-
     let client = context.grpc_client()?;
 
+    let gas_price = match (gas_price, gas_price_strategy) {
+        (Some(gas_price), _) => gas_price,
+        (None, Some(strategy)) => strategy.gas_price(&client).await?,
+        (None, None) => context.get_reference_gas_price().await?,
+    };
+
     let signer = sender.unwrap_or(signer);
 
+    let tx_kind = if let Some(coin_type) = pay_gas_with {
+        let (Some(gas_sponsor), Some(gas_budget), Some(amount_in)) =
+            (gas_sponsor, gas_budget, gas_swap_amount)
+        else {
+            bail!("--pay-gas-with requires --gas-sponsor, --gas-budget and --gas-swap-amount");
+        };
+        pay_gas_with_coin(
+            context,
+            &client,
+            tx_kind,
+            signer,
+            gas_sponsor,
+            coin_type,
+            amount_in,
+            gas_swap_slippage_bps.unwrap_or(DEFAULT_GAS_SWAP_SLIPPAGE_BPS),
+            gas_budget,
+            gas_price,
+        )
+        .await?
+    } else {
+        tx_kind
+    };
+
     if dev_inspect {
         return execute_dev_inspect(
             context,
@@ -3661,16 +4339,44 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     }
 
     if dry_run {
-        return execute_dry_run(
+        let result = execute_dry_run(
             context,
             signer,
-            tx_kind,
+            tx_kind.clone(),
             gas_budget,
             gas_price,
             gas_payment.clone(),
             None,
         )
-        .await;
+        .await?;
+        let SuiClientCommandResult::DryRun(response) = result else {
+            return Ok(result);
+        };
+        if !gas_profile {
+            return Ok(SuiClientCommandResult::DryRun(response));
+        }
+
+        let gas_budget = match gas_budget {
+            Some(gas_budget) => gas_budget,
+            None => max_gas_budget(&client).await?,
+        };
+        let profile = profile_gas(
+            &client,
+            &tx_kind,
+            signer,
+            signer,
+            gas_payment.clone(),
+            gas_budget,
+            gas_price,
+            response.transaction.transaction.digest(),
+            response
+                .transaction
+                .effects
+                .gas_cost_summary()
+                .computation_cost,
+        )
+        .await?;
+        return Ok(SuiClientCommandResult::DryRunGasProfile(response, profile));
     }
 
     let gas_budget = match gas_budget {
@@ -3727,6 +4433,10 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
         let signatures = if skip_signing {
             vec![]
         } else {
+            if preview {
+                preview_transaction(&client, &tx_data, strict_preview).await?;
+            }
+
             let mut signatures = vec![
                 context
                     .sign_secure(
@@ -3734,7 +4444,8 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
                         &tx_data,
                         Intent::sui_transaction(),
                     )
-                    .await?
+                    .await
+                    .error_code(ErrorCode::Signing)?
                     .into(),
             ];
 
@@ -3748,7 +4459,8 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
                             &tx_data,
                             Intent::sui_transaction(),
                         )
-                        .await?
+                        .await
+                        .error_code(ErrorCode::Signing)?
                         .into(),
                 );
             }
@@ -3765,11 +4477,18 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             debug!("Executing transaction: {:?}", transaction);
             let response = context
                 .execute_transaction_may_fail(transaction.clone())
-                .await?;
+                .await
+                .rpc_error_code()?;
             debug!("Transaction executed: {:?}", transaction);
+            record_transaction(context, &response, label);
             if let ExecutionStatus::Failure(ExecutionFailure { error, command }) =
                 response.effects.status()
             {
+                let code = if response.effects.status().is_cancelled() {
+                    ErrorCode::Congestion
+                } else {
+                    ErrorCode::Abort
+                };
                 let description = if let Some(command) = command {
                     format!("{error:?} in command {command}")
                 } else {
@@ -3783,13 +4502,88 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
                 return Err(anyhow!(
                     "Error executing transaction '{}': {error}",
                     response.transaction.digest(),
-                ));
+                ))
+                .error_code(code);
             }
             Ok(SuiClientCommandResult::TransactionBlock(response))
         }
     }
 }
 
+/// Extend `tx_kind` to pay `sponsor` back for gas in `coin_type`: `amount_in` of the signer's
+/// coins are swapped for SUI through the route configured for `coin_type` in the active
+/// environment, and `gas_budget` of the output goes to the sponsor. The swap is quoted first, and
+/// fails on-chain if it produces less than the quote, minus `slippage_bps`.
+#[allow(clippy::too_many_arguments)]
+async fn pay_gas_with_coin(
+    context: &WalletContext,
+    client: &Client,
+    tx_kind: TransactionKind,
+    signer: SuiAddress,
+    sponsor: SuiAddress,
+    coin_type: TypeTag,
+    amount_in: u64,
+    slippage_bps: u16,
+    gas_budget: u64,
+    gas_price: u64,
+) -> Result<TransactionKind, anyhow::Error> {
+    let route = context
+        .config
+        .get_active_env()?
+        .gas_swap_route(&coin_type)?;
+    let builder = client.transaction_builder();
+
+    let quote_tx = TransactionData::new_with_gas_coins(
+        builder.gas_swap_quote_tx_kind(&route, amount_in).await?,
+        signer,
+        vec![],
+        max_gas_budget(client).await?,
+        gas_price,
+    );
+    let response = client.simulate_transaction(&quote_tx, false, false).await?;
+    ensure!(
+        response.transaction.effects.status().is_ok(),
+        "Failed to quote swapping {amount_in} of {coin_type} for SUI: {:?}",
+        response.transaction.effects.status()
+    );
+
+    let outputs = response
+        .command_outputs
+        .first()
+        .ok_or_else(|| anyhow!("Missing gas swap quote"))?
+        .return_values
+        .iter()
+        .map(|v| {
+            let value = v
+                .value_opt()
+                .ok_or_else(|| anyhow!("Missing gas swap quote value"))?;
+            Ok(bcs::from_bytes::<u64>(value.value())?)
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+    let [base_out, quote_out, deep_required] = outputs[..] else {
+        bail!("Unexpected number of gas swap quote values");
+    };
+
+    let quote = route.quote(amount_in, (base_out, quote_out, deep_required));
+    ensure!(
+        quote.deep_required == 0,
+        "The pool for {coin_type} charges {} DEEP in fees, so it cannot be used to pay for gas",
+        quote.deep_required
+    );
+
+    builder
+        .pay_gas_with_coin_tx_kind(
+            tx_kind,
+            signer,
+            sponsor,
+            &route,
+            amount_in,
+            quote.min_sui_out(slippage_bps),
+            gas_budget,
+        )
+        .await
+}
+
 async fn execute_dev_inspect(
     context: &mut WalletContext,
     signer: SuiAddress,
@@ -4017,6 +4811,100 @@ pub async fn load_root_pkg_for_publish_upgrade(
         .await?)
 }
 
+/// Resolve the ID of the package named `package_name` from the publications recorded for the
+/// active environment by the Move package at `package_path` and its dependencies.
+pub async fn resolve_package_id_by_name(
+    wallet: &WalletContext,
+    package_path: &Path,
+    package_name: &str,
+) -> anyhow::Result<ObjectID> {
+    let root_pkg =
+        load_root_pkg_for_publish_upgrade(wallet, &MoveBuildConfig::default(), package_path)
+            .await?;
+    let Some(package) = root_pkg
+        .packages()
+        .into_iter()
+        .find(|package| package.display_name() == package_name)
+    else {
+        bail!(
+            "Package `{package_name}` is neither the package at {} nor one of its dependencies",
+            package_path.display()
+        );
+    };
+    let published = package
+        .published()
+        .ok_or(PublishedAtError::NotPresent)
+        .with_context(|| format!("Cannot resolve the ID of package `{package_name}`"))?;
+    Ok(ObjectID::from_address(published.published_at.0))
+}
+
+/// Fill in the arguments of `module::function` in `package` that were left out of `args` with the
+/// presets the function declares in the argument-preset manifest at `presets`.
+async fn fill_preset_args(
+    client: &mut Client,
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    args: Vec<SuiJsonValue>,
+    presets: &Path,
+) -> anyhow::Result<Vec<SuiJsonValue>> {
+    let manifest: ArgumentPresetManifest = serde_json::from_str(
+        &fs::read_to_string(presets)
+            .with_context(|| format!("Cannot read presets from {}", presets.display()))?,
+    )
+    .with_context(|| format!("{} is not an argument-preset manifest", presets.display()))?;
+    let Some(function_presets) = manifest.function(module, function) else {
+        return Ok(args);
+    };
+
+    let object = client.get_object(package).await?;
+    let Some(package) = object.data.try_as_package() else {
+        bail!("Object {package} is not a package");
+    };
+    let compiled = package.deserialize_module_by_str(module, &BinaryConfig::standard())?;
+    let Some((_, def)) = compiled.find_function_def_by_name(function) else {
+        bail!(
+            "Function {module}::{function} does not exist in package {}",
+            package.id()
+        );
+    };
+    let signature = &compiled
+        .signature_at(compiled.function_handle_at(def.function).parameters)
+        .0;
+
+    // Arguments are passed for every parameter but the `TxContext`, while presets refer to
+    // positions in the full signature.
+    let positions: Vec<_> = (0..signature.len())
+        .filter(|idx| {
+            matches!(
+                TxContext::kind(&compiled, &signature[*idx]),
+                TxContextKind::None
+            )
+        })
+        .collect();
+    let parameters: Vec<_> = positions
+        .iter()
+        .map(|idx| signature[*idx].clone())
+        .collect();
+    let mut defaults = BTreeMap::new();
+    for (name, preset) in function_presets {
+        let Some(position) = positions.iter().position(|idx| *idx == preset.index) else {
+            bail!("Preset for '{name}' of {module}::{function} does not match its signature");
+        };
+        defaults.insert(position, SuiJsonValue::new(preset.value.clone())?);
+    }
+
+    let found = args.len();
+    fill_default_args(
+        &compiled,
+        &parameters,
+        args,
+        defaults,
+        SuiJsonValue::from_object_id,
+    )
+    .ok_or_else(|| anyhow!("Expected {} args, found {found}", parameters.len()))
+}
+
 pub async fn load_root_pkg_for_ephemeral_publish_or_upgrade(
     package_path: &Path,
     chain_id: &str,
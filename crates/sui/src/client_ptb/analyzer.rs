@@ -0,0 +1,682 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use move_binary_format::{
+    CompiledModule,
+    binary_config::BinaryConfig,
+    file_format::{DatatypeHandleIndex, SignatureToken, Visibility},
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{StructTag, TypeTag},
+};
+use serde::Serialize;
+use sui_rpc_api::Client;
+use sui_types::{
+    SUI_FRAMEWORK_PACKAGE_ID,
+    base_types::{ObjectID, SuiAddress, TxContext, TxContextKind},
+    coin::Coin,
+    gas_coin::GasCoin,
+    move_package::{MovePackage, UpgradeCap, UpgradeReceipt},
+    transaction::{
+        Argument, CallArg, Command, ObjectArg, ProgrammableMoveCall, ProgrammableTransaction,
+    },
+    transfer::Receiving,
+};
+
+/// How serious a finding of the analysis is. Errors will make the transaction fail or lose funds,
+/// warnings are likely mistakes.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// An issue found in a PTB, with the index of the command it was found in, if any.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub level: Level,
+    pub command: Option<usize>,
+    pub message: String,
+}
+
+/// The result of statically analyzing a PTB with `--analyze`.
+#[derive(Serialize, Debug, Default)]
+pub struct Analysis {
+    pub findings: Vec<Finding>,
+}
+
+/// How a command uses one of its arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Usage {
+    Move,
+    Borrow,
+    BorrowMut,
+}
+
+/// Tracks the state of the PTB's values while walking its commands in order.
+struct Checker<'a> {
+    ptb: &'a ProgrammableTransaction,
+    input_types: &'a [Option<TypeTag>],
+    packages: &'a BTreeMap<ObjectID, MovePackage>,
+    /// The types of each command's results, or `None` if not even their number is known.
+    results: Vec<Option<Vec<Option<TypeTag>>>>,
+    /// Results that have been used, either as a whole (`None`) or individually.
+    used: BTreeSet<(u16, Option<u16>)>,
+    /// Owned object inputs that have been moved, and the command that moved them.
+    moved: BTreeMap<u16, usize>,
+    /// Owned object inputs used by the current command, and how.
+    command_uses: BTreeMap<u16, Usage>,
+    findings: Vec<Finding>,
+}
+
+impl Analysis {
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.level == Level::Error)
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "No issues found in the PTB.");
+        }
+        for (i, finding) in self.findings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{finding}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        };
+        match self.command {
+            Some(command) => write!(f, "{level} (command {command}): {}", self.message),
+            None => write!(f, "{level}: {}", self.message),
+        }
+    }
+}
+
+/// Analyze `ptb` without executing it: type-check its Move calls against the signatures of the
+/// functions on chain, and flag unused results, transfers to the zero address, and owned objects
+/// used after being moved. `gas` are the objects paying for the transaction.
+pub async fn analyze(
+    client: &mut Client,
+    ptb: &ProgrammableTransaction,
+    gas: &[ObjectID],
+) -> Analysis {
+    let mut findings = vec![];
+
+    let mut input_types = vec![];
+    for (i, input) in ptb.inputs.iter().enumerate() {
+        let (id, receiving) = match input {
+            CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
+            | CallArg::Object(ObjectArg::SharedObject { id, .. }) => (*id, false),
+            CallArg::Object(ObjectArg::Receiving((id, _, _))) => (*id, true),
+            CallArg::Pure(_) | CallArg::FundsWithdrawal(_) => {
+                input_types.push(None);
+                continue;
+            }
+        };
+        let type_ = match client.get_object(id).await {
+            Ok(object) => object.struct_tag().map(|tag| {
+                if receiving {
+                    let mut receiving = Receiving::struct_tag();
+                    receiving.type_params.push(tag.into());
+                    receiving.into()
+                } else {
+                    tag.into()
+                }
+            }),
+            Err(e) => {
+                findings.push(Finding {
+                    level: Level::Error,
+                    command: None,
+                    message: format!("Input {i}: could not fetch object {id}: {}", e.message()),
+                });
+                None
+            }
+        };
+        input_types.push(type_);
+    }
+
+    let mut packages = BTreeMap::new();
+    for (i, command) in ptb.commands.iter().enumerate() {
+        let Command::MoveCall(call) = command else {
+            continue;
+        };
+        if packages.contains_key(&call.package) {
+            continue;
+        }
+        let package = client
+            .get_object(call.package)
+            .await
+            .map_err(|e| e.message().to_owned());
+        match package.and_then(|p| {
+            p.data
+                .try_as_package()
+                .cloned()
+                .ok_or_else(|| "not a package".to_owned())
+        }) {
+            Ok(package) => {
+                packages.insert(call.package, package);
+            }
+            Err(e) => findings.push(Finding {
+                level: Level::Error,
+                command: Some(i),
+                message: format!("Could not fetch package {}: {e}", call.package),
+            }),
+        }
+    }
+
+    findings.extend(check(ptb, &input_types, &packages, gas));
+    Analysis { findings }
+}
+
+/// Check `ptb` given the types of its inputs (`None` for pure inputs and objects that could not be
+/// fetched) and the packages it calls into.
+pub fn check(
+    ptb: &ProgrammableTransaction,
+    input_types: &[Option<TypeTag>],
+    packages: &BTreeMap<ObjectID, MovePackage>,
+    gas: &[ObjectID],
+) -> Vec<Finding> {
+    let gas: BTreeSet<_> = gas.iter().copied().collect();
+    let mut checker = Checker {
+        ptb,
+        input_types,
+        packages,
+        results: vec![],
+        used: BTreeSet::new(),
+        moved: BTreeMap::new(),
+        command_uses: BTreeMap::new(),
+        findings: vec![],
+    };
+
+    for (i, input) in ptb.inputs.iter().enumerate() {
+        if let CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _))) = input
+            && gas.contains(id)
+        {
+            checker.warn(
+                None,
+                format!("Input {i}: object {id} is also used to pay for gas, use `gas` instead"),
+            );
+        }
+    }
+
+    for (i, command) in ptb.commands.iter().enumerate() {
+        checker.command_uses.clear();
+        let results = checker.check_command(i, command);
+        checker.results.push(results);
+    }
+
+    checker.check_unused_results();
+    checker.findings
+}
+
+impl Checker<'_> {
+    fn error(&mut self, command: Option<usize>, message: String) {
+        self.findings.push(Finding {
+            level: Level::Error,
+            command,
+            message,
+        });
+    }
+
+    fn warn(&mut self, command: Option<usize>, message: String) {
+        self.findings.push(Finding {
+            level: Level::Warning,
+            command,
+            message,
+        });
+    }
+
+    /// Check a command, returning the types of its results.
+    fn check_command(&mut self, i: usize, command: &Command) -> Option<Vec<Option<TypeTag>>> {
+        match command {
+            Command::MoveCall(call) => self.check_move_call(i, call),
+
+            Command::TransferObjects(objects, recipient) => {
+                for object in objects {
+                    self.use_arg(i, object, Usage::Move);
+                }
+                self.use_arg(i, recipient, Usage::Move);
+                if self.is_zero_address(recipient) {
+                    self.error(
+                        Some(i),
+                        "Objects are transferred to the zero address, where nobody can access \
+                         them"
+                            .to_owned(),
+                    );
+                }
+                Some(vec![])
+            }
+
+            Command::SplitCoins(coin, amounts) => {
+                let coin_type = self.use_arg(i, coin, Usage::BorrowMut);
+                for amount in amounts {
+                    self.use_arg(i, amount, Usage::Move);
+                }
+                if let Some(type_) = &coin_type
+                    && !is_coin(type_)
+                {
+                    self.error(Some(i), format!("Cannot split {type_}, it is not a coin"));
+                }
+                Some(vec![coin_type; amounts.len()])
+            }
+
+            Command::MergeCoins(target, sources) => {
+                let target_type = self.use_arg(i, target, Usage::BorrowMut);
+                for source in sources {
+                    let source_type = self.use_arg(i, source, Usage::Move);
+                    if let (Some(target), Some(source)) = (&target_type, &source_type)
+                        && (target != source || !is_coin(target))
+                    {
+                        self.error(Some(i), format!("Cannot merge {source} into {target}"));
+                    }
+                }
+                Some(vec![])
+            }
+
+            Command::MakeMoveVec(type_, elements) => {
+                let mut type_ = type_.as_ref().and_then(|t| t.to_type_tag().ok());
+                for element in elements {
+                    let element_type = self.use_arg(i, element, Usage::Move);
+                    if let (Some(expected), Some(actual)) = (&type_, &element_type)
+                        && expected != actual
+                    {
+                        self.error(
+                            Some(i),
+                            format!("Vector of {expected} cannot hold a value of {actual}"),
+                        );
+                    }
+                    if type_.is_none() {
+                        type_ = element_type;
+                    }
+                }
+                Some(vec![type_.map(|t| TypeTag::Vector(Box::new(t)))])
+            }
+
+            Command::Publish(_, _) => Some(vec![Some(UpgradeCap::type_().into())]),
+
+            Command::Upgrade(_, _, _, ticket) => {
+                self.use_arg(i, ticket, Usage::Move);
+                Some(vec![Some(UpgradeReceipt::type_().into())])
+            }
+        }
+    }
+
+    fn check_move_call(
+        &mut self,
+        i: usize,
+        call: &ProgrammableMoveCall,
+    ) -> Option<Vec<Option<TypeTag>>> {
+        let function = format!("{}::{}::{}", call.package, call.module, call.function);
+        let Some(package) = self.packages.get(&call.package) else {
+            // The package could not be fetched, which has already been reported. Still track the
+            // arguments, without knowing how they are used.
+            for arg in &call.arguments {
+                self.use_arg(i, arg, Usage::Borrow);
+            }
+            return None;
+        };
+
+        let module =
+            match package.deserialize_module_by_str(&call.module, &BinaryConfig::standard()) {
+                Ok(module) => module,
+                Err(e) => {
+                    self.error(Some(i), format!("Module of {function} not found: {e}"));
+                    return None;
+                }
+            };
+        let Some(fdef) = module.function_defs.iter().find(|fdef| {
+            module
+                .identifier_at(module.function_handle_at(fdef.function).name)
+                .as_str()
+                == call.function
+        }) else {
+            self.error(Some(i), format!("Function {function} not found"));
+            return None;
+        };
+        if fdef.visibility != Visibility::Public && !fdef.is_entry {
+            self.error(
+                Some(i),
+                format!("Function {function} is neither public nor entry"),
+            );
+        }
+
+        let handle = module.function_handle_at(fdef.function);
+        let type_args: Vec<_> = call
+            .type_arguments
+            .iter()
+            .map(|t| t.to_type_tag().ok())
+            .collect::<Option<_>>()
+            .unwrap_or_default();
+        if call.type_arguments.len() != handle.type_parameters.len() {
+            self.error(
+                Some(i),
+                format!(
+                    "Function {function} expects {} type argument(s), got {}",
+                    handle.type_parameters.len(),
+                    call.type_arguments.len()
+                ),
+            );
+            return None;
+        }
+
+        let parameters: Vec<_> = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .filter(|token| matches!(TxContext::kind(&module, token), TxContextKind::None))
+            .collect();
+        if parameters.len() != call.arguments.len() {
+            self.error(
+                Some(i),
+                format!(
+                    "Function {function} expects {} argument(s), got {}",
+                    parameters.len(),
+                    call.arguments.len()
+                ),
+            );
+            return None;
+        }
+
+        let self_address = *module.address();
+        let is_transfer = call.package == SUI_FRAMEWORK_PACKAGE_ID && call.module == "transfer";
+        for (k, (param, arg)) in parameters.into_iter().zip(&call.arguments).enumerate() {
+            let (usage, param) = match param {
+                SignatureToken::Reference(inner) => (Usage::Borrow, &**inner),
+                SignatureToken::MutableReference(inner) => (Usage::BorrowMut, &**inner),
+                param => (Usage::Move, param),
+            };
+            let actual = self.use_arg(i, arg, usage);
+            let expected = token_type(&module, param, &type_args);
+            if let (Some(expected), Some(actual)) = (&expected, &actual)
+                && !types_match(expected, actual, self_address)
+            {
+                self.error(
+                    Some(i),
+                    format!("Argument {k} of {function} should be {expected}, got {actual}"),
+                );
+            }
+            if is_transfer && expected == Some(TypeTag::Address) && self.is_zero_address(arg) {
+                self.error(
+                    Some(i),
+                    format!("{function} transfers to the zero address, where nobody can access it"),
+                );
+            }
+        }
+
+        Some(
+            module
+                .signature_at(handle.return_)
+                .0
+                .iter()
+                .map(|token| token_type(&module, token, &type_args))
+                .collect(),
+        )
+    }
+
+    /// Record that command `i` uses `arg` as `usage`, returning its type if known.
+    fn use_arg(&mut self, i: usize, arg: &Argument, usage: Usage) -> Option<TypeTag> {
+        match *arg {
+            Argument::GasCoin => Some(GasCoin::type_().into()),
+
+            Argument::Input(input) => {
+                if let Some(CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))) =
+                    self.ptb.inputs.get(input as usize)
+                {
+                    self.use_owned_object(i, input, *id, usage);
+                }
+                self.input_types.get(input as usize).cloned().flatten()
+            }
+
+            Argument::Result(result) => {
+                self.used.insert((result, None));
+                match self.results.get(result as usize)?.as_deref()? {
+                    [type_] => type_.clone(),
+                    _ => None,
+                }
+            }
+
+            Argument::NestedResult(result, nested) => {
+                self.used.insert((result, Some(nested)));
+                self.results
+                    .get(result as usize)?
+                    .as_ref()?
+                    .get(nested as usize)
+                    .cloned()
+                    .flatten()
+            }
+        }
+    }
+
+    fn use_owned_object(&mut self, i: usize, input: u16, id: ObjectID, usage: Usage) {
+        if let Some(moved_by) = self.moved.get(&input) {
+            let message = if *moved_by == i {
+                format!("Owned object {id} is used again after being moved by this command")
+            } else {
+                format!("Owned object {id} is used after being moved by command {moved_by}")
+            };
+            self.warn(Some(i), message);
+            return;
+        }
+
+        if let Some(previous) = self.command_uses.insert(input, usage)
+            && (previous != Usage::Borrow || usage != Usage::Borrow)
+        {
+            self.warn(
+                Some(i),
+                format!("Owned object {id} is used more than once by this command"),
+            );
+        }
+        if usage == Usage::Move {
+            self.moved.insert(input, i);
+        }
+    }
+
+    fn is_zero_address(&self, arg: &Argument) -> bool {
+        let Argument::Input(input) = arg else {
+            return false;
+        };
+        matches!(
+            self.ptb.inputs.get(*input as usize),
+            Some(CallArg::Pure(bytes)) if bytes.as_slice() == SuiAddress::ZERO.as_ref()
+        )
+    }
+
+    fn check_unused_results(&mut self) {
+        let mut unused = vec![];
+        for (i, results) in self.results.iter().enumerate() {
+            let Some(results) = results else {
+                continue;
+            };
+            let i = i as u16;
+            if self.used.contains(&(i, None)) {
+                continue;
+            }
+            for (j, type_) in results.iter().enumerate() {
+                let j = j as u16;
+                if self.used.contains(&(i, Some(j))) {
+                    continue;
+                }
+                // Primitive values can always be dropped, so only flag values that may not be.
+                match type_ {
+                    Some(type_) if !contains_struct(type_) => {}
+                    Some(type_) => unused.push((i, format!("Result {j} ({type_}) is never used"))),
+                    None => unused.push((i, format!("Result {j} is never used"))),
+                }
+            }
+        }
+        for (i, message) in unused {
+            self.warn(
+                Some(i as usize),
+                format!("{message}, the transaction aborts unless it has the `drop` ability"),
+            );
+        }
+    }
+}
+
+/// The type of a value of signature `token`, instantiated with `type_args`. `None` for references
+/// and type parameters without a type argument.
+fn token_type(
+    module: &CompiledModule,
+    token: &SignatureToken,
+    type_args: &[TypeTag],
+) -> Option<TypeTag> {
+    use SignatureToken as S;
+    Some(match token {
+        S::Bool => TypeTag::Bool,
+        S::U8 => TypeTag::U8,
+        S::U16 => TypeTag::U16,
+        S::U32 => TypeTag::U32,
+        S::U64 => TypeTag::U64,
+        S::U128 => TypeTag::U128,
+        S::U256 => TypeTag::U256,
+        S::Address => TypeTag::Address,
+        S::Signer => TypeTag::Signer,
+        S::Vector(inner) => TypeTag::Vector(Box::new(token_type(module, inner, type_args)?)),
+        S::Datatype(idx) => datatype_tag(module, *idx, vec![]).into(),
+        S::DatatypeInstantiation(inst) => {
+            let (idx, params) = &**inst;
+            let params = params
+                .iter()
+                .map(|param| token_type(module, param, type_args))
+                .collect::<Option<_>>()?;
+            datatype_tag(module, *idx, params).into()
+        }
+        S::TypeParameter(idx) => type_args.get(*idx as usize)?.clone(),
+        S::Reference(_) | S::MutableReference(_) => return None,
+    })
+}
+
+fn datatype_tag(
+    module: &CompiledModule,
+    idx: DatatypeHandleIndex,
+    type_params: Vec<TypeTag>,
+) -> StructTag {
+    let handle = module.datatype_handle_at(idx);
+    let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+    StructTag {
+        address: *module_id.address(),
+        module: module_id.name().to_owned(),
+        name: module.identifier_at(handle.name).to_owned(),
+        type_params,
+    }
+}
+
+/// Whether a value of type `actual` can be passed where `expected` is. Types defined by the called
+/// package are referred to by its original ID in signatures, but by the ID of the version that
+/// introduced them in values, so their addresses are not compared.
+fn types_match(expected: &TypeTag, actual: &TypeTag, self_address: AccountAddress) -> bool {
+    match (expected, actual) {
+        (TypeTag::Vector(expected), TypeTag::Vector(actual)) => {
+            types_match(expected, actual, self_address)
+        }
+        (TypeTag::Struct(expected), TypeTag::Struct(actual)) => {
+            (expected.address == actual.address || expected.address == self_address)
+                && expected.module == actual.module
+                && expected.name == actual.name
+                && expected.type_params.len() == actual.type_params.len()
+                && expected
+                    .type_params
+                    .iter()
+                    .zip(&actual.type_params)
+                    .all(|(expected, actual)| types_match(expected, actual, self_address))
+        }
+        (expected, actual) => expected == actual,
+    }
+}
+
+fn is_coin(type_: &TypeTag) -> bool {
+    matches!(type_, TypeTag::Struct(tag) if Coin::is_coin(tag))
+}
+
+fn contains_struct(type_: &TypeTag) -> bool {
+    match type_ {
+        TypeTag::Struct(_) => true,
+        TypeTag::Vector(inner) => contains_struct(inner),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::{
+        base_types::random_object_ref,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+    };
+
+    fn messages(findings: &[Finding]) -> Vec<(Level, Option<usize>)> {
+        findings.iter().map(|f| (f.level, f.command)).collect()
+    }
+
+    #[test]
+    fn test_unused_split_and_zero_address() {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let amount = builder.pure(100u64).unwrap();
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount, amount]));
+        builder.transfer_arg(SuiAddress::ZERO, Argument::NestedResult(0, 0));
+        let ptb = builder.finish();
+
+        let findings = check(&ptb, &[None, None], &BTreeMap::new(), &[]);
+        assert_eq!(
+            messages(&findings),
+            vec![(Level::Error, Some(1)), (Level::Warning, Some(0))]
+        );
+        assert!(findings[0].message.contains("zero address"));
+        assert!(findings[1].message.contains("Result 1"));
+    }
+
+    #[test]
+    fn test_owned_object_moved_twice() {
+        let coin = random_object_ref();
+        let coin_type: TypeTag = GasCoin::type_().into();
+        let recipient = SuiAddress::random_for_testing_only();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let input = builder.obj(ObjectArg::ImmOrOwnedObject(coin)).unwrap();
+        builder.transfer_arg(recipient, input);
+        builder.command(Command::MergeCoins(Argument::GasCoin, vec![input]));
+        let ptb = builder.finish();
+
+        let findings = check(&ptb, &[Some(coin_type), None], &BTreeMap::new(), &[coin.0]);
+        assert_eq!(
+            messages(&findings),
+            vec![(Level::Warning, None), (Level::Warning, Some(1))]
+        );
+        assert!(findings[0].message.contains("pay for gas"));
+        assert!(findings[1].message.contains("moved by command 0"));
+    }
+
+    #[test]
+    fn test_merge_mismatched_coins() {
+        let other_coin: TypeTag = Coin::type_("0x42::token::TOKEN".parse().unwrap()).into();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let input = builder
+            .obj(ObjectArg::ImmOrOwnedObject(random_object_ref()))
+            .unwrap();
+        builder.command(Command::MergeCoins(Argument::GasCoin, vec![input]));
+        let ptb = builder.finish();
+
+        let findings = check(&ptb, &[Some(other_coin)], &BTreeMap::new(), &[]);
+        assert_eq!(messages(&findings), vec![(Level::Error, Some(0))]);
+        assert!(findings[0].message.starts_with("Cannot merge"));
+    }
+}
@@ -24,12 +24,14 @@ pub type ParsedProgram = (Program, ProgramMetadata);
 pub const TRANSFER_OBJECTS: &str = "transfer-objects";
 pub const SPLIT_COINS: &str = "split-coins";
 pub const MERGE_COINS: &str = "merge-coins";
+pub const PAY_EXACT: &str = "pay-exact";
 pub const MAKE_MOVE_VEC: &str = "make-move-vec";
 pub const MOVE_CALL: &str = "move-call";
 pub const PUBLISH: &str = "publish";
 pub const UPGRADE: &str = "upgrade";
 pub const ASSIGN: &str = "assign";
 pub const PREVIEW: &str = "preview";
+pub const ANALYZE: &str = "analyze";
 pub const WARN_SHADOWS: &str = "warn-shadows";
 pub const GAS_BUDGET: &str = "gas-budget";
 pub const GAS_PRICE: &str = "gas-price";
@@ -39,6 +41,7 @@ pub const GAS_COIN: &str = "gas-coin";
 pub const JSON: &str = "json";
 pub const TX_DIGEST: &str = "tx-digest";
 pub const DRY_RUN: &str = "dry-run";
+pub const GAS_PROFILE: &str = "gas-profile";
 pub const DEV_INSPECT: &str = "dev-inspect";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
@@ -68,12 +71,14 @@ pub const COMMANDS: &[&str] = &[
     TRANSFER_OBJECTS,
     SPLIT_COINS,
     MERGE_COINS,
+    PAY_EXACT,
     MAKE_MOVE_VEC,
     MOVE_CALL,
     PUBLISH,
     UPGRADE,
     ASSIGN,
     PREVIEW,
+    ANALYZE,
     WARN_SHADOWS,
     GAS_BUDGET,
     GAS_PRICE,
@@ -81,6 +86,7 @@ pub const COMMANDS: &[&str] = &[
     GAS_COIN,
     JSON,
     DRY_RUN,
+    GAS_PROFILE,
     DEV_INSPECT,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
@@ -114,6 +120,7 @@ pub struct Program {
 #[derive(Debug, Clone)]
 pub struct ProgramMetadata {
     pub preview_set: bool,
+    pub analyze_set: bool,
     pub summary_set: bool,
     pub serialize_unsigned_set: bool,
     pub serialize_signed_set: bool,
@@ -121,6 +128,7 @@ pub struct ProgramMetadata {
     pub json_set: bool,
     pub tx_digest_set: bool,
     pub dry_run_set: bool,
+    pub gas_profile_set: bool,
     pub dev_inspect_set: bool,
     pub gas_budget: Option<Spanned<u64>>,
     pub gas_price: Option<Spanned<u64>>,
@@ -143,6 +151,8 @@ pub enum ParsedPTBCommand {
     TransferObjects(Spanned<Vec<Spanned<Argument>>>, Spanned<Argument>),
     SplitCoins(Spanned<Argument>, Spanned<Vec<Spanned<Argument>>>),
     MergeCoins(Spanned<Argument>, Spanned<Vec<Spanned<Argument>>>),
+    /// Pay an amount of a coin type to a recipient, out of the sender's coins of that type.
+    PayExact(Spanned<ParsedType>, Spanned<Argument>, Spanned<Argument>),
     MakeMoveVec(Spanned<ParsedType>, Spanned<Vec<Spanned<Argument>>>),
     MoveCall(
         Spanned<ModuleAccess>,
@@ -409,6 +419,13 @@ impl fmt::Display for ParsedPTBCommand {
                 delimited_list(f, ", ", args.value.iter().map(|x| &x.value))?;
                 write!(f, "]")
             }
+            ParsedPTBCommand::PayExact(ty, amount, recipient) => write!(
+                f,
+                "{PAY_EXACT} <{}> {} {}",
+                TyDisplay(&ty.value),
+                amount.value,
+                recipient.value
+            ),
             ParsedPTBCommand::Assign(arg, arg_opt) => write!(
                 f,
                 "{ASSIGN} {}{}",
@@ -12,6 +12,7 @@ use crate::{
 use anyhow::{Result, anyhow};
 use async_recursion::async_recursion;
 use async_trait::async_trait;
+use futures::TryStreamExt;
 use miette::Severity;
 use move_binary_format::{
     CompiledModule, binary_config::BinaryConfig, file_format::SignatureToken,
@@ -30,12 +31,16 @@ use move_core_types::{
 use move_package_alt_compilation::build_config::BuildConfig as MoveBuildConfig;
 use mysten_common::ZipDebugEqIteratorExt;
 use std::{collections::BTreeMap, path::Path};
-use sui_json::{is_receiving_argument, primitive_type};
+use sui_json::{fill_well_known_object_args, is_receiving_argument, primitive_type};
 use sui_rpc_api::Client;
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::{
     Identifier, SUI_FRAMEWORK_PACKAGE_ID, TypeTag,
-    base_types::{ObjectID, TxContext, TxContextKind, is_primitive_type_tag},
+    base_types::{
+        ObjectID, ObjectRef, SuiAddress, TxContext, TxContextKind, is_primitive_type_tag,
+    },
+    coin::Coin,
+    gas_coin::GAS,
     move_package::MovePackage,
     object::Owner,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
@@ -240,6 +245,8 @@ pub struct PTBBuilder<'a> {
     resolved_arguments: BTreeMap<String, Tx::Argument>,
     /// Read API for reading objects from chain. Needed for object resolution.
     reader: Client,
+    /// The sender of the transaction, if known, whose coins are selected to pay with.
+    sender: Option<SuiAddress>,
     /// Wallet used to find the active environment for the publish command
     wallet: &'a WalletContext,
     /// The last command that we have added. This is used to support assignment commands.
@@ -297,6 +304,7 @@ impl<'a> PTBBuilder<'a> {
         starting_env: BTreeMap<String, AddressData>,
         reader: Client,
         wallet: &'a WalletContext,
+        sender: Option<SuiAddress>,
     ) -> Self {
         Self {
             addresses: starting_env,
@@ -305,6 +313,7 @@ impl<'a> PTBBuilder<'a> {
             resolved_arguments: BTreeMap::new(),
             ptb: ProgrammableTransactionBuilder::new(),
             reader,
+            sender,
             wallet,
             last_command: None,
             errors: Vec::new(),
@@ -570,20 +579,27 @@ impl<'a> PTBBuilder<'a> {
             .filter(|tok| matches!(TxContext::kind(&module, tok), TxContextKind::None))
             .collect();
 
-        if parameters.len() != args.len() {
-            let loc = if args.is_empty() {
-                package_name_loc.widen(*mloc).widen(*floc)
-            } else {
-                args[0].span.widen_opt(args.last().map(|x| x.span))
-            };
+        let loc = if args.is_empty() {
+            package_name_loc.widen(*mloc).widen(*floc)
+        } else {
+            args[0].span.widen_opt(args.last().map(|x| x.span))
+        };
+        let found = args.len();
+        // Parameters that refer to well-known system objects, like `&Clock`, can be left out.
+        let Some(args) = fill_well_known_object_args(&module, &parameters, args, |id| {
+            loc.wrap(PTBArg::Address(NumericalAddress::new(
+                id.into_bytes(),
+                NumberFormat::Hex,
+            )))
+        }) else {
             error!(
                 loc,
                 "Expected {} argument{}, but got {}",
                 parameters.len(),
                 if parameters.len() == 1 { "" } else { "s" },
-                args.len()
+                found
             );
-        }
+        };
 
         let mut call_args = vec![];
         for (param, arg) in parameters.iter().zip_debug_eq(args) {
@@ -760,6 +776,32 @@ impl<'a> PTBBuilder<'a> {
         }
     }
 
+    /// Select coins of `coin_type` owned by `owner` until they hold at least `amount` between
+    /// them, returning them and their balance.
+    async fn select_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: &TypeTag,
+        amount: u64,
+    ) -> Result<(Vec<ObjectRef>, u64)> {
+        let mut coins = vec![];
+        let mut balance = 0u64;
+        let mut owned = std::pin::pin!(
+            self.reader
+                .list_owned_objects(owner, Some(Coin::type_(coin_type.clone())))
+        );
+        while balance < amount
+            && let Some(object) = owned.try_next().await?
+        {
+            let Some(coin) = object.as_coin_maybe() else {
+                continue;
+            };
+            coins.push(object.compute_object_reference());
+            balance = balance.saturating_add(coin.value());
+        }
+        Ok((coins, balance))
+    }
+
     /// Create a "did you mean" message for an identifier with the context of our different binding
     /// environments.
     fn did_you_mean_identifier(&self, ident: &str) -> Option<String> {
@@ -857,6 +899,56 @@ impl<'a> PTBBuilder<'a> {
                 let res = self.ptb.command(Tx::Command::MergeCoins(coin, args));
                 self.last_command = Some(res);
             }
+            ParsedPTBCommand::PayExact(sp!(ty_loc, ty), sp!(amount_loc, amount), recipient) => {
+                let coin_type = into_type_tag(&self.addresses, ty, &resolve_address)
+                    .map_err(|e| err!(ty_loc, "{e}"))?;
+                let amount = match amount {
+                    PTBArg::U64(amount) => amount,
+                    PTBArg::InferredNum(amount) => u64::try_from(amount)
+                        .map_err(|_| err!(amount_loc, "The amount does not fit in a u64"))?,
+                    _ => error!(amount_loc, "Expected the amount to pay, as a u64"),
+                };
+                let recipient = self
+                    .resolve(recipient, ToPure::new(TypeTag::Address))
+                    .await?;
+
+                if GAS::is_gas_type(&coin_type) {
+                    // The change stays in the gas coin.
+                    let amount = self.ptb.pure(amount).map_err(|e| err!(amount_loc, "{e}"))?;
+                    let Tx::Argument::Result(split) = self
+                        .ptb
+                        .command(Tx::Command::SplitCoins(Tx::Argument::GasCoin, vec![amount]))
+                    else {
+                        unreachable!("Commands always produce a result");
+                    };
+                    self.ptb.command(Tx::Command::TransferObjects(
+                        vec![Tx::Argument::NestedResult(split, 0)],
+                        recipient,
+                    ));
+                } else {
+                    let Some(sender) = self.sender else {
+                        error!(
+                            cmd_span => help: { "Set the sender with --sender" },
+                            "Cannot select coins to pay with without knowing the sender"
+                        );
+                    };
+                    let (coins, balance) = self
+                        .select_coins(sender, &coin_type, amount)
+                        .await
+                        .map_err(|e| err!(cmd_span, "{e}"))?;
+                    if balance < amount {
+                        error!(
+                            cmd_span,
+                            "Sender {sender} only has {balance} of {coin_type}, but {amount} is \
+                             needed"
+                        );
+                    }
+                    self.ptb
+                        .pay_exact_arg(coins, balance, recipient, amount)
+                        .map_err(|e| err!(cmd_span, "{e}"))?;
+                }
+                self.last_command = None;
+            }
             ParsedPTBCommand::MoveCall(
                 sp!(
                     mod_access_loc,
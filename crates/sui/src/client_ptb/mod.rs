@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod analyzer;
 pub mod ast;
 pub mod builder;
 pub mod error;
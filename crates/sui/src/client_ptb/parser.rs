@@ -36,6 +36,7 @@ struct ProgramParsingState {
     errors: Vec<PTBError>,
     mvr_names_with_span: BTreeMap<String, Span>,
     preview_set: bool,
+    analyze_set: bool,
     summary_set: bool,
     warn_shadows_set: bool,
     serialize_unsigned_set: bool,
@@ -43,6 +44,7 @@ struct ProgramParsingState {
     json_set: bool,
     tx_digest_set: bool,
     dry_run_set: bool,
+    gas_profile_set: bool,
     dev_inspect_set: bool,
     gas_object_ids: Option<Vec<Spanned<ObjectID>>>,
     gas_budget: Option<Spanned<u64>>,
@@ -70,6 +72,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 errors: Vec::new(),
                 mvr_names_with_span: BTreeMap::new(),
                 preview_set: false,
+                analyze_set: false,
                 summary_set: false,
                 warn_shadows_set: false,
                 serialize_unsigned_set: false,
@@ -77,6 +80,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 json_set: false,
                 tx_digest_set: false,
                 dry_run_set: false,
+                gas_profile_set: false,
                 dev_inspect_set: false,
                 gas_object_ids: None,
                 gas_budget: None,
@@ -129,8 +133,10 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::JSON) => flag!(json_set),
                 L(T::Command, A::TX_DIGEST) => flag!(tx_digest_set),
                 L(T::Command, A::DRY_RUN) => flag!(dry_run_set),
+                L(T::Command, A::GAS_PROFILE) => flag!(gas_profile_set),
                 L(T::Command, A::DEV_INSPECT) => flag!(dev_inspect_set),
                 L(T::Command, A::PREVIEW) => flag!(preview_set),
+                L(T::Command, A::ANALYZE) => flag!(analyze_set),
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
                 L(T::Command, A::GAS_COIN) => {
                     let coins = try_!(self.parse_gas_coins());
@@ -183,6 +189,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::TRANSFER_OBJECTS) => command!(self.parse_transfer_objects()),
                 L(T::Command, A::SPLIT_COINS) => command!(self.parse_split_coins()),
                 L(T::Command, A::MERGE_COINS) => command!(self.parse_merge_coins()),
+                L(T::Command, A::PAY_EXACT) => command!(self.parse_pay_exact()),
                 L(T::Command, A::ASSIGN) => command!(self.parse_assign()),
                 L(T::Command, A::MAKE_MOVE_VEC) => command!(self.parse_make_move_vec()),
                 L(T::Command, A::MOVE_CALL) => command!(self.parse_move_call()),
@@ -249,6 +256,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 },
                 A::ProgramMetadata {
                     preview_set: self.state.preview_set,
+                    analyze_set: self.state.analyze_set,
                     summary_set: self.state.summary_set,
                     serialize_unsigned_set: self.state.serialize_unsigned_set,
                     serialize_signed_set: self.state.serialize_signed_set,
@@ -256,6 +264,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     json_set: self.state.json_set,
                     tx_digest_set: self.state.tx_digest_set,
                     dry_run_set: self.state.dry_run_set,
+                    gas_profile_set: self.state.gas_profile_set,
                     dev_inspect_set: self.state.dev_inspect_set,
                     gas_budget: self.state.gas_budget,
                     gas_price: self.state.gas_price,
@@ -342,6 +351,22 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
         Ok(sp.wrap(ParsedPTBCommand::MergeCoins(merge_into, coins)))
     }
 
+    /// Parse a pay-exact command.
+    /// The expected format is: `--pay-exact <type> <amount> <recipient>`
+    fn parse_pay_exact(&mut self) -> PTBResult<Spanned<ParsedPTBCommand>> {
+        use Token as T;
+
+        let sp!(start_sp, _) = self.expect(T::LAngle)?;
+        let coin_type = self.parse_type()?;
+        self.expect(T::RAngle)?;
+
+        let amount = self.parse_argument()?;
+        let recipient = self.parse_argument()?;
+
+        let sp = start_sp.widen(recipient.span);
+        Ok(sp.wrap(ParsedPTBCommand::PayExact(coin_type, amount, recipient)))
+    }
+
     /// Parse an assign command.
     /// The expected format is: `--assign <variable> (<value>)?`
     fn parse_assign(&mut self) -> PTBResult<Spanned<ParsedPTBCommand>> {
@@ -6,6 +6,7 @@ use crate::{
         GasDataArgs, SuiClientCommandResult, TxProcessingArgs, dry_run_or_execute_or_serialize,
     },
     client_ptb::{
+        analyzer::analyze,
         ast::{ParsedProgram, Program},
         builder::{PTBBuilder, resolve_package},
         error::{PTBError, Span, build_error_reports},
@@ -26,7 +27,7 @@ use sui_keys::keystore::AccountKeystore;
 use sui_rpc_api::Client;
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::{
-    base_types::ObjectID,
+    base_types::{ObjectID, SuiAddress},
     digests::TransactionDigest,
     effects::TransactionEffectsAPI,
     execution_status::ExecutionStatus,
@@ -117,6 +118,10 @@ impl PTB {
             !program_metadata.serialize_unsigned_set || !program_metadata.serialize_signed_set,
             "Cannot specify both flags: --serialize-unsigned-transaction and --serialize-signed-transaction."
         );
+        ensure!(
+            !program_metadata.gas_profile_set || program_metadata.dry_run_set,
+            "The --gas-profile flag requires --dry-run."
+        );
 
         if program_metadata.preview_set {
             println!(
@@ -131,18 +136,7 @@ impl PTB {
 
         let mut client = context.grpc_client()?;
 
-        let mut starting_addresses: BTreeMap<String, AddressData> = context
-            .config
-            .keystore
-            .addresses_with_alias()
-            .into_iter()
-            .map(|(sa, alias)| {
-                (
-                    alias.alias.clone(),
-                    AddressData::AccountAddress(AccountAddress::from(*sa)),
-                )
-            })
-            .collect();
+        let mut starting_addresses = keystore_addresses(context);
 
         let mvr_names = program_metadata.mvr_names.clone();
         let mvr_resolver = MvrResolver {
@@ -161,8 +155,28 @@ impl PTB {
             starting_addresses.extend(mvr_data);
         }
 
-        let (res, warnings) =
-            Self::build_ptb(program, starting_addresses, client.clone(), context).await;
+        let gas: Vec<_> = program_metadata
+            .gas_object_ids
+            .into_iter()
+            .flatten()
+            .map(|x| x.value)
+            .collect();
+
+        // the sender is the gas object if gas is provided, otherwise the active address
+        let sender = if let Some(sender) = program_metadata.sender {
+            sender.value.into_inner().into()
+        } else {
+            context.infer_sender(&gas).await?
+        };
+
+        let (res, warnings) = Self::build_ptb(
+            program,
+            starting_addresses,
+            client.clone(),
+            context,
+            Some(sender),
+        )
+        .await;
 
         // Render warnings
         if !warnings.is_empty() {
@@ -186,19 +200,19 @@ impl PTB {
             Ok(x) => x,
         };
 
-        let gas: Vec<_> = program_metadata
-            .gas_object_ids
-            .into_iter()
-            .flatten()
-            .map(|x| x.value)
-            .collect();
-
-        // the sender is the gas object if gas is provided, otherwise the active address
-        let sender = if let Some(sender) = program_metadata.sender {
-            sender.value.into_inner().into()
-        } else {
-            context.infer_sender(&gas).await?
-        };
+        if program_metadata.analyze_set {
+            let analysis = analyze(&mut client, &ptb, &gas).await;
+            if program_metadata.json_set {
+                println!("{}", serde_json::to_string_pretty(&analysis)?);
+            } else {
+                println!("{analysis}");
+            }
+            ensure!(
+                !analysis.has_errors(),
+                "PTB analysis found errors, fix them before executing the PTB"
+            );
+            return Ok(());
+        }
 
         // build the tx kind
         let tx_kind = TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
@@ -212,16 +226,21 @@ impl PTB {
             gas_sponsor: program_metadata
                 .gas_sponsor
                 .map(|x| x.value.into_inner().into()),
+            ..Default::default()
         };
 
         let processing = TxProcessingArgs {
             tx_digest: program_metadata.tx_digest_set,
             dry_run: program_metadata.dry_run_set,
+            gas_profile: program_metadata.gas_profile_set,
             dev_inspect: program_metadata.dev_inspect_set,
             serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
             serialize_signed_transaction: program_metadata.serialize_signed_set,
             sender: program_metadata.sender.map(|x| x.value.into_inner().into()),
             skip_signing: false,
+            label: None,
+            preview: false,
+            strict_preview: false,
         };
 
         let gas_payment = client.transaction_builder().input_refs(&gas).await?;
@@ -239,6 +258,7 @@ impl PTB {
         let transaction_response = match transaction_response {
             SuiClientCommandResult::ComputeTransactionDigest(_)
             | SuiClientCommandResult::DryRun(_)
+            | SuiClientCommandResult::DryRunGasProfile(..)
             | SuiClientCommandResult::SerializedUnsignedTransaction(_)
             | SuiClientCommandResult::SerializedSignedTransaction(_) => {
                 println!("{transaction_response}");
@@ -294,11 +314,12 @@ impl PTB {
         starting_addresses: BTreeMap<String, AddressData>,
         reader: Client,
         wallet: &WalletContext,
+        sender: Option<SuiAddress>,
     ) -> (
         Result<ProgrammableTransaction, Vec<PTBError>>,
         Vec<PTBError>,
     ) {
-        let builder = PTBBuilder::new(starting_addresses, reader, wallet);
+        let builder = PTBBuilder::new(starting_addresses, reader, wallet, sender);
         builder.build(program).await
     }
 
@@ -310,6 +331,22 @@ impl PTB {
     }
 }
 
+/// The addresses of the keystore, by alias, so that PTBs can refer to them as `@alias`.
+pub(crate) fn keystore_addresses(context: &WalletContext) -> BTreeMap<String, AddressData> {
+    context
+        .config
+        .keystore
+        .addresses_with_alias()
+        .into_iter()
+        .map(|(sa, alias)| {
+            (
+                alias.alias.clone(),
+                AddressData::AccountAddress(AccountAddress::from(*sa)),
+            )
+        })
+        .collect()
+}
+
 /// Convert a vector of shell tokens into a single string, with each shell token separated by a
 /// space with each command starting on a new line.
 /// NB: we add a space to the end of the source string to ensure that for unexpected EOF
@@ -363,6 +400,11 @@ pub fn ptb_description() -> clap::Command {
             --"dry-run"
             "Perform a dry run of the PTB instead of executing it."
         ))
+        .arg(arg!(
+            --"gas-profile"
+            "With --dry-run, break down the computation cost of the PTB by command, and save it as \
+            a flamegraph-compatible (speedscope) profile in the working directory."
+        ))
         .arg(arg!(
             --"dev-inspect"
             "Perform a dev-inspect of the PTB instead of executing it."
@@ -425,6 +467,19 @@ pub fn ptb_description() -> clap::Command {
             \n --move-call std::option::is_none <u64> a"
         )
         .value_names(["PACKAGE::MODULE::FUNCTION", "TYPE_ARGS", "FUNCTION_ARGS"]))
+        .arg(arg!(
+            --"pay-exact" <PAY_EXACT>
+            "Pay an exact amount of a coin type to a recipient, out of the sender's coins."
+        )
+        .long_help(
+            "Pay an exact amount of a coin type to a recipient, out of the sender's coins. \
+            Enough of the sender's coins of that type are merged to cover the amount, and the \
+            change stays with the sender. SUI is paid out of the gas coin.\
+            \n\nExamples:\
+            \n --pay-exact <sui::sui::SUI> 1000 @0x02a212de6a9dfa3a69e22387acfbafbb1a9e591bd9d636e7895dcfc8de05f331\
+            \n --pay-exact <0xc0ffee::usdc::USDC> 5000 @alice"
+        )
+        .value_names(["TYPE", "AMOUNT", "RECIPIENT"]))
         .arg(arg!(
             --"split-coins" <SPLIT_COINS>
             "Split the coin into N coins as per the given array of amounts."
@@ -470,6 +525,12 @@ pub fn ptb_description() -> clap::Command {
             --"preview"
             "Instead of executing the transaction, preview its PTB commands."
         ))
+        .arg(arg!(
+            --"analyze"
+            "Instead of executing the transaction, check it for mistakes: Move call arguments that \
+            do not match the on-chain function signatures, unused results, transfers to the zero \
+            address, and owned objects used after being moved."
+        ))
         .arg(arg!(
             --"tx-digest"
             "Instead of executing the transaction, print its digest."
@@ -0,0 +1,575 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aes_gcm::{
+    Aes256Gcm, Nonce,
+    aead::{Aead, KeyInit, Payload},
+};
+use anyhow::{Context, anyhow, bail, ensure};
+use clap::*;
+use fastcrypto::{
+    encoding::{Base64, Encoding, Hex},
+    hash::{Blake2b256, HashFunction},
+};
+use hmac::Hmac;
+use rand::{RngCore, rngs::OsRng};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sui_keys::{
+    key_identity::KeyIdentity,
+    keystore::{AccountKeystore, Keystore},
+};
+use sui_sdk::{
+    sui_client_config::{SuiClientConfig, SuiEnv, WatchOnlyAddress},
+    wallet_context::WalletContext,
+};
+use sui_types::{base_types::SuiAddress, crypto::SuiKeyPair};
+
+use crate::client_commands::SuiClientCommandResult;
+
+#[cfg(test)]
+#[path = "unit_tests/config_tests.rs"]
+mod config_tests;
+
+/// Version of the bundle format written by `export`.
+const BUNDLE_VERSION: u8 = 1;
+
+/// PBKDF2-HMAC-SHA256 rounds used to derive the key that encrypts private keys in a bundle.
+/// Tests use fewer rounds to stay fast; the count is stored in the bundle either way.
+const KDF_ROUNDS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+
+/// Prefix of the associated data bound to the encrypted keys, so they cannot be decrypted as
+/// anything else.
+const KEYS_AAD: &[u8] = b"sui-config-bundle-keys";
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum ConfigCommand {
+    /// Export environments, addresses, aliases and optionally private keys into a single bundle
+    /// file, to set up the client on another machine with `sui client config import`.
+    #[clap(name = "export")]
+    Export {
+        /// Path of the bundle file to write
+        #[clap(long)]
+        bundle: PathBuf,
+        /// Include the private keys of the keystore in the bundle. Without this, addresses are
+        /// imported as watch-only.
+        #[clap(long)]
+        include_keys: bool,
+        /// Encrypt the private keys with a passphrase
+        #[clap(long, requires = "include_keys")]
+        encrypt: bool,
+        /// Read the passphrase from this file instead of prompting for it
+        #[clap(long, requires = "encrypt")]
+        passphrase_file: Option<PathBuf>,
+    },
+
+    /// Import a bundle written by `sui client config export`, adding its environments,
+    /// addresses and keys to this client. Existing environments and keys are left untouched.
+    #[clap(name = "import")]
+    Import {
+        /// Path of the bundle file to read
+        #[clap(long)]
+        bundle: PathBuf,
+        /// Also switch to the bundle's active environment and address
+        #[clap(long)]
+        switch: bool,
+        /// Read the passphrase for encrypted keys from this file instead of prompting for it
+        #[clap(long)]
+        passphrase_file: Option<PathBuf>,
+    },
+}
+
+/// A portable snapshot of a client configuration.
+///
+/// The checksum detects corrupted bundles. It is not a signature: anyone who can modify a bundle
+/// can recompute it. Encrypted keys are additionally bound to the rest of the payload, so an
+/// encrypted bundle cannot be modified without its passphrase.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundle {
+    pub version: u8,
+    pub payload: BundlePayload,
+    /// Hex encoded Blake2b256 digest of the JSON serialized payload.
+    pub checksum: String,
+}
+
+/// A bundle as read from a file, with its payload left as JSON so that its checksum is verified
+/// against the payload as written, including any field this version does not know about.
+#[derive(Deserialize)]
+struct RawConfigBundle {
+    version: u8,
+    payload: serde_json::Value,
+    checksum: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundlePayload {
+    pub envs: Vec<SuiEnv>,
+    pub active_env: Option<String>,
+    pub active_address: Option<SuiAddress>,
+    /// Addresses of the keystore with their aliases, whether or not their keys are included.
+    pub addresses: Vec<WatchOnlyAddress>,
+    pub watch_only: Vec<WatchOnlyAddress>,
+    pub keys: Option<BundleKeys>,
+}
+
+/// Private keys in a bundle, Bech32 encoded (`suiprivkey...`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum BundleKeys {
+    Plain {
+        keys: Vec<String>,
+    },
+    /// Keys encrypted with AES-256-GCM under a key derived from a passphrase with
+    /// PBKDF2-HMAC-SHA256. The rest of the payload is authenticated as associated data.
+    #[serde(rename_all = "camelCase")]
+    Encrypted {
+        rounds: u32,
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundleOutput {
+    pub bundle: PathBuf,
+    pub imported: bool,
+    pub envs: usize,
+    pub addresses: usize,
+    pub keys: usize,
+    pub watch_only: usize,
+    pub encrypted: bool,
+}
+
+impl ConfigCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(SuiClientCommandResult::ConfigBundle(match self {
+            ConfigCommand::Export {
+                bundle: path,
+                include_keys,
+                encrypt,
+                passphrase_file,
+            } => {
+                let passphrase = if encrypt {
+                    Some(read_passphrase(passphrase_file.as_deref(), true)?)
+                } else {
+                    None
+                };
+
+                let bundle = export_bundle(&context.config, include_keys, passphrase.as_deref())?;
+                write_bundle(&path, &bundle, include_keys)?;
+
+                ConfigBundleOutput {
+                    bundle: path,
+                    imported: false,
+                    envs: bundle.payload.envs.len(),
+                    addresses: bundle.payload.addresses.len(),
+                    keys: if include_keys {
+                        bundle.payload.addresses.len()
+                    } else {
+                        0
+                    },
+                    watch_only: bundle.payload.watch_only.len(),
+                    encrypted: encrypt,
+                }
+            }
+
+            ConfigCommand::Import {
+                bundle: path,
+                switch,
+                passphrase_file,
+            } => {
+                let contents = fs::read_to_string(&path)
+                    .with_context(|| format!("Cannot read bundle at {path:?}"))?;
+                let payload = ConfigBundle::parse(&contents)
+                    .with_context(|| format!("Invalid bundle at {path:?}"))?;
+
+                let encrypted = matches!(payload.keys, Some(BundleKeys::Encrypted { .. }));
+                let passphrase = if encrypted {
+                    Some(read_passphrase(passphrase_file.as_deref(), false)?)
+                } else {
+                    None
+                };
+                let keys = payload.decode_keys(passphrase.as_deref())?;
+
+                let mut output = import_bundle(&mut context.config, &payload, keys, switch).await?;
+                context.config.save()?;
+
+                output.bundle = path;
+                output.encrypted = encrypted;
+                output
+            }
+        }))
+    }
+}
+
+impl ConfigBundle {
+    pub fn new(payload: BundlePayload) -> Result<Self, anyhow::Error> {
+        let checksum = payload.checksum()?;
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            payload,
+            checksum,
+        })
+    }
+
+    /// Parse a bundle from the contents of its file, checking its version and checksum, and
+    /// returning its payload.
+    pub fn parse(contents: &str) -> Result<BundlePayload, anyhow::Error> {
+        let raw: RawConfigBundle = serde_json::from_str(contents)?;
+        ensure!(
+            raw.version == BUNDLE_VERSION,
+            "Unsupported bundle version {}, expected {BUNDLE_VERSION}",
+            raw.version
+        );
+        ensure!(
+            checksum(&raw.payload)? == raw.checksum,
+            "Bundle checksum mismatch: the bundle is corrupted or was modified"
+        );
+        Ok(serde_json::from_value(raw.payload)?)
+    }
+}
+
+impl BundlePayload {
+    fn checksum(&self) -> Result<String, anyhow::Error> {
+        checksum(self)
+    }
+
+    /// The associated data the encrypted keys are bound to: the payload without its keys.
+    fn associated_data(&self) -> Result<Vec<u8>, anyhow::Error> {
+        let rest = BundlePayload {
+            keys: None,
+            ..self.clone()
+        };
+        let mut aad = KEYS_AAD.to_vec();
+        aad.extend(serde_json::to_vec(&rest)?);
+        Ok(aad)
+    }
+
+    /// Decode the keys of the bundle, decrypting them with `passphrase` if they are encrypted.
+    /// Every key must belong to one of the bundle's addresses.
+    pub fn decode_keys(&self, passphrase: Option<&str>) -> Result<Vec<SuiKeyPair>, anyhow::Error> {
+        let Some(keys) = &self.keys else {
+            return Ok(vec![]);
+        };
+        let keys = keys.decode(passphrase, &self.associated_data()?)?;
+        for key in &keys {
+            let address = SuiAddress::from(&key.public());
+            ensure!(
+                self.addresses.iter().any(|a| a.address == address),
+                "The bundle holds a key for {address}, which is not one of its addresses"
+            );
+        }
+        Ok(keys)
+    }
+}
+
+/// Hex encoded Blake2b256 digest of `value` serialized as JSON.
+fn checksum(value: &impl Serialize) -> Result<String, anyhow::Error> {
+    let bytes = serde_json::to_vec(value)?;
+    Ok(Hex::encode(Blake2b256::digest(bytes).digest))
+}
+
+impl BundleKeys {
+    fn new(
+        keys: &[&SuiKeyPair],
+        passphrase: Option<&str>,
+        aad: &[u8],
+    ) -> Result<Self, anyhow::Error> {
+        let keys = keys
+            .iter()
+            .map(|kp| kp.encode().map_err(|e| anyhow!("Cannot encode key: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Some(passphrase) = passphrase else {
+            return Ok(BundleKeys::Plain { keys });
+        };
+
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let cipher = cipher(passphrase, &salt, KDF_ROUNDS)?;
+        let plaintext = serde_json::to_vec(&keys)?;
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad,
+                },
+            )
+            .map_err(|_| anyhow!("Failed to encrypt keys"))?;
+
+        Ok(BundleKeys::Encrypted {
+            rounds: KDF_ROUNDS,
+            salt: Hex::encode(salt),
+            nonce: Hex::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    /// Decode the keys, decrypting them with `passphrase` if they are encrypted, and checking
+    /// that they were encrypted along with `aad`.
+    fn decode(
+        &self,
+        passphrase: Option<&str>,
+        aad: &[u8],
+    ) -> Result<Vec<SuiKeyPair>, anyhow::Error> {
+        let keys = match self {
+            BundleKeys::Plain { keys } => keys.clone(),
+            BundleKeys::Encrypted {
+                rounds,
+                salt,
+                nonce,
+                ciphertext,
+            } => {
+                let passphrase = passphrase
+                    .ok_or_else(|| anyhow!("A passphrase is required to decrypt keys"))?;
+                let salt = Hex::decode(salt).map_err(|e| anyhow!("Invalid salt: {e}"))?;
+                let nonce = Hex::decode(nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+                ensure!(nonce.len() == 12, "Invalid nonce length {}", nonce.len());
+                let ciphertext =
+                    Base64::decode(ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+
+                let plaintext = cipher(passphrase, &salt, *rounds)?
+                    .decrypt(
+                        Nonce::from_slice(&nonce),
+                        Payload {
+                            msg: &ciphertext,
+                            aad,
+                        },
+                    )
+                    .map_err(|_| {
+                        anyhow!(
+                            "Failed to decrypt keys: wrong passphrase, or the bundle was modified"
+                        )
+                    })?;
+                serde_json::from_slice(&plaintext)?
+            }
+        };
+
+        keys.iter()
+            .map(|key| SuiKeyPair::decode(key).map_err(|e| anyhow!("Invalid key in bundle: {e}")))
+            .collect()
+    }
+}
+
+/// Derive an AES-256-GCM cipher from `passphrase`.
+fn cipher(passphrase: &str, salt: &[u8], rounds: u32) -> Result<Aes256Gcm, anyhow::Error> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, rounds, &mut key);
+    Aes256Gcm::new_from_slice(&key).map_err(|_| anyhow!("Invalid key length"))
+}
+
+fn read_passphrase(file: Option<&Path>, confirm: bool) -> Result<String, anyhow::Error> {
+    let passphrase = if let Some(file) = file {
+        fs::read_to_string(file)
+            .with_context(|| format!("Cannot read passphrase from {file:?}"))?
+            .trim_end_matches(['\r', '\n'])
+            .to_string()
+    } else {
+        let prompt = inquire::Password::new("Bundle passphrase:")
+            .with_display_mode(inquire::PasswordDisplayMode::Hidden);
+        let prompt = if confirm {
+            prompt
+        } else {
+            prompt.without_confirmation()
+        };
+        prompt.prompt()?
+    };
+
+    ensure!(!passphrase.is_empty(), "The passphrase cannot be empty");
+    Ok(passphrase)
+}
+
+/// Snapshot `config` as a bundle. Only keys of the main keystore can be included: external keys
+/// live outside of the client and their addresses are exported as watch-only.
+pub fn export_bundle(
+    config: &SuiClientConfig,
+    include_keys: bool,
+    passphrase: Option<&str>,
+) -> Result<ConfigBundle, anyhow::Error> {
+    let addresses: Vec<_> = config
+        .keystore
+        .addresses_with_alias()
+        .into_iter()
+        .map(|(address, alias)| WatchOnlyAddress {
+            alias: alias.alias.clone(),
+            address: *address,
+        })
+        .collect();
+
+    let mut watch_only = config.watch_only.clone();
+    if let Some(external_keys) = &config.external_keys {
+        watch_only.extend(external_keys.addresses_with_alias().into_iter().map(
+            |(address, alias)| WatchOnlyAddress {
+                alias: alias.alias.clone(),
+                address: *address,
+            },
+        ));
+    }
+
+    let mut payload = BundlePayload {
+        envs: config.envs.clone(),
+        active_env: config.active_env.clone(),
+        active_address: config.active_address,
+        addresses,
+        watch_only,
+        keys: None,
+    };
+
+    if include_keys {
+        let keys = payload
+            .addresses
+            .iter()
+            .map(|a| config.keystore.export(&a.address))
+            .collect::<Result<Vec<_>, _>>()?;
+        payload.keys = Some(BundleKeys::new(
+            &keys,
+            passphrase,
+            &payload.associated_data()?,
+        )?);
+    }
+
+    ConfigBundle::new(payload)
+}
+
+/// Merge a verified bundle into `config`. Keys are imported under their bundle alias when it is
+/// free, and addresses whose keys are not in the bundle are added as watch-only.
+pub async fn import_bundle(
+    config: &mut SuiClientConfig,
+    payload: &BundlePayload,
+    keys: Vec<SuiKeyPair>,
+    switch: bool,
+) -> Result<ConfigBundleOutput, anyhow::Error> {
+    let mut output = ConfigBundleOutput {
+        bundle: PathBuf::new(),
+        imported: true,
+        envs: 0,
+        addresses: 0,
+        keys: 0,
+        watch_only: 0,
+        encrypted: false,
+    };
+
+    for env in &payload.envs {
+        if config.get_env(&Some(env.alias.clone())).is_none() {
+            config.add_env(env.clone());
+            output.envs += 1;
+        }
+    }
+
+    let alias_of = |address: &SuiAddress| {
+        payload
+            .addresses
+            .iter()
+            .find(|a| &a.address == address)
+            .map(|a| a.alias.clone())
+    };
+
+    let mut with_keys = vec![];
+    for key in keys {
+        let address = SuiAddress::from(&key.public());
+        with_keys.push(address);
+        if is_known(config, &address) {
+            continue;
+        }
+
+        let alias = alias_of(&address).filter(|alias| !alias_is_taken(config, alias));
+        config.keystore.import(alias, key).await?;
+        output.keys += 1;
+        output.addresses += 1;
+    }
+
+    let watched = payload
+        .addresses
+        .iter()
+        .filter(|a| !with_keys.contains(&a.address))
+        .chain(&payload.watch_only);
+    for watch in watched {
+        if is_known(config, &watch.address) {
+            continue;
+        }
+
+        let alias = if alias_is_taken(config, &watch.alias) {
+            config.keystore.create_alias(None)?
+        } else {
+            watch.alias.clone()
+        };
+        config.add_watch_only(WatchOnlyAddress {
+            alias,
+            address: watch.address,
+        })?;
+        output.watch_only += 1;
+        output.addresses += 1;
+    }
+
+    if switch {
+        if let Some(env) = &payload.active_env {
+            ensure!(
+                config.get_env(&Some(env.clone())).is_some(),
+                "Active environment {env} of the bundle is not configured"
+            );
+            config.active_env = Some(env.clone());
+        }
+        if let Some(address) = payload.active_address {
+            if !is_known(config, &address) {
+                bail!("Active address {address} of the bundle is not known to this client");
+            }
+            config.active_address = Some(address);
+        }
+    }
+
+    Ok(output)
+}
+
+fn write_bundle(path: &Path, bundle: &ConfigBundle, secret: bool) -> Result<(), anyhow::Error> {
+    fs::write(path, serde_json::to_string_pretty(bundle)?)
+        .with_context(|| format!("Cannot write bundle to {path:?}"))?;
+
+    #[cfg(unix)]
+    if secret {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Cannot set permissions for bundle {path:?}"))?;
+    }
+    #[cfg(not(unix))]
+    let _ = secret;
+
+    Ok(())
+}
+
+/// Whether `address` already has a key or is watched.
+fn is_known(config: &SuiClientConfig, address: &SuiAddress) -> bool {
+    let has_key = |keystore: &Keystore| keystore.addresses().contains(address);
+    has_key(&config.keystore)
+        || config.external_keys.as_ref().is_some_and(has_key)
+        || config
+            .get_watch_only(&KeyIdentity::Address(*address))
+            .is_some()
+}
+
+fn alias_is_taken(config: &SuiClientConfig, alias: &str) -> bool {
+    config.keystore.alias_exists(alias)
+        || config
+            .external_keys
+            .as_ref()
+            .is_some_and(|keys| keys.alias_exists(alias))
+        || config
+            .get_watch_only(&KeyIdentity::Alias(alias.to_string()))
+            .is_some()
+}
@@ -0,0 +1,297 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure};
+use clap::*;
+use serde::Serialize;
+use sui_keys::key_identity::KeyIdentity;
+use sui_sdk::wallet_context::WalletContext;
+use sui_transaction_builder::{DenyCapVersion, DenyListAction};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    transaction::TransactionData,
+};
+
+use crate::client_commands::{
+    GasDataArgs, PaymentArgs, SuiClientCommandResult, TxProcessingArgs,
+    dry_run_or_execute_or_serialize, max_gas_budget,
+};
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum DenyListCommand {
+    /// Deny an address from using a regulated coin. The address can no longer use the coin as a
+    /// transaction input right away, and can no longer receive it from the next epoch.
+    #[clap(name = "add")]
+    Add {
+        /// ID of the coin's DenyCap or DenyCapV2 object
+        #[clap(long)]
+        deny_cap_id: ObjectID,
+        /// Address (or its alias) to deny
+        #[clap(long)]
+        address: KeyIdentity,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Allow a previously denied address to use a regulated coin again.
+    #[clap(name = "remove")]
+    Remove {
+        /// ID of the coin's DenyCap or DenyCapV2 object
+        #[clap(long)]
+        deny_cap_id: ObjectID,
+        /// Address (or its alias) to allow again
+        #[clap(long)]
+        address: KeyIdentity,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Pause a regulated coin for every address. Only supported for coins whose DenyCapV2 was
+    /// created with global pause allowed.
+    #[clap(name = "enable-global-pause")]
+    EnableGlobalPause {
+        /// ID of the coin's DenyCapV2 object
+        #[clap(long)]
+        deny_cap_id: ObjectID,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Lift the global pause of a regulated coin.
+    #[clap(name = "disable-global-pause")]
+    DisableGlobalPause {
+        /// ID of the coin's DenyCapV2 object
+        #[clap(long)]
+        deny_cap_id: ObjectID,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Check whether the given addresses are denied from using a regulated coin in the current
+    /// and in the next epoch, and whether the coin is globally paused. The deny list cannot be
+    /// enumerated, so only the given addresses are checked.
+    #[clap(name = "check")]
+    Check {
+        /// ID of the coin's DenyCap or DenyCapV2 object
+        #[clap(long)]
+        deny_cap_id: ObjectID,
+        /// Addresses (or their aliases) to check
+        #[clap(long, num_args(1..))]
+        addresses: Vec<KeyIdentity>,
+    },
+}
+
+/// Deny list entries of a regulated coin for a set of addresses, as shown by
+/// `sui client deny-list check`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DenyListCheckOutput {
+    pub coin_type: String,
+    /// Whether the coin is globally paused, absent for coins regulated by a `DenyCap`, which
+    /// cannot be paused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_pause_current_epoch: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_pause_next_epoch: Option<bool>,
+    pub addresses: Vec<DeniedAddressOutput>,
+}
+
+/// Denials by a `DenyCap` apply immediately, so both fields are the same for those coins.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeniedAddressOutput {
+    pub address: SuiAddress,
+    pub denied_current_epoch: bool,
+    pub denied_next_epoch: bool,
+}
+
+impl DenyListCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        let (deny_cap_id, action, payment, gas_data, processing) = match self {
+            DenyListCommand::Add {
+                deny_cap_id,
+                address,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let address = context.get_identity_address(Some(address))?;
+                (
+                    deny_cap_id,
+                    DenyListAction::Add(address),
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            DenyListCommand::Remove {
+                deny_cap_id,
+                address,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let address = context.get_identity_address(Some(address))?;
+                (
+                    deny_cap_id,
+                    DenyListAction::Remove(address),
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            DenyListCommand::EnableGlobalPause {
+                deny_cap_id,
+                payment,
+                gas_data,
+                processing,
+            } => (
+                deny_cap_id,
+                DenyListAction::EnableGlobalPause,
+                payment,
+                gas_data,
+                processing,
+            ),
+            DenyListCommand::DisableGlobalPause {
+                deny_cap_id,
+                payment,
+                gas_data,
+                processing,
+            } => (
+                deny_cap_id,
+                DenyListAction::DisableGlobalPause,
+                payment,
+                gas_data,
+                processing,
+            ),
+            DenyListCommand::Check {
+                deny_cap_id,
+                addresses,
+            } => {
+                let addresses = addresses
+                    .into_iter()
+                    .map(|a| context.get_identity_address(Some(a)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                return Ok(SuiClientCommandResult::DenyListCheck(
+                    deny_list_check(context, deny_cap_id, addresses).await?,
+                ));
+            }
+        };
+
+        let signer = context.get_object_owner(&deny_cap_id).await?;
+        let client = context.grpc_client()?;
+        let transaction_builder = client.transaction_builder();
+        let tx_kind = transaction_builder
+            .deny_list_tx_kind(deny_cap_id, action)
+            .await?;
+        let gas_payment = transaction_builder.input_refs(&payment.gas).await?;
+        dry_run_or_execute_or_serialize(signer, tx_kind, context, gas_payment, gas_data, processing)
+            .await
+    }
+}
+
+async fn deny_list_check(
+    context: &mut WalletContext,
+    deny_cap_id: ObjectID,
+    addresses: Vec<SuiAddress>,
+) -> Result<DenyListCheckOutput, anyhow::Error> {
+    let client = context.grpc_client()?;
+    let transaction_builder = client.transaction_builder();
+    let (coin_type, version) = transaction_builder.deny_cap_coin_type(deny_cap_id).await?;
+    let tx_kind = transaction_builder
+        .deny_list_check_tx_kind(coin_type.clone(), version, &addresses)
+        .await?;
+    let coin_type = coin_type.to_canonical_string(true);
+    let tx = TransactionData::new_with_gas_coins(
+        tx_kind,
+        context.active_address()?,
+        vec![],
+        max_gas_budget(&client).await?,
+        context.get_reference_gas_price().await?,
+    );
+    let response = client.simulate_transaction(&tx, false, false).await?;
+    ensure!(
+        response.transaction.effects.status().is_ok(),
+        "Failed to query the deny list of {coin_type}: {:?}",
+        response.transaction.effects.status()
+    );
+
+    let mut flags = response
+        .command_outputs
+        .iter()
+        .map(|output| {
+            let value = output
+                .return_values
+                .first()
+                .and_then(|v| v.value_opt())
+                .ok_or_else(|| anyhow!("Missing deny list query result"))?;
+            Ok(bcs::from_bytes::<bool>(value.value())?)
+        })
+        .collect::<Result<Vec<_>, anyhow::Error>>()?
+        .into_iter();
+    let expected = match version {
+        DenyCapVersion::V1 => addresses.len(),
+        DenyCapVersion::V2 => addresses.len() * 2 + 2,
+    };
+    ensure!(
+        flags.len() == expected,
+        "Unexpected number of deny list query results"
+    );
+
+    // Safe to unwrap below, the number of flags was checked above.
+    let addresses = addresses
+        .into_iter()
+        .map(|address| {
+            let (denied_current_epoch, denied_next_epoch) = match version {
+                DenyCapVersion::V1 => {
+                    let denied = flags.next().unwrap();
+                    (denied, denied)
+                }
+                DenyCapVersion::V2 => (flags.next().unwrap(), flags.next().unwrap()),
+            };
+            DeniedAddressOutput {
+                address,
+                denied_current_epoch,
+                denied_next_epoch,
+            }
+        })
+        .collect();
+    Ok(DenyListCheckOutput {
+        coin_type,
+        global_pause_current_epoch: flags.next(),
+        global_pause_next_epoch: flags.next(),
+        addresses,
+    })
+}
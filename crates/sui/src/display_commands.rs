@@ -0,0 +1,627 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, btree_map::Entry},
+    fmt,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use anyhow::{anyhow, bail, ensure};
+use async_trait::async_trait;
+use clap::*;
+use move_binary_format::{CompiledModule, binary_config::BinaryConfig};
+use move_bytecode_utils::{layout::TypeLayoutBuilder, module_cache::GetModule};
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value::MoveTypeLayout,
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+};
+use serde::Serialize;
+use serde_json::Value;
+use sui_display::v2::{Interpreter, Limits, OwnedSlice, Store};
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{
+    SUI_FRAMEWORK_PACKAGE_ID,
+    base_types::{ObjectID, ObjectRef, SequenceNumber},
+    collection_types::{self, VecMap},
+    display_registry::{DISPLAY_REGISTRY_MODULE_NAME, Display, display_object_id},
+    move_package::MovePackage,
+    parse_sui_type_tag,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Argument, ObjectArg, ProgrammableTransaction, TransactionKind},
+};
+
+use crate::{
+    client_commands::{
+        GasDataArgs, PaymentArgs, SuiClientCommandResult, TxProcessingArgs,
+        dry_run_or_execute_or_serialize,
+    },
+    kiosk_commands::{deserialize_move_object, object_arg, object_type},
+};
+
+#[cfg(test)]
+#[path = "unit_tests/display_tests.rs"]
+mod display_tests;
+
+/// Maximum size of the rendered output of a Display, across all its fields.
+const MAX_OUTPUT_SIZE: usize = 1024 * 1024;
+
+/// Maximum depth of a Move value that a v2 template can output as JSON.
+const MAX_MOVE_VALUE_DEPTH: usize = 32;
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum DisplayCommand {
+    /// Show the Display template registered for a type.
+    #[clap(name = "show")]
+    Show {
+        /// The type whose Display to show, e.g. `0x42::nft::Nft`
+        #[clap(name = "type", value_parser = parse_sui_type_tag)]
+        type_: TypeTag,
+    },
+
+    /// Render the Display of an object's type against the object, substituting its fields.
+    #[clap(name = "render")]
+    Render {
+        /// ID of the object to render
+        #[clap(name = "object_id")]
+        object_id: ObjectID,
+        /// Render this template instead of the registered one. The file holds a YAML or JSON map
+        /// from field names to templates.
+        #[clap(long)]
+        template: Option<PathBuf>,
+        /// The syntax of the templates in `--template`. Detected from the templates if omitted.
+        #[clap(long, value_enum, requires = "template")]
+        template_version: Option<TemplateVersion>,
+    },
+
+    /// Check that every field referenced by a Display template exists in an object, failing if
+    /// any is missing.
+    #[clap(name = "validate")]
+    Validate {
+        /// ID of an object of the template's type
+        #[clap(name = "object_id")]
+        object_id: ObjectID,
+        /// Validate this template instead of the registered one. The file holds a YAML or JSON
+        /// map from field names to templates.
+        #[clap(long)]
+        template: Option<PathBuf>,
+        /// The syntax of the templates in `--template`. Detected from the templates if omitted.
+        #[clap(long, value_enum, requires = "template")]
+        template_version: Option<TemplateVersion>,
+    },
+
+    /// Update the Display of a type, using its DisplayCap. Fields are cleared first (with
+    /// `--clear`), then unset, then set.
+    #[clap(name = "set")]
+    Set {
+        /// The type whose Display to update
+        #[clap(name = "type", value_parser = parse_sui_type_tag)]
+        type_: TypeTag,
+        /// ID of the DisplayCap of the type
+        #[clap(long)]
+        cap_id: ObjectID,
+        /// Fields to set, as `name=template`, using the v2 template syntax of the registry
+        #[clap(long = "set", value_parser = parse_field, num_args(1..))]
+        set: Vec<(String, String)>,
+        /// Names of fields to remove
+        #[clap(long, num_args(1..))]
+        unset: Vec<String>,
+        /// Remove all fields before setting new ones
+        #[clap(long)]
+        clear: bool,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+}
+
+/// The syntax of a Display template. Displays in the registry use v2 templates, while the
+/// `0x2::display::Display<T>` objects that preceded them use v1 templates.
+#[derive(ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateVersion {
+    #[value(name = "1")]
+    V1,
+    #[value(name = "2")]
+    V2,
+}
+
+/// A Display template, and the result of rendering it if an object was given.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayOutput {
+    pub object_type: String,
+    pub version: TemplateVersion,
+    /// ID of the Display object, unless the template was read from a file.
+    pub display_id: Option<ObjectID>,
+    pub cap_id: Option<ObjectID>,
+    /// The object the template was rendered against.
+    pub object_id: Option<ObjectID>,
+    pub fields: Vec<DisplayFieldOutput>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayFieldOutput {
+    pub name: String,
+    pub template: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Fetches the objects a Display is rendered against, building the layouts of their types from
+/// the bytecode of the packages that define them.
+struct ObjectResolver {
+    client: sui_rpc_api::Client,
+    packages: Mutex<PackageCache>,
+}
+
+#[derive(Default)]
+struct PackageCache {
+    packages: BTreeMap<ObjectID, MovePackage>,
+    /// The ID and version of the newest known version of each package, by original ID.
+    latest: BTreeMap<ObjectID, (ObjectID, SequenceNumber)>,
+    /// The package that was missing when a layout last failed to build.
+    missing: Cell<Option<ObjectID>>,
+}
+
+impl DisplayCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        let mut client = context.grpc_client()?;
+        Ok(match self {
+            DisplayCommand::Show { type_ } => {
+                let (display_id, display) = fetch_display(&mut client, &type_).await?;
+                SuiClientCommandResult::Display(DisplayOutput {
+                    object_type: type_.to_canonical_string(true),
+                    version: TemplateVersion::V2,
+                    display_id: Some(display_id),
+                    cap_id: display.cap_id,
+                    object_id: None,
+                    fields: display
+                        .fields()
+                        .map(|(name, template)| DisplayFieldOutput {
+                            name: name.to_owned(),
+                            template: template.to_owned(),
+                            rendered: None,
+                            error: None,
+                        })
+                        .collect(),
+                })
+            }
+            DisplayCommand::Render {
+                object_id,
+                template,
+                template_version,
+            } => SuiClientCommandResult::Display(
+                render_object(&mut client, object_id, template, template_version).await?,
+            ),
+            DisplayCommand::Validate {
+                object_id,
+                template,
+                template_version,
+            } => {
+                let output =
+                    render_object(&mut client, object_id, template, template_version).await?;
+                let errors: Vec<_> = output
+                    .fields
+                    .iter()
+                    .filter_map(|f| Some(format!("{}: {}", f.name, f.error.as_ref()?)))
+                    .collect();
+                ensure!(
+                    errors.is_empty(),
+                    "Display of {} is invalid for {object_id}:\n{}",
+                    output.object_type,
+                    errors.join("\n")
+                );
+                SuiClientCommandResult::Display(output)
+            }
+            DisplayCommand::Set {
+                type_,
+                cap_id,
+                set,
+                unset,
+                clear,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                ensure!(
+                    clear || !set.is_empty() || !unset.is_empty(),
+                    "Nothing to update: pass --set, --unset or --clear"
+                );
+                for (name, template) in &set {
+                    parse_template(TemplateVersion::V2, template)
+                        .map_err(|e| anyhow!("Invalid template for field {name:?}: {e}"))?;
+                }
+
+                let signer = context.get_object_owner(&cap_id).await?;
+                let display_id = display_object_id(type_.clone())?;
+                let display = client.get_object(display_id).await.map_err(|e| {
+                    anyhow!("Failed to fetch the Display of {type_} ({display_id}): {e}")
+                })?;
+                let cap = client.get_object(cap_id).await?;
+                let ptb = update_display_ptb(
+                    object_arg(&display, true)?,
+                    cap.compute_object_reference(),
+                    type_,
+                    clear,
+                    unset,
+                    set,
+                )?;
+
+                let gas_payment = client
+                    .transaction_builder()
+                    .input_refs(&payment.gas)
+                    .await?;
+                dry_run_or_execute_or_serialize(
+                    signer,
+                    TransactionKind::ProgrammableTransaction(ptb),
+                    context,
+                    gas_payment,
+                    gas_data,
+                    processing,
+                )
+                .await?
+            }
+        })
+    }
+}
+
+/// Fetch the Display registered for `type_`, returning its ID along with it.
+async fn fetch_display(
+    client: &mut sui_rpc_api::Client,
+    type_: &TypeTag,
+) -> Result<(ObjectID, Display), anyhow::Error> {
+    let display_id = display_object_id(type_.clone())?;
+    let object = client
+        .get_object(display_id)
+        .await
+        .map_err(|e| anyhow!("No Display is registered for {type_} ({display_id}): {e}"))?;
+    Ok((display_id, deserialize_move_object(&object)?))
+}
+
+/// Render a Display against the object `object_id`. Registered Displays use v2 templates, and
+/// the version of templates read from a file is `version`, or detected from the templates.
+async fn render_object(
+    client: &mut sui_rpc_api::Client,
+    object_id: ObjectID,
+    template: Option<PathBuf>,
+    version: Option<TemplateVersion>,
+) -> Result<DisplayOutput, anyhow::Error> {
+    let object = client.get_object(object_id).await?;
+    let type_ = object_type(&object)?;
+
+    let (display_id, cap_id, version, fields): (_, _, _, Vec<(String, String)>) = match template {
+        Some(path) => {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| anyhow!("Failed to read {}: {e}", path.display()))?;
+            let fields: BTreeMap<String, String> = serde_yaml::from_str(&content)
+                .map_err(|e| anyhow!("Failed to parse {}: {e}", path.display()))?;
+            let fields: Vec<_> = fields.into_iter().collect();
+            let version = version.unwrap_or_else(|| detect_version(&fields));
+            (None, None, version, fields)
+        }
+        None => {
+            let (display_id, display) = fetch_display(client, &type_).await?;
+            let fields = display
+                .fields()
+                .map(|(name, template)| (name.to_owned(), template.to_owned()))
+                .collect();
+            (
+                Some(display_id),
+                display.cap_id,
+                TemplateVersion::V2,
+                fields,
+            )
+        }
+    };
+
+    let contents = object
+        .data
+        .try_as_move()
+        .ok_or_else(|| anyhow!("{object_id} is not a Move object"))?
+        .contents()
+        .to_vec();
+    let resolver = ObjectResolver::new(client.clone());
+    let layout = resolver.layout(&type_).await?;
+    Ok(DisplayOutput {
+        object_type: type_.to_canonical_string(true),
+        version,
+        display_id,
+        cap_id,
+        object_id: Some(object_id),
+        fields: render_fields(version, fields, contents, layout, resolver).await?,
+    })
+}
+
+/// The version of a set of templates: v2, unless only the v1 syntax accepts all of them.
+pub fn detect_version(fields: &[(String, String)]) -> TemplateVersion {
+    let parse_all = |version| {
+        fields
+            .iter()
+            .all(|(_, template)| parse_template(version, template).is_ok())
+    };
+    if !parse_all(TemplateVersion::V2) && parse_all(TemplateVersion::V1) {
+        TemplateVersion::V1
+    } else {
+        TemplateVersion::V2
+    }
+}
+
+/// Check the syntax of a single template.
+pub fn parse_template(version: TemplateVersion, template: &str) -> Result<(), anyhow::Error> {
+    match version {
+        TemplateVersion::V1 => {
+            sui_display::v1::Format::parse(Limits::default().max_depth, &single_field(template))
+                .map_err(|e| anyhow!("{e:#}"))?;
+        }
+        TemplateVersion::V2 => {
+            sui_display::v2::Format::parse(Limits::default(), template)
+                .map_err(|e| anyhow!("{e}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Render each `(name, template)` pair against an object, given by its BCS `contents` and the
+/// `layout` of its type, recording per-field errors rather than failing on the first one. v2
+/// templates load any other objects they refer to from `store`.
+pub async fn render_fields(
+    version: TemplateVersion,
+    fields: Vec<(String, String)>,
+    contents: Vec<u8>,
+    layout: MoveTypeLayout,
+    store: impl Store,
+) -> Result<Vec<DisplayFieldOutput>, anyhow::Error> {
+    let output = |name, template, result: Result<String, String>| {
+        let (rendered, error) = match result {
+            Ok(rendered) => (Some(rendered), None),
+            Err(e) => (None, Some(e)),
+        };
+        DisplayFieldOutput {
+            name,
+            template,
+            rendered,
+            error,
+        }
+    };
+
+    match version {
+        TemplateVersion::V1 => {
+            let mut outputs = vec![];
+            for (name, template) in fields {
+                let result = sui_display::v1::Format::parse(
+                    Limits::default().max_depth,
+                    &single_field(&template),
+                )
+                .and_then(|format| format.display(MAX_OUTPUT_SIZE, &contents, &layout))
+                .and_then(|rendered| {
+                    rendered
+                        .into_values()
+                        .next()
+                        .unwrap_or_else(|| Err(anyhow!("No output")))
+                })
+                .map_err(|e| format!("{e:#}"));
+                outputs.push(output(name, template, result));
+            }
+            Ok(outputs)
+        }
+        TemplateVersion::V2 => {
+            let display = sui_display::v2::Display::parse(
+                Limits::default(),
+                fields.iter().map(|(n, t)| (n.as_str(), t.as_str())),
+            )?;
+            let interpreter = Interpreter::new(OwnedSlice::new(layout, contents), store);
+            let rendered = display
+                .display::<Value>(MAX_MOVE_VALUE_DEPTH, MAX_OUTPUT_SIZE, &interpreter)
+                .await?;
+            Ok(fields
+                .iter()
+                .zip(rendered)
+                .map(|((_, template), (name, value))| {
+                    let result = match value {
+                        Ok(Value::String(s)) => Ok(s),
+                        Ok(Value::Null) => {
+                            Err("A field it refers to is missing or empty".to_owned())
+                        }
+                        Ok(value) => Ok(value.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    output(name, template.clone(), result)
+                })
+                .collect())
+        }
+    }
+}
+
+/// A v1 Display holding only `template`, to parse it on its own.
+fn single_field(template: &str) -> VecMap<String, String> {
+    VecMap {
+        contents: vec![collection_types::Entry {
+            key: String::new(),
+            value: template.to_owned(),
+        }],
+    }
+}
+
+/// Update the Display of `type_`: clear it if `clear`, then remove `unset` and set `set` fields.
+pub fn update_display_ptb(
+    display: ObjectArg,
+    cap: ObjectRef,
+    type_: TypeTag,
+    clear: bool,
+    unset: Vec<String>,
+    set: Vec<(String, String)>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let display = builder.obj(display)?;
+    let cap = builder.obj(ObjectArg::ImmOrOwnedObject(cap))?;
+
+    let call = |builder: &mut ProgrammableTransactionBuilder,
+                function: &IdentStr,
+                arguments: Vec<Argument>| {
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            DISPLAY_REGISTRY_MODULE_NAME.to_owned(),
+            function.to_owned(),
+            vec![type_.clone()],
+            arguments,
+        );
+    };
+
+    if clear {
+        call(&mut builder, ident_str!("clear"), vec![display, cap]);
+    }
+    for name in unset {
+        let name = builder.pure(name)?;
+        call(&mut builder, ident_str!("unset"), vec![display, cap, name]);
+    }
+    for (name, value) in set {
+        let name = builder.pure(name)?;
+        let value = builder.pure(value)?;
+        call(
+            &mut builder,
+            ident_str!("set"),
+            vec![display, cap, name, value],
+        );
+    }
+    Ok(builder.finish())
+}
+
+fn parse_field(s: &str) -> Result<(String, String), anyhow::Error> {
+    let (name, template) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected a field as `name=template`, got {s:?}"))?;
+    ensure!(!name.is_empty(), "Field name cannot be empty in {s:?}");
+    Ok((name.to_owned(), template.to_owned()))
+}
+
+impl fmt::Display for TemplateVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateVersion::V1 => write!(f, "v1"),
+            TemplateVersion::V2 => write!(f, "v2"),
+        }
+    }
+}
+
+impl ObjectResolver {
+    fn new(client: sui_rpc_api::Client) -> Self {
+        Self {
+            client,
+            packages: Mutex::new(PackageCache::default()),
+        }
+    }
+
+    /// Build the layout of `type_`, fetching the packages it needs that have not been fetched
+    /// yet, one at a time.
+    async fn layout(&self, type_: &TypeTag) -> Result<MoveTypeLayout, anyhow::Error> {
+        loop {
+            let missing = {
+                let packages = self.packages.lock().unwrap();
+                match TypeLayoutBuilder::build_with_types(type_, &*packages) {
+                    Ok(layout) => return Ok(layout),
+                    Err(e) => packages
+                        .missing
+                        .take()
+                        .ok_or_else(|| anyhow!("Failed to build the layout of {type_}: {e}"))?,
+                }
+            };
+
+            let object = self
+                .client
+                .clone()
+                .get_object(missing)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch package {missing}: {e}"))?;
+            let package = object
+                .data
+                .try_as_package()
+                .ok_or_else(|| anyhow!("{missing} is not a package"))?;
+            self.packages.lock().unwrap().insert(package.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl Store for ObjectResolver {
+    async fn latest(
+        &self,
+        id: AccountAddress,
+    ) -> anyhow::Result<Option<(MoveTypeLayout, Vec<u8>)>> {
+        let object = match self.client.clone().get_object(id.into()).await {
+            Ok(object) => object,
+            Err(status) if status.code() == tonic::Code::NotFound => return Ok(None),
+            Err(e) => bail!("Failed to fetch {id}: {e}"),
+        };
+        let Some(move_object) = object.data.try_as_move() else {
+            return Ok(None);
+        };
+        let layout = self.layout(&object_type(&object)?).await?;
+        Ok(Some((layout, move_object.contents().to_vec())))
+    }
+}
+
+impl PackageCache {
+    fn insert(&mut self, package: MovePackage) {
+        self.link(
+            package.original_package_id(),
+            package.id(),
+            package.version(),
+        );
+        for (original_id, upgrade) in package.linkage_table() {
+            self.link(*original_id, upgrade.upgraded_id, upgrade.upgraded_version);
+        }
+        self.packages.insert(package.id(), package);
+    }
+
+    /// Record that version `version` of package `original_id` is `id`, if it is the newest known.
+    fn link(&mut self, original_id: ObjectID, id: ObjectID, version: SequenceNumber) {
+        match self.latest.entry(original_id) {
+            Entry::Vacant(entry) => {
+                entry.insert((id, version));
+            }
+            Entry::Occupied(mut entry) if entry.get().1 < version => {
+                entry.insert((id, version));
+            }
+            Entry::Occupied(_) => {}
+        }
+    }
+}
+
+/// Modules are looked up in the newest known version of their package, as types in bytecode refer
+/// to packages by their original ID. A module of a package that has not been fetched yet is
+/// reported as missing, to fetch the package and try again.
+impl GetModule for PackageCache {
+    type Error = anyhow::Error;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> Result<Option<CompiledModule>, anyhow::Error> {
+        let address = ObjectID::from(*id.address());
+        let package_id = self.latest.get(&address).map_or(address, |(id, _)| *id);
+        let Some(package) = self.packages.get(&package_id) else {
+            self.missing.set(Some(package_id));
+            return Ok(None);
+        };
+        Ok(package
+            .deserialize_module_by_str(id.name().as_str(), &BinaryConfig::standard())
+            .ok())
+    }
+}
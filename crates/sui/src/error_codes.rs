@@ -0,0 +1,339 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stable classes of CLI failures, each with its own process exit code, so that scripts wrapping
+//! the CLI can branch on the class of a failure instead of parsing its message.
+//!
+//! Commands attach an [`ErrorCode`] to the errors they return with [`ErrorCodeExt::error_code`],
+//! or [`ErrorCodeExt::rpc_error_code`] for the errors of RPC requests. Attaching a code does not
+//! change how the error is displayed. Failures that are not attached a code exit with
+//! [`UNCLASSIFIED_EXIT_CODE`]. `sui --explain <code>` describes each code.
+
+use std::fmt;
+
+use sui_types::error::{SuiError, SuiErrorKind};
+use tonic::{Code, Status};
+
+/// The exit code of failures that do not belong to any [`ErrorCode`].
+pub const UNCLASSIFIED_EXIT_CODE: i32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// The CLI or client configuration is missing or invalid.
+    Config,
+    /// The network could not be reached, or did not respond as expected.
+    Network,
+    /// A transaction could not be signed.
+    Signing,
+    /// A Move package failed to build.
+    Build,
+    /// A transaction was executed, but aborted on-chain.
+    Abort,
+    /// A transaction was rejected or cancelled because the network or the shared objects it
+    /// uses are congested. Retrying later, or with a higher gas price, may succeed.
+    Congestion,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 6] = [
+        ErrorCode::Config,
+        ErrorCode::Network,
+        ErrorCode::Signing,
+        ErrorCode::Build,
+        ErrorCode::Abort,
+        ErrorCode::Congestion,
+    ];
+
+    /// The process exit code of failures with this code. These are stable across releases.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCode::Config => 10,
+            ErrorCode::Network => 11,
+            ErrorCode::Signing => 12,
+            ErrorCode::Build => 13,
+            ErrorCode::Abort => 14,
+            ErrorCode::Congestion => 15,
+        }
+    }
+
+    pub fn from_exit_code(exit_code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.exit_code() == exit_code)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCode::Config => "config",
+            ErrorCode::Network => "network",
+            ErrorCode::Signing => "signing",
+            ErrorCode::Build => "build",
+            ErrorCode::Abort => "abort",
+            ErrorCode::Congestion => "congestion",
+        }
+    }
+
+    /// What failures with this code mean, and what can be done about them.
+    pub fn explanation(self) -> &'static str {
+        match self {
+            ErrorCode::Config => {
+                "The CLI or client configuration is missing or invalid, for example the \
+                 client.yaml file, the active environment or the keystore could not be loaded. \
+                 Check the files under the Sui config directory, or the paths passed with \
+                 `--client.config`."
+            }
+            ErrorCode::Network => {
+                "The CLI could not reach the RPC endpoint of the active environment, or the \
+                 endpoint returned an error. The transaction may or may not have been executed. \
+                 Check the endpoint with `sui client active-env` and retry."
+            }
+            ErrorCode::Signing => {
+                "A transaction could not be signed, for example because the keystore has no key \
+                 for the sender or gas sponsor, or an external signer failed."
+            }
+            ErrorCode::Build => {
+                "A Move package failed to build. The compiler diagnostics are printed above the \
+                 error."
+            }
+            ErrorCode::Abort => {
+                "The transaction was executed, but failed on-chain, for example because a Move \
+                 function aborted or the gas budget was exhausted. Gas was charged. The error \
+                 includes the transaction digest and the reason of the failure."
+            }
+            ErrorCode::Congestion => {
+                "The transaction was rejected by overloaded validators, or cancelled because the \
+                 shared objects it uses were congested. Retry later, or with a higher gas price."
+            }
+        }
+    }
+
+    /// The code of `err`: the outermost code attached to it, or [`ErrorCode::Congestion`] if it
+    /// was caused by validators being overloaded.
+    pub fn of(err: &anyhow::Error) -> Option<Self> {
+        if let Some(coded) = err.chain().find_map(|e| e.downcast_ref::<CodedError>()) {
+            return Some(coded.code);
+        }
+
+        err.chain()
+            .filter_map(|e| e.downcast_ref::<SuiError>())
+            .any(|e| is_overload(e.as_inner()))
+            .then_some(ErrorCode::Congestion)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} error (exit code {}, see `sui --explain {}`)",
+            self.name(),
+            self.exit_code(),
+            self.exit_code()
+        )
+    }
+}
+
+impl std::error::Error for ErrorCode {}
+
+/// The code of an RPC request that failed with `status`: [`ErrorCode::Congestion`] if the network
+/// is overloaded, [`ErrorCode::Network`] if the request could not be served, or `None` if the
+/// request itself was rejected.
+pub fn status_error_code(status: &Status) -> Option<ErrorCode> {
+    match status.code() {
+        Code::ResourceExhausted => Some(ErrorCode::Congestion),
+        // Overloaded fullnodes report themselves as unavailable.
+        Code::Unavailable if status.message().contains("overloaded") => Some(ErrorCode::Congestion),
+        Code::Unavailable
+        | Code::DeadlineExceeded
+        | Code::Cancelled
+        | Code::Unknown
+        | Code::Internal
+        | Code::Unimplemented => Some(ErrorCode::Network),
+        _ => None,
+    }
+}
+
+/// An error with an [`ErrorCode`] attached. It is displayed as the error it wraps, and its
+/// sources are that error's sources.
+#[derive(Debug)]
+struct CodedError {
+    code: ErrorCode,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only the headline: the sources are reported through `source`.
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for CodedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+fn is_overload(kind: &SuiErrorKind) -> bool {
+    matches!(
+        kind,
+        SuiErrorKind::TooManyTransactionsPendingExecution { .. }
+            | SuiErrorKind::TooManyTransactionsPendingOnObject { .. }
+            | SuiErrorKind::TooOldTransactionPendingOnObject { .. }
+            | SuiErrorKind::TooManyTransactionsPendingConsensus
+            | SuiErrorKind::TransactionRejectedDueToOutbiddingDuringCongestion { .. }
+            | SuiErrorKind::ValidatorOverloadedRetryAfter { .. }
+    )
+}
+
+pub trait ErrorCodeExt<T> {
+    /// Attach `code` to the error, if any.
+    fn error_code(self, code: ErrorCode) -> anyhow::Result<T>;
+
+    /// Attach the code of the failed RPC request the error comes from, if any, using
+    /// [`status_error_code`]. Errors without a gRPC status are attached [`ErrorCode::Network`], as
+    /// the request could not be made.
+    fn rpc_error_code(self) -> anyhow::Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> ErrorCodeExt<T> for Result<T, E> {
+    fn error_code(self, code: ErrorCode) -> anyhow::Result<T> {
+        self.map_err(|e| {
+            CodedError {
+                code,
+                error: e.into(),
+            }
+            .into()
+        })
+    }
+
+    fn rpc_error_code(self) -> anyhow::Result<T> {
+        self.map_err(|e| {
+            let error = e.into();
+            let code = match error.chain().find_map(|e| e.downcast_ref::<Status>()) {
+                Some(status) => status_error_code(status),
+                None => Some(ErrorCode::Network),
+            };
+            match code {
+                Some(code) => CodedError { code, error }.into(),
+                None => error,
+            }
+        })
+    }
+}
+
+/// The process exit code for the outcome of a command.
+pub fn exit_code(result: &anyhow::Result<()>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(err) => ErrorCode::of(err).map_or(UNCLASSIFIED_EXIT_CODE, ErrorCode::exit_code),
+    }
+}
+
+/// The text printed by `sui --explain <code>`.
+pub fn explain(exit_code: i32) -> String {
+    match ErrorCode::from_exit_code(exit_code) {
+        Some(code) => format!(
+            "{} ({}): {}",
+            code.exit_code(),
+            code.name(),
+            code.explanation()
+        ),
+        None if exit_code == UNCLASSIFIED_EXIT_CODE => format!(
+            "{UNCLASSIFIED_EXIT_CODE}: The command failed for a reason without a more specific \
+             code. See the error message for details."
+        ),
+        None => {
+            let codes: Vec<_> = ErrorCode::ALL
+                .iter()
+                .map(|c| format!("{} ({})", c.exit_code(), c.name()))
+                .collect();
+            format!(
+                "{exit_code} is not a Sui CLI error code. The error codes are {}, and \
+                 {UNCLASSIFIED_EXIT_CODE} for other failures.",
+                codes.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn outermost_code_wins() {
+        let err: anyhow::Result<()> = Err(anyhow!("connection refused"));
+        let err = err
+            .error_code(ErrorCode::Network)
+            .error_code(ErrorCode::Config);
+        assert_eq!(exit_code(&err), ErrorCode::Config.exit_code());
+    }
+
+    #[test]
+    fn codes_do_not_change_messages() {
+        let err = anyhow!("connection refused").context("Failed to fetch objects");
+        let expected = (format!("{err}"), format!("{err:#}"), format!("{err:?}"));
+
+        let coded = Err::<(), _>(err)
+            .error_code(ErrorCode::Network)
+            .unwrap_err();
+        assert_eq!(
+            (
+                format!("{coded}"),
+                format!("{coded:#}"),
+                format!("{coded:?}")
+            ),
+            expected
+        );
+        assert_eq!(ErrorCode::of(&coded), Some(ErrorCode::Network));
+    }
+
+    #[test]
+    fn rpc_errors_are_classified_by_status() {
+        let code = |status: Status| {
+            let err = Err::<(), _>(status).rpc_error_code().unwrap_err();
+            ErrorCode::of(&err)
+        };
+
+        assert_eq!(
+            code(Status::resource_exhausted("validator overloaded")),
+            Some(ErrorCode::Congestion)
+        );
+        assert_eq!(
+            code(Status::unavailable("system is overloaded")),
+            Some(ErrorCode::Congestion)
+        );
+        assert_eq!(
+            code(Status::unavailable(
+                "timed-out before finality could be reached"
+            )),
+            Some(ErrorCode::Network)
+        );
+        assert_eq!(
+            code(Status::deadline_exceeded("timeout")),
+            Some(ErrorCode::Network)
+        );
+        assert_eq!(code(Status::invalid_argument("bad signature")), None);
+
+        let err = Err::<(), _>(anyhow!("invalid url"))
+            .rpc_error_code()
+            .unwrap_err();
+        assert_eq!(ErrorCode::of(&err), Some(ErrorCode::Network));
+    }
+
+    #[test]
+    fn overloaded_validators_are_congestion() {
+        let err: anyhow::Error =
+            SuiError::from(SuiErrorKind::TooManyTransactionsPendingConsensus).into();
+        assert_eq!(ErrorCode::of(&err), Some(ErrorCode::Congestion));
+        assert_eq!(ErrorCode::of(&anyhow!("boom")), None);
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_exit_code(code.exit_code()), Some(code));
+            assert_ne!(code.exit_code(), UNCLASSIFIED_EXIT_CODE);
+        }
+    }
+}
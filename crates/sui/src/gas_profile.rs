@@ -0,0 +1,170 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::{Context, bail, ensure};
+use futures::{StreamExt, TryStreamExt};
+use move_vm_profiler::trace_converter::{GasProfiler, ProfilerConfig};
+use serde::Serialize;
+use sui_rpc_api::Client;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    digests::TransactionDigest,
+    effects::TransactionEffectsAPI,
+    transaction::{Command, ProgrammableTransaction, TransactionData, TransactionKind},
+};
+
+/// Most commands a profiled transaction can have. Profiling dry-runs every prefix of the
+/// transaction, so its cost on the fullnode grows with the square of the number of commands.
+const MAX_PROFILED_COMMANDS: usize = 128;
+
+/// Number of prefixes dry-run at the same time.
+const MAX_CONCURRENT_DRY_RUNS: usize = 8;
+
+/// Computation cost of a single command of a profiled transaction.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandGasOutput {
+    pub command: String,
+    pub computation_cost: u64,
+}
+
+/// Breakdown of the computation cost of a transaction by PTB command, as produced by
+/// `--dry-run --gas-profile`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GasProfileOutput {
+    /// Speedscope profile with one frame per command, loadable by flamegraph viewers.
+    pub profile_path: PathBuf,
+    pub computation_cost: u64,
+    /// Cost of running the transaction without any command: loading its inputs and the minimum
+    /// charge.
+    pub base_cost: u64,
+    pub commands: Vec<CommandGasOutput>,
+}
+
+/// Profile the computation cost of each command of `kind`, whose dry run has the given `digest` and
+/// `computation_cost`, by dry-running every prefix of its commands with the same gas payment and
+/// sponsor. The result is also saved as a speedscope profile in the working directory.
+///
+/// Costs are derived from the gas charged to the prefixes, so they are as coarse as the gas
+/// buckets of the protocol. Per-function frames need a Move execution trace, which dry runs do not
+/// produce: use `sui replay --trace` and `sui analyze-trace gas-profile` for those.
+pub(crate) async fn profile_gas(
+    client: &Client,
+    kind: &TransactionKind,
+    sender: SuiAddress,
+    sponsor: SuiAddress,
+    gas_payment: Vec<ObjectRef>,
+    gas_budget: u64,
+    gas_price: u64,
+    digest: TransactionDigest,
+    computation_cost: u64,
+) -> Result<GasProfileOutput, anyhow::Error> {
+    let TransactionKind::ProgrammableTransaction(ptb) = kind else {
+        bail!("Gas profiles are only available for programmable transactions");
+    };
+
+    ensure!(
+        ptb.commands.len() <= MAX_PROFILED_COMMANDS,
+        "Gas profiles are limited to transactions of at most {MAX_PROFILED_COMMANDS} commands, \
+        this one has {}",
+        ptb.commands.len()
+    );
+
+    // Cumulative computation cost of the first `k` commands, for each `k`. Prefixes pay with the
+    // same gas coins as the profiled transaction, so that commands using the gas coin behave the
+    // same way in both.
+    let mut cumulative: Vec<u64> = futures::stream::iter(0..ptb.commands.len())
+        .map(|k| {
+            let prefix = ProgrammableTransaction {
+                inputs: ptb.inputs.clone(),
+                commands: ptb.commands[..k].to_vec(),
+            };
+            // Prefixes may leave values unused and fail at the end, but they are still charged
+            // for the commands they ran.
+            let tx_data = TransactionData::new_with_gas_coins_allow_sponsor(
+                TransactionKind::ProgrammableTransaction(prefix),
+                sender,
+                gas_payment.clone(),
+                gas_budget,
+                gas_price,
+                sponsor,
+            );
+            async move {
+                let response = client
+                    .simulate_transaction(&tx_data, true, false)
+                    .await
+                    .with_context(|| format!("Failed to dry run the first {k} command(s)"))?;
+                Ok::<_, anyhow::Error>(
+                    response
+                        .transaction
+                        .effects
+                        .gas_cost_summary()
+                        .computation_cost,
+                )
+            }
+        })
+        .buffered(MAX_CONCURRENT_DRY_RUNS)
+        .try_collect()
+        .await?;
+    cumulative.push(computation_cost);
+
+    let base_cost = cumulative[0];
+    let commands: Vec<_> = ptb
+        .commands
+        .iter()
+        .zip(cumulative.windows(2))
+        .enumerate()
+        .map(|(i, (command, costs))| CommandGasOutput {
+            command: format!("{i}: {}", command_name(command)),
+            computation_cost: costs[1].saturating_sub(costs[0]),
+        })
+        .collect();
+
+    let name = format!("gas_profile_{digest}");
+    let mut profiler = GasProfiler::init(
+        ProfilerConfig {
+            output_dir: None,
+            use_long_function_name: true,
+        },
+        name.clone(),
+    );
+    // The profiler measures gas as it is consumed from a starting amount.
+    let mut remaining = computation_cost;
+    profiler.open_frame("transaction".to_string(), String::new(), remaining);
+    for (frame, cost) in std::iter::once(("base".to_string(), base_cost)).chain(
+        commands
+            .iter()
+            .map(|c| (c.command.clone(), c.computation_cost)),
+    ) {
+        profiler.open_frame(frame.clone(), String::new(), remaining);
+        remaining = remaining.saturating_sub(cost);
+        profiler.close_frame(frame, String::new(), remaining);
+    }
+    profiler.close_frame("transaction".to_string(), String::new(), remaining);
+
+    let profile_path = PathBuf::from(format!("{name}.json"));
+    std::fs::write(&profile_path, serde_json::to_string_pretty(&profiler)?)
+        .with_context(|| format!("Failed to write gas profile to {}", profile_path.display()))?;
+
+    Ok(GasProfileOutput {
+        profile_path,
+        computation_cost,
+        base_cost,
+        commands,
+    })
+}
+
+fn command_name(command: &Command) -> String {
+    match command {
+        Command::MoveCall(call) => format!("{}::{}::{}", call.package, call.module, call.function),
+        Command::TransferObjects(..) => "TransferObjects".to_string(),
+        Command::SplitCoins(..) => "SplitCoins".to_string(),
+        Command::MergeCoins(..) => "MergeCoins".to_string(),
+        Command::Publish(..) => "Publish".to_string(),
+        Command::MakeMoveVec(..) => "MakeMoveVec".to_string(),
+        Command::Upgrade(..) => "Upgrade".to_string(),
+    }
+}
@@ -1,19 +1,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use camino::Utf8PathBuf;
-use clap::Parser;
+use clap::{Args, Parser};
 use fastcrypto::encoding::{Encoding, Hex};
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
 use std::path::PathBuf;
+use sui_config::genesis::Genesis;
 use sui_config::{SUI_GENESIS_FILENAME, genesis::UnsignedGenesis};
 use sui_genesis_builder::Builder;
+use sui_genesis_builder::ceremony::{CeremonyState, Contribution};
+use sui_genesis_builder::validator_info::{GenesisValidatorInfo, ValidatorInfo};
 use sui_types::multiaddr::Multiaddr;
 use sui_types::{
     base_types::SuiAddress,
     committee::ProtocolVersion,
     crypto::{
-        AuthorityKeyPair, KeypairTraits, NetworkKeyPair, SuiKeyPair, generate_proof_of_possession,
+        AuthorityKeyPair, AuthoritySignInfo, AuthoritySignature, KeypairTraits, NetworkKeyPair,
+        SuiAuthoritySignature, SuiKeyPair, generate_proof_of_possession,
     },
     message_envelope::Message,
 };
@@ -49,30 +54,8 @@ pub enum CeremonyCommand {
     ValidateState,
 
     AddValidator {
-        #[clap(long)]
-        name: String,
-        #[clap(long)]
-        validator_key_file: PathBuf,
-        #[clap(long)]
-        worker_key_file: PathBuf,
-        #[clap(long)]
-        account_key_file: PathBuf,
-        #[clap(long)]
-        network_key_file: PathBuf,
-        #[clap(long)]
-        network_address: Multiaddr,
-        #[clap(long)]
-        p2p_address: Multiaddr,
-        #[clap(long)]
-        narwhal_primary_address: Multiaddr,
-        #[clap(long)]
-        narwhal_worker_address: Multiaddr,
-        #[clap(long)]
-        description: String,
-        #[clap(long)]
-        image_url: String,
-        #[clap(long)]
-        project_url: String,
+        #[clap(flatten)]
+        validator: ValidatorArgs,
     },
 
     ListValidators,
@@ -87,6 +70,119 @@ pub enum CeremonyCommand {
     },
 
     Finalize,
+
+    /// Show the step the ceremony is at, and whether genesis can be finalized
+    Status,
+
+    /// Write the state of the ceremony to a single file, for validators to verify and sign the
+    /// unsigned genesis checkpoint with `contribute-signature`
+    ExportState {
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Write a contribution for a validator to join the ceremony, to send to its coordinator.
+    /// Does not need the ceremony directory
+    ContributeValidator {
+        #[clap(flatten)]
+        validator: ValidatorArgs,
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Verify the unsigned genesis checkpoint of an exported ceremony state, and write a
+    /// contribution signing it, to send to the coordinator. Does not need the ceremony directory
+    ContributeSignature {
+        /// The ceremony state written by `export-state`
+        #[clap(long)]
+        state: PathBuf,
+        #[clap(long)]
+        key_file: PathBuf,
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Check that a contribution can be added to the ceremony, without adding it
+    VerifyContribution {
+        contribution: PathBuf,
+    },
+
+    /// Verify a contribution, and add it to the ceremony
+    AddContribution {
+        contribution: PathBuf,
+    },
+
+    /// Check that a genesis blob holds the ceremony's genesis checkpoint, certified by a quorum of
+    /// its validators
+    VerifyGenesis {
+        /// Defaults to the genesis blob written by `finalize` in the ceremony directory
+        #[clap(long)]
+        genesis: Option<PathBuf>,
+        /// The ceremony state written by `export-state`, to verify against instead of the
+        /// ceremony directory
+        #[clap(long)]
+        state: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+pub struct ValidatorArgs {
+    #[clap(long)]
+    name: String,
+    #[clap(long)]
+    validator_key_file: PathBuf,
+    #[clap(long)]
+    worker_key_file: PathBuf,
+    #[clap(long)]
+    account_key_file: PathBuf,
+    #[clap(long)]
+    network_key_file: PathBuf,
+    #[clap(long)]
+    network_address: Multiaddr,
+    #[clap(long)]
+    p2p_address: Multiaddr,
+    #[clap(long)]
+    narwhal_primary_address: Multiaddr,
+    #[clap(long)]
+    narwhal_worker_address: Multiaddr,
+    #[clap(long)]
+    description: String,
+    #[clap(long)]
+    image_url: String,
+    #[clap(long)]
+    project_url: String,
+}
+
+impl ValidatorArgs {
+    /// The validator's info, with a proof of possession of its protocol key.
+    fn into_genesis_validator_info(self) -> Result<GenesisValidatorInfo> {
+        let keypair: AuthorityKeyPair = read_authority_keypair_from_file(self.validator_key_file)?;
+        let account_keypair: SuiKeyPair = read_keypair_from_file(self.account_key_file)?;
+        let worker_keypair: NetworkKeyPair = read_network_keypair_from_file(self.worker_key_file)?;
+        let network_keypair: NetworkKeyPair =
+            read_network_keypair_from_file(self.network_key_file)?;
+        let proof_of_possession =
+            generate_proof_of_possession(&keypair, (&account_keypair.public()).into());
+        Ok(GenesisValidatorInfo {
+            info: ValidatorInfo {
+                name: self.name,
+                protocol_key: keypair.public().into(),
+                worker_key: worker_keypair.public().clone(),
+                account_address: SuiAddress::from(&account_keypair.public()),
+                network_key: network_keypair.public().clone(),
+                gas_price: sui_config::node::DEFAULT_VALIDATOR_GAS_PRICE,
+                commission_rate: sui_config::node::DEFAULT_COMMISSION_RATE,
+                network_address: self.network_address,
+                p2p_address: self.p2p_address,
+                narwhal_primary_address: self.narwhal_primary_address,
+                narwhal_worker_address: self.narwhal_worker_address,
+                description: self.description,
+                image_url: self.image_url,
+                project_url: self.project_url,
+            },
+            proof_of_possession,
+        })
+    }
 }
 
 pub fn run(cmd: Ceremony) -> Result<()> {
@@ -113,46 +209,13 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             builder.validate()?;
         }
 
-        CeremonyCommand::AddValidator {
-            name,
-            validator_key_file,
-            worker_key_file,
-            account_key_file,
-            network_key_file,
-            network_address,
-            p2p_address,
-            narwhal_primary_address,
-            narwhal_worker_address,
-            description,
-            image_url,
-            project_url,
-        } => {
-            let mut builder = Builder::load(&dir)?;
-            let keypair: AuthorityKeyPair = read_authority_keypair_from_file(validator_key_file)?;
-            let account_keypair: SuiKeyPair = read_keypair_from_file(account_key_file)?;
-            let worker_keypair: NetworkKeyPair = read_network_keypair_from_file(worker_key_file)?;
-            let network_keypair: NetworkKeyPair = read_network_keypair_from_file(network_key_file)?;
-            let pop = generate_proof_of_possession(&keypair, (&account_keypair.public()).into());
-            builder = builder.add_validator(
-                sui_genesis_builder::validator_info::ValidatorInfo {
-                    name,
-                    protocol_key: keypair.public().into(),
-                    worker_key: worker_keypair.public().clone(),
-                    account_address: SuiAddress::from(&account_keypair.public()),
-                    network_key: network_keypair.public().clone(),
-                    gas_price: sui_config::node::DEFAULT_VALIDATOR_GAS_PRICE,
-                    commission_rate: sui_config::node::DEFAULT_COMMISSION_RATE,
-                    network_address,
-                    p2p_address,
-                    narwhal_primary_address,
-                    narwhal_worker_address,
-                    description,
-                    image_url,
-                    project_url,
-                },
-                pop,
-            );
-            builder.save(dir)?;
+        CeremonyCommand::AddValidator { validator } => {
+            let builder = Builder::load(&dir)?;
+            let GenesisValidatorInfo {
+                info,
+                proof_of_possession,
+            } = validator.into_genesis_validator_info()?;
+            builder.add_validator(info, proof_of_possession).save(dir)?;
         }
 
         CeremonyCommand::ListValidators => {
@@ -241,6 +304,97 @@ pub fn run(cmd: Ceremony) -> Result<()> {
                 Hex::encode(genesis.hash())
             );
         }
+
+        CeremonyCommand::Status => {
+            let builder = Builder::load(&dir)?;
+            println!("{}", builder.stage());
+        }
+
+        CeremonyCommand::ExportState { output } => {
+            let builder = Builder::load(&dir)?;
+            builder.state().save(&output)?;
+            println!("Exported the ceremony state to {}", output.display());
+        }
+
+        CeremonyCommand::ContributeValidator { validator, output } => {
+            let contribution = Contribution::Validator(validator.into_genesis_validator_info()?);
+            Builder::new().verify_contribution(&contribution)?;
+            contribution.save(&output)?;
+            println!(
+                "Wrote a contribution for {contribution} to {}",
+                output.display()
+            );
+        }
+
+        CeremonyCommand::ContributeSignature {
+            state,
+            key_file,
+            output,
+        } => {
+            let keypair: AuthorityKeyPair = read_authority_keypair_from_file(key_file)?;
+            let builder = Builder::from_state(CeremonyState::load(&state)?)?;
+            check_protocol_version(&builder, protocol_version)?;
+
+            let Some(UnsignedGenesis { checkpoint, .. }) = builder.unsigned_genesis_checkpoint()
+            else {
+                return Err(anyhow!(
+                    "Unable to sign genesis checkpoint; it hasn't been built yet"
+                ));
+            };
+            let contribution = Contribution::Signature(AuthoritySignInfo {
+                epoch: checkpoint.epoch,
+                authority: keypair.public().into(),
+                signature: AuthoritySignature::new_secure(
+                    &IntentMessage::new(
+                        Intent::sui_app(IntentScope::CheckpointSummary),
+                        checkpoint.clone(),
+                    ),
+                    &checkpoint.epoch,
+                    &keypair,
+                ),
+            });
+            builder.verify_contribution(&contribution)?;
+            contribution.save(&output)?;
+
+            println!(
+                "Successfully verified and signed genesis checkpoint {}, and wrote the signature \
+                 to {}",
+                checkpoint.digest(),
+                output.display()
+            );
+        }
+
+        CeremonyCommand::VerifyContribution { contribution } => {
+            let builder = Builder::load(&dir)?;
+            let contribution = Contribution::load(contribution)?;
+            builder.verify_contribution(&contribution)?;
+            println!("Verified {contribution}");
+        }
+
+        CeremonyCommand::AddContribution { contribution } => {
+            let builder = Builder::load(&dir)?;
+            let contribution = Contribution::load(contribution)?;
+            let description = contribution.to_string();
+            let builder = builder.add_contribution(contribution)?;
+            println!("Added {description}");
+            println!("{}", builder.stage());
+            builder.save(dir)?;
+        }
+
+        CeremonyCommand::VerifyGenesis { genesis, state } => {
+            let builder = match state {
+                Some(state) => Builder::from_state(CeremonyState::load(state)?)?,
+                None => Builder::load(&dir)?,
+            };
+            let genesis_path = genesis.unwrap_or_else(|| dir.join(SUI_GENESIS_FILENAME).into());
+            let genesis = Genesis::load(&genesis_path)?;
+            builder.verify_genesis(&genesis)?;
+            println!(
+                "Verified {}, blake2b-256: {}",
+                genesis_path.display(),
+                Hex::encode(genesis.hash())
+            );
+        }
     }
 
     Ok(())
@@ -268,14 +422,15 @@ mod test {
     use sui_genesis_builder::validator_info::ValidatorInfo;
     use sui_keys::keypair_file::{write_authority_keypair_to_file, write_keypair_to_file};
     use sui_macros::nondeterministic;
+    use sui_types::base_types::ObjectID;
     use sui_types::crypto::{AccountKeyPair, AuthorityKeyPair, SuiKeyPair, get_key_pair_from_rng};
+    use sui_types::object::Object;
 
-    #[test]
-    #[cfg_attr(msim, ignore)]
-    fn ceremony() -> Result<()> {
-        let dir = nondeterministic!(tempfile::TempDir::new().unwrap());
+    type ValidatorFiles = (PathBuf, PathBuf, PathBuf, PathBuf, ValidatorInfo);
 
-        let validators = (0..10)
+    /// `count` validators, with their protocol, worker, network and account key files in `dir`.
+    fn generate_validators(dir: &std::path::Path, count: usize) -> Vec<ValidatorFiles> {
+        (0..count)
             .map(|i| {
                 let keypair: AuthorityKeyPair = get_key_pair_from_rng(&mut rand::rngs::OsRng).1;
                 let worker_keypair: NetworkKeyPair =
@@ -300,18 +455,18 @@ mod test {
                     image_url: String::new(),
                     project_url: String::new(),
                 };
-                let key_file = dir.path().join(format!("{}-0.key", info.name));
+                let key_file = dir.join(format!("{}-0.key", info.name));
                 write_authority_keypair_to_file(&keypair, &key_file).unwrap();
 
-                let worker_key_file = dir.path().join(format!("{}.key", info.name));
+                let worker_key_file = dir.join(format!("{}.key", info.name));
                 write_keypair_to_file(&SuiKeyPair::Ed25519(worker_keypair), &worker_key_file)
                     .unwrap();
 
-                let network_key_file = dir.path().join(format!("{}-1.key", info.name));
+                let network_key_file = dir.join(format!("{}-1.key", info.name));
                 write_keypair_to_file(&SuiKeyPair::Ed25519(network_keypair), &network_key_file)
                     .unwrap();
 
-                let account_key_file = dir.path().join(format!("{}-2.key", info.name));
+                let account_key_file = dir.join(format!("{}-2.key", info.name));
                 write_keypair_to_file(&SuiKeyPair::Ed25519(account_keypair), &account_key_file)
                     .unwrap();
 
@@ -323,7 +478,38 @@ mod test {
                     info,
                 )
             })
-            .collect::<Vec<_>>();
+            .collect()
+    }
+
+    fn validator_args(
+        validator: &ValidatorInfo,
+        key_file: &std::path::Path,
+        worker_key_file: &std::path::Path,
+        network_key_file: &std::path::Path,
+        account_key_file: &std::path::Path,
+    ) -> ValidatorArgs {
+        ValidatorArgs {
+            name: validator.name().to_owned(),
+            validator_key_file: key_file.into(),
+            worker_key_file: worker_key_file.into(),
+            network_key_file: network_key_file.into(),
+            account_key_file: account_key_file.into(),
+            network_address: validator.network_address().to_owned(),
+            p2p_address: validator.p2p_address().to_owned(),
+            narwhal_primary_address: validator.narwhal_primary_address.clone(),
+            narwhal_worker_address: validator.narwhal_worker_address.clone(),
+            description: String::new(),
+            image_url: String::new(),
+            project_url: String::new(),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn ceremony() -> Result<()> {
+        let dir = nondeterministic!(tempfile::TempDir::new().unwrap());
+
+        let validators = generate_validators(dir.path(), 10);
 
         // Initialize
         let command = Ceremony {
@@ -341,18 +527,13 @@ mod test {
                 path: Some(dir.path().into()),
                 protocol_version: None,
                 command: CeremonyCommand::AddValidator {
-                    name: validator.name().to_owned(),
-                    validator_key_file: key_file.into(),
-                    worker_key_file: worker_key_file.into(),
-                    network_key_file: network_key_file.into(),
-                    account_key_file: account_key_file.into(),
-                    network_address: validator.network_address().to_owned(),
-                    p2p_address: validator.p2p_address().to_owned(),
-                    narwhal_primary_address: validator.narwhal_primary_address.clone(),
-                    narwhal_worker_address: validator.narwhal_worker_address.clone(),
-                    description: String::new(),
-                    image_url: String::new(),
-                    project_url: String::new(),
+                    validator: validator_args(
+                        validator,
+                        key_file,
+                        worker_key_file,
+                        network_key_file,
+                        account_key_file,
+                    ),
                 },
             };
             command.run()?;
@@ -402,4 +583,100 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn ceremony_with_contributions() -> Result<()> {
+        let coordinator = nondeterministic!(tempfile::TempDir::new().unwrap());
+        let participants = nondeterministic!(tempfile::TempDir::new().unwrap());
+        let validators = generate_validators(participants.path(), 4);
+        let ceremony = |command| {
+            Ceremony {
+                path: Some(coordinator.path().into()),
+                protocol_version: None,
+                command,
+            }
+            .run()
+        };
+
+        ceremony(CeremonyCommand::Init)?;
+
+        // The coordinator inserts an extra object, which the exported state must carry
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        Builder::load(coordinator.path())?
+            .add_object(object.clone())
+            .save(coordinator.path())?;
+
+        // Validators join by sending contributions, which the coordinator adds
+        for (i, (key_file, worker_key_file, network_key_file, account_key_file, validator)) in
+            validators.iter().enumerate()
+        {
+            let contribution = participants
+                .path()
+                .join(format!("validator-{i}.contribution"));
+            ceremony(CeremonyCommand::ContributeValidator {
+                validator: validator_args(
+                    validator,
+                    key_file,
+                    worker_key_file,
+                    network_key_file,
+                    account_key_file,
+                ),
+                output: contribution.clone(),
+            })?;
+            ceremony(CeremonyCommand::AddContribution { contribution })?;
+        }
+        ceremony(CeremonyCommand::BuildUnsignedCheckpoint)?;
+
+        // Validators can't join once the checkpoint is built
+        assert!(
+            ceremony(CeremonyCommand::VerifyContribution {
+                contribution: participants.path().join("validator-0.contribution"),
+            })
+            .is_err()
+        );
+
+        // Validators sign the exported state, without the ceremony directory
+        let state = participants.path().join("ceremony.state");
+        ceremony(CeremonyCommand::ExportState {
+            output: state.clone(),
+        })?;
+        for (i, (key_file, ..)) in validators.iter().enumerate() {
+            let contribution = participants
+                .path()
+                .join(format!("signature-{i}.contribution"));
+            ceremony(CeremonyCommand::ContributeSignature {
+                state: state.clone(),
+                key_file: key_file.clone(),
+                output: contribution.clone(),
+            })?;
+            ceremony(CeremonyCommand::VerifyContribution {
+                contribution: contribution.clone(),
+            })?;
+            ceremony(CeremonyCommand::AddContribution { contribution })?;
+        }
+        assert!(Builder::load(coordinator.path())?.stage().can_finalize());
+        let restored = Builder::from_state(CeremonyState::load(&state)?)?;
+        assert!(
+            restored
+                .unsigned_genesis_checkpoint()
+                .unwrap()
+                .objects
+                .iter()
+                .any(|o| o.id() == object.id())
+        );
+
+        // Finalize, and check the genesis against both the directory and the exported state
+        ceremony(CeremonyCommand::Finalize)?;
+        ceremony(CeremonyCommand::VerifyGenesis {
+            genesis: None,
+            state: None,
+        })?;
+        ceremony(CeremonyCommand::VerifyGenesis {
+            genesis: Some(coordinator.path().join(SUI_GENESIS_FILENAME)),
+            state: Some(state),
+        })?;
+
+        Ok(())
+    }
 }
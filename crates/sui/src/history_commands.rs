@@ -0,0 +1,292 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, anyhow};
+use clap::*;
+use serde::{Deserialize, Serialize};
+use sui_rpc_api::client::ExecutedTransaction;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{
+    base_types::SuiAddress, digests::TransactionDigest, effects::TransactionEffectsAPI,
+    execution_status::ExecutionStatus, transaction::TransactionDataAPI,
+};
+use tracing::warn;
+
+use crate::client_commands::SuiClientCommandResult;
+
+#[cfg(test)]
+#[path = "unit_tests/history_tests.rs"]
+mod history_tests;
+
+/// Name of the history file, stored next to the client config.
+pub const HISTORY_FILE_NAME: &str = "tx_history.json";
+
+/// Oldest entries are dropped once the history grows past this many transactions.
+const MAX_HISTORY_ENTRIES: usize = 10_000;
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum HistoryCommand {
+    /// List transactions submitted from this client, most recent first.
+    #[clap(name = "list")]
+    List {
+        /// Only show transactions submitted to this environment
+        #[clap(long)]
+        env: Option<String>,
+        /// Only show transactions whose label contains this text
+        #[clap(long)]
+        label: Option<String>,
+        /// Maximum number of transactions to show
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Show the recorded details of a transaction.
+    #[clap(name = "show")]
+    Show {
+        /// Digest of the transaction
+        #[clap(name = "digest")]
+        digest: TransactionDigest,
+    },
+
+    /// Set or replace the label of a recorded transaction.
+    #[clap(name = "annotate")]
+    Annotate {
+        /// Digest of the transaction
+        #[clap(name = "digest")]
+        digest: TransactionDigest,
+        /// The new label
+        #[clap(name = "label")]
+        label: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HistoryStatus {
+    Success,
+    Failure,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub digest: TransactionDigest,
+    pub env: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    pub sender: SuiAddress,
+    pub status: HistoryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub timestamp_ms: u64,
+}
+
+/// Transactions submitted by the CLI, persisted as JSON in the client config directory.
+pub struct TransactionHistory {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl HistoryCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        let path = TransactionHistory::path_for(context.config.path());
+
+        Ok(match self {
+            HistoryCommand::List { env, label, limit } => SuiClientCommandResult::History(
+                TransactionHistory::load(&path)?
+                    .list(env.as_deref(), label.as_deref(), limit)
+                    .cloned()
+                    .collect(),
+            ),
+
+            HistoryCommand::Show { digest } => {
+                let entry = TransactionHistory::load(&path)?
+                    .find(&digest)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Transaction {digest} is not in the local history"))?;
+                SuiClientCommandResult::History(vec![entry])
+            }
+
+            HistoryCommand::Annotate { digest, label } => {
+                let entry = TransactionHistory::update(&path, |history| {
+                    history.annotate(&digest, label).cloned()
+                })?;
+                SuiClientCommandResult::History(vec![entry])
+            }
+        })
+    }
+}
+
+impl TransactionHistory {
+    /// Location of the history file for the client config at `config_path`.
+    pub fn path_for(config_path: &Path) -> PathBuf {
+        config_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(HISTORY_FILE_NAME)
+    }
+
+    /// Read the history at `path`, starting empty if it does not exist yet.
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let entries = if path.exists() {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("Cannot read transaction history at {path:?}"))?;
+            serde_json::from_str(&contents)
+                .with_context(|| format!("Cannot parse transaction history at {path:?}"))?
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Load the history at `path`, apply `f` to it and save the result. Other clients updating
+    /// the same history wait on a file lock until the result is saved, so that concurrent
+    /// commands do not drop each other's entries.
+    pub fn update<T>(
+        path: &Path,
+        f: impl FnOnce(&mut Self) -> Result<T, anyhow::Error>,
+    ) -> Result<T, anyhow::Error> {
+        let lock_path = path.with_extension("json.lock");
+        let lock = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Cannot open transaction history lock at {lock_path:?}"))?;
+        lock.lock()
+            .with_context(|| format!("Cannot lock transaction history at {lock_path:?}"))?;
+
+        // The lock is released when `lock` is dropped, after the history is saved.
+        let mut history = Self::load(path)?;
+        let result = f(&mut history)?;
+        history.save()?;
+        Ok(result)
+    }
+
+    /// Write the history back to disk, replacing the previous file atomically. Use `update`
+    /// rather than `load` and `save` when other clients may be updating the history too.
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(&self.entries)?)
+            .with_context(|| format!("Cannot write transaction history at {tmp:?}"))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Cannot write transaction history at {:?}", self.path))?;
+        Ok(())
+    }
+
+    /// Add `entry`, replacing any previous entry for the same digest but keeping its label if
+    /// the new entry does not have one.
+    pub fn record(&mut self, mut entry: HistoryEntry) {
+        if let Some(pos) = self.entries.iter().position(|e| e.digest == entry.digest) {
+            let previous = self.entries.remove(pos);
+            entry.label = entry.label.or(previous.label);
+        }
+
+        self.entries.push(entry);
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let excess = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..excess);
+        }
+    }
+
+    pub fn find(&self, digest: &TransactionDigest) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|e| &e.digest == digest)
+    }
+
+    pub fn annotate(
+        &mut self,
+        digest: &TransactionDigest,
+        label: String,
+    ) -> Result<&HistoryEntry, anyhow::Error> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| &e.digest == digest)
+            .ok_or_else(|| anyhow!("Transaction {digest} is not in the local history"))?;
+        entry.label = Some(label);
+        Ok(entry)
+    }
+
+    /// Entries matching the filters, most recent first.
+    pub fn list<'a>(
+        &'a self,
+        env: Option<&'a str>,
+        label: Option<&'a str>,
+        limit: usize,
+    ) -> impl Iterator<Item = &'a HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(move |e| env.is_none_or(|env| e.env == env))
+            .filter(move |e| {
+                label.is_none_or(|label| e.label.as_deref().is_some_and(|l| l.contains(label)))
+            })
+            .take(limit)
+    }
+}
+
+impl HistoryEntry {
+    pub fn new(env: String, chain_id: Option<String>, response: &ExecutedTransaction) -> Self {
+        let (status, error) = match response.effects.status() {
+            ExecutionStatus::Success => (HistoryStatus::Success, None),
+            ExecutionStatus::Failure(failure) => {
+                (HistoryStatus::Failure, Some(format!("{:?}", failure.error)))
+            }
+        };
+
+        Self {
+            digest: response.transaction.digest(),
+            env,
+            chain_id,
+            sender: response.transaction.sender(),
+            status,
+            error,
+            label: None,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis() as u64),
+        }
+    }
+}
+
+/// Record an executed transaction in the local history. Failing to update the history does not
+/// fail the command, since the transaction has already been submitted.
+pub(crate) fn record_transaction(
+    context: &WalletContext,
+    response: &ExecutedTransaction,
+    label: Option<String>,
+) {
+    let result = (|| {
+        let env = context.get_active_env()?;
+        let mut entry = HistoryEntry::new(env.alias.clone(), env.chain_id.clone(), response);
+        entry.label = label;
+
+        TransactionHistory::update(
+            &TransactionHistory::path_for(context.config.path()),
+            |history| {
+                history.record(entry);
+                Ok(())
+            },
+        )
+    })();
+
+    if let Err(e) = result {
+        warn!("Failed to record transaction in local history: {e}");
+    }
+}
@@ -36,13 +36,14 @@ use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use sui_keys::key_derive::generate_new_key;
+use sui_keys::key_derive::{derive_bip85_child_mnemonic, generate_new_key, generate_vanity_key};
 use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
 };
 use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::passkey::{PasskeyCredential, PasskeySigner};
 use sui_sdk::wallet_context::WalletContext;
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
@@ -104,9 +105,22 @@ pub enum KeyToolCommand {
         #[clap(long, default_value = "0")]
         cur_epoch: u64,
     },
+    /// Derive an independent child mnemonic from a master mnemonic phrase, following BIP-85
+    /// (m/83696968'/39'/0'/{words}'/{index}'). Children can be handed to other wallets or
+    /// devices, and derived again from the master phrase if they are lost. Word length can be
+    /// { word12 | word18 | word24 }, default to word12 if not specified.
+    DeriveChildMnemonic {
+        mnemonic_phrase: String,
+        /// The index of the child to derive
+        #[clap(long, default_value = "0")]
+        index: u32,
+        #[clap(long)]
+        word_length: Option<String>,
+    },
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
-    /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
+    /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Secp256r1
+    /// also accepts fully hardened paths m/74'/784'/{account}'/{change}'/{index}'. Word
     /// length can be { word12 | word15 | word18 | word21 | word24} default to word12
     /// if not specified.
     ///
@@ -147,6 +161,35 @@ pub enum KeyToolCommand {
         #[clap(long, short = 's')]
         sort_by_alias: bool,
     },
+    /// Register a new passkey (WebAuthn credential) for the relying party `rp_id` with an OS-level
+    /// authenticator, reached through the `helper` binary. Prints the credential ID and the
+    /// passkey's Sui address and public key, which are needed to sign with it.
+    ///
+    /// The passkey is not added to the keystore, so `sui client` cannot sign with it.
+    PasskeyRegister {
+        /// The passkey helper binary talking to the authenticator
+        #[clap(long)]
+        helper: String,
+        #[clap(long)]
+        rp_id: String,
+        #[clap(long, default_value = "sui")]
+        user_name: String,
+    },
+    /// Sign a test personal message with a registered passkey through the `helper` binary, and
+    /// verify the resulting passkey signature.
+    PasskeyTest {
+        /// The passkey helper binary talking to the authenticator
+        #[clap(long)]
+        helper: String,
+        #[clap(long)]
+        rp_id: String,
+        /// The Base64URL credential ID printed by `passkey-register`
+        #[clap(long)]
+        credential_id: String,
+        /// The Base64 encoded `flag || pk` passkey public key printed by `passkey-register`
+        #[clap(long)]
+        public_key: String,
+    },
     /// This reads the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
     /// (Base64 encoded `privkey`). This prints out the account keypair as Base64 encoded `flag || privkey`,
@@ -229,6 +272,27 @@ pub enum KeyToolCommand {
     /// and prints out its Sui address, Base64 encoded public key, the key scheme, and the key scheme flag.
     Unpack { keypair: String },
 
+    /// Generate a new mnemonic and search its accounts m/44'/784'/{account}'/0'/0' (or the
+    /// secp256k1 and secp256r1 equivalents) for an address starting with the given hex prefix,
+    /// on a pool of worker threads. Each additional hex digit makes the search 16 times longer.
+    ///
+    /// The keypair file is output to the current directory, and the key can be recovered from
+    /// the printed mnemonic and derivation path.
+    Vanity {
+        key_scheme: SignatureScheme,
+        /// The hex digits the address must start with, with or without `0x`
+        #[clap(long)]
+        prefix: String,
+        /// Number of worker threads, default to the number of available CPUs
+        #[clap(long)]
+        workers: Option<usize>,
+        /// Give up after searching this many accounts
+        #[clap(long, default_value = "10000000")]
+        max_attempts: u32,
+        #[clap(long)]
+        word_length: Option<String>,
+    },
+
     /// Given the max_epoch, generate an OAuth url, ask user to paste the redirect with id_token, call salt server, then call the prover server,
     /// create a test transaction, use the ephemeral key to sign and execute it by assembling to a serialized zkLogin signature.
     ZkLoginSignAndExecuteTx {
@@ -344,6 +408,14 @@ pub struct Key {
     peer_id: Option<String>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildMnemonic {
+    index: u32,
+    derivation_path: String,
+    mnemonic: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExportedKey {
@@ -400,6 +472,23 @@ pub struct ConvertOutput {
     scheme: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyOutput {
+    sui_address: SuiAddress,
+    public_base64_key: String,
+    rp_id: String,
+    credential_id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyTestOutput {
+    sui_address: SuiAddress,
+    message: String,
+    passkey_signature: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrivateKeyBase64 {
@@ -430,6 +519,13 @@ pub struct SignData {
     sui_signature: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VanityKey {
+    derivation_path: String,
+    key: Key,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ZkLoginSignAndExecuteTx {
@@ -463,6 +559,7 @@ pub enum CommandOutput {
     Convert(ConvertOutput),
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeOrVerifyTx(DecodeOrVerifyTxOutput),
+    DeriveChildMnemonic(ChildMnemonic),
     Error(String),
     Generate(Key),
     Import(Key),
@@ -472,10 +569,13 @@ pub enum CommandOutput {
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
+    PasskeyRegister(PasskeyOutput),
+    PasskeyTest(PasskeyTestOutput),
     PrivateKeyBase64(PrivateKeyBase64),
     Show(Key),
     Sign(SignData),
     SignKMS(SerializedSig),
+    Vanity(VanityKey),
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
     ZkLoginSigVerify(ZkLoginSigVerifyResponse),
@@ -596,6 +696,19 @@ impl KeyToolCommand {
                     }
                 }
             }
+            KeyToolCommand::DeriveChildMnemonic {
+                mnemonic_phrase,
+                index,
+                word_length,
+            } => {
+                let (mnemonic, path) =
+                    derive_bip85_child_mnemonic(&mnemonic_phrase, word_length, index)?;
+                CommandOutput::DeriveChildMnemonic(ChildMnemonic {
+                    index,
+                    derivation_path: path.to_string(),
+                    mnemonic,
+                })
+            }
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -720,6 +833,53 @@ impl KeyToolCommand {
                 CommandOutput::List(keys)
             }
 
+            KeyToolCommand::PasskeyRegister {
+                helper,
+                rp_id,
+                user_name,
+            } => {
+                let credential = PasskeySigner::new(helper)
+                    .create_credential(&rp_id, &user_name)
+                    .await?;
+                CommandOutput::PasskeyRegister(PasskeyOutput {
+                    sui_address: credential.address(),
+                    public_base64_key: credential.public_key.encode_base64(),
+                    rp_id: credential.rp_id,
+                    credential_id: credential.credential_id,
+                })
+            }
+
+            KeyToolCommand::PasskeyTest {
+                helper,
+                rp_id,
+                credential_id,
+                public_key,
+            } => {
+                let public_key = PublicKey::decode_base64(&public_key)
+                    .map_err(|e| anyhow!("Invalid Base64 passkey public key: {:?}", e))?;
+                let credential = PasskeyCredential {
+                    rp_id,
+                    credential_id,
+                    public_key,
+                };
+                let message = "Testing a passkey with the Sui CLI".to_string();
+                let intent_msg = IntentMessage::new(
+                    Intent::personal_message(),
+                    PersonalMessage {
+                        message: message.as_bytes().to_vec(),
+                    },
+                );
+                // The signature is verified against the credential before it is returned.
+                let sig = PasskeySigner::new(helper)
+                    .sign(&credential, &intent_msg)
+                    .await?;
+                CommandOutput::PasskeyTest(PasskeyTestOutput {
+                    sui_address: credential.address(),
+                    message,
+                    passkey_signature: Base64::encode(sig.as_ref()),
+                })
+            }
+
             KeyToolCommand::LoadKeypair { file } => {
                 let output = match read_keypair_from_file(&file) {
                     Ok(keypair) => {
@@ -955,6 +1115,41 @@ impl KeyToolCommand {
                 CommandOutput::Show(key)
             }
 
+            KeyToolCommand::Vanity {
+                key_scheme,
+                prefix,
+                workers,
+                max_attempts,
+                word_length,
+            } => {
+                let workers = workers
+                    .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+                let search_prefix = prefix.clone();
+                let found = tokio::task::spawn_blocking(move || {
+                    generate_vanity_key(
+                        key_scheme,
+                        &search_prefix,
+                        word_length,
+                        workers,
+                        max_attempts,
+                    )
+                })
+                .await??;
+                let Some((sui_address, skp, path, phrase)) = found else {
+                    return Err(anyhow!(
+                        "No address starting with {prefix} found in {max_attempts} attempts, \
+                         try a shorter prefix or a larger --max-attempts"
+                    ));
+                };
+                write_keypair_to_file(&skp, format!("{sui_address}.key"))?;
+                let mut key = Key::from(&skp);
+                key.mnemonic = Some(phrase);
+                CommandOutput::Vanity(VanityKey {
+                    derivation_path: path.to_string(),
+                    key,
+                })
+            }
+
             KeyToolCommand::ZkLoginInsecureSignPersonalMessage { data, max_epoch } => {
                 let msg = PersonalMessage {
                     message: data.as_bytes().to_vec(),
@@ -0,0 +1,834 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, bail, ensure};
+use clap::*;
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::TypeTag};
+use serde::{Deserialize, Serialize};
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::SuiTypeTag;
+use sui_rpc::proto::sui::rpc::v2::dynamic_field::DynamicFieldKind;
+use sui_rpc_api::Client;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{
+    MoveTypeTagTrait, SUI_FRAMEWORK_PACKAGE_ID,
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    kiosk::{
+        Item, KIOSK_MODULE_NAME, Kiosk, Listing, Lock, TRANSFER_POLICY_MODULE_NAME, TransferPolicy,
+    },
+    object::{Object, Owner},
+    parse_sui_type_tag,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{
+        Argument, Command, ObjectArg, ProgrammableTransaction, SharedObjectMutability,
+        TransactionKind,
+    },
+};
+
+use crate::client_commands::{
+    GasDataArgs, PaymentArgs, SuiClientCommandResult, TxProcessingArgs,
+    dry_run_or_execute_or_serialize,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/kiosk_tests.rs"]
+mod kiosk_tests;
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum KioskCommand {
+    /// Create a shared kiosk owned by the sender, who receives its KioskOwnerCap.
+    #[clap(name = "create")]
+    Create {
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Show a kiosk's owner, profits, and the items it holds with their listing prices.
+    #[clap(name = "show")]
+    Show {
+        /// ID of the kiosk
+        #[clap(name = "kiosk_id")]
+        kiosk_id: ObjectID,
+    },
+
+    /// Place an item owned by the sender in a kiosk, optionally listing it for sale.
+    #[clap(name = "place")]
+    Place {
+        #[clap(flatten)]
+        kiosk: KioskOwnerArgs,
+        /// ID of the item to place
+        #[clap(long)]
+        item_id: ObjectID,
+        /// List the item for this price, in MIST
+        #[clap(long)]
+        price: Option<u64>,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// List an item of a kiosk for sale.
+    #[clap(name = "list")]
+    List {
+        #[clap(flatten)]
+        kiosk: KioskOwnerArgs,
+        /// ID of the item to list
+        #[clap(long)]
+        item_id: ObjectID,
+        /// Price of the item, in MIST
+        #[clap(long)]
+        price: u64,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Take an item of a kiosk off sale.
+    #[clap(name = "delist")]
+    Delist {
+        #[clap(flatten)]
+        kiosk: KioskOwnerArgs,
+        /// ID of the item to delist
+        #[clap(long)]
+        item_id: ObjectID,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Buy a listed item, paying from the gas coin, and send it to the sender. Only transfer
+    /// policies without rules are supported, as rules have to be satisfied by their own calls.
+    #[clap(name = "purchase")]
+    Purchase {
+        /// ID of the kiosk selling the item
+        #[clap(long)]
+        kiosk_id: ObjectID,
+        /// ID of the item to buy
+        #[clap(long)]
+        item_id: ObjectID,
+        /// ID of the TransferPolicy of the item's type
+        #[clap(long)]
+        policy_id: ObjectID,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Withdraw the profits of a kiosk to the sender.
+    #[clap(name = "withdraw")]
+    Withdraw {
+        #[clap(flatten)]
+        kiosk: KioskOwnerArgs,
+        /// Amount to withdraw, in MIST. Withdraws all profits if not set.
+        #[clap(long)]
+        amount: Option<u64>,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Install a rule on a transfer policy by calling the rule module's function, e.g.
+    /// `--package <RULES_PACKAGE> --module royalty_rule --args 500 0`. The policy and its cap are
+    /// passed as the first two arguments, followed by `--args`.
+    #[clap(name = "add-rule")]
+    AddRule {
+        #[clap(flatten)]
+        policy: PolicyOwnerArgs,
+        /// Package defining the rule
+        #[clap(long)]
+        package: ObjectID,
+        /// Module defining the rule
+        #[clap(long)]
+        module: String,
+        /// Function installing the rule
+        #[clap(long, default_value = "add")]
+        function: String,
+        /// Additional arguments of the function, e.g. the rule's configuration
+        #[clap(long, num_args(0..))]
+        args: Vec<SuiJsonValue>,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Remove a rule from a transfer policy.
+    #[clap(name = "remove-rule")]
+    RemoveRule {
+        #[clap(flatten)]
+        policy: PolicyOwnerArgs,
+        /// Type of the rule's witness, e.g. `0x...::royalty_rule::Rule`
+        #[clap(long)]
+        rule_type: String,
+        /// Type of the rule's configuration, e.g. `0x...::royalty_rule::Config`
+        #[clap(long)]
+        config_type: String,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct KioskOwnerArgs {
+    /// ID of the kiosk
+    #[clap(long)]
+    pub kiosk_id: ObjectID,
+    /// ID of the KioskOwnerCap of the kiosk
+    #[clap(long)]
+    pub cap_id: ObjectID,
+}
+
+#[derive(Args, Debug)]
+pub struct PolicyOwnerArgs {
+    /// ID of the TransferPolicy
+    #[clap(long)]
+    pub policy_id: ObjectID,
+    /// ID of the TransferPolicyCap of the policy
+    #[clap(long)]
+    pub policy_cap_id: ObjectID,
+}
+
+/// Contents of a kiosk, as shown by `sui client kiosk show`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KioskOutput {
+    pub kiosk_id: ObjectID,
+    pub owner: SuiAddress,
+    pub profits: u64,
+    pub item_count: u32,
+    pub items: Vec<KioskItemOutput>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KioskItemOutput {
+    pub object_id: ObjectID,
+    pub object_type: String,
+    /// Price in MIST, if the item is listed.
+    pub price: Option<u64>,
+    /// Whether the item is listed exclusively through a PurchaseCap.
+    pub exclusive: bool,
+    /// Whether the item can only leave the kiosk through a purchase.
+    pub locked: bool,
+}
+
+impl KioskCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        let mut client = context.grpc_client()?;
+        let (signer, ptb, payment, gas_data, processing) = match self {
+            KioskCommand::Show { kiosk_id } => {
+                return Ok(SuiClientCommandResult::Kiosk(
+                    kiosk_contents(&mut client, kiosk_id).await?,
+                ));
+            }
+            KioskCommand::Create {
+                payment,
+                gas_data,
+                processing,
+            } => (
+                context.active_address()?,
+                create_kiosk_ptb(),
+                payment,
+                gas_data,
+                processing,
+            ),
+            KioskCommand::Place {
+                kiosk,
+                item_id,
+                price,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (signer, kiosk_arg, cap) = kiosk_owner(&mut client, &kiosk).await?;
+                let item = client.get_object(item_id).await?;
+                (
+                    signer,
+                    place_ptb(
+                        kiosk_arg,
+                        cap,
+                        item.compute_object_reference(),
+                        object_type(&item)?,
+                        price,
+                    )?,
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            KioskCommand::List {
+                kiosk,
+                item_id,
+                price,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (signer, kiosk_arg, cap) = kiosk_owner(&mut client, &kiosk).await?;
+                let item_type = object_type(&client.get_object(item_id).await?)?;
+                (
+                    signer,
+                    list_ptb(kiosk_arg, cap, item_id, item_type, price)?,
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            KioskCommand::Delist {
+                kiosk,
+                item_id,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (signer, kiosk_arg, cap) = kiosk_owner(&mut client, &kiosk).await?;
+                let item_type = object_type(&client.get_object(item_id).await?)?;
+                (
+                    signer,
+                    delist_ptb(kiosk_arg, cap, item_id, item_type)?,
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            KioskCommand::Purchase {
+                kiosk_id,
+                item_id,
+                policy_id,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let contents = kiosk_contents(&mut client, kiosk_id).await?;
+                let item = contents
+                    .items
+                    .iter()
+                    .find(|item| item.object_id == item_id)
+                    .ok_or_else(|| anyhow!("Item {item_id} is not in kiosk {kiosk_id}"))?;
+                let (Some(price), false) = (item.price, item.exclusive) else {
+                    bail!("Item {item_id} is not listed for sale in kiosk {kiosk_id}");
+                };
+                let item_type = parse_sui_type_tag(&item.object_type)?;
+
+                let policy = client.get_object(policy_id).await?;
+                let policy_type = object_type(&policy)?;
+                ensure!(
+                    transfer_policy_item_type(&policy_type) == Some(&item_type),
+                    "{policy_id} is not a TransferPolicy for {item_type}"
+                );
+                let rules = TransferPolicy::try_from(&policy)?.rules;
+                ensure!(
+                    rules.contents.is_empty(),
+                    "TransferPolicy {policy_id} has rules which have to be satisfied in a PTB: {}",
+                    rules
+                        .contents
+                        .iter()
+                        .map(|r| r.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+
+                let signer = match processing.sender {
+                    Some(sender) => sender,
+                    None => context.active_address()?,
+                };
+                let kiosk_arg = object_arg(&client.get_object(kiosk_id).await?, true)?;
+                let policy_arg = object_arg(&policy, false)?;
+                (
+                    signer,
+                    purchase_ptb(kiosk_arg, policy_arg, item_id, item_type, price, signer)?,
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            KioskCommand::Withdraw {
+                kiosk,
+                amount,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (signer, kiosk_arg, cap) = kiosk_owner(&mut client, &kiosk).await?;
+                (
+                    signer,
+                    withdraw_ptb(kiosk_arg, cap, amount, signer)?,
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+            KioskCommand::AddRule {
+                policy,
+                package,
+                module,
+                function,
+                mut args,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let signer = context.get_object_owner(&policy.policy_cap_id).await?;
+                let policy_type = object_type(&client.get_object(policy.policy_id).await?)?;
+                let item_type = transfer_policy_item_type(&policy_type)
+                    .ok_or_else(|| anyhow!("{} is not a TransferPolicy", policy.policy_id))?;
+                args.splice(
+                    0..0,
+                    [
+                        SuiJsonValue::from_object_id(policy.policy_id),
+                        SuiJsonValue::from_object_id(policy.policy_cap_id),
+                    ],
+                );
+                let transaction_builder = client.transaction_builder();
+                let tx_kind = transaction_builder
+                    .move_call_tx_kind(
+                        package,
+                        &module,
+                        &function,
+                        vec![SuiTypeTag::from(item_type.clone())],
+                        args,
+                    )
+                    .await?;
+                let gas_payment = transaction_builder.input_refs(&payment.gas).await?;
+                return dry_run_or_execute_or_serialize(
+                    signer,
+                    tx_kind,
+                    context,
+                    gas_payment,
+                    gas_data,
+                    processing,
+                )
+                .await;
+            }
+            KioskCommand::RemoveRule {
+                policy,
+                rule_type,
+                config_type,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let signer = context.get_object_owner(&policy.policy_cap_id).await?;
+                let policy_object = client.get_object(policy.policy_id).await?;
+                let policy_type = object_type(&policy_object)?;
+                let item_type = transfer_policy_item_type(&policy_type)
+                    .ok_or_else(|| anyhow!("{} is not a TransferPolicy", policy.policy_id))?
+                    .clone();
+                let cap = client.get_object(policy.policy_cap_id).await?;
+                (
+                    signer,
+                    remove_rule_ptb(
+                        object_arg(&policy_object, true)?,
+                        cap.compute_object_reference(),
+                        item_type,
+                        parse_sui_type_tag(&rule_type)?,
+                        parse_sui_type_tag(&config_type)?,
+                    )?,
+                    payment,
+                    gas_data,
+                    processing,
+                )
+            }
+        };
+
+        let gas_payment = client
+            .transaction_builder()
+            .input_refs(&payment.gas)
+            .await?;
+        dry_run_or_execute_or_serialize(
+            signer,
+            TransactionKind::ProgrammableTransaction(ptb),
+            context,
+            gas_payment,
+            gas_data,
+            processing,
+        )
+        .await
+    }
+}
+
+/// The owner of the kiosk cap, with the arguments referring to the kiosk and its cap.
+async fn kiosk_owner(
+    client: &mut Client,
+    args: &KioskOwnerArgs,
+) -> Result<(SuiAddress, ObjectArg, ObjectRef), anyhow::Error> {
+    let kiosk = client.get_object(args.kiosk_id).await?;
+    let cap = client.get_object(args.cap_id).await?;
+    let Owner::AddressOwner(owner) = cap.owner() else {
+        bail!(
+            "KioskOwnerCap {} is not owned by an address but {:?}",
+            args.cap_id,
+            cap.owner()
+        );
+    };
+    Ok((
+        *owner,
+        object_arg(&kiosk, true)?,
+        cap.compute_object_reference(),
+    ))
+}
+
+async fn kiosk_contents(
+    client: &mut Client,
+    kiosk_id: ObjectID,
+) -> Result<KioskOutput, anyhow::Error> {
+    let kiosk_object = client.get_object(kiosk_id).await?;
+    ensure!(
+        kiosk_object
+            .struct_tag()
+            .is_some_and(|tag| Kiosk::is_kiosk(&tag)),
+        "{kiosk_id} is not a kiosk"
+    );
+    let kiosk = Kiosk::try_from(&kiosk_object)?;
+
+    let item_name = Item::get_type_tag().to_canonical_string(true);
+    let listing_name = Listing::get_type_tag().to_canonical_string(true);
+    let lock_name = Lock::get_type_tag().to_canonical_string(true);
+
+    let mut items = BTreeMap::new();
+    let mut listings = BTreeMap::new();
+    let mut locks = BTreeSet::new();
+    let mut page_token = None;
+    loop {
+        let response = client
+            .get_dynamic_fields(kiosk_id, None, page_token)
+            .await?;
+        for field in &response.dynamic_fields {
+            let name = field.name();
+            if name.name() == item_name && field.kind() == DynamicFieldKind::Object {
+                let id = name.deserialize::<Item>()?.id.bytes;
+                items.insert(id, field.value_type().to_owned());
+            } else if name.name() == listing_name {
+                let listing = name.deserialize::<Listing>()?;
+                let price = field.value().deserialize::<u64>()?;
+                listings.insert(listing.id.bytes, (price, listing.is_exclusive));
+            } else if name.name() == lock_name {
+                locks.insert(name.deserialize::<Lock>()?.id.bytes);
+            }
+        }
+        page_token = response.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(KioskOutput {
+        kiosk_id,
+        owner: kiosk.owner,
+        profits: kiosk.profits.value(),
+        item_count: kiosk.item_count,
+        items: items
+            .into_iter()
+            .map(|(object_id, object_type)| {
+                let listing = listings.get(&object_id);
+                KioskItemOutput {
+                    object_id,
+                    object_type,
+                    price: listing.map(|(price, _)| *price),
+                    exclusive: listing.is_some_and(|(_, exclusive)| *exclusive),
+                    locked: locks.contains(&object_id),
+                }
+            })
+            .collect(),
+    })
+}
+
+/// The item type `T` of a `TransferPolicy<T>` type.
+fn transfer_policy_item_type(policy_type: &TypeTag) -> Option<&TypeTag> {
+    match policy_type {
+        TypeTag::Struct(tag) => TransferPolicy::item_type(tag),
+        _ => None,
+    }
+}
+
+/// The type of a Move object.
+pub(crate) fn object_type(object: &Object) -> Result<TypeTag, anyhow::Error> {
+    let tag = object
+        .struct_tag()
+        .ok_or_else(|| anyhow!("{} is not a Move object", object.id()))?;
+    Ok(TypeTag::Struct(Box::new(tag)))
+}
+
+/// Deserialize the contents of a Move object.
+pub(crate) fn deserialize_move_object<T: for<'de> Deserialize<'de>>(
+    object: &Object,
+) -> Result<T, anyhow::Error> {
+    let move_object = object
+        .data
+        .try_as_move()
+        .ok_or_else(|| anyhow!("{} is not a Move object", object.id()))?;
+    Ok(bcs::from_bytes(move_object.contents())?)
+}
+
+/// The input referring to `object`, taking it by mutable reference if `mutable` and it is shared.
+pub(crate) fn object_arg(object: &Object, mutable: bool) -> Result<ObjectArg, anyhow::Error> {
+    Ok(match object.owner() {
+        Owner::Shared {
+            initial_shared_version,
+        } => ObjectArg::SharedObject {
+            id: object.id(),
+            initial_shared_version: *initial_shared_version,
+            mutability: if mutable {
+                SharedObjectMutability::Mutable
+            } else {
+                SharedObjectMutability::Immutable
+            },
+        },
+        Owner::AddressOwner(_) | Owner::Immutable => {
+            ObjectArg::ImmOrOwnedObject(object.compute_object_reference())
+        }
+        owner => bail!(
+            "Object {} cannot be used as an input: {owner:?}",
+            object.id()
+        ),
+    })
+}
+
+fn call(
+    builder: &mut ProgrammableTransactionBuilder,
+    module: &IdentStr,
+    function: &IdentStr,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<Argument>,
+) -> Argument {
+    builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        module.to_owned(),
+        function.to_owned(),
+        type_arguments,
+        arguments,
+    )
+}
+
+/// Create a kiosk shared in its default configuration.
+pub fn create_kiosk_ptb() -> ProgrammableTransaction {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    call(
+        &mut builder,
+        KIOSK_MODULE_NAME,
+        ident_str!("default"),
+        vec![],
+        vec![],
+    );
+    builder.finish()
+}
+
+/// Place `item` in the kiosk, and list it if a `price` is given.
+pub fn place_ptb(
+    kiosk: ObjectArg,
+    cap: ObjectRef,
+    item: ObjectRef,
+    item_type: TypeTag,
+    price: Option<u64>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let mut arguments = vec![
+        builder.obj(kiosk)?,
+        builder.obj(ObjectArg::ImmOrOwnedObject(cap))?,
+        builder.obj(ObjectArg::ImmOrOwnedObject(item))?,
+    ];
+    let function = match price {
+        Some(price) => {
+            arguments.push(builder.pure(price)?);
+            ident_str!("place_and_list")
+        }
+        None => ident_str!("place"),
+    };
+    call(
+        &mut builder,
+        KIOSK_MODULE_NAME,
+        function,
+        vec![item_type],
+        arguments,
+    );
+    Ok(builder.finish())
+}
+
+/// List the kiosk's `item_id` for `price`.
+pub fn list_ptb(
+    kiosk: ObjectArg,
+    cap: ObjectRef,
+    item_id: ObjectID,
+    item_type: TypeTag,
+    price: u64,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let arguments = vec![
+        builder.obj(kiosk)?,
+        builder.obj(ObjectArg::ImmOrOwnedObject(cap))?,
+        builder.pure(item_id)?,
+        builder.pure(price)?,
+    ];
+    call(
+        &mut builder,
+        KIOSK_MODULE_NAME,
+        ident_str!("list"),
+        vec![item_type],
+        arguments,
+    );
+    Ok(builder.finish())
+}
+
+/// Take the kiosk's `item_id` off sale.
+pub fn delist_ptb(
+    kiosk: ObjectArg,
+    cap: ObjectRef,
+    item_id: ObjectID,
+    item_type: TypeTag,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let arguments = vec![
+        builder.obj(kiosk)?,
+        builder.obj(ObjectArg::ImmOrOwnedObject(cap))?,
+        builder.pure(item_id)?,
+    ];
+    call(
+        &mut builder,
+        KIOSK_MODULE_NAME,
+        ident_str!("delist"),
+        vec![item_type],
+        arguments,
+    );
+    Ok(builder.finish())
+}
+
+/// Pay `price` out of the gas coin for the kiosk's `item_id`, confirm the transfer against a
+/// policy without rules, and send the item to `buyer`.
+pub fn purchase_ptb(
+    kiosk: ObjectArg,
+    policy: ObjectArg,
+    item_id: ObjectID,
+    item_type: TypeTag,
+    price: u64,
+    buyer: SuiAddress,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let kiosk = builder.obj(kiosk)?;
+    let policy = builder.obj(policy)?;
+    let id = builder.pure(item_id)?;
+    let price = builder.pure(price)?;
+    let Argument::Result(payment) =
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![price]))
+    else {
+        unreachable!("commands always produce a result");
+    };
+    let Argument::Result(purchase) = call(
+        &mut builder,
+        KIOSK_MODULE_NAME,
+        ident_str!("purchase"),
+        vec![item_type.clone()],
+        vec![kiosk, id, Argument::NestedResult(payment, 0)],
+    ) else {
+        unreachable!("commands always produce a result");
+    };
+    call(
+        &mut builder,
+        TRANSFER_POLICY_MODULE_NAME,
+        ident_str!("confirm_request"),
+        vec![item_type],
+        vec![policy, Argument::NestedResult(purchase, 1)],
+    );
+    builder.transfer_arg(buyer, Argument::NestedResult(purchase, 0));
+    Ok(builder.finish())
+}
+
+/// Withdraw `amount` of the kiosk's profits, or all of them, to `recipient`.
+pub fn withdraw_ptb(
+    kiosk: ObjectArg,
+    cap: ObjectRef,
+    amount: Option<u64>,
+    recipient: SuiAddress,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let arguments = vec![
+        builder.obj(kiosk)?,
+        builder.obj(ObjectArg::ImmOrOwnedObject(cap))?,
+        builder.pure(amount)?,
+    ];
+    let coin = call(
+        &mut builder,
+        KIOSK_MODULE_NAME,
+        ident_str!("withdraw"),
+        vec![],
+        arguments,
+    );
+    builder.transfer_arg(recipient, coin);
+    Ok(builder.finish())
+}
+
+/// Remove the rule `rule_type`, configured with `config_type`, from a transfer policy.
+pub fn remove_rule_ptb(
+    policy: ObjectArg,
+    cap: ObjectRef,
+    item_type: TypeTag,
+    rule_type: TypeTag,
+    config_type: TypeTag,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let arguments = vec![
+        builder.obj(policy)?,
+        builder.obj(ObjectArg::ImmOrOwnedObject(cap))?,
+    ];
+    call(
+        &mut builder,
+        TRANSFER_POLICY_MODULE_NAME,
+        ident_str!("remove_rule"),
+        vec![item_type, rule_type, config_type],
+        arguments,
+    );
+    Ok(builder.finish())
+}
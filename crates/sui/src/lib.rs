@@ -2,19 +2,33 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod bench;
+pub mod cli_manifest;
 pub mod client_commands;
 #[macro_use]
 pub mod client_ptb;
 mod clever_error_rendering;
+pub mod config_commands;
+pub mod deny_list_commands;
+pub mod display_commands;
 pub mod displays;
+pub mod error_codes;
 pub mod external_signer;
 pub mod fire_drill;
+pub mod gas_profile;
 pub mod genesis_ceremony;
 pub mod genesis_inspector;
+pub mod history_commands;
 pub mod keytool;
+pub mod kiosk_commands;
 pub mod mvr_resolver;
+pub mod package_commands;
+pub mod scenario;
+pub mod serve_stdio;
+pub mod stake_commands;
 pub mod sui_commands;
 pub mod trace_analysis_commands;
+pub mod upgrade_cap_commands;
 pub mod upgrade_compatibility;
 pub mod validator_commands;
 mod verifier_meter;
@@ -3,8 +3,8 @@
 
 use clap::*;
 use colored::Colorize;
+use sui::error_codes;
 use sui::sui_commands::SuiCommand;
-use sui_types::exit_main;
 use tracing::debug;
 
 // Define the `GIT_REVISION` and `VERSION` consts
@@ -21,11 +21,15 @@ bin_version::bin_version!();
 )]
 struct Args {
     #[clap(subcommand)]
-    command: SuiCommand,
+    command: Option<SuiCommand>,
 
     /// Display less output
     #[arg(short, long, global = true)]
     quiet: bool,
+
+    /// Explain what the given exit code of a failed command means
+    #[arg(long, value_name = "CODE", exclusive = true)]
+    explain: Option<i32>,
 }
 
 #[tokio::main]
@@ -34,6 +38,15 @@ async fn main() {
     colored::control::set_virtual_terminal(true).unwrap();
 
     let args = Args::parse();
+    if let Some(code) = args.explain {
+        println!("{}", error_codes::explain(code));
+        return;
+    }
+    let Some(command) = args.command else {
+        Args::command().print_help().unwrap();
+        std::process::exit(2);
+    };
+
     let mut builder = telemetry_subscribers::TelemetryConfig::new()
         .with_log_level("error")
         .with_env();
@@ -44,5 +57,12 @@ async fn main() {
 
     let _guard = builder.init();
     debug!("Sui CLI version: {VERSION}");
-    exit_main!(args.command.execute().await);
+    let result = command.execute().await;
+    if let Err(err) = &result {
+        println!("{}", format!("{err:?}").bold().red());
+        if let Some(code) = error_codes::ErrorCode::of(err) {
+            println!("{code}");
+        }
+        std::process::exit(error_codes::exit_code(&result));
+    }
 }
@@ -0,0 +1,245 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, bail};
+use clap::*;
+use move_binary_format::{
+    CompiledModule,
+    file_format::{AbilitySet, Visibility},
+    normalized::{self, RcIdentifier},
+};
+use move_disassembler::disassembler::Disassembler;
+use move_ir_types::location::Spanned;
+use serde::Serialize;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    move_package::MovePackage,
+};
+
+use crate::client_commands::SuiClientCommandResult;
+
+#[cfg(test)]
+#[path = "unit_tests/package_tests.rs"]
+mod package_tests;
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum PackageCommand {
+    /// Download an on-chain package and show its modules: public and entry functions, structs
+    /// with their abilities, and the package's linkage table.
+    #[clap(name = "inspect")]
+    Inspect {
+        /// ID of the package to inspect
+        #[clap(name = "package_id")]
+        package_id: ObjectID,
+        /// Only inspect these modules
+        #[clap(long, num_args(1..))]
+        module: Vec<String>,
+        /// Include the disassembled bytecode of each module
+        #[clap(long)]
+        disassemble: bool,
+    },
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageOutput {
+    pub package_id: ObjectID,
+    pub original_package_id: ObjectID,
+    pub version: SequenceNumber,
+    pub modules: Vec<PackageModuleOutput>,
+    pub linkage: Vec<PackageLinkageOutput>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageModuleOutput {
+    pub name: String,
+    pub functions: Vec<PackageFunctionOutput>,
+    pub structs: Vec<PackageStructOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disassembly: Option<String>,
+}
+
+/// A function that can be called from outside its package: `public`, `entry`, or both.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageFunctionOutput {
+    pub name: String,
+    pub is_public: bool,
+    pub is_entry: bool,
+    /// The ability constraints of each type parameter, e.g. `key + store`.
+    pub type_parameters: Vec<String>,
+    pub parameters: Vec<String>,
+    pub return_: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageStructOutput {
+    pub name: String,
+    pub abilities: Vec<String>,
+    pub type_parameters: Vec<String>,
+    pub fields: Vec<PackageFieldOutput>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageFieldOutput {
+    pub name: String,
+    pub type_: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageLinkageOutput {
+    pub original_id: ObjectID,
+    pub upgraded_id: ObjectID,
+    pub upgraded_version: SequenceNumber,
+}
+
+impl PackageCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            PackageCommand::Inspect {
+                package_id,
+                module,
+                disassemble,
+            } => {
+                let object = context
+                    .grpc_client()?
+                    .get_object(package_id)
+                    .await
+                    .with_context(|| format!("Failed to fetch package {package_id}"))?;
+                let Some(package) = object.data.try_as_package() else {
+                    bail!("Object {package_id} is not a package");
+                };
+                SuiClientCommandResult::Package(inspect_package(package, &module, disassemble)?)
+            }
+        })
+    }
+}
+
+/// Summarizes `package`, restricted to the modules named in `modules` if it is not empty.
+pub fn inspect_package(
+    package: &MovePackage,
+    modules: &[String],
+    disassemble: bool,
+) -> Result<PackageOutput, anyhow::Error> {
+    let module_map = package.serialized_module_map();
+    if let Some(missing) = modules.iter().find(|m| !module_map.contains_key(*m)) {
+        bail!("Package {} has no module {missing}", package.id());
+    }
+
+    let mut outputs = vec![];
+    for (name, bytes) in module_map {
+        if !modules.is_empty() && !modules.contains(name) {
+            continue;
+        }
+        // This is a read path, so it is fine to accept any supported binary version.
+        let module = CompiledModule::deserialize_with_defaults(bytes)
+            .with_context(|| format!("Failed to deserialize module {name}"))?;
+        outputs.push(inspect_module(&module, disassemble)?);
+    }
+
+    Ok(PackageOutput {
+        package_id: package.id(),
+        original_package_id: package.original_package_id(),
+        version: package.version(),
+        modules: outputs,
+        linkage: package
+            .linkage_table()
+            .iter()
+            .map(|(original_id, info)| PackageLinkageOutput {
+                original_id: *original_id,
+                upgraded_id: info.upgraded_id,
+                upgraded_version: info.upgraded_version,
+            })
+            .collect(),
+    })
+}
+
+fn inspect_module(
+    module: &CompiledModule,
+    disassemble: bool,
+) -> Result<PackageModuleOutput, anyhow::Error> {
+    let normalized = normalized::Module::<RcIdentifier>::new(
+        &mut normalized::RcPool::new(),
+        module,
+        /* include_code */ false,
+    );
+
+    let functions = normalized
+        .functions
+        .values()
+        .filter(|f| f.visibility == Visibility::Public || f.is_entry)
+        .map(|f| PackageFunctionOutput {
+            name: f.name.to_string(),
+            is_public: f.visibility == Visibility::Public,
+            is_entry: f.is_entry,
+            type_parameters: f
+                .type_parameters
+                .iter()
+                .map(|constraints| format_abilities(*constraints))
+                .collect(),
+            parameters: f.parameters.iter().map(|t| t.to_string()).collect(),
+            return_: f.return_.iter().map(|t| t.to_string()).collect(),
+        })
+        .collect();
+
+    let structs = normalized
+        .structs
+        .values()
+        .map(|s| PackageStructOutput {
+            name: s.name.to_string(),
+            abilities: s.abilities.into_iter().map(|a| a.to_string()).collect(),
+            type_parameters: s
+                .type_parameters
+                .iter()
+                .map(|tp| {
+                    let constraints = format_abilities(tp.constraints);
+                    if tp.is_phantom {
+                        format!("phantom {constraints}").trim_end().to_owned()
+                    } else {
+                        constraints
+                    }
+                })
+                .collect(),
+            fields: s
+                .fields
+                .0
+                .values()
+                .map(|f| PackageFieldOutput {
+                    name: f.name.to_string(),
+                    type_: f.type_.to_string(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let disassembly = if disassemble {
+        let disassembler = Disassembler::from_module(module, Spanned::unsafe_no_loc(()).loc)?;
+        Some(disassembler.disassemble()?)
+    } else {
+        None
+    };
+
+    Ok(PackageModuleOutput {
+        name: module.name().to_string(),
+        functions,
+        structs,
+        disassembly,
+    })
+}
+
+fn format_abilities(abilities: AbilitySet) -> String {
+    abilities
+        .into_iter()
+        .map(|a| a.to_string())
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
@@ -0,0 +1,712 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Run end-to-end scenarios described in YAML against a fresh local network.
+//!
+//! A scenario names a set of actors (funded addresses), the packages to publish, and a sequence
+//! of steps. Each step executes a PTB (in `sui client ptb` syntax) as one of the actors and checks
+//! its outcome against expectations on the transaction status, emitted events, object state and
+//! balances. Values produced by earlier steps are referred to as `${name}`:
+//!
+//! ```yaml
+//! actors: [alice, bob]
+//! packages:
+//!   - name: counter
+//!     path: ./counter
+//! steps:
+//!   - name: create a counter
+//!     sender: alice
+//!     ptb: --move-call ${counter}::counter::create
+//!     capture:
+//!       counter_id: { created: "${counter}::counter::Counter" }
+//!     expect:
+//!       objects:
+//!         - { id: "${counter_id}", owner: shared, fields: { value: "0" } }
+//! ```
+
+use crate::{
+    client_commands::{
+        GasDataArgs, PaymentArgs, PublishArgs, SuiClientCommandResult, SuiClientCommands,
+        TestPublishArgs, TxProcessingArgs, dry_run_or_execute_or_serialize, max_gas_budget,
+    },
+    client_ptb::{
+        error::build_error_reports,
+        ptb::{PTB, keystore_addresses},
+    },
+};
+use anyhow::{Context, anyhow, bail, ensure};
+use clap::*;
+use move_core_types::language_storage::StructTag;
+use move_package_alt_compilation::build_config::BuildConfig as MoveBuildConfig;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::LazyLock,
+};
+use sui_keys::keystore::AccountKeystore;
+use sui_rpc_api::client::ExecutedTransaction;
+use sui_sdk::wallet_context::WalletContext;
+use sui_swarm_config::genesis_config::{
+    AccountConfig, DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT,
+};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    effects::TransactionEffectsAPI,
+    gas_coin::GAS,
+    object::Owner,
+    transaction::{ProgrammableTransaction, TransactionKind},
+};
+use test_cluster::{TestCluster, TestClusterBuilder};
+
+static VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum ScenarioCommand {
+    /// Run scenario files, each against its own fresh local network. Fails if any step of any
+    /// scenario does not meet its expectations.
+    Run {
+        /// Paths to the scenario files
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+/// A scenario file.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Name of the scenario in reports. Defaults to the file name.
+    pub name: Option<String>,
+    /// Configuration of the local network.
+    #[serde(default)]
+    pub network: NetworkSpec,
+    /// Names of the actors. Each one gets a funded address, available as `${name}` and `@name`.
+    /// The first actor is the default sender.
+    pub actors: Vec<String>,
+    /// Packages to publish before the steps run, in order.
+    #[serde(default)]
+    pub packages: Vec<PackageSpec>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkSpec {
+    pub num_validators: Option<usize>,
+    pub epoch_duration_ms: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PackageSpec {
+    /// The package ID is available as `${name}` once published.
+    pub name: String,
+    /// Path to the package, relative to the scenario file.
+    pub path: PathBuf,
+    /// The actor publishing the package. Defaults to the first actor.
+    pub sender: Option<String>,
+    /// The build environment whose dependency addresses are used.
+    #[serde(default = "default_build_env")]
+    pub build_env: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    pub name: Option<String>,
+    /// The actor signing the transaction. Defaults to the first actor.
+    pub sender: Option<String>,
+    /// The commands of the PTB, as passed to `sui client ptb`.
+    pub ptb: String,
+    /// Variables to set from the effects of the transaction.
+    #[serde(default)]
+    pub capture: BTreeMap<String, Capture>,
+    #[serde(default)]
+    pub expect: Expect,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Capture {
+    /// The ID of the object of this type created by the transaction.
+    pub created: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Expect {
+    #[serde(default)]
+    pub status: ExpectedStatus,
+    /// A substring of the execution error, for failed transactions.
+    pub error: Option<String>,
+    /// Events that the transaction must emit, in any order.
+    #[serde(default)]
+    pub events: Vec<ExpectedEvent>,
+    /// The state of objects after the transaction.
+    #[serde(default)]
+    pub objects: Vec<ExpectedObject>,
+    /// The balances of actors after the transaction.
+    #[serde(default)]
+    pub balances: Vec<ExpectedBalance>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpectedStatus {
+    #[default]
+    Success,
+    Failure,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedEvent {
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Fields the event must have, compared as a subset of its JSON representation.
+    pub fields: Option<Value>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedObject {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    /// `shared`, `immutable`, an actor, or the address or ID of the owner.
+    pub owner: Option<String>,
+    /// Fields the object must have, compared as a subset of its JSON representation.
+    pub fields: Option<Value>,
+    /// Whether the object must no longer exist.
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedBalance {
+    pub actor: String,
+    /// Defaults to SUI.
+    pub coin_type: Option<String>,
+    pub equals: Option<u64>,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+fn default_build_env() -> String {
+    "testnet".to_string()
+}
+
+pub async fn run_scenario_command(cmd: ScenarioCommand) -> anyhow::Result<()> {
+    match cmd {
+        ScenarioCommand::Run { files } => {
+            let mut failed = vec![];
+            for file in &files {
+                if let Err(e) = run_scenario_file(file).await {
+                    eprintln!("{}: {e:#}", file.display());
+                    failed.push(file.display().to_string());
+                }
+            }
+            ensure!(
+                failed.is_empty(),
+                "{} of {} scenarios failed: {}",
+                failed.len(),
+                files.len(),
+                failed.join(", ")
+            );
+            Ok(())
+        }
+    }
+}
+
+async fn run_scenario_file(file: &Path) -> anyhow::Result<()> {
+    let contents = fs::read_to_string(file)
+        .with_context(|| format!("Cannot read scenario file {}", file.display()))?;
+    let scenario: Scenario = serde_yaml::from_str(&contents)
+        .with_context(|| format!("Invalid scenario file {}", file.display()))?;
+    scenario.validate()?;
+
+    let name = scenario.name.clone().unwrap_or_else(|| {
+        file.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+    let base_dir = file.parent().unwrap_or(Path::new("."));
+    println!("Running scenario {name}");
+    let mut runner = ScenarioRunner::start(&scenario).await?;
+    runner.publish_packages(&scenario, base_dir).await?;
+
+    for (i, step) in scenario.steps.iter().enumerate() {
+        let step_name = step
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("step {}", i + 1));
+        match runner.run_step(step).await {
+            Ok(()) => println!("[ PASS ] {name}::{step_name}"),
+            Err(e) => {
+                println!("[ FAIL ] {name}::{step_name}");
+                return Err(e.context(format!("Step '{step_name}' failed")));
+            }
+        }
+    }
+    println!("Scenario result: OK. Total steps: {}", scenario.steps.len());
+    Ok(())
+}
+
+impl Scenario {
+    /// Check that the scenario only refers to actors it declares.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            !self.actors.is_empty(),
+            "A scenario needs at least one actor"
+        );
+        for (i, actor) in self.actors.iter().enumerate() {
+            ensure!(
+                !self.actors[..i].contains(actor),
+                "Actor '{actor}' is declared twice"
+            );
+        }
+        let senders = self
+            .packages
+            .iter()
+            .filter_map(|p| p.sender.as_ref())
+            .chain(self.steps.iter().filter_map(|s| s.sender.as_ref()));
+        for sender in senders {
+            ensure!(
+                self.actors.contains(sender),
+                "Unknown actor '{sender}', expected one of: {}",
+                self.actors.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Replaces `${name}` with the value of variable `name`.
+pub fn substitute(input: &str, variables: &BTreeMap<String, String>) -> anyhow::Result<String> {
+    let mut unknown = None;
+    let output = VARIABLE.replace_all(input, |caps: &regex::Captures| {
+        let name = &caps[1];
+        variables.get(name).cloned().unwrap_or_else(|| {
+            unknown.get_or_insert_with(|| name.to_string());
+            String::new()
+        })
+    });
+    if let Some(name) = unknown {
+        bail!("Unknown variable '${{{name}}}'");
+    }
+    Ok(output.into_owned())
+}
+
+fn substitute_json(value: &Value, variables: &BTreeMap<String, String>) -> anyhow::Result<Value> {
+    Ok(match value {
+        Value::String(s) => Value::String(substitute(s, variables)?),
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|v| substitute_json(v, variables))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), substitute_json(v, variables)?)))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        v => v.clone(),
+    })
+}
+
+/// Whether `actual` contains `expected`: objects may have extra fields, and numbers match their
+/// string representation, since large integers are rendered as strings in JSON.
+pub fn json_contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected
+            .iter()
+            .all(|(k, e)| actual.get(k).is_some_and(|a| json_contains(a, e))),
+        (Value::Array(actual), Value::Array(expected)) => {
+            actual.len() == expected.len()
+                && actual
+                    .iter()
+                    .zip(expected)
+                    .all(|(a, e)| json_contains(a, e))
+        }
+        (Value::String(actual), Value::Number(expected)) => *actual == expected.to_string(),
+        (actual, expected) => actual == expected,
+    }
+}
+
+/// Whether `actual` is an instance of `expected`. Type parameters are only compared if
+/// `expected` has some.
+pub fn type_matches(actual: &StructTag, expected: &StructTag) -> bool {
+    actual.address == expected.address
+        && actual.module == expected.module
+        && actual.name == expected.name
+        && (expected.type_params.is_empty() || actual.type_params == expected.type_params)
+}
+
+struct ScenarioRunner {
+    cluster: TestCluster,
+    actors: BTreeMap<String, SuiAddress>,
+    default_actor: String,
+    variables: BTreeMap<String, String>,
+    // keeps the publication files of the packages alive until the scenario is over
+    pubfile_dir: tempfile::TempDir,
+}
+
+impl ScenarioRunner {
+    /// Start a local network with a funded address for each actor, aliased to the actor's name.
+    async fn start(scenario: &Scenario) -> anyhow::Result<Self> {
+        let account = AccountConfig {
+            address: None,
+            gas_amounts: vec![DEFAULT_GAS_AMOUNT; DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT],
+        };
+        let mut builder =
+            TestClusterBuilder::new().with_accounts(vec![account; scenario.actors.len()]);
+        if let Some(num_validators) = scenario.network.num_validators {
+            builder = builder.with_num_validators(num_validators);
+        }
+        if let Some(epoch_duration_ms) = scenario.network.epoch_duration_ms {
+            builder = builder.with_epoch_duration_ms(epoch_duration_ms);
+        }
+        let mut cluster = builder.build().await;
+
+        let addresses = cluster.get_addresses();
+        ensure!(
+            addresses.len() >= scenario.actors.len(),
+            "The local network has fewer accounts than actors"
+        );
+        let mut actors = BTreeMap::new();
+        let mut variables = BTreeMap::new();
+        let keystore = &mut cluster.wallet.config.keystore;
+        for (actor, address) in scenario.actors.iter().zip(addresses) {
+            let alias = keystore.get_alias(&address)?;
+            keystore.update_alias(&alias, Some(actor.as_str())).await?;
+            actors.insert(actor.clone(), address);
+            variables.insert(actor.clone(), address.to_string());
+        }
+
+        Ok(Self {
+            cluster,
+            actors,
+            default_actor: scenario.actors[0].clone(),
+            variables,
+            pubfile_dir: tempfile::tempdir()?,
+        })
+    }
+
+    fn wallet_as(&mut self, actor: Option<&String>) -> (&mut WalletContext, SuiAddress) {
+        let actor = actor.unwrap_or(&self.default_actor);
+        let sender = self.actors[actor];
+        let wallet = &mut self.cluster.wallet;
+        wallet.config.active_address = Some(sender);
+        (wallet, sender)
+    }
+
+    async fn publish_packages(
+        &mut self,
+        scenario: &Scenario,
+        base_dir: &Path,
+    ) -> anyhow::Result<()> {
+        for package in &scenario.packages {
+            ensure!(
+                !self.variables.contains_key(&package.name),
+                "Package name '{}' is already used",
+                package.name
+            );
+            let build_config = MoveBuildConfig {
+                default_flavor: Some(move_compiler::editions::Flavor::Sui),
+                environment: Some(package.build_env.clone()),
+                pubfile_path: Some(self.pubfile_dir.path().join("Pub.scenario.toml")),
+                ..Default::default()
+            };
+            let package_path = base_dir.join(&package.path);
+            let (context, _) = self.wallet_as(package.sender.as_ref());
+            let response = SuiClientCommands::TestPublish(TestPublishArgs {
+                publish_args: PublishArgs {
+                    package_path: package_path.clone(),
+                    build_config,
+                    skip_dependency_verification: true,
+                    verify_deps: false,
+                    with_unpublished_dependencies: false,
+                    payment: PaymentArgs { gas: vec![] },
+                    gas_data: GasDataArgs::default(),
+                    processing: TxProcessingArgs::default(),
+                },
+                publish_unpublished_deps: true,
+            })
+            .execute(context)
+            .await
+            .with_context(|| format!("Failed to publish {}", package_path.display()))?;
+
+            let SuiClientCommandResult::TransactionBlock(response) = response else {
+                bail!("Unexpected response when publishing {}", package.name);
+            };
+            ensure!(
+                response.effects.status().is_ok(),
+                "Publishing {} failed: {:?}",
+                package.name,
+                response.effects.status()
+            );
+            let package_id = response
+                .effects
+                .created()
+                .into_iter()
+                .find(|(_, owner)| matches!(owner, Owner::Immutable))
+                .map(|((id, _, _), _)| id)
+                .ok_or_else(|| anyhow!("No package created when publishing {}", package.name))?;
+            println!("Published {} at {package_id}", package.name);
+            self.variables
+                .insert(package.name.clone(), package_id.to_string());
+        }
+        Ok(())
+    }
+
+    async fn run_step(&mut self, step: &Step) -> anyhow::Result<()> {
+        let response = self.execute_ptb(step).await?;
+        let status = response.effects.status();
+        match (step.expect.status, status.is_ok()) {
+            (ExpectedStatus::Success, false) => bail!("Transaction failed: {status:?}"),
+            (ExpectedStatus::Failure, true) => bail!("Transaction succeeded, expected a failure"),
+            _ => (),
+        }
+        if let Some(error) = &step.expect.error {
+            let error = substitute(error, &self.variables)?;
+            let actual = format!("{status:?}");
+            ensure!(
+                actual.contains(&error),
+                "Expected an error containing '{error}', got: {actual}"
+            );
+        }
+
+        for (name, capture) in &step.capture {
+            let id = self.find_created(&response, &capture.created).await?;
+            self.variables.insert(name.clone(), id.to_string());
+        }
+        for event in &step.expect.events {
+            self.check_event(&response, event)?;
+        }
+        for object in &step.expect.objects {
+            self.check_object(object).await?;
+        }
+        for balance in &step.expect.balances {
+            self.check_balance(balance).await?;
+        }
+        Ok(())
+    }
+
+    async fn execute_ptb(&mut self, step: &Step) -> anyhow::Result<ExecutedTransaction> {
+        let source = substitute(&step.ptb, &self.variables)?;
+        let args =
+            shlex::split(&source).ok_or_else(|| anyhow!("Cannot split PTB into arguments"))?;
+        let (program, metadata) = PTB::parse_ptb_commands(args)
+            .map_err(|errors| anyhow!("Cannot parse PTB:\n{}", render_errors(&source, errors)))?;
+
+        let (context, sender) = self.wallet_as(step.sender.as_ref());
+        let client = context.grpc_client()?;
+        let starting_addresses = keystore_addresses(context);
+        let (ptb, _warnings) = PTB::build_ptb(
+            program,
+            starting_addresses,
+            client.clone(),
+            context,
+            Some(sender),
+        )
+        .await;
+        let ptb =
+            ptb.map_err(|errors| anyhow!("Cannot build PTB:\n{}", render_errors(&source, errors)))?;
+
+        let tx_kind = TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+            inputs: ptb.inputs,
+            commands: ptb.commands,
+        });
+        // the gas budget cannot be estimated for a transaction that fails, so use the maximum
+        let gas_budget = match metadata.gas_budget {
+            Some(budget) => Some(budget.value),
+            None if step.expect.status == ExpectedStatus::Failure => {
+                Some(max_gas_budget(&client).await?)
+            }
+            None => None,
+        };
+        let gas_data = GasDataArgs {
+            gas_budget,
+            gas_price: metadata.gas_price.map(|x| x.value),
+            gas_sponsor: None,
+            ..Default::default()
+        };
+        let response = dry_run_or_execute_or_serialize(
+            sender,
+            tx_kind,
+            context,
+            vec![],
+            gas_data,
+            TxProcessingArgs::default(),
+        )
+        .await?;
+        let SuiClientCommandResult::TransactionBlock(response) = response else {
+            bail!("Unexpected response when executing the PTB");
+        };
+        Ok(response)
+    }
+
+    async fn find_created(
+        &self,
+        response: &ExecutedTransaction,
+        type_: &str,
+    ) -> anyhow::Result<ObjectID> {
+        let expected = self.parse_type(type_)?;
+        let mut client = self.cluster.wallet.grpc_client()?;
+        for ((id, _, _), _) in response.effects.created() {
+            let object = client.get_object(id).await?;
+            if object
+                .struct_tag()
+                .is_some_and(|actual| type_matches(&actual, &expected))
+            {
+                return Ok(id);
+            }
+        }
+        bail!("The transaction created no object of type {expected}")
+    }
+
+    fn check_event(
+        &self,
+        response: &ExecutedTransaction,
+        expected: &ExpectedEvent,
+    ) -> anyhow::Result<()> {
+        let type_ = self.parse_type(&expected.type_)?;
+        let fields = expected
+            .fields
+            .as_ref()
+            .map(|f| substitute_json(f, &self.variables))
+            .transpose()?;
+        let events = response
+            .events
+            .as_ref()
+            .map(|e| e.data.as_slice())
+            .unwrap_or_default();
+        let found = events.iter().enumerate().any(|(i, event)| {
+            type_matches(&event.type_, &type_)
+                && fields.as_ref().is_none_or(|fields| {
+                    response
+                        .event_json
+                        .get(i)
+                        .and_then(Option::as_ref)
+                        .is_some_and(|json| json_contains(json, fields))
+                })
+        });
+        ensure!(found, "No matching event of type {type_} was emitted");
+        Ok(())
+    }
+
+    async fn check_object(&self, expected: &ExpectedObject) -> anyhow::Result<()> {
+        let id = ObjectID::from_str(&substitute(&expected.id, &self.variables)?)?;
+        let mut client = self.cluster.wallet.grpc_client()?;
+        let result = client.get_object_with_json(id).await;
+        if expected.deleted {
+            ensure!(result.is_err(), "Object {id} still exists");
+            return Ok(());
+        }
+        let (object, json) = result.with_context(|| format!("Cannot read object {id}"))?;
+
+        if let Some(type_) = &expected.type_ {
+            let type_ = self.parse_type(type_)?;
+            ensure!(
+                object
+                    .struct_tag()
+                    .is_some_and(|actual| type_matches(&actual, &type_)),
+                "Object {id} is not a {type_}"
+            );
+        }
+        if let Some(owner) = &expected.owner {
+            ensure!(
+                self.owner_matches(&object.owner, owner)?,
+                "Object {id} is owned by {}, expected {owner}",
+                object.owner
+            );
+        }
+        if let Some(fields) = &expected.fields {
+            let fields = substitute_json(fields, &self.variables)?;
+            let json = json.unwrap_or_default();
+            ensure!(
+                json_contains(&json, &fields),
+                "Object {id} does not match the expected fields. Actual: {json}"
+            );
+        }
+        Ok(())
+    }
+
+    async fn check_balance(&self, expected: &ExpectedBalance) -> anyhow::Result<()> {
+        let address = self
+            .actors
+            .get(&expected.actor)
+            .ok_or_else(|| anyhow!("Unknown actor '{}'", expected.actor))?;
+        let coin_type = match &expected.coin_type {
+            Some(coin_type) => self.parse_type(coin_type)?,
+            None => GAS::type_(),
+        };
+        let client = self.cluster.wallet.grpc_client()?;
+        let balance = client.get_balance(*address, &coin_type).await?.balance();
+        let actor = &expected.actor;
+        if let Some(equals) = expected.equals {
+            ensure!(
+                balance == equals,
+                "{actor} has a balance of {balance}, expected {equals}"
+            );
+        }
+        if let Some(min) = expected.min {
+            ensure!(
+                balance >= min,
+                "{actor} has a balance of {balance}, expected at least {min}"
+            );
+        }
+        if let Some(max) = expected.max {
+            ensure!(
+                balance <= max,
+                "{actor} has a balance of {balance}, expected at most {max}"
+            );
+        }
+        Ok(())
+    }
+
+    fn owner_matches(&self, owner: &Owner, expected: &str) -> anyhow::Result<bool> {
+        Ok(match expected {
+            "shared" => matches!(owner, Owner::Shared { .. }),
+            "immutable" => matches!(owner, Owner::Immutable),
+            _ => {
+                let address = match self.actors.get(expected) {
+                    Some(address) => *address,
+                    None => SuiAddress::from_str(&substitute(expected, &self.variables)?)?,
+                };
+                match owner {
+                    Owner::AddressOwner(a) | Owner::ObjectOwner(a) => *a == address,
+                    Owner::ConsensusAddressOwner { owner, .. } => *owner == address,
+                    _ => false,
+                }
+            }
+        })
+    }
+
+    fn parse_type(&self, type_: &str) -> anyhow::Result<StructTag> {
+        let type_ = substitute(type_, &self.variables)?;
+        StructTag::from_str(&type_).with_context(|| format!("Invalid type '{type_}'"))
+    }
+}
+
+fn render_errors(source: &str, errors: Vec<crate::client_ptb::error::PTBError>) -> String {
+    build_error_reports(source, errors)
+        .iter()
+        .map(|report| format!("{report:?}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+#[path = "unit_tests/scenario_tests.rs"]
+mod scenario_tests;
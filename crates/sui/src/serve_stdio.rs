@@ -0,0 +1,253 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `sui serve-stdio`: CLI operations as JSON-RPC 2.0 over stdin and stdout.
+//!
+//! Requests and responses are newline-delimited JSON objects. One wallet context, with its
+//! clients and caches, serves every request, so that editor extensions and automation tools do not
+//! pay for starting the CLI on each operation. Only responses are written to stdout: the commands
+//! behind the methods print as they do on the command line, so while serving, anything printed to
+//! stdout is redirected to stderr. The methods are:
+//!
+//! - `build`, with params `{ path?, withUnpublishedDependencies? }`, returning the package as
+//!   `sui move build --dump-bytecode-as-base64` prints it. Relative paths are resolved against the
+//!   directory the server was started in.
+//! - `sign`, with params `{ address, txBytes }`, returning `{ signature }`: the Base64 signature
+//!   of the Base64 BCS transaction data by the key of an address or alias.
+//! - `submit`, with params `{ txBytes, signatures }`, executing a signed transaction like
+//!   `sui client execute-signed-tx`.
+//! - `client`, with params `{ args }`, running `sui client <args...>` and returning its JSON
+//!   output.
+//!
+//! Failed operations are reported with error code -32000, and the exit code the CLI would have
+//! failed with (see `sui --explain`) as `data.exitCode`.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use clap::Parser;
+use fastcrypto::encoding::{Base64, Encoding};
+use jsonrpc::types::{JsonRpcResult, RemoteError, Request, Response, TwoPointZero};
+use move_package_alt_compilation::build_config::BuildConfig;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use shared_crypto::intent::Intent;
+use sui_keys::key_identity::KeyIdentity;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::crypto::EncodeDecodeBase64;
+use sui_types::transaction::TransactionData;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::client_commands::SuiClientCommands;
+use crate::error_codes::{ErrorCode, UNCLASSIFIED_EXIT_CODE};
+use crate::sui_commands::dump_package_as_base64;
+
+const PARSE_ERROR: i32 = -32700;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const OPERATION_FAILED: i32 = -32000;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildParams {
+    path: Option<String>,
+    #[serde(default)]
+    with_unpublished_dependencies: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignParams {
+    address: String,
+    tx_bytes: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubmitParams {
+    tx_bytes: String,
+    signatures: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ClientParams {
+    args: Vec<String>,
+}
+
+/// The arguments of `sui client`, without the binary name.
+#[derive(Parser)]
+#[clap(no_binary_name = true)]
+struct ClientArgs {
+    #[clap(subcommand)]
+    cmd: SuiClientCommands,
+}
+
+/// Answer the requests read from stdin on stdout, until stdin is closed. Output printed by the
+/// commands that serve the requests goes to stderr instead, so that it does not end up between
+/// the responses.
+pub async fn serve_stdio(context: &mut WalletContext) -> Result<(), anyhow::Error> {
+    let output = redirect_stdout_to_stderr()?;
+    serve(context, tokio::io::stdin(), output).await
+}
+
+/// Point the process's stdout at stderr, and return a handle to the original stdout.
+#[cfg(unix)]
+fn redirect_stdout_to_stderr() -> Result<tokio::fs::File, anyhow::Error> {
+    use std::io::Write;
+    use std::os::fd::AsFd;
+
+    let mut stdout = std::io::stdout().lock();
+    stdout.flush()?;
+    let original = stdout
+        .as_fd()
+        .try_clone_to_owned()
+        .context("Failed to duplicate stdout")?;
+    // SAFETY: Both are descriptors of standard streams that stay open for the life of the
+    // process, and the stdout lock keeps anything from writing to it while it is replaced.
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        return Err(std::io::Error::last_os_error()).context("Failed to redirect stdout");
+    }
+    Ok(tokio::fs::File::from_std(std::fs::File::from(original)))
+}
+
+/// Without a way to redirect stdout, output printed by commands is interleaved with responses.
+#[cfg(not(unix))]
+fn redirect_stdout_to_stderr() -> Result<tokio::io::Stdout, anyhow::Error> {
+    Ok(tokio::io::stdout())
+}
+
+/// Answer the requests read from `input` on `output`, until `input` is closed.
+pub async fn serve(
+    context: &mut WalletContext,
+    input: impl AsyncRead + Unpin,
+    mut output: impl AsyncWrite + Unpin,
+) -> Result<(), anyhow::Error> {
+    let root = std::env::current_dir().context("Failed to get the current directory")?;
+    let mut lines = BufReader::new(input).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request<Value>>(&line) {
+            Ok(request) => {
+                let result = match handle(context, &root, &request.method, request.params).await {
+                    Ok(result) => JsonRpcResult::Ok { result },
+                    Err(error) => JsonRpcResult::Err { error },
+                };
+                serde_json::to_value(Response {
+                    jsonrpc: TwoPointZero,
+                    id: request.id,
+                    result,
+                })?
+            }
+            // The ID of a request that does not parse is unknown.
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": RemoteError {
+                    code: PARSE_ERROR,
+                    message: format!("Invalid request: {e}"),
+                    data: None,
+                },
+            }),
+        };
+
+        let mut bytes = serde_json::to_vec(&response)?;
+        bytes.push(b'\n');
+        output.write_all(&bytes).await?;
+        output.flush().await?;
+    }
+
+    Ok(())
+}
+
+async fn handle(
+    context: &mut WalletContext,
+    root: &Path,
+    method: &str,
+    params: Value,
+) -> Result<Value, RemoteError> {
+    match method {
+        "build" => {
+            let params: BuildParams = parse_params(params)?;
+            let path = params.path.map(|path| root.join(path));
+            dump_package_as_base64(
+                path.as_deref(),
+                BuildConfig::default(),
+                params.with_unpublished_dependencies,
+                false,
+                context,
+            )
+            .await
+            .map_err(failed)
+        }
+
+        "sign" => {
+            let params: SignParams = parse_params(params)?;
+            let key = KeyIdentity::from_str(&params.address)
+                .map_err(|e| invalid_params(format!("Invalid address: {e}")))?;
+            let tx_data: TransactionData = Base64::decode(&params.tx_bytes)
+                .ok()
+                .and_then(|bytes| bcs::from_bytes(&bytes).ok())
+                .ok_or_else(|| {
+                    invalid_params("Invalid txBytes, expected Base64 BCS transaction data")
+                })?;
+            let signature = context
+                .sign_secure(&key, &tx_data, Intent::sui_transaction())
+                .await
+                .map_err(failed)?;
+            Ok(json!({ "signature": signature.encode_base64() }))
+        }
+
+        "submit" => {
+            let params: SubmitParams = parse_params(params)?;
+            let result = SuiClientCommands::ExecuteSignedTx {
+                tx_bytes: params.tx_bytes,
+                signatures: params.signatures,
+            }
+            .execute(context)
+            .await
+            .map_err(failed)?;
+            serde_json::to_value(result).map_err(|e| failed(e.into()))
+        }
+
+        "client" => {
+            let params: ClientParams = parse_params(params)?;
+            let ClientArgs { cmd } = ClientArgs::try_parse_from(params.args)
+                .map_err(|e| invalid_params(e.to_string()))?;
+            let result = cmd.execute(context).await.map_err(failed)?;
+            serde_json::to_value(result).map_err(|e| failed(e.into()))
+        }
+
+        _ => Err(RemoteError {
+            code: METHOD_NOT_FOUND,
+            message: format!("Unknown method: {method}"),
+            data: None,
+        }),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RemoteError> {
+    serde_json::from_value(params).map_err(|e| invalid_params(e.to_string()))
+}
+
+fn invalid_params(message: impl Into<String>) -> RemoteError {
+    RemoteError {
+        code: INVALID_PARAMS,
+        message: message.into(),
+        data: None,
+    }
+}
+
+fn failed(err: anyhow::Error) -> RemoteError {
+    let exit_code = ErrorCode::of(&err).map_or(UNCLASSIFIED_EXIT_CODE, ErrorCode::exit_code);
+    RemoteError {
+        code: OPERATION_FAILED,
+        message: format!("{err:#}"),
+        data: Some(json!({ "exitCode": exit_code })),
+    }
+}
@@ -0,0 +1,551 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use anyhow::{bail, ensure};
+use clap::*;
+use move_core_types::ident_str;
+use serde::{Deserialize, Serialize};
+use sui_keys::key_identity::KeyIdentity;
+use sui_sdk::wallet_context::WalletContext;
+use sui_types::{
+    SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID,
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    gas_coin::GAS,
+    governance::{ADD_STAKE_FUN_NAME, WITHDRAW_STAKE_FUN_NAME},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    sui_system_state::SUI_SYSTEM_MODULE_NAME,
+    transaction::{
+        Argument, CallArg, Command, ObjectArg, ProgrammableTransaction, TransactionKind,
+    },
+};
+
+use crate::client_commands::{
+    GasDataArgs, PaymentArgs, SuiClientCommandResult, TxProcessingArgs,
+    dry_run_or_execute_or_serialize,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/stake_tests.rs"]
+mod stake_tests;
+
+/// Smallest amount, in MIST, that can be staked with a validator (1 SUI).
+pub const MIN_STAKE_AMOUNT: u64 = 1_000_000_000;
+
+const DEFAULT_COMPOUND_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum StakeCommand {
+    /// Stake SUI with a validator. The stake is split off the gas coin.
+    #[clap(name = "add")]
+    Add {
+        /// Address of the validator to stake with
+        #[clap(long)]
+        validator: SuiAddress,
+        /// Amount to stake, in MIST. Must be at least 1 SUI.
+        #[clap(long)]
+        amount: u64,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Withdraw stakes, including their rewards, back to their owner as SUI.
+    #[clap(name = "withdraw")]
+    Withdraw {
+        /// IDs of the StakedSui objects to withdraw
+        #[clap(long, num_args(1..), required = true)]
+        staked_sui_ids: Vec<ObjectID>,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// List the stakes owned by an address, along with their estimated rewards.
+    #[clap(name = "list")]
+    List {
+        /// Address (or its alias) owning the stakes. Defaults to the active address.
+        #[clap(name = "owner_address")]
+        address: Option<KeyIdentity>,
+    },
+
+    /// Withdraw stakes that accrued enough rewards and stake the proceeds again, split across
+    /// the validators of a policy file, in a single transaction.
+    ///
+    /// The policy is a YAML file such as:
+    ///
+    ///   validators:
+    ///     - address: 0x...
+    ///       weight: 2
+    ///     - address: 0x...
+    ///   min-rewards: 1000000000
+    ///   interval-secs: 86400
+    ///
+    /// Restaked SUI only starts earning rewards again from the next epoch.
+    #[clap(name = "auto-compound")]
+    AutoCompound {
+        /// Path to the compounding policy file
+        #[clap(long)]
+        policy: PathBuf,
+        /// Keep running and compound every `interval-secs` of the policy, instead of once.
+        #[clap(long)]
+        daemon: bool,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+}
+
+/// Which stakes `sui client stake auto-compound` restakes, and where to.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompoundPolicy {
+    /// Validators to stake the withdrawn SUI with.
+    pub validators: Vec<ValidatorWeight>,
+    /// Only stakes whose estimated rewards are at least this many MIST are withdrawn.
+    #[serde(default)]
+    pub min_rewards: u64,
+    /// Seconds between two compounding rounds in daemon mode.
+    #[serde(default = "default_compound_interval_secs")]
+    pub interval_secs: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ValidatorWeight {
+    pub address: SuiAddress,
+    /// Share of the withdrawn SUI staked with this validator, relative to the other weights.
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+}
+
+fn default_compound_interval_secs() -> u64 {
+    DEFAULT_COMPOUND_INTERVAL_SECS
+}
+
+fn default_weight() -> u64 {
+    1
+}
+
+impl CompoundPolicy {
+    pub fn load(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let policy: Self = serde_yaml::from_slice(&fs::read(path)?)?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        ensure!(
+            !self.validators.is_empty(),
+            "Compounding policy must list at least one validator"
+        );
+        ensure!(
+            self.validators.iter().all(|v| v.weight > 0),
+            "Validator weights in the compounding policy must be positive"
+        );
+        ensure!(
+            self.interval_secs > 0,
+            "Compounding interval must be positive"
+        );
+        Ok(())
+    }
+
+    /// Split `total` MIST across the policy's validators according to their weights. Rounding
+    /// leftovers go to the last validator.
+    pub fn allocate(&self, total: u64) -> Result<Vec<(SuiAddress, u64)>, anyhow::Error> {
+        let total_weight: u128 = self.validators.iter().map(|v| v.weight as u128).sum();
+        let mut remaining = total;
+        let mut allocations = Vec::with_capacity(self.validators.len());
+        for (i, validator) in self.validators.iter().enumerate() {
+            let amount = if i + 1 == self.validators.len() {
+                remaining
+            } else {
+                (total as u128 * validator.weight as u128 / total_weight) as u64
+            };
+            if amount < MIN_STAKE_AMOUNT {
+                bail!(
+                    "Staking {amount} MIST with {} is below the minimum stake of {MIN_STAKE_AMOUNT} MIST",
+                    validator.address
+                );
+            }
+            remaining -= amount;
+            allocations.push((validator.address, amount));
+        }
+        Ok(allocations)
+    }
+}
+
+/// A stake owned by an address, as shown by `sui client stake list`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeOutput {
+    pub staked_sui_id: ObjectID,
+    pub validator_address: SuiAddress,
+    pub principal: u64,
+    pub estimated_reward: u64,
+    pub activation_epoch: u64,
+    /// Whether the stake is already earning rewards, i.e. its activation epoch has started.
+    pub active: bool,
+}
+
+/// The outcome of a `sui client stake auto-compound` round.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompoundOutput {
+    pub sender: SuiAddress,
+    pub min_rewards: u64,
+    /// Stakes withdrawn in this round, empty if none had enough rewards.
+    pub staked_sui_ids: Vec<ObjectID>,
+    /// Principal and rewards of the withdrawn stakes, in MIST.
+    pub total: u64,
+    pub allocations: Vec<CompoundAllocation>,
+    /// Result of the compounding transaction, if any stakes were withdrawn.
+    #[serde(skip)]
+    pub transaction: Option<Box<SuiClientCommandResult>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompoundAllocation {
+    pub validator_address: SuiAddress,
+    pub amount: u64,
+}
+
+impl StakeCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        Ok(match self {
+            StakeCommand::Add {
+                validator,
+                amount,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                ensure!(
+                    amount >= MIN_STAKE_AMOUNT,
+                    "Stake amount must be at least {MIN_STAKE_AMOUNT} MIST"
+                );
+                let signer = context.active_address()?;
+                let client = context.grpc_client()?;
+                let tx_kind =
+                    TransactionKind::ProgrammableTransaction(add_stake_ptb(validator, amount)?);
+                let gas_payment = client
+                    .transaction_builder()
+                    .input_refs(&payment.gas)
+                    .await?;
+                dry_run_or_execute_or_serialize(
+                    signer,
+                    tx_kind,
+                    context,
+                    gas_payment,
+                    gas_data,
+                    processing,
+                )
+                .await?
+            }
+            StakeCommand::Withdraw {
+                staked_sui_ids,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let signer = context.get_object_owner(&staked_sui_ids[0]).await?;
+                let staked_sui = context.get_object_refs(&staked_sui_ids).await?;
+                let client = context.grpc_client()?;
+                let tx_kind =
+                    TransactionKind::ProgrammableTransaction(withdraw_stake_ptb(staked_sui)?);
+                let gas_payment = client
+                    .transaction_builder()
+                    .input_refs(&payment.gas)
+                    .await?;
+                dry_run_or_execute_or_serialize(
+                    signer,
+                    tx_kind,
+                    context,
+                    gas_payment,
+                    gas_data,
+                    processing,
+                )
+                .await?
+            }
+            StakeCommand::List { address } => {
+                let address = context.get_identity_address(address)?;
+                SuiClientCommandResult::Stakes(list_stakes(context, address).await?)
+            }
+            StakeCommand::AutoCompound {
+                policy,
+                daemon,
+                gas_data,
+                processing,
+            } => {
+                let policy = CompoundPolicy::load(&policy)?;
+                if daemon {
+                    compound_daemon(context, &policy, gas_data, processing, false).await;
+                    SuiClientCommandResult::NoOutput
+                } else {
+                    SuiClientCommandResult::Compound(
+                        compound_once(context, &policy, gas_data, processing).await?,
+                    )
+                }
+            }
+        })
+    }
+
+    /// Whether the command keeps running instead of returning a result.
+    pub fn is_daemon(&self) -> bool {
+        matches!(self, StakeCommand::AutoCompound { daemon: true, .. })
+    }
+
+    /// Run `auto-compound --daemon`, printing the result of every round as JSON if `json` is
+    /// set. Only returns if the policy cannot be loaded.
+    pub async fn run_daemon(
+        self,
+        context: &mut WalletContext,
+        json: bool,
+    ) -> Result<(), anyhow::Error> {
+        let StakeCommand::AutoCompound {
+            policy,
+            daemon: true,
+            gas_data,
+            processing,
+        } = self
+        else {
+            bail!("Only `auto-compound --daemon` runs as a daemon");
+        };
+        let _ = context.cache_chain_id().await?;
+        let policy = CompoundPolicy::load(&policy)?;
+        compound_daemon(context, &policy, gas_data, processing, json).await;
+        Ok(())
+    }
+}
+
+/// Compound every `interval-secs` of the policy, forever. A failed round is reported and
+/// retried at the next interval.
+async fn compound_daemon(
+    context: &mut WalletContext,
+    policy: &CompoundPolicy,
+    gas_data: GasDataArgs,
+    processing: TxProcessingArgs,
+    json: bool,
+) {
+    loop {
+        match compound_once(context, policy, gas_data.clone(), processing.clone()).await {
+            Ok(output) => SuiClientCommandResult::Compound(output).print(!json),
+            Err(e) => eprintln!("Compounding round failed: {e}"),
+        }
+        tokio::time::sleep(Duration::from_secs(policy.interval_secs)).await;
+    }
+}
+
+async fn list_stakes(
+    context: &WalletContext,
+    address: SuiAddress,
+) -> Result<Vec<StakeOutput>, anyhow::Error> {
+    let client = context.grpc_client()?;
+    let epoch = client.get_current_epoch().await?;
+    Ok(client
+        .list_delegated_stake(address)
+        .await?
+        .into_iter()
+        .map(|stake| StakeOutput {
+            staked_sui_id: stake.staked_sui_id.into(),
+            validator_address: stake.validator_address.into(),
+            principal: stake.principal,
+            estimated_reward: stake.rewards,
+            activation_epoch: stake.activation_epoch,
+            active: stake.activation_epoch <= epoch,
+        })
+        .collect())
+}
+
+/// Run a single compounding round: withdraw every active stake of the sender whose rewards
+/// reached the policy's threshold and restake the proceeds with the policy's validators.
+async fn compound_once(
+    context: &mut WalletContext,
+    policy: &CompoundPolicy,
+    gas_data: GasDataArgs,
+    processing: TxProcessingArgs,
+) -> Result<CompoundOutput, anyhow::Error> {
+    let signer = match processing.sender {
+        Some(sender) => sender,
+        None => context.active_address()?,
+    };
+    let stakes: Vec<_> = list_stakes(context, signer)
+        .await?
+        .into_iter()
+        .filter(|s| s.active && s.estimated_reward >= policy.min_rewards)
+        .collect();
+    let mut output = CompoundOutput {
+        sender: signer,
+        min_rewards: policy.min_rewards,
+        staked_sui_ids: stakes.iter().map(|s| s.staked_sui_id).collect(),
+        total: 0,
+        allocations: vec![],
+        transaction: None,
+    };
+    if stakes.is_empty() {
+        return Ok(output);
+    }
+
+    output.total = stakes
+        .iter()
+        .map(|s| s.principal + s.estimated_reward)
+        .sum::<u64>();
+    let allocations = policy.allocate(output.total)?;
+    let staked_sui = context.get_object_refs(&output.staked_sui_ids).await?;
+    let tx_kind = TransactionKind::ProgrammableTransaction(compound_ptb(staked_sui, &allocations)?);
+    output.allocations = allocations
+        .into_iter()
+        .map(|(validator_address, amount)| CompoundAllocation {
+            validator_address,
+            amount,
+        })
+        .collect();
+    output.transaction = Some(Box::new(
+        dry_run_or_execute_or_serialize(signer, tx_kind, context, vec![], gas_data, processing)
+            .await?,
+    ));
+    Ok(output)
+}
+
+fn call_add_stake(
+    builder: &mut ProgrammableTransactionBuilder,
+    system: Argument,
+    coin: Argument,
+    validator: SuiAddress,
+) -> Result<(), anyhow::Error> {
+    let validator = builder.pure(validator)?;
+    builder.programmable_move_call(
+        SUI_SYSTEM_PACKAGE_ID,
+        SUI_SYSTEM_MODULE_NAME.to_owned(),
+        ADD_STAKE_FUN_NAME.to_owned(),
+        vec![],
+        vec![system, coin, validator],
+    );
+    Ok(())
+}
+
+/// Split `amount` off the gas coin and stake it with `validator`.
+pub fn add_stake_ptb(
+    validator: SuiAddress,
+    amount: u64,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let system = builder.input(CallArg::SUI_SYSTEM_MUT)?;
+    let amount = builder.pure(amount)?;
+    let Argument::Result(split) =
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]))
+    else {
+        unreachable!("commands always produce a result");
+    };
+    call_add_stake(
+        &mut builder,
+        system,
+        Argument::NestedResult(split, 0),
+        validator,
+    )?;
+    Ok(builder.finish())
+}
+
+/// Withdraw all of `staked_sui` to the sender.
+pub fn withdraw_stake_ptb(
+    staked_sui: Vec<ObjectRef>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let system = builder.input(CallArg::SUI_SYSTEM_MUT)?;
+    for staked_sui in staked_sui {
+        let staked_sui = builder.obj(ObjectArg::ImmOrOwnedObject(staked_sui))?;
+        builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            WITHDRAW_STAKE_FUN_NAME.to_owned(),
+            vec![],
+            vec![system, staked_sui],
+        );
+    }
+    Ok(builder.finish())
+}
+
+/// Withdraw all of `staked_sui` into a single coin and stake it again according to
+/// `allocations`. The last allocation receives whatever is left of the coin, so that rewards
+/// that accrued after the allocation was computed are not left behind.
+pub fn compound_ptb(
+    staked_sui: Vec<ObjectRef>,
+    allocations: &[(SuiAddress, u64)],
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    ensure!(!staked_sui.is_empty(), "No stakes to compound");
+    let Some(((last_validator, _), allocations)) = allocations.split_last() else {
+        bail!("No validators to stake with");
+    };
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let system = builder.input(CallArg::SUI_SYSTEM_MUT)?;
+    let mut balances = vec![];
+    for staked_sui in staked_sui {
+        let staked_sui = builder.obj(ObjectArg::ImmOrOwnedObject(staked_sui))?;
+        balances.push(builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            ident_str!("request_withdraw_stake_non_entry").to_owned(),
+            vec![],
+            vec![system, staked_sui],
+        ));
+    }
+    let balance = balances[0];
+    for other in &balances[1..] {
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            ident_str!("balance").to_owned(),
+            ident_str!("join").to_owned(),
+            vec![GAS::type_tag()],
+            vec![balance, *other],
+        );
+    }
+    let coin = builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        ident_str!("coin").to_owned(),
+        ident_str!("from_balance").to_owned(),
+        vec![GAS::type_tag()],
+        vec![balance],
+    );
+
+    if !allocations.is_empty() {
+        let amounts = allocations
+            .iter()
+            .map(|(_, amount)| builder.pure(*amount))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Argument::Result(split) = builder.command(Command::SplitCoins(coin, amounts)) else {
+            unreachable!("commands always produce a result");
+        };
+        for (i, (validator, _)) in allocations.iter().enumerate() {
+            call_add_stake(
+                &mut builder,
+                system,
+                Argument::NestedResult(split, i as u16),
+                *validator,
+            )?;
+        }
+    }
+    call_add_stake(&mut builder, system, coin, *last_validator)?;
+    Ok(builder.finish())
+}
@@ -20,6 +20,7 @@ use futures::future;
 use move_analyzer::analyzer;
 use move_command_line_common::files::MOVE_COMPILED_EXTENSION;
 use move_compiler::editions::Flavor;
+use move_package_alt::SourcePackageLayout;
 use move_package_alt_compilation::build_config::BuildConfig;
 use mysten_common::{ZipDebugEqIteratorExt, tempdir};
 use prometheus::Registry;
@@ -30,7 +31,7 @@ use sui_bridge::config::BridgeCommitteeConfig;
 use sui_bridge::metrics::BridgeMetrics;
 use sui_bridge::sui_client::SuiBridgeClient;
 use sui_bridge::sui_transaction_builder::build_committee_register_transaction;
-use sui_config::node::Genesis;
+use sui_config::node::{CommitLatencyConfig, Genesis};
 use sui_config::p2p::SeedPeer;
 use sui_config::{
     Config, FULL_NODE_DB_PATH, NodeConfig, PersistedConfig, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG,
@@ -87,14 +88,19 @@ use tokio::time::interval;
 use tracing::info;
 use url::Url;
 
+use crate::bench::BenchArgs;
+use crate::cli_manifest::command_manifest;
 use crate::client_commands::{
     SuiClientCommands, USER_AGENT, check_for_unpublished_deps,
     load_root_pkg_for_ephemeral_publish_or_upgrade, load_root_pkg_for_publish_upgrade,
     pkg_tree_shake,
 };
+use crate::error_codes::{ErrorCode, ErrorCodeExt};
 use crate::fire_drill::{FireDrill, run_fire_drill};
 use crate::genesis_ceremony::{Ceremony, run};
 use crate::keytool::KeyToolCommand;
+use crate::scenario::{ScenarioCommand, run_scenario_command};
+use crate::serve_stdio;
 use crate::trace_analysis_commands::AnalyzeTraceCommand;
 use crate::validator_commands::SuiValidatorCommand;
 
@@ -179,6 +185,10 @@ pub struct SuiEnvConfig {
     /// Create a new sui config without prompting if none exists
     #[clap(short = 'y', long = "yes")]
     accept_defaults: bool,
+    /// Refuse to sign any transaction or message, for use on shared machines that should only
+    /// query the network.
+    #[clap(long = "read-only", global = true)]
+    read_only: bool,
 }
 
 impl SuiEnvConfig {
@@ -187,6 +197,7 @@ impl SuiEnvConfig {
             config,
             env,
             accept_defaults: false,
+            read_only: false,
         }
     }
 }
@@ -274,6 +285,15 @@ pub enum SuiCommand {
         /// genesis with the desired number of validators.
         #[clap(long)]
         committee_size: Option<usize>,
+        /// Delay the handling of every consensus commit by the validators, so that transactions
+        /// take about as long to finalize as on a real network. Localnet commits are otherwise
+        /// handled almost instantly.
+        #[clap(long, value_name = "MS")]
+        consensus_latency_ms: Option<u64>,
+        /// Add a random delay of up to this many milliseconds to each consensus commit, on top of
+        /// `--consensus-latency-ms`. The delays are the same across runs.
+        #[clap(long, value_name = "MS", requires = "consensus_latency_ms")]
+        consensus_jitter_ms: Option<u64>,
     },
     #[clap(name = "network")]
     Network {
@@ -402,6 +422,36 @@ pub enum SuiCommand {
         fire_drill: FireDrill,
     },
 
+    /// Run end-to-end test scenarios described in YAML against a fresh local network
+    #[clap(name = "scenario")]
+    Scenario {
+        #[clap(subcommand)]
+        cmd: ScenarioCommand,
+    },
+
+    /// Drive load against a local or remote network and report throughput, latency and the
+    /// congestion seen by validators
+    #[clap(name = "bench")]
+    Bench {
+        #[clap(flatten)]
+        config: SuiEnvConfig,
+
+        #[clap(flatten)]
+        args: BenchArgs,
+
+        /// Output the report as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Serve CLI operations (build, sign, submit and client commands) as JSON-RPC over stdin and
+    /// stdout, reusing one wallet context across requests
+    #[clap(name = "serve-stdio")]
+    ServeStdio {
+        #[clap(flatten)]
+        config: SuiEnvConfig,
+    },
+
     /// Invoke Sui's move-analyzer via CLI
     #[clap(name = "analyzer", hide = true)]
     Analyzer,
@@ -436,13 +486,22 @@ pub enum SuiCommand {
         node: Option<String>,
     },
 
-    /// Generate shell completion scripts for CLI
+    /// Generate shell completion scripts for CLI, e.g. `sui completion zsh > _sui`. The shell is
+    /// detected from the environment if not provided.
     #[clap(name = "completion")]
     Completion {
-        /// If provided, outputs the completion file for given shell
-        #[arg(long = "generate", value_enum)]
-        generator: clap_complete::Shell,
+        /// The shell to output the completion script for
+        #[arg(value_enum, conflicts_with = "generator")]
+        shell: Option<clap_complete::Shell>,
+        /// Same as the positional shell argument, kept for backwards compatibility
+        #[arg(long = "generate", value_enum, hide = true)]
+        generator: Option<clap_complete::Shell>,
     },
+
+    /// Print the full command, subcommand and flag tree of the CLI as JSON, for documentation
+    /// tooling and wrapper generators.
+    #[clap(name = "__manifest", hide = true)]
+    Manifest,
 }
 
 impl SuiCommand {
@@ -481,7 +540,14 @@ impl SuiCommand {
                 no_full_node,
                 epoch_duration_ms,
                 committee_size,
+                consensus_latency_ms,
+                consensus_jitter_ms,
             } => {
+                let commit_latency = consensus_latency_ms.map(|latency_ms| CommitLatencyConfig {
+                    latency_ms,
+                    jitter_ms: consensus_jitter_ms.unwrap_or(0),
+                    seed: 0,
+                });
                 start(
                     config_dir.clone(),
                     with_faucet,
@@ -492,6 +558,7 @@ impl SuiCommand {
                     data_ingestion_dir,
                     no_full_node,
                     committee_size,
+                    commit_latency,
                 )
                 .await?;
 
@@ -550,7 +617,13 @@ impl SuiCommand {
             SuiCommand::Client { config, cmd, json } => {
                 if let Some(cmd) = cmd {
                     let mut context = get_wallet_context(&config).await?;
-                    cmd.execute(&mut context).await?.print(!json);
+                    match cmd {
+                        // Prints the result of every round, as it never returns one.
+                        SuiClientCommands::Stake { cmd } if cmd.is_daemon() => {
+                            cmd.run_daemon(&mut context, json).await?
+                        }
+                        cmd => cmd.execute(&mut context).await?.print(!json),
+                    }
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -627,95 +700,32 @@ impl SuiCommand {
                         Ok(())
                     }
                     sui_move::Command::Build(ref build) if build.dump_bytecode_as_base64 => {
-                        // Resolve pubfile_path to absolute before reroot_path changes CWD
-                        let pubfile_path = build_config.pubfile_path.as_ref().map(|p| {
-                            if p.is_absolute() {
-                                p.clone()
-                            } else {
-                                std::env::current_dir()
-                                    .expect("failed to get current directory")
-                                    .join(p)
-                            }
-                        });
-
-                        let rerooted_path = move_cli::base::reroot_path(package_path.as_deref())?;
-
-                        let with_unpublished_deps = build.with_unpublished_dependencies;
-                        let environment = find_environment(
-                            &rerooted_path,
-                            build_config.environment.clone(),
+                        let package = dump_package_as_base64(
+                            package_path.as_deref(),
+                            build_config,
+                            build.with_unpublished_dependencies,
+                            build.no_tree_shaking,
                             &context,
-                            false,
                         )
                         .await?;
-
-                        let mut root_pkg = if let Some(pubfile_path) = pubfile_path {
-                            // for ephemeral dumping, we take the chain ID from the real
-                            // environment.
-                            let chain_id = environment.id();
-
-                            let modes = build_config.mode_set();
-                            load_root_pkg_for_ephemeral_publish_or_upgrade(
-                                &rerooted_path,
-                                chain_id,
-                                build_config.environment.clone(),
-                                pubfile_path,
-                                modes,
-                            )
-                            .await?
-                        } else {
-                            load_root_pkg_for_publish_upgrade(
-                                &context,
-                                &build_config,
-                                &rerooted_path,
-                            )
-                            .await?
-                        };
-
-                        if !with_unpublished_deps {
-                            let _ = check_for_unpublished_deps(&root_pkg, with_unpublished_deps)?;
-                        }
-
-                        // explicitly tell the compiler to set unpublished dependencies' addresses
-                        // to 0x0
-                        let mut config = build_config.clone();
-                        config.set_unpublished_deps_to_zero = with_unpublished_deps;
-                        config.root_as_zero = true;
-
-                        let mut pkg = SuiBuildConfig {
-                            config,
-                            run_bytecode_verifier: true,
-                            print_diags_to_stderr: true,
-                            environment,
-                            flavor: SuiFlavor::with_client(&context),
-                        }
-                        .build_async_from_root_pkg(&mut root_pkg)
-                        .await?;
-
-                        if !build.no_tree_shaking {
-                            let client = context.grpc_client()?;
-                            pkg_tree_shake(client, with_unpublished_deps, &mut pkg).await?;
-                        }
-
-                        println!(
-                            "{}",
-                            json!({
-                                "modules": pkg.get_package_base64(with_unpublished_deps),
-                                "dependencies": pkg.get_dependency_storage_package_ids(),
-                                "digest": pkg.get_package_digest(with_unpublished_deps),
-                            })
-                        );
+                        println!("{package}");
                         Ok(())
                     }
                     _ => {
-                        execute_move_command(
+                        let is_build = matches!(cmd, sui_move::Command::Build(_));
+                        let result = execute_move_command(
                             package_path.as_deref(),
                             build_config,
                             cmd,
                             None,
                             &context,
                         )
-                        .await
+                        .await;
+                        if is_build {
+                            result.error_code(ErrorCode::Build)
+                        } else {
+                            result
+                        }
                     }
                 }
             }
@@ -804,6 +814,32 @@ impl SuiCommand {
                 Ok(())
             }
             SuiCommand::FireDrill { fire_drill } => run_fire_drill(fire_drill).await,
+            SuiCommand::Scenario { cmd } => run_scenario_command(cmd).await,
+            SuiCommand::Bench { config, args, json } => {
+                let context = if args.local {
+                    None
+                } else {
+                    let mut context = get_wallet_context(&config).await?;
+                    if let Some(env_override) = config.env {
+                        context = context.with_env_override(env_override);
+                    }
+                    Some(context)
+                };
+                let report = args.execute(context).await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    println!("{report}");
+                }
+                Ok(())
+            }
+            SuiCommand::ServeStdio { config } => {
+                let mut context = get_wallet_context(&config).await?;
+                if let Some(env_override) = config.env {
+                    context = context.with_env_override(env_override);
+                }
+                serve_stdio::serve_stdio(&mut context).await
+            }
             SuiCommand::Analyzer => {
                 analyzer::run::<SuiFlavor>(Arc::new(SuiFlavor::new()), Some(Flavor::Sui));
                 Ok(())
@@ -850,12 +886,24 @@ impl SuiCommand {
 
                 Ok(())
             }
-            SuiCommand::Completion { generator } => {
+            SuiCommand::Completion { shell, generator } => {
+                let Some(generator) = shell.or(generator).or_else(clap_complete::Shell::from_env)
+                else {
+                    bail!(
+                        "Cannot detect the shell from the environment, please provide one, e.g. \
+                         `sui completion bash`"
+                    );
+                };
                 let mut app: Command = SuiCommand::command();
                 let name = app.get_name().to_string();
                 clap_complete::generate(generator, &mut app, name, &mut std::io::stdout());
                 Ok(())
             }
+            SuiCommand::Manifest => {
+                let manifest = command_manifest(SuiCommand::command());
+                println!("{}", serde_json::to_string_pretty(&manifest)?);
+                Ok(())
+            }
         }
     }
 }
@@ -871,6 +919,7 @@ async fn start(
     data_ingestion_dir: Option<PathBuf>,
     no_full_node: bool,
     committee_size: Option<usize>,
+    commit_latency: Option<CommitLatencyConfig>,
 ) -> Result<(), anyhow::Error> {
     if force_regenesis {
         ensure!(
@@ -928,6 +977,9 @@ async fn start(
         swarm_builder = swarm_builder.with_genesis_config(genesis_config);
         let epoch_duration_ms = epoch_duration_ms.unwrap_or(DEFAULT_EPOCH_DURATION_MS);
         swarm_builder = swarm_builder.with_epoch_duration_ms(epoch_duration_ms);
+        if let Some(commit_latency) = commit_latency {
+            swarm_builder = swarm_builder.with_consensus_commit_latency(commit_latency);
+        }
         mysten_common::tempdir()?.keep()
     } else {
         // If the config path looks like a YAML file, it is treated as if it is the network.yaml
@@ -1017,14 +1069,22 @@ async fn start(
         };
 
         // Load the config of the Sui authority.
-        let network_config: NetworkConfig =
-            PersistedConfig::read(&network_config_path).map_err(|err| {
+        let mut network_config: NetworkConfig = PersistedConfig::read(&network_config_path)
+            .map_err(|err| {
                 err.context(format!(
                     "Cannot open Sui network config file at {:?}",
                     network_config_path
                 ))
             })?;
 
+        if let Some(commit_latency) = commit_latency {
+            for validator in &mut network_config.validator_configs {
+                if let Some(consensus_config) = &mut validator.consensus_config {
+                    consensus_config.commit_latency = Some(commit_latency.clone());
+                }
+            }
+        }
+
         swarm_builder = swarm_builder
             .dir(sui_config_path.clone())
             .with_network_config(network_config);
@@ -1294,9 +1354,11 @@ async fn start(
                     ws: None,
                     basic_auth: None,
                     chain_id: None,
+                    gas_swap_routes: vec![],
                 }],
                 active_address: Some(address),
                 active_env: Some("localnet".to_string()),
+                watch_only: vec![],
             }
             .persisted(config_dir.join(SUI_CLIENT_CONFIG).as_path())
             .save()
@@ -1309,10 +1371,7 @@ async fn start(
         )
         .await?;
 
-        let app_state = Arc::new(AppState {
-            faucet: local_faucet,
-            config,
-        });
+        let app_state = Arc::new(AppState::new(local_faucet, config));
 
         rpc_services = rpc_services.merge(
             start_faucet(app_state)
@@ -1482,12 +1541,12 @@ async fn genesis(
         builder
             .with_genesis_config(genesis_conf)
             .with_validators(validators)
-            .build()
+            .try_build()?
     } else {
         builder
             .committee_size(committee_size)
             .with_genesis_config(genesis_conf)
-            .build()
+            .try_build()?
     };
 
     let mut keystore = FileBasedKeystore::load_or_create(&keystore_path)?;
@@ -1595,6 +1654,7 @@ async fn genesis(
         ws: None,
         basic_auth: None,
         chain_id: None,
+        gas_swap_routes: vec![],
     });
     client_config.add_env(SuiEnv::devnet());
 
@@ -1656,6 +1716,7 @@ async fn prompt_if_no_config(
         external_keys: Some(external_keystore),
         active_address: Some(address),
         active_env: Some(default_env_name.clone()),
+        watch_only: vec![],
     }
     .persisted(wallet_conf_file)
     .save()?;
@@ -1710,15 +1771,108 @@ fn read_line() -> Result<String, anyhow::Error> {
     Ok(s.trim_end().to_string())
 }
 
+/// Build the package at `package_path` (the current directory by default), and describe it as
+/// `sui move build --dump-bytecode-as-base64` does: its modules in Base64, the IDs of its
+/// dependencies and its digest. Relative paths are resolved against the current directory, which
+/// is left unchanged.
+pub(crate) async fn dump_package_as_base64(
+    package_path: Option<&Path>,
+    build_config: BuildConfig,
+    with_unpublished_deps: bool,
+    no_tree_shaking: bool,
+    context: &WalletContext,
+) -> Result<serde_json::Value, anyhow::Error> {
+    let pubfile_path = build_config.pubfile_path.as_ref().map(|p| {
+        if p.is_absolute() {
+            p.clone()
+        } else {
+            std::env::current_dir()
+                .expect("failed to get current directory")
+                .join(p)
+        }
+    });
+
+    // Find the package root without changing into it, as `reroot_path` would.
+    let package_path = match package_path {
+        Some(path) => path.canonicalize()?,
+        None => std::env::current_dir()?,
+    };
+    let rerooted_path = SourcePackageLayout::try_find_root(&package_path)?;
+
+    let environment = find_environment(
+        &rerooted_path,
+        build_config.environment.clone(),
+        context,
+        false,
+    )
+    .await?;
+
+    let mut root_pkg = if let Some(pubfile_path) = pubfile_path {
+        // for ephemeral dumping, we take the chain ID from the real
+        // environment.
+        let chain_id = environment.id();
+
+        let modes = build_config.mode_set();
+        load_root_pkg_for_ephemeral_publish_or_upgrade(
+            &rerooted_path,
+            chain_id,
+            build_config.environment.clone(),
+            pubfile_path,
+            modes,
+        )
+        .await?
+    } else {
+        load_root_pkg_for_publish_upgrade(context, &build_config, &rerooted_path).await?
+    };
+
+    if !with_unpublished_deps {
+        let _ = check_for_unpublished_deps(&root_pkg, with_unpublished_deps)?;
+    }
+
+    // explicitly tell the compiler to set unpublished dependencies' addresses
+    // to 0x0
+    let mut config = build_config.clone();
+    config.set_unpublished_deps_to_zero = with_unpublished_deps;
+    config.root_as_zero = true;
+
+    let mut pkg = SuiBuildConfig {
+        config,
+        run_bytecode_verifier: true,
+        print_diags_to_stderr: true,
+        environment,
+        flavor: SuiFlavor::with_client(context),
+    }
+    .build_async_from_root_pkg(&mut root_pkg)
+    .await
+    .error_code(ErrorCode::Build)?;
+
+    if !no_tree_shaking {
+        let client = context.grpc_client()?;
+        pkg_tree_shake(client, with_unpublished_deps, &mut pkg).await?;
+    }
+
+    Ok(json!({
+        "modules": pkg.get_package_base64(with_unpublished_deps),
+        "dependencies": pkg.get_dependency_storage_package_ids(),
+        "digest": pkg.get_package_digest(with_unpublished_deps),
+    }))
+}
+
 /// Get the currently configured wallet context, creating one if it doesn't exist
 async fn get_wallet_context(client_config: &SuiEnvConfig) -> Result<WalletContext, anyhow::Error> {
-    let wallet_conf_file = client_config
-        .config
-        .clone()
-        .unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+    let wallet_conf_file = client_config.config.clone().unwrap_or(
+        sui_config_dir()
+            .error_code(ErrorCode::Config)?
+            .join(SUI_CLIENT_CONFIG),
+    );
 
-    prompt_if_no_config(&wallet_conf_file, client_config.accept_defaults).await?;
-    let mut context = WalletContext::new(&wallet_conf_file)?;
+    prompt_if_no_config(&wallet_conf_file, client_config.accept_defaults)
+        .await
+        .error_code(ErrorCode::Config)?;
+    let mut context = WalletContext::new(&wallet_conf_file)
+        .error_code(ErrorCode::Config)?
+        .with_read_only(client_config.read_only)
+        .with_metadata_cache(true);
 
     if let Some(env_override) = &client_config.env {
         context = context.with_env_override(env_override.clone());
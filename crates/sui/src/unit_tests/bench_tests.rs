@@ -0,0 +1,135 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn args(workloads: Vec<Workload>) -> BenchArgs {
+    BenchArgs {
+        workloads,
+        workers: 2,
+        duration: 5,
+        max_transactions: Some(20),
+        gas_budget: 500_000_000,
+        gas_per_worker: None,
+        shared_objects: 1,
+        mint_batch: 5,
+        ptb_size: 100,
+        metrics_urls: vec![],
+        network_config: None,
+        local: false,
+        committee_size: 4,
+    }
+}
+
+#[test]
+fn test_percentiles() {
+    assert_eq!(percentile_ms(&[], 50.0), 0.0);
+
+    let latencies: Vec<_> = (1..=100).map(Duration::from_millis).collect();
+    assert_eq!(percentile_ms(&latencies, 50.0), 50.0);
+    assert_eq!(percentile_ms(&latencies, 90.0), 90.0);
+    assert_eq!(percentile_ms(&latencies, 99.0), 99.0);
+    assert_eq!(percentile_ms(&latencies, 100.0), 100.0);
+    assert_eq!(percentile_ms(&latencies[..1], 99.0), 1.0);
+}
+
+#[test]
+fn test_workload_report() {
+    let samples: Vec<_> = [10, 30, 20, 40]
+        .into_iter()
+        .map(|ms| Sample {
+            workload: Workload::OwnedTransfer,
+            latency: Duration::from_millis(ms),
+            ok: ms != 20,
+        })
+        .collect();
+    let report = workload_report("total", &samples, Duration::from_secs(2));
+    assert_eq!(report.transactions, 4);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.tps, 2.0);
+    assert_eq!(
+        report.latency_ms,
+        LatencyReport {
+            p50: 20.0,
+            p90: 40.0,
+            p99: 40.0,
+            max: 40.0,
+        }
+    );
+}
+
+#[test]
+fn test_parse_counters() {
+    let text = "\
+# HELP consensus_handler_deferred_transactions Number of deferred transactions
+# TYPE consensus_handler_deferred_transactions counter
+consensus_handler_deferred_transactions 12
+consensus_handler_congested_transactions{reason=\"a\"} 3
+consensus_handler_congested_transactions{reason=\"b\"} 4.0
+consensus_handler_congested_transactions_total 100
+";
+    let counters = parse_counters(text, &[DEFERRED_METRIC, CONGESTED_METRIC]);
+    assert_eq!(counters.get(DEFERRED_METRIC), Some(&12));
+    assert_eq!(counters.get(CONGESTED_METRIC), Some(&7));
+}
+
+#[test]
+fn test_congestion_report_takes_largest_increase() {
+    let url = |port| Url::parse(&format!("http://127.0.0.1:{port}/metrics")).unwrap();
+    let counters = |deferred, congested| {
+        BTreeMap::from([
+            (DEFERRED_METRIC.to_string(), deferred),
+            (CONGESTED_METRIC.to_string(), congested),
+        ])
+    };
+
+    assert_eq!(congestion_report(vec![], vec![]), None);
+    let report = congestion_report(
+        vec![(url(1), counters(10, 0)), (url(2), counters(0, 0))],
+        vec![
+            (url(1), counters(15, 2)),
+            (url(2), counters(3, 4)),
+            (url(3), counters(100, 100)),
+        ],
+    );
+    assert_eq!(
+        report,
+        Some(CongestionReport {
+            deferred_transactions: 5,
+            congested_transactions: 4,
+        })
+    );
+}
+
+#[test]
+fn test_invalid_args() {
+    let mut bench = args(vec![Workload::LargePtb]);
+    bench.ptb_size = MAX_PTB_SIZE + 1;
+    let err = futures::executor::block_on(bench.execute(None)).unwrap_err();
+    assert!(err.to_string().contains("PTB size"));
+
+    let mut bench = args(vec![Workload::OwnedTransfer]);
+    bench.workers = 0;
+    let err = futures::executor::block_on(bench.execute(None)).unwrap_err();
+    assert!(err.to_string().contains("number of workers"));
+}
+
+#[tokio::test]
+async fn test_bench_all_workloads() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let report = args(vec![
+        Workload::OwnedTransfer,
+        Workload::SharedCounter,
+        Workload::NftMint,
+        Workload::LargePtb,
+    ])
+    .run(&cluster.wallet, vec![])
+    .await?;
+
+    assert_eq!(report.errors, 0, "{:?}", report.first_error);
+    assert_eq!(report.total.transactions, 20);
+    assert_eq!(report.total.failed, 0);
+    assert_eq!(report.workloads.len(), 4);
+    assert!(report.congestion.is_none());
+    Ok(())
+}
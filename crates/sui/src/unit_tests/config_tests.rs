@@ -0,0 +1,143 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_keys::keystore::InMemKeystore;
+
+fn config(keys: usize) -> SuiClientConfig {
+    let mut config =
+        SuiClientConfig::new(Keystore::InMem(InMemKeystore::new_insecure_for_tests(keys)));
+    config.envs = vec![SuiEnv::testnet(), SuiEnv::devnet()];
+    config.active_env = Some("devnet".to_string());
+    config.active_address = config.keystore.addresses().first().copied();
+    config
+}
+
+fn round_trip(bundle: &ConfigBundle) -> BundlePayload {
+    let json = serde_json::to_string_pretty(bundle).unwrap();
+    ConfigBundle::parse(&json).unwrap()
+}
+
+#[tokio::test]
+async fn test_bundle_with_plain_keys() {
+    let source = config(2);
+    let bundle = export_bundle(&source, true, None).unwrap();
+    let payload = round_trip(&bundle);
+    let keys = payload.decode_keys(None).unwrap();
+    assert_eq!(keys.len(), 2);
+
+    let mut target =
+        SuiClientConfig::new(Keystore::InMem(InMemKeystore::new_insecure_for_tests(0)));
+    let output = import_bundle(&mut target, &payload, keys, true)
+        .await
+        .unwrap();
+
+    assert_eq!((output.envs, output.keys, output.watch_only), (2, 2, 0));
+    assert_eq!(target.keystore.addresses(), source.keystore.addresses());
+    for address in source.keystore.addresses() {
+        assert_eq!(
+            target.keystore.get_alias(&address).unwrap(),
+            source.keystore.get_alias(&address).unwrap()
+        );
+    }
+    assert_eq!(target.active_env, source.active_env);
+    assert_eq!(target.active_address, source.active_address);
+
+    // Importing again is a no-op.
+    let keys = payload.decode_keys(None).unwrap();
+    let output = import_bundle(&mut target, &payload, keys, false)
+        .await
+        .unwrap();
+    assert_eq!((output.envs, output.addresses), (0, 0));
+}
+
+#[tokio::test]
+async fn test_bundle_without_keys_imports_watch_only() {
+    let source = config(2);
+    let payload = round_trip(&export_bundle(&source, false, None).unwrap());
+    assert!(payload.keys.is_none());
+
+    let mut target =
+        SuiClientConfig::new(Keystore::InMem(InMemKeystore::new_insecure_for_tests(0)));
+    let output = import_bundle(&mut target, &payload, vec![], true)
+        .await
+        .unwrap();
+
+    assert_eq!((output.keys, output.watch_only), (0, 2));
+    assert!(target.keystore.addresses().is_empty());
+    for address in source.keystore.addresses() {
+        let watched = target
+            .get_watch_only(&KeyIdentity::Address(address))
+            .unwrap();
+        assert_eq!(watched.alias, source.keystore.get_alias(&address).unwrap());
+    }
+    assert_eq!(target.active_address, source.active_address);
+}
+
+#[test]
+fn test_bundle_encrypted_keys() {
+    let source = config(1);
+    let payload = round_trip(&export_bundle(&source, true, Some("correct horse")).unwrap());
+    assert!(matches!(payload.keys, Some(BundleKeys::Encrypted { .. })));
+
+    let decoded = payload.decode_keys(Some("correct horse")).unwrap();
+    assert_eq!(
+        SuiAddress::from(&decoded[0].public()),
+        source.keystore.addresses()[0]
+    );
+
+    let err = payload.decode_keys(Some("battery staple")).unwrap_err();
+    assert!(err.to_string().contains("wrong passphrase"), "{err}");
+    assert!(payload.decode_keys(None).is_err());
+}
+
+#[test]
+fn test_bundle_integrity_check() {
+    let source = config(1);
+    let bundle = export_bundle(&source, false, None).unwrap();
+    let json = serde_json::to_string_pretty(&bundle).unwrap();
+
+    let tampered = json.replace(&bundle.payload.envs[0].rpc, "https://attacker.example");
+    let err = ConfigBundle::parse(&tampered).unwrap_err();
+    assert!(err.to_string().contains("checksum mismatch"), "{err}");
+
+    // Fields unknown to this version are covered by the checksum too.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["payload"]["extra"] = serde_json::json!("injected");
+    let err = ConfigBundle::parse(&value.to_string()).unwrap_err();
+    assert!(err.to_string().contains("checksum mismatch"), "{err}");
+
+    let mut other_version = bundle;
+    other_version.version = BUNDLE_VERSION + 1;
+    let err = ConfigBundle::parse(&serde_json::to_string(&other_version).unwrap()).unwrap_err();
+    assert!(
+        err.to_string().contains("Unsupported bundle version"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_encrypted_bundle_is_authenticated() {
+    let source = config(1);
+    let mut bundle = export_bundle(&source, true, Some("correct horse")).unwrap();
+
+    // Modifying an encrypted bundle and recomputing its public checksum is not enough: the
+    // encrypted keys are bound to the rest of the payload.
+    bundle.payload.envs[0].rpc = "https://attacker.example".to_string();
+    let payload = round_trip(&ConfigBundle::new(bundle.payload).unwrap());
+    let err = payload.decode_keys(Some("correct horse")).unwrap_err();
+    assert!(err.to_string().contains("was modified"), "{err}");
+}
+
+#[test]
+fn test_bundle_keys_must_match_addresses() {
+    let source = config(2);
+    let mut payload = export_bundle(&source, true, None).unwrap().payload;
+    payload.addresses.pop();
+
+    let err = payload.decode_keys(None).unwrap_err();
+    assert!(
+        err.to_string().contains("not one of its addresses"),
+        "{err}"
+    );
+}
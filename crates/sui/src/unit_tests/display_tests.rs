@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use super::*;
+use move_core_types::{
+    annotated_value::{MoveFieldLayout, MoveStructLayout},
+    identifier::Identifier,
+    language_storage::StructTag,
+};
+use sui_types::{
+    base_types::{SequenceNumber, move_utf8_str_layout, random_object_ref},
+    transaction::{Command, SharedObjectMutability},
+    type_input::TypeInput,
+};
+
+/// A store for templates that don't load any other objects.
+struct NoObjects;
+
+#[async_trait]
+impl Store for NoObjects {
+    async fn latest(
+        &self,
+        _id: AccountAddress,
+    ) -> anyhow::Result<Option<(MoveTypeLayout, Vec<u8>)>> {
+        Ok(None)
+    }
+}
+
+/// An object of type `0x42::nft::Nft { name: String, level: u64, scores: vector<u64> }`.
+fn nft() -> (Vec<u8>, MoveTypeLayout) {
+    let contents = bcs::to_bytes(&("Capy", 3u64, vec![10u64, 20u64])).unwrap();
+    let field = |name: &str, layout| MoveFieldLayout::new(Identifier::new(name).unwrap(), layout);
+    let layout = MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+        type_: StructTag::from_str("0x42::nft::Nft").unwrap(),
+        fields: vec![
+            field(
+                "name",
+                MoveTypeLayout::Struct(Box::new(move_utf8_str_layout())),
+            ),
+            field("level", MoveTypeLayout::U64),
+            field(
+                "scores",
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U64)),
+            ),
+        ],
+    }));
+    (contents, layout)
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .map(|(name, template)| (name.to_string(), template.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_parse_template() {
+    use TemplateVersion::*;
+    for version in [V1, V2] {
+        parse_template(version, "https://capy.art/{ id }/{attrs.color}.svg").unwrap();
+        assert!(parse_template(version, "{unclosed").is_err());
+        assert!(parse_template(version, "stray}").is_err());
+    }
+
+    // Only v1 escapes braces with a backslash, and only v2 by doubling them.
+    parse_template(V1, r"\{literal\}").unwrap();
+    assert!(parse_template(V2, r"\{literal\}").is_err());
+    parse_template(V2, "{{literal}}").unwrap();
+    assert!(parse_template(V1, "{{literal}}").is_err());
+
+    // Indices and transforms are only supported by v2.
+    parse_template(V2, "{scores[0u64]:hex}").unwrap();
+    assert!(parse_template(V1, "{scores[0u64]:hex}").is_err());
+}
+
+#[test]
+fn test_detect_version() {
+    assert_eq!(
+        detect_version(&fields(&[("name", "{name}"), ("score", "{scores[0u64]}")])),
+        TemplateVersion::V2
+    );
+    assert_eq!(
+        detect_version(&fields(&[("name", "{name}"), ("brace", r"\{{name}\}")])),
+        TemplateVersion::V1
+    );
+    // Templates valid in neither version are reported against the registry's syntax.
+    assert_eq!(
+        detect_version(&fields(&[
+            ("brace", r"\{{name}\}"),
+            ("escaped", "{{name}}")
+        ])),
+        TemplateVersion::V2
+    );
+}
+
+#[tokio::test]
+async fn test_render_fields_v1() {
+    let (contents, layout) = nft();
+    let output = render_fields(
+        TemplateVersion::V1,
+        fields(&[
+            ("name", "{name} #{level}"),
+            ("brace", r"\{{name}\}"),
+            ("image", "{image_url}"),
+        ]),
+        contents,
+        layout,
+        NoObjects,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(output[0].rendered.as_deref(), Some("Capy #3"));
+    assert_eq!(output[1].rendered.as_deref(), Some("{Capy}"));
+    assert_eq!(output[2].rendered, None);
+    assert!(output[2].error.is_some());
+}
+
+#[tokio::test]
+async fn test_render_fields_v2() {
+    let (contents, layout) = nft();
+    let output = render_fields(
+        TemplateVersion::V2,
+        fields(&[
+            ("name", "{name} #{level}"),
+            ("score", "{{{scores[1u64]}}}"),
+            ("image", "{image_url}"),
+            ("invalid", r"\{name\}"),
+        ]),
+        contents,
+        layout,
+        NoObjects,
+    )
+    .await
+    .unwrap();
+
+    let names: Vec<_> = output.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "score", "image", "invalid"]);
+    assert_eq!(output[0].rendered.as_deref(), Some("Capy #3"));
+    assert_eq!(output[1].rendered.as_deref(), Some("{20}"));
+    assert!(output[2].error.as_ref().unwrap().contains("missing"));
+    assert_eq!(output[3].rendered, None);
+    assert!(output[3].error.is_some());
+}
+
+#[test]
+fn test_update_display_ptb() {
+    let display = ObjectArg::SharedObject {
+        id: ObjectID::random(),
+        initial_shared_version: SequenceNumber::from_u64(1),
+        mutability: SharedObjectMutability::Mutable,
+    };
+    let type_ = parse_sui_type_tag("0x42::nft::Nft").unwrap();
+    let ptb = update_display_ptb(
+        display,
+        random_object_ref(),
+        type_.clone(),
+        true,
+        vec!["link".to_owned()],
+        vec![("name".to_owned(), "{name}".to_owned())],
+    )
+    .unwrap();
+
+    let functions: Vec<_> = ptb
+        .commands
+        .iter()
+        .map(|command| {
+            let Command::MoveCall(call) = command else {
+                panic!("Expected a move call, got {command:?}");
+            };
+            assert_eq!(call.type_arguments, vec![TypeInput::from(type_.clone())]);
+            call.function.as_str()
+        })
+        .collect();
+    assert_eq!(functions, vec!["clear", "unset", "set"]);
+}
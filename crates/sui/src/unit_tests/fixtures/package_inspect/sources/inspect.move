@@ -0,0 +1,23 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module package_inspect::inspect;
+
+public struct Pair<T, phantom P> has copy, drop, store {
+    first: T,
+    count: u64,
+}
+
+public fun new<T: copy + drop, P>(first: T): Pair<T, P> {
+    Pair { first, count: zero() }
+}
+
+public(package) fun count<T, P>(pair: &Pair<T, P>): u64 {
+    pair.count
+}
+
+entry fun touch(_count: u64) {}
+
+fun zero(): u64 {
+    0
+}
@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn entry(digest: TransactionDigest, env: &str, label: Option<&str>) -> HistoryEntry {
+    HistoryEntry {
+        digest,
+        env: env.to_string(),
+        chain_id: None,
+        sender: SuiAddress::ZERO,
+        status: HistoryStatus::Success,
+        error: None,
+        label: label.map(str::to_string),
+        timestamp_ms: 0,
+    }
+}
+
+#[test]
+fn test_history_path_next_to_config() {
+    assert_eq!(
+        TransactionHistory::path_for(Path::new("/home/user/.sui/sui_config/client.yaml")),
+        PathBuf::from("/home/user/.sui/sui_config").join(HISTORY_FILE_NAME),
+    );
+}
+
+#[test]
+fn test_history_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(HISTORY_FILE_NAME);
+
+    let mut history = TransactionHistory::load(&path).unwrap();
+    assert!(history.list(None, None, usize::MAX).next().is_none());
+
+    let digest = TransactionDigest::random();
+    history.record(entry(digest, "testnet", Some("publish")));
+    history.save().unwrap();
+
+    let history = TransactionHistory::load(&path).unwrap();
+    assert_eq!(
+        history.find(&digest),
+        Some(&entry(digest, "testnet", Some("publish")))
+    );
+}
+
+#[test]
+fn test_history_concurrent_updates_keep_all_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(HISTORY_FILE_NAME);
+
+    let digests: Vec<_> = (0..8).map(|_| TransactionDigest::random()).collect();
+    std::thread::scope(|s| {
+        for digest in &digests {
+            let path = &path;
+            s.spawn(move || {
+                TransactionHistory::update(path, |history| {
+                    history.record(entry(*digest, "testnet", None));
+                    Ok(())
+                })
+                .unwrap()
+            });
+        }
+    });
+
+    let history = TransactionHistory::load(&path).unwrap();
+    for digest in &digests {
+        assert!(history.find(digest).is_some());
+    }
+}
+
+#[test]
+fn test_history_list_filters_most_recent_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut history = TransactionHistory::load(&dir.path().join(HISTORY_FILE_NAME)).unwrap();
+
+    let digests: Vec<_> = (0..4).map(|_| TransactionDigest::random()).collect();
+    history.record(entry(digests[0], "testnet", Some("mint nft")));
+    history.record(entry(digests[1], "devnet", Some("mint coin")));
+    history.record(entry(digests[2], "testnet", None));
+    history.record(entry(digests[3], "testnet", Some("burn")));
+
+    let listed = |env, label, limit| -> Vec<_> {
+        history.list(env, label, limit).map(|e| e.digest).collect()
+    };
+
+    assert_eq!(
+        listed(None, None, usize::MAX),
+        vec![digests[3], digests[2], digests[1], digests[0]],
+    );
+    assert_eq!(listed(None, None, 2), vec![digests[3], digests[2]]);
+    assert_eq!(
+        listed(Some("testnet"), None, usize::MAX),
+        vec![digests[3], digests[2], digests[0]],
+    );
+    assert_eq!(
+        listed(None, Some("mint"), usize::MAX),
+        vec![digests[1], digests[0]],
+    );
+    assert_eq!(
+        listed(Some("testnet"), Some("mint"), usize::MAX),
+        vec![digests[0]],
+    );
+}
+
+#[test]
+fn test_history_annotate() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut history = TransactionHistory::load(&dir.path().join(HISTORY_FILE_NAME)).unwrap();
+
+    let digest = TransactionDigest::random();
+    history.record(entry(digest, "testnet", None));
+
+    let annotated = history.annotate(&digest, "upgrade".to_string()).unwrap();
+    assert_eq!(annotated.label.as_deref(), Some("upgrade"));
+
+    let err = history
+        .annotate(&TransactionDigest::random(), "missing".to_string())
+        .unwrap_err();
+    assert!(err.to_string().contains("not in the local history"));
+}
+
+#[test]
+fn test_history_record_replaces_and_keeps_label() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut history = TransactionHistory::load(&dir.path().join(HISTORY_FILE_NAME)).unwrap();
+
+    let digest = TransactionDigest::random();
+    history.record(entry(digest, "testnet", Some("first")));
+    history.record(entry(digest, "testnet", None));
+
+    let entries: Vec<_> = history.list(None, None, usize::MAX).collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].label.as_deref(), Some("first"));
+}
+
+#[test]
+fn test_history_is_capped() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut history = TransactionHistory::load(&dir.path().join(HISTORY_FILE_NAME)).unwrap();
+
+    let first = TransactionDigest::random();
+    history.record(entry(first, "testnet", None));
+    for _ in 0..MAX_HISTORY_ENTRIES {
+        history.record(entry(TransactionDigest::random(), "testnet", None));
+    }
+
+    assert_eq!(
+        history.list(None, None, usize::MAX).count(),
+        MAX_HISTORY_ENTRIES
+    );
+    assert!(history.find(&first).is_none());
+}
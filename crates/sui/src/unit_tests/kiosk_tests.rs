@@ -0,0 +1,138 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_types::base_types::{SequenceNumber, random_object_ref};
+
+fn shared(mutability: SharedObjectMutability) -> ObjectArg {
+    ObjectArg::SharedObject {
+        id: ObjectID::random(),
+        initial_shared_version: SequenceNumber::from_u64(1),
+        mutability,
+    }
+}
+
+fn item_type() -> TypeTag {
+    parse_sui_type_tag("0x42::nft::Nft").unwrap()
+}
+
+fn move_call(command: &Command) -> (&str, &str, Vec<TypeTag>) {
+    let Command::MoveCall(call) = command else {
+        panic!("Expected a move call, got {command:?}");
+    };
+    (
+        call.module.as_str(),
+        call.function.as_str(),
+        call.type_arguments
+            .iter()
+            .map(|t| t.to_type_tag().unwrap())
+            .collect(),
+    )
+}
+
+#[test]
+fn test_place_ptb() {
+    let kiosk = shared(SharedObjectMutability::Mutable);
+    let ptb = place_ptb(
+        kiosk,
+        random_object_ref(),
+        random_object_ref(),
+        item_type(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(ptb.inputs.len(), 3);
+    assert_eq!(
+        move_call(&ptb.commands[0]),
+        ("kiosk", "place", vec![item_type()])
+    );
+
+    let ptb = place_ptb(
+        kiosk,
+        random_object_ref(),
+        random_object_ref(),
+        item_type(),
+        Some(100),
+    )
+    .unwrap();
+    assert_eq!(ptb.inputs.len(), 4);
+    assert_eq!(
+        move_call(&ptb.commands[0]),
+        ("kiosk", "place_and_list", vec![item_type()])
+    );
+}
+
+#[test]
+fn test_purchase_ptb() {
+    let buyer = SuiAddress::random_for_testing_only();
+    let ptb = purchase_ptb(
+        shared(SharedObjectMutability::Mutable),
+        shared(SharedObjectMutability::Immutable),
+        ObjectID::random(),
+        item_type(),
+        100,
+        buyer,
+    )
+    .unwrap();
+
+    assert_eq!(ptb.commands.len(), 4);
+    assert!(matches!(
+        &ptb.commands[0],
+        Command::SplitCoins(Argument::GasCoin, amounts) if amounts.len() == 1
+    ));
+    assert_eq!(
+        move_call(&ptb.commands[1]),
+        ("kiosk", "purchase", vec![item_type()])
+    );
+    let Command::MoveCall(purchase) = &ptb.commands[1] else {
+        unreachable!()
+    };
+    assert_eq!(purchase.arguments[2], Argument::NestedResult(0, 0));
+
+    // The transfer request is confirmed and the item sent to the buyer.
+    assert_eq!(
+        move_call(&ptb.commands[2]),
+        ("transfer_policy", "confirm_request", vec![item_type()])
+    );
+    let Command::MoveCall(confirm) = &ptb.commands[2] else {
+        unreachable!()
+    };
+    assert_eq!(confirm.arguments[1], Argument::NestedResult(1, 1));
+    assert!(matches!(
+        &ptb.commands[3],
+        Command::TransferObjects(objects, _) if objects == &[Argument::NestedResult(1, 0)]
+    ));
+}
+
+#[test]
+fn test_withdraw_ptb() {
+    let ptb = withdraw_ptb(
+        shared(SharedObjectMutability::Mutable),
+        random_object_ref(),
+        None,
+        SuiAddress::random_for_testing_only(),
+    )
+    .unwrap();
+    assert_eq!(move_call(&ptb.commands[0]), ("kiosk", "withdraw", vec![]));
+    assert!(matches!(
+        &ptb.commands[1],
+        Command::TransferObjects(objects, _) if objects == &[Argument::Result(0)]
+    ));
+}
+
+#[test]
+fn test_transfer_policy_item_type() {
+    let policy_type = parse_sui_type_tag(&format!(
+        "0x2::transfer_policy::TransferPolicy<{}>",
+        item_type()
+    ))
+    .unwrap();
+    assert_eq!(transfer_policy_item_type(&policy_type), Some(&item_type()));
+
+    let cap_type = parse_sui_type_tag(&format!(
+        "0x2::transfer_policy::TransferPolicyCap<{}>",
+        item_type()
+    ))
+    .unwrap();
+    assert_eq!(transfer_policy_item_type(&cap_type), None);
+}
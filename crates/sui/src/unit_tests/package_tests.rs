@@ -0,0 +1,58 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::path::PathBuf;
+use sui_move_build::BuildConfig;
+
+fn inspect_fixture(disassemble: bool) -> PackageModuleOutput {
+    let mut path: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/unit_tests/fixtures/package_inspect");
+    let modules = BuildConfig::new_for_testing()
+        .build(&path)
+        .unwrap()
+        .into_modules();
+    assert_eq!(modules.len(), 1);
+    inspect_module(&modules[0], disassemble).unwrap()
+}
+
+#[test]
+fn test_inspect_module_functions() {
+    let module = inspect_fixture(false);
+    assert_eq!(module.name, "inspect");
+    assert!(module.disassembly.is_none());
+
+    // Only functions callable from outside the package are listed.
+    let mut names: Vec<_> = module.functions.iter().map(|f| f.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["new", "touch"]);
+
+    let new = module.functions.iter().find(|f| f.name == "new").unwrap();
+    assert!(new.is_public && !new.is_entry);
+    assert_eq!(new.type_parameters, vec!["copy + drop", ""]);
+    assert_eq!(new.parameters, vec!["T0"]);
+    assert_eq!(new.return_, vec!["0x0::inspect::Pair<T0, T1>"]);
+
+    let touch = module.functions.iter().find(|f| f.name == "touch").unwrap();
+    assert!(!touch.is_public && touch.is_entry);
+    assert_eq!(touch.parameters, vec!["u64"]);
+    assert!(touch.return_.is_empty());
+}
+
+#[test]
+fn test_inspect_module_structs() {
+    let module = inspect_fixture(true);
+    assert!(module.disassembly.unwrap().contains("module 0.inspect"));
+
+    assert_eq!(module.structs.len(), 1);
+    let pair = &module.structs[0];
+    assert_eq!(pair.name, "Pair");
+    assert_eq!(pair.abilities, vec!["copy", "drop", "store"]);
+    assert_eq!(pair.type_parameters, vec!["", "phantom"]);
+    let fields: Vec<_> = pair
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.type_.as_str()))
+        .collect();
+    assert_eq!(fields, vec![("first", "T0"), ("count", "u64")]);
+}
@@ -0,0 +1,150 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use serde_json::json;
+
+fn parse(yaml: &str) -> Scenario {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn test_parse_scenario() {
+    let scenario = parse(
+        r#"
+actors: [alice, bob]
+packages:
+  - name: counter
+    path: ./counter
+steps:
+  - sender: bob
+    ptb: --move-call ${counter}::counter::create
+    capture:
+      counter_id: { created: "${counter}::counter::Counter" }
+    expect:
+      events:
+        - type: "${counter}::counter::Created"
+          fields: { value: 0 }
+      objects:
+        - { id: "${counter_id}", owner: shared }
+      balances:
+        - { actor: bob, min: 1 }
+"#,
+    );
+    scenario.validate().unwrap();
+    assert_eq!(scenario.packages[0].build_env, "testnet");
+    let step = &scenario.steps[0];
+    assert_eq!(step.expect.status, ExpectedStatus::Success);
+    assert_eq!(
+        step.capture["counter_id"].created,
+        "${counter}::counter::Counter"
+    );
+    assert_eq!(step.expect.objects[0].owner.as_deref(), Some("shared"));
+}
+
+#[test]
+fn test_validate_rejects_unknown_actor() {
+    let scenario = parse(
+        r#"
+actors: [alice]
+steps:
+  - sender: carol
+    ptb: --split-coins gas [1]
+"#,
+    );
+    let err = scenario.validate().unwrap_err().to_string();
+    assert!(err.contains("Unknown actor 'carol'"), "{err}");
+
+    let scenario = parse("actors: [alice, alice]\nsteps: []\n");
+    assert!(scenario.validate().is_err());
+
+    let scenario = parse("actors: []\nsteps: []\n");
+    assert!(scenario.validate().is_err());
+}
+
+#[test]
+fn test_unknown_fields_are_rejected() {
+    assert!(serde_yaml::from_str::<Scenario>("actors: [alice]\nsteps: []\nactor: bob\n").is_err());
+}
+
+#[test]
+fn test_substitute() {
+    let variables = BTreeMap::from([
+        ("pkg".to_string(), "0x2".to_string()),
+        ("alice".to_string(), "0xa11ce".to_string()),
+    ]);
+    assert_eq!(
+        substitute("--move-call ${pkg}::m::f @${alice}", &variables).unwrap(),
+        "--move-call 0x2::m::f @0xa11ce"
+    );
+    assert_eq!(
+        substitute("no variables", &variables).unwrap(),
+        "no variables"
+    );
+    let err = substitute("${bob}", &variables).unwrap_err().to_string();
+    assert!(err.contains("${bob}"), "{err}");
+}
+
+#[test]
+fn test_json_contains() {
+    let actual = json!({ "id": "0x1", "value": "42", "items": [1, 2], "nested": { "a": true } });
+    assert!(json_contains(&actual, &json!({ "value": 42 })));
+    assert!(json_contains(
+        &actual,
+        &json!({ "value": "42", "nested": {} })
+    ));
+    assert!(json_contains(&actual, &json!({ "items": [1, 2] })));
+    assert!(!json_contains(&actual, &json!({ "items": [1] })));
+    assert!(!json_contains(&actual, &json!({ "value": 41 })));
+    assert!(!json_contains(&actual, &json!({ "missing": null })));
+}
+
+#[test]
+fn test_type_matches() {
+    let coin = StructTag::from_str("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+    assert!(type_matches(
+        &coin,
+        &StructTag::from_str("0x2::coin::Coin").unwrap()
+    ));
+    assert!(type_matches(&coin, &coin));
+    assert!(!type_matches(
+        &coin,
+        &StructTag::from_str("0x2::coin::Coin<0x2::coin::Coin<0x2::sui::SUI>>").unwrap()
+    ));
+    assert!(!type_matches(
+        &coin,
+        &StructTag::from_str("0x2::balance::Balance").unwrap()
+    ));
+}
+
+#[tokio::test]
+async fn test_run_transfer_scenario() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("transfer.yaml");
+    fs::write(
+        &file,
+        r#"
+actors: [alice, bob]
+steps:
+  - name: pay bob
+    ptb: --split-coins gas [1000] --assign coin --transfer-objects [coin] @bob
+    capture:
+      coin_id: { created: "0x2::coin::Coin<0x2::sui::SUI>" }
+    expect:
+      objects:
+        - { id: "${coin_id}", owner: bob, fields: { balance: 1000 } }
+  - name: bob returns the coin
+    sender: bob
+    ptb: --transfer-objects [@${coin_id}] @alice
+    expect:
+      objects:
+        - { id: "${coin_id}", owner: alice }
+  - name: overdraw
+    ptb: --split-coins gas [1000000000000000000] --assign coin --transfer-objects [coin] @bob
+    expect:
+      status: failure
+"#,
+    )
+    .unwrap();
+    run_scenario_file(&file).await.unwrap();
+}
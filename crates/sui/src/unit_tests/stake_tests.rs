@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use sui_types::{base_types::random_object_ref, transaction::Command};
+
+fn policy(weights: &[u64]) -> CompoundPolicy {
+    CompoundPolicy {
+        validators: weights
+            .iter()
+            .map(|weight| ValidatorWeight {
+                address: SuiAddress::random_for_testing_only(),
+                weight: *weight,
+            })
+            .collect(),
+        min_rewards: 0,
+        interval_secs: DEFAULT_COMPOUND_INTERVAL_SECS,
+    }
+}
+
+#[test]
+fn test_parse_policy() {
+    let address = SuiAddress::random_for_testing_only();
+    let policy: CompoundPolicy = serde_yaml::from_str(&format!(
+        "validators:\n  - address: {address}\n    weight: 3\nmin-rewards: 10\n"
+    ))
+    .unwrap();
+    assert_eq!(
+        policy.validators,
+        vec![ValidatorWeight { address, weight: 3 }]
+    );
+    assert_eq!(policy.min_rewards, 10);
+    assert_eq!(policy.interval_secs, DEFAULT_COMPOUND_INTERVAL_SECS);
+    policy.validate().unwrap();
+
+    assert!(self::policy(&[]).validate().is_err());
+    assert!(self::policy(&[1, 0]).validate().is_err());
+}
+
+#[test]
+fn test_allocate() {
+    let policy = policy(&[1, 2]);
+    let allocations = policy.allocate(10 * MIN_STAKE_AMOUNT + 1).unwrap();
+    assert_eq!(allocations[0].1, 3 * MIN_STAKE_AMOUNT + 333_333_333);
+    // The last validator gets the rounding leftovers.
+    assert_eq!(allocations[1].1, 6 * MIN_STAKE_AMOUNT + 666_666_668);
+    assert_eq!(
+        allocations.iter().map(|(_, a)| a).sum::<u64>(),
+        10 * MIN_STAKE_AMOUNT + 1
+    );
+
+    // Every validator must receive at least the minimum stake.
+    assert!(policy.allocate(2 * MIN_STAKE_AMOUNT).is_err());
+}
+
+#[test]
+fn test_compound_ptb() {
+    let policy = policy(&[1, 1, 1]);
+    let allocations = policy.allocate(3 * MIN_STAKE_AMOUNT).unwrap();
+    let ptb = compound_ptb(vec![random_object_ref(), random_object_ref()], &allocations).unwrap();
+
+    let functions: Vec<_> = ptb
+        .commands
+        .iter()
+        .map(|command| match command {
+            Command::MoveCall(call) => call.function.to_string(),
+            Command::SplitCoins(_, amounts) => format!("split_coins({})", amounts.len()),
+            other => panic!("Unexpected command {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        functions,
+        vec![
+            "request_withdraw_stake_non_entry",
+            "request_withdraw_stake_non_entry",
+            "join",
+            "from_balance",
+            "split_coins(2)",
+            "request_add_stake",
+            "request_add_stake",
+            "request_add_stake",
+        ]
+    );
+
+    assert!(compound_ptb(vec![], &allocations).is_err());
+    assert!(compound_ptb(vec![random_object_ref()], &[]).is_err());
+}
+
+#[test]
+fn test_compound_output_json() {
+    let sender = SuiAddress::random_for_testing_only();
+    let result = SuiClientCommandResult::Compound(CompoundOutput {
+        sender,
+        min_rewards: 10,
+        staked_sui_ids: vec![],
+        total: 0,
+        allocations: vec![],
+        transaction: Some(Box::new(SuiClientCommandResult::ActiveAddress(Some(
+            sender,
+        )))),
+    });
+    let json: serde_json::Value = serde_json::from_str(&format!("{result:?}")).unwrap();
+    assert_eq!(json["sender"], serde_json::json!(sender));
+    assert_eq!(json["minRewards"], 10);
+    assert_eq!(json["transaction"], serde_json::json!(sender));
+}
@@ -0,0 +1,54 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn upgrade_cap(policy: u8) -> UpgradeCap {
+    let mut cap = UpgradeCap::new(ObjectID::random(), ObjectID::random());
+    cap.policy = policy;
+    cap
+}
+
+#[test]
+fn test_policy_name() {
+    assert_eq!(policy_name(UpgradePolicy::COMPATIBLE), "COMPATIBLE");
+    assert_eq!(policy_name(UpgradePolicy::ADDITIVE), "ADDITIVE");
+    assert_eq!(policy_name(UpgradePolicy::DEP_ONLY), "DEP_ONLY");
+    assert_eq!(policy_name(7), "UNKNOWN (7)");
+}
+
+#[test]
+fn test_restrict_preview() {
+    let cap = upgrade_cap(UpgradePolicy::COMPATIBLE);
+    let preview = restrict_preview(&cap, RestrictedPolicy::Additive).unwrap();
+    assert!(preview.contains("from COMPATIBLE to ADDITIVE"));
+    assert!(preview.contains("cannot be undone"));
+
+    let cap = upgrade_cap(UpgradePolicy::ADDITIVE);
+    assert!(restrict_preview(&cap, RestrictedPolicy::DepOnly).is_ok());
+    // Restricting to the current policy would be a no-op.
+    assert!(restrict_preview(&cap, RestrictedPolicy::Additive).is_err());
+
+    let cap = upgrade_cap(UpgradePolicy::DEP_ONLY);
+    let err = restrict_preview(&cap, RestrictedPolicy::Additive).unwrap_err();
+    assert!(err.to_string().contains("already DEP_ONLY"));
+}
+
+#[test]
+fn test_confirm_irreversible() {
+    let execute = TxProcessingArgs::default();
+    assert!(confirm_irreversible("preview", false, &execute).is_err());
+    assert!(confirm_irreversible("preview", true, &execute).is_ok());
+
+    // Nothing is executed, so no confirmation is needed.
+    let dry_run = TxProcessingArgs {
+        dry_run: true,
+        ..Default::default()
+    };
+    assert!(confirm_irreversible("preview", false, &dry_run).is_ok());
+    let serialize = TxProcessingArgs {
+        serialize_unsigned_transaction: true,
+        ..Default::default()
+    };
+    assert!(confirm_irreversible("preview", false, &serialize).is_ok());
+}
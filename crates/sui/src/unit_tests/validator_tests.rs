@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::validator_commands::{
-    SuiValidatorCommand, SuiValidatorCommandResponse, get_validator_summary,
+    RotatedKey, RotatedKeyFile, SuiValidatorCommand, SuiValidatorCommandResponse,
+    check_staged_keys_active, get_validator_summary, swap_key_files,
 };
 use anyhow::Ok;
 use fastcrypto::encoding::{Base64, Encoding};
 use shared_crypto::intent::{Intent, IntentMessage};
+use sui_rpc::proto::sui::rpc::v2 as proto;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::transaction::TransactionData;
 use sui_types::{base_types::SuiAddress, crypto::Signature, transaction::Transaction};
@@ -65,3 +67,84 @@ async fn test_print_raw_rgp_txn() -> Result<(), anyhow::Error> {
     assert_eq!(summary.next_epoch_gas_price(), 42);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_set_commission_rate_above_max() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let mut context = test_cluster.wallet;
+    let err = SuiValidatorCommand::SetCommissionRate {
+        commission_rate: 2_001,
+        tx_args: Default::default(),
+    }
+    .execute(&mut context)
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("above the maximum"));
+    Ok(())
+}
+
+#[test]
+fn test_swap_key_files() -> Result<(), anyhow::Error> {
+    let dir = tempfile::tempdir()?;
+    let mut files = vec![];
+    for (key, name) in [
+        (RotatedKey::Protocol, "protocol.key"),
+        (RotatedKey::Network, "network.key"),
+    ] {
+        let path = dir.path().join(name);
+        let staged_path = dir.path().join(format!("{name}.next"));
+        std::fs::write(&path, "old")?;
+        std::fs::write(&staged_path, "new")?;
+        files.push(RotatedKeyFile {
+            key,
+            path,
+            staged_path,
+            backup_path: None,
+        });
+    }
+
+    // A missing staged file aborts the swap without touching anything.
+    std::fs::rename(&files[1].staged_path, dir.path().join("moved"))?;
+    assert!(swap_key_files(&mut files, "bak").is_err());
+    assert_eq!(std::fs::read_to_string(&files[0].path)?, "old");
+    assert_eq!(std::fs::read_to_string(&files[0].staged_path)?, "new");
+    std::fs::rename(dir.path().join("moved"), &files[1].staged_path)?;
+
+    swap_key_files(&mut files, "bak")?;
+    for file in &files {
+        assert_eq!(std::fs::read_to_string(&file.path)?, "new");
+        assert!(!file.staged_path.exists());
+        let backup_path = file.backup_path.as_ref().unwrap();
+        assert_eq!(std::fs::read_to_string(backup_path)?, "old");
+    }
+
+    // Swapping again with the same backup suffix must not clobber the backups.
+    for file in &files {
+        std::fs::write(&file.staged_path, "newer")?;
+    }
+    assert!(swap_key_files(&mut files, "bak").is_err());
+    assert_eq!(std::fs::read_to_string(&files[0].path)?, "new");
+    Ok(())
+}
+
+#[test]
+fn test_staged_keys_swap_only_once_active() -> Result<(), anyhow::Error> {
+    let old_key = vec![1u8; 32];
+    let new_key = vec![2u8; 32];
+    let staged = [(RotatedKey::Network, new_key.clone())];
+
+    // Staged for the next epoch: the node must keep its current key until then.
+    let mut summary = proto::Validator::default();
+    summary.network_public_key = Some(old_key.clone().into());
+    summary.next_epoch_network_public_key = Some(new_key.clone().into());
+    assert!(check_staged_keys_active(&summary, &staged).is_err());
+
+    // Never staged on-chain.
+    summary.next_epoch_network_public_key = None;
+    assert!(check_staged_keys_active(&summary, &staged).is_err());
+
+    // The next epoch has started, and the staged key is now the current one.
+    summary.network_public_key = Some(new_key.into());
+    check_staged_keys_active(&summary, &staged)?;
+    Ok(())
+}
@@ -0,0 +1,301 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, ensure};
+use clap::*;
+use serde::Serialize;
+use sui_keys::key_identity::KeyIdentity;
+use sui_sdk::wallet_context::WalletContext;
+use sui_transaction_builder::UpgradeCapAction;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    move_package::{UpgradeCap, UpgradePolicy},
+    object::{Object, Owner},
+};
+
+use crate::{
+    client_commands::{
+        GasDataArgs, PaymentArgs, SuiClientCommandResult, TxProcessingArgs,
+        dry_run_or_execute_or_serialize,
+    },
+    kiosk_commands::deserialize_move_object,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/upgrade_cap_tests.rs"]
+mod upgrade_cap_tests;
+
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum UpgradeCapCommand {
+    /// Show the package an UpgradeCap governs, its current version and its upgrade policy.
+    #[clap(name = "show")]
+    Show {
+        /// ID of the UpgradeCap
+        #[clap(name = "upgrade_cap_id")]
+        upgrade_cap_id: ObjectID,
+    },
+
+    /// Restrict the upgrade policy of a package. Policies can only become stricter, so this
+    /// cannot be undone.
+    #[clap(name = "restrict")]
+    Restrict {
+        /// ID of the package's UpgradeCap
+        #[clap(name = "upgrade_cap_id")]
+        upgrade_cap_id: ObjectID,
+        /// The new policy
+        #[clap(long, value_enum)]
+        policy: RestrictedPolicy,
+        /// Confirm the irreversible change. Without it, only a preview of the change is shown.
+        #[clap(long)]
+        yes: bool,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Make a package immutable by destroying its UpgradeCap. The package can never be upgraded
+    /// again.
+    #[clap(name = "make-immutable")]
+    MakeImmutable {
+        /// ID of the package's UpgradeCap
+        #[clap(name = "upgrade_cap_id")]
+        upgrade_cap_id: ObjectID,
+        /// Confirm the irreversible change. Without it, only a preview of the change is shown.
+        #[clap(long)]
+        yes: bool,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+
+    /// Transfer an UpgradeCap to another address, e.g. a multisig. Only the recipient can upgrade
+    /// the package afterwards, or give the cap back.
+    #[clap(name = "transfer")]
+    Transfer {
+        /// ID of the package's UpgradeCap
+        #[clap(name = "upgrade_cap_id")]
+        upgrade_cap_id: ObjectID,
+        /// Recipient address (or its alias)
+        #[clap(long)]
+        to: KeyIdentity,
+        /// Confirm the transfer. Without it, only a preview of the transfer is shown.
+        #[clap(long)]
+        yes: bool,
+
+        #[clap(flatten)]
+        payment: PaymentArgs,
+
+        #[clap(flatten)]
+        gas_data: GasDataArgs,
+
+        #[clap(flatten)]
+        processing: TxProcessingArgs,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestrictedPolicy {
+    /// Only allow upgrades that add new code, without changing existing function bodies
+    Additive,
+    /// Only allow upgrades that change dependencies
+    DepOnly,
+}
+
+/// The state of an UpgradeCap, as shown by `sui client upgrade-cap show`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeCapOutput {
+    pub upgrade_cap_id: ObjectID,
+    pub package_id: ObjectID,
+    pub version: u64,
+    pub policy: String,
+    pub owner: Owner,
+}
+
+impl RestrictedPolicy {
+    fn policy(self) -> UpgradePolicy {
+        match self {
+            RestrictedPolicy::Additive => UpgradePolicy::Additive,
+            RestrictedPolicy::DepOnly => UpgradePolicy::DepOnly,
+        }
+    }
+
+    fn action(self) -> UpgradeCapAction {
+        match self {
+            RestrictedPolicy::Additive => UpgradeCapAction::OnlyAdditiveUpgrades,
+            RestrictedPolicy::DepOnly => UpgradeCapAction::OnlyDepUpgrades,
+        }
+    }
+}
+
+impl UpgradeCapCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        let client = context.grpc_client()?;
+        let (upgrade_cap_id, tx_kind, payment, gas_data, processing) = match self {
+            UpgradeCapCommand::Show { upgrade_cap_id } => {
+                let (object, cap) = fetch_upgrade_cap(context, upgrade_cap_id).await?;
+                return Ok(SuiClientCommandResult::UpgradeCap(UpgradeCapOutput {
+                    upgrade_cap_id,
+                    package_id: cap.package.bytes,
+                    version: cap.version,
+                    policy: policy_name(cap.policy),
+                    owner: object.owner().clone(),
+                }));
+            }
+            UpgradeCapCommand::Restrict {
+                upgrade_cap_id,
+                policy,
+                yes,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (_, cap) = fetch_upgrade_cap(context, upgrade_cap_id).await?;
+                let preview = restrict_preview(&cap, policy)?;
+                confirm_irreversible(&preview, yes, &processing)?;
+                let tx_kind = client
+                    .transaction_builder()
+                    .upgrade_cap_tx_kind(upgrade_cap_id, policy.action())
+                    .await?;
+                (upgrade_cap_id, tx_kind, payment, gas_data, processing)
+            }
+            UpgradeCapCommand::MakeImmutable {
+                upgrade_cap_id,
+                yes,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (_, cap) = fetch_upgrade_cap(context, upgrade_cap_id).await?;
+                let preview = format!(
+                    "Making package {} immutable destroys UpgradeCap {upgrade_cap_id}. The \
+                     package will never be upgradeable again, and this cannot be undone.",
+                    cap.package.bytes
+                );
+                confirm_irreversible(&preview, yes, &processing)?;
+                let tx_kind = client
+                    .transaction_builder()
+                    .upgrade_cap_tx_kind(upgrade_cap_id, UpgradeCapAction::MakeImmutable)
+                    .await?;
+                (upgrade_cap_id, tx_kind, payment, gas_data, processing)
+            }
+            UpgradeCapCommand::Transfer {
+                upgrade_cap_id,
+                to,
+                yes,
+                payment,
+                gas_data,
+                processing,
+            } => {
+                let (_, cap) = fetch_upgrade_cap(context, upgrade_cap_id).await?;
+                let recipient = context.get_identity_address(Some(to))?;
+                let preview = transfer_preview(&cap, upgrade_cap_id, recipient);
+                confirm_irreversible(&preview, yes, &processing)?;
+                let tx_kind = client
+                    .transaction_builder()
+                    .transfer_object_tx_kind(upgrade_cap_id, recipient)
+                    .await?;
+                (upgrade_cap_id, tx_kind, payment, gas_data, processing)
+            }
+        };
+
+        let signer = context.get_object_owner(&upgrade_cap_id).await?;
+        let gas_payment = client
+            .transaction_builder()
+            .input_refs(&payment.gas)
+            .await?;
+        dry_run_or_execute_or_serialize(signer, tx_kind, context, gas_payment, gas_data, processing)
+            .await
+    }
+}
+
+async fn fetch_upgrade_cap(
+    context: &WalletContext,
+    upgrade_cap_id: ObjectID,
+) -> Result<(Object, UpgradeCap), anyhow::Error> {
+    let object = context
+        .grpc_client()?
+        .get_object(upgrade_cap_id)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch UpgradeCap {upgrade_cap_id}: {e}"))?;
+    let object_type = object
+        .struct_tag()
+        .ok_or_else(|| anyhow!("{upgrade_cap_id} is not a Move object"))?;
+    ensure!(
+        object_type == UpgradeCap::type_(),
+        "{upgrade_cap_id} is not an UpgradeCap, found {object_type}"
+    );
+    let cap = deserialize_move_object(&object)?;
+    Ok((object, cap))
+}
+
+fn policy_name(policy: u8) -> String {
+    match UpgradePolicy::try_from(policy) {
+        Ok(policy) => policy.to_string(),
+        Err(()) => format!("UNKNOWN ({policy})"),
+    }
+}
+
+/// Describes restricting `cap` to `policy`, failing if its policy is already as strict.
+fn restrict_preview(cap: &UpgradeCap, policy: RestrictedPolicy) -> Result<String, anyhow::Error> {
+    let target = policy.policy();
+    if cap.policy >= target as u8 {
+        bail!(
+            "The upgrade policy of package {} is already {}, which is at least as strict as {target}",
+            cap.package.bytes,
+            policy_name(cap.policy),
+        );
+    }
+    Ok(format!(
+        "Restricting the upgrade policy of package {} from {} to {target}. Upgrade policies can \
+         only become stricter, so this cannot be undone.",
+        cap.package.bytes,
+        policy_name(cap.policy),
+    ))
+}
+
+fn transfer_preview(cap: &UpgradeCap, upgrade_cap_id: ObjectID, recipient: SuiAddress) -> String {
+    format!(
+        "Transferring UpgradeCap {upgrade_cap_id} of package {} (policy {}) to {recipient}. Only \
+         {recipient} will be able to upgrade the package, restrict its policy or make it \
+         immutable, and only they can transfer the cap back.",
+        cap.package.bytes,
+        policy_name(cap.policy),
+    )
+}
+
+/// Prints `preview`, and fails unless the change was confirmed with `--yes` or the transaction
+/// will not be executed.
+fn confirm_irreversible(
+    preview: &str,
+    yes: bool,
+    processing: &TxProcessingArgs,
+) -> Result<(), anyhow::Error> {
+    eprintln!("{preview}");
+    let executes = !(processing.dry_run
+        || processing.dev_inspect
+        || processing.tx_digest
+        || processing.serialize_unsigned_transaction
+        || processing.serialize_signed_transaction);
+    ensure!(
+        yes || !executes,
+        "Re-run with --yes to confirm, or with --dry-run to preview the transaction"
+    );
+    Ok(())
+}
@@ -7,7 +7,7 @@ use std::{
     collections::{BTreeMap, HashSet},
     fmt::{self, Debug, Display, Formatter, Write},
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use sui_genesis_builder::validator_info::GenesisValidatorInfo;
@@ -18,7 +18,7 @@ use sui_rpc_api::Client;
 use sui_rpc_api::client::ExecutedTransaction;
 use sui_types::{
     SUI_SYSTEM_PACKAGE_ID,
-    base_types::{ObjectID, ObjectRef, SuiAddress},
+    base_types::{FullObjectRef, ObjectID, ObjectRef, SuiAddress},
     crypto::{AuthorityPublicKey, DEFAULT_EPOCH_ID, NetworkPublicKey, Signable},
     effects::TransactionEffectsAPI,
     multiaddr::Multiaddr,
@@ -28,6 +28,7 @@ use sui_types::{
 use tap::tap::TapOptional;
 
 use crate::fire_drill::get_gas_obj_ref;
+use crate::history_commands::record_transaction;
 use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
@@ -62,6 +63,7 @@ use sui_types::transaction::{CallArg, ObjectArg, Transaction, TransactionData};
 mod validator_tests;
 
 const DEFAULT_GAS_BUDGET: u64 = 200_000_000; // 0.2 SUI
+const MAX_COMMISSION_RATE: u64 = 2_000; // 20%, in basis points
 
 /// Arguments related to transaction processing
 #[derive(Args, Debug, Default)]
@@ -71,6 +73,9 @@ pub struct TxProcessingArgs {
     /// be used to execute transaction with `sui client execute-signed-tx --tx-bytes <TX_BYTES>`.
     #[arg(long)]
     pub serialize_unsigned_transaction: bool,
+    /// Simulate the transaction and print its effects instead of executing it.
+    #[arg(long, conflicts_with = "serialize_unsigned_transaction")]
+    pub dry_run: bool,
     /// Gas budget for this transaction
     #[clap(name = "gas-budget", long)]
     pub gas_budget: Option<u64>,
@@ -150,6 +155,42 @@ pub enum SuiValidatorCommand {
         #[clap(flatten)]
         tx_args: TxProcessingArgs,
     },
+    /// Set the commission rate charged on the staking rewards of the validator's stakers.
+    /// Effectuate from next epoch for active and pending validators, immediately for candidates.
+    #[clap(name = "set-commission-rate")]
+    SetCommissionRate {
+        /// New commission rate in basis points, e.g. 200 for 2%. At most 2000 (20%).
+        #[clap(name = "commission-rate")]
+        commission_rate: u64,
+        #[clap(flatten)]
+        tx_args: TxProcessingArgs,
+    },
+    /// Revoke the validator's current OperationCap and create a new one owned by the validator.
+    /// This takes back the rights delegated to whoever holds the current OperationCap.
+    #[clap(name = "rotate-operation-cap")]
+    RotateOperationCap {
+        #[clap(flatten)]
+        tx_args: TxProcessingArgs,
+    },
+    /// Transfer the validator's OperationCap to another address, delegating gas price updates
+    /// and validator reports to it.
+    #[clap(name = "transfer-operation-cap")]
+    TransferOperationCap {
+        /// The Sui Address receiving the OperationCap.
+        #[clap(name = "recipient")]
+        recipient: SuiAddress,
+        #[clap(flatten)]
+        tx_args: TxProcessingArgs,
+    },
+    /// Withdraw a StakedSui object owned by the sender, including its accrued rewards.
+    /// Validators receive their commission as StakedSui objects in their own staking pool.
+    #[clap(name = "withdraw-stake")]
+    WithdrawStake {
+        #[clap(name = "staked-sui-id")]
+        staked_sui_id: ObjectID,
+        #[clap(flatten)]
+        tx_args: TxProcessingArgs,
+    },
     /// Serialize the payload that is used to generate Proof of Possession.
     /// This is useful to take the payload offline for an Authority protocol keypair to sign.
     #[clap(name = "serialize-payload-pop")]
@@ -196,6 +237,30 @@ pub enum SuiValidatorCommand {
         #[clap(name = "gas-budget", long)]
         gas_budget: Option<u64>,
     },
+    /// Rotate the validator's protocol, network and worker keys for the next epoch.
+    /// Generates new key files next to the current ones, submits their public keys as the
+    /// validator's next epoch keys, and checks that they are reflected on-chain. The key files
+    /// in use are left untouched, so the running node keeps its keys until the end of the
+    /// epoch. Once the next epoch has started, run the command again with `--swap` to move the
+    /// new key files in place, then restart the node. Re-running the command after a failure
+    /// reuses the already generated key files.
+    #[clap(name = "rotate-keys")]
+    RotateKeys {
+        /// Directory holding the validator's `protocol.key`, `network.key` and `worker.key`.
+        #[clap(long, default_value = ".")]
+        key_dir: PathBuf,
+        /// Keys to rotate. All of them are rotated if not set.
+        #[clap(long, value_enum, value_delimiter = ',')]
+        keys: Vec<RotatedKey>,
+        /// Move the staged key files in place of the ones in use, keeping the previous keys as
+        /// backups. Refused until the staged keys are the validator's current keys on-chain,
+        /// which happens at the start of the epoch after they were staged.
+        #[clap(long)]
+        swap: bool,
+        /// Gas budget for each of the key update transactions.
+        #[clap(name = "gas-budget", long)]
+        gas_budget: Option<u64>,
+    },
     /// Update sui native bridge committee node url
     UpdateBridgeCommitteeNodeUrl {
         /// New node url to be registered in the on chain bridge object.
@@ -243,6 +308,22 @@ pub enum SuiValidatorCommandResponse {
         response: Option<ExecutedTransaction>,
         serialized_unsigned_transaction: Option<String>,
     },
+    SetCommissionRate {
+        response: Option<ExecutedTransaction>,
+        serialized_unsigned_transaction: Option<String>,
+    },
+    RotateOperationCap {
+        response: Option<ExecutedTransaction>,
+        serialized_unsigned_transaction: Option<String>,
+    },
+    TransferOperationCap {
+        response: Option<ExecutedTransaction>,
+        serialized_unsigned_transaction: Option<String>,
+    },
+    WithdrawStake {
+        response: Option<ExecutedTransaction>,
+        serialized_unsigned_transaction: Option<String>,
+    },
     SerializedPayload(String),
     DisplayGasPriceUpdateRawTxn {
         data: TransactionData,
@@ -256,6 +337,115 @@ pub enum SuiValidatorCommandResponse {
         execution_response: Option<ExecutedTransaction>,
         serialized_unsigned_transaction: Option<String>,
     },
+    RotateKeys {
+        responses: Vec<ExecutedTransaction>,
+        rotated_keys: Vec<RotatedKeyFile>,
+    },
+}
+
+/// A validator key that can be rotated with `sui validator rotate-keys`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotatedKey {
+    Protocol,
+    Network,
+    Worker,
+}
+
+impl RotatedKey {
+    const ALL: [RotatedKey; 3] = [
+        RotatedKey::Protocol,
+        RotatedKey::Network,
+        RotatedKey::Worker,
+    ];
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            RotatedKey::Protocol => "protocol.key",
+            RotatedKey::Network => "network.key",
+            RotatedKey::Worker => "worker.key",
+        }
+    }
+}
+
+/// Location of a rotated key on disk.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RotatedKeyFile {
+    pub key: RotatedKey,
+    /// Key file in use by the node, holding the new key once swapped.
+    pub path: PathBuf,
+    /// Newly generated key file, consumed by `--swap`.
+    pub staged_path: PathBuf,
+    /// Where the previous key was moved to, if the files were swapped.
+    pub backup_path: Option<PathBuf>,
+}
+
+const STAGED_KEY_SUFFIX: &str = "next";
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Move every staged key file in place of the live one, moving the live one to a backup path
+/// first. If any step fails, the files that were already swapped are restored so that the
+/// validator is never left with a mix of old and new keys.
+pub(crate) fn swap_key_files(files: &mut [RotatedKeyFile], backup_suffix: &str) -> Result<()> {
+    for file in files.iter() {
+        if !file.staged_path.exists() {
+            bail!("Staged key file {:?} does not exist", file.staged_path);
+        }
+        let backup_path = path_with_suffix(&file.path, backup_suffix);
+        if backup_path.exists() {
+            bail!("Backup key file {:?} already exists", backup_path);
+        }
+    }
+
+    // (backup, live, staged) for every file that has been (partially) swapped.
+    let mut swapped: Vec<(PathBuf, PathBuf, PathBuf)> = vec![];
+    let mut result = Ok(());
+    for file in files.iter() {
+        let backup_path = path_with_suffix(&file.path, backup_suffix);
+        if let Err(e) = fs::rename(&file.path, &backup_path) {
+            result = Err(anyhow!("Failed to back up {:?}: {e}", file.path));
+            break;
+        }
+        swapped.push((backup_path, file.path.clone(), file.staged_path.clone()));
+        if let Err(e) = fs::rename(&file.staged_path, &file.path) {
+            result = Err(anyhow!(
+                "Failed to move {:?} to {:?}: {e}",
+                file.staged_path,
+                file.path
+            ));
+            break;
+        }
+    }
+
+    if let Err(e) = result {
+        for (backup, live, staged) in swapped.into_iter().rev() {
+            if live.exists()
+                && !staged.exists()
+                && let Err(re) = fs::rename(&live, &staged)
+            {
+                eprintln!("Failed to restore staged key file {:?}: {re}", staged);
+            }
+            if let Err(re) = fs::rename(&backup, &live) {
+                eprintln!(
+                    "Failed to restore key file {:?} from {:?}: {re}",
+                    live, backup
+                );
+            }
+        }
+        return Err(e);
+    }
+
+    for file in files.iter_mut() {
+        file.backup_path = Some(path_with_suffix(&file.path, backup_suffix));
+    }
+    Ok(())
 }
 
 fn make_key_files(
@@ -419,6 +609,7 @@ impl SuiValidatorCommand {
                     args,
                     gas_budget,
                     tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
                 )
                 .await?;
                 SuiValidatorCommandResponse::BecomeCandidate {
@@ -435,6 +626,7 @@ impl SuiValidatorCommand {
                     vec![],
                     gas_budget,
                     tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
                 )
                 .await?;
                 SuiValidatorCommandResponse::JoinCommittee {
@@ -454,6 +646,7 @@ impl SuiValidatorCommand {
                     vec![],
                     gas_budget,
                     tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
                 )
                 .await?;
                 SuiValidatorCommandResponse::LeaveCommittee {
@@ -480,6 +673,7 @@ impl SuiValidatorCommand {
                     metadata,
                     gas_budget,
                     tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
                 )
                 .await?;
                 SuiValidatorCommandResponse::UpdateMetadata {
@@ -500,6 +694,7 @@ impl SuiValidatorCommand {
                     gas_price,
                     gas_budget,
                     tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
                 )
                 .await?;
                 SuiValidatorCommandResponse::UpdateGasPrice {
@@ -523,6 +718,7 @@ impl SuiValidatorCommand {
                     undo_report,
                     gas_budget,
                     tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
                 )
                 .await?;
                 SuiValidatorCommandResponse::ReportValidator {
@@ -531,6 +727,82 @@ impl SuiValidatorCommand {
                 }
             }
 
+            SuiValidatorCommand::SetCommissionRate {
+                commission_rate,
+                tx_args,
+            } => {
+                let gas_budget = tx_args.gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
+                let (response, serialized_unsigned_transaction) = set_commission_rate(
+                    context,
+                    commission_rate,
+                    gas_budget,
+                    tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
+                )
+                .await?;
+                SuiValidatorCommandResponse::SetCommissionRate {
+                    response,
+                    serialized_unsigned_transaction,
+                }
+            }
+
+            SuiValidatorCommand::RotateOperationCap { tx_args } => {
+                let _status = check_status(
+                    context,
+                    HashSet::from([ValidatorStatus::Active, ValidatorStatus::Pending]),
+                )
+                .await?;
+                let gas_budget = tx_args.gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
+                let (response, serialized_unsigned_transaction) = call_0x5(
+                    context,
+                    "rotate_operation_cap",
+                    vec![],
+                    gas_budget,
+                    tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
+                )
+                .await?;
+                SuiValidatorCommandResponse::RotateOperationCap {
+                    response,
+                    serialized_unsigned_transaction,
+                }
+            }
+
+            SuiValidatorCommand::TransferOperationCap { recipient, tx_args } => {
+                let gas_budget = tx_args.gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
+                let (response, serialized_unsigned_transaction) = transfer_operation_cap(
+                    context,
+                    recipient,
+                    gas_budget,
+                    tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
+                )
+                .await?;
+                SuiValidatorCommandResponse::TransferOperationCap {
+                    response,
+                    serialized_unsigned_transaction,
+                }
+            }
+
+            SuiValidatorCommand::WithdrawStake {
+                staked_sui_id,
+                tx_args,
+            } => {
+                let gas_budget = tx_args.gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
+                let (response, serialized_unsigned_transaction) = withdraw_stake(
+                    context,
+                    staked_sui_id,
+                    gas_budget,
+                    tx_args.serialize_unsigned_transaction,
+                    tx_args.dry_run,
+                )
+                .await?;
+                SuiValidatorCommandResponse::WithdrawStake {
+                    response,
+                    serialized_unsigned_transaction,
+                }
+            }
+
             SuiValidatorCommand::SerializePayloadForPoP {
                 account_address,
                 protocol_public_key,
@@ -648,8 +920,9 @@ impl SuiValidatorCommand {
                         serialized_unsigned_transaction: Some(serialized_data),
                     }
                 } else {
-                    let tx = context.sign_transaction(&tx_data).await;
+                    let tx = context.try_sign_transaction(&tx_data).await?;
                     let response = context.execute_transaction_must_succeed(tx).await;
+                    record_transaction(context, &response, None);
                     println!(
                         "Committee registration successful. Transaction digest: {}",
                         response.transaction.digest()
@@ -660,6 +933,27 @@ impl SuiValidatorCommand {
                     }
                 }
             }
+            SuiValidatorCommand::RotateKeys {
+                key_dir,
+                keys,
+                swap,
+                gas_budget,
+            } => {
+                let gas_budget = gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
+                let keys = if keys.is_empty() {
+                    RotatedKey::ALL.to_vec()
+                } else {
+                    RotatedKey::ALL
+                        .into_iter()
+                        .filter(|k| keys.contains(k))
+                        .collect()
+                };
+                if swap {
+                    swap_rotated_keys(context, &key_dir, keys).await?
+                } else {
+                    stage_key_rotation(context, &key_dir, keys, gas_budget).await?
+                }
+            }
             SuiValidatorCommand::UpdateBridgeCommitteeNodeUrl {
                 bridge_authority_url,
                 print_unsigned_transaction_only,
@@ -726,8 +1020,9 @@ impl SuiValidatorCommand {
                         serialized_unsigned_transaction: Some(serialized_data),
                     }
                 } else {
-                    let tx = context.sign_transaction(&tx_data).await;
+                    let tx = context.try_sign_transaction(&tx_data).await?;
                     let response = context.execute_transaction_must_succeed(tx).await;
+                    record_transaction(context, &response, None);
                     println!(
                         "Update Bridge validator node URL successful. Transaction digest: {}",
                         response.transaction.digest()
@@ -811,6 +1106,7 @@ async fn update_gas_price(
     gas_price: u64,
     gas_budget: u64,
     serialize_unsigned_transaction: bool,
+    dry_run: bool,
 ) -> Result<(Option<ExecutedTransaction>, Option<String>)> {
     let (_status, _summary, cap_obj_ref) = get_cap_object_ref(context, operation_cap_id).await?;
 
@@ -826,6 +1122,7 @@ async fn update_gas_price(
         args,
         gas_budget,
         serialize_unsigned_transaction,
+        dry_run,
     )
     .await
 }
@@ -837,6 +1134,7 @@ async fn report_validator(
     undo_report: bool,
     gas_budget: u64,
     serialize_unsigned_transaction: bool,
+    dry_run: bool,
 ) -> Result<(Option<ExecutedTransaction>, Option<String>)> {
     let (status, summary, cap_obj_ref) = get_cap_object_ref(context, operation_cap_id).await?;
 
@@ -864,6 +1162,107 @@ async fn report_validator(
         args,
         gas_budget,
         serialize_unsigned_transaction,
+        dry_run,
+    )
+    .await
+}
+
+async fn set_commission_rate(
+    context: &mut WalletContext,
+    commission_rate: u64,
+    gas_budget: u64,
+    serialize_unsigned_transaction: bool,
+    dry_run: bool,
+) -> Result<(Option<ExecutedTransaction>, Option<String>)> {
+    if commission_rate > MAX_COMMISSION_RATE {
+        bail!(
+            "Commission rate {commission_rate} is above the maximum of {MAX_COMMISSION_RATE} basis points"
+        );
+    }
+    let sui_client = context.grpc_client()?;
+    let validator_address = context.active_address()?;
+    // Active and pending validators set the rate for the next epoch, anyone else is expected
+    // to be a candidate whose rate can be set right away.
+    let function = match get_validator_summary(&sui_client, validator_address).await? {
+        Some(_) => "request_set_commission_rate",
+        None => "set_candidate_validator_commission_rate",
+    };
+    let args = vec![CallArg::Pure(bcs::to_bytes(&commission_rate).unwrap())];
+    call_0x5(
+        context,
+        function,
+        args,
+        gas_budget,
+        serialize_unsigned_transaction,
+        dry_run,
+    )
+    .await
+}
+
+async fn transfer_operation_cap(
+    context: &mut WalletContext,
+    recipient: SuiAddress,
+    gas_budget: u64,
+    serialize_unsigned_transaction: bool,
+    dry_run: bool,
+) -> Result<(Option<ExecutedTransaction>, Option<String>)> {
+    let sender = context.active_address()?;
+    let (_status, _summary, cap_obj_ref) = get_cap_object_ref(context, None).await?;
+    let sui_client = context.grpc_client()?;
+    let rgp = sui_client.get_reference_gas_price().await?;
+    let gas_obj_ref = get_gas_obj_ref(sender, &sui_client, gas_budget).await?;
+    let tx_data = TransactionData::new_transfer(
+        recipient,
+        FullObjectRef::from_fastpath_ref(cap_obj_ref),
+        sender,
+        gas_obj_ref,
+        gas_budget,
+        rgp,
+    );
+    execute_or_serialize(
+        context,
+        sender,
+        tx_data,
+        serialize_unsigned_transaction,
+        dry_run,
+    )
+    .await
+}
+
+async fn withdraw_stake(
+    context: &mut WalletContext,
+    staked_sui_id: ObjectID,
+    gas_budget: u64,
+    serialize_unsigned_transaction: bool,
+    dry_run: bool,
+) -> Result<(Option<ExecutedTransaction>, Option<String>)> {
+    let sender = context.active_address()?;
+    let staked_sui = context.grpc_client()?.get_object(staked_sui_id).await?;
+    if !staked_sui
+        .data
+        .try_as_move()
+        .is_some_and(|o| o.is_staked_sui())
+    {
+        bail!("Object {staked_sui_id} is not a StakedSui object");
+    }
+    if staked_sui.owner() != &Owner::AddressOwner(sender) {
+        bail!(
+            "StakedSui {} is not owned by the sender address {} but {:?}",
+            staked_sui_id,
+            sender,
+            staked_sui.owner()
+        );
+    }
+    let args = vec![CallArg::Object(ObjectArg::ImmOrOwnedObject(
+        staked_sui.compute_object_reference(),
+    ))];
+    call_0x5(
+        context,
+        "request_withdraw_stake",
+        args,
+        gas_budget,
+        serialize_unsigned_transaction,
+        dry_run,
     )
     .await
 }
@@ -933,14 +1332,42 @@ async fn call_0x5(
     call_args: Vec<CallArg>,
     gas_budget: u64,
     serialize_unsigned_transaction: bool,
+    dry_run: bool,
 ) -> anyhow::Result<(Option<ExecutedTransaction>, Option<String>)> {
     let sender = context.active_address()?;
     let tx_data =
         construct_unsigned_0x5_txn(context, sender, function, call_args, gas_budget).await?;
+    execute_or_serialize(
+        context,
+        sender,
+        tx_data,
+        serialize_unsigned_transaction,
+        dry_run,
+    )
+    .await
+}
+
+/// Serialize, simulate, or sign and execute `tx_data` on behalf of `sender`.
+async fn execute_or_serialize(
+    context: &mut WalletContext,
+    sender: SuiAddress,
+    tx_data: TransactionData,
+    serialize_unsigned_transaction: bool,
+    dry_run: bool,
+) -> anyhow::Result<(Option<ExecutedTransaction>, Option<String>)> {
     if serialize_unsigned_transaction {
         let serialized_data = Base64::encode(bcs::to_bytes(&tx_data)?);
         return Ok((None, Some(serialized_data)));
     }
+    if dry_run {
+        let response = context
+            .grpc_client()?
+            .simulate_transaction(&tx_data, true, false)
+            .await?;
+        println!("Dry run only, the transaction was not executed.");
+        return Ok((Some(response.transaction), None));
+    }
+    context.ensure_can_sign()?;
     let signature = context
         .config
         .keystore
@@ -951,6 +1378,7 @@ async fn call_0x5(
         .grpc_client()?
         .execute_transaction_and_wait_for_checkpoint(&transaction)
         .await?;
+    record_transaction(context, &response, None);
     Ok((Some(response), None))
 }
 
@@ -983,6 +1411,22 @@ impl Display for SuiValidatorCommandResponse {
             | SuiValidatorCommandResponse::ReportValidator {
                 response,
                 serialized_unsigned_transaction,
+            }
+            | SuiValidatorCommandResponse::SetCommissionRate {
+                response,
+                serialized_unsigned_transaction,
+            }
+            | SuiValidatorCommandResponse::RotateOperationCap {
+                response,
+                serialized_unsigned_transaction,
+            }
+            | SuiValidatorCommandResponse::TransferOperationCap {
+                response,
+                serialized_unsigned_transaction,
+            }
+            | SuiValidatorCommandResponse::WithdrawStake {
+                response,
+                serialized_unsigned_transaction,
             } => {
                 if let Some(response) = response {
                     write!(writer, "{}", write_transaction_response(response)?)?;
@@ -1025,6 +1469,28 @@ impl Display for SuiValidatorCommandResponse {
                     )?;
                 }
             }
+            SuiValidatorCommandResponse::RotateKeys {
+                responses,
+                rotated_keys,
+            } => {
+                for response in responses {
+                    write!(writer, "{}", write_transaction_response(response)?)?;
+                }
+                for file in rotated_keys {
+                    match &file.backup_path {
+                        Some(backup) => writeln!(
+                            writer,
+                            "Rotated {:?} key {:?}, previous key backed up to {:?}",
+                            file.key, file.path, backup
+                        )?,
+                        None => writeln!(
+                            writer,
+                            "Staged {:?} key at {:?}",
+                            file.key, file.staged_path
+                        )?,
+                    }
+                }
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -1220,6 +1686,7 @@ async fn update_metadata(
     metadata: MetadataUpdate,
     gas_budget: u64,
     serialize_unsigned_transaction: bool,
+    dry_run: bool,
 ) -> anyhow::Result<(Option<ExecutedTransaction>, Option<String>)> {
     use ValidatorStatus::*;
     match metadata {
@@ -1231,6 +1698,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1244,6 +1712,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1257,6 +1726,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1270,6 +1740,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1286,6 +1757,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1301,6 +1773,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1317,6 +1790,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1332,6 +1806,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1348,6 +1823,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1364,6 +1840,7 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
@@ -1388,12 +1865,212 @@ async fn update_metadata(
                 args,
                 gas_budget,
                 serialize_unsigned_transaction,
+                dry_run,
             )
             .await
         }
     }
 }
 
+/// The staged key files of `keys` under `key_dir`.
+fn rotated_key_files(key_dir: &Path, keys: Vec<RotatedKey>) -> Result<Vec<RotatedKeyFile>> {
+    keys.into_iter()
+        .map(|key| {
+            let path = key_dir.join(key.file_name());
+            if !path.exists() {
+                bail!("Key file {:?} to rotate does not exist", path);
+            }
+            Ok(RotatedKeyFile {
+                key,
+                staged_path: path_with_suffix(&path, STAGED_KEY_SUFFIX),
+                path,
+                backup_path: None,
+            })
+        })
+        .collect()
+}
+
+/// The public key in a staged key file.
+fn staged_public_key(file: &RotatedKeyFile) -> Result<Vec<u8>> {
+    Ok(match file.key {
+        RotatedKey::Protocol => read_authority_keypair_from_file(&file.staged_path)?
+            .public()
+            .as_bytes()
+            .to_vec(),
+        RotatedKey::Network | RotatedKey::Worker => {
+            read_network_keypair_from_file(&file.staged_path)?
+                .public()
+                .as_bytes()
+                .to_vec()
+        }
+    })
+}
+
+/// The validator's on-chain key for `key`, in the current epoch or, if `next_epoch` is set, in
+/// the next one.
+fn on_chain_key(summary: &proto::Validator, key: RotatedKey, next_epoch: bool) -> Option<&[u8]> {
+    let key = match (key, next_epoch) {
+        (RotatedKey::Protocol, false) => summary.protocol_public_key_opt(),
+        (RotatedKey::Network, false) => summary.network_public_key_opt(),
+        (RotatedKey::Worker, false) => summary.worker_public_key_opt(),
+        (RotatedKey::Protocol, true) => summary.next_epoch_protocol_public_key_opt(),
+        (RotatedKey::Network, true) => summary.next_epoch_network_public_key_opt(),
+        (RotatedKey::Worker, true) => summary.next_epoch_worker_public_key_opt(),
+    };
+    key.map(|k| k.as_ref())
+}
+
+/// Check that the staged keys are the validator's current keys on-chain, so that swapping them
+/// in does not leave the node with keys its committee does not know about.
+pub(crate) fn check_staged_keys_active(
+    summary: &proto::Validator,
+    staged_keys: &[(RotatedKey, Vec<u8>)],
+) -> Result<()> {
+    for (key, public_key) in staged_keys {
+        if on_chain_key(summary, *key, false) == Some(public_key.as_slice()) {
+            continue;
+        }
+        if on_chain_key(summary, *key, true) == Some(public_key.as_slice()) {
+            bail!(
+                "The staged {:?} key only takes effect in the next epoch. Swap the key files once \
+                 the next epoch has started.",
+                key
+            );
+        }
+        bail!(
+            "The staged {:?} key is neither the current nor the next epoch key of the validator",
+            key
+        );
+    }
+    Ok(())
+}
+
+async fn stage_key_rotation(
+    context: &mut WalletContext,
+    key_dir: &Path,
+    keys: Vec<RotatedKey>,
+    gas_budget: u64,
+) -> Result<SuiValidatorCommandResponse> {
+    use ValidatorStatus::*;
+    check_status(context, HashSet::from([Pending, Active])).await?;
+    let sui_address = context.active_address()?;
+
+    let rotated_keys = rotated_key_files(key_dir, keys)?;
+    for file in &rotated_keys {
+        make_key_files(
+            file.staged_path.clone(),
+            file.key == RotatedKey::Protocol,
+            None,
+        )?;
+    }
+
+    // Submit the next epoch public keys, and remember what we expect to read back.
+    let mut responses = vec![];
+    let mut expected = vec![];
+    for file in &rotated_keys {
+        let public_key = staged_public_key(file)?;
+        let (function, args) = match file.key {
+            RotatedKey::Protocol => {
+                let keypair = read_authority_keypair_from_file(&file.staged_path)?;
+                let pop = generate_proof_of_possession(&keypair, sui_address);
+                let args = vec![
+                    CallArg::Pure(
+                        bcs::to_bytes(&AuthorityPublicKeyBytes::from_bytes(&public_key)?).unwrap(),
+                    ),
+                    CallArg::Pure(bcs::to_bytes(&pop.as_ref().to_vec()).unwrap()),
+                ];
+                ("update_validator_next_epoch_protocol_pubkey", args)
+            }
+            RotatedKey::Network | RotatedKey::Worker => {
+                let function = if file.key == RotatedKey::Network {
+                    "update_validator_next_epoch_network_pubkey"
+                } else {
+                    "update_validator_next_epoch_worker_pubkey"
+                };
+                let args = vec![CallArg::Pure(bcs::to_bytes(&public_key).unwrap())];
+                (function, args)
+            }
+        };
+        let (response, _) = call_0x5(context, function, args, gas_budget, false).await?;
+        // Safe to unwrap as the transaction was not serialized only.
+        let response = response.unwrap();
+        if !response.effects.status().is_ok() {
+            bail!(
+                "Transaction {} updating the next epoch {:?} key failed: {:?}",
+                response.transaction.digest(),
+                file.key,
+                response.effects.status()
+            );
+        }
+        println!(
+            "Submitted next epoch {:?} key. Transaction digest: {}",
+            file.key,
+            response.transaction.digest()
+        );
+        responses.push(response);
+        expected.push((file.key, public_key));
+    }
+
+    // Make sure the system state reflects the new keys, so that they take effect next epoch.
+    let sui_client = context.grpc_client()?;
+    let (_, summary) = get_validator_summary(&sui_client, sui_address)
+        .await?
+        .ok_or_else(|| anyhow!("{} is not a validator.", sui_address))?;
+    for (key, public_key) in &expected {
+        if on_chain_key(&summary, *key, true) != Some(public_key.as_slice()) {
+            bail!(
+                "Next epoch {:?} key of validator {} does not match the submitted key",
+                key,
+                sui_address
+            );
+        }
+    }
+
+    let epoch = sui_client.get_current_epoch().await?;
+    println!(
+        "New keys staged for epoch {}. The key files in use were left untouched. Once epoch {} \
+         has started, run `sui validator rotate-keys --swap` and restart the node.",
+        epoch + 1,
+        epoch + 1
+    );
+
+    Ok(SuiValidatorCommandResponse::RotateKeys {
+        responses,
+        rotated_keys,
+    })
+}
+
+async fn swap_rotated_keys(
+    context: &mut WalletContext,
+    key_dir: &Path,
+    keys: Vec<RotatedKey>,
+) -> Result<SuiValidatorCommandResponse> {
+    use ValidatorStatus::*;
+    check_status(context, HashSet::from([Pending, Active])).await?;
+    let sui_address = context.active_address()?;
+
+    let mut rotated_keys = rotated_key_files(key_dir, keys)?;
+    let staged_keys = rotated_keys
+        .iter()
+        .map(|file| Ok((file.key, staged_public_key(file)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let sui_client = context.grpc_client()?;
+    let (_, summary) = get_validator_summary(&sui_client, sui_address)
+        .await?
+        .ok_or_else(|| anyhow!("{} is not a validator.", sui_address))?;
+    check_staged_keys_active(&summary, &staged_keys)?;
+
+    let epoch = sui_client.get_current_epoch().await?;
+    swap_key_files(&mut rotated_keys, &format!("epoch-{epoch}.bak"))?;
+    println!("Key files swapped. Restart the node to pick up the new keys.");
+
+    Ok(SuiValidatorCommandResponse::RotateKeys {
+        responses: vec![],
+        rotated_keys,
+    })
+}
+
 async fn check_status(
     context: &mut WalletContext,
     allowed_status: HashSet<ValidatorStatus>,
@@ -23,6 +23,28 @@ pub struct Accumulator {
     pub children: Vec<Accumulator>,
 }
 
+/// A module or function whose verification used a large share of the ticks allowed for its scope.
+#[derive(Clone, Debug, Serialize)]
+pub struct NearLimit {
+    /// The module name, or `module::function` for functions.
+    pub name: String,
+    #[serde(skip)]
+    pub scope: Scope,
+    pub ticks: u128,
+    pub limit: u128,
+}
+
+impl NearLimit {
+    pub fn percent(&self) -> u128 {
+        percent_of_limit(self.ticks, self.limit)
+    }
+}
+
+/// `ticks` as a percentage of `limit`, rounded down.
+pub fn percent_of_limit(ticks: u128, limit: u128) -> u128 {
+    ticks.saturating_mul(100) / limit.max(1)
+}
+
 impl AccumulatingMeter {
     pub fn new() -> Self {
         Self {
@@ -76,6 +98,52 @@ impl Accumulator {
 
         curr
     }
+
+    /// The modules and functions within this package scope that used at least `threshold_percent`
+    /// of `module_limit` or `function_limit` respectively, closest to their limit first.
+    pub fn near_limit(
+        &self,
+        module_limit: Option<u128>,
+        function_limit: Option<u128>,
+        threshold_percent: u8,
+    ) -> Vec<NearLimit> {
+        let is_near = |ticks, limit| percent_of_limit(ticks, limit) >= threshold_percent as u128;
+
+        let mut near = vec![];
+        for module in self.children.iter().filter(|c| c.scope == Scope::Module) {
+            if let Some(limit) = module_limit
+                && is_near(module.ticks, limit)
+            {
+                near.push(NearLimit {
+                    name: module.name.clone(),
+                    scope: Scope::Module,
+                    ticks: module.ticks,
+                    limit,
+                });
+            }
+
+            let Some(limit) = function_limit else {
+                continue;
+            };
+            for function in module
+                .children
+                .iter()
+                .filter(|c| c.scope == Scope::Function)
+            {
+                if is_near(function.ticks, limit) {
+                    near.push(NearLimit {
+                        name: format!("{}::{}", module.name, function.name),
+                        scope: Scope::Function,
+                        ticks: function.ticks,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        near.sort_by_key(|n| std::cmp::Reverse(n.percent()));
+        near
+    }
 }
 
 impl Meter for AccumulatingMeter {
@@ -97,3 +165,59 @@ impl Meter for AccumulatingMeter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acc(name: &str, scope: Scope, ticks: u128, children: Vec<Accumulator>) -> Accumulator {
+        Accumulator {
+            name: name.to_string(),
+            scope,
+            ticks,
+            children,
+        }
+    }
+
+    #[test]
+    fn near_limit_scopes() {
+        let package = acc(
+            "pkg",
+            Scope::Package,
+            1_500,
+            vec![
+                acc(
+                    "a",
+                    Scope::Module,
+                    950,
+                    vec![
+                        acc("f", Scope::Function, 850, vec![]),
+                        acc("g", Scope::Function, 100, vec![]),
+                    ],
+                ),
+                acc(
+                    "b",
+                    Scope::Module,
+                    550,
+                    vec![acc("h", Scope::Function, 900, vec![])],
+                ),
+            ],
+        );
+
+        let near: Vec<_> = package
+            .near_limit(Some(1_000), Some(1_000), 80)
+            .into_iter()
+            .map(|n| (n.name, n.percent()))
+            .collect();
+        assert_eq!(
+            near,
+            vec![
+                ("a".to_string(), 95),
+                ("b::h".to_string(), 90),
+                ("a::f".to_string(), 85),
+            ]
+        );
+
+        assert!(package.near_limit(None, None, 0).is_empty());
+    }
+}
@@ -18,6 +18,7 @@ use sui::client_commands::{
     GasDataArgs, PaymentArgs, PublishArgs, TestPublishArgs, TxProcessingArgs, UpgradeArgs,
 };
 use sui::client_ptb::ptb::PTB;
+use sui::deny_list_commands::{DenyListCheckOutput, DenyListCommand};
 use sui::sui_commands::RpcArgs;
 use sui_keys::key_identity::KeyIdentity;
 use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
@@ -46,6 +47,7 @@ use std::{fs, io};
 use sui::{
     client_commands::{
         SuiClientCommandResult, SuiClientCommands, SwitchResponse, estimate_gas_budget,
+        resolve_package_id_by_name,
     },
     sui_commands::{SuiCommand, parse_host_port},
 };
@@ -621,11 +623,14 @@ async fn test_ptb_publish_and_complex_arg_resolution() -> Result<(), anyhow::Err
     let package_id_str = package.0.0;
 
     let start_call_result = SuiClientCommands::Call {
-        package: package.0.0,
+        package: Some(package.0.0),
+        package_name: None,
+        package_path: None,
         module: "test_module".to_string(),
         function: "new_shared".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
@@ -921,11 +926,14 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Test case with no gas specified
     let resp = SuiClientCommands::Call {
-        package,
+        package: Some(package),
+        package_name: None,
+        package_path: None,
         module: "object_basics".to_string(),
         function: "create".to_string(),
         type_args: vec![],
         args,
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -956,11 +964,14 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     }
 
     let resp = SuiClientCommands::Call {
-        package,
+        package: Some(package),
+        package_name: None,
+        package_path: None,
         module: "object_basics".to_string(),
         function: "create".to_string(),
         type_args: vec![],
         args: args.to_vec(),
+        presets: None,
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -984,11 +995,14 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     ];
 
     let resp = SuiClientCommands::Call {
-        package,
+        package: Some(package),
+        package_name: None,
+        package_path: None,
         module: "object_basics".to_string(),
         function: "transfer".to_string(),
         type_args: vec![],
         args: args.to_vec(),
+        presets: None,
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -1009,11 +1023,14 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     ];
 
     let resp = SuiClientCommands::Call {
-        package,
+        package: Some(package),
+        package_name: None,
+        package_path: None,
         module: "object_basics".to_string(),
         function: "transfer".to_string(),
         type_args: vec![],
         args: args.to_vec(),
+        presets: None,
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -1045,11 +1062,14 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     ];
 
     SuiClientCommands::Call {
-        package,
+        package: Some(package),
+        package_name: None,
+        package_path: None,
         module: "object_basics".to_string(),
         function: "transfer".to_string(),
         type_args: vec![],
         args: args.to_vec(),
+        presets: None,
         payment: PaymentArgs { gas: vec![gas] },
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -1067,11 +1087,14 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
     ];
 
     let result = SuiClientCommands::Call {
-        package,
+        package: Some(package),
+        package_name: None,
+        package_path: None,
         module: "object_basics".to_string(),
         function: "create".to_string(),
         type_args: vec![],
         args,
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -1299,11 +1322,14 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
 
     // Start and then receive the object
     let start_call_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "sod".to_string(),
         function: "start".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1321,11 +1347,14 @@ async fn test_delete_shared_object() -> Result<(), anyhow::Error> {
     };
 
     let delete_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "sod".to_string(),
         function: "delete".to_string(),
         type_args: vec![],
         args: vec![SuiJsonValue::from_str(&shared_id.to_string()).unwrap()],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1408,11 +1437,14 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
 
     // Start and then receive the object
     let start_call_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "tto".to_string(),
         function: "start".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1444,7 +1476,9 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
         };
 
     let receive_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "tto".to_string(),
         function: "receiver".to_string(),
         type_args: vec![],
@@ -1452,6 +1486,7 @@ async fn test_receive_argument() -> Result<(), anyhow::Error> {
             SuiJsonValue::from_str(&parent.0.to_string()).unwrap(),
             SuiJsonValue::from_str(&child.0.to_string()).unwrap(),
         ],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1534,11 +1569,14 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
 
     // Start and then receive the object
     let start_call_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "tto".to_string(),
         function: "start".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1570,7 +1608,9 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
         };
 
     let receive_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "tto".to_string(),
         function: "invalid_call_immut_ref".to_string(),
         type_args: vec![],
@@ -1578,6 +1618,7 @@ async fn test_receive_argument_by_immut_ref() -> Result<(), anyhow::Error> {
             SuiJsonValue::from_str(&parent.0.to_string()).unwrap(),
             SuiJsonValue::from_str(&child.0.to_string()).unwrap(),
         ],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1660,11 +1701,14 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
 
     // Start and then receive the object
     let start_call_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "tto".to_string(),
         function: "start".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -1696,7 +1740,9 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
         };
 
     let receive_result = SuiClientCommands::Call {
-        package: package_id.0,
+        package: Some(package_id.0),
+        package_name: None,
+        package_path: None,
         module: "tto".to_string(),
         function: "invalid_call_mut_ref".to_string(),
         type_args: vec![],
@@ -1704,6 +1750,7 @@ async fn test_receive_argument_by_mut_ref() -> Result<(), anyhow::Error> {
             SuiJsonValue::from_str(&parent.0.to_string()).unwrap(),
             SuiJsonValue::from_str(&child.0.to_string()).unwrap(),
         ],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -2478,6 +2525,135 @@ async fn test_remove_address_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_watch_address_command() -> Result<(), anyhow::Error> {
+    let mut cluster = TestClusterBuilder::new().build().await;
+    let sender = cluster.get_address_0();
+    let rgp = cluster.get_reference_gas_price().await;
+    let context = cluster.wallet_mut();
+
+    let watched = SuiAddress::random_for_testing_only();
+    SuiClientCommands::WatchAddress {
+        address: watched,
+        alias: Some("watched".to_string()),
+    }
+    .execute(context)
+    .await?;
+
+    // Watched addresses resolve by alias and can become the active address.
+    assert_eq!(
+        context.get_identity_address(Some(KeyIdentity::Alias("watched".to_string())))?,
+        watched
+    );
+    SuiClientCommands::Switch {
+        address: Some(KeyIdentity::Alias("watched".to_string())),
+        env: None,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.active_address()?, watched);
+
+    // Watching the same address twice, or one with a key, is rejected.
+    assert!(
+        SuiClientCommands::WatchAddress {
+            address: watched,
+            alias: None,
+        }
+        .execute(context)
+        .await
+        .is_err()
+    );
+    assert!(
+        SuiClientCommands::WatchAddress {
+            address: sender,
+            alias: None,
+        }
+        .execute(context)
+        .await
+        .is_err()
+    );
+
+    // Signing on behalf of a watched address fails.
+    let coin = context.gas_objects(sender).await?.first().unwrap().1.id();
+    let result = SuiClientCommands::TransferSui {
+        to: KeyIdentity::Address(sender),
+        sui_coin_object_id: coin,
+        amount: Some(1),
+        gas_data: GasDataArgs {
+            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            ..Default::default()
+        },
+        processing: TxProcessingArgs {
+            sender: Some(watched),
+            ..Default::default()
+        },
+    }
+    .execute(context)
+    .await;
+    assert!(result.is_err());
+
+    SuiClientCommands::RemoveAddress {
+        alias_or_address: "watched".to_string(),
+    }
+    .execute(context)
+    .await?;
+    assert!(context.config.watch_only.is_empty());
+    assert_ne!(context.active_address()?, watched);
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_read_only_refuses_to_sign() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let sender = cluster.get_address_0();
+    let rgp = cluster.get_reference_gas_price().await;
+    let mut context = cluster.wallet.with_read_only(true);
+
+    // Queries still work.
+    SuiClientCommands::Gas {
+        address: Some(KeyIdentity::Address(sender)),
+    }
+    .execute(&mut context)
+    .await?;
+
+    let coin = context
+        .gas_objects(sender)
+        .await?
+        .first()
+        .unwrap()
+        .1
+        .clone();
+    let err = SuiClientCommands::TransferSui {
+        to: KeyIdentity::Address(sender),
+        sui_coin_object_id: coin.id(),
+        amount: Some(1),
+        gas_data: GasDataArgs {
+            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            ..Default::default()
+        },
+        processing: TxProcessingArgs::default(),
+    }
+    .execute(&mut context)
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("read-only"), "{err}");
+
+    // Signing through the context directly reports the same error instead of panicking.
+    let tx_data = TransactionData::new_transfer_sui(
+        sender,
+        sender,
+        Some(1),
+        coin.compute_object_reference(),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        rgp,
+    );
+    let err = context.try_sign_transaction(&tx_data).await.unwrap_err();
+    assert!(err.to_string().contains("read-only"), "{err}");
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_active_address_command() -> Result<(), anyhow::Error> {
     let mut cluster = TestClusterBuilder::new().build().await;
@@ -2536,6 +2712,98 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_serve_stdio() -> Result<(), anyhow::Error> {
+    let mut cluster = TestClusterBuilder::new().build().await;
+    let context = cluster.wallet_mut();
+    let addr2 = context.config.keystore.addresses().get(1).cloned().unwrap();
+
+    let requests = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "client", "params": {"args": ["switch", "--address", addr2.to_string()]}}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "client", "params": {"args": ["active-address"]}}),
+        json!({"jsonrpc": "2.0", "id": 3, "method": "client", "params": {"args": ["no-such-command"]}}),
+        json!({"jsonrpc": "2.0", "id": 4, "method": "deploy", "params": {}}),
+    ];
+    let mut input = String::new();
+    for request in requests {
+        input.push_str(&format!("{request}\n"));
+    }
+    input.push_str("not json\n");
+
+    let mut output = vec![];
+    sui::serve_stdio::serve(context, input.as_bytes(), &mut output).await?;
+    let responses: Vec<serde_json::Value> = str::from_utf8(&output)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(responses.len(), 5);
+
+    // The switch applies to later requests, which share the wallet context.
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["result"], json!(addr2.to_string()));
+    assert_eq!(context.active_address()?, addr2);
+
+    assert_eq!(responses[2]["error"]["code"], -32602);
+    assert_eq!(responses[3]["error"]["code"], -32601);
+    assert_eq!(responses[4]["id"], serde_json::Value::Null);
+    assert_eq!(responses[4]["error"]["code"], -32700);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[allow(deprecated)] // cargo_bin is deprecated but cargo_bin_cmd! doesn't work with assert_cmd
+async fn test_serve_stdio_writes_only_responses_to_stdout() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let context = &mut test_cluster.wallet;
+    let client_config_path = context.config.path().to_path_buf();
+    let chain_id = context.cache_chain_id().await?;
+    let (temp_dir, _) =
+        create_temp_dir_with_framework_packages("dummy_modules_publish", Some(chain_id))?;
+
+    // Both paths are relative to the directory the server runs in: building the first package
+    // must not change it. Verifying the bytecode prints progress, which must not reach stdout.
+    let requests = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "build", "params": {"path": "test"}}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "client", "params": {"args": ["verify-bytecode-meter", "--package", "test"]}}),
+    ];
+    let mut input = String::new();
+    for request in requests {
+        input.push_str(&format!("{request}\n"));
+    }
+
+    let output = assert_cmd::Command::cargo_bin("sui")
+        .unwrap()
+        .arg("serve-stdio")
+        .arg("--client.config")
+        .arg(&client_config_path)
+        .current_dir(temp_dir.path())
+        .write_stdin(input)
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let responses: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(responses.len(), 2, "stdout: {stdout}\nstderr: {stderr}");
+    assert!(
+        responses[0]["result"]["modules"].is_array(),
+        "{}",
+        responses[0]
+    );
+    assert_eq!(responses[1]["id"], 2);
+    assert!(responses[1].get("result").is_some(), "{}", responses[1]);
+    assert!(
+        stderr.contains("Running bytecode verifier"),
+        "stderr: {stderr}"
+    );
+
+    temp_dir.close()?;
+    Ok(())
+}
+
 fn get_gas_value(o: &Object) -> u64 {
     GasCoin::try_from(o).unwrap().value()
 }
@@ -3321,6 +3589,59 @@ async fn test_dry_run() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_dry_run_gas_profile() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+    let client = context.grpc_client()?;
+    let object_refs = client.get_owned_objects(address, None, None, None).await?;
+    let object_id = object_refs.items.first().unwrap().id();
+
+    // PaySui splits the amounts off the gas coin, so its prefixes only succeed if they are paid
+    // with the same coin.
+    let profiled = SuiClientCommands::PaySui {
+        input_coins: vec![object_id],
+        recipients: vec![KeyIdentity::Address(SuiAddress::random_for_testing_only())],
+        amounts: vec![1],
+        gas_data: GasDataArgs {
+            gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+            ..Default::default()
+        },
+        processing: TxProcessingArgs {
+            dry_run: true,
+            gas_profile: true,
+            ..Default::default()
+        },
+    }
+    .execute(context)
+    .await?;
+
+    let SuiClientCommandResult::DryRunGasProfile(response, profile) = profiled else {
+        panic!("Expected a gas profile, got {profiled:?}");
+    };
+    assert!(response.transaction.effects.status().is_ok());
+    assert_eq!(
+        response.transaction.effects.gas_object().unwrap().0.0,
+        object_id
+    );
+    assert_eq!(
+        profile.computation_cost,
+        response
+            .transaction
+            .effects
+            .gas_cost_summary()
+            .computation_cost
+    );
+    assert!(!profile.commands.is_empty());
+    assert!(profile.base_cost <= profile.computation_cost);
+    assert!(profile.profile_path.exists());
+    std::fs::remove_file(&profile.profile_path)?;
+
+    Ok(())
+}
+
 async fn test_cluster_helper() -> (
     TestCluster,
     Client,
@@ -3768,7 +4089,9 @@ async fn publish_and_mint_trusted_coin(
         // `trusted_coin::mint` returns the coin rather than transferring it, and `Call` leaves
         // return values unused, so mint through the framework's entry function instead.
         let result = SuiClientCommands::Call {
-            package: SUI_FRAMEWORK_PACKAGE_ID,
+            package: Some(SUI_FRAMEWORK_PACKAGE_ID),
+            package_name: None,
+            package_path: None,
             module: "coin".to_string(),
             function: "mint_and_transfer".to_string(),
             type_args: vec![coin_type.clone()],
@@ -3777,6 +4100,7 @@ async fn publish_and_mint_trusted_coin(
                 SuiJsonValue::new(json!(amount.to_string()))?,
                 SuiJsonValue::new(json!(sender.to_string()))?,
             ],
+            presets: None,
             payment: PaymentArgs::default(),
             gas_data: GasDataArgs {
                 gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS),
@@ -5000,11 +5324,14 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
 
     // Normal abort
     let non_clever_abort = SuiClientCommands::Call {
-        package: package.0.0,
+        package: Some(package.0.0),
+        package_name: None,
+        package_path: None,
         module: "clever_errors".to_string(),
         function: "aborter".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -5018,11 +5345,14 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
 
     // Line-only abort
     let line_only_abort = SuiClientCommands::Call {
-        package: package.0.0,
+        package: Some(package.0.0),
+        package_name: None,
+        package_path: None,
         module: "clever_errors".to_string(),
         function: "aborter_line_no".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -5036,11 +5366,14 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
 
     // Full clever error with utf-8 string
     let clever_error_utf8 = SuiClientCommands::Call {
-        package: package.0.0,
+        package: Some(package.0.0),
+        package_name: None,
+        package_path: None,
         module: "clever_errors".to_string(),
         function: "clever_aborter".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -5054,11 +5387,14 @@ async fn test_clever_errors() -> Result<(), anyhow::Error> {
 
     // Full clever error with non-utf-8 string
     let clever_error_non_utf8 = SuiClientCommands::Call {
-        package: package.0.0,
+        package: Some(package.0.0),
+        package_name: None,
+        package_path: None,
         module: "clever_errors".to_string(),
         function: "clever_aborter_not_a_string".to_string(),
         type_args: vec![],
         args: vec![],
+        presets: None,
         payment: PaymentArgs::default(),
         gas_data: GasDataArgs {
             gas_budget: Some(rgp * TEST_ONLY_GAS_UNIT_FOR_PUBLISH),
@@ -5142,6 +5478,67 @@ async fn test_tree_shaking_package_with_unpublished_deps() -> Result<(), anyhow:
     Ok(())
 }
 
+#[sim_test]
+async fn test_call_with_package_name() -> Result<(), anyhow::Error> {
+    let mut test = TreeShakingTest::new().await?;
+    let chain_id = test.client.get_chain_identifier().await?.to_string();
+    let _ = update_toml_with_localnet_chain_id(&test.package_path("A"), chain_id.clone());
+    let _ = update_toml_with_localnet_chain_id(&test.package_path("B_A"), chain_id);
+    let (package_a_id, _) = test.publish_package("A", false).await?;
+
+    // The package is found both from its own directory and from a package depending on it.
+    let wallet = &test.test_cluster.wallet;
+    for path in [test.package_path("A"), test.package_path("B_A")] {
+        assert_eq!(
+            resolve_package_id_by_name(wallet, &path, "a").await?,
+            package_a_id
+        );
+    }
+
+    // Unpublished and unknown packages can't be resolved.
+    let error = resolve_package_id_by_name(wallet, &test.package_path("B_A"), "b")
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{error:#}").contains("Cannot resolve the ID of package `b`"),
+        "{error:#}"
+    );
+    let error = resolve_package_id_by_name(wallet, &test.package_path("A"), "b")
+        .await
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("is neither the package at"),
+        "{error}"
+    );
+
+    let SuiClientCommandResult::TransactionBlock(response) = SuiClientCommands::Call {
+        package: None,
+        package_name: Some("a".to_string()),
+        package_path: Some(test.package_path("B_A")),
+        module: "a".to_string(),
+        function: "a".to_string(),
+        type_args: vec![],
+        args: vec![],
+        presets: None,
+        payment: PaymentArgs {
+            gas: vec![test.gas_obj_id],
+        },
+        gas_data: GasDataArgs {
+            gas_budget: Some(test.rgp * TEST_ONLY_GAS_UNIT_FOR_GENERIC),
+            ..Default::default()
+        },
+        processing: TxProcessingArgs::default(),
+    }
+    .execute(test.test_cluster.wallet_mut())
+    .await?
+    else {
+        unreachable!("Invalid response");
+    };
+    assert!(response.effects.status().is_ok());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_tree_shaking_package_without_dependencies() -> Result<(), anyhow::Error> {
     let mut test = TreeShakingTest::new().await?;
@@ -5899,3 +6296,141 @@ async fn test_publish_sender_flag_respected_in_serialized_transaction() -> Resul
 
     Ok(())
 }
+
+/// Finds the `DenyCap` or `DenyCapV2` of `coin_module` owned by `owner`.
+async fn find_deny_cap(
+    client: &Client,
+    owner: SuiAddress,
+    cap_name: &str,
+    coin_module: &str,
+) -> Result<ObjectID, anyhow::Error> {
+    let objects = client.get_owned_objects(owner, None, None, None).await?;
+    objects
+        .items
+        .iter()
+        .find(|object| {
+            object.struct_tag().is_some_and(|tag| {
+                tag.name.as_str() == cap_name
+                    && matches!(
+                        tag.type_params.first(),
+                        Some(TypeTag::Struct(coin)) if coin.module.as_str() == coin_module
+                    )
+            })
+        })
+        .map(|object| object.id())
+        .ok_or_else(|| anyhow::anyhow!("No {cap_name} found for {coin_module}"))
+}
+
+async fn deny_list_check(
+    context: &mut WalletContext,
+    deny_cap_id: ObjectID,
+    addresses: Vec<SuiAddress>,
+) -> Result<DenyListCheckOutput, anyhow::Error> {
+    let result = SuiClientCommands::DenyList {
+        cmd: DenyListCommand::Check {
+            deny_cap_id,
+            addresses: addresses.into_iter().map(KeyIdentity::Address).collect(),
+        },
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::DenyListCheck(output) = result else {
+        panic!("deny-list check did not return a deny list check result");
+    };
+    Ok(output)
+}
+
+/// `deny-list check` reads the deny list through the API matching the coin's cap: denials by a
+/// `DenyCap` apply immediately and cannot be paused, denials and pauses by a `DenyCapV2` apply
+/// from the next epoch.
+#[sim_test]
+async fn test_deny_list_check() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let context = &mut test_cluster.wallet;
+    let sender = context.active_address()?;
+    let client = context.grpc_client()?;
+
+    let chain_id = client.get_chain_identifier().await.unwrap().to_string();
+    let (_tmp, package_path) =
+        create_temp_dir_with_framework_packages("regulated_coin", Some(chain_id))?;
+    let mut build_config = BuildConfig::new_for_testing().config;
+    build_config.install_dir = None;
+    let result = SuiClientCommands::Publish(PublishArgs {
+        package_path,
+        build_config,
+        skip_dependency_verification: false,
+        verify_deps: false,
+        with_unpublished_dependencies: false,
+        payment: PaymentArgs::default(),
+        gas_data: GasDataArgs::default(),
+        processing: TxProcessingArgs::default(),
+    })
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::TransactionBlock(response) = result else {
+        panic!("Publish did not return a transaction block");
+    };
+    assert!(response.effects.status().is_ok());
+
+    let cap_v1 = find_deny_cap(&client, sender, "DenyCap", "regulated_coin_v1").await?;
+    let cap_v2 = find_deny_cap(&client, sender, "DenyCapV2", "regulated_coin_v2").await?;
+    let denied = SuiAddress::random_for_testing_only();
+    let allowed = SuiAddress::random_for_testing_only();
+
+    for deny_cap_id in [cap_v1, cap_v2] {
+        SuiClientCommands::DenyList {
+            cmd: DenyListCommand::Add {
+                deny_cap_id,
+                address: KeyIdentity::Address(denied),
+                payment: PaymentArgs::default(),
+                gas_data: GasDataArgs::default(),
+                processing: TxProcessingArgs::default(),
+            },
+        }
+        .execute(context)
+        .await?;
+    }
+
+    let v1 = deny_list_check(context, cap_v1, vec![denied, allowed]).await?;
+    assert!(
+        v1.coin_type
+            .ends_with("::regulated_coin_v1::REGULATED_COIN_V1")
+    );
+    assert_eq!(v1.global_pause_current_epoch, None);
+    assert_eq!(v1.global_pause_next_epoch, None);
+    assert_eq!(v1.addresses[0].address, denied);
+    assert!(v1.addresses[0].denied_current_epoch);
+    assert!(v1.addresses[0].denied_next_epoch);
+    assert_eq!(v1.addresses[1].address, allowed);
+    assert!(!v1.addresses[1].denied_current_epoch);
+    assert!(!v1.addresses[1].denied_next_epoch);
+
+    let v2 = deny_list_check(context, cap_v2, vec![denied, allowed]).await?;
+    assert!(
+        v2.coin_type
+            .ends_with("::regulated_coin_v2::REGULATED_COIN_V2")
+    );
+    assert_eq!(v2.global_pause_current_epoch, Some(false));
+    assert_eq!(v2.global_pause_next_epoch, Some(false));
+    assert!(!v2.addresses[0].denied_current_epoch);
+    assert!(v2.addresses[0].denied_next_epoch);
+    assert!(!v2.addresses[1].denied_current_epoch);
+    assert!(!v2.addresses[1].denied_next_epoch);
+
+    SuiClientCommands::DenyList {
+        cmd: DenyListCommand::EnableGlobalPause {
+            deny_cap_id: cap_v2,
+            payment: PaymentArgs::default(),
+            gas_data: GasDataArgs::default(),
+            processing: TxProcessingArgs::default(),
+        },
+    }
+    .execute(context)
+    .await?;
+
+    let v2 = deny_list_check(context, cap_v2, vec![allowed]).await?;
+    assert_eq!(v2.global_pause_current_epoch, Some(false));
+    assert_eq!(v2.global_pause_next_epoch, Some(true));
+
+    Ok(())
+}
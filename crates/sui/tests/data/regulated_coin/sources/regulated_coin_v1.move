@@ -0,0 +1,25 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Regulated coin whose deny list is managed through a `DenyCap`.
+module regulated_coin::regulated_coin_v1;
+
+use sui::coin;
+
+public struct REGULATED_COIN_V1 has drop {}
+
+#[allow(deprecated_usage)]
+fun init(witness: REGULATED_COIN_V1, ctx: &mut TxContext) {
+    let (treasury_cap, deny_cap, metadata) = coin::create_regulated_currency(
+        witness,
+        2,
+        b"RC1",
+        b"",
+        b"",
+        option::none(),
+        ctx,
+    );
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(deny_cap, ctx.sender())
+}
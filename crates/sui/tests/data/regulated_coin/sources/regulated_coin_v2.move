@@ -0,0 +1,26 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Regulated coin whose deny list is managed through a `DenyCapV2` that allows global pause.
+module regulated_coin::regulated_coin_v2;
+
+use sui::coin;
+
+public struct REGULATED_COIN_V2 has drop {}
+
+#[allow(deprecated_usage)]
+fun init(witness: REGULATED_COIN_V2, ctx: &mut TxContext) {
+    let (treasury_cap, deny_cap, metadata) = coin::create_regulated_currency_v2(
+        witness,
+        2,
+        b"RC2",
+        b"",
+        b"",
+        option::none(),
+        true,
+        ctx,
+    );
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(deny_cap, ctx.sender())
+}
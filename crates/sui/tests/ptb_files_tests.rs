@@ -69,7 +69,8 @@ async fn test_ptb_files(path: &Path) -> datatest_stable::Result<()> {
     let context = &test_cluster.wallet;
     let client = context.grpc_client()?;
 
-    let (built_ptb, warnings) = PTB::build_ptb(program, BTreeMap::new(), client, context).await;
+    let (built_ptb, warnings) =
+        PTB::build_ptb(program, BTreeMap::new(), client, context, None).await;
 
     if !warnings.is_empty() {
         let rendered = build_error_reports(&file_contents, warnings);
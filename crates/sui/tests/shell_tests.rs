@@ -135,9 +135,11 @@ fn make_temp_config_dir() -> TempDir {
             ws: None,
             basic_auth: None,
             chain_id: None,
+            gas_swap_routes: vec![],
         }],
         active_env: Some("testnet".to_string()),
         active_address: None,
+        watch_only: vec![],
     }
     .persisted(&result.path().join(SUI_CLIENT_CONFIG))
     .save()
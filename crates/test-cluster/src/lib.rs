@@ -15,10 +15,12 @@ use std::time::Duration;
 use sui_config::genesis::Genesis;
 use sui_config::node::FundsWithdrawSchedulerType;
 use sui_config::node::{AuthorityOverloadConfig, DBCheckpointConfig, RunWithRange};
+use sui_config::p2p::SeedPeer;
 use sui_config::{Config, ExecutionCacheConfig, SUI_CLIENT_CONFIG, SUI_NETWORK_CONFIG};
 use sui_config::{NodeConfig, PersistedConfig, SUI_KEYSTORE_FILENAME};
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_client::NetworkAuthorityClient;
+use sui_keys::key_derive::derive_key_pairs_from_mnemonic;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_node::SuiNodeHandle;
 use sui_protocol_config::{Chain, ProtocolVersion};
@@ -28,8 +30,10 @@ use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
 use sui_sdk::wallet_context::WalletContext;
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_swarm::memory::{Swarm, SwarmBuilder};
+use sui_swarm_config::genesis_cache::GenesisCache;
 use sui_swarm_config::genesis_config::{
-    AccountConfig, DEFAULT_GAS_AMOUNT, GenesisConfig, ValidatorGenesisConfig,
+    AccountConfig, CoinAllocation, DEFAULT_GAS_AMOUNT, DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT,
+    GenesisConfig, ValidatorGenesisConfig,
 };
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_swarm_config::network_config_builder::{
@@ -45,7 +49,7 @@ use sui_types::base_types::{AuthorityName, ObjectID, ObjectRef, SuiAddress};
 use sui_types::committee::CommitteeTrait;
 use sui_types::committee::{Committee, EpochId};
 use sui_types::crypto::KeypairTraits;
-use sui_types::crypto::SuiKeyPair;
+use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 use sui_types::digests::{ChainIdentifier, TransactionDigest};
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
@@ -99,12 +103,26 @@ impl FullNodeHandle {
             rpc_url,
         }
     }
+
+    pub fn name(&self) -> AuthorityName {
+        self.sui_node.with(|node| node.state().name)
+    }
 }
 
 pub struct TestCluster {
     pub swarm: Swarm,
     pub wallet: WalletContext,
     pub fullnode_handle: FullNodeHandle,
+    protocol_upgrade_rehearsal: Option<ProtocolUpgradeRehearsal>,
+}
+
+/// An upgrade from one protocol version to the next at a chosen epoch, set up with
+/// [TestClusterBuilder::with_protocol_upgrade_rehearsal].
+#[derive(Clone, Copy, Debug)]
+pub struct ProtocolUpgradeRehearsal {
+    pub from: ProtocolVersion,
+    pub to: ProtocolVersion,
+    pub upgrade_epoch: EpochId,
 }
 
 impl TestCluster {
@@ -182,12 +200,82 @@ impl TestCluster {
         .await
     }
 
+    /// Start a new fullnode that only knows the fullnodes already in the cluster, so that it
+    /// discovers the network and state-syncs through them rather than through the validators.
+    pub async fn spawn_new_fullnode_from_fullnodes(&mut self) -> FullNodeHandle {
+        let seed_peers = self
+            .swarm
+            .fullnodes()
+            .filter(|node| node.is_running())
+            .map(|node| {
+                let config = node.config();
+                SeedPeer {
+                    peer_id: Some(anemo::PeerId(
+                        config.network_key_pair().public().0.to_bytes(),
+                    )),
+                    address: config.p2p_config.external_address.clone().unwrap(),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert!(!seed_peers.is_empty(), "No running fullnode to sync from");
+
+        self.start_fullnode_from_config(
+            self.fullnode_config_builder()
+                .with_seed_peers(seed_peers)
+                .build(&mut OsRng, self.swarm.config()),
+        )
+        .await
+    }
+
     pub async fn start_fullnode_from_config(&mut self, config: NodeConfig) -> FullNodeHandle {
         let json_rpc_address = config.json_rpc_address;
         let node = self.swarm.spawn_new_node(config).await;
         FullNodeHandle::new(node, json_rpc_address).await
     }
 
+    /// Stop the fullnode named `name` and remove it from the cluster. If it is the fullnode the
+    /// cluster and its wallet talk to, they fail over to another running fullnode.
+    pub async fn remove_fullnode(&mut self, name: &AuthorityName) {
+        let node = self
+            .swarm
+            .node(name)
+            .unwrap_or_else(|| panic!("No node named {}", name.concise()));
+        assert!(
+            node.config().intended_node_role().is_fullnode(),
+            "{} is not a fullnode",
+            name.concise()
+        );
+        self.swarm.remove_node(name);
+        info!(name =% name.concise(), "Removed fullnode from the cluster");
+
+        if self.fullnode_handle.name() != *name {
+            return;
+        }
+
+        let fullnode = self
+            .swarm
+            .fullnodes()
+            .find(|node| node.is_running())
+            .expect("No running fullnode left to fail over to");
+        let json_rpc_address = fullnode.config().json_rpc_address;
+        let sui_node = fullnode.get_node_handle().unwrap();
+        self.fullnode_handle = FullNodeHandle::new(sui_node, json_rpc_address).await;
+
+        let wallet_conf = self.wallet.config.path().to_path_buf();
+        let mut config: SuiClientConfig = PersistedConfig::read(&wallet_conf).unwrap();
+        for env in &mut config.envs {
+            if env.alias == "localnet" {
+                env.rpc = self.fullnode_handle.rpc_url.clone();
+            }
+        }
+        config.persisted(&wallet_conf).save().unwrap();
+        self.wallet = WalletContext::new(&wallet_conf).unwrap();
+        info!(
+            rpc_url = self.fullnode_handle.rpc_url,
+            "Failed over to another fullnode"
+        );
+    }
+
     pub fn all_node_handles(&self) -> Vec<SuiNodeHandle> {
         self.swarm
             .all_nodes()
@@ -556,6 +644,61 @@ impl TestCluster {
         }
     }
 
+    /// Run the upgrade set up with [TestClusterBuilder::with_protocol_upgrade_rehearsal]. `before`
+    /// runs while the network is still on the old version. Epochs are then advanced, with the
+    /// validators restarted to support the new version in the epoch before the upgrade epoch, and
+    /// `after` runs once every node has upgraded. Returns the system state of the upgrade epoch.
+    ///
+    /// Epochs are advanced with [Self::trigger_reconfiguration], so the epoch duration should be
+    /// long enough that epochs do not also end on their own.
+    pub async fn rehearse_protocol_upgrade(
+        &self,
+        before: impl AsyncFnOnce(&TestCluster),
+        after: impl AsyncFnOnce(&TestCluster),
+    ) -> SuiSystemState {
+        let ProtocolUpgradeRehearsal {
+            from,
+            to,
+            upgrade_epoch,
+        } = self
+            .protocol_upgrade_rehearsal
+            .expect("The cluster was not built with_protocol_upgrade_rehearsal");
+
+        assert_eq!(
+            self.highest_protocol_version(),
+            from,
+            "The network is not on the protocol version the rehearsal starts from"
+        );
+        before(self).await;
+
+        loop {
+            let epoch = self.get_sui_system_state().epoch();
+            assert!(
+                epoch < upgrade_epoch,
+                "The network reached epoch {epoch} before upgrading at epoch {upgrade_epoch}"
+            );
+            if epoch + 1 == upgrade_epoch {
+                break;
+            }
+            self.trigger_reconfiguration().await;
+        }
+
+        info!("Upgrading validators to support protocol version {to:?}");
+        self.update_validator_supported_versions(SupportedProtocolVersions::new_for_testing(
+            from.as_u64(),
+            to.as_u64(),
+        ))
+        .await;
+        self.trigger_reconfiguration().await;
+        self.wait_for_all_nodes_upgrade_to(to.as_u64()).await;
+
+        let system_state = self.get_sui_system_state();
+        assert_eq!(system_state.epoch(), upgrade_epoch);
+        assert_eq!(system_state.protocol_version(), to.as_u64());
+        after(self).await;
+        system_state
+    }
+
     /// Wait for all nodes in the network to upgrade to `protocol_version`.
     pub async fn wait_for_all_nodes_upgrade_to(&self, protocol_version: u64) {
         for h in self.all_node_handles() {
@@ -1315,6 +1458,8 @@ pub struct TestClusterBuilder {
     genesis_config: Option<GenesisConfig>,
     network_config: Option<NetworkConfig>,
     additional_objects: Vec<Object>,
+    seeded_account_keys: Vec<SuiKeyPair>,
+    protocol_upgrade_rehearsal: Option<ProtocolUpgradeRehearsal>,
     num_validators: Option<usize>,
     validators: Option<Vec<ValidatorGenesisConfig>>,
     fullnode_rpc_port: Option<u16>,
@@ -1352,6 +1497,8 @@ pub struct TestClusterBuilder {
     peer_deny_sync_config_callback:
         Option<sui_swarm_config::network_config_builder::PeerDenySyncConfigCallback>,
 
+    genesis_cache: Option<GenesisCache>,
+
     #[cfg(msim)]
     inject_synthetic_execution_time: bool,
 }
@@ -1363,6 +1510,8 @@ impl TestClusterBuilder {
             network_config: None,
             chain_override: None,
             additional_objects: vec![],
+            seeded_account_keys: vec![],
+            protocol_upgrade_rehearsal: None,
             fullnode_rpc_port: None,
             num_validators: None,
             validators: None,
@@ -1398,6 +1547,7 @@ impl TestClusterBuilder {
             validator_observer_config: None,
             state_sync_config: None,
             peer_deny_sync_config_callback: None,
+            genesis_cache: None,
             #[cfg(msim)]
             inject_synthetic_execution_time: false,
         }
@@ -1560,6 +1710,36 @@ impl TestClusterBuilder {
         self
     }
 
+    /// Start the network on protocol version `from` and set it up to upgrade to the next version
+    /// at `upgrade_epoch`, which [TestCluster::rehearse_protocol_upgrade] then carries out. Every
+    /// validator supports the new version from the epoch before, so the upgrade does not depend on
+    /// the buffer stake. The fullnode supports both versions from the start.
+    pub fn with_protocol_upgrade_rehearsal(
+        mut self,
+        from: ProtocolVersion,
+        upgrade_epoch: EpochId,
+    ) -> Self {
+        assert!(upgrade_epoch > 0, "The upgrade cannot happen at genesis");
+        assert!(
+            from < ProtocolVersion::MAX_ALLOWED,
+            "There is no protocol version to upgrade to from {from:?}"
+        );
+        let to = from + 1;
+        self.protocol_upgrade_rehearsal = Some(ProtocolUpgradeRehearsal {
+            from,
+            to,
+            upgrade_epoch,
+        });
+        self.with_protocol_version(from)
+            .with_supported_protocol_versions(SupportedProtocolVersions::new_for_testing(
+                from.as_u64(),
+                from.as_u64(),
+            ))
+            .with_fullnode_supported_protocol_versions_config(
+                SupportedProtocolVersions::new_for_testing(from.as_u64(), to.as_u64()),
+            )
+    }
+
     pub fn with_supported_protocol_version_callback(
         mut self,
         func: SupportedProtocolVersionsCallback,
@@ -1601,16 +1781,51 @@ impl TestClusterBuilder {
         self
     }
 
+    /// Fund `count` Ed25519 accounts derived from the mnemonic `phrase` instead of the default
+    /// random accounts, and add their keys to the wallet with the first one active. Account `i`
+    /// uses the derivation path m/44'/784'/{i}'/0'/0', so tests and docs can refer to the same
+    /// addresses on every run.
+    pub fn with_seeded_accounts(mut self, phrase: &str, count: u32) -> Self {
+        let keys = derive_key_pairs_from_mnemonic(phrase, &SignatureScheme::ED25519, count)
+            .expect("Cannot derive seeded accounts");
+        self.get_or_init_genesis_config().accounts = keys
+            .iter()
+            .map(|(address, _)| AccountConfig {
+                address: Some(*address),
+                gas_amounts: vec![DEFAULT_GAS_AMOUNT; DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT],
+            })
+            .collect();
+        self.seeded_account_keys = keys.into_iter().map(|(_, key)| key).collect();
+        self
+    }
+
     pub fn with_additional_accounts(mut self, accounts: Vec<AccountConfig>) -> Self {
         self.get_or_init_genesis_config().accounts.extend(accounts);
         self
     }
 
+    /// Create coins of types other than SUI at genesis. The packages defining the coin types must
+    /// be part of genesis, either as system packages or through [Self::with_objects].
+    pub fn with_coin_allocations(mut self, allocations: Vec<CoinAllocation>) -> Self {
+        self.get_or_init_genesis_config()
+            .coin_allocations
+            .extend(allocations);
+        self
+    }
+
     pub fn with_config_dir(mut self, config_dir: PathBuf) -> Self {
         self.config_dir = Some(config_dir);
         self
     }
 
+    /// Reuse the genesis built by an earlier cluster with the same configuration from `cache`,
+    /// instead of building it again. Outside of simulation tests, clusters use the cache in
+    /// `$SUI_TEST_GENESIS_CACHE_DIR` by default, if it is set.
+    pub fn with_genesis_cache(mut self, cache: GenesisCache) -> Self {
+        self.genesis_cache = Some(cache);
+        self
+    }
+
     pub fn with_default_jwks(mut self) -> Self {
         self.default_jwks = true;
         self
@@ -1695,6 +1910,7 @@ impl TestClusterBuilder {
             ws: None,
             basic_auth: None,
             chain_id: None,
+            gas_swap_routes: vec![],
         });
         wallet_conf.active_env = Some("localnet".to_string());
 
@@ -1710,6 +1926,7 @@ impl TestClusterBuilder {
             swarm,
             wallet,
             fullnode_handle,
+            protocol_upgrade_rehearsal: self.protocol_upgrade_rehearsal,
         };
 
         // The embedded rpc-store indexes the tip asynchronously, so genesis
@@ -1812,6 +2029,19 @@ impl TestClusterBuilder {
             builder = builder.with_validator_observer_config(validator_observer_config);
         }
 
+        // Simulation tests are not cached by default, as a cached genesis doesn't draw the same
+        // random numbers as a built one, which would fail their determinism checks.
+        let genesis_cache = self.genesis_cache.take().or_else(|| {
+            if cfg!(msim) {
+                None
+            } else {
+                GenesisCache::from_env()
+            }
+        });
+        if let Some(genesis_cache) = genesis_cache {
+            builder = builder.with_genesis_cache(genesis_cache);
+        }
+
         #[cfg(msim)]
         {
             if let Some(mut config) = self.execution_time_observer_config.clone() {
@@ -1844,8 +2074,14 @@ impl TestClusterBuilder {
                 .import(None, SuiKeyPair::Ed25519(key.copy()))
                 .await?;
         }
+        for key in &self.seeded_account_keys {
+            keystore.import(None, key.copy()).await?;
+        }
 
-        let active_address = keystore.addresses().first().cloned();
+        let active_address = match self.seeded_account_keys.first() {
+            Some(key) => Some(SuiAddress::from(&key.public())),
+            None => keystore.addresses().first().cloned(),
+        };
 
         // Create wallet config with stated authorities port
         SuiClientConfig {
@@ -1854,6 +2090,7 @@ impl TestClusterBuilder {
             envs: Default::default(),
             active_address,
             active_env: Default::default(),
+            watch_only: vec![],
         }
         .save(wallet_path)?;
 
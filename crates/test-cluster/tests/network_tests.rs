@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use sui_framework::BuiltInFramework;
+use sui_keys::key_derive::derive_key_pairs_from_mnemonic;
 use sui_macros::sim_test;
 use sui_types::{
     MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID,
-    base_types::ObjectID, digests::TransactionDigest, object::Object,
+    base_types::ObjectID, crypto::SignatureScheme, digests::TransactionDigest, object::Object,
 };
 use test_cluster::TestClusterBuilder;
 
@@ -70,3 +71,34 @@ async fn test_package_override() {
 
     assert_ne!(framework_ref, modified_ref);
 }
+
+#[sim_test]
+async fn test_seeded_accounts() {
+    let phrase = "result crisp session latin must fruit genuine question prevent start coconut brave speak student dismiss";
+    let cluster = TestClusterBuilder::new()
+        .with_seeded_accounts(phrase, 3)
+        .build()
+        .await;
+
+    // The accounts are the same on every run, with the first one active and funded.
+    let expected: Vec<_> = derive_key_pairs_from_mnemonic(phrase, &SignatureScheme::ED25519, 3)
+        .unwrap()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+    let mut addresses = cluster.get_addresses();
+    addresses.sort();
+    let mut sorted = expected.clone();
+    sorted.sort();
+    assert_eq!(addresses, sorted);
+
+    let mut wallet = cluster.wallet;
+    assert_eq!(wallet.active_address().unwrap(), expected[0]);
+    assert!(
+        wallet
+            .get_one_gas_object_owned_by_address(expected[2])
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
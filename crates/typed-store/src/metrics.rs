@@ -316,6 +316,9 @@ pub struct OperationMetrics {
     pub rocksdb_very_slow_batch_writes_duration_ms: IntCounterVec,
     pub rocksdb_very_slow_puts_count: IntCounterVec,
     pub rocksdb_very_slow_puts_duration_ms: IntCounterVec,
+    pub rocksdb_batch_table_write_bytes: HistogramVec,
+    pub rocksdb_batch_table_write_entries: HistogramVec,
+    pub rocksdb_slow_table_writes: IntCounterVec,
 }
 
 impl OperationMetrics {
@@ -478,6 +481,36 @@ impl OperationMetrics {
                 registry,
             )
             .unwrap(),
+            rocksdb_batch_table_write_bytes: register_histogram_vec_with_registry!(
+                "rocksdb_batch_table_write_bytes",
+                "Bytes written to the table by each batch commit, for databases with a slow \
+                 write threshold",
+                &["cf_name"],
+                prometheus::exponential_buckets(1.0, 4.0, 15)
+                    .unwrap()
+                    .to_vec(),
+                registry,
+            )
+            .unwrap(),
+            rocksdb_batch_table_write_entries: register_histogram_vec_with_registry!(
+                "rocksdb_batch_table_write_entries",
+                "Entries written to the table by each batch commit, for databases with a slow \
+                 write threshold",
+                &["cf_name"],
+                prometheus::exponential_buckets(1.0, 4.0, 15)
+                    .unwrap()
+                    .to_vec(),
+                registry,
+            )
+            .unwrap(),
+            rocksdb_slow_table_writes: register_int_counter_vec_with_registry!(
+                "rocksdb_slow_table_writes",
+                "Number of puts and batch commits writing to the table that took longer than \
+                 the database's slow write threshold",
+                &["cf_name"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
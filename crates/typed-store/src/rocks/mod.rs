@@ -26,6 +26,7 @@ use crate::{
     traits::{Map, TableSummary},
 };
 use backoff::backoff::Backoff;
+use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::hash::{Digest, HashFunction};
 use mysten_common::debug_fatal;
 use mysten_metrics::RegistryID;
@@ -43,10 +44,13 @@ use std::{
     marker::PhantomData,
     ops::RangeBounds,
     path::{Path, PathBuf},
-    sync::{Arc, OnceLock},
+    sync::{Arc, Mutex, OnceLock},
     time::Duration,
 };
-use std::{collections::HashSet, ffi::CStr};
+use std::{
+    collections::{BTreeMap, HashSet},
+    ffi::CStr,
+};
 use sui_macros::{fail_point, nondeterministic};
 #[cfg(tidehunter)]
 use tidehunter::{db::Db as TideHunterDb, key_shape::KeySpace};
@@ -393,12 +397,7 @@ impl Database {
         }
     }
 
-    fn put_cf(
-        &self,
-        cf: &ColumnFamily,
-        key: Vec<u8>,
-        value: Vec<u8>,
-    ) -> Result<(), TypedStoreError> {
+    fn put_cf(&self, cf: &ColumnFamily, key: &[u8], value: Vec<u8>) -> Result<(), TypedStoreError> {
         fail_point!("put-cf-before");
         let ret = match (&self.storage, cf) {
             (Storage::Rocks(db), ColumnFamily::Rocks(_)) => db
@@ -406,12 +405,12 @@ impl Database {
                 .put_cf(&cf.rocks_cf(db), key, value)
                 .map_err(typed_store_err_from_rocks_err),
             (Storage::InMemory(db), ColumnFamily::InMemory(cf_name)) => {
-                db.put(cf_name, key, value);
+                db.put(cf_name, key.to_vec(), value);
                 Ok(())
             }
             #[cfg(tidehunter)]
             (Storage::TideHunter(db), ColumnFamily::TideHunter((ks, prefix))) => db
-                .insert(*ks, transform_th_key(&key, prefix), value)
+                .insert(*ks, transform_th_key(key, prefix), value)
                 .map_err(typed_store_error_from_th_error),
             _ => Err(TypedStoreError::RocksDBError(
                 "typed store invariant violation".to_string(),
@@ -549,6 +548,10 @@ impl Database {
         self.metric_conf.iter_sample_interval.new_from_self()
     }
 
+    pub fn slow_write_threshold(&self) -> Option<Duration> {
+        self.metric_conf.slow_write_threshold
+    }
+
     fn db_name(&self) -> String {
         let name = &self.metric_conf.db_name;
         if name.is_empty() {
@@ -597,6 +600,10 @@ pub struct MetricConf {
     /// When true and the database is opened with the tidehunter backend, each
     /// committed `WriteBatch` is written as a single lz4-compressed WAL entry.
     pub enable_th_batch_compression: bool,
+    /// Puts and batch commits taking longer than this are logged, with the keys and sizes of
+    /// what they wrote to each table. Batches writing to a database with a threshold also record
+    /// how many entries and bytes they wrote to each table.
+    pub slow_write_threshold: Option<Duration>,
 }
 
 impl MetricConf {
@@ -610,6 +617,7 @@ impl MetricConf {
             write_sample_interval: SamplingInterval::default(),
             iter_sample_interval: SamplingInterval::default(),
             enable_th_batch_compression: false,
+            slow_write_threshold: None,
         }
     }
 
@@ -622,6 +630,11 @@ impl MetricConf {
         self.enable_th_batch_compression = true;
         self
     }
+
+    pub fn with_slow_write_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_write_threshold = Some(threshold);
+        self
+    }
 }
 const CF_METRICS_REPORT_PERIOD_SECS: u64 = 30;
 const METRICS_ERROR: i64 = -1;
@@ -634,6 +647,8 @@ pub struct DBMap<K, V> {
     column_family: ColumnFamily,
     // the column family under which the map is stored
     cf: String,
+    /// `cf`, interned so that batches can track their writes to the table without allocating.
+    cf_label: &'static str,
     pub opts: ReadWriteOptions,
     db_metrics: Arc<DBMetrics>,
     get_sample_interval: SamplingInterval,
@@ -688,6 +703,7 @@ impl<K, V> DBMap<K, V> {
             _phantom: PhantomData,
             column_family,
             cf: opt_cf.to_string(),
+            cf_label: intern_cf_name(opt_cf),
             db_metrics: db_metrics_cloned,
             _metrics_task_cancel_handle: Arc::new(sender),
             get_sample_interval: db.get_sampling_interval(),
@@ -1449,6 +1465,44 @@ pub struct DBBatch {
     batch: StorageWriteBatch,
     db_metrics: Arc<DBMetrics>,
     write_sample_interval: SamplingInterval,
+    /// What the batch writes to each table, tracked if the database has a slow write threshold.
+    table_writes: Option<BTreeMap<&'static str, TableWrites>>,
+}
+
+/// The writes of a batch to a table.
+#[derive(Default)]
+struct TableWrites {
+    entries: usize,
+    bytes: usize,
+    largest_key: Vec<u8>,
+    largest_entry_bytes: usize,
+}
+
+impl TableWrites {
+    /// Records an entry written to the table, keeping its key if it is the largest so far.
+    fn record(&mut self, key: Vec<u8>, value_bytes: usize) {
+        let entry_bytes = key.len() + value_bytes;
+        self.entries += 1;
+        self.bytes += entry_bytes;
+        if entry_bytes > self.largest_entry_bytes {
+            self.largest_key = key;
+            self.largest_entry_bytes = entry_bytes;
+        }
+    }
+}
+
+/// Interns a column family name. Databases have a fixed set of column families, so this only ever
+/// allocates a bounded amount of memory, however many times they are opened.
+fn intern_cf_name(cf: &str) -> &'static str {
+    static CF_NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut names = CF_NAMES.get_or_init(Default::default).lock().unwrap();
+    if let Some(name) = names.get(cf) {
+        return name;
+    }
+
+    let name: &'static str = Box::leak(cf.into());
+    names.insert(name);
+    name
 }
 
 impl DBBatch {
@@ -1466,6 +1520,7 @@ impl DBBatch {
             batch,
             db_metrics: db_metrics.clone(),
             write_sample_interval: write_sample_interval.clone(),
+            table_writes: dbref.slow_write_threshold().map(|_| BTreeMap::new()),
         }
     }
 
@@ -1514,6 +1569,11 @@ impl DBBatch {
                 .report_metrics(&db_name);
         }
         let elapsed = timer.stop_and_record();
+        if let (Some(table_writes), Some(threshold)) =
+            (&self.table_writes, self.database.slow_write_threshold())
+        {
+            report_batch_table_writes(&self.db_metrics, &db_name, table_writes, threshold, elapsed);
+        }
         if elapsed > 1.0 {
             warn!(?elapsed, ?db_name, "very slow batch write");
             self.db_metrics
@@ -1602,16 +1662,20 @@ impl DBBatch {
             return Err(TypedStoreError::CrossDBBatch);
         }
 
+        let mut table_writes = self
+            .table_writes
+            .as_mut()
+            .map(|writes| writes.entry(db.cf_label).or_default());
         purged_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|k| {
                 let k_buf = be_fix_int_ser(k.borrow());
                 match (&mut self.batch, &db.column_family) {
                     (StorageWriteBatch::Rocks(b), ColumnFamily::Rocks(name)) => {
-                        b.delete_cf(&rocks_cf_from_db(&self.database, name)?, k_buf)
+                        b.delete_cf(&rocks_cf_from_db(&self.database, name)?, &k_buf)
                     }
                     (StorageWriteBatch::InMemory(b), ColumnFamily::InMemory(name)) => {
-                        b.delete_cf(name, k_buf)
+                        b.delete_cf(name, &k_buf)
                     }
                     #[cfg(tidehunter)]
                     (StorageWriteBatch::TideHunter(b), ColumnFamily::TideHunter((ks, prefix))) => {
@@ -1621,6 +1685,9 @@ impl DBBatch {
                         "typed store invariant violation".to_string(),
                     ))?,
                 }
+                if let Some(table_writes) = &mut table_writes {
+                    table_writes.record(k_buf, 0);
+                }
                 Ok(())
             })?;
         Ok(())
@@ -1668,12 +1735,17 @@ impl DBBatch {
             return Err(TypedStoreError::CrossDBBatch);
         }
         let mut total = 0usize;
+        let mut table_writes = self
+            .table_writes
+            .as_mut()
+            .map(|writes| writes.entry(db.cf_label).or_default());
         new_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow());
                 let v_buf = bcs::to_bytes(v.borrow()).map_err(typed_store_err_from_bcs_err)?;
-                total += k_buf.len() + v_buf.len();
+                let v_len = v_buf.len();
+                total += k_buf.len() + v_len;
                 if db.opts.log_value_hash {
                     let key_hash = default_hash(&k_buf);
                     let value_hash = default_hash(&v_buf);
@@ -1686,10 +1758,10 @@ impl DBBatch {
                 }
                 match (&mut self.batch, &db.column_family) {
                     (StorageWriteBatch::Rocks(b), ColumnFamily::Rocks(name)) => {
-                        b.put_cf(&rocks_cf_from_db(&self.database, name)?, k_buf, v_buf)
+                        b.put_cf(&rocks_cf_from_db(&self.database, name)?, &k_buf, v_buf)
                     }
                     (StorageWriteBatch::InMemory(b), ColumnFamily::InMemory(name)) => {
-                        b.put_cf(name, k_buf, v_buf)
+                        b.put_cf(name, &k_buf, v_buf)
                     }
                     #[cfg(tidehunter)]
                     (StorageWriteBatch::TideHunter(b), ColumnFamily::TideHunter((ks, prefix))) => {
@@ -1699,6 +1771,9 @@ impl DBBatch {
                         "typed store invariant violation".to_string(),
                     ))?,
                 }
+                if let Some(table_writes) = &mut table_writes {
+                    table_writes.record(k_buf, v_len);
+                }
                 Ok(())
             })?;
         self.db_metrics
@@ -1717,25 +1792,76 @@ impl DBBatch {
         if !Arc::ptr_eq(&db.db, &self.database) {
             return Err(TypedStoreError::CrossDBBatch);
         }
+        let mut table_writes = self
+            .table_writes
+            .as_mut()
+            .map(|writes| writes.entry(db.cf_label).or_default());
         new_vals
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow());
                 let v_buf = bcs::to_bytes(v.borrow()).map_err(typed_store_err_from_bcs_err)?;
+                let v_len = v_buf.len();
                 match &mut self.batch {
                     StorageWriteBatch::Rocks(b) => b.merge_cf(
                         &rocks_cf_from_db(&self.database, db.cf_name())?,
-                        k_buf,
+                        &k_buf,
                         v_buf,
                     ),
                     _ => unimplemented!("merge operator is only implemented for RocksDB"),
                 }
+                if let Some(table_writes) = &mut table_writes {
+                    table_writes.record(k_buf, v_len);
+                }
                 Ok(())
             })?;
         Ok(self)
     }
 }
 
+/// Records how much a committed batch wrote to each table, and logs those writes if the batch
+/// took longer than `threshold` to commit. The batch's latency is only known for the batch as a
+/// whole, and is recorded per database.
+fn report_batch_table_writes(
+    db_metrics: &DBMetrics,
+    db_name: &str,
+    table_writes: &BTreeMap<&'static str, TableWrites>,
+    threshold: Duration,
+    elapsed: f64,
+) {
+    let slow = elapsed >= threshold.as_secs_f64();
+    // Tables are added to the batch's writes before their entries, so they can be empty.
+    for (cf, writes) in table_writes.iter().filter(|(_, writes)| writes.entries > 0) {
+        db_metrics
+            .op_metrics
+            .rocksdb_batch_table_write_bytes
+            .with_label_values(&[cf])
+            .observe(writes.bytes as f64);
+        db_metrics
+            .op_metrics
+            .rocksdb_batch_table_write_entries
+            .with_label_values(&[cf])
+            .observe(writes.entries as f64);
+        if slow {
+            warn!(
+                ?elapsed,
+                ?db_name,
+                ?cf,
+                entries = writes.entries,
+                bytes = writes.bytes,
+                largest_key = Hex::encode(&writes.largest_key),
+                largest_entry_bytes = writes.largest_entry_bytes,
+                "slow batch write to table"
+            );
+            db_metrics
+                .op_metrics
+                .rocksdb_slow_table_writes
+                .with_label_values(&[cf])
+                .inc();
+        }
+    }
+}
+
 impl<'a, K, V> Map<'a, K, V> for DBMap<K, V>
 where
     K: Serialize + DeserializeOwned,
@@ -1843,19 +1969,36 @@ where
         };
         let key_buf = be_fix_int_ser(key);
         let value_buf = bcs::to_bytes(value).map_err(typed_store_err_from_bcs_err)?;
+        let put_bytes = key_buf.len() + value_buf.len();
         self.db_metrics
             .op_metrics
             .rocksdb_put_bytes
             .with_label_values(&[&self.cf])
-            .observe((key_buf.len() + value_buf.len()) as f64);
+            .observe(put_bytes as f64);
         if perf_ctx.is_some() {
             self.db_metrics
                 .write_perf_ctx_metrics
                 .report_metrics(&self.cf);
         }
-        self.db.put_cf(&self.column_family, key_buf, value_buf)?;
+        self.db.put_cf(&self.column_family, &key_buf, value_buf)?;
 
         let elapsed = timer.stop_and_record();
+        if let Some(threshold) = self.db.slow_write_threshold()
+            && elapsed >= threshold.as_secs_f64()
+        {
+            warn!(
+                ?elapsed,
+                cf = ?self.cf,
+                key = Hex::encode(&key_buf),
+                bytes = put_bytes,
+                "slow insert"
+            );
+            self.db_metrics
+                .op_metrics
+                .rocksdb_slow_table_writes
+                .with_label_values(&[&self.cf])
+                .inc();
+        }
         if elapsed > 1.0 {
             warn!(?elapsed, cf = ?self.cf, "very slow insert");
             self.db_metrics
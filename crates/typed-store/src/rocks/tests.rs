@@ -328,6 +328,82 @@ async fn test_insert_batch_across_cf() {
     }
 }
 
+#[tokio::test]
+async fn test_batch_table_writes() {
+    // Metrics are shared by every database in the process, so the tables are named for this test.
+    let (first_cf, second_cf) = ("batch_table_writes_first", "batch_table_writes_second");
+    let opts = rocksdb::Options::default();
+    let rocks = open_cf_opts(
+        temp_dir(),
+        None,
+        MetricConf::new("batch_table_writes").with_slow_write_threshold(Duration::ZERO),
+        &[(first_cf, opts.clone()), (second_cf, opts)],
+    )
+    .expect("Failed to open storage");
+
+    let first =
+        DBMap::<u64, String>::reopen(&rocks, Some(first_cf), &ReadWriteOptions::default(), false)
+            .expect("Failed to open storage");
+    let second =
+        DBMap::<u64, String>::reopen(&rocks, Some(second_cf), &ReadWriteOptions::default(), false)
+            .expect("Failed to open storage");
+
+    let mut batch = first.batch();
+    batch
+        .insert_batch(&first, (0..10u64).map(|i| (i, "x".repeat(i as usize))))
+        .expect("Failed to batch insert")
+        .insert_batch(&second, [(0u64, "y".to_string())])
+        .expect("Failed to batch insert");
+    batch
+        .delete_batch(&second, [1u64, 2])
+        .expect("Failed to batch delete");
+    batch.write().expect("Failed to execute batch");
+
+    // Keys are 8 bytes, and values are their BCS length prefix followed by their contents.
+    let op_metrics = &DBMetrics::get().op_metrics;
+    for (cf, entries, bytes) in [(first_cf, 10, 10 * 9 + 45), (second_cf, 3, 3 * 8 + 2)] {
+        let entries_histogram = op_metrics
+            .rocksdb_batch_table_write_entries
+            .with_label_values(&[cf]);
+        assert_eq!(entries_histogram.get_sample_count(), 1, "{cf}");
+        assert_eq!(entries_histogram.get_sample_sum(), entries as f64, "{cf}");
+
+        let bytes_histogram = op_metrics
+            .rocksdb_batch_table_write_bytes
+            .with_label_values(&[cf]);
+        assert_eq!(bytes_histogram.get_sample_count(), 1, "{cf}");
+        assert_eq!(bytes_histogram.get_sample_sum(), bytes as f64, "{cf}");
+
+        let slow_writes = op_metrics
+            .rocksdb_slow_table_writes
+            .with_label_values(&[cf]);
+        assert_eq!(slow_writes.get(), 1, "{cf}");
+    }
+}
+
+#[test]
+fn test_table_writes_keep_largest_key() {
+    let mut writes = TableWrites::default();
+    writes.record(vec![1], 10);
+    writes.record(vec![2, 2], 20);
+    writes.record(vec![3], 5);
+
+    assert_eq!(writes.entries, 3);
+    assert_eq!(writes.bytes, 11 + 22 + 6);
+    assert_eq!(writes.largest_key, vec![2, 2]);
+    assert_eq!(writes.largest_entry_bytes, 22);
+}
+
+#[test]
+fn test_intern_cf_name() {
+    let name = intern_cf_name("interned_cf");
+    assert_eq!(name, "interned_cf");
+    assert!(std::ptr::eq(
+        name,
+        intern_cf_name(&"interned_cf".to_string())
+    ));
+}
+
 #[tokio::test]
 async fn test_insert_batch_across_different_db() {
     let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"]);
@@ -95,6 +95,8 @@ const references = [
 			'references/cli/external-signers',
 			'references/cli/move',
       		'references/cli/replay',
+			'references/cli/scenario',
+			'references/cli/bench',
 			'references/cli/trace-analysis',
 			'references/cli/validator',
 		],
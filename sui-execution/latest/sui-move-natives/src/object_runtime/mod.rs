@@ -672,6 +672,18 @@ impl<'a> ObjectRuntime<'a> {
         self.child_object_store.wrapped_object_containers().clone()
     }
 
+    /// Track an object that `test_scenario` pulled in from a fork as if it had been an input
+    /// object of this runtime, so that its child objects can be loaded from storage.
+    pub(crate) fn add_forked_object(
+        &mut self,
+        id: ObjectID,
+        version: SequenceNumber,
+        contained_uids: BTreeSet<ObjectID>,
+    ) {
+        self.child_object_store
+            .add_root_object(id, version, contained_uids);
+    }
+
     pub fn record_settlement_sui_conservation(&mut self, input_sui: u64, output_sui: u64) {
         self.state.settlement_input_sui += input_sui;
         self.state.settlement_output_sui += output_sui;
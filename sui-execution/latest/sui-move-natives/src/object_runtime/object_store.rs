@@ -6,7 +6,7 @@ use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{annotated_value as A, runtime_value as R, vm_status::StatusCode};
 use move_vm_runtime::execution::values::{GlobalValue, StructRef, Value};
 use std::{
-    collections::{BTreeMap, btree_map},
+    collections::{BTreeMap, BTreeSet, btree_map},
     sync::Arc,
 };
 use sui_protocol_config::{LimitThresholdCrossed, ProtocolConfig, check_limit_by_meter};
@@ -748,6 +748,24 @@ impl<'a> ChildObjectStore<'a> {
         &self.inner.wrapped_object_containers
     }
 
+    /// Register a top-level object loaded after the transaction started, so that its child objects
+    /// (and those of any objects it wraps) can be read at `version`.
+    pub(super) fn add_root_object(
+        &mut self,
+        id: ObjectID,
+        version: SequenceNumber,
+        contained_uids: BTreeSet<ObjectID>,
+    ) {
+        for contained_uid in contained_uids {
+            self.inner.root_version.insert(contained_uid, version);
+            if contained_uid != id {
+                self.inner
+                    .wrapped_object_containers
+                    .insert(contained_uid, id);
+            }
+        }
+    }
+
     // retrieve the effects for the child objects
     pub(super) fn take_effects(&mut self) -> PartialVMResult<ChildObjectEffects> {
         let effects = std::mem::take(&mut self.store)
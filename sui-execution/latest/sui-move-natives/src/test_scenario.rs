@@ -3,7 +3,9 @@
 
 use crate::{
     get_extension, get_extension_mut, get_nth_struct_field, get_tag_and_layouts, legacy_test_cost,
-    object_runtime::{ObjectRuntime, RuntimeResults, object_store::ChildObjectEffects},
+    object_runtime::{
+        ObjectRuntime, RuntimeResults, get_all_uids, object_store::ChildObjectEffects,
+    },
     scratch::ScratchRuntime,
 };
 use better_any::{Tid, TidAble};
@@ -43,6 +45,7 @@ use sui_types::{
     config,
     digests::{ObjectDigest, TransactionDigest},
     dynamic_field::DynamicFieldInfo,
+    error::SuiResult,
     execution::DynamicallyLoadedObjectMetadata,
     id::UID,
     in_memory_storage::InMemoryStorage,
@@ -59,13 +62,66 @@ const E_UNABLE_TO_DEALLOCATE_RECEIVING_TICKET: u64 = 7;
 
 type Set<K> = IndexSet<K>;
 
+/// A source of objects that exist outside of a test, e.g. the state of a live network at some
+/// checkpoint. Tests running against a fork see these objects as if they had been created before
+/// the test started.
+pub trait ForkedObjectSource {
+    /// Return the latest version of object `id` in the fork, or `None` if it does not exist.
+    fn get_object(&self, id: &ObjectID) -> SuiResult<Option<Object>>;
+}
+
 /// An in-memory test store is a thin wrapper around the in-memory storage in a mutex. The mutex
 /// allows this to be used by both the object runtime (for reading) and the test scenario (for
 /// writing) while hiding mutability.
+///
+/// The store can optionally be backed by a [`ForkedObjectSource`]. Objects missing from the
+/// in-memory storage are then fetched from the fork on first access and cached in the storage, so
+/// later changes made by the test (including deletions) are never overwritten by the fork.
 #[derive(Tid)]
-pub struct InMemoryTestStore(pub RefCell<InMemoryStorage>);
+pub struct InMemoryTestStore(pub RefCell<InMemoryStorage>, Option<ForkedStore>);
 impl<'a> NativeExtensionMarker<'a> for &'a InMemoryTestStore {}
 
+struct ForkedStore {
+    source: Box<dyn ForkedObjectSource>,
+    // objects that have already been looked up in the fork, whether or not they were found
+    fetched: RefCell<BTreeSet<ObjectID>>,
+}
+
+impl InMemoryTestStore {
+    pub fn new(storage: InMemoryStorage) -> Self {
+        Self(RefCell::new(storage), None)
+    }
+
+    pub fn new_forked(storage: InMemoryStorage, source: Box<dyn ForkedObjectSource>) -> Self {
+        Self(
+            RefCell::new(storage),
+            Some(ForkedStore {
+                source,
+                fetched: RefCell::new(BTreeSet::new()),
+            }),
+        )
+    }
+
+    /// Fetch object `id` from the fork into the in-memory storage, returning it only if it was
+    /// loaded by this call. Each object is looked up in the fork at most once, but a lookup that
+    /// fails is not recorded, so it is retried on the next access.
+    fn load_from_fork(&self, id: &ObjectID) -> SuiResult<Option<Object>> {
+        let Some(fork) = &self.1 else {
+            return Ok(None);
+        };
+        if fork.fetched.borrow().contains(id) || self.0.borrow().get_object(id).is_some() {
+            return Ok(None);
+        }
+        let object = fork.source.get_object(id)?;
+        fork.fetched.borrow_mut().insert(*id);
+        let Some(object) = object else {
+            return Ok(None);
+        };
+        self.0.borrow_mut().insert_object(object.clone());
+        Ok(Some(object))
+    }
+}
+
 impl RuntimeObjectResolver for InMemoryTestStore {
     fn read_child_object(
         &self,
@@ -73,6 +129,7 @@ impl RuntimeObjectResolver for InMemoryTestStore {
         child: &ObjectID,
         child_version_upper_bound: SequenceNumber,
     ) -> sui_types::error::SuiResult<Option<Object>> {
+        self.load_from_fork(child)?;
         self.0
             .borrow()
             .read_child_object(parent, child, child_version_upper_bound)
@@ -394,6 +451,7 @@ pub fn take_from_address_by_id(
     pop_arg!(args, StructRef);
     safe_assert!(args.is_empty());
     let specified_obj_ty = object_type_of_type(context, &specified_ty)?;
+    load_forked_object(context, &specified_ty, &specified_obj_ty, id)?;
     let object_runtime: &mut ObjectRuntime = get_extension_mut!(context)?;
     let inventories = &mut object_runtime.test_inventories;
     let res = take_from_inventory(
@@ -495,6 +553,7 @@ pub fn take_immutable_by_id(
     pop_arg!(args, StructRef);
     safe_assert!(args.is_empty());
     let specified_obj_ty = object_type_of_type(context, &specified_ty)?;
+    load_forked_object(context, &specified_ty, &specified_obj_ty, id)?;
     let object_runtime: &mut ObjectRuntime = get_extension_mut!(context)?;
     let inventories = &mut object_runtime.test_inventories;
     let res = take_from_inventory(
@@ -580,6 +639,7 @@ pub fn take_shared_by_id(
     pop_arg!(args, StructRef);
     safe_assert!(args.is_empty());
     let specified_obj_ty = object_type_of_type(context, &specified_ty)?;
+    load_forked_object(context, &specified_ty, &specified_obj_ty, id)?;
     let object_runtime: &mut ObjectRuntime = get_extension_mut!(context)?;
     let inventories = &mut object_runtime.test_inventories;
     let res = take_from_inventory(
@@ -766,6 +826,72 @@ pub fn deallocate_receiving_ticket_for_object(
 
 // impls
 
+/// If the test store is backed by a fork and object `id` has not been seen by the test yet, fetch
+/// it from the fork and add it to the test inventories, as if it had been created by an earlier
+/// transaction. Objects whose type does not match `specified_obj_ty` are left out of the
+/// inventories, so taking them fails the same way as for any other missing object.
+fn load_forked_object(
+    context: &mut NativeContext,
+    specified_ty: &Type,
+    specified_obj_ty: &MoveObjectType,
+    id: ObjectID,
+) -> PartialVMResult<()> {
+    let object_runtime: &ObjectRuntime = get_extension!(context)?;
+    let inventories = &object_runtime.test_inventories;
+    if inventories.objects.contains_key(&id) || inventories.taken.contains_key(&id) {
+        return Ok(());
+    }
+    let store: &&InMemoryTestStore = get_extension!(context)?;
+    let object = match store.load_from_fork(&id) {
+        Ok(Some(object)) => object,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            return Err(PartialVMError::new(StatusCode::STORAGE_ERROR)
+                .with_message(format!("Failed to load object {id} from fork: {e}")));
+        }
+    };
+    let Some(move_obj) = object.data.try_as_move() else {
+        return Ok(());
+    };
+    if move_obj.type_() != specified_obj_ty {
+        return Ok(());
+    }
+    let Some((_, layout, annotated_layout)) = get_tag_and_layouts(context, specified_ty)? else {
+        return Ok(());
+    };
+    let Some(value) = Value::simple_deserialize(move_obj.contents(), &layout) else {
+        return Err(
+            PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE).with_message(format!(
+                "Failed to deserialize object {id} with type {specified_obj_ty}"
+            )),
+        );
+    };
+    let contained_uids = get_all_uids(&annotated_layout, move_obj.contents()).map_err(|e| {
+        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR).with_message(format!(
+            "Failed to find UIDs for forked object {id}. ERROR: {e}"
+        ))
+    })?;
+
+    let object_runtime: &mut ObjectRuntime = get_extension_mut!(context)?;
+    let inventories = &mut object_runtime.test_inventories;
+    let inventory = match &object.owner {
+        Owner::AddressOwner(a) | Owner::ConsensusAddressOwner { owner: a, .. } => {
+            inventories.address_inventories.entry(*a).or_default()
+        }
+        Owner::Shared { .. } => &mut inventories.shared_inventory,
+        Owner::Immutable => &mut inventories.immutable_inventory,
+        // only top-level objects can be taken
+        Owner::ObjectOwner(_) | Owner::Party { .. } => return Ok(()),
+    };
+    inventory
+        .entry(specified_obj_ty.clone())
+        .or_default()
+        .insert(id);
+    inventories.objects.insert(id, value);
+    object_runtime.add_forked_object(id, move_obj.version(), contained_uids);
+    Ok(())
+}
+
 fn take_from_inventory(
     is_in_inventory: impl FnOnce(&ObjectID) -> bool,
     objects: &BTreeMap<ObjectID, Value>,